@@ -0,0 +1,89 @@
+/*
+Benchmarks of the row kernels in `utils::simd`. Run them with and without the `simd`
+feature to compare the scalar and SIMD code paths:
+
+    cargo bench --bench raster_kernels
+    cargo bench --bench raster_kernels --features simd
+*/
+
+use std::time::Instant;
+use whitebox_tools::utils::simd::{self, BinaryOp};
+
+const ROWS: usize = 2048;
+const COLUMNS: usize = 4096;
+const NODATA: f64 = -32768f64;
+const ITERATIONS: usize = 5;
+
+/// A synthetic DEM row, with a scattering of NoData cells.
+fn dem_row(row: usize) -> Vec<f64> {
+    (0..COLUMNS)
+        .map(|col| {
+            if (row * COLUMNS + col) % 97 == 0 {
+                NODATA
+            } else {
+                100f64 + (row as f64 / 50f64).sin() * 20f64 + (col as f64 / 70f64).cos() * 15f64
+            }
+        })
+        .collect()
+}
+
+/// Runs `f` over every row of the raster ITERATIONS times, printing the mean time
+/// per cell.
+fn bench<F: FnMut(&[Vec<f64>], usize)>(name: &str, dem: &[Vec<f64>], mut f: F) {
+    // warm up
+    for row in 1..ROWS - 1 {
+        f(dem, row);
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for row in 1..ROWS - 1 {
+            f(dem, row);
+        }
+    }
+    let nanos = start.elapsed().as_nanos() as f64;
+    let cells = (ITERATIONS * (ROWS - 2) * COLUMNS) as f64;
+    println!("{:<24} {:>8.3} ns/cell", name, nanos / cells);
+}
+
+fn main() {
+    println!(
+        "SIMD feature {}",
+        if cfg!(feature = "simd") {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    let dem: Vec<Vec<f64>> = (0..ROWS).map(dem_row).collect();
+    let mut out = vec![0f64; COLUMNS];
+    let mut sum = vec![0f64; COLUMNS];
+    let mut sum_weights = vec![0f64; COLUMNS];
+
+    let weights_x = [-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0];
+    bench("window3x3 (slope)", &dem, |dem, row| {
+        simd::window3x3(
+            &dem[row - 1],
+            &dem[row],
+            &dem[row + 1],
+            NODATA,
+            &weights_x,
+            &mut out,
+        );
+    });
+
+    bench("accumulate_weighted", &dem, |dem, row| {
+        simd::accumulate_weighted(&dem[row], NODATA, 0.25, &mut sum, &mut sum_weights);
+    });
+
+    for (name, op) in [
+        ("binary_op (add)", BinaryOp::Add),
+        ("binary_op (multiply)", BinaryOp::Multiply),
+        ("binary_op (divide)", BinaryOp::Divide),
+    ]
+    .iter()
+    {
+        bench(name, &dem, |dem, row| {
+            simd::binary_op(*op, &dem[row], NODATA, &dem[row + 1], NODATA, &mut out);
+        });
+    }
+}
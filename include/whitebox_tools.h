@@ -0,0 +1,54 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+C interface to the WhiteboxTools shared library. Strings returned by the library
+must be released with wbt_free_string.
+*/
+
+#ifndef WHITEBOX_TOOLS_H
+#define WHITEBOX_TOOLS_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define WBT_SUCCESS 0
+#define WBT_INVALID_ARGUMENTS 1
+#define WBT_TOOL_ERROR 2
+#define WBT_TOOL_PANIC 3
+
+/* Receives each message printed by a running tool; progress is the percentage
+   reported by the message, or -1 if the message does not report progress. */
+typedef void (*wbt_callback)(const char *message, int progress, void *user_data);
+
+/* Returns the library version. */
+char *wbt_version(void);
+
+/* Returns a JSON array of the available tools, each with a name, description, and toolbox. */
+char *wbt_list_tools(void);
+
+/* Returns the parameters (in json form) of a tool, or NULL if the tool is not recognized. */
+char *wbt_tool_parameters(const char *tool_name);
+
+/* Runs a tool with command-line style arguments, e.g. "--input=DEM.tif". The
+   working_directory and callback may be NULL. Returns WBT_SUCCESS or an error code. */
+int wbt_run_tool(const char *tool_name, const char *working_directory, const char *const *args,
+                 size_t num_args, wbt_callback callback, void *user_data);
+
+/* Returns a description of the most recent error on the calling thread, or NULL. */
+char *wbt_last_error(void);
+
+/* Releases a string returned by the library. */
+void wbt_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -233,6 +233,41 @@ impl Triangulation {
         adjacent_triangles
     }
 
+    /// Flips the edge shared by the two triangles adjacent to halfedge `a`, replacing it with
+    /// the other diagonal of the quadrilateral formed by the two triangles. Returns the
+    /// halfedge of the new diagonal, or `EMPTY` if `a` is on the convex hull. The caller is
+    /// responsible for ensuring that the quadrilateral is convex.
+    pub fn flip_edge(&mut self, a: usize) -> usize {
+        let b = self.halfedges[a];
+        if b == EMPTY {
+            return EMPTY;
+        }
+        let ar = self.prev_halfedge(a);
+        let bl = self.prev_halfedge(b);
+
+        let p0 = self.triangles[ar];
+        let p1 = self.triangles[bl];
+
+        self.triangles[a] = p1;
+        self.triangles[b] = p0;
+
+        let hbl = self.halfedges[bl];
+        let har = self.halfedges[ar];
+
+        self.halfedges[a] = hbl;
+        self.halfedges[b] = har;
+        self.halfedges[ar] = bl;
+        self.halfedges[bl] = ar;
+
+        if hbl != EMPTY {
+            self.halfedges[hbl] = a;
+        }
+        if har != EMPTY {
+            self.halfedges[har] = b;
+        }
+        ar
+    }
+
     fn add_triangle(
         &mut self,
         i0: usize,
@@ -0,0 +1,174 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use std::f64::consts::PI;
+
+/// Performs an in-place, radix-2 fast Fourier transform of the complex sequence with real
+/// parts `re` and imaginary parts `im`. The sequence length must be a power of two. The
+/// inverse transform is scaled by 1/n, such that a forward transform followed by an inverse
+/// transform returns the original sequence.
+pub fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    let n = re.len();
+    assert!(
+        n == im.len() && n.is_power_of_two(),
+        "The FFT sequence length must be a power of two."
+    );
+    if n < 2 {
+        return;
+    }
+
+    // bit-reversal permutation
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1f64 } else { -1f64 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2f64 * PI / len as f64;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let (mut cur_re, mut cur_im) = (1f64, 0f64);
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1f64 / n as f64;
+        for i in 0..n {
+            re[i] *= scale;
+            im[i] *= scale;
+        }
+    }
+}
+
+/// Performs an in-place two-dimensional FFT of a row-major grid of complex values, by
+/// transforming each row and then each column. Both `rows` and `columns` must be powers of two.
+pub fn fft2d(re: &mut [f64], im: &mut [f64], rows: usize, columns: usize, inverse: bool) {
+    assert!(re.len() == rows * columns && im.len() == rows * columns);
+    for row in 0..rows {
+        let s = row * columns;
+        fft(&mut re[s..s + columns], &mut im[s..s + columns], inverse);
+    }
+    let mut col_re = vec![0f64; rows];
+    let mut col_im = vec![0f64; rows];
+    for col in 0..columns {
+        for row in 0..rows {
+            col_re[row] = re[row * columns + col];
+            col_im[row] = im[row * columns + col];
+        }
+        fft(&mut col_re, &mut col_im, inverse);
+        for row in 0..rows {
+            re[row * columns + col] = col_re[row];
+            im[row * columns + col] = col_im[row];
+        }
+    }
+}
+
+/// Returns the signed frequency, in cycles per sample (-0.5 to 0.5), associated with
+/// index `k` of an FFT of length `n`.
+pub fn fft_frequency(k: usize, n: usize) -> f64 {
+    if k <= n / 2 {
+        k as f64 / n as f64
+    } else {
+        (k as f64 - n as f64) / n as f64
+    }
+}
+
+/// Copies a row-major image into a grid whose dimensions are the next powers of two,
+/// reflecting the image across its bottom and right edges to fill the padding, which
+/// reduces the spectral artifacts associated with the image edges. Returns the padded
+/// data and its rows and columns.
+pub fn fft_pad_image(data: &[f64], rows: usize, columns: usize) -> (Vec<f64>, usize, usize) {
+    let padded_rows = rows.next_power_of_two();
+    let padded_columns = columns.next_power_of_two();
+    let mut padded = vec![0f64; padded_rows * padded_columns];
+    for row in 0..padded_rows {
+        let src_row = if row < rows {
+            row
+        } else {
+            (2 * rows).saturating_sub(row + 1).min(rows - 1)
+        };
+        for col in 0..padded_columns {
+            let src_col = if col < columns {
+                col
+            } else {
+                (2 * columns).saturating_sub(col + 1).min(columns - 1)
+            };
+            padded[row * padded_columns + col] = data[src_row * columns + src_col];
+        }
+    }
+    (padded, padded_rows, padded_columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fft, fft2d, fft_frequency, fft_pad_image};
+    #[test]
+    fn test_fft() {
+        let mut re = vec![1f64, 2f64, 3f64, 4f64];
+        let mut im = vec![0f64; 4];
+        fft(&mut re, &mut im, false);
+        let expected_re = [10f64, -2f64, -2f64, -2f64];
+        let expected_im = [0f64, 2f64, 0f64, -2f64];
+        for i in 0..4 {
+            assert!((re[i] - expected_re[i]).abs() < 1e-10);
+            assert!((im[i] - expected_im[i]).abs() < 1e-10);
+        }
+        fft(&mut re, &mut im, true);
+        for i in 0..4 {
+            assert!((re[i] - (i + 1) as f64).abs() < 1e-10);
+            assert!(im[i].abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_fft2d_round_trip() {
+        let (rows, columns) = (4, 8);
+        let original: Vec<f64> = (0..rows * columns).map(|i| (i * i % 7) as f64).collect();
+        let mut re = original.clone();
+        let mut im = vec![0f64; rows * columns];
+        fft2d(&mut re, &mut im, rows, columns, false);
+        assert!((re[0] - original.iter().sum::<f64>()).abs() < 1e-10);
+        fft2d(&mut re, &mut im, rows, columns, true);
+        for i in 0..rows * columns {
+            assert!((re[i] - original[i]).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_fft_pad_image() {
+        let (padded, rows, columns) = fft_pad_image(&[1f64, 2f64, 3f64], 1, 3);
+        assert_eq!((rows, columns), (1, 4));
+        assert_eq!(padded, vec![1f64, 2f64, 3f64, 3f64]);
+        assert_eq!(fft_frequency(3, 4), -0.25);
+    }
+}
@@ -0,0 +1,536 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::structures::Point2D;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::f64;
+
+/// Simplifies a line using the Douglas-Peucker algorithm, retaining the vertices needed to keep
+/// the simplified line within `tolerance` of the original. The end points are always retained.
+/// If the line is closed (i.e. the first and last points are the same), it is split at the vertex
+/// farthest from the first point, such that the result is also closed, and at least three distinct
+/// vertices are retained.
+pub fn douglas_peucker(points: &[Point2D], tolerance: f64) -> Vec<Point2D> {
+    let n = points.len();
+    if n < 3 || !(tolerance > 0f64) {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+    let mut stack = vec![];
+    if points[0] == points[n - 1] {
+        let mut farthest = 0;
+        let mut max_dist = 0f64;
+        for i in 1..n - 1 {
+            let dist = points[i].distance_squared(&points[0]);
+            if dist > max_dist {
+                max_dist = dist;
+                farthest = i;
+            }
+        }
+        if farthest == 0 {
+            return points.to_vec();
+        }
+        keep[farthest] = true;
+        stack.push((0, farthest));
+        stack.push((farthest, n - 1));
+    } else {
+        stack.push((0, n - 1));
+    }
+    while let Some((a, b)) = stack.pop() {
+        let mut farthest = 0;
+        let mut max_dist = 0f64;
+        for i in a + 1..b {
+            let dist = point_segment_distance(&points[i], &points[a], &points[b]);
+            if dist > max_dist {
+                max_dist = dist;
+                farthest = i;
+            }
+        }
+        if max_dist > tolerance {
+            keep[farthest] = true;
+            stack.push((a, farthest));
+            stack.push((farthest, b));
+        }
+    }
+    if points[0] == points[n - 1] && keep.iter().filter(|k| **k).count() < 4 {
+        // retain the vertex farthest from the chord, such that the ring doesn't collapse
+        let mid = (1..n - 1).find(|&i| keep[i]).unwrap_or(0);
+        let mut farthest = 0;
+        let mut max_dist = 0f64;
+        for i in 1..n - 1 {
+            let (a, b) = if i < mid { (0, mid) } else { (mid, n - 1) };
+            let dist = point_segment_distance(&points[i], &points[a], &points[b]);
+            if !keep[i] && dist > max_dist {
+                max_dist = dist;
+                farthest = i;
+            }
+        }
+        keep[farthest] = true;
+    }
+    (0..n).filter(|&i| keep[i]).map(|i| points[i]).collect()
+}
+
+/// Simplifies a line using the Visvalingam-Whyatt algorithm, which repeatedly removes the vertex
+/// forming the triangle of smallest area with its two neighbours, until all remaining triangles
+/// have areas of at least `min_area`. The end points are always retained, as are at least three
+/// distinct vertices of closed lines.
+pub fn visvalingam_whyatt(points: &[Point2D], min_area: f64) -> Vec<Point2D> {
+    let n = points.len();
+    if n < 3 || !(min_area > 0f64) {
+        return points.to_vec();
+    }
+    let min_remaining = if points[0] == points[n - 1] { 4 } else { 2 };
+    let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1).min(n - 1)).collect();
+    let mut removed = vec![false; n];
+    let mut area = vec![f64::INFINITY; n];
+    let mut queue = BinaryHeap::new();
+    for i in 1..n - 1 {
+        area[i] = triangle_area(&points[i - 1], &points[i], &points[i + 1]);
+        queue.push(AreaItem {
+            area: area[i],
+            index: i,
+        });
+    }
+    let mut remaining = n;
+    while let Some(item) = queue.pop() {
+        if removed[item.index] || item.area != area[item.index] {
+            continue; // stale entry
+        }
+        if item.area >= min_area || remaining <= min_remaining {
+            break;
+        }
+        let i = item.index;
+        removed[i] = true;
+        remaining -= 1;
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        for &j in &[p, q] {
+            if j > 0 && j < n - 1 {
+                // the area of a neighbour can't be less than that of the removed vertex, so
+                // that vertices are removed in order of increasing area
+                area[j] =
+                    triangle_area(&points[prev[j]], &points[j], &points[next[j]]).max(item.area);
+                queue.push(AreaItem {
+                    area: area[j],
+                    index: j,
+                });
+            }
+        }
+    }
+    (0..n).filter(|&i| !removed[i]).map(|i| points[i]).collect()
+}
+
+/// Smooths a line by replacing each vertex with the average position of the vertices within a
+/// moving window of `filter_size` vertices. The end points of open lines are fixed; closed lines
+/// are smoothed cyclically.
+pub fn moving_average_smoothing(
+    points: &[Point2D],
+    filter_size: usize,
+    closed: bool,
+) -> Vec<Point2D> {
+    let n = points.len();
+    let half_filter = (filter_size / 2) as isize;
+    if closed && n > 4 {
+        let m = (n - 1) as isize; // the last point duplicates the first
+        let mut ret: Vec<Point2D> = (0..m)
+            .map(|i| {
+                let (mut x, mut y) = (0f64, 0f64);
+                for j in i - half_filter..=i + half_filter {
+                    let k = j.rem_euclid(m) as usize;
+                    x += points[k].x;
+                    y += points[k].y;
+                }
+                let w = (2 * half_filter + 1) as f64;
+                Point2D::new(x / w, y / w)
+            })
+            .collect();
+        ret.push(ret[0]);
+        ret
+    } else if !closed && n > 3 {
+        let mut ret = points.to_vec();
+        for i in 1..n - 1 {
+            let (mut x, mut y, mut w) = (0f64, 0f64, 0f64);
+            let a = (i as isize - half_filter).max(0) as usize;
+            let b = (i + half_filter as usize).min(n - 1);
+            for p in &points[a..=b] {
+                x += p.x;
+                y += p.y;
+                w += 1f64;
+            }
+            ret[i] = Point2D::new(x / w, y / w);
+        }
+        ret
+    } else {
+        points.to_vec()
+    }
+}
+
+/// Smooths a line using Chaikin's corner-cutting algorithm, replacing each segment with points at
+/// one quarter and three quarters of its length, for the specified number of iterations. The end
+/// points of open lines are fixed.
+pub fn chaikin_smoothing(points: &[Point2D], iterations: usize, closed: bool) -> Vec<Point2D> {
+    let mut line = points.to_vec();
+    if line.len() < 3 {
+        return line;
+    }
+    for _ in 0..iterations {
+        let n = line.len();
+        let mut smoothed = Vec::with_capacity(2 * n);
+        if !closed {
+            smoothed.push(line[0]);
+        }
+        for i in 0..n - 1 {
+            let (p, q) = (line[i], line[i + 1]);
+            smoothed.push(Point2D::new(
+                0.75 * p.x + 0.25 * q.x,
+                0.75 * p.y + 0.25 * q.y,
+            ));
+            smoothed.push(Point2D::new(
+                0.25 * p.x + 0.75 * q.x,
+                0.25 * p.y + 0.75 * q.y,
+            ));
+        }
+        if closed {
+            smoothed.push(smoothed[0]);
+        } else {
+            smoothed.push(line[n - 1]);
+        }
+        line = smoothed;
+    }
+    line
+}
+
+/// Smooths a line using the polynomial approximation with exponential kernel (PAEK) method. The
+/// line is densified at one tenth of the smoothing `tolerance` and each densified point is replaced
+/// by the average of its neighbours within half the tolerance along the line, weighted by a Gaussian
+/// kernel of their distance along the line. Larger tolerances produce smoother lines. The end points
+/// of open lines are fixed; closed lines are smoothed cyclically.
+pub fn paek_smoothing(points: &[Point2D], tolerance: f64, closed: bool) -> Vec<Point2D> {
+    if points.len() < 3 || !(tolerance > 0f64) {
+        return points.to_vec();
+    }
+    let step = tolerance / 10f64;
+    let mut dense = vec![points[0]];
+    let mut s = vec![0f64]; // distance along the line of each densified point
+    for i in 1..points.len() {
+        let (p, q) = (points[i - 1], points[i]);
+        let length = p.distance(&q);
+        let num_steps = (length / step).ceil().max(1f64) as usize;
+        let s0 = s[s.len() - 1];
+        for k in 1..=num_steps {
+            let t = k as f64 / num_steps as f64;
+            dense.push(Point2D::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y)));
+            s.push(s0 + t * length);
+        }
+    }
+    let total_length = s[s.len() - 1];
+    let n = if closed { dense.len() - 1 } else { dense.len() };
+    let half_window = tolerance / 2f64;
+    let sigma = tolerance / 4f64;
+    let mut ret = Vec::with_capacity(dense.len());
+    for i in 0..n {
+        if !closed && (i == 0 || i == n - 1) {
+            ret.push(dense[i]);
+            continue;
+        }
+        let (mut x, mut y, mut w) = (0f64, 0f64, 0f64);
+        for offset in &[-1isize, 1isize] {
+            let mut j = i as isize;
+            if *offset == 1 {
+                j += 1; // the centre point is included by the backward pass
+            }
+            loop {
+                let k = if closed {
+                    j.rem_euclid(n as isize) as usize
+                } else if j < 0 || j >= n as isize {
+                    break;
+                } else {
+                    j as usize
+                };
+                let mut ds = (s[k] - s[i]).abs();
+                if closed {
+                    ds = ds.min(total_length - ds);
+                }
+                if ds > half_window || (j - i as isize).abs() as usize >= n {
+                    break;
+                }
+                let weight = (-0.5 * (ds / sigma).powi(2)).exp();
+                x += weight * dense[k].x;
+                y += weight * dense[k].y;
+                w += weight;
+                j += offset;
+            }
+        }
+        ret.push(Point2D::new(x / w, y / w));
+    }
+    if closed {
+        ret.push(ret[0]);
+    }
+    ret
+}
+
+/// Applies a line generalization function to a set of lines and polygon rings, e.g. all of the
+/// parts in a vector layer, returning the generalized parts in the same order. The function is
+/// passed the points of a line and a flag indicating whether the line is closed and may be
+/// generalized cyclically.
+///
+/// If `preserve_topology` is true, the parts are first divided into arcs at nodes, i.e. the
+/// vertices at which three or more lines meet and the end points of open lines, and each arc is
+/// generalized only once, with fixed end points, before the parts are reassembled. Boundaries
+/// shared by adjacent polygons, or lines that meet at shared vertices, are therefore generalized
+/// identically and remain coincident. Closed rings without any nodes are divided at their
+/// lowest-left vertex.
+pub fn generalize_lines<F>(
+    parts: &[Vec<Point2D>],
+    preserve_topology: bool,
+    generalize: F,
+) -> Vec<Vec<Point2D>>
+where
+    F: Fn(&[Point2D], bool) -> Vec<Point2D>,
+{
+    let is_closed = |p: &[Point2D]| p.len() > 3 && p[0] == p[p.len() - 1];
+    if !preserve_topology {
+        return parts.iter().map(|p| generalize(p, is_closed(p))).collect();
+    }
+
+    let key = |p: &Point2D| (p.x.to_bits(), p.y.to_bits());
+
+    // remove repeated vertices and find the neighbours of each vertex
+    let parts: Vec<Vec<Point2D>> = parts
+        .iter()
+        .map(|p| {
+            let mut q: Vec<Point2D> = Vec::with_capacity(p.len());
+            for pt in p {
+                if q.is_empty() || q[q.len() - 1] != *pt {
+                    q.push(*pt);
+                }
+            }
+            q
+        })
+        .collect();
+    let mut neighbours: HashMap<(u64, u64), HashSet<(u64, u64)>> = HashMap::new();
+    let mut end_points: HashSet<(u64, u64)> = HashSet::new();
+    for p in &parts {
+        for i in 1..p.len() {
+            let (a, b) = (key(&p[i - 1]), key(&p[i]));
+            neighbours.entry(a).or_insert_with(HashSet::new).insert(b);
+            neighbours.entry(b).or_insert_with(HashSet::new).insert(a);
+        }
+        if !p.is_empty() && !is_closed(p) {
+            end_points.insert(key(&p[0]));
+            end_points.insert(key(&p[p.len() - 1]));
+        }
+    }
+    let is_node = |p: &Point2D| {
+        let k = key(p);
+        end_points.contains(&k) || neighbours.get(&k).map_or(true, |n| n.len() != 2)
+    };
+
+    let mut cache: HashMap<((u64, u64), (u64, u64)), Vec<Point2D>> = HashMap::new();
+    let mut ret = Vec::with_capacity(parts.len());
+    for p in &parts {
+        if p.len() < 2 {
+            ret.push(p.clone());
+            continue;
+        }
+        let line: Vec<Point2D> = if is_closed(p) {
+            // rotate the ring to start at a node
+            let m = p.len() - 1;
+            let start = match (0..m).find(|&i| is_node(&p[i])) {
+                Some(i) => i,
+                None => (0..m)
+                    .min_by(|&a, &b| {
+                        (p[a].x, p[a].y)
+                            .partial_cmp(&(p[b].x, p[b].y))
+                            .unwrap_or(Ordering::Equal)
+                    })
+                    .unwrap(),
+            };
+            (0..=m).map(|i| p[(start + i) % m]).collect()
+        } else {
+            p.clone()
+        };
+
+        let mut out: Vec<Point2D> = vec![line[0]];
+        let mut arc_start = 0;
+        for i in 1..line.len() {
+            if i < line.len() - 1 && !is_node(&line[i]) {
+                continue;
+            }
+            let arc = &line[arc_start..=i];
+            let forward = (key(&arc[0]), key(&arc[1]));
+            let backward = (key(&arc[arc.len() - 1]), key(&arc[arc.len() - 2]));
+            let reversed = backward < forward;
+            let id = if reversed { backward } else { forward };
+            let generalized = cache.entry(id).or_insert_with(|| {
+                if reversed {
+                    let mut a = arc.to_vec();
+                    a.reverse();
+                    generalize(&a, false)
+                } else {
+                    generalize(arc, false)
+                }
+            });
+            if reversed {
+                out.extend(generalized.iter().rev().skip(1));
+            } else {
+                out.extend(generalized.iter().skip(1));
+            }
+            arc_start = i;
+        }
+        ret.push(out);
+    }
+    ret
+}
+
+#[derive(Debug)]
+struct AreaItem {
+    area: f64,
+    index: usize,
+}
+
+impl PartialEq for AreaItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AreaItem {}
+
+impl PartialOrd for AreaItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AreaItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed for a min-heap
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+fn triangle_area(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+    ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2f64
+}
+
+fn point_segment_distance(p: &Point2D, a: &Point2D, b: &Point2D) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len_sqr = dx * dx + dy * dy;
+    if len_sqr == 0f64 {
+        return p.distance(a);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len_sqr)
+        .max(0f64)
+        .min(1f64);
+    p.distance(&Point2D::new(a.x + t * dx, a.y + t * dy))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        chaikin_smoothing, douglas_peucker, generalize_lines, paek_smoothing, visvalingam_whyatt,
+    };
+    use crate::structures::Point2D;
+
+    fn zigzag() -> Vec<Point2D> {
+        (0..11)
+            .map(|i| Point2D::new(i as f64, if i % 2 == 0 { 0.0 } else { 0.1 }))
+            .collect()
+    }
+
+    #[test]
+    fn test_simplification() {
+        let line = zigzag();
+        assert_eq!(
+            douglas_peucker(&line, 0.2),
+            vec![Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)]
+        );
+        assert_eq!(douglas_peucker(&line, 0.05).len(), 11);
+        assert_eq!(visvalingam_whyatt(&line, 1.0).len(), 2);
+        assert_eq!(visvalingam_whyatt(&line, 0.01).len(), 11);
+
+        let square = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(0.0, 1.0),
+            Point2D::new(0.5, 1.01),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(0.0, 0.0),
+        ];
+        assert_eq!(douglas_peucker(&square, 0.1).len(), 5);
+        assert_eq!(visvalingam_whyatt(&square, 0.1).len(), 5);
+        assert_eq!(visvalingam_whyatt(&square, 100.0).len(), 4);
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let line = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(2.0, 0.0),
+        ];
+        let c = chaikin_smoothing(&line, 1, false);
+        assert_eq!(c.len(), 6);
+        assert_eq!(c[0], line[0]);
+        assert_eq!(c[5], line[2]);
+        assert_eq!(c[2], Point2D::new(0.75, 0.75));
+
+        let p = paek_smoothing(&line, 1.0, false);
+        assert_eq!(p[0], line[0]);
+        assert_eq!(p[p.len() - 1], line[2]);
+        let peak = p.iter().map(|q| q.y).fold(0.0, f64::max);
+        assert!(peak < 1.0 && peak > 0.5);
+    }
+
+    #[test]
+    fn test_topology_preservation() {
+        // two squares sharing the edge x = 1, with the shared edge traversed in opposite directions
+        let a = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(0.0, 2.0),
+            Point2D::new(1.0, 2.0),
+            Point2D::new(1.1, 1.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(0.0, 0.0),
+        ];
+        let b = vec![
+            Point2D::new(1.0, 0.0),
+            Point2D::new(1.1, 1.0),
+            Point2D::new(1.0, 2.0),
+            Point2D::new(2.0, 2.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(1.0, 0.0),
+        ];
+        let parts = generalize_lines(&[a, b], true, |p, closed| chaikin_smoothing(p, 2, closed));
+        // the shared boundary is the same sequence of points in both results
+        let shared_a: Vec<Point2D> = parts[0]
+            .iter()
+            .cloned()
+            .filter(|p| p.x > 0.9 && p.x < 1.2 && p.y > 0.0 && p.y < 2.0)
+            .collect();
+        let mut shared_b: Vec<Point2D> = parts[1]
+            .iter()
+            .cloned()
+            .filter(|p| p.x > 0.9 && p.x < 1.2 && p.y > 0.0 && p.y < 2.0)
+            .collect();
+        shared_b.reverse();
+        assert!(shared_a.len() > 3);
+        assert_eq!(shared_a, shared_b);
+        assert_eq!(parts[0][0], parts[0][parts[0].len() - 1]);
+    }
+}
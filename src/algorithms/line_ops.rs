@@ -2,7 +2,7 @@
 This code is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 15/10/2018
-Last Modified: 15/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -40,6 +40,54 @@ use crate::structures::{BoundingBox, LineSegment, Point2D, Polyline};
 //     false
 // }
 
+/// Finds the pairs of segments, one from each of two lines, with overlapping bounding boxes,
+/// using a sweep along the x-axis. Segments are entered into the sweep in order of their minimum
+/// x-coordinate and are only compared with the active segments of the other line, i.e. those
+/// whose x-ranges have not yet been passed by the sweep, such that the number of comparisons is
+/// proportional to the number of segments and the number of overlapping pairs, rather than the
+/// product of the number of segments in each line. The returned pairs of segment indices are
+/// sorted by the index of the first line segment and then the second.
+pub fn find_candidate_segment_pairs(line1: &[Point2D], line2: &[Point2D]) -> Vec<(usize, usize)> {
+    let mut ret: Vec<(usize, usize)> = vec![];
+    if line1.len() < 2 || line2.len() < 2 {
+        return ret;
+    }
+    // (min x, max x, min y, max y, line, segment)
+    let mut segments: Vec<(f64, f64, f64, f64, usize, usize)> =
+        Vec::with_capacity(line1.len() + line2.len() - 2);
+    for (line, points) in [line1, line2].iter().enumerate() {
+        for a in 0..points.len() - 1 {
+            segments.push((
+                points[a].x.min(points[a + 1].x),
+                points[a].x.max(points[a + 1].x),
+                points[a].y.min(points[a + 1].y),
+                points[a].y.max(points[a + 1].y),
+                line,
+                a,
+            ));
+        }
+    }
+    segments.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut active: [Vec<usize>; 2] = [vec![], vec![]];
+    for s in 0..segments.len() {
+        let (min_x, _, min_y, max_y, line, index) = segments[s];
+        let other = 1 - line;
+        active[other].retain(|&t| segments[t].1 >= min_x);
+        for &t in &active[other] {
+            if segments[t].2 <= max_y && segments[t].3 >= min_y {
+                if line == 0 {
+                    ret.push((index, segments[t].5));
+                } else {
+                    ret.push((segments[t].5, index));
+                }
+            }
+        }
+        active[line].push(s);
+    }
+    ret.sort();
+    ret
+}
+
 pub fn find_line_intersections(line1: &[Point2D], line2: &[Point2D]) -> Vec<LineSegment> {
     let mut ret: Vec<LineSegment> = vec![];
     let box1 = BoundingBox::from_points(&line1);
@@ -47,14 +95,12 @@ pub fn find_line_intersections(line1: &[Point2D], line2: &[Point2D]) -> Vec<Line
     if box1.overlaps(box2) {
         let mut ls1: LineSegment;
         let mut ls2: LineSegment;
-        for a in 0..line1.len() - 1 {
+        for (a, b) in find_candidate_segment_pairs(line1, line2) {
             ls1 = LineSegment::new(line1[a], line1[a + 1]);
-            for b in 0..line2.len() - 1 {
-                ls2 = LineSegment::new(line2[b], line2[b + 1]);
-                match ls1.get_intersection(&ls2) {
-                    Some(p) => ret.push(p),
-                    None => {} // do nothing, the don't intersect
-                }
+            ls2 = LineSegment::new(line2[b], line2[b + 1]);
+            match ls1.get_intersection(&ls2) {
+                Some(p) => ret.push(p),
+                None => {} // do nothing, the don't intersect
             }
         }
     }
@@ -67,16 +113,14 @@ pub fn do_polylines_intersect(line1: &Polyline, line2: &Polyline) -> bool {
     if box1.overlaps(box2) {
         let mut ls1: LineSegment;
         let mut ls2: LineSegment;
-        for a in 0..line1.len() - 1 {
+        for (a, b) in find_candidate_segment_pairs(&line1.vertices, &line2.vertices) {
             ls1 = LineSegment::new(line1[a], line1[a + 1]);
-            for b in 0..line2.len() - 1 {
-                ls2 = LineSegment::new(line2[b], line2[b + 1]);
-                match ls1.get_intersection(&ls2) {
-                    Some(_) => {
-                        return true;
-                    }
-                    None => {} // do nothing, the don't intersect
+            ls2 = LineSegment::new(line2[b], line2[b + 1]);
+            match ls1.get_intersection(&ls2) {
+                Some(_) => {
+                    return true;
                 }
+                None => {} // do nothing, the don't intersect
             }
         }
     }
@@ -89,41 +133,39 @@ pub fn find_split_points_at_line_intersections(line1: &mut Polyline, line2: &mut
     if box1.overlaps(box2) {
         let mut ls1: LineSegment;
         let mut ls2: LineSegment;
-        for a in 0..line1.len() - 1 {
+        for (a, b) in find_candidate_segment_pairs(&line1.vertices, &line2.vertices) {
             ls1 = LineSegment::new(line1[a], line1[a + 1]);
-            for b in 0..line2.len() - 1 {
-                ls2 = LineSegment::new(line2[b], line2[b + 1]);
-                match ls1.get_intersection(&ls2) {
-                    Some(ls) => {
+            ls2 = LineSegment::new(line2[b], line2[b + 1]);
+            match ls1.get_intersection(&ls2) {
+                Some(ls) => {
+                    line1.insert_split_point(
+                        a as f64
+                            + ls.p1.distance_squared(&ls1.p1)
+                                / ls1.p2.distance_squared(&ls1.p1), //(ls.p1.x - ls1.p1.x) / (ls1.p2.x - ls1.p1.x),
+                        ls.p1,
+                    );
+                    line2.insert_split_point(
+                        b as f64
+                            + ls.p1.distance_squared(&ls2.p1)
+                                / ls2.p2.distance_squared(&ls2.p1), //(ls.p1.x - ls2.p1.x) / (ls2.p2.x - ls2.p1.x),
+                        ls.p1,
+                    );
+                    if ls.p1 != ls.p2 {
                         line1.insert_split_point(
                             a as f64
-                                + ls.p1.distance_squared(&ls1.p1)
-                                    / ls1.p2.distance_squared(&ls1.p1), //(ls.p1.x - ls1.p1.x) / (ls1.p2.x - ls1.p1.x),
-                            ls.p1,
+                                + ls.p2.distance_squared(&ls1.p1)
+                                    / ls1.p2.distance_squared(&ls1.p1), //(ls.p2.x - ls1.p1.x) / (ls1.p2.x - ls1.p1.x),
+                            ls.p2,
                         );
                         line2.insert_split_point(
                             b as f64
-                                + ls.p1.distance_squared(&ls2.p1)
-                                    / ls2.p2.distance_squared(&ls2.p1), //(ls.p1.x - ls2.p1.x) / (ls2.p2.x - ls2.p1.x),
-                            ls.p1,
+                                + ls.p2.distance_squared(&ls2.p1)
+                                    / ls2.p2.distance_squared(&ls2.p1), //(ls.p2.x - ls2.p1.x) / (ls2.p2.x - ls2.p1.x),
+                            ls.p2,
                         );
-                        if ls.p1 != ls.p2 {
-                            line1.insert_split_point(
-                                a as f64
-                                    + ls.p2.distance_squared(&ls1.p1)
-                                        / ls1.p2.distance_squared(&ls1.p1), //(ls.p2.x - ls1.p1.x) / (ls1.p2.x - ls1.p1.x),
-                                ls.p2,
-                            );
-                            line2.insert_split_point(
-                                b as f64
-                                    + ls.p2.distance_squared(&ls2.p1)
-                                        / ls2.p2.distance_squared(&ls2.p1), //(ls.p2.x - ls2.p1.x) / (ls2.p2.x - ls2.p1.x),
-                                ls.p2,
-                            );
-                        }
                     }
-                    None => {} // do nothing, the don't intersect
                 }
+                None => {} // do nothing, the don't intersect
             }
         }
     }
@@ -131,7 +173,7 @@ pub fn find_split_points_at_line_intersections(line1: &mut Polyline, line2: &mut
 
 #[cfg(test)]
 mod test {
-    use super::find_line_intersections;
+    use super::{find_candidate_segment_pairs, find_line_intersections};
     use crate::structures::{LineSegment, Point2D};
 
     #[test]
@@ -196,4 +238,34 @@ mod test {
         )];
         assert_eq!(intersections, intersections_should_be);
     }
+
+    #[test]
+    fn test_find_candidate_segment_pairs() {
+        let line1 = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 10.0),
+            Point2D::new(12.0, 6.0),
+            Point2D::new(6.0, 0.0),
+        ];
+        let line2 = vec![
+            Point2D::new(-1.0, 5.0),
+            Point2D::new(6.0, 5.0),
+            Point2D::new(6.0, 2.0),
+            Point2D::new(12.0, 2.0),
+        ];
+        // compare against the brute-force pairwise test of the segment bounding boxes
+        let mut brute_force = vec![];
+        for a in 0..line1.len() - 1 {
+            for b in 0..line2.len() - 1 {
+                if line1[a].x.min(line1[a + 1].x) <= line2[b].x.max(line2[b + 1].x)
+                    && line1[a].x.max(line1[a + 1].x) >= line2[b].x.min(line2[b + 1].x)
+                    && line1[a].y.min(line1[a + 1].y) <= line2[b].y.max(line2[b + 1].y)
+                    && line1[a].y.max(line1[a + 1].y) >= line2[b].y.min(line2[b + 1].y)
+                {
+                    brute_force.push((a, b));
+                }
+            }
+        }
+        assert_eq!(find_candidate_segment_pairs(&line1, &line2), brute_force);
+    }
 }
@@ -8,25 +8,46 @@ License: MIT
 // private sub-module defined in other files
 mod convex_hull;
 mod delaunay_triangulation;
+mod fft;
 mod is_clockwise_order;
+mod line_generalization;
 mod line_ops;
+mod mfd_flow_accumulation;
 mod minimum_bounding_box;
+mod otsu_threshold;
 mod poly_area;
 mod poly_ops;
 mod poly_perimeter;
+mod polygon_union;
+mod raster_polygons;
 mod smallest_enclosing_circle;
+mod time_series;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::convex_hull::convex_hull;
-pub use self::delaunay_triangulation::{triangulate, Triangulation};
+pub use self::delaunay_triangulation::{triangulate, Triangulation, EMPTY};
+pub use self::fft::{fft, fft2d, fft_frequency, fft_pad_image};
 pub use self::is_clockwise_order::is_clockwise_order;
+pub use self::line_generalization::{
+    chaikin_smoothing, douglas_peucker, generalize_lines, moving_average_smoothing, paek_smoothing,
+    visvalingam_whyatt,
+};
 pub use self::line_ops::{
-    do_polylines_intersect, find_line_intersections, find_split_points_at_line_intersections,
+    do_polylines_intersect, find_candidate_segment_pairs, find_line_intersections,
+    find_split_points_at_line_intersections,
 };
+pub use self::mfd_flow_accumulation::{mfd_flow_accumulation, mfd_proportions};
 pub use self::minimum_bounding_box::{minimum_bounding_box, MinimizationCriterion};
+pub use self::otsu_threshold::otsu_threshold;
 pub use self::poly_area::polygon_area;
 pub use self::poly_ops::{
     interior_point, point_in_poly, poly_in_poly, poly_is_convex, poly_overlaps_poly, winding_number,
 };
 pub use self::poly_perimeter::polygon_perimeter;
+pub use self::polygon_union::union_rings;
+pub use self::raster_polygons::raster_to_polygons;
 pub use self::smallest_enclosing_circle::smallest_enclosing_circle;
+pub use self::time_series::{
+    double_logistic, fit_double_logistic, mann_kendall, savitzky_golay, theil_sen,
+    upper_envelope_weights, weighted_savitzky_golay, whittaker_smooth, MannKendall,
+};
@@ -0,0 +1,91 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/// Calculates the threshold value that best separates a set of values into two classes
+/// using Otsu's (1979) method, i.e. the threshold that maximizes the between-class variance
+/// of a histogram of the values containing `num_bins` bins. Non-finite values are ignored.
+/// Returns NaN if there are no finite values.
+pub fn otsu_threshold(values: &[f64], num_bins: usize) -> f64 {
+    let num_bins = num_bins.max(2);
+    let mut min_val = f64::INFINITY;
+    let mut max_val = f64::NEG_INFINITY;
+    for &z in values {
+        if z.is_finite() {
+            if z < min_val {
+                min_val = z;
+            }
+            if z > max_val {
+                max_val = z;
+            }
+        }
+    }
+    if min_val > max_val {
+        return f64::NAN;
+    }
+    if min_val == max_val {
+        return min_val;
+    }
+
+    let bin_size = (max_val - min_val) / num_bins as f64;
+    let mut histo = vec![0f64; num_bins];
+    for &z in values {
+        if z.is_finite() {
+            let bin = (((z - min_val) / bin_size) as usize).min(num_bins - 1);
+            histo[bin] += 1f64;
+        }
+    }
+
+    let total: f64 = histo.iter().sum();
+    let mut total_sum = 0f64;
+    for i in 0..num_bins {
+        total_sum += i as f64 * histo[i];
+    }
+
+    let mut weight_below = 0f64;
+    let mut sum_below = 0f64;
+    let mut max_variance = -1f64;
+    let mut threshold_bin = 0;
+    for i in 0..num_bins - 1 {
+        weight_below += histo[i];
+        sum_below += i as f64 * histo[i];
+        let weight_above = total - weight_below;
+        if weight_below == 0f64 || weight_above == 0f64 {
+            continue;
+        }
+        let mean_below = sum_below / weight_below;
+        let mean_above = (total_sum - sum_below) / weight_above;
+        let variance =
+            weight_below * weight_above * (mean_below - mean_above) * (mean_below - mean_above);
+        if variance > max_variance {
+            max_variance = variance;
+            threshold_bin = i;
+        }
+    }
+
+    // the threshold is the upper edge of the last bin in the lower class
+    min_val + (threshold_bin + 1) as f64 * bin_size
+}
+
+#[cfg(test)]
+mod test {
+    use super::otsu_threshold;
+    #[test]
+    fn test_otsu_threshold() {
+        let mut values = vec![];
+        for i in 0..100 {
+            values.push(10f64 + (i % 10) as f64 * 0.1);
+            values.push(50f64 + (i % 10) as f64 * 0.1);
+        }
+        values.push(f64::NAN);
+        let threshold = otsu_threshold(&values, 256);
+        assert!(threshold > 10.9f64 && threshold < 50f64);
+
+        assert_eq!(otsu_threshold(&[3f64, 3f64], 256), 3f64);
+        assert!(otsu_threshold(&[], 256).is_nan());
+    }
+}
@@ -0,0 +1,391 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::point_in_poly;
+use crate::structures::Point2D;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Calculates the union of a set of rings using the positive winding fill rule, i.e. the
+/// output covers the area in which the sum of the winding numbers of the input rings is
+/// greater than zero. Counter-clockwise rings therefore add area and clockwise rings remove
+/// it, which allows the rings to overlap and self-intersect, as is the case with the raw
+/// offset curves used in buffering. The rings need not be closed.
+///
+/// The rings are noded by splitting their edges at all intersections, found using a sweep
+/// along the x-axis. Each of the resulting edges is retained if the area to one side of it is
+/// filled and the other is not, and the retained edges are then linked into rings. Coordinates
+/// that are within a small tolerance (relative to the extent of the data) of one another are
+/// merged.
+///
+/// Returns a list of polygons, each of which is a list of closed rings, with the first being
+/// the counter-clockwise exterior ring and any remaining rings being its clockwise holes.
+pub fn union_rings(rings: &[Vec<Point2D>]) -> Vec<Vec<Vec<Point2D>>> {
+    // collect the edges
+    let mut edges: Vec<(Point2D, Point2D)> = vec![];
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for ring in rings {
+        let mut n = ring.len();
+        if n > 1 && ring[0] == ring[n - 1] {
+            n -= 1;
+        }
+        if n < 3 {
+            continue;
+        }
+        for i in 0..n {
+            let (p1, p2) = (ring[i], ring[(i + 1) % n]);
+            if p1 != p2 {
+                edges.push((p1, p2));
+            }
+            min_x = min_x.min(p1.x);
+            min_y = min_y.min(p1.y);
+            max_x = max_x.max(p1.x);
+            max_y = max_y.max(p1.y);
+        }
+    }
+    if edges.len() < 3 {
+        return vec![];
+    }
+    let extent = (max_x - min_x).max(max_y - min_y).max(f64::MIN_POSITIVE);
+    let snap = extent * 1e-10;
+
+    // find the points at which each edge must be split
+    let mut splits: Vec<Vec<f64>> = vec![vec![]; edges.len()];
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by(|&a, &b| {
+        edges[a]
+            .0
+            .x
+            .min(edges[a].1.x)
+            .partial_cmp(&edges[b].0.x.min(edges[b].1.x))
+            .unwrap()
+    });
+    let mut active: Vec<usize> = vec![];
+    for &a in &order {
+        let (p1, p2) = edges[a];
+        let left = p1.x.min(p2.x) - snap;
+        active.retain(|&b| edges[b].0.x.max(edges[b].1.x) >= left);
+        for &b in &active {
+            let (q1, q2) = edges[b];
+            if p1.y.min(p2.y) - snap > q1.y.max(q2.y) || p1.y.max(p2.y) + snap < q1.y.min(q2.y) {
+                continue;
+            }
+            let (ta, tb) = edge_intersections(&p1, &p2, &q1, &q2, snap);
+            splits[a].extend(ta);
+            splits[b].extend(tb);
+        }
+        active.push(a);
+    }
+
+    // node the edges, merging coincident points and counting the net number of times each
+    // noded edge is traversed in each direction
+    let mut node_ids: HashMap<(i64, i64), usize> = HashMap::new();
+    let mut nodes: Vec<Point2D> = vec![];
+    let mut get_node = |p: Point2D| -> usize {
+        let key = ((p.x / snap).round() as i64, (p.y / snap).round() as i64);
+        *node_ids.entry(key).or_insert_with(|| {
+            nodes.push(p);
+            nodes.len() - 1
+        })
+    };
+    let mut multiplicity: HashMap<(usize, usize), i32> = HashMap::new();
+    for a in 0..edges.len() {
+        let (p1, p2) = edges[a];
+        splits[a].sort_by(|x, y| x.partial_cmp(y).unwrap());
+        let mut prev = get_node(p1);
+        for &t in splits[a].iter().chain([1f64].iter()) {
+            let p = if t < 1f64 {
+                Point2D::new(p1.x + t * (p2.x - p1.x), p1.y + t * (p2.y - p1.y))
+            } else {
+                p2
+            };
+            let next = get_node(p);
+            if next != prev {
+                if prev < next {
+                    *multiplicity.entry((prev, next)).or_insert(0) += 1;
+                } else {
+                    *multiplicity.entry((next, prev)).or_insert(0) -= 1;
+                }
+                prev = next;
+            }
+        }
+    }
+
+    // index the input edges into horizontal strips for the winding number calculations
+    let num_strips = ((edges.len() as f64).sqrt().ceil() as usize).max(1);
+    let strip_height = (max_y - min_y).max(f64::MIN_POSITIVE) / num_strips as f64;
+    let strip_of = |y: f64| -> usize {
+        (((y - min_y) / strip_height).floor().max(0f64) as usize).min(num_strips - 1)
+    };
+    let mut strips: Vec<Vec<usize>> = vec![vec![]; num_strips];
+    for a in 0..edges.len() {
+        let (p1, p2) = edges[a];
+        for s in strip_of(p1.y.min(p2.y))..=strip_of(p1.y.max(p2.y)) {
+            strips[s].push(a);
+        }
+    }
+    let winding = |p: &Point2D| -> i32 {
+        let mut wn = 0i32;
+        for &a in &strips[strip_of(p.y)] {
+            let (p1, p2) = edges[a];
+            let is_left = (p2.x - p1.x) * (p.y - p1.y) - (p.x - p1.x) * (p2.y - p1.y);
+            if p1.y <= p.y {
+                if p2.y > p.y && is_left > 0f64 {
+                    wn += 1;
+                }
+            } else if p2.y <= p.y && is_left < 0f64 {
+                wn -= 1;
+            }
+        }
+        wn
+    };
+
+    // retain the edges that separate filled and unfilled areas, oriented with the filled
+    // area on the left
+    let mut out_edges: Vec<(usize, usize)> = vec![];
+    for (&(u, v), &m) in multiplicity.iter() {
+        let (p1, p2) = (nodes[u], nodes[v]);
+        let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+        let len = (dx * dx + dy * dy).sqrt();
+        let eps = (len * 1e-3).max(snap * 10f64);
+        let right = Point2D::new(
+            (p1.x + p2.x) / 2f64 + eps * dy / len,
+            (p1.y + p2.y) / 2f64 - eps * dx / len,
+        );
+        let wr = winding(&right);
+        let wl = wr + m;
+        if (wl > 0) != (wr > 0) {
+            if wl > 0 {
+                out_edges.push((u, v));
+            } else {
+                out_edges.push((v, u));
+            }
+        }
+    }
+    out_edges.sort();
+
+    // link the edges into rings, turning as far left as possible at each node
+    let mut outgoing: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+    for e in 0..out_edges.len() {
+        outgoing[out_edges[e].0].push(e);
+    }
+    let mut used = vec![false; out_edges.len()];
+    let mut exteriors: Vec<(Vec<Point2D>, f64)> = vec![];
+    let mut holes: Vec<Vec<Point2D>> = vec![];
+    for start in 0..out_edges.len() {
+        if used[start] {
+            continue;
+        }
+        let start_node = out_edges[start].0;
+        let mut ring = vec![nodes[start_node]];
+        let mut e = start;
+        let mut closed = false;
+        loop {
+            used[e] = true;
+            let (u, v) = out_edges[e];
+            ring.push(nodes[v]);
+            if v == start_node {
+                closed = true;
+                break;
+            }
+            let back = (nodes[u].y - nodes[v].y).atan2(nodes[u].x - nodes[v].x);
+            let mut next = None;
+            let mut min_angle = f64::INFINITY;
+            for &c in &outgoing[v] {
+                if !used[c] {
+                    let w = out_edges[c].1;
+                    let a = (nodes[w].y - nodes[v].y).atan2(nodes[w].x - nodes[v].x);
+                    let mut clockwise = (back - a).rem_euclid(2f64 * PI);
+                    if clockwise == 0f64 {
+                        clockwise = 2f64 * PI;
+                    }
+                    if clockwise < min_angle {
+                        min_angle = clockwise;
+                        next = Some(c);
+                    }
+                }
+            }
+            match next {
+                Some(c) => e = c,
+                None => break,
+            }
+        }
+        if !closed {
+            continue;
+        }
+        let ring = remove_collinear_vertices(ring, snap);
+        if ring.len() < 4 {
+            continue;
+        }
+        let area = signed_area(&ring);
+        if area > 0f64 {
+            exteriors.push((ring, area));
+        } else if area < 0f64 {
+            holes.push(ring);
+        }
+    }
+
+    // assign each hole to the smallest exterior ring containing it
+    let mut polygons: Vec<Vec<Vec<Point2D>>> =
+        exteriors.iter().map(|(r, _)| vec![r.clone()]).collect();
+    for hole in holes {
+        // a point just inside the filled area bordering the hole
+        let (p1, p2) = (hole[0], hole[1]);
+        let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+        let len = (dx * dx + dy * dy).sqrt();
+        let eps = (len * 1e-3).max(snap * 10f64);
+        let p = Point2D::new(
+            (p1.x + p2.x) / 2f64 - eps * dy / len,
+            (p1.y + p2.y) / 2f64 + eps * dx / len,
+        );
+        let mut containing = None;
+        let mut min_area = f64::INFINITY;
+        for (i, (ring, area)) in exteriors.iter().enumerate() {
+            if *area < min_area && point_in_poly(&p, ring) {
+                min_area = *area;
+                containing = Some(i);
+            }
+        }
+        if let Some(i) = containing {
+            polygons[i].push(hole);
+        }
+    }
+    polygons
+}
+
+/// Returns the parameters, along each of the edges p1-p2 and q1-q2, of the points at
+/// which the edges intersect, excluding points within `snap` of the edge ends.
+fn edge_intersections(
+    p1: &Point2D,
+    p2: &Point2D,
+    q1: &Point2D,
+    q2: &Point2D,
+    snap: f64,
+) -> (Vec<f64>, Vec<f64>) {
+    let (mut ta, mut tb) = (vec![], vec![]);
+    let (rx, ry) = (p2.x - p1.x, p2.y - p1.y);
+    let (sx, sy) = (q2.x - q1.x, q2.y - q1.y);
+    let len_r = (rx * rx + ry * ry).sqrt();
+    let len_s = (sx * sx + sy * sy).sqrt();
+    let denom = rx * sy - ry * sx;
+    let (qpx, qpy) = (q1.x - p1.x, q1.y - p1.y);
+    let interior = |t: f64, len: f64| t * len > snap && (1f64 - t) * len > snap;
+    if denom.abs() > 1e-12 * len_r * len_s {
+        let t = (qpx * sy - qpy * sx) / denom;
+        let u = (qpx * ry - qpy * rx) / denom;
+        let (tol_t, tol_u) = (snap / len_r, snap / len_s);
+        if t >= -tol_t && t <= 1f64 + tol_t && u >= -tol_u && u <= 1f64 + tol_u {
+            if interior(t, len_r) {
+                ta.push(t);
+            }
+            if interior(u, len_s) {
+                tb.push(u);
+            }
+        }
+    } else if (qpx * ry - qpy * rx).abs() / len_r <= snap {
+        // the edges are collinear; split each at the other's end points
+        for q in [q1, q2].iter() {
+            let t = ((q.x - p1.x) * rx + (q.y - p1.y) * ry) / (len_r * len_r);
+            if interior(t, len_r) {
+                ta.push(t);
+            }
+        }
+        for p in [p1, p2].iter() {
+            let u = ((p.x - q1.x) * sx + (p.y - q1.y) * sy) / (len_s * len_s);
+            if interior(u, len_s) {
+                tb.push(u);
+            }
+        }
+    }
+    (ta, tb)
+}
+
+/// Removes the vertices of a closed ring that lie on the straight line between their
+/// neighbours, such as those created where the edges were split at intersections.
+fn remove_collinear_vertices(ring: Vec<Point2D>, snap: f64) -> Vec<Point2D> {
+    let mut points: Vec<Point2D> = ring[0..ring.len() - 1].to_vec();
+    let mut i = 0;
+    while points.len() > 3 && i < points.len() {
+        let n = points.len();
+        let (prev, p, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (dx, dy) = (next.x - prev.x, next.y - prev.y);
+        let cross = (p.x - prev.x) * dy - (p.y - prev.y) * dx;
+        let dot = (p.x - prev.x) * (next.x - p.x) + (p.y - prev.y) * (next.y - p.y);
+        if dot > 0f64 && cross.abs() <= snap * (dx * dx + dy * dy).sqrt() {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    if !points.is_empty() {
+        points.push(points[0]);
+    }
+    points
+}
+
+/// Signed area of a closed ring; positive for counter-clockwise rings.
+fn signed_area(ring: &[Point2D]) -> f64 {
+    let mut area = 0f64;
+    for i in 0..ring.len() - 1 {
+        area += ring[i].x * ring[i + 1].y - ring[i + 1].x * ring[i].y;
+    }
+    area / 2f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::{signed_area, union_rings};
+    use crate::structures::Point2D;
+
+    fn square(x: f64, y: f64, size: f64) -> Vec<Point2D> {
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x + size, y),
+            Point2D::new(x + size, y + size),
+            Point2D::new(x, y + size),
+        ]
+    }
+
+    #[test]
+    fn test_union_of_overlapping_squares() {
+        let polygons = union_rings(&[square(0.0, 0.0, 2.0), square(1.0, 1.0, 2.0)]);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].len(), 1);
+        assert!((signed_area(&polygons[0][0]) - 7.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_union_with_hole() {
+        // a clockwise ring within a counter-clockwise ring creates a hole
+        let mut hole = square(1.0, 1.0, 1.0);
+        hole.reverse();
+        let polygons = union_rings(&[square(0.0, 0.0, 3.0), hole]);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].len(), 2);
+        assert!((signed_area(&polygons[0][1]) + 1.0).abs() < 1e-10);
+
+        // disjoint squares remain separate polygons
+        let polygons = union_rings(&[square(0.0, 0.0, 1.0), square(5.0, 5.0, 1.0)]);
+        assert_eq!(polygons.len(), 2);
+    }
+
+    #[test]
+    fn test_union_of_self_intersecting_ring() {
+        // a bow-tie; only the counter-clockwise lobe is retained
+        let ring = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(2.0, 2.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(0.0, 2.0),
+        ];
+        let polygons = union_rings(&[ring]);
+        assert_eq!(polygons.len(), 1);
+        assert!((signed_area(&polygons[0][0]) - 1.0).abs() < 1e-10);
+    }
+}
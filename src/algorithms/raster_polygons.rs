@@ -0,0 +1,400 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::{douglas_peucker, generalize_lines, is_clockwise_order};
+use crate::raster::Raster;
+use crate::structures::{Array2D, Point2D};
+use crate::vector::{ShapeType, ShapefileGeometry};
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use std::collections::VecDeque;
+use std::f64;
+use std::io::Error;
+
+/// Traces polygons around the regions of a raster. All grid cells containing non-zero, non-NoData
+/// values are considered part of a region, and cells that are diagonally connected to a region of
+/// the same value are considered part of that region. Polygons are traced along the edges of grid
+/// cells, and regions of other values that are enclosed by a polygon are represented by interior
+/// rings (holes). If `tolerance` is greater than zero, the boundaries are simplified using the
+/// Douglas-Peucker algorithm, such that the boundaries shared by adjacent polygons remain identical.
+///
+/// Returns the polygon of each region along with the region's cell value.
+pub fn raster_to_polygons(
+    input: &Raster,
+    tolerance: f64,
+    verbose: bool,
+) -> Result<(Vec<ShapefileGeometry>, Vec<f64>), Error> {
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let res_x = input.configs.resolution_x;
+    let res_y = input.configs.resolution_y;
+    let half_res_x = res_x / 2f64;
+    let half_res_y = res_y / 2f64;
+    let west = input.configs.west;
+    let north = input.configs.north;
+
+    let get_x_from_column = |col| -> f64 { west + half_res_x + col as f64 * res_x };
+    let get_y_from_row = |row| -> f64 { north - half_res_y - row as f64 * res_y };
+
+    let dx = [0, 1, 0, -1, 1, 1, -1, -1];
+    let dy = [-1, 0, 1, 0, -1, 1, 1, -1];
+    let (mut rn, mut cn): (isize, isize);
+    let (mut z, mut zn): (f64, f64);
+
+    // Clump the input raster
+    let mut clumps: Array2D<u32> = Array2D::new(rows, columns, 0u32, 0u32)?;
+    let mut visited: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+    let mut queue = VecDeque::new();
+    let mut clump_val = 1u32;
+    let mut clump_to_value = vec![];
+    clump_to_value.push(0f64); // clump values start at 1
+    for row in 0..rows {
+        for col in 0..columns {
+            z = input.get_value(row, col);
+            if z != nodata && z != 0f64 && visited.get_value(row, col) != 1 {
+                clump_to_value.push(z);
+                clumps.set_value(row, col, clump_val);
+                visited.set_value(row, col, 1);
+                queue.push_back((row, col));
+                while let Some(cell) = queue.pop_front() {
+                    for n in 0..8 {
+                        rn = cell.0 + dy[n];
+                        cn = cell.1 + dx[n];
+                        zn = input.get_value(rn, cn);
+                        if z == zn && visited.get_value(rn, cn) != 1 {
+                            clumps.increment(rn, cn, clump_val);
+                            visited.set_value(rn, cn, 1);
+                            queue.push_back((rn, cn));
+                        }
+                    }
+                }
+                clump_val += 1;
+            }
+        }
+
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Clumping polygons: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    drop(visited);
+
+    /*  Diagram 1:
+     *  Edge Numbering (shared edges between cells)
+     *  _____________
+     *  |     |     |
+     *  |     3     |
+     *  |__2__|__0__|
+     *  |     |     |
+     *  |     1     |
+     *  |_____|_____|
+     *
+     */
+
+    /* Diagram 2:
+     * Cell Edge Numbering
+     *
+     *  ___0___
+     * |       |
+     * |       |
+     * 3       1
+     * |       |
+     * |___2___|
+     *
+     */
+
+    const EPSILON: f64 = std::f64::EPSILON;
+    let prec = (5f64 * EPSILON).tan();
+    let (mut p1, mut p2, mut p3): (Point2D, Point2D, Point2D);
+    // let mut edges: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+    // let mut num_edges: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+    // let mut cell_edges: u8;
+    let mut z: u32;
+    let mut zn: u32;
+    let (mut x, mut y): (f64, f64);
+    let (mut edge_x, mut edge_y): (f64, f64);
+    let mut line_segments: Vec<LineSegment> = vec![];
+    let edge_offsets_pt1_x = [-half_res_x, half_res_x, half_res_x, -half_res_x];
+    let edge_offsets_pt1_y = [half_res_y, half_res_y, -half_res_y, -half_res_y];
+    let edge_offsets_pt3_x = [half_res_x, half_res_x, -half_res_x, -half_res_x];
+    let edge_offsets_pt3_y = [half_res_y, -half_res_y, -half_res_y, half_res_y];
+    let dimensions = 2;
+    let capacity_per_node = 64;
+    let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
+    let mut endnode = 0usize;
+    for row in 0..rows {
+        for col in 0..columns {
+            z = clumps.get_value(row, col);
+            if z != 0 {
+                // cell_edges = 0u8;
+                for n in 0..4 {
+                    zn = clumps.get_value(row + dy[n], col + dx[n]);
+                    if z != zn {
+                        // cell_edges |= 1u8 << n;
+                        // if n < 4 {
+                        //     // Edges are only counted on the non-diagonal cells
+                        //     num_edges.increment(row, col, 1u8);
+                        // }
+                        x = get_x_from_column(col);
+                        y = get_y_from_row(row);
+
+                        edge_x = x + edge_offsets_pt1_x[n];
+                        edge_y = y + edge_offsets_pt1_y[n];
+                        p1 = Point2D::new(edge_x, edge_y);
+
+                        tree.add([p1.x, p1.y], endnode).unwrap();
+                        endnode += 1;
+
+                        edge_x = x + edge_offsets_pt3_x[n];
+                        edge_y = y + edge_offsets_pt3_y[n];
+                        p2 = Point2D::new(edge_x, edge_y);
+
+                        tree.add([p2.x, p2.y], endnode).unwrap();
+                        endnode += 1;
+
+                        line_segments.push(LineSegment::new(p1, p2, z));
+                    }
+                }
+                // edges.set_value(row, col, cell_edges);
+            }
+        }
+
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Finding edges: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let mut geometries = vec![ShapefileGeometry::new(ShapeType::Polygon); clump_val as usize - 1];
+    let mut segment_live = vec![true; line_segments.len()];
+    let num_nodes = line_segments.len() * 2;
+    let mut line_segment_n: usize;
+    let mut current_node: usize;
+    let mut heading: f64;
+    let mut max_heading: f64;
+    let mut node_of_max_deflection: usize;
+    let mut node: usize;
+    let mut line_start: usize;
+    let mut flag: bool;
+    for line_segment in 0..line_segments.len() {
+        if segment_live[line_segment] {
+            z = line_segments[line_segment].value;
+
+            line_start = line_segment * 2;
+            current_node = line_start;
+            let mut points = vec![];
+            flag = true;
+            while flag {
+                line_segment_n = current_node / 2;
+
+                // Add the current_node to points.
+                // Is the current_node a starting point?
+                p1 = if current_node % 2 == 0 {
+                    line_segments[line_segment_n].first_vertex()
+                } else {
+                    line_segments[line_segment_n].last_vertex()
+                };
+                points.push(p1);
+
+                // Is it the first node encountered from this segment?
+                if segment_live[line_segment_n] {
+                    segment_live[line_segment_n] = false;
+                    // This is the first node encountered from this segment, retrieve the other end
+                    current_node = if current_node % 2 == 0 {
+                        current_node + 1
+                    } else {
+                        current_node - 1
+                    };
+                    points.push(line_segments[line_segment_n].half_point());
+                } else {
+                    // We've now added both ends of this segment. Find the next connecting segment.
+                    let ret = tree
+                        .within(&[p1.x, p1.y], prec, &squared_euclidean)
+                        .unwrap();
+
+                    let mut connected_nodes: Vec<usize> = Vec::with_capacity(ret.len());
+                    for a in 0..ret.len() {
+                        node = *ret[a].1;
+                        line_segment_n = node / 2;
+                        zn = line_segments[line_segment_n].value;
+                        if zn == z && segment_live[line_segment_n] {
+                            connected_nodes.push(node);
+                        }
+                    }
+
+                    if connected_nodes.len() == 0 {
+                        flag = false; // end of the line; no other connected segments
+                    } else if connected_nodes.len() == 1 {
+                        current_node = connected_nodes[0]; // only one connected segment; move there.
+                    } else if connected_nodes.len() >= 2 {
+                        // there are two or more connected segments; choose the node the represents the greatest deflection in path
+                        line_segment_n = current_node / 2;
+                        p1 = if current_node % 2 == 0 {
+                            line_segments[line_segment_n].last_vertex()
+                        } else {
+                            line_segments[line_segment_n].first_vertex()
+                        };
+
+                        p2 = if current_node % 2 == 0 {
+                            line_segments[line_segment_n].first_vertex()
+                        } else {
+                            line_segments[line_segment_n].last_vertex()
+                        };
+
+                        max_heading = 0f64;
+                        node_of_max_deflection = num_nodes;
+                        for n in 0..connected_nodes.len() {
+                            line_segment_n = connected_nodes[n] / 2;
+                            p3 = if connected_nodes[n] % 2 == 0 {
+                                // get the other end of this segment
+                                line_segments[line_segment_n].last_vertex()
+                            } else {
+                                line_segments[line_segment_n].first_vertex()
+                            };
+                            heading = Point2D::change_in_heading(p1, p2, p3).abs();
+                            if heading > max_heading {
+                                max_heading = heading;
+                                node_of_max_deflection = n;
+                            }
+                        }
+                        if node_of_max_deflection < num_nodes {
+                            current_node = connected_nodes[node_of_max_deflection];
+                        } else {
+                            flag = false; // we should not get here
+                        }
+                    }
+                }
+            }
+
+            if points.len() > 2 {
+                // Remove unnecessary points
+                for a in (1..points.len() - 1).rev() {
+                    p1 = points[a - 1];
+                    p2 = points[a];
+                    p3 = points[a + 1];
+                    if ((p2.y - p1.y) * (p3.x - p2.x) - (p3.y - p2.y) * (p2.x - p1.x)).abs()
+                        <= ((p2.x - p1.x) * (p3.x - p2.x) + (p2.y - p1.y) * (p3.y - p2.y)).abs()
+                            * prec
+                    {
+                        points.remove(a);
+                    }
+                }
+                if points[0] != points[points.len() - 1] {
+                    points.push(points[0].clone());
+                }
+                if geometries[z as usize - 1].num_parts > 0 {
+                    // It's a hole.
+                    if is_clockwise_order(&points) {
+                        points.reverse();
+                    }
+                }
+                geometries[z as usize - 1].add_part(&points);
+            }
+        }
+        if verbose {
+            progress =
+                (100.0_f64 * line_segment as f64 / (line_segments.len() - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Tracing polygons: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    if tolerance > 0f64 {
+        if verbose {
+            println!("Simplifying polygons...")
+        };
+        // Snap the vertices to the half-cell grid so that the boundaries shared by
+        // neighbouring polygons have identical coordinates.
+        let snap = |p: &Point2D| -> Point2D {
+            Point2D::new(
+                west + ((p.x - west) / half_res_x).round() * half_res_x,
+                north - ((north - p.y) / half_res_y).round() * half_res_y,
+            )
+        };
+        let mut parts: Vec<Vec<Point2D>> = vec![];
+        for geometry in &geometries {
+            for part in 0..geometry.num_parts as usize {
+                let start_point = geometry.parts[part] as usize;
+                let end_point = if part < geometry.num_parts as usize - 1 {
+                    geometry.parts[part + 1] as usize
+                } else {
+                    geometry.num_points as usize
+                };
+                parts.push(
+                    geometry.points[start_point..end_point]
+                        .iter()
+                        .map(snap)
+                        .collect(),
+                );
+            }
+        }
+        let simplified =
+            generalize_lines(&parts, true, |points, _| douglas_peucker(points, tolerance));
+        let mut p = 0;
+        for geometry in geometries.iter_mut() {
+            let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+            for _ in 0..geometry.num_parts {
+                // rings that degenerate are left unchanged
+                if simplified[p].len() >= 4 {
+                    sfg.add_part(&simplified[p]);
+                } else {
+                    sfg.add_part(&parts[p]);
+                }
+                p += 1;
+            }
+            *geometry = sfg;
+        }
+    }
+
+    clump_to_value.remove(0);
+    Ok((geometries, clump_to_value))
+}
+
+#[derive(Clone, Copy)]
+struct LineSegment {
+    p1: Point2D,
+    p2: Point2D,
+    value: u32,
+}
+
+impl LineSegment {
+    fn new(p1: Point2D, p2: Point2D, value: u32) -> LineSegment {
+        LineSegment {
+            p1: p1,
+            p2: p2,
+            value: value,
+        }
+    }
+
+    pub fn first_vertex(&self) -> Point2D {
+        self.p1
+    }
+
+    pub fn last_vertex(&self) -> Point2D {
+        self.p2
+    }
+
+    pub fn half_point(&self) -> Point2D {
+        Point2D::new(
+            (self.p1.x + self.p2.x) / 2f64,
+            (self.p1.y + self.p2.y) / 2f64,
+        )
+    }
+}
@@ -0,0 +1,552 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use statrs::distribution::{Normal, Univariate};
+
+/// The result of a Mann-Kendall test for a monotonic trend in a series of values.
+#[derive(Clone, Copy, Debug)]
+pub struct MannKendall {
+    /// The Mann-Kendall S statistic, i.e. the number of increasing pairs of values
+    /// minus the number of decreasing pairs.
+    pub s: f64,
+    /// The variance of S, corrected for tied values.
+    pub variance: f64,
+    /// The continuity-corrected standard normal score of S.
+    pub z: f64,
+    /// The two-sided p-value of the test.
+    pub p_value: f64,
+    /// Kendall's tau, i.e. S divided by the number of pairs of values.
+    pub tau: f64,
+}
+
+/// Estimates the slope and intercept of the line through a set of (`x`, `y`) observations
+/// using the Theil-Sen estimator, i.e. the median of the slopes between all pairs of
+/// observations with distinct `x` values, and the median of `y - slope * x`. The estimator
+/// is robust to outliers, tolerating up to about 29% of corrupted observations.
+/// Observations with a non-finite `x` or `y` are ignored. Returns `None` if there are fewer
+/// than two usable observations with distinct `x` values.
+pub fn theil_sen(x: &[f64], y: &[f64]) -> Option<(f64, f64)> {
+    let pts: Vec<(f64, f64)> = x
+        .iter()
+        .zip(y.iter())
+        .filter(|(a, b)| a.is_finite() && b.is_finite())
+        .map(|(a, b)| (*a, *b))
+        .collect();
+    let mut slopes = Vec::with_capacity(pts.len() * pts.len().saturating_sub(1) / 2);
+    for i in 0..pts.len() {
+        for j in i + 1..pts.len() {
+            if pts[j].0 != pts[i].0 {
+                slopes.push((pts[j].1 - pts[i].1) / (pts[j].0 - pts[i].0));
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return None;
+    }
+    let slope = median(&mut slopes);
+    let mut residuals: Vec<f64> = pts.iter().map(|p| p.1 - slope * p.0).collect();
+    let intercept = median(&mut residuals);
+    Some((slope, intercept))
+}
+
+/// Performs the Mann-Kendall test for a monotonic trend in a series of values, which
+/// are assumed to be in chronological order. Non-finite values, i.e. missing observations,
+/// are ignored. The variance of the S statistic is corrected for groups of tied values.
+/// Returns `None` if there are fewer than three usable values.
+pub fn mann_kendall(y: &[f64]) -> Option<MannKendall> {
+    let values: Vec<f64> = y.iter().cloned().filter(|v| v.is_finite()).collect();
+    let n = values.len();
+    if n < 3 {
+        return None;
+    }
+    let mut s = 0f64;
+    for i in 0..n {
+        for j in i + 1..n {
+            if values[j] > values[i] {
+                s += 1f64;
+            } else if values[j] < values[i] {
+                s -= 1f64;
+            }
+        }
+    }
+
+    // correction for tied groups
+    let mut sorted = values.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut ties = 0f64;
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && sorted[j] == sorted[i] {
+            j += 1;
+        }
+        let t = (j - i) as f64;
+        if t > 1f64 {
+            ties += t * (t - 1f64) * (2f64 * t + 5f64);
+        }
+        i = j;
+    }
+    let nf = n as f64;
+    let variance = (nf * (nf - 1f64) * (2f64 * nf + 5f64) - ties) / 18f64;
+    let z = if variance <= 0f64 {
+        0f64
+    } else if s > 0f64 {
+        (s - 1f64) / variance.sqrt()
+    } else if s < 0f64 {
+        (s + 1f64) / variance.sqrt()
+    } else {
+        0f64
+    };
+    let distribution = Normal::new(0.0, 1.0).unwrap();
+    let p_value = (2f64 * (1f64 - distribution.cdf(z.abs()))).min(1f64);
+    let tau = s / (nf * (nf - 1f64) / 2f64);
+    Some(MannKendall {
+        s: s,
+        variance: variance,
+        z: z,
+        p_value: p_value,
+        tau: tau,
+    })
+}
+
+/// Smooths a series of observations using a Savitzky-Golay filter, i.e. by fitting a
+/// polynomial of the specified `degree`, by least squares, to the observations within
+/// `half_window` positions on either side of each observation. The polynomials are fitted
+/// in time (`times`), such that irregularly spaced observations are handled correctly.
+/// Missing observations (non-finite values) are given zero weight and are estimated from
+/// their neighbours; positions for which fewer than `degree + 1` valid observations fall
+/// within the window are returned as NaN.
+pub fn savitzky_golay(
+    times: &[f64],
+    values: &[f64],
+    half_window: usize,
+    degree: usize,
+) -> Vec<f64> {
+    let weights: Vec<f64> = values
+        .iter()
+        .map(|v| if v.is_finite() { 1f64 } else { 0f64 })
+        .collect();
+    weighted_savitzky_golay(times, values, &weights, half_window, degree)
+}
+
+/// A Savitzky-Golay filter in which each observation is given a weight in the least-squares
+/// fits, e.g. to reduce the influence of observations that are likely to be contaminated by
+/// clouds. Observations with non-finite values or non-positive weights are ignored.
+pub fn weighted_savitzky_golay(
+    times: &[f64],
+    values: &[f64],
+    weights: &[f64],
+    half_window: usize,
+    degree: usize,
+) -> Vec<f64> {
+    let n = values.len();
+    let mut smoothed = vec![f64::NAN; n];
+    let num_coefs = degree + 1;
+    for k in 0..n {
+        let start = k.saturating_sub(half_window);
+        let end = (k + half_window + 1).min(n);
+        let mut scale = 0f64;
+        for i in start..end {
+            scale = scale.max((times[i] - times[k]).abs());
+        }
+        if scale == 0f64 {
+            scale = 1f64;
+        }
+        // normal equations of the weighted polynomial fit, centred on times[k]
+        let mut ata = vec![vec![0f64; num_coefs]; num_coefs];
+        let mut atb = vec![0f64; num_coefs];
+        let mut count = 0;
+        for i in start..end {
+            if !values[i].is_finite() || !(weights[i] > 0f64) {
+                continue;
+            }
+            count += 1;
+            let x = (times[i] - times[k]) / scale;
+            let mut powers = vec![1f64; num_coefs];
+            for j in 1..num_coefs {
+                powers[j] = powers[j - 1] * x;
+            }
+            for r in 0..num_coefs {
+                for c in 0..num_coefs {
+                    ata[r][c] += weights[i] * powers[r] * powers[c];
+                }
+                atb[r] += weights[i] * powers[r] * values[i];
+            }
+        }
+        if count < num_coefs {
+            continue;
+        }
+        if let Some(coefs) = solve_linear_system(ata, atb) {
+            smoothed[k] = coefs[0];
+        }
+    }
+    smoothed
+}
+
+/// Smooths a series of equally spaced observations using the Whittaker smoother (Eilers,
+/// 2003), i.e. the series that minimizes the weighted sum of squared deviations from the
+/// observations plus `lambda` times the sum of squared differences of order `order`. Larger
+/// values of `lambda` produce smoother series. Observations with non-finite values or
+/// non-positive weights are ignored, and are estimated by the smoother. Returns a series of
+/// NaN values if the system cannot be solved, e.g. if there are no valid observations.
+pub fn whittaker_smooth(values: &[f64], weights: &[f64], lambda: f64, order: usize) -> Vec<f64> {
+    let n = values.len();
+    let d = order.max(1).min(n.saturating_sub(1));
+    if n == 0 || d == 0 {
+        return values.to_vec();
+    }
+    // the coefficients of the difference operator of order d
+    let mut coefs = vec![1f64];
+    for _ in 0..d {
+        let mut next = vec![0f64; coefs.len() + 1];
+        for (j, c) in coefs.iter().enumerate() {
+            next[j] -= c;
+            next[j + 1] += c;
+        }
+        coefs = next;
+    }
+    // the lower band of W + lambda D'D; band[i][k] holds the element (i, i - k)
+    let mut band = vec![vec![0f64; d + 1]; n];
+    let mut rhs = vec![0f64; n];
+    for i in 0..n {
+        if values[i].is_finite() && weights[i] > 0f64 {
+            band[i][0] += weights[i];
+            rhs[i] = weights[i] * values[i];
+        }
+    }
+    for r in 0..n - d {
+        for a in 0..=d {
+            for b in 0..=a {
+                band[r + a][a - b] += lambda * coefs[a] * coefs[b];
+            }
+        }
+    }
+    // banded Cholesky decomposition
+    for i in 0..n {
+        for k in (0..=d.min(i)).rev() {
+            let j = i - k; // column, in increasing order
+            let mut sum = band[i][k];
+            for m in 1..=(d - k).min(j) {
+                // L(i, j - m) * L(j, j - m)
+                sum -= band[i][k + m] * band[j][m];
+            }
+            if k == 0 {
+                if !(sum > 0f64) {
+                    return vec![f64::NAN; n];
+                }
+                band[i][0] = sum.sqrt();
+            } else {
+                band[i][k] = sum / band[j][0];
+            }
+        }
+    }
+    // forward and back substitution
+    let mut y = vec![0f64; n];
+    for i in 0..n {
+        let mut sum = rhs[i];
+        for k in 1..=d.min(i) {
+            sum -= band[i][k] * y[i - k];
+        }
+        y[i] = sum / band[i][0];
+    }
+    let mut z = vec![0f64; n];
+    for i in (0..n).rev() {
+        let mut sum = y[i];
+        for k in 1..=d.min(n - 1 - i) {
+            sum -= band[i + k][k] * z[i + k];
+        }
+        z[i] = sum / band[i][0];
+    }
+    z
+}
+
+/// Updates the weights of a series of observations so that a subsequent smoothing fits the
+/// upper envelope of the series, following Chen et al. (2004). Observations at or above the
+/// fitted (smoothed) values retain their weights, while those below are down-weighted in
+/// proportion to their distance below the fit, relative to the largest such distance. This
+/// suits vegetation indices, which are biased low by clouds and atmospheric effects.
+pub fn upper_envelope_weights(values: &[f64], fitted: &[f64], weights: &mut [f64]) {
+    let mut max_dist = 0f64;
+    for i in 0..values.len() {
+        if values[i].is_finite() && fitted[i].is_finite() && fitted[i] - values[i] > max_dist {
+            max_dist = fitted[i] - values[i];
+        }
+    }
+    if max_dist == 0f64 {
+        return;
+    }
+    for i in 0..values.len() {
+        if values[i].is_finite() && fitted[i].is_finite() && values[i] < fitted[i] {
+            weights[i] *= 1f64 - (fitted[i] - values[i]) / max_dist;
+        }
+    }
+}
+
+/// Evaluates a double logistic function, which describes the seasonal rise and fall of
+/// vegetation greenness, at time `t`. The parameters are the minimum (dormant) value,
+/// the maximum value, the time and rate of green-up, and the time and rate of senescence.
+pub fn double_logistic(params: &[f64; 6], t: f64) -> f64 {
+    params[0]
+        + (params[1] - params[0])
+            * (1f64 / (1f64 + (-params[3] * (t - params[2])).exp())
+                - 1f64 / (1f64 + (-params[5] * (t - params[4])).exp()))
+}
+
+/// Fits a double logistic function (see `double_logistic`) to a series of observations
+/// using the Levenberg-Marquardt algorithm. Missing observations (non-finite values) are
+/// ignored. Returns `None` if there are fewer than seven valid observations or the fit
+/// fails to produce a rise followed by a fall in values.
+pub fn fit_double_logistic(times: &[f64], values: &[f64]) -> Option<[f64; 6]> {
+    let pts: Vec<(f64, f64)> = times
+        .iter()
+        .zip(values.iter())
+        .filter(|(t, v)| t.is_finite() && v.is_finite())
+        .map(|(t, v)| (*t, *v))
+        .collect();
+    if pts.len() < 7 {
+        return None;
+    }
+    let t_min = pts.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let t_max = pts.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let (mut v_min, mut v_max, mut t_peak) = (f64::INFINITY, f64::NEG_INFINITY, t_min);
+    for p in &pts {
+        if p.1 < v_min {
+            v_min = p.1;
+        }
+        if p.1 > v_max {
+            v_max = p.1;
+            t_peak = p.0;
+        }
+    }
+    if !(t_max > t_min) || !(v_max > v_min) {
+        return None;
+    }
+    let rate = 8f64 / (t_max - t_min);
+    let mut params = [
+        v_min,
+        v_max,
+        (t_min + t_peak) / 2f64,
+        rate,
+        (t_peak + t_max) / 2f64,
+        rate,
+    ];
+
+    let sse = |p: &[f64; 6]| -> f64 {
+        pts.iter()
+            .map(|q| (q.1 - double_logistic(p, q.0)).powi(2))
+            .sum()
+    };
+    let mut error = sse(&params);
+    let mut lambda = 0.001f64;
+    for _ in 0..200 {
+        // Jacobian by forward differences
+        let residuals: Vec<f64> = pts
+            .iter()
+            .map(|q| q.1 - double_logistic(&params, q.0))
+            .collect();
+        let mut jacobian = vec![[0f64; 6]; pts.len()];
+        for j in 0..6 {
+            let h = 1e-6 * params[j].abs().max(1e-3);
+            let mut shifted = params;
+            shifted[j] += h;
+            for (i, q) in pts.iter().enumerate() {
+                jacobian[i][j] =
+                    (double_logistic(&shifted, q.0) - double_logistic(&params, q.0)) / h;
+            }
+        }
+        let mut jtj = vec![vec![0f64; 6]; 6];
+        let mut jtr = vec![0f64; 6];
+        for i in 0..pts.len() {
+            for r in 0..6 {
+                for c in 0..6 {
+                    jtj[r][c] += jacobian[i][r] * jacobian[i][c];
+                }
+                jtr[r] += jacobian[i][r] * residuals[i];
+            }
+        }
+        let mut improved = false;
+        while lambda < 1e10 {
+            let mut a = jtj.clone();
+            for d in 0..6 {
+                a[d][d] += lambda * jtj[d][d].max(1e-12);
+            }
+            if let Some(step) = solve_linear_system(a, jtr.clone()) {
+                let mut candidate = params;
+                for j in 0..6 {
+                    candidate[j] += step[j];
+                }
+                let candidate_error = sse(&candidate);
+                if candidate_error.is_finite() && candidate_error < error {
+                    let change = (error - candidate_error) / error.max(1e-12);
+                    params = candidate;
+                    error = candidate_error;
+                    lambda = (lambda / 10f64).max(1e-12);
+                    improved = change > 1e-10;
+                    break;
+                }
+            }
+            lambda *= 10f64;
+        }
+        if !improved {
+            break;
+        }
+    }
+    if params.iter().any(|p| !p.is_finite())
+        || params[1] <= params[0]
+        || params[3] <= 0f64
+        || params[5] <= 0f64
+        || params[2] >= params[4]
+    {
+        return None;
+    }
+    Some(params)
+}
+
+/// Solves a small, dense system of linear equations using Gaussian elimination with
+/// partial pivoting. Returns `None` if the system is singular.
+fn solve_linear_system(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let mut pivot = col;
+        for row in col + 1..n {
+            if a[row][col].abs() > a[pivot][col].abs() {
+                pivot = row;
+            }
+        }
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            for c in col..n {
+                a[row][c] -= factor * a[col][c];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0f64; n];
+    for row in (0..n).rev() {
+        let mut sum = b[row];
+        for c in row + 1..n {
+            sum -= a[row][c] * x[c];
+        }
+        x[row] = sum / a[row][row];
+    }
+    Some(x)
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = values.len();
+    if n % 2 == 0 {
+        (values[n / 2 - 1] + values[n / 2]) / 2f64
+    } else {
+        values[n / 2]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        double_logistic, fit_double_logistic, mann_kendall, savitzky_golay, theil_sen,
+        whittaker_smooth,
+    };
+    #[test]
+    fn test_theil_sen() {
+        let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let mut y: Vec<f64> = x.iter().map(|v| 2f64 * v + 1f64).collect();
+        y[7] = 100f64; // an outlier
+        y[3] = f64::NAN; // a missing observation
+        let (slope, intercept) = theil_sen(&x, &y).unwrap();
+        assert!((slope - 2f64).abs() < 1e-10);
+        assert!((intercept - 1f64).abs() < 1e-10);
+
+        assert!(theil_sen(&[1f64, 1f64], &[2f64, 3f64]).is_none());
+    }
+
+    #[test]
+    fn test_mann_kendall() {
+        let increasing: Vec<f64> = (0..12).map(|i| i as f64 + (i % 3) as f64 * 0.5).collect();
+        let mk = mann_kendall(&increasing).unwrap();
+        assert!(mk.s > 0f64);
+        assert!(mk.z > 0f64);
+        assert!(mk.p_value < 0.01);
+        assert!(mk.tau > 0.8 && mk.tau <= 1f64);
+
+        // no trend: S is zero and the p-value is one
+        let flat = vec![2f64, 1f64, 1f64, 2f64];
+        let mk = mann_kendall(&flat).unwrap();
+        assert_eq!(mk.s, 0f64);
+        assert!((mk.p_value - 1f64).abs() < 1e-6);
+
+        // n = 4 with no ties: Var(S) = 4 * 3 * 13 / 18
+        let mk = mann_kendall(&[4f64, 3f64, 2f64, 1f64]).unwrap();
+        assert_eq!(mk.s, -6f64);
+        assert!((mk.variance - 156f64 / 18f64).abs() < 1e-10);
+        assert_eq!(mk.tau, -1f64);
+
+        assert!(mann_kendall(&[1f64, f64::NAN, 2f64]).is_none());
+    }
+
+    #[test]
+    fn test_savitzky_golay() {
+        // a quadratic is reproduced exactly by a second-degree filter, including at a gap
+        let times: Vec<f64> = (0..15).map(|i| i as f64 * 16f64).collect();
+        let mut values: Vec<f64> = times
+            .iter()
+            .map(|t| 0.001 * t * t - 0.2 * t + 3f64)
+            .collect();
+        let expected = values[6];
+        values[6] = f64::NAN;
+        let smoothed = savitzky_golay(&times, &values, 3, 2);
+        assert!((smoothed[6] - expected).abs() < 1e-8);
+        assert!((smoothed[0] - values[0]).abs() < 1e-8);
+
+        // too few valid observations within the window
+        let sparse = vec![1f64, f64::NAN, f64::NAN, f64::NAN, 2f64];
+        let smoothed = savitzky_golay(&[0f64, 1f64, 2f64, 3f64, 4f64], &sparse, 1, 1);
+        assert!(smoothed[2].is_nan());
+    }
+
+    #[test]
+    fn test_fit_double_logistic() {
+        let truth = [0.2f64, 0.8f64, 120f64, 0.08f64, 270f64, 0.06f64];
+        let times: Vec<f64> = (0..23).map(|i| i as f64 * 16f64).collect();
+        let values: Vec<f64> = times.iter().map(|t| double_logistic(&truth, *t)).collect();
+        let params = fit_double_logistic(&times, &values).unwrap();
+        assert!((params[2] - truth[2]).abs() < 1f64);
+        assert!((params[4] - truth[4]).abs() < 1f64);
+        assert!((params[1] - truth[1]).abs() < 0.01);
+
+        assert!(fit_double_logistic(&[0f64, 1f64], &[1f64, 2f64]).is_none());
+    }
+
+    #[test]
+    fn test_whittaker_smooth() {
+        // second-order differences of a line are zero, so a line is reproduced exactly,
+        // and the missing observation is interpolated
+        let mut values: Vec<f64> = (0..10).map(|i| 0.5 * i as f64 + 2f64).collect();
+        values[4] = f64::NAN;
+        let weights = vec![1f64; 10];
+        let smoothed = whittaker_smooth(&values, &weights, 100f64, 2);
+        for i in 0..10 {
+            assert!((smoothed[i] - (0.5 * i as f64 + 2f64)).abs() < 1e-8);
+        }
+
+        // noise is reduced
+        let noisy: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 1f64 } else { -1f64 })
+            .collect();
+        let smoothed = whittaker_smooth(&noisy, &vec![1f64; 20], 10f64, 2);
+        assert!(smoothed[10].abs() < 0.5);
+
+        assert!(whittaker_smooth(&[f64::NAN; 5], &[1f64; 5], 10f64, 2)[0].is_nan());
+    }
+}
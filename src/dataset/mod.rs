@@ -0,0 +1,121 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+In-memory datasets allow tools to be chained together by library users without
+round-tripping intermediate results through the file system. A dataset is
+registered under a file name beginning with `memory://`, and `Raster::new`,
+`Shapefile::read` and their `write` methods consult the registry whenever they
+encounter such a name, rather than accessing the disk.
+*/
+
+use crate::raster::Raster;
+use crate::vector::Shapefile;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// The file name prefix used to identify in-memory datasets.
+pub const MEMORY_PREFIX: &str = "memory://";
+
+static DATASETS: Mutex<BTreeMap<String, Dataset>> = Mutex::new(BTreeMap::new());
+
+/// A raster or vector dataset that is held in memory.
+#[derive(Clone)]
+pub enum Dataset {
+    Raster(Raster),
+    Vector(Shapefile),
+}
+
+impl Dataset {
+    /// Returns the file name associated with the dataset.
+    pub fn file_name(&self) -> &str {
+        match self {
+            Dataset::Raster(r) => &r.file_name,
+            Dataset::Vector(sf) => &sf.file_name,
+        }
+    }
+
+    /// Returns the file extension used when registering the dataset in memory.
+    pub fn extension(&self) -> &str {
+        match self {
+            Dataset::Raster(_) => "tif",
+            Dataset::Vector(_) => "shp",
+        }
+    }
+
+    pub fn as_raster(&self) -> Option<&Raster> {
+        match self {
+            Dataset::Raster(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    pub fn as_vector(&self) -> Option<&Shapefile> {
+        match self {
+            Dataset::Vector(sf) => Some(sf),
+            _ => None,
+        }
+    }
+
+    pub fn into_raster(self) -> Option<Raster> {
+        match self {
+            Dataset::Raster(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    pub fn into_vector(self) -> Option<Shapefile> {
+        match self {
+            Dataset::Vector(sf) => Some(sf),
+            _ => None,
+        }
+    }
+}
+
+impl From<Raster> for Dataset {
+    fn from(r: Raster) -> Dataset {
+        Dataset::Raster(r)
+    }
+}
+
+impl From<Shapefile> for Dataset {
+    fn from(sf: Shapefile) -> Dataset {
+        Dataset::Vector(sf)
+    }
+}
+
+/// Returns true if the file name refers to an in-memory dataset.
+pub fn is_in_memory(file_name: &str) -> bool {
+    file_name.starts_with(MEMORY_PREFIX)
+}
+
+/// Registers a dataset under an in-memory file name, replacing any existing
+/// dataset of the same name.
+pub fn store_dataset(file_name: &str, dataset: Dataset) {
+    DATASETS
+        .lock()
+        .expect("Error accessing in-memory datasets.")
+        .insert(file_name.to_string(), dataset);
+}
+
+/// Returns a copy of the in-memory dataset registered under a file name.
+pub fn get_dataset(file_name: &str) -> Option<Dataset> {
+    DATASETS
+        .lock()
+        .expect("Error accessing in-memory datasets.")
+        .get(file_name)
+        .cloned()
+}
+
+/// Removes the in-memory dataset registered under a file name, returning it.
+pub fn remove_dataset(file_name: &str) -> Option<Dataset> {
+    DATASETS
+        .lock()
+        .expect("Error accessing in-memory datasets.")
+        .remove(file_name)
+}
@@ -0,0 +1,355 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+A C ABI for embedding WhiteboxTools as a shared library. The declarations are also
+provided in the `include/whitebox_tools.h` header. Strings returned by the library
+are allocated by Rust and must be released with `wbt_free_string`.
+
+When a callback is supplied to `wbt_run_tool`, the tool is run in verbose mode and
+each line of its output is passed to the callback, along with the progress
+percentage when the line reports progress (or -1 otherwise). On Unix-like systems,
+the messages are captured by temporarily redirecting the process's standard output,
+and so runs with callbacks are serialized. The callback is always invoked on the
+calling thread.
+*/
+
+use crate::tools::ToolManager;
+use crate::utils::parse_progress;
+use libc::{c_char, c_int, c_void, size_t};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::io::{Error, ErrorKind};
+use std::panic;
+use std::path;
+use std::ptr;
+use std::sync::mpsc;
+use std::thread;
+
+/// The signature of the callback used to report tool messages and progress.
+pub type WbtCallback =
+    Option<extern "C" fn(message: *const c_char, progress: c_int, user_data: *mut c_void)>;
+
+/// Returned by `wbt_run_tool` when the tool completes successfully.
+pub const WBT_SUCCESS: c_int = 0;
+/// Returned by `wbt_run_tool` when the arguments are invalid, e.g. null or non-UTF-8.
+pub const WBT_INVALID_ARGUMENTS: c_int = 1;
+/// Returned by `wbt_run_tool` when the tool returns an error.
+pub const WBT_TOOL_ERROR: c_int = 2;
+/// Returned by `wbt_run_tool` when the tool panics.
+pub const WBT_TOOL_PANIC: c_int = 3;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = RefCell::new(None);
+}
+
+enum RunMessage {
+    Line(String),
+    Done(Result<(), Error>, bool),
+}
+
+/// Returns the library version. The string must be released with `wbt_free_string`.
+#[no_mangle]
+pub extern "C" fn wbt_version() -> *mut c_char {
+    to_c_string(env!("CARGO_PKG_VERSION"))
+}
+
+/// Returns a JSON array describing the available tools, each with a `name`,
+/// `description`, and `toolbox`. The string must be released with `wbt_free_string`.
+#[no_mangle]
+pub extern "C" fn wbt_list_tools() -> *mut c_char {
+    let tm = match ToolManager::new("", &false) {
+        Ok(tm) => tm,
+        Err(e) => {
+            set_last_error(&e.to_string());
+            return ptr::null_mut();
+        }
+    };
+    let tools: Vec<Value> = tm
+        .get_tool_details()
+        .into_iter()
+        .map(|(name, description, toolbox)| {
+            json!({ "name": name, "description": description, "toolbox": toolbox })
+        })
+        .collect();
+    to_c_string(&Value::Array(tools).to_string())
+}
+
+/// Returns the parameters (in json form) of a tool, or null if the tool is not
+/// recognized. The string must be released with `wbt_free_string`.
+#[no_mangle]
+pub extern "C" fn wbt_tool_parameters(tool_name: *const c_char) -> *mut c_char {
+    let tool_name = match from_c_string(tool_name) {
+        Some(s) => s,
+        None => {
+            set_last_error("The tool name is null or is not valid UTF-8.");
+            return ptr::null_mut();
+        }
+    };
+    let tm = match ToolManager::new("", &false) {
+        Ok(tm) => tm,
+        Err(e) => {
+            set_last_error(&e.to_string());
+            return ptr::null_mut();
+        }
+    };
+    match tm.get_tool_parameters(&tool_name) {
+        Ok(parameters) => to_c_string(&parameters),
+        Err(e) => {
+            set_last_error(&e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Runs a tool. The `args` are `num_args` command-line style arguments, e.g.
+/// `--input=DEM.tif`, and `working_directory` may be null. The `callback`, which may
+/// be null, receives the tool's messages and progress; `user_data` is passed through
+/// to the callback unchanged. Returns `WBT_SUCCESS` (0) or an error code, in which
+/// case `wbt_last_error` describes the error.
+#[no_mangle]
+pub extern "C" fn wbt_run_tool(
+    tool_name: *const c_char,
+    working_directory: *const c_char,
+    args: *const *const c_char,
+    num_args: size_t,
+    callback: WbtCallback,
+    user_data: *mut c_void,
+) -> c_int {
+    let tool_name = match from_c_string(tool_name) {
+        Some(s) => s,
+        None => {
+            set_last_error("The tool name is null or is not valid UTF-8.");
+            return WBT_INVALID_ARGUMENTS;
+        }
+    };
+    let mut working_dir = if working_directory.is_null() {
+        String::new()
+    } else {
+        match from_c_string(working_directory) {
+            Some(s) => s,
+            None => {
+                set_last_error("The working directory is not valid UTF-8.");
+                return WBT_INVALID_ARGUMENTS;
+            }
+        }
+    };
+    let sep = path::MAIN_SEPARATOR.to_string();
+    if !working_dir.is_empty() && !working_dir.ends_with(&sep) {
+        working_dir.push_str(&sep);
+    }
+    if args.is_null() && num_args > 0 {
+        set_last_error("The argument array is null.");
+        return WBT_INVALID_ARGUMENTS;
+    }
+    let mut tool_args: Vec<String> = Vec::with_capacity(num_args);
+    for i in 0..num_args {
+        match from_c_string(unsafe { *args.add(i) }) {
+            Some(s) => tool_args.push(s.trim().to_string()),
+            None => {
+                set_last_error(&format!(
+                    "Argument {} is null or is not valid UTF-8.",
+                    i + 1
+                ));
+                return WBT_INVALID_ARGUMENTS;
+            }
+        }
+    }
+
+    let (result, panicked) = match callback {
+        Some(cb) => run_with_callback(tool_name, working_dir, tool_args, |line| {
+            if let Ok(message) = CString::new(line) {
+                cb(
+                    message.as_ptr(),
+                    parse_progress(line).map_or(-1, |p| p as c_int),
+                    user_data,
+                );
+            }
+        }),
+        None => run_tool(tool_name, working_dir, tool_args, false),
+    };
+    match result {
+        Ok(()) => WBT_SUCCESS,
+        Err(e) => {
+            set_last_error(&e.to_string());
+            if panicked {
+                WBT_TOOL_PANIC
+            } else {
+                WBT_TOOL_ERROR
+            }
+        }
+    }
+}
+
+/// Returns a description of the most recent error on the calling thread, or null if
+/// there has been no error. The string must be released with `wbt_free_string`.
+#[no_mangle]
+pub extern "C" fn wbt_last_error() -> *mut c_char {
+    LAST_ERROR.with(|e| match *e.borrow() {
+        Some(ref message) => to_c_string(message),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Releases a string returned by the library.
+#[no_mangle]
+pub extern "C" fn wbt_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        unsafe {
+            drop(CString::from_raw(s));
+        }
+    }
+}
+
+/// Runs a tool, converting any panic into an error. The boolean is true if the tool panicked.
+pub(crate) fn run_tool(
+    tool_name: String,
+    working_dir: String,
+    args: Vec<String>,
+    verbose: bool,
+) -> (Result<(), Error>, bool) {
+    let result = panic::catch_unwind(move || {
+        let tm = ToolManager::new(&working_dir, &verbose)?;
+        tm.run_tool(tool_name, args)
+    });
+    match result {
+        Ok(r) => (r, false),
+        Err(e) => {
+            let message = if let Some(s) = e.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = e.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "The tool panicked.".to_string()
+            };
+            (Err(Error::new(ErrorKind::Other, message)), true)
+        }
+    }
+}
+
+/// Runs a tool on a separate thread in verbose mode, passing each line that it prints
+/// to `on_line` on the calling thread.
+#[cfg(unix)]
+pub(crate) fn run_with_callback<F: FnMut(&str)>(
+    tool_name: String,
+    working_dir: String,
+    args: Vec<String>,
+    mut on_line: F,
+) -> (Result<(), Error>, bool) {
+    use std::fs::File;
+    use std::io::{stdout, BufRead, BufReader, Write};
+    use std::os::unix::io::FromRawFd;
+    use std::sync::Mutex;
+
+    static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+    let _guard = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    // redirect the standard output into a pipe
+    let _ = stdout().flush();
+    let mut fds = [0 as c_int; 2];
+    let saved_stdout = unsafe {
+        if libc::pipe(fds.as_mut_ptr()) != 0 {
+            return (Err(Error::last_os_error()), false);
+        }
+        let saved = libc::dup(1);
+        libc::dup2(fds[1], 1);
+        libc::close(fds[1]);
+        saved
+    };
+    let (tx, rx) = mpsc::channel();
+    let reader_tx = tx.clone();
+    let reader = thread::spawn(move || {
+        let pipe = unsafe { File::from_raw_fd(fds[0]) };
+        for line in BufReader::new(pipe).lines() {
+            match line {
+                Ok(line) => {
+                    if reader_tx.send(RunMessage::Line(line)).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    });
+    thread::spawn(move || {
+        let (result, panicked) = run_tool(tool_name, working_dir, args, true);
+        let _ = stdout().flush();
+        let _ = tx.send(RunMessage::Done(result, panicked));
+    });
+
+    let mut outcome = (Ok(()), false);
+    for message in rx {
+        match message {
+            RunMessage::Line(line) => on_line(&line),
+            RunMessage::Done(result, panicked) => {
+                outcome = (result, panicked);
+                // restoring the standard output closes the pipe, ending the reader
+                unsafe {
+                    libc::dup2(saved_stdout, 1);
+                    libc::close(saved_stdout);
+                }
+            }
+        }
+    }
+    let _ = reader.join();
+    outcome
+}
+
+/// Runs a tool on a separate thread, reporting its completion to `on_line` on the
+/// calling thread. Tool messages are not captured on this platform.
+#[cfg(not(unix))]
+pub(crate) fn run_with_callback<F: FnMut(&str)>(
+    tool_name: String,
+    working_dir: String,
+    args: Vec<String>,
+    mut on_line: F,
+) -> (Result<(), Error>, bool) {
+    let (tx, rx) = mpsc::channel();
+    let name = tool_name.clone();
+    thread::spawn(move || {
+        let (result, panicked) = run_tool(tool_name, working_dir, args, false);
+        let _ = tx.send(RunMessage::Done(result, panicked));
+    });
+    match rx.recv() {
+        Ok(RunMessage::Done(result, panicked)) => {
+            if result.is_ok() {
+                on_line(&format!("{} completed: 100%", name));
+            }
+            (result, panicked)
+        }
+        _ => (
+            Err(Error::new(
+                ErrorKind::Other,
+                "The tool thread ended unexpectedly.",
+            )),
+            true,
+        ),
+    }
+}
+
+fn set_last_error(message: &str) {
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message.to_string()));
+}
+
+fn to_c_string(s: &str) -> *mut c_char {
+    match CString::new(s.replace('\0', "")) {
+        Ok(s) => s.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+fn from_c_string(s: *const c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .ok()
+        .map(|s| s.to_string())
+}
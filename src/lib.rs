@@ -0,0 +1,37 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*!
+The WhiteboxTools library. Rust applications may use the `tools::ToolManager` to run
+tools directly, or `tools::WhiteboxTool::run_in_memory` to pass in-memory datasets
+between tools. The library is also built as a shared library (cdylib) exposing a
+C ABI, see the `ffi` module, so that it may be embedded within R, Julia, C#, C++,
+and other applications without spawning a subprocess for each tool run. When
+compiled for the `wasm32` target, the `wasm` module instead exports an interface for
+running a subset of the raster tools within a web browser.
+*/
+
+#[macro_use]
+extern crate serde_derive;
+
+pub mod algorithms;
+pub mod dataset;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ffi;
+pub mod lidar;
+pub mod raster;
+pub mod rendering;
+pub mod spatial_ref_system;
+pub mod structures;
+pub mod tools;
+pub mod utils;
+pub mod vector;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+
+use nalgebra as na;
@@ -1873,6 +1873,34 @@ pub enum LidarPointRecord {
     },
 }
 
+impl LidarPointRecord {
+    /// Sets the classification of the point, leaving the remaining point data unaltered.
+    pub fn set_classification(&mut self, value: u8) {
+        self.point_data_mut().set_classification(value);
+    }
+
+    /// Sets the intensity of the point, leaving the remaining point data unaltered.
+    pub fn set_intensity(&mut self, value: u16) {
+        self.point_data_mut().intensity = value;
+    }
+
+    fn point_data_mut(&mut self) -> &mut PointData {
+        match self {
+            LidarPointRecord::PointRecord0 { point_data }
+            | LidarPointRecord::PointRecord1 { point_data, .. }
+            | LidarPointRecord::PointRecord2 { point_data, .. }
+            | LidarPointRecord::PointRecord3 { point_data, .. }
+            | LidarPointRecord::PointRecord4 { point_data, .. }
+            | LidarPointRecord::PointRecord5 { point_data, .. }
+            | LidarPointRecord::PointRecord6 { point_data, .. }
+            | LidarPointRecord::PointRecord7 { point_data, .. }
+            | LidarPointRecord::PointRecord8 { point_data, .. }
+            | LidarPointRecord::PointRecord9 { point_data, .. }
+            | LidarPointRecord::PointRecord10 { point_data, .. } => point_data,
+        }
+    }
+}
+
 #[derive(Default, Copy, Clone, Debug)]
 pub struct PointRecord0 {
     pub point_data: PointData,
@@ -3,6 +3,7 @@ mod header;
 mod las;
 mod point_data;
 mod vlr;
+mod voxel;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::header::LasHeader;
@@ -27,3 +28,5 @@ pub use self::point_data::ColourData;
 pub use self::point_data::PointData;
 pub use self::point_data::WaveformPacket;
 pub use self::vlr::Vlr;
+pub use self::voxel::VoxelGrid;
+pub use self::voxel::VoxelMetric;
@@ -0,0 +1,201 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::structures::BoundingBox;
+use std::f64;
+
+/// The per-voxel metrics that can be derived from a `VoxelGrid`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VoxelMetric {
+    /// The number of points in the voxel.
+    Count,
+    /// One if the voxel contains at least one point, zero otherwise.
+    Occupancy,
+    /// The proportion of the column's points that are in the voxel.
+    Density,
+    /// The plant area density (m<sup>2</sup>/m<sup>3</sup>) estimated from the gap fraction
+    /// above and below the voxel, i.e. the MacArthur-Horn method.
+    PlantAreaDensity,
+}
+
+impl VoxelMetric {
+    pub fn from_str(s: &str) -> Option<VoxelMetric> {
+        let s = s.trim().to_lowercase();
+        if s.contains("count") {
+            Some(VoxelMetric::Count)
+        } else if s.contains("occ") {
+            Some(VoxelMetric::Occupancy)
+        } else if s.contains("pad") || s.contains("plant") || s.contains("lad") {
+            Some(VoxelMetric::PlantAreaDensity)
+        } else if s.contains("dens") {
+            Some(VoxelMetric::Density)
+        } else {
+            None
+        }
+    }
+}
+
+/// A regular three-dimensional grid of point counts. Voxels are indexed by row (from the
+/// north), column (from the west), and layer (upward from `min_z`). Points that fall below
+/// the lowest layer are tallied with their column, so that they contribute to the column
+/// totals and gap fractions, but are not assigned to a voxel; points above the top layer or
+/// outside of the grid's horizontal extent are ignored.
+#[derive(Clone, Debug)]
+pub struct VoxelGrid {
+    pub west: f64,
+    pub north: f64,
+    pub min_z: f64,
+    pub resolution: f64,
+    pub vertical_resolution: f64,
+    pub rows: usize,
+    pub columns: usize,
+    pub layers: usize,
+    counts: Vec<u32>,
+    below: Vec<u32>,
+}
+
+impl VoxelGrid {
+    /// Creates an empty voxel grid covering a horizontal extent and the vertical extent from
+    /// `min_z` to `max_z`.
+    pub fn new(
+        extent: BoundingBox,
+        min_z: f64,
+        max_z: f64,
+        resolution: f64,
+        vertical_resolution: f64,
+    ) -> VoxelGrid {
+        let west = extent.min_x;
+        let north = extent.max_y;
+        let columns = (((extent.max_x - west) / resolution).ceil() as usize).max(1);
+        let rows = (((north - extent.min_y) / resolution).ceil() as usize).max(1);
+        let layers = (((max_z - min_z) / vertical_resolution).ceil() as usize).max(1);
+        VoxelGrid {
+            west: west,
+            north: north,
+            min_z: min_z,
+            resolution: resolution,
+            vertical_resolution: vertical_resolution,
+            rows: rows,
+            columns: columns,
+            layers: layers,
+            counts: vec![0u32; rows * columns * layers],
+            below: vec![0u32; rows * columns],
+        }
+    }
+
+    /// Adds a point to the grid, returning false if it is outside of the grid.
+    pub fn insert(&mut self, x: f64, y: f64, z: f64) -> bool {
+        let col = ((x - self.west) / self.resolution).floor();
+        let row = ((self.north - y) / self.resolution).floor();
+        if col < 0f64 || row < 0f64 {
+            return false;
+        }
+        let (row, col) = (row as usize, col as usize);
+        // points on the eastern and southern edges belong to the last column and row
+        let col = if col == self.columns && x - self.west <= self.columns as f64 * self.resolution {
+            col - 1
+        } else {
+            col
+        };
+        let row = if row == self.rows && self.north - y <= self.rows as f64 * self.resolution {
+            row - 1
+        } else {
+            row
+        };
+        if row >= self.rows || col >= self.columns {
+            return false;
+        }
+        let layer = ((z - self.min_z) / self.vertical_resolution).floor();
+        if layer < 0f64 {
+            self.below[row * self.columns + col] += 1;
+            return true;
+        }
+        let layer = if layer as usize == self.layers
+            && z - self.min_z <= self.layers as f64 * self.vertical_resolution
+        {
+            self.layers - 1
+        } else {
+            layer as usize
+        };
+        if layer >= self.layers {
+            return false;
+        }
+        self.counts[self.index(row, col, layer)] += 1;
+        true
+    }
+
+    fn index(&self, row: usize, col: usize, layer: usize) -> usize {
+        (layer * self.rows + row) * self.columns + col
+    }
+
+    /// Returns the number of points in a voxel.
+    pub fn count(&self, row: usize, col: usize, layer: usize) -> u32 {
+        self.counts[self.index(row, col, layer)]
+    }
+
+    /// Returns the number of points in a column, including those below the lowest layer.
+    pub fn column_total(&self, row: usize, col: usize) -> u32 {
+        let mut total = self.below[row * self.columns + col];
+        for layer in 0..self.layers {
+            total += self.count(row, col, layer);
+        }
+        total
+    }
+
+    /// Returns the height of the base of a layer.
+    pub fn layer_base(&self, layer: usize) -> f64 {
+        self.min_z + layer as f64 * self.vertical_resolution
+    }
+
+    /// Returns the vertical profile of a metric for a column, with one value per layer from
+    /// the bottom up, or None if the column does not contain any points. Plant area densities
+    /// are undefined (NaN) for layers below which no points are recorded, since the gap
+    /// fraction is zero; `extinction` is the extinction coefficient used to convert gap
+    /// fractions into plant area densities and is ignored by the other metrics.
+    pub fn profile(
+        &self,
+        row: usize,
+        col: usize,
+        metric: VoxelMetric,
+        extinction: f64,
+    ) -> Option<Vec<f64>> {
+        let total = self.column_total(row, col);
+        if total == 0 {
+            return None;
+        }
+        let counts: Vec<u32> = (0..self.layers)
+            .map(|layer| self.count(row, col, layer))
+            .collect();
+        Some(match metric {
+            VoxelMetric::Count => counts.iter().map(|&n| n as f64).collect(),
+            VoxelMetric::Occupancy => counts
+                .iter()
+                .map(|&n| if n > 0 { 1f64 } else { 0f64 })
+                .collect(),
+            VoxelMetric::Density => counts.iter().map(|&n| n as f64 / total as f64).collect(),
+            VoxelMetric::PlantAreaDensity => {
+                // the number of points below the base of each layer
+                let mut below = self.below[row * self.columns + col] as f64;
+                let mut pad = Vec::with_capacity(self.layers);
+                for &n in &counts {
+                    let gap_below = below / total as f64;
+                    let gap_above = (below + n as f64) / total as f64;
+                    pad.push(if n == 0 {
+                        0f64
+                    } else if gap_below > 0f64 {
+                        (gap_above / gap_below).ln() / (extinction * self.vertical_resolution)
+                    } else {
+                        f64::NAN
+                    });
+                    below += n as f64;
+                }
+                pad
+            }
+        })
+    }
+}
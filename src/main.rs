@@ -2,7 +2,7 @@
 This code is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: June 21, 2017
-Last Modified: 07/11/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -17,38 +17,41 @@ by the WhiteboxTools library:
 
 | Command           | Description                                                                                       |
 | ----------------- | ------------------------------------------------------------------------------------------------- |
+| --cache_size      | The daemon's dataset cache size limit, in megabytes; used in conjunction with --daemon.           |
 | --cd, --wd        | Changes the working directory; used in conjunction with --run flag.                               |
+| --compress        | Compresses GeoTIFF outputs; --compress=deflate, --compress=lzw, or --compress=zstd.               |
+| --compress_level  | DEFLATE or ZSTD compression level, from 1 (fastest) to 9 (smallest); default 6.                   |
+| --daemon          | Runs a daemon that caches input datasets between tool runs; --daemon=/tmp/wbt.sock.               |
+| --device          | Compute device for tools that support GPU acceleration; 'cpu' (default) or 'gpu'.                 |
+| --dist_halo       | Cells of overlap around each tile; used in conjunction with --distribute; default 1.              |
+| --dist_tile_size  | Tile size, in rows and columns; used in conjunction with --distribute; default 4096.              |
+| --distribute      | Runs a tool on tiles over workers listed in a file or list; --distribute=local:4,user@node01:8.   |
 | -h, --help        | Prints help information.                                                                          |
 | -l, --license     | Prints the whitebox-tools license.                                                                |
 | --listtools       | Lists all available tools, with tool descriptions. Keywords may also be used, --listtools slope.  |
+| --max_jobs        | The maximum number of concurrently running jobs; used in conjunction with --serve; default 1.     |
+| --max_mem         | Memory budget for tool arrays, beyond which they are spilled to disk; --max_mem=16G.             |
 | -r, --run         | Runs a tool; used in conjunction with --cd flag; -r="LidarInfo".                                  |
+| --predictor       | Predictor applied before compression; 'auto' (default), 'none', 'horizontal', or 'float'.         |
+| --resample_inputs | Resamples mismatched raster inputs onto the grid and CRS of the first input, rather than failing. |
+| --resume          | Resumes a failed pipeline, skipping completed steps; used in conjunction with --run_pipeline.     |
+| --run_pipeline    | Runs a workflow of tools described in a JSON file; --run_pipeline="workflow.json".                |
+| --seed            | Seeds the random number generators of stochastic tools, for reproducible results; --seed=42.      |
+| --serve           | Runs an HTTP service exposing the tools to web clients; --serve=127.0.0.1:8080.                   |
 | --toolbox         | Prints the toolbox associated with a tool; --toolbox=Slope.                                       |
 | --toolhelp        | Prints the help associated with a tool; --toolhelp="LidarInfo".                                   |
 | --toolparameters  | Prints the parameters (in json form) for a specific tool; --toolparameters=\"LidarInfo\".         |
 | -v                | Verbose mode. Without this flag, tool outputs will not be printed.                                |
 | --viewcode        | Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".                       |
 | --version         | Prints the version information.                                                                   |
+| --worker_exe      | Path of whitebox_tools on the workers; used in conjunction with --distribute.                     |
 
 */
 
-pub mod algorithms;
-pub mod lidar;
-pub mod raster;
-pub mod rendering;
-pub mod spatial_ref_system;
-pub mod structures;
-pub mod tools;
-pub mod utils;
-pub mod vector;
-
-use crate::tools::ToolManager;
-use nalgebra as na;
 use std::env;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use std::path;
-
-#[macro_use]
-extern crate serde_derive;
+use whitebox_tools::tools::ToolManager;
 
 /// WhiteboxTools is an advanced geospatial data analysis engine.
 ///
@@ -85,6 +88,20 @@ fn run() -> Result<(), Error> {
     let mut working_dir = String::new();
     let mut tool_name = String::new();
     let mut run_tool = false;
+    let mut pipeline_file = String::new();
+    let mut run_pipeline = false;
+    let mut resume = false;
+    let mut serve_address = String::new();
+    let mut serve = false;
+    let mut max_jobs = 1usize;
+    let mut daemon_address = String::new();
+    let mut daemon = false;
+    let mut cache_size = 0usize;
+    let mut workers = String::new();
+    let mut distribute = false;
+    let mut dist_tile_size = 4096usize;
+    let mut dist_halo = 1usize;
+    let mut worker_exe = String::new();
     let mut tool_help = false;
     let mut tool_parameters = false;
     let mut toolbox = false;
@@ -128,6 +145,126 @@ fn run() -> Result<(), Error> {
                 v.push_str(sep);
             }
             working_dir = v.to_string();
+        } else if arg.starts_with("-run_pipeline") || arg.starts_with("--run_pipeline") {
+            let mut v = arg
+                .replace("--run_pipeline", "")
+                .replace("-run_pipeline", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            pipeline_file = v;
+            run_pipeline = true;
+        } else if arg.starts_with("-resume") || arg.starts_with("--resume") {
+            resume = true;
+        } else if arg.starts_with("-serve") || arg.starts_with("--serve") {
+            let mut v = arg
+                .replace("--serve", "")
+                .replace("-serve", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            serve_address = v;
+            serve = true;
+        } else if arg.starts_with("-daemon") || arg.starts_with("--daemon") {
+            let mut v = arg
+                .replace("--daemon", "")
+                .replace("-daemon", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            daemon_address = v;
+            daemon = true;
+        } else if arg.starts_with("-cache_size") || arg.starts_with("--cache_size") {
+            let v = arg
+                .replace("--cache_size", "")
+                .replace("-cache_size", "")
+                .replace("=", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            cache_size = match v.trim().parse::<usize>() {
+                Ok(n) => n,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --cache_size value must be a non-negative integer number of megabytes.",
+                    ))
+                }
+            };
+        } else if arg.starts_with("-max_jobs") || arg.starts_with("--max_jobs") {
+            let v = arg
+                .replace("--max_jobs", "")
+                .replace("-max_jobs", "")
+                .replace("=", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            max_jobs = match v.trim().parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --max_jobs value must be a positive integer.",
+                    ))
+                }
+            };
+        } else if arg.starts_with("-distribute") || arg.starts_with("--distribute") {
+            let mut v = arg
+                .replace("--distribute", "")
+                .replace("-distribute", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            workers = v;
+            distribute = true;
+        } else if arg.starts_with("-dist_tile_size") || arg.starts_with("--dist_tile_size") {
+            let v = arg
+                .replace("--dist_tile_size", "")
+                .replace("-dist_tile_size", "")
+                .replace("=", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            dist_tile_size = match v.trim().parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --dist_tile_size value must be a positive integer number of cells.",
+                    ))
+                }
+            };
+        } else if arg.starts_with("-dist_halo") || arg.starts_with("--dist_halo") {
+            let v = arg
+                .replace("--dist_halo", "")
+                .replace("-dist_halo", "")
+                .replace("=", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            dist_halo = match v.trim().parse::<usize>() {
+                Ok(n) => n,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --dist_halo value must be a non-negative integer number of cells.",
+                    ))
+                }
+            };
+        } else if arg.starts_with("-worker_exe") || arg.starts_with("--worker_exe") {
+            let mut v = arg
+                .replace("--worker_exe", "")
+                .replace("-worker_exe", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            worker_exe = v;
         } else if arg.starts_with("-run") || arg.starts_with("--run") || arg.starts_with("-r") {
             let mut v = arg
                 .replace("--run", "")
@@ -231,15 +368,44 @@ fn run() -> Result<(), Error> {
         }
     }
 
+    if serve && working_dir.is_empty() {
+        // the service only exposes files within the working directory
+        working_dir = env::current_dir()?.to_string_lossy().to_string();
+    }
     let sep = path::MAIN_SEPARATOR;
     if !working_dir.ends_with(sep) {
         working_dir.push_str(&(sep.to_string()));
     }
     let tm = ToolManager::new(&working_dir, &verbose)?;
-    if run_tool {
+    if daemon {
+        if daemon_address.is_empty() {
+            daemon_address = "127.0.0.1:8090".to_string();
+        }
+        return tm.run_daemon(&daemon_address, cache_size, tool_args_vec);
+    } else if serve {
+        if serve_address.is_empty() {
+            serve_address = "127.0.0.1:8080".to_string();
+        }
+        return tm.serve(&serve_address, max_jobs, tool_args_vec);
+    } else if run_pipeline {
+        if pipeline_file.is_empty() && keywords.len() > 0 {
+            pipeline_file = keywords[0].clone();
+        }
+        return tm.run_pipeline(&pipeline_file, tool_args_vec, resume);
+    } else if run_tool {
         if tool_name.is_empty() && keywords.len() > 0 {
             tool_name = keywords[0].clone();
         }
+        if distribute {
+            return tm.run_distributed(
+                &tool_name,
+                tool_args_vec,
+                &workers,
+                dist_tile_size,
+                dist_halo,
+                &worker_exe,
+            );
+        }
         return tm.run_tool(tool_name, tool_args_vec);
     } else if tool_help {
         if tool_name.is_empty() && keywords.len() > 0 {
@@ -286,17 +452,34 @@ fn help() {
     let s = "WhiteboxTools Help
 
 The following commands are recognized:
+--cache_size     The daemon's dataset cache size limit, in megabytes; used with --daemon.
 --cd, --wd       Changes the working directory; used in conjunction with --run flag.
+--compress       Compresses GeoTIFF outputs; --compress=deflate, --compress=lzw, or --compress=zstd.
+--compress_level DEFLATE or ZSTD compression level, from 1 (fastest) to 9 (smallest); default 6.
+--daemon         Runs a daemon that caches input datasets between tool runs; --daemon=/tmp/wbt.sock.
+--device         Compute device for tools that support GPU acceleration; 'cpu' (default) or 'gpu'.
+--dist_halo      Cells of overlap around each tile; used with --distribute; default 1.
+--dist_tile_size Tile size, in rows and columns; used with --distribute; default 4096.
+--distribute     Runs a tool on tiles over workers listed in a file or list; --distribute=local:4,user@node01:8.
 -h, --help       Prints help information.
 -l, --license    Prints the whitebox-tools license.
 --listtools      Lists all available tools. Keywords may also be used, --listtools slope.
+--max_jobs       The maximum number of concurrently running jobs; used with --serve; default 1.
+--max_mem        Memory budget for tool arrays, beyond which they are spilled to disk; --max_mem=16G.
 -r, --run        Runs a tool; used in conjuction with --wd flag; -r=\"LidarInfo\".
+--predictor      Predictor applied before compression; 'auto', 'none', 'horizontal', or 'float'.
+--resample_inputs Resamples mismatched raster inputs onto the grid and CRS of the first input.
+--resume         Resumes a failed pipeline, skipping completed steps; used with --run_pipeline.
+--run_pipeline   Runs a workflow of tools described in a JSON file; --run_pipeline=\"workflow.json\".
+--seed           Seeds the random number generators of stochastic tools, for reproducible results; --seed=42.
+--serve          Runs an HTTP service exposing the tools to web clients; --serve=127.0.0.1:8080.
 --toolbox        Prints the toolbox associated with a tool; --toolbox=Slope.
 --toolhelp       Prints the help associated with a tool; --toolhelp=\"LidarInfo\".
 --toolparameters Prints the parameters (in json form) for a specific tool; --toolparameters=\"LidarInfo\".
 -v               Verbose mode. Without this flag, tool outputs will not be printed.
 --viewcode       Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".
 --version        Prints the version information.
+--worker_exe     Path of whitebox_tools on the workers; used with --distribute.
 
 Example Usage:
 >> .*EXE_NAME -r=lidar_info --cd=\"*path*to*data*\" -i=input.las --vlr --geokeys
@@ -9,6 +9,7 @@ use crate::raster::geotiff::tiff_consts::*;
 use crate::raster::*;
 use crate::spatial_ref_system::esri_wkt_from_epsg;
 use crate::structures::{Point2D, PolynomialRegression2D};
+use crate::utils::vfs::{is_virtual, read_file, write_file};
 use crate::utils::{ByteOrderReader, ByteOrderWriter, Endianness};
 use libflate::zlib::Decoder;
 use std::cmp::min;
@@ -16,9 +17,11 @@ use std::collections::HashMap;
 use std::default::Default;
 use std::f64;
 // use std::fs;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use ifd::{Entry, Ifd};
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, Read};
+use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, Read, Seek, Write};
 use std::mem;
 
 pub fn print_tags<'a>(file_name: &'a String) -> Result<(), Error> {
@@ -226,14 +229,26 @@ pub fn read_geotiff<'a>(
     configs: &'a mut RasterConfigs,
     data: &'a mut Vec<f64>,
 ) -> Result<(), Error> {
+    if is_virtual(file_name) {
+        let br = Cursor::new(read_file(file_name)?);
+        let th = ByteOrderReader::<Cursor<Vec<u8>>>::new(br, configs.endian);
+        return read_geotiff_data(th, configs, data);
+    }
     let f = File::open(file_name.clone())?;
+    let br = BufReader::new(f);
+    let th = ByteOrderReader::<BufReader<File>>::new(br, configs.endian);
+    read_geotiff_data(th, configs, data)
+}
 
+fn read_geotiff_data<'a, R: Read + Seek>(
+    mut th: ByteOrderReader<R>,
+    configs: &'a mut RasterConfigs,
+    data: &'a mut Vec<f64>,
+) -> Result<(), Error> {
     //////////////////////////
     // Read the TIFF header //
     //////////////////////////
 
-    let br = BufReader::new(f);
-    let mut th = ByteOrderReader::<BufReader<File>>::new(br, configs.endian);
 
     let bo_indicator1 = th.read_u8()?;
     let bo_indicator2 = th.read_u8()?;
@@ -451,11 +466,12 @@ pub fn read_geotiff<'a>(
         && compression != COMPRESS_PACKBITS
         && compression != COMPRESS_LZW
         && compression != COMPRESS_DEFLATE
+        && compression != COMPRESS_ZSTD
     {
         println!("Compression: {}", compression);
         return Err(Error::new(
             ErrorKind::InvalidData,
-            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, and DEFLATE compression.",
+            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, DEFLATE, and ZSTD compression.",
         ));
     }
 
@@ -687,6 +703,20 @@ pub fn read_geotiff<'a>(
         let lower_left = Point2D::new(lower_left_x, lower_left_y);
         configs.resolution_x = upper_right.distance(&upper_left) / configs.columns as f64;
         configs.resolution_y = upper_left.distance(&lower_left) / configs.rows as f64;
+    } else if configs.model_transformation[1] != 0.0 || configs.model_transformation[4] != 0.0 {
+        // A rotated grid. The extent describes the grid before its rotation about the
+        // upper-left corner.
+        let a = configs.model_transformation[0];
+        let b = configs.model_transformation[1];
+        let e = configs.model_transformation[4];
+        let f = configs.model_transformation[5];
+        configs.resolution_x = (a * a + e * e).sqrt();
+        configs.resolution_y = (b * b + f * f).sqrt();
+        configs.rotation = e.atan2(a).to_degrees();
+        configs.west = configs.model_transformation[3];
+        configs.north = configs.model_transformation[7];
+        configs.east = configs.west + configs.resolution_x * configs.columns as f64;
+        configs.south = configs.north - configs.resolution_y * configs.rows as f64;
     } else if configs.model_transformation[0] != 0.0 {
         configs.resolution_x = configs.model_transformation[0];
         configs.resolution_y = configs.model_transformation[5].abs();
@@ -741,6 +771,14 @@ pub fn read_geotiff<'a>(
         ));
     }
 
+    // GDAL_METADATA, used to record the cell shape of hexagonal grids
+    if let Some(ifd) = ifd_map.get(&TAG_GDAL_METADATA) {
+        let md = ifd.interpret_as_ascii().to_lowercase();
+        if md.contains("name=\"grid_type\">hexagonal") {
+            configs.grid_type = GridType::Hexagonal;
+        }
+    }
+
     // Get the EPSG code and WKT CRS
     configs.epsg_code = geokeys.find_epsg_code();
     configs.coordinate_ref_system_wkt = esri_wkt_from_epsg(configs.epsg_code);
@@ -1051,20 +1089,49 @@ pub fn read_geotiff<'a>(
         };
     }
 
+    // Check to see if a predictor is used; it is reversed on the decompressed bytes of each block
+    let predictor = match ifd_map.get(&317) {
+        Some(ifd) => ifd.interpret_as_u16()[0],
+        _ => 1u16,
+    };
+    let bytes_per_sample = (bits_per_sample[0] / 8) as usize;
+
+    // Grey images with more than one sample per pixel are read as multi-band rasters, with
+    // each band stored as a separate plane of the data. The samples may be interleaved
+    // (PlanarConfiguration = 1) or stored in separate blocks for each band (PlanarConfiguration = 2).
+    let num_bands = if (mode == IM_GRAY || mode == IM_GRAYINVERT) && bits_per_sample.len() > 1 {
+        bits_per_sample.len()
+    } else {
+        1usize
+    };
+    let is_planar = match ifd_map.get(&284) {
+        Some(ifd) => ifd.interpret_as_u16()[0] == 2 && bits_per_sample.len() > 1,
+        _ => false,
+    };
+    let num_planes = if is_planar {
+        bits_per_sample.len()
+    } else {
+        1usize
+    };
+    let plane_size = configs.rows * configs.columns;
+    configs.bands = num_bands;
+
     ////////////////////
     // Read the data! //
     ////////////////////
     if data.len() > 0 {
         data.clear();
     }
-    data.reserve_exact(configs.rows * configs.columns);
+    data.reserve_exact(plane_size * num_bands);
     unsafe {
         // The memory will be initialized when we read
         // the pixel values.
-        data.set_len(configs.rows * configs.columns);
+        data.set_len(plane_size * num_bands);
     }
 
-    for i in 0..blocks_across {
+    for block_column in 0..blocks_across * num_planes {
+        let plane = block_column / blocks_across;
+        let i = block_column % blocks_across;
         let mut blk_w = block_width;
         if !block_padding && i == blocks_across - 1 && width % block_width != 0 {
             blk_w = width % block_width;
@@ -1074,8 +1141,9 @@ pub fn read_geotiff<'a>(
             if !block_padding && j == blocks_down - 1 && height % block_height != 0 {
                 blk_h = height % block_height;
             }
-            let offset = block_offsets[j * blocks_across + i] as usize;
-            let n = block_counts[j * blocks_across + i] as usize;
+            let block_index = plane * blocks_across * blocks_down + j * blocks_across + i;
+            let offset = block_offsets[block_index] as usize;
+            let n = block_counts[block_index] as usize;
             let mut buf: Vec<u8> = vec![];
             if n != 0 {
                 // it's not a sparse tile
@@ -1119,15 +1187,34 @@ pub fn read_geotiff<'a>(
                         let mut decoder = Decoder::new(&compressed[..])?;
                         decoder.read_to_end(&mut buf).unwrap();
                     }
+                    COMPRESS_ZSTD => {
+                        th.seek(offset);
+                        let mut compressed = vec![0u8; n];
+                        th.read_exact(&mut compressed)?;
+                        buf = zstd_decode(&compressed)?;
+                    }
                     _ => {
                         return Err(Error::new(
                             ErrorKind::InvalidData,
-                            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, and DEFLATE compression.",
+                            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, DEFLATE, and ZSTD compression.",
                         ))
                     }
                 }
             }
 
+            if n != 0 && predictor > 1 && bytes_per_sample > 0 {
+                let row_width = if block_padding { block_width } else { blk_w };
+                let samples_per_pixel = bits_per_sample.len() / num_planes;
+                undo_predictor(
+                    &mut buf,
+                    predictor,
+                    row_width * samples_per_pixel,
+                    samples_per_pixel,
+                    bytes_per_sample,
+                    configs.endian == Endianness::LittleEndian,
+                );
+            }
+
             let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(Cursor::new(buf), configs.endian);
 
             let xmin = i * block_width;
@@ -1147,7 +1234,29 @@ pub fn read_geotiff<'a>(
             let mut off = 0;
             let mut i: usize;
             let (mut red, mut green, mut blue): (u32, u32, u32);
-            if n != 0 {
+            if num_bands > 1 {
+                // multi-band grey image
+                let bands = if is_planar {
+                    plane..plane + 1
+                } else {
+                    0..num_bands
+                };
+                let skip = skip_bytes * bytes_per_sample * bands.len();
+                for y in ymin..ymax {
+                    for x in xmin..xmax {
+                        for band in bands.clone() {
+                            data[band * plane_size + y * width + x] = if n != 0 {
+                                read_sample_value(&mut bor, bits_per_sample[0], sample_format[0])?
+                            } else {
+                                configs.nodata
+                            };
+                        }
+                    }
+                    if skip > 0 && n != 0 {
+                        bor.inc_pos(skip);
+                    }
+                }
+            } else if n != 0 {
                 match mode {
                     IM_GRAYINVERT | IM_GRAY => {
                         match sample_format[0] {
@@ -1567,25 +1676,6 @@ pub fn read_geotiff<'a>(
         }
     }
 
-    // Check to see if a predictor is used with LZW
-    match ifd_map.get(&317) {
-        Some(ifd) => {
-            if ifd.interpret_as_u16()[0] == 2 {
-                // Horizontal predictor
-                // transform the data
-                let mut idx: usize;
-                for row in 0..configs.rows {
-                    for col in 1..configs.columns {
-                        //(0..configs.columns-1).rev() {
-                        idx = row * configs.columns + col;
-                        data[idx] += data[idx - 1];
-                    }
-                }
-            }
-        }
-        _ => {} // do nothing,
-    }
-
     // match geokeys_map.get(&1024) {
     //     Some(ifd) => geokeys.add_key_directory(&ifd.data),
     //     _ => return Err(Error::new(ErrorKind::InvalidData, "The TIFF file does not contain geokeys")),
@@ -1606,13 +1696,34 @@ pub fn read_geotiff<'a>(
 }
 
 pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
+    if is_virtual(&r.file_name) {
+        let bow = ByteOrderWriter::<Vec<u8>>::new(vec![], r.configs.endian);
+        let bytes = write_geotiff_data(r, bow)?;
+        return write_file(&r.file_name, bytes);
+    }
     // get the ByteOrderWriter
     let f = File::create(r.file_name.clone())?;
     let writer = BufWriter::new(f);
-    let mut bow = ByteOrderWriter::<BufWriter<File>>::new(writer, r.configs.endian);
+    let bow = ByteOrderWriter::<BufWriter<File>>::new(writer, r.configs.endian);
+    let mut writer = write_geotiff_data(r, bow)?;
+    writer.flush()
+}
+
+/// Writes a GeoTIFF data stream, returning the underlying writer once complete.
+fn write_geotiff_data<'a, W: Write>(
+    r: &'a mut Raster,
+    mut bow: ByteOrderWriter<W>,
+) -> Result<W, Error> {
+
+    // multi-band rasters are written with one sample per band in each pixel
+    let bands = if r.configs.photometric_interp == PhotometricInterpretation::RGB {
+        1usize
+    } else {
+        r.configs.bands.max(1)
+    };
 
     // get the bytes per pixel
-    let total_bytes_per_pixel = r.configs.data_type.get_data_size();
+    let total_bytes_per_pixel = r.configs.data_type.get_data_size() * bands;
     if total_bytes_per_pixel == 0 {
         return Err(Error::new(
             ErrorKind::InvalidData,
@@ -1623,24 +1734,60 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         ));
     }
 
-    // is it a BigTiff?
-    let is_big_tiff = if 8usize
-        + (r.configs.rows * r.configs.columns) as usize * total_bytes_per_pixel
-        >= 4_000_000_000
+    // At the moment, categorical and paletted output is not supported.
+    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
+        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
     {
-        true
+        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    }
+    if r.configs.photometric_interp == PhotometricInterpretation::Unknown {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Error while writing GeoTIFF file.",
+        ));
+    }
+
+    // compressed rasters are written in multi-row strips, each of which is encoded in memory first
+    let predictor = match r.configs.compression {
+        RasterCompression::None => RasterPredictor::None,
+        _ => match (r.configs.predictor, r.configs.data_type) {
+            (RasterPredictor::Auto, DataType::F32) | (RasterPredictor::Auto, DataType::F64) => {
+                RasterPredictor::FloatingPoint
+            }
+            (RasterPredictor::Auto, _) => RasterPredictor::Horizontal,
+            (RasterPredictor::FloatingPoint, DataType::F32)
+            | (RasterPredictor::FloatingPoint, DataType::F64) => RasterPredictor::FloatingPoint,
+            (RasterPredictor::FloatingPoint, _) => RasterPredictor::Horizontal,
+            (p, _) => p,
+        },
+    };
+    let row_length_in_bytes = r.configs.columns * total_bytes_per_pixel;
+    let rows_per_strip = match r.configs.compression {
+        RasterCompression::None => 1usize,
+        _ => (65536 / row_length_in_bytes.max(1))
+            .max(1)
+            .min(r.configs.rows.max(1))
+            .min(65535),
+    };
+    let strips = match r.configs.compression {
+        RasterCompression::None => None,
+        _ => Some(encode_strips(r, rows_per_strip, predictor)?),
+    };
+    let header_size = if 8usize + r.configs.rows * row_length_in_bytes >= 4_000_000_000 {
+        16usize
     } else {
-        false
+        8usize
+    };
+    let image_data_size = match strips {
+        Some(ref s) => s.iter().map(|strip| strip.len()).sum::<usize>(),
+        None => r.configs.rows * row_length_in_bytes,
     };
 
+    // is it a BigTiff?
+    let is_big_tiff = header_size == 16usize;
+
     // get the offset to the first ifd
-    let mut ifd_start = if !is_big_tiff {
-        (8usize + (r.configs.rows * r.configs.columns) as usize * total_bytes_per_pixel) as u64
-    // plus the 8-byte header
-    } else {
-        (16usize + (r.configs.rows * r.configs.columns) as usize * total_bytes_per_pixel) as u64
-        // plus the 8-byte header
-    };
+    let mut ifd_start = (header_size + image_data_size) as u64; // plus the header
     let mut ifd_start_needs_extra_byte = false;
     if ifd_start % 2 == 1 {
         ifd_start += 1;
@@ -1674,173 +1821,19 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         bow.write_u64(ifd_start)?;
     }
 
-    // At the moment, categorical and paletted output is not supported.
-    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
-        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
-    {
-        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
-    }
-
     //////////////////////////
     // Write the image data //
     //////////////////////////
-    match r.configs.photometric_interp {
-        PhotometricInterpretation::Continuous
-        | PhotometricInterpretation::Categorical
-        | PhotometricInterpretation::Boolean => match r.configs.data_type {
-            DataType::F64 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_f64(r.data[i])?;
-                    }
-                }
-            }
-            DataType::F32 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_f32(r.data[i] as f32)?;
-                    }
-                }
-            }
-            DataType::U64 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_u64(r.data[i] as u64)?;
-                    }
-                }
-            }
-            DataType::U32 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_u32(r.data[i] as u32)?;
-                    }
-                }
-            }
-            DataType::U16 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_u16(r.data[i] as u16)?;
-                    }
-                }
-            }
-            DataType::U8 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_u8(r.data[i] as u8)?;
-                    }
-                }
-            }
-            DataType::I64 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_i64(r.data[i] as i64)?;
-                    }
-                }
-            }
-            DataType::I32 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_i32(r.data[i] as i32)?;
-                    }
-                }
-            }
-            DataType::I16 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_i16(r.data[i] as i16)?;
-                    }
-                }
-            }
-            DataType::I8 => {
-                let mut i: usize;
-                for row in 0..r.configs.rows {
-                    for col in 0..r.configs.columns {
-                        i = row * r.configs.columns + col;
-                        bow.write_i8(r.data[i] as i8)?;
-                    }
-                }
-            }
-            _ => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    format!(
-                        "Unknown data type: {:?}. Photomet interp: {:?}",
-                        r.configs.data_type, r.configs.photometric_interp
-                    ),
-                ));
-            }
-        },
-        PhotometricInterpretation::RGB => {
-            match r.configs.data_type {
-                DataType::RGB24 => {
-                    let mut bytes: [u8; 3] = [0u8; 3];
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            let val = r.data[i] as u32;
-                            bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
-                            bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
-                            bytes[0] = (val & 0xFF) as u8; // red
-                            bow.write_bytes(&bytes)?;
-                        }
-                    }
-                }
-                DataType::RGBA32 | DataType::U32 => {
-                    let mut i: usize;
-                    let mut bytes: [u8; 4] = [0u8; 4];
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            let val = r.data[i] as u32;
-                            bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
-                            bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
-                            bytes[0] = (val & 0xFF) as u8; // red
-                            bytes[3] = ((val >> 24u32) & 0xFF) as u8; // a
-                            bow.write_bytes(&bytes)?;
-                        }
-                    }
-                }
-                _ => {
-                    return Err(Error::new(
-                        ErrorKind::InvalidData,
-                        format!(
-                            "Unknown data type: {:?}. Photomet interp: {:?}",
-                            r.configs.data_type, r.configs.photometric_interp
-                        ),
-                    ));
-                }
+    match strips {
+        Some(ref strips) => {
+            for strip in strips {
+                bow.write_bytes(strip)?;
             }
         }
-        PhotometricInterpretation::Paletted => {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "Paletted GeoTIFFs are currently unsupported for writing.",
-            ));
-        }
-        PhotometricInterpretation::Unknown => {
-            return Err(Error::new(
-                ErrorKind::InvalidData,
-                "Error while writing GeoTIFF file.",
-            ));
+        None => {
+            for row in 0..r.configs.rows {
+                write_raster_row(&mut bow, r, row)?;
+            }
         }
     }
 
@@ -1967,10 +1960,10 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     };
 
     let samples_per_pixel = match r.configs.data_type {
-        DataType::I8 | DataType::U8 => 1u16,
-        DataType::I16 | DataType::U16 => 1u16,
-        DataType::I32 | DataType::U32 | DataType::F32 => 1u16,
-        DataType::I64 | DataType::U64 | DataType::F64 => 1u16,
+        DataType::I8 | DataType::U8 => bands as u16,
+        DataType::I16 | DataType::U16 => bands as u16,
+        DataType::I32 | DataType::U32 | DataType::F32 => bands as u16,
+        DataType::I64 | DataType::U64 | DataType::F64 => bands as u16,
         DataType::RGB24 => 3u16,
         DataType::RGBA32 => 4u16,
         DataType::RGB48 => 3u16,
@@ -1988,6 +1981,14 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 samples_per_pixel as u64,
                 bits_per_sample as u64,
             ));
+        } else if samples_per_pixel == 2 {
+            // two short values are stored within the entry itself
+            ifd_entries.push(Entry::new(
+                TAG_BITSPERSAMPLE,
+                DT_SHORT,
+                2u64,
+                pack_two_shorts(bits_per_sample, bits_per_sample, r.configs.endian),
+            ));
         } else {
             ifd_entries.push(Entry::new(
                 TAG_BITSPERSAMPLE,
@@ -2002,11 +2003,17 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     }
 
     // Compression tag (259)
+    let compression = match r.configs.compression {
+        RasterCompression::Deflate => COMPRESS_DEFLATE,
+        RasterCompression::Lzw => COMPRESS_LZW,
+        RasterCompression::Zstd => COMPRESS_ZSTD,
+        _ => COMPRESS_NONE,
+    };
     ifd_entries.push(Entry::new(
         TAG_COMPRESSION,
         DT_SHORT,
         1u64,
-        COMPRESS_NONE as u64,
+        compression as u64,
     ));
 
     // PhotometricInterpretation tag (262)
@@ -2030,27 +2037,43 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     ));
 
     // StripOffsets tag (273)
-    if !is_big_tiff {
+    let strip_byte_counts: Vec<u64> = match strips {
+        Some(ref strips) => strips.iter().map(|strip| strip.len() as u64).collect(),
+        None => vec![row_length_in_bytes as u64; r.configs.rows],
+    };
+    let num_strips = strip_byte_counts.len() as u64;
+    if num_strips == 1 {
+        // a single value fits within the IFD entry itself
         ifd_entries.push(Entry::new(
             TAG_STRIPOFFSETS,
             DT_LONG,
-            r.configs.rows as u64,
+            1u64,
+            header_size as u64,
+        ));
+    } else if !is_big_tiff {
+        ifd_entries.push(Entry::new(
+            TAG_STRIPOFFSETS,
+            DT_LONG,
+            num_strips,
             larger_values_data.len() as u64,
         ));
-        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
-        for i in 0..r.configs.rows as u32 {
-            larger_values_data.write_u32(8u32 + row_length_in_bytes * i)?;
-        }
     } else {
         ifd_entries.push(Entry::new(
             TAG_STRIPOFFSETS,
             DT_TIFF_LONG8,
-            r.configs.rows as u64,
+            num_strips,
             larger_values_data.len() as u64,
         ));
-        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
-        for i in 0..r.configs.rows as u64 {
-            larger_values_data.write_u64(8u64 + row_length_in_bytes * i)?;
+    }
+    if num_strips > 1 {
+        let mut strip_offset = header_size as u64;
+        for count in &strip_byte_counts {
+            if !is_big_tiff {
+                larger_values_data.write_u32(strip_offset as u32)?;
+            } else {
+                larger_values_data.write_u64(strip_offset)?;
+            }
+            strip_offset += count;
         }
     }
 
@@ -2063,54 +2086,40 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     ));
 
     // RowsPerStrip tag (278)
-    ifd_entries.push(Entry::new(TAG_ROWSPERSTRIP, DT_SHORT, 1u64, 1u64));
+    ifd_entries.push(Entry::new(
+        TAG_ROWSPERSTRIP,
+        DT_SHORT,
+        1u64,
+        rows_per_strip as u64,
+    ));
 
     // StripByteCounts tag (279)
-    if !is_big_tiff {
+    if num_strips == 1 {
+        ifd_entries.push(Entry::new(
+            TAG_STRIPBYTECOUNTS,
+            DT_LONG,
+            1u64,
+            strip_byte_counts[0],
+        ));
+    } else if !is_big_tiff {
         ifd_entries.push(Entry::new(
             TAG_STRIPBYTECOUNTS,
             DT_LONG,
-            r.configs.rows as u64,
+            num_strips,
             larger_values_data.len() as u64,
         ));
-        let total_bytes_per_pixel = match r.configs.data_type {
-            DataType::I8 | DataType::U8 => 1u32,
-            DataType::I16 | DataType::U16 => 2u32,
-            DataType::I32 | DataType::U32 | DataType::F32 => 4u32,
-            DataType::I64 | DataType::U64 | DataType::F64 => 8u32,
-            DataType::RGB24 => 3u32,
-            DataType::RGBA32 => 4u32,
-            DataType::RGB48 => 6u32,
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
-            }
-        };
-        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
-        for _ in 0..r.configs.rows as u32 {
-            larger_values_data.write_u32(row_length_in_bytes)?;
+        for count in &strip_byte_counts {
+            larger_values_data.write_u32(*count as u32)?;
         }
     } else {
         ifd_entries.push(Entry::new(
             TAG_STRIPBYTECOUNTS,
             DT_TIFF_LONG8,
-            r.configs.rows as u64,
+            num_strips,
             larger_values_data.len() as u64,
         ));
-        let total_bytes_per_pixel = match r.configs.data_type {
-            DataType::I8 | DataType::U8 => 1u64,
-            DataType::I16 | DataType::U16 => 2u64,
-            DataType::I32 | DataType::U32 | DataType::F32 => 4u64,
-            DataType::I64 | DataType::U64 | DataType::F64 => 8u64,
-            DataType::RGB24 => 3u64,
-            DataType::RGBA32 => 4u64,
-            DataType::RGB48 => 6u64,
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
-            }
-        };
-        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
-        for _ in 0..r.configs.rows as u32 {
-            larger_values_data.write_u64(row_length_in_bytes)?;
+        for count in &strip_byte_counts {
+            larger_values_data.write_u64(*count)?;
         }
     }
 
@@ -2150,7 +2159,38 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     ));
     larger_values_data.write_bytes(&soft_bytes)?;
 
-    if samples_per_pixel == 4 {
+    // Predictor tag (317)
+    match predictor {
+        RasterPredictor::Horizontal => {
+            ifd_entries.push(Entry::new(TAG_PREDICTOR, DT_SHORT, 1u64, 2u64));
+        }
+        RasterPredictor::FloatingPoint => {
+            ifd_entries.push(Entry::new(TAG_PREDICTOR, DT_SHORT, 1u64, 3u64));
+        }
+        _ => {}
+    }
+
+    if bands > 1 {
+        // ExtraSamples tag (338); the bands beyond the first are of unspecified type (0)
+        let num_extra = (bands - 1) as u64;
+        if num_extra <= 2 {
+            ifd_entries.push(Entry::new(TAG_EXTRASAMPLES, DT_SHORT, num_extra, 0u64));
+        } else {
+            ifd_entries.push(Entry::new(
+                TAG_EXTRASAMPLES,
+                DT_SHORT,
+                num_extra,
+                larger_values_data.len() as u64,
+            ));
+            for _ in 0..num_extra {
+                larger_values_data.write_u16(0u16)?;
+            }
+            if num_extra % 2 == 1 {
+                // keep the next value on a word boundary
+                larger_values_data.write_u16(0u16)?;
+            }
+        }
+    } else if samples_per_pixel == 4 {
         // ExtraSamples tag (338)
         ifd_entries.push(Entry::new(TAG_EXTRASAMPLES, DT_SHORT, 1u64, 2u64));
     }
@@ -2172,6 +2212,13 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             samples_per_pixel as u64,
             samples_format as u64,
         ));
+    } else if samples_per_pixel == 2 {
+        ifd_entries.push(Entry::new(
+            TAG_SAMPLEFORMAT,
+            DT_SHORT,
+            2u64,
+            pack_two_shorts(samples_format, samples_format, r.configs.endian),
+        ));
     } else {
         ifd_entries.push(Entry::new(
             TAG_SAMPLEFORMAT,
@@ -2184,6 +2231,22 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
+    if r.configs.rotation != 0f64 {
+        // rotated grids are georeferenced using the ModelTransformationTag alone
+        let (sin, cos) = r.configs.rotation.to_radians().sin_cos();
+        let mut t = [0f64; 16];
+        t[0] = r.configs.resolution_x * cos;
+        t[1] = r.configs.resolution_y * sin;
+        t[3] = r.configs.west;
+        t[4] = r.configs.resolution_x * sin;
+        t[5] = -r.configs.resolution_y * cos;
+        t[7] = r.configs.north;
+        t[15] = 1f64;
+        r.configs.model_tiepoint = vec![];
+        r.configs.model_pixel_scale = [0f64; 3];
+        r.configs.model_transformation = t;
+    }
+
     // ModelPixelScaleTag tag (33550)
     if r.configs.model_pixel_scale[0] == 0f64
         && r.configs.model_tiepoint.is_empty()
@@ -2293,6 +2356,23 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         larger_values_data.write_bytes(&nodata_bytes)?;
     }
 
+    if r.configs.grid_type == GridType::Hexagonal {
+        // TAG_GDAL_METADATA tag (42112)
+        let md = "<GDALMetadata><Item name=\"GRID_TYPE\">HEXAGONAL</Item></GDALMetadata>";
+        let mut md_bytes = md.to_string().into_bytes();
+        if md_bytes.len() % 2 == 0 {
+            md_bytes.push(32);
+        }
+        md_bytes.push(0);
+        ifd_entries.push(Entry::new(
+            TAG_GDAL_METADATA,
+            DT_ASCII,
+            md_bytes.len() as u64,
+            larger_values_data.len() as u64,
+        ));
+        larger_values_data.write_bytes(&md_bytes)?;
+    }
+
     let kw_map = get_keyword_map();
     let geographic_type_map = match kw_map.get(&2048u16) {
         Some(map) => map,
@@ -2683,7 +2763,7 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     //////////////////////////////////
     bow.write_bytes(larger_values_data.get_inner())?;
 
-    Ok(())
+    Ok(bow.into_inner())
 }
 
 // An implimentation of a PackBits reader
@@ -2708,3 +2788,468 @@ pub fn packbits_decoder(input_data: Vec<u8>) -> Vec<u8> {
     }
     output_data
 }
+
+/// Writes the values in one row of a raster to a GeoTIFF data stream.
+fn write_raster_row<W: Write>(
+    bow: &mut ByteOrderWriter<W>,
+    r: &Raster,
+    row: usize,
+) -> Result<(), Error> {
+    let columns = r.configs.columns;
+    let values = &r.data[row * columns..(row + 1) * columns];
+    match r.configs.photometric_interp {
+        PhotometricInterpretation::RGB => match r.configs.data_type {
+            DataType::RGB24 => {
+                for value in values {
+                    let val = *value as u32;
+                    let bytes = [
+                        (val & 0xFF) as u8,            // red
+                        ((val >> 8u32) & 0xFF) as u8,  // green
+                        ((val >> 16u32) & 0xFF) as u8, // blue
+                    ];
+                    bow.write_bytes(&bytes)?;
+                }
+            }
+            DataType::RGBA32 | DataType::U32 => {
+                for value in values {
+                    let val = *value as u32;
+                    let bytes = [
+                        (val & 0xFF) as u8,            // red
+                        ((val >> 8u32) & 0xFF) as u8,  // green
+                        ((val >> 16u32) & 0xFF) as u8, // blue
+                        ((val >> 24u32) & 0xFF) as u8, // a
+                    ];
+                    bow.write_bytes(&bytes)?;
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Unknown data type: {:?}. Photomet interp: {:?}",
+                        r.configs.data_type, r.configs.photometric_interp
+                    ),
+                ));
+            }
+        },
+        _ if r.configs.bands > 1 => {
+            // interleave the bands of each pixel
+            let plane_size = r.configs.rows * columns;
+            for col in 0..columns {
+                for band in 0..r.configs.bands {
+                    let value = r.data[band * plane_size + row * columns + col];
+                    match r.configs.data_type {
+                        DataType::F64 => bow.write_f64(value)?,
+                        DataType::F32 => bow.write_f32(value as f32)?,
+                        DataType::U64 => bow.write_u64(value as u64)?,
+                        DataType::U32 => bow.write_u32(value as u32)?,
+                        DataType::U16 => bow.write_u16(value as u16)?,
+                        DataType::U8 => bow.write_u8(value as u8)?,
+                        DataType::I64 => bow.write_i64(value as i64)?,
+                        DataType::I32 => bow.write_i32(value as i32)?,
+                        DataType::I16 => bow.write_i16(value as i16)?,
+                        DataType::I8 => bow.write_i8(value as i8)?,
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                format!(
+                                    "Unknown data type: {:?}. Photomet interp: {:?}",
+                                    r.configs.data_type, r.configs.photometric_interp
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        _ => match r.configs.data_type {
+            DataType::F64 => {
+                for value in values {
+                    bow.write_f64(*value)?;
+                }
+            }
+            DataType::F32 => {
+                for value in values {
+                    bow.write_f32(*value as f32)?;
+                }
+            }
+            DataType::U64 => {
+                for value in values {
+                    bow.write_u64(*value as u64)?;
+                }
+            }
+            DataType::U32 => {
+                for value in values {
+                    bow.write_u32(*value as u32)?;
+                }
+            }
+            DataType::U16 => {
+                for value in values {
+                    bow.write_u16(*value as u16)?;
+                }
+            }
+            DataType::U8 => {
+                for value in values {
+                    bow.write_u8(*value as u8)?;
+                }
+            }
+            DataType::I64 => {
+                for value in values {
+                    bow.write_i64(*value as i64)?;
+                }
+            }
+            DataType::I32 => {
+                for value in values {
+                    bow.write_i32(*value as i32)?;
+                }
+            }
+            DataType::I16 => {
+                for value in values {
+                    bow.write_i16(*value as i16)?;
+                }
+            }
+            DataType::I8 => {
+                for value in values {
+                    bow.write_i8(*value as i8)?;
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Unknown data type: {:?}. Photomet interp: {:?}",
+                        r.configs.data_type, r.configs.photometric_interp
+                    ),
+                ));
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Encodes the raster's data as a series of compressed strips, each containing
+/// `rows_per_strip` rows, applying the predictor to each row before compression.
+fn encode_strips(
+    r: &Raster,
+    rows_per_strip: usize,
+    predictor: RasterPredictor,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let (bytes_per_sample, samples_per_pixel) =
+        if r.configs.photometric_interp == PhotometricInterpretation::RGB {
+            (1usize, r.configs.data_type.get_data_size())
+        } else {
+            (r.configs.data_type.get_data_size(), r.configs.bands.max(1))
+        };
+    let little_endian = r.configs.endian == Endianness::LittleEndian;
+    let row_length = r.configs.columns * samples_per_pixel;
+    let mut strips = vec![];
+    let mut row = 0usize;
+    while row < r.configs.rows {
+        let last_row = (row + rows_per_strip).min(r.configs.rows);
+        let mut bow = ByteOrderWriter::<Vec<u8>>::new(vec![], r.configs.endian);
+        for rw in row..last_row {
+            write_raster_row(&mut bow, r, rw)?;
+        }
+        let mut bytes = bow.get_inner().clone();
+        match predictor {
+            RasterPredictor::Horizontal => apply_predictor(
+                &mut bytes,
+                2u16,
+                row_length,
+                samples_per_pixel,
+                bytes_per_sample,
+                little_endian,
+            ),
+            RasterPredictor::FloatingPoint => apply_predictor(
+                &mut bytes,
+                3u16,
+                row_length,
+                samples_per_pixel,
+                bytes_per_sample,
+                little_endian,
+            ),
+            _ => {}
+        }
+        let compressed = match r.configs.compression {
+            RasterCompression::Deflate => {
+                let mut encoder = ZlibEncoder::new(
+                    vec![],
+                    Compression::new(r.configs.compression_level.max(1).min(9) as u32),
+                );
+                encoder.write_all(&bytes)?;
+                encoder.finish()?
+            }
+            RasterCompression::Lzw => lzw_encode(&bytes),
+            RasterCompression::Zstd => {
+                zstd_encode(&bytes, r.configs.compression_level.max(1).min(9) as i32)?
+            }
+            _ => bytes,
+        };
+        strips.push(compressed);
+        row = last_row;
+    }
+    Ok(strips)
+}
+
+fn read_sample(bytes: &[u8], little_endian: bool) -> u64 {
+    let mut value = 0u64;
+    if little_endian {
+        for b in bytes.iter().rev() {
+            value = (value << 8) | *b as u64;
+        }
+    } else {
+        for b in bytes {
+            value = (value << 8) | *b as u64;
+        }
+    }
+    value
+}
+
+fn write_sample(bytes: &mut [u8], mut value: u64, little_endian: bool) {
+    let n = bytes.len();
+    for i in 0..n {
+        if little_endian {
+            bytes[i] = (value & 0xFF) as u8;
+        } else {
+            bytes[n - 1 - i] = (value & 0xFF) as u8;
+        }
+        value >>= 8;
+    }
+}
+
+/// Applies the TIFF horizontal (2) or floating-point (3) predictor to a block of data
+/// containing whole rows of `row_length` samples.
+fn apply_predictor(
+    data: &mut [u8],
+    predictor: u16,
+    row_length: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    little_endian: bool,
+) {
+    let row_bytes = row_length * bytes_per_sample;
+    if row_bytes == 0 {
+        return;
+    }
+    let mut tmp = vec![0u8; row_bytes];
+    for row_data in data.chunks_mut(row_bytes) {
+        let n = row_data.len() / bytes_per_sample;
+        if predictor == 2 {
+            for i in (samples_per_pixel..n).rev() {
+                let a = read_sample(
+                    &row_data[i * bytes_per_sample..(i + 1) * bytes_per_sample],
+                    little_endian,
+                );
+                let b = read_sample(
+                    &row_data[(i - samples_per_pixel) * bytes_per_sample
+                        ..(i - samples_per_pixel + 1) * bytes_per_sample],
+                    little_endian,
+                );
+                write_sample(
+                    &mut row_data[i * bytes_per_sample..(i + 1) * bytes_per_sample],
+                    a.wrapping_sub(b),
+                    little_endian,
+                );
+            }
+        } else if predictor == 3 {
+            // rearrange the bytes so that those of equal significance are grouped,
+            // most significant first, then difference the bytes.
+            for i in 0..n {
+                for b in 0..bytes_per_sample {
+                    let significance = if little_endian {
+                        bytes_per_sample - 1 - b
+                    } else {
+                        b
+                    };
+                    tmp[significance * n + i] = row_data[i * bytes_per_sample + b];
+                }
+            }
+            row_data.copy_from_slice(&tmp[0..row_data.len()]);
+            for i in (samples_per_pixel..row_data.len()).rev() {
+                row_data[i] = row_data[i].wrapping_sub(row_data[i - samples_per_pixel]);
+            }
+        }
+    }
+}
+
+/// Reverses the TIFF horizontal (2) or floating-point (3) predictor on a block of
+/// decompressed data containing whole rows of `row_length` samples.
+fn undo_predictor(
+    data: &mut [u8],
+    predictor: u16,
+    row_length: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    little_endian: bool,
+) {
+    let row_bytes = row_length * bytes_per_sample;
+    if row_bytes == 0 {
+        return;
+    }
+    let mut tmp = vec![0u8; row_bytes];
+    for row_data in data.chunks_mut(row_bytes) {
+        let n = row_data.len() / bytes_per_sample;
+        if predictor == 2 {
+            for i in samples_per_pixel..n {
+                let a = read_sample(
+                    &row_data[i * bytes_per_sample..(i + 1) * bytes_per_sample],
+                    little_endian,
+                );
+                let b = read_sample(
+                    &row_data[(i - samples_per_pixel) * bytes_per_sample
+                        ..(i - samples_per_pixel + 1) * bytes_per_sample],
+                    little_endian,
+                );
+                write_sample(
+                    &mut row_data[i * bytes_per_sample..(i + 1) * bytes_per_sample],
+                    a.wrapping_add(b),
+                    little_endian,
+                );
+            }
+        } else if predictor == 3 {
+            for i in samples_per_pixel..row_data.len() {
+                row_data[i] = row_data[i].wrapping_add(row_data[i - samples_per_pixel]);
+            }
+            tmp[0..row_data.len()].copy_from_slice(row_data);
+            for i in 0..n {
+                for b in 0..bytes_per_sample {
+                    let significance = if little_endian {
+                        bytes_per_sample - 1 - b
+                    } else {
+                        b
+                    };
+                    row_data[i * bytes_per_sample + b] = tmp[significance * n + i];
+                }
+            }
+        }
+    }
+}
+
+/// Compresses data using Zstandard at the given level.
+#[cfg(feature = "zstd")]
+fn zstd_encode(data: &[u8], level: i32) -> Result<Vec<u8>, Error> {
+    zstd::stream::encode_all(data, level)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_encode(_data: &[u8], _level: i32) -> Result<Vec<u8>, Error> {
+    Err(zstd_unsupported())
+}
+
+/// Decompresses a Zstandard-compressed strip or tile.
+#[cfg(feature = "zstd")]
+fn zstd_decode(data: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd::stream::decode_all(data)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_decode(_data: &[u8]) -> Result<Vec<u8>, Error> {
+    Err(zstd_unsupported())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_unsupported() -> Error {
+    Error::new(
+        ErrorKind::Other,
+        "This build of WhiteboxTools does not include ZSTD support; rebuild with --features zstd.",
+    )
+}
+
+/// Compresses data using the TIFF variant of the LZW algorithm, i.e. with
+/// MSB-first bit packing and 'early change' of the code width.
+fn lzw_encode(data: &[u8]) -> Vec<u8> {
+    const CLEAR_CODE: u32 = 256;
+    const EOI_CODE: u32 = 257;
+    const FIRST_CODE: u32 = 258;
+    const MAX_CODE: u32 = 4094;
+
+    let mut out: Vec<u8> = Vec::with_capacity(data.len() / 2 + 16);
+    let mut bit_buffer = 0u64;
+    let mut num_bits = 0u32;
+    let mut put_code = |code: u32, width: u32, out: &mut Vec<u8>| {
+        bit_buffer = (bit_buffer << width) | code as u64;
+        num_bits += width;
+        while num_bits >= 8 {
+            out.push((bit_buffer >> (num_bits - 8)) as u8);
+            num_bits -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u32, u8), u32> = HashMap::new();
+    let mut next_code = FIRST_CODE;
+    let mut width = 9u32;
+    put_code(CLEAR_CODE, width, &mut out);
+    if data.is_empty() {
+        put_code(EOI_CODE, width, &mut out);
+    } else {
+        let mut prefix = data[0] as u32;
+        for &c in &data[1..] {
+            if let Some(&code) = table.get(&(prefix, c)) {
+                prefix = code;
+                continue;
+            }
+            put_code(prefix, width, &mut out);
+            table.insert((prefix, c), next_code);
+            next_code += 1;
+            if next_code == MAX_CODE {
+                put_code(CLEAR_CODE, width, &mut out);
+                table.clear();
+                next_code = FIRST_CODE;
+                width = 9;
+            } else if next_code > (1 << width) - 1 {
+                width += 1;
+            }
+            prefix = c as u32;
+        }
+        put_code(prefix, width, &mut out);
+        next_code += 1;
+        if next_code == MAX_CODE {
+            put_code(CLEAR_CODE, width, &mut out);
+            width = 9;
+        } else if next_code > (1 << width) - 1 {
+            width += 1;
+        }
+        put_code(EOI_CODE, width, &mut out);
+    }
+    if num_bits > 0 {
+        out.push((bit_buffer << (8 - num_bits)) as u8);
+    }
+    out
+}
+
+/// Reads a single sample value of the specified bit depth and TIFF sample format.
+fn read_sample_value(
+    bor: &mut ByteOrderReader<Cursor<Vec<u8>>>,
+    bits_per_sample: u16,
+    sample_format: u16,
+) -> Result<f64, Error> {
+    let value = match (sample_format, bits_per_sample) {
+        (3, 32) => bor.read_f32()? as f64,
+        (3, 64) => bor.read_f64()?,
+        (2, 8) => bor.read_i8()? as f64,
+        (2, 16) => bor.read_i16()? as f64,
+        (2, 32) => bor.read_i32()? as f64,
+        (2, 64) => bor.read_i64()? as f64,
+        (_, 8) => bor.read_u8()? as f64,
+        (_, 16) => bor.read_u16()? as f64,
+        (_, 32) => bor.read_u32()? as f64,
+        (_, 64) => bor.read_u64()? as f64,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The raster was not read correctly",
+            ))
+        }
+    };
+    Ok(value)
+}
+
+/// Packs two short values into the value field of an IFD entry, in the order in
+/// which they would be written by a `ByteOrderWriter` with the given byte order.
+fn pack_two_shorts(first: u16, second: u16, endian: Endianness) -> u64 {
+    if endian == Endianness::LittleEndian {
+        (first as u64) | ((second as u64) << 16)
+    } else {
+        ((first as u64) << 16) | (second as u64)
+    }
+}
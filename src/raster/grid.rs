@@ -0,0 +1,162 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+Grid geometries other than the default north-up grid of square cells. A raster's
+grid may be rotated about its upper-left corner (`RasterConfigs.rotation`), and its
+cells may be hexagonal (`RasterConfigs.grid_type`). Hexagonal grids use pointy-topped
+hexagons in an 'odd-r' offset layout: cells are stored in rows, as in a square grid,
+but odd-numbered rows are shifted half a cell to the east. `resolution_x` is the
+spacing of cell centres along a row and `resolution_y` is the spacing of rows; for
+regular hexagons, `resolution_y` is `resolution_x * sqrt(3) / 2`. The west, east,
+north, and south values describe the extent of the grid before rotation, with the
+east edge excluding the half-cell shift of the odd rows of hexagonal grids.
+*/
+
+use super::RasterConfigs;
+use std::f64;
+use std::fmt;
+
+/// The shape of the cells of a raster grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridType {
+    Square,
+    Hexagonal,
+}
+
+impl Default for GridType {
+    fn default() -> GridType {
+        GridType::Square
+    }
+}
+
+impl GridType {
+    pub fn from_str(s: &str) -> Option<GridType> {
+        let s = s.trim().to_lowercase();
+        if s.starts_with("sq") || s.starts_with("rect") {
+            Some(GridType::Square)
+        } else if s.starts_with("hex") {
+            Some(GridType::Hexagonal)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for GridType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridType::Square => write!(f, "SQUARE"),
+            GridType::Hexagonal => write!(f, "HEXAGONAL"),
+        }
+    }
+}
+
+/// Returns the row spacing of a hexagonal grid of regular hexagons with the
+/// specified spacing of cell centres along a row.
+pub fn hex_row_spacing(cell_spacing: f64) -> f64 {
+    cell_spacing * 3f64.sqrt() / 2f64
+}
+
+/// Returns the (row, column) offsets of the six neighbours of a cell in the
+/// specified row of a hexagonal grid, clockwise from the north-east neighbour.
+pub fn hex_neighbour_offsets(row: isize) -> [(isize, isize); 6] {
+    if row & 1 == 0 {
+        [(-1, 0), (0, 1), (1, 0), (1, -1), (0, -1), (-1, -1)]
+    } else {
+        [(-1, 1), (0, 1), (1, 1), (1, 0), (0, -1), (-1, 0)]
+    }
+}
+
+/// Converts the offset (row, column) coordinates of a hexagonal grid cell into cube
+/// coordinates.
+fn hex_offset_to_cube(row: isize, column: isize) -> (isize, isize, isize) {
+    let x = column - (row - (row & 1)) / 2;
+    let z = row;
+    (x, -x - z, z)
+}
+
+/// Returns the number of cells separating two cells of a hexagonal grid.
+pub fn hex_distance(row1: isize, column1: isize, row2: isize, column2: isize) -> isize {
+    let (x1, y1, z1) = hex_offset_to_cube(row1, column1);
+    let (x2, y2, z2) = hex_offset_to_cube(row2, column2);
+    (x1 - x2).abs().max((y1 - y2).abs()).max((z1 - z2).abs())
+}
+
+/// Returns the (row, column) offsets of the cells of a hexagonal grid within `radius`
+/// cells of a cell in the specified row, including the cell itself.
+pub fn hex_neighbourhood(row: isize, radius: isize) -> Vec<(isize, isize)> {
+    let mut offsets = vec![];
+    for dr in -radius..=radius {
+        // a row within the radius spans at most 2 * radius + 1 cells
+        for dc in -radius - 1..=radius + 1 {
+            if hex_distance(row, 0, row + dr, dc) <= radius {
+                offsets.push((dr, dc));
+            }
+        }
+    }
+    offsets
+}
+
+/// Returns the (row, column) of the hexagonal grid cell containing a point, where
+/// `u` and `v` are the point's distances east and south of the grid origin, in units
+/// of the cell spacing and row spacing respectively. The result may lie outside of
+/// the grid.
+pub fn hex_cell_from_point(u: f64, v: f64) -> (isize, isize) {
+    // axial coordinates of the point, relative to the centre of cell (0, 0)
+    let r = v - 0.5;
+    let q = u - 0.5 - r / 2f64;
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    let row = rr as isize;
+    let column = rq as isize + (row - (row & 1)) / 2;
+    (row, column)
+}
+
+/// Sets the extent, rows, and columns of `configs`, the grid type, rotation, and
+/// resolution of which must already be set, so that its grid covers a bounding box.
+/// Hexagonal grids are padded by a cell on each side, so that every point within the
+/// bounding box lies within a cell of the grid.
+pub fn fit_grid_to_extent(
+    configs: &mut RasterConfigs,
+    west: f64,
+    south: f64,
+    east: f64,
+    north: f64,
+) {
+    let (sin, cos) = configs.rotation.to_radians().sin_cos();
+    // the corners' distances along the row (a) and column (b) directions of the grid
+    let (mut min_a, mut max_a) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut min_b, mut max_b) = (f64::INFINITY, f64::NEG_INFINITY);
+    for (x, y) in &[(west, north), (east, north), (east, south), (west, south)] {
+        let a = x * cos + y * sin;
+        let b = x * sin - y * cos;
+        min_a = min_a.min(a);
+        max_a = max_a.max(a);
+        min_b = min_b.min(b);
+        max_b = max_b.max(b);
+    }
+    if configs.grid_type == GridType::Hexagonal {
+        min_a -= configs.resolution_x;
+        max_a += configs.resolution_x;
+        min_b -= configs.resolution_y;
+        max_b += configs.resolution_y;
+    }
+    configs.columns = (((max_a - min_a) / configs.resolution_x).ceil() as usize).max(1);
+    configs.rows = (((max_b - min_b) / configs.resolution_y).ceil() as usize).max(1);
+    configs.west = min_a * cos + min_b * sin;
+    configs.north = min_a * sin - min_b * cos;
+    configs.east = configs.west + configs.columns as f64 * configs.resolution_x;
+    configs.south = configs.north - configs.rows as f64 * configs.resolution_y;
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 02/06/2017
-Last Modified: 09/02/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -10,22 +10,32 @@ pub mod arcascii_raster;
 pub mod arcbinary_raster;
 pub mod geotiff;
 pub mod grass_raster;
+pub mod grid;
 pub mod idrisi_raster;
 pub mod saga_raster;
+pub mod satellite_scene;
 pub mod surfer7_raster;
 pub mod surfer_ascii_raster;
 pub mod whitebox_raster;
+pub mod whitebox_raster_v2;
 
 use self::arcascii_raster::*;
 use self::arcbinary_raster::*;
 use self::geotiff::*;
 use self::grass_raster::*;
+use self::grid::hex_cell_from_point;
+pub use self::grid::GridType;
 use self::idrisi_raster::*;
 use self::saga_raster::*;
+use self::satellite_scene::*;
 use self::surfer7_raster::*;
 use self::surfer_ascii_raster::*;
 use self::whitebox_raster::*;
+use self::whitebox_raster_v2::*;
+use crate::dataset::{get_dataset, is_in_memory, store_dataset, Dataset};
+use crate::spatial_ref_system::CoordinateReferenceSystem;
 use crate::structures::{Array2D, BoundingBox};
+use crate::utils::thread;
 use crate::utils::*;
 use std::cmp::Ordering::Equal;
 use std::default::Default;
@@ -37,13 +47,14 @@ use std::io::Error;
 use std::io::ErrorKind;
 use std::ops::{AddAssign, Index, IndexMut, SubAssign};
 use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// Raster is a common data structure that abstracts over several raster data formats,
 /// including GeoTIFFs, ArcGIS ASCII and binary rasters, Whitebox rasters, Idrisi
-/// rasters, Saga rasters, and GRASS ASCII rasters.
+/// rasters, Saga rasters, and GRASS ASCII rasters. Landsat Collection 2 scenes and
+/// Sentinel-2 SAFE products can also be read, as multi-band rasters.
 ///
 /// Examples:
 ///
@@ -120,11 +131,25 @@ impl Raster {
     /// read from an existing file (`file_name`; `file_mode` is 'r') or
     /// prepared for new file creation (`file_mode` is 'w') The raster format
     /// will be determined by the file extension of the `file_name` string.
+    /// File names beginning with `memory://` refer to in-memory datasets.
     ///
     /// To create a new `Raster` file, most applications should prefer the
     /// `initialize_using_config` or `initialize_using_file` functions instead.
     pub fn new<'a>(file_name: &'a str, file_mode: &'a str) -> Result<Raster, Error> {
         let fm: String = file_mode.to_lowercase();
+        if is_in_memory(file_name) && fm.contains("r") {
+            return match get_dataset(file_name) {
+                Some(Dataset::Raster(mut r)) => {
+                    r.file_name = file_name.to_string();
+                    r.file_mode = fm;
+                    Ok(r)
+                }
+                _ => Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("No in-memory raster named {} was found.", file_name),
+                )),
+            };
+        }
         let mut r = Raster {
             file_name: file_name.to_string(),
             file_mode: fm.clone(),
@@ -158,6 +183,11 @@ impl Raster {
                     let _ = read_saga(&r.file_name, &mut r.configs, &mut r.data)?;
                     return Ok(r);
                 }
+                RasterType::SatelliteScene => {
+                    let _ = read_satellite_scene(&r.file_name, &mut r.configs, &mut r.data)?;
+                    r.update_min_max();
+                    return Ok(r);
+                }
                 RasterType::Surfer7Binary => {
                     let _ = read_surfer7(&r.file_name, &mut r.configs, &mut r.data)?;
                     return Ok(r);
@@ -168,6 +198,12 @@ impl Raster {
                 }
                 RasterType::Whitebox => {
                     let _ = read_whitebox(&r.file_name, &mut r.configs, &mut r.data)?;
+                    // only the first stack of a Whitebox raster is read
+                    r.configs.bands = (r.data.len() / r.num_cells().max(1)).max(1);
+                    return Ok(r);
+                }
+                RasterType::WhiteboxV2 => {
+                    let _ = read_whitebox_v2(&r.file_name, &mut r.configs, &mut r.data)?;
                     return Ok(r);
                 }
                 RasterType::Unknown => {
@@ -220,6 +256,8 @@ impl Raster {
         output.configs.model_tiepoint = configs.model_tiepoint.clone();
         output.configs.model_pixel_scale = configs.model_pixel_scale.clone();
         output.configs.model_transformation = configs.model_transformation.clone();
+        output.configs.grid_type = configs.grid_type;
+        output.configs.rotation = configs.rotation;
         output.configs.geo_key_directory = configs.geo_key_directory.clone();
         output.configs.geo_double_params = configs.geo_double_params.clone();
         output.configs.geo_ascii_params = configs.geo_ascii_params.clone();
@@ -275,6 +313,8 @@ impl Raster {
         output.configs.model_tiepoint = input.configs.model_tiepoint.clone();
         output.configs.model_pixel_scale = input.configs.model_pixel_scale.clone();
         output.configs.model_transformation = input.configs.model_transformation.clone();
+        output.configs.grid_type = input.configs.grid_type;
+        output.configs.rotation = input.configs.rotation;
         output.configs.geo_key_directory = input.configs.geo_key_directory.clone();
         output.configs.geo_double_params = input.configs.geo_double_params.clone();
         output.configs.geo_ascii_params = input.configs.geo_ascii_params.clone();
@@ -333,6 +373,8 @@ impl Raster {
         output.configs.model_tiepoint = configs.model_tiepoint.clone();
         output.configs.model_pixel_scale = configs.model_pixel_scale.clone();
         output.configs.model_transformation = configs.model_transformation.clone();
+        output.configs.grid_type = configs.grid_type;
+        output.configs.rotation = configs.rotation;
         output.configs.geo_key_directory = configs.geo_key_directory.clone();
         output.configs.geo_double_params = configs.geo_double_params.clone();
         output.configs.geo_ascii_params = configs.geo_ascii_params.clone();
@@ -583,7 +625,7 @@ impl Raster {
     }
 
     pub fn reinitialize_values(&mut self, value: f64) {
-        self.data = vec![value; self.configs.rows * self.configs.columns];
+        self.data = vec![value; self.configs.rows * self.configs.columns * self.configs.bands.max(1)];
     }
 
     pub fn get_value_as_rgba(&self, row: isize, column: isize) -> (u8, u8, u8, u8) {
@@ -655,6 +697,24 @@ impl Raster {
         ((self.configs.north - y) / self.configs.resolution_y).floor() as isize
     }
 
+    /// Returns true if the grid has square cells and is not rotated, i.e. if the
+    /// `get_x_from_column`, `get_y_from_row`, `get_column_from_x`, and `get_row_from_y`
+    /// functions apply to it.
+    pub fn is_north_up_square_grid(&self) -> bool {
+        self.configs.is_north_up_square_grid()
+    }
+
+    /// Returns the (x, y) coordinates of the centre of a cell, for any grid geometry.
+    pub fn get_cell_centre(&self, row: isize, column: isize) -> (f64, f64) {
+        self.configs.get_cell_centre(row, column)
+    }
+
+    /// Returns the (row, column) of the cell containing a point, for any grid
+    /// geometry. The result may lie outside of the grid.
+    pub fn get_cell_from_point(&self, x: f64, y: f64) -> (isize, isize) {
+        self.configs.get_cell_from_point(x, y)
+    }
+
     pub fn clip_display_min_max(&mut self, percent: f64) {
         let t = (percent / 100.0 * (self.configs.rows * self.configs.columns) as f64) as usize;
         let mut d = self.data.clone();
@@ -1049,6 +1109,27 @@ impl Raster {
                 "Cannot write raster that is not created in write mmode ('w').",
             ));
         }
+        if is_in_memory(&self.file_name) {
+            self.update_min_max();
+            store_dataset(&self.file_name, Dataset::Raster(self.clone()));
+            return Ok(());
+        }
+        if self.configs.bands > 1 && !self.raster_type.supports_multiple_bands() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "Multi-band rasters can currently only be written in the GeoTIFF and Whitebox v2 (.wbr) formats.",
+            ));
+        }
+        if !self.is_north_up_square_grid()
+            && self.raster_type != RasterType::GeoTiff
+            && self.raster_type != RasterType::Whitebox
+            && self.raster_type != RasterType::WhiteboxV2
+        {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "Hexagonal and rotated grids can currently only be written in the GeoTIFF and Whitebox formats.",
+            ));
+        }
         match self.raster_type {
             RasterType::ArcAscii => {
                 let _ = match write_arcascii(self) {
@@ -1086,6 +1167,12 @@ impl Raster {
                     Err(e) => println!("error while writing: {:?}", e),
                 };
             }
+            RasterType::SatelliteScene => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "Satellite scenes cannot be written; use the GeoTIFF or Whitebox v2 (.wbr) formats instead.",
+                ));
+            }
             RasterType::Surfer7Binary => {
                 let _ = match write_surfer7(self) {
                     Ok(_) => (),
@@ -1104,6 +1191,12 @@ impl Raster {
                     Err(e) => println!("error while writing: {:?}", e),
                 };
             }
+            RasterType::WhiteboxV2 => {
+                let _ = match write_whitebox_v2(self) {
+                    Ok(_) => (),
+                    Err(e) => println!("error while writing: {:?}", e),
+                };
+            }
             RasterType::Unknown => {
                 return Err(Error::new(ErrorKind::Other, "Unrecognized raster type"));
             }
@@ -1122,6 +1215,17 @@ impl Raster {
         String::new()
     }
 
+    /// Returns the value of the first metadata entry of the form 'key: value', e.g. the
+    /// 'Sun elevation' of a satellite scene.
+    pub fn get_metadata_value(&self, key: &str) -> Option<String> {
+        let prefix = format!("{}:", key);
+        self.configs
+            .metadata
+            .iter()
+            .find(|entry| entry.starts_with(&prefix))
+            .map(|entry| entry[prefix.len()..].trim().to_string())
+    }
+
     pub fn get_bounding_box(&self) -> BoundingBox {
         BoundingBox::new(
             self.configs.west,
@@ -1155,6 +1259,413 @@ impl Raster {
         }
         false
     }
+
+    /// Returns the coordinate reference system of the raster, if one is defined
+    /// and supported by the CRS engine.
+    pub fn get_crs(&self) -> Option<CoordinateReferenceSystem> {
+        if self.configs.epsg_code != 0 {
+            if let Ok(crs) = CoordinateReferenceSystem::from_epsg(self.configs.epsg_code) {
+                return Some(crs);
+            }
+        }
+        for wkt in &[
+            &self.configs.coordinate_ref_system_wkt,
+            &self.configs.projection,
+        ] {
+            if let Ok(crs) = CoordinateReferenceSystem::from_wkt(wkt) {
+                return Some(crs);
+            }
+        }
+        None
+    }
+
+    /// Returns true if the two rasters are in the same coordinate reference system. Rasters
+    /// with an undefined CRS are assumed to match any other raster.
+    pub fn has_same_crs(&self, other: &Raster) -> bool {
+        if self.configs.epsg_code != 0 && self.configs.epsg_code == other.configs.epsg_code {
+            return true;
+        }
+        match (self.get_crs(), other.get_crs()) {
+            (Some(crs1), Some(crs2)) => crs1.is_equivalent(&crs2),
+            _ => true,
+        }
+    }
+
+    /// Returns true if the two rasters share the same grid, i.e. the same number of rows
+    /// and columns, the same cell shape, rotation, and resolution, and an extent that
+    /// agrees to within a fraction of a grid cell.
+    pub fn is_grid_aligned_with(&self, other: &Raster) -> bool {
+        let tol_x = 0.01 * self.configs.resolution_x.abs();
+        let tol_y = 0.01 * self.configs.resolution_y.abs();
+        self.configs.rows == other.configs.rows
+            && self.configs.columns == other.configs.columns
+            && (self.configs.resolution_x - other.configs.resolution_x).abs() <= tol_x
+            && (self.configs.resolution_y - other.configs.resolution_y).abs() <= tol_y
+            && (self.configs.west - other.configs.west).abs() <= tol_x
+            && (self.configs.north - other.configs.north).abs() <= tol_y
+            && self.configs.grid_type == other.configs.grid_type
+            && (self.configs.rotation - other.configs.rotation).abs() < 1e-6
+    }
+
+    /// Returns a short description of the raster's grid and CRS, used in error messages.
+    pub fn describe_grid(&self) -> String {
+        let crs = if self.configs.epsg_code != 0 {
+            format!("EPSG:{}", self.configs.epsg_code)
+        } else {
+            match self.get_crs() {
+                Some(crs) => crs.name,
+                None => "undefined CRS".to_string(),
+            }
+        };
+        let geometry = if self.is_north_up_square_grid() {
+            String::new()
+        } else {
+            format!(
+                ", {} cells, rotated {} degrees",
+                self.configs.grid_type.to_string().to_lowercase(),
+                self.configs.rotation
+            )
+        };
+        format!(
+            "{} ({} rows x {} columns{}, resolution {} x {}, extent W={} E={} S={} N={}, {})",
+            self.get_short_filename(),
+            self.configs.rows,
+            self.configs.columns,
+            geometry,
+            self.configs.resolution_x,
+            self.configs.resolution_y,
+            self.configs.west,
+            self.configs.east,
+            self.configs.south,
+            self.configs.north,
+            crs
+        )
+    }
+
+    /// Creates a copy of this raster resampled, using nearest-neighbour interpolation, onto
+    /// the grid of `base`. If the two rasters are in different coordinate reference systems,
+    /// cell centres are transformed between CRSs during the resampling.
+    pub fn resample_to_grid(&self, base: &Raster) -> Raster {
+        let mut configs = base.configs.clone();
+        configs.nodata = self.configs.nodata;
+        configs.data_type = self.configs.data_type;
+        configs.photometric_interp = self.configs.photometric_interp;
+        configs.palette = self.configs.palette.clone();
+        configs.z_units = self.configs.z_units.clone();
+        configs.metadata = self.configs.metadata.clone();
+        let rows = configs.rows as isize;
+        let columns = configs.columns as isize;
+        let transform = if self.has_same_crs(base) {
+            None
+        } else {
+            match (base.get_crs(), self.get_crs()) {
+                (Some(from), Some(to)) => Some((from, to)),
+                _ => None,
+            }
+        };
+        let bands = self.configs.bands.max(1);
+        configs.bands = bands;
+        let mut output = Raster {
+            file_name: self.file_name.clone(),
+            file_mode: self.file_mode.clone(),
+            raster_type: self.raster_type.clone(),
+            configs: configs,
+            data: vec![self.configs.nodata; (rows * columns) as usize * bands],
+        };
+        for row in 0..rows {
+            for col in 0..columns {
+                let (x, y) = base.get_cell_centre(row, col);
+                let (sx, sy) = match transform {
+                    Some((ref from, ref to)) => from.transform_to(to, x, y),
+                    None => (x, y),
+                };
+                let (r, c) = self.get_cell_from_point(sx, sy);
+                if r < 0
+                    || c < 0
+                    || r >= self.configs.rows as isize
+                    || c >= self.configs.columns as isize
+                {
+                    continue;
+                }
+                for band in 0..bands {
+                    output.data[band * (rows * columns) as usize + (row * columns + col) as usize] =
+                        self.get_value_band(r, c, band);
+                }
+            }
+        }
+        output.update_min_max();
+        output
+    }
+
+    /// Returns the number of bands in the raster. Multi-band rasters store each band
+    /// as a separate plane of values, while RGB colour composites, which store packed
+    /// red, green, and blue values in each cell, have three bands. All other rasters
+    /// have a single band.
+    pub fn num_bands(&self) -> usize {
+        if self.configs.bands > 1 {
+            self.configs.bands
+        } else if self.configs.photometric_interp == PhotometricInterpretation::RGB {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Returns the value of a band (zero-based) at a grid cell. For single-band rasters
+    /// this is the same as `get_value`.
+    pub fn get_band_value(&self, row: isize, column: isize, band: usize) -> f64 {
+        self.get_value_band(row, column, band)
+    }
+
+    /// Returns the value of a band (zero-based) at a grid cell. Band zero is the raster's
+    /// primary band, i.e. the one addressed by `get_value`. For RGB colour composites, bands
+    /// zero to two are the red, green, and blue components of the packed cell values. The
+    /// NoData value is returned for cells or bands outside of the raster.
+    pub fn get_value_band(&self, row: isize, column: isize, band: usize) -> f64 {
+        if self.configs.bands > 1 {
+            if band >= self.configs.bands
+                || row < 0
+                || column < 0
+                || row as usize >= self.configs.rows
+                || column as usize >= self.configs.columns
+            {
+                return self.configs.nodata;
+            }
+            return self.data
+                [band * self.num_cells() + row as usize * self.configs.columns + column as usize];
+        }
+        let z = self.get_value(row, column);
+        if band == 0 && self.num_bands() == 1 {
+            return z;
+        }
+        if band >= self.num_bands() || z == self.configs.nodata {
+            return self.configs.nodata;
+        }
+        ((z as u32 >> (8 * band as u32)) & 0xFF) as f64
+    }
+
+    /// Sets the value of a band (zero-based) at a grid cell of a multi-band raster.
+    /// For single-band rasters, setting band zero is the same as `set_value`.
+    pub fn set_value_band(&mut self, row: isize, column: isize, band: usize, value: f64) {
+        if band >= self.configs.bands.max(1)
+            || row < 0
+            || column < 0
+            || row as usize >= self.configs.rows
+            || column as usize >= self.configs.columns
+        {
+            return;
+        }
+        let idx = band * self.num_cells() + row as usize * self.configs.columns + column as usize;
+        self.data[idx] = value;
+    }
+
+    /// Returns the values in a row of a band (zero-based).
+    pub fn get_row_data_band(&self, row: isize, band: usize) -> Vec<f64> {
+        (0..self.configs.columns as isize)
+            .map(|col| self.get_value_band(row, col, band))
+            .collect()
+    }
+
+    /// Sets the values in a row of a band (zero-based) of a multi-band raster.
+    pub fn set_row_data_band(&mut self, row: isize, band: usize, values: Vec<f64>) {
+        for col in 0..values.len().min(self.configs.columns) {
+            self.set_value_band(row, col as isize, band, values[col]);
+        }
+    }
+
+    /// Sets the number of bands stored in the raster. Existing bands are retained and any
+    /// new bands are initialized to the NoData value. Multi-band rasters can currently only
+    /// be written in the GeoTIFF and Whitebox v2 (.wbr) formats.
+    pub fn set_num_bands(&mut self, bands: usize) {
+        let bands = bands.max(1);
+        let nodata = self.configs.nodata;
+        let n = self.num_cells();
+        self.data.resize(n * bands, nodata);
+        self.configs.bands = bands;
+    }
+
+    /// Returns a copy of a band (zero-based) as a new, single-band in-memory raster with
+    /// the same grid, NoData value, and data type as this raster. The bands of RGB colour
+    /// composites are returned as 8-bit rasters.
+    pub fn get_band(&self, band: usize) -> Raster {
+        let mut output = Raster {
+            file_name: self.file_name.clone(),
+            file_mode: self.file_mode.clone(),
+            raster_type: self.raster_type.clone(),
+            configs: self.configs.clone(),
+            data: vec![],
+        };
+        output.configs.bands = 1;
+        if self.configs.bands <= 1 && self.num_bands() > 1 {
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.data_type = DataType::U8;
+        }
+        let n = self.num_cells();
+        if self.configs.bands > 1 {
+            output.data = if band < self.configs.bands {
+                self.data[band * n..(band + 1) * n].to_vec()
+            } else {
+                vec![self.configs.nodata; n]
+            };
+        } else {
+            output.data = vec![self.configs.nodata; n];
+            for row in 0..self.configs.rows as isize {
+                for col in 0..self.configs.columns as isize {
+                    output.data[row as usize * self.configs.columns + col as usize] =
+                        self.get_value_band(row, col, band);
+                }
+            }
+        }
+        output.update_min_max();
+        output
+    }
+
+    /// Interpolates the raster value at a fractional grid position, where integer row
+    /// and column values correspond to grid cell centres.
+    pub fn get_interpolated_value(&self, row: f64, column: f64, method: InterpolationMethod) -> f64 {
+        interpolate(
+            |r, c| self.get_value(r, c),
+            self.configs.nodata,
+            row,
+            column,
+            method,
+        )
+    }
+
+    /// Interpolates the value of a band (zero-based) at a fractional grid position.
+    pub fn get_interpolated_band_value(
+        &self,
+        row: f64,
+        column: f64,
+        band: usize,
+        method: InterpolationMethod,
+    ) -> f64 {
+        interpolate(
+            |r, c| self.get_band_value(r, c, band),
+            self.configs.nodata,
+            row,
+            column,
+            method,
+        )
+    }
+
+    /// Returns the fractional (row, column) grid position of an (x, y) coordinate, for
+    /// use with the interpolation methods.
+    pub fn get_grid_position(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (self.configs.north - y) / self.configs.resolution_y - 0.5,
+            (x - self.configs.west) / self.configs.resolution_x - 0.5,
+        )
+    }
+}
+
+/// Methods of interpolating raster values between grid cell centres.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InterpolationMethod {
+    NearestNeighbour,
+    Bilinear,
+    CubicConvolution,
+}
+
+impl InterpolationMethod {
+    /// Parses an interpolation method from a tool parameter value, e.g. 'nn',
+    /// 'bilinear', or 'cc'/'bicubic'. Unrecognized values return `None`.
+    pub fn from_name(s: &str) -> Option<InterpolationMethod> {
+        let s = s.to_lowercase();
+        if s.contains("nn") || s.contains("nearest") {
+            Some(InterpolationMethod::NearestNeighbour)
+        } else if s.contains("bilinear") {
+            Some(InterpolationMethod::Bilinear)
+        } else if s.contains("cc") || s.contains("cubic") {
+            Some(InterpolationMethod::CubicConvolution)
+        } else {
+            None
+        }
+    }
+}
+
+fn interpolate<F: Fn(isize, isize) -> f64>(
+    get_value: F,
+    nodata: f64,
+    row: f64,
+    column: f64,
+    method: InterpolationMethod,
+) -> f64 {
+    match method {
+        InterpolationMethod::NearestNeighbour => {
+            get_value(row.round() as isize, column.round() as isize)
+        }
+        InterpolationMethod::Bilinear => bilinear(&get_value, nodata, row, column),
+        InterpolationMethod::CubicConvolution => {
+            cubic_convolution(&get_value, nodata, row, column)
+        }
+    }
+}
+
+/// Bilinear interpolation at a fractional (row, column) position. NoData cells are
+/// excluded and the weights of the remaining cells renormalized.
+fn bilinear<F: Fn(isize, isize) -> f64>(get_value: &F, nodata: f64, row: f64, column: f64) -> f64 {
+    let r0 = row.floor() as isize;
+    let c0 = column.floor() as isize;
+    let dr = row - r0 as f64;
+    let dc = column - c0 as f64;
+    let mut sum = 0f64;
+    let mut sum_w = 0f64;
+    for (r, c, w) in [
+        (r0, c0, (1f64 - dr) * (1f64 - dc)),
+        (r0, c0 + 1, (1f64 - dr) * dc),
+        (r0 + 1, c0, dr * (1f64 - dc)),
+        (r0 + 1, c0 + 1, dr * dc),
+    ]
+    .iter()
+    {
+        let z = get_value(*r, *c);
+        if z != nodata && *w > 0f64 {
+            sum += z * w;
+            sum_w += w;
+        }
+    }
+    if sum_w > 0f64 {
+        sum / sum_w
+    } else {
+        nodata
+    }
+}
+
+/// Cubic convolution interpolation (a = -0.5) at a fractional (row, column)
+/// position. Falls back on bilinear interpolation near NoData cells.
+fn cubic_convolution<F: Fn(isize, isize) -> f64>(
+    get_value: &F,
+    nodata: f64,
+    row: f64,
+    column: f64,
+) -> f64 {
+    let r0 = row.floor() as isize;
+    let c0 = column.floor() as isize;
+    let dr = row - r0 as f64;
+    let dc = column - c0 as f64;
+    let kernel = |t: f64| -> f64 {
+        let t = t.abs();
+        if t <= 1f64 {
+            1.5 * t * t * t - 2.5 * t * t + 1f64
+        } else if t < 2f64 {
+            -0.5 * t * t * t + 2.5 * t * t - 4f64 * t + 2f64
+        } else {
+            0f64
+        }
+    };
+    let mut sum = 0f64;
+    for m in -1..3isize {
+        let wr = kernel(m as f64 - dr);
+        for n in -1..3isize {
+            let z = get_value(r0 + m, c0 + n);
+            if z == nodata {
+                return bilinear(get_value, nodata, row, column);
+            }
+            sum += z * wr * kernel(n as f64 - dc);
+        }
+    }
+    sum
 }
 
 #[derive(Debug, Clone)]
@@ -1162,7 +1673,7 @@ pub struct RasterConfigs {
     pub title: String,
     pub rows: usize,
     pub columns: usize,
-    pub bands: u8,
+    pub bands: usize,
     pub nodata: f64,
     pub north: f64,
     pub south: f64,
@@ -1189,10 +1700,17 @@ pub struct RasterConfigs {
     pub model_tiepoint: Vec<f64>,
     pub model_pixel_scale: [f64; 3],
     pub model_transformation: [f64; 16],
+    /// The shape of the grid's cells; see the `grid` module.
+    pub grid_type: GridType,
+    /// The counter-clockwise rotation of the grid about its upper-left corner, in degrees.
+    pub rotation: f64,
     pub geo_key_directory: Vec<u16>,
     pub geo_double_params: Vec<f64>,
     pub geo_ascii_params: String,
     pub metadata: Vec<String>,
+    pub compression: RasterCompression,
+    pub compression_level: u8,
+    pub predictor: RasterPredictor,
 }
 
 impl Default for RasterConfigs {
@@ -1228,10 +1746,58 @@ impl Default for RasterConfigs {
             model_tiepoint: vec![],
             model_pixel_scale: [0f64; 3],
             model_transformation: [0f64; 16],
+            grid_type: GridType::Square,
+            rotation: 0f64,
             geo_key_directory: vec![],
             geo_double_params: vec![],
             geo_ascii_params: String::new(),
             metadata: vec![],
+            compression: RasterCompression::from_u8(DEFAULT_COMPRESSION.load(Ordering::SeqCst)),
+            compression_level: DEFAULT_COMPRESSION_LEVEL.load(Ordering::SeqCst),
+            predictor: RasterPredictor::from_u8(DEFAULT_PREDICTOR.load(Ordering::SeqCst)),
+        }
+    }
+}
+
+impl RasterConfigs {
+    /// Returns true if the grid has square cells and is not rotated.
+    pub fn is_north_up_square_grid(&self) -> bool {
+        self.grid_type == GridType::Square && self.rotation == 0f64
+    }
+
+    /// Returns the (x, y) coordinates of the centre of a cell, for any grid geometry.
+    pub fn get_cell_centre(&self, row: isize, column: isize) -> (f64, f64) {
+        let mut u = column as f64 + 0.5;
+        if self.grid_type == GridType::Hexagonal && row & 1 == 1 {
+            u += 0.5;
+        }
+        let dx = u * self.resolution_x;
+        let dy = (row as f64 + 0.5) * self.resolution_y;
+        if self.rotation == 0f64 {
+            return (self.west + dx, self.north - dy);
+        }
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        (
+            self.west + dx * cos + dy * sin,
+            self.north + dx * sin - dy * cos,
+        )
+    }
+
+    /// Returns the (row, column) of the cell containing a point, for any grid
+    /// geometry. The result may lie outside of the grid.
+    pub fn get_cell_from_point(&self, x: f64, y: f64) -> (isize, isize) {
+        let (mut dx, mut dy) = (x - self.west, self.north - y);
+        if self.rotation != 0f64 {
+            let (sin, cos) = self.rotation.to_radians().sin_cos();
+            let (ex, ey) = (dx, -dy);
+            dx = ex * cos + ey * sin;
+            dy = ex * sin - ey * cos;
+        }
+        let u = dx / self.resolution_x;
+        let v = dy / self.resolution_y;
+        match self.grid_type {
+            GridType::Square => (v.floor() as isize, u.floor() as isize),
+            GridType::Hexagonal => hex_cell_from_point(u, v),
         }
     }
 }
@@ -1245,9 +1811,11 @@ pub enum RasterType {
     GrassAscii,
     IdrisiBinary,
     SagaBinary,
+    SatelliteScene,
     Surfer7Binary,
     SurferAscii,
     Whitebox, // EsriBIL
+    WhiteboxV2,
 }
 
 impl Default for RasterType {
@@ -1256,7 +1824,18 @@ impl Default for RasterType {
     }
 }
 
+impl RasterType {
+    /// Returns true if rasters with more than one band can be written in the format.
+    pub fn supports_multiple_bands(&self) -> bool {
+        *self == RasterType::GeoTiff || *self == RasterType::WhiteboxV2
+    }
+}
+
 fn get_raster_type_from_file(file_name: String, file_mode: String) -> RasterType {
+    // scenes are named by their directory or their metadata file
+    if file_mode.contains("r") && is_satellite_scene(&file_name) {
+        return RasterType::SatelliteScene;
+    }
     // get the file extension
     let extension: String = match Path::new(&file_name).extension().unwrap().to_str() {
         Some(n) => n.to_string().to_lowercase(),
@@ -1270,6 +1849,8 @@ fn get_raster_type_from_file(file_name: String, file_mode: String) -> RasterType
     }
     if extension == "tas" || extension == "dep" {
         return RasterType::Whitebox;
+    } else if extension == "wbr" {
+        return RasterType::WhiteboxV2;
     } else if extension == "tif"
         || extension == "tiff"
         || extension == "gtif"
@@ -1396,6 +1977,115 @@ impl Default for PhotometricInterpretation {
     }
 }
 
+/// The compression codec applied when writing a raster. Compression is currently only
+/// supported for GeoTIFF outputs; other formats are always written uncompressed. `Zstd`
+/// requires the `zstd` feature; without it, reading or writing ZSTD-compressed GeoTIFFs fails
+/// with an error.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RasterCompression {
+    None,
+    Deflate,
+    Lzw,
+    Zstd,
+}
+
+impl Default for RasterCompression {
+    fn default() -> RasterCompression {
+        RasterCompression::None
+    }
+}
+
+impl RasterCompression {
+    pub fn from_str<'a>(val: &'a str) -> Option<RasterCompression> {
+        match val.trim().to_lowercase().as_ref() {
+            "none" | "no" | "false" => Some(RasterCompression::None),
+            "deflate" | "zip" | "zlib" => Some(RasterCompression::Deflate),
+            "lzw" => Some(RasterCompression::Lzw),
+            "zstd" => Some(RasterCompression::Zstd),
+            _ => None,
+        }
+    }
+
+    fn from_u8(val: u8) -> RasterCompression {
+        match val {
+            1 => RasterCompression::Deflate,
+            2 => RasterCompression::Lzw,
+            3 => RasterCompression::Zstd,
+            _ => RasterCompression::None,
+        }
+    }
+}
+
+/// The predictor applied to raster data prior to compression. The horizontal predictor
+/// stores the differences between neighbouring samples and suits integer data, while
+/// the floating-point predictor (Adobe TIFF Technical Note 3) suits F32 and F64 data.
+/// `Auto` selects whichever of the two is appropriate for the raster's data type.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RasterPredictor {
+    None,
+    Horizontal,
+    FloatingPoint,
+    Auto,
+}
+
+impl Default for RasterPredictor {
+    fn default() -> RasterPredictor {
+        RasterPredictor::Auto
+    }
+}
+
+impl RasterPredictor {
+    pub fn from_str<'a>(val: &'a str) -> Option<RasterPredictor> {
+        match val.trim().to_lowercase().as_ref() {
+            "none" | "no" | "1" => Some(RasterPredictor::None),
+            "horizontal" | "2" => Some(RasterPredictor::Horizontal),
+            "float" | "floating" | "floatingpoint" | "floating_point" | "3" => {
+                Some(RasterPredictor::FloatingPoint)
+            }
+            "auto" => Some(RasterPredictor::Auto),
+            _ => None,
+        }
+    }
+
+    fn from_u8(val: u8) -> RasterPredictor {
+        match val {
+            0 => RasterPredictor::None,
+            1 => RasterPredictor::Horizontal,
+            2 => RasterPredictor::FloatingPoint,
+            _ => RasterPredictor::Auto,
+        }
+    }
+}
+
+static DEFAULT_COMPRESSION: AtomicU8 = AtomicU8::new(0);
+static DEFAULT_COMPRESSION_LEVEL: AtomicU8 = AtomicU8::new(6);
+static DEFAULT_PREDICTOR: AtomicU8 = AtomicU8::new(3);
+
+/// Sets the compression codec, compression level (1-9, used by DEFLATE), and predictor
+/// assigned to any `RasterConfigs` that are subsequently created. This is how the global
+/// `--compress` flag is applied to the outputs of all tools.
+pub fn set_default_compression(
+    compression: RasterCompression,
+    level: u8,
+    predictor: RasterPredictor,
+) {
+    let compression = match compression {
+        RasterCompression::None => 0u8,
+        RasterCompression::Deflate => 1u8,
+        RasterCompression::Lzw => 2u8,
+        RasterCompression::Zstd => 3u8,
+    };
+    let predictor = match predictor {
+        RasterPredictor::None => 0u8,
+        RasterPredictor::Horizontal => 1u8,
+        RasterPredictor::FloatingPoint => 2u8,
+        RasterPredictor::Auto => 3u8,
+    };
+    DEFAULT_COMPRESSION.store(compression, Ordering::SeqCst);
+    DEFAULT_COMPRESSION_LEVEL.store(level.max(1).min(9), Ordering::SeqCst);
+    DEFAULT_PREDICTOR.store(predictor, Ordering::SeqCst);
+}
+
 // #[derive(Debug, Copy, Clone, PartialEq)]
 // pub enum RasterByteOrder {
 //     LittleEndian,
@@ -1435,3 +2125,82 @@ impl Default for PhotometricInterpretation {
 //     }
 //
 // }
+
+#[cfg(test)]
+mod test {
+    use super::{DataType, Raster, RasterConfigs};
+    use std::env;
+    use std::fs;
+
+    fn three_band_raster(file: &str) -> Raster {
+        let configs = RasterConfigs {
+            rows: 6,
+            columns: 7,
+            north: 6.0,
+            south: 0.0,
+            east: 7.0,
+            west: 0.0,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            nodata: -32768.0,
+            data_type: DataType::F32,
+            ..Default::default()
+        };
+        let mut raster = Raster::initialize_using_config(file, &configs);
+        raster.set_num_bands(3);
+        for band in 0..3 {
+            for row in 0..6 {
+                for col in 0..7 {
+                    let z = (band * 100) as f64 + (row * 10 + col) as f64 + 0.5;
+                    raster.set_value_band(row, col, band as usize, z);
+                }
+            }
+        }
+        raster.set_value_band(2, 3, 1, -32768.0);
+        raster
+    }
+
+    #[test]
+    fn test_multiband_round_trip() {
+        let dir = env::temp_dir().join(format!("wbt_multiband_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for extension in &["tif", "wbr"] {
+            let file = dir.join(format!("bands.{}", extension));
+            let file = file.to_string_lossy();
+            let mut raster = three_band_raster(&file);
+            raster.write().unwrap();
+            let input = Raster::new(&file, "r").unwrap();
+            assert_eq!(input.configs.bands, 3, "{} bands", extension);
+            for band in 0..3 {
+                for row in 0..6 {
+                    for col in 0..7 {
+                        assert_eq!(
+                            input.get_value_band(row, col, band),
+                            raster.get_value_band(row, col, band),
+                            "{} band {} cell ({}, {})",
+                            extension,
+                            band,
+                            row,
+                            col
+                        );
+                    }
+                }
+            }
+            assert_eq!(input.get_value_band(2, 3, 1), -32768.0);
+            assert_eq!(input.get_value_band(0, 0, 3), -32768.0);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_multiband_write_unsupported_format() {
+        let dir = env::temp_dir().join(format!("wbt_multiband_asc_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for extension in &["asc", "dep", "sdat"] {
+            let file = dir.join(format!("bands.{}", extension));
+            let mut raster = three_band_raster(&file.to_string_lossy());
+            assert!(raster.write().is_err(), "{} accepted 3 bands", extension);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,679 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Satellite scenes are delivered as directories of single-band image files described
+by a metadata file, i.e. the _MTL.txt file of Landsat Collection 2 scenes and the
+MTD_MSIL1C.xml or MTD_MSIL2A.xml file of Sentinel-2 SAFE products. A scene is read, by
+naming either its directory or its metadata file, as a single multi-band raster of
+reflectance (or, for thermal bands, temperature in kelvin) values, with the scaling and
+offsets of the metadata applied and the fill value (zero) converted to NoData. All bands
+are placed on the grid of the scene's first band; finer bands are averaged and coarser
+bands are sampled onto it. For Sentinel-2 this is the 20 m grid of the red-edge bands.
+The acquisition date and time, solar geometry, and the role of each band (e.g. 'red' or
+'nir') are stored in the raster's metadata as 'Key: value' entries, where downstream tools
+can find them with Raster::get_metadata_value. Scenes are read-only.
+
+The band files of Sentinel-2 products are JPEG 2000 (.jp2) images, which cannot be
+decoded here. They must be converted to GeoTIFFs of the same name (e.g. with
+gdal_translate) in the product's IMG_DATA directories before the product can be read.
+*/
+
+use super::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// The metadata key under which the role of a band (e.g. 'Band 3 role') is stored.
+pub const BAND_ROLE_KEY: &str = "role";
+
+#[derive(Clone, Copy, PartialEq)]
+enum SceneKind {
+    Landsat,
+    Sentinel2,
+}
+
+struct SceneBand {
+    file: PathBuf,
+    name: String,
+    role: String,
+    scale: f64,
+    offset: f64,
+    /// The K1 and K2 constants converting the radiance of a thermal band into brightness
+    /// temperature.
+    thermal_constants: Option<(f64, f64)>,
+}
+
+/// Returns true if the file name refers to a Landsat Collection 2 scene or a Sentinel-2
+/// SAFE product, i.e. either the scene's directory or its metadata file.
+pub fn is_satellite_scene(file_name: &str) -> bool {
+    find_scene_metadata(file_name).is_some()
+}
+
+/// Returns the index of the band of a raster with the specified role (e.g. 'nir'), if the
+/// raster was read from a satellite scene.
+pub fn find_band_by_role(raster: &Raster, role: &str) -> Option<usize> {
+    let role = role.trim().to_lowercase();
+    (0..raster.configs.bands).find(|b| {
+        raster
+            .get_metadata_value(&format!("Band {} {}", b + 1, BAND_ROLE_KEY))
+            .map_or(false, |r| r == role)
+    })
+}
+
+/// Reads a satellite scene as a multi-band raster.
+pub fn read_satellite_scene(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let (kind, metadata_file) = find_scene_metadata(file_name).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "{} is not a Landsat Collection 2 scene or Sentinel-2 SAFE product.",
+                file_name
+            ),
+        )
+    })?;
+    let (bands, metadata) = match kind {
+        SceneKind::Landsat => read_landsat_metadata(&metadata_file)?,
+        SceneKind::Sentinel2 => read_sentinel2_metadata(&metadata_file)?,
+    };
+    if bands.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("No image bands are listed in {}.", metadata_file.display()),
+        ));
+    }
+
+    let nodata = -32768f64;
+    let mut base: Option<RasterConfigs> = None;
+    data.clear();
+    for band in &bands {
+        let band_file = band.file.to_string_lossy().to_string();
+        let layer = Raster::new(&band_file, "r")?;
+        let values: Vec<f64> = layer
+            .data
+            .iter()
+            .map(|&dn| {
+                if dn == 0f64 || dn == layer.configs.nodata {
+                    nodata
+                } else {
+                    let z = dn * band.scale + band.offset;
+                    match band.thermal_constants {
+                        Some((k1, k2)) => k2 / (k1 / z + 1f64).ln(),
+                        None => z,
+                    }
+                }
+            })
+            .collect();
+        match &base {
+            None => {
+                data.extend(values);
+                base = Some(layer.configs.clone());
+            }
+            Some(base_configs) => {
+                data.extend(regrid(&values, &layer.configs, base_configs, nodata));
+            }
+        }
+    }
+
+    *configs = base.unwrap();
+    configs.bands = bands.len();
+    configs.nodata = nodata;
+    configs.data_type = DataType::F32;
+    configs.photometric_interp = PhotometricInterpretation::Continuous;
+    configs.palette = "grey.plt".to_string();
+    configs.metadata = metadata;
+    for (b, band) in bands.iter().enumerate() {
+        configs
+            .metadata
+            .push(format!("Band {} name: {}", b + 1, band.name));
+        if !band.role.is_empty() {
+            configs
+                .metadata
+                .push(format!("Band {} {}: {}", b + 1, BAND_ROLE_KEY, band.role));
+        }
+    }
+    Ok(())
+}
+
+fn find_scene_metadata(file_name: &str) -> Option<(SceneKind, PathBuf)> {
+    let path = Path::new(file_name);
+    if path.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .ok()?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+        return entries
+            .into_iter()
+            .find_map(|p| metadata_kind(&p).map(|kind| (kind, p)));
+    }
+    metadata_kind(path).map(|kind| (kind, path.to_path_buf()))
+}
+
+fn metadata_kind(path: &Path) -> Option<SceneKind> {
+    let name = path.file_name()?.to_str()?.to_uppercase();
+    if name.ends_with("_MTL.TXT") {
+        Some(SceneKind::Landsat)
+    } else if name.starts_with("MTD_MSIL") && name.ends_with(".XML") {
+        Some(SceneKind::Sentinel2)
+    } else {
+        None
+    }
+}
+
+/// Places the values of a band onto the base grid. Bands with finer cells are averaged
+/// within each base cell, while bands with coarser cells are sampled at the cell centres.
+fn regrid(values: &[f64], from: &RasterConfigs, to: &RasterConfigs, nodata: f64) -> Vec<f64> {
+    let n = to.rows * to.columns;
+    if from.rows == to.rows
+        && from.columns == to.columns
+        && (from.west - to.west).abs() < 0.01 * to.resolution_x
+        && (from.north - to.north).abs() < 0.01 * to.resolution_y
+    {
+        return values.to_vec();
+    }
+    let mut output = vec![nodata; n];
+    if from.resolution_x < to.resolution_x {
+        let mut sum = vec![0f64; n];
+        let mut count = vec![0usize; n];
+        for row in 0..from.rows {
+            let y = from.north - (row as f64 + 0.5) * from.resolution_y;
+            let to_row = ((to.north - y) / to.resolution_y).floor();
+            if to_row < 0f64 || to_row >= to.rows as f64 {
+                continue;
+            }
+            for col in 0..from.columns {
+                let z = values[row * from.columns + col];
+                if z == nodata {
+                    continue;
+                }
+                let x = from.west + (col as f64 + 0.5) * from.resolution_x;
+                let to_col = ((x - to.west) / to.resolution_x).floor();
+                if to_col >= 0f64 && to_col < to.columns as f64 {
+                    let idx = to_row as usize * to.columns + to_col as usize;
+                    sum[idx] += z;
+                    count[idx] += 1;
+                }
+            }
+        }
+        for idx in 0..n {
+            if count[idx] > 0 {
+                output[idx] = sum[idx] / count[idx] as f64;
+            }
+        }
+    } else {
+        for row in 0..to.rows {
+            let y = to.north - (row as f64 + 0.5) * to.resolution_y;
+            let from_row = ((from.north - y) / from.resolution_y).floor();
+            if from_row < 0f64 || from_row >= from.rows as f64 {
+                continue;
+            }
+            for col in 0..to.columns {
+                let x = to.west + (col as f64 + 0.5) * to.resolution_x;
+                let from_col = ((x - from.west) / from.resolution_x).floor();
+                if from_col >= 0f64 && from_col < from.columns as f64 {
+                    output[row * to.columns + col] =
+                        values[from_row as usize * from.columns + from_col as usize];
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Parses an MTL metadata file into a map of 'GROUP.KEY' to value, where GROUP is the
+/// innermost group containing the key.
+fn parse_mtl(contents: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let mut groups: Vec<String> = vec![];
+    for line in contents.lines() {
+        let mut parts = line.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts.next().unwrap_or("").trim().trim_matches('"');
+        if key == "GROUP" {
+            groups.push(value.to_string());
+        } else if key == "END_GROUP" {
+            groups.pop();
+        } else if !key.is_empty() && !value.is_empty() {
+            let group = groups.last().map_or("", |g| g.as_str());
+            map.insert(format!("{}.{}", group, key), value.to_string());
+        }
+    }
+    map
+}
+
+fn read_landsat_metadata(metadata_file: &Path) -> Result<(Vec<SceneBand>, Vec<String>), Error> {
+    let mtl = parse_mtl(&fs::read_to_string(metadata_file)?);
+    let dir = metadata_file.parent().unwrap_or(Path::new(""));
+    let get = |key: &str| mtl.get(key).map(|v| v.as_str());
+    let get_f64 = |key: &str| get(key).and_then(|v| v.parse::<f64>().ok());
+
+    let spacecraft = get("IMAGE_ATTRIBUTES.SPACECRAFT_ID")
+        .or(get("PRODUCT_CONTENTS.SPACECRAFT_ID"))
+        .unwrap_or("LANDSAT")
+        .to_string();
+    let level = get("PRODUCT_CONTENTS.PROCESSING_LEVEL")
+        .unwrap_or("")
+        .to_string();
+    let is_level2 = level.starts_with("L2");
+    let sun_elevation = get_f64("IMAGE_ATTRIBUTES.SUN_ELEVATION").unwrap_or(f64::NAN);
+    let sun_azimuth = get_f64("IMAGE_ATTRIBUTES.SUN_AZIMUTH").unwrap_or(f64::NAN);
+    let is_oli = spacecraft.ends_with('8') || spacecraft.ends_with('9');
+
+    // the band files, in the order listed in the metadata
+    let mut ids: Vec<(usize, String, String)> = vec![];
+    for (key, file) in mtl.iter() {
+        if let Some(id) = key.strip_prefix("PRODUCT_CONTENTS.FILE_NAME_BAND_") {
+            ids.push((band_sort_key(id), id.to_string(), file.clone()));
+        }
+    }
+    ids.sort();
+
+    let mut bands = vec![];
+    for (_, id, file) in ids {
+        let (scale, offset) = if is_level2 {
+            match (
+                get_f64(&format!(
+                    "LEVEL2_SURFACE_REFLECTANCE_PARAMETERS.REFLECTANCE_MULT_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL2_SURFACE_REFLECTANCE_PARAMETERS.REFLECTANCE_ADD_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL2_SURFACE_TEMPERATURE_PARAMETERS.TEMPERATURE_MULT_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL2_SURFACE_TEMPERATURE_PARAMETERS.TEMPERATURE_ADD_BAND_{}",
+                    id
+                )),
+            ) {
+                (Some(m), Some(a), _, _) => (m, a),
+                (_, _, Some(m), Some(a)) => (m, a),
+                _ => continue,
+            }
+        } else {
+            match (
+                get_f64(&format!(
+                    "LEVEL1_RADIOMETRIC_RESCALING.REFLECTANCE_MULT_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL1_RADIOMETRIC_RESCALING.REFLECTANCE_ADD_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL1_RADIOMETRIC_RESCALING.RADIANCE_MULT_BAND_{}",
+                    id
+                )),
+                get_f64(&format!(
+                    "LEVEL1_RADIOMETRIC_RESCALING.RADIANCE_ADD_BAND_{}",
+                    id
+                )),
+            ) {
+                // top-of-atmosphere reflectance, corrected for the solar elevation
+                (Some(m), Some(a), _, _) => {
+                    let sin_elev = sun_elevation.to_radians().sin();
+                    (m / sin_elev, a / sin_elev)
+                }
+                (_, _, Some(m), Some(a)) => (m, a),
+                _ => continue,
+            }
+        };
+        let number = id.trim_start_matches("ST_B");
+        let number = number.split('_').next().unwrap_or("");
+        bands.push(SceneBand {
+            file: dir.join(&file),
+            name: id.clone(),
+            role: landsat_band_role(number, is_oli).to_string(),
+            scale: scale,
+            offset: offset,
+            thermal_constants: match (
+                get_f64(&format!("LEVEL1_THERMAL_CONSTANTS.K1_CONSTANT_BAND_{}", id)),
+                get_f64(&format!("LEVEL1_THERMAL_CONSTANTS.K2_CONSTANT_BAND_{}", id)),
+            ) {
+                (Some(k1), Some(k2)) if !is_level2 => Some((k1, k2)),
+                _ => None,
+            },
+        });
+    }
+
+    let mut metadata = vec![];
+    metadata.push("Scene type: Landsat Collection 2".to_string());
+    metadata.push(format!("Metadata file: {}", metadata_file.display()));
+    metadata.push(format!("Spacecraft: {}", spacecraft));
+    metadata.push(format!("Processing level: {}", level));
+    if let Some(date) = get("IMAGE_ATTRIBUTES.DATE_ACQUIRED") {
+        metadata.push(format!("Acquisition date: {}", date));
+    }
+    if let Some(time) = get("IMAGE_ATTRIBUTES.SCENE_CENTER_TIME") {
+        metadata.push(format!("Acquisition time: {}", time));
+    }
+    metadata.push(format!("Sun azimuth: {}", sun_azimuth));
+    metadata.push(format!("Sun elevation: {}", sun_elevation));
+    metadata.push(format!("Sun zenith: {}", 90f64 - sun_elevation));
+    if let Some(d) = get_f64("IMAGE_ATTRIBUTES.EARTH_SUN_DISTANCE") {
+        metadata.push(format!("Earth-Sun distance: {}", d));
+    }
+    metadata.push(format!(
+        "Values: {}",
+        if is_level2 {
+            "surface reflectance; surface temperature (K) for thermal bands"
+        } else {
+            "top-of-atmosphere reflectance; brightness temperature (K) for thermal bands"
+        }
+    ));
+    if let Some(qa) = get("PRODUCT_CONTENTS.FILE_NAME_QUALITY_L1_PIXEL") {
+        metadata.push(format!("Quality file: {}", dir.join(qa).display()));
+    }
+    Ok((bands, metadata))
+}
+
+/// Orders the Landsat band identifiers (e.g. '1', '6_VCID_1', 'ST_B10') numerically.
+fn band_sort_key(id: &str) -> usize {
+    let digits: String = id
+        .trim_start_matches("ST_B")
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse::<usize>().unwrap_or(usize::MAX) * 10 + id.len()
+}
+
+fn landsat_band_role(number: &str, is_oli: bool) -> &'static str {
+    if is_oli {
+        match number {
+            "1" => "coastal",
+            "2" => "blue",
+            "3" => "green",
+            "4" => "red",
+            "5" => "nir",
+            "6" => "swir1",
+            "7" => "swir2",
+            "8" => "pan",
+            "9" => "cirrus",
+            "10" => "tir1",
+            "11" => "tir2",
+            _ => "",
+        }
+    } else {
+        match number {
+            "1" => "blue",
+            "2" => "green",
+            "3" => "red",
+            "4" => "nir",
+            "5" => "swir1",
+            "6" => "tir1",
+            "7" => "swir2",
+            "8" => "pan",
+            _ => "",
+        }
+    }
+}
+
+/// The Sentinel-2 bands that are read, with their roles. The 60 m atmospheric bands (B01,
+/// B09 and B10) are omitted, as is the 10 m B08, in favour of the narrow 20 m B8A band.
+const SENTINEL2_BANDS: [(&str, usize, &str); 9] = [
+    ("B02", 1, "blue"),
+    ("B03", 2, "green"),
+    ("B04", 3, "red"),
+    ("B05", 4, "rededge1"),
+    ("B06", 5, "rededge2"),
+    ("B07", 6, "rededge3"),
+    ("B8A", 8, "nir"),
+    ("B11", 11, "swir1"),
+    ("B12", 12, "swir2"),
+];
+
+/// Returns the contents and attributes of each of the elements with the specified tag.
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut elements = vec![];
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // make sure that the tag name is complete, e.g. <U> rather than <UNIT>
+        if !after.starts_with('>') && !after.starts_with(' ') {
+            rest = after;
+            continue;
+        }
+        let attr_end = match after.find('>') {
+            Some(i) => i,
+            None => break,
+        };
+        let content = &after[attr_end + 1..];
+        let end = match content.find(&close) {
+            Some(i) => i,
+            None => break,
+        };
+        elements.push((after[..attr_end].trim(), content[..end].trim()));
+        rest = &content[end..];
+    }
+    elements
+}
+
+fn xml_value<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    xml_elements(xml, tag).first().map(|e| e.1)
+}
+
+/// Returns the value of an attribute, e.g. band_id="3".
+fn xml_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let key = format!("{}=\"", name);
+    let start = attributes.find(&key)? + key.len();
+    let end = attributes[start..].find('"')?;
+    Some(&attributes[start..start + end])
+}
+
+fn read_sentinel2_metadata(metadata_file: &Path) -> Result<(Vec<SceneBand>, Vec<String>), Error> {
+    let xml = fs::read_to_string(metadata_file)?;
+    let dir = metadata_file.parent().unwrap_or(Path::new(""));
+    let level = xml_value(&xml, "PROCESSING_LEVEL")
+        .unwrap_or("")
+        .to_string();
+    let is_level2 = level.contains("2A");
+
+    let quantification = xml_value(
+        &xml,
+        if is_level2 {
+            "BOA_QUANTIFICATION_VALUE"
+        } else {
+            "QUANTIFICATION_VALUE"
+        },
+    )
+    .and_then(|v| v.parse::<f64>().ok())
+    .unwrap_or(10000f64);
+    // radiometric offsets were introduced with processing baseline 04.00
+    let mut add_offsets: HashMap<usize, f64> = HashMap::new();
+    for (attributes, value) in xml_elements(
+        &xml,
+        if is_level2 {
+            "BOA_ADD_OFFSET"
+        } else {
+            "RADIO_ADD_OFFSET"
+        },
+    ) {
+        if let (Some(id), Ok(v)) = (
+            xml_attribute(attributes, "band_id").and_then(|id| id.parse::<usize>().ok()),
+            value.parse::<f64>(),
+        ) {
+            add_offsets.insert(id, v);
+        }
+    }
+    let mut irradiance: HashMap<usize, f64> = HashMap::new();
+    for (attributes, value) in xml_elements(&xml, "SOLAR_IRRADIANCE") {
+        if let (Some(id), Ok(v)) = (
+            xml_attribute(attributes, "bandId").and_then(|id| id.parse::<usize>().ok()),
+            value.parse::<f64>(),
+        ) {
+            irradiance.insert(id, v);
+        }
+    }
+
+    // the image files are listed without their extensions
+    let image_files: Vec<&str> = xml_elements(&xml, "IMAGE_FILE")
+        .iter()
+        .map(|e| e.1)
+        .collect();
+    let mut bands = vec![];
+    let mut irradiance_metadata = vec![];
+    for (name, band_id, role) in SENTINEL2_BANDS.iter() {
+        // L2A products provide every band at 20 m, apart from B08
+        let suffixes = if is_level2 {
+            vec![format!("_{}_20m", name)]
+        } else {
+            vec![format!("_{}", name)]
+        };
+        let suffixes: Vec<&str> = suffixes.iter().map(|s| s.as_str()).collect();
+        let image = find_image(&image_files, &suffixes).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Band {} is not listed in {}.",
+                    name,
+                    metadata_file.display()
+                ),
+            )
+        })?;
+        let offset = add_offsets.get(band_id).cloned().unwrap_or(0f64);
+        if let Some(e) = irradiance.get(band_id) {
+            irradiance_metadata.push(format!("Band {} solar irradiance: {}", bands.len() + 1, e));
+        }
+        bands.push(SceneBand {
+            file: sentinel2_band_file(dir, image)?,
+            name: name.to_string(),
+            role: role.to_string(),
+            scale: 1f64 / quantification,
+            thermal_constants: None,
+            offset: offset / quantification,
+        });
+    }
+
+    let mut metadata = vec![];
+    metadata.push("Scene type: Sentinel-2 SAFE".to_string());
+    metadata.push(format!("Metadata file: {}", metadata_file.display()));
+    metadata.push(format!(
+        "Spacecraft: {}",
+        xml_value(&xml, "SPACECRAFT_NAME").unwrap_or("Sentinel-2")
+    ));
+    metadata.push(format!("Processing level: {}", level));
+    if let Some(start) = xml_value(&xml, "PRODUCT_START_TIME") {
+        let mut parts = start.splitn(2, 'T');
+        metadata.push(format!("Acquisition date: {}", parts.next().unwrap_or("")));
+        if let Some(time) = parts.next() {
+            metadata.push(format!("Acquisition time: {}", time));
+        }
+    }
+    // the solar angles are given in the metadata of the granule
+    if let Some(granule_metadata) = image_files
+        .first()
+        .and_then(|f| f.split("/IMG_DATA").next())
+        .map(|granule| dir.join(granule).join("MTD_TL.xml"))
+    {
+        if let Ok(tile_xml) = fs::read_to_string(&granule_metadata) {
+            if let Some(angles) = xml_value(&tile_xml, "Mean_Sun_Angle") {
+                let zenith = xml_value(angles, "ZENITH_ANGLE")
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .unwrap_or(f64::NAN);
+                let azimuth = xml_value(angles, "AZIMUTH_ANGLE")
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .unwrap_or(f64::NAN);
+                metadata.push(format!("Sun azimuth: {}", azimuth));
+                metadata.push(format!("Sun elevation: {}", 90f64 - zenith));
+                metadata.push(format!("Sun zenith: {}", zenith));
+            }
+        }
+    }
+    // U is the square of the ratio of the mean to the actual Earth-Sun distance
+    if let Some(u) = xml_value(&xml, "U").and_then(|v| v.parse::<f64>().ok()) {
+        metadata.push(format!("Earth-Sun distance: {}", 1f64 / u.sqrt()));
+    }
+    metadata.push(format!(
+        "Values: {}",
+        if is_level2 {
+            "surface reflectance"
+        } else {
+            "top-of-atmosphere reflectance"
+        }
+    ));
+    if is_level2 {
+        if let Some(scl) = find_image(&image_files, &["_SCL_20m"]) {
+            metadata.push(format!(
+                "Quality file: {}",
+                sentinel2_band_file(dir, scl)?.display()
+            ));
+        }
+    }
+    metadata.extend(irradiance_metadata);
+    Ok((bands, metadata))
+}
+
+/// Returns the first of the image files that ends with one of the suffixes.
+fn find_image<'a>(image_files: &[&'a str], suffixes: &[&str]) -> Option<&'a str> {
+    for suffix in suffixes {
+        if let Some(f) = image_files.iter().find(|f| f.ends_with(suffix)) {
+            return Some(f);
+        }
+    }
+    None
+}
+
+/// Locates the file of a Sentinel-2 image, preferring a GeoTIFF conversion of the
+/// JPEG 2000 original.
+fn sentinel2_band_file(dir: &Path, image: &str) -> Result<PathBuf, Error> {
+    for ext in &["tif", "tiff", "TIF"] {
+        let file = dir.join(format!("{}.{}", image, ext));
+        if file.exists() {
+            return Ok(file);
+        }
+    }
+    Err(Error::new(
+        ErrorKind::InvalidData,
+        format!(
+            "No GeoTIFF version of the Sentinel-2 image {}.jp2 was found. JPEG 2000 images are not supported; convert the band to a GeoTIFF of the same name (e.g. {}.tif) within the product.",
+            dir.join(image).display(),
+            image.rsplit('/').next().unwrap_or(image)
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{band_sort_key, parse_mtl, xml_attribute, xml_elements, xml_value};
+
+    #[test]
+    fn test_parse_mtl() {
+        let mtl = "GROUP = LANDSAT_METADATA_FILE\n  GROUP = PRODUCT_CONTENTS\n    FILE_NAME_BAND_1 = \"LC08_SR_B1.TIF\"\n  END_GROUP = PRODUCT_CONTENTS\n  GROUP = IMAGE_ATTRIBUTES\n    SUN_ELEVATION = 55.1\n  END_GROUP = IMAGE_ATTRIBUTES\nEND_GROUP = LANDSAT_METADATA_FILE\nEND\n";
+        let map = parse_mtl(mtl);
+        assert_eq!(
+            map.get("PRODUCT_CONTENTS.FILE_NAME_BAND_1").unwrap(),
+            "LC08_SR_B1.TIF"
+        );
+        assert_eq!(map.get("IMAGE_ATTRIBUTES.SUN_ELEVATION").unwrap(), "55.1");
+    }
+
+    #[test]
+    fn test_band_sort_key() {
+        let mut ids = vec!["ST_B10", "7", "10", "1", "6_VCID_2", "6_VCID_1"];
+        ids.sort_by_key(|id| (band_sort_key(id), id.to_string()));
+        assert_eq!(ids, vec!["1", "6_VCID_1", "6_VCID_2", "7", "10", "ST_B10"]);
+    }
+
+    #[test]
+    fn test_xml_elements() {
+        let xml = "<U>1.03</U><UNIT>x</UNIT><BOA_ADD_OFFSET band_id=\"1\">-1000</BOA_ADD_OFFSET>";
+        assert_eq!(xml_value(xml, "U"), Some("1.03"));
+        let offsets = xml_elements(xml, "BOA_ADD_OFFSET");
+        assert_eq!(offsets.len(), 1);
+        assert_eq!(xml_attribute(offsets[0].0, "band_id"), Some("1"));
+        assert_eq!(offsets[0].1, "-1000");
+    }
+}
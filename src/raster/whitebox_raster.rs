@@ -35,7 +35,7 @@ pub fn read_whitebox(
         } else if vec[0].to_lowercase().contains("col") {
             configs.columns = vec[1].trim().parse::<f32>().unwrap() as usize;
         } else if vec[0].to_lowercase().contains("stacks") {
-            configs.bands = vec[1].trim().to_string().parse::<u8>().unwrap();
+            configs.bands = vec[1].trim().to_string().parse::<usize>().unwrap();
         } else if vec[0].to_lowercase().contains("north") {
             configs.north = vec[1].trim().to_string().parse::<f64>().unwrap();
         } else if vec[0].to_lowercase().contains("south") {
@@ -109,6 +109,10 @@ pub fn read_whitebox(
             } else {
                 configs.endian = Endianness::BigEndian;
             }
+        } else if vec[0].to_lowercase().contains("grid type") {
+            configs.grid_type = GridType::from_str(vec[1]).unwrap_or(GridType::Square);
+        } else if vec[0].to_lowercase().contains("rotation") {
+            configs.rotation = vec[1].trim().to_string().parse::<f64>().unwrap_or(0f64);
         } else if vec[0].to_lowercase().contains("metadata") {
             configs.metadata.push(vec[1].trim().to_string());
         }
@@ -405,6 +409,16 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
     );
     writer.write_all(s.as_bytes())?;
 
+    if r.configs.grid_type != GridType::Square {
+        let s = format!("Grid Type:\t{}\n", r.configs.grid_type);
+        writer.write_all(s.as_bytes())?;
+    }
+
+    if r.configs.rotation != 0.0 {
+        let s = format!("Rotation:\t{}\n", r.configs.rotation);
+        writer.write_all(s.as_bytes())?;
+    }
+
     for md in &r.configs.metadata {
         let s = format!("Metadata Entry:\t{}\n", md.replace(":", ";"));
         writer.write_all(s.as_bytes())?;
@@ -0,0 +1,623 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Version 2 of the native Whitebox raster format stores a raster in a single .wbr
+file. The file begins with a 16-byte preamble, i.e. the 'WBR2' signature, the format
+version (u16), two reserved bytes, and the offset (u64) of the header. The header is a
+UTF-8 JSON document at the end of the file that describes the grid, the CRS (as an EPSG
+code, the original WKT, and OGC WKT2), the per-band statistics, and the location of each
+chunk. Values are stored in square chunks of CHUNK_SIZE x CHUNK_SIZE cells (smaller along
+the eastern and southern edges), band by band, in little-endian byte order. The bytes of
+each chunk are shuffled, so that the n-th bytes of all of the chunk's values are stored
+together, before being DEFLATE compressed, which greatly improves the compression of
+floating-point data. The full-resolution grid is followed by a pyramid of overviews, each
+half of the resolution of the previous level, down to a single chunk. Because the chunks
+are independent, they are compressed and decompressed in parallel.
+*/
+
+use super::*;
+use crate::utils::thread;
+use crate::utils::vfs::{create_file, read_file};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use libflate::zlib::Decoder;
+use serde_json::{json, Value};
+use std::f64;
+use std::io::prelude::*;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+const SIGNATURE: &[u8; 4] = b"WBR2";
+const VERSION: u16 = 2;
+const PREAMBLE_SIZE: usize = 16;
+
+/// The width and height, in cells, of the chunks in which values are stored.
+pub const CHUNK_SIZE: usize = 256;
+
+/// Reads a Whitebox v2 raster at full resolution.
+pub fn read_whitebox_v2(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    read_whitebox_v2_overview(file_name, 0, configs, data)
+}
+
+/// Reads one level of a Whitebox v2 raster's pyramid, where level zero is the full
+/// resolution grid and each subsequent level is an overview at half of the resolution of
+/// the previous level. The configs describe the grid of the requested level.
+pub fn read_whitebox_v2_overview(
+    file_name: &String,
+    level: usize,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let bytes = read_file(file_name)?;
+    let header = parse_header(&bytes)?;
+    let levels = match header["levels"].as_array() {
+        Some(l) => l,
+        None => return Err(invalid_data("The raster header does not list any levels.")),
+    };
+    if level >= levels.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "The raster has {} overview levels; level {} does not exist.",
+                levels.len() - 1,
+                level
+            ),
+        ));
+    }
+    header_to_configs(&header, configs)?;
+    let data_type = configs.data_type;
+    let bands = configs.bands.max(1);
+
+    // the overview covers the raster's extent, rounded up to a whole number of its cells
+    let rows = levels[level]["rows"].as_u64().unwrap_or(0) as usize;
+    let columns = levels[level]["columns"].as_u64().unwrap_or(0) as usize;
+    if level > 0 {
+        let factor = levels[level]["factor"].as_f64().unwrap_or(1f64);
+        configs.resolution_x *= factor;
+        configs.resolution_y *= factor;
+        configs.east = configs.west + columns as f64 * configs.resolution_x;
+        configs.south = configs.north - rows as f64 * configs.resolution_y;
+        configs.rows = rows;
+        configs.columns = columns;
+    }
+
+    let mut index = vec![];
+    if let Some(chunks) = levels[level]["chunks"].as_array() {
+        for c in chunks {
+            let offset = c[0].as_u64().unwrap_or(0) as usize;
+            let length = c[1].as_u64().unwrap_or(0) as usize;
+            if offset + length > bytes.len() {
+                return Err(invalid_data(
+                    "A raster chunk lies beyond the end of the file.",
+                ));
+            }
+            index.push((offset, length));
+        }
+    }
+    let chunk_rows = (rows + CHUNK_SIZE - 1) / CHUNK_SIZE;
+    let chunk_cols = (columns + CHUNK_SIZE - 1) / CHUNK_SIZE;
+    let num_chunks = bands * chunk_rows * chunk_cols;
+    if index.len() != num_chunks {
+        return Err(invalid_data(
+            "The raster's chunk index does not match its dimensions.",
+        ));
+    }
+    let compressed = header["compression"].as_str().unwrap_or("none") != "none";
+
+    let bytes = Arc::new(bytes);
+    let index = Arc::new(index);
+    let num_procs = num_cpus::get().min(num_chunks).max(1);
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let bytes = bytes.clone();
+        let index = index.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for i in (0..num_chunks).filter(|i| i % num_procs == tid) {
+                let (offset, length) = index[i];
+                let raw = &bytes[offset..offset + length];
+                let result = if compressed {
+                    inflate(raw)
+                } else {
+                    Ok(raw.to_vec())
+                };
+                let result = result.map(|b| decode_values(&unshuffle(&b, data_type), data_type));
+                tx.send((i, result)).unwrap();
+            }
+        });
+    }
+
+    data.clear();
+    data.resize(bands * rows * columns, configs.nodata);
+    let mut error = None;
+    for _ in 0..num_chunks {
+        let (i, result) = rx.recv().expect("Error receiving data from thread.");
+        let values = match result {
+            Ok(v) => v,
+            Err(e) => {
+                error = Some(e);
+                continue;
+            }
+        };
+        let band = i / (chunk_rows * chunk_cols);
+        let (chunk_row, chunk_col) = ((i / chunk_cols) % chunk_rows, i % chunk_cols);
+        let (start_col, end_col) = (
+            chunk_col * CHUNK_SIZE,
+            ((chunk_col + 1) * CHUNK_SIZE).min(columns),
+        );
+        let width = end_col - start_col;
+        for (n, row) in
+            (chunk_row * CHUNK_SIZE..((chunk_row + 1) * CHUNK_SIZE).min(rows)).enumerate()
+        {
+            if (n + 1) * width > values.len() {
+                error = Some(invalid_data("A raster chunk is shorter than expected."));
+                break;
+            }
+            let start = band * rows * columns + row * columns;
+            data[start + start_col..start + end_col]
+                .copy_from_slice(&values[n * width..(n + 1) * width]);
+        }
+    }
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Returns the JSON header of a Whitebox v2 raster, which includes the per-band
+/// statistics and the dimensions of each overview level, without decoding the raster's
+/// values.
+pub fn read_whitebox_v2_header(file_name: &String) -> Result<Value, Error> {
+    parse_header(&read_file(file_name)?)
+}
+
+pub fn write_whitebox_v2<'a>(r: &'a mut Raster) -> Result<(), Error> {
+    let data_type = storage_type(r.configs.data_type);
+    let bands = r.configs.bands.max(1);
+    let (rows, columns) = (r.configs.rows, r.configs.columns);
+    let nodata = r.configs.nodata;
+    if r.data.len() < bands * rows * columns {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The raster does not contain a value for each cell.",
+        ));
+    }
+
+    let mut statistics = vec![];
+    for band in 0..bands {
+        let (min, max, mean, stdev, n) = band_statistics(
+            &r.data[band * rows * columns..(band + 1) * rows * columns],
+            nodata,
+        );
+        if band == 0 {
+            r.configs.minimum = min;
+            r.configs.maximum = max;
+        }
+        statistics.push(json!({
+            "band": band,
+            "minimum": min,
+            "maximum": max,
+            "mean": mean,
+            "stdev": stdev,
+            "valid_cells": n,
+        }));
+    }
+    if r.configs.display_min == f64::INFINITY {
+        r.configs.display_min = r.configs.minimum;
+    }
+    if r.configs.display_max == f64::NEG_INFINITY {
+        r.configs.display_max = r.configs.maximum;
+    }
+    if r.configs.palette_nonlinearity < 0.0 {
+        r.configs.palette_nonlinearity = 1.0;
+    }
+
+    // build the pyramid; overviews are only meaningful for north-up square grids
+    let mut levels = vec![(rows, columns, 1usize, Arc::new(r.data.clone()))];
+    if r.is_north_up_square_grid() {
+        let nearest = r.configs.photometric_interp != PhotometricInterpretation::Continuous
+            && r.configs.photometric_interp != PhotometricInterpretation::Unknown;
+        loop {
+            let (rows, columns, factor, ref data) = levels[levels.len() - 1];
+            if rows <= CHUNK_SIZE && columns <= CHUNK_SIZE {
+                break;
+            }
+            let (r2, c2, d2) = downsample(data, rows, columns, bands, nodata, nearest);
+            levels.push((r2, c2, factor * 2, Arc::new(d2)));
+        }
+    }
+
+    let compression_level = match r.configs.compression {
+        RasterCompression::None => None,
+        _ => Some(r.configs.compression_level.max(1).min(9) as u32),
+    };
+    let mut offset = PREAMBLE_SIZE as u64;
+    let mut chunks = vec![];
+    let mut level_entries = vec![];
+    for (rows, columns, factor, data) in &levels {
+        let level_chunks =
+            encode_chunks(data, *rows, *columns, bands, data_type, compression_level)?;
+        let mut index = vec![];
+        for c in &level_chunks {
+            index.push(json!([offset, c.len()]));
+            offset += c.len() as u64;
+        }
+        level_entries.push(json!({
+            "factor": factor,
+            "rows": rows,
+            "columns": columns,
+            "chunks": index,
+        }));
+        chunks.extend(level_chunks);
+    }
+
+    let compression = if compression_level.is_some() {
+        "deflate"
+    } else {
+        "none"
+    };
+    let crs_wkt2 = match r.get_crs() {
+        Some(crs) => crs.to_wkt2(),
+        None => String::new(),
+    };
+    let header = json!({
+        "format": "WhiteboxRaster",
+        "version": VERSION,
+        "rows": rows,
+        "columns": columns,
+        "bands": bands,
+        "north": r.configs.north,
+        "south": r.configs.south,
+        "east": r.configs.east,
+        "west": r.configs.west,
+        "resolution_x": r.configs.resolution_x,
+        "resolution_y": r.configs.resolution_y,
+        "grid_type": format!("{}", r.configs.grid_type),
+        "rotation": r.configs.rotation,
+        "data_type": format!("{:?}", data_type),
+        "nodata": nodata,
+        "photometric_interp": format!("{:?}", r.configs.photometric_interp),
+        "palette": r.configs.palette,
+        "palette_nonlinearity": r.configs.palette_nonlinearity,
+        "display_min": r.configs.display_min,
+        "display_max": r.configs.display_max,
+        "z_units": r.configs.z_units,
+        "xy_units": r.configs.xy_units,
+        "epsg": r.configs.epsg_code,
+        "projection": r.configs.projection,
+        "crs_wkt": r.configs.coordinate_ref_system_wkt,
+        "crs_wkt2": crs_wkt2,
+        "metadata": r.configs.metadata,
+        "chunk_size": CHUNK_SIZE,
+        "compression": compression,
+        "shuffle": true,
+        "statistics": statistics,
+        "levels": level_entries,
+    });
+
+    let mut writer = create_file(&r.file_name)?;
+    writer.write_all(SIGNATURE)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[0u8; 2])?;
+    writer.write_all(&offset.to_le_bytes())?;
+    for c in &chunks {
+        writer.write_all(c)?;
+    }
+    writer.write_all(header.to_string().as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn invalid_data(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+fn parse_header(bytes: &[u8]) -> Result<Value, Error> {
+    if bytes.len() < PREAMBLE_SIZE || &bytes[0..4] != SIGNATURE {
+        return Err(invalid_data("The file is not a Whitebox v2 (.wbr) raster."));
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version > VERSION {
+        return Err(invalid_data(&format!(
+            "Whitebox raster version {} is not supported by this version of WhiteboxTools.",
+            version
+        )));
+    }
+    let mut offset = [0u8; 8];
+    offset.copy_from_slice(&bytes[8..16]);
+    let offset = u64::from_le_bytes(offset) as usize;
+    if offset < PREAMBLE_SIZE || offset > bytes.len() {
+        return Err(invalid_data("The raster header offset is invalid."));
+    }
+    serde_json::from_slice(&bytes[offset..])
+        .map_err(|e| invalid_data(&format!("Error reading the raster header: {}", e)))
+}
+
+fn header_to_configs(header: &Value, configs: &mut RasterConfigs) -> Result<(), Error> {
+    let num = |key: &str, default: f64| -> f64 { header[key].as_f64().unwrap_or(default) };
+    let text = |key: &str| -> String { header[key].as_str().unwrap_or("").to_string() };
+    configs.rows = header["rows"].as_u64().unwrap_or(0) as usize;
+    configs.columns = header["columns"].as_u64().unwrap_or(0) as usize;
+    configs.bands = header["bands"].as_u64().unwrap_or(1) as usize;
+    configs.north = num("north", 0f64);
+    configs.south = num("south", 0f64);
+    configs.east = num("east", 0f64);
+    configs.west = num("west", 0f64);
+    configs.resolution_x = num("resolution_x", 1f64);
+    configs.resolution_y = num("resolution_y", 1f64);
+    configs.grid_type = GridType::from_str(&text("grid_type")).unwrap_or(GridType::Square);
+    configs.rotation = num("rotation", 0f64);
+    configs.data_type = match data_type_from_str(&text("data_type")) {
+        Some(dt) => dt,
+        None => return Err(invalid_data("The raster data type is unknown.")),
+    };
+    configs.nodata = num("nodata", -32768f64);
+    configs.photometric_interp = match text("photometric_interp").to_lowercase().as_str() {
+        "continuous" => PhotometricInterpretation::Continuous,
+        "categorical" => PhotometricInterpretation::Categorical,
+        "boolean" => PhotometricInterpretation::Boolean,
+        "rgb" => PhotometricInterpretation::RGB,
+        "paletted" => PhotometricInterpretation::Paletted,
+        _ => PhotometricInterpretation::Unknown,
+    };
+    if configs.photometric_interp == PhotometricInterpretation::RGB
+        && configs.data_type == DataType::U32
+    {
+        configs.data_type = DataType::RGBA32;
+    }
+    configs.palette = text("palette");
+    configs.palette_nonlinearity = num("palette_nonlinearity", 1f64);
+    configs.display_min = num("display_min", f64::INFINITY);
+    configs.display_max = num("display_max", f64::NEG_INFINITY);
+    configs.z_units = text("z_units");
+    configs.xy_units = text("xy_units");
+    configs.epsg_code = header["epsg"].as_u64().unwrap_or(0) as u16;
+    configs.projection = text("projection");
+    configs.coordinate_ref_system_wkt = text("crs_wkt");
+    if let Some(entries) = header["metadata"].as_array() {
+        for m in entries {
+            if let Some(s) = m.as_str() {
+                configs.metadata.push(s.to_string());
+            }
+        }
+    }
+    configs.minimum = header["statistics"][0]["minimum"]
+        .as_f64()
+        .unwrap_or(f64::INFINITY);
+    configs.maximum = header["statistics"][0]["maximum"]
+        .as_f64()
+        .unwrap_or(f64::NEG_INFINITY);
+    if header["compression"].as_str() == Some("deflate") {
+        configs.compression = RasterCompression::Deflate;
+    }
+    Ok(())
+}
+
+/// The type in which values are stored; packed colour types are stored as 32-bit
+/// integers and unknown types as doubles.
+fn storage_type(data_type: DataType) -> DataType {
+    match data_type {
+        DataType::RGB24 | DataType::RGBA32 => DataType::U32,
+        DataType::RGB48 | DataType::Unknown => DataType::F64,
+        dt => dt,
+    }
+}
+
+fn data_type_from_str(s: &str) -> Option<DataType> {
+    Some(match s {
+        "F64" => DataType::F64,
+        "F32" => DataType::F32,
+        "I64" => DataType::I64,
+        "I32" => DataType::I32,
+        "I16" => DataType::I16,
+        "I8" => DataType::I8,
+        "U64" => DataType::U64,
+        "U32" => DataType::U32,
+        "U16" => DataType::U16,
+        "U8" => DataType::U8,
+        _ => return None,
+    })
+}
+
+/// Returns the minimum, maximum, mean, standard deviation, and number of valid cells of
+/// a band.
+fn band_statistics(values: &[f64], nodata: f64) -> (f64, f64, f64, f64, usize) {
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut sum, mut sq_sum, mut n) = (0f64, 0f64, 0usize);
+    for &v in values {
+        if v != nodata && !v.is_nan() {
+            min = min.min(v);
+            max = max.max(v);
+            sum += v;
+            sq_sum += v * v;
+            n += 1;
+        }
+    }
+    if n == 0 {
+        return (min, max, f64::NAN, f64::NAN, 0);
+    }
+    let mean = sum / n as f64;
+    let variance = (sq_sum / n as f64 - mean * mean).max(0f64);
+    (min, max, mean, variance.sqrt(), n)
+}
+
+/// Halves the resolution of a grid. Each overview cell is the mean of the valid cells in
+/// the corresponding 2 x 2 block or, for categorical data, the first valid cell.
+fn downsample(
+    data: &[f64],
+    rows: usize,
+    columns: usize,
+    bands: usize,
+    nodata: f64,
+    nearest: bool,
+) -> (usize, usize, Vec<f64>) {
+    let (rows2, columns2) = ((rows + 1) / 2, (columns + 1) / 2);
+    let mut out = vec![nodata; bands * rows2 * columns2];
+    for band in 0..bands {
+        let plane = &data[band * rows * columns..(band + 1) * rows * columns];
+        for row in 0..rows2 {
+            for col in 0..columns2 {
+                let (mut sum, mut n) = (0f64, 0usize);
+                for r in 2 * row..(2 * row + 2).min(rows) {
+                    for c in 2 * col..(2 * col + 2).min(columns) {
+                        let v = plane[r * columns + c];
+                        if v != nodata && (n == 0 || !nearest) {
+                            sum += v;
+                            n += 1;
+                        }
+                    }
+                }
+                if n > 0 {
+                    out[band * rows2 * columns2 + row * columns2 + col] = sum / n as f64;
+                }
+            }
+        }
+    }
+    (rows2, columns2, out)
+}
+
+/// Encodes the chunks of a grid, in parallel, in band, chunk row, chunk column order.
+fn encode_chunks(
+    data: &Arc<Vec<f64>>,
+    rows: usize,
+    columns: usize,
+    bands: usize,
+    data_type: DataType,
+    compression_level: Option<u32>,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let chunk_rows = (rows + CHUNK_SIZE - 1) / CHUNK_SIZE;
+    let chunk_cols = (columns + CHUNK_SIZE - 1) / CHUNK_SIZE;
+    let num_chunks = bands * chunk_rows * chunk_cols;
+    let num_procs = num_cpus::get().min(num_chunks).max(1);
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let data = data.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for i in (0..num_chunks).filter(|i| i % num_procs == tid) {
+                let band = i / (chunk_rows * chunk_cols);
+                let (chunk_row, chunk_col) = ((i / chunk_cols) % chunk_rows, i % chunk_cols);
+                let (start_col, end_col) = (
+                    chunk_col * CHUNK_SIZE,
+                    ((chunk_col + 1) * CHUNK_SIZE).min(columns),
+                );
+                let mut values = Vec::with_capacity(CHUNK_SIZE * CHUNK_SIZE);
+                for row in chunk_row * CHUNK_SIZE..((chunk_row + 1) * CHUNK_SIZE).min(rows) {
+                    let start = band * rows * columns + row * columns;
+                    values.extend_from_slice(&data[start + start_col..start + end_col]);
+                }
+                let bytes = shuffle(
+                    &encode_values(&values, data_type),
+                    data_type.get_data_size(),
+                );
+                let result = match compression_level {
+                    Some(level) => deflate(&bytes, level),
+                    None => Ok(bytes),
+                };
+                tx.send((i, result)).unwrap();
+            }
+        });
+    }
+    let mut chunks: Vec<Result<Vec<u8>, Error>> = (0..num_chunks).map(|_| Ok(vec![])).collect();
+    for _ in 0..num_chunks {
+        let (i, result) = rx.recv().expect("Error receiving data from thread.");
+        chunks[i] = result;
+    }
+    chunks.into_iter().collect()
+}
+
+fn deflate(bytes: &[u8], level: u32) -> Result<Vec<u8>, Error> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::new(level));
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+fn inflate(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut decoder = Decoder::new(bytes)?;
+    let mut out = vec![];
+    decoder.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+fn encode_values(values: &[f64], data_type: DataType) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len() * data_type.get_data_size());
+    for &v in values {
+        match data_type {
+            DataType::F64 => bytes.extend_from_slice(&v.to_bits().to_le_bytes()),
+            DataType::F32 => bytes.extend_from_slice(&(v as f32).to_bits().to_le_bytes()),
+            DataType::I64 => bytes.extend_from_slice(&(v as i64).to_le_bytes()),
+            DataType::I32 => bytes.extend_from_slice(&(v as i32).to_le_bytes()),
+            DataType::I16 => bytes.extend_from_slice(&(v as i16).to_le_bytes()),
+            DataType::I8 => bytes.push(v as i8 as u8),
+            DataType::U64 => bytes.extend_from_slice(&(v as u64).to_le_bytes()),
+            DataType::U32 => bytes.extend_from_slice(&(v as u32).to_le_bytes()),
+            DataType::U16 => bytes.extend_from_slice(&(v as u16).to_le_bytes()),
+            _ => bytes.push(v as u8),
+        }
+    }
+    bytes
+}
+
+fn decode_values(bytes: &[u8], data_type: DataType) -> Vec<f64> {
+    let size = data_type.get_data_size();
+    let mut values = Vec::with_capacity(bytes.len() / size);
+    for b in bytes.chunks_exact(size) {
+        let mut buf = [0u8; 8];
+        buf[..size].copy_from_slice(b);
+        values.push(match data_type {
+            DataType::F64 => f64::from_bits(u64::from_le_bytes(buf)),
+            DataType::F32 => {
+                f32::from_bits(u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]])) as f64
+            }
+            DataType::I64 => i64::from_le_bytes(buf) as f64,
+            DataType::I32 => i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            DataType::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+            DataType::I8 => buf[0] as i8 as f64,
+            DataType::U64 => u64::from_le_bytes(buf) as f64,
+            DataType::U32 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            DataType::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+            _ => buf[0] as f64,
+        });
+    }
+    values
+}
+
+/// Groups the n-th bytes of each value together.
+fn shuffle(bytes: &[u8], size: usize) -> Vec<u8> {
+    if size < 2 {
+        return bytes.to_vec();
+    }
+    let n = bytes.len() / size;
+    let mut out = vec![0u8; bytes.len()];
+    for i in 0..n {
+        for j in 0..size {
+            out[j * n + i] = bytes[i * size + j];
+        }
+    }
+    out
+}
+
+fn unshuffle(bytes: &[u8], data_type: DataType) -> Vec<u8> {
+    let size = data_type.get_data_size();
+    if size < 2 {
+        return bytes.to_vec();
+    }
+    let n = bytes.len() / size;
+    let mut out = vec![0u8; bytes.len()];
+    for i in 0..n {
+        for j in 0..size {
+            out[i * size + j] = bytes[j * n + i];
+        }
+    }
+    out
+}
@@ -0,0 +1,587 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Output of triangulated surface meshes in the Wavefront OBJ, Stanford PLY (binary),
+STL (binary), glTF (.gltf and .glb), and Cesium 3D Tiles (a tileset.json and a single
+Batched 3D Model tile) formats. Coordinates are written in the coordinate reference system
+of the input data, except for the STL and glTF-based formats, which store single-precision
+vertex positions relative to the mesh.
+*/
+
+use crate::spatial_ref_system::{CoordinateReferenceSystem, Ellipsoid};
+use serde_json::{json, Value};
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path::Path;
+
+/// A triangulated surface mesh with optional per-vertex colours. Triangles are stored as
+/// triples of vertex indices ordered counter-clockwise when viewed from above.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub vertices: Vec<[f64; 3]>,
+    /// Either empty or one red-green-blue colour per vertex.
+    pub colours: Vec<[u8; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// The mesh file formats supported by `Mesh::write`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeshFormat {
+    Obj,
+    Ply,
+    Stl,
+    Gltf,
+    Glb,
+    Tiles3d,
+}
+
+impl MeshFormat {
+    /// Returns the mesh format implied by a file name's extension; 3D Tiles outputs are
+    /// identified by a .json (i.e. tileset.json) extension.
+    pub fn from_file_name(file_name: &str) -> Option<MeshFormat> {
+        let f = file_name.to_lowercase();
+        if f.ends_with(".obj") {
+            Some(MeshFormat::Obj)
+        } else if f.ends_with(".ply") {
+            Some(MeshFormat::Ply)
+        } else if f.ends_with(".stl") {
+            Some(MeshFormat::Stl)
+        } else if f.ends_with(".gltf") {
+            Some(MeshFormat::Gltf)
+        } else if f.ends_with(".glb") {
+            Some(MeshFormat::Glb)
+        } else if f.ends_with(".json") {
+            Some(MeshFormat::Tiles3d)
+        } else {
+            None
+        }
+    }
+}
+
+impl Mesh {
+    pub fn new() -> Mesh {
+        Mesh {
+            ..Default::default()
+        }
+    }
+
+    /// Adds a vertex, returning its index.
+    pub fn add_vertex(&mut self, x: f64, y: f64, z: f64) -> u32 {
+        self.vertices.push([x, y, z]);
+        (self.vertices.len() - 1) as u32
+    }
+
+    pub fn add_triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.triangles.push([a, b, c]);
+    }
+
+    pub fn has_colours(&self) -> bool {
+        !self.colours.is_empty() && self.colours.len() == self.vertices.len()
+    }
+
+    /// Returns the minimum and maximum x, y, and z coordinates of the vertices.
+    pub fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for v in &self.vertices {
+            for k in 0..3 {
+                min[k] = min[k].min(v[k]);
+                max[k] = max[k].max(v[k]);
+            }
+        }
+        (min, max)
+    }
+
+    /// Returns the centre of the vertices' bounding box.
+    pub fn centre(&self) -> [f64; 3] {
+        let (min, max) = self.bounds();
+        [
+            (min[0] + max[0]) / 2f64,
+            (min[1] + max[1]) / 2f64,
+            (min[2] + max[2]) / 2f64,
+        ]
+    }
+
+    /// Returns the area-weighted unit normal of each vertex, calculated from the given vertex
+    /// positions, which must correspond to the mesh's vertices.
+    fn vertex_normals(&self, positions: &[[f64; 3]]) -> Vec<[f64; 3]> {
+        let mut normals = vec![[0f64; 3]; positions.len()];
+        for t in &self.triangles {
+            let (a, b, c) = (
+                positions[t[0] as usize],
+                positions[t[1] as usize],
+                positions[t[2] as usize],
+            );
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            for &i in t {
+                for k in 0..3 {
+                    normals[i as usize][k] += n[k];
+                }
+            }
+        }
+        for n in normals.iter_mut() {
+            let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if len > 0f64 {
+                for k in 0..3 {
+                    n[k] /= len;
+                }
+            } else {
+                *n = [0f64, 0f64, 1f64];
+            }
+        }
+        normals
+    }
+
+    /// Writes the mesh in the format implied by the file name's extension. The coordinate
+    /// reference system, if known, is used to georeference 3D Tiles outputs.
+    pub fn write(
+        &self,
+        file_name: &str,
+        crs: Option<&CoordinateReferenceSystem>,
+    ) -> Result<(), Error> {
+        match MeshFormat::from_file_name(file_name) {
+            Some(MeshFormat::Obj) => self.write_obj(file_name),
+            Some(MeshFormat::Ply) => self.write_ply(file_name),
+            Some(MeshFormat::Stl) => self.write_stl(file_name),
+            Some(MeshFormat::Gltf) => self.write_gltf(file_name),
+            Some(MeshFormat::Glb) => self.write_glb(file_name),
+            Some(MeshFormat::Tiles3d) => self.write_3d_tiles(file_name, crs),
+            None => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unsupported mesh format; the output file must have a .obj, .ply, .stl, .gltf, .glb, or .json (3D Tiles tileset) extension.",
+            )),
+        }
+    }
+
+    /// Writes the mesh as a Wavefront OBJ file. Vertex colours, if present, are written
+    /// using the widely supported 'v x y z r g b' extension.
+    pub fn write_obj(&self, file_name: &str) -> Result<(), Error> {
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(b"# Created by WhiteboxTools\n")?;
+        let has_colours = self.has_colours();
+        for (i, v) in self.vertices.iter().enumerate() {
+            if has_colours {
+                let c = self.colours[i];
+                writer.write_all(
+                    format!(
+                        "v {} {} {} {:.4} {:.4} {:.4}\n",
+                        v[0],
+                        v[1],
+                        v[2],
+                        c[0] as f64 / 255f64,
+                        c[1] as f64 / 255f64,
+                        c[2] as f64 / 255f64
+                    )
+                    .as_bytes(),
+                )?;
+            } else {
+                writer.write_all(format!("v {} {} {}\n", v[0], v[1], v[2]).as_bytes())?;
+            }
+        }
+        for t in &self.triangles {
+            writer.write_all(format!("f {} {} {}\n", t[0] + 1, t[1] + 1, t[2] + 1).as_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the mesh as a binary (little-endian) Stanford PLY file, with double-precision
+    /// vertex coordinates.
+    pub fn write_ply(&self, file_name: &str) -> Result<(), Error> {
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        let has_colours = self.has_colours();
+        let mut header = String::from(
+            "ply\nformat binary_little_endian 1.0\ncomment Created by WhiteboxTools\n",
+        );
+        header.push_str(&format!("element vertex {}\n", self.vertices.len()));
+        header.push_str("property double x\nproperty double y\nproperty double z\n");
+        if has_colours {
+            header.push_str("property uchar red\nproperty uchar green\nproperty uchar blue\n");
+        }
+        header.push_str(&format!("element face {}\n", self.triangles.len()));
+        header.push_str("property list uchar int vertex_indices\nend_header\n");
+        writer.write_all(header.as_bytes())?;
+        for (i, v) in self.vertices.iter().enumerate() {
+            for k in 0..3 {
+                writer.write_all(&v[k].to_le_bytes())?;
+            }
+            if has_colours {
+                writer.write_all(&self.colours[i])?;
+            }
+        }
+        for t in &self.triangles {
+            writer.write_all(&[3u8])?;
+            for k in 0..3 {
+                writer.write_all(&(t[k] as i32).to_le_bytes())?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the mesh as a binary STL file. Because STL coordinates are single-precision,
+    /// vertex positions are written relative to the minimum corner of the mesh's bounding box,
+    /// which also places the model at the origin of the build plate for 3D printing. STL files
+    /// do not store colours.
+    pub fn write_stl(&self, file_name: &str) -> Result<(), Error> {
+        let (min, _) = self.bounds();
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        let mut header = [b' '; 80];
+        let text = b"Created by WhiteboxTools";
+        header[..text.len()].copy_from_slice(text);
+        writer.write_all(&header)?;
+        writer.write_all(&(self.triangles.len() as u32).to_le_bytes())?;
+        for t in &self.triangles {
+            let p: Vec<[f64; 3]> = t
+                .iter()
+                .map(|&i| {
+                    let v = self.vertices[i as usize];
+                    [v[0] - min[0], v[1] - min[1], v[2] - min[2]]
+                })
+                .collect();
+            let u = [p[1][0] - p[0][0], p[1][1] - p[0][1], p[1][2] - p[0][2]];
+            let v = [p[2][0] - p[0][0], p[2][1] - p[0][1], p[2][2] - p[0][2]];
+            let mut n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if len > 0f64 {
+                for k in 0..3 {
+                    n[k] /= len;
+                }
+            }
+            for k in 0..3 {
+                writer.write_all(&(n[k] as f32).to_le_bytes())?;
+            }
+            for vertex in &p {
+                for k in 0..3 {
+                    writer.write_all(&(vertex[k] as f32).to_le_bytes())?;
+                }
+            }
+            writer.write_all(&0u16.to_le_bytes())?; // attribute byte count
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the mesh as a binary glTF (GLB) file. Vertex positions are written relative
+    /// to the centre of the mesh, with glTF's y-up axis convention.
+    pub fn write_glb(&self, file_name: &str) -> Result<(), Error> {
+        let centre = self.centre();
+        let local: Vec<[f64; 3]> = self
+            .vertices
+            .iter()
+            .map(|v| [v[0] - centre[0], v[1] - centre[1], v[2] - centre[2]])
+            .collect();
+        let mut f = File::create(file_name)?;
+        f.write_all(&self.glb_bytes(&local))?;
+        Ok(())
+    }
+
+    /// Writes the mesh as a glTF (.gltf) file, with the binary buffer embedded as a base64
+    /// data URI. Vertex positions are written relative to the centre of the mesh, with glTF's
+    /// y-up axis convention.
+    pub fn write_gltf(&self, file_name: &str) -> Result<(), Error> {
+        let centre = self.centre();
+        let local: Vec<[f64; 3]> = self
+            .vertices
+            .iter()
+            .map(|v| [v[0] - centre[0], v[1] - centre[1], v[2] - centre[2]])
+            .collect();
+        let (mut gltf, bin) = self.gltf_document(&local);
+        gltf["buffers"][0]["uri"] = json!(format!(
+            "data:application/octet-stream;base64,{}",
+            base64_encode(&bin)
+        ));
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(gltf.to_string().as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the glTF document describing the mesh and its binary buffer, given vertex
+    /// positions in a local, z-up frame (i.e. east, north, up). The positions are converted
+    /// to glTF's y-up frame.
+    fn gltf_document(&self, local: &[[f64; 3]]) -> (Value, Vec<u8>) {
+        let normals = self.vertex_normals(local);
+        let has_colours = self.has_colours();
+        let num_vertices = local.len();
+
+        // the binary buffer: positions, normals, colours, and indices
+        let mut bin: Vec<u8> = Vec::with_capacity(num_vertices * 36 + self.triangles.len() * 12);
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for p in local {
+            let v = [p[0] as f32, p[2] as f32, -p[1] as f32];
+            for k in 0..3 {
+                min[k] = min[k].min(v[k]);
+                max[k] = max[k].max(v[k]);
+                bin.extend_from_slice(&v[k].to_le_bytes());
+            }
+        }
+        let normals_offset = bin.len();
+        for n in &normals {
+            for &val in &[n[0] as f32, n[2] as f32, -n[1] as f32] {
+                bin.extend_from_slice(&val.to_le_bytes());
+            }
+        }
+        let colours_offset = bin.len();
+        if has_colours {
+            for c in &self.colours {
+                for k in 0..3 {
+                    // glTF vertex colours are linear, rather than sRGB, values
+                    let val = (c[k] as f32 / 255f32).powf(2.2);
+                    bin.extend_from_slice(&val.to_le_bytes());
+                }
+            }
+        }
+        let indices_offset = bin.len();
+        for t in &self.triangles {
+            for k in 0..3 {
+                bin.extend_from_slice(&t[k].to_le_bytes());
+            }
+        }
+        let bin_length = bin.len();
+        while bin.len() % 4 != 0 {
+            bin.push(0u8);
+        }
+
+        let mut attributes = json!({ "POSITION": 0, "NORMAL": 1 });
+        let mut buffer_views = vec![
+            json!({ "buffer": 0, "byteOffset": 0, "byteLength": normals_offset, "target": 34962 }),
+            json!({ "buffer": 0, "byteOffset": normals_offset, "byteLength": colours_offset - normals_offset, "target": 34962 }),
+        ];
+        let mut accessors = vec![
+            json!({ "bufferView": 0, "componentType": 5126, "count": num_vertices, "type": "VEC3", "min": min, "max": max }),
+            json!({ "bufferView": 1, "componentType": 5126, "count": num_vertices, "type": "VEC3" }),
+        ];
+        if has_colours {
+            attributes["COLOR_0"] = json!(accessors.len());
+            accessors.push(json!({ "bufferView": buffer_views.len(), "componentType": 5126, "count": num_vertices, "type": "VEC3" }));
+            buffer_views.push(json!({ "buffer": 0, "byteOffset": colours_offset, "byteLength": indices_offset - colours_offset, "target": 34962 }));
+        }
+        let indices = accessors.len();
+        accessors.push(json!({ "bufferView": buffer_views.len(), "componentType": 5125, "count": self.triangles.len() * 3, "type": "SCALAR" }));
+        buffer_views.push(json!({ "buffer": 0, "byteOffset": indices_offset, "byteLength": bin_length - indices_offset, "target": 34963 }));
+        let material = if has_colours {
+            json!({ "pbrMetallicRoughness": { "baseColorFactor": [1.0, 1.0, 1.0, 1.0], "metallicFactor": 0.0, "roughnessFactor": 1.0 }, "doubleSided": true })
+        } else {
+            json!({ "pbrMetallicRoughness": { "baseColorFactor": [0.8, 0.8, 0.8, 1.0], "metallicFactor": 0.0, "roughnessFactor": 1.0 }, "doubleSided": true })
+        };
+        let gltf = json!({
+            "asset": { "version": "2.0", "generator": "WhiteboxTools" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "mesh": 0 }],
+            "meshes": [{ "primitives": [{ "attributes": attributes, "indices": indices, "material": 0, "mode": 4 }] }],
+            "materials": [material],
+            "accessors": accessors,
+            "bufferViews": buffer_views,
+            "buffers": [{ "byteLength": bin.len() }],
+        });
+        (gltf, bin)
+    }
+
+    /// Returns the binary glTF (GLB) encoding of the mesh, given vertex positions in a local,
+    /// z-up frame.
+    fn glb_bytes(&self, local: &[[f64; 3]]) -> Vec<u8> {
+        let (gltf, bin) = self.gltf_document(local);
+        let mut json_bytes = gltf.to_string().into_bytes();
+        while json_bytes.len() % 4 != 0 {
+            json_bytes.push(b' ');
+        }
+
+        let total_length = 12 + 8 + json_bytes.len() + 8 + bin.len();
+        let mut glb = Vec::with_capacity(total_length);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(total_length as u32).to_le_bytes());
+        glb.extend_from_slice(&(json_bytes.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json_bytes);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+        glb
+    }
+
+    /// Writes the mesh as a Cesium 3D Tiles tileset, consisting of the tileset file and a
+    /// single Batched 3D Model (.b3dm) tile of the same name, written to the same directory.
+    /// When the coordinate reference system is known, the tile is placed on the WGS84
+    /// ellipsoid, with vertex positions in the local east-north-up frame of the mesh centre;
+    /// otherwise the tileset is written in the mesh's local coordinates.
+    pub fn write_3d_tiles(
+        &self,
+        file_name: &str,
+        crs: Option<&CoordinateReferenceSystem>,
+    ) -> Result<(), Error> {
+        if self.vertices.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The mesh does not contain any vertices.",
+            ));
+        }
+        let path = Path::new(file_name);
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("tileset")
+            .to_string();
+        let tile_name = format!("{}.b3dm", stem);
+        let tile_file = match path.parent() {
+            Some(dir) => dir.join(&tile_name),
+            None => Path::new(&tile_name).to_path_buf(),
+        };
+
+        let centre = self.centre();
+        let (min, max) = self.bounds();
+        let (local, transform, bounding_volume) = match crs {
+            Some(crs) => {
+                let ellipsoid = Ellipsoid::wgs84();
+                let (lon0, lat0) = crs.unproject(centre[0], centre[1]);
+                let (lon0, lat0) = (lon0.to_radians(), lat0.to_radians());
+                let origin = ellipsoid.geodetic_to_geocentric(lon0, lat0, centre[2]);
+                let east = [-lon0.sin(), lon0.cos(), 0f64];
+                let north = [
+                    -lat0.sin() * lon0.cos(),
+                    -lat0.sin() * lon0.sin(),
+                    lat0.cos(),
+                ];
+                let up = [lat0.cos() * lon0.cos(), lat0.cos() * lon0.sin(), lat0.sin()];
+                let mut region = [
+                    f64::INFINITY,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::NEG_INFINITY,
+                ];
+                let local: Vec<[f64; 3]> = self
+                    .vertices
+                    .iter()
+                    .map(|v| {
+                        let (lon, lat) = crs.unproject(v[0], v[1]);
+                        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+                        region[0] = region[0].min(lon);
+                        region[1] = region[1].min(lat);
+                        region[2] = region[2].max(lon);
+                        region[3] = region[3].max(lat);
+                        let p = ellipsoid.geodetic_to_geocentric(lon, lat, v[2]);
+                        let d = [p.0 - origin.0, p.1 - origin.1, p.2 - origin.2];
+                        [
+                            d[0] * east[0] + d[1] * east[1] + d[2] * east[2],
+                            d[0] * north[0] + d[1] * north[1] + d[2] * north[2],
+                            d[0] * up[0] + d[1] * up[1] + d[2] * up[2],
+                        ]
+                    })
+                    .collect();
+                let transform = vec![
+                    east[0], east[1], east[2], 0f64, north[0], north[1], north[2], 0f64, up[0],
+                    up[1], up[2], 0f64, origin.0, origin.1, origin.2, 1f64,
+                ];
+                let volume = json!({ "region": [region[0], region[1], region[2], region[3], min[2], max[2]] });
+                (local, Some(transform), volume)
+            }
+            None => {
+                let local: Vec<[f64; 3]> = self
+                    .vertices
+                    .iter()
+                    .map(|v| [v[0] - centre[0], v[1] - centre[1], v[2] - centre[2]])
+                    .collect();
+                let half = [
+                    (max[0] - min[0]) / 2f64,
+                    (max[1] - min[1]) / 2f64,
+                    (max[2] - min[2]) / 2f64,
+                ];
+                let volume = json!({ "box": [0.0, 0.0, 0.0, half[0], 0.0, 0.0, 0.0, half[1], 0.0, 0.0, 0.0, half[2]] });
+                (local, None, volume)
+            }
+        };
+
+        // the Batched 3D Model tile
+        let glb = self.glb_bytes(&local);
+        let mut feature_table = json!({ "BATCH_LENGTH": 0 }).to_string().into_bytes();
+        while (28 + feature_table.len()) % 8 != 0 {
+            feature_table.push(b' ');
+        }
+        let byte_length = 28 + feature_table.len() + glb.len();
+        let mut b3dm = Vec::with_capacity(byte_length);
+        b3dm.extend_from_slice(b"b3dm");
+        b3dm.extend_from_slice(&1u32.to_le_bytes());
+        b3dm.extend_from_slice(&(byte_length as u32).to_le_bytes());
+        b3dm.extend_from_slice(&(feature_table.len() as u32).to_le_bytes());
+        for _ in 0..3 {
+            // the feature table binary and batch table lengths
+            b3dm.extend_from_slice(&0u32.to_le_bytes());
+        }
+        b3dm.extend_from_slice(&feature_table);
+        b3dm.extend_from_slice(&glb);
+        let mut f = File::create(&tile_file)?;
+        f.write_all(&b3dm)?;
+
+        // the tileset
+        let diagonal = ((max[0] - min[0]).powi(2) + (max[1] - min[1]).powi(2)).sqrt();
+        let mut root = json!({
+            "boundingVolume": bounding_volume,
+            "geometricError": 0.0,
+            "refine": "ADD",
+            "content": { "uri": tile_name },
+        });
+        if let Some(transform) = transform {
+            root["transform"] = json!(transform);
+        }
+        let tileset = json!({
+            "asset": { "version": "1.0", "generator": "WhiteboxTools" },
+            "geometricError": diagonal,
+            "root": root,
+        });
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(serde_json::to_string_pretty(&tileset)?.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Encodes bytes as standard, padded base64.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut s = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            if chunk.len() > 1 { chunk[1] } else { 0 },
+            if chunk.len() > 2 { chunk[2] } else { 0 },
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        s.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        s.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        s.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        s.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    s
+}
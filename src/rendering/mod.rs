@@ -1,10 +1,12 @@
 // private sub-module defined in other files
 mod histogram;
 mod line_graph;
+mod mesh;
 mod scattergram;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::histogram::Histogram;
 pub use self::line_graph::LineGraph;
+pub use self::mesh::{Mesh, MeshFormat};
 pub use self::scattergram::Scattergram;
 pub mod html;
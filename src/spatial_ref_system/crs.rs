@@ -0,0 +1,1176 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: A small coordinate reference system (CRS) engine. CRSs are built either
+from an EPSG code (via the built-in EPSG-to-WKT database) or directly from an
+ESRI-style WKT string. The supported map projections are Transverse Mercator
+(including UTM), Lambert Conformal Conic (1SP and 2SP), polar and oblique
+Stereographic, and Mercator, all on the ellipsoid. Transformations between
+CRSs that are based on different ellipsoids are carried out through geocentric
+coordinates; no datum-shift parameters are applied, which is appropriate for
+WGS84/NAD83-type datum pairs but will leave a residual (typically metres to
+hundreds of metres) for older local datums.
+*/
+
+use super::esri_wkt_from_epsg;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+use std::io::{Error, ErrorKind};
+
+/// A reference ellipsoid, described by its semi-major axis (`a`, in metres) and
+/// inverse flattening (`inv_f`). An inverse flattening of zero denotes a sphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipsoid {
+    pub a: f64,
+    pub inv_f: f64,
+}
+
+impl Ellipsoid {
+    pub fn new(a: f64, inv_f: f64) -> Ellipsoid {
+        Ellipsoid { a: a, inv_f: inv_f }
+    }
+
+    pub fn wgs84() -> Ellipsoid {
+        Ellipsoid::new(6378137.0, 298.257223563)
+    }
+
+    /// Returns the flattening.
+    pub fn f(&self) -> f64 {
+        if self.inv_f == 0f64 {
+            0f64
+        } else {
+            1f64 / self.inv_f
+        }
+    }
+
+    /// Returns the squared first eccentricity.
+    pub fn e2(&self) -> f64 {
+        let f = self.f();
+        f * (2f64 - f)
+    }
+
+    /// Returns the first eccentricity.
+    pub fn e(&self) -> f64 {
+        self.e2().sqrt()
+    }
+
+    /// Converts geodetic coordinates (radians, metres) into geocentric X, Y, Z.
+    pub fn geodetic_to_geocentric(&self, lon: f64, lat: f64, h: f64) -> (f64, f64, f64) {
+        let e2 = self.e2();
+        let n = self.a / (1f64 - e2 * lat.sin() * lat.sin()).sqrt();
+        (
+            (n + h) * lat.cos() * lon.cos(),
+            (n + h) * lat.cos() * lon.sin(),
+            (n * (1f64 - e2) + h) * lat.sin(),
+        )
+    }
+
+    /// Converts geocentric X, Y, Z into geodetic coordinates (radians, metres).
+    pub fn geocentric_to_geodetic(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let e2 = self.e2();
+        let lon = y.atan2(x);
+        let p = (x * x + y * y).sqrt();
+        let mut lat = z.atan2(p * (1f64 - e2));
+        let mut h = 0f64;
+        for _ in 0..10 {
+            let n = self.a / (1f64 - e2 * lat.sin() * lat.sin()).sqrt();
+            h = if lat.cos().abs() > 1e-10 {
+                p / lat.cos() - n
+            } else {
+                z.abs() - n * (1f64 - e2)
+            };
+            let new_lat = z.atan2(p * (1f64 - e2 * n / (n + h)));
+            if (new_lat - lat).abs() < 1e-14 {
+                lat = new_lat;
+                break;
+            }
+            lat = new_lat;
+        }
+        (lon, lat, h)
+    }
+
+    /// Meridional arc length from the equator to latitude `phi` (radians).
+    fn meridian_distance(&self, phi: f64) -> f64 {
+        let e2 = self.e2();
+        let e4 = e2 * e2;
+        let e6 = e4 * e2;
+        self.a
+            * ((1f64 - e2 / 4f64 - 3f64 * e4 / 64f64 - 5f64 * e6 / 256f64) * phi
+                - (3f64 * e2 / 8f64 + 3f64 * e4 / 32f64 + 45f64 * e6 / 1024f64)
+                    * (2f64 * phi).sin()
+                + (15f64 * e4 / 256f64 + 45f64 * e6 / 1024f64) * (4f64 * phi).sin()
+                - (35f64 * e6 / 3072f64) * (6f64 * phi).sin())
+    }
+
+    /// Snyder's `m` function (eq. 14-15).
+    fn m(&self, phi: f64) -> f64 {
+        phi.cos() / (1f64 - self.e2() * phi.sin() * phi.sin()).sqrt()
+    }
+
+    /// Snyder's `t` function (eq. 15-9).
+    fn t(&self, phi: f64) -> f64 {
+        let e = self.e();
+        let es = e * phi.sin();
+        (FRAC_PI_4 - phi / 2f64).tan() / ((1f64 - es) / (1f64 + es)).powf(e / 2f64)
+    }
+
+    /// Inverts Snyder's `t` function by iteration (eq. 7-9).
+    fn phi_from_t(&self, t: f64) -> f64 {
+        let e = self.e();
+        let mut phi = FRAC_PI_2 - 2f64 * t.atan();
+        for _ in 0..15 {
+            let es = e * phi.sin();
+            let new_phi =
+                FRAC_PI_2 - 2f64 * (t * ((1f64 - es) / (1f64 + es)).powf(e / 2f64)).atan();
+            if (new_phi - phi).abs() < 1e-12 {
+                return new_phi;
+            }
+            phi = new_phi;
+        }
+        phi
+    }
+
+    /// Conformal latitude (Snyder eq. 3-1).
+    fn conformal_lat(&self, phi: f64) -> f64 {
+        let e = self.e();
+        let es = e * phi.sin();
+        2f64 * ((FRAC_PI_4 + phi / 2f64).tan() * ((1f64 - es) / (1f64 + es)).powf(e / 2f64))
+            .atan()
+            - FRAC_PI_2
+    }
+
+    /// Inverts the conformal latitude by iteration (Snyder eq. 3-4).
+    fn phi_from_conformal_lat(&self, chi: f64) -> f64 {
+        let e = self.e();
+        let mut phi = chi;
+        for _ in 0..15 {
+            let es = e * phi.sin();
+            let new_phi = 2f64
+                * ((FRAC_PI_4 + chi / 2f64).tan() * ((1f64 + es) / (1f64 - es)).powf(e / 2f64))
+                    .atan()
+                - FRAC_PI_2;
+            if (new_phi - phi).abs() < 1e-12 {
+                return new_phi;
+            }
+            phi = new_phi;
+        }
+        phi
+    }
+}
+
+/// The map projections supported by the CRS engine. All angles are stored in
+/// radians and false origins in metres.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Projection {
+    Geographic,
+    TransverseMercator {
+        lon0: f64,
+        lat0: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    LambertConformalConic {
+        lon0: f64,
+        lat0: f64,
+        lat1: f64,
+        lat2: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    PolarStereographic {
+        lon0: f64,
+        lat_ts: f64,
+        k0: f64,
+        north: bool,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    ObliqueStereographic {
+        lon0: f64,
+        lat0: f64,
+        k0: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+    Mercator {
+        lon0: f64,
+        lat_ts: f64,
+        false_easting: f64,
+        false_northing: f64,
+    },
+}
+
+/// A coordinate reference system, i.e. an ellipsoid plus a map projection.
+///
+/// Examples:
+///
+/// ```
+/// let src = CoordinateReferenceSystem::from_epsg(4326)?;
+/// let dst = CoordinateReferenceSystem::from_epsg(32617)?;
+/// let (x, y) = src.transform_to(&dst, -80.5, 43.5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CoordinateReferenceSystem {
+    pub name: String,
+    pub epsg_code: u16,
+    pub ellipsoid: Ellipsoid,
+    pub projection: Projection,
+    /// Size of the linear unit in metres (projected CRSs only).
+    pub linear_unit: f64,
+    pub wkt: String,
+}
+
+impl CoordinateReferenceSystem {
+    /// Creates a CRS from an EPSG code using the built-in EPSG database.
+    pub fn from_epsg(code: u16) -> Result<CoordinateReferenceSystem, Error> {
+        let wkt = esri_wkt_from_epsg(code);
+        if wkt == "Unknown EPSG Code" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("EPSG code {} is not in the CRS database.", code),
+            ));
+        }
+        let mut crs = CoordinateReferenceSystem::from_wkt(&wkt)?;
+        crs.epsg_code = code;
+        Ok(crs)
+    }
+
+    /// Creates a CRS from an ESRI-style (i.e. *.prj) WKT string.
+    pub fn from_wkt(wkt: &str) -> Result<CoordinateReferenceSystem, Error> {
+        let wkt = wkt.trim();
+        let is_projected = wkt.to_uppercase().starts_with("PROJCS");
+        if !is_projected && !wkt.to_uppercase().starts_with("GEOGCS") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized coordinate reference system WKT.",
+            ));
+        }
+        let name = first_quoted_value(wkt).unwrap_or_default();
+        let spheroid = bracketed_values(wkt, "SPHEROID");
+        if spheroid.len() < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The WKT does not define a SPHEROID.",
+            ));
+        }
+        let ellipsoid = Ellipsoid::new(parse_num(&spheroid[1])?, parse_num(&spheroid[2])?);
+        let epsg_code = match bracketed_values_last(wkt, "AUTHORITY") {
+            Some(v) if v.len() > 1 => v[1].parse::<u16>().unwrap_or(0),
+            _ => 0,
+        };
+
+        if !is_projected {
+            return Ok(CoordinateReferenceSystem {
+                name: name,
+                epsg_code: epsg_code,
+                ellipsoid: ellipsoid,
+                projection: Projection::Geographic,
+                linear_unit: 1f64,
+                wkt: wkt.to_string(),
+            });
+        }
+
+        let projection_name = match bracketed_values(wkt, "PROJECTION").first() {
+            Some(s) => s.to_lowercase(),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The WKT does not define a PROJECTION.",
+                ))
+            }
+        };
+        let params = parameters(wkt);
+        let linear_unit = match bracketed_values_last(wkt, "UNIT") {
+            Some(v) if v.len() > 1 => parse_num(&v[1])?,
+            _ => 1f64,
+        };
+        let p = |key: &str, default: f64| -> f64 { *params.get(key).unwrap_or(&default) };
+        // false origins are given in the CRS's linear unit
+        let fe = p("false_easting", 0f64) * linear_unit;
+        let fn_ = p("false_northing", 0f64) * linear_unit;
+        let lon0 = p("central_meridian", p("longitude_of_origin", 0f64)).to_radians();
+        let lat0 = p("latitude_of_origin", 0f64).to_radians();
+        let k0 = p("scale_factor", 1f64);
+
+        let projection = if projection_name.contains("transverse_mercator")
+            || projection_name.contains("gauss_kruger")
+        {
+            Projection::TransverseMercator {
+                lon0: lon0,
+                lat0: lat0,
+                k0: k0,
+                false_easting: fe,
+                false_northing: fn_,
+            }
+        } else if projection_name.contains("lambert_conformal_conic") {
+            let lat1 = p("standard_parallel_1", lat0.to_degrees()).to_radians();
+            let lat2 = p("standard_parallel_2", lat1.to_degrees()).to_radians();
+            Projection::LambertConformalConic {
+                lon0: lon0,
+                lat0: lat0,
+                lat1: lat1,
+                lat2: lat2,
+                k0: k0,
+                false_easting: fe,
+                false_northing: fn_,
+            }
+        } else if projection_name.contains("stereographic") {
+            let lat_ts = p("standard_parallel_1", lat0.to_degrees()).to_radians();
+            let polar = projection_name.contains("pole")
+                || projection_name.contains("polar")
+                || (lat0.abs() - FRAC_PI_2).abs() < 1e-9;
+            if polar {
+                let north = if projection_name.contains("south") {
+                    false
+                } else if projection_name.contains("north") {
+                    true
+                } else {
+                    lat_ts >= 0f64 && lat0 >= 0f64
+                };
+                Projection::PolarStereographic {
+                    lon0: lon0,
+                    lat_ts: lat_ts,
+                    k0: k0,
+                    north: north,
+                    false_easting: fe,
+                    false_northing: fn_,
+                }
+            } else {
+                Projection::ObliqueStereographic {
+                    lon0: lon0,
+                    lat0: lat0,
+                    k0: k0,
+                    false_easting: fe,
+                    false_northing: fn_,
+                }
+            }
+        } else if projection_name == "mercator" || projection_name.starts_with("mercator_") {
+            Projection::Mercator {
+                lon0: lon0,
+                lat_ts: p("standard_parallel_1", 0f64).to_radians(),
+                false_easting: fe,
+                false_northing: fn_,
+            }
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unsupported map projection: {}", projection_name),
+            ));
+        };
+
+        Ok(CoordinateReferenceSystem {
+            name: name,
+            epsg_code: epsg_code,
+            ellipsoid: ellipsoid,
+            projection: projection,
+            linear_unit: linear_unit,
+            wkt: wkt.to_string(),
+        })
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        self.projection == Projection::Geographic
+    }
+
+    /// Returns true if the two CRSs are equivalent, i.e. they share an ellipsoid,
+    /// projection, and linear unit.
+    pub fn is_equivalent(&self, other: &CoordinateReferenceSystem) -> bool {
+        (self.ellipsoid.a - other.ellipsoid.a).abs() < 1e-6
+            && (self.ellipsoid.inv_f - other.ellipsoid.inv_f).abs() < 1e-6
+            && self.projection == other.projection
+            && (self.linear_unit - other.linear_unit).abs() < 1e-12
+    }
+
+    /// Converts coordinates in this CRS to geographic longitude and latitude
+    /// (decimal degrees) on this CRS's ellipsoid.
+    pub fn unproject(&self, x: f64, y: f64) -> (f64, f64) {
+        let el = &self.ellipsoid;
+        let (lon, lat) = match self.projection {
+            Projection::Geographic => return (x, y),
+            Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let x = x * self.linear_unit - false_easting;
+                let y = y * self.linear_unit - false_northing;
+                let a = el.a;
+                let e2 = el.e2();
+                let ep2 = e2 / (1f64 - e2);
+                let m = el.meridian_distance(lat0) + y / k0;
+                let mu = m
+                    / (a * (1f64
+                        - e2 / 4f64
+                        - 3f64 * e2 * e2 / 64f64
+                        - 5f64 * e2 * e2 * e2 / 256f64));
+                let e1 = (1f64 - (1f64 - e2).sqrt()) / (1f64 + (1f64 - e2).sqrt());
+                let phi1 = mu
+                    + (3f64 * e1 / 2f64 - 27f64 * e1.powi(3) / 32f64) * (2f64 * mu).sin()
+                    + (21f64 * e1 * e1 / 16f64 - 55f64 * e1.powi(4) / 32f64) * (4f64 * mu).sin()
+                    + (151f64 * e1.powi(3) / 96f64) * (6f64 * mu).sin()
+                    + (1097f64 * e1.powi(4) / 512f64) * (8f64 * mu).sin();
+                let (sin1, cos1, tan1) = (phi1.sin(), phi1.cos(), phi1.tan());
+                let c1 = ep2 * cos1 * cos1;
+                let t1 = tan1 * tan1;
+                let n1 = a / (1f64 - e2 * sin1 * sin1).sqrt();
+                let r1 = a * (1f64 - e2) / (1f64 - e2 * sin1 * sin1).powf(1.5);
+                let d = x / (n1 * k0);
+                let lat = phi1
+                    - (n1 * tan1 / r1)
+                        * (d * d / 2f64
+                            - (5f64 + 3f64 * t1 + 10f64 * c1 - 4f64 * c1 * c1 - 9f64 * ep2)
+                                * d.powi(4)
+                                / 24f64
+                            + (61f64 + 90f64 * t1 + 298f64 * c1 + 45f64 * t1 * t1
+                                - 252f64 * ep2
+                                - 3f64 * c1 * c1)
+                                * d.powi(6)
+                                / 720f64);
+                let lon = lon0
+                    + (d - (1f64 + 2f64 * t1 + c1) * d.powi(3) / 6f64
+                        + (5f64 - 2f64 * c1 + 28f64 * t1 - 3f64 * c1 * c1
+                            + 8f64 * ep2
+                            + 24f64 * t1 * t1)
+                            * d.powi(5)
+                            / 120f64)
+                        / cos1;
+                (lon, lat)
+            }
+            Projection::LambertConformalConic {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let x = x * self.linear_unit - false_easting;
+                let y = y * self.linear_unit - false_northing;
+                let (n, f, rho0) = lcc_constants(el, lat0, lat1, lat2, k0);
+                let sign = n.signum();
+                let rho = sign * (x * x + (rho0 - y) * (rho0 - y)).sqrt();
+                let theta = (sign * x).atan2(sign * (rho0 - y));
+                let lat = if rho == 0f64 {
+                    sign * FRAC_PI_2
+                } else {
+                    el.phi_from_t((rho / (el.a * k0 * f)).powf(1f64 / n))
+                };
+                (theta / n + lon0, lat)
+            }
+            Projection::PolarStereographic {
+                lon0,
+                lat_ts,
+                k0,
+                north,
+                false_easting,
+                false_northing,
+            } => {
+                let mut x = x * self.linear_unit - false_easting;
+                let mut y = y * self.linear_unit - false_northing;
+                let s = if north { 1f64 } else { -1f64 };
+                x *= s;
+                y *= s;
+                let rho = (x * x + y * y).sqrt();
+                let t = polar_stereographic_t_from_rho(el, rho, s * lat_ts, k0);
+                let lat = el.phi_from_t(t);
+                let lon = s * (s * lon0 + x.atan2(-y));
+                (lon, s * lat)
+            }
+            Projection::ObliqueStereographic {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let x = x * self.linear_unit - false_easting;
+                let y = y * self.linear_unit - false_northing;
+                let chi1 = el.conformal_lat(lat0);
+                let m1 = el.m(lat0);
+                let rho = (x * x + y * y).sqrt();
+                if rho < 1e-10 {
+                    (lon0, lat0)
+                } else {
+                    let ce = 2f64 * (rho * chi1.cos() / (2f64 * el.a * k0 * m1)).atan();
+                    let chi = (ce.cos() * chi1.sin() + y * ce.sin() * chi1.cos() / rho).asin();
+                    let lon = lon0
+                        + (x * ce.sin())
+                            .atan2(rho * chi1.cos() * ce.cos() - y * chi1.sin() * ce.sin());
+                    (lon, el.phi_from_conformal_lat(chi))
+                }
+            }
+            Projection::Mercator {
+                lon0,
+                lat_ts,
+                false_easting,
+                false_northing,
+            } => {
+                let x = x * self.linear_unit - false_easting;
+                let y = y * self.linear_unit - false_northing;
+                let k0 = el.m(lat_ts);
+                let lat = el.phi_from_t((-y / (el.a * k0)).exp());
+                (x / (el.a * k0) + lon0, lat)
+            }
+        };
+        (normalize_lon(lon).to_degrees(), lat.to_degrees())
+    }
+
+    /// Converts geographic longitude and latitude (decimal degrees, on this
+    /// CRS's ellipsoid) into coordinates in this CRS.
+    pub fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+        let el = &self.ellipsoid;
+        let lambda = lon.to_radians();
+        let phi = lat.to_radians();
+        let (x, y) = match self.projection {
+            Projection::Geographic => return (lon, lat),
+            Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let a = el.a;
+                let e2 = el.e2();
+                let ep2 = e2 / (1f64 - e2);
+                let (sin, cos, tan) = (phi.sin(), phi.cos(), phi.tan());
+                let n = a / (1f64 - e2 * sin * sin).sqrt();
+                let t = tan * tan;
+                let c = ep2 * cos * cos;
+                let aa = normalize_lon(lambda - lon0) * cos;
+                let m = el.meridian_distance(phi);
+                let m0 = el.meridian_distance(lat0);
+                let x = k0
+                    * n
+                    * (aa
+                        + (1f64 - t + c) * aa.powi(3) / 6f64
+                        + (5f64 - 18f64 * t + t * t + 72f64 * c - 58f64 * ep2) * aa.powi(5)
+                            / 120f64);
+                let y = k0
+                    * (m - m0
+                        + n * tan
+                            * (aa * aa / 2f64
+                                + (5f64 - t + 9f64 * c + 4f64 * c * c) * aa.powi(4) / 24f64
+                                + (61f64 - 58f64 * t + t * t + 600f64 * c - 330f64 * ep2)
+                                    * aa.powi(6)
+                                    / 720f64));
+                (x + false_easting, y + false_northing)
+            }
+            Projection::LambertConformalConic {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let (n, f, rho0) = lcc_constants(el, lat0, lat1, lat2, k0);
+                let rho = el.a * k0 * f * el.t(phi).powf(n);
+                let theta = n * normalize_lon(lambda - lon0);
+                (
+                    rho * theta.sin() + false_easting,
+                    rho0 - rho * theta.cos() + false_northing,
+                )
+            }
+            Projection::PolarStereographic {
+                lon0,
+                lat_ts,
+                k0,
+                north,
+                false_easting,
+                false_northing,
+            } => {
+                let s = if north { 1f64 } else { -1f64 };
+                let t = el.t(s * phi);
+                let rho = polar_stereographic_rho_from_t(el, t, s * lat_ts, k0);
+                let dl = s * normalize_lon(lambda - lon0);
+                (
+                    s * rho * dl.sin() + false_easting,
+                    -s * rho * dl.cos() + false_northing,
+                )
+            }
+            Projection::ObliqueStereographic {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => {
+                let chi1 = el.conformal_lat(lat0);
+                let m1 = el.m(lat0);
+                let chi = el.conformal_lat(phi);
+                let dl = normalize_lon(lambda - lon0);
+                let aa = 2f64 * el.a * k0 * m1
+                    / (chi1.cos()
+                        * (1f64 + chi1.sin() * chi.sin() + chi1.cos() * chi.cos() * dl.cos()));
+                (
+                    aa * chi.cos() * dl.sin() + false_easting,
+                    aa * (chi1.cos() * chi.sin() - chi1.sin() * chi.cos() * dl.cos())
+                        + false_northing,
+                )
+            }
+            Projection::Mercator {
+                lon0,
+                lat_ts,
+                false_easting,
+                false_northing,
+            } => {
+                let k0 = el.m(lat_ts);
+                (
+                    el.a * k0 * normalize_lon(lambda - lon0) + false_easting,
+                    -el.a * k0 * el.t(phi).ln() + false_northing,
+                )
+            }
+        };
+        (x / self.linear_unit, y / self.linear_unit)
+    }
+
+    /// Transforms a coordinate from this CRS into the `target` CRS.
+    pub fn transform_to(&self, target: &CoordinateReferenceSystem, x: f64, y: f64) -> (f64, f64) {
+        let (lon, lat) = self.unproject(x, y);
+        if self.ellipsoid == target.ellipsoid {
+            return target.project(lon, lat);
+        }
+        let (gx, gy, gz) =
+            self.ellipsoid
+                .geodetic_to_geocentric(lon.to_radians(), lat.to_radians(), 0f64);
+        let (lon2, lat2, _) = target.ellipsoid.geocentric_to_geodetic(gx, gy, gz);
+        target.project(lon2.to_degrees(), lat2.to_degrees())
+    }
+
+    /// Returns an OGC WKT2:2019 (ISO 19162) description of the CRS. Datum and ellipsoid
+    /// names are taken from the CRS's ESRI-style WKT, where available.
+    pub fn to_wkt2(&self) -> String {
+        let degree = "ANGLEUNIT[\"degree\",0.0174532925199433]";
+        let geogcrs = format!(
+            "DATUM[\"{}\",ELLIPSOID[\"{}\",{},{},LENGTHUNIT[\"metre\",1]]],PRIMEM[\"Greenwich\",0,{}]",
+            self.wkt_name("DATUM", "unknown"),
+            self.wkt_name("SPHEROID", "unknown"),
+            self.ellipsoid.a,
+            self.ellipsoid.inv_f,
+            degree
+        );
+        let id = if self.epsg_code != 0 {
+            format!(",ID[\"EPSG\",{}]", self.epsg_code)
+        } else {
+            String::new()
+        };
+        let (method, method_code, params) = match self.conversion() {
+            Some(c) => c,
+            None => {
+                return format!(
+                    "GEOGCRS[\"{}\",{},CS[ellipsoidal,2],AXIS[\"geodetic latitude (Lat)\",north,ORDER[1]],AXIS[\"geodetic longitude (Lon)\",east,ORDER[2]],{}{}]",
+                    self.name, geogcrs, degree, id
+                );
+            }
+        };
+        let length_unit = if (self.linear_unit - 1f64).abs() < 1e-12 {
+            "LENGTHUNIT[\"metre\",1]".to_string()
+        } else {
+            format!("LENGTHUNIT[\"unknown\",{}]", self.linear_unit)
+        };
+        let params: Vec<String> = params
+            .iter()
+            .map(|&(name, code, value, is_angle)| {
+                let value_unit = if is_angle {
+                    degree.to_string()
+                } else if name.starts_with("Scale") {
+                    "SCALEUNIT[\"unity\",1]".to_string()
+                } else {
+                    length_unit.clone()
+                };
+                format!(
+                    "PARAMETER[\"{}\",{},{},ID[\"EPSG\",{}]]",
+                    name, value, value_unit, code
+                )
+            })
+            .collect();
+        format!(
+            "PROJCRS[\"{}\",BASEGEOGCRS[\"{}\",{},{}],CONVERSION[\"unnamed\",METHOD[\"{}\",ID[\"EPSG\",{}]],{}],CS[Cartesian,2],AXIS[\"easting (E)\",east,ORDER[1]],AXIS[\"northing (N)\",north,ORDER[2]],{}{}]",
+            self.name,
+            self.wkt_name("GEOGCS", "unknown"),
+            geogcrs,
+            degree,
+            method,
+            method_code,
+            params.join(","),
+            length_unit,
+            id
+        )
+    }
+
+    /// Returns a PROJJSON description of the CRS, as used by GeoParquet.
+    pub fn to_projjson(&self) -> Value {
+        let id = if self.epsg_code != 0 {
+            json!({ "authority": "EPSG", "code": self.epsg_code })
+        } else {
+            Value::Null
+        };
+        let geographic = |name: String| -> Value {
+            json!({
+                "type": "GeographicCRS",
+                "name": name,
+                "datum": {
+                    "type": "GeodeticReferenceFrame",
+                    "name": self.wkt_name("DATUM", "unknown"),
+                    "ellipsoid": {
+                        "name": self.wkt_name("SPHEROID", "unknown"),
+                        "semi_major_axis": self.ellipsoid.a,
+                        "inverse_flattening": self.ellipsoid.inv_f,
+                    },
+                },
+                "coordinate_system": {
+                    "subtype": "ellipsoidal",
+                    "axis": [
+                        { "name": "Geodetic latitude", "abbreviation": "Lat", "direction": "north", "unit": "degree" },
+                        { "name": "Geodetic longitude", "abbreviation": "Lon", "direction": "east", "unit": "degree" },
+                    ],
+                },
+            })
+        };
+        let (method, method_code, params) = match self.conversion() {
+            Some(c) => c,
+            None => {
+                let mut crs = geographic(self.name.clone());
+                if !id.is_null() {
+                    crs["id"] = id;
+                }
+                return crs;
+            }
+        };
+        let length_unit = if (self.linear_unit - 1f64).abs() < 1e-12 {
+            json!("metre")
+        } else {
+            json!({ "type": "LinearUnit", "name": "unknown", "conversion_factor": self.linear_unit })
+        };
+        let params: Vec<Value> = params
+            .iter()
+            .map(|&(name, code, value, is_angle)| {
+                let unit = if is_angle {
+                    json!("degree")
+                } else if name.starts_with("Scale") {
+                    json!("unity")
+                } else {
+                    length_unit.clone()
+                };
+                json!({
+                    "name": name,
+                    "value": value,
+                    "unit": unit,
+                    "id": { "authority": "EPSG", "code": code },
+                })
+            })
+            .collect();
+        let mut crs = json!({
+            "type": "ProjectedCRS",
+            "name": self.name,
+            "base_crs": geographic(self.wkt_name("GEOGCS", "unknown")),
+            "conversion": {
+                "name": "unnamed",
+                "method": {
+                    "name": method,
+                    "id": { "authority": "EPSG", "code": method_code },
+                },
+                "parameters": params,
+            },
+            "coordinate_system": {
+                "subtype": "Cartesian",
+                "axis": [
+                    { "name": "Easting", "abbreviation": "E", "direction": "east", "unit": length_unit },
+                    { "name": "Northing", "abbreviation": "N", "direction": "north", "unit": length_unit },
+                ],
+            },
+        });
+        if !id.is_null() {
+            crs["id"] = id;
+        }
+        crs
+    }
+
+    /// Returns the name and EPSG code of the map projection method, along with the name,
+    /// EPSG code, and value of each of its parameters and whether the value is an angle, or
+    /// None for geographic CRSs. Angles are in degrees and false origins are in the CRS's
+    /// linear unit.
+    fn conversion(&self) -> Option<(&'static str, u32, Vec<(&'static str, u32, f64, bool)>)> {
+        let unit = self.linear_unit;
+        Some(match self.projection {
+            Projection::TransverseMercator {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => (
+                "Transverse Mercator",
+                9807,
+                vec![
+                    ("Latitude of natural origin", 8801, lat0.to_degrees(), true),
+                    ("Longitude of natural origin", 8802, lon0.to_degrees(), true),
+                    ("Scale factor at natural origin", 8805, k0, false),
+                    ("False easting", 8806, false_easting / unit, false),
+                    ("False northing", 8807, false_northing / unit, false),
+                ],
+            ),
+            Projection::LambertConformalConic {
+                lon0,
+                lat0,
+                lat1,
+                lat2,
+                false_easting,
+                false_northing,
+                ..
+            } => (
+                "Lambert Conic Conformal (2SP)",
+                9802,
+                vec![
+                    ("Latitude of false origin", 8821, lat0.to_degrees(), true),
+                    ("Longitude of false origin", 8822, lon0.to_degrees(), true),
+                    (
+                        "Latitude of 1st standard parallel",
+                        8823,
+                        lat1.to_degrees(),
+                        true,
+                    ),
+                    (
+                        "Latitude of 2nd standard parallel",
+                        8824,
+                        lat2.to_degrees(),
+                        true,
+                    ),
+                    ("Easting at false origin", 8826, false_easting / unit, false),
+                    (
+                        "Northing at false origin",
+                        8827,
+                        false_northing / unit,
+                        false,
+                    ),
+                ],
+            ),
+            Projection::PolarStereographic {
+                lon0,
+                k0,
+                north,
+                false_easting,
+                false_northing,
+                ..
+            } if (k0 - 1f64).abs() > 1e-12 => (
+                "Polar Stereographic (variant A)",
+                9810,
+                vec![
+                    (
+                        "Latitude of natural origin",
+                        8801,
+                        if north { 90f64 } else { -90f64 },
+                        true,
+                    ),
+                    ("Longitude of natural origin", 8802, lon0.to_degrees(), true),
+                    ("Scale factor at natural origin", 8805, k0, false),
+                    ("False easting", 8806, false_easting / unit, false),
+                    ("False northing", 8807, false_northing / unit, false),
+                ],
+            ),
+            Projection::PolarStereographic {
+                lon0,
+                lat_ts,
+                north,
+                false_easting,
+                false_northing,
+                ..
+            } => (
+                "Polar Stereographic (variant B)",
+                9829,
+                vec![
+                    (
+                        "Latitude of standard parallel",
+                        8832,
+                        if north {
+                            lat_ts.abs().to_degrees()
+                        } else {
+                            -lat_ts.abs().to_degrees()
+                        },
+                        true,
+                    ),
+                    ("Longitude of origin", 8833, lon0.to_degrees(), true),
+                    ("False easting", 8806, false_easting / unit, false),
+                    ("False northing", 8807, false_northing / unit, false),
+                ],
+            ),
+            Projection::ObliqueStereographic {
+                lon0,
+                lat0,
+                k0,
+                false_easting,
+                false_northing,
+            } => (
+                "Oblique Stereographic",
+                9809,
+                vec![
+                    ("Latitude of natural origin", 8801, lat0.to_degrees(), true),
+                    ("Longitude of natural origin", 8802, lon0.to_degrees(), true),
+                    ("Scale factor at natural origin", 8805, k0, false),
+                    ("False easting", 8806, false_easting / unit, false),
+                    ("False northing", 8807, false_northing / unit, false),
+                ],
+            ),
+            Projection::Mercator {
+                lon0,
+                lat_ts,
+                false_easting,
+                false_northing,
+            } => (
+                "Mercator (variant B)",
+                9805,
+                vec![
+                    (
+                        "Latitude of 1st standard parallel",
+                        8823,
+                        lat_ts.to_degrees(),
+                        true,
+                    ),
+                    ("Longitude of natural origin", 8802, lon0.to_degrees(), true),
+                    ("False easting", 8806, false_easting / unit, false),
+                    ("False northing", 8807, false_northing / unit, false),
+                ],
+            ),
+            Projection::Geographic => return None,
+        })
+    }
+
+    /// Returns the name of a node of the CRS's ESRI-style WKT, e.g. its DATUM.
+    fn wkt_name(&self, key: &str, default: &str) -> String {
+        match bracketed_values(&self.wkt, key).first() {
+            Some(n) if !n.is_empty() => n.clone(),
+            _ => default.to_string(),
+        }
+    }
+}
+
+fn lcc_constants(el: &Ellipsoid, lat0: f64, lat1: f64, lat2: f64, k0: f64) -> (f64, f64, f64) {
+    let m1 = el.m(lat1);
+    let t1 = el.t(lat1);
+    let n = if (lat1 - lat2).abs() < 1e-10 {
+        lat1.sin()
+    } else {
+        (m1.ln() - el.m(lat2).ln()) / (t1.ln() - el.t(lat2).ln())
+    };
+    let f = m1 / (n * t1.powf(n));
+    let rho0 = el.a * k0 * f * el.t(lat0).powf(n);
+    (n, f, rho0)
+}
+
+fn polar_stereographic_rho_from_t(el: &Ellipsoid, t: f64, lat_ts: f64, k0: f64) -> f64 {
+    if (lat_ts - FRAC_PI_2).abs() < 1e-10 {
+        let e = el.e();
+        2f64 * el.a * k0 * t / ((1f64 + e).powf(1f64 + e) * (1f64 - e).powf(1f64 - e)).sqrt()
+    } else {
+        el.a * el.m(lat_ts) * t / el.t(lat_ts)
+    }
+}
+
+fn polar_stereographic_t_from_rho(el: &Ellipsoid, rho: f64, lat_ts: f64, k0: f64) -> f64 {
+    if (lat_ts - FRAC_PI_2).abs() < 1e-10 {
+        let e = el.e();
+        rho * ((1f64 + e).powf(1f64 + e) * (1f64 - e).powf(1f64 - e)).sqrt() / (2f64 * el.a * k0)
+    } else {
+        rho * el.t(lat_ts) / (el.a * el.m(lat_ts))
+    }
+}
+
+/// Wraps a longitude (radians) into the range [-PI, PI].
+fn normalize_lon(lon: f64) -> f64 {
+    let mut lon = lon;
+    while lon > std::f64::consts::PI {
+        lon -= 2f64 * std::f64::consts::PI;
+    }
+    while lon < -std::f64::consts::PI {
+        lon += 2f64 * std::f64::consts::PI;
+    }
+    lon
+}
+
+fn parse_num(s: &str) -> Result<f64, Error> {
+    s.trim().parse::<f64>().map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Error parsing WKT numeric value '{}'.", s),
+        )
+    })
+}
+
+fn first_quoted_value(wkt: &str) -> Option<String> {
+    let start = wkt.find('"')? + 1;
+    let end = start + wkt[start..].find('"')?;
+    Some(wkt[start..end].to_string())
+}
+
+/// Splits the comma-delimited, top-level contents of a WKT node.
+fn split_node(contents: &str) -> Vec<String> {
+    let mut ret = vec![];
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in contents.chars() {
+        match c {
+            '[' => {
+                depth += 1;
+                current.push(c);
+            }
+            ']' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' if depth == 0 => {
+                ret.push(current.trim().trim_matches('"').to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    ret.push(current.trim().trim_matches('"').to_string());
+    ret
+}
+
+/// Returns the contents of the node starting at byte `start` (just after the '[').
+fn node_contents(wkt: &str, start: usize) -> &str {
+    let mut depth = 1;
+    for (i, c) in wkt[start..].char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &wkt[start..start + i];
+                }
+            }
+            _ => {}
+        }
+    }
+    &wkt[start..]
+}
+
+fn bracketed_values(wkt: &str, key: &str) -> Vec<String> {
+    let pattern = format!("{}[", key);
+    match wkt.to_uppercase().find(&pattern) {
+        Some(idx) => split_node(node_contents(wkt, idx + pattern.len())),
+        None => vec![],
+    }
+}
+
+fn bracketed_values_last(wkt: &str, key: &str) -> Option<Vec<String>> {
+    // only consider nodes that are direct children of the outermost node
+    let pattern = format!("{}[", key);
+    let upper = wkt.to_uppercase();
+    let mut depth = 0;
+    let mut last = None;
+    for (i, c) in wkt.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ => {
+                if depth == 1 && upper[i..].starts_with(&pattern) {
+                    last = Some(split_node(node_contents(wkt, i + pattern.len())));
+                }
+            }
+        }
+    }
+    last
+}
+
+fn parameters(wkt: &str) -> HashMap<String, f64> {
+    let mut ret = HashMap::new();
+    let upper = wkt.to_uppercase();
+    let pattern = "PARAMETER[";
+    let mut offset = 0;
+    while let Some(idx) = upper[offset..].find(pattern) {
+        let start = offset + idx + pattern.len();
+        let vals = split_node(node_contents(wkt, start));
+        if vals.len() > 1 {
+            if let Ok(v) = vals[1].parse::<f64>() {
+                ret.insert(vals[0].to_lowercase(), v);
+            }
+        }
+        offset = start;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::CoordinateReferenceSystem;
+
+    #[test]
+    fn test_utm_central_meridian() {
+        let geog = CoordinateReferenceSystem::from_epsg(4326).unwrap();
+        let utm = CoordinateReferenceSystem::from_epsg(32617).unwrap();
+        let (x, y) = geog.transform_to(&utm, -81.0, 0.0);
+        assert!((x - 500000.0).abs() < 1e-6);
+        assert!(y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_utm_round_trip() {
+        let geog = CoordinateReferenceSystem::from_epsg(4326).unwrap();
+        let utm = CoordinateReferenceSystem::from_epsg(32617).unwrap();
+        let (x, y) = geog.transform_to(&utm, -80.2, 43.55);
+        let (lon, lat) = utm.transform_to(&geog, x, y);
+        assert!((lon + 80.2).abs() < 1e-8);
+        assert!((lat - 43.55).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_lcc_round_trip() {
+        let wkt = "PROJCS[\"Test_LCC\",GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Lambert_Conformal_Conic\"],PARAMETER[\"False_Easting\",1000000.0],PARAMETER[\"False_Northing\",0.0],PARAMETER[\"Central_Meridian\",-95.0],PARAMETER[\"Standard_Parallel_1\",49.0],PARAMETER[\"Standard_Parallel_2\",77.0],PARAMETER[\"Latitude_Of_Origin\",0.0],UNIT[\"Meter\",1.0]]";
+        let lcc = CoordinateReferenceSystem::from_wkt(wkt).unwrap();
+        let (x, y) = lcc.project(-75.7, 45.4);
+        let (lon, lat) = lcc.unproject(x, y);
+        assert!((lon + 75.7).abs() < 1e-8);
+        assert!((lat - 45.4).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_wkt2() {
+        let utm = CoordinateReferenceSystem::from_epsg(32617).unwrap();
+        let wkt2 = utm.to_wkt2();
+        assert!(wkt2.starts_with("PROJCRS["));
+        assert!(wkt2.contains("METHOD[\"Transverse Mercator\",ID[\"EPSG\",9807]]"));
+        assert!(wkt2.contains("PARAMETER[\"Longitude of natural origin\",-81,"));
+        assert!(wkt2.ends_with("ID[\"EPSG\",32617]]"));
+        let geog = CoordinateReferenceSystem::from_epsg(4326).unwrap();
+        assert!(geog.to_wkt2().starts_with("GEOGCRS["));
+    }
+
+    #[test]
+    fn test_projjson() {
+        let utm = CoordinateReferenceSystem::from_epsg(32617)
+            .unwrap()
+            .to_projjson();
+        assert_eq!(utm["type"], "ProjectedCRS");
+        assert_eq!(utm["base_crs"]["type"], "GeographicCRS");
+        assert_eq!(utm["conversion"]["method"]["id"]["code"], 9807);
+        assert_eq!(utm["id"]["code"], 32617);
+        let geog = CoordinateReferenceSystem::from_epsg(4326)
+            .unwrap()
+            .to_projjson();
+        assert_eq!(geog["type"], "GeographicCRS");
+    }
+
+    #[test]
+    fn test_polar_stereographic_round_trip() {
+        let wkt = "PROJCS[\"Test_PS\",GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]],PROJECTION[\"Stereographic_South_Pole\"],PARAMETER[\"False_Easting\",0.0],PARAMETER[\"False_Northing\",0.0],PARAMETER[\"Central_Meridian\",0.0],PARAMETER[\"Standard_Parallel_1\",-71.0],UNIT[\"Meter\",1.0]]";
+        let ps = CoordinateReferenceSystem::from_wkt(wkt).unwrap();
+        let (x, y) = ps.project(45.0, -75.0);
+        let (lon, lat) = ps.unproject(x, y);
+        assert!((lon - 45.0).abs() < 1e-8);
+        assert!((lat + 75.0).abs() < 1e-8);
+    }
+}
@@ -1,3 +1,5 @@
+mod crs;
 mod epsg_to_wkt;
 
+pub use self::crs::{CoordinateReferenceSystem, Ellipsoid, Projection};
 pub use self::epsg_to_wkt::esri_wkt_from_epsg;
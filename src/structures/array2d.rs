@@ -1,14 +1,20 @@
 /////////////////////////////////////////////
 // A generic 2-dimensional array structure //
 /////////////////////////////////////////////
+use crate::utils::memory;
+use std::fmt;
 use std::io::Error;
 use std::io::ErrorKind;
-use std::ops::{AddAssign, Index, IndexMut, SubAssign};
+use std::ops::{AddAssign, Deref, DerefMut, Index, IndexMut, SubAssign};
 
 /// A simple in-memory 2-D raster data structure that is not connected to a file.
 /// Pixel values can contain any data type or structure that implements the Copy,
 /// AddAssign, and SubAssign traits.
 ///
+/// The values are stored on the heap unless doing so would exceed the memory budget
+/// set by the `--max_mem` flag, in which case they are backed by a memory-mapped
+/// temporary file (see `utils::memory`).
+///
 /// Example:
 ///
 /// ```
@@ -24,7 +30,7 @@ use std::ops::{AddAssign, Index, IndexMut, SubAssign};
 pub struct Array2D<T: Copy + AddAssign + SubAssign> {
     pub columns: isize,
     pub rows: isize,
-    data: Vec<T>,
+    data: ArrayData<T>,
     pub nodata: T,
 }
 
@@ -59,7 +65,7 @@ where
             columns: columns,
             rows: rows,
             nodata: nodata,
-            data: vec![initial_value; (rows * columns) as usize],
+            data: ArrayData::new((rows * columns) as usize, initial_value)?,
         };
         Ok(array)
     }
@@ -152,7 +158,7 @@ where
                 "Rasters must have the same dimensions and extent.",
             ));
         }
-        self.data = other.data.clone();
+        self.data.copy_from_slice(&other.data);
         Ok(())
     }
 
@@ -166,7 +172,9 @@ where
     }
 
     pub fn reinitialize_values(&mut self, value: T) {
-        self.data = vec![value; (self.rows * self.columns) as usize];
+        for v in self.data.iter_mut() {
+            *v = value;
+        }
     }
 
     pub fn columns(&self) -> isize {
@@ -229,3 +237,130 @@ where
         &mut self.data[idx as usize]
     }
 }
+
+/// The storage of an `Array2D`, either on the heap or, when the memory budget has
+/// been exhausted, in a memory-mapped temporary file.
+enum Storage<T: Copy> {
+    Heap(Vec<T>),
+    Mapped(memory::MappedBuffer<T>),
+}
+
+struct ArrayData<T: Copy> {
+    storage: Storage<T>,
+    // the number of bytes reserved against the memory budget
+    reserved: usize,
+}
+
+impl<T: Copy> ArrayData<T> {
+    fn new(len: usize, value: T) -> Result<ArrayData<T>, Error> {
+        let bytes = len * std::mem::size_of::<T>();
+        if !memory::try_reserve(bytes) {
+            if let Ok(buffer) = memory::MappedBuffer::new(len, value) {
+                return Ok(ArrayData {
+                    storage: Storage::Mapped(buffer),
+                    reserved: 0,
+                });
+            }
+            // the array cannot be spilled to disk on this platform
+            memory::force_reserve(bytes);
+        }
+        Ok(ArrayData {
+            storage: Storage::Heap(vec![value; len]),
+            reserved: bytes,
+        })
+    }
+}
+
+impl<T: Copy> Deref for ArrayData<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.storage {
+            Storage::Heap(v) => v,
+            Storage::Mapped(b) => b,
+        }
+    }
+}
+
+impl<T: Copy> DerefMut for ArrayData<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            Storage::Heap(v) => v,
+            Storage::Mapped(b) => b,
+        }
+    }
+}
+
+impl<T: Copy> Clone for ArrayData<T> {
+    fn clone(&self) -> ArrayData<T> {
+        match self.first() {
+            Some(v) => {
+                let mut data =
+                    ArrayData::new(self.len(), *v).expect("Error allocating array data.");
+                data.copy_from_slice(self);
+                data
+            }
+            None => ArrayData {
+                storage: Storage::Heap(vec![]),
+                reserved: 0,
+            },
+        }
+    }
+}
+
+impl<T: Copy> Drop for ArrayData<T> {
+    fn drop(&mut self) {
+        memory::release(self.reserved);
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for ArrayData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// memory mapping is only supported on Unix-like systems
+#[cfg(all(test, unix))]
+mod test {
+    use super::{Array2D, Storage};
+    use crate::utils::memory::{
+        mapped_file_paths, memory_budget, set_memory_budget, BUDGET_TEST_LOCK,
+    };
+
+    #[test]
+    fn test_array2d_spills_to_mapped_buffer() {
+        let _guard = BUDGET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let budget = memory_budget();
+        set_memory_budget(1);
+        let mut a: Array2D<f64> = Array2D::new(50, 40, 1f64, -32768f64).unwrap();
+        let b = a.clone();
+        set_memory_budget(budget);
+
+        // neither array is counted against the budget
+        assert!(matches!(a.data.storage, Storage::Mapped(_)));
+        assert!(matches!(b.data.storage, Storage::Mapped(_)));
+        assert_eq!(a.data.reserved, 0);
+        assert_eq!(b.data.reserved, 0);
+
+        a.set_value(0, 0, 5f64);
+        a.set_value(49, 39, -2f64);
+        a.set_value(50, 0, 9f64); // outside of the grid
+        assert_eq!(a.get_value(0, 0), 5f64);
+        assert_eq!(a.get_value(49, 39), -2f64);
+        assert_eq!(a.get_value(25, 20), 1f64);
+        assert_eq!(a.get_value(50, 0), -32768f64);
+        // the clone is a copy of the data, not a view of it
+        assert_eq!(b.get_value(0, 0), 1f64);
+        assert_eq!(b.get_value(49, 39), 1f64);
+        let c = a.clone();
+        assert_eq!(c.get_value(0, 0), 5f64);
+
+        drop(a);
+        drop(b);
+        drop(c);
+        for path in mapped_file_paths() {
+            assert!(!path.exists(), "{} was not removed", path.display());
+        }
+    }
+}
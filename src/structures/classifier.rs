@@ -0,0 +1,504 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Supervised classifiers used by the TrainClassifier and ApplyClassifier tools.
+The random forest is an ensemble of CART decision trees, grown on bootstrap samples
+using the Gini impurity criterion and a random subset of the features at each split.
+The k-nearest neighbour classifier operates on z-score standardized features. Models
+are serialized to disk as JSON.
+*/
+
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use rand::rngs::SmallRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+use std::f64;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum TreeNode {
+    Leaf {
+        probabilities: Vec<f64>,
+    },
+    Split {
+        feature: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+struct TreeParameters {
+    num_classes: usize,
+    max_features: usize,
+    min_samples_split: usize,
+    max_depth: usize,
+}
+
+/// A CART classification tree.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionTree {
+    nodes: Vec<TreeNode>,
+}
+
+impl DecisionTree {
+    fn fit(
+        features: &[Vec<f64>],
+        labels: &[usize],
+        indices: &mut [usize],
+        params: &TreeParameters,
+        rng: &mut SmallRng,
+    ) -> DecisionTree {
+        let mut tree = DecisionTree { nodes: vec![] };
+        tree.grow(features, labels, indices, 0, params, rng);
+        tree
+    }
+
+    fn grow(
+        &mut self,
+        features: &[Vec<f64>],
+        labels: &[usize],
+        indices: &mut [usize],
+        depth: usize,
+        params: &TreeParameters,
+        rng: &mut SmallRng,
+    ) -> usize {
+        let node_id = self.nodes.len();
+        let n = indices.len();
+        let mut counts = vec![0usize; params.num_classes];
+        for &i in indices.iter() {
+            counts[labels[i]] += 1;
+        }
+        let probabilities: Vec<f64> = counts.iter().map(|&c| c as f64 / n as f64).collect();
+        let is_pure = counts.iter().filter(|&&c| c > 0).count() <= 1;
+        if is_pure || n < params.min_samples_split || depth >= params.max_depth {
+            self.nodes.push(TreeNode::Leaf { probabilities });
+            return node_id;
+        }
+
+        // find the split that minimizes the weighted Gini impurity of the children
+        let num_features = features[indices[0]].len();
+        let mut best_score = gini(&counts, n) * n as f64 - 1e-9;
+        let mut best_split: Option<(usize, f64)> = None;
+        let mut sorted = indices.to_vec();
+        for f in sample(rng, num_features, params.max_features.min(num_features)).iter() {
+            sorted.sort_by(|a, b| features[*a][f].partial_cmp(&features[*b][f]).unwrap());
+            let mut left_counts = vec![0usize; params.num_classes];
+            let mut right_counts = counts.clone();
+            for i in 0..n - 1 {
+                let c = labels[sorted[i]];
+                left_counts[c] += 1;
+                right_counts[c] -= 1;
+                let v = features[sorted[i]][f];
+                let v_next = features[sorted[i + 1]][f];
+                if v == v_next {
+                    continue;
+                }
+                let (n_left, n_right) = (i + 1, n - i - 1);
+                let score = gini(&left_counts, n_left) * n_left as f64
+                    + gini(&right_counts, n_right) * n_right as f64;
+                if score < best_score {
+                    best_score = score;
+                    best_split = Some((f, (v + v_next) / 2f64));
+                }
+            }
+        }
+
+        let (feature, threshold) = match best_split {
+            Some(s) => s,
+            None => {
+                self.nodes.push(TreeNode::Leaf { probabilities });
+                return node_id;
+            }
+        };
+
+        // partition the indices in place and grow the children
+        self.nodes.push(TreeNode::Leaf {
+            probabilities: vec![],
+        }); // placeholder
+        let mut num_left = 0;
+        for i in 0..n {
+            if features[indices[i]][feature] <= threshold {
+                indices.swap(i, num_left);
+                num_left += 1;
+            }
+        }
+        let (left_indices, right_indices) = indices.split_at_mut(num_left);
+        let left = self.grow(features, labels, left_indices, depth + 1, params, rng);
+        let right = self.grow(features, labels, right_indices, depth + 1, params, rng);
+        self.nodes[node_id] = TreeNode::Split {
+            feature,
+            threshold,
+            left,
+            right,
+        };
+        node_id
+    }
+
+    /// Returns the class probabilities of the leaf into which a feature vector falls.
+    pub fn predict_proba(&self, x: &[f64]) -> &[f64] {
+        let mut i = 0;
+        loop {
+            match &self.nodes[i] {
+                TreeNode::Leaf { probabilities } => return probabilities,
+                TreeNode::Split {
+                    feature,
+                    threshold,
+                    left,
+                    right,
+                } => {
+                    i = if x[*feature] <= *threshold {
+                        *left
+                    } else {
+                        *right
+                    };
+                }
+            }
+        }
+    }
+}
+
+fn gini(counts: &[usize], n: usize) -> f64 {
+    if n == 0 {
+        return 0f64;
+    }
+    let mut sum = 0f64;
+    for &c in counts {
+        let p = c as f64 / n as f64;
+        sum += p * p;
+    }
+    1f64 - sum
+}
+
+/// A random forest classifier.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RandomForest {
+    pub num_classes: usize,
+    pub num_features: usize,
+    /// The out-of-bag estimate of the classification accuracy.
+    pub oob_accuracy: f64,
+    trees: Vec<DecisionTree>,
+}
+
+impl RandomForest {
+    /// Grows a forest of `num_trees` trees. Class labels must be in the range 0..num_classes.
+    /// If `max_features` is zero, the square root of the number of features is used.
+    pub fn fit(
+        features: &[Vec<f64>],
+        labels: &[usize],
+        num_classes: usize,
+        num_trees: usize,
+        max_features: usize,
+        min_samples_split: usize,
+        max_depth: usize,
+        seed: u64,
+    ) -> Result<RandomForest, Error> {
+        let n = features.len();
+        if n == 0 || n != labels.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The training data are empty or the numbers of features and labels differ.",
+            ));
+        }
+        let num_features = features[0].len();
+        let params = TreeParameters {
+            num_classes,
+            max_features: if max_features == 0 {
+                ((num_features as f64).sqrt().round() as usize).max(1)
+            } else {
+                max_features.min(num_features)
+            },
+            min_samples_split: min_samples_split.max(2),
+            max_depth: if max_depth == 0 {
+                usize::max_value()
+            } else {
+                max_depth
+            },
+        };
+
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut trees = Vec::with_capacity(num_trees);
+        let mut oob_votes = vec![vec![0f64; num_classes]; n];
+        let mut in_bag = vec![false; n];
+        for _ in 0..num_trees {
+            for b in in_bag.iter_mut() {
+                *b = false;
+            }
+            let mut indices: Vec<usize> = (0..n)
+                .map(|_| {
+                    let i = rng.gen_range(0, n);
+                    in_bag[i] = true;
+                    i
+                })
+                .collect();
+            let tree = DecisionTree::fit(features, labels, &mut indices, &params, &mut rng);
+            for i in 0..n {
+                if !in_bag[i] {
+                    let p = tree.predict_proba(&features[i]);
+                    for c in 0..num_classes {
+                        oob_votes[i][c] += p[c];
+                    }
+                }
+            }
+            trees.push(tree);
+        }
+
+        let (mut num_correct, mut num_oob) = (0usize, 0usize);
+        for i in 0..n {
+            if oob_votes[i].iter().any(|&v| v > 0f64) {
+                num_oob += 1;
+                if argmax(&oob_votes[i]) == labels[i] {
+                    num_correct += 1;
+                }
+            }
+        }
+        let oob_accuracy = if num_oob > 0 {
+            num_correct as f64 / num_oob as f64
+        } else {
+            f64::NAN
+        };
+
+        Ok(RandomForest {
+            num_classes,
+            num_features,
+            oob_accuracy,
+            trees,
+        })
+    }
+
+    pub fn num_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Returns the mean class probabilities of the trees in the forest.
+    pub fn predict_proba(&self, x: &[f64]) -> Vec<f64> {
+        let mut probabilities = vec![0f64; self.num_classes];
+        for tree in &self.trees {
+            let p = tree.predict_proba(x);
+            for c in 0..self.num_classes {
+                probabilities[c] += p[c];
+            }
+        }
+        let num_trees = self.trees.len().max(1) as f64;
+        for p in probabilities.iter_mut() {
+            *p /= num_trees;
+        }
+        probabilities
+    }
+}
+
+/// A k-nearest neighbour classifier operating on standardized features.
+#[derive(Serialize, Deserialize)]
+pub struct KnnClassifier {
+    pub k: usize,
+    pub num_classes: usize,
+    means: Vec<f64>,
+    std_devs: Vec<f64>,
+    features: Vec<Vec<f64>>,
+    labels: Vec<usize>,
+    #[serde(skip)]
+    index: Option<KdTree<f64, usize, Vec<f64>>>,
+}
+
+impl KnnClassifier {
+    pub fn fit(
+        features: &[Vec<f64>],
+        labels: &[usize],
+        num_classes: usize,
+        k: usize,
+    ) -> Result<KnnClassifier, Error> {
+        let n = features.len();
+        if n == 0 || n != labels.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The training data are empty or the numbers of features and labels differ.",
+            ));
+        }
+        let num_features = features[0].len();
+        let mut means = vec![0f64; num_features];
+        let mut std_devs = vec![0f64; num_features];
+        for x in features {
+            for f in 0..num_features {
+                means[f] += x[f] / n as f64;
+            }
+        }
+        for x in features {
+            for f in 0..num_features {
+                std_devs[f] += (x[f] - means[f]) * (x[f] - means[f]) / n as f64;
+            }
+        }
+        for s in std_devs.iter_mut() {
+            *s = if *s > 0f64 { s.sqrt() } else { 1f64 };
+        }
+        let mut knn = KnnClassifier {
+            k: k.max(1).min(n),
+            num_classes,
+            means,
+            std_devs,
+            features: vec![],
+            labels: labels.to_vec(),
+            index: None,
+        };
+        knn.features = features.iter().map(|x| knn.standardize(x)).collect();
+        Ok(knn)
+    }
+
+    fn standardize(&self, x: &[f64]) -> Vec<f64> {
+        x.iter()
+            .enumerate()
+            .map(|(f, v)| (v - self.means[f]) / self.std_devs[f])
+            .collect()
+    }
+
+    /// Builds the spatial index used to accelerate neighbour searches. This is
+    /// required after a model is read from disk.
+    pub fn build_index(&mut self) {
+        let dimensions = self.means.len();
+        let mut tree = KdTree::with_capacity(dimensions, 64);
+        for i in 0..self.features.len() {
+            tree.add(self.features[i].clone(), self.labels[i])
+                .expect("Error adding feature to the k-d tree.");
+        }
+        self.index = Some(tree);
+    }
+
+    /// Returns the proportion of the k nearest training samples belonging to each class.
+    pub fn predict_proba(&self, x: &[f64]) -> Vec<f64> {
+        let z = self.standardize(x);
+        let mut probabilities = vec![0f64; self.num_classes];
+        let weight = 1f64 / self.k as f64;
+        match self.index {
+            Some(ref tree) => {
+                for (_, &label) in tree
+                    .nearest(&z, self.k, &squared_euclidean)
+                    .expect("Error searching the k-d tree.")
+                {
+                    probabilities[label] += weight;
+                }
+            }
+            None => {
+                let mut dist: Vec<(f64, usize)> = self
+                    .features
+                    .iter()
+                    .zip(self.labels.iter())
+                    .map(|(f, &label)| (squared_euclidean(f, &z), label))
+                    .collect();
+                dist.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                for &(_, label) in dist.iter().take(self.k) {
+                    probabilities[label] += weight;
+                }
+            }
+        }
+        probabilities
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum Classifier {
+    RandomForest(RandomForest),
+    KNearestNeighbour(KnnClassifier),
+}
+
+/// A trained classification model, including the mapping between the model's class
+/// indices and the original class values.
+#[derive(Serialize, Deserialize)]
+pub struct ClassifierModel {
+    pub class_values: Vec<f64>,
+    pub num_features: usize,
+    pub classifier: Classifier,
+}
+
+impl ClassifierModel {
+    pub fn num_classes(&self) -> usize {
+        self.class_values.len()
+    }
+
+    /// Returns the probability of each class for a feature vector.
+    pub fn predict_proba(&self, x: &[f64]) -> Vec<f64> {
+        match self.classifier {
+            Classifier::RandomForest(ref rf) => rf.predict_proba(x),
+            Classifier::KNearestNeighbour(ref knn) => knn.predict_proba(x),
+        }
+    }
+
+    /// Returns the class index with the highest probability.
+    pub fn predict(&self, x: &[f64]) -> usize {
+        argmax(&self.predict_proba(x))
+    }
+
+    pub fn write(&self, file_name: &str) -> Result<(), Error> {
+        let f = File::create(file_name)?;
+        serde_json::to_writer(BufWriter::new(f), self)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{}", e)))
+    }
+
+    pub fn read(file_name: &str) -> Result<ClassifierModel, Error> {
+        let f = File::open(file_name)?;
+        let mut model: ClassifierModel = serde_json::from_reader(BufReader::new(f))
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error reading the classifier model file: {}", e),
+                )
+            })?;
+        if let Classifier::KNearestNeighbour(ref mut knn) = model.classifier {
+            knn.build_index();
+        }
+        Ok(model)
+    }
+}
+
+fn argmax(values: &[f64]) -> usize {
+    let mut max_index = 0;
+    for i in 1..values.len() {
+        if values[i] > values[max_index] {
+            max_index = i;
+        }
+    }
+    max_index
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn training_data() -> (Vec<Vec<f64>>, Vec<usize>) {
+        let mut features = vec![];
+        let mut labels = vec![];
+        for i in 0..50 {
+            let v = i as f64 / 10.0;
+            features.push(vec![v, 10.0 - v]);
+            labels.push(0);
+            features.push(vec![v + 20.0, 3.0 - v]);
+            labels.push(1);
+        }
+        (features, labels)
+    }
+
+    #[test]
+    fn test_random_forest() {
+        let (features, labels) = training_data();
+        let rf = RandomForest::fit(&features, &labels, 2, 25, 0, 2, 0, 42).unwrap();
+        assert_eq!(rf.num_trees(), 25);
+        assert!(rf.oob_accuracy > 0.95);
+        let p = rf.predict_proba(&[1.0, 9.0]);
+        assert!(p[0] > 0.9);
+        let p = rf.predict_proba(&[22.0, 1.0]);
+        assert!(p[1] > 0.9);
+    }
+
+    #[test]
+    fn test_knn() {
+        let (features, labels) = training_data();
+        let mut knn = KnnClassifier::fit(&features, &labels, 2, 5).unwrap();
+        assert_eq!(knn.predict_proba(&[1.0, 9.0]), vec![1.0, 0.0]);
+        knn.build_index();
+        assert_eq!(knn.predict_proba(&[22.0, 1.0]), vec![0.0, 1.0]);
+    }
+}
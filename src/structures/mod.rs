@@ -2,6 +2,7 @@
 mod array2d;
 mod bounding_box;
 mod circle;
+mod classifier;
 mod fixed_radius_search;
 mod line_segment;
 mod n_maximizer;
@@ -10,11 +11,16 @@ mod point2d;
 mod polyline;
 mod polynomial_regression_2d;
 mod radial_basis_function;
+mod rtree;
+mod semivariogram;
+mod spatial_weights;
+mod tin;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::array2d::Array2D;
 pub use self::bounding_box::BoundingBox;
 pub use self::circle::Circle;
+pub use self::classifier::{Classifier, ClassifierModel, KnnClassifier, RandomForest};
 pub use self::fixed_radius_search::{DistanceMetric, FixedRadiusSearch2D, FixedRadiusSearch3D};
 pub use self::line_segment::LineSegment;
 pub use self::n_maximizer::NMaximizer;
@@ -25,3 +31,11 @@ pub use self::polyline::MultiPolyline;
 pub use self::polyline::Polyline;
 pub use self::polynomial_regression_2d::PolynomialRegression2D;
 pub use self::radial_basis_function::{Basis, RadialBasisFunction};
+pub use self::rtree::RTree;
+pub use self::semivariogram::{EmpiricalSemivariogram, Semivariogram, VariogramModel};
+pub use self::spatial_weights::{
+    gearys_c, gearys_c_test, local_test, morans_i, morans_i_test, pseudo_p_value,
+    AutocorrelationTest, GridWeights, LocalStatistic, LocalTest, Moments, SpatialWeights,
+    WeightsList, WeightsSummary,
+};
+pub use self::tin::{construct_tin, Tin};
@@ -0,0 +1,325 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::BoundingBox;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f64;
+
+/// The maximum number of children of each R-tree node.
+const NODE_CAPACITY: usize = 16;
+
+#[derive(Clone, Copy, Debug)]
+struct RTreeNode {
+    bb: BoundingBox,
+    start: usize, // index of the first child node, or entry for leaf nodes
+    end: usize,
+    is_leaf: bool,
+}
+
+/// A static 2-D R-tree spatial index of bounding boxes, each associated with a value of
+/// type T (usually a feature or record index). The tree is bulk-loaded using the
+/// Sort-Tile-Recursive (STR) algorithm and cannot be modified after it is built.
+///
+/// ## Example
+///     let tree = RTree::new(vec![
+///         (BoundingBox::new(0.0, 1.0, 0.0, 1.0), 0usize),
+///         (BoundingBox::new(5.0, 6.0, 5.0, 6.0), 1usize),
+///     ]);
+///     let found = tree.search(BoundingBox::new(0.5, 2.0, 0.5, 2.0)); // [0]
+///     let nearest = tree.nearest(BoundingBox::new(4.0, 4.0, 4.0, 4.0), f64::INFINITY, |_| 0.0);
+pub struct RTree<T: Copy> {
+    entries: Vec<(BoundingBox, T)>,
+    nodes: Vec<RTreeNode>,
+}
+
+impl<T: Copy> RTree<T> {
+    /// Builds an R-tree from a list of bounding boxes and associated values.
+    pub fn new(mut entries: Vec<(BoundingBox, T)>) -> RTree<T> {
+        let mut nodes: Vec<RTreeNode> = vec![];
+        if entries.is_empty() {
+            return RTree {
+                entries: entries,
+                nodes: nodes,
+            };
+        }
+
+        str_sort(&mut entries, |e| centre(&e.0));
+        let mut level: Vec<RTreeNode> = (0..entries.len())
+            .step_by(NODE_CAPACITY)
+            .map(|start| {
+                let end = (start + NODE_CAPACITY).min(entries.len());
+                let mut bb = entries[start].0;
+                for e in &entries[start + 1..end] {
+                    bb.expand_to(e.0);
+                }
+                RTreeNode {
+                    bb: bb,
+                    start: start,
+                    end: end,
+                    is_leaf: true,
+                }
+            })
+            .collect();
+
+        // pack each level into parent nodes until a single root remains; the root is
+        // always the last node.
+        while level.len() > 1 {
+            str_sort(&mut level, |n| centre(&n.bb));
+            let base = nodes.len();
+            let n = level.len();
+            nodes.extend(level);
+            level = (base..base + n)
+                .step_by(NODE_CAPACITY)
+                .map(|start| {
+                    let end = (start + NODE_CAPACITY).min(base + n);
+                    let mut bb = nodes[start].bb;
+                    for node in &nodes[start + 1..end] {
+                        bb.expand_to(node.bb);
+                    }
+                    RTreeNode {
+                        bb: bb,
+                        start: start,
+                        end: end,
+                        is_leaf: false,
+                    }
+                })
+                .collect();
+        }
+        nodes.extend(level);
+
+        RTree {
+            entries: entries,
+            nodes: nodes,
+        }
+    }
+
+    /// Returns the number of entries in the tree.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the tree contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the values of all entries with bounding boxes that overlap a query box,
+    /// including those that only touch its edges.
+    pub fn search(&self, query: BoundingBox) -> Vec<T> {
+        let mut ret = vec![];
+        if self.nodes.is_empty() {
+            return ret;
+        }
+        let mut stack = vec![self.nodes.len() - 1];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.bb.overlaps(query) {
+                continue;
+            }
+            if node.is_leaf {
+                for e in &self.entries[node.start..node.end] {
+                    if e.0.overlaps(query) {
+                        ret.push(e.1);
+                    }
+                }
+            } else {
+                stack.extend(node.start..node.end);
+            }
+        }
+        ret
+    }
+
+    /// Finds the entry nearest to a query box, within a maximum distance, using a
+    /// best-first traversal of the tree. Because entries are only indexed by their
+    /// bounding boxes, the exact distance to each candidate is measured by the `distance`
+    /// function, which must never be smaller than the distance between the query box and
+    /// the candidate's bounding box. Returns the value and distance of the nearest entry.
+    pub fn nearest<F>(
+        &self,
+        query: BoundingBox,
+        max_distance: f64,
+        mut distance: F,
+    ) -> Option<(T, f64)>
+    where
+        F: FnMut(T) -> f64,
+    {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut queue = BinaryHeap::new();
+        queue.push(QueueItem {
+            dist: box_distance(&self.nodes[self.nodes.len() - 1].bb, &query),
+            index: self.nodes.len() - 1,
+            kind: ItemKind::Node,
+        });
+        while let Some(item) = queue.pop() {
+            if item.dist > max_distance {
+                return None;
+            }
+            match item.kind {
+                ItemKind::Value => return Some((self.entries[item.index].1, item.dist)),
+                ItemKind::Entry => {
+                    let dist = distance(self.entries[item.index].1);
+                    queue.push(QueueItem {
+                        dist: dist,
+                        index: item.index,
+                        kind: ItemKind::Value,
+                    });
+                }
+                ItemKind::Node => {
+                    let node = &self.nodes[item.index];
+                    for i in node.start..node.end {
+                        let bb = if node.is_leaf {
+                            &self.entries[i].0
+                        } else {
+                            &self.nodes[i].bb
+                        };
+                        queue.push(QueueItem {
+                            dist: box_distance(bb, &query),
+                            index: i,
+                            kind: if node.is_leaf {
+                                ItemKind::Entry
+                            } else {
+                                ItemKind::Node
+                            },
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ItemKind {
+    Node,
+    Entry, // an entry for which only the bounding box distance is known
+    Value, // an entry for which the exact distance is known
+}
+
+#[derive(Debug)]
+struct QueueItem {
+    dist: f64,
+    index: usize,
+    kind: ItemKind,
+}
+
+impl PartialEq for QueueItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueueItem {}
+
+impl PartialOrd for QueueItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueueItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed so that the BinaryHeap is a min-heap; exact distances are popped before
+        // bounding box distances that are equal to them.
+        match other.dist.partial_cmp(&self.dist) {
+            Some(Ordering::Equal) | None => {
+                (self.kind == ItemKind::Value).cmp(&(other.kind == ItemKind::Value))
+            }
+            Some(o) => o,
+        }
+    }
+}
+
+fn centre(bb: &BoundingBox) -> (f64, f64) {
+    ((bb.min_x + bb.max_x) / 2f64, (bb.min_y + bb.max_y) / 2f64)
+}
+
+/// Returns the minimum distance between two boxes, which is zero if they overlap.
+fn box_distance(a: &BoundingBox, b: &BoundingBox) -> f64 {
+    let dx = (a.min_x - b.max_x).max(b.min_x - a.max_x).max(0f64);
+    let dy = (a.min_y - b.max_y).max(b.min_y - a.max_y).max(0f64);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Orders items for Sort-Tile-Recursive packing: the items are sorted by x into vertical
+/// slices of roughly sqrt(n / NODE_CAPACITY) nodes each, and each slice is sorted by y.
+fn str_sort<I, F>(items: &mut [I], centre: F)
+where
+    F: Fn(&I) -> (f64, f64),
+{
+    let num_nodes = (items.len() + NODE_CAPACITY - 1) / NODE_CAPACITY;
+    let num_slices = (num_nodes as f64).sqrt().ceil() as usize;
+    let slice_size = num_slices.max(1) * NODE_CAPACITY;
+    items.sort_by(|a, b| {
+        centre(a)
+            .0
+            .partial_cmp(&centre(b).0)
+            .unwrap_or(Ordering::Equal)
+    });
+    for slice in items.chunks_mut(slice_size) {
+        slice.sort_by(|a, b| {
+            centre(a)
+                .1
+                .partial_cmp(&centre(b).1)
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RTree;
+    use crate::structures::BoundingBox;
+    use std::f64;
+
+    fn grid_tree() -> RTree<usize> {
+        // a 30 x 30 grid of unit boxes with one-unit gaps
+        let mut entries = vec![];
+        for row in 0..30 {
+            for col in 0..30 {
+                let (x, y) = (col as f64 * 2.0, row as f64 * 2.0);
+                entries.push((BoundingBox::new(x, x + 1.0, y, y + 1.0), row * 30 + col));
+            }
+        }
+        RTree::new(entries)
+    }
+
+    #[test]
+    fn test_rtree_search() {
+        let tree = grid_tree();
+        assert_eq!(tree.len(), 900);
+        let mut found = tree.search(BoundingBox::new(1.5, 4.0, 0.5, 2.5));
+        found.sort();
+        assert_eq!(found, vec![1, 2, 31, 32]);
+        assert!(tree.search(BoundingBox::new(1.2, 1.8, 1.2, 1.8)).is_empty());
+    }
+
+    #[test]
+    fn test_rtree_nearest() {
+        let tree = grid_tree();
+        // distances to the box centres
+        let centre_dist = |i: usize| {
+            let (cx, cy) = ((i % 30) as f64 * 2.0 + 0.5, (i / 30) as f64 * 2.0 + 0.5);
+            ((cx - 13.4f64).powi(2) + (cy - 21.3f64).powi(2)).sqrt()
+        };
+        let query = BoundingBox::new(13.4, 13.4, 21.3, 21.3);
+        let (value, dist) = tree.nearest(query, f64::INFINITY, centre_dist).unwrap();
+        let expected = (0..900)
+            .min_by(|a, b| centre_dist(*a).partial_cmp(&centre_dist(*b)).unwrap())
+            .unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(dist, centre_dist(expected));
+        assert!(tree.nearest(query, 0.1, centre_dist).is_none());
+
+        let empty: RTree<usize> = RTree::new(vec![]);
+        assert!(empty.nearest(query, f64::INFINITY, |_| 0.0).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Empirical semivariogram estimation and fitting of the common authorized
+semivariogram models (spherical, exponential and Gaussian). The range parameter
+is the practical (effective) range, i.e. the lag at which the model reaches 95%
+of the sill for the asymptotic models.
+*/
+
+use crate::structures::Point2D;
+use std::f64;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VariogramModel {
+    Spherical,
+    Exponential,
+    Gaussian,
+}
+
+impl VariogramModel {
+    pub fn from_name(name: &str) -> Option<VariogramModel> {
+        let s = name.to_lowercase();
+        if s.contains("sph") {
+            Some(VariogramModel::Spherical)
+        } else if s.contains("exp") {
+            Some(VariogramModel::Exponential)
+        } else if s.contains("gau") {
+            Some(VariogramModel::Gaussian)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the normalized model shape (0 at h = 0, approaching 1 at the range).
+    fn shape(&self, h: f64, range: f64) -> f64 {
+        if h <= 0f64 {
+            return 0f64;
+        }
+        let r = h / range;
+        match self {
+            VariogramModel::Spherical => {
+                if r >= 1f64 {
+                    1f64
+                } else {
+                    1.5 * r - 0.5 * r * r * r
+                }
+            }
+            VariogramModel::Exponential => 1f64 - (-3f64 * r).exp(),
+            VariogramModel::Gaussian => 1f64 - (-3f64 * r * r).exp(),
+        }
+    }
+}
+
+impl fmt::Display for VariogramModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            VariogramModel::Spherical => "spherical",
+            VariogramModel::Exponential => "exponential",
+            VariogramModel::Gaussian => "gaussian",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An experimental (empirical) semivariogram, binned into equal-width lag classes.
+pub struct EmpiricalSemivariogram {
+    pub lag_size: f64,
+    /// Mean separation distance of the point pairs in each lag class.
+    pub distances: Vec<f64>,
+    /// Semivariance of each lag class.
+    pub gamma: Vec<f64>,
+    /// Number of point pairs in each lag class.
+    pub num_pairs: Vec<usize>,
+}
+
+impl EmpiricalSemivariogram {
+    /// Calculates the Matheron estimator of semivariance for `num_lags` lag classes of
+    /// width `lag_size`. Empty lag classes are excluded from the output.
+    pub fn new(
+        points: &[Point2D],
+        z_values: &[f64],
+        lag_size: f64,
+        num_lags: usize,
+    ) -> EmpiricalSemivariogram {
+        let max_dist = lag_size * num_lags as f64;
+        let mut sum_dist = vec![0f64; num_lags];
+        let mut sum_sqr_diff = vec![0f64; num_lags];
+        let mut counts = vec![0usize; num_lags];
+        let n = points.len().min(z_values.len());
+        let (mut dist, mut diff): (f64, f64);
+        let mut bin: usize;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                dist = points[i].distance(&points[j]);
+                if dist > 0f64 && dist < max_dist {
+                    bin = ((dist / lag_size).floor() as usize).min(num_lags - 1);
+                    diff = z_values[i] - z_values[j];
+                    sum_dist[bin] += dist;
+                    sum_sqr_diff[bin] += diff * diff;
+                    counts[bin] += 1;
+                }
+            }
+        }
+
+        let mut distances = vec![];
+        let mut gamma = vec![];
+        let mut num_pairs = vec![];
+        for bin in 0..num_lags {
+            if counts[bin] > 0 {
+                distances.push(sum_dist[bin] / counts[bin] as f64);
+                gamma.push(sum_sqr_diff[bin] / (2f64 * counts[bin] as f64));
+                num_pairs.push(counts[bin]);
+            }
+        }
+
+        EmpiricalSemivariogram {
+            lag_size: lag_size,
+            distances: distances,
+            gamma: gamma,
+            num_pairs: num_pairs,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.gamma.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gamma.is_empty()
+    }
+}
+
+/// A theoretical semivariogram model.
+#[derive(Clone, Copy, Debug)]
+pub struct Semivariogram {
+    pub model: VariogramModel,
+    pub nugget: f64,
+    /// The partial sill, i.e. the total sill minus the nugget.
+    pub sill: f64,
+    pub range: f64,
+}
+
+impl Semivariogram {
+    pub fn new(model: VariogramModel, nugget: f64, sill: f64, range: f64) -> Semivariogram {
+        Semivariogram {
+            model: model,
+            nugget: nugget,
+            sill: sill,
+            range: range,
+        }
+    }
+
+    /// Fits the model to an empirical semivariogram using weighted least-squares, with
+    /// weights equal to the number of pairs divided by the squared lag distance. For each
+    /// candidate range, the nugget and partial sill are solved for directly, constrained
+    /// to be non-negative. Returns None if there are too few lag classes to fit.
+    pub fn fit(model: VariogramModel, empirical: &EmpiricalSemivariogram) -> Option<Semivariogram> {
+        let n = empirical.len();
+        if n < 2 {
+            return None;
+        }
+        let weights: Vec<f64> = (0..n)
+            .map(|i| {
+                let h = empirical.distances[i].max(f64::EPSILON);
+                empirical.num_pairs[i] as f64 / (h * h)
+            })
+            .collect();
+        let max_lag = empirical.distances[n - 1];
+        let min_range = empirical.distances[0].min(empirical.lag_size);
+        const NUM_CANDIDATES: usize = 250;
+
+        let mut best: Option<Semivariogram> = None;
+        let mut best_sse = f64::INFINITY;
+        for c in 0..NUM_CANDIDATES {
+            let range =
+                min_range + (2f64 * max_lag - min_range) * c as f64 / (NUM_CANDIDATES - 1) as f64;
+            let f: Vec<f64> = empirical
+                .distances
+                .iter()
+                .map(|&h| model.shape(h, range))
+                .collect();
+
+            // weighted normal equations for gamma = nugget + sill * f
+            let (mut sw, mut swf, mut swff, mut swg, mut swfg) = (0f64, 0f64, 0f64, 0f64, 0f64);
+            for i in 0..n {
+                let w = weights[i];
+                let g = empirical.gamma[i];
+                sw += w;
+                swf += w * f[i];
+                swff += w * f[i] * f[i];
+                swg += w * g;
+                swfg += w * f[i] * g;
+            }
+            let det = sw * swff - swf * swf;
+            let (mut nugget, mut sill) = if det.abs() > f64::EPSILON * sw * swff {
+                (
+                    (swff * swg - swf * swfg) / det,
+                    (sw * swfg - swf * swg) / det,
+                )
+            } else {
+                (0f64, if swff > 0f64 { swfg / swff } else { 0f64 })
+            };
+            if nugget < 0f64 {
+                nugget = 0f64;
+                sill = if swff > 0f64 { swfg / swff } else { 0f64 };
+            }
+            if sill < 0f64 {
+                sill = 0f64;
+                nugget = swg / sw;
+            }
+
+            let mut sse = 0f64;
+            for i in 0..n {
+                let r = empirical.gamma[i] - (nugget + sill * f[i]);
+                sse += weights[i] * r * r;
+            }
+            if sse < best_sse {
+                best_sse = sse;
+                best = Some(Semivariogram::new(model, nugget, sill, range));
+            }
+        }
+        best
+    }
+
+    /// Returns the modelled semivariance at lag distance h.
+    pub fn value(&self, h: f64) -> f64 {
+        if h <= 0f64 {
+            return 0f64;
+        }
+        self.nugget + self.sill * self.model.shape(h, self.range)
+    }
+
+    /// Returns the modelled covariance at lag distance h.
+    pub fn covariance(&self, h: f64) -> f64 {
+        self.nugget + self.sill - self.value(h)
+    }
+
+    /// Returns the weighted sum of squared residuals relative to an empirical semivariogram.
+    pub fn weighted_sse(&self, empirical: &EmpiricalSemivariogram) -> f64 {
+        let mut sse = 0f64;
+        for i in 0..empirical.len() {
+            let h = empirical.distances[i].max(f64::EPSILON);
+            let r = empirical.gamma[i] - self.value(h);
+            sse += empirical.num_pairs[i] as f64 / (h * h) * r * r;
+        }
+        sse
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_semivariogram_fit() {
+        let truth = Semivariogram::new(VariogramModel::Spherical, 2.0, 10.0, 40.0);
+        let distances: Vec<f64> = (0..20).map(|i| 2.5 + 5.0 * i as f64).collect();
+        let empirical = EmpiricalSemivariogram {
+            lag_size: 5.0,
+            gamma: distances.iter().map(|&h| truth.value(h)).collect(),
+            num_pairs: vec![100; distances.len()],
+            distances: distances,
+        };
+        let fitted = Semivariogram::fit(VariogramModel::Spherical, &empirical).unwrap();
+        assert!((fitted.nugget - 2.0).abs() < 0.1);
+        assert!((fitted.sill - 10.0).abs() < 0.2);
+        assert!((fitted.range - 40.0).abs() < 1.0);
+        assert_eq!(truth.value(0.0), 0.0);
+        assert_eq!(truth.covariance(100.0), 0.0);
+    }
+
+    #[test]
+    fn test_empirical_semivariogram() {
+        let points = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(3.0, 0.0),
+        ];
+        let z = vec![0.0, 2.0, 4.0];
+        let empirical = EmpiricalSemivariogram::new(&points, &z, 1.5, 2);
+        // the pair separated by 3.0 falls beyond the last lag class
+        assert_eq!(empirical.len(), 2);
+        assert_eq!(empirical.num_pairs, vec![1, 1]);
+        assert_eq!(empirical.gamma, vec![2.0, 2.0]);
+    }
+}
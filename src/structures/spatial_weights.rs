@@ -0,0 +1,705 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Spatial weights and the global (Moran's I, Geary's C) and local (Moran's I,
+Geary's c, Getis-Ord Gi*) spatial autocorrelation statistics computed with them.
+Weights are either stored explicitly, as neighbour lists (e.g. for vector features),
+or implied by a neighbourhood of grid cells (for rasters), which avoids storing the
+neighbours of every cell. Local statistics are tested against conditional
+randomization, i.e. the value of a feature is held fixed while the values of its
+neighbours are drawn at random from those of the other features.
+*/
+
+use crate::structures::Point2D;
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use rand::seq::index::sample;
+use rand::Rng;
+use std::collections::HashMap;
+use std::f64;
+
+/// A spatial weights matrix, w_ij, relating each of a set of features, numbered from 0, to
+/// its neighbours.
+pub trait SpatialWeights {
+    /// Returns the number of features.
+    fn len(&self) -> usize;
+
+    /// Calls `f(j, w_ij)` for each neighbour j of feature i with a non-zero weight.
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, i: usize, f: F);
+
+    /// Returns the weight w_ij, which is zero if j is not a neighbour of i.
+    fn weight(&self, i: usize, j: usize) -> f64;
+}
+
+/// Spatial weights stored as a list of (neighbour, weight) pairs for each feature.
+#[derive(Clone, Debug, Default)]
+pub struct WeightsList {
+    pub neighbours: Vec<Vec<(usize, f64)>>,
+}
+
+impl WeightsList {
+    /// Creates binary contiguity weights for polygon features, each described by its rings.
+    /// Features are queen's contiguous if they share a vertex and rook's contiguous if they
+    /// share an edge. Shared vertices must have identical coordinates.
+    pub fn from_polygon_contiguity(features: &[Vec<Vec<Point2D>>], rook: bool) -> WeightsList {
+        let n = features.len();
+        let mut shared: HashMap<(u64, u64, u64, u64), Vec<usize>> = HashMap::new();
+        for (i, rings) in features.iter().enumerate() {
+            for ring in rings {
+                for k in 0..ring.len() {
+                    let a = vertex_key(&ring[k]);
+                    let key = if rook {
+                        if k + 1 == ring.len() {
+                            break;
+                        }
+                        let b = vertex_key(&ring[k + 1]);
+                        if a == b {
+                            continue;
+                        }
+                        if a < b {
+                            (a.0, a.1, b.0, b.1)
+                        } else {
+                            (b.0, b.1, a.0, a.1)
+                        }
+                    } else {
+                        (a.0, a.1, 0, 0)
+                    };
+                    let list = shared.entry(key).or_insert(vec![]);
+                    if list.last() != Some(&i) {
+                        list.push(i);
+                    }
+                }
+            }
+        }
+
+        let mut neighbours: Vec<Vec<usize>> = vec![vec![]; n];
+        for list in shared.values() {
+            for a in 0..list.len() {
+                for b in 0..list.len() {
+                    if list[a] != list[b] {
+                        neighbours[list[a]].push(list[b]);
+                    }
+                }
+            }
+        }
+        WeightsList::from_neighbour_sets(neighbours)
+    }
+
+    /// Creates binary weights in which each feature's neighbours are the other features
+    /// within `distance` of it.
+    pub fn from_distance_band(points: &[Point2D], distance: f64) -> WeightsList {
+        let tree = build_tree(points);
+        let mut neighbours: Vec<Vec<usize>> = vec![vec![]; points.len()];
+        for i in 0..points.len() {
+            if let Ok(ret) = tree.within(
+                &[points[i].x, points[i].y],
+                distance * distance,
+                &squared_euclidean,
+            ) {
+                neighbours[i] = ret
+                    .into_iter()
+                    .map(|(_, &j)| j)
+                    .filter(|&j| j != i)
+                    .collect();
+            }
+        }
+        WeightsList::from_neighbour_sets(neighbours)
+    }
+
+    /// Creates binary weights in which each feature's neighbours are its `k` nearest
+    /// features. These weights are generally asymmetric.
+    pub fn from_k_nearest(points: &[Point2D], k: usize) -> WeightsList {
+        let tree = build_tree(points);
+        let mut neighbours: Vec<Vec<usize>> = vec![vec![]; points.len()];
+        for i in 0..points.len() {
+            if let Ok(ret) = tree.nearest(&[points[i].x, points[i].y], k + 1, &squared_euclidean) {
+                neighbours[i] = ret
+                    .into_iter()
+                    .map(|(_, &j)| j)
+                    .filter(|&j| j != i)
+                    .take(k)
+                    .collect();
+            }
+        }
+        WeightsList::from_neighbour_sets(neighbours)
+    }
+
+    fn from_neighbour_sets(mut neighbours: Vec<Vec<usize>>) -> WeightsList {
+        WeightsList {
+            neighbours: neighbours
+                .iter_mut()
+                .map(|list| {
+                    list.sort();
+                    list.dedup();
+                    list.iter().map(|&j| (j, 1f64)).collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Scales the weights of each feature such that they sum to one.
+    pub fn row_standardize(&mut self) {
+        for list in self.neighbours.iter_mut() {
+            let sum: f64 = list.iter().map(|(_, w)| w).sum();
+            if sum > 0f64 {
+                for entry in list.iter_mut() {
+                    entry.1 /= sum;
+                }
+            }
+        }
+    }
+
+    /// Returns the number of features without neighbours.
+    pub fn num_islands(&self) -> usize {
+        self.neighbours
+            .iter()
+            .filter(|list| list.is_empty())
+            .count()
+    }
+}
+
+impl SpatialWeights for WeightsList {
+    fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, i: usize, mut f: F) {
+        for &(j, w) in &self.neighbours[i] {
+            f(j, w);
+        }
+    }
+
+    fn weight(&self, i: usize, j: usize) -> f64 {
+        match self.neighbours[i].binary_search_by(|entry| entry.0.cmp(&j)) {
+            Ok(k) => self.neighbours[i][k].1,
+            Err(_) => 0f64,
+        }
+    }
+}
+
+/// Converts a vertex into a hashable key; adding zero maps -0.0 to 0.0.
+fn vertex_key(p: &Point2D) -> (u64, u64) {
+    ((p.x + 0f64).to_bits(), (p.y + 0f64).to_bits())
+}
+
+fn build_tree(points: &[Point2D]) -> KdTree<f64, usize, [f64; 2]> {
+    let mut tree = KdTree::with_capacity(2, 64);
+    for (i, p) in points.iter().enumerate() {
+        tree.add([p.x, p.y], i).unwrap();
+    }
+    tree
+}
+
+/// Binary (or row-standardized) spatial weights of the valid cells of a grid, each of
+/// which is a feature. The neighbours of a cell are the valid cells at the specified
+/// (row, column) offsets, which may differ between even and odd rows (e.g. for
+/// hexagonal grids). Each set of offsets must be symmetric, i.e. if a cell is a
+/// neighbour of another, the reverse must also be true.
+pub struct GridWeights {
+    rows: isize,
+    columns: isize,
+    index: Vec<usize>,
+    cells: Vec<(isize, isize)>,
+    offsets: [Vec<(isize, isize)>; 2],
+    scale: Vec<f64>,
+}
+
+const NO_FEATURE: usize = usize::MAX;
+
+impl GridWeights {
+    pub fn new<F: Fn(isize, isize) -> bool>(
+        rows: isize,
+        columns: isize,
+        is_valid: F,
+        even_row_offsets: Vec<(isize, isize)>,
+        odd_row_offsets: Vec<(isize, isize)>,
+        row_standardize: bool,
+    ) -> GridWeights {
+        let mut index = vec![NO_FEATURE; (rows * columns) as usize];
+        let mut cells = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if is_valid(row, col) {
+                    index[(row * columns + col) as usize] = cells.len();
+                    cells.push((row, col));
+                }
+            }
+        }
+        let mut weights = GridWeights {
+            rows: rows,
+            columns: columns,
+            index: index,
+            cells: cells,
+            offsets: [even_row_offsets, odd_row_offsets],
+            scale: vec![],
+        };
+        weights.scale = (0..weights.cells.len())
+            .map(|i| {
+                let mut count = 0usize;
+                weights.for_each_neighbour(i, |_, _| count += 1);
+                if row_standardize && count > 0 {
+                    1f64 / count as f64
+                } else {
+                    1f64
+                }
+            })
+            .collect();
+        weights
+    }
+
+    /// Returns the (row, column) of a feature.
+    pub fn cell(&self, i: usize) -> (isize, isize) {
+        self.cells[i]
+    }
+
+    /// Returns the feature number of a cell, or `None` if the cell is not valid.
+    pub fn feature(&self, row: isize, column: isize) -> Option<usize> {
+        if row < 0 || column < 0 || row >= self.rows || column >= self.columns {
+            return None;
+        }
+        match self.index[(row * self.columns + column) as usize] {
+            NO_FEATURE => None,
+            i => Some(i),
+        }
+    }
+}
+
+impl SpatialWeights for GridWeights {
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn for_each_neighbour<F: FnMut(usize, f64)>(&self, i: usize, mut f: F) {
+        let (row, col) = self.cells[i];
+        // the scale is unset while it is being calculated
+        let w = if self.scale.is_empty() {
+            1f64
+        } else {
+            self.scale[i]
+        };
+        for &(dr, dc) in &self.offsets[(row & 1) as usize] {
+            if let Some(j) = self.feature(row + dr, col + dc) {
+                f(j, w);
+            }
+        }
+    }
+
+    fn weight(&self, i: usize, j: usize) -> f64 {
+        let (row, col) = self.cells[i];
+        let (row_j, col_j) = self.cells[j];
+        if self.offsets[(row & 1) as usize].contains(&(row_j - row, col_j - col)) {
+            self.scale[i]
+        } else {
+            0f64
+        }
+    }
+}
+
+/// The sums of the weights used in the moments of the global statistics.
+#[derive(Clone, Copy, Debug)]
+pub struct WeightsSummary {
+    /// The sum of the weights.
+    pub s0: f64,
+    /// Half the sum over all pairs of (w_ij + w_ji)^2.
+    pub s1: f64,
+    /// The sum over features of the squared sum of their row and column weights.
+    pub s2: f64,
+}
+
+impl WeightsSummary {
+    pub fn new<W: SpatialWeights>(weights: &W) -> WeightsSummary {
+        let n = weights.len();
+        let mut row_sums = vec![0f64; n];
+        let mut column_sums = vec![0f64; n];
+        let mut s1 = 0f64;
+        for i in 0..n {
+            weights.for_each_neighbour(i, |j, w| {
+                row_sums[i] += w;
+                column_sums[j] += w;
+                s1 += w * w + w * weights.weight(j, i);
+            });
+        }
+        WeightsSummary {
+            s0: row_sums.iter().sum(),
+            s1: s1,
+            s2: (0..n)
+                .map(|i| (row_sums[i] + column_sums[i]) * (row_sums[i] + column_sums[i]))
+                .sum(),
+        }
+    }
+}
+
+/// The sums of the powers of the deviations of a set of values from their mean.
+#[derive(Clone, Copy, Debug)]
+pub struct Moments {
+    pub n: usize,
+    pub mean: f64,
+    pub sum2: f64,
+    pub sum3: f64,
+    pub sum4: f64,
+}
+
+impl Moments {
+    pub fn new(values: &[f64]) -> Moments {
+        let n = values.len();
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let (mut sum2, mut sum3, mut sum4) = (0f64, 0f64, 0f64);
+        for x in values {
+            let z = x - mean;
+            sum2 += z * z;
+            sum3 += z * z * z;
+            sum4 += z * z * z * z;
+        }
+        Moments {
+            n: n,
+            mean: mean,
+            sum2: sum2,
+            sum3: sum3,
+            sum4: sum4,
+        }
+    }
+
+    /// Returns the sample kurtosis, m4 / m2^2.
+    pub fn kurtosis(&self) -> f64 {
+        self.n as f64 * self.sum4 / (self.sum2 * self.sum2)
+    }
+}
+
+/// A statistic and its expected value and variance under the null hypothesis of no
+/// spatial autocorrelation.
+#[derive(Clone, Copy, Debug)]
+pub struct AutocorrelationTest {
+    pub statistic: f64,
+    pub expected: f64,
+    pub variance: f64,
+}
+
+impl AutocorrelationTest {
+    pub fn z_score(&self) -> f64 {
+        (self.statistic - self.expected) / self.variance.sqrt()
+    }
+}
+
+/// Calculates the global Moran's I of a set of values.
+pub fn morans_i<W: SpatialWeights>(weights: &W, values: &[f64], moments: &Moments) -> f64 {
+    let mut cross_products = 0f64;
+    let mut s0 = 0f64;
+    for i in 0..values.len() {
+        let z = values[i] - moments.mean;
+        weights.for_each_neighbour(i, |j, w| {
+            cross_products += w * z * (values[j] - moments.mean);
+            s0 += w;
+        });
+    }
+    moments.n as f64 * cross_products / (s0 * moments.sum2)
+}
+
+/// Calculates the global Geary's C of a set of values.
+pub fn gearys_c<W: SpatialWeights>(weights: &W, values: &[f64], moments: &Moments) -> f64 {
+    let mut sum_sqr_diff = 0f64;
+    let mut s0 = 0f64;
+    for i in 0..values.len() {
+        weights.for_each_neighbour(i, |j, w| {
+            sum_sqr_diff += w * (values[i] - values[j]) * (values[i] - values[j]);
+            s0 += w;
+        });
+    }
+    (moments.n as f64 - 1f64) * sum_sqr_diff / (2f64 * s0 * moments.sum2)
+}
+
+/// Tests the global Moran's I of a set of values under the randomization assumption.
+pub fn morans_i_test<W: SpatialWeights>(
+    weights: &W,
+    summary: &WeightsSummary,
+    values: &[f64],
+) -> AutocorrelationTest {
+    let moments = Moments::new(values);
+    let n = moments.n as f64;
+    let b2 = moments.kurtosis();
+    let (s0, s1, s2) = (summary.s0, summary.s1, summary.s2);
+    let expected = -1f64 / (n - 1f64);
+    let variance = (n * ((n * n - 3f64 * n + 3f64) * s1 - n * s2 + 3f64 * s0 * s0)
+        - b2 * ((n * n - n) * s1 - 2f64 * n * s2 + 6f64 * s0 * s0))
+        / ((n - 1f64) * (n - 2f64) * (n - 3f64) * s0 * s0)
+        - expected * expected;
+    AutocorrelationTest {
+        statistic: morans_i(weights, values, &moments),
+        expected: expected,
+        variance: variance,
+    }
+}
+
+/// Tests the global Geary's C of a set of values under the randomization assumption.
+pub fn gearys_c_test<W: SpatialWeights>(
+    weights: &W,
+    summary: &WeightsSummary,
+    values: &[f64],
+) -> AutocorrelationTest {
+    let moments = Moments::new(values);
+    let n = moments.n as f64;
+    let b2 = moments.kurtosis();
+    let (s0, s1, s2) = (summary.s0, summary.s1, summary.s2);
+    let variance = ((n - 1f64) * s1 * (n * n - 3f64 * n + 3f64 - (n - 1f64) * b2)
+        - 0.25 * (n - 1f64) * s2 * (n * n + 3f64 * n - 6f64 - (n * n - n + 2f64) * b2)
+        + s0 * s0 * (n * n - 3f64 - (n - 1f64) * (n - 1f64) * b2))
+        / (n * (n - 2f64) * (n - 3f64) * s0 * s0);
+    AutocorrelationTest {
+        statistic: gearys_c(weights, values, &moments),
+        expected: 1f64,
+        variance: variance,
+    }
+}
+
+/// Returns the folded pseudo p-value of a statistic, i.e. the proportion of the simulated
+/// statistics, including the observed statistic, that are at least as extreme as the
+/// observed statistic in the direction of the smaller tail.
+pub fn pseudo_p_value(observed: f64, simulated: &[f64]) -> f64 {
+    let num_sims = simulated.len();
+    let mut larger = simulated.iter().filter(|&&s| s >= observed).count();
+    if num_sims - larger < larger {
+        larger = num_sims - larger;
+    }
+    (larger + 1) as f64 / (num_sims + 1) as f64
+}
+
+/// The local indicators of spatial association.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocalStatistic {
+    MoransI,
+    GearysC,
+    GetisOrdGiStar,
+}
+
+impl LocalStatistic {
+    pub fn from_name(name: &str) -> Option<LocalStatistic> {
+        let name = name.trim().to_lowercase();
+        if name.contains("moran") {
+            Some(LocalStatistic::MoransI)
+        } else if name.contains("geary") {
+            Some(LocalStatistic::GearysC)
+        } else if name.contains("getis") || name.contains("gi") {
+            Some(LocalStatistic::GetisOrdGiStar)
+        } else {
+            None
+        }
+    }
+}
+
+/// A local statistic, its z-score, and its pseudo p-value (NaN without permutations).
+#[derive(Clone, Copy, Debug)]
+pub struct LocalTest {
+    pub statistic: f64,
+    pub z_score: f64,
+    pub pseudo_p: f64,
+}
+
+/// Calculates and tests a local statistic for feature `i`, returning `None` if the
+/// feature has no neighbours. The z-scores of local Moran's I and Geary's c are based on
+/// the moments of the statistic under conditional randomization; the Gi* statistic is
+/// reported as the ratio of the weighted sum of the values of the feature and its
+/// neighbours to the sum of all values, and its z-score is that of Getis and Ord (1995).
+/// Gi* includes the feature itself with a weight equal to the largest weight of its
+/// neighbours. Pseudo p-values are based on `permutations` conditional permutations.
+pub fn local_test<W: SpatialWeights, R: Rng>(
+    weights: &W,
+    values: &[f64],
+    moments: &Moments,
+    i: usize,
+    statistic: LocalStatistic,
+    permutations: usize,
+    rng: &mut R,
+) -> Option<LocalTest> {
+    let mut neighbours = vec![];
+    weights.for_each_neighbour(i, |j, w| neighbours.push((j, w)));
+    let n = moments.n;
+    if neighbours.is_empty() || n < 3 {
+        return None;
+    }
+    let nf = n as f64;
+    let m2 = moments.sum2 / nf;
+    let sum_w: f64 = neighbours.iter().map(|(_, w)| w).sum();
+    let sum_w2: f64 = neighbours.iter().map(|(_, w)| w * w).sum();
+    let xi = values[i];
+    let zi = xi - moments.mean;
+
+    // the local statistic given the values of the neighbours
+    let self_weight = neighbours.iter().fold(0f64, |m, &(_, w)| m.max(w));
+    let sum_x = moments.mean * nf;
+    let local = |neighbour_values: &mut dyn Iterator<Item = (f64, f64)>| -> f64 {
+        match statistic {
+            LocalStatistic::MoransI => {
+                zi / m2
+                    * neighbour_values
+                        .map(|(w, x)| w * (x - moments.mean))
+                        .sum::<f64>()
+            }
+            LocalStatistic::GearysC => {
+                neighbour_values
+                    .map(|(w, x)| w * (xi - x) * (xi - x))
+                    .sum::<f64>()
+                    / m2
+            }
+            LocalStatistic::GetisOrdGiStar => {
+                (self_weight * xi + neighbour_values.map(|(w, x)| w * x).sum::<f64>()) / sum_x
+            }
+        }
+    };
+    let observed = local(&mut neighbours.iter().map(|&(j, w)| (w, values[j])));
+
+    // the moments of a weighted sum of values drawn without replacement from the
+    // n - 1 other features, given their mean and (population) variance
+    let others = nf - 1f64;
+    let sum_moments = |mean: f64, variance: f64| {
+        (
+            sum_w * mean,
+            variance * (sum_w2 - (sum_w * sum_w - sum_w2) / (others - 1f64)),
+        )
+    };
+    let z_score = match statistic {
+        LocalStatistic::MoransI => {
+            let mean = -zi / others;
+            let variance = (moments.sum2 - zi * zi) / others - mean * mean;
+            let (e, v) = sum_moments(mean, variance);
+            let scale = zi / m2;
+            (observed - scale * e) / (scale * scale * v).sqrt()
+        }
+        LocalStatistic::GearysC => {
+            // the squared differences between the feature's value and the others'
+            let sum_d = nf * zi * zi + moments.sum2;
+            let sum_d2 = nf * zi * zi * zi * zi + 6f64 * zi * zi * moments.sum2
+                - 4f64 * zi * moments.sum3
+                + moments.sum4;
+            let mean = sum_d / others;
+            let variance = sum_d2 / others - mean * mean;
+            let (e, v) = sum_moments(mean, variance);
+            (observed - e / m2) / (v / (m2 * m2)).sqrt()
+        }
+        LocalStatistic::GetisOrdGiStar => {
+            let w_star = sum_w + self_weight;
+            let w2_star = sum_w2 + self_weight * self_weight;
+            let s = (moments.sum2 / nf).sqrt();
+            (observed * sum_x - moments.mean * w_star)
+                / (s * ((nf * w2_star - w_star * w_star) / (nf - 1f64)).sqrt())
+        }
+    };
+
+    let pseudo_p = if permutations > 0 && neighbours.len() < n {
+        let k = neighbours.len();
+        let mut simulated = Vec::with_capacity(permutations);
+        for _ in 0..permutations {
+            let drawn = sample(rng, n - 1, k);
+            simulated.push(local(
+                &mut drawn
+                    .iter()
+                    .zip(neighbours.iter())
+                    .map(|(j, &(_, w))| (w, values[if j >= i { j + 1 } else { j }])),
+            ));
+        }
+        pseudo_p_value(observed, &simulated)
+    } else {
+        f64::NAN
+    };
+
+    Some(LocalTest {
+        statistic: observed,
+        z_score: z_score,
+        pseudo_p: pseudo_p,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    fn square(row: f64, col: f64) -> Vec<Vec<Point2D>> {
+        vec![vec![
+            Point2D::new(col, row),
+            Point2D::new(col, row + 1.0),
+            Point2D::new(col + 1.0, row + 1.0),
+            Point2D::new(col + 1.0, row),
+            Point2D::new(col, row),
+        ]]
+    }
+
+    #[test]
+    fn test_polygon_contiguity() {
+        // a 2 x 2 block of squares
+        let features = vec![
+            square(0.0, 0.0),
+            square(0.0, 1.0),
+            square(1.0, 0.0),
+            square(1.0, 1.0),
+        ];
+        let queen = WeightsList::from_polygon_contiguity(&features, false);
+        assert_eq!(queen.neighbours[0].len(), 3);
+        let rook = WeightsList::from_polygon_contiguity(&features, true);
+        assert_eq!(rook.neighbours[0].len(), 2);
+        assert_eq!(rook.weight(0, 3), 0.0);
+        assert_eq!(rook.weight(0, 1), 1.0);
+    }
+
+    #[test]
+    fn test_global_statistics() {
+        // a checkerboard is perfectly dispersed under rook's contiguity
+        let rows = 6;
+        let columns = 6;
+        let values: Vec<f64> = (0..rows * columns)
+            .map(|i| ((i / columns + i % columns) % 2) as f64)
+            .collect();
+        let weights = GridWeights::new(
+            rows as isize,
+            columns as isize,
+            |_, _| true,
+            vec![(-1, 0), (0, 1), (1, 0), (0, -1)],
+            vec![(-1, 0), (0, 1), (1, 0), (0, -1)],
+            false,
+        );
+        let summary = WeightsSummary::new(&weights);
+        assert_eq!(summary.s0, 2.0 * (2 * rows * (columns - 1)) as f64);
+        let moran = morans_i_test(&weights, &summary, &values);
+        assert!((moran.statistic + 1.0).abs() < 1e-10);
+        assert!(moran.z_score() < -5.0);
+        let geary = gearys_c_test(&weights, &summary, &values);
+        assert!(geary.statistic > 1.0);
+    }
+
+    #[test]
+    fn test_local_statistics() {
+        let points: Vec<Point2D> = (0..10).map(|i| Point2D::new(i as f64, 0.0)).collect();
+        let values: Vec<f64> = (0..10).map(|i| if i < 5 { 1.0 } else { 10.0 }).collect();
+        let weights = WeightsList::from_k_nearest(&points, 2);
+        let moments = Moments::new(&values);
+        let mut rng = SmallRng::seed_from_u64(1);
+        let moran = local_test(
+            &weights,
+            &values,
+            &moments,
+            0,
+            LocalStatistic::MoransI,
+            99,
+            &mut rng,
+        )
+        .unwrap();
+        assert!(moran.statistic > 0.0);
+        assert!(moran.z_score > 0.0);
+        assert!(moran.pseudo_p > 0.0 && moran.pseudo_p <= 1.0);
+        let gi = local_test(
+            &weights,
+            &values,
+            &moments,
+            9,
+            LocalStatistic::GetisOrdGiStar,
+            0,
+            &mut rng,
+        )
+        .unwrap();
+        assert!(gi.z_score > 0.0);
+        assert!(gi.pseudo_p.is_nan());
+    }
+}
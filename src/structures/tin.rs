@@ -0,0 +1,614 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{triangulate, Triangulation, EMPTY};
+use crate::structures::Point2D;
+use crate::vector::{ShapeType, ShapeTypeDimension, Shapefile};
+use std::collections::{HashSet, VecDeque};
+use std::f64;
+use std::io::{Error, ErrorKind};
+
+/// A triangulated irregular network (TIN), i.e. a Delaunay triangulation of a set of
+/// points with associated z values. The triangulation may optionally be constrained to
+/// include breakline edges, in which case it is a constrained Delaunay triangulation.
+///
+/// Breaklines are inserted by flipping the edges that they cross (Sloan, 1993), and so
+/// their vertices must be included in the set of points used to construct the TIN.
+pub struct Tin {
+    /// The TIN vertices.
+    pub points: Vec<Point2D>,
+    /// The z value of each vertex.
+    pub z_values: Vec<f64>,
+    /// The underlying triangulation.
+    pub triangulation: Triangulation,
+    constrained_edges: HashSet<(usize, usize)>,
+    in_triangulation: Vec<bool>,
+}
+
+impl Tin {
+    /// Triangulates a set of points. Returns `None` if no triangulation exists for the input
+    /// (e.g. all points are collinear).
+    pub fn new(points: Vec<Point2D>, z_values: Vec<f64>) -> Option<Tin> {
+        if points.len() != z_values.len() {
+            panic!("Error creating TIN. Points and z_values must be equal length.");
+        }
+        let triangulation = triangulate(&points)?;
+        let mut in_triangulation = vec![false; points.len()];
+        for p in &triangulation.triangles {
+            in_triangulation[*p] = true;
+        }
+        Some(Tin {
+            points: points,
+            z_values: z_values,
+            triangulation: triangulation,
+            constrained_edges: HashSet::new(),
+            in_triangulation: in_triangulation,
+        })
+    }
+
+    /// The number of triangles in the TIN.
+    pub fn num_triangles(&self) -> usize {
+        self.triangulation.len()
+    }
+
+    /// Returns the vertex indices of a triangle.
+    pub fn points_of_triangle(&self, triangle: usize) -> [usize; 3] {
+        self.triangulation.points_of_triangle(triangle)
+    }
+
+    /// Returns true if the edge between two vertices is part of a breakline.
+    pub fn is_constrained_edge(&self, p1: usize, p2: usize) -> bool {
+        self.constrained_edges.contains(&edge_key(p1, p2))
+    }
+
+    /// Returns true if the two vertices are connected by a triangle edge.
+    pub fn has_edge(&self, p1: usize, p2: usize) -> bool {
+        let t = &self.triangulation;
+        (0..t.triangles.len()).any(|e| {
+            let (a, b) = (t.triangles[e], t.triangles[t.next_halfedge(e)]);
+            (a == p1 && b == p2) || (a == p2 && b == p1)
+        })
+    }
+
+    /// Inserts a breakline, specified by the indices of its vertices, into the TIN. Returns
+    /// false if one or more breakline segments could not be enforced, which occurs when two
+    /// breaklines cross one another.
+    pub fn add_breakline(&mut self, vertices: &[usize]) -> bool {
+        let mut success = true;
+        for i in 1..vertices.len() {
+            let a = self.triangulated_vertex(vertices[i - 1]);
+            let b = self.triangulated_vertex(vertices[i]);
+            if !self.enforce_segment(a, b, 0) {
+                success = false;
+            }
+        }
+        success
+    }
+
+    /// Finds the triangle containing a point, walking through the triangulation from the
+    /// `start` triangle. Returns `None` if the point is outside of the TIN.
+    pub fn locate(&self, p: &Point2D, start: usize) -> Option<usize> {
+        let t = &self.triangulation;
+        let num_triangles = t.len();
+        if num_triangles == 0 {
+            return None;
+        }
+        let mut triangle = if start < num_triangles { start } else { 0 };
+        let mut steps = 0;
+        'walk: while steps < num_triangles {
+            steps += 1;
+            let pts = self.points_of_triangle(triangle);
+            let s = orientation(
+                &self.points[pts[0]],
+                &self.points[pts[1]],
+                &self.points[pts[2]],
+            )
+            .signum();
+            for e in t.edges_of_triangle(triangle).iter() {
+                let u = &self.points[t.triangles[*e]];
+                let v = &self.points[t.triangles[t.next_halfedge(*e)]];
+                if orientation(u, v, p) * s < 0f64 {
+                    let twin = t.halfedges[*e];
+                    if twin == EMPTY {
+                        return None;
+                    }
+                    triangle = t.triangle_of_edge(twin);
+                    continue 'walk;
+                }
+            }
+            return Some(triangle);
+        }
+        // the walk did not converge; fall back on an exhaustive search
+        (0..num_triangles).find(|tri| self.triangle_contains(*tri, p))
+    }
+
+    /// Returns true if a point is within (or on the boundary of) a triangle.
+    pub fn triangle_contains(&self, triangle: usize, p: &Point2D) -> bool {
+        let pts = self.points_of_triangle(triangle);
+        let (a, b, c) = (
+            &self.points[pts[0]],
+            &self.points[pts[1]],
+            &self.points[pts[2]],
+        );
+        let d1 = orientation(a, b, p);
+        let d2 = orientation(b, c, p);
+        let d3 = orientation(c, a, p);
+        let has_neg = d1 < 0f64 || d2 < 0f64 || d3 < 0f64;
+        let has_pos = d1 > 0f64 || d2 > 0f64 || d3 > 0f64;
+        !(has_neg && has_pos)
+    }
+
+    /// Interpolates the z value at a point within a triangle using the plane passing
+    /// through the triangle's vertices.
+    pub fn interpolate_linear(&self, triangle: usize, p: &Point2D) -> f64 {
+        let pts = self.points_of_triangle(triangle);
+        let (a, b, c) = (
+            &self.points[pts[0]],
+            &self.points[pts[1]],
+            &self.points[pts[2]],
+        );
+        let area = orientation(a, b, c);
+        if area == 0f64 {
+            return (self.z_values[pts[0]] + self.z_values[pts[1]] + self.z_values[pts[2]])
+                / 3f64;
+        }
+        let w0 = orientation(b, c, p) / area;
+        let w1 = orientation(c, a, p) / area;
+        let w2 = 1f64 - w0 - w1;
+        w0 * self.z_values[pts[0]] + w1 * self.z_values[pts[1]] + w2 * self.z_values[pts[2]]
+    }
+
+    /// Interpolates the z value at a point within a triangle using Sibson's natural
+    /// neighbour method. The natural neighbours are not searched across breaklines.
+    pub fn interpolate_natural_neighbour(&self, triangle: usize, p: &Point2D) -> f64 {
+        let t = &self.triangulation;
+        for v in self.points_of_triangle(triangle).iter() {
+            if self.points[*v].nearly_equals(p) {
+                return self.z_values[*v];
+            }
+        }
+
+        // find the triangles with circumcircles containing the point, i.e. the triangles
+        // that would be removed by inserting the point into the triangulation
+        let mut cavity = HashSet::new();
+        cavity.insert(triangle);
+        let mut stack = vec![triangle];
+        while let Some(tri) = stack.pop() {
+            for e in t.edges_of_triangle(tri).iter() {
+                let twin = t.halfedges[*e];
+                if twin == EMPTY
+                    || self.is_constrained_edge(t.triangles[*e], t.triangles[t.next_halfedge(*e)])
+                {
+                    continue;
+                }
+                let neighbour = t.triangle_of_edge(twin);
+                if !cavity.contains(&neighbour) && self.in_circumcircle(neighbour, p) {
+                    cavity.insert(neighbour);
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        // the area stolen from each natural neighbour's Voronoi cell is bounded by the
+        // circumcentres of the new triangles along the cavity boundary and of the removed
+        // triangles incident to the neighbour.
+        let mut cell_vertices: Vec<(usize, Vec<Point2D>)> = vec![];
+        let mut add_vertex = |v: usize, c: Point2D| match cell_vertices.iter_mut().find(|a| a.0 == v)
+        {
+            Some(a) => a.1.push(c),
+            None => cell_vertices.push((v, vec![c])),
+        };
+        for tri in cavity.iter() {
+            let pts = self.points_of_triangle(*tri);
+            let c = self.points[pts[0]].circumcenter(&self.points[pts[1]], &self.points[pts[2]]);
+            for v in pts.iter() {
+                add_vertex(*v, c);
+            }
+            for e in t.edges_of_triangle(*tri).iter() {
+                let twin = t.halfedges[*e];
+                if twin == EMPTY || !cavity.contains(&t.triangle_of_edge(twin)) {
+                    let u = t.triangles[*e];
+                    let v = t.triangles[t.next_halfedge(*e)];
+                    let g = p.circumcenter(&self.points[u], &self.points[v]);
+                    if !g.x.is_finite() || !g.y.is_finite() {
+                        // the point is on the cavity boundary
+                        return self.interpolate_linear(triangle, p);
+                    }
+                    add_vertex(u, g);
+                    add_vertex(v, g);
+                }
+            }
+        }
+
+        let mut sum_weights = 0f64;
+        let mut z = 0f64;
+        for (v, vertices) in cell_vertices.iter() {
+            let w = convex_polygon_area(vertices);
+            sum_weights += w;
+            z += w * self.z_values[*v];
+        }
+        if sum_weights > 0f64 && z.is_finite() {
+            z / sum_weights
+        } else {
+            self.interpolate_linear(triangle, p)
+        }
+    }
+
+    fn in_circumcircle(&self, triangle: usize, p: &Point2D) -> bool {
+        let pts = self.points_of_triangle(triangle);
+        let c = self.points[pts[0]].circumcenter(&self.points[pts[1]], &self.points[pts[2]]);
+        c.distance_squared(p) < c.distance_squared(&self.points[pts[0]])
+    }
+
+    /// Returns the index of a vertex that is part of the triangulation. Vertices that were
+    /// skipped during triangulation, as near-duplicates, are mapped to the retained vertex.
+    fn triangulated_vertex(&self, v: usize) -> usize {
+        if self.in_triangulation[v] {
+            return v;
+        }
+        let p = self.points[v];
+        (0..self.points.len())
+            .find(|i| self.in_triangulation[*i] && self.points[*i].nearly_equals(&p))
+            .unwrap_or(v)
+    }
+
+    fn enforce_segment(&mut self, a: usize, b: usize, depth: usize) -> bool {
+        if a == b || !self.in_triangulation[a] || !self.in_triangulation[b] {
+            return false;
+        }
+        let (pa, pb) = (self.points[a], self.points[b]);
+
+        // only the triangles overlapping the segment's bounding box can be affected
+        let min_x = pa.x.min(pb.x);
+        let max_x = pa.x.max(pb.x);
+        let min_y = pa.y.min(pb.y);
+        let max_y = pa.y.max(pb.y);
+        let candidates = (0..self.num_triangles())
+            .filter(|tri| {
+                let pts = self.points_of_triangle(*tri);
+                let xs = pts.iter().map(|i| self.points[*i].x);
+                let ys = pts.iter().map(|i| self.points[*i].y);
+                xs.clone().fold(f64::INFINITY, f64::min) <= max_x
+                    && xs.fold(f64::NEG_INFINITY, f64::max) >= min_x
+                    && ys.clone().fold(f64::INFINITY, f64::min) <= max_y
+                    && ys.fold(f64::NEG_INFINITY, f64::max) >= min_y
+            })
+            .collect::<Vec<usize>>();
+        let halfedges = candidates
+            .iter()
+            .flat_map(|tri| self.triangulation.edges_of_triangle(*tri).to_vec())
+            .collect::<Vec<usize>>();
+
+        if self.find_halfedge(&halfedges, a, b).is_some() {
+            self.constrained_edges.insert(edge_key(a, b));
+            return true;
+        }
+
+        // if a vertex lies on the segment, split the segment at the vertex
+        let len_sqrd = pa.distance_squared(&pb);
+        for e in &halfedges {
+            let c = self.triangulation.triangles[*e];
+            if c == a || c == b {
+                continue;
+            }
+            let pc = self.points[c];
+            let t = ((pc.x - pa.x) * (pb.x - pa.x) + (pc.y - pa.y) * (pb.y - pa.y)) / len_sqrd;
+            if t > 0f64
+                && t < 1f64
+                && orientation(&pa, &pb, &pc).abs() <= 1e-12 * len_sqrd
+                && depth < 1000
+            {
+                let ret1 = self.enforce_segment(a, c, depth + 1);
+                let ret2 = self.enforce_segment(c, b, depth + 1);
+                return ret1 && ret2;
+            }
+        }
+
+        // find the edges crossing the segment
+        let mut queue = VecDeque::new();
+        for e in &halfedges {
+            let twin = self.triangulation.halfedges[*e];
+            if twin != EMPTY && *e > twin {
+                continue; // only consider each edge once
+            }
+            let (u, v) = self.edge_vertices(*e);
+            if segments_cross(&pa, &pb, &self.points[u], &self.points[v]) {
+                if self.is_constrained_edge(u, v) {
+                    // crossing breaklines
+                    return false;
+                }
+                queue.push_back((u, v));
+            }
+        }
+
+        // flip the crossing edges until the segment is part of the triangulation
+        let mut new_edges = vec![];
+        let max_iterations = 10 * (queue.len() + 1) * (queue.len() + 1);
+        let mut iterations = 0;
+        while let Some((u, v)) = queue.pop_front() {
+            iterations += 1;
+            if iterations > max_iterations {
+                return false;
+            }
+            let e = match self.find_halfedge(&halfedges, u, v) {
+                Some(e) => e,
+                None => continue,
+            };
+            if self.is_flippable(e) {
+                let d = self.triangulation.flip_edge(e);
+                let (p, q) = self.edge_vertices(d);
+                if p != a
+                    && p != b
+                    && q != a
+                    && q != b
+                    && segments_cross(&pa, &pb, &self.points[p], &self.points[q])
+                {
+                    queue.push_back((p, q));
+                } else {
+                    new_edges.push((p, q));
+                }
+            } else {
+                queue.push_back((u, v));
+            }
+        }
+        self.constrained_edges.insert(edge_key(a, b));
+
+        // restore the Delaunay criterion for the newly created edges
+        let mut swapped = true;
+        let mut passes = 0;
+        while swapped && passes < 100 {
+            swapped = false;
+            passes += 1;
+            for i in 0..new_edges.len() {
+                let (u, v) = new_edges[i];
+                if self.is_constrained_edge(u, v) {
+                    continue;
+                }
+                if let Some(e) = self.find_halfedge(&halfedges, u, v) {
+                    if !self.is_locally_delaunay(e) && self.is_flippable(e) {
+                        let d = self.triangulation.flip_edge(e);
+                        new_edges[i] = self.edge_vertices(d);
+                        swapped = true;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn edge_vertices(&self, e: usize) -> (usize, usize) {
+        let t = &self.triangulation;
+        (t.triangles[e], t.triangles[t.next_halfedge(e)])
+    }
+
+    fn find_halfedge(&self, halfedges: &[usize], u: usize, v: usize) -> Option<usize> {
+        halfedges.iter().copied().find(|e| {
+            let (p, q) = self.edge_vertices(*e);
+            (p == u && q == v) || (p == v && q == u)
+        })
+    }
+
+    /// An edge can be flipped if the quadrilateral formed by its two triangles is convex.
+    fn is_flippable(&self, e: usize) -> bool {
+        let t = &self.triangulation;
+        let twin = t.halfedges[e];
+        if twin == EMPTY {
+            return false;
+        }
+        let (u, v) = self.edge_vertices(e);
+        let p0 = t.triangles[t.prev_halfedge(e)];
+        let p1 = t.triangles[t.prev_halfedge(twin)];
+        segments_cross(
+            &self.points[u],
+            &self.points[v],
+            &self.points[p0],
+            &self.points[p1],
+        )
+    }
+
+    fn is_locally_delaunay(&self, e: usize) -> bool {
+        let t = &self.triangulation;
+        let twin = t.halfedges[e];
+        if twin == EMPTY {
+            return true;
+        }
+        let p1 = t.triangles[t.prev_halfedge(twin)];
+        !self.in_circumcircle(t.triangle_of_edge(e), &self.points[p1])
+    }
+}
+
+fn edge_key(p1: usize, p2: usize) -> (usize, usize) {
+    if p1 < p2 {
+        (p1, p2)
+    } else {
+        (p2, p1)
+    }
+}
+
+/// Twice the signed area of triangle (a, b, c); positive for counter-clockwise order.
+fn orientation(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Returns true if segments (p1, p2) and (p3, p4) cross at a point interior to both.
+fn segments_cross(p1: &Point2D, p2: &Point2D, p3: &Point2D, p4: &Point2D) -> bool {
+    let d1 = orientation(p1, p2, p3);
+    let d2 = orientation(p1, p2, p4);
+    let d3 = orientation(p3, p4, p1);
+    let d4 = orientation(p3, p4, p2);
+    d1 * d2 < 0f64 && d3 * d4 < 0f64
+}
+
+/// Calculates the area of a convex polygon from an unordered set of its vertices.
+fn convex_polygon_area(vertices: &[Point2D]) -> f64 {
+    if vertices.len() < 3 {
+        return 0f64;
+    }
+    let n = vertices.len() as f64;
+    let cx = vertices.iter().map(|p| p.x).sum::<f64>() / n;
+    let cy = vertices.iter().map(|p| p.y).sum::<f64>() / n;
+    let mut sorted = vertices.to_vec();
+    sorted.sort_by(|a, b| {
+        let a1 = (a.y - cy).atan2(a.x - cx);
+        let a2 = (b.y - cy).atan2(b.x - cx);
+        a1.partial_cmp(&a2).unwrap()
+    });
+    let mut area = 0f64;
+    for i in 0..sorted.len() {
+        let j = (i + 1) % sorted.len();
+        area += sorted[i].x * sorted[j].y - sorted[j].x * sorted[i].y;
+    }
+    area.abs() / 2f64
+}
+
+/// Constructs a TIN from a set of points and, optionally, the breaklines contained in a
+/// polyline or polygon vector file. Breakline vertex heights are taken from the vector's
+/// z values, where available, and are otherwise interpolated from the TIN of the points.
+/// Returns the TIN and the number of breaklines that could not be fully enforced.
+pub fn construct_tin(
+    mut points: Vec<Point2D>,
+    mut z_values: Vec<f64>,
+    breaklines_file: &str,
+) -> Result<(Tin, usize), Error> {
+    if breaklines_file.is_empty() {
+        let tin = Tin::new(points, z_values)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "No triangulation exists."))?;
+        return Ok((tin, 0));
+    }
+    let breaklines = Shapefile::read(breaklines_file)?;
+    let base_type = breaklines.header.shape_type.base_shape_type();
+    if base_type != ShapeType::PolyLine && base_type != ShapeType::Polygon {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The breaklines vector data must be of POLYLINE or POLYGON base shape type.",
+        ));
+    }
+    let use_z = breaklines.header.shape_type.dimension() == ShapeTypeDimension::Z;
+    let point_tin = if use_z {
+        None
+    } else {
+        Tin::new(points.clone(), z_values.clone())
+    };
+
+    let mut lines: Vec<Vec<usize>> = vec![];
+    let mut hint = 0usize;
+    for record_num in 0..breaklines.num_records {
+        let record = breaklines.get_record(record_num);
+        for part in 0..record.num_parts as usize {
+            let start = record.parts[part] as usize;
+            let end = if part < record.num_parts as usize - 1 {
+                record.parts[part + 1] as usize
+            } else {
+                record.num_points as usize
+            };
+            let mut line = vec![];
+            for i in start..end {
+                let p = record.points[i];
+                let z = if use_z {
+                    record.z_array[i]
+                } else {
+                    let tin = point_tin.as_ref().ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidInput, "No triangulation exists.")
+                    })?;
+                    match tin.locate(&p, hint) {
+                        Some(tri) => {
+                            hint = tri;
+                            tin.interpolate_linear(tri, &p)
+                        }
+                        None => continue, // vertices outside of the data coverage are ignored
+                    }
+                };
+                line.push(points.len());
+                points.push(p);
+                z_values.push(z);
+            }
+            lines.push(line);
+        }
+    }
+
+    let mut tin = Tin::new(points, z_values)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "No triangulation exists."))?;
+    let mut num_failed = 0;
+    for line in &lines {
+        if !tin.add_breakline(line) {
+            num_failed += 1;
+        }
+    }
+    Ok((tin, num_failed))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tin;
+    use crate::structures::Point2D;
+
+    fn plane(x: f64, y: f64) -> f64 {
+        2f64 * x - 3f64 * y + 10f64
+    }
+
+    fn grid_tin() -> Tin {
+        let mut points = vec![];
+        let mut z_values = vec![];
+        for row in 0..6 {
+            for col in 0..6 {
+                // perturb the points to avoid co-circular configurations
+                let x = col as f64 + 0.1 * ((row * 7 + col * 3) % 5) as f64;
+                let y = row as f64 + 0.1 * ((row * 3 + col * 5) % 4) as f64;
+                points.push(Point2D::new(x, y));
+                z_values.push(plane(x, y));
+            }
+        }
+        Tin::new(points, z_values).unwrap()
+    }
+
+    #[test]
+    fn test_tin_linear_interpolation() {
+        let tin = grid_tin();
+        let p = Point2D::new(2.3, 2.6);
+        let tri = tin.locate(&p, 0).unwrap();
+        assert!(tin.triangle_contains(tri, &p));
+        assert!((tin.interpolate_linear(tri, &p) - plane(p.x, p.y)).abs() < 1e-9);
+        assert!(tin.locate(&Point2D::new(-10.0, -10.0), 0).is_none());
+    }
+
+    #[test]
+    fn test_tin_natural_neighbour_interpolation() {
+        // Sibson's interpolant reproduces linear functions exactly
+        let tin = grid_tin();
+        for p in &[Point2D::new(2.3, 2.6), Point2D::new(1.05, 3.9), Point2D::new(3.7, 1.2)] {
+            let tri = tin.locate(p, 0).unwrap();
+            let z = tin.interpolate_natural_neighbour(tri, p);
+            assert!((z - plane(p.x, p.y)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_tin_breakline() {
+        // the Delaunay triangulation of these points uses the short diagonal (2, 3)
+        let points = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(2.0, 1.0),
+            Point2D::new(2.0, -1.0),
+        ];
+        let mut tin = Tin::new(points, vec![0.0; 4]).unwrap();
+        assert!(tin.has_edge(2, 3));
+        assert!(!tin.has_edge(0, 1));
+        assert!(tin.add_breakline(&[0, 1]));
+        assert!(tin.has_edge(0, 1));
+        assert!(!tin.has_edge(2, 3));
+        assert!(tin.is_constrained_edge(1, 0));
+
+        // a breakline crossing many triangles
+        let mut tin = grid_tin();
+        assert!(tin.add_breakline(&[0, 34]));
+        assert!(tin.has_edge(0, 34));
+        assert!(tin.is_constrained_edge(0, 34));
+        assert_eq!(tin.num_triangles(), grid_tin().num_triangles());
+    }
+}
@@ -0,0 +1,487 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Runs WhiteboxTools as a long-running daemon that keeps frequently used input
+datasets memory-resident between successive tool runs, so that iterative workflows
+do not repeatedly read the same large DEM from disk. Clients connect to a Unix domain
+socket (when the address is a file path) or a TCP socket (when the address is of the
+form host:port) and send requests as JSON objects, one per line, e.g.:
+
+{ "command": "run", "tool": "Slope", "args": { "dem": "DEM.tif", "output": "slope.tif" } }
+{ "command": "cache", "files": ["DEM.tif"] }
+{ "command": "release", "files": ["DEM.tif"] }
+{ "command": "cache_info" }
+{ "command": "shutdown" }
+
+A run request may also specify a `working_dir` and whether to run in `verbose` mode.
+When verbose, each of the tool's messages is sent back as a line of the form
+{ "message": "...", "progress": 45 } before the final response, which always has a
+`status` of either "ok" or "error".
+
+The raster and vector inputs of a run are loaded into the cache, unless they are
+already resident and unchanged on disk, and are then passed to the tool as in-memory
+datasets. Cached datasets that are overwritten by a tool's outputs are released.
+Tools without any new-file outputs may modify their inputs in place, and so they are
+always run on the files themselves, with their inputs released from the cache
+afterwards. When the cache grows beyond its size limit, the least recently used
+datasets are released. Tool runs are serialized.
+*/
+
+use super::pipeline::{parse_step_args, trim_flag};
+use super::{apply_framework_flags, ParameterFileType, ParameterType, ToolManager, ToolParameter};
+use crate::dataset::{remove_dataset, store_dataset, Dataset, MEMORY_PREFIX};
+use crate::ffi::{run_tool, run_with_callback};
+use crate::raster::Raster;
+use crate::utils::{get_formatted_elapsed_time, parse_progress, Instant};
+use crate::vector::Shapefile;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::SystemTime;
+
+struct CacheEntry {
+    memory_name: String,
+    modified: Option<SystemTime>,
+    size: usize,
+    last_used: usize,
+}
+
+/// The memory-resident datasets, keyed by their file names on disk.
+struct DatasetCache {
+    entries: HashMap<String, CacheEntry>,
+    // the maximum total size of the cached datasets, in bytes; 0 is unlimited
+    max_size: usize,
+    use_count: usize,
+}
+
+impl DatasetCache {
+    fn total_size(&self) -> usize {
+        self.entries.values().map(|e| e.size).sum()
+    }
+
+    /// Returns the in-memory name of a dataset, loading it into the cache if it is
+    /// not already resident or has been modified on disk since it was loaded. None
+    /// is returned if the dataset is too large to be cached. Datasets in `keep` are
+    /// not released to make room.
+    fn load(&mut self, file: &str, keep: &[String]) -> Result<Option<String>, Error> {
+        self.use_count += 1;
+        let modified = fs::metadata(file).and_then(|m| m.modified()).ok();
+        if let Some(entry) = self.entries.get_mut(file) {
+            if entry.modified == modified {
+                entry.last_used = self.use_count;
+                return Ok(Some(entry.memory_name.clone()));
+            }
+        }
+        self.release(file);
+
+        let dataset = if file.to_lowercase().ends_with(".shp") {
+            Dataset::Vector(Shapefile::read(file)?)
+        } else {
+            Dataset::Raster(Raster::new(file, "r")?)
+        };
+        let size = match &dataset {
+            Dataset::Raster(r) => r.num_cells() * r.configs.bands.max(1) * 8,
+            Dataset::Vector(_) => {
+                let mut size = 0usize;
+                for ext in &["shp", "dbf"] {
+                    let f = path::Path::new(file).with_extension(ext);
+                    size += fs::metadata(f).map(|m| m.len() as usize).unwrap_or(0);
+                }
+                size
+            }
+        };
+        if self.max_size > 0 {
+            if size > self.max_size {
+                return Ok(None);
+            }
+            // release the least recently used datasets to make room
+            while self.total_size() + size > self.max_size {
+                let lru = self
+                    .entries
+                    .iter()
+                    .filter(|(f, _)| !keep.contains(f))
+                    .min_by_key(|(_, e)| e.last_used)
+                    .map(|(f, _)| f.clone());
+                match lru {
+                    Some(f) => self.release(&f),
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        let memory_name = format!(
+            "{}cache/{}",
+            MEMORY_PREFIX,
+            file.replace("\\", "/").trim_start_matches('/')
+        );
+        store_dataset(&memory_name, dataset);
+        self.entries.insert(
+            file.to_string(),
+            CacheEntry {
+                memory_name: memory_name.clone(),
+                modified,
+                size,
+                last_used: self.use_count,
+            },
+        );
+        Ok(Some(memory_name))
+    }
+
+    fn release(&mut self, file: &str) -> bool {
+        match self.entries.remove(file) {
+            Some(entry) => {
+                remove_dataset(&entry.memory_name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn release_all(&mut self) {
+        let files: Vec<String> = self.entries.keys().cloned().collect();
+        for f in files {
+            self.release(&f);
+        }
+    }
+}
+
+struct DaemonState {
+    tm: ToolManager,
+    framework_args: Vec<String>,
+    cache: Mutex<DatasetCache>,
+}
+
+impl ToolManager {
+    /// Runs a daemon, listening on `address` (either a Unix domain socket path or
+    /// host:port), that keeps input datasets memory-resident between tool runs. The
+    /// cache is limited to `cache_size` megabytes, or is unlimited if `cache_size` is 0.
+    /// The `args` may contain the framework-level flags (e.g. `--compress`), which
+    /// then apply to every run. The process exits when a client requests a shutdown.
+    pub fn run_daemon(&self, address: &str, cache_size: usize, args: Vec<String>) -> Result<(), Error> {
+        let _ = apply_framework_flags(args.clone())?;
+        let state = Arc::new(DaemonState {
+            tm: ToolManager::new(&self.working_dir, &self.verbose)?,
+            framework_args: args,
+            cache: Mutex::new(DatasetCache {
+                entries: HashMap::new(),
+                max_size: cache_size * 1_048_576,
+                use_count: 0,
+            }),
+        });
+
+        if address.parse::<SocketAddr>().is_ok() {
+            let listener = TcpListener::bind(address)?;
+            println!("WhiteboxTools daemon is listening on {}", address);
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+                let state = state.clone();
+                thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone()?);
+                    handle_client(&state, reader, stream)
+                });
+            }
+            return Ok(());
+        }
+        listen_on_socket_file(state, address)
+    }
+}
+
+#[cfg(unix)]
+fn listen_on_socket_file(state: Arc<DaemonState>, address: &str) -> Result<(), Error> {
+    use std::os::unix::net::UnixListener;
+
+    // a socket file left behind by an earlier daemon would prevent binding
+    if path::Path::new(address).exists() {
+        fs::remove_file(address)?;
+    }
+    let listener = UnixListener::bind(address)?;
+    println!("WhiteboxTools daemon is listening on {}", address);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        let state = state.clone();
+        thread::spawn(move || {
+            let reader = BufReader::new(stream.try_clone()?);
+            handle_client(&state, reader, stream)
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen_on_socket_file(_state: Arc<DaemonState>, address: &str) -> Result<(), Error> {
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "Unrecognized daemon address '{}'; on this platform the address must be of the form host:port.",
+            address
+        ),
+    ))
+}
+
+/// Handles the requests sent over a client connection, one JSON object per line.
+fn handle_client<R: BufRead, W: Write>(
+    state: &DaemonState,
+    reader: R,
+    mut writer: W,
+) -> Result<(), Error> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
+            Err(e) => {
+                send(&mut writer, &error_response(&format!("Error parsing the request: {}", e)))?;
+                continue;
+            }
+        };
+        let response = match request["command"].as_str().unwrap_or("") {
+            "run" => run_request(state, &request, &mut writer),
+            "cache" => {
+                let mut cache = state.cache.lock().unwrap();
+                let mut cached = vec![];
+                let mut result = Ok(());
+                for file in request_files(state, &request) {
+                    match cache.load(&file, &[]) {
+                        Ok(Some(_)) => cached.push(file),
+                        Ok(None) => {}
+                        Err(e) => {
+                            result = Err(Error::new(
+                                e.kind(),
+                                format!("Error caching {}: {}", file, e),
+                            ));
+                            break;
+                        }
+                    }
+                }
+                result.map(|_| json!({ "status": "ok", "cached": cached }))
+            }
+            "release" => {
+                let mut cache = state.cache.lock().unwrap();
+                let released: Vec<String> = if request["files"].is_array() {
+                    request_files(state, &request)
+                        .into_iter()
+                        .filter(|f| cache.release(f))
+                        .collect()
+                } else {
+                    let files = cache.entries.keys().cloned().collect();
+                    cache.release_all();
+                    files
+                };
+                Ok(json!({ "status": "ok", "released": released }))
+            }
+            "cache_info" => {
+                let cache = state.cache.lock().unwrap();
+                let datasets: Vec<Value> = cache
+                    .entries
+                    .iter()
+                    .map(|(f, e)| json!({ "file": f, "size": e.size }))
+                    .collect();
+                Ok(json!({
+                    "status": "ok",
+                    "datasets": datasets,
+                    "total_size": cache.total_size(),
+                    "max_size": cache.max_size,
+                }))
+            }
+            "shutdown" => {
+                send(&mut writer, &json!({ "status": "ok" }))?;
+                state.cache.lock().unwrap().release_all();
+                process::exit(0);
+            }
+            c => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized command '{}'.", c),
+            )),
+        };
+        match response {
+            Ok(v) => send(&mut writer, &v)?,
+            Err(e) => send(&mut writer, &error_response(&e.to_string()))?,
+        }
+    }
+    Ok(())
+}
+
+/// Runs a tool, substituting cached datasets for its raster and vector inputs.
+fn run_request<W: Write>(state: &DaemonState, request: &Value, writer: &mut W) -> Result<Value, Error> {
+    let tool = match request["tool"].as_str() {
+        Some(tool) => tool.to_string(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The run request does not specify a tool.",
+            ))
+        }
+    };
+    let parameters: Vec<ToolParameter> = match state.tm.get_tool(&tool) {
+        Some(t) => {
+            let o: Value = serde_json::from_str(&t.get_tool_parameters())?;
+            serde_json::from_value(o["parameters"].clone())?
+        }
+        None => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Unrecognized tool name {}.", tool),
+            ))
+        }
+    };
+    let working_dir = request_working_dir(state, request);
+    let verbose = request["verbose"].as_bool().unwrap_or(false);
+    let modifies_inputs = !parameters.iter().any(|p| match p.parameter_type {
+        ParameterType::NewFile(_) => true,
+        _ => false,
+    });
+
+    // holding the cache lock for the duration of the run also serializes runs
+    let mut cache = state.cache.lock().unwrap();
+    let start = Instant::now();
+    let mut args = vec![];
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+    for (flag, value) in parse_step_args(&request["args"])? {
+        let p = parameters
+            .iter()
+            .find(|p| p.flags.iter().any(|f| trim_flag(f) == flag));
+        let (is_input, is_list) = match p.map(|p| &p.parameter_type) {
+            Some(ParameterType::ExistingFile(ft)) => (is_cacheable(ft), false),
+            Some(ParameterType::ExistingFileOrFloat(ft)) => {
+                let unquoted = value.replace("'", "");
+                (is_cacheable(ft) && unquoted.trim().parse::<f64>().is_err(), false)
+            }
+            Some(ParameterType::FileList(ft)) => (is_cacheable(ft), true),
+            Some(ParameterType::NewFile(_)) => {
+                outputs.push(resolve_path(&working_dir, &value));
+                (false, false)
+            }
+            _ => (false, false),
+        };
+        let mut value = value;
+        if is_input {
+            let files: Vec<String> = if is_list {
+                value
+                    .split(|c: char| c == ';' || c == ',')
+                    .filter(|f| !f.trim().is_empty())
+                    .map(|f| resolve_path(&working_dir, f))
+                    .collect()
+            } else {
+                vec![resolve_path(&working_dir, &value)]
+            };
+            inputs.extend(files.iter().cloned());
+            if !modifies_inputs {
+                let mut names = vec![];
+                for file in &files {
+                    names.push(match cache.load(file, &inputs)? {
+                        Some(memory_name) => memory_name,
+                        None => file.clone(),
+                    });
+                }
+                value = names.join(";");
+            }
+        }
+        if value.is_empty() {
+            args.push(format!("--{}", flag));
+        } else {
+            args.push(format!("--{}={}", flag, value));
+        }
+    }
+    args.extend(state.framework_args.iter().cloned());
+
+    let (result, _) = if verbose {
+        run_with_callback(tool, working_dir, args, |line| {
+            let _ = send(
+                writer,
+                &json!({ "message": line, "progress": parse_progress(line) }),
+            );
+        })
+    } else {
+        run_tool(tool, working_dir, args, false)
+    };
+
+    // datasets that were overwritten or possibly modified are no longer valid
+    for file in &outputs {
+        cache.release(file);
+    }
+    if modifies_inputs {
+        for file in &inputs {
+            cache.release(file);
+        }
+    }
+    result.map(|_| json!({ "status": "ok", "elapsed_time": get_formatted_elapsed_time(start) }))
+}
+
+fn is_cacheable(file_type: &ParameterFileType) -> bool {
+    match file_type {
+        ParameterFileType::Raster
+        | ParameterFileType::Vector(_)
+        | ParameterFileType::RasterAndVector(_) => true,
+        _ => false,
+    }
+}
+
+fn request_working_dir(state: &DaemonState, request: &Value) -> String {
+    let sep = path::MAIN_SEPARATOR.to_string();
+    match request["working_dir"].as_str() {
+        Some(wd) if !wd.trim().is_empty() => {
+            let mut wd = wd.trim().to_string();
+            if !wd.ends_with(&sep) {
+                wd.push_str(&sep);
+            }
+            wd
+        }
+        _ => state.tm.working_dir.clone(),
+    }
+}
+
+fn request_files(state: &DaemonState, request: &Value) -> Vec<String> {
+    let working_dir = request_working_dir(state, request);
+    match request["files"].as_array() {
+        Some(files) => files
+            .iter()
+            .filter_map(|f| f.as_str())
+            .map(|f| resolve_path(&working_dir, f))
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Resolves a file name the way that the tools do, prepending the working directory
+/// to bare file names, and then canonicalizes it so that it may be used as a cache key.
+fn resolve_path(working_dir: &str, file: &str) -> String {
+    let sep = path::MAIN_SEPARATOR.to_string();
+    let file = file.trim().replace("\"", "").replace("'", "");
+    let file = if !file.contains(&sep) && !file.contains("/") {
+        format!("{}{}", working_dir, file)
+    } else {
+        file
+    };
+    match fs::canonicalize(&file) {
+        Ok(p) => p.to_string_lossy().to_string(),
+        Err(_) => file,
+    }
+}
+
+fn error_response(message: &str) -> Value {
+    json!({ "status": "error", "error": message })
+}
+
+fn send<W: Write>(writer: &mut W, v: &Value) -> Result<(), Error> {
+    writeln!(writer, "{}", v.to_string())?;
+    writer.flush()
+}
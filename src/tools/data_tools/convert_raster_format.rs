@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 6, 2017
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -18,6 +18,9 @@ use std::path;
 /// This is more important for distinguishing output files since input files can be read and
 /// distiguishing features idenfitied from the file structure. At the moment, this tool does not
 /// support user hints however.
+///
+/// # See Also
+/// `ConvertToWhiteboxRaster`
 pub struct ConvertRasterFormat {
     name: String,
     description: String,
@@ -0,0 +1,321 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::path::Path;
+
+/// This tool converts one or more rasters (`--inputs`), in any of the supported raster formats,
+/// into the Whitebox v2 (.wbr) raster format. The output files are written alongside the
+/// inputs, with the same name and a .wbr extension. Whitebox v2 rasters store their values in
+/// independent, compressed 256 x 256 cell chunks, together with per-band statistics, the
+/// coordinate reference system (including an OGC WKT2 description), and a pyramid of
+/// overviews. Because the chunks are compressed and decompressed in parallel, .wbr files are
+/// typically much faster to read and write than the other supported formats, which makes
+/// them well suited to the intermediate products of long processing pipelines. Any tool can
+/// also write a .wbr file directly, simply by specifying an output file with a .wbr extension.
+///
+/// Values are DEFLATE compressed using the specified `--compression_level`, from 1 (fastest)
+/// to 9 (smallest), unless the `--uncompressed` flag is used. Unlike `ConvertRasterFormat`,
+/// all of the bands of multi-band inputs are retained.
+///
+/// # See Also
+/// `ConvertRasterFormat`
+pub struct ConvertToWhiteboxRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ConvertToWhiteboxRaster {
+    pub fn new() -> ConvertToWhiteboxRaster {
+        // public constructor
+        let name = "ConvertToWhiteboxRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Converts rasters into the chunked and compressed Whitebox v2 (.wbr) format."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Compression Level (1-9)".to_owned(),
+            flags: vec!["--compression_level".to_owned()],
+            description: "DEFLATE compression level, from 1 (fastest) to 9 (smallest).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("6".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Store values uncompressed?".to_owned(),
+            flags: vec!["--uncompressed".to_owned()],
+            description: "Store the chunks without compression.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i='DEM.tif;slope.dep' --compression_level=4",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ConvertToWhiteboxRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ConvertToWhiteboxRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files = String::new();
+        let mut compression_level = 6u8;
+        let mut uncompressed = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
+                input_files = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-compression_level" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                compression_level = match val.trim().parse::<f64>() {
+                    Ok(v) => v.max(1f64).min(9f64) as u8,
+                    Err(_) => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Error parsing {}", flag_val),
+                        ))
+                    }
+                };
+            } else if flag_val == "-uncompressed" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    uncompressed = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut cmd = input_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let files: Vec<String> = vec
+            .iter()
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| {
+                let f = f.trim().to_string();
+                if !f.contains(&sep) && !f.contains("/") {
+                    format!("{}{}", working_directory, f)
+                } else {
+                    f
+                }
+            })
+            .collect();
+        if files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input raster file is required.",
+            ));
+        }
+
+        let start = Instant::now();
+        let (mut input_bytes, mut output_bytes) = (0u64, 0u64);
+        for (i, input_file) in files.iter().enumerate() {
+            let output_file = Path::new(input_file)
+                .with_extension("wbr")
+                .into_os_string()
+                .into_string()
+                .unwrap();
+            if output_file == *input_file {
+                if verbose {
+                    println!("{} is already a Whitebox v2 raster.", input_file);
+                }
+                continue;
+            }
+            if verbose {
+                println!(
+                    "Converting {} ({} of {})...",
+                    Path::new(input_file)
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy(),
+                    i + 1,
+                    files.len()
+                );
+            }
+
+            let input = Raster::new(input_file, "r")?;
+            let mut output = Raster::initialize_using_file(&output_file, &input);
+            let bands = input.configs.bands.max(1);
+            if bands > 1 {
+                output.set_num_bands(bands);
+                for band in 0..bands {
+                    for row in 0..input.configs.rows as isize {
+                        output.set_row_data_band(row, band, input.get_row_data_band(row, band));
+                    }
+                }
+            } else {
+                output.set_data_from_raster(&input)?;
+            }
+            for entry in &input.configs.metadata {
+                output.add_metadata_entry(entry.clone());
+            }
+            drop(input);
+
+            output.configs.compression = if uncompressed {
+                RasterCompression::None
+            } else {
+                RasterCompression::Deflate
+            };
+            output.configs.compression_level = compression_level;
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.write()?;
+
+            input_bytes += file_size(input_file);
+            output_bytes += file_size(&output_file);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            if input_bytes > 0 && output_bytes > 0 {
+                println!(
+                    "Output size: {:.1}% of the input size",
+                    100f64 * output_bytes as f64 / input_bytes as f64
+                );
+            }
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the size of a raster on disk, including the companion file of formats that
+/// store the header separately from the data.
+fn file_size(file_name: &str) -> u64 {
+    let path = Path::new(file_name);
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let companion = match extension.as_str() {
+        "tas" => Some("dep"),
+        "dep" => Some("tas"),
+        "flt" => Some("hdr"),
+        "rst" => Some("rdc"),
+        "rdc" => Some("rst"),
+        "sdat" => Some("sgrd"),
+        "sgrd" => Some("sdat"),
+        _ => None,
+    };
+    let mut size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if let Some(ext) = companion {
+        size += fs::metadata(path.with_extension(ext))
+            .map(|m| m.len())
+            .unwrap_or(0);
+    }
+    size
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Prof. John Lindsay
 Created: 07/08/2019
-Last Modified: 28/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -343,7 +343,10 @@ impl WhiteboxTool for CsvPointsToVector {
                                 )),
                                 FieldDataType::Text => imported_data
                                     .push(FieldData::Text(line_vec[a].trim().to_string())),
-                                FieldDataType::Date => imported_data
+                                FieldDataType::Int64 => imported_data.push(FieldData::Int64(
+                                    line_vec[a].trim().parse::<i64>().unwrap(),
+                                )),
+                                FieldDataType::Date | FieldDataType::DateTime => imported_data
                                     .push(FieldData::Text(line_vec[a].trim().to_string())),
                             }
                         }
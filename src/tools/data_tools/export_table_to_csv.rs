@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 24/04/2018
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -23,7 +23,7 @@ use std::path;
 /// column in the output CSV file.
 ///
 /// # See Also
-/// `MergeTableWithCsv`
+/// `MergeTableWithCsv`, `ExportTableToParquet`
 pub struct ExportTableToCsv {
     name: String,
     description: String,
@@ -222,6 +222,9 @@ impl WhiteboxTool for ExportTableToCsv {
                     FieldData::Int(ref val) => {
                         s.push_str(&format!(",{}", val));
                     }
+                    FieldData::Int64(ref val) => {
+                        s.push_str(&format!(",{}", val));
+                    }
                     FieldData::Real(ref val) => {
                         s.push_str(&format!(",{}", (val * multiplier).round() / multiplier));
                     }
@@ -231,6 +234,9 @@ impl WhiteboxTool for ExportTableToCsv {
                     FieldData::Date(ref val) => {
                         s.push_str(&format!(",{}", val));
                     }
+                    FieldData::DateTime(ref val) => {
+                        s.push_str(&format!(",{}", val));
+                    }
                     FieldData::Bool(ref val) => {
                         s.push_str(&format!(",{}", val));
                     }
@@ -0,0 +1,217 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use crate::vector::{write_parquet, Shapefile};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to export a vector's attribute table to an Apache Parquet file. Parquet is
+/// a compressed, columnar table format that can be read directly by pyarrow, pandas
+/// (`pandas.read_parquet`), polars, DuckDB, and most other data analytics tools. Unlike the dBase
+/// (.dbf) files that store the attributes of Shapefiles, Parquet files do not limit the length of
+/// field names or the number of fields and records, and the field types are retained; integer
+/// fields are written as 64-bit integers, real fields as doubles, date fields as dates, date-time
+/// fields as timestamps, logical fields as booleans, and all other fields as UTF-8 text. Null
+/// attribute values are preserved.
+///
+/// If the `--geometry` flag is specified, the output table also includes a 'geometry' column
+/// containing the WKB encoding of each record's shape, and the file is written as GeoParquet,
+/// including the coordinate reference system of the input vector, such that it may be read by
+/// GeoPandas (`geopandas.read_parquet`) and other GIS software.
+///
+/// # See Also
+/// `ExportTableToCsv`, `LidarToParquet`
+pub struct ExportTableToParquet {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportTableToParquet {
+    /// public constructor
+    pub fn new() -> ExportTableToParquet {
+        let name = "ExportTableToParquet".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Exports an attribute table, and optionally its geometries, to a Parquet file."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output Parquet file (*.parquet).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Include geometries (GeoParquet)?".to_owned(),
+            flags: vec!["--geometry".to_owned()],
+            description: "Include a WKB geometry column and write the file as GeoParquet."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lines.shp -o=lines.parquet --geometry",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ExportTableToParquet {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportTableToParquet {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut include_geometry = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-geometry" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    include_geometry = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let vector_data = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        if verbose {
+            println!("Writing table...")
+        };
+        write_parquet(&vector_data, &output_file, include_geometry)?;
+
+        if verbose {
+            let elapsed_time = get_formatted_elapsed_time(start);
+            println!("Exported {} records", vector_data.num_records);
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
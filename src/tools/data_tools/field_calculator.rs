@@ -0,0 +1,516 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the values of an attribute field from an expression evaluated against
+/// each feature's existing attributes and geometry, e.g. `POP / ($area / 1000000)` or
+/// `upper(NAME) || '-' || $fid`. If the named field already exists in the attribute table its
+/// values are replaced, converting the results to the field's type; otherwise a new field is
+/// appended to the table. The type of a new field may be specified with `--field_type`
+/// (`integer`, `integer64`, `real`, `text`, `boolean`, or `datetime`); by default (`auto`) it is
+/// inferred from the calculated values. Field names are not limited in length, although names
+/// longer than 10 characters are shortened when the table is written to a .dbf file. Records for which the expression evaluates to null, e.g. because a
+/// referenced field is empty or a division by zero occurs, receive null values. The attribute
+/// table of the input file is modified, unless an output file is specified.
+///
+/// Expressions may refer to fields by name, using double quotes or square brackets for names
+/// that contain spaces, and to text values in single quotes. They may use the arithmetic
+/// operators `+ - * / % ^`, the concatenation operator `||`, comparison and logical operators
+/// (`= != <> < <= > >= AND OR NOT LIKE ILIKE IN BETWEEN IS NULL`), and the following:
+///
+/// | Name | Description |
+/// |------|-------------|
+/// | `$area`, `$perimeter`, `$length` | Polygon area and perimeter, and line length |
+/// | `$x`, `$y` | Point coordinates, or the centre of the feature's extent |
+/// | `$xmin`, `$xmax`, `$ymin`, `$ymax` | Feature extent |
+/// | `$num_points`, `$num_parts`, `$fid` | Vertex and part counts, and one-based record number |
+/// | `abs`, `sqrt`, `exp`, `ln`, `log10`, `pow(x, y)` | Mathematical functions |
+/// | `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `atan2(y, x)` | Trigonometric functions (radians) |
+/// | `floor`, `ceil`, `round(x [, digits])`, `min(...)`, `max(...)` | Rounding and extremes |
+/// | `upper`, `lower`, `trim`, `length`, `substr(s, start [, len])`, `concat(...)` | Text functions |
+/// | `to_number`, `to_int`, `to_string` | Type conversions |
+/// | `if(condition, a, b)`, `coalesce(...)` | Conditional values |
+///
+/// # See Also
+/// `ExtractByAttribute`, `AddPointCoordinatesToTable`
+pub struct FieldCalculator {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FieldCalculator {
+    pub fn new() -> FieldCalculator {
+        // public constructor
+        let name = "FieldCalculator".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Calculates the values of an attribute field from an expression of other fields and geometry properties.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Name of the new or existing field to calculate.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Expression".to_owned(),
+            flags: vec!["--expression".to_owned()],
+            description: "Expression used to calculate the field values, e.g. \"POP / $area\"."
+                .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Type".to_owned(),
+            flags: vec!["--field_type".to_owned()],
+            description: "Type of a new field; options include 'auto', 'integer', 'integer64', 'real', 'text', 'boolean', and 'datetime'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "auto".to_owned(),
+                "integer".to_owned(),
+                "integer64".to_owned(),
+                "real".to_owned(),
+                "text".to_owned(),
+                "boolean".to_owned(),
+                "datetime".to_owned(),
+            ]),
+            default_value: Some("auto".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File (optional)".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Optional output vector file; if unspecified, the input file is modified."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=parcels.shp --field=DENSITY --expression=\"POP / ($area / 1000000)\" --field_type=real",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        FieldCalculator {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FieldCalculator {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut field_name = String::new();
+        let mut statement = String::new();
+        let mut field_type = "auto".to_string();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-field" {
+                field_name = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-expression" {
+                // the expression may itself contain quotes and equals signs
+                statement = if keyval {
+                    args[i].splitn(2, '=').nth(1).unwrap_or("").to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                let s = statement.trim();
+                if s.len() > 1 && s.starts_with('"') && s.ends_with('"') {
+                    statement = s[1..s.len() - 1].to_string();
+                }
+            } else if flag_val == "-field_type" {
+                field_type = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if output_file.trim().is_empty() {
+            output_file = input_file.clone();
+        } else if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        field_name = field_name.trim().to_string();
+        if field_name.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A field name must be specified.",
+            ));
+        }
+        if statement.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An expression must be specified.",
+            ));
+        }
+        let new_field_type = if field_type.contains("int64") {
+            Some(FieldDataType::Int64)
+        } else if field_type.contains("int") {
+            Some(FieldDataType::Int)
+        } else if field_type.contains("datetime") {
+            Some(FieldDataType::DateTime)
+        } else if field_type.contains("real") || field_type.contains("float") {
+            Some(FieldDataType::Real)
+        } else if field_type.contains("text") || field_type.contains("str") {
+            Some(FieldDataType::Text)
+        } else if field_type.contains("bool") {
+            Some(FieldDataType::Bool)
+        } else {
+            None
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        let expression = Expression::parse(&statement, &input.attributes)?;
+
+        let mut values = Vec::with_capacity(input.num_records);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let atts = input.attributes.get_record(record_num);
+            values.push(expression.evaluate(&atts, &record, record_num));
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        let field_index = output.attributes.get_field_num(&field_name);
+        let (data_type, decimals) = match field_index {
+            Some(index) => {
+                let field = output.attributes.get_field(index);
+                let data_type = match field.field_type {
+                    'N' | 'F' | 'I' | 'O' => {
+                        if field.decimal_count == 0 {
+                            FieldDataType::Int
+                        } else {
+                            FieldDataType::Real
+                        }
+                    }
+                    'L' => FieldDataType::Bool,
+                    'D' => FieldDataType::Date,
+                    'T' => FieldDataType::DateTime,
+                    _ => FieldDataType::Text,
+                };
+                (data_type, field.decimal_count)
+            }
+            None => {
+                let data_type = match new_field_type {
+                    Some(t) => t,
+                    None => infer_field_type(&values),
+                };
+                let values_out: Vec<FieldData> = values
+                    .iter()
+                    .map(|v| to_field_data(v, &data_type))
+                    .collect();
+                let (length, decimals) = field_width(&values_out, &data_type);
+                output.attributes.add_field(&AttributeField::new(
+                    &field_name,
+                    data_type.clone(),
+                    length,
+                    decimals,
+                ));
+                (data_type, decimals)
+            }
+        };
+
+        for record_num in 0..input.num_records {
+            output.add_record(input.get_record(record_num).clone());
+            let mut atts = input.attributes.get_record(record_num);
+            let mut value = to_field_data(&values[record_num], &data_type);
+            if let FieldData::Real(v) = value {
+                // round to the field's precision, since the values are truncated when written
+                let m = 10f64.powi(decimals as i32);
+                value = FieldData::Real((v * m).round() / m);
+            }
+            match field_index {
+                Some(index) => atts[index] = value,
+                None => atts.push(value),
+            }
+            output.attributes.add_record(atts, false);
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// Infers a field type from the non-null calculated values.
+fn infer_field_type(values: &[ExpressionValue]) -> FieldDataType {
+    let mut all_bool = true;
+    let mut all_numeric = true;
+    let mut all_integer = true;
+    for v in values {
+        match v {
+            ExpressionValue::Null => {}
+            ExpressionValue::Bool(_) => {
+                all_numeric = false;
+            }
+            ExpressionValue::Number(z) => {
+                all_bool = false;
+                if z.fract() != 0f64 || z.abs() > i32::max_value() as f64 {
+                    all_integer = false;
+                }
+            }
+            ExpressionValue::Text(_) => {
+                all_bool = false;
+                all_numeric = false;
+            }
+        }
+    }
+    if values.iter().all(|v| v.is_null()) {
+        FieldDataType::Real
+    } else if all_bool {
+        FieldDataType::Bool
+    } else if all_numeric && all_integer {
+        FieldDataType::Int
+    } else if all_numeric {
+        FieldDataType::Real
+    } else {
+        FieldDataType::Text
+    }
+}
+
+/// Converts a calculated value to the data type of the output field.
+fn to_field_data(value: &ExpressionValue, data_type: &FieldDataType) -> FieldData {
+    if value.is_null() {
+        return FieldData::Null;
+    }
+    match data_type {
+        FieldDataType::Int => match value.as_number() {
+            Some(z) if z.abs() <= i32::max_value() as f64 => FieldData::Int(z.round() as i32),
+            _ => FieldData::Null,
+        },
+        FieldDataType::Int64 => match value.as_number() {
+            Some(z) if z.abs() < i64::max_value() as f64 => FieldData::Int64(z.round() as i64),
+            _ => FieldData::Null,
+        },
+        FieldDataType::Real => match value.as_number() {
+            Some(z) => FieldData::Real(z),
+            None => FieldData::Null,
+        },
+        FieldDataType::Bool => match value {
+            ExpressionValue::Text(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "1" => FieldData::Bool(true),
+                "false" | "f" | "no" | "n" | "0" => FieldData::Bool(false),
+                _ => FieldData::Null,
+            },
+            _ => FieldData::Bool(value.is_true()),
+        },
+        FieldDataType::Date => {
+            let s = value.as_text().replace("-", "").replace("/", "");
+            if s.len() == 8 && s.chars().all(|c| c.is_ascii_digit()) {
+                FieldData::Date(DateData {
+                    year: s[0..4].parse::<u16>().unwrap_or(0),
+                    month: s[4..6].parse::<u8>().unwrap_or(0),
+                    day: s[6..8].parse::<u8>().unwrap_or(0),
+                })
+            } else {
+                FieldData::Null
+            }
+        }
+        FieldDataType::DateTime => match DateTimeData::parse(&value.as_text()) {
+            Some(dt) => FieldData::DateTime(dt),
+            None => FieldData::Null,
+        },
+        FieldDataType::Text => FieldData::Text(value.as_text()),
+    }
+}
+
+/// Returns the field length and decimal count needed to store a set of values.
+fn field_width(values: &[FieldData], data_type: &FieldDataType) -> (u8, u8) {
+    match data_type {
+        FieldDataType::Int => {
+            let mut length = 10usize;
+            for v in values {
+                if let FieldData::Int(z) = v {
+                    length = length.max(z.to_string().len());
+                }
+            }
+            (length as u8, 0u8)
+        }
+        FieldDataType::Real => {
+            let decimals = 6usize;
+            let mut length = 12usize;
+            for v in values {
+                if let FieldData::Real(z) = v {
+                    length = length.max(format!("{}", z.trunc() as i64).len() + 1 + decimals);
+                }
+            }
+            (length.min(32) as u8, decimals as u8)
+        }
+        FieldDataType::Text => {
+            let mut length = 1usize;
+            for v in values {
+                if let FieldData::Text(s) = v {
+                    length = length.max(s.len());
+                }
+            }
+            (length.min(254) as u8, 0u8)
+        }
+        FieldDataType::Bool => (1u8, 0u8),
+        FieldDataType::Int64 => (20u8, 0u8),
+        FieldDataType::Date => (8u8, 0u8),
+        FieldDataType::DateTime => (23u8, 0u8),
+    }
+}
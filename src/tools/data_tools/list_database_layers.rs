@@ -0,0 +1,181 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use crate::vector::{is_spatialite_file, list_spatialite_layers};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool lists the vector layers contained within a SpatiaLite (SQLite) database, such as
+/// one used to store the data of a QGIS project. For each layer, the table name, geometry type,
+/// spatial reference identifier (SRID), number of features, and attribute fields are printed
+/// to the StdOut output stream (e.g. console).
+///
+/// Any vector tool can read a layer from, or write a layer to, a SpatiaLite database by
+/// appending the layer name to the database file name, e.g. `data.sqlite|layername=roads`.
+/// When reading, the features may also be filtered by the database using an SQL condition on
+/// the layer's attributes, e.g. `data.sqlite|layername=roads|where=lanes >= 4`. Output layers
+/// replace any existing layer of the same name. SpatiaLite support requires that WhiteboxTools
+/// be compiled with the `spatialite` feature.
+///
+/// # See Also
+/// `ExportTableToParquet`, `MergeVectors`
+pub struct ListDatabaseLayers {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ListDatabaseLayers {
+    pub fn new() -> ListDatabaseLayers {
+        // public constructor
+        let name = "ListDatabaseLayers".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Lists the vector layers within a SpatiaLite database.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Database File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input SpatiaLite database file (e.g. *.sqlite).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --input=project.sqlite",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ListDatabaseLayers {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ListDatabaseLayers {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+
+        if !is_spatialite_file(&input_file) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file must be a SpatiaLite database (*.sqlite, *.sqlite3, *.spatialite, or *.db).",
+            ));
+        }
+
+        let layers = list_spatialite_layers(&input_file)?;
+        if layers.is_empty() {
+            println!("The database does not contain any vector layers.");
+        }
+        for layer in &layers {
+            println!("\nLayer: {}", layer.name);
+            println!(
+                "Geometry: {} ({})",
+                layer.geometry_type, layer.geometry_column
+            );
+            println!("SRID: {}", layer.srid);
+            println!("Features: {}", layer.num_features);
+            println!("Fields:");
+            for (name, declared_type) in &layer.fields {
+                println!("  {} ({})", name, declared_type);
+            }
+        }
+
+        Ok(())
+    }
+}
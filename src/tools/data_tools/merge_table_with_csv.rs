@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Prof. John Lindsay
 Created: 11/10/2018
-Last Modified: 07/08/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -361,7 +361,9 @@ impl WhiteboxTool for MergeTableWithCsv {
                         FieldDataType::Text => {
                             imported_data.push(FieldData::Text(line_vec[i].trim().to_string()))
                         }
-                        FieldDataType::Date => {
+                        FieldDataType::Int64 => imported_data
+                            .push(FieldData::Int64(line_vec[i].trim().parse::<i64>().unwrap())),
+                        FieldDataType::Date | FieldDataType::DateTime => {
                             imported_data.push(FieldData::Text(line_vec[i].trim().to_string()))
                         }
                     }
@@ -3,10 +3,14 @@ mod add_point_coordinates_to_table;
 mod clean_vector;
 mod convert_nodata_to_zero;
 mod convert_raster_format;
+mod convert_to_whitebox_raster;
 mod csv_points_to_vector;
 mod export_table_to_csv;
+mod export_table_to_parquet;
+mod field_calculator;
 mod join_tables;
 mod lines_to_polygons;
+mod list_database_layers;
 mod merge_table_with_csv;
 mod merge_vectors;
 mod modify_nodata_value;
@@ -14,26 +18,34 @@ mod multipart_to_singlepart;
 mod new_raster;
 mod polygons_to_lines;
 mod print_geotiff_tags;
+mod raster_to_mesh;
 mod raster_to_vector_lines;
 mod raster_to_vector_points;
 mod raster_to_vector_polygons;
 mod reinitialize_attribute_table;
 mod remove_polygon_holes;
+mod reproject_raster;
+mod reproject_vector;
 mod set_nodata_value;
 mod singlepart_to_multipart;
 mod vector_lines_to_raster;
 mod vector_points_to_raster;
 mod vector_polygons_to_raster;
+mod vector_to_raster;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::add_point_coordinates_to_table::AddPointCoordinatesToTable;
 pub use self::clean_vector::CleanVector;
 pub use self::convert_nodata_to_zero::ConvertNodataToZero;
 pub use self::convert_raster_format::ConvertRasterFormat;
+pub use self::convert_to_whitebox_raster::ConvertToWhiteboxRaster;
 pub use self::csv_points_to_vector::CsvPointsToVector;
 pub use self::export_table_to_csv::ExportTableToCsv;
+pub use self::export_table_to_parquet::ExportTableToParquet;
+pub use self::field_calculator::FieldCalculator;
 pub use self::join_tables::JoinTables;
 pub use self::lines_to_polygons::LinesToPolygons;
+pub use self::list_database_layers::ListDatabaseLayers;
 pub use self::merge_table_with_csv::MergeTableWithCsv;
 pub use self::merge_vectors::MergeVectors;
 pub use self::modify_nodata_value::ModifyNoDataValue;
@@ -41,13 +53,17 @@ pub use self::multipart_to_singlepart::MultiPartToSinglePart;
 pub use self::new_raster::NewRasterFromBase;
 pub use self::polygons_to_lines::PolygonsToLines;
 pub use self::print_geotiff_tags::PrintGeoTiffTags;
+pub use self::raster_to_mesh::RasterToMesh;
 pub use self::raster_to_vector_lines::RasterToVectorLines;
 pub use self::raster_to_vector_points::RasterToVectorPoints;
 pub use self::raster_to_vector_polygons::RasterToVectorPolygons;
 pub use self::reinitialize_attribute_table::ReinitializeAttributeTable;
 pub use self::remove_polygon_holes::RemovePolygonHoles;
+pub use self::reproject_raster::ReprojectRaster;
+pub use self::reproject_vector::ReprojectVector;
 pub use self::set_nodata_value::SetNodataValue;
 pub use self::singlepart_to_multipart::SinglePartToMultiPart;
 pub use self::vector_lines_to_raster::VectorLinesToRaster;
 pub use self::vector_points_to_raster::VectorPointsToRaster;
 pub use self::vector_polygons_to_raster::VectorPolygonsToRaster;
+pub use self::vector_to_raster::VectorToRaster;
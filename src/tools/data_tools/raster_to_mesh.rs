@@ -0,0 +1,542 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::rendering::{Mesh, MeshFormat};
+use crate::tools::*;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a digital elevation model (DEM; `--dem`) into a triangulated surface mesh
+/// for 3D printing and web-based 3D visualization. The format of the output file (`--output`) is
+/// determined by its extension:
+///
+/// | Extension | Format |
+/// | :--- | :--- |
+/// | .stl | Binary STL, positioned at the origin for 3D printing; colours are not stored |
+/// | .obj | Wavefront OBJ, with vertex colours written as 'v x y z r g b' records |
+/// | .ply | Binary Stanford PLY, with double-precision coordinates and vertex colours |
+/// | .gltf | glTF 2.0, with an embedded buffer, for web viewers such as three.js and Cesium |
+/// | .glb | Binary glTF 2.0 |
+/// | .json | Cesium 3D Tiles tileset, with a single Batched 3D Model (.b3dm) tile of the same name |
+///
+/// Each vertex of the mesh corresponds to the centre of a grid cell, and each block of four
+/// neighbouring cells is split into two triangles. Blocks containing NoData cells are represented
+/// by a single triangle, if three of the cells are valid, or are left as holes otherwise. The mesh
+/// may be decimated (`--decimation`) by only using every n<sup>th</sup> row and column of the DEM
+/// (along with the last row and column), which reduces the number of triangles by a factor of
+/// roughly n<sup>2</sup>. Elevations are multiplied by the vertical exaggeration (`--z_factor`).
+///
+/// The mesh may optionally be draped with an image (`--image`), which is sampled at the vertex
+/// locations to give the vertex colours. Colour composite (RGB) and multi-band images are coloured
+/// using their first three bands, while single-band images are displayed in greyscale, stretched
+/// between their minimum and maximum values.
+///
+/// For 3D printing, the `--solid` flag closes the surface into a watertight solid by adding walls
+/// around its edges and a flat base at a distance of `--base_thickness` (in exaggerated elevation
+/// units) below the lowest elevation, by default 10% of the exaggerated relief. The
+/// `--model_width` parameter scales the model uniformly, such that its east-west extent equals the
+/// specified width (e.g. in millimetres), and places its south-west corner and base at the origin.
+/// Scaled models are not georeferenced; otherwise, the DEM's coordinate reference system is used to
+/// place 3D Tiles outputs on the globe.
+///
+/// # See Also
+/// `LidarToMesh`, `Hillshade`
+pub struct RasterToMesh {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RasterToMesh {
+    pub fn new() -> RasterToMesh {
+        // public constructor
+        let name = "RasterToMesh".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Converts a DEM, optionally draped with an image, into an STL, OBJ, PLY, glTF, or 3D Tiles mesh.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Drape Image File (optional)".to_owned(),
+            flags: vec!["--image".to_owned()],
+            description: "Optional input image draped over the mesh as vertex colours.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output mesh file (*.stl, *.obj, *.ply, *.gltf, *.glb, or a 3D Tiles *.json tileset).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Vertical Exaggeration".to_owned(),
+            flags: vec!["--z_factor".to_owned()],
+            description: "Vertical exaggeration applied to the elevations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Decimation Factor".to_owned(),
+            flags: vec!["--decimation".to_owned()],
+            description: "Use every nth row and column of the DEM.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output a closed solid?".to_owned(),
+            flags: vec!["--solid".to_owned()],
+            description: "Close the surface with walls and a flat base, e.g. for 3D printing."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Thickness (optional)".to_owned(),
+            flags: vec!["--base_thickness".to_owned()],
+            description: "Optional distance of the base below the lowest elevation, in exaggerated elevation units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Model Width (optional)".to_owned(),
+            flags: vec!["--model_width".to_owned()],
+            description:
+                "Optional east-west width to which the model is scaled, e.g. in millimetres."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=terrain.stl --z_factor=2.0 --decimation=4 --solid --model_width=150.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --image=orthophoto.tif -o=terrain.glb --z_factor=1.5", short_exe, name).replace("*", &sep);
+
+        RasterToMesh {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RasterToMesh {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut dem_file = String::new();
+        let mut image_file = String::new();
+        let mut output_file = String::new();
+        let mut z_factor = 1f64;
+        let mut decimation = 1usize;
+        let mut solid = false;
+        let mut base_thickness = f64::NAN;
+        let mut model_width = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-image" {
+                image_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-solid" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    solid = true;
+                }
+            } else if flag_val == "-decimation" {
+                decimation = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f32>()
+                .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-z_factor"
+                || flag_val == "-base_thickness"
+                || flag_val == "-model_width"
+            {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-z_factor" => z_factor = val,
+                    "-base_thickness" => base_thickness = val,
+                    _ => model_width = val,
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if MeshFormat::from_file_name(&output_file).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unsupported mesh format; the output file must have a .stl, .obj, .ply, .gltf, .glb, or .json (3D Tiles tileset) extension.",
+            ));
+        }
+        if decimation < 1 {
+            decimation = 1;
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !dem_file.contains(sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !image_file.is_empty() && !image_file.contains(sep) && !image_file.contains("/") {
+            image_file = format!("{}{}", working_directory, image_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let image = if !image_file.is_empty() {
+            let image = Raster::new(&image_file, "r")?;
+            // the image is sampled at the vertex coordinates and may have a different resolution,
+            // but it must share the CRS of the DEM
+            if image.has_same_crs(&dem) {
+                Some(image)
+            } else {
+                Some(align_raster_input(&dem, image)?)
+            }
+        } else {
+            None
+        };
+        if !dem.is_north_up_square_grid() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "This tool does not currently support hexagonal or rotated grids.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let mut sample_rows: Vec<isize> = (0..rows).step_by(decimation).collect();
+        if *sample_rows.last().unwrap() != rows - 1 {
+            sample_rows.push(rows - 1);
+        }
+        let mut sample_cols: Vec<isize> = (0..columns).step_by(decimation).collect();
+        if *sample_cols.last().unwrap() != columns - 1 {
+            sample_cols.push(columns - 1);
+        }
+
+        // the top surface vertices
+        let mut mesh = Mesh::new();
+        let mut vertex_ids = vec![vec![None; sample_cols.len()]; sample_rows.len()];
+        for (i, &row) in sample_rows.iter().enumerate() {
+            for (j, &col) in sample_cols.iter().enumerate() {
+                let z = dem.get_value(row, col);
+                if z != nodata {
+                    vertex_ids[i][j] = Some(mesh.add_vertex(
+                        dem.get_x_from_column(col),
+                        dem.get_y_from_row(row),
+                        z * z_factor,
+                    ));
+                }
+            }
+        }
+        if mesh.vertices.len() < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The DEM does not contain enough valid grid cells to build a mesh.",
+            ));
+        }
+
+        // the top surface triangles, counter-clockwise when viewed from above
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for i in 0..sample_rows.len() - 1 {
+            for j in 0..sample_cols.len() - 1 {
+                let tl = vertex_ids[i][j];
+                let tr = vertex_ids[i][j + 1];
+                let bl = vertex_ids[i + 1][j];
+                let br = vertex_ids[i + 1][j + 1];
+                match (tl, tr, bl, br) {
+                    (Some(tl), Some(tr), Some(bl), Some(br)) => {
+                        mesh.add_triangle(tl, bl, br);
+                        mesh.add_triangle(tl, br, tr);
+                    }
+                    (None, Some(tr), Some(bl), Some(br)) => mesh.add_triangle(tr, bl, br),
+                    (Some(tl), None, Some(bl), Some(br)) => mesh.add_triangle(tl, bl, br),
+                    (Some(tl), Some(tr), None, Some(br)) => mesh.add_triangle(tl, br, tr),
+                    (Some(tl), Some(tr), Some(bl), None) => mesh.add_triangle(tl, bl, tr),
+                    _ => {}
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (sample_rows.len() - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Triangulating: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // drape the image over the surface
+        if let Some(ref image) = image {
+            if verbose {
+                println!("Draping image...");
+            }
+            let num_bands = image.num_bands();
+            let samples: Vec<Vec<f64>> = mesh
+                .vertices
+                .iter()
+                .map(|v| {
+                    let row = image.get_row_from_y(v[1]);
+                    let col = image.get_column_from_x(v[0]);
+                    (0..num_bands.min(3))
+                        .map(|band| image.get_value_band(row, col, band))
+                        .collect()
+                })
+                .collect();
+            let image_nodata = image.configs.nodata;
+            let (min_value, max_value) = if num_bands >= 3 {
+                // RGB composites are 8-bit, while the bands of other images may be wider
+                let mut max_value = 0f64;
+                for s in &samples {
+                    for &val in s {
+                        if val != image_nodata && val > max_value {
+                            max_value = val;
+                        }
+                    }
+                }
+                (
+                    0f64,
+                    if max_value > 255f64 {
+                        max_value
+                    } else {
+                        255f64
+                    },
+                )
+            } else {
+                (image.configs.minimum, image.configs.maximum)
+            };
+            let range = (max_value - min_value).max(f64::EPSILON);
+            for s in &samples {
+                if s.iter().any(|&val| val == image_nodata) {
+                    mesh.colours.push([128u8, 128u8, 128u8]);
+                } else {
+                    let c: Vec<u8> = s
+                        .iter()
+                        .map(|&val| {
+                            ((val - min_value) / range * 255f64).max(0f64).min(255f64) as u8
+                        })
+                        .collect();
+                    if c.len() >= 3 {
+                        mesh.colours.push([c[0], c[1], c[2]]);
+                    } else {
+                        mesh.colours.push([c[0], c[0], c[0]]);
+                    }
+                }
+            }
+        }
+
+        // close the surface into a solid
+        if solid {
+            let (min, max) = mesh.bounds();
+            if base_thickness.is_nan() || base_thickness <= 0f64 {
+                base_thickness = ((max[2] - min[2]) * 0.1).max(f64::EPSILON);
+                if max[2] == min[2] {
+                    base_thickness = dem.configs.resolution_x;
+                }
+            }
+            let base_z = min[2] - base_thickness;
+            let num_top_vertices = mesh.vertices.len();
+            for k in 0..num_top_vertices {
+                let v = mesh.vertices[k];
+                mesh.add_vertex(v[0], v[1], base_z);
+            }
+            if !mesh.colours.is_empty() {
+                for k in 0..num_top_vertices {
+                    let c = mesh.colours[k];
+                    mesh.colours.push(c);
+                }
+            }
+            let offset = num_top_vertices as u32;
+
+            // edges used by a single triangle are on the boundary of the surface
+            let mut edge_counts: HashMap<(u32, u32), usize> = HashMap::new();
+            for t in &mesh.triangles {
+                for k in 0..3 {
+                    let (a, b) = (t[k], t[(k + 1) % 3]);
+                    *edge_counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+                }
+            }
+            let top_triangles = mesh.triangles.clone();
+            for t in &top_triangles {
+                // the base mirrors the top, with the opposite orientation
+                mesh.add_triangle(t[0] + offset, t[2] + offset, t[1] + offset);
+                for k in 0..3 {
+                    let (a, b) = (t[k], t[(k + 1) % 3]);
+                    if edge_counts[&(a.min(b), a.max(b))] == 1 {
+                        mesh.add_triangle(a, a + offset, b + offset);
+                        mesh.add_triangle(a, b + offset, b);
+                    }
+                }
+            }
+        }
+
+        // scale the model
+        let scaled = model_width > 0f64;
+        if scaled {
+            let (min, max) = mesh.bounds();
+            let scale = model_width / (max[0] - min[0]).max(f64::EPSILON);
+            for v in mesh.vertices.iter_mut() {
+                for k in 0..3 {
+                    v[k] = (v[k] - min[k]) * scale;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let crs = if scaled { None } else { dem.get_crs() };
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match mesh.write(&output_file, crs.as_ref()) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!("Number of vertices: {}", mesh.vertices.len());
+            println!("Number of triangles: {}", mesh.triangles.len());
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
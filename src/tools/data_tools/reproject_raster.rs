@@ -0,0 +1,441 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::spatial_ref_system::CoordinateReferenceSystem;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool transforms a raster (`--input`) from its current coordinate reference system (CRS)
+/// into the CRS specified by an EPSG code (`--epsg`). The input raster must have a defined CRS,
+/// either through an EPSG code (e.g. GeoTIFF geokeys) or a WKT projection string. The supported
+/// map projections include Transverse Mercator (and therefore all UTM zones), Lambert Conformal
+/// Conic, polar and oblique Stereographic, and Mercator, in addition to geographic coordinates.
+///
+/// The extent of the output grid is determined by transforming the boundary of the input grid
+/// into the target CRS. The output grid resolution (`--resolution`) is optional; by default, a
+/// resolution approximately equal to the input resolution at the centre of the grid is used.
+/// Each output grid cell is then mapped back into the source CRS and its value is estimated
+/// using one of three resampling methods (`--method`): nearest neighbour ('nn'), bilinear
+/// interpolation ('bilinear'), and cubic convolution ('cc'). Nearest neighbour resampling
+/// should be used for categorical data.
+///
+/// Transformations between CRSs based on different ellipsoids are carried out through
+/// geocentric coordinates and do not apply datum-shift parameters.
+///
+/// # See Also
+/// `ReprojectVector`, `Resample`
+pub struct ReprojectRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectRaster {
+    pub fn new() -> ReprojectRaster {
+        // public constructor
+        let name = "ReprojectRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Transforms a raster into a new coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target EPSG Code".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG code of the output coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Grid Resolution (optional)".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Output grid resolution, in the units of the target CRS.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Resampling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Resampling method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution)".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "nn".to_owned(),
+                "bilinear".to_owned(),
+                "cc".to_owned(),
+            ]),
+            default_value: Some("cc".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem_latlong.tif -o=dem_utm.tif --epsg=32617 --resolution=10.0 --method=bilinear", short_exe, name).replace("*", &sep);
+
+        ReprojectRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut epsg = 0u16;
+        let mut resolution = 0f64;
+        let mut method = String::from("cc");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-epsg" {
+                epsg = if keyval {
+                    vec[1].to_string().parse::<u16>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<u16>().unwrap()
+                };
+            } else if flag_val == "-resolution" {
+                resolution = if keyval {
+                    vec[1].to_string().parse::<f64>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<f64>().unwrap()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_string().to_lowercase()
+                } else {
+                    args[i + 1].to_string().to_lowercase()
+                };
+                if method.contains("nn") || method.contains("nearest") {
+                    method = "nn".to_string();
+                } else if method.contains("bi") {
+                    method = "bilinear".to_string();
+                } else {
+                    method = "cc".to_string();
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let source_crs = if input.configs.epsg_code != 0 {
+            CoordinateReferenceSystem::from_epsg(input.configs.epsg_code)?
+        } else if !input.configs.coordinate_ref_system_wkt.is_empty() {
+            CoordinateReferenceSystem::from_wkt(&input.configs.coordinate_ref_system_wkt)?
+        } else if !input.configs.projection.is_empty() {
+            CoordinateReferenceSystem::from_wkt(&input.configs.projection)?
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input raster does not have a defined coordinate reference system.",
+            ));
+        };
+        let target_crs = CoordinateReferenceSystem::from_epsg(epsg)?;
+
+        // Find the output extent by transforming the boundary of the input grid.
+        let rows_in = input.configs.rows as isize;
+        let columns_in = input.configs.columns as isize;
+        let (west, east) = (input.configs.west, input.configs.east);
+        let (south, north) = (input.configs.south, input.configs.north);
+        let num_edge_samples = 200;
+        let mut out_west = f64::INFINITY;
+        let mut out_east = f64::NEG_INFINITY;
+        let mut out_south = f64::INFINITY;
+        let mut out_north = f64::NEG_INFINITY;
+        for i in 0..=num_edge_samples {
+            let f = i as f64 / num_edge_samples as f64;
+            let x = west + f * (east - west);
+            let y = south + f * (north - south);
+            for &(px, py) in &[(x, north), (x, south), (west, y), (east, y)] {
+                let (tx, ty) = source_crs.transform_to(&target_crs, px, py);
+                if tx.is_finite() && ty.is_finite() {
+                    out_west = out_west.min(tx);
+                    out_east = out_east.max(tx);
+                    out_south = out_south.min(ty);
+                    out_north = out_north.max(ty);
+                }
+            }
+        }
+        if !out_west.is_finite() || !out_north.is_finite() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input raster extent could not be transformed into the target CRS.",
+            ));
+        }
+
+        if resolution <= 0f64 {
+            // estimate the output resolution at the grid centre
+            let xc = (west + east) / 2f64;
+            let yc = (south + north) / 2f64;
+            let (x0, y0) = source_crs.transform_to(&target_crs, xc, yc);
+            let (x1, y1) =
+                source_crs.transform_to(&target_crs, xc + input.configs.resolution_x, yc);
+            let (x2, y2) =
+                source_crs.transform_to(&target_crs, xc, yc + input.configs.resolution_y);
+            resolution = (((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt()
+                + ((x2 - x0).powi(2) + (y2 - y0).powi(2)).sqrt())
+                / 2f64;
+        }
+
+        let rows = ((out_north - out_south) / resolution).ceil() as isize;
+        let columns = ((out_east - out_west) / resolution).ceil() as isize;
+
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        configs.rows = rows as usize;
+        configs.columns = columns as usize;
+        configs.north = out_north;
+        configs.south = out_north - rows as f64 * resolution;
+        configs.west = out_west;
+        configs.east = out_west + columns as f64 * resolution;
+        configs.resolution_x = resolution;
+        configs.resolution_y = resolution;
+        configs.nodata = input.configs.nodata;
+        configs.data_type = input.configs.data_type;
+        configs.photometric_interp = input.configs.photometric_interp;
+        configs.palette = input.configs.palette.clone();
+        configs.z_units = input.configs.z_units.clone();
+        configs.xy_units = if target_crs.is_geographic() {
+            "degrees".to_string()
+        } else {
+            "metres".to_string()
+        };
+        configs.epsg_code = epsg;
+        configs.coordinate_ref_system_wkt = target_crs.wkt.clone();
+        configs.projection = target_crs.wkt.clone();
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        if method != "nn"
+            && output.configs.data_type != DataType::F32
+            && output.configs.data_type != DataType::F64
+        {
+            output.configs.data_type = DataType::F32;
+        }
+
+        let interp_method = match method.as_ref() {
+            "nn" => InterpolationMethod::NearestNeighbour,
+            "bilinear" => InterpolationMethod::Bilinear,
+            _ => InterpolationMethod::CubicConvolution,
+        };
+        let source_crs = Arc::new(source_crs);
+        let target_crs = Arc::new(target_crs);
+        let out_north = output.configs.north;
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let source_crs = source_crs.clone();
+            let target_crs = target_crs.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = input.configs.nodata;
+                let res_x = input.configs.resolution_x;
+                let res_y = input.configs.resolution_y;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    let y = out_north - (row as f64 + 0.5) * resolution;
+                    for col in 0..columns {
+                        let x = out_west + (col as f64 + 0.5) * resolution;
+                        let (sx, sy) = target_crs.transform_to(&source_crs, x, y);
+                        if !sx.is_finite() || !sy.is_finite() {
+                            continue;
+                        }
+                        // fractional grid position of the point in the source image
+                        let src_col = (sx - input.configs.west) / res_x - 0.5;
+                        let src_row = (input.configs.north - sy) / res_y - 0.5;
+                        if src_col < -0.5
+                            || src_row < -0.5
+                            || src_col > columns_in as f64 - 0.5
+                            || src_row > rows_in as f64 - 0.5
+                        {
+                            continue;
+                        }
+                        data[col as usize] =
+                            input.get_interpolated_value(src_row, src_col, interp_method);
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        for row in 0..rows {
+            let (r, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(r, data);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Target EPSG code: {}", epsg));
+        output.add_metadata_entry(format!("Resampling method: {}", method));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
@@ -0,0 +1,274 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::spatial_ref_system::CoordinateReferenceSystem;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool transforms the coordinates of a vector file (`--input`) from its current coordinate
+/// reference system (CRS), defined by the Shapefile's projection (.prj) file, into the CRS
+/// specified by an EPSG code (`--epsg`). All geometry types are supported and the attribute
+/// table is copied to the output file unaltered. Z and M values are not modified.
+///
+/// The supported map projections include Transverse Mercator (and therefore all UTM zones),
+/// Lambert Conformal Conic, polar and oblique Stereographic, and Mercator, in addition to
+/// geographic coordinates. Transformations between CRSs based on different ellipsoids are
+/// carried out through geocentric coordinates and do not apply datum-shift parameters.
+///
+/// Straight line segments are not densified prior to transformation, and therefore long
+/// segments in the input may not follow the curved path that they would in the target CRS.
+///
+/// # See Also
+/// `ReprojectRaster`
+pub struct ReprojectVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectVector {
+    pub fn new() -> ReprojectVector {
+        // public constructor
+        let name = "ReprojectVector".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Transforms a vector file into a new coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target EPSG Code".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG code of the output coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads_latlong.shp -o=roads_utm.shp --epsg=32617",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ReprojectVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut epsg = 0u16;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-epsg" {
+                epsg = if keyval {
+                    vec[1].to_string().parse::<u16>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<u16>().unwrap()
+                };
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let input = Shapefile::read(&input_file)?;
+
+        if input.projection.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector does not have a defined coordinate reference system (.prj file).",
+            ));
+        }
+        let source_crs = CoordinateReferenceSystem::from_wkt(&input.projection)?;
+        let target_crs = CoordinateReferenceSystem::from_epsg(epsg)?;
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+        output.projection = target_crs.wkt.clone();
+
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let mut new_record = ShapefileGeometry::new(record.shape_type);
+            new_record.parts = record.parts.clone();
+            new_record.num_parts = record.num_parts;
+            for p in &record.points {
+                let (x, y) = source_crs.transform_to(&target_crs, p.x, p.y);
+                new_record.add_point(Point2D::new(x, y));
+            }
+            new_record.z_array = record.z_array.clone();
+            new_record.z_min = record.z_min;
+            new_record.z_max = record.z_max;
+            new_record.m_array = record.m_array.clone();
+            new_record.m_min = record.m_min;
+            new_record.m_max = record.m_max;
+            output.add_record(new_record);
+
+            let atts = input.attributes.get_record(record_num);
+            output.attributes.add_record(atts.clone(), false);
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
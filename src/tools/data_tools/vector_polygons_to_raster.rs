@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 17/04/2018
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -279,9 +279,11 @@ impl WhiteboxTool for VectorPolygonsToRaster {
             for record_num in 0..vector_data.num_records {
                 key = match vector_data.attributes.get_value(record_num, &field_name) {
                     FieldData::Int(val) => val.to_string(),
+                    FieldData::Int64(val) => val.to_string(),
                     FieldData::Real(val) => val.to_string(),
                     FieldData::Text(val) => val.to_string(),
                     FieldData::Date(val) => val.to_string(),
+                    FieldData::DateTime(val) => val.to_string(),
                     FieldData::Bool(val) => val.to_string(),
                     FieldData::Null => "null".to_string(),
                 };
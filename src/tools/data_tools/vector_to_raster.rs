@@ -0,0 +1,736 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::{Array2D, BoundingBox, Point2D};
+use crate::tools::*;
+use crate::vector::{FieldData, ShapeType, Shapefile};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a vector file of any base ShapeType (points, lines or polygons) into a raster.
+/// The value that is burned into the grid cells covered by each feature is either read from a
+/// numeric field in the attribute table (`--field`) or, if no field is specified, a constant
+/// (`--value`, default 1.0). Features with null attribute values are not rasterized.
+///
+/// Point features are burned into the grid cell that contains them. By default, line features
+/// are rasterized as a single-cell wide, 8-connected path between the cells containing each
+/// pair of vertices (i.e. Bresenham's line algorithm), and polygons are filled with those cells
+/// whose centres are within the polygon, with holes excluded. When the `--all_touched` flag is
+/// specified, every cell that is touched by a line, or by a polygon's boundary, is burned, which
+/// ensures that narrow or small features are not lost and that lines are represented by a
+/// 4-connected path.
+///
+/// Where features overlap, the priority rule (`--priority`) determines which value is retained
+/// in a grid cell: the value of the first or last feature in the file ('first', 'last'), or the
+/// smallest or largest of the overlapping values ('min', 'max'). The default is 'last'.
+///
+/// The output raster's rows, columns, cell size and alignment are taken from a reference raster
+/// (`--base`), if one is specified. Otherwise, the user must specify a cell size (`--cell_size`)
+/// and the output grid will cover the extent of the input vector. Grid cells that are not
+/// covered by any feature are assigned 0.0, or NoData if the `--nodata` flag is specified. The
+/// output is of an integer data type when all of the burned values are whole numbers.
+///
+/// # See Also
+/// `VectorPointsToRaster`, `VectorLinesToRaster`, `VectorPolygonsToRaster`
+pub struct VectorToRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl VectorToRaster {
+    /// public constructor
+    pub fn new() -> VectorToRaster {
+        let name = "VectorToRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Converts a vector of points, lines or polygons into a raster by burning an attribute or constant value."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name (optional)".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Optional numeric attribute field containing the values to burn."
+                .to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Constant Value".to_owned(),
+            flags: vec!["--value".to_owned()],
+            description: "Constant value to burn when no field is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Priority For Overlapping Features".to_owned(),
+            flags: vec!["--priority".to_owned()],
+            description:
+                "Value retained where features overlap; options include 'first', 'last', 'min' and 'max'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "first".to_owned(),
+                "last".to_owned(),
+                "min".to_owned(),
+                "max".to_owned(),
+            ]),
+            default_value: Some("last".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Burn all touched cells?".to_owned(),
+            flags: vec!["--all_touched".to_owned()],
+            description: "Burn every grid cell touched by a line or polygon boundary.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Background value is NoData?".to_owned(),
+            flags: vec!["--nodata".to_owned()],
+            description:
+                "Background value to set to NoData. Without this flag, it will be set to 0.0."
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("true".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description:
+                "Optionally specified input base raster file, from which the output grid is taken."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp --field=ELEV -o=output.tif --priority=max --nodata --cell_size=10.0
+        >>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp --value=1.0 -o=output.tif --all_touched --base=existing_raster.tif", short_exe, name).replace("*", &sep);
+
+        VectorToRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for VectorToRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut constant_value = 1f64;
+        let mut output_file = String::new();
+        let mut priority = Priority::Last;
+        let mut all_touched = false;
+        let mut cell_size = 0f64;
+        let mut base_file = String::new();
+        let nodata = -32768.0f64;
+        let mut background_val = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-field" {
+                field_name = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-value" {
+                constant_value = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-priority" {
+                let val = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                priority = if val.contains("first") {
+                    Priority::First
+                } else if val.contains("min") {
+                    Priority::Min
+                } else if val.contains("max") {
+                    Priority::Max
+                } else {
+                    Priority::Last
+                };
+            } else if flag_val == "-all_touched" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    all_touched = true;
+                }
+            } else if flag_val == "-cell_size" {
+                cell_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-base" {
+                base_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-nodata" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    background_val = nodata;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if base_file.trim().is_empty() && cell_size <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a base raster or a cell size greater than zero must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let vector_data = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        let shape_type = vector_data.header.shape_type.base_shape_type();
+
+        // get the burn value of each feature; features with null values are not rasterized.
+        let mut burn_values: Vec<Option<f64>> = vec![Some(constant_value); vector_data.num_records];
+        if !field_name.trim().is_empty() {
+            let field_index = match vector_data.attributes.get_field_num(&field_name) {
+                Some(i) => i,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The field '{}' was not found in the attribute table.",
+                            field_name
+                        ),
+                    ));
+                }
+            };
+            let field_type = vector_data.attributes.get_field(field_index).field_type;
+            if field_type != 'N' && field_type != 'F' && field_type != 'I' && field_type != 'O' {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The burn field must be of a numeric data type.",
+                ));
+            }
+            for record_num in 0..vector_data.num_records {
+                burn_values[record_num] =
+                    match vector_data.attributes.get_value(record_num, &field_name) {
+                        FieldData::Int(val) => Some(val as f64),
+                        FieldData::Real(val) => Some(val),
+                        _ => None,
+                    };
+            }
+        }
+
+        // Create the output raster, using the grid of the base raster if one was specified.
+        let mut output = if !base_file.trim().is_empty() {
+            if !base_file.contains(&sep) && !base_file.contains("/") {
+                base_file = format!("{}{}", working_directory, base_file);
+            }
+            let base = Raster::new(&base_file, "r")?;
+            let mut output = Raster::initialize_using_file(&output_file, &base);
+            output.configs.nodata = nodata;
+            output
+        } else {
+            // base the output raster on the cell_size and the
+            // extent of the input vector.
+            let west: f64 = vector_data.header.x_min;
+            let north: f64 = vector_data.header.y_max;
+            let rows: isize =
+                (((north - vector_data.header.y_min) / cell_size).ceil() as isize).max(1);
+            let columns: isize =
+                (((vector_data.header.x_max - west) / cell_size).ceil() as isize).max(1);
+            let south: f64 = north - rows as f64 * cell_size;
+            let east = west + columns as f64 * cell_size;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows as usize;
+            configs.columns = columns as usize;
+            configs.north = north;
+            configs.south = south;
+            configs.east = east;
+            configs.west = west;
+            configs.resolution_x = cell_size;
+            configs.resolution_y = cell_size;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            configs.projection = vector_data.projection.clone();
+
+            Raster::initialize_using_config(&output_file, &configs)
+        };
+        output.reinitialize_values(background_val);
+
+        if burn_values.iter().all(|v| match v {
+            Some(val) => val.fract() == 0f64 && val.abs() < i32::max_value() as f64,
+            None => true,
+        }) {
+            output.configs.data_type = DataType::I32;
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        } else {
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        }
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let grid = Grid {
+            west: output.configs.west,
+            north: output.configs.north,
+            resolution_x: output.configs.resolution_x,
+            resolution_y: output.configs.resolution_y,
+            rows: rows,
+            columns: columns,
+        };
+        let raster_bb = BoundingBox::new(
+            output.configs.west,
+            output.configs.east,
+            output.configs.south,
+            output.configs.north,
+        );
+
+        let mut burned: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut output_something = false;
+        let num_records = vector_data.num_records;
+        for record_num in 0..vector_data.num_records {
+            let value = match burn_values[record_num] {
+                Some(v) => v,
+                None => continue,
+            };
+            let record = vector_data.get_record(record_num);
+            let rec_bb = BoundingBox::new(record.x_min, record.x_max, record.y_min, record.y_max);
+            if !rec_bb.overlaps(raster_bb) {
+                continue;
+            }
+
+            let mut cells: Vec<(isize, isize)> = vec![];
+            let mut rings: Vec<&[Point2D]> = vec![];
+            // point records may not have parts
+            let num_parts = record.num_parts.max(1) as usize;
+            for part in 0..num_parts {
+                let start_point_in_part = if record.num_parts > 0 {
+                    record.parts[part] as usize
+                } else {
+                    0
+                };
+                let end_point_in_part = if part + 1 < num_parts {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                let points = &record.points[start_point_in_part..end_point_in_part];
+                if shape_type == ShapeType::Polygon {
+                    rings.push(points);
+                    if all_touched {
+                        for i in 0..points.len() {
+                            let j = (i + 1) % points.len();
+                            cells.extend(grid.line_cells(&points[i], &points[j], true));
+                        }
+                    }
+                } else if shape_type == ShapeType::PolyLine {
+                    for i in 0..points.len().saturating_sub(1) {
+                        cells.extend(grid.line_cells(&points[i], &points[i + 1], all_touched));
+                    }
+                    if points.len() == 1 {
+                        cells.push(grid.cell(&points[0]));
+                    }
+                } else {
+                    for p in points {
+                        cells.push(grid.cell(p));
+                    }
+                }
+            }
+            if !rings.is_empty() {
+                cells.extend(grid.polygon_cells(&rings));
+            }
+
+            for (row, col) in cells {
+                if row < 0 || col < 0 || row >= rows || col >= columns {
+                    continue;
+                }
+                let burn = if burned.get_value(row, col) == 0u8 {
+                    true
+                } else {
+                    let current = output.get_value(row, col);
+                    match priority {
+                        Priority::First => false,
+                        Priority::Last => true,
+                        Priority::Min => value < current,
+                        Priority::Max => value > current,
+                    }
+                };
+                if burn {
+                    output.set_value(row, col, value);
+                    burned.set_value(row, col, 1u8);
+                    output_something = true;
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    println!(
+                        "Rasterizing {} of {}: {}%",
+                        record_num + 1,
+                        num_records,
+                        progress
+                    );
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !output_something && verbose {
+            println!("Warning: No features were output to the raster.");
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Priority {
+    First,
+    Last,
+    Min,
+    Max,
+}
+
+/// The geometry of the output raster grid.
+struct Grid {
+    west: f64,
+    north: f64,
+    resolution_x: f64,
+    resolution_y: f64,
+    rows: isize,
+    columns: isize,
+}
+
+impl Grid {
+    /// Returns the continuous (column, row) grid coordinates of a point.
+    fn grid_coords(&self, p: &Point2D) -> (f64, f64) {
+        (
+            (p.x - self.west) / self.resolution_x,
+            (self.north - p.y) / self.resolution_y,
+        )
+    }
+
+    /// Returns the (row, column) of the cell containing a point.
+    fn cell(&self, p: &Point2D) -> (isize, isize) {
+        let (gx, gy) = self.grid_coords(p);
+        (gy.floor() as isize, gx.floor() as isize)
+    }
+
+    /// Returns the cells along a line segment. When `all_touched` is true, every cell that the
+    /// segment passes through is returned; otherwise, the cells of an 8-connected Bresenham
+    /// line between the cells containing the end points are returned.
+    fn line_cells(&self, p1: &Point2D, p2: &Point2D, all_touched: bool) -> Vec<(isize, isize)> {
+        let mut cells = vec![];
+        let (mut row, mut col) = self.cell(p1);
+        let (end_row, end_col) = self.cell(p2);
+        if !all_touched {
+            let dc = (end_col - col).abs();
+            let dr = -(end_row - row).abs();
+            let step_c = if col < end_col { 1 } else { -1 };
+            let step_r = if row < end_row { 1 } else { -1 };
+            let mut err = dc + dr;
+            loop {
+                cells.push((row, col));
+                if row == end_row && col == end_col {
+                    break;
+                }
+                let e2 = 2 * err;
+                if e2 >= dr {
+                    err += dr;
+                    col += step_c;
+                }
+                if e2 <= dc {
+                    err += dc;
+                    row += step_r;
+                }
+            }
+            return cells;
+        }
+
+        // a grid traversal (Amanatides and Woo, 1987) of the cells crossed by the segment
+        let (gx1, gy1) = self.grid_coords(p1);
+        let (gx2, gy2) = self.grid_coords(p2);
+        let (dx, dy) = (gx2 - gx1, gy2 - gy1);
+        let step_c = if dx > 0f64 { 1 } else { -1 };
+        let step_r = if dy > 0f64 { 1 } else { -1 };
+        let t_delta_x = if dx != 0f64 {
+            1f64 / dx.abs()
+        } else {
+            f64::INFINITY
+        };
+        let t_delta_y = if dy != 0f64 {
+            1f64 / dy.abs()
+        } else {
+            f64::INFINITY
+        };
+        let mut t_max_x = if dx > 0f64 {
+            (col as f64 + 1f64 - gx1) / dx
+        } else if dx < 0f64 {
+            (gx1 - col as f64) / -dx
+        } else {
+            f64::INFINITY
+        };
+        let mut t_max_y = if dy > 0f64 {
+            (row as f64 + 1f64 - gy1) / dy
+        } else if dy < 0f64 {
+            (gy1 - row as f64) / -dy
+        } else {
+            f64::INFINITY
+        };
+        let num_steps = (end_col - col).abs() + (end_row - row).abs();
+        cells.push((row, col));
+        for _ in 0..num_steps {
+            if t_max_x < t_max_y {
+                col += step_c;
+                t_max_x += t_delta_x;
+            } else {
+                row += step_r;
+                t_max_y += t_delta_y;
+            }
+            cells.push((row, col));
+        }
+        cells
+    }
+
+    /// Returns the cells with centres inside of a polygon, using a scan-line fill with the
+    /// even-odd rule, such that the cells within holes are excluded.
+    fn polygon_cells(&self, rings: &[&[Point2D]]) -> Vec<(isize, isize)> {
+        let mut cells = vec![];
+        let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+        for ring in rings {
+            for p in ring.iter() {
+                min_y = min_y.min(p.y);
+                max_y = max_y.max(p.y);
+            }
+        }
+        if min_y > max_y {
+            return cells;
+        }
+        let start_row = (((self.north - max_y) / self.resolution_y - 0.5).floor() as isize).max(0);
+        let end_row =
+            (((self.north - min_y) / self.resolution_y - 0.5).ceil() as isize).min(self.rows - 1);
+        let mut crossings: Vec<f64> = vec![];
+        for row in start_row..=end_row {
+            let y = self.north - (row as f64 + 0.5) * self.resolution_y;
+            crossings.clear();
+            for ring in rings {
+                let n = ring.len();
+                for i in 0..n {
+                    let (p1, p2) = (&ring[i], &ring[(i + 1) % n]);
+                    if (p1.y > y) != (p2.y > y) {
+                        crossings.push(p1.x + (y - p1.y) / (p2.y - p1.y) * (p2.x - p1.x));
+                    }
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for pair in crossings.chunks(2) {
+                if pair.len() < 2 {
+                    break;
+                }
+                // the columns with centres between the two crossings
+                let start_col =
+                    (((pair[0] - self.west) / self.resolution_x - 0.5).ceil() as isize).max(0);
+                let end_col = (((pair[1] - self.west) / self.resolution_x - 0.5).ceil() as isize
+                    - 1)
+                .min(self.columns - 1);
+                for col in start_col..=end_col {
+                    cells.push((row, col));
+                }
+            }
+        }
+        cells
+    }
+}
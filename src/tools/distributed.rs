@@ -0,0 +1,1105 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Runs a tool over a raster that is split into tiles, dispatching the tool runs
+for the tiles to worker processes on one or more machines, e.g.
+
+    whitebox_tools -r=Slope --wd=/shared/data/ --dem=DEM.tif -o=slope.tif \
+        --distribute=workers.txt --dist_tile_size=8192 --dist_halo=1
+
+Workers are listed, either in a file with one worker per line or as a comma-separated
+list, as `local` (a process on this machine) or as an ssh destination (e.g.
+`user@node01`), optionally followed by the number of tiles that the worker may
+process concurrently (e.g. `node01:8`). Remote workers are run over ssh, which must
+be configured for non-interactive (key-based) authentication, and must share the
+working directory with this machine, e.g. over NFS, at the same path. Tiles are held
+in a queue from which idle workers take the next tile, so that faster machines process
+more of the tiles, and a tile that fails is returned to the queue to be retried by
+any worker.
+
+Each tile is extended by a halo of `--dist_halo` cells, which must be at least the
+radius of the tool's neighbourhood (e.g. 1 for Slope) for the stitched output to
+match that of a single run of the tool. Tools whose results depend on cells beyond a
+fixed neighbourhood (e.g. depression removal and watershed delineation) cannot be
+distributed in this way, with the exception of D8FlowAccumulation, for which the flow
+pointer is calculated by tile and the flow accumulated within each tile is then
+reconciled across the tile edges.
+*/
+
+use super::pipeline::trim_flag;
+use super::{apply_framework_flags, ParameterFileType, ParameterType, ToolManager, ToolParameter};
+use crate::raster::{DataType, PhotometricInterpretation, Raster};
+use crate::structures::Array2D;
+use crate::utils::{get_formatted_elapsed_time, Instant};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The number of times that a tile is attempted before the run fails.
+const MAX_ATTEMPTS: usize = 3;
+
+/// Used to give the tile directory of each tiled run a unique name.
+static TILED_RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Tools whose results depend on cells beyond a fixed neighbourhood, and which
+/// therefore cannot be computed tile by tile.
+const GLOBAL_TOOLS: [&str; 25] = [
+    "basins",
+    "breachdepressions",
+    "breachdepressionsleastcost",
+    "clump",
+    "costdistance",
+    "d8massflux",
+    "depthinsink",
+    "dinfflowaccumulation",
+    "dinfmassflux",
+    "downslopedistancetostream",
+    "elevationabovestream",
+    "euclideandistance",
+    "fd8flowaccumulation",
+    "filldepressions",
+    "filldepressionswangandliu",
+    "flowaccumulationfullworkflow",
+    "hillslopes",
+    "impoundmentsizeindex",
+    "isobasins",
+    "sink",
+    "stochasticdepressionanalysis",
+    "strahlerorderbasins",
+    "subbasins",
+    "unnestbasins",
+    "watershed",
+];
+
+enum WorkerHost {
+    Local,
+    Ssh(String),
+}
+
+struct Worker {
+    host: WorkerHost,
+    slots: usize,
+}
+
+impl Worker {
+    fn name(&self) -> String {
+        match &self.host {
+            WorkerHost::Local => "local".to_string(),
+            WorkerHost::Ssh(h) => h.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Tile {
+    // the cells of the tile, excluding the halo
+    row_start: isize,
+    row_end: isize,
+    col_start: isize,
+    col_end: isize,
+    // the cells written to the tile's input rasters, including the halo
+    halo_row_start: isize,
+    halo_row_end: isize,
+    halo_col_start: isize,
+    halo_col_end: isize,
+}
+
+/// The settings shared by each of the tiled tool runs of a distributed run.
+struct DistributedRun {
+    workers: Vec<Worker>,
+    exe: String,
+    working_dir: String,
+    tile_size: usize,
+    framework_args: Vec<String>,
+    verbose: bool,
+}
+
+impl ToolManager {
+    /// Runs a tool over tiles of its raster inputs, dispatching the tiles to the
+    /// `workers` (a file listing the workers, or a comma-separated list), and stitches
+    /// the tiled outputs together. Each tile is `tile_size` cells square, and is extended
+    /// by `halo` cells on each side. `worker_exe` is the path of the whitebox_tools
+    /// executable on the workers, by default the path of this executable.
+    pub fn run_distributed(
+        &self,
+        tool_name: &str,
+        args: Vec<String>,
+        workers: &str,
+        tile_size: usize,
+        halo: usize,
+        worker_exe: &str,
+    ) -> Result<(), Error> {
+        let start = Instant::now();
+        // the framework-level flags apply both here, when writing the stitched outputs,
+        // and to each of the tiled tool runs
+        let tool_args = apply_framework_flags(args.clone())?;
+        let framework_args: Vec<String> = args
+            .into_iter()
+            .filter(|a| !tool_args.contains(a))
+            .collect();
+        let parameters: Vec<ToolParameter> = match self.get_tool(tool_name) {
+            Some(t) => {
+                let o: Value = serde_json::from_str(&t.get_tool_parameters())?;
+                serde_json::from_value(o["parameters"].clone())?
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("Unrecognized tool name {}.", tool_name),
+                ))
+            }
+        };
+        if tile_size == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --dist_tile_size value must be a positive number of cells.",
+            ));
+        }
+        let exe = if worker_exe.is_empty() {
+            env::current_exe()?.to_string_lossy().to_string()
+        } else {
+            worker_exe.to_string()
+        };
+        let run = DistributedRun {
+            workers: parse_workers(workers)?,
+            exe,
+            working_dir: self.working_dir.clone(),
+            tile_size,
+            framework_args,
+            verbose: self.verbose,
+        };
+
+        let normalized_name = tool_name.to_lowercase().replace("_", "");
+        if GLOBAL_TOOLS.contains(&normalized_name.as_str()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} depends on cells beyond a fixed neighbourhood and cannot be run on tiles.",
+                    tool_name
+                ),
+            ));
+        }
+
+        // sort the arguments into the raster inputs and outputs, which are tiled, and
+        // the remaining arguments, which are passed unchanged to each tile
+        let mut fixed_args = vec![];
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for (flag, value) in parse_cli_args(&tool_args) {
+            let p = parameters
+                .iter()
+                .find(|p| p.flags.iter().any(|f| trim_flag(f) == flag));
+            let parameter_type = match p {
+                Some(p) => &p.parameter_type,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unrecognized argument '{}' for tool {}.", flag, tool_name),
+                    ))
+                }
+            };
+            match parameter_type {
+                ParameterType::ExistingFile(ParameterFileType::Raster) => {
+                    inputs.push((flag, resolve_path(&self.working_dir, &value)?));
+                }
+                ParameterType::ExistingFileOrFloat(ParameterFileType::Raster)
+                    if value.parse::<f64>().is_err() =>
+                {
+                    inputs.push((flag, resolve_path(&self.working_dir, &value)?));
+                }
+                ParameterType::NewFile(ParameterFileType::Raster) => {
+                    outputs.push((flag, resolve_path(&self.working_dir, &value)?));
+                }
+                ParameterType::NewFile(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The '{}' output of {} is not a raster; only tools with raster outputs can be run on tiles.",
+                            flag, tool_name
+                        ),
+                    ))
+                }
+                ParameterType::FileList(ParameterFileType::Raster) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Lists of raster inputs, such as '{}', cannot be run on tiles.",
+                            flag
+                        ),
+                    ))
+                }
+                ParameterType::ExistingFile(_) | ParameterType::Directory => {
+                    fixed_args.push(format_arg(&flag, &resolve_path(&self.working_dir, &value)?));
+                }
+                _ => fixed_args.push(format_arg(&flag, &value)),
+            }
+        }
+        if inputs.is_empty() || outputs.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} must have both raster inputs and raster outputs to be run on tiles.",
+                    tool_name
+                ),
+            ));
+        }
+
+        if normalized_name == "d8flowaccumulation" {
+            run.d8_flow_accumulation(&fixed_args, &inputs, &outputs)?;
+        } else {
+            run.run_tiled(tool_name, &fixed_args, &inputs, &outputs, halo)?;
+        }
+
+        if self.verbose {
+            println!(
+                "Elapsed Time (including I/O): {}",
+                get_formatted_elapsed_time(start)
+            );
+        }
+        Ok(())
+    }
+}
+
+impl DistributedRun {
+    /// Runs `tool` on each tile of the `inputs`, a list of (flag, file) pairs, stitching
+    /// the tiles of each of the (flag, file) `outputs` together.
+    fn run_tiled(
+        &self,
+        tool: &str,
+        fixed_args: &[String],
+        inputs: &[(String, String)],
+        outputs: &[(String, String)],
+        halo: usize,
+    ) -> Result<(), Error> {
+        if self.verbose {
+            println!("Reading data...")
+        };
+        let mut rasters = vec![];
+        for (_, file) in inputs {
+            let raster = Raster::new(file, "r")?;
+            if raster.num_bands() > 1 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "Multi-band rasters, such as {}, cannot be run on tiles.",
+                        file
+                    ),
+                ));
+            }
+            if let Some(first) = rasters.first() {
+                if !raster.is_grid_aligned_with(first) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The raster inputs must share the same grid to be run on tiles.",
+                    ));
+                }
+            }
+            rasters.push(raster);
+        }
+        let tiles = make_tiles(
+            rasters[0].configs.rows as isize,
+            rasters[0].configs.columns as isize,
+            self.tile_size as isize,
+            halo as isize,
+        );
+
+        let sep = path::MAIN_SEPARATOR;
+        let tile_dir = format!(
+            "{}wbt_tiles_{}_{}{}",
+            self.working_dir,
+            std::process::id(),
+            TILED_RUN_ID.fetch_add(1, Ordering::SeqCst),
+            sep
+        );
+        fs::create_dir_all(&tile_dir)?;
+        let result = (|| {
+            if self.verbose {
+                println!("Writing {} tiles...", tiles.len());
+            }
+            let tile_file = |index: usize, flag: &str, output: bool| {
+                format!(
+                    "{}{}_{}{}.tif",
+                    tile_dir,
+                    index,
+                    flag,
+                    if output { "_out" } else { "" }
+                )
+            };
+            let mut tile_args = vec![];
+            for (index, tile) in tiles.iter().enumerate() {
+                let mut args = vec![format!("--run={}", tool), format!("--wd={}", tile_dir)];
+                args.extend(fixed_args.iter().cloned());
+                for ((flag, _), raster) in inputs.iter().zip(rasters) {
+                    let file = tile_file(index, flag, false);
+                    write_tile(raster, &file, tile)?;
+                    args.push(format_arg(flag, &file));
+                }
+                for (flag, _) in outputs {
+                    args.push(format_arg(flag, &tile_file(index, flag, true)));
+                }
+                args.extend(self.framework_args.iter().cloned());
+                tile_args.push(args);
+            }
+
+            // idle worker slots take the next tile from the queue
+            let queue: Arc<Mutex<VecDeque<(usize, usize)>>> =
+                Arc::new(Mutex::new((0..tiles.len()).map(|i| (i, 1)).collect()));
+            let done = Arc::new(AtomicBool::new(false));
+            let tile_args = Arc::new(tile_args);
+            let (tx, rx) = mpsc::channel();
+            for worker in &self.workers {
+                for _ in 0..worker.slots {
+                    let queue = queue.clone();
+                    let done = done.clone();
+                    let tile_args = tile_args.clone();
+                    let tx = tx.clone();
+                    let exe = self.exe.clone();
+                    let host = match &worker.host {
+                        WorkerHost::Local => WorkerHost::Local,
+                        WorkerHost::Ssh(h) => WorkerHost::Ssh(h.clone()),
+                    };
+                    let name = worker.name();
+                    thread::spawn(move || {
+                        while !done.load(Ordering::SeqCst) {
+                            let next = queue.lock().unwrap().pop_front();
+                            let (index, attempt) = match next {
+                                Some(t) => t,
+                                None => {
+                                    // a failed tile may yet be returned to the queue
+                                    thread::sleep(Duration::from_millis(100));
+                                    continue;
+                                }
+                            };
+                            match run_on_worker(&host, &exe, &tile_args[index]) {
+                                Ok(()) => {
+                                    if tx.send(Ok(index)).is_err() {
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    if attempt < MAX_ATTEMPTS {
+                                        queue.lock().unwrap().push_back((index, attempt + 1));
+                                    } else {
+                                        let _ = tx.send(Err(Error::new(
+                                            e.kind(),
+                                            format!(
+                                                "Tile {} failed {} times; the last attempt, on {}, failed with: {}",
+                                                index, MAX_ATTEMPTS, name, e
+                                            ),
+                                        )));
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+            drop(tx);
+
+            let mut stitched: Vec<Option<Raster>> = outputs.iter().map(|_| None).collect();
+            let mut progress: usize;
+            let mut old_progress: usize = 1;
+            for n in 0..tiles.len() {
+                let index = match rx.recv() {
+                    Ok(Ok(index)) => index,
+                    Ok(Err(e)) => {
+                        done.store(true, Ordering::SeqCst);
+                        return Err(e);
+                    }
+                    Err(_) => {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            "The workers stopped before all of the tiles were processed.",
+                        ))
+                    }
+                };
+                let tile = &tiles[index];
+                for (i, (flag, file)) in outputs.iter().enumerate() {
+                    let tile_output = Raster::new(&tile_file(index, flag, true), "r")?;
+                    if stitched[i].is_none() {
+                        let mut output = Raster::initialize_using_file(file, &rasters[0]);
+                        output.configs.data_type = tile_output.configs.data_type;
+                        output.configs.nodata = tile_output.configs.nodata;
+                        output.configs.photometric_interp = tile_output.configs.photometric_interp;
+                        output.configs.palette = tile_output.configs.palette.clone();
+                        output.reinitialize_values(tile_output.configs.nodata);
+                        stitched[i] = Some(output);
+                    }
+                    let output = stitched[i].as_mut().unwrap();
+                    for row in tile.row_start..tile.row_end {
+                        let data = tile_output.get_row_data(row - tile.halo_row_start);
+                        let a = (tile.col_start - tile.halo_col_start) as usize;
+                        let b = (tile.col_end - tile.halo_col_start) as usize;
+                        for (col, z) in (tile.col_start..tile.col_end).zip(&data[a..b]) {
+                            output.set_value(row, col, *z);
+                        }
+                    }
+                }
+                for (flag, _) in inputs {
+                    let _ = fs::remove_file(tile_file(index, flag, false));
+                }
+                for (flag, _) in outputs {
+                    let _ = fs::remove_file(tile_file(index, flag, true));
+                }
+                if self.verbose {
+                    progress = (100.0_f64 * (n + 1) as f64 / tiles.len() as f64) as usize;
+                    if progress != old_progress {
+                        println!("Tiles completed: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            done.store(true, Ordering::SeqCst);
+
+            if self.verbose {
+                println!("Saving data...")
+            };
+            for output in stitched.iter_mut() {
+                if let Some(output) = output {
+                    output.add_metadata_entry(format!(
+                        "Created by whitebox_tools\' {} tool, run on {} tiles",
+                        tool,
+                        tiles.len()
+                    ));
+                    output.write()?;
+                }
+            }
+            Ok(())
+        })();
+        let _ = fs::remove_dir_all(&tile_dir);
+        result
+    }
+
+    /// Runs D8FlowAccumulation on tiles. The D8 flow pointer is calculated by tile, with
+    /// a one-cell halo, and the flow is then accumulated within each tile of the stitched
+    /// pointer. Finally, the flow leaving each tile is routed through the tiles downslope
+    /// of it, in the upslope-to-downslope order of the tile edge crossings.
+    fn d8_flow_accumulation(
+        &self,
+        fixed_args: &[String],
+        inputs: &[(String, String)],
+        outputs: &[(String, String)],
+    ) -> Result<(), Error> {
+        let mut out_type = String::from("sca");
+        let mut log_transform = false;
+        let mut clip_max = false;
+        let mut pntr_input = false;
+        let mut esri_style = false;
+        for (flag, value) in parse_cli_args(fixed_args) {
+            let set = !value.to_lowercase().contains("false");
+            match flag.as_str() {
+                "out_type" => {
+                    let v = value.to_lowercase();
+                    out_type = if v.contains("specific") || v.contains("sca") {
+                        "sca".to_string()
+                    } else if v.contains("cells") {
+                        "cells".to_string()
+                    } else {
+                        "ca".to_string()
+                    };
+                }
+                "log" => log_transform = set,
+                "clip" => clip_max = set,
+                "pntr" => pntr_input = set,
+                "esri_pntr" => esri_style = set,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The '{}' argument of D8FlowAccumulation is not supported when it is run on tiles.",
+                            flag
+                        ),
+                    ))
+                }
+            }
+        }
+        // the ESRI pointer scheme only applies to an input pointer
+        let esri_style = esri_style && pntr_input;
+        let input_file = match inputs.iter().find(|(f, _)| f == "i" || f == "input") {
+            Some((_, file)) => file.clone(),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The weights argument of D8FlowAccumulation is not supported when it is run on tiles.",
+                ))
+            }
+        };
+        if inputs.len() > 1 || outputs.len() > 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The weights, out_cells, and out_sca arguments of D8FlowAccumulation are not supported when it is run on tiles.",
+            ));
+        }
+        let output_file = outputs[0].1.clone();
+
+        let sep = path::MAIN_SEPARATOR;
+        let scratch_dir = format!(
+            "{}wbt_d8_{}_{}{}",
+            self.working_dir,
+            std::process::id(),
+            TILED_RUN_ID.fetch_add(1, Ordering::SeqCst),
+            sep
+        );
+        fs::create_dir_all(&scratch_dir)?;
+        let result = (|| {
+            let pointer_file = if pntr_input {
+                input_file.clone()
+            } else {
+                if self.verbose {
+                    println!("Calculating the flow pointer...");
+                }
+                let pointer_file = format!("{}pointer.tif", scratch_dir);
+                self.run_tiled(
+                    "D8Pointer",
+                    &[],
+                    &[("dem".to_string(), input_file.clone())],
+                    &[("output".to_string(), pointer_file.clone())],
+                    1,
+                )?;
+                pointer_file
+            };
+
+            if self.verbose {
+                println!("Accumulating flow within tiles...");
+            }
+            let accum_file = format!("{}accum.tif", scratch_dir);
+            let mut accum_args = vec!["--pntr".to_string(), "--out_type=cells".to_string()];
+            if esri_style {
+                accum_args.push("--esri_pntr".to_string());
+            }
+            self.run_tiled(
+                "D8FlowAccumulation",
+                &accum_args,
+                &[("input".to_string(), pointer_file.clone())],
+                &[("output".to_string(), accum_file.clone())],
+                0,
+            )?;
+
+            if self.verbose {
+                println!("Reconciling flow across tile edges...");
+            }
+            let pointer = Raster::new(&pointer_file, "r")?;
+            let accum = Raster::new(&accum_file, "r")?;
+            let flow_dir = decode_pointer(&pointer, esri_style)?;
+            let rows = pointer.configs.rows as isize;
+            let columns = pointer.configs.columns as isize;
+            let mut output = Raster::initialize_using_file(&output_file, &pointer);
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            if output.configs.data_type != DataType::F64 {
+                output.configs.data_type = DataType::F32;
+            }
+            output.configs.nodata = pointer.configs.nodata;
+            let mut cells: Array2D<f64> = Array2D::new(rows, columns, 0f64, 0f64)?;
+            for row in 0..rows {
+                cells.set_row_data(row, accum.get_row_data(row));
+            }
+            drop(accum);
+            reconcile_flow_accumulation(&flow_dir, &mut cells, self.tile_size as isize)?;
+
+            let cell_size_x = pointer.configs.resolution_x;
+            let cell_size_y = pointer.configs.resolution_y;
+            let avg_cell_size = (cell_size_x + cell_size_y) / 2.0;
+            let (cell_area, flow_width) = match out_type.as_str() {
+                "cells" => (1f64, 1f64),
+                "ca" => (cell_size_x * cell_size_y, 1f64),
+                _ => (cell_size_x * cell_size_y, avg_cell_size),
+            };
+            let nodata = output.configs.nodata;
+            for row in 0..rows {
+                for col in 0..columns {
+                    if flow_dir.get_value(row, col) == -2 {
+                        output.set_value(row, col, nodata);
+                    } else {
+                        let v = cells.get_value(row, col) * cell_area / flow_width;
+                        output.set_value(row, col, if log_transform { v.ln() } else { v });
+                    }
+                }
+            }
+            output.configs.palette = "blueyellow.plt".to_string();
+            if clip_max {
+                output.clip_display_max(1.0);
+            }
+            output.add_metadata_entry(
+                "Created by whitebox_tools\' D8FlowAccumulation tool, run on tiles".to_string(),
+            );
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            if self.verbose {
+                println!("Saving data...")
+            };
+            output.write()
+        })();
+        let _ = fs::remove_dir_all(&scratch_dir);
+        result
+    }
+}
+
+/// Parses the worker list, which is either the name of a file listing one worker per
+/// line, or a comma-separated list of workers.
+fn parse_workers(workers: &str) -> Result<Vec<Worker>, Error> {
+    let workers = workers.trim().replace("\"", "").replace("\'", "");
+    let entries: Vec<String> = if path::Path::new(&workers).is_file() {
+        fs::read_to_string(&workers)?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect()
+    } else {
+        workers
+            .split(',')
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .collect()
+    };
+    let mut ret = vec![];
+    for entry in entries {
+        let mut parts = entry.rsplitn(2, ':');
+        let last = parts.next().unwrap_or("");
+        let (host, slots) = match (parts.next(), last.parse::<usize>()) {
+            (Some(host), Ok(slots)) => (host.to_string(), slots),
+            _ => (entry.clone(), 1),
+        };
+        if slots == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The worker '{}' must have at least one slot.", entry),
+            ));
+        }
+        let host = if host.to_lowercase() == "local" || host.to_lowercase() == "localhost" {
+            WorkerHost::Local
+        } else {
+            WorkerHost::Ssh(host)
+        };
+        ret.push(Worker { host, slots });
+    }
+    if ret.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "No workers were specified; use e.g. --distribute=local:4,user@node01:8.",
+        ));
+    }
+    Ok(ret)
+}
+
+/// Divides a grid into tiles of `tile_size` cells square, each extended by `halo`
+/// cells on each side where the grid allows.
+fn make_tiles(rows: isize, columns: isize, tile_size: isize, halo: isize) -> Vec<Tile> {
+    let mut tiles = vec![];
+    let mut row = 0;
+    while row < rows {
+        let mut col = 0;
+        while col < columns {
+            let row_end = (row + tile_size).min(rows);
+            let col_end = (col + tile_size).min(columns);
+            tiles.push(Tile {
+                row_start: row,
+                row_end,
+                col_start: col,
+                col_end,
+                halo_row_start: (row - halo).max(0),
+                halo_row_end: (row_end + halo).min(rows),
+                halo_col_start: (col - halo).max(0),
+                halo_col_end: (col_end + halo).min(columns),
+            });
+            col += tile_size;
+        }
+        row += tile_size;
+    }
+    tiles
+}
+
+/// Writes the cells of a tile, including its halo, to a new raster.
+fn write_tile(input: &Raster, file: &str, tile: &Tile) -> Result<(), Error> {
+    let mut configs = input.configs.clone();
+    configs.rows = (tile.halo_row_end - tile.halo_row_start) as usize;
+    configs.columns = (tile.halo_col_end - tile.halo_col_start) as usize;
+    configs.north = input.get_y_from_row(tile.halo_row_start) + configs.resolution_y / 2.0;
+    configs.south = configs.north - configs.rows as f64 * configs.resolution_y;
+    configs.west = input.get_x_from_column(tile.halo_col_start) - configs.resolution_x / 2.0;
+    configs.east = configs.west + configs.columns as f64 * configs.resolution_x;
+    // the tile is georeferenced by its extent
+    configs.model_tiepoint = vec![];
+    configs.model_pixel_scale = [0f64; 3];
+    configs.model_transformation = [0f64; 16];
+    let mut output = Raster::initialize_using_config(file, &configs);
+    let a = tile.halo_col_start as usize;
+    let b = tile.halo_col_end as usize;
+    for row in tile.halo_row_start..tile.halo_row_end {
+        let data = input.get_row_data(row);
+        output.set_row_data(row - tile.halo_row_start, data[a..b].to_vec());
+    }
+    output.write()
+}
+
+/// Runs whitebox_tools with `args` on a worker, returning an error containing the
+/// tool's error output if it does not complete successfully.
+fn run_on_worker(host: &WorkerHost, exe: &str, args: &[String]) -> Result<(), Error> {
+    let mut command = match host {
+        WorkerHost::Local => {
+            let mut c = Command::new(exe);
+            c.args(args);
+            c
+        }
+        WorkerHost::Ssh(destination) => {
+            // ssh passes the command to the remote shell, so each argument is quoted
+            let remote_command: Vec<String> = std::iter::once(exe.to_string())
+                .chain(args.iter().cloned())
+                .map(|a| format!("'{}'", a.replace("'", "'\\''")))
+                .collect();
+            let mut c = Command::new("ssh");
+            c.arg("-o")
+                .arg("BatchMode=yes")
+                .arg(destination)
+                .arg(remote_command.join(" "));
+            c
+        }
+    };
+    let output = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message: Vec<&str> = stderr
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with("note:"))
+            .collect();
+        Err(Error::new(
+            ErrorKind::Other,
+            if message.is_empty() {
+                "The tool did not complete successfully.".to_string()
+            } else {
+                message.join("\n")
+            },
+        ))
+    }
+}
+
+/// Converts a D8 flow pointer raster into flow directions, where -2 indicates NoData,
+/// -1 indicates no downslope neighbour, and 0-7 indicate flow to one neighbour.
+fn decode_pointer(pointer: &Raster, esri_style: bool) -> Result<Array2D<i8>, Error> {
+    let rows = pointer.configs.rows as isize;
+    let columns = pointer.configs.columns as isize;
+    let nodata = pointer.configs.nodata;
+    let mut pntr_matches: [i8; 129] = [-1i8; 129];
+    let values = [1usize, 2, 4, 8, 16, 32, 64, 128];
+    for (i, v) in values.iter().enumerate() {
+        pntr_matches[*v] = if esri_style {
+            ((i + 1) % 8) as i8
+        } else {
+            i as i8
+        };
+    }
+    let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = pointer.get_value(row, col);
+            if z != nodata {
+                let dir = if z > 0f64 && z <= 128f64 {
+                    pntr_matches[z as usize]
+                } else {
+                    -1i8
+                };
+                flow_dir.set_value(row, col, dir);
+            }
+        }
+    }
+    Ok(flow_dir)
+}
+
+/// Adds the flow entering each tile from upslope tiles to the flow accumulated within
+/// the tiles, `cells`. The flow leaving a tile at an exit cell is the sum of the flow
+/// accumulated within the tile at that cell and the flow entering the tile upslope of
+/// it, so exits are resolved in topological order, from upslope to downslope tiles.
+fn reconcile_flow_accumulation(
+    flow_dir: &Array2D<i8>,
+    cells: &mut Array2D<f64>,
+    tile_size: isize,
+) -> Result<(), Error> {
+    let rows = flow_dir.rows;
+    let columns = flow_dir.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let tile_of = |cell: (isize, isize)| (cell.0 / tile_size, cell.1 / tile_size);
+    let downstream = |cell: (isize, isize)| -> Option<(isize, isize)> {
+        let dir = flow_dir.get_value(cell.0, cell.1);
+        if dir < 0 {
+            return None;
+        }
+        let n = (cell.0 + dy[dir as usize], cell.1 + dx[dir as usize]);
+        if n.0 < 0 || n.0 >= rows || n.1 < 0 || n.1 >= columns {
+            return None;
+        }
+        Some(n)
+    };
+
+    // exit cells, which flow into a cell (an entry) of a neighbouring tile
+    let mut exit_index: HashMap<(isize, isize), usize> = HashMap::new();
+    let mut exits: Vec<((isize, isize), (isize, isize))> = vec![];
+    for row in 0..rows {
+        for col in 0..columns {
+            if let Some(n) = downstream((row, col)) {
+                if tile_of(n) != tile_of((row, col)) {
+                    exit_index.insert((row, col), exits.len());
+                    exits.push(((row, col), n));
+                }
+            }
+        }
+    }
+
+    // the exit, if any, through which the flow from each entry leaves its tile
+    let max_path_length = (tile_size * tile_size) as usize;
+    let mut entry_exit: HashMap<(isize, isize), Option<usize>> = HashMap::new();
+    for (_, entry) in &exits {
+        if entry_exit.contains_key(entry) {
+            continue;
+        }
+        let mut cell = *entry;
+        let mut exit = None;
+        for _ in 0..max_path_length {
+            if let Some(e) = exit_index.get(&cell) {
+                exit = Some(*e);
+                break;
+            }
+            match downstream(cell) {
+                Some(n) => cell = n,
+                None => break,
+            }
+        }
+        entry_exit.insert(*entry, exit);
+    }
+
+    let mut pending = vec![0usize; exits.len()];
+    for (_, entry) in &exits {
+        if let Some(e) = entry_exit[entry] {
+            pending[e] += 1;
+        }
+    }
+    let mut outflow: Vec<f64> = exits
+        .iter()
+        .map(|(cell, _)| cells.get_value(cell.0, cell.1))
+        .collect();
+    let mut stack: Vec<usize> = (0..exits.len()).filter(|e| pending[*e] == 0).collect();
+    let mut inflow: HashMap<(isize, isize), f64> = HashMap::new();
+    let mut num_resolved = 0;
+    while let Some(e) = stack.pop() {
+        num_resolved += 1;
+        let entry = exits[e].1;
+        *inflow.entry(entry).or_insert(0f64) += outflow[e];
+        if let Some(e2) = entry_exit[&entry] {
+            outflow[e2] += outflow[e];
+            pending[e2] -= 1;
+            if pending[e2] == 0 {
+                stack.push(e2);
+            }
+        }
+    }
+    if num_resolved < exits.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The flow pointer contains a cycle.",
+        ));
+    }
+
+    // add each entry's inflow to the cells along its flow path within its tile
+    for (entry, amount) in inflow {
+        let mut cell = entry;
+        for _ in 0..max_path_length {
+            cells.increment(cell.0, cell.1, amount);
+            if exit_index.contains_key(&cell) {
+                break;
+            }
+            match downstream(cell) {
+                Some(n) => cell = n,
+                None => break,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses command-line style tool arguments (`--flag=value`, `--flag value`, or a
+/// boolean `--flag`) into (flag, value) pairs.
+fn parse_cli_args(args: &[String]) -> Vec<(String, String)> {
+    let mut ret = vec![];
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].trim();
+        let mut parts = arg.splitn(2, '=');
+        let flag = trim_flag(parts.next().unwrap_or(""));
+        let value = match parts.next() {
+            Some(v) => v.to_string(),
+            None => {
+                // a following argument that is not itself a flag is the value
+                match args.get(i + 1) {
+                    Some(next) if !next.starts_with('-') || next.trim().parse::<f64>().is_ok() => {
+                        i += 1;
+                        next.trim().to_string()
+                    }
+                    _ => String::new(),
+                }
+            }
+        };
+        ret.push((flag, value.replace("\"", "").replace("\'", "")));
+        i += 1;
+    }
+    ret
+}
+
+fn format_arg(flag: &str, value: &str) -> String {
+    if value.is_empty() {
+        format!("--{}", flag)
+    } else {
+        format!("--{}={}", flag, value)
+    }
+}
+
+/// Resolves a file name relative to the working directory, returning an absolute path
+/// that is also valid on the workers.
+fn resolve_path(working_dir: &str, file: &str) -> Result<String, Error> {
+    let file = file.trim().replace("\"", "").replace("\'", "");
+    let p = path::Path::new(&file);
+    if p.is_absolute() {
+        return Ok(file);
+    }
+    let sep = path::MAIN_SEPARATOR.to_string();
+    if !file.contains(&sep) && !file.contains("/") {
+        return Ok(format!("{}{}", working_dir, file));
+    }
+    Ok(env::current_dir()?.join(p).to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_pointer, make_tiles, reconcile_flow_accumulation, write_tile};
+    use crate::raster::{DataType, Raster, RasterConfigs};
+    use crate::structures::Array2D;
+    use crate::tools::hydro_analysis::{D8FlowAccumulation, D8Pointer};
+    use crate::tools::WhiteboxTool;
+    use std::env;
+    use std::fs;
+
+    /// Runs D8FlowAccumulation on a pointer, returning the accumulation in cells.
+    fn accumulate(pointer_file: &str, output_file: &str, wd: &str) -> Raster {
+        D8FlowAccumulation::new()
+            .run(
+                vec![
+                    format!("--input={}", pointer_file),
+                    format!("--output={}", output_file),
+                    "--pntr".to_string(),
+                    "--out_type=cells".to_string(),
+                ],
+                wd,
+                false,
+            )
+            .unwrap();
+        Raster::new(output_file, "r").unwrap()
+    }
+
+    #[test]
+    fn test_tiled_d8_flow_accumulation_matches_untiled() {
+        let dir = env::temp_dir().join(format!("wbt_distributed_d8_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let wd = format!("{}/", dir.display());
+        let (rows, columns, tile_size) = (23isize, 19isize, 4isize);
+
+        // a valley that meanders across the tile edges as it drains towards the last row,
+        // with a NoData hole on one of its slopes
+        let configs = RasterConfigs {
+            rows: rows as usize,
+            columns: columns as usize,
+            north: rows as f64,
+            south: 0.0,
+            east: columns as f64,
+            west: 0.0,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            nodata: -32768.0,
+            data_type: DataType::F32,
+            ..Default::default()
+        };
+        let mut dem = Raster::initialize_using_config(&format!("{}dem.tif", wd), &configs);
+        for row in 0..rows {
+            let thalweg = 9.0 + 5.0 * (row as f64 / 2.5).sin();
+            for col in 0..columns {
+                let z = 0.5 * (rows - 1 - row) as f64 + (col as f64 - thalweg).abs();
+                dem.set_value(row, col, z);
+            }
+        }
+        for row in 5..8 {
+            for col in 1..4 {
+                dem.set_value(row, col, -32768.0);
+            }
+        }
+        dem.write().unwrap();
+        D8Pointer::new()
+            .run(
+                vec![
+                    "--dem=dem.tif".to_string(),
+                    "--output=pointer.tif".to_string(),
+                ],
+                &wd,
+                false,
+            )
+            .unwrap();
+        let pointer = Raster::new(&format!("{}pointer.tif", wd), "r").unwrap();
+        let untiled = accumulate(
+            &format!("{}pointer.tif", wd),
+            &format!("{}accum.tif", wd),
+            &wd,
+        );
+
+        // accumulate the flow within each tile, as the workers would, and reconcile it
+        let mut cells: Array2D<f64> = Array2D::new(rows, columns, 0f64, 0f64).unwrap();
+        for (i, tile) in make_tiles(rows, columns, tile_size, 0).iter().enumerate() {
+            let tile_pointer = format!("{}pointer_{}.tif", wd, i);
+            write_tile(&pointer, &tile_pointer, tile).unwrap();
+            let accum = accumulate(&tile_pointer, &format!("{}accum_{}.tif", wd, i), &wd);
+            for row in tile.row_start..tile.row_end {
+                for col in tile.col_start..tile.col_end {
+                    let v = accum.get_value(row - tile.row_start, col - tile.col_start);
+                    cells.set_value(row, col, v);
+                }
+            }
+        }
+        let flow_dir = decode_pointer(&pointer, false).unwrap();
+        reconcile_flow_accumulation(&flow_dir, &mut cells, tile_size).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut max_accum = 0f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                let expected = untiled.get_value(row, col);
+                if flow_dir.get_value(row, col) == -2 {
+                    assert_eq!(expected, untiled.configs.nodata);
+                    continue;
+                }
+                assert_eq!(
+                    cells.get_value(row, col),
+                    expected,
+                    "cell ({}, {})",
+                    row,
+                    col
+                );
+                max_accum = max_accum.max(expected);
+            }
+        }
+        // the flow must have crossed tile edges to drain more cells than a tile holds
+        assert!(max_accum > (tile_size * tile_size) as f64);
+    }
+}
@@ -1,1903 +1,779 @@
-// /*
-// This tool is part of the WhiteboxTools geospatial analysis library.
-// Authors: Dr. John Lindsay
-// Created: 22/11/2018
-// Last Modified: 07/12/2018
-// License: MIT
-// */
-// extern crate kdtree;
-
-// use crate::algorithms::{
-//     find_split_points_at_line_intersections, interior_point, is_clockwise_order, point_in_poly,
-//     poly_in_poly,
-// };
-// use crate::structures::{BoundingBox, MultiPolyline, Polyline};
-// use crate::tools::*;
-// use crate::vector::*;
-// use kdtree::distance::squared_euclidean;
-// use kdtree::KdTree;
-// use std::cmp::Ordering;
-// use std::collections::{BinaryHeap, HashSet};
-// use std::env;
-// use std::f64::consts::PI;
-// use std::io::{Error, ErrorKind};
-// use std::path;
-
-// const EPSILON: f64 = std::f64::EPSILON;
-
-// /// This tool
-// ///
-// /// # See Also
-// ///
-// pub struct BufferVector {
-//     name: String,
-//     description: String,
-//     toolbox: String,
-//     parameters: Vec<ToolParameter>,
-//     example_usage: String,
-// }
-
-// impl BufferVector {
-//     pub fn new() -> BufferVector {
-//         // public constructor
-//         let name = "BufferVector".to_string();
-//         let toolbox = "GIS Analysis".to_string();
-//         let description =
-//             "Removes the interior, or shared, boundaries within a vector polygon coverage."
-//                 .to_string();
-
-//         let mut parameters = vec![];
-//         parameters.push(ToolParameter {
-//             name: "Input Vector File".to_owned(),
-//             flags: vec!["-i".to_owned(), "--input".to_owned()],
-//             description: "Input vector file.".to_owned(),
-//             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
-//                 VectorGeometryType::Any,
-//             )),
-//             default_value: None,
-//             optional: false,
-//         });
-
-//         parameters.push(ToolParameter {
-//             name: "Output Vector File".to_owned(),
-//             flags: vec!["-o".to_owned(), "--output".to_owned()],
-//             description: "Output vector file.".to_owned(),
-//             parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
-//                 VectorGeometryType::Any,
-//             )),
-//             default_value: None,
-//             optional: false,
-//         });
-
-//         parameters.push(ToolParameter {
-//             name: "Distance".to_owned(),
-//             flags: vec!["--dist".to_owned(), "--distance".to_owned()],
-//             description: "Buffer distance.".to_owned(),
-//             parameter_type: ParameterType::Float,
-//             default_value: Some("10.0".to_owned()),
-//             optional: false,
-//         });
-
-//         parameters.push(ToolParameter {
-//             name: "Dissolve overlapping polygons?".to_owned(),
-//             flags: vec!["--dissolve".to_owned()],
-//             description: "Optional flag to request the output polygons be dissolved.".to_owned(),
-//             parameter_type: ParameterType::Boolean,
-//             default_value: Some("True".to_owned()),
-//             optional: true,
-//         });
-
-//         parameters.push(ToolParameter {
-//             name: "Snap Tolerance".to_owned(),
-//             flags: vec!["--snap".to_owned()],
-//             description: "Snap tolerance.".to_owned(),
-//             parameter_type: ParameterType::Float,
-//             default_value: Some("0.0".to_owned()),
-//             optional: true,
-//         });
-
-//         let sep: String = path::MAIN_SEPARATOR.to_string();
-//         let p = format!("{}", env::current_dir().unwrap().display());
-//         let e = format!("{}", env::current_exe().unwrap().display());
-//         let mut short_exe = e
-//             .replace(&p, "")
-//             .replace(".exe", "")
-//             .replace(".", "")
-//             .replace(&sep, "");
-//         if e.contains(".exe") {
-//             short_exe += ".exe";
-//         }
-//         let usage = format!(
-//             ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -input=layer1.shp -o=out_file.shp --dist=25.0 --dissolve",
-//             short_exe, name
-//         ).replace("*", &sep);
-
-//         BufferVector {
-//             name: name,
-//             description: description,
-//             toolbox: toolbox,
-//             parameters: parameters,
-//             example_usage: usage,
-//         }
-//     }
-// }
-
-// impl WhiteboxTool for BufferVector {
-//     fn get_source_file(&self) -> String {
-//         String::from(file!())
-//     }
-
-//     fn get_tool_name(&self) -> String {
-//         self.name.clone()
-//     }
-
-//     fn get_tool_description(&self) -> String {
-//         self.description.clone()
-//     }
-
-//     fn get_tool_parameters(&self) -> String {
-//         let mut s = String::from("{\"parameters\": [");
-//         for i in 0..self.parameters.len() {
-//             if i < self.parameters.len() - 1 {
-//                 s.push_str(&(self.parameters[i].to_string()));
-//                 s.push_str(",");
-//             } else {
-//                 s.push_str(&(self.parameters[i].to_string()));
-//             }
-//         }
-//         s.push_str("]}");
-//         s
-//     }
-
-//     fn get_example_usage(&self) -> String {
-//         self.example_usage.clone()
-//     }
-
-//     fn get_toolbox(&self) -> String {
-//         self.toolbox.clone()
-//     }
-
-//     fn run<'a>(
-//         &self,
-//         args: Vec<String>,
-//         working_directory: &'a str,
-//         verbose: bool,
-//     ) -> Result<(), Error> {
-//         let mut input_file = String::new();
-//         let mut output_file = String::new();
-//         let mut distance = 10f64;
-//         let mut dissolve = false;
-//         let mut precision = EPSILON;
-
-//         // read the arguments
-//         if args.len() == 0 {
-//             return Err(Error::new(
-//                 ErrorKind::InvalidInput,
-//                 "Tool run with no parameters.",
-//             ));
-//         }
-//         for i in 0..args.len() {
-//             let mut arg = args[i].replace("\"", "");
-//             arg = arg.replace("\'", "");
-//             let cmd = arg.split("="); // in case an equals sign was used
-//             let vec = cmd.collect::<Vec<&str>>();
-//             let mut keyval = false;
-//             if vec.len() > 1 {
-//                 keyval = true;
-//             }
-//             let flag_val = vec[0].to_lowercase().replace("--", "-");
-//             if flag_val == "-i" || flag_val.contains("-input") {
-//                 input_file = if keyval {
-//                     vec[1].to_string()
-//                 } else {
-//                     args[i + 1].to_string()
-//                 };
-//             } else if flag_val == "-o" || flag_val == "-output" {
-//                 output_file = if keyval {
-//                     vec[1].to_string()
-//                 } else {
-//                     args[i + 1].to_string()
-//                 };
-//             } else if flag_val.contains("-dist") {
-//                 distance = if keyval {
-//                     vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-//                 } else {
-//                     args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-//                 };
-//             } else if flag_val == "-dissolve" {
-//                 dissolve = true;
-//             } else if flag_val == "-snap" {
-//                 precision = if keyval {
-//                     vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-//                 } else {
-//                     args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-//                 };
-//                 if precision < EPSILON {
-//                     precision = EPSILON;
-//                 }
-//             }
-//         }
-
-//         let sep: String = path::MAIN_SEPARATOR.to_string();
-//         let mut progress: usize;
-//         let mut old_progress: usize = 1;
-
-//         let start = Instant::now();
-
-//         if verbose {
-//             println!("***************{}", "*".repeat(self.get_tool_name().len()));
-//             println!("* Welcome to {} *", self.get_tool_name());
-//             println!("***************{}", "*".repeat(self.get_tool_name().len()));
-//         }
-
-//         if !input_file.contains(&sep) && !input_file.contains("/") {
-//             input_file = format!("{}{}", working_directory, input_file);
-//         }
-//         if !output_file.contains(&sep) && !output_file.contains("/") {
-//             output_file = format!("{}{}", working_directory, output_file);
-//         }
-
-//         if verbose {
-//             println!("Reading data...")
-//         };
-
-//         let input = Shapefile::read(&input_file)?;
-//         let projection = input.projection.clone();
-
-//         // create output file
-//         let mut output =
-//             Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, false)?;
-//         output.projection = projection;
-
-//         // add the attributes
-//         output
-//             .attributes
-//             .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
-
-//         let num_vertices_in_circle = 32usize;
-//         let angular_resolution = 2f64 * std::f64::consts::PI / num_vertices_in_circle as f64;
-//         let (mut x, mut y): (f64, f64);
-//         let mut p: Point2D;
-//         let (mut slope, mut slope1, mut slope2): (f64, f64, f64);
-//         let (mut slope3, mut slope4): (f64, f64);
-//         let mut polygons: Vec<Polyline> = Vec::with_capacity(input.num_records);
-
-//         match input.header.shape_type.base_shape_type() {
-//             ShapeType::Point => {
-//                 if distance < 0f64 {
-//                     return Err(Error::new(ErrorKind::InvalidInput, "Error: distance < 0.0"));
-//                 }
-//                 for record_num in 0..input.num_records {
-//                     let record = input.get_record(record_num);
-//                     p = record.points[0];
-
-//                     let mut circle =
-//                         Polyline::new_with_capacity(record_num, num_vertices_in_circle + 1);
-//                     for i in 0..num_vertices_in_circle {
-//                         slope = i as f64 * angular_resolution;
-//                         x = p.x + distance * slope.sin();
-//                         y = p.y + distance * slope.cos();
-//                         circle.push(Point2D::new(x, y));
-//                     }
-
-//                     // now add a last point same as the first.
-//                     circle.close_line();
-
-//                     polygons.push(circle);
-
-//                     if verbose {
-//                         progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64)
-//                             as usize;
-//                         if progress != old_progress {
-//                             println!("Progress: {}%", progress);
-//                             old_progress = progress;
-//                         }
-//                     }
-//                 }
-//             }
-//             ShapeType::MultiPoint => {
-//                 if distance < 0f64 {
-//                     return Err(Error::new(ErrorKind::InvalidInput, "Error: distance < 0.0"));
-//                 }
-//                 let mut total_points = 0;
-//                 for record_num in 0..input.num_records {
-//                     let record = input.get_record(record_num);
-//                     total_points += record.points.len();
-//                 }
-//                 let mut points_read = 0;
-//                 for record_num in 0..input.num_records {
-//                     let record = input.get_record(record_num);
-//                     for i in 0..record.points.len() {
-//                         points_read += 1;
-//                         p = record.points[i];
-
-//                         let mut circle =
-//                             Polyline::new_with_capacity(points_read, num_vertices_in_circle + 1);
-//                         for i in 0..num_vertices_in_circle {
-//                             slope = i as f64 * angular_resolution;
-//                             x = p.x + distance * slope.sin();
-//                             y = p.y + distance * slope.cos();
-//                             circle.push(Point2D::new(x, y));
-//                         }
-
-//                         // now add a last point same as the first.
-//                         circle.close_line();
-
-//                         polygons.push(circle);
-//                         if verbose {
-//                             progress =
-//                                 (100.0_f64 * points_read as f64 / total_points as f64) as usize;
-//                             if progress != old_progress {
-//                                 println!("Progress: {}%", progress);
-//                                 old_progress = progress;
-//                             }
-//                         }
-//                     }
-//                 }
-//             }
-//             ShapeType::PolyLine => {
-//                 if distance < 0f64 {
-//                     return Err(Error::new(ErrorKind::InvalidInput, "Error: distance < 0.0"));
-//                 }
-//                 let right_angle = std::f64::consts::PI / 2f64;
-//                 let mut slope: f64;
-//                 // let mut slopes = Vec::with_capacity(num_vertices_in_circle + 4);
-//                 let mut total_points = 0;
-//                 let (mut x, mut y): (f64, f64);
-//                 let (mut x_translated, mut y_translated): (f64, f64);
-//                 for record_num in 0..input.num_records {
-//                     let record = input.get_record(record_num);
-//                     total_points += record.points.len();
-//                 }
-//                 let mut p1: Point2D;
-//                 let mut p2: Point2D;
-//                 let mut p3: Point2D;
-//                 // output.header.shape_type = ShapeType::PolyLine;
-//                 for record_num in 0..input.num_records {
-//                     let record = input.get_record(record_num);
-//                     let mut line_polys: Vec<Polyline> = Vec::with_capacity(input.num_records);
-//                     let mut line1 = Polyline::new_empty(record_num);
-//                     let mut line2 = Polyline::new_empty(record_num);
-//                     for i in 0..record.points.len() {
-//                         p = record.points[i];
-//                         if i == 0 || i == record.points.len() - 1 {
-//                             slope = if i == 0 {
-//                                 (record.points[i + 1].y - p.y).atan2(record.points[i + 1].x - p.x)
-//                             } else {
-//                                 (p.y - record.points[i - 1].y).atan2(p.x - record.points[i - 1].x)
-//                             };
-
-//                             slope1 = slope + right_angle;
-//                             // if slope1 < 0.0 {
-//                             //     slope1 += 2.0 * PI;
-//                             // }
-//                             // if slope1 > 2.0 * PI {
-//                             //     slope1 -= 2.0 * PI;
-//                             // }
-//                             slope2 = slope - right_angle;
-//                             // if slope2 < 0.0 {
-//                             //     slope2 += 2.0 * PI;
-//                             // }
-//                             // if slope2 > 2.0 * PI {
-//                             //     slope2 -= 2.0 * PI;
-//                             // }
-
-//                             if i == 0 {
-//                                 // x = p.x + distance * slope2.cos();
-//                                 // y = p.y + distance * slope2.sin();
-//                                 // line1.push(Point2D::new(x, y));
-//                                 for j in 0..(num_vertices_in_circle / 2 + 1) {
-//                                     slope = slope2 - j as f64 * angular_resolution;
-//                                     x = p.x + distance * slope.cos();
-//                                     y = p.y + distance * slope.sin();
-//                                     line1.push(Point2D::new(x, y));
-//                                 }
-//                             // x = p.x + distance * slope1.cos();
-//                             // y = p.y + distance * slope1.sin();
-//                             // line1.push(Point2D::new(x, y));
-//                             } else {
-//                                 // x = p.x + distance * slope1.cos();
-//                                 // y = p.y + distance * slope1.sin();
-//                                 // line1.push(Point2D::new(x, y));
-//                                 for j in 0..(num_vertices_in_circle / 2 + 1) {
-//                                     slope = slope1 - j as f64 * angular_resolution;
-//                                     x = p.x + distance * slope.cos();
-//                                     y = p.y + distance * slope.sin();
-//                                     line1.push(Point2D::new(x, y));
-//                                 }
-//                                 // x = p.x + distance * slope2.cos();
-//                                 // y = p.y + distance * slope2.sin();
-//                                 // line1.push(Point2D::new(x, y));
-//                             }
-
-//                             x = p.x + distance * slope2.cos();
-//                             y = p.y + distance * slope2.sin();
-//                             line2.push(Point2D::new(x, y));
-
-//                         // let mut added_slope1 = false;
-//                         // let mut added_slope2 = false;
-//                         // let mut circle =
-//                         //     Polyline::new_with_capacity(i, num_vertices_in_circle + 3);
-//                         // for j in 0..num_vertices_in_circle {
-//                         //     slope = j as f64 * angular_resolution;
-//                         //     if j > 0 {
-//                         //         if slope1 > ((j - 1) as f64 * angular_resolution)
-//                         //             && slope1 < slope
-//                         //         {
-//                         //             x = p.x + distance * slope1.cos();
-//                         //             y = p.y + distance * slope1.sin();
-//                         //             circle.push(Point2D::new(x, y));
-//                         //             added_slope1 = true;
-//                         //         }
-//                         //         if slope2 > ((j - 1) as f64 * angular_resolution)
-//                         //             && slope2 < slope
-//                         //         {
-//                         //             x = p.x + distance * slope2.cos();
-//                         //             y = p.y + distance * slope2.sin();
-//                         //             circle.push(Point2D::new(x, y));
-//                         //             added_slope2 = true;
-//                         //         }
-//                         //     }
-//                         //     if slope == slope1 {
-//                         //         added_slope1 = true;
-//                         //     }
-//                         //     if slope == slope2 {
-//                         //         added_slope2 = true;
-//                         //     }
-//                         //     // if (added_slope1 && !added_slope2)
-//                         //     //     || (!added_slope1 && added_slope2)
-//                         //     // {
-//                         //     x = p.x + distance * slope.cos();
-//                         //     y = p.y + distance * slope.sin();
-//                         //     circle.push(Point2D::new(x, y));
-//                         //     // }
-//                         // }
-
-//                         // if !added_slope1 {
-//                         //     x = p.x + distance * slope1.cos();
-//                         //     y = p.y + distance * slope1.sin();
-//                         //     circle.push(Point2D::new(x, y));
-//                         // }
-
-//                         // if !added_slope2 {
-//                         //     x = p.x + distance * slope2.cos();
-//                         //     y = p.y + distance * slope2.sin();
-//                         //     circle.push(Point2D::new(x, y));
-//                         // }
-
-//                         // // now add a last point same as the first.
-//                         // circle.close_line();
-//                         // line_polys.push(circle);
-
-//                         // x_translated = p.x + distance * slope1.cos();
-//                         // y_translated = p.y + distance * slope1.sin();
-//                         // line1.push(Point2D::new(x_translated, y_translated));
-
-//                         // x_translated = p.x + distance * slope2.cos();
-//                         // y_translated = p.y + distance * slope2.sin();
-//                         // line2.push(Point2D::new(x_translated, y_translated));
-//                         } else {
-//                             slope =
-//                                 (p.y - record.points[i - 1].y).atan2(p.x - record.points[i - 1].x);
-
-//                             slope1 = slope + right_angle;
-//                             // if slope1 < 0.0 {
-//                             //     slope1 += 2.0 * PI;
-//                             // }
-//                             // if slope1 > 2.0 * PI {
-//                             //     slope1 -= 2.0 * PI;
-//                             // }
-//                             slope2 = slope - right_angle;
-//                             // if slope2 < 0.0 {
-//                             //     slope2 += 2.0 * PI;
-//                             // }
-//                             // if slope2 > 2.0 * PI {
-//                             //     slope2 -= 2.0 * PI;
-//                             // }
-
-//                             x = p.x + distance * slope1.cos();
-//                             y = p.y + distance * slope1.sin();
-//                             line1.push(Point2D::new(x, y));
-//                             // line_polys.push(line1);
-//                             // line1 = Polyline::new_empty(record_num);
-
-//                             x = p.x + distance * slope2.cos();
-//                             y = p.y + distance * slope2.sin();
-//                             line2.push(Point2D::new(x, y));
-//                             // line_polys.push(line2);
-//                             // line2 = Polyline::new_empty(record_num);
-
-//                             slope =
-//                                 (record.points[i + 1].y - p.y).atan2(record.points[i + 1].x - p.x);
-
-//                             slope3 = slope + right_angle;
-//                             // if slope3 < 0.0 {
-//                             //     slope3 += 2.0 * PI;
-//                             // }
-//                             // if slope3 > 2.0 * PI {
-//                             //     slope3 -= 2.0 * PI;
-//                             // }
-//                             slope4 = slope - right_angle;
-//                             // if slope4 < 0.0 {
-//                             //     slope4 += 2.0 * PI;
-//                             // }
-//                             // if slope4 > 2.0 * PI {
-//                             //     slope4 -= 2.0 * PI;
-//                             // }
-
-//                             if record.points[i + 1].is_left(&record.points[i - 1], &p) < 0.0 {
-//                                 // if Point2D::change_in_heading(
-//                                 //     record.points[i - 1],
-//                                 //     p,
-//                                 //     record.points[i + 1],
-//                                 // ) > 0.0
-//                                 // {
-//                                 let mut slope_change = slope3 - slope1;
-
-//                                 let mut num_ticks =
-//                                     (slope_change.abs() / angular_resolution).ceil() as usize;
-//                                 if num_ticks > 0 {
-//                                     if slope_change > 0.0 {
-//                                         slope_change -= 2.0 * PI;
-//                                         num_ticks = (slope_change.abs() / angular_resolution).ceil()
-//                                             as usize;
-//                                     }
-//                                     for j in 0..num_ticks {
-//                                         slope = slope1 - j as f64 * angular_resolution;
-//                                         x = p.x + distance * slope.cos();
-//                                         y = p.y + distance * slope.sin();
-//                                         line1.push(Point2D::new(x, y));
-//                                     }
-//                                 }
-
-//                                 line_polys.push(line2);
-//                                 line2 = Polyline::new_empty(record_num);
-//                             } else if record.points[i + 1].is_left(&record.points[i - 1], &p) > 0.0
-//                             {
-//                                 // } else if Point2D::change_in_heading(
-//                                 //     record.points[i - 1],
-//                                 //     p,
-//                                 //     record.points[i + 1],
-//                                 // ) < 0.0
-//                                 // {
-//                                 let mut slope_change = slope4 - slope2;
-//                                 let mut num_ticks =
-//                                     (slope_change.abs() / angular_resolution).ceil() as usize;
-//                                 if num_ticks > 0 {
-//                                     if slope_change < 0.0 {
-//                                         slope_change += 2.0 * PI;
-//                                         num_ticks = (slope_change.abs() / angular_resolution).ceil()
-//                                             as usize;
-//                                     }
-//                                     for j in 0..num_ticks {
-//                                         slope = slope2 + j as f64 * angular_resolution;
-//                                         x = p.x + distance * slope.cos();
-//                                         y = p.y + distance * slope.sin();
-//                                         line2.push(Point2D::new(x, y));
-//                                     }
-//                                 }
-
-//                                 // line1.push(Point2D::new(0.0, 0.0));
-//                                 line_polys.push(line1);
-//                                 line1 = Polyline::new_empty(record_num);
-//                                 // for j in 0..num_ticks {
-//                                 //     slope = slope1 + j as f64 * angular_resolution;
-//                                 //     x = p.x + distance * slope.cos();
-//                                 //     y = p.y + distance * slope.sin();
-//                                 //     line1.push(Point2D::new(x, y));
-//                                 // }
-//                             }
-
-//                             //         let mut added_slope1 = false;
-//                             //         let mut added_slope2 = false;
-//                             //         let mut added_slope3 = false;
-//                             //         let mut added_slope4 = false;
-//                             //         // let mut circle =
-//                             //         //     Polyline::new_with_capacity(i, num_vertices_in_circle + 3);
-//                             //         for j in 0..num_vertices_in_circle {
-//                             //             slope = j as f64 * angular_resolution;
-//                             //             if j > 0 {
-//                             //                 if slope1 > ((j - 1) as f64 * angular_resolution)
-//                             //                     && slope1 < slope
-//                             //                 {
-//                             //                     x = p.x + distance * slope1.cos();
-//                             //                     y = p.y + distance * slope1.sin();
-//                             //                     line1.push(Point2D::new(x, y));
-//                             //                     // circle.push(Point2D::new(x, y));
-//                             //                     added_slope1 = true;
-//                             //                 }
-//                             //                 if slope2 > ((j - 1) as f64 * angular_resolution)
-//                             //                     && slope2 < slope
-//                             //                 {
-//                             //                     x = p.x + distance * slope2.cos();
-//                             //                     y = p.y + distance * slope2.sin();
-//                             //                     line2.push(Point2D::new(x, y));
-//                             //                     // circle.push(Point2D::new(x, y));
-//                             //                     added_slope2 = true;
-//                             //                 }
-
-//                             //                 if slope3 > ((j - 1) as f64 * angular_resolution)
-//                             //                     && slope3 < slope
-//                             //                 {
-//                             //                     x = p.x + distance * slope3.cos();
-//                             //                     y = p.y + distance * slope3.sin();
-//                             //                     line1.push(Point2D::new(x, y));
-//                             //                     // circle.push(Point2D::new(x, y));
-//                             //                     added_slope3 = true;
-//                             //                 }
-
-//                             //                 if slope4 > ((j - 1) as f64 * angular_resolution)
-//                             //                     && slope4 < slope
-//                             //                 {
-//                             //                     x = p.x + distance * slope4.cos();
-//                             //                     y = p.y + distance * slope4.sin();
-//                             //                     line2.push(Point2D::new(x, y));
-//                             //                     // circle.push(Point2D::new(x, y));
-//                             //                     added_slope4 = true;
-//                             //                 }
-//                             //             }
-//                             //             if slope == slope1 {
-//                             //                 added_slope1 = true;
-//                             //             }
-//                             //             if slope == slope2 {
-//                             //                 added_slope2 = true;
-//                             //             }
-//                             //             if slope == slope3 {
-//                             //                 added_slope3 = true;
-//                             //             }
-//                             //             if slope == slope4 {
-//                             //                 added_slope4 = true;
-//                             //             }
-
-//                             //             if (added_slope1 && !added_slope3)
-//                             //                 || (!added_slope1 && added_slope3)
-//                             //             {
-//                             //                 x = p.x + distance * slope.cos();
-//                             //                 y = p.y + distance * slope.sin();
-//                             //                 line1.push(Point2D::new(x, y));
-//                             //             }
-
-//                             //             if (added_slope2 && !added_slope4)
-//                             //                 || (!added_slope2 && added_slope4)
-//                             //             {
-//                             //                 x = p.x + distance * slope.cos();
-//                             //                 y = p.y + distance * slope.sin();
-//                             //                 line2.push(Point2D::new(x, y));
-//                             //             }
-
-//                             //             // if (added_slope1 && !added_slope3)
-//                             //             //     || (!added_slope1 && added_slope3)
-//                             //             //     || (added_slope2 && !added_slope4)
-//                             //             //     || (!added_slope2 && added_slope4)
-//                             //             // {
-//                             //             //     x = p.x + distance * slope.cos();
-//                             //             //     y = p.y + distance * slope.sin();
-//                             //             //     circle.push(Point2D::new(x, y));
-//                             //             // }
-//                             //         }
-
-//                             //         if !added_slope1 {
-//                             //             x = p.x + distance * slope1.cos();
-//                             //             y = p.y + distance * slope1.sin();
-//                             //             line1.push(Point2D::new(x, y));
-//                             //             // circle.push(Point2D::new(x, y));
-//                             //         }
-
-//                             //         if !added_slope2 {
-//                             //             x = p.x + distance * slope2.cos();
-//                             //             y = p.y + distance * slope2.sin();
-//                             //             line2.push(Point2D::new(x, y));
-//                             //             // circle.push(Point2D::new(x, y));
-//                             //         }
-
-//                             //         if !added_slope3 {
-//                             //             x = p.x + distance * slope3.cos();
-//                             //             y = p.y + distance * slope3.sin();
-//                             //             line1.push(Point2D::new(x, y));
-//                             //             // circle.push(Point2D::new(x, y));
-//                             //         }
-
-//                             //         if !added_slope4 {
-//                             //             x = p.x + distance * slope4.cos();
-//                             //             y = p.y + distance * slope4.sin();
-//                             //             line2.push(Point2D::new(x, y));
-//                             //             // circle.push(Point2D::new(x, y));
-//                             //         }
-
-//                             x = p.x + distance * slope3.cos();
-//                             y = p.y + distance * slope3.sin();
-//                             line1.push(Point2D::new(x, y));
-
-//                             x = p.x + distance * slope4.cos();
-//                             y = p.y + distance * slope4.sin();
-//                             line2.push(Point2D::new(x, y));
-
-//                             // now add a last point same as the first.
-//                             // circle.close_line();
-//                             // line_polys.push(circle);
-//                         }
-//                     }
-
-//                     line_polys.push(line1);
-//                     line_polys.push(line2);
-
-//                     let mut dissolved = intersect(&mut line_polys, precision);
-//                     remove_dangling_arcs(&mut dissolved, precision);
-
-//                     // let dissolved = dissolve_polygons(line_polys, precision);
-//                     for i in 0..dissolved.len() {
-//                         // for j in 0..dissolved[i].len() {
-//                         //     polygons.push(dissolved[i][j].clone());
-//                         // }
-//                         polygons.push(dissolved[i].clone());
-//                     }
-
-//                     if verbose {
-//                         progress = (100.0_f64 * record_num as f64 / (input.num_records - 1) as f64)
-//                             as usize;
-//                         if progress != old_progress {
-//                             println!("Progress: {}%", progress);
-//                             old_progress = progress;
-//                         }
-//                     }
-//                 }
-//             }
-//             // ShapeType::Polygon => {}
-//             _ => {
-//                 return Err(Error::new(
-//                     ErrorKind::InvalidInput,
-//                     "Invalid input data ShapeType.",
-//                 ));
-//             }
-//         }
-
-//         dissolve = false;
-
-//         if dissolve {
-//             if verbose {
-//                 println!("Dissolving polygons...");
-//             }
-//             let dissolved = dissolve_polygons(polygons, precision);
-
-//             output.header.shape_type = ShapeType::PolyLine;
-//             for record_num in 0..dissolved.len() {
-//                 // output the polygon
-//                 let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
-//                 for i in 0..dissolved[record_num].len() {
-//                     sfg.add_part(&(dissolved[record_num][i].vertices));
-//                 }
-//                 output.add_record(sfg);
-
-//                 output
-//                     .attributes
-//                     .add_record(vec![FieldData::Int(record_num as i32 + 1i32)], false);
-
-//                 if verbose {
-//                     progress =
-//                         (100.0_f64 * (record_num + 1) as f64 / dissolved.len() as f64) as usize;
-//                     if progress != old_progress {
-//                         println!("Progress: {}%", progress);
-//                         old_progress = progress;
-//                     }
-//                 }
-//             }
-//         } else {
-//             output.header.shape_type = ShapeType::PolyLine;
-//             for record_num in 0..polygons.len() {
-//                 // output the polygon
-//                 let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
-//                 sfg.add_part(&polygons[record_num].vertices);
-//                 output.add_record(sfg);
-
-//                 output
-//                     .attributes
-//                     .add_record(vec![FieldData::Int(record_num as i32 + 1i32)], false);
-
-//                 if verbose {
-//                     progress =
-//                         (100.0_f64 * (record_num + 1) as f64 / polygons.len() as f64) as usize;
-//                     if progress != old_progress {
-//                         println!("Progress: {}%", progress);
-//                         old_progress = progress;
-//                     }
-//                 }
-//             }
-//         }
-
-//         if verbose {
-//             println!("Saving data...")
-//         };
-//         let _ = match output.write() {
-//             Ok(_) => {
-//                 if verbose {
-//                     println!("Output file written")
-//                 }
-//             }
-//             Err(e) => return Err(e),
-//         };
-
-//         let elapsed_time = get_formatted_elapsed_time(start);
-
-//         if verbose {
-//             println!("{}", &format!("Elapsed Time: {}", elapsed_time));
-//         }
-
-//         Ok(())
-//     }
-// }
-
-// #[derive(Debug)]
-// struct Link {
-//     id: usize,
-//     priority: f64,
-// }
-
-// impl PartialEq for Link {
-//     fn eq(&self, other: &Self) -> bool {
-//         (self.priority - other.priority).abs() < EPSILON && self.id == other.id
-//     }
-// }
-
-// impl Eq for Link {}
-
-// impl Ord for Link {
-//     fn cmp(&self, other: &Link) -> Ordering {
-//         // this sorts priorities from low to high
-//         // and when priorities are equal, id's from
-//         // high to low.
-//         let mut ord = other.priority.partial_cmp(&self.priority).unwrap();
-//         if ord == Ordering::Equal {
-//             ord = self.id.cmp(&other.id);
-//         }
-//         ord
-//     }
-// }
-
-// impl PartialOrd for Link {
-//     fn partial_cmp(&self, other: &Link) -> Option<Ordering> {
-//         Some(self.cmp(other))
-//     }
-// }
-
-// fn get_other_endnode(index: usize) -> usize {
-//     if index % 2 == 0 {
-//         // it's a starting node and we need the end
-//         return index + 1;
-//     }
-//     // it's an end node and we need the starting node
-//     index - 1
-// }
-
-// fn is_first_node(index: usize) -> bool {
-//     index % 2 == 0
-// }
-
-// fn first_node_id(polyline: usize) -> usize {
-//     polyline * 2
-// }
-
-// fn last_node_id(polyline: usize) -> usize {
-//     polyline * 2 + 1
-// }
-
-// pub fn intersect(lines: &mut Vec<Polyline>, precision: f64) -> Vec<Polyline> {
-//     // hunt for intersections
-//     let mut features_bb = Vec::with_capacity(lines.len());
-//     for i in 0..lines.len() {
-//         features_bb.push(lines[i].get_bounding_box());
-//     }
-
-//     let mut polylines = vec![];
-//     let mut lengths = vec![];
-//     let mut line_length: f64;
-//     for i in 0..lines.len() {
-//         let mut pl = lines[i].clone();
-//         for j in i + 1..lines.len() {
-//             if features_bb[i].overlaps(features_bb[j]) {
-//                 // find any intersections between the polylines
-//                 find_split_points_at_line_intersections(&mut pl, &mut lines[j]);
-//             }
-//         }
-//         let split_lines = pl.split();
-//         for j in 0..split_lines.len() {
-//             line_length = split_lines[j].length();
-//             if line_length > precision {
-//                 polylines.push(split_lines[j].clone());
-//                 lengths.push(line_length);
-//             }
-//         }
-//     }
-
-//     // Remove any zero-length line segments
-//     for i in 0..polylines.len() {
-//         for j in (1..polylines[i].len()).rev() {
-//             if polylines[i][j] == polylines[i][j - 1] {
-//                 polylines[i].remove(j);
-//             }
-//         }
-//     }
-//     // Remove any single-point lines result from above.
-//     for i in (0..polylines.len()).rev() {
-//         if polylines[i].len() < 2 {
-//             polylines.remove(i);
-//         }
-//     }
-
-//     polylines
-// }
-
-// pub fn remove_dangling_arcs(polylines: &mut Vec<Polyline>, precision: f64) {
-//     let num_endnodes = polylines.len() * 2;
-//     /*
-//         The structure of endnodes is as such:
-//         1. the starting node for polyline 'a' is a * 2.
-//         2. the ending node for polyline 'a' is a * 2 + 1.
-//         3. endnode to polyline = e / 2
-//         4. is an endnode a starting point? e % 2 == 0
-//     */
-//     let mut endnodes: Vec<Vec<usize>> = vec![vec![]; num_endnodes];
-
-//     // now add the endpoints of each polyline into a kd tree
-//     let dimensions = 2;
-//     let capacity_per_node = 64;
-//     let mut kdtree = KdTree::new_with_capacity(dimensions, capacity_per_node);
-//     let mut p1: Point2D;
-//     let mut p2: Point2D;
-//     let mut p3: Point2D;
-//     let mut p4: Point2D;
-//     for i in 0..polylines.len() {
-//         p1 = polylines[i].first_vertex();
-//         kdtree.add([p1.x, p1.y], first_node_id(i)).unwrap();
-
-//         p2 = polylines[i].last_vertex();
-//         kdtree.add([p2.x, p2.y], last_node_id(i)).unwrap();
-//     }
-
-//     // Find the neighbours of each endnode and check for dangling arcs
-//     // and self-closing arcs which form single-line polys.
-//     let mut is_acyclic_arc = vec![false; polylines.len()];
-//     let mut node_angles: Vec<Vec<f64>> = vec![vec![]; num_endnodes];
-//     let mut heading: f64;
-//     for i in 0..polylines.len() {
-//         p1 = polylines[i].first_vertex();
-//         p2 = polylines[i].last_vertex();
-
-//         // check the first vertex
-//         let ret = kdtree
-//             .within(&[p1.x, p1.y], precision, &squared_euclidean)
-//             .unwrap();
-//         if ret.len() == 1 {
-//             is_acyclic_arc[i] = true;
-//         } else {
-//             p3 = polylines[i][1];
-//             for a in 0..ret.len() {
-//                 let index = *ret[a].1;
-//                 if index == last_node_id(i) && polylines[i].len() <= 2 {
-//                     is_acyclic_arc[i] = true;
-//                 }
-//                 if index != first_node_id(i) && !is_acyclic_arc[index / 2] {
-//                     p4 = if is_first_node(index) {
-//                         polylines[index / 2][1]
-//                     } else {
-//                         polylines[index / 2][polylines[index / 2].len() - 2]
-//                     };
-//                     heading = Point2D::change_in_heading(p3, p1, p4);
-//                     if !heading.is_nan() {
-//                         node_angles[first_node_id(i)].push(heading);
-//                         endnodes[first_node_id(i)].push(index);
-//                     }
-//                 }
-//             }
-//             if endnodes[first_node_id(i)].len() == 0 {
-//                 is_acyclic_arc[i] = true;
-//             }
-//         }
-
-//         // the first vertex showed that this line is a dangling arc,
-//         // don't bother connecting it to the graph
-//         if !is_acyclic_arc[i] {
-//             // check the last vertex
-//             let ret = kdtree
-//                 .within(&[p2.x, p2.y], EPSILON, &squared_euclidean)
-//                 .unwrap();
-//             if ret.len() == 1 {
-//                 is_acyclic_arc[i] = true;
-//             } else {
-//                 p3 = polylines[i][polylines[i].len() - 2];
-//                 for a in 0..ret.len() {
-//                     let index = *ret[a].1;
-//                     if index != last_node_id(i) && !is_acyclic_arc[index / 2] {
-//                         p4 = if is_first_node(index) {
-//                             polylines[index / 2][1]
-//                         } else {
-//                             polylines[index / 2][polylines[index / 2].len() - 2]
-//                         };
-//                         heading = Point2D::change_in_heading(p3, p2, p4);
-//                         if !heading.is_nan() {
-//                             node_angles[last_node_id(i)].push(heading);
-//                             endnodes[last_node_id(i)].push(index);
-//                         }
-//                     }
-//                 }
-//                 if endnodes[last_node_id(i)].len() == 0 {
-//                     is_acyclic_arc[i] = true;
-//                 }
-//             }
-//         }
-//     }
-
-//     // Remove any acyclic arcs.
-//     for i in (0..polylines.len()).rev() {
-//         if is_acyclic_arc[i] {
-//             polylines.remove(i);
-//         }
-//     }
-// }
-
-// pub fn dissolve_polygons(polygons: Vec<Polyline>, precision: f64) -> Vec<MultiPolyline> {
-//     let mut polygons2 = polygons.clone();
-
-//     // let mut feature_geometries: Vec<MultiPolyline> = vec![];
-//     // for i in 0..polygons2.len() {
-//     //     let mut mp = MultiPolyline::new(i + 1);
-//     //     mp.push(&polygons2[i]);
-//     //     feature_geometries.push(mp);
-//     // }
-
-//     // return feature_geometries;
-
-//     // Remove any zero-length line segments
-//     for i in 0..polygons2.len() {
-//         for j in (1..polygons2[i].len()).rev() {
-//             if polygons2[i][j] == polygons2[i][j - 1] {
-//                 polygons2[i].remove(j);
-//             }
-//         }
-//     }
-//     // Remove any single-point lines result from above.
-//     for i in (0..polygons2.len()).rev() {
-//         if polygons2[i].len() < 2 {
-//             polygons2.remove(i);
-//         }
-//     }
-
-//     // Find duplicate polylines and remove them
-//     let mut duplicate = vec![false; polygons2.len()];
-//     for i in 0..polygons2.len() {
-//         if !duplicate[i] {
-//             for j in (i + 1)..polygons2.len() {
-//                 if polygons2[i].nearly_equals(&polygons2[j], precision) {
-//                     duplicate[j] = true;
-//                     break;
-//                 }
-//             }
-//         }
-//     }
-//     for i in (0..polygons2.len()).rev() {
-//         if duplicate[i] {
-//             polygons2.remove(i);
-//         }
-//     }
-
-//     // hunt for intersections
-//     let mut features_bb = Vec::with_capacity(polygons2.len());
-//     for i in 0..polygons2.len() {
-//         features_bb.push(polygons2[i].get_bounding_box());
-//     }
-
-//     let mut polylines = vec![];
-//     let mut lengths = vec![];
-//     let mut line_length: f64;
-//     for i in 0..polygons2.len() {
-//         let mut pl = polygons2[i].clone();
-//         for j in i + 1..polygons2.len() {
-//             if features_bb[i].overlaps(features_bb[j]) {
-//                 // find any intersections between the polylines
-//                 find_split_points_at_line_intersections(&mut pl, &mut (polygons2[j]));
-//             }
-//         }
-//         let split_lines = pl.split();
-//         for j in 0..split_lines.len() {
-//             line_length = split_lines[j].length();
-//             if line_length > precision {
-//                 polylines.push(split_lines[j].clone());
-//                 lengths.push(line_length);
-//             }
-//         }
-//     }
-
-//     // Remove any zero-length line segments
-//     for i in 0..polylines.len() {
-//         for j in (1..polylines[i].len()).rev() {
-//             if polylines[i][j] == polylines[i][j - 1] {
-//                 polylines[i].remove(j);
-//             }
-//         }
-//     }
-//     // Remove any single-point lines result from above.
-//     for i in (0..polylines.len()).rev() {
-//         if polylines[i].len() < 2 {
-//             polylines.remove(i);
-//         }
-//     }
-
-//     // let mut interior_line = vec![false; polylines.len()];
-//     // for i in 0..polylines.len() {
-//     //     let bb = polylines[i].get_bounding_box();
-//     //     let test_point = Point2D::midpoint(&(polylines[i][0]), &(polylines[i][1]));
-//     //     for j in 0..polygons2.len() {
-//     //         if bb.overlaps(features_bb[j]) && polylines[i].id != polygons2[j].id {
-//     //             if point_in_poly(&test_point, &polygons2[j].vertices) {
-//     //                 interior_line[i] = true;
-//     //                 break;
-//     //             }
-//     //         }
-//     //     }
-//     // }
-
-//     // for i in (0..polylines.len()).rev() {
-//     //     if interior_line[i] {
-//     //         polylines.remove(i);
-//     //     }
-//     // }
-
-//     // let mut feature_geometries: Vec<MultiPolyline> = vec![];
-//     // for i in 0..polylines.len() {
-//     //     let mut mp = MultiPolyline::new(i + 1);
-//     //     mp.push(&polylines[i]);
-//     //     feature_geometries.push(mp);
-//     // }
-
-//     // return feature_geometries;
-
-//     let num_endnodes = polylines.len() * 2;
-//     /*
-//         The structure of endnodes is as such:
-//         1. the starting node for polyline 'a' is a * 2.
-//         2. the ending node for polyline 'a' is a * 2 + 1.
-//         3. endnode to polyline = e / 2
-//         4. is an endnode a starting point? e % 2 == 0
-//     */
-//     let mut endnodes: Vec<Vec<usize>> = vec![vec![]; num_endnodes];
-
-//     // now add the endpoints of each polyline into a kd tree
-//     let dimensions = 2;
-//     let capacity_per_node = 64;
-//     let mut kdtree = KdTree::new_with_capacity(dimensions, capacity_per_node);
-//     let mut p1: Point2D;
-//     let mut p2: Point2D;
-//     let mut p3: Point2D;
-//     let mut p4: Point2D;
-//     for i in 0..polylines.len() {
-//         p1 = polylines[i].first_vertex();
-//         kdtree.add([p1.x, p1.y], first_node_id(i)).unwrap();
-
-//         p2 = polylines[i].last_vertex();
-//         kdtree.add([p2.x, p2.y], last_node_id(i)).unwrap();
-//     }
-
-//     // Find the neighbours of each endnode and check for dangling arcs
-//     // and self-closing arcs which form single-line polys.
-//     let mut is_acyclic_arc = vec![false; polylines.len()];
-//     let mut node_angles: Vec<Vec<f64>> = vec![vec![]; num_endnodes];
-//     let mut heading: f64;
-//     for i in 0..polylines.len() {
-//         p1 = polylines[i].first_vertex();
-//         p2 = polylines[i].last_vertex();
-
-//         // check the first vertex
-//         let ret = kdtree
-//             .within(&[p1.x, p1.y], precision, &squared_euclidean)
-//             .unwrap();
-//         if ret.len() == 1 {
-//             is_acyclic_arc[i] = true;
-//         } else {
-//             p3 = polylines[i][1];
-//             for a in 0..ret.len() {
-//                 let index = *ret[a].1;
-//                 if index == last_node_id(i) && polylines[i].len() <= 2 {
-//                     is_acyclic_arc[i] = true;
-//                 }
-//                 if index != first_node_id(i) && !is_acyclic_arc[index / 2] {
-//                     p4 = if is_first_node(index) {
-//                         polylines[index / 2][1]
-//                     } else {
-//                         polylines[index / 2][polylines[index / 2].len() - 2]
-//                     };
-//                     heading = Point2D::change_in_heading(p3, p1, p4);
-//                     if !heading.is_nan() {
-//                         node_angles[first_node_id(i)].push(heading);
-//                         endnodes[first_node_id(i)].push(index);
-//                     }
-//                 }
-//             }
-//             if endnodes[first_node_id(i)].len() == 0 {
-//                 is_acyclic_arc[i] = true;
-//             }
-//         }
-
-//         // the first vertex showed that this line is a dangling arc,
-//         // don't bother connecting it to the graph
-//         if !is_acyclic_arc[i] {
-//             // check the last vertex
-//             let ret = kdtree
-//                 .within(&[p2.x, p2.y], precision, &squared_euclidean)
-//                 .unwrap();
-//             if ret.len() == 1 {
-//                 is_acyclic_arc[i] = true;
-//             } else {
-//                 p3 = polylines[i][polylines[i].len() - 2];
-//                 for a in 0..ret.len() {
-//                     let index = *ret[a].1;
-//                     if index != last_node_id(i) && !is_acyclic_arc[index / 2] {
-//                         p4 = if is_first_node(index) {
-//                             polylines[index / 2][1]
-//                         } else {
-//                             polylines[index / 2][polylines[index / 2].len() - 2]
-//                         };
-//                         heading = Point2D::change_in_heading(p3, p2, p4);
-//                         if !heading.is_nan() {
-//                             node_angles[last_node_id(i)].push(heading);
-//                             endnodes[last_node_id(i)].push(index);
-//                         }
-//                     }
-//                 }
-//                 if endnodes[last_node_id(i)].len() == 0 {
-//                     is_acyclic_arc[i] = true;
-//                 }
-//             }
-//         }
-//     }
-
-//     // Find connecting arcs. These are arcs that don't form loops. The only way to
-//     // travel from one endnode to the other is to travel through the polyline. They
-//     // can be safely removed from the graph.
-//     let mut source_node: usize;
-//     let mut target_node: usize;
-//     for i in 0..polylines.len() {
-//         if !is_acyclic_arc[i] {
-//             source_node = last_node_id(i); // start with the end node of the polyline
-//             target_node = first_node_id(i); // end with the start node of the polyline
-
-//             let mut prev = vec![num_endnodes; num_endnodes];
-
-//             // set the source node's prev value to anything other than num_endnodes
-//             prev[source_node] = num_endnodes + 1;
-
-//             // initialize the queue
-//             let mut queue = BinaryHeap::with_capacity(num_endnodes);
-//             for a in &endnodes[source_node] {
-//                 prev[*a] = source_node;
-//                 queue.push(Link {
-//                     id: *a,
-//                     priority: 0f64,
-//                 });
-//             }
-
-//             let mut target_found = false;
-//             while !queue.is_empty() && !target_found {
-//                 let link = queue.pop().expect("Error during pop operation.");
-//                 if link.id == target_node {
-//                     // This happens for a single-line polygon.
-//                     target_found = true;
-//                     break;
-//                 }
-//                 let other_side = get_other_endnode(link.id);
-//                 prev[other_side] = link.id;
-//                 for a in &endnodes[other_side] {
-//                     if prev[*a] == num_endnodes {
-//                         prev[*a] = other_side;
-//                         if *a == target_node {
-//                             target_found = true;
-//                             break;
-//                         }
-//                         if !is_acyclic_arc[*a / 2] {
-//                             queue.push(Link {
-//                                 id: *a,
-//                                 priority: link.priority + lengths[link.id / 2],
-//                             });
-//                         }
-//                     }
-//                 }
-//             }
-
-//             if !target_found {
-//                 is_acyclic_arc[i] = true;
-//             }
-//         }
-//     }
-
-//     let mut node1: usize;
-//     let mut node2: usize;
-//     for i in 0..polylines.len() {
-//         node1 = first_node_id(i);
-//         for n in (0..endnodes[node1].len()).rev() {
-//             node2 = endnodes[node1][n];
-//             if is_acyclic_arc[node2 / 2] {
-//                 endnodes[node1].remove(n);
-//                 node_angles[node1].remove(n);
-//             }
-//         }
-//         node1 = last_node_id(i);
-//         for n in (0..endnodes[node1].len()).rev() {
-//             node2 = endnodes[node1][n];
-//             if is_acyclic_arc[node2 / 2] {
-//                 endnodes[node1].remove(n);
-//                 node_angles[node1].remove(n);
-//             }
-//         }
-//     }
-
-//     /////////////////////////////////////////////////////////////////////////////////////////
-//     // This is the main part of the analysis. It is responsible for building the polygons. //
-//     /////////////////////////////////////////////////////////////////////////////////////////
-//     let mut bb: Vec<BoundingBox> = vec![];
-//     let mut current_node: usize;
-//     let mut neighbour_node: usize;
-//     let mut num_neighbours: usize;
-//     let mut existing_polygons = HashSet::new();
-//     let mut existing_hull = HashSet::new();
-//     let mut feature_geometries: Vec<MultiPolyline> = vec![];
-//     // let mut overlay_poly_id: Vec<usize> = vec![];
-//     let mut hull_geometries: Vec<Polyline> = vec![];
-//     let mut fid = 1;
-//     let mut p: Point2D;
-//     let mut max_val: f64;
-//     let mut max_val_index: usize;
-//     let mut k: usize;
-//     let mut num_vertices: usize;
-//     let mut other_side: usize;
-//     let mut target_found: bool;
-//     let mut assigned = vec![0usize; polylines.len()];
-//     let mut is_clockwise: bool;
-//     // let mut overlaps_with_other: bool;
-//     let mut overlaps_with_poly: bool;
-//     // let mut poly_is_hole: bool;
-//     // let mut other_is_hole: bool;
-//     let mut last_index: usize;
-
-//     for i in 0..polylines.len() {
-//         let mut mp = MultiPolyline::new(i + 1);
-//         mp.push(&polylines[i]);
-//         feature_geometries.push(mp);
-//     }
-
-//     return feature_geometries;
-
-//     // println!("12962 {:?}", endnodes[12962]);
-//     // println!("12963 {:?}", endnodes[12963]);
-//     // println!("length: {}", polylines[6481].length());
-
-//     for i in 0..polylines.len() {
-//         // if i == 3602 {
-//         //     println!("I'm here at {}", i);
-//         // }
-//         if !is_acyclic_arc[i] && assigned[i] < 2 {
-//             // if i == 3602 {
-//             //     println!("I'm here at {}", i);
-//             // }
-//             // starting at the last vertex, traverse a chain of lines, always
-//             // taking the rightmost line at each junction. Stop when you encounter
-//             // the first vertex of the line.
-
-//             source_node = last_node_id(i); // start with the end node of the polyline
-//             target_node = first_node_id(i); // end with the start node of the polyline
-
-//             let mut prev = vec![num_endnodes; num_endnodes];
-
-//             target_found = false;
-
-//             current_node = source_node;
-//             loop {
-//                 num_neighbours = endnodes[current_node].len();
-//                 if num_neighbours > 1 {
-//                     // We're at a junction and we should take the
-//                     // rightmost line.
-//                     max_val = node_angles[current_node][0];
-//                     max_val_index = 0;
-//                     for a in 1..num_neighbours {
-//                         if node_angles[current_node][a] > max_val {
-//                             max_val = node_angles[current_node][a];
-//                             max_val_index = a;
-//                         }
-//                         if endnodes[current_node][a] == target_node {
-//                             neighbour_node = endnodes[current_node][a];
-//                             prev[neighbour_node] = current_node;
-//                             break;
-//                         }
-//                     }
-//                     neighbour_node = endnodes[current_node][max_val_index];
-//                     other_side = get_other_endnode(neighbour_node);
-//                     if prev[other_side] != num_endnodes && other_side != target_node {
-//                         break;
-//                     }
-//                     prev[neighbour_node] = current_node;
-//                     prev[other_side] = neighbour_node;
-//                     if neighbour_node == target_node || other_side == target_node {
-//                         target_found = true;
-//                         break;
-//                     }
-//                     current_node = other_side;
-//                 } else if num_neighbours == 1 {
-//                     // There's only one way forward, so take it.
-//                     neighbour_node = endnodes[current_node][0];
-//                     other_side = get_other_endnode(neighbour_node);
-//                     // if i == 3602 {
-//                     //     println!(
-//                     //         "neighbour_node {} ({}) other_side {} ({})",
-//                     //         neighbour_node,
-//                     //         neighbour_node / 2,
-//                     //         other_side,
-//                     //         other_side / 2
-//                     //     );
-//                     // }
-//                     if prev[other_side] != num_endnodes && other_side != target_node {
-//                         break;
-//                     }
-//                     prev[neighbour_node] = current_node;
-//                     prev[other_side] = neighbour_node;
-//                     if neighbour_node == target_node || other_side == target_node {
-//                         target_found = true;
-//                         break;
-//                     }
-//                     current_node = other_side;
-//                 } else {
-//                     // because we've removed links to dangling arcs, this should never occur
-//                     break;
-//                 }
-//             }
-
-//             if target_found {
-//                 // if i == 3602 {
-//                 //     println!("target found at {}", i);
-//                 // }
-//                 // traverse from the target to the source
-//                 let mut lines: Vec<usize> = vec![];
-//                 let mut backlinks: Vec<usize> = vec![];
-//                 k = target_node;
-//                 num_vertices = 0;
-//                 while k != source_node {
-//                     k = prev[k];
-//                     backlinks.push(k);
-//                     let pl = k / 2;
-//                     if !is_first_node(k) {
-//                         // don't add polylines twice. Add at the ending node.
-//                         lines.push(pl);
-//                         num_vertices += polylines[pl].len() - 1;
-//                     }
-//                 }
-//                 backlinks.push(target_node);
-
-//                 // join the lines
-//                 lines.reverse();
-//                 backlinks.reverse();
-//                 let mut vertices: Vec<Point2D> = Vec::with_capacity(num_vertices);
-//                 for a in 0..lines.len() {
-//                     let pl = lines[a];
-//                     let mut v = (polylines[pl].vertices).clone();
-//                     if backlinks[a * 2] > backlinks[a * 2 + 1] {
-//                         v.reverse();
-//                     }
-//                     if a < lines.len() - 1 {
-//                         v.pop();
-//                     }
-//                     vertices.append(&mut v);
-//                 }
-
-//                 // Is it clockwise order?
-//                 is_clockwise = is_clockwise_order(&vertices);
-
-//                 // don't add the same poly more than once
-//                 let mut test_poly = lines.clone();
-//                 test_poly.sort();
-//                 if !existing_polygons.contains(&test_poly) {
-//                     if is_clockwise {
-//                         existing_polygons.insert(test_poly);
-//                         for a in 0..lines.len() {
-//                             assigned[lines[a]] += 1;
-//                         }
-//                         if vertices.len() > 3 {
-//                             // a minimum of four points are needed to form a closed polygon (triangle)
-//                             if !vertices[0].nearly_equals(&vertices[vertices.len() - 1]) {
-//                                 p = vertices[0];
-//                                 if vertices[0].distance(&vertices[vertices.len() - 1]) < precision {
-//                                     last_index = vertices.len() - 1;
-//                                     vertices[last_index] = p;
-//                                 } else {
-//                                     vertices.push(p);
-//                                 }
-//                             }
-//                             p = interior_point(&vertices);
-//                             overlaps_with_poly = false;
-//                             for j in 0..polygons2.len() {
-//                                 if point_in_poly(&p, &(polygons2[j].vertices)) {
-//                                     overlaps_with_poly = true;
-//                                     break;
-//                                     // if polygons[j].source_file != feature_source_file {
-//                                     //     other_poly_id = polygons[j].id;
-//                                     //     if !is_part_a_hole2[j] && !other_is_hole {
-//                                     //         overlaps_with_other = true;
-//                                     //     } else {
-//                                     //         overlaps_with_other = false;
-//                                     //         other_is_hole = true;
-//                                     //     }
-//                                     // } else {
-//                                     //     if !is_part_a_hole2[j] && !poly_is_hole {
-//                                     //         overlaps_with_poly = true;
-//                                     //     } else {
-//                                     //         overlaps_with_poly = false;
-//                                     //         poly_is_hole = true;
-//                                     //     }
-//                                     // }
-//                                 }
-//                             }
-
-//                             if overlaps_with_poly {
-//                                 // output the polygon
-//                                 let sfg = Polyline::new(&vertices, fid);
-//                                 fid += 1;
-//                                 bb.push(sfg.get_bounding_box());
-//                                 let mut mp = MultiPolyline::new(fid);
-//                                 mp.push(&sfg);
-//                                 feature_geometries.push(mp);
-//                             // if i == 3602 {
-//                             //     println!("Polygon found at {}", i);
-//                             // }
-//                             // if i == 3600 {
-//                             //     println!("Polygon found at {} {:?}", i, lines.clone());
-//                             // }
-//                             } else {
-//                                 let mut test_poly = lines.clone();
-//                                 test_poly.sort();
-//                                 if !existing_hull.contains(&test_poly) {
-//                                     existing_hull.insert(test_poly);
-//                                     for a in 0..lines.len() {
-//                                         assigned[lines[a]] += 1;
-//                                     }
-//                                     hull_geometries.push(Polyline::new(&vertices, 0));
-//                                     // if i == 3602 {
-//                                     //     println!("Possible hull found at {}", i);
-//                                     // }
-//                                 }
-//                             }
-//                         }
-//                     } else {
-//                         // This could be a hull.
-//                         test_poly = lines.clone();
-//                         test_poly.sort();
-//                         if !existing_hull.contains(&test_poly) {
-//                             existing_hull.insert(test_poly);
-//                             for a in 0..lines.len() {
-//                                 assigned[lines[a]] += 1;
-//                             }
-//                             if vertices.len() > 3 {
-//                                 // a minimum of four points are needed to form a closed polygon (triangle)
-//                                 if !vertices[0].nearly_equals(&vertices[vertices.len() - 1]) {
-//                                     p = vertices[0];
-//                                     if vertices[0].distance(&vertices[vertices.len() - 1])
-//                                         < precision
-//                                     {
-//                                         last_index = vertices.len() - 1;
-//                                         vertices[last_index] = p;
-//                                     } else {
-//                                         vertices.push(p);
-//                                     }
-//                                 }
-//                                 p = interior_point(&vertices);
-//                                 overlaps_with_poly = false;
-//                                 for j in 0..polygons2.len() {
-//                                     if point_in_poly(&p, &(polygons2[j].vertices)) {
-//                                         overlaps_with_poly = true;
-//                                         break;
-//                                     }
-//                                 }
-//                                 if !overlaps_with_poly {
-//                                     hull_geometries.push(Polyline::new(&vertices, 0));
-//                                     // if i == 3602 {
-//                                     //     println!("ccw hull found at {}", i);
-//                                     // }
-//                                 }
-//                             }
-//                         }
-//                     }
-//                 }
-//             }
-
-//             if assigned[i] < 2 {
-//                 ///////////////////////////////////////
-//                 // now check for a left-side polygon //
-//                 ///////////////////////////////////////
-//                 source_node = first_node_id(i); // start with the first node of the polyline
-//                 target_node = last_node_id(i); // end with the last node of the polyline
-
-//                 let mut prev = vec![num_endnodes; num_endnodes];
-
-//                 target_found = false;
-
-//                 current_node = source_node;
-//                 loop {
-//                     num_neighbours = endnodes[current_node].len();
-//                     if num_neighbours > 1 {
-//                         // // We're at a junction and we should take the
-//                         // // rightmost line.
-//                         // max_val = node_angles[current_node][0];
-//                         // max_val_index = 0;
-//                         // for a in 1..num_neighbours {
-//                         //     if node_angles[current_node][a] > max_val {
-//                         //         max_val = node_angles[current_node][a];
-//                         //         max_val_index = a;
-//                         //     }
-//                         //     if endnodes[current_node][a] == target_node {
-//                         //         neighbour_node = endnodes[current_node][a];
-//                         //         prev[neighbour_node] = current_node;
-//                         //         break;
-//                         //     }
-//                         // }
-//                         // neighbour_node = endnodes[current_node][max_val_index];
-
-//                         // We're at a junction and we should take the
-//                         // leftmost line.
-//                         let mut min_val = node_angles[current_node][0];
-//                         let mut min_val_index = 0;
-//                         for a in 1..num_neighbours {
-//                             if node_angles[current_node][a] < min_val {
-//                                 min_val = node_angles[current_node][a];
-//                                 min_val_index = a;
-//                             }
-//                             if endnodes[current_node][a] == target_node {
-//                                 neighbour_node = endnodes[current_node][a];
-//                                 prev[neighbour_node] = current_node;
-//                                 break;
-//                             }
-//                         }
-//                         neighbour_node = endnodes[current_node][min_val_index];
-//                         other_side = get_other_endnode(neighbour_node);
-//                         if prev[other_side] != num_endnodes && other_side != target_node {
-//                             break;
-//                         }
-//                         prev[neighbour_node] = current_node;
-//                         prev[other_side] = neighbour_node;
-//                         if neighbour_node == target_node || other_side == target_node {
-//                             target_found = true;
-//                             break;
-//                         }
-//                         current_node = other_side;
-//                     } else if num_neighbours == 1 {
-//                         // There's only one way forward, so take it.
-//                         neighbour_node = endnodes[current_node][0];
-//                         other_side = get_other_endnode(neighbour_node);
-//                         if prev[other_side] != num_endnodes && other_side != target_node {
-//                             break;
-//                         }
-//                         prev[neighbour_node] = current_node;
-//                         prev[other_side] = neighbour_node;
-//                         if neighbour_node == target_node || other_side == target_node {
-//                             target_found = true;
-//                             break;
-//                         }
-//                         current_node = other_side;
-//                     } else {
-//                         // because we've removed links to danling arcs, this should never occur
-//                         break;
-//                     }
-//                 }
-
-//                 if target_found {
-//                     // if i == 3602 {
-//                     //     println!("second target found at {}", i);
-//                     // }
-//                     // traverse from the target to the source
-//                     let mut lines: Vec<usize> = vec![];
-//                     let mut backlinks: Vec<usize> = vec![];
-//                     k = target_node;
-//                     num_vertices = 0;
-//                     while k != source_node {
-//                         k = prev[k];
-//                         backlinks.push(k);
-//                         let pl = k / 2;
-//                         if is_first_node(k) {
-//                             // don't add polylines twice. Add at the first node.
-//                             lines.push(pl);
-//                             num_vertices += polylines[pl].len() - 1;
-//                         }
-//                     }
-//                     backlinks.push(target_node);
-
-//                     // join the lines and then output the polygon
-//                     lines.reverse();
-//                     backlinks.reverse();
-//                     let mut vertices: Vec<Point2D> = Vec::with_capacity(num_vertices);
-//                     for a in 0..lines.len() {
-//                         let pl = lines[a];
-//                         let mut v = (polylines[pl].vertices).clone();
-//                         if backlinks[a * 2] > backlinks[a * 2 + 1] {
-//                             v.reverse();
-//                         }
-//                         if a < lines.len() - 1 {
-//                             v.pop();
-//                         }
-//                         vertices.append(&mut v);
-//                     }
-
-//                     // if i == 3602 {
-//                     //     println!("Lines at {} {:?}", i, lines.clone());
-//                     // }
-
-//                     // Is it clockwise order?
-//                     is_clockwise = is_clockwise_order(&vertices);
-
-//                     // don't add the same poly more than once
-//                     let mut test_poly = lines.clone();
-//                     test_poly.sort();
-//                     if !existing_polygons.contains(&test_poly) {
-//                         if is_clockwise {
-//                             existing_polygons.insert(test_poly);
-//                             for a in 0..lines.len() {
-//                                 assigned[lines[a]] += 1;
-//                             }
-//                             if vertices.len() > 3 {
-//                                 // a minimum of four points are needed to form a closed polygon (triangle)
-//                                 if !vertices[0].nearly_equals(&vertices[vertices.len() - 1]) {
-//                                     p = vertices[0];
-//                                     if vertices[0].distance(&vertices[vertices.len() - 1])
-//                                         < precision
-//                                     {
-//                                         last_index = vertices.len() - 1;
-//                                         vertices[last_index] = p;
-//                                     } else {
-//                                         vertices.push(p);
-//                                     }
-//                                 }
-//                                 p = interior_point(&vertices);
-//                                 // overlaps_with_other = false;
-//                                 overlaps_with_poly = false;
-//                                 // poly_is_hole = false;
-//                                 // other_is_hole = false;
-//                                 // let mut other_poly_id = 0;
-//                                 for j in 0..polygons2.len() {
-//                                     if point_in_poly(&p, &(polygons2[j].vertices)) {
-//                                         overlaps_with_poly = true;
-//                                         break;
-//                                         // if polygons[j].source_file != feature_source_file {
-//                                         //     other_poly_id = polygons[j].id;
-//                                         //     if !is_part_a_hole2[j] && !other_is_hole {
-//                                         //         overlaps_with_other = true;
-//                                         //     } else {
-//                                         //         overlaps_with_other = false;
-//                                         //         other_is_hole = true;
-//                                         //     }
-//                                         // } else {
-//                                         //     if !is_part_a_hole2[j] && !poly_is_hole {
-//                                         //         overlaps_with_poly = true;
-//                                         //     } else {
-//                                         //         overlaps_with_poly = false;
-//                                         //         poly_is_hole = true;
-//                                         //     }
-//                                         // }
-//                                     }
-//                                 }
-//                                 if overlaps_with_poly {
-//                                     // output the polygon
-//                                     let sfg = Polyline::new(&vertices, fid);
-//                                     fid += 1;
-//                                     bb.push(sfg.get_bounding_box());
-//                                     let mut mp = MultiPolyline::new(fid);
-//                                     mp.push(&sfg);
-//                                     feature_geometries.push(mp);
-//                                 // if i == 3602 {
-//                                 //     println!("second polygon found at {}", i);
-//                                 // }
-//                                 } else {
-//                                     let mut test_poly = lines.clone();
-//                                     test_poly.sort();
-//                                     if !existing_hull.contains(&test_poly) {
-//                                         existing_hull.insert(test_poly);
-//                                         for a in 0..lines.len() {
-//                                             assigned[lines[a]] += 1;
-//                                         }
-//                                         hull_geometries.push(Polyline::new(&vertices, 0));
-//                                         // if i == 3602 {
-//                                         //     println!("second hull found at {}", i);
-//                                         // }
-//                                     }
-//                                 }
-//                             }
-//                         } else {
-//                             // This could be a hull.
-//                             test_poly = lines.clone();
-//                             test_poly.sort();
-//                             if !existing_hull.contains(&test_poly) {
-//                                 for a in 0..lines.len() {
-//                                     assigned[lines[a]] += 1;
-//                                 }
-//                                 existing_hull.insert(test_poly);
-//                                 if vertices.len() > 3 {
-//                                     // a minimum of four points are needed to form a closed polygon (triangle)
-//                                     if !vertices[0].nearly_equals(&vertices[vertices.len() - 1]) {
-//                                         p = vertices[0];
-//                                         if vertices[0].distance(&vertices[vertices.len() - 1])
-//                                             < precision
-//                                         {
-//                                             last_index = vertices.len() - 1;
-//                                             vertices[last_index] = p;
-//                                         } else {
-//                                             vertices.push(p);
-//                                         }
-//                                     }
-//                                     p = interior_point(&vertices);
-//                                     // overlaps_with_other = false;
-//                                     overlaps_with_poly = false;
-//                                     // poly_is_hole = false;
-//                                     // other_is_hole = false;
-//                                     // let mut other_poly_id = 0;
-//                                     for j in 0..polygons2.len() {
-//                                         if point_in_poly(&p, &(polygons2[j].vertices)) {
-//                                             overlaps_with_poly = true;
-//                                             break;
-//                                             // if polygons[j].source_file != feature_source_file {
-//                                             //     // other_poly_id = polygons[j].id;
-//                                             //     if !is_part_a_hole2[j] && !other_is_hole {
-//                                             //         overlaps_with_other = true;
-//                                             //     } else {
-//                                             //         overlaps_with_other = false;
-//                                             //         other_is_hole = true;
-//                                             //     }
-//                                             // } else {
-//                                             //     if !is_part_a_hole2[j] && !poly_is_hole {
-//                                             //         overlaps_with_poly = true;
-//                                             //     } else {
-//                                             //         overlaps_with_poly = false;
-//                                             //         poly_is_hole = true;
-//                                             //     }
-//                                             // }
-//                                         }
-//                                     }
-//                                     if !overlaps_with_poly {
-//                                         hull_geometries.push(Polyline::new(&vertices, 0));
-//                                         // if i == 3602 {
-//                                         //     println!("second ccw hull found at {}", i);
-//                                         // }
-//                                         // } else {
-//                                         //     let mut test_poly = lines.clone();
-//                                         //     test_poly.sort();
-//                                         //     existing_polygons.insert(test_poly);
-//                                         //     for a in 0..lines.len() {
-//                                         //         assigned[lines[a]] += 1;
-//                                         //     }
-//                                         //     // output the polygon
-//                                         //     let mut sfg = Polyline::new(&vertices, fid);
-//                                         //     fid += 1;
-//                                         //     bb.push(sfg.get_bounding_box());
-//                                         //     let mut mp = MultiPolyline::new(fid);
-//                                         //     mp.push(&sfg);
-//                                         //     feature_geometries.push(mp);
-//                                     }
-//                                 }
-//                             }
-//                         }
-//                     }
-//                 }
-//             }
-//         }
-//     }
-
-//     // can any of the hulls be added as holes in other polygons?
-//     for a in 0..hull_geometries.len() {
-//         let hull_bb = hull_geometries[a].get_bounding_box();
-//         for b in 0..bb.len() {
-//             if hull_bb.entirely_contained_within(bb[b]) {
-//                 if poly_in_poly(
-//                     &(hull_geometries[a].vertices),
-//                     &(feature_geometries[b][0].vertices),
-//                 ) {
-//                     if is_clockwise_order(&hull_geometries[a].vertices) {
-//                         hull_geometries[a].vertices.reverse();
-//                     }
-//                     feature_geometries[b].push(&hull_geometries[a]);
-//                 }
-//             }
-//         }
-//     }
-
-//     feature_geometries
-// }
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 22/11/2018
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::union_rings;
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use num_cpus;
+use std::env;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool creates buffer polygons around the features in an input vector file (`--input`),
+/// i.e. polygons enclosing the area within a specified distance of each feature. The input may
+/// contain points, lines, or polygons. The buffer distance is either a constant (`--dist`) or is
+/// read, for each feature, from a numeric field in the attribute table (`--dist_field`). Features
+/// with null distance values are not buffered.
+///
+/// The shape of the buffers at the ends of lines is determined by the cap style (`--cap`), which
+/// may be 'round', 'flat', in which case the buffer ends at the line end, or 'square', in which case
+/// the buffer extends beyond the line end by the buffer distance. Point features are buffered using
+/// circles with the round cap style and squares otherwise. The shape of the buffer at the outside of
+/// the bends in lines and polygon boundaries is determined by the join style (`--join`), which
+/// may be 'round', 'mitre', or 'bevel'. Mitre joins extend the offset edges until they meet, unless
+/// the mitre point is further from the vertex than the mitre limit (`--mitre_limit`) multiplied by
+/// the buffer distance, in which case the join is bevelled. Curves are approximated using a number
+/// of segments per quarter circle (`--segments`).
+///
+/// Polygons may be buffered using negative distances, which shrink the polygons inwards. Polygons,
+/// or parts of polygons, that are narrower than twice the buffer distance will be removed by a
+/// negative buffer. Negative distances are ignored for point and line features.
+///
+/// By default, the buffer of each input feature is output as a separate polygon feature, carrying
+/// the attributes of the input feature, and the buffers of neighbouring features may overlap.
+/// Alternatively, the buffers may be dissolved (`--dissolve`), in which case overlapping buffers are
+/// merged and each contiguous buffered area is output as a separate feature, with only a feature
+/// identifier (`FID`) attribute.
+///
+/// # See Also
+/// `BufferRaster`, `Dissolve`, `Union`
+pub struct BufferVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BufferVector {
+    pub fn new() -> BufferVector {
+        // public constructor
+        let name = "BufferVector".to_string();
+        let toolbox = "GIS Analysis/Distance Tools".to_string();
+        let description =
+            "Creates buffer polygons around the point, line, or polygon features in a vector file."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygon file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Buffer Distance".to_owned(),
+            flags: vec!["--dist".to_owned(), "--distance".to_owned()],
+            description: "Buffer distance.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Buffer Distance Field (optional)".to_owned(),
+            flags: vec!["--dist_field".to_owned()],
+            description: "Optional numeric field containing the buffer distance of each feature."
+                .to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cap Style".to_owned(),
+            flags: vec!["--cap".to_owned()],
+            description: "Style of the buffer at line ends; options include 'round', 'flat', and 'square'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "round".to_owned(),
+                "flat".to_owned(),
+                "square".to_owned(),
+            ]),
+            default_value: Some("round".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Join Style".to_owned(),
+            flags: vec!["--join".to_owned()],
+            description: "Style of the buffer at bends; options include 'round', 'mitre', and 'bevel'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "round".to_owned(),
+                "mitre".to_owned(),
+                "bevel".to_owned(),
+            ]),
+            default_value: Some("round".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Mitre Limit".to_owned(),
+            flags: vec!["--mitre_limit".to_owned()],
+            description: "Maximum distance of a mitre join from its vertex, as a multiple of the buffer distance.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Segments Per Quarter Circle".to_owned(),
+            flags: vec!["--segments".to_owned()],
+            description: "Number of segments used to approximate a quarter circle.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Dissolve overlapping buffers?".to_owned(),
+            flags: vec!["--dissolve".to_owned()],
+            description: "Optional flag to request that overlapping buffers be dissolved."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp -o=road_buffers.shp --dist=25.0 --cap=flat --dissolve
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp -o=setbacks.shp --dist_field=SETBACK --join=mitre",
+            short_exe, name
+        ).replace("*", &sep);
+
+        BufferVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BufferVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut distance = 10f64;
+        let mut field_name = String::new();
+        let mut cap = String::from("round");
+        let mut join = String::from("round");
+        let mut mitre_limit = 2f64;
+        let mut segments = 8usize;
+        let mut dissolve = false;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dist" || flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-dist_field" {
+                field_name = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-cap" {
+                cap = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                if cap.contains("flat") || cap.contains("butt") {
+                    cap = "flat".to_string();
+                } else if cap.contains("sq") {
+                    cap = "square".to_string();
+                } else {
+                    cap = "round".to_string();
+                }
+            } else if flag_val == "-join" {
+                join = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                if join.contains("mit") || join.contains("miter") {
+                    join = "mitre".to_string();
+                } else if join.contains("bev") {
+                    join = "bevel".to_string();
+                } else {
+                    join = "round".to_string();
+                }
+            } else if flag_val == "-mitre_limit" || flag_val == "-miter_limit" {
+                mitre_limit = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+                if mitre_limit < 1f64 {
+                    mitre_limit = 1f64;
+                }
+            } else if flag_val == "-segments" {
+                segments = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize
+                };
+                if segments < 1 {
+                    segments = 1;
+                }
+            } else if flag_val == "-dissolve" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    dissolve = true;
+                }
+            }
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::Point
+            && shape_type != ShapeType::MultiPoint
+            && shape_type != ShapeType::PolyLine
+            && shape_type != ShapeType::Polygon
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of a point, line, or polygon base shape type.",
+            ));
+        }
+        if !field_name.is_empty() {
+            if input.attributes.get_field_num(&field_name).is_none() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified distance field does not exist in the input attribute table.",
+                ));
+            }
+        } else if distance == 0f64 || (distance < 0f64 && shape_type != ShapeType::Polygon) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The buffer distance must be greater than zero (negative distances are only permitted for polygons).",
+            ));
+        }
+
+        // create the raw buffer rings of each feature
+        let step = PI / 2f64 / segments as f64;
+        let mut feature_rings: Vec<Vec<Vec<Point2D>>> = Vec::with_capacity(input.num_records);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let d = if field_name.is_empty() {
+                distance
+            } else {
+                match input.attributes.get_value(record_num, &field_name) {
+                    FieldData::Int(val) => val as f64,
+                    FieldData::Real(val) => val,
+                    _ => 0f64, // likely a null field
+                }
+            };
+            let mut rings = vec![];
+            if d > 0f64 || (d < 0f64 && shape_type == ShapeType::Polygon) {
+                // point records do not have parts
+                let num_parts = (record.num_parts as usize).max(1);
+                for part in 0..num_parts {
+                    let start_point = if record.num_parts > 0 {
+                        record.parts[part] as usize
+                    } else {
+                        0
+                    };
+                    let end_point = if part < num_parts - 1 {
+                        record.parts[part + 1] as usize
+                    } else {
+                        record.num_points as usize
+                    };
+                    let mut points: Vec<Point2D> = vec![];
+                    for p in start_point..end_point {
+                        if points.is_empty() || points[points.len() - 1] != record.points[p] {
+                            points.push(record.points[p]);
+                        }
+                    }
+                    if shape_type == ShapeType::Polygon {
+                        if points.len() > 1 && points[0] == points[points.len() - 1] {
+                            points.pop();
+                        }
+                        if points.len() < 3 {
+                            continue;
+                        }
+                        // orient the ring with the polygon interior on its left
+                        let ccw = signed_area(&points) > 0f64;
+                        if ccw == record.is_hole(part as i32) {
+                            points.reverse();
+                        }
+                        rings.push(offset_vertices(&points, d, true, &join, mitre_limit, step));
+                    } else if points.len() == 1 || shape_type != ShapeType::PolyLine {
+                        for p in points {
+                            rings.push(point_buffer(p, d, &cap, step));
+                        }
+                    } else if points.len() > 3 && points[0] == points[points.len() - 1] {
+                        // a closed line is buffered on either side, without caps
+                        points.pop();
+                        rings.push(offset_vertices(&points, d, true, &join, mitre_limit, step));
+                        points.reverse();
+                        rings.push(offset_vertices(&points, d, true, &join, mitre_limit, step));
+                    } else {
+                        rings.push(line_buffer(&points, d, &cap, &join, mitre_limit, step));
+                    }
+                }
+            }
+            feature_rings.push(rings);
+        }
+
+        // the groups of rings that are unioned together
+        let (groups, group_records): (Vec<Vec<Vec<Point2D>>>, Vec<usize>) = if dissolve {
+            let mut all_rings = vec![];
+            for rings in feature_rings {
+                all_rings.extend(rings);
+            }
+            let groups = group_overlapping_rings(all_rings);
+            let n = groups.len();
+            (groups, vec![0; n])
+        } else {
+            let mut records = vec![];
+            let mut groups = vec![];
+            for (record_num, rings) in feature_rings.into_iter().enumerate() {
+                if !rings.is_empty() {
+                    records.push(record_num);
+                    groups.push(rings);
+                }
+            }
+            (groups, records)
+        };
+
+        let num_groups = groups.len();
+        let groups = Arc::new(groups);
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let groups = groups.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for g in (0..num_groups).filter(|g| g % num_procs == tid) {
+                    tx.send((g, union_rings(&groups[g]))).unwrap();
+                }
+            });
+        }
+        let mut polygons: Vec<Vec<Vec<Vec<Point2D>>>> = vec![vec![]; num_groups];
+        for g in 0..num_groups {
+            let (group, polys) = rx.recv().expect("Error receiving data from thread.");
+            polygons[group] = polys;
+            if verbose {
+                progress = (100.0_f64 * (g + 1) as f64 / num_groups as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, !dissolve)?;
+        if dissolve {
+            output
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        }
+
+        let mut fid = 1i32;
+        for g in 0..num_groups {
+            if polygons[g].is_empty() {
+                continue;
+            }
+            if dissolve {
+                for polygon in &polygons[g] {
+                    output.add_record(polygon_geometry(&[polygon.clone()]));
+                    output
+                        .attributes
+                        .add_record(vec![FieldData::Int(fid)], false);
+                    fid += 1;
+                }
+            } else {
+                output.add_record(polygon_geometry(&polygons[g]));
+                output
+                    .attributes
+                    .add_record(input.attributes.get_record(group_records[g]), false);
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a set of polygons, with counter-clockwise exterior rings and clockwise holes,
+/// into a polygon geometry, reversing the rings to follow the Shapefile convention of
+/// clockwise exterior rings.
+fn polygon_geometry(polygons: &[Vec<Vec<Point2D>>]) -> ShapefileGeometry {
+    let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+    for polygon in polygons {
+        for ring in polygon {
+            let mut points = ring.clone();
+            points.reverse();
+            sfg.add_part(&points);
+        }
+    }
+    sfg
+}
+
+/// Groups rings with overlapping bounding boxes, such that each group can be unioned
+/// independently.
+fn group_overlapping_rings(rings: Vec<Vec<Point2D>>) -> Vec<Vec<Vec<Point2D>>> {
+    let n = rings.len();
+    let mut boxes = Vec::with_capacity(n);
+    for ring in &rings {
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for p in ring {
+            min_x = min_x.min(p.x);
+            min_y = min_y.min(p.y);
+            max_x = max_x.max(p.x);
+            max_y = max_y.max(p.y);
+        }
+        boxes.push((min_x, min_y, max_x, max_y));
+    }
+    let mut parent: Vec<usize> = (0..n).collect();
+    fn find(parent: &mut Vec<usize>, mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    // sweep along the x-axis to find the overlapping boxes
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| boxes[a].0.partial_cmp(&boxes[b].0).unwrap());
+    let mut active: Vec<usize> = vec![];
+    for &a in &order {
+        active.retain(|&b| boxes[b].2 >= boxes[a].0);
+        for &b in &active {
+            if boxes[b].1 <= boxes[a].3 && boxes[b].3 >= boxes[a].1 {
+                let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+                if ra != rb {
+                    parent[ra] = rb;
+                }
+            }
+        }
+        active.push(a);
+    }
+    let mut group_of = vec![usize::max_value(); n];
+    let mut groups: Vec<Vec<Vec<Point2D>>> = vec![];
+    for (i, ring) in rings.into_iter().enumerate() {
+        let root = find(&mut parent, i);
+        if group_of[root] == usize::max_value() {
+            group_of[root] = groups.len();
+            groups.push(vec![]);
+        }
+        groups[group_of[root]].push(ring);
+    }
+    groups
+}
+
+/// Creates the counter-clockwise buffer ring of a point; a circle for the round cap style
+/// and a square otherwise.
+fn point_buffer(p: Point2D, d: f64, cap: &str, step: f64) -> Vec<Point2D> {
+    if cap == "round" {
+        let n = (2f64 * PI / step).round() as usize;
+        (0..n)
+            .map(|i| {
+                let a = i as f64 * 2f64 * PI / n as f64;
+                Point2D::new(p.x + d * a.cos(), p.y + d * a.sin())
+            })
+            .collect()
+    } else {
+        vec![
+            Point2D::new(p.x - d, p.y - d),
+            Point2D::new(p.x + d, p.y - d),
+            Point2D::new(p.x + d, p.y + d),
+            Point2D::new(p.x - d, p.y + d),
+        ]
+    }
+}
+
+/// Creates the raw, counter-clockwise buffer ring of a line, made up of the offset of the
+/// line on its right side, the end cap, the offset of the reversed line, and the start cap.
+fn line_buffer(
+    points: &[Point2D],
+    d: f64,
+    cap: &str,
+    join: &str,
+    mitre_limit: f64,
+    step: f64,
+) -> Vec<Point2D> {
+    let n = points.len();
+    let mut ring = offset_vertices(points, d, false, join, mitre_limit, step);
+    ring.extend(cap_vertices(&points[n - 2], &points[n - 1], d, cap, step));
+    let reversed: Vec<Point2D> = points.iter().rev().cloned().collect();
+    ring.extend(offset_vertices(&reversed, d, false, join, mitre_limit, step));
+    ring.extend(cap_vertices(&points[1], &points[0], d, cap, step));
+    ring
+}
+
+/// Returns the vertices of a line cap, between the right and left offsets of the line end
+/// `p2`, where the line arrives from `p1`.
+fn cap_vertices(p1: &Point2D, p2: &Point2D, d: f64, cap: &str, step: f64) -> Vec<Point2D> {
+    let (ux, uy) = unit_vector(p1, p2);
+    let mut ret = vec![];
+    if cap == "round" {
+        let n = (PI / step).round() as usize;
+        let a0 = (-ux).atan2(uy); // direction of the right normal
+        for i in 1..n {
+            let a = a0 + i as f64 * PI / n as f64;
+            ret.push(Point2D::new(p2.x + d * a.cos(), p2.y + d * a.sin()));
+        }
+    } else if cap == "square" {
+        ret.push(Point2D::new(p2.x + d * (ux + uy), p2.y + d * (uy - ux)));
+        ret.push(Point2D::new(p2.x + d * (ux - uy), p2.y + d * (uy + ux)));
+    }
+    ret
+}
+
+/// Offsets a sequence of vertices by a distance to its right (or to its left for negative
+/// distances), adding joins at the vertices. Joins on the outside of bends are round,
+/// mitred, or bevelled, while on the inside of bends the vertex itself is included, which
+/// ensures that the resulting loops are removed when the rings are unioned. Closed sequences
+/// are treated as rings.
+fn offset_vertices(
+    points: &[Point2D],
+    d: f64,
+    closed: bool,
+    join: &str,
+    mitre_limit: f64,
+    step: f64,
+) -> Vec<Point2D> {
+    let n = points.len();
+    let mut ret = vec![];
+    if !closed {
+        let (ux, uy) = unit_vector(&points[0], &points[1]);
+        ret.push(Point2D::new(points[0].x + d * uy, points[0].y - d * ux));
+    }
+    let (first, last) = if closed { (0, n) } else { (1, n - 1) };
+    for i in first..last {
+        let prev = &points[(i + n - 1) % n];
+        let p = &points[i];
+        let next = &points[(i + 1) % n];
+        let (ux1, uy1) = unit_vector(prev, p);
+        let (ux2, uy2) = unit_vector(p, next);
+        // the offset vectors of the incoming and outgoing edges
+        let (v1x, v1y) = (d * uy1, -d * ux1);
+        let (v2x, v2y) = (d * uy2, -d * ux2);
+        let cross = ux1 * uy2 - uy1 * ux2;
+        let dot = ux1 * ux2 + uy1 * uy2;
+        if cross.abs() < 1e-12 && dot > 0f64 {
+            // collinear
+            ret.push(Point2D::new(p.x + v1x, p.y + v1y));
+        } else if cross * d > 0f64 {
+            // the outside of the bend
+            if join == "round" {
+                let sweep = (v1x * v2y - v1y * v2x).atan2(v1x * v2x + v1y * v2y);
+                let steps = ((sweep.abs() / step).ceil() as usize).max(1);
+                for k in 0..=steps {
+                    let a = sweep * k as f64 / steps as f64;
+                    let (sin, cos) = a.sin_cos();
+                    ret.push(Point2D::new(
+                        p.x + v1x * cos - v1y * sin,
+                        p.y + v1x * sin + v1y * cos,
+                    ));
+                }
+            } else if join == "mitre" && 1f64 / ((1f64 + dot) / 2f64).sqrt() <= mitre_limit {
+                let scale = 1f64 / (1f64 + dot);
+                ret.push(Point2D::new(
+                    p.x + (v1x + v2x) * scale,
+                    p.y + (v1y + v2y) * scale,
+                ));
+            } else {
+                ret.push(Point2D::new(p.x + v1x, p.y + v1y));
+                ret.push(Point2D::new(p.x + v2x, p.y + v2y));
+            }
+        } else {
+            // the inside of the bend
+            ret.push(Point2D::new(p.x + v1x, p.y + v1y));
+            ret.push(*p);
+            ret.push(Point2D::new(p.x + v2x, p.y + v2y));
+        }
+    }
+    if !closed {
+        let (ux, uy) = unit_vector(&points[n - 2], &points[n - 1]);
+        ret.push(Point2D::new(points[n - 1].x + d * uy, points[n - 1].y - d * ux));
+    }
+    ret
+}
+
+fn unit_vector(p1: &Point2D, p2: &Point2D) -> (f64, f64) {
+    let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+    let len = (dx * dx + dy * dy).sqrt();
+    (dx / len, dy / len)
+}
+
+fn signed_area(ring: &[Point2D]) -> f64 {
+    let n = ring.len();
+    let mut area = 0f64;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += ring[i].x * ring[j].y - ring[j].x * ring[i].y;
+    }
+    area / 2f64
+}
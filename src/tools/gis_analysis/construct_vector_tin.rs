@@ -2,14 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/09/2018
-Last Modified: 07/12/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use self::na::Vector3;
-use crate::algorithms::triangulate;
 use crate::na;
-use crate::structures::Point2D;
+use crate::structures::{construct_tin, Point2D};
 use crate::tools::*;
 use crate::vector::ShapefileGeometry;
 use crate::vector::*;
@@ -28,8 +27,15 @@ use std::path;
 /// in convex regions along the data boundary. To avoid these spurious triangles, the user may optionally
 /// specify the maximum allowable edge length of a triangular facet (`--max_triangle_edge_length`).
 ///
+/// Breaklines (`--breaklines`), such as stream lines, ridge lines, shorelines, and road edges, may optionally
+/// be used to constrain the triangulation, i.e. to create a constrained Delaunay triangulation. Each breakline
+/// segment is forced to be a triangle edge, such that no triangle spans the breakline. The breaklines input may be
+/// of polyline or polygon type. Breakline vertex heights are taken from the breaklines' z values, if the
+/// file is of a z-dimension *ShapeTypeDimension*, and are otherwise interpolated from the input points. Breakline
+/// vertices outside of the data coverage are ignored, as are segments that cross previously inserted breaklines.
+///
 /// # See Also
-/// `LidarConstructVectorTIN`
+/// `LidarConstructVectorTIN`, `TINGridding`
 pub struct ConstructVectorTIN {
     name: String,
     description: String,
@@ -102,6 +108,17 @@ impl ConstructVectorTIN {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input Breaklines File (optional)".to_owned(),
+            flags: vec!["--breaklines".to_owned()],
+            description: "Optional input vector breaklines file, of polyline or polygon type.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -115,7 +132,8 @@ impl ConstructVectorTIN {
         }
         let usage = format!(
             ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=HEIGHT -o=tin.shp
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=tin.shp",
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=tin.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=HEIGHT --breaklines=streams.shp -o=tin.shp",
             short_exe, name
         )
         .replace("*", &sep);
@@ -177,6 +195,7 @@ impl WhiteboxTool for ConstructVectorTIN {
         let mut use_field = false;
         let mut output_file: String = "".to_string();
         let mut max_triangle_edge_length = f64::INFINITY;
+        let mut breaklines_file = String::new();
 
         // read the arguments
         if args.len() == 0 {
@@ -232,6 +251,12 @@ impl WhiteboxTool for ConstructVectorTIN {
                 };
 
                 max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
+            } else if flag_val == "-breaklines" {
+                breaklines_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
             }
         }
 
@@ -255,6 +280,13 @@ impl WhiteboxTool for ConstructVectorTIN {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
+        if !breaklines_file.is_empty()
+            && !breaklines_file.contains(&sep)
+            && !breaklines_file.contains("/")
+        {
+            breaklines_file = format!("{}{}", working_directory, breaklines_file);
+        }
+
         let input = Shapefile::read(&input_file)?;
 
         // make sure the input vector file is of points type
@@ -347,6 +379,8 @@ impl WhiteboxTool for ConstructVectorTIN {
                             z_values.push(0f64);
                         }
                     }
+                } else {
+                    z_values.push(0f64);
                 }
             }
 
@@ -364,7 +398,16 @@ impl WhiteboxTool for ConstructVectorTIN {
             println!("Performing triangulation...");
         }
         // this is where the heavy-lifting is
-        let result = triangulate(&points).expect("No triangulation exists.");
+        let (tin, num_failed) = construct_tin(points, z_values, &breaklines_file)?;
+        if num_failed > 0 && verbose {
+            println!(
+                "Warning: {} breaklines could not be fully enforced, likely because they cross other breaklines.",
+                num_failed
+            );
+        }
+        let result = &tin.triangulation;
+        let points = &tin.points;
+        let z_values = &tin.z_values;
 
         let (mut p1, mut p2, mut p3): (usize, usize, usize);
         let (mut fx, mut fy): (f64, f64);
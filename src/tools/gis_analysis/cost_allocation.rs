@@ -196,7 +196,8 @@ impl WhiteboxTool for CostAllocation {
         };
 
         let pntr = Raster::new(&d8_file, "r")?;
-        let pourpts = Raster::new(&pourpts_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let pourpts = align_raster_input(&pntr, Raster::new(&pourpts_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -206,16 +207,6 @@ impl WhiteboxTool for CostAllocation {
         let pntr_nodata = pntr.configs.nodata;
         let palette = pourpts.configs.palette.clone();
 
-        // make sure the input files have the same size
-        if pourpts.configs.rows != pntr.configs.rows
-            || pourpts.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
         let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
 
@@ -2,10 +2,8 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 04/07/2017
-Last Modified: 15/11/2018
+Last Modified: 15/10/2026
 License: MIT
-
-NOTES: Add anisotropy option.
 */
 
 use crate::raster::*;
@@ -43,11 +41,24 @@ use std::path;
 /// for the cost distance accumulation operation uses a type of priority-flood method similar to
 /// what is used for depression filling and flow accumulation operations.
 ///
+/// The cost of moving between cells may optionally be made anisotropic, i.e. dependent upon the
+/// direction of travel over the terrain, by specifying an input digital elevation model (`--dem`).
+/// In this case, the cost of each move is multiplied by a slope factor derived from Tobler's hiking
+/// function, *f* = exp(3.5|*s* + 0.05|) / exp(3.5 &times; 0.05), where *s* is the signed slope
+/// gradient (rise over run) in the direction of travel away from the source cells. The factor is
+/// equal to one on flat ground, is at a minimum on gentle down-slopes, and increases rapidly on
+/// steep up- and down-slopes. The DEM must share the cost raster's grid and its elevations must be
+/// in the same units as the horizontal units.
+///
 /// NoData values in the input cost surface image are ignored during processing and assigned NoData values
 /// in the outputs. The output cost accumulation raster is of the float data type and continuous data scale.
 ///
+/// # Reference
+/// Tobler, W. (1993). Three presentations on geographical analysis and modeling. Technical Report 93-1,
+/// National Center for Geographic Information and Analysis, University of California, Santa Barbara.
+///
 /// # See Also
-/// `CostAllocation`, `CostPathway`, `WeightedOverlay`
+/// `CostAllocation`, `CostPathway`, `LeastCostPath`, `WeightedOverlay`
 pub struct CostDistance {
     name: String,
     description: String,
@@ -102,6 +113,16 @@ impl CostDistance {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input DEM File (optional)".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Optional input DEM raster file, used for anisotropic slope costs."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -113,7 +134,8 @@ impl CostDistance {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=src.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=src.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=src.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif --dem=dem.tif", short_exe, name).replace("*", &sep);
 
         CostDistance {
             name: name,
@@ -163,6 +185,7 @@ impl WhiteboxTool for CostDistance {
         let mut cost_file = String::new();
         let mut accum_file = String::new();
         let mut backlink_file = String::new();
+        let mut dem_file = String::new();
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -204,6 +227,12 @@ impl WhiteboxTool for CostDistance {
                 } else {
                     args[i + 1].to_string()
                 };
+            } else if flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
             }
         }
 
@@ -230,6 +259,9 @@ impl WhiteboxTool for CostDistance {
         if !backlink_file.contains(&sep) && !backlink_file.contains("/") {
             backlink_file = format!("{}{}", working_directory, backlink_file);
         }
+        if !dem_file.is_empty() && !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
 
         if verbose {
             println!("Reading source data...")
@@ -239,17 +271,17 @@ impl WhiteboxTool for CostDistance {
         if verbose {
             println!("Reading cost data...")
         };
-        let cost = Raster::new(&cost_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let cost = align_raster_input(&source, Raster::new(&cost_file, "r")?)?;
 
-        // make sure the input files have the same size
-        if source.configs.rows != cost.configs.rows
-            || source.configs.columns != cost.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
+        let dem = if !dem_file.is_empty() {
+            if verbose {
+                println!("Reading DEM data...")
+            };
+            Some(align_raster_input(&source, Raster::new(&dem_file, "r")?)?)
+        } else {
+            None
+        };
 
         let start = Instant::now();
         let rows = source.configs.rows as isize;
@@ -313,6 +345,10 @@ impl WhiteboxTool for CostDistance {
         let dx = [1, 1, 1, 0, -1, -1, -1, 0];
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
         let backlink_dir = [16.0, 32.0, 64.0, 128.0, 1.0, 2.0, 4.0, 8.0];
+        // Tobler's hiking function, normalized to one on flat ground
+        let flat_ground_factor = (3.5f64 * 0.05).exp();
+        let (mut z1, mut z2): (f64, f64);
+        let mut slope_factor = 1f64;
         let mut solved: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
         while !minheap.is_empty() {
             let cell = minheap.pop().expect("Error during pop operation.");
@@ -328,7 +364,18 @@ impl WhiteboxTool for CostDistance {
                     row_n = row + dy[n];
                     if output.get_value(row_n, col_n) != nodata {
                         cost2 = cost.get_value(row_n, col_n);
-                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if let Some(ref dem) = dem {
+                            z1 = dem.get_value(row, col);
+                            z2 = dem.get_value(row_n, col_n);
+                            slope_factor = if z1 != dem.configs.nodata && z2 != dem.configs.nodata
+                            {
+                                (3.5 * ((z2 - z1) / dist[n] + 0.05).abs()).exp()
+                                    / flat_ground_factor
+                            } else {
+                                1f64
+                            };
+                        }
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n] * slope_factor;
                         if new_cost < output.get_value(row_n, col_n) {
                             if solved.get_value(row_n, col_n) == 0 {
                                 output.set_value(row_n, col_n, new_cost);
@@ -651,6 +698,9 @@ impl WhiteboxTool for CostDistance {
         ));
         output.add_metadata_entry(format!("Source raster file: {}", source_file));
         output.add_metadata_entry(format!("Cost raster: {}", cost_file));
+        if !dem_file.is_empty() {
+            output.add_metadata_entry(format!("DEM raster: {}", dem_file));
+        }
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
@@ -25,7 +25,7 @@ use std::path;
 /// NoData values in the input *back-link* image are assigned NoData values in the output image.
 ///
 /// # See Also
-/// `CostDistance`, `CostAllocation`
+/// `CostDistance`, `CostAllocation`, `LeastCostPath`
 pub struct CostPathway {
     name: String,
     description: String,
@@ -217,17 +217,8 @@ impl WhiteboxTool for CostPathway {
         if verbose {
             println!("Reading backlink data...")
         };
-        let backlink = Raster::new(&backlink_file, "r")?;
-
-        // make sure the input files have the same size
-        if destination.configs.rows != backlink.configs.rows
-            || destination.configs.columns != backlink.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
+        // make sure the inputs share the same grid and CRS
+        let backlink = align_raster_input(&destination, Raster::new(&backlink_file, "r")?)?;
 
         let start = Instant::now();
         let rows = destination.configs.rows as isize;
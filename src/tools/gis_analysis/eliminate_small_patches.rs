@@ -0,0 +1,491 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to generalize a categorical raster, such as a classified image, by
+/// eliminating patches that are smaller than a user-specified size threshold (`--threshold`), in
+/// grid cells. A patch is a contiguous group of grid cells with the same class value. Each small
+/// patch is merged into its dominant neighbouring patch, i.e. the adjacent patch with which it shares
+/// the longest boundary, with ties broken in favour of the larger neighbour. Patches are eliminated
+/// in order of increasing size, and a patch that grows through the merging of smaller neighbours is
+/// re-evaluated using its new size, such that no patch smaller than the threshold remains in the output
+/// unless it is completely surrounded by NoData (or background) cells. Patch connectivity can optionally
+/// include diagonally neighbouring cells if the `--diag` flag is specified. Use the *treat zeros as
+/// background cells* option (`--zero_back`) if zero-valued cells should neither be eliminated nor
+/// absorb neighbouring small patches.
+///
+/// This tool is commonly used to remove the 'salt-and-pepper' noise that is characteristic of per-pixel
+/// image classifications. Unlike `MajorityFilter`, it does not alter the boundaries of patches that are
+/// larger than the size threshold.
+///
+/// # See Also
+/// `Clump`, `MajorityFilter`
+pub struct EliminateSmallPatches {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl EliminateSmallPatches {
+    pub fn new() -> EliminateSmallPatches {
+        // public constructor
+        let name = "EliminateSmallPatches".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Merges patches smaller than a size threshold into their dominant neighbouring patch."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input categorical raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Patch Size (grid cells)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Minimum patch size, in grid cells; smaller patches are eliminated."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Include diagonal connections?".to_owned(),
+            flags: vec!["--diag".to_owned()],
+            description: "Flag indicating whether diagonal connections should be considered."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Treat zero values as background?".to_owned(),
+            flags: vec!["--zero_back".to_owned()],
+            description: "Flag indicating whether zero values should be treated as a background."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=classes.tif -o=output.tif --threshold=25 --diag",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        EliminateSmallPatches {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for EliminateSmallPatches {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut threshold = 0usize;
+        let mut diag = false;
+        let mut zero_back = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize
+                };
+            } else if flag_val == "-diag" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    diag = true;
+                }
+            } else if flag_val == "-zero_back" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    zero_back = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if threshold < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The minimum patch size threshold must be at least two grid cells.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let nodata = input.configs.nodata;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let num_cells = (rows * columns) as usize;
+
+        let mut dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let mut dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let mut num_neighbours = 8;
+        if !diag {
+            dx = [0, 1, 0, -1, 0, 0, 0, 0];
+            dy = [-1, 0, 1, 0, 0, 0, 0, 0];
+            num_neighbours = 4;
+        }
+        let back_val = if zero_back { 0f64 } else { f64::NEG_INFINITY };
+
+        // label the patches
+        const UNLABELLED: usize = usize::max_value();
+        let mut labels = vec![UNLABELLED; num_cells];
+        let mut patch_value: Vec<f64> = vec![];
+        let mut patch_cells: Vec<Vec<usize>> = vec![];
+        let mut stack = vec![];
+        let (mut z, mut zn): (f64, f64);
+        let (mut r, mut c, mut rn, mut cn): (isize, isize, isize, isize);
+        let mut idx: usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                idx = (row * columns + col) as usize;
+                z = input.get_value(row, col);
+                if z != nodata && z != back_val && labels[idx] == UNLABELLED {
+                    let label = patch_value.len();
+                    let mut cells = vec![idx];
+                    labels[idx] = label;
+                    stack.push((row, col));
+                    while let Some((r, c)) = stack.pop() {
+                        for n in 0..num_neighbours {
+                            rn = r + dy[n];
+                            cn = c + dx[n];
+                            if rn >= 0 && rn < rows && cn >= 0 && cn < columns {
+                                idx = (rn * columns + cn) as usize;
+                                if labels[idx] == UNLABELLED && input.get_value(rn, cn) == z {
+                                    labels[idx] = label;
+                                    cells.push(idx);
+                                    stack.push((rn, cn));
+                                }
+                            }
+                        }
+                    }
+                    patch_value.push(z);
+                    patch_cells.push(cells);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Labelling patches: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let num_patches = patch_value.len();
+        let mut parent: Vec<usize> = (0..num_patches).collect();
+        let mut heap = BinaryHeap::new();
+        for p in 0..num_patches {
+            if patch_cells[p].len() < threshold {
+                heap.push(Patch {
+                    id: p,
+                    size: patch_cells[p].len(),
+                });
+            }
+        }
+        let num_small = heap.len();
+
+        // merge the small patches into their dominant neighbours, smallest first
+        let mut num_eliminated = 0usize;
+        let mut processed = 0usize;
+        while let Some(patch) = heap.pop() {
+            let p = patch.id;
+            if parent[p] != p || patch_cells[p].len() != patch.size {
+                continue; // stale entry
+            }
+            processed += 1;
+
+            // count the length of the boundary shared with each neighbouring patch
+            let mut shared: HashMap<usize, usize> = HashMap::new();
+            for &cell in &patch_cells[p] {
+                r = cell as isize / columns;
+                c = cell as isize % columns;
+                for n in 0..num_neighbours {
+                    rn = r + dy[n];
+                    cn = c + dx[n];
+                    if rn >= 0 && rn < rows && cn >= 0 && cn < columns {
+                        let label = labels[(rn * columns + cn) as usize];
+                        if label != UNLABELLED {
+                            let q = find_root(&mut parent, label);
+                            if q != p {
+                                *shared.entry(q).or_insert(0) += 1;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let mut target = UNLABELLED;
+            let mut max_shared = 0usize;
+            for (&q, &count) in &shared {
+                if count > max_shared
+                    || (count == max_shared
+                        && (patch_cells[q].len() > patch_cells[target].len()
+                            || (patch_cells[q].len() == patch_cells[target].len() && q < target)))
+                {
+                    max_shared = count;
+                    target = q;
+                }
+            }
+            if target == UNLABELLED {
+                continue; // isolated by nodata or background cells
+            }
+
+            parent[p] = target;
+            let cells = std::mem::replace(&mut patch_cells[p], vec![]);
+            patch_cells[target].extend(cells);
+            num_eliminated += 1;
+            if patch_cells[target].len() < threshold {
+                heap.push(Patch {
+                    id: target,
+                    size: patch_cells[target].len(),
+                });
+            }
+
+            if verbose {
+                progress = (100.0_f64 * processed as f64 / num_small.max(1) as f64) as usize;
+                if progress != old_progress && progress <= 100 {
+                    println!("Eliminating patches: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        for row in 0..rows {
+            for col in 0..columns {
+                idx = (row * columns + col) as usize;
+                if labels[idx] != UNLABELLED {
+                    let q = find_root(&mut parent, labels[idx]);
+                    output.set_value(row, col, patch_value[q]);
+                } else {
+                    zn = input.get_value(row, col);
+                    output.set_value(row, col, zn);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Creating output: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!(
+                "Number of patches: {}; eliminated: {}",
+                num_patches, num_eliminated
+            );
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Size threshold: {}", threshold));
+        output.add_metadata_entry(format!("Diagonal connectivity: {}", diag));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the patch into which a patch has been merged, compressing the path.
+fn find_root(parent: &mut Vec<usize>, id: usize) -> usize {
+    let mut root = id;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut i = id;
+    while parent[i] != root {
+        let next = parent[i];
+        parent[i] = root;
+        i = next;
+    }
+    root
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct Patch {
+    id: usize,
+    size: usize,
+}
+
+impl PartialOrd for Patch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Patch {
+    // reversed, such that the BinaryHeap pops the smallest patch first
+    fn cmp(&self, other: &Patch) -> Ordering {
+        other
+            .size
+            .cmp(&self.size)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
@@ -34,7 +34,7 @@ const EPSILON: f64 = std::f64::EPSILON;
 /// vector features, or target features.
 ///
 /// # See Also
-/// `Clip`
+/// `Clip`, `Difference`, `Intersect`, `Union`, `SymmetricalDifference`
 pub struct Erase {
     name: String,
     description: String,
@@ -26,7 +26,7 @@ use std::path;
 /// neighborhood, *Computer Vision and Image Understanding*, 93: 195-205.
 ///
 /// # See Also
-/// `EuclideanDistance`, `VoronoiDiagram`, `CostAllocation`
+/// `EuclideanDistance`, `VoronoiDiagram`, `CostAllocation`, `GeodesicDistance`
 pub struct EuclideanAllocation {
     name: String,
     description: String,
@@ -34,7 +34,7 @@ use std::path;
 /// neighborhood, *Computer Vision and Image Understanding*, 93: 195-205.
 ///
 /// # See Also
-/// `EuclideanAllocation`, `CostDistance`
+/// `EuclideanAllocation`, `CostDistance`, `GeodesicDistance`
 pub struct EuclideanDistance {
     name: String,
     description: String,
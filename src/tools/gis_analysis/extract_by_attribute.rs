@@ -0,0 +1,293 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool extracts the features of a vector file that satisfy a SQL-like query statement
+/// evaluated against each feature's attributes and geometry, e.g.
+/// `LANDUSE = 'forest' AND $area > 10000`. Features for which the statement evaluates to
+/// true are copied, along with their attributes, into the output vector, which has the same
+/// shape type as the input. Features for which the statement is false or null (e.g. because
+/// a referenced field is empty) are excluded, unless the `--invert` flag is specified, in
+/// which case only these features are output.
+///
+/// Statements may refer to fields by name, using double quotes or square brackets for names
+/// that contain spaces, and to text values in single quotes. They may use the arithmetic
+/// operators `+ - * / % ^`, the concatenation operator `||`, the comparison operators
+/// `= != <> < <= > >=`, `AND`, `OR`, `NOT`, `LIKE` and `ILIKE` (case-insensitive) with the
+/// `%` and `_` wildcards, `IN (...)`, `BETWEEN ... AND ...`, and `IS [NOT] NULL`. The geometry
+/// properties `$area`, `$perimeter`, `$length`, `$x`, `$y`, `$xmin`, `$xmax`, `$ymin`, `$ymax`,
+/// `$num_points`, `$num_parts`, and `$fid` and the functions listed in the `FieldCalculator`
+/// documentation are also available.
+///
+/// # See Also
+/// `FieldCalculator`, `Clip`
+pub struct ExtractByAttribute {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractByAttribute {
+    pub fn new() -> ExtractByAttribute {
+        // public constructor
+        let name = "ExtractByAttribute".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Extracts the features in a vector file that satisfy an attribute query statement."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statement".to_owned(),
+            flags: vec!["--statement".to_owned()],
+            description: "Query statement, e.g. \"LANDUSE = 'forest' AND $area > 10000\"."
+                .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Extract features that do not satisfy the statement?".to_owned(),
+            flags: vec!["--invert".to_owned()],
+            description: "Extract the features for which the statement is false or null."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=lakes.shp --statement=\"AREA > 5000 AND NAME LIKE 'Lake%'\" -o=out.shp",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ExtractByAttribute {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractByAttribute {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut statement = String::new();
+        let mut invert = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-statement" {
+                // the statement may itself contain quotes and equals signs
+                statement = if keyval {
+                    args[i].splitn(2, '=').nth(1).unwrap_or("").to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                let s = statement.trim();
+                if s.len() > 1 && s.starts_with('"') && s.ends_with('"') {
+                    statement = s[1..s.len() - 1].to_string();
+                }
+            } else if flag_val == "-invert" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    invert = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if statement.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A query statement must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        let expression = Expression::parse(&statement, &input.attributes)?;
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let atts = input.attributes.get_record(record_num);
+            if expression.evaluate(&atts, &record, record_num).is_true() != invert {
+                output.add_record(record.clone());
+                output.attributes.add_record(atts, false);
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No features satisfied the query statement; the output file was not created.",
+            ));
+        }
+
+        if verbose {
+            println!(
+                "{} of {} features were extracted.",
+                output.num_records, input.num_records
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 17/06/2018
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -20,11 +20,23 @@ use std::path;
 /// to standard output (*stdout*). Attribute fields will be added to the table of the points file, with field
 /// names, *VALUE1*, *VALUE2*, *VALUE3*, etc. each corresponding to the order of input rasters.
 ///
+/// Multi-band inputs, i.e. red-green-blue (RGB) colour composite images, are split into their
+/// component bands and one attribute field is added for each band, with names *VALUE1_B1*,
+/// *VALUE1_B2*, *VALUE1_B3*, etc.
+///
+/// By default, the value of the grid cell containing each point is extracted. Alternatively, the
+/// interpolation method (`--method`) may be set to bilinear interpolation ('bilinear') or cubic
+/// convolution ('cc'), which estimate the value at the exact point location from the four or sixteen
+/// nearest cell centres respectively. Interpolation is generally preferable when building datasets
+/// of continuous variables, such as DEM derivatives, for regression modelling. NoData cells are
+/// excluded from the bilinear interpolation, and cubic convolution falls back on bilinear
+/// interpolation near NoData cells.
+///
 /// If you need to plot a chart of values from a raster stack at a set of points, the `ImageStackProfile` may be
 /// more suitable for this application.
 ///
 /// # See Also
-/// `ImageStackProfile`, `FindLowestOrHighestPoints`
+/// `ImageStackProfile`, `FindLowestOrHighestPoints`, `SplitColourComposite`
 pub struct ExtractRasterValuesAtPoints {
     name: String,
     description: String,
@@ -61,6 +73,19 @@ impl ExtractRasterValuesAtPoints {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Interpolation method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution)".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "nn".to_owned(),
+                "bilinear".to_owned(),
+                "cc".to_owned(),
+            ]),
+            default_value: Some("nn".to_owned()),
+            optional: true,
+        });
+
         parameters.push(ToolParameter {
             name: "Output text?".to_owned(),
             flags: vec!["--out_text".to_owned()],
@@ -83,7 +108,8 @@ impl ExtractRasterValuesAtPoints {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='image1.tif;image2.tif;image3.tif' -points=points.shp", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='image1.tif;image2.tif;image3.tif' -points=points.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='slope.tif;wetness_index.tif' -points=samples.shp --method=bilinear", short_exe, name).replace("*", &sep);
 
         ExtractRasterValuesAtPoints {
             name: name,
@@ -139,6 +165,7 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
         let mut input_files = String::new();
         let mut points_file = String::new();
         let mut output_text = false;
+        let mut method = InterpolationMethod::NearestNeighbour;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -168,6 +195,21 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
                 } else {
                     args[i + 1].to_string()
                 };
+            } else if flag_val == "-method" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                method = match InterpolationMethod::from_name(&val) {
+                    Some(m) => m,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unrecognized interpolation method '{}'.", val),
+                        ))
+                    }
+                };
             } else if flag_val.contains("-out_text") {
                 if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
                     output_text = true;
@@ -212,22 +254,13 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
         let (mut row, mut col): (isize, isize);
         let mut x_vals = Vec::with_capacity(num_records);
         let mut y_vals = Vec::with_capacity(num_records);
-        let mut raster_values = vec![vec![0f64; num_files]; num_records];
+        let mut raster_values = vec![vec![]; num_records];
         for record_num in 0..num_records {
             let record = points.get_record(record_num);
             y_vals.push(record.points[0].y);
             x_vals.push(record.points[0].x);
         }
 
-        // add the attributes for each raster
-        for i in 0..num_files {
-            if !v[i].trim().is_empty() {
-                let val =
-                    AttributeField::new(&format!("VALUE{}", i + 1), FieldDataType::Real, 12u8, 6u8);
-                points.attributes.add_field(&val);
-            }
-        }
-
         let mut z: f64;
         let mut i = 1;
         for value in v {
@@ -242,18 +275,40 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
                 }
                 let input = Raster::new(&input_file, "r")?;
 
+                // add an attribute for each band of the raster
+                let num_bands = input.num_bands();
+                let field_names = if num_bands == 1 {
+                    vec![format!("VALUE{}", i)]
+                } else {
+                    (1..=num_bands)
+                        .map(|b| format!("VALUE{}_B{}", i, b))
+                        .collect::<Vec<String>>()
+                };
+                for name in &field_names {
+                    let val = AttributeField::new(name, FieldDataType::Real, 12u8, 6u8);
+                    points.attributes.add_field(&val);
+                }
+
                 for record_num in 0..num_records {
-                    row = input.get_row_from_y(y_vals[record_num]);
-                    col = input.get_column_from_x(x_vals[record_num]);
-                    z = input.get_value(row, col);
-                    points.attributes.set_value(
-                        record_num,
-                        &format!("VALUE{}", i),
-                        FieldData::Real(z),
-                    );
-
-                    if output_text {
-                        raster_values[record_num][i - 1] = z;
+                    for band in 0..num_bands {
+                        z = if method == InterpolationMethod::NearestNeighbour {
+                            row = input.get_row_from_y(y_vals[record_num]);
+                            col = input.get_column_from_x(x_vals[record_num]);
+                            input.get_band_value(row, col, band)
+                        } else {
+                            let (r, c) =
+                                input.get_grid_position(x_vals[record_num], y_vals[record_num]);
+                            input.get_interpolated_band_value(r, c, band, method)
+                        };
+                        points.attributes.set_value(
+                            record_num,
+                            &field_names[band],
+                            FieldData::Real(z),
+                        );
+
+                        if output_text {
+                            raster_values[record_num].push(z);
+                        }
                     }
                 }
 
@@ -0,0 +1,602 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+// the fast marching states of the grid cells
+const FAR: i8 = 0;
+const TRIAL: i8 = 1;
+const ACCEPTED: i8 = 2;
+const OBSTACLE: i8 = -1;
+
+/// This tool estimates the geodesic distance between each grid cell and the nearest 'target cell'
+/// in the input image (`--input`), i.e. the length of the shortest path between them that does not
+/// cross any impassable cells. Target cells are all positive, non-NoData grid cells. The passable
+/// areas are specified by an optional mask raster (`--mask`), in which positive values are passable
+/// and zero and NoData values are impassable obstacles, e.g. buildings, water bodies, or cliffs.
+/// Without a mask, all non-NoData cells are passable and the geodesic distance approximates the
+/// Euclidean distance. Distance in the output image (`--output`) is measured in the same units as the
+/// horizontal units of the input image. Impassable cells, and cells that cannot be reached from any
+/// target cell, are assigned NoData.
+///
+/// The tool can simultaneously output an allocation raster (`--out_allocation`), containing the value
+/// of the target cell that is nearest to each cell along a passable path, and a direction raster
+/// (`--out_direction`), containing the azimuth (in degrees clockwise from north, with 360 for north)
+/// of the direction of travel from each cell towards its nearest target cell along the path. Target
+/// cells have a direction of zero.
+///
+/// # Algorithm Description
+/// The distances are calculated using the fast marching method (Sethian, 1996), which solves the
+/// eikonal equation |∇*T*| = 1 by propagating a front outwards from the target cells in order of
+/// increasing distance. Unlike the chamfer distances of cost-distance analysis, which measure paths along
+/// the eight grid directions, the fast marching method measures paths in any direction and is therefore
+/// free of the octagonal distortion of grid-based path lengths. The second-order upwind scheme of
+/// Sethian (1999) is used wherever the front has already passed through two cells in a grid direction,
+/// and the first-order scheme elsewhere. Paths may not pass diagonally between two impassable cells.
+///
+/// # Reference
+/// Sethian, J. A. (1996). A fast marching level set method for monotonically advancing fronts.
+/// *Proceedings of the National Academy of Sciences*, 93(4), 1591-1595.
+///
+/// Sethian, J. A. (1999). *Level Set Methods and Fast Marching Methods*. Cambridge University Press.
+///
+/// # See Also
+/// `EuclideanDistance`, `EuclideanAllocation`, `CostDistance`, `CostAllocation`
+pub struct GeodesicDistance {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GeodesicDistance {
+    pub fn new() -> GeodesicDistance {
+        // public constructor
+        let name = "GeodesicDistance".to_string();
+        let toolbox = "GIS Analysis/Distance Tools".to_string();
+        let description =
+            "Calculates the geodesic distance, allocation, and direction to target cells around impassable obstacles using the fast marching method.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file of target cells.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Passable Mask File (optional)".to_owned(),
+            flags: vec!["--mask".to_owned()],
+            description: "Optional input raster in which positive values are passable and zero or NoData values impassable.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Distance File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output geodesic distance raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Allocation File (optional)".to_owned(),
+            flags: vec!["--out_allocation".to_owned()],
+            description: "Optional output allocation raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Direction File (optional)".to_owned(),
+            flags: vec!["--out_direction".to_owned()],
+            description:
+                "Optional output direction (azimuth towards the nearest target) raster file."
+                    .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=shelters.tif --mask=walkable.tif -o=distance.tif --out_allocation=allocation.tif --out_direction=direction.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        GeodesicDistance {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GeodesicDistance {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut mask_file = String::new();
+        let mut output_file = String::new();
+        let mut allocation_file = String::new();
+        let mut direction_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-mask" {
+                mask_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_allocation" {
+                allocation_file = value;
+            } else if flag_val == "-out_direction" {
+                direction_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !mask_file.is_empty() && !mask_file.contains(&sep) && !mask_file.contains("/") {
+            mask_file = format!("{}{}", working_directory, mask_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !allocation_file.is_empty()
+            && !allocation_file.contains(&sep)
+            && !allocation_file.contains("/")
+        {
+            allocation_file = format!("{}{}", working_directory, allocation_file);
+        }
+        if !direction_file.is_empty()
+            && !direction_file.contains(&sep)
+            && !direction_file.contains("/")
+        {
+            direction_file = format!("{}{}", working_directory, direction_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let mask = if !mask_file.is_empty() {
+            Some(align_raster_input(&input, Raster::new(&mask_file, "r")?)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let num_cells = (rows * columns) as usize;
+        let nodata = input.configs.nodata;
+        let hx = input.configs.resolution_x;
+        let hy = input.configs.resolution_y;
+
+        let mut state: Array2D<i8> = Array2D::new(rows, columns, FAR, OBSTACLE)?;
+        let mut dist: Array2D<f64> = Array2D::new(rows, columns, f64::INFINITY, f64::INFINITY)?;
+        let mut allocation: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut heap = BinaryHeap::with_capacity(num_cells);
+        let mut num_solved = 0usize;
+        let mut num_passable = 0usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                let passable = z != nodata
+                    && match mask {
+                        Some(ref m) => {
+                            let v = m.get_value(row, col);
+                            v != m.configs.nodata && v > 0f64
+                        }
+                        None => true,
+                    };
+                if !passable {
+                    state.set_value(row, col, OBSTACLE);
+                    continue;
+                }
+                num_passable += 1;
+                if z > 0f64 {
+                    state.set_value(row, col, TRIAL);
+                    dist.set_value(row, col, 0f64);
+                    allocation.set_value(row, col, z);
+                    heap.push(GridCell {
+                        row: row,
+                        column: col,
+                        priority: 0f64,
+                    });
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Initializing: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if heap.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input raster does not contain any passable target cells.",
+            ));
+        }
+
+        // the neighbours of the four grid directions, in (dy, dx) order
+        let neighbours = [(0isize, 1isize), (0, -1), (1, 0), (-1, 0)];
+        while let Some(cell) = heap.pop() {
+            let (row, col) = (cell.row, cell.column);
+            if state.get_value(row, col) == ACCEPTED || cell.priority > dist.get_value(row, col) {
+                continue; // a stale entry
+            }
+            state.set_value(row, col, ACCEPTED);
+            num_solved += 1;
+            for (dy, dx) in neighbours.iter() {
+                let (r, c) = (row + dy, col + dx);
+                let s = state.get_value(r, c);
+                if s == OBSTACLE || s == ACCEPTED {
+                    continue;
+                }
+                let (t, source) = solve_eikonal(&dist, &state, &allocation, r, c, hx, hy);
+                if t < dist.get_value(r, c) {
+                    dist.set_value(r, c, t);
+                    allocation.set_value(r, c, source);
+                    state.set_value(r, c, TRIAL);
+                    heap.push(GridCell {
+                        row: r,
+                        column: c,
+                        priority: t,
+                    });
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * num_solved as f64 / num_passable.max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        for row in 0..rows {
+            let mut data = vec![out_nodata; columns as usize];
+            for col in 0..columns {
+                if state.get_value(row, col) == ACCEPTED {
+                    data[col as usize] = dist.get_value(row, col);
+                }
+            }
+            output.set_row_data(row, data);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        if !mask_file.is_empty() {
+            output.add_metadata_entry(format!("Mask file: {}", mask_file));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        drop(output);
+
+        if !allocation_file.is_empty() {
+            let mut output = Raster::initialize_using_file(&allocation_file, &input);
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            output.configs.palette = "qual.plt".to_string();
+            for row in 0..rows {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    if state.get_value(row, col) == ACCEPTED {
+                        data[col as usize] = allocation.get_value(row, col);
+                    }
+                }
+                output.set_row_data(row, data);
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Allocation file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !direction_file.is_empty() {
+            let mut output = Raster::initialize_using_file(&direction_file, &input);
+            output.configs.data_type = DataType::F32;
+            output.configs.nodata = out_nodata;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = "circular_bw.plt".to_string();
+            let accepted_dist = |r: isize, c: isize| -> f64 {
+                if state.get_value(r, c) == ACCEPTED {
+                    dist.get_value(r, c)
+                } else {
+                    f64::INFINITY
+                }
+            };
+            for row in 0..rows {
+                let mut data = vec![out_nodata; columns as usize];
+                for col in 0..columns {
+                    if state.get_value(row, col) != ACCEPTED {
+                        continue;
+                    }
+                    let t = dist.get_value(row, col);
+                    if t == 0f64 {
+                        data[col as usize] = 0f64;
+                        continue;
+                    }
+                    // the direction of travel is down the upwind gradient of the distance
+                    let (east, west) = (accepted_dist(row, col + 1), accepted_dist(row, col - 1));
+                    let (north, south) = (accepted_dist(row - 1, col), accepted_dist(row + 1, col));
+                    let x = if east.min(west) < t {
+                        if east < west {
+                            (t - east) / hx
+                        } else {
+                            -(t - west) / hx
+                        }
+                    } else {
+                        0f64
+                    };
+                    let y = if north.min(south) < t {
+                        if north < south {
+                            (t - north) / hy
+                        } else {
+                            -(t - south) / hy
+                        }
+                    } else {
+                        0f64
+                    };
+                    let mut azimuth = x.atan2(y).to_degrees();
+                    if azimuth <= 0f64 {
+                        azimuth += 360f64;
+                    }
+                    data[col as usize] = azimuth;
+                }
+                output.set_row_data(row, data);
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Direction file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Solves the eikonal equation at a cell from its accepted neighbours, returning the arrival
+/// time (distance) and the allocation of the neighbour from which the front arrived. The
+/// second-order upwind difference is used in a grid direction when the two cells upwind of the
+/// cell in that direction are accepted and monotonic, and the first-order difference otherwise.
+fn solve_eikonal(
+    dist: &Array2D<f64>,
+    state: &Array2D<i8>,
+    allocation: &Array2D<f64>,
+    row: isize,
+    col: isize,
+    hx: f64,
+    hy: f64,
+) -> (f64, f64) {
+    let accepted = |r: isize, c: isize| -> f64 {
+        if state.get_value(r, c) == ACCEPTED {
+            dist.get_value(r, c)
+        } else {
+            f64::INFINITY
+        }
+    };
+    // the (alpha, beta, upwind value, upwind cell) terms of each grid direction
+    let mut terms: Vec<(f64, f64, f64, (isize, isize))> = Vec::with_capacity(2);
+    for &(dy, dx, h) in [(0isize, 1isize, hx), (1, 0, hy)].iter() {
+        let t_plus = accepted(row + dy, col + dx);
+        let t_minus = accepted(row - dy, col - dx);
+        let (t1, sign) = if t_plus <= t_minus {
+            (t_plus, 1isize)
+        } else {
+            (t_minus, -1isize)
+        };
+        if !t1.is_finite() {
+            continue;
+        }
+        let cell = (row + sign * dy, col + sign * dx);
+        let t2 = accepted(row + 2 * sign * dy, col + 2 * sign * dx);
+        if t2 <= t1 {
+            terms.push((1.5 / h, (4f64 * t1 - t2) / 3f64, t1, cell));
+        } else {
+            terms.push((1f64 / h, t1, t1, cell));
+        }
+    }
+    // the allocation is that of the nearest upwind neighbour
+    let nearest = terms
+        .iter()
+        .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+        .map(|term| term.3)
+        .unwrap_or((row, col));
+    let source = allocation.get_value(nearest.0, nearest.1);
+
+    let one_sided = |terms: &[(f64, f64, f64, (isize, isize))]| -> f64 {
+        terms
+            .iter()
+            .map(|(alpha, beta, _, _)| beta + 1f64 / alpha)
+            .fold(f64::INFINITY, f64::min)
+    };
+    if terms.len() < 2 {
+        return (one_sided(&terms), source);
+    }
+    let (mut a, mut b, mut c) = (0f64, 0f64, -1f64);
+    for (alpha, beta, _, _) in &terms {
+        let a2 = alpha * alpha;
+        a += a2;
+        b -= 2f64 * a2 * beta;
+        c += a2 * beta * beta;
+    }
+    let disc = b * b - 4f64 * a * c;
+    if disc >= 0f64 {
+        let t = (-b + disc.sqrt()) / (2f64 * a);
+        if terms.iter().all(|term| t >= term.2) {
+            return (t, source);
+        }
+    }
+    (one_sided(&terms), source)
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
@@ -0,0 +1,399 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to map the least-cost pathway connecting each destination point
+/// in a cost-distance analysis to a source cell as vector polylines. It is the vector
+/// equivalent of the `CostPathway` tool. The user must specify the names of the input
+/// *destination* points vector file (`--destination`), the *back-link* raster file
+/// (`--backlink`), and the output polyline vector file (`--output`). A *back-link* raster
+/// file can be created using the `CostDistance` tool and describes the connectivity between
+/// neighbouring cells on the accumulated cost surface. Optionally, the accumulated cost
+/// raster (`--accum`) that was output alongside the back-link raster may also be specified,
+/// in which case the accumulated cost at each destination is stored in the output attribute
+/// table (`COST`).
+///
+/// Each output polyline begins at the centre of the source cell that is connected to the
+/// destination by the minimum accumulated-cost path and ends at the destination point. The
+/// output attribute table also contains the record number of the destination (`DEST_ID`) and
+/// the length of the path (`LENGTH`), in the horizontal units of the back-link raster.
+/// Destinations that are located outside of the back-link raster, or on NoData cells, are
+/// ignored.
+///
+/// # See Also
+/// `CostDistance`, `CostPathway`, `CostAllocation`
+pub struct LeastCostPath {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LeastCostPath {
+    pub fn new() -> LeastCostPath {
+        // public constructor
+        let name = "LeastCostPath".to_string();
+        let toolbox = "GIS Analysis/Distance Tools".to_string();
+        let description =
+            "Maps the least-cost pathways from a set of destination points to the source cells as vector polylines."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Destination Points File".to_owned(),
+            flags: vec!["--destination".to_owned()],
+            description: "Input destination points vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Backlink File".to_owned(),
+            flags: vec!["--backlink".to_owned()],
+            description: "Input backlink raster file generated by the cost-distance tool."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Cost Accumulation File (optional)".to_owned(),
+            flags: vec!["--accum".to_owned()],
+            description:
+                "Optional input cost accumulation raster file generated by the cost-distance tool."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output least-cost path vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --destination=dst.shp --backlink=backlink.tif --accum=accum.tif --output=cost_path.shp", short_exe, name).replace("*", &sep);
+
+        LeastCostPath {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LeastCostPath {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut destination_file = String::new();
+        let mut backlink_file = String::new();
+        let mut accum_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-destination" {
+                destination_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-backlink" {
+                backlink_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-accum" {
+                accum_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !destination_file.contains(&sep) && !destination_file.contains("/") {
+            destination_file = format!("{}{}", working_directory, destination_file);
+        }
+        if !backlink_file.contains(&sep) && !backlink_file.contains("/") {
+            backlink_file = format!("{}{}", working_directory, backlink_file);
+        }
+        if !accum_file.is_empty() && !accum_file.contains(&sep) && !accum_file.contains("/") {
+            accum_file = format!("{}{}", working_directory, accum_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let destination = Shapefile::read(&destination_file)?;
+        if destination.header.shape_type.base_shape_type() != ShapeType::Point {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input destination vector data must be of point base shape type.",
+            ));
+        }
+
+        let backlink = Raster::new(&backlink_file, "r")?;
+
+        let accum = if !accum_file.is_empty() {
+            // make sure the inputs share the same grid and CRS
+            Some(align_raster_input(&backlink, Raster::new(&accum_file, "r")?)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+        let rows = backlink.configs.rows as isize;
+        let columns = backlink.configs.columns as isize;
+        let nodata = backlink.configs.nodata;
+
+        // create output file
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = backlink.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("DEST_ID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "LENGTH",
+            FieldDataType::Real,
+            12u8,
+            4u8,
+        ));
+        if accum.is_some() {
+            output.attributes.add_field(&AttributeField::new(
+                "COST",
+                FieldDataType::Real,
+                12u8,
+                4u8,
+            ));
+        }
+
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let mut pntr_matches: [usize; 129] = [0usize; 129];
+        // This maps Whitebox-style D8 pointer values
+        // onto the cell offsets in dx and dy.
+        pntr_matches[1] = 0usize;
+        pntr_matches[2] = 1usize;
+        pntr_matches[4] = 2usize;
+        pntr_matches[8] = 3usize;
+        pntr_matches[16] = 4usize;
+        pntr_matches[32] = 5usize;
+        pntr_matches[64] = 6usize;
+        pntr_matches[128] = 7usize;
+        let max_steps = rows * columns;
+        let (mut row, mut col): (isize, isize);
+        let mut dir: f64;
+        let mut fid = 1i32;
+        for record_num in 0..destination.num_records {
+            let record = destination.get_record(record_num);
+            for p in &record.points {
+                row = backlink.get_row_from_y(p.y);
+                col = backlink.get_column_from_x(p.x);
+                if row < 0 || row >= rows || col < 0 || col >= columns {
+                    continue;
+                }
+                if backlink.get_value(row, col) == nodata {
+                    continue;
+                }
+
+                let cost = match accum {
+                    Some(ref accum) => Some(accum.get_value(row, col)),
+                    None => None,
+                };
+
+                // trace the back-links from the destination to the source
+                let mut points = vec![Point2D::new(p.x, p.y)];
+                let mut num_steps = 0;
+                loop {
+                    dir = backlink.get_value(row, col);
+                    if dir == nodata || dir <= 0.0 || dir > 128.0 {
+                        break;
+                    }
+                    col += dx[pntr_matches[dir as usize]];
+                    row += dy[pntr_matches[dir as usize]];
+                    points.push(Point2D::new(
+                        backlink.get_x_from_column(col),
+                        backlink.get_y_from_row(row),
+                    ));
+                    num_steps += 1;
+                    if num_steps > max_steps {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "The back-link raster contains a loop and is likely not the output of CostDistance.",
+                        ));
+                    }
+                }
+                if points.len() < 2 {
+                    // the destination is located in a source cell
+                    points.push(Point2D::new(
+                        backlink.get_x_from_column(col),
+                        backlink.get_y_from_row(row),
+                    ));
+                }
+                points.reverse();
+
+                let mut length = 0f64;
+                for i in 1..points.len() {
+                    length += points[i].distance(&points[i - 1]);
+                }
+
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&points);
+                output.add_record(sfg);
+                let mut atts = vec![
+                    FieldData::Int(fid),
+                    FieldData::Int(record_num as i32 + 1),
+                    FieldData::Real(length),
+                ];
+                if let Some(c) = cost {
+                    atts.push(if c != accum.as_ref().unwrap().configs.nodata {
+                        FieldData::Real(c)
+                    } else {
+                        FieldData::Null
+                    });
+                }
+                output.attributes.add_record(atts, false);
+                fid += 1;
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / destination.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The skeletonization routines shared by the SkeletonizeRaster and PolygonMedialAxis
+tools. A binary grid (foreground 1, background 0) is thinned to a one-cell-wide skeleton
+using the structuring elements of the LineThinning tool, its spurs are pruned, and it is
+traced into lines between its end points and junctions. The width of the feature at each
+skeleton cell is twice the Euclidean distance to the nearest background cell, less one cell.
+Grids use zero as their NoData value, such that cells beyond the grid edges are background.
+*/
+
+use crate::structures::Array2D;
+use std::collections::HashSet;
+use std::f64;
+use std::io::Error;
+
+const DX: [isize; 8] = [1, 1, 1, 0, -1, -1, -1, 0];
+const DY: [isize; 8] = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+/// Thins the foreground (1) of a binary grid to a one-cell-wide skeleton, prunes spurs that are
+/// shorter than `min_spur` (in map units), or, if `min_spur` is zero, shorter than the feature's
+/// width at the junction from which they branch, and returns the skeleton and width grids.
+pub fn skeletonize(
+    foreground: &Array2D<i8>,
+    cell_size_x: f64,
+    cell_size_y: f64,
+    min_spur: f64,
+) -> Result<(Array2D<i8>, Array2D<f64>), Error> {
+    let width = feature_width(foreground, cell_size_x, cell_size_y)?;
+    let mut skeleton = foreground.duplicate();
+    thin(&mut skeleton);
+    prune_spurs(&mut skeleton, &width, cell_size_x, cell_size_y, min_spur);
+    thin(&mut skeleton);
+    Ok((skeleton, width))
+}
+
+/// Traces a skeleton into lines of (row, column) cells. Lines run between the skeleton's nodes,
+/// i.e. its end points and junctions, and closed loops without nodes are traced as closed lines.
+pub fn trace_lines(skeleton: &Array2D<i8>) -> Vec<Vec<(isize, isize)>> {
+    let rows = skeleton.rows;
+    let columns = skeleton.columns;
+    let is_node = |row: isize, col: isize| neighbours(skeleton, row, col).len() != 2;
+    let mut visited: HashSet<(isize, isize)> = HashSet::new();
+    let mut node_links: HashSet<((isize, isize), (isize, isize))> = HashSet::new();
+    let mut lines = vec![];
+    for row in 0..rows {
+        for col in 0..columns {
+            if skeleton.get_value(row, col) != 1 || !is_node(row, col) {
+                continue;
+            }
+            let nbs = neighbours(skeleton, row, col);
+            if nbs.is_empty() {
+                continue; // an isolated cell
+            }
+            for first in nbs {
+                if visited.contains(&first) {
+                    continue;
+                }
+                if is_node(first.0, first.1) {
+                    // a link between two adjacent nodes
+                    let key = if (row, col) < first {
+                        ((row, col), first)
+                    } else {
+                        (first, (row, col))
+                    };
+                    if node_links.insert(key) {
+                        lines.push(vec![(row, col), first]);
+                    }
+                    continue;
+                }
+                let mut line = vec![(row, col)];
+                let mut prev = (row, col);
+                let mut current = first;
+                loop {
+                    line.push(current);
+                    if is_node(current.0, current.1) {
+                        break;
+                    }
+                    visited.insert(current);
+                    let next = neighbours(skeleton, current.0, current.1)
+                        .into_iter()
+                        .find(|n| *n != prev && !visited.contains(n));
+                    match next {
+                        Some(n) => {
+                            prev = current;
+                            current = n;
+                        }
+                        None => {
+                            // the line returns to its starting node
+                            if neighbours(skeleton, current.0, current.1).contains(&(row, col))
+                                && line.len() > 2
+                            {
+                                line.push((row, col));
+                            }
+                            break;
+                        }
+                    }
+                }
+                lines.push(line);
+            }
+        }
+    }
+    // the remaining cells belong to closed loops
+    for row in 0..rows {
+        for col in 0..columns {
+            if skeleton.get_value(row, col) != 1 || visited.contains(&(row, col)) {
+                continue;
+            }
+            if is_node(row, col) {
+                continue;
+            }
+            let mut line = vec![(row, col)];
+            visited.insert((row, col));
+            let mut current = (row, col);
+            loop {
+                let next = neighbours(skeleton, current.0, current.1)
+                    .into_iter()
+                    .find(|n| !visited.contains(n));
+                match next {
+                    Some(n) => {
+                        visited.insert(n);
+                        line.push(n);
+                        current = n;
+                    }
+                    None => break,
+                }
+            }
+            line.push((row, col));
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Returns the length of a line of cells, in map units.
+pub fn line_length(line: &[(isize, isize)], cell_size_x: f64, cell_size_y: f64) -> f64 {
+    let mut length = 0f64;
+    for i in 1..line.len() {
+        let dx = (line[i].1 - line[i - 1].1) as f64 * cell_size_x;
+        let dy = (line[i].0 - line[i - 1].0) as f64 * cell_size_y;
+        length += (dx * dx + dy * dy).sqrt();
+    }
+    length
+}
+
+/// Returns the average, minimum, and maximum feature widths along a line of cells.
+pub fn width_statistics(line: &[(isize, isize)], width: &Array2D<f64>) -> (f64, f64, f64) {
+    let mut total = 0f64;
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for &(row, col) in line {
+        let w = width.get_value(row, col);
+        total += w;
+        min = min.min(w);
+        max = max.max(w);
+    }
+    (total / line.len() as f64, min, max)
+}
+
+/// Returns the skeleton neighbours of a cell. Diagonal neighbours that are also connected to
+/// the cell through one of its orthogonal neighbours are excluded, such that the corners of
+/// staircase lines are not mistaken for junctions.
+fn neighbours(skeleton: &Array2D<i8>, row: isize, col: isize) -> Vec<(isize, isize)> {
+    let mut nbs = Vec::with_capacity(8);
+    for n in 0..8 {
+        let (r, c) = (row + DY[n], col + DX[n]);
+        if skeleton.get_value(r, c) != 1 {
+            continue;
+        }
+        if DX[n] != 0 && DY[n] != 0 {
+            // a diagonal neighbour
+            if skeleton.get_value(row, c) == 1 || skeleton.get_value(r, col) == 1 {
+                continue;
+            }
+        }
+        nbs.push((r, c));
+    }
+    nbs
+}
+
+/// Thins the foreground using the structuring elements of the LineThinning tool.
+fn thin(grid: &mut Array2D<i8>) {
+    let elements1 = [
+        [6, 7, 0, 4, 3, 2],
+        [0, 1, 2, 4, 5, 6],
+        [2, 3, 4, 6, 7, 0],
+        [4, 5, 6, 0, 1, 2],
+    ];
+    let elements2 = [
+        [7, 0, 1, 3, 5],
+        [1, 2, 3, 5, 7],
+        [3, 4, 5, 7, 1],
+        [5, 6, 7, 1, 3],
+    ];
+    let vals1 = [0i8, 0, 0, 1, 1, 1];
+    let vals2 = [0i8, 0, 0, 1, 1];
+    let mut neighbours = [0i8; 8];
+    let mut did_something = true;
+    while did_something {
+        did_something = false;
+        for a in 0..4 {
+            // cells are removed after each pass, such that the thinning is symmetrical
+            let mut removals = vec![];
+            for row in 0..grid.rows {
+                for col in 0..grid.columns {
+                    if grid.get_value(row, col) != 1 {
+                        continue;
+                    }
+                    for i in 0..8 {
+                        neighbours[i] = grid.get_value(row + DY[i], col + DX[i]);
+                    }
+                    if (0..6).all(|i| neighbours[elements1[a][i]] == vals1[i])
+                        || (0..5).all(|i| neighbours[elements2[a][i]] == vals2[i])
+                    {
+                        removals.push((row, col));
+                    }
+                }
+            }
+            for &(row, col) in &removals {
+                grid.set_value(row, col, 0);
+            }
+            did_something = did_something || !removals.is_empty();
+        }
+    }
+}
+
+/// Removes the spurs of a skeleton, i.e. the branches between an end point and a junction,
+/// that are shorter than the threshold length.
+fn prune_spurs(
+    skeleton: &mut Array2D<i8>,
+    width: &Array2D<f64>,
+    cell_size_x: f64,
+    cell_size_y: f64,
+    min_spur: f64,
+) {
+    let rows = skeleton.rows;
+    let columns = skeleton.columns;
+    let mut did_something = true;
+    while did_something {
+        did_something = false;
+        for row in 0..rows {
+            for col in 0..columns {
+                if skeleton.get_value(row, col) != 1 || neighbours(skeleton, row, col).len() != 1 {
+                    continue;
+                }
+                // trace the branch back to its junction
+                let mut branch = vec![(row, col)];
+                let mut prev = (row, col);
+                let mut current = neighbours(skeleton, row, col)[0];
+                let junction = loop {
+                    let nbs = neighbours(skeleton, current.0, current.1);
+                    if nbs.len() >= 3 {
+                        break Some(current);
+                    }
+                    if nbs.len() < 2 {
+                        break None; // a line without junctions
+                    }
+                    branch.push(current);
+                    let next = if nbs[0] == prev { nbs[1] } else { nbs[0] };
+                    prev = current;
+                    current = next;
+                };
+                if let Some(junction) = junction {
+                    let mut path = branch.clone();
+                    path.push(junction);
+                    let threshold = if min_spur > 0f64 {
+                        min_spur
+                    } else {
+                        width.get_value(junction.0, junction.1)
+                    };
+                    if line_length(&path, cell_size_x, cell_size_y) < threshold {
+                        for (r, c) in branch {
+                            skeleton.set_value(r, c, 0);
+                        }
+                        did_something = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Calculates the width of the foreground at each of its cells, i.e. twice the Euclidean
+/// distance to the nearest background cell, less one cell, using a two-pass propagation of
+/// the offsets to the nearest background cell (Danielsson, 1980).
+fn feature_width(
+    foreground: &Array2D<i8>,
+    cell_size_x: f64,
+    cell_size_y: f64,
+) -> Result<Array2D<f64>, Error> {
+    let rows = foreground.rows;
+    let columns = foreground.columns;
+    // the offsets are stored for a grid padded with a one-cell border of background
+    let (prows, pcols) = (rows + 2, columns + 2);
+    let far = (i32::max_value() / 4) as isize;
+    let mut offsets = vec![(far, far); (prows * pcols) as usize];
+    for r in 0..prows {
+        for c in 0..pcols {
+            if foreground.get_value(r - 1, c - 1) != 1 {
+                offsets[(r * pcols + c) as usize] = (0, 0);
+            }
+        }
+    }
+    let dist2 = |o: (isize, isize)| -> f64 {
+        let (y, x) = (o.0 as f64 * cell_size_y, o.1 as f64 * cell_size_x);
+        x * x + y * y
+    };
+    let relax = |offsets: &mut Vec<(isize, isize)>, r: isize, c: isize, dy: isize, dx: isize| {
+        let (rn, cn) = (r + dy, c + dx);
+        if rn < 0 || cn < 0 || rn >= prows || cn >= pcols {
+            return;
+        }
+        let o = offsets[(rn * pcols + cn) as usize];
+        if o.0 == far {
+            return;
+        }
+        let candidate = (o.0 + dy, o.1 + dx);
+        let i = (r * pcols + c) as usize;
+        if dist2(candidate) < dist2(offsets[i]) {
+            offsets[i] = candidate;
+        }
+    };
+    for r in 0..prows {
+        for c in 0..pcols {
+            for &(dy, dx) in &[(-1isize, -1isize), (-1, 0), (-1, 1), (0, -1)] {
+                relax(&mut offsets, r, c, dy, dx);
+            }
+        }
+        for c in (0..pcols).rev() {
+            relax(&mut offsets, r, c, 0, 1);
+        }
+    }
+    for r in (0..prows).rev() {
+        for c in (0..pcols).rev() {
+            for &(dy, dx) in &[(1isize, 1isize), (1, 0), (1, -1), (0, 1)] {
+                relax(&mut offsets, r, c, dy, dx);
+            }
+        }
+        for c in 0..pcols {
+            relax(&mut offsets, r, c, 0, -1);
+        }
+    }
+    let cell_size = (cell_size_x + cell_size_y) / 2f64;
+    let mut width: Array2D<f64> = Array2D::new(rows, columns, 0f64, 0f64)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if foreground.get_value(row, col) == 1 {
+                let o = offsets[((row + 1) * pcols + col + 1) as usize];
+                width.set_value(row, col, 2f64 * dist2(o).sqrt() - cell_size);
+            }
+        }
+    }
+    Ok(width)
+}
@@ -5,7 +5,7 @@ mod block_maximum;
 mod block_minimum;
 mod boundary_shape_complexity;
 mod buffer_raster;
-// mod buffer_vector;
+mod buffer_vector;
 mod centroid;
 mod centroid_vector;
 mod clip;
@@ -24,26 +24,31 @@ mod difference;
 mod dissolve;
 mod edge_proportion;
 mod eliminate_coincident_points;
+mod eliminate_small_patches;
 mod elongation_ratio;
 mod erase;
 mod erase_polygon_from_raster;
 mod euclidean_allocation;
 mod euclidean_distance;
 mod extend_vector_lines;
+mod extract_by_attribute;
 mod extract_nodes;
 mod extract_raster_values_at_points;
 mod find_lowest_or_highest_points;
 mod find_patch_edge_cells;
+mod geodesic_distance;
 mod highest_pos;
 mod hole_proportion;
 mod idw_interpolation;
 mod intersect;
 mod layer_footprint;
+mod least_cost_path;
 mod line_intersections;
 mod linearity_index;
 mod lowest_pos;
 mod max_abs_overlay;
 mod max_overlay;
+mod medial_axis;
 mod medoid;
 mod merge_line_segments;
 mod min_abs_overlay;
@@ -63,6 +68,7 @@ mod perimeter_area_ratio;
 mod pick_from_list;
 mod polygon_area;
 mod polygon_long_axis;
+mod polygon_medial_axis;
 mod polygon_perimeter;
 mod polygon_short_axis;
 mod polygonize;
@@ -77,7 +83,10 @@ mod reclass_from_file;
 mod related_circumscribing_circle;
 mod shape_complexity_index;
 mod shape_complexity_raster;
+mod simplify_vectors;
+mod skeletonize_raster;
 mod smooth_vectors;
+mod spatial_join;
 mod split_with_lines;
 mod sum_overlay;
 mod symmetrical_difference;
@@ -95,7 +104,7 @@ pub use self::block_maximum::BlockMaximumGridding;
 pub use self::block_minimum::BlockMinimumGridding;
 pub use self::boundary_shape_complexity::BoundaryShapeComplexity;
 pub use self::buffer_raster::BufferRaster;
-// pub use self::buffer_vector::BufferVector;
+pub use self::buffer_vector::BufferVector;
 pub use self::centroid::Centroid;
 pub use self::centroid_vector::CentroidVector;
 pub use self::clip::Clip;
@@ -114,21 +123,25 @@ pub use self::difference::Difference;
 pub use self::dissolve::Dissolve;
 pub use self::edge_proportion::EdgeProportion;
 pub use self::eliminate_coincident_points::EliminateCoincidentPoints;
+pub use self::eliminate_small_patches::EliminateSmallPatches;
 pub use self::elongation_ratio::ElongationRatio;
 pub use self::erase::Erase;
 pub use self::erase_polygon_from_raster::ErasePolygonFromRaster;
 pub use self::euclidean_allocation::EuclideanAllocation;
 pub use self::euclidean_distance::EuclideanDistance;
 pub use self::extend_vector_lines::ExtendVectorLines;
+pub use self::extract_by_attribute::ExtractByAttribute;
 pub use self::extract_nodes::ExtractNodes;
 pub use self::extract_raster_values_at_points::ExtractRasterValuesAtPoints;
 pub use self::find_lowest_or_highest_points::FindLowestOrHighestPoints;
 pub use self::find_patch_edge_cells::FindPatchOrClassEdgeCells;
+pub use self::geodesic_distance::GeodesicDistance;
 pub use self::highest_pos::HighestPosition;
 pub use self::hole_proportion::HoleProportion;
 pub use self::idw_interpolation::IdwInterpolation;
 pub use self::intersect::Intersect;
 pub use self::layer_footprint::LayerFootprint;
+pub use self::least_cost_path::LeastCostPath;
 pub use self::line_intersections::LineIntersections;
 pub use self::linearity_index::LinearityIndex;
 pub use self::lowest_pos::LowestPosition;
@@ -153,6 +166,7 @@ pub use self::perimeter_area_ratio::PerimeterAreaRatio;
 pub use self::pick_from_list::PickFromList;
 pub use self::polygon_area::PolygonArea;
 pub use self::polygon_long_axis::PolygonLongAxis;
+pub use self::polygon_medial_axis::PolygonMedialAxis;
 pub use self::polygon_perimeter::PolygonPerimeter;
 pub use self::polygon_short_axis::PolygonShortAxis;
 pub use self::polygonize::Polygonize;
@@ -167,7 +181,10 @@ pub use self::reclass_from_file::ReclassFromFile;
 pub use self::related_circumscribing_circle::RelatedCircumscribingCircle;
 pub use self::shape_complexity_index::ShapeComplexityIndex;
 pub use self::shape_complexity_raster::ShapeComplexityIndexRaster;
+pub use self::simplify_vectors::SimplifyVectors;
+pub use self::skeletonize_raster::SkeletonizeRaster;
 pub use self::smooth_vectors::SmoothVectors;
+pub use self::spatial_join::SpatialJoin;
 pub use self::split_with_lines::SplitWithLines;
 pub use self::sum_overlay::SumOverlay;
 pub use self::symmetrical_difference::SymmetricalDifference;
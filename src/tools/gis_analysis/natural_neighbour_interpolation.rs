@@ -54,7 +54,7 @@ use std::thread;
 /// Interpolating Multivariate Data. Chichester: John Wiley. pp. 21–36.
 ///
 /// # See Also
-/// `IdwInterpolation`, `NearestNeighbourGridding`
+/// `IdwInterpolation`, `NearestNeighbourGridding`, `OrdinaryKriging`
 pub struct NaturalNeighbourInterpolation {
     name: String,
     description: String,
@@ -225,12 +225,8 @@ impl WhiteboxTool for PercentEqualTo {
                 }
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
-                // check to ensure that all inputs have the same rows and columns
-                if input.configs.rows as isize != rows || input.configs.columns as isize != columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // check to ensure that all inputs share the same grid and CRS
+                let input = align_raster_input(&comparison, input)?;
 
                 for row in 0..rows {
                     for col in 0..columns {
@@ -21,6 +21,9 @@ use std::path;
 /// to completely enclose a feature. The long axis is drawn for each polygon in the input
 /// vector file such that it passes through the centre point of the MBB. The output file is
 /// therefore a vector of simple two-point polylines forming a vector field.
+///
+/// # See Also
+/// `PolygonShortAxis`, `PolygonMedialAxis`, `MinimumBoundingBox`
 pub struct PolygonLongAxis {
     name: String,
     description: String,
@@ -0,0 +1,401 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::medial_axis::{line_length, skeletonize, trace_lines, width_statistics};
+use crate::algorithms::{polygon_area, polygon_perimeter};
+use crate::structures::{Array2D, Point2D};
+use crate::tools::*;
+use crate::vector::ShapefileGeometry;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+// the maximum number of grid cells used to rasterize a single polygon
+const MAX_CELLS: f64 = 25_000_000f64;
+
+/// This tool maps the medial axes, or centrelines, of vector polygons (`--input`), e.g. converting
+/// river, road, or glacier outlines into centreline networks. The medial axis is the set of points within
+/// a polygon that are equidistant from two or more points on its boundary; it branches wherever the
+/// polygon does, e.g. at channel confluences, and forms loops around the holes in polygons, e.g.
+/// around the islands of a braided river.
+///
+/// The output vector (`--output`) contains one line for each segment of a medial axis between end
+/// points and junctions. Its attribute table contains the FID of the PARENT_ID polygon, the LENGTH
+/// of the line, and the AVG_WIDTH, MIN_WIDTH, and MAX_WIDTH of the polygon along it, where width is
+/// twice the distance from the medial axis to the polygon boundary. Lengths and widths are measured in
+/// the units of the input vector's coordinate system, which should be projected.
+///
+/// Each polygon is rasterized using a grid resolution (`--cell_size`) that should be fine enough to
+/// resolve the narrowest parts of the polygon. By default, the resolution of each polygon is set to
+/// 1/20th of the polygon's mean width, estimated as 2*A*/*P*, where *A* is its area and *P* its
+/// perimeter. The resolution is coarsened where needed to limit each polygon to 25 million grid cells.
+/// Irregular polygon outlines produce short side branches, or spurs. Spurs that are shorter than the
+/// minimum spur length (`--min_spur`), in map units, are pruned. If the minimum spur length is zero
+/// (the default), each spur is pruned if it is shorter than the width of the polygon at the junction
+/// from which it branches.
+///
+/// # Algorithm Description
+/// The medial axis is approximated by thinning the rasterized polygon to a one-cell-wide skeleton, in
+/// the same manner as the `SkeletonizeRaster` tool, and tracing the skeleton into lines. The lines are
+/// therefore accurate to about one grid cell.
+///
+/// # See Also
+/// `SkeletonizeRaster`, `PolygonLongAxis`, `PolygonShortAxis`, `LineThinning`
+pub struct PolygonMedialAxis {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl PolygonMedialAxis {
+    pub fn new() -> PolygonMedialAxis {
+        // public constructor
+        let name = "PolygonMedialAxis".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Maps the medial axes (centrelines) of vector polygons, with polygon width attributes."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Polygon File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Lines File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polyline file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Grid Resolution (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optional grid resolution used to rasterize polygons; by default 1/20th of each polygon's mean width.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Spur Length".to_owned(),
+            flags: vec!["--min_spur".to_owned()],
+            description: "Minimum spur length, in map units; zero prunes spurs shorter than the local polygon width.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=rivers.shp -o=centrelines.shp --cell_size=1.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        PolygonMedialAxis {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for PolygonMedialAxis {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut cell_size = 0f64;
+        let mut min_spur = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-cell_size" {
+                cell_size = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-min_spur" {
+                min_spur = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if cell_size < 0f64 || min_spur < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The grid resolution and minimum spur length must not be negative.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        if input.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "ERROR: This tool is intended to operate on Polygon type vector files only.",
+            ));
+        }
+
+        // create output file
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = input.projection.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "PARENT_ID",
+            FieldDataType::Int,
+            7u8,
+            0u8,
+        ));
+        for field in &["LENGTH", "AVG_WIDTH", "MIN_WIDTH", "MAX_WIDTH"] {
+            output.attributes.add_field(&AttributeField::new(
+                field,
+                FieldDataType::Real,
+                12u8,
+                4u8,
+            ));
+        }
+
+        let mut fid = 1i32;
+        let mut part_start: usize;
+        let mut part_end: usize;
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let mut parts = vec![];
+            let mut area = 0f64;
+            let mut perimeter = 0f64;
+            for part in 0..record.num_parts as usize {
+                part_start = record.parts[part] as usize;
+                part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize - 1
+                } else {
+                    record.num_points as usize - 1
+                };
+                if part_end <= part_start {
+                    continue;
+                }
+                if !record.is_hole(part as i32) {
+                    area += polygon_area(&record.points[part_start..part_end]);
+                } else {
+                    area -= polygon_area(&record.points[part_start..part_end]);
+                }
+                perimeter += polygon_perimeter(&record.points[part_start..part_end]);
+                parts.push((part_start, part_end));
+            }
+            if area <= 0f64 || perimeter <= 0f64 {
+                continue;
+            }
+
+            // rasterize the polygon with a border of background cells
+            let (width, height) = (record.x_max - record.x_min, record.y_max - record.y_min);
+            let mut res = if cell_size > 0f64 {
+                cell_size
+            } else {
+                2f64 * area / perimeter / 20f64
+            };
+            res = res.max(((width * height) / MAX_CELLS).sqrt());
+            let columns = (width / res).ceil() as isize + 2;
+            let rows = (height / res).ceil() as isize + 2;
+            let west = record.x_min - res;
+            let north = record.y_max + res;
+            let mut grid: Array2D<i8> = Array2D::new(rows, columns, 0, 0)?;
+            let mut crossings = vec![];
+            for row in 0..rows {
+                let y = north - (row as f64 + 0.5) * res;
+                crossings.clear();
+                for &(part_start, part_end) in &parts {
+                    for i in part_start..part_end {
+                        let (p1, p2) = (record.points[i], record.points[i + 1]);
+                        if (p1.y <= y) != (p2.y <= y) {
+                            crossings.push(p1.x + (y - p1.y) / (p2.y - p1.y) * (p2.x - p1.x));
+                        }
+                    }
+                }
+                crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                for pair in crossings.chunks(2) {
+                    if pair.len() < 2 {
+                        break;
+                    }
+                    let first = ((pair[0] - west) / res - 0.5).ceil() as isize;
+                    let last = ((pair[1] - west) / res - 0.5).floor() as isize;
+                    for col in first.max(0)..=last.min(columns - 1) {
+                        grid.set_value(row, col, 1);
+                    }
+                }
+            }
+
+            let (skeleton, widths) = skeletonize(&grid, res, res, min_spur)?;
+            for line in trace_lines(&skeleton) {
+                let points: Vec<Point2D> = line
+                    .iter()
+                    .map(|&(row, col)| {
+                        Point2D::new(
+                            west + (col as f64 + 0.5) * res,
+                            north - (row as f64 + 0.5) * res,
+                        )
+                    })
+                    .collect();
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&points);
+                output.add_record(sfg);
+                let (avg_width, min_width, max_width) = width_statistics(&line, &widths);
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(record_num as i32 + 1),
+                        FieldData::Real(line_length(&line, res, res)),
+                        FieldData::Real(avg_width),
+                        FieldData::Real(min_width),
+                        FieldData::Real(max_width),
+                    ],
+                    false,
+                );
+                fid += 1;
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
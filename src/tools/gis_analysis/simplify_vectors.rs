@@ -0,0 +1,361 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{douglas_peucker, generalize_lines, visvalingam_whyatt};
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool simplifies the lines or polygon boundaries in a vector coverage of either a POLYLINE
+/// or POLYGON base ShapeType by removing vertices. Two simplification methods are available
+/// (`--method`). The Douglas-Peucker method ('douglas_peucker') retains the vertices needed to keep
+/// the simplified line within a specified distance (`--tolerance`) of the original line. The
+/// Visvalingam-Whyatt method ('visvalingam') repeatedly removes the vertex that forms the triangle of
+/// smallest area with its two neighbours, until all remaining triangles have areas of at least the
+/// tolerance, which is therefore specified in squared map units. The Visvalingam-Whyatt method
+/// tends to produce more natural-looking generalizations of sinuous features, while the
+/// Douglas-Peucker method better retains sharp corners. The end points of lines are never removed
+/// and polygon rings retain at least three distinct vertices.
+///
+/// When the `--preserve_topology` flag is specified, the lines are divided into arcs at the
+/// vertices where three or more lines meet and at line end points, and each arc is simplified only
+/// once. Boundaries shared by adjacent polygons are thereby simplified identically, such that no
+/// gaps or overlaps develop between them, and lines that meet at a vertex continue to do so. Note
+/// that shared vertices must have identical coordinates. Without this flag, each feature part is
+/// simplified independently.
+///
+/// The attributes of the input features are copied to the output. Z and M values are not retained.
+///
+/// # See Also
+/// `SmoothVectors`
+pub struct SimplifyVectors {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SimplifyVectors {
+    pub fn new() -> SimplifyVectors {
+        // public constructor
+        let name = "SimplifyVectors".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Simplifies a vector coverage of either a POLYLINE or POLYGON base ShapeType by removing vertices.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector POLYLINE or POLYGON file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::LineOrPolygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::LineOrPolygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Simplification Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description:
+                "Simplification method; options include 'douglas_peucker' and 'visvalingam'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "douglas_peucker".to_owned(),
+                "visvalingam".to_owned(),
+            ]),
+            default_value: Some("douglas_peucker".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Simplification tolerance; a distance for the Douglas-Peucker method and an area for the Visvalingam-Whyatt method.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Preserve topology?".to_owned(),
+            flags: vec!["--preserve_topology".to_owned()],
+            description: "Simplify shared boundaries identically, such that no gaps develop between adjacent features.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=in_file.shp -o=out_file.shp --method=douglas_peucker --tolerance=5.0 --preserve_topology",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SimplifyVectors {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SimplifyVectors {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut method = "douglas_peucker".to_string();
+        let mut tolerance = 0f64;
+        let mut preserve_topology = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-tolerance" {
+                tolerance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-preserve_topology" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    preserve_topology = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if tolerance <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The simplification tolerance must be greater than zero.",
+            ));
+        }
+        let visvalingam = method.contains("vis") || method.contains("vw");
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        // make sure the input vector file is of PolyLine or Polygon type
+        let shape_type = input.header.shape_type.base_shape_type();
+        if shape_type != ShapeType::PolyLine && shape_type != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of POLYLINE or POLYGON base shape type.",
+            ));
+        }
+
+        let mut parts: Vec<Vec<Point2D>> = vec![];
+        let mut record_parts = Vec::with_capacity(input.num_records);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let first_part = parts.len();
+            for part in 0..record.num_parts as usize {
+                let start_point = record.parts[part] as usize;
+                let end_point = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                parts.push(record.points[start_point..end_point].to_vec());
+            }
+            record_parts.push(first_part..parts.len());
+        }
+
+        if verbose {
+            println!("Simplifying...")
+        };
+        let simplified = generalize_lines(&parts, preserve_topology, |points, _| {
+            if visvalingam {
+                visvalingam_whyatt(points, tolerance)
+            } else {
+                douglas_peucker(points, tolerance)
+            }
+        });
+
+        // create output file
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+
+        let min_points = if shape_type == ShapeType::Polygon {
+            4
+        } else {
+            2
+        };
+        let (mut num_points_in, mut num_points_out) = (0usize, 0usize);
+        for record_num in 0..input.num_records {
+            let mut sfg = ShapefileGeometry::new(shape_type);
+            for p in record_parts[record_num].clone() {
+                num_points_in += parts[p].len();
+                // parts that degenerate are left unchanged
+                let points = if simplified[p].len() >= min_points {
+                    &simplified[p]
+                } else {
+                    &parts[p]
+                };
+                num_points_out += points.len();
+                sfg.add_part(points);
+            }
+            output.add_record(sfg);
+            output
+                .attributes
+                .add_record(input.attributes.get_record(record_num), false);
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!(
+                "Number of vertices reduced from {} to {}",
+                num_points_in, num_points_out
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
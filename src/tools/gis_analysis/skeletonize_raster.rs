@@ -0,0 +1,376 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::medial_axis::{line_length, skeletonize, trace_lines, width_statistics};
+use crate::raster::*;
+use crate::structures::{Array2D, Point2D};
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool reduces the features in a binary raster (`--input`), e.g. a classified river or road
+/// mask, to one-cell-wide skeletons that follow their medial axes. Features are all positive,
+/// non-NoData grid cells. Each skeleton cell in the output raster (`--output`) is assigned the width
+/// of the feature at that location, i.e. twice the Euclidean distance to the nearest background cell,
+/// less one grid cell; all other cells are assigned zero. Widths are measured in the horizontal units
+/// of the input raster.
+///
+/// Thinning produces short side branches, or spurs, wherever the outline of a feature is irregular.
+/// Spurs, i.e. branches between an end point and a junction, that are shorter than the minimum spur
+/// length (`--min_spur`), in map units, are pruned from the skeleton. If the minimum spur length is
+/// zero (the default), each spur is pruned if it is shorter than the width of the feature at the
+/// junction from which it branches, which removes the spurs produced by the bumps in a feature's
+/// outline while retaining genuine tributaries.
+///
+/// The skeleton can optionally be converted into a vector of centrelines (`--out_lines`), with one line
+/// for each skeleton segment between end points and junctions. The attribute table of the centrelines
+/// contains the LENGTH of each line and the AVG_WIDTH, MIN_WIDTH, and MAX_WIDTH of the feature along it.
+///
+/// # Algorithm Description
+/// The features are thinned using the structuring elements of the `LineThinning` tool, with the cells
+/// matched in each directional pass removed simultaneously, such that the skeleton is centred within
+/// features. Feature widths are derived from a Euclidean distance transform (Danielsson, 1980).
+///
+/// # Reference
+/// Danielsson, P. E. (1980). Euclidean distance mapping. *Computer Graphics and Image Processing*,
+/// 14(3), 227-248.
+///
+/// # See Also
+/// `PolygonMedialAxis`, `LineThinning`, `RemoveSpurs`, `RasterToVectorLines`
+pub struct SkeletonizeRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SkeletonizeRaster {
+    pub fn new() -> SkeletonizeRaster {
+        // public constructor
+        let name = "SkeletonizeRaster".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Thins binary raster features to pruned, one-cell-wide skeletons with width values and optional vector centrelines.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input binary raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Skeleton File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output skeleton raster file, containing feature widths.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Centrelines File (optional)".to_owned(),
+            flags: vec!["--out_lines".to_owned()],
+            description: "Optional output vector lines file of skeleton centrelines.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Spur Length".to_owned(),
+            flags: vec!["--min_spur".to_owned()],
+            description: "Minimum spur length, in map units; zero prunes spurs shorter than the local feature width.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=river_mask.tif -o=skeleton.tif --out_lines=centrelines.shp --min_spur=50.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SkeletonizeRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SkeletonizeRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut lines_file = String::new();
+        let mut min_spur = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_lines" {
+                lines_file = value;
+            } else if flag_val == "-min_spur" {
+                min_spur = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !lines_file.is_empty() && !lines_file.contains(&sep) && !lines_file.contains("/") {
+            lines_file = format!("{}{}", working_directory, lines_file);
+        }
+        if min_spur < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The minimum spur length must not be negative.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let cell_size_x = input.configs.resolution_x;
+        let cell_size_y = input.configs.resolution_y;
+
+        let mut foreground: Array2D<i8> = Array2D::new(rows, columns, 0, 0)?;
+        let mut z: f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                z = input.get_value(row, col);
+                if z > 0f64 && z != nodata {
+                    foreground.set_value(row, col, 1);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Reading features: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Thinning features...");
+        }
+        let (skeleton, width) = skeletonize(&foreground, cell_size_x, cell_size_y, min_spur)?;
+        drop(foreground);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        for row in 0..rows {
+            let mut data = vec![0f64; columns as usize];
+            for col in 0..columns {
+                if input.get_value(row, col) == nodata {
+                    data[col as usize] = nodata;
+                } else if skeleton.get_value(row, col) == 1 {
+                    data[col as usize] = width.get_value(row, col);
+                }
+            }
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if !lines_file.is_empty() {
+            if verbose {
+                println!("Tracing centrelines...");
+            }
+            let mut lines = Shapefile::new(&lines_file, ShapeType::PolyLine)?;
+            lines.projection = input.configs.coordinate_ref_system_wkt.clone();
+            lines
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+            for field in &["LENGTH", "AVG_WIDTH", "MIN_WIDTH", "MAX_WIDTH"] {
+                lines.attributes.add_field(&AttributeField::new(
+                    field,
+                    FieldDataType::Real,
+                    12u8,
+                    4u8,
+                ));
+            }
+            let mut fid = 1i32;
+            for line in trace_lines(&skeleton) {
+                let points: Vec<Point2D> = line
+                    .iter()
+                    .map(|&(row, col)| {
+                        Point2D::new(input.get_x_from_column(col), input.get_y_from_row(row))
+                    })
+                    .collect();
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&points);
+                lines.add_record(sfg);
+                let (avg_width, min_width, max_width) = width_statistics(&line, &width);
+                lines.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Real(line_length(&line, cell_size_x, cell_size_y)),
+                        FieldData::Real(avg_width),
+                        FieldData::Real(min_width),
+                        FieldData::Real(max_width),
+                    ],
+                    false,
+                );
+                fid += 1;
+            }
+
+            if verbose {
+                println!("Saving centrelines...")
+            };
+            let _ = match lines.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Minimum spur length: {}", min_spur));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,20 +2,40 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/10/2018
-Last Modified: 13/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
+use crate::algorithms::{
+    chaikin_smoothing, generalize_lines, moving_average_smoothing, paek_smoothing,
+};
+use crate::structures::Point2D;
 use crate::tools::*;
 use crate::vector::*;
 use std::env;
 use std::io::{Error, ErrorKind};
 use std::path;
 
-/// This tool smooths a vector coverage of either a POLYLINE or POLYGON base ShapeType. The algorithm
-/// uses a simple moving average method for smoothing, where the size of the averaging window is specified
-/// by the user. The default filter size is 3 and can be any odd integer larger than or equal to 3. The
-/// larger the averaging window, the greater the degree of line smoothing.
+/// This tool smooths a vector coverage of either a POLYLINE or POLYGON base ShapeType. Three smoothing
+/// methods are available (`--method`). The default method ('moving_average') uses a simple moving average,
+/// where the size of the averaging window is specified by the user. The default filter size is 3 and can be
+/// any odd integer larger than or equal to 3. The larger the averaging window, the greater the degree of line
+/// smoothing. Chaikin's corner-cutting method ('chaikin') replaces each line segment with two points, at one
+/// quarter and three quarters of its length, for a specified number of iterations (`--iterations`), which
+/// doubles the number of vertices at each iteration. The polynomial approximation with exponential kernel
+/// method ('paek') densifies the lines and replaces each point with a Gaussian-weighted average of the points
+/// within a specified distance along the line (`--tolerance`); larger tolerances produce smoother lines. The
+/// end points of lines are fixed by each of the methods, while closed lines and polygon rings are smoothed
+/// cyclically.
+///
+/// When the `--preserve_topology` flag is specified, the lines are divided into arcs at the vertices where
+/// three or more lines meet and at line end points, and each arc is smoothed only once, with its end points
+/// fixed. Boundaries shared by adjacent polygons are thereby smoothed identically, such that no gaps or
+/// overlaps develop between them. Note that shared vertices must have identical coordinates. Z and M values
+/// are only retained by the moving average method without topology preservation.
+///
+/// # See Also
+/// `SimplifyVectors`
 pub struct SmoothVectors {
     name: String,
     description: String,
@@ -66,6 +86,48 @@ impl SmoothVectors {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Smoothing Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description:
+                "Smoothing method; options include 'moving_average', 'chaikin', and 'paek'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "moving_average".to_owned(),
+                "chaikin".to_owned(),
+                "paek".to_owned(),
+            ]),
+            default_value: Some("moving_average".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Iterations".to_owned(),
+            flags: vec!["--iterations".to_owned()],
+            description: "Number of iterations of the Chaikin method.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Smoothing distance along the line for the PAEK method.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Preserve topology?".to_owned(),
+            flags: vec!["--preserve_topology".to_owned()],
+            description: "Smooth shared boundaries identically, such that no gaps develop between adjacent features.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -78,7 +140,8 @@ impl SmoothVectors {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=in_file.shp -o=out_file.shp --filter=9",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=in_file.shp -o=out_file.shp --filter=9
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=in_file.shp -o=out_file.shp --method=paek --tolerance=100.0 --preserve_topology",
             short_exe, name
         )
         .replace("*", &sep);
@@ -137,6 +200,10 @@ impl WhiteboxTool for SmoothVectors {
         let mut input_file: String = "".to_string();
         let mut output_file: String = "".to_string();
         let mut filter: usize = 3;
+        let mut method = "moving_average".to_string();
+        let mut iterations: usize = 3;
+        let mut tolerance = 0f64;
+        let mut preserve_topology = false;
 
         // read the arguments
         if args.len() == 0 {
@@ -179,6 +246,40 @@ impl WhiteboxTool for SmoothVectors {
                         .parse::<usize>()
                         .expect(&format!("Error parsing {}", flag_val))
                 };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-iterations" {
+                iterations = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-tolerance" {
+                tolerance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-preserve_topology" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    preserve_topology = true;
+                }
             }
         }
 
@@ -202,7 +303,19 @@ impl WhiteboxTool for SmoothVectors {
             filter += 1;
         }
 
-        let half_filter = (filter / 2) as i32;
+        let method = if method.contains("chaikin") {
+            SmoothingMethod::Chaikin
+        } else if method.contains("paek") {
+            if tolerance <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A smoothing tolerance greater than zero must be specified for the PAEK method.",
+                ));
+            }
+            SmoothingMethod::Paek
+        } else {
+            SmoothingMethod::MovingAverage
+        };
 
         if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
             input_file = format!("{}{}", working_directory, input_file);
@@ -224,123 +337,69 @@ impl WhiteboxTool for SmoothVectors {
             ));
         }
 
-        // create output file
-        let mut output =
-            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
-
-        let (mut x, mut y): (f64, f64);
-        let mut n: f64;
-        let (mut start_point_in_part, mut end_point_in_part): (i32, i32);
-
-        if input.header.shape_type.base_shape_type() == ShapeType::PolyLine {
-            for record_num in 0..input.num_records {
-                let in_record = input.get_record(record_num);
-                let mut out_record = in_record.clone();
-
-                for part in 0..in_record.num_parts as usize {
-                    start_point_in_part = in_record.parts[part];
-                    end_point_in_part = if (part as i32) < in_record.num_parts - 1 {
-                        in_record.parts[part + 1] - 1
-                    } else {
-                        in_record.num_points - 1
-                    };
-                    if end_point_in_part - start_point_in_part > 2 {
-                        // Notice, we are pinning the starting and ending points positions.
-                        // This is because the edge effects of the filter will have the effect
-                        // of shortening the polyline otherwise.
-                        for i in start_point_in_part + 1..end_point_in_part {
-                            n = 0f64;
-                            x = 0f64;
-                            y = 0f64;
-                            for j in (i - half_filter)..=(i + half_filter) {
-                                if j >= start_point_in_part && j <= end_point_in_part {
-                                    n += 1f64;
-                                    x += in_record.points[j as usize].x;
-                                    y += in_record.points[j as usize].y;
-                                }
-                            }
-                            if n > 0f64 {
-                                out_record.points[i as usize].x = x / n;
-                                out_record.points[i as usize].y = y / n;
-                            }
-                        }
-                    }
-                }
-
-                output.add_record(out_record);
+        let mut parts: Vec<Vec<Point2D>> = vec![];
+        let mut record_parts = Vec::with_capacity(input.num_records);
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let first_part = parts.len();
+            for part in 0..record.num_parts as usize {
+                let start_point = record.parts[part] as usize;
+                let end_point = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                parts.push(record.points[start_point..end_point].to_vec());
+            }
+            record_parts.push(first_part..parts.len());
+        }
 
-                let atts = input.attributes.get_record(record_num);
-                output.attributes.add_record(atts.clone(), false);
+        let smoothed = generalize_lines(&parts, preserve_topology, |points, closed| match method {
+            SmoothingMethod::MovingAverage => moving_average_smoothing(points, filter, closed),
+            SmoothingMethod::Chaikin => chaikin_smoothing(points, iterations, closed),
+            SmoothingMethod::Paek => paek_smoothing(points, tolerance, closed),
+        });
 
-                if verbose {
-                    progress =
-                        (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
-                }
-            }
+        // The moving average filter doesn't change the number of vertices, so unless the lines
+        // are divided into arcs, the input records, including any z and m values, can be updated.
+        let update_records = method == SmoothingMethod::MovingAverage && !preserve_topology;
+        let shape_type = if update_records {
+            input.header.shape_type
         } else {
-            // Polygon
-            let mut k: i32;
-            for record_num in 0..input.num_records {
-                let in_record = input.get_record(record_num);
-                let mut out_record = in_record.clone();
-
-                for part in 0..in_record.num_parts as usize {
-                    start_point_in_part = in_record.parts[part];
-                    end_point_in_part = if (part as i32) < in_record.num_parts - 1 {
-                        in_record.parts[part + 1] - 1
-                    } else {
-                        in_record.num_points - 1
-                    };
-                    if end_point_in_part - start_point_in_part > 4 {
-                        // We won't smooth the last point in the part. This
-                        // will be set to the same position as the first
-                        // after the smoothing operation.
-                        for i in start_point_in_part..end_point_in_part {
-                            n = 0f64;
-                            x = 0f64;
-                            y = 0f64;
-                            for j in (i - half_filter)..=(i + half_filter) {
-                                k = j;
-                                if j < start_point_in_part {
-                                    k = end_point_in_part - (start_point_in_part - j);
-                                }
-                                if j > end_point_in_part {
-                                    k = start_point_in_part + (j - end_point_in_part);
-                                }
-                                if k >= start_point_in_part && k <= end_point_in_part {
-                                    n += 1f64;
-                                    x += in_record.points[k as usize].x;
-                                    y += in_record.points[k as usize].y;
-                                }
-                            }
-                            if n > 0f64 {
-                                out_record.points[i as usize].x = x / n;
-                                out_record.points[i as usize].y = y / n;
-                            }
-                        }
-                        out_record.points[end_point_in_part as usize].x =
-                            out_record.points[start_point_in_part as usize].x;
-                        out_record.points[end_point_in_part as usize].y =
-                            out_record.points[start_point_in_part as usize].y;
+            input.header.shape_type.base_shape_type()
+        };
+
+        // create output file
+        let mut output = Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+
+        for record_num in 0..input.num_records {
+            if update_records {
+                let mut out_record = input.get_record(record_num).clone();
+                let mut i = 0;
+                for p in record_parts[record_num].clone() {
+                    for pt in &smoothed[p] {
+                        out_record.points[i] = *pt;
+                        i += 1;
                     }
                 }
-
                 output.add_record(out_record);
+            } else {
+                let mut sfg = ShapefileGeometry::new(shape_type);
+                for p in record_parts[record_num].clone() {
+                    sfg.add_part(&smoothed[p]);
+                }
+                output.add_record(sfg);
+            }
 
-                let atts = input.attributes.get_record(record_num);
-                output.attributes.add_record(atts.clone(), false);
+            let atts = input.attributes.get_record(record_num);
+            output.attributes.add_record(atts.clone(), false);
 
-                if verbose {
-                    progress =
-                        (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
         }
@@ -366,3 +425,10 @@ impl WhiteboxTool for SmoothVectors {
         Ok(())
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SmoothingMethod {
+    MovingAverage,
+    Chaikin,
+    Paek,
+}
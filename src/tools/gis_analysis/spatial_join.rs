@@ -0,0 +1,746 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::structures::{BoundingBox, Point2D, RTree};
+use crate::tools::*;
+use crate::vector::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool transfers the attributes of the features in a join vector file (`--join`) to the
+/// features of a target vector file (`--input`) based on their spatial relationship. The output
+/// vector contains the geometries and attributes of the target features along with the attributes
+/// of their matching join features. The spatial relationship (`--predicate`) may be:
+///
+/// - 'intersects': the features share at least one location, e.g. a point lies within a polygon,
+///   two lines cross, or two polygons overlap or one contains the other;
+/// - 'within_distance': the features are no more than `--distance` apart; or
+/// - 'nearest': the join feature is the one closest to the target feature, optionally searching
+///   no further than `--distance`.
+///
+/// Distances are measured between the feature geometries (i.e. their vertices and edges) rather
+/// than their centroids and are expressed in the units of the input coordinate system. Target and
+/// join features may be of any shape type, and the two files should share the same projection.
+///
+/// When the cardinality (`--cardinality`) is 'one_to_one', the output contains one record for
+/// each target feature. If a target feature matches more than one join feature, each numeric join
+/// field is summarized using the specified statistic (`--stat`), i.e. 'first', 'mean', 'min',
+/// 'max', or 'sum', where the first match is the closest join feature; other fields take the
+/// value of the first match. A JOIN_COUNT field records the number of matches. When the cardinality
+/// is 'one_to_many', the output contains a record, and a copy of the target geometry, for each
+/// matching pair of features, with a JOIN_FID field containing the one-based record number of the
+/// join feature. For the 'within_distance' and 'nearest' predicates, a JOIN_DIST field contains
+/// the distance to the (closest) matched join feature. Target features without any matches are
+/// output with null join attributes, unless the `--matched_only` flag is specified. Join fields
+/// with names that already exist in the target table are renamed with a numeric suffix.
+///
+/// Candidate features are found using an R-tree index of the join features' bounding boxes.
+///
+/// # See Also
+/// `ExtractByAttribute`, `JoinTables`, `Clip`
+pub struct SpatialJoin {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SpatialJoin {
+    pub fn new() -> SpatialJoin {
+        // public constructor
+        let name = "SpatialJoin".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Transfers attributes between vector files based on the spatial relationships of their features.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Target Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input target vector file, to which attributes are joined.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Join Vector File".to_owned(),
+            flags: vec!["--join".to_owned()],
+            description: "Input join vector file, from which attributes are taken.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spatial Predicate".to_owned(),
+            flags: vec!["--predicate".to_owned()],
+            description: "Spatial relationship; options include 'intersects', 'within_distance', and 'nearest'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "intersects".to_owned(),
+                "within_distance".to_owned(),
+                "nearest".to_owned(),
+            ]),
+            default_value: Some("intersects".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Distance".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Search distance for the 'within_distance' predicate, or optional maximum distance for 'nearest'.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cardinality".to_owned(),
+            flags: vec!["--cardinality".to_owned()],
+            description: "Join cardinality; options include 'one_to_one' and 'one_to_many'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "one_to_one".to_owned(),
+                "one_to_many".to_owned(),
+            ]),
+            default_value: Some("one_to_one".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Summary Statistic".to_owned(),
+            flags: vec!["--stat".to_owned()],
+            description: "Statistic used to summarize numeric fields of multiple matches in a one-to-one join; options include 'first', 'mean', 'min', 'max', and 'sum'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "first".to_owned(),
+                "mean".to_owned(),
+                "min".to_owned(),
+                "max".to_owned(),
+                "sum".to_owned(),
+            ]),
+            default_value: Some("first".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output matched features only?".to_owned(),
+            flags: vec!["--matched_only".to_owned()],
+            description: "Exclude target features that do not match any join features.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=wells.shp --join=watersheds.shp -o=output.shp --predicate=intersects
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=parcels.shp --join=trees.shp -o=output.shp --predicate=within_distance --distance=50.0 --stat=sum",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SpatialJoin {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SpatialJoin {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut join_file = String::new();
+        let mut output_file = String::new();
+        let mut predicate = "intersects".to_string();
+        let mut distance = f64::NAN;
+        let mut cardinality = "one_to_one".to_string();
+        let mut stat = "first".to_string();
+        let mut matched_only = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-join" {
+                join_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-predicate" {
+                predicate = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1].to_string().parse::<f64>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<f64>().unwrap()
+                };
+            } else if flag_val == "-cardinality" {
+                cardinality = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-stat" {
+                stat = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-matched_only" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    matched_only = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !join_file.contains(&sep) && !join_file.contains("/") {
+            join_file = format!("{}{}", working_directory, join_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let predicate = if predicate.contains("near") {
+            Predicate::Nearest
+        } else if predicate.contains("dist") {
+            Predicate::WithinDistance
+        } else {
+            Predicate::Intersects
+        };
+        let max_distance = match predicate {
+            Predicate::Intersects => 0f64,
+            Predicate::WithinDistance => {
+                if !(distance >= 0f64) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "A non-negative --distance must be specified for the 'within_distance' predicate.",
+                    ));
+                }
+                distance
+            }
+            Predicate::Nearest => {
+                if distance > 0f64 {
+                    distance
+                } else {
+                    f64::INFINITY
+                }
+            }
+        };
+        let one_to_many = cardinality.contains("many");
+        let stat = if stat.contains("mean") || stat.contains("av") {
+            Statistic::Mean
+        } else if stat.contains("min") {
+            Statistic::Min
+        } else if stat.contains("max") {
+            Statistic::Max
+        } else if stat.contains("sum") {
+            Statistic::Sum
+        } else {
+            Statistic::First
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let join = Shapefile::read(&join_file)?;
+
+        let start = Instant::now();
+
+        if verbose {
+            println!("Building spatial index...")
+        };
+        let targets = Arc::new(
+            (0..input.num_records)
+                .map(|i| Feature::new(input.get_record(i)))
+                .collect::<Vec<Feature>>(),
+        );
+        let join_features: Vec<Feature> = (0..join.num_records)
+            .map(|i| Feature::new(join.get_record(i)))
+            .collect();
+        let tree = Arc::new(RTree::new(
+            join_features
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| !f.points.is_empty())
+                .map(|(i, f)| (f.bb, i))
+                .collect(),
+        ));
+        let join_features = Arc::new(join_features);
+
+        let num_targets = input.num_records;
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let targets = targets.clone();
+            let join_features = join_features.clone();
+            let tree = tree.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for i in (0..num_targets).filter(|i| i % num_procs == tid) {
+                    let target = &targets[i];
+                    let mut matches: Vec<(usize, f64)> = vec![];
+                    if !target.points.is_empty() {
+                        if predicate == Predicate::Nearest {
+                            if let Some(m) = tree.nearest(target.bb, max_distance, |j| {
+                                target.distance(&join_features[j], 0f64)
+                            }) {
+                                matches.push(m);
+                            }
+                        } else {
+                            let mut query = target.bb;
+                            query.expand_by(max_distance);
+                            for j in tree.search(query) {
+                                let dist = target.distance(&join_features[j], max_distance);
+                                if dist <= max_distance {
+                                    matches.push((j, dist));
+                                }
+                            }
+                            matches
+                                .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+                        }
+                    }
+                    tx.send((i, matches)).unwrap();
+                }
+            });
+        }
+
+        let mut matches: Vec<Vec<(usize, f64)>> = vec![vec![]; num_targets];
+        for i in 0..num_targets {
+            let (target, m) = rx.recv().expect("Error receiving data from thread.");
+            matches[target] = m;
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_targets as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+
+        // add the join fields, renaming any that duplicate existing field names
+        let num_join_fields = join.attributes.get_num_fields();
+        let mut join_is_int = vec![false; num_join_fields];
+        for j in 0..num_join_fields {
+            let mut field = join.attributes.get_field(j).clone();
+            let numeric = match field.field_type {
+                'N' | 'F' | 'I' | 'O' => true,
+                _ => false,
+            };
+            join_is_int[j] = numeric && field.decimal_count == 0;
+            if !one_to_many && stat == Statistic::Mean && join_is_int[j] {
+                field.field_type = 'F';
+                field.decimal_count = 4;
+                field.field_length = field.field_length.saturating_add(5);
+            }
+            if output.attributes.get_field_num(&field.name).is_some() {
+                let base = field.name.clone();
+                let mut n = 1;
+                loop {
+                    let suffix = format!("_{}", n);
+                    let len = base.len().min(10 - suffix.len());
+                    let name = format!("{}{}", &base[0..len], suffix);
+                    if output.attributes.get_field_num(&name).is_none() {
+                        field.name = name;
+                        break;
+                    }
+                    n += 1;
+                }
+            }
+            output.attributes.add_field(&field);
+        }
+        let extra_field = if one_to_many {
+            "JOIN_FID"
+        } else {
+            "JOIN_COUNT"
+        };
+        output.attributes.add_field(&AttributeField::new(
+            extra_field,
+            FieldDataType::Int,
+            10u8,
+            0u8,
+        ));
+        let report_distance = predicate != Predicate::Intersects;
+        if report_distance {
+            output.attributes.add_field(&AttributeField::new(
+                "JOIN_DIST",
+                FieldDataType::Real,
+                18u8,
+                6u8,
+            ));
+        }
+
+        let mut num_matched = 0;
+        for i in 0..num_targets {
+            let m = &matches[i];
+            if !m.is_empty() {
+                num_matched += 1;
+            } else if matched_only {
+                continue;
+            }
+            let target_atts = input.attributes.get_record(i);
+            if one_to_many && !m.is_empty() {
+                for &(j, dist) in m {
+                    output.add_record(input.get_record(i).clone());
+                    let mut atts = target_atts.clone();
+                    atts.extend(join.attributes.get_record(j));
+                    atts.push(FieldData::Int(j as i32 + 1));
+                    if report_distance {
+                        atts.push(FieldData::Real(dist));
+                    }
+                    output.attributes.add_record(atts, false);
+                }
+            } else {
+                output.add_record(input.get_record(i).clone());
+                let mut atts = target_atts;
+                if m.is_empty() {
+                    atts.extend(vec![FieldData::Null; num_join_fields]);
+                    atts.push(if one_to_many {
+                        FieldData::Null
+                    } else {
+                        FieldData::Int(0)
+                    });
+                    if report_distance {
+                        atts.push(FieldData::Null);
+                    }
+                } else {
+                    let records: Vec<Vec<FieldData>> = m
+                        .iter()
+                        .map(|&(j, _)| join.attributes.get_record(j))
+                        .collect();
+                    for f in 0..num_join_fields {
+                        atts.push(summarize(&records, f, stat, join_is_int[f]));
+                    }
+                    atts.push(FieldData::Int(m.len() as i32));
+                    if report_distance {
+                        atts.push(FieldData::Real(m[0].1));
+                    }
+                }
+                output.attributes.add_record(atts, false);
+            }
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No target features matched any join features; the output file was not created.",
+            ));
+        }
+
+        if verbose {
+            println!(
+                "{} of {} target features matched join features.",
+                num_matched, num_targets
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Predicate {
+    Intersects,
+    WithinDistance,
+    Nearest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    First,
+    Mean,
+    Min,
+    Max,
+    Sum,
+}
+
+/// Summarizes a field over the attribute records of the join features matching a target.
+/// The records are ordered from the closest match.
+fn summarize(records: &[Vec<FieldData>], field: usize, stat: Statistic, is_int: bool) -> FieldData {
+    let values: Vec<f64> = records
+        .iter()
+        .filter_map(|r| match r[field] {
+            FieldData::Int(v) => Some(v as f64),
+            FieldData::Real(v) => Some(v),
+            _ => None,
+        })
+        .collect();
+    if stat == Statistic::First || records.len() == 1 || values.is_empty() {
+        return records[0][field].clone();
+    }
+    let z = match stat {
+        Statistic::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        Statistic::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+        Statistic::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        _ => values.iter().sum::<f64>(),
+    };
+    if is_int && stat != Statistic::Mean {
+        FieldData::Int(z.max(i32::min_value() as f64).min(i32::max_value() as f64) as i32)
+    } else {
+        FieldData::Real(z)
+    }
+}
+
+/// The vertices of a feature, grouped into parts, for measuring distances between features.
+struct Feature {
+    points: Vec<Point2D>,
+    parts: Vec<(usize, usize)>,
+    is_polygon: bool,
+    bb: BoundingBox,
+}
+
+impl Feature {
+    fn new(record: &ShapefileGeometry) -> Feature {
+        let base_type = record.shape_type.base_shape_type();
+        let num_points = record.points.len();
+        let parts: Vec<(usize, usize)> =
+            if base_type == ShapeType::Point || base_type == ShapeType::MultiPoint {
+                (0..num_points).map(|i| (i, i + 1)).collect()
+            } else {
+                (0..record.num_parts as usize)
+                    .map(|p| {
+                        let start = record.parts[p] as usize;
+                        let end = if p < record.num_parts as usize - 1 {
+                            record.parts[p + 1] as usize
+                        } else {
+                            num_points
+                        };
+                        (start, end)
+                    })
+                    .filter(|(start, end)| end > start)
+                    .collect()
+            };
+        let bb = if num_points > 0 {
+            BoundingBox::from_points(&record.points)
+        } else {
+            BoundingBox::default()
+        };
+        Feature {
+            points: record.points.clone(),
+            parts: parts,
+            is_polygon: base_type == ShapeType::Polygon,
+            bb: bb,
+        }
+    }
+
+    /// Returns true if a point lies within the area of a polygon feature, accounting for holes.
+    fn contains(&self, p: &Point2D) -> bool {
+        if !self.is_polygon || !self.bb.is_point_in_box(p.x, p.y) {
+            return false;
+        }
+        let mut inside = false;
+        for &(start, end) in &self.parts {
+            let ring = &self.points[start..end];
+            let mut j = ring.len() - 1;
+            for i in 0..ring.len() {
+                if (ring[i].y > p.y) != (ring[j].y > p.y)
+                    && p.x
+                        < (ring[j].x - ring[i].x) * (p.y - ring[i].y) / (ring[j].y - ring[i].y)
+                            + ring[i].x
+                {
+                    inside = !inside;
+                }
+                j = i;
+            }
+        }
+        inside
+    }
+
+    /// Returns the minimum distance between two features, which is zero if they intersect.
+    /// The search stops early once a distance no greater than `tolerance` is found.
+    fn distance(&self, other: &Feature, tolerance: f64) -> f64 {
+        if other.is_polygon && self.points.iter().any(|p| other.contains(p)) {
+            return 0f64;
+        }
+        if self.is_polygon && other.points.iter().any(|p| self.contains(p)) {
+            return 0f64;
+        }
+        let mut min_dist = f64::INFINITY;
+        for &(s1, e1) in &self.parts {
+            for a in s1..e1 {
+                let a2 = if a + 1 < e1 { a + 1 } else { a };
+                for &(s2, e2) in &other.parts {
+                    for b in s2..e2 {
+                        let b2 = if b + 1 < e2 { b + 1 } else { b };
+                        let dist = segment_distance(
+                            &self.points[a],
+                            &self.points[a2],
+                            &other.points[b],
+                            &other.points[b2],
+                        );
+                        if dist < min_dist {
+                            min_dist = dist;
+                            if min_dist <= tolerance {
+                                return min_dist;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        min_dist
+    }
+}
+
+/// Returns the distance between segments p1-p2 and q1-q2, either of which may be a single point.
+fn segment_distance(p1: &Point2D, p2: &Point2D, q1: &Point2D, q2: &Point2D) -> f64 {
+    let d1 = p1.is_left(q1, q2);
+    let d2 = p2.is_left(q1, q2);
+    let d3 = q1.is_left(p1, p2);
+    let d4 = q2.is_left(p1, p2);
+    if ((d1 > 0f64 && d2 < 0f64) || (d1 < 0f64 && d2 > 0f64))
+        && ((d3 > 0f64 && d4 < 0f64) || (d3 < 0f64 && d4 > 0f64))
+    {
+        return 0f64;
+    }
+    point_segment_distance(p1, q1, q2)
+        .min(point_segment_distance(p2, q1, q2))
+        .min(point_segment_distance(q1, p1, p2))
+        .min(point_segment_distance(q2, p1, p2))
+}
+
+fn point_segment_distance(p: &Point2D, a: &Point2D, b: &Point2D) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len_sqr = dx * dx + dy * dy;
+    if len_sqr == 0f64 {
+        return p.distance(a);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len_sqr)
+        .max(0f64)
+        .min(1f64);
+    p.distance(&Point2D::new(a.x + t * dx, a.y + t * dy))
+}
@@ -2,15 +2,15 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 23/09/2018
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use self::na::Vector3;
-use crate::algorithms::{point_in_poly, triangulate};
+use crate::algorithms::point_in_poly;
 use crate::na;
 use crate::raster::*;
-use crate::structures::Point2D;
+use crate::structures::{construct_tin, Point2D};
 use crate::tools::*;
 use crate::vector::*;
 use std::env;
@@ -19,8 +19,17 @@ use std::io::{Error, ErrorKind};
 use std::path;
 
 /// Creates a raster grid based on a triangular irregular network (TIN) fitted to vector points
-/// and linear interpolation within each triangular-shaped plane. The TIN creation algorithm is based on
-/// [Delaunay triangulation](https://en.wikipedia.org/wiki/Delaunay_triangulation).
+/// and interpolation within each triangular facet. The TIN creation algorithm is based on
+/// [Delaunay triangulation](https://en.wikipedia.org/wiki/Delaunay_triangulation). By default, values are
+/// linearly interpolated within each triangular-shaped plane (`--method=linear`). Alternatively, Sibson's natural
+/// neighbour interpolation (`--method=natural_neighbour`) may be used, which results in a smoother surface
+/// that is continuous in slope everywhere except at the data points.
+///
+/// Breaklines (`--breaklines`), such as stream lines, ridge lines, and shorelines, may optionally be used to
+/// constrain the triangulation, in which case no triangle spans a breakline and, for natural neighbour
+/// interpolation, points on opposite sides of a breakline are not treated as natural neighbours. Breakline vertex
+/// heights are taken from the breaklines' z values, if available, and are otherwise interpolated from the input
+/// points. See `ConstructVectorTIN` for further details.
 ///
 /// The user must specify the attribute field containing point values (`--field`). Alternatively, if the input Shapefile
 /// contains z-values, the interpolation may be based on these values (`--use_z`). Either an output grid resolution
@@ -120,6 +129,29 @@ impl TINGridding {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Interpolation method; options include 'linear' and 'natural_neighbour'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "linear".to_owned(),
+                "natural_neighbour".to_owned(),
+            ]),
+            default_value: Some("linear".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Breaklines File (optional)".to_owned(),
+            flags: vec!["--breaklines".to_owned()],
+            description: "Optional input vector breaklines file, of polyline or polygon type.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -133,7 +165,8 @@ impl TINGridding {
         }
         let usage = format!(
             ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=HEIGHT -o=tin.shp --resolution=10.0
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=tin.shp --resolution=5.0",
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=tin.shp --resolution=5.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=tin.shp --resolution=5.0 --method=natural_neighbour --breaklines=streams.shp",
             short_exe, name
         ).replace("*", &sep);
 
@@ -196,6 +229,8 @@ impl WhiteboxTool for TINGridding {
         let mut grid_res: f64 = 0.0;
         let mut base_file = String::new();
         let mut max_triangle_edge_length = f64::INFINITY;
+        let mut natural_neighbour = false;
+        let mut breaklines_file = String::new();
 
         // read the arguments
         if args.len() == 0 {
@@ -263,6 +298,19 @@ impl WhiteboxTool for TINGridding {
                 };
 
                 max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
+            } else if flag_val == "-method" {
+                let method = if keyval {
+                    vec[1].to_string().to_lowercase()
+                } else {
+                    args[i + 1].to_string().to_lowercase()
+                };
+                natural_neighbour = method.contains("nat") || method == "nn";
+            } else if flag_val == "-breaklines" {
+                breaklines_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
             } else if flag_val == "-base" {
                 base_file = if keyval {
                     vec[1].to_string()
@@ -292,6 +340,13 @@ impl WhiteboxTool for TINGridding {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
+        if !breaklines_file.is_empty()
+            && !breaklines_file.contains(&sep)
+            && !breaklines_file.contains("/")
+        {
+            breaklines_file = format!("{}{}", working_directory, breaklines_file);
+        }
+
         let input = Shapefile::read(&input_file)?;
 
         // make sure the input vector file is of points type
@@ -452,7 +507,16 @@ impl WhiteboxTool for TINGridding {
             println!("Performing triangulation...");
         }
         // this is where the heavy-lifting is
-        let delaunay = triangulate(&points).expect("No triangulation exists.");
+        let (tin, num_failed) = construct_tin(points, z_values, &breaklines_file)?;
+        if num_failed > 0 && verbose {
+            println!(
+                "Warning: {} breaklines could not be fully enforced, likely because they cross other breaklines.",
+                num_failed
+            );
+        }
+        let delaunay = &tin.triangulation;
+        let points = &tin.points;
+        let z_values = &tin.z_values;
         let num_triangles = delaunay.triangles.len() / 3;
 
         let (mut p1, mut p2, mut p3): (usize, usize, usize);
@@ -521,7 +585,14 @@ impl WhiteboxTool for TINGridding {
                             y = north - (row as f64 + 0.5) * res_y;
                             if point_in_poly(&Point2D::new(x, y), &tri_points) {
                                 // calculate the z values
-                                z = -(norm.x * x + norm.y * y + k) / norm.z;
+                                z = if natural_neighbour {
+                                    tin.interpolate_natural_neighbour(
+                                        triangle,
+                                        &Point2D::new(x, y),
+                                    )
+                                } else {
+                                    -(norm.x * x + norm.y * y + k) / norm.z
+                                };
                                 output.set_value(row, col, z);
                             }
                         }
@@ -546,6 +617,17 @@ impl WhiteboxTool for TINGridding {
         ));
         output.add_metadata_entry(format!("Input file: {}", input_file));
         output.add_metadata_entry(format!("Grid resolution: {}", grid_res));
+        output.add_metadata_entry(format!(
+            "Interpolation method: {}",
+            if natural_neighbour {
+                "natural neighbour"
+            } else {
+                "linear"
+            }
+        ));
+        if !breaklines_file.is_empty() {
+            output.add_metadata_entry(format!("Breaklines file: {}", breaklines_file));
+        }
         output.add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
 
         if verbose {
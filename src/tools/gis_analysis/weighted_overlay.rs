@@ -354,12 +354,8 @@ impl WhiteboxTool for WeightedOverlay {
                     output = Raster::initialize_using_file(&output_file, &input);
                     output.reinitialize_values(0.0);
                 }
-                // check to ensure that all inputs have the same rows and columns
-                if input.configs.rows as isize != rows || input.configs.columns as isize != columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // check to ensure that all inputs share the same grid and CRS
+                let input = align_raster_input(&output, input)?;
 
                 min_val = input.configs.minimum;
                 range = input.configs.maximum - min_val;
@@ -418,12 +414,8 @@ impl WhiteboxTool for WeightedOverlay {
                 }
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
-                // check to ensure that all inputs have the same rows and columns
-                if input.configs.rows as isize != rows || input.configs.columns as isize != columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // check to ensure that all inputs share the same grid and CRS
+                let input = align_raster_input(&output, input)?;
 
                 for row in 0..rows {
                     for col in 0..columns {
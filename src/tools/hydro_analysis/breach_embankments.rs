@@ -0,0 +1,676 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use num_cpus;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+// the maximum number of breaching passes
+const MAX_PASSES: usize = 10;
+
+/// This tool removes the artifact dams that road and railway embankments create in digital elevation
+/// models (DEMs) at culverts and bridges, while leaving natural depressions intact. Fine-resolution DEMs,
+/// particularly those derived from lidar, represent the top of an embankment crossing a drainage line but
+/// not the culvert or bridge beneath it, such that the embankment impounds an artificial depression
+/// upstream. Unlike depression breaching tools (e.g. `BreachDepressionsLeastCost`), which breach every
+/// depression, this tool breaches only those depressions that are impounded by an embankment.
+///
+/// Embankments are detected using the dam-profile approach of the `ImpoundmentSizeIndex` tool in
+/// reverse. Whereas that tool finds the lowest dam crest across a valley, this tool searches profiles in
+/// each of the four grid directions through each grid cell, extending half the maximum embankment width
+/// (`--width`, in map units) to either side, for a cell that stands higher than the lowest ground on both
+/// sides. A cell is an embankment cell if it stands at least a minimum height (`--min_height`) above the
+/// higher of the two lowest points, i.e. if it lies on a raised feature narrower than the maximum width.
+///
+/// Depressions are then identified by priority-flood filling the DEM. A depression is breached if an
+/// embankment cell separates it from lower ground, i.e. if the lowest point on one side of the
+/// embankment's profile lies in the depression and the lowest point on the other side lies below the
+/// depression's spill elevation. Where several embankment cells satisfy this condition, the breach is
+/// placed where it results in the lowest outlet, which is where the culvert is most likely to be located.
+/// The cells along the breach profile are lowered to a straight grade between the lowest points on either
+/// side of the embankment. Because breaching one embankment can reveal another embankment upstream, the
+/// procedure is repeated until no further embankment-impounded depressions are found. Depressions that are
+/// not impounded by embankments, and the residual natural parts of breached depressions, are left intact.
+///
+/// The tool outputs the breached DEM (`--output`) and can optionally output a raster of the depth by
+/// which each cell was lowered (`--out_breaches`), which is useful for checking the breach locations
+/// against mapped culverts and bridges. The breached DEM can be processed further with depression filling
+/// or breaching tools prior to flow routing. Natural ridges that are narrower than the maximum embankment
+/// width, e.g. levees and eskers, can also be identified as embankments, and the maximum width should be
+/// no larger than the widest embankment in the area.
+///
+/// # See Also
+/// `BurnStreamsAtRoads`, `ImpoundmentSizeIndex`, `BreachDepressionsLeastCost`, `FillDepressions`
+pub struct BreachEmbankments {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BreachEmbankments {
+    pub fn new() -> BreachEmbankments {
+        // public constructor
+        let name = "BreachEmbankments".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Breaches the depressions impounded by road and railway embankments in a DEM, leaving natural depressions intact.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output breached DEM file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Breach Depth File (optional)".to_owned(),
+            flags: vec!["--out_breaches".to_owned()],
+            description: "Optional output raster of the depth by which cells were lowered."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Embankment Width".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "Maximum embankment width, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("30.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Embankment Height".to_owned(),
+            flags: vec!["--min_height".to_owned()],
+            description:
+                "Minimum height of embankments above the adjacent ground, in elevation units."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=lidar_dem.tif -o=breached.tif --out_breaches=breaches.tif --width=25.0 --min_height=0.75",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        BreachEmbankments {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BreachEmbankments {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut breaches_file = String::new();
+        let mut width = 30f64;
+        let mut min_height = 1f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_breaches" {
+                breaches_file = value;
+            } else if flag_val == "-width" {
+                width = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-min_height" {
+                min_height = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !breaches_file.is_empty()
+            && !breaches_file.contains(&sep)
+            && !breaches_file.contains("/")
+        {
+            breaches_file = format!("{}{}", working_directory, breaches_file);
+        }
+        if width <= 0f64 || min_height <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum embankment width and minimum embankment height must be positive.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let grid_res = (input.configs.resolution_x + input.configs.resolution_y) / 2f64;
+        let half_width = ((width / 2f64) / grid_res).round().max(1f64) as isize;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+        // Find the narrow ridge cells. Each is assigned the direction of the profile, across the
+        // ridge, in which it stands highest above the ground on both sides.
+        let mut ridge_dir: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+        let (tx, rx) = mpsc::channel();
+        let num_procs = num_cpus::get() as isize;
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![-1i8; columns as usize];
+                    for col in 0..columns {
+                        let z = input.get_value(row, col);
+                        if z == nodata {
+                            continue;
+                        }
+                        let mut highest = min_height;
+                        for dir in 0..4 {
+                            let mut side_min = [f64::INFINITY; 2];
+                            for (side, d) in [dir, dir + 4].iter().enumerate() {
+                                for i in 1..=half_width {
+                                    let zn = input.get_value(row + dy[*d] * i, col + dx[*d] * i);
+                                    if zn != nodata && zn < side_min[side] {
+                                        side_min[side] = zn;
+                                    }
+                                }
+                            }
+                            let height = z - side_min[0].max(side_min[1]);
+                            if height >= highest {
+                                highest = height;
+                                data[col as usize] = dir as i8;
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            ridge_dir.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding embankments: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut dem: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            dem.set_row_data(row, input.get_row_data(row));
+        }
+        let embankment_dir = elongated_ridges(&ridge_dir, &dem, half_width, nodata)?;
+        drop(ridge_dir);
+        if verbose {
+            let mut num_embankment_cells = 0usize;
+            for row in 0..rows {
+                for col in 0..columns {
+                    if embankment_dir.get_value(row, col) >= 0 {
+                        num_embankment_cells += 1;
+                    }
+                }
+            }
+            println!("Number of embankment cells: {}", num_embankment_cells);
+        }
+
+        let num_breaches =
+            breach_embankments(&mut dem, &embankment_dir, half_width, nodata, verbose)?;
+        if verbose {
+            println!("Total number of breaches: {}", num_breaches);
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        for row in 0..rows {
+            output.set_row_data(row, dem.get_row_data(row));
+        }
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Maximum embankment width: {}", width));
+        output.add_metadata_entry(format!("Minimum embankment height: {}", min_height));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        drop(output);
+
+        if !breaches_file.is_empty() {
+            let mut output = Raster::initialize_using_file(&breaches_file, &input);
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = "spectrum.plt".to_string();
+            for row in 0..rows {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if z != nodata {
+                        data[col as usize] = z - dem.get_value(row, col);
+                    }
+                }
+                output.set_row_data(row, data);
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Breach depth file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Retains the ridge cells that belong to elongated ridges, i.e. those for which the cells along
+/// the ridge axis, perpendicular to the ridge profile, are also ridge cells for at least half the
+/// maximum embankment width in both directions. This distinguishes embankments from the rims of
+/// small natural depressions.
+fn elongated_ridges(
+    ridge_dir: &Array2D<i8>,
+    dem: &Array2D<f64>,
+    half_width: isize,
+    nodata: f64,
+) -> Result<Array2D<i8>, Error> {
+    let rows = dem.rows;
+    let columns = dem.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut embankment_dir: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let dir = ridge_dir.get_value(row, col);
+            if dir < 0 {
+                continue;
+            }
+            let axis = [(dir as usize + 2) % 8, (dir as usize + 6) % 8];
+            let is_elongated = axis.iter().all(|&a| {
+                (1..=half_width).all(|i| {
+                    let (r, c) = (row + dy[a] * i, col + dx[a] * i);
+                    ridge_dir.get_value(r, c) >= 0 || dem.get_value(r, c) == nodata
+                })
+            });
+            if is_elongated {
+                embankment_dir.set_value(row, col, dir);
+            }
+        }
+    }
+    Ok(embankment_dir)
+}
+
+/// Breaches the depressions that are impounded by embankment cells, repeating until no further
+/// embankment-impounded depressions are found, and returns the number of breaches.
+fn breach_embankments(
+    dem: &mut Array2D<f64>,
+    embankment_dir: &Array2D<i8>,
+    half_width: isize,
+    nodata: f64,
+    verbose: bool,
+) -> Result<usize, Error> {
+    let rows = dem.rows;
+    let columns = dem.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    // the lowest upstream cells of the breaches made in previous passes
+    let mut breached_inlets: HashSet<(isize, isize)> = HashSet::new();
+    let mut num_breaches = 0usize;
+    for pass in 0..MAX_PASSES {
+        let filled = priority_flood(dem, nodata)?;
+        let (labels, spill) = label_depressions(dem, &filled, nodata)?;
+
+        // the best breach of each depression: (outlet elevation, upstream cell, downstream cell)
+        let mut best: Vec<Option<(f64, (isize, isize), (isize, isize))>> = vec![None; spill.len()];
+        let mut skip = vec![false; spill.len()];
+        for &(row, col) in &breached_inlets {
+            let label = labels.get_value(row, col);
+            if label >= 0 {
+                skip[label as usize] = true; // the residual of a breached depression
+            }
+        }
+        for row in 0..rows {
+            for col in 0..columns {
+                let dir = embankment_dir.get_value(row, col);
+                if dir < 0 {
+                    continue;
+                }
+                // the lowest cell on either side of the embankment
+                let mut lowest = [(row, col); 2];
+                for (side, d) in [dir as usize, dir as usize + 4].iter().enumerate() {
+                    let mut z_min = f64::INFINITY;
+                    for i in 1..=half_width {
+                        let (r, c) = (row + dy[*d] * i, col + dx[*d] * i);
+                        let zn = dem.get_value(r, c);
+                        if zn != nodata && zn < z_min {
+                            z_min = zn;
+                            lowest[side] = (r, c);
+                        }
+                    }
+                }
+                if lowest[0] == (row, col) || lowest[1] == (row, col) {
+                    continue;
+                }
+                for &(up, down) in &[(lowest[0], lowest[1]), (lowest[1], lowest[0])] {
+                    let label = labels.get_value(up.0, up.1);
+                    if label < 0
+                        || skip[label as usize]
+                        || labels.get_value(down.0, down.1) == label
+                    {
+                        continue;
+                    }
+                    let z_up = dem.get_value(up.0, up.1);
+                    let z_down = dem.get_value(down.0, down.1);
+                    let outlet = z_up.max(z_down);
+                    if z_down >= spill[label as usize] || outlet >= spill[label as usize] {
+                        continue; // the breach would not lower the depression's outlet
+                    }
+                    let is_better = match best[label as usize] {
+                        Some((z, _, _)) => outlet < z,
+                        None => true,
+                    };
+                    if is_better {
+                        best[label as usize] = Some((outlet, up, down));
+                    }
+                }
+            }
+        }
+
+        // carve the breaches
+        let mut num_lowered = 0usize;
+        for b in best.iter() {
+            if let Some((_, up, down)) = *b {
+                let z_up = dem.get_value(up.0, up.1);
+                let z_down = dem.get_value(down.0, down.1);
+                let steps = (up.0 - down.0).abs().max((up.1 - down.1).abs());
+                for i in 1..steps {
+                    let r = up.0 + (down.0 - up.0) * i / steps;
+                    let c = up.1 + (down.1 - up.1) * i / steps;
+                    let z = dem.get_value(r, c);
+                    let z_grade = z_up + (z_down - z_up) * i as f64 / steps as f64;
+                    if z != nodata && z > z_grade {
+                        dem.set_value(r, c, z_grade);
+                        num_lowered += 1;
+                    }
+                }
+                breached_inlets.insert(up);
+                num_breaches += 1;
+            }
+        }
+        if verbose {
+            println!(
+                "Pass {}: {} embankment breaches",
+                pass + 1,
+                best.iter().filter(|b| b.is_some()).count()
+            );
+        }
+        if num_lowered == 0 {
+            break;
+        }
+    }
+    Ok(num_breaches)
+}
+
+/// Fills the depressions in a DEM using the priority-flood method, flooding inwards from the
+/// grid edges and the edges of NoData areas.
+fn priority_flood(dem: &Array2D<f64>, nodata: f64) -> Result<Array2D<f64>, Error> {
+    let rows = dem.rows;
+    let columns = dem.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut filled: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut queued: Array2D<u8> = Array2D::new(rows, columns, 0u8, 1u8)?;
+    let mut minheap = BinaryHeap::new();
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = dem.get_value(row, col);
+            if z == nodata {
+                queued.set_value(row, col, 1u8);
+                continue;
+            }
+            let is_edge = (0..8).any(|i| dem.get_value(row + dy[i], col + dx[i]) == nodata);
+            if is_edge {
+                filled.set_value(row, col, z);
+                queued.set_value(row, col, 1u8);
+                minheap.push(GridCell {
+                    row: row,
+                    column: col,
+                    priority: z,
+                });
+            }
+        }
+    }
+    while let Some(cell) = minheap.pop() {
+        let zf = cell.priority;
+        for i in 0..8 {
+            let (r, c) = (cell.row + dy[i], cell.column + dx[i]);
+            if queued.get_value(r, c) == 0u8 {
+                let z = dem.get_value(r, c).max(zf);
+                filled.set_value(r, c, z);
+                queued.set_value(r, c, 1u8);
+                minheap.push(GridCell {
+                    row: r,
+                    column: c,
+                    priority: z,
+                });
+            }
+        }
+    }
+    Ok(filled)
+}
+
+/// Labels the depressions of a DEM, i.e. the connected areas that are raised by filling to the
+/// same spill elevation, and returns the labels (-1 outside depressions) and spill elevations.
+fn label_depressions(
+    dem: &Array2D<f64>,
+    filled: &Array2D<f64>,
+    nodata: f64,
+) -> Result<(Array2D<i32>, Vec<f64>), Error> {
+    let rows = dem.rows;
+    let columns = dem.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut labels: Array2D<i32> = Array2D::new(rows, columns, -1i32, -1i32)?;
+    let mut spill = vec![];
+    let mut queue = VecDeque::new();
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = dem.get_value(row, col);
+            let zf = filled.get_value(row, col);
+            if z == nodata || zf <= z || labels.get_value(row, col) >= 0 {
+                continue;
+            }
+            let label = spill.len() as i32;
+            spill.push(zf);
+            labels.set_value(row, col, label);
+            queue.push_back((row, col));
+            while let Some((r, c)) = queue.pop_front() {
+                for i in 0..8 {
+                    let (rn, cn) = (r + dy[i], c + dx[i]);
+                    let zn = dem.get_value(rn, cn);
+                    if zn != nodata
+                        && labels.get_value(rn, cn) < 0
+                        && filled.get_value(rn, cn) == zf
+                        && zf > zn
+                    {
+                        labels.set_value(rn, cn, label);
+                        queue.push_back((rn, cn));
+                    }
+                }
+            }
+        }
+    }
+    Ok((labels, spill))
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
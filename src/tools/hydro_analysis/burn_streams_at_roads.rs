@@ -30,7 +30,7 @@ use std::path;
 /// 41(5): 658–668. DOI: 10.1002/esp.3888
 ///
 /// # See Also
-/// `RasterStreamsToVector`, `RasterizeStreams`
+/// `RasterStreamsToVector`, `RasterizeStreams`, `BreachEmbankments`
 pub struct BurnStreamsAtRoads {
     name: String,
     description: String,
@@ -0,0 +1,930 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// This tool can be used to calibrate the flow-accumulation threshold used to extract a stream network
+/// (e.g. with `ExtractStreams`) against stream gauges with known drainage areas. The user must specify
+/// the input stream gauge points (`--gauges`), the name of the attribute field containing the observed
+/// (published) drainage area of each gauge (`--area_field`) and the units of these areas (`--area_units`;
+/// `km2`, `ha`, or `m2`), and the input D8 flow-accumulation raster (`--flow_accum`). The accumulation
+/// raster may contain either the number of upslope grid cells (`--accum_type=cells`, the default) or the
+/// upslope catchment area (`--accum_type=ca`), as produced by the `D8FlowAccumulation` tool. Map units are
+/// assumed to be metres.
+///
+/// Gauge locations rarely coincide exactly with the digital stream network. For a given channelization
+/// threshold, each gauge is therefore snapped to the nearest grid cell, within the snap distance
+/// (`--snap_dist`), that has an accumulation value at or above the threshold, in a manner similar to
+/// `JensonSnapPourPoints`. A gauge is considered to be matched when the relative difference between its
+/// snapped DEM-derived drainage area and the observed area is no greater than the tolerance (`--tolerance`,
+/// 0.1 by default). A range of logarithmically spaced candidate thresholds, from two grid cells up to the
+/// largest observed drainage area, is evaluated and the recommended threshold is the one that matches the
+/// greatest number of gauges, with ties resolved by the lowest mean absolute relative area error and then by
+/// the largest threshold (i.e. the sparsest network).
+///
+/// The output vector (`--output`) contains the gauges at their snapped locations under the recommended
+/// threshold, with the following per-gauge diagnostics (areas in `--area_units`):
+///
+/// | Field | Description |
+/// |-------|-------------|
+/// | GAUGE | Record number of the gauge in the input file |
+/// | OBS_AREA | Observed drainage area |
+/// | AT_AREA | DEM-derived drainage area at the unsnapped gauge location |
+/// | SNAP_AREA | DEM-derived drainage area at the snapped location |
+/// | REL_ERR | Relative area error, (SNAP_AREA - OBS_AREA) / OBS_AREA |
+/// | SNAP_DIST | Distance the gauge was moved |
+/// | BEST_ERR | Smallest relative area error of any cell within the snap distance |
+/// | MATCHED | 1 if the gauge was matched, otherwise 0 |
+///
+/// Gauges for which no grid cell within the snap distance exceeds the threshold are left in place and
+/// possess null SNAP_AREA and REL_ERR values. A large BEST_ERR value indicates that no cell near the gauge
+/// has a drainage area that is consistent with the observed area, which may point to an erroneous gauge
+/// location, an incorrect observed area, or flow-routing errors in the DEM. The calibration report
+/// (`--report`) is an HTML file that summarizes the per-gauge diagnostics and the performance of each
+/// candidate threshold.
+///
+/// # See Also
+/// `ExtractStreams`, `D8FlowAccumulation`, `JensonSnapPourPoints`
+pub struct CalibrateChannelThreshold {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CalibrateChannelThreshold {
+    pub fn new() -> CalibrateChannelThreshold {
+        // public constructor
+        let name = "CalibrateChannelThreshold".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Calibrates a channelization threshold against stream gauges with observed drainage areas.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Stream Gauges File".to_owned(),
+            flags: vec!["--gauges".to_owned()],
+            description: "Input vector stream gauge points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Observed Drainage Area Field Name".to_owned(),
+            flags: vec!["--area_field".to_owned()],
+            description: "Input field name of the observed drainage areas.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--gauges".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Observed Drainage Area Units".to_owned(),
+            flags: vec!["--area_units".to_owned()],
+            description:
+                "Units of the observed drainage areas; one of 'km2' (default), 'ha', and 'm2'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "km2".to_owned(),
+                "ha".to_owned(),
+                "m2".to_owned(),
+            ]),
+            default_value: Some("km2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input D8 Flow Accumulation File".to_owned(),
+            flags: vec!["--flow_accum".to_owned()],
+            description: "Input D8 flow accumulation raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Flow Accumulation Type".to_owned(),
+            flags: vec!["--accum_type".to_owned()],
+            description:
+                "Flow accumulation type; one of 'cells' (default) and 'ca' (catchment area)."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["cells".to_owned(), "ca".to_owned()]),
+            default_value: Some("cells".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Snap Distance (map units)".to_owned(),
+            flags: vec!["--snap_dist".to_owned()],
+            description: "Maximum snap distance in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Relative Area Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Maximum relative area error for a gauge to be considered matched."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Gauges File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector gauge points file containing the calibration diagnostics."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Report File".to_owned(),
+            flags: vec!["--report".to_owned()],
+            description: "Output HTML calibration report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --gauges=gauges.shp --area_field=AREA_KM2 --flow_accum=flow_accum.tif --snap_dist=150.0 -o=calibrated_gauges.shp --report=calibration.html
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --gauges=gauges.shp --area_field=AREA --area_units=ha --flow_accum=ca.tif --accum_type=ca --snap_dist=150.0 --tolerance=0.05 -o=calibrated_gauges.shp --report=calibration.html", short_exe, name).replace("*", &sep);
+
+        CalibrateChannelThreshold {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CalibrateChannelThreshold {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut gauges_file = String::new();
+        let mut area_field = String::new();
+        let mut area_units = String::from("km2");
+        let mut accum_file = String::new();
+        let mut accum_is_cells = true;
+        let mut snap_dist = 0f64;
+        let mut tolerance = 0.1f64;
+        let mut output_file = String::new();
+        let mut report_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-gauges" {
+                gauges_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-area_field" {
+                area_field = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-area_units" {
+                area_units = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-flow_accum" {
+                accum_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-accum_type" {
+                let accum_type = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                accum_is_cells = accum_type.contains("cells");
+            } else if flag_val == "-snap_dist" {
+                snap_dist = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-tolerance" {
+                tolerance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-report" {
+                report_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !gauges_file.contains(&sep) && !gauges_file.contains("/") {
+            gauges_file = format!("{}{}", working_directory, gauges_file);
+        }
+        if !accum_file.contains(&sep) && !accum_file.contains("/") {
+            accum_file = format!("{}{}", working_directory, accum_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if report_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output report file (--report) must be specified.",
+            ));
+        }
+        if !report_file.contains(&sep) && !report_file.contains("/") {
+            report_file = format!("{}{}", working_directory, report_file);
+        }
+        if !report_file.ends_with(".html") {
+            report_file = report_file + ".html";
+        }
+
+        let units_to_m2 = match area_units.as_str() {
+            "km2" | "km" => 1_000_000f64,
+            "ha" => 10_000f64,
+            "m2" | "m" => 1f64,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The area_units must be one of 'km2', 'ha', or 'm2'.",
+                ))
+            }
+        };
+        if snap_dist <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The snap distance (--snap_dist) must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let gauges = Shapefile::read(&gauges_file)?;
+        if gauges.header.shape_type.base_shape_type() != ShapeType::Point {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input gauges vector data must be of point base shape type.",
+            ));
+        }
+        let field_index = match gauges.attributes.get_field_num(&area_field) {
+            Some(i) => i,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The attribute field '{}' does not exist in the gauges file.",
+                        area_field
+                    ),
+                ))
+            }
+        };
+        let field_type = gauges.attributes.get_field(field_index).field_type;
+        if field_type != 'N' && field_type != 'F' && field_type != 'I' && field_type != 'O' {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The drainage area field must be of a numeric data type.",
+            ));
+        }
+
+        let accum = Raster::new(&accum_file, "r")?;
+
+        let start = Instant::now();
+
+        let nodata = accum.configs.nodata;
+        let cell_area = accum.configs.resolution_x * accum.configs.resolution_y;
+        // converts accumulation values into the units of the observed areas
+        let accum_to_units = if accum_is_cells {
+            cell_area / units_to_m2
+        } else {
+            1f64 / units_to_m2
+        };
+
+        // Gather the candidate snap cells surrounding each gauge.
+        let radius_cells = (snap_dist / accum.configs.resolution_x.min(accum.configs.resolution_y))
+            .ceil() as isize;
+        let snap_dist_sqrd = snap_dist * snap_dist;
+        let num_gauges = gauges.num_records;
+        let mut gauge_list: Vec<Gauge> = Vec::with_capacity(num_gauges);
+        for record_num in 0..num_gauges {
+            let record = gauges.get_record(record_num);
+            if record.points.is_empty() {
+                continue;
+            }
+            let obs_area = match gauges.attributes.get_value(record_num, &area_field) {
+                FieldData::Int(val) => val as f64,
+                FieldData::Real(val) => val,
+                _ => f64::NAN,
+            };
+            if !(obs_area > 0f64) {
+                if verbose {
+                    println!(
+                        "Warning: Gauge {} does not have a valid observed drainage area and is ignored.",
+                        record_num + 1
+                    );
+                }
+                continue;
+            }
+            let (x, y) = (record.points[0].x, record.points[0].y);
+            let row = accum.get_row_from_y(y);
+            let col = accum.get_column_from_x(x);
+            let z = accum.get_value(row, col);
+            let at_area = if z != nodata {
+                z * accum_to_units
+            } else {
+                f64::NAN
+            };
+            let mut candidates = vec![];
+            for r in (row - radius_cells)..(row + radius_cells + 1) {
+                for c in (col - radius_cells)..(col + radius_cells + 1) {
+                    let zn = accum.get_value(r, c);
+                    if zn != nodata {
+                        let xn = accum.get_x_from_column(c);
+                        let yn = accum.get_y_from_row(r);
+                        let dist = (xn - x) * (xn - x) + (yn - y) * (yn - y);
+                        if dist <= snap_dist_sqrd {
+                            candidates.push(SnapCell {
+                                x: xn,
+                                y: yn,
+                                dist: dist.sqrt(),
+                                area: zn * accum_to_units,
+                            });
+                        }
+                    }
+                }
+            }
+            // nearest first, so that the first candidate above a threshold is the snapped cell
+            candidates.sort_by(|a, b| a.dist.partial_cmp(&b.dist).unwrap());
+            let best_err = candidates
+                .iter()
+                .map(|c| ((c.area - obs_area) / obs_area).abs())
+                .fold(f64::NAN, f64::min);
+            gauge_list.push(Gauge {
+                record_num: record_num,
+                x: x,
+                y: y,
+                obs_area: obs_area,
+                at_area: at_area,
+                best_err: best_err,
+                candidates: candidates,
+            });
+
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / num_gauges as f64) as usize;
+                if progress != old_progress {
+                    println!("Reading gauges: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if gauge_list.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There are no gauges with valid observed drainage areas.",
+            ));
+        }
+
+        // Evaluate the logarithmically spaced candidate thresholds, in accumulation units.
+        let num_thresholds = 50;
+        let min_threshold = 2f64 * if accum_is_cells { 1f64 } else { cell_area };
+        let max_threshold = (gauge_list
+            .iter()
+            .map(|g| g.obs_area)
+            .fold(f64::NEG_INFINITY, f64::max)
+            / accum_to_units)
+            .max(min_threshold);
+        let mut evaluations: Vec<ThresholdEvaluation> = Vec::with_capacity(num_thresholds);
+        for i in 0..num_thresholds {
+            let threshold = min_threshold
+                * (max_threshold / min_threshold).powf(i as f64 / (num_thresholds - 1) as f64);
+            let threshold_area = threshold * accum_to_units;
+            let mut num_matched = 0usize;
+            let mut num_snapped = 0usize;
+            let mut sum_err = 0f64;
+            for g in &gauge_list {
+                if let Some(c) = g.snap(threshold_area) {
+                    let err = ((c.area - g.obs_area) / g.obs_area).abs();
+                    num_snapped += 1;
+                    sum_err += err;
+                    if err <= tolerance {
+                        num_matched += 1;
+                    }
+                }
+            }
+            evaluations.push(ThresholdEvaluation {
+                threshold: threshold,
+                num_matched: num_matched,
+                num_snapped: num_snapped,
+                mean_err: if num_snapped > 0 {
+                    sum_err / num_snapped as f64
+                } else {
+                    f64::NAN
+                },
+            });
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_thresholds as f64) as usize;
+                if progress != old_progress {
+                    println!("Evaluating thresholds: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut best = 0usize;
+        for i in 1..num_thresholds {
+            let (e, b) = (&evaluations[i], &evaluations[best]);
+            let e_err = if e.mean_err.is_nan() {
+                f64::INFINITY
+            } else {
+                e.mean_err
+            };
+            let b_err = if b.mean_err.is_nan() {
+                f64::INFINITY
+            } else {
+                b.mean_err
+            };
+            // later thresholds are larger, so ties are resolved in their favour
+            if e.num_matched > b.num_matched || (e.num_matched == b.num_matched && e_err <= b_err) {
+                best = i;
+            }
+        }
+        let recommended = evaluations[best].threshold;
+        let recommended_area = recommended * accum_to_units;
+
+        // Output the gauges, snapped using the recommended threshold.
+        let mut output = Shapefile::new(&output_file, ShapeType::Point)?;
+        output.projection = gauges.projection.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("GAUGE", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "OBS_AREA",
+            FieldDataType::Real,
+            14u8,
+            4u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "AT_AREA",
+            FieldDataType::Real,
+            14u8,
+            4u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "SNAP_AREA",
+            FieldDataType::Real,
+            14u8,
+            4u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "REL_ERR",
+            FieldDataType::Real,
+            10u8,
+            4u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "SNAP_DIST",
+            FieldDataType::Real,
+            10u8,
+            2u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "BEST_ERR",
+            FieldDataType::Real,
+            10u8,
+            4u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "MATCHED",
+            FieldDataType::Int,
+            2u8,
+            0u8,
+        ));
+
+        let real_or_null = |v: f64| {
+            if v.is_finite() {
+                FieldData::Real(v)
+            } else {
+                FieldData::Null
+            }
+        };
+        let mut rows_html = String::new();
+        let mut num_matched = 0usize;
+        for (i, g) in gauge_list.iter().enumerate() {
+            let snapped = g.snap(recommended_area);
+            let (x, y, snap_area, snap_dist) = match snapped {
+                Some(c) => (c.x, c.y, c.area, c.dist),
+                None => (g.x, g.y, f64::NAN, 0f64),
+            };
+            let rel_err = (snap_area - g.obs_area) / g.obs_area;
+            let matched = rel_err.abs() <= tolerance;
+            if matched {
+                num_matched += 1;
+            }
+            output.add_point_record(x, y);
+            output.attributes.add_record(
+                vec![
+                    FieldData::Int(i as i32 + 1),
+                    FieldData::Int(g.record_num as i32 + 1),
+                    FieldData::Real(g.obs_area),
+                    real_or_null(g.at_area),
+                    real_or_null(snap_area),
+                    real_or_null(rel_err),
+                    FieldData::Real(snap_dist),
+                    real_or_null(g.best_err),
+                    FieldData::Int(matched as i32),
+                ],
+                false,
+            );
+            rows_html.push_str(&format!(
+                "<tr><td class=\"numberCell\">{}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{:.2}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td></tr>",
+                g.record_num + 1,
+                g.obs_area,
+                format_value(g.at_area),
+                format_value(snap_area),
+                format_value(rel_err),
+                snap_dist,
+                format_value(g.best_err),
+                if matched { "Yes" } else { "No" }
+            ));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!(
+                "Recommended threshold: {:.2} {} ({:.4} {})",
+                recommended,
+                if accum_is_cells {
+                    "cells"
+                } else {
+                    "map units squared"
+                },
+                recommended_area,
+                area_units
+            );
+            println!(
+                "Gauges matched within tolerance: {} of {}",
+                num_matched,
+                gauge_list.len()
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        ///////////////////////
+        // Output the report //
+        ///////////////////////
+        let f = File::create(report_file.clone())?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <html>
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Channel Threshold Calibration</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"
+            </head>
+            <body>
+                <h1>Channel Threshold Calibration Report</h1>
+                <p>"#
+                .as_bytes(),
+        )?;
+
+        writer.write_all(
+            &format!(
+                "<strong>Gauges</strong>: {}<br>",
+                path::Path::new(&gauges_file)
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(
+            &format!(
+                "<strong>Flow accumulation</strong>: {}<br>",
+                path::Path::new(&accum_file)
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+            )
+            .as_bytes(),
+        )?;
+        writer
+            .write_all(&format!("<strong>Snap distance</strong>: {}<br>", snap_dist).as_bytes())?;
+        writer.write_all(
+            &format!(
+                "<strong>Relative area tolerance</strong>: {}<br>",
+                tolerance
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(
+            &format!(
+                "<strong>Recommended threshold</strong>: {:.2} {} ({:.4} {})<br>",
+                recommended,
+                if accum_is_cells {
+                    "cells"
+                } else {
+                    "map units squared"
+                },
+                recommended_area,
+                area_units
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(
+            &format!(
+                "<strong>Gauges matched</strong>: {} of {}<br>",
+                num_matched,
+                gauge_list.len()
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all("</p>".as_bytes())?;
+
+        writer.write_all(
+            &format!(
+                "<p><table>
+            <caption>Gauge Diagnostics (areas in {})</caption>
+            <tr>
+                <th class=\"headerCell\">Gauge</th>
+                <th class=\"headerCell\">Observed Area</th>
+                <th class=\"headerCell\">Area at Gauge</th>
+                <th class=\"headerCell\">Snapped Area</th>
+                <th class=\"headerCell\">Relative Error</th>
+                <th class=\"headerCell\">Snap Distance</th>
+                <th class=\"headerCell\">Best Relative Error</th>
+                <th class=\"headerCell\">Matched</th>
+            </tr>",
+                area_units
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(rows_html.as_bytes())?;
+        writer.write_all("</table></p>".as_bytes())?;
+
+        writer.write_all(
+            &format!(
+                "<p><table>
+            <caption>Candidate Thresholds</caption>
+            <tr>
+                <th class=\"headerCell\">Threshold ({})</th>
+                <th class=\"headerCell\">Threshold ({})</th>
+                <th class=\"headerCell\">Gauges Snapped</th>
+                <th class=\"headerCell\">Gauges Matched</th>
+                <th class=\"headerCell\">Mean Abs. Relative Error</th>
+            </tr>",
+                if accum_is_cells {
+                    "cells"
+                } else {
+                    "map units squared"
+                },
+                area_units
+            )
+            .as_bytes(),
+        )?;
+        for (i, e) in evaluations.iter().enumerate() {
+            let style = if i == best {
+                " style=\"font-weight:bold\""
+            } else {
+                ""
+            };
+            writer.write_all(
+                &format!(
+                    "<tr{}><td class=\"numberCell\">{:.2}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td></tr>",
+                    style,
+                    e.threshold,
+                    e.threshold * accum_to_units,
+                    e.num_snapped,
+                    e.num_matched,
+                    format_value(e.mean_err)
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        writer.write_all("</body>".as_bytes())?;
+        writer.write_all("</html>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+
+            println!("Complete! Please see {} for the report.", report_file);
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn format_value(v: f64) -> String {
+    if v.is_finite() {
+        format!("{:.4}", v)
+    } else {
+        "-".to_string()
+    }
+}
+
+struct SnapCell {
+    x: f64,
+    y: f64,
+    dist: f64,
+    area: f64,
+}
+
+struct Gauge {
+    record_num: usize,
+    x: f64,
+    y: f64,
+    obs_area: f64,
+    at_area: f64,
+    best_err: f64,
+    candidates: Vec<SnapCell>, // sorted by increasing distance
+}
+
+impl Gauge {
+    /// Returns the nearest cell within the snap distance with an area of at least `threshold_area`.
+    fn snap(&self, threshold_area: f64) -> Option<&SnapCell> {
+        self.candidates.iter().find(|c| c.area >= threshold_area)
+    }
+}
+
+struct ThresholdEvaluation {
+    threshold: f64,
+    num_matched: usize,
+    num_snapped: usize,
+    mean_err: f64,
+}
@@ -0,0 +1,665 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::point_in_poly;
+use crate::raster::*;
+use crate::structures::{Array2D, Point2D};
+use crate::tools::*;
+use crate::vector::*;
+use std::collections::VecDeque;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+const DRY: u8 = 0;
+const OCEAN: u8 = 1;
+const FLOODED: u8 = 2;
+const LOW_LYING: u8 = 3;
+
+/// This tool maps the coastal inundation resulting from a given sea level or storm-surge height
+/// (`--level`) over a digital elevation model (DEM; `--dem`). Unlike a simple 'bathtub' threshold
+/// of the DEM, in which every cell lying below the water level is deemed to be flooded, a cell is
+/// only flooded if it is hydrologically connected to the ocean, i.e. if there is a path of cells
+/// lying below the water level that leads from the ocean to the cell. Low-lying areas that are
+/// protected by higher ground, such as dykes, levees, and coastal dunes, therefore remain dry. By
+/// default, cells are connected to their eight neighbours; the `--four_connected` flag restricts
+/// the connections to the four cardinal neighbours, which is a more conservative choice that
+/// prevents water from leaking diagonally through barriers that are one cell wide.
+///
+/// The ocean may be specified with an optional raster (`--ocean`), in which all non-zero,
+/// non-NoData cells are treated as ocean, and which must share the grid of the DEM. Otherwise, the
+/// ocean is taken to be any cell lying below the water level that is on the edge of the DEM or next
+/// to a NoData cell, which is appropriate for DEMs in which the sea is masked as NoData. Note that
+/// in the latter case, inland NoData areas (e.g. masked lakes) also act as sources of flooding.
+///
+/// The output raster (`--output`) contains the water depth, i.e. the water level less the ground
+/// elevation, in flooded cells and zero in dry cells. Ocean cells with valid elevations, e.g. from
+/// bathymetry, are assigned their water depth, but are excluded from the reported flooded area and
+/// volume. The optional classification raster (`--out_class`) distinguishes dry land (0), the ocean
+/// (1), flooded land (2), and low-lying land that lies below the water level but is not connected
+/// to the ocean (3). The flooded area and volume, and the area of disconnected low-lying land, are
+/// reported in the squared horizontal units (and the cubed units) of the DEM.
+///
+/// An optional infrastructure vector (`--infrastructure`), e.g. buildings, roads, or utilities,
+/// may be assessed for flooding, in which case the affected features are written to the output
+/// vector (`--out_features`). Each feature is sampled at the grid cells that it occupies (points),
+/// that it crosses (lines), or whose centres it contains (polygons), and the output copies the
+/// input attributes and adds whether the feature is flooded (FLOODED), the maximum water depth at
+/// the feature (MAX_DEPTH), and the fraction of the sampled cells that are flooded (FLOOD_FRAC).
+///
+/// # See Also
+/// `FillSpillMerge`, `DepthInSink`, `FillDepressions`, `ImpoundmentSizeIndex`
+pub struct CoastalFlooding {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CoastalFlooding {
+    pub fn new() -> CoastalFlooding {
+        // public constructor
+        let name = "CoastalFlooding".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Maps the ocean-connected inundation resulting from a sea level or storm-surge height."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Water Level".to_owned(),
+            flags: vec!["--level".to_owned()],
+            description: "Sea level or storm-surge height, in the elevation units of the DEM."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Ocean File (optional)".to_owned(),
+            flags: vec!["--ocean".to_owned()],
+            description: "Optional input raster in which non-zero cells are the ocean.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Flood Depth File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of flood depths.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Classification File (optional)".to_owned(),
+            flags: vec!["--out_class".to_owned()],
+            description: "Optional output raster classifying dry, ocean, flooded, and disconnected low-lying cells.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Use four-cell connectivity?".to_owned(),
+            flags: vec!["--four_connected".to_owned()],
+            description: "Only connect cells to their four cardinal neighbours.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Infrastructure File (optional)".to_owned(),
+            flags: vec!["--infrastructure".to_owned()],
+            description: "Optional input vector of infrastructure features.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Features File (optional)".to_owned(),
+            flags: vec!["--out_features".to_owned()],
+            description: "Output vector of the flood status of the infrastructure features."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --level=2.5 -o=flood_depth.tif --out_class=flood_class.tif --infrastructure=buildings.shp --out_features=flooded_buildings.shp",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        CoastalFlooding {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CoastalFlooding {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut ocean_file = String::new();
+        let mut output_file = String::new();
+        let mut class_file = String::new();
+        let mut infrastructure_file = String::new();
+        let mut features_file = String::new();
+        let mut level = f64::NAN;
+        let mut four_connected = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-ocean" {
+                ocean_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_class" {
+                class_file = value;
+            } else if flag_val == "-infrastructure" {
+                infrastructure_file = value;
+            } else if flag_val == "-out_features" {
+                features_file = value;
+            } else if flag_val == "-level" {
+                level = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-four_connected" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    four_connected = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !ocean_file.is_empty() && !ocean_file.contains(&sep) && !ocean_file.contains("/") {
+            ocean_file = format!("{}{}", working_directory, ocean_file);
+        }
+        if !class_file.is_empty() && !class_file.contains(&sep) && !class_file.contains("/") {
+            class_file = format!("{}{}", working_directory, class_file);
+        }
+        if !infrastructure_file.is_empty() {
+            if features_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An output features file (--out_features) must be specified along with the infrastructure file.",
+                ));
+            }
+            if !infrastructure_file.contains(&sep) && !infrastructure_file.contains("/") {
+                infrastructure_file = format!("{}{}", working_directory, infrastructure_file);
+            }
+            if !features_file.contains(&sep) && !features_file.contains("/") {
+                features_file = format!("{}{}", working_directory, features_file);
+            }
+        }
+        if !level.is_finite() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A water level (--level) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let ocean = if !ocean_file.is_empty() {
+            // make sure the inputs share the same grid and CRS
+            Some(align_raster_input(&input, Raster::new(&ocean_file, "r")?)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let cell_area = input.configs.resolution_x * input.configs.resolution_y;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let neighbours: Vec<usize> = if four_connected {
+            vec![1, 3, 5, 7]
+        } else {
+            (0..8).collect()
+        };
+
+        // find the ocean cells from which the flood spreads
+        let mut state: Array2D<u8> = Array2D::new(rows, columns, DRY, DRY)?;
+        let mut queue: VecDeque<(isize, isize)> = VecDeque::new();
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if let Some(ref ocean) = ocean {
+                    let v = ocean.get_value(row, col);
+                    if v != 0f64 && v != ocean.configs.nodata {
+                        state.set_value(row, col, OCEAN);
+                        queue.push_back((row, col));
+                    }
+                } else if z != nodata && z < level {
+                    let is_shore = (0..8).any(|n| {
+                        let (rn, cn) = (row + dy[n], col + dx[n]);
+                        rn < 0
+                            || cn < 0
+                            || rn >= rows
+                            || cn >= columns
+                            || input.get_value(rn, cn) == nodata
+                    });
+                    if is_shore {
+                        state.set_value(row, col, FLOODED);
+                        queue.push_back((row, col));
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding the ocean: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // spread the flood through the connected cells lying below the water level
+        if verbose {
+            println!("Flooding...");
+        }
+        while let Some((row, col)) = queue.pop_front() {
+            for &n in neighbours.iter() {
+                let (rn, cn) = (row + dy[n], col + dx[n]);
+                if state.get_value(rn, cn) != DRY {
+                    continue;
+                }
+                let z = input.get_value(rn, cn);
+                if z != nodata && z < level {
+                    state.set_value(rn, cn, FLOODED);
+                    queue.push_back((rn, cn));
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        let (mut num_flooded, mut num_low_lying) = (0usize, 0usize);
+        let mut volume = 0f64;
+        for row in 0..rows {
+            let mut data = vec![nodata; columns as usize];
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                match state.get_value(row, col) {
+                    FLOODED => {
+                        num_flooded += 1;
+                        volume += level - z;
+                        data[col as usize] = level - z;
+                    }
+                    OCEAN => {
+                        data[col as usize] = (level - z).max(0f64);
+                    }
+                    _ => {
+                        if z < level {
+                            state.set_value(row, col, LOW_LYING);
+                            num_low_lying += 1;
+                        }
+                        data[col as usize] = 0f64;
+                    }
+                }
+            }
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let flooded_area = num_flooded as f64 * cell_area;
+        let flooded_volume = volume * cell_area;
+        let low_lying_area = num_low_lying as f64 * cell_area;
+        if verbose {
+            println!("Flooded area: {:.3}", flooded_area);
+            println!("Flooded volume: {:.3}", flooded_volume);
+            println!("Disconnected low-lying area: {:.3}", low_lying_area);
+        }
+
+        output.configs.palette = "blueyellow.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+        if !ocean_file.is_empty() {
+            output.add_metadata_entry(format!("Input ocean file: {}", ocean_file));
+        }
+        output.add_metadata_entry(format!("Water level: {}", level));
+        output.add_metadata_entry(format!("Four-connected: {}", four_connected));
+        output.add_metadata_entry(format!("Flooded area: {}", flooded_area));
+        output.add_metadata_entry(format!("Flooded volume: {}", flooded_volume));
+        output.add_metadata_entry(format!("Disconnected low-lying area: {}", low_lying_area));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !class_file.is_empty() {
+            let mut class = Raster::initialize_using_file(&class_file, &input);
+            class.configs.data_type = DataType::I16;
+            class.configs.nodata = -32768f64;
+            class.reinitialize_values(-32768f64);
+            for row in 0..rows {
+                for col in 0..columns {
+                    let s = state.get_value(row, col);
+                    if input.get_value(row, col) != nodata || s == OCEAN {
+                        class.set_value(row, col, s as f64);
+                    }
+                }
+            }
+            class.configs.palette = "qual.plt".to_string();
+            class.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            class.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            class.add_metadata_entry(format!("Input DEM file: {}", input_file));
+            class.add_metadata_entry(format!("Water level: {}", level));
+            class.add_metadata_entry(
+                "Classes: 0 = dry, 1 = ocean, 2 = flooded, 3 = disconnected low-lying".to_string(),
+            );
+            let _ = match class.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Classification file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !infrastructure_file.is_empty() {
+            if verbose {
+                println!("Assessing the infrastructure...");
+            }
+            let features = Shapefile::read(&infrastructure_file)?;
+            let mut out = Shapefile::initialize_using_file(
+                &features_file,
+                &features,
+                features.header.shape_type,
+                true,
+            )?;
+            out.attributes.add_field(&AttributeField::new(
+                "FLOODED",
+                FieldDataType::Int,
+                1u8,
+                0u8,
+            ));
+            out.attributes.add_field(&AttributeField::new(
+                "MAX_DEPTH",
+                FieldDataType::Real,
+                12u8,
+                4u8,
+            ));
+            out.attributes.add_field(&AttributeField::new(
+                "FLOOD_FRAC",
+                FieldDataType::Real,
+                8u8,
+                4u8,
+            ));
+            let mut num_affected = 0usize;
+            for record_num in 0..features.num_records {
+                let record = features.get_record(record_num);
+                let (mut num_cells, mut num_wet) = (0usize, 0usize);
+                let mut max_depth = 0f64;
+                for (row, col) in sample_cells(record, &input) {
+                    let z = input.get_value(row, col);
+                    if z == nodata {
+                        continue;
+                    }
+                    num_cells += 1;
+                    let s = state.get_value(row, col);
+                    if (s == FLOODED || s == OCEAN) && z < level {
+                        num_wet += 1;
+                        max_depth = max_depth.max(level - z);
+                    }
+                }
+                if num_wet > 0 {
+                    num_affected += 1;
+                }
+                let mut atts = features.attributes.get_record(record_num);
+                atts.push(FieldData::Int(if num_wet > 0 { 1 } else { 0 }));
+                if num_cells > 0 {
+                    atts.push(FieldData::Real(max_depth));
+                    atts.push(FieldData::Real(num_wet as f64 / num_cells as f64));
+                } else {
+                    atts.push(FieldData::Null);
+                    atts.push(FieldData::Null);
+                }
+                out.add_record(record.clone());
+                out.attributes.add_record(atts, false);
+            }
+            if verbose {
+                println!(
+                    "Affected features: {} of {}",
+                    num_affected, features.num_records
+                );
+            }
+            let _ = match out.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Features file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the grid cells occupied by a point, crossed by a line, or covered by a polygon. Lines
+/// and polygon boundaries are sampled at a spacing of half a grid cell, and the cells whose centres
+/// lie within a polygon, but not within its holes, are added.
+fn sample_cells(record: &ShapefileGeometry, input: &Raster) -> Vec<(isize, isize)> {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let step = input.configs.resolution_x.min(input.configs.resolution_y) / 2f64;
+    let mut cells: Vec<(isize, isize)> = vec![];
+    let num_parts = record.num_parts as usize;
+    let part_range = |part: usize| {
+        let start = record.parts[part] as usize;
+        let end = if part < num_parts - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.num_points as usize
+        };
+        start..end
+    };
+    match record.shape_type.base_shape_type() {
+        ShapeType::Point | ShapeType::MultiPoint => {
+            for p in record.points.iter() {
+                cells.push((input.get_row_from_y(p.y), input.get_column_from_x(p.x)));
+            }
+        }
+        ShapeType::PolyLine | ShapeType::Polygon => {
+            for part in 0..num_parts {
+                let points = &record.points[part_range(part)];
+                if points.len() == 1 {
+                    let p = points[0];
+                    cells.push((input.get_row_from_y(p.y), input.get_column_from_x(p.x)));
+                }
+                for i in 1..points.len() {
+                    let (p1, p2) = (points[i - 1], points[i]);
+                    let n = (p1.distance(&p2) / step).ceil().max(1f64) as usize;
+                    for j in 0..=n {
+                        let t = j as f64 / n as f64;
+                        let x = p1.x + t * (p2.x - p1.x);
+                        let y = p1.y + t * (p2.y - p1.y);
+                        cells.push((input.get_row_from_y(y), input.get_column_from_x(x)));
+                    }
+                }
+            }
+            if record.shape_type.base_shape_type() == ShapeType::Polygon {
+                let top = input.get_row_from_y(record.y_max).max(0);
+                let bottom = input.get_row_from_y(record.y_min).min(rows - 1);
+                let left = input.get_column_from_x(record.x_min).max(0);
+                let right = input.get_column_from_x(record.x_max).min(columns - 1);
+                for row in top..=bottom {
+                    for col in left..=right {
+                        let p =
+                            Point2D::new(input.get_x_from_column(col), input.get_y_from_row(row));
+                        let mut inside = false;
+                        for part in 0..num_parts {
+                            let points = &record.points[part_range(part)];
+                            if points.len() > 3 && point_in_poly(&p, points) {
+                                inside = !record.is_hole(part as i32);
+                                if !inside {
+                                    break;
+                                }
+                            }
+                        }
+                        if inside {
+                            cells.push((row, col));
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    cells.retain(|&(row, col)| row >= 0 && col >= 0 && row < rows && col < columns);
+    cells.sort();
+    cells.dedup();
+    cells
+}
@@ -0,0 +1,386 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a D8 flow pointer (flow direction) raster between the encodings used by
+/// WhiteboxTools and other hydrological software, such that intermediate products can be exchanged
+/// between packages. The user must specify the input pointer raster (`--input`), the output raster
+/// (`--output`), and the encodings of the input (`--from`) and output (`--to`) pointers. The
+/// following encodings are supported, where the values correspond to the flow directions east
+/// (E), northeast (NE), north (N), etc.:
+///
+/// | Encoding    | E   | NE  | N   | NW  | W   | SW  | S   | SE  | No flow |
+/// |-------------|-----|-----|-----|-----|-----|-----|-----|-----|---------|
+/// | 'whitebox'  | 2   | 1   | 128 | 64  | 32  | 16  | 8   | 4   | 0       |
+/// | 'esri'      | 1   | 128 | 64  | 32  | 16  | 8   | 4   | 2   | 0       |
+/// | 'taudem'    | 1   | 2   | 3   | 4   | 5   | 6   | 7   | 8   | NoData  |
+/// | 'grass'     | 8   | 1   | 2   | 3   | 4   | 5   | 6   | 7   | 0       |
+///
+/// The GRASS GIS r.watershed tool assigns negative directions to cells whose flow leaves the
+/// region; the absolute values of these directions are used. Cells without a flow direction, such
+/// as pits and edge cells, are written as NoData for the 'taudem' encoding, which does not define a
+/// no-flow value. NoData cells in the input are NoData in the output. An error is returned if the
+/// input raster contains a value that is not valid for the input encoding.
+///
+/// # See Also
+/// `ConvertDinfPointer`, `D8Pointer`
+pub struct ConvertD8Pointer {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ConvertD8Pointer {
+    pub fn new() -> ConvertD8Pointer {
+        // public constructor
+        let name = "ConvertD8Pointer".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Converts a D8 flow pointer raster between the WhiteboxTools, ESRI, TauDEM and GRASS encodings."
+                .to_string();
+
+        let encodings = vec![
+            "whitebox".to_owned(),
+            "esri".to_owned(),
+            "taudem".to_owned(),
+            "grass".to_owned(),
+        ];
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Pointer Encoding".to_owned(),
+            flags: vec!["--from".to_owned()],
+            description: "Encoding of the input pointer; options include 'whitebox', 'esri', 'taudem' and 'grass'.".to_owned(),
+            parameter_type: ParameterType::OptionList(encodings.clone()),
+            default_value: Some("whitebox".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Pointer Encoding".to_owned(),
+            flags: vec!["--to".to_owned()],
+            description: "Encoding of the output pointer; options include 'whitebox', 'esri', 'taudem' and 'grass'.".to_owned(),
+            parameter_type: ParameterType::OptionList(encodings),
+            default_value: Some("taudem".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=D8.tif -o=taudem_p.tif --from=whitebox --to=taudem
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=fdr.tif -o=D8.tif --from=esri --to=whitebox", short_exe, name).replace("*", &sep);
+
+        ConvertD8Pointer {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ConvertD8Pointer {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut from = String::from("whitebox");
+        let mut to = String::from("taudem");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-from" {
+                from = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-to" {
+                to = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let from = PointerEncoding::from_str(&from)?;
+        let to = PointerEncoding::from_str(&to)?;
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::I16;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        if nodata.fract() != 0f64 || nodata.abs() > 32768f64 {
+            output.configs.nodata = -32768f64;
+        }
+        let out_nodata = output.configs.nodata;
+
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    output.set_value(row, col, out_nodata);
+                    continue;
+                }
+                let dir = match from.decode(z) {
+                    Some(dir) => dir,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("An unexpected value ({}) has been identified in the input pointer raster. Check that the input encoding is correct.", z),
+                        ));
+                    }
+                };
+                let value = to.encode(dir).unwrap_or(out_nodata);
+                output.set_value(row, col, value);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PointerEncoding {
+    Whitebox,
+    Esri,
+    TauDem,
+    Grass,
+}
+
+impl PointerEncoding {
+    fn from_str(s: &str) -> Result<PointerEncoding, Error> {
+        if s.contains("white") || s == "wbt" {
+            Ok(PointerEncoding::Whitebox)
+        } else if s.contains("esri") || s.contains("arc") {
+            Ok(PointerEncoding::Esri)
+        } else if s.contains("tau") {
+            Ok(PointerEncoding::TauDem)
+        } else if s.contains("grass") {
+            Ok(PointerEncoding::Grass)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized pointer encoding '{}'; options include 'whitebox', 'esri', 'taudem' and 'grass'.", s),
+            ))
+        }
+    }
+
+    /// Decodes a pointer value into a direction index, numbered clockwise from northeast (0)
+    /// to north (7), or None for an invalid value. Some(None) denotes a cell without a flow
+    /// direction.
+    fn decode(&self, z: f64) -> Option<Option<usize>> {
+        if z.fract() != 0f64 {
+            return None;
+        }
+        match self {
+            PointerEncoding::Whitebox | PointerEncoding::Esri => {
+                if z == 0f64 {
+                    return Some(None);
+                }
+                if z < 0f64 || z > 128f64 || (z as u32).count_ones() != 1 {
+                    return None;
+                }
+                let power = (z as u32).trailing_zeros() as usize;
+                if *self == PointerEncoding::Whitebox {
+                    Some(Some(power))
+                } else {
+                    Some(Some((power + 1) % 8))
+                }
+            }
+            PointerEncoding::TauDem => {
+                if z < 1f64 || z > 8f64 {
+                    return None;
+                }
+                Some(Some((10 - z as usize) % 8))
+            }
+            PointerEncoding::Grass => {
+                let z = z.abs();
+                if z == 0f64 {
+                    return Some(None);
+                }
+                if z > 8f64 {
+                    return None;
+                }
+                Some(Some((9 - z as usize) % 8))
+            }
+        }
+    }
+
+    /// Encodes a direction index (see `decode`), returning None where the encoding does not
+    /// define a value, i.e. for cells without a flow direction in the TauDEM encoding.
+    fn encode(&self, dir: Option<usize>) -> Option<f64> {
+        match dir {
+            None => match self {
+                PointerEncoding::TauDem => None,
+                _ => Some(0f64),
+            },
+            Some(d) => Some(match self {
+                PointerEncoding::Whitebox => (1u32 << d) as f64,
+                PointerEncoding::Esri => (1u32 << ((d + 7) % 8)) as f64,
+                PointerEncoding::TauDem => ((9 - d) % 8 + 1) as f64,
+                PointerEncoding::Grass => ((8 - d) % 8 + 1) as f64,
+            }),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a D-infinity flow pointer raster between the angle conventions used by
+/// WhiteboxTools and TauDEM, such that intermediate products can be exchanged between the two
+/// packages. The user must specify the input pointer raster (`--input`), the output raster
+/// (`--output`), and the conventions of the input (`--from`) and output (`--to`) pointers. The
+/// `DInfPointer` tool outputs flow directions as azimuths, in degrees clockwise from north
+/// ('whitebox'), with a value of -1 for cells without a flow direction. TauDEM's D-infinity flow
+/// direction grids contain angles, in radians counter-clockwise from east, between 0 and 2π
+/// ('taudem'), and cells without a flow direction are NoData.
+///
+/// NoData cells in the input are NoData in the output.
+///
+/// # See Also
+/// `ConvertDinfPointer`, `DInfPointer`
+pub struct ConvertDinfPointer {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ConvertDinfPointer {
+    pub fn new() -> ConvertDinfPointer {
+        // public constructor
+        let name = "ConvertDinfPointer".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Converts a D-infinity flow pointer raster between the WhiteboxTools and TauDEM angle conventions."
+                .to_string();
+
+        let conventions = vec!["whitebox".to_owned(), "taudem".to_owned()];
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input D-infinity Pointer File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster D-infinity pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Pointer Convention".to_owned(),
+            flags: vec!["--from".to_owned()],
+            description:
+                "Angle convention of the input pointer; options include 'whitebox' and 'taudem'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(conventions.clone()),
+            default_value: Some("whitebox".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Pointer Convention".to_owned(),
+            flags: vec!["--to".to_owned()],
+            description:
+                "Angle convention of the output pointer; options include 'whitebox' and 'taudem'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(conventions),
+            default_value: Some("taudem".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dinf.tif -o=taudem_ang.tif --from=whitebox --to=taudem
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=taudem_ang.tif -o=dinf.tif --from=taudem --to=whitebox", short_exe, name).replace("*", &sep);
+
+        ConvertDinfPointer {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ConvertDinfPointer {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut from = String::from("whitebox");
+        let mut to = String::from("taudem");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-from" {
+                from = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-to" {
+                to = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let from_taudem = from.contains("tau");
+        let to_taudem = to.contains("tau");
+        for c in [&from, &to].iter() {
+            if !c.contains("tau") && !c.contains("white") && c.as_str() != "wbt" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unrecognized pointer convention '{}'; options include 'whitebox' and 'taudem'.", c),
+                ));
+            }
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        if nodata == -1f64 {
+            output.configs.nodata = -32768f64;
+        }
+        let out_nodata = output.configs.nodata;
+
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    output.set_value(row, col, out_nodata);
+                    continue;
+                }
+                // the flow direction as an azimuth, in degrees clockwise from north
+                let azimuth = if z < 0f64 {
+                    None
+                } else if from_taudem {
+                    Some(90f64 - z.to_degrees())
+                } else {
+                    Some(z)
+                };
+                let value = match azimuth {
+                    Some(azimuth) => {
+                        let mut a = if to_taudem {
+                            (90f64 - azimuth).rem_euclid(360f64).to_radians()
+                        } else {
+                            azimuth.rem_euclid(360f64)
+                        };
+                        if !to_taudem && a == 0f64 {
+                            a = 360f64; // DInfPointer assigns due north a value of 360
+                        }
+                        if to_taudem && a >= 2f64 * f64::consts::PI {
+                            a = 0f64;
+                        }
+                        a
+                    }
+                    None => {
+                        if to_taudem {
+                            out_nodata
+                        } else {
+                            -1f64
+                        }
+                    }
+                };
+                output.set_value(row, col, value);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,767 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool estimates the direct runoff resulting from a design storm using the Soil Conservation
+/// Service (SCS; now the Natural Resources Conservation Service) curve number method, and
+/// optionally the peak discharge of the storm at a set of pour points using the SCS dimensionless
+/// unit hydrograph. The curve number (CN) of each grid cell is determined from a land-cover raster
+/// (`--landcover`) and a hydrologic soil group raster (`--hsg`), in which soil groups A, B, C, and
+/// D are coded 1, 2, 3, and 4 respectively, using a lookup table (`--cn_table`). The table is a
+/// text file in which each line contains a land-cover class value followed by the curve numbers of
+/// the four soil groups, e.g. `41, 30, 55, 70, 77`, separated by commas, semicolons, spaces, or
+/// tabs. Lines that cannot be parsed, such as a header line, are ignored. The tabulated curve
+/// numbers are assumed to describe average antecedent moisture conditions (AMC II); they may be
+/// adjusted to dry (AMC I) or wet (AMC III) conditions (`--amc`) using the equations of Chow et al.
+/// (1988).
+///
+/// The runoff depth, *Q* (mm), is calculated from the rainfall depth of the storm, *P* (mm;
+/// `--rainfall`), as:
+///
+/// > *Q* = (*P* - *I*<sub>a</sub>)<sup>2</sup> / (*P* - *I*<sub>a</sub> + *S*)
+///
+/// for *P* > *I*<sub>a</sub>, and zero otherwise, where *S* = 25400 / CN - 254 is the potential
+/// maximum retention (mm) and *I*<sub>a</sub> = λ*S* is the initial abstraction. The initial
+/// abstraction ratio, λ, is 0.2 by default (`--ia_ratio`), although a value of 0.05 has been
+/// suggested for many watersheds. The runoff depth is written to the output raster (`--output`),
+/// and the curve numbers may be written to an optional raster (`--out_cn`). Cells with a land-cover
+/// class missing from the table, or with a soil group other than 1-4, are assigned NoData.
+///
+/// Peak discharges are estimated when a vector of pour points (`--pour_pts`), a D8 flow pointer
+/// raster (`--d8_pntr`), a travel-time raster (`--travel_time`), and an output vector
+/// (`--out_peaks`) are specified. The travel-time raster contains the flow travel time (hours) from
+/// each cell to a common downstream location, e.g. the output of the `DownslopeFlowpathLength` tool
+/// weighted by the inverse of the flow velocity, such that the time of concentration
+/// (*T*<sub>c</sub>) of each pour point is the largest difference in travel time between the pour
+/// point and a cell in its watershed. The time to peak is then *T*<sub>p</sub> = *D* / 2 +
+/// 0.6*T*<sub>c</sub>, where *D* is the duration of excess rainfall (hours; `--duration`), which by
+/// default is taken to be the recommended unit duration of 0.133*T*<sub>c</sub>. The peak discharge
+/// (m<sup>3</sup>/s) is *q*<sub>p</sub> = 0.208*AQ* / *T*<sub>p</sub>, where *A* is the watershed
+/// area (km<sup>2</sup>) and *Q* is its average runoff depth (mm). The output vector copies the
+/// pour points and adds the watershed area (AREA_KM2), average curve number (CN), runoff depth
+/// (RUNOFF_MM), runoff volume (VOLUME_M3), time of concentration (TC_HR), time to peak (TP_HR), and
+/// peak discharge (QP_CMS). Pour points should be snapped to the stream network, e.g. using
+/// `JensonSnapPourPoints`, beforehand. By default, the pointer raster is assumed to use the
+/// Whitebox flow-direction encoding; the `--esri_pntr` flag indicates the ESRI encoding.
+///
+/// All of the input rasters must share the same grid. For rasters in geographic coordinates, the
+/// cell area is converted to square metres.
+///
+/// # References
+/// Chow, V. T., Maidment, D. R., and Mays, L. W. (1988). *Applied Hydrology*. McGraw-Hill, New
+/// York.
+///
+/// USDA-NRCS (1986). Urban hydrology for small watersheds. Technical Release 55, Washington, DC.
+///
+/// USDA-NRCS (2007). National Engineering Handbook, Part 630 Hydrology, Chapter 16: Hydrographs.
+///
+/// # See Also
+/// `DownslopeFlowpathLength`, `Watershed`, `JensonSnapPourPoints`, `D8Pointer`
+pub struct CurveNumberRunoff {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CurveNumberRunoff {
+    pub fn new() -> CurveNumberRunoff {
+        // public constructor
+        let name = "CurveNumberRunoff".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Estimates design-storm runoff and peak discharges using the SCS curve number method."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Land Cover File".to_owned(),
+            flags: vec!["--landcover".to_owned()],
+            description: "Input raster of land-cover classes.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Hydrologic Soil Group File".to_owned(),
+            flags: vec!["--hsg".to_owned()],
+            description: "Input raster of hydrologic soil groups (A = 1, B = 2, C = 3, D = 4)."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Curve Number Table".to_owned(),
+            flags: vec!["--cn_table".to_owned()],
+            description:
+                "Input text file of the curve numbers of each land-cover class and soil group."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Rainfall Depth (mm)".to_owned(),
+            flags: vec!["--rainfall".to_owned()],
+            description: "Rainfall depth of the design storm, in millimetres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Runoff Depth File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of runoff depths (mm).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Curve Number File (optional)".to_owned(),
+            flags: vec!["--out_cn".to_owned()],
+            description: "Optional output raster file of curve numbers.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Antecedent Moisture Condition".to_owned(),
+            flags: vec!["--amc".to_owned()],
+            description: "Antecedent moisture condition (I = dry, II = average, III = wet)."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "I".to_owned(),
+                "II".to_owned(),
+                "III".to_owned(),
+            ]),
+            default_value: Some("II".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Initial Abstraction Ratio".to_owned(),
+            flags: vec!["--ia_ratio".to_owned()],
+            description: "Ratio of the initial abstraction to the potential maximum retention."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Pour Points File (optional)".to_owned(),
+            flags: vec!["--pour_pts".to_owned()],
+            description: "Optional input vector pour points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File (optional)".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file, required for peak discharges.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Travel Time File (optional)".to_owned(),
+            flags: vec!["--travel_time".to_owned()],
+            description: "Input raster of flow travel times (hours), required for peak discharges."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Excess Rainfall Duration (hours; optional)".to_owned(),
+            flags: vec!["--duration".to_owned()],
+            description: "Duration of excess rainfall, in hours; defaults to 0.133 Tc.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_pntr".to_owned()],
+            description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Peak Discharge File (optional)".to_owned(),
+            flags: vec!["--out_peaks".to_owned()],
+            description: "Output vector of the runoff and peak discharge at each pour point."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --landcover=landcover.tif --hsg=soil_groups.tif --cn_table=cn.csv --rainfall=75.0 -o=runoff.tif --pour_pts=outlets.shp --d8_pntr=pntr.tif --travel_time=travel_time.tif --out_peaks=peaks.shp",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        CurveNumberRunoff {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CurveNumberRunoff {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut landcover_file = String::new();
+        let mut hsg_file = String::new();
+        let mut table_file = String::new();
+        let mut output_file = String::new();
+        let mut cn_file = String::new();
+        let mut pourpts_file = String::new();
+        let mut pntr_file = String::new();
+        let mut travel_time_file = String::new();
+        let mut peaks_file = String::new();
+        let mut rainfall = f64::NAN;
+        let mut amc = "ii".to_string();
+        let mut ia_ratio = 0.2f64;
+        let mut duration = f64::NAN;
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-landcover" {
+                landcover_file = value;
+            } else if flag_val == "-hsg" {
+                hsg_file = value;
+            } else if flag_val == "-cn_table" {
+                table_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_cn" {
+                cn_file = value;
+            } else if flag_val == "-pour_pts" {
+                pourpts_file = value;
+            } else if flag_val == "-d8_pntr" {
+                pntr_file = value;
+            } else if flag_val == "-travel_time" {
+                travel_time_file = value;
+            } else if flag_val == "-out_peaks" {
+                peaks_file = value;
+            } else if flag_val == "-amc" {
+                amc = value.trim().to_lowercase();
+            } else if flag_val == "-rainfall" {
+                rainfall = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-ia_ratio" {
+                ia_ratio = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-duration" {
+                duration = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    esri_style = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !landcover_file.contains(&sep) && !landcover_file.contains("/") {
+            landcover_file = format!("{}{}", working_directory, landcover_file);
+        }
+        if !hsg_file.contains(&sep) && !hsg_file.contains("/") {
+            hsg_file = format!("{}{}", working_directory, hsg_file);
+        }
+        if !table_file.contains(&sep) && !table_file.contains("/") {
+            table_file = format!("{}{}", working_directory, table_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !cn_file.is_empty() && !cn_file.contains(&sep) && !cn_file.contains("/") {
+            cn_file = format!("{}{}", working_directory, cn_file);
+        }
+        let estimate_peaks = !pourpts_file.is_empty();
+        if estimate_peaks {
+            if pntr_file.is_empty() || travel_time_file.is_empty() || peaks_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The D8 pointer (--d8_pntr), travel time (--travel_time), and output peaks (--out_peaks) files must be specified along with the pour points file.",
+                ));
+            }
+            if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
+                pourpts_file = format!("{}{}", working_directory, pourpts_file);
+            }
+            if !pntr_file.contains(&sep) && !pntr_file.contains("/") {
+                pntr_file = format!("{}{}", working_directory, pntr_file);
+            }
+            if !travel_time_file.contains(&sep) && !travel_time_file.contains("/") {
+                travel_time_file = format!("{}{}", working_directory, travel_time_file);
+            }
+            if !peaks_file.contains(&sep) && !peaks_file.contains("/") {
+                peaks_file = format!("{}{}", working_directory, peaks_file);
+            }
+        }
+        if !rainfall.is_finite() || rainfall < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A non-negative rainfall depth (--rainfall) must be specified.",
+            ));
+        }
+        if ia_ratio < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The initial abstraction ratio (--ia_ratio) must be non-negative.",
+            ));
+        }
+        if amc != "i" && amc != "ii" && amc != "iii" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The antecedent moisture condition (--amc) must be one of I, II, or III.",
+            ));
+        }
+
+        // read the curve number table
+        let f = File::open(&table_file)?;
+        let f = BufReader::new(f);
+        let mut cn_table: HashMap<i64, [f64; 4]> = HashMap::new();
+        for line in f.lines() {
+            let line_unwrapped = line?;
+            // delimiter can be a comma, semicolon, space, or tab.
+            let v: Vec<f64> = line_unwrapped
+                .split(|c| c == ',' || c == ';' || c == ' ' || c == '\t')
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| s.trim().parse::<f64>().unwrap_or(f64::NAN))
+                .collect();
+            if v.len() == 5 && v.iter().all(|x| x.is_finite()) {
+                if v[1..].iter().any(|&cn| cn <= 0f64 || cn > 100f64) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Invalid curve number in the table entry '{}'.",
+                            line_unwrapped
+                        ),
+                    ));
+                }
+                cn_table.insert(v[0].round() as i64, [v[1], v[2], v[3], v[4]]);
+            }
+        }
+        if cn_table.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No valid entries were found in the curve number table.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let landcover = Raster::new(&landcover_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let hsg = align_raster_input(&landcover, Raster::new(&hsg_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = landcover.configs.rows as isize;
+        let columns = landcover.configs.columns as isize;
+        let lc_nodata = landcover.configs.nodata;
+        let hsg_nodata = hsg.configs.nodata;
+        let nodata = -32768f64;
+
+        let mut output = Raster::initialize_using_file(&output_file, &landcover);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = nodata;
+        output.reinitialize_values(nodata);
+        let mut cn_vals: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut num_unmatched = 0usize;
+        for row in 0..rows {
+            let mut data = vec![nodata; columns as usize];
+            for col in 0..columns {
+                let lc = landcover.get_value(row, col);
+                let group = hsg.get_value(row, col);
+                if lc == lc_nodata || group == hsg_nodata {
+                    continue;
+                }
+                let group = group.round() as i64;
+                let cn = match cn_table.get(&(lc.round() as i64)) {
+                    Some(cns) if group >= 1 && group <= 4 => cns[(group - 1) as usize],
+                    _ => {
+                        num_unmatched += 1;
+                        continue;
+                    }
+                };
+                // adjust the curve number for the antecedent moisture condition
+                let cn = if amc == "i" {
+                    4.2 * cn / (10.0 - 0.058 * cn)
+                } else if amc == "iii" {
+                    23.0 * cn / (10.0 + 0.13 * cn)
+                } else {
+                    cn
+                };
+                cn_vals.set_value(row, col, cn);
+                let s = 25400.0 / cn - 254.0;
+                let ia = ia_ratio * s;
+                data[col as usize] = if rainfall > ia {
+                    (rainfall - ia) * (rainfall - ia) / (rainfall - ia + s)
+                } else {
+                    0f64
+                };
+            }
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Runoff depth: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if num_unmatched > 0 && verbose {
+            println!(
+                "Warning: {} cells had a land-cover class or soil group missing from the table.",
+                num_unmatched
+            );
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "blueyellow.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input land cover file: {}", landcover_file));
+        output.add_metadata_entry(format!("Input hydrologic soil group file: {}", hsg_file));
+        output.add_metadata_entry(format!("Input curve number table: {}", table_file));
+        output.add_metadata_entry(format!("Rainfall depth (mm): {}", rainfall));
+        output.add_metadata_entry(format!(
+            "Antecedent moisture condition: {}",
+            amc.to_uppercase()
+        ));
+        output.add_metadata_entry(format!("Initial abstraction ratio: {}", ia_ratio));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if !cn_file.is_empty() {
+            let mut cn_output = Raster::initialize_using_file(&cn_file, &landcover);
+            cn_output.configs.data_type = DataType::F32;
+            cn_output.configs.nodata = nodata;
+            for row in 0..rows {
+                for col in 0..columns {
+                    cn_output.set_value(row, col, cn_vals.get_value(row, col));
+                }
+            }
+            cn_output.configs.palette = "spectrum.plt".to_string();
+            cn_output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            cn_output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            cn_output.add_metadata_entry(format!(
+                "Antecedent moisture condition: {}",
+                amc.to_uppercase()
+            ));
+            let _ = match cn_output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Curve number file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if estimate_peaks {
+            if verbose {
+                println!("Estimating peak discharges...");
+            }
+            let pntr = align_raster_input(&landcover, Raster::new(&pntr_file, "r")?)?;
+            let travel_time = align_raster_input(&landcover, Raster::new(&travel_time_file, "r")?)?;
+            let pourpts = Shapefile::read(&pourpts_file)?;
+            if pourpts.header.shape_type.base_shape_type() != ShapeType::Point {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input pour points vector data must be of point base shape type.",
+                ));
+            }
+            let pntr_nodata = pntr.configs.nodata;
+            let tt_nodata = travel_time.configs.nodata;
+
+            // the cell area, in square metres
+            let mut res_x = landcover.configs.resolution_x;
+            let mut res_y = landcover.configs.resolution_y;
+            if landcover.is_in_geographic_coordinates() {
+                let mid_lat =
+                    ((landcover.configs.north + landcover.configs.south) / 2.0).to_radians();
+                res_x = res_x * 113200.0 * mid_lat.cos();
+                res_y = res_y * 113200.0;
+            }
+            let cell_area = res_x * res_y;
+
+            let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+            let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+            let inflowing_vals = if esri_style {
+                [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64]
+            } else {
+                [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64]
+            };
+
+            let mut out = Shapefile::initialize_using_file(
+                &peaks_file,
+                &pourpts,
+                pourpts.header.shape_type,
+                true,
+            )?;
+            let field_names = [
+                "AREA_KM2",
+                "CN",
+                "RUNOFF_MM",
+                "VOLUME_M3",
+                "TC_HR",
+                "TP_HR",
+                "QP_CMS",
+            ];
+            for name in field_names.iter() {
+                out.attributes.add_field(&AttributeField::new(
+                    name,
+                    FieldDataType::Real,
+                    12u8,
+                    4u8,
+                ));
+            }
+
+            // the watershed of each pour point is found by tracing the pointer upslope
+            let mut visited: Array2D<u32> = Array2D::new(rows, columns, 0u32, 0u32)?;
+            for record_num in 0..pourpts.num_records {
+                let record = pourpts.get_record(record_num);
+                let row = pntr.get_row_from_y(record.points[0].y);
+                let col = pntr.get_column_from_x(record.points[0].x);
+                let mut atts = pourpts.attributes.get_record(record_num);
+                let outlet_time = travel_time.get_value(row, col);
+                if pntr.get_value(row, col) == pntr_nodata || outlet_time == tt_nodata {
+                    if verbose {
+                        println!(
+                            "Warning: pour point {} lies outside of the valid data.",
+                            record_num + 1
+                        );
+                    }
+                    for _ in 0..field_names.len() {
+                        atts.push(FieldData::Null);
+                    }
+                    out.add_record(record.clone());
+                    out.attributes.add_record(atts, false);
+                    continue;
+                }
+
+                let id = (record_num + 1) as u32;
+                let mut stack = vec![(row, col)];
+                visited.set_value(row, col, id);
+                let (mut num_cells, mut num_runoff_cells) = (0usize, 0usize);
+                let (mut sum_cn, mut sum_runoff) = (0f64, 0f64);
+                let mut tc = 0f64;
+                while let Some((r, c)) = stack.pop() {
+                    num_cells += 1;
+                    let q = output.get_value(r, c);
+                    if q != nodata {
+                        num_runoff_cells += 1;
+                        sum_runoff += q;
+                        sum_cn += cn_vals.get_value(r, c);
+                    }
+                    let t = travel_time.get_value(r, c);
+                    if t != tt_nodata && t - outlet_time > tc {
+                        tc = t - outlet_time;
+                    }
+                    for i in 0..8 {
+                        let (rn, coln) = (r + dy[i], c + dx[i]);
+                        if pntr.get_value(rn, coln) == inflowing_vals[i]
+                            && visited.get_value(rn, coln) != id
+                        {
+                            visited.set_value(rn, coln, id);
+                            stack.push((rn, coln));
+                        }
+                    }
+                }
+
+                let area_km2 = num_cells as f64 * cell_area / 1_000_000f64;
+                let (mean_cn, runoff) = if num_runoff_cells > 0 {
+                    (
+                        sum_cn / num_runoff_cells as f64,
+                        sum_runoff / num_runoff_cells as f64,
+                    )
+                } else {
+                    (0f64, 0f64)
+                };
+                let volume = runoff / 1000f64 * num_cells as f64 * cell_area;
+                let d = if duration.is_finite() {
+                    duration
+                } else {
+                    0.133 * tc
+                };
+                let tp = d / 2f64 + 0.6 * tc;
+                let qp = if tp > 0f64 {
+                    0.208 * area_km2 * runoff / tp
+                } else {
+                    0f64
+                };
+                for v in [area_km2, mean_cn, runoff, volume, tc, tp, qp].iter() {
+                    atts.push(FieldData::Real(*v));
+                }
+                out.add_record(record.clone());
+                out.attributes.add_record(atts, false);
+
+                if verbose {
+                    progress = (100.0_f64 * record_num as f64
+                        / (pourpts.num_records - 1).max(1) as f64)
+                        as usize;
+                    if progress != old_progress {
+                        println!("Peak discharges: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            let _ = match out.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Peak discharge file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/016/2017
-Last Modified: 21/02/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -45,8 +45,20 @@ use std::thread;
 /// Grid cells possessing the **NoData** value in the input DEM/pointer raster are assigned the **NoData**
 /// value in the output flow-accumulation image.
 ///
+/// An optional weights raster (`--weights`), e.g. a runoff depth or source loading grid, may be supplied.
+/// In this case, each grid cell contributes its weight, rather than a value of one, to the accumulation
+/// and the main output (`--output`) is the weighted accumulation, scaled according to `--out_type`. The
+/// weights raster must have the same dimensions as the input DEM/pointer. The `--nodata_policy` parameter
+/// determines how **NoData** cells in the weights raster are treated: `ignore` (the default) treats them as
+/// contributing nothing, while `propagate` assigns **NoData** to the weighted accumulation of every cell
+/// that they drain to, such that cells with incomplete upslope information are clearly identified.
+///
+/// The unweighted number of upslope cells (`--out_cells`) and specific contributing area (`--out_sca`)
+/// may also be written during the same pass, which avoids re-computing the flow directions and flow
+/// accumulation for each required output. The `--log` and `--clip` options only affect the main output.
+///
 /// # See Also:
-/// `DInfPointer`, `DInfFlowAccumulation`, `BreachDepressionsLeastCost`, `FillDepressions`
+/// `DInfPointer`, `DInfFlowAccumulation`, `D8MassFlux`, `BreachDepressionsLeastCost`, `FillDepressions`
 pub struct D8FlowAccumulation {
     name: String,
     description: String,
@@ -128,6 +140,46 @@ impl D8FlowAccumulation {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input Weights File (optional)".to_owned(),
+            flags: vec!["--weights".to_owned()],
+            description: "Optional input weights raster (e.g. runoff depth) used in place of unit cell contributions.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Weights NoData Policy".to_owned(),
+            flags: vec!["--nodata_policy".to_owned()],
+            description: "Treatment of NoData weights; one of 'ignore' (default) and 'propagate'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "ignore".to_owned(),
+                "propagate".to_owned(),
+            ]),
+            default_value: Some("ignore".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Cell Count File (optional)".to_owned(),
+            flags: vec!["--out_cells".to_owned()],
+            description: "Optional output raster of the number of upslope cells.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Specific Contributing Area File (optional)".to_owned(),
+            flags: vec!["--out_sca".to_owned()],
+            description: "Optional output raster of the specific contributing area.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -140,7 +192,8 @@ impl D8FlowAccumulation {
             short_exe += ".exe";
         }
         let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=output.tif --out_type='cells'
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=output.tif --out_type='specific catchment area' --log --clip", short_exe, name).replace("*", &sep);
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=output.tif --out_type='specific catchment area' --log --clip
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=runoff_accum.tif --weights=runoff.tif --nodata_policy=propagate --out_cells=cells.tif --out_sca=sca.tif", short_exe, name).replace("*", &sep);
 
         D8FlowAccumulation {
             name: name,
@@ -193,6 +246,10 @@ impl WhiteboxTool for D8FlowAccumulation {
         let mut clip_max = false;
         let mut pntr_input = false;
         let mut esri_style = false;
+        let mut weights_file = String::new();
+        let mut propagate_nodata = false;
+        let mut out_cells_file = String::new();
+        let mut out_sca_file = String::new();
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -252,6 +309,40 @@ impl WhiteboxTool for D8FlowAccumulation {
                     esri_style = true;
                     pntr_input = true;
                 }
+            } else if flag_val == "-weights" {
+                if keyval {
+                    weights_file = vec[1].to_string();
+                } else {
+                    weights_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-nodata_policy" {
+                let policy = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                propagate_nodata = if policy.contains("prop") {
+                    true
+                } else if policy.contains("ignore") {
+                    false
+                } else {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The nodata_policy must be one of 'ignore' or 'propagate'.",
+                    ));
+                };
+            } else if flag_val == "-out_cells" {
+                if keyval {
+                    out_cells_file = vec[1].to_string();
+                } else {
+                    out_cells_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-out_sca" {
+                if keyval {
+                    out_sca_file = vec[1].to_string();
+                } else {
+                    out_sca_file = args[i + 1].to_string();
+                }
             }
         }
 
@@ -272,12 +363,30 @@ impl WhiteboxTool for D8FlowAccumulation {
         if !output_file.contains(&sep) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
         }
+        if !weights_file.is_empty() && !weights_file.contains(&sep) && !weights_file.contains("/") {
+            weights_file = format!("{}{}", working_directory, weights_file);
+        }
+        if !out_cells_file.is_empty()
+            && !out_cells_file.contains(&sep)
+            && !out_cells_file.contains("/")
+        {
+            out_cells_file = format!("{}{}", working_directory, out_cells_file);
+        }
+        if !out_sca_file.is_empty() && !out_sca_file.contains(&sep) && !out_sca_file.contains("/") {
+            out_sca_file = format!("{}{}", working_directory, out_sca_file);
+        }
 
         if verbose {
             println!("Reading data...")
         };
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
+        let weights = if !weights_file.is_empty() {
+            // make sure the weights share the grid and CRS of the DEM/pointer
+            Some(align_raster_input(&input, Raster::new(&weights_file, "r")?)?)
+        } else {
+            None
+        };
 
         let start = Instant::now();
         let rows = input.configs.rows as isize;
@@ -453,9 +562,43 @@ impl WhiteboxTool for D8FlowAccumulation {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.configs.photometric_interp = PhotometricInterpretation::Continuous; // if the input is a pointer, this may not be the case by default.
+        if output.configs.data_type != DataType::F64 {
+            output.configs.data_type = DataType::F32; // integer pointers would otherwise truncate the accumulation
+        }
         output.reinitialize_values(1.0);
         drop(input);
 
+        // Unweighted cell counts are only tracked separately when they are requested as outputs.
+        let mut cell_counts: Option<Array2D<f64>> =
+            if !out_cells_file.is_empty() || !out_sca_file.is_empty() {
+                Some(Array2D::new(rows, columns, 1f64, nodata)?)
+            } else {
+                None
+            };
+
+        // 1 indicates a cell that is, or drains, a NoData weight under the 'propagate' policy.
+        let mut nodata_drained: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut weighted_nodata_found = false;
+        if let Some(ref w) = weights {
+            let w_nodata = w.configs.nodata;
+            let mut z: f64;
+            for row in 0..rows {
+                for col in 0..columns {
+                    z = w.get_value(row, col);
+                    if z != w_nodata {
+                        output.set_value(row, col, z);
+                    } else {
+                        output.set_value(row, col, 0f64);
+                        if propagate_nodata {
+                            nodata_drained.set_value(row, col, 1u8);
+                            weighted_nodata_found = true;
+                        }
+                    }
+                }
+            }
+        }
+        drop(weights);
+
         // calculate the number of inflowing cells
         let flow_dir = Arc::new(flow_dir);
         let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
@@ -533,6 +676,13 @@ impl WhiteboxTool for D8FlowAccumulation {
                 row_n = row + dy[dir as usize];
                 col_n = col + dx[dir as usize];
                 output.increment(row_n, col_n, fa);
+                if let Some(ref mut counts) = cell_counts {
+                    let count = counts.get_value(row, col);
+                    counts.increment(row_n, col_n, count);
+                }
+                if weighted_nodata_found && nodata_drained.get_value(row, col) == 1u8 {
+                    nodata_drained.set_value(row_n, col_n, 1u8);
+                }
                 num_inflowing.decrement(row_n, col_n, 1i8);
                 if num_inflowing.get_value(row_n, col_n) == 0i8 {
                     stack.push((row_n, col_n));
@@ -587,7 +737,9 @@ impl WhiteboxTool for D8FlowAccumulation {
             for row in 0..rows {
                 for col in 0..columns {
                     // if input[(row, col)] == nodata {
-                    if flow_dir.get_value(row, col) == -2 {
+                    if flow_dir.get_value(row, col) == -2
+                        || nodata_drained.get_value(row, col) == 1u8
+                    {
                         output[(row, col)] = nodata;
                     } else {
                         let dir = flow_dir[(row, col)];
@@ -613,7 +765,9 @@ impl WhiteboxTool for D8FlowAccumulation {
             for row in 0..rows {
                 for col in 0..columns {
                     // if input[(row, col)] == nodata {
-                    if flow_dir.get_value(row, col) == -2 {
+                    if flow_dir.get_value(row, col) == -2
+                        || nodata_drained.get_value(row, col) == 1u8
+                    {
                         output[(row, col)] = nodata;
                     } else {
                         let dir = flow_dir.get_value(row, col);
@@ -653,11 +807,55 @@ impl WhiteboxTool for D8FlowAccumulation {
             self.get_tool_name()
         ));
         output.add_metadata_entry(format!("Input file: {}", input_file));
+        if !weights_file.is_empty() {
+            output.add_metadata_entry(format!("Weights file: {}", weights_file));
+            output.add_metadata_entry(format!(
+                "NoData policy: {}",
+                if propagate_nodata {
+                    "propagate"
+                } else {
+                    "ignore"
+                }
+            ));
+        }
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
             println!("Saving data...")
         };
+
+        if let Some(counts) = cell_counts {
+            let sca_scale = cell_size_x * cell_size_y / avg_cell_size;
+            for (file, scale) in [(&out_cells_file, 1f64), (&out_sca_file, sca_scale)].iter() {
+                if file.is_empty() {
+                    continue;
+                }
+                let mut out = Raster::initialize_using_file(file, &output);
+                for row in 0..rows {
+                    for col in 0..columns {
+                        if flow_dir.get_value(row, col) != -2 {
+                            out.set_value(row, col, counts.get_value(row, col) * scale);
+                        } else {
+                            out.set_value(row, col, nodata);
+                        }
+                    }
+                }
+                out.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                out.add_metadata_entry(format!("Input file: {}", input_file));
+                out.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+                let _ = match out.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Output file written: {}", file)
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+        }
         let _ = match output.write() {
             Ok(_) => {
                 if verbose {
@@ -0,0 +1,581 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter, Error, ErrorKind};
+use std::path;
+
+/// This tool implements a spatially distributed temperature-index (degree-day) model of snow
+/// accumulation and melt. The model is driven by a daily time series of air temperature (°C) and
+/// precipitation (mm) observed at a climate station (`--climate`), which are distributed over a
+/// digital elevation model (DEM; `--dem`) using lapse rates. The climate file is a comma-separated
+/// text file with one record per time step, containing a date (or other time-step label), the
+/// temperature, and the precipitation, e.g. '2026-01-15,-4.2,6.5'; a header line is ignored.
+///
+/// At each time step, the temperature of each grid cell is estimated from the station temperature
+/// and the difference between the cell's elevation and the station elevation (`--station_elev`),
+/// using the temperature lapse rate (`--temp_lapse`, in °C per 1000 m; default -6.5). The
+/// precipitation increases by a fraction (`--precip_gradient`) of the station precipitation per
+/// 1000 m of elevation above the station. Precipitation falls entirely as snow below a threshold
+/// temperature (`--t_snow`; default 0°C) and entirely as rain above a second threshold
+/// (`--t_rain`; default 2°C), with the snow fraction varying linearly in between. Snowmelt (M, in
+/// mm of water equivalent) is calculated as:
+///
+/// > M = DDF RI max(T - T<sub>m</sub>, 0)
+///
+/// where DDF is the degree-day factor (`--ddf`, in mm/°C/day; default 3.0), T is the cell
+/// temperature, T<sub>m</sub> is the melt threshold temperature (`--t_melt`; default 0°C), and RI is
+/// an optional radiation index (`--radiation`), which accounts for the greater melt rates of
+/// sun-exposed slopes (Hock, 1999). The radiation index may be derived from any raster of incoming
+/// radiation, e.g. the output of the `SolarRadiation` tool, and is divided by its mean value, such
+/// that it averages one over the DEM. Melt is limited to the snow water equivalent (SWE) available
+/// in the cell. An optional raster of the initial SWE (`--initial_swe`, in mm) may be specified;
+/// otherwise, the model starts without snow.
+///
+/// Rasters of the SWE and of the melt at the end of each time step are written to the output
+/// directory (`--output`), named swe_*label*.tif and melt_*label*.tif, where *label* is the time
+/// step's date with any non-alphanumeric characters removed. A summary table, summary.csv, of the
+/// DEM-averaged temperature, snowfall, rainfall, melt, and SWE, and the snow-covered fraction of
+/// the DEM at each time step, is also written to the output directory. The DEM elevations are
+/// assumed to be in metres.
+///
+/// # Reference
+/// Hock, R. (1999). A distributed temperature-index ice- and snowmelt model including potential
+/// direct solar radiation. *Journal of Glaciology*, 45(149), 101-111.
+///
+/// Hock, R. (2003). Temperature index melt modelling in mountain areas. *Journal of Hydrology*,
+/// 282(1-4), 104-115.
+///
+/// # See Also
+/// `SolarRadiation`, `FillSpillMerge`
+pub struct DegreeDaySnowmelt {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DegreeDaySnowmelt {
+    pub fn new() -> DegreeDaySnowmelt {
+        // public constructor
+        let name = "DegreeDaySnowmelt".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Models snow accumulation and melt over a DEM using a distributed temperature-index (degree-day) model.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Climate File".to_owned(),
+            flags: vec!["--climate".to_owned()],
+            description:
+                "Input CSV file of daily station data (date, temperature in °C, precipitation in mm)."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Station Elevation".to_owned(),
+            flags: vec!["--station_elev".to_owned()],
+            description: "Elevation of the climate station, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Radiation File (optional)".to_owned(),
+            flags: vec!["--radiation".to_owned()],
+            description: "Optional input raster of incoming radiation, used as a radiation index."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Initial SWE File (optional)".to_owned(),
+            flags: vec!["--initial_swe".to_owned()],
+            description: "Optional input raster of the initial snow water equivalent, in mm."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output directory.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Degree-Day Factor (mm/°C/day)".to_owned(),
+            flags: vec!["--ddf".to_owned()],
+            description: "Degree-day melt factor, in mm/°C/day.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("3.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Melt Threshold Temperature (°C)".to_owned(),
+            flags: vec!["--t_melt".to_owned()],
+            description: "Temperature above which melt occurs.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "All-Snow Threshold Temperature (°C)".to_owned(),
+            flags: vec!["--t_snow".to_owned()],
+            description: "Temperature below which precipitation falls as snow.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "All-Rain Threshold Temperature (°C)".to_owned(),
+            flags: vec!["--t_rain".to_owned()],
+            description: "Temperature above which precipitation falls as rain.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Temperature Lapse Rate (°C/1000 m)".to_owned(),
+            flags: vec!["--temp_lapse".to_owned()],
+            description: "Change in temperature per 1000 m increase in elevation.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("-6.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Precipitation Gradient (fraction/1000 m)".to_owned(),
+            flags: vec!["--precip_gradient".to_owned()],
+            description: "Fractional increase in precipitation per 1000 m increase in elevation."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --climate=station.csv --station_elev=1250.0 --radiation=solar.tif -o=snow --ddf=3.5 --temp_lapse=-6.0 --precip_gradient=0.08",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DegreeDaySnowmelt {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DegreeDaySnowmelt {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut climate_file = String::new();
+        let mut station_elev = f64::NAN;
+        let mut radiation_file = String::new();
+        let mut swe_file = String::new();
+        let mut output_dir = String::new();
+        let mut ddf = 3.0f64;
+        let mut t_melt = 0.0f64;
+        let mut t_snow = 0.0f64;
+        let mut t_rain = 2.0f64;
+        let mut temp_lapse = -6.5f64;
+        let mut precip_gradient = 0.0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-climate" {
+                climate_file = value;
+            } else if flag_val == "-radiation" {
+                radiation_file = value;
+            } else if flag_val == "-initial_swe" {
+                swe_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_dir = value;
+            } else if flag_val == "-station_elev"
+                || flag_val == "-ddf"
+                || flag_val == "-t_melt"
+                || flag_val == "-t_snow"
+                || flag_val == "-t_rain"
+                || flag_val == "-temp_lapse"
+                || flag_val == "-precip_gradient"
+            {
+                let v = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-station_elev" => station_elev = v,
+                    "-ddf" => ddf = v,
+                    "-t_melt" => t_melt = v,
+                    "-t_snow" => t_snow = v,
+                    "-t_rain" => t_rain = v,
+                    "-temp_lapse" => temp_lapse = v,
+                    _ => precip_gradient = v,
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        for file in [
+            &mut input_file,
+            &mut climate_file,
+            &mut radiation_file,
+            &mut swe_file,
+            &mut output_dir,
+        ]
+        .iter_mut()
+        {
+            if !file.is_empty() && !file.contains(&sep) && !file.contains("/") {
+                **file = format!("{}{}", working_directory, file);
+            }
+        }
+        if !output_dir.ends_with(&sep) && !output_dir.ends_with("/") {
+            output_dir.push_str(&sep);
+        }
+        if station_elev.is_nan() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The elevation of the climate station (--station_elev) must be specified.",
+            ));
+        }
+        if t_rain < t_snow {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The all-rain threshold temperature must not be less than the all-snow threshold.",
+            ));
+        }
+        if ddf < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The degree-day factor must not be negative.",
+            ));
+        }
+
+        // read the climate time series
+        let f = match File::open(&climate_file) {
+            Ok(v) => v,
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Error opening the climate CSV file.",
+                ));
+            }
+        };
+        let mut time_steps: Vec<(String, f64, f64)> = vec![];
+        for line in BufReader::new(f).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(",").map(|s| s.trim()).collect();
+            if fields.len() < 3 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The climate record '{}' does not contain three fields.",
+                        line
+                    ),
+                ));
+            }
+            match (fields[1].parse::<f64>(), fields[2].parse::<f64>()) {
+                (Ok(t), Ok(p)) => time_steps.push((fields[0].to_string(), t, p.max(0f64))),
+                _ => {
+                    if !time_steps.is_empty() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Error parsing the climate record '{}'.", line),
+                        ));
+                    } // otherwise, it is the header
+                }
+            }
+        }
+        if time_steps.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The climate file does not contain any records.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let start = Instant::now();
+
+        // the radiation index, i.e. the radiation divided by its mean
+        let mut radiation_index: Array2D<f64> = Array2D::new(rows, columns, 1f64, 1f64)?;
+        if !radiation_file.is_empty() {
+            let radiation = align_raster_input(&input, Raster::new(&radiation_file, "r")?)?;
+            let rad_nodata = radiation.configs.nodata;
+            let (mut sum, mut n) = (0f64, 0f64);
+            for row in 0..rows {
+                for col in 0..columns {
+                    let r = radiation.get_value(row, col);
+                    if r != rad_nodata && input.get_value(row, col) != nodata {
+                        sum += r;
+                        n += 1f64;
+                    }
+                }
+            }
+            if n == 0f64 || sum <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The radiation raster does not contain any positive values within the DEM.",
+                ));
+            }
+            let mean = sum / n;
+            for row in 0..rows {
+                for col in 0..columns {
+                    let r = radiation.get_value(row, col);
+                    if r != rad_nodata {
+                        radiation_index.set_value(row, col, r.max(0f64) / mean);
+                    }
+                }
+            }
+        }
+
+        let mut swe: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        if !swe_file.is_empty() {
+            let initial = align_raster_input(&input, Raster::new(&swe_file, "r")?)?;
+            let swe_nodata = initial.configs.nodata;
+            for row in 0..rows {
+                for col in 0..columns {
+                    let s = initial.get_value(row, col);
+                    if s != swe_nodata {
+                        swe.set_value(row, col, s.max(0f64));
+                    }
+                }
+            }
+        }
+
+        fs::create_dir_all(&output_dir)?;
+        let f = File::create(format!("{}summary.csv", output_dir))?;
+        let mut summary = BufWriter::new(f);
+        summary.write_all("DATE,TEMPERATURE,SNOWFALL,RAINFALL,MELT,SWE,SNOW_COVER\n".as_bytes())?;
+
+        let num_steps = time_steps.len();
+        let mut melt: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        for (step, (date, t_station, p_station)) in time_steps.iter().enumerate() {
+            let (mut sum_t, mut sum_snow, mut sum_rain, mut sum_melt, mut sum_swe) =
+                (0f64, 0f64, 0f64, 0f64, 0f64);
+            let (mut num_cells, mut num_snow) = (0usize, 0usize);
+            for row in 0..rows {
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if z == nodata {
+                        continue;
+                    }
+                    let dz = (z - station_elev) / 1000f64;
+                    let t = t_station + temp_lapse * dz;
+                    let p = (p_station * (1f64 + precip_gradient * dz)).max(0f64);
+                    let snow_fraction = if t <= t_snow {
+                        1f64
+                    } else if t >= t_rain {
+                        0f64
+                    } else {
+                        (t_rain - t) / (t_rain - t_snow)
+                    };
+                    let snowfall = p * snow_fraction;
+                    let mut s = swe.get_value(row, col) + snowfall;
+                    let m = if t > t_melt {
+                        (ddf * radiation_index.get_value(row, col) * (t - t_melt)).min(s)
+                    } else {
+                        0f64
+                    };
+                    s -= m;
+                    swe.set_value(row, col, s);
+                    melt.set_value(row, col, m);
+
+                    sum_t += t;
+                    sum_snow += snowfall;
+                    sum_rain += p - snowfall;
+                    sum_melt += m;
+                    sum_swe += s;
+                    num_cells += 1;
+                    if s > 0f64 {
+                        num_snow += 1;
+                    }
+                }
+            }
+
+            let n = (num_cells as f64).max(1f64);
+            summary.write_all(
+                format!(
+                    "{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.4}\n",
+                    date,
+                    sum_t / n,
+                    sum_snow / n,
+                    sum_rain / n,
+                    sum_melt / n,
+                    sum_swe / n,
+                    num_snow as f64 / n
+                )
+                .as_bytes(),
+            )?;
+
+            let label: String = date.chars().filter(|c| c.is_alphanumeric()).collect();
+            let label = if label.is_empty() {
+                format!("{}", step + 1)
+            } else {
+                label
+            };
+            for (prefix, data) in [("swe", &swe), ("melt", &melt)].iter() {
+                let file_name = format!("{}{}_{}.tif", output_dir, prefix, label);
+                let mut output = Raster::initialize_using_file(&file_name, &input);
+                output.configs.data_type = DataType::F32;
+                output.configs.palette = "blueyellow.plt".to_string();
+                for row in 0..rows {
+                    let mut values = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        if input.get_value(row, col) != nodata {
+                            values[col as usize] = data.get_value(row, col);
+                        }
+                    }
+                    output.set_row_data(row, values);
+                }
+                output.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+                output.add_metadata_entry(format!("Time step: {}", date));
+                output.write()?;
+            }
+
+            if verbose {
+                println!(
+                    "Time step {} of {} ({}): mean SWE {:.2} mm, melt {:.2} mm",
+                    step + 1,
+                    num_steps,
+                    date,
+                    sum_swe / n,
+                    sum_melt / n
+                );
+            }
+        }
+        summary.flush()?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Outputs written to {}", output_dir);
+            println!("Elapsed Time (including I/O): {}", elapsed_time);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::depression_tree::{DepressionTree, OCEAN};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use serde_json::json;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path;
+
+/// This tool builds the hierarchy of nested depressions in a digital elevation model (DEM; `--dem`),
+/// i.e. which depressions merge with one another, and at which spill elevations, as they fill with
+/// water (Barnes et al., 2020). The leaves of the hierarchy are the catchments of the DEM's pits, i.e.
+/// the areas that drain to each pit by steepest descent. When a depression fills to its spill
+/// elevation, it either merges with a neighbouring depression, forming a meta-depression that
+/// contains both, or overflows towards the grid edge, referred to as the 'ocean'. Meta-depressions
+/// merge in turn, such that the hierarchy is a forest of binary trees. The hierarchy is the basis of
+/// the fill-spill-merge runoff model (see `FillSpillMerge`) and of the analysis of depression storage
+/// with changing water levels.
+///
+/// The hierarchy is written to a JSON file (`--out_json`) with a record for each depression,
+/// containing: its `id`; its `parent` meta-depression (zero for depressions that overflow to the
+/// ocean); its `children`, which are empty for leaf depressions; the row, column, and elevation of its
+/// `pit`, i.e. its lowest cell; its `spill_elevation`; the `outlet` cell over which it spills and the
+/// `overflow_into` leaf depression into whose catchment the water spills (zero for the ocean); and its
+/// `cell_count`, `area`, `volume`, and `max_depth` when filled to the spill elevation. Areas are in
+/// squared map units and volumes in squared map units times elevation units. Depression ids are
+/// also the values of the output raster.
+///
+/// The output raster (`--output`) labels each cell with the id of its leaf depression catchment;
+/// cells that drain to the ocean are assigned zero. If a stage elevation (`--stage`) is specified,
+/// the raster instead labels each cell that would be submerged if each depression filled to the
+/// stage elevation, or to its spill elevation if it is lower, with the id of the body of water that
+/// contains it. Depressions that spill below the stage have merged with their neighbours, such that
+/// the bodies of water are identified by meta-depressions. Cells that would not be submerged are
+/// assigned NoData.
+///
+/// # Reference
+/// Barnes, R., Callaghan, K. L., and Wickert, A. D. (2020). Computing water flow through complex
+/// landscapes - Part 2: Finding hierarchies in depressions and morphological segmentations. *Earth
+/// Surface Dynamics*, 8(2), 431-445.
+///
+/// # See Also
+/// `FillSpillMerge`, `DepthInSink`, `Sink`, `StochasticDepressionAnalysis`
+pub struct DepressionHierarchy {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DepressionHierarchy {
+    pub fn new() -> DepressionHierarchy {
+        // public constructor
+        let name = "DepressionHierarchy".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Builds the hierarchy of nested depressions in a DEM, with their spill elevations and volumes.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Depression Labels File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of depression labels.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Hierarchy JSON File".to_owned(),
+            flags: vec!["--out_json".to_owned()],
+            description: "Output JSON file of the depression hierarchy.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Stage Elevation (optional)".to_owned(),
+            flags: vec!["--stage".to_owned()],
+            description:
+                "Optional water surface elevation at which to label submerged depressions."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=depressions.tif --out_json=hierarchy.json --stage=245.5",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DepressionHierarchy {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DepressionHierarchy {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut json_file = String::new();
+        let mut stage: Option<f64> = None;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_json" {
+                json_file = value;
+            } else if flag_val == "-stage" {
+                stage = Some(
+                    value
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)),
+                );
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if json_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output JSON file (--out_json) must be specified.",
+            ));
+        }
+        if !json_file.contains(&sep) && !json_file.contains("/") {
+            json_file = format!("{}{}", working_directory, json_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let cell_area = input.configs.resolution_x * input.configs.resolution_y;
+
+        let mut dem: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            dem.set_row_data(row, input.get_row_data(row));
+        }
+
+        if verbose {
+            println!("Building the depression hierarchy...");
+        }
+        let tree = DepressionTree::new(
+            &dem,
+            nodata,
+            input.configs.resolution_x,
+            input.configs.resolution_y,
+        )?;
+        if verbose {
+            println!(
+                "Number of depressions: {} ({} pits)",
+                tree.depressions.len(),
+                tree.num_leaves
+            );
+        }
+
+        // depression ids are one-based, with zero representing the ocean
+        let id = |d: Option<usize>| -> usize {
+            match d {
+                Some(i) => i + 1,
+                None => 0,
+            }
+        };
+        let mut records = vec![];
+        for (i, d) in tree.depressions.iter().enumerate() {
+            let spill = if d.spill_elevation.is_finite() {
+                json!(d.spill_elevation)
+            } else {
+                json!(null)
+            };
+            records.push(json!({
+                "id": i + 1,
+                "parent": id(d.parent),
+                "children": d.children.iter().map(|&c| c + 1).collect::<Vec<usize>>(),
+                "pit": { "row": d.pit.0, "column": d.pit.1, "elevation": d.pit_elevation },
+                "spill_elevation": spill,
+                "outlet": { "row": d.outlet.0, "column": d.outlet.1 },
+                "overflow_into": id(d.overflow_leaf),
+                "cell_count": d.cell_count,
+                "area": d.cell_count as f64 * cell_area,
+                "volume": d.volume * cell_area,
+                "max_depth": if d.spill_elevation.is_finite() {
+                    d.spill_elevation - d.pit_elevation
+                } else {
+                    0f64
+                },
+            }));
+        }
+        let report = json!({
+            "dem": input_file,
+            "num_depressions": tree.depressions.len(),
+            "num_leaves": tree.num_leaves,
+            "roots": tree
+                .depressions
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| d.parent.is_none())
+                .map(|(i, _)| i + 1)
+                .collect::<Vec<usize>>(),
+            "depressions": records,
+        });
+        let f = File::create(&json_file)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(
+            serde_json::to_string_pretty(&report)
+                .map_err(|e| Error::new(ErrorKind::Other, e))?
+                .as_bytes(),
+        )?;
+        writer.flush()?;
+        if verbose {
+            println!("Depression hierarchy written to {}", json_file);
+        }
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::I32;
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        for row in 0..rows {
+            let mut data = vec![out_nodata; columns as usize];
+            for col in 0..columns {
+                let label = tree.labels.get_value(row, col);
+                match stage {
+                    None => {
+                        if label >= 0 {
+                            data[col as usize] = (label + 1) as f64;
+                        } else if label == OCEAN {
+                            data[col as usize] = 0f64;
+                        }
+                    }
+                    Some(s) => {
+                        if label >= 0 {
+                            let z = dem.get_value(row, col);
+                            // water rises to the stage, unless the body overflows to the ocean,
+                            // in which case it stands at the spill elevation of its root
+                            let (body, level) = match tree.depression_at_stage(label as usize, s) {
+                                Some(i) => (i, s),
+                                None => {
+                                    let mut i = label as usize;
+                                    while let Some(p) = tree.depressions[i].parent {
+                                        i = p;
+                                    }
+                                    (i, tree.depressions[i].spill_elevation)
+                                }
+                            };
+                            if z < level {
+                                data[col as usize] = (body + 1) as f64;
+                            }
+                        }
+                    }
+                }
+            }
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        if let Some(s) = stage {
+            output.add_metadata_entry(format!("Stage elevation: {}", s));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
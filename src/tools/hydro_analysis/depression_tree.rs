@@ -0,0 +1,397 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The depression hierarchy (tree) shared by the DepressionHierarchy and FillSpillMerge tools,
+following Barnes et al. (2020). Each cell is assigned to the catchment of the pit that it drains
+to by steepest descent, or to the 'ocean' if it drains to the grid edge or to NoData. The pits'
+catchments are the leaves of the hierarchy. The outlets between neighbouring catchments, i.e. the
+lowest saddles along their shared boundaries, are then processed in order of increasing
+elevation; where an outlet links two depressions, they are merged into a new meta-depression at
+the outlet's elevation, and where it links a depression to the ocean, the depression overflows
+to the ocean. Because every cell that drains to a pit by steepest descent and lies below the
+pit's spill elevation is connected to the pit below that elevation, depression volumes can be
+calculated from the catchment labels without flooding the DEM.
+
+Reference:
+Barnes, R., Callaghan, K. L., and Wickert, A. D. (2020). Computing water flow through complex
+landscapes - Part 2: Finding hierarchies in depressions and morphological segmentations. Earth
+Surface Dynamics, 8(2), 431-445.
+*/
+
+use crate::structures::Array2D;
+use std::cmp::Ordering::Equal;
+use std::collections::{HashMap, VecDeque};
+use std::f64;
+use std::io::Error;
+
+/// The label of cells that drain to the grid edge or to NoData.
+pub const OCEAN: i32 = -1;
+/// The label of NoData cells.
+pub const NO_LABEL: i32 = -2;
+// the label of the cells of a pit that is being grouped
+const PENDING: i32 = -3;
+
+const DX: [isize; 8] = [1, 1, 1, 0, -1, -1, -1, 0];
+const DY: [isize; 8] = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+/// A depression, either a pit's catchment (a leaf) or the meta-depression formed where two
+/// depressions merge.
+#[derive(Clone, Debug)]
+pub struct Depression {
+    /// The parent meta-depression, or `None` if the depression overflows to the ocean.
+    pub parent: Option<usize>,
+    /// The two depressions that merge to form a meta-depression; empty for leaves.
+    pub children: Vec<usize>,
+    /// The lowest cell in the depression.
+    pub pit: (isize, isize),
+    pub pit_elevation: f64,
+    /// The elevation at which the depression overflows, i.e. its outlet's elevation.
+    pub spill_elevation: f64,
+    /// The cell on the depression's side of its outlet.
+    pub outlet: (isize, isize),
+    /// The cell on the far side of the outlet, into which the depression overflows.
+    pub overflow_cell: (isize, isize),
+    /// The leaf depression into whose catchment the depression overflows, or `None` for the ocean.
+    pub overflow_leaf: Option<usize>,
+    /// The number of cells below the spill elevation.
+    pub cell_count: usize,
+    /// The volume below the spill elevation, in elevation units times cells.
+    pub volume: f64,
+}
+
+impl Depression {
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// The hierarchy of the depressions in a DEM and the leaf-catchment label of each cell.
+pub struct DepressionTree {
+    pub depressions: Vec<Depression>,
+    /// The leaf depression catchment of each cell, `OCEAN`, or `NO_LABEL`.
+    pub labels: Array2D<i32>,
+    /// The steepest-descent direction of each cell, or -1 for pits and edge outlets.
+    pub flow_dir: Array2D<i8>,
+    pub num_leaves: usize,
+}
+
+impl DepressionTree {
+    /// Builds the depression hierarchy of a DEM.
+    pub fn new(
+        dem: &Array2D<f64>,
+        nodata: f64,
+        cell_size_x: f64,
+        cell_size_y: f64,
+    ) -> Result<Self, Error> {
+        let rows = dem.rows;
+        let columns = dem.columns;
+        let diag = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+        let lengths = [
+            diag,
+            cell_size_x,
+            diag,
+            cell_size_y,
+            diag,
+            cell_size_x,
+            diag,
+            cell_size_y,
+        ];
+
+        // steepest-descent flow directions; cells without a lower neighbour are pits or, if
+        // they are on the edge of the data, outlets to the ocean
+        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+        let mut is_edge: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = dem.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                let mut max_slope = 0f64;
+                for n in 0..8 {
+                    let zn = dem.get_value(row + DY[n], col + DX[n]);
+                    if zn == nodata {
+                        is_edge.set_value(row, col, 1u8);
+                        continue;
+                    }
+                    let slope = (z - zn) / lengths[n];
+                    if slope > max_slope {
+                        max_slope = slope;
+                        flow_dir.set_value(row, col, n as i8);
+                    }
+                }
+            }
+        }
+
+        // Group the connected cells of equal elevation without lower neighbours into pits; a
+        // group containing an edge cell drains to the ocean.
+        let mut labels: Array2D<i32> = Array2D::new(rows, columns, NO_LABEL, NO_LABEL)?;
+        let mut depressions: Vec<Depression> = vec![];
+        let mut queue = VecDeque::new();
+        let mut group = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = dem.get_value(row, col);
+                if z == nodata
+                    || flow_dir.get_value(row, col) >= 0
+                    || labels.get_value(row, col) != NO_LABEL
+                {
+                    continue;
+                }
+                group.clear();
+                let mut drains_to_ocean = false;
+                labels.set_value(row, col, PENDING);
+                queue.push_back((row, col));
+                while let Some((r, c)) = queue.pop_front() {
+                    group.push((r, c));
+                    if is_edge.get_value(r, c) == 1u8 {
+                        drains_to_ocean = true;
+                    }
+                    for n in 0..8 {
+                        let (rn, cn) = (r + DY[n], c + DX[n]);
+                        if dem.get_value(rn, cn) == z
+                            && flow_dir.get_value(rn, cn) < 0
+                            && labels.get_value(rn, cn) == NO_LABEL
+                        {
+                            labels.set_value(rn, cn, PENDING);
+                            queue.push_back((rn, cn));
+                        }
+                    }
+                }
+                let label = if drains_to_ocean {
+                    OCEAN
+                } else {
+                    depressions.push(Depression {
+                        parent: None,
+                        children: vec![],
+                        pit: (row, col),
+                        pit_elevation: z,
+                        spill_elevation: f64::INFINITY,
+                        outlet: (row, col),
+                        overflow_cell: (row, col),
+                        overflow_leaf: None,
+                        cell_count: 0,
+                        volume: 0f64,
+                    });
+                    depressions.len() as i32 - 1
+                };
+                for &(r, c) in &group {
+                    labels.set_value(r, c, label);
+                    queue.push_back((r, c));
+                }
+
+                // label the catchment by tracing the flow directions upslope
+                while let Some((r, c)) = queue.pop_front() {
+                    for n in 0..8 {
+                        let (rn, cn) = (r + DY[n], c + DX[n]);
+                        let dir = flow_dir.get_value(rn, cn);
+                        if dir >= 0
+                            && rn + DY[dir as usize] == r
+                            && cn + DX[dir as usize] == c
+                            && labels.get_value(rn, cn) == NO_LABEL
+                        {
+                            labels.set_value(rn, cn, label);
+                            queue.push_back((rn, cn));
+                        }
+                    }
+                }
+            }
+        }
+        let num_leaves = depressions.len();
+
+        // find the lowest outlet between each pair of neighbouring catchments
+        let mut outlets: HashMap<(i32, i32), (f64, (isize, isize), (isize, isize))> =
+            HashMap::new();
+        for row in 0..rows {
+            for col in 0..columns {
+                let label = labels.get_value(row, col);
+                if label == NO_LABEL {
+                    continue;
+                }
+                let z = dem.get_value(row, col);
+                for n in 0..8 {
+                    let (rn, cn) = (row + DY[n], col + DX[n]);
+                    let mut label_n = labels.get_value(rn, cn);
+                    if label_n == NO_LABEL {
+                        label_n = OCEAN; // the edge of the data
+                    }
+                    if label_n == label || (label_n != OCEAN && label_n < label) {
+                        continue; // each pair of neighbouring catchments is visited once
+                    }
+                    let zn = dem.get_value(rn, cn);
+                    let elev = if zn == nodata { z } else { z.max(zn) };
+                    let key = (label, label_n);
+                    let is_lower = match outlets.get(&key) {
+                        Some(o) => elev < o.0,
+                        None => true,
+                    };
+                    if is_lower {
+                        outlets.insert(key, (elev, (row, col), (rn, cn)));
+                    }
+                }
+            }
+        }
+        let mut outlets: Vec<((i32, i32), (f64, (isize, isize), (isize, isize)))> =
+            outlets.into_iter().collect();
+        outlets.sort_by(|a, b| (a.1).0.partial_cmp(&(b.1).0).unwrap_or(Equal));
+
+        // merge the depressions in order of increasing outlet elevation
+        let mut uf: Vec<usize> = (0..num_leaves).collect();
+        let mut drains_to_ocean = vec![false; num_leaves];
+        for ((label1, label2), (elev, cell1, cell2)) in outlets {
+            let root1 = if label1 == OCEAN {
+                None
+            } else {
+                Some(find(&mut uf, label1 as usize))
+            };
+            let root2 = if label2 == OCEAN {
+                None
+            } else {
+                Some(find(&mut uf, label2 as usize))
+            };
+            let root1 = root1.filter(|&r| !drains_to_ocean[r]);
+            let root2 = root2.filter(|&r| !drains_to_ocean[r]);
+            let leaf_of = |label: i32| {
+                if label == OCEAN {
+                    None
+                } else {
+                    Some(label as usize)
+                }
+            };
+            match (root1, root2) {
+                (None, None) => {} // both sides already drain to the ocean
+                (Some(r), None) | (None, Some(r)) => {
+                    let (inside, outside, outside_label) = if root1.is_some() {
+                        (cell1, cell2, label2)
+                    } else {
+                        (cell2, cell1, label1)
+                    };
+                    let d = &mut depressions[r];
+                    d.spill_elevation = elev;
+                    d.outlet = inside;
+                    d.overflow_cell = outside;
+                    d.overflow_leaf = leaf_of(outside_label);
+                    drains_to_ocean[r] = true;
+                }
+                (Some(r1), Some(r2)) => {
+                    if r1 == r2 {
+                        continue;
+                    }
+                    let id = depressions.len();
+                    for &(r, inside, outside, outside_label) in
+                        &[(r1, cell1, cell2, label2), (r2, cell2, cell1, label1)]
+                    {
+                        let d = &mut depressions[r];
+                        d.parent = Some(id);
+                        d.spill_elevation = elev;
+                        d.outlet = inside;
+                        d.overflow_cell = outside;
+                        d.overflow_leaf = leaf_of(outside_label);
+                    }
+                    let (pit, pit_elevation) =
+                        if depressions[r1].pit_elevation <= depressions[r2].pit_elevation {
+                            (depressions[r1].pit, depressions[r1].pit_elevation)
+                        } else {
+                            (depressions[r2].pit, depressions[r2].pit_elevation)
+                        };
+                    depressions.push(Depression {
+                        parent: None,
+                        children: vec![r1, r2],
+                        pit: pit,
+                        pit_elevation: pit_elevation,
+                        spill_elevation: f64::INFINITY,
+                        outlet: pit,
+                        overflow_cell: pit,
+                        overflow_leaf: None,
+                        cell_count: 0,
+                        volume: 0f64,
+                    });
+                    uf.push(id);
+                    drains_to_ocean.push(false);
+                    uf[r1] = id;
+                    uf[r2] = id;
+                }
+            }
+        }
+
+        // Each cell below a depression's spill elevation contributes to the volume of the lowest
+        // depression containing it and, in turn, to each of that depression's ancestors.
+        let n = depressions.len();
+        let mut counts = vec![0usize; n];
+        let mut sums = vec![0f64; n];
+        for row in 0..rows {
+            for col in 0..columns {
+                let label = labels.get_value(row, col);
+                if label < 0 {
+                    continue;
+                }
+                let z = dem.get_value(row, col);
+                let mut node = Some(label as usize);
+                while let Some(i) = node {
+                    if z < depressions[i].spill_elevation {
+                        break;
+                    }
+                    node = depressions[i].parent;
+                }
+                if let Some(i) = node {
+                    counts[i] += 1;
+                    sums[i] += z;
+                }
+            }
+        }
+        // children always precede their parents
+        for i in 0..n {
+            if let Some(p) = depressions[i].parent {
+                counts[p] += counts[i];
+                sums[p] += sums[i];
+            }
+            let d = &mut depressions[i];
+            d.cell_count = counts[i];
+            d.volume = if d.spill_elevation.is_finite() {
+                counts[i] as f64 * d.spill_elevation - sums[i]
+            } else {
+                0f64
+            };
+        }
+
+        Ok(DepressionTree {
+            depressions: depressions,
+            labels: labels,
+            flow_dir: flow_dir,
+            num_leaves: num_leaves,
+        })
+    }
+
+    /// Returns the depression that forms a single body of water containing a leaf depression when
+    /// the water surface is at the specified elevation, i.e. the leaf's lowest ancestor that spills
+    /// above that elevation, or `None` if the water overflows to the ocean.
+    pub fn depression_at_stage(&self, leaf: usize, stage: f64) -> Option<usize> {
+        let mut node = Some(leaf);
+        while let Some(i) = node {
+            if self.depressions[i].spill_elevation > stage {
+                return Some(i);
+            }
+            node = self.depressions[i].parent;
+        }
+        None
+    }
+}
+
+fn find(uf: &mut Vec<usize>, x: usize) -> usize {
+    let mut root = x;
+    while uf[root] != root {
+        root = uf[root];
+    }
+    // path compression
+    let mut i = x;
+    while uf[i] != root {
+        let next = uf[i];
+        uf[i] = root;
+        i = next;
+    }
+    root
+}
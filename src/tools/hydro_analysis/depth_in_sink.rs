@@ -36,7 +36,7 @@ use std::thread;
 /// Modelling, 138(1-3), 247-254.
 ///
 /// # See Also
-/// `FillDepressions`
+/// `FillDepressions`, `DepressionHierarchy`
 pub struct DepthInSink {
     name: String,
     description: String,
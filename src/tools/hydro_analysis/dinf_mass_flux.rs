@@ -257,26 +257,22 @@ impl WhiteboxTool for DInfMassFlux {
         let cell_size_y = input.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
 
-        let efficiency = Arc::new(Raster::new(&efficiency_file, "r")?); // the efficiency raster
-        if efficiency.configs.rows as isize != rows
-            || efficiency.configs.columns as isize != columns
-        {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
-        }
+        // the efficiency raster, which must share the grid and CRS of the DEM
+        let efficiency = Arc::new(align_raster_input(
+            &input,
+            Raster::new(&efficiency_file, "r")?,
+        )?);
         let efficiency_multiplier = if efficiency.configs.maximum > 1f64 {
             0.01f64 // assumpted to be percent...need proportion
         } else {
             1f64
         };
 
-        let absorption = Arc::new(Raster::new(&absorption_file, "r")?); // the absorption raster
-        if absorption.configs.rows as isize != rows
-            || absorption.configs.columns as isize != columns
-        {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
-        }
+        // the absorption raster, which must share the grid and CRS of the DEM
+        let absorption = Arc::new(align_raster_input(
+            &input,
+            Raster::new(&absorption_file, "r")?,
+        )?);
 
         // calculate the flow directions
         let mut flow_dir: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
@@ -480,12 +476,9 @@ impl WhiteboxTool for DInfMassFlux {
         // Create the output image
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        // read in the loading file and initialize output with these data.
-        let loading = Raster::new(&loading_file, "r")?; // the loading raster
-        if loading.configs.rows as isize != rows || loading.configs.columns as isize != columns {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
-        }
+        // read in the loading file, which must share the grid and CRS of the DEM, and
+        // initialize output with these data.
+        let loading = align_raster_input(&input, Raster::new(&loading_file, "r")?)?;
         let load_nodata = absorption.configs.nodata;
 
         if load_nodata == nodata {
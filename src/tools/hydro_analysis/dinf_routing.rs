@@ -0,0 +1,186 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: D-infinity (Tarboton, 1997) flow routing shared by tools that route flow over a DEM with
+the D-infinity method rather than reading a DInfPointer raster. Flow directions are measured in
+degrees clockwise from north, with -1 marking cells that have no downslope neighbour, in the same
+manner as the DInfPointer and DInfFlowAccumulation tools, and the flow of each cell is split
+between the two neighbours bounding its flow direction in proportion to their angular proximity.
+*/
+
+use crate::raster::Raster;
+use crate::structures::Array2D;
+use std::f64::consts::PI;
+use std::io::Error;
+
+/// The column and row offsets of the eight neighbours, clockwise from north.
+const DX: [isize; 8] = [0, 1, 1, 1, 0, -1, -1, -1];
+const DY: [isize; 8] = [-1, -1, 0, 1, 1, 1, 0, -1];
+
+/// Calculates the D-infinity flow direction and the slope gradient (rise over run) in that
+/// direction for each cell of a DEM, using grid resolutions `res_x` and `res_y` (in the units of
+/// the elevations). Cells without a downslope neighbour have a direction of -1 and a slope of zero,
+/// and NoData cells have a direction and slope equal to the DEM's NoData value.
+pub fn dinf_flow_directions(
+    input: &Raster,
+    res_x: f64,
+    res_y: f64,
+) -> Result<(Array2D<f64>, Array2D<f64>), Error> {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let grid_res = (res_x + res_y) / 2.0;
+    let diag_cell_size = (res_x * res_x + res_y * res_y).sqrt();
+    let mut flow_dir: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut slope: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+
+    // the facets of Tarboton (1997), each bounded by a cardinal (e1) and a diagonal (e2) neighbour
+    let ac_vals = [0f64, 1f64, 1f64, 2f64, 2f64, 3f64, 3f64, 4f64];
+    let af_vals = [1f64, -1f64, 1f64, -1f64, 1f64, -1f64, 1f64, -1f64];
+    let e1_col = [1, 0, 0, -1, -1, 0, 0, 1];
+    let e1_row = [0, -1, -1, 0, 0, 1, 1, 0];
+    let e2_col = [1, 1, -1, -1, -1, -1, 1, 1];
+    let e2_row = [-1, -1, -1, -1, 1, 1, 1, 1];
+    let atanof1 = 1.0f64.atan();
+    const HALF_PI: f64 = PI / 2f64;
+
+    for row in 0..rows {
+        for col in 0..columns {
+            let e0 = input.get_value(row, col);
+            if e0 == nodata {
+                continue;
+            }
+            let mut dir = 360.0;
+            let mut max_slope = f64::MIN;
+            for i in 0..8 {
+                let e1 = input.get_value(row + e1_row[i], col + e1_col[i]);
+                let e2 = input.get_value(row + e2_row[i], col + e2_col[i]);
+                if e1 == nodata || e2 == nodata {
+                    continue;
+                }
+                let (r, s) = if e0 > e1 && e0 > e2 {
+                    let s1 = (e0 - e1) / grid_res;
+                    let s2 = (e1 - e2) / grid_res;
+                    let r = if s1 != 0f64 {
+                        (s2 / s1).atan()
+                    } else {
+                        PI / 2.0
+                    };
+                    if r < 0.0 {
+                        (0.0, s1)
+                    } else if r > atanof1 {
+                        (atanof1, (e0 - e2) / diag_cell_size)
+                    } else {
+                        (r, (s1 * s1 + s2 * s2).sqrt())
+                    }
+                } else if e0 > e1 {
+                    (0.0, (e0 - e1) / grid_res)
+                } else if e0 > e2 {
+                    (atanof1, (e0 - e2) / diag_cell_size)
+                } else {
+                    continue;
+                };
+                if s >= max_slope {
+                    max_slope = s;
+                    dir = af_vals[i] * r + ac_vals[i] * HALF_PI;
+                }
+            }
+
+            if max_slope > 0f64 {
+                dir = 360.0 - dir.to_degrees() + 90.0;
+                if dir > 360.0 {
+                    dir = dir - 360.0;
+                }
+                flow_dir.set_value(row, col, dir);
+                slope.set_value(row, col, max_slope);
+            } else {
+                flow_dir.set_value(row, col, -1f64);
+                slope.set_value(row, col, 0f64);
+            }
+        }
+    }
+
+    Ok((flow_dir, slope))
+}
+
+/// Returns the two neighbours of a cell that receive flow in the D-infinity direction `dir`, as
+/// (row, column, proportion) tuples, or None if the cell has no downslope neighbour.
+pub fn dinf_receivers(dir: f64, row: isize, col: isize) -> Option<[(isize, isize, f64); 2]> {
+    if dir < 0f64 {
+        return None;
+    }
+    let dir = dir % 360f64;
+    let k = ((dir / 45f64).floor() as usize).min(7);
+    let p2 = (dir - k as f64 * 45f64) / 45f64;
+    let n = (k + 1) % 8;
+    Some([
+        (row + DY[k], col + DX[k], 1f64 - p2),
+        (row + DY[n], col + DX[n], p2),
+    ])
+}
+
+/// Calculates the D-infinity upslope contributing area of each cell, including the area of the
+/// cell itself, from a grid of flow directions produced by `dinf_flow_directions`. NoData cells
+/// in the flow direction grid receive no flow and are assigned the grid's NoData value.
+pub fn dinf_contributing_area(
+    flow_dir: &Array2D<f64>,
+    cell_area: f64,
+) -> Result<Array2D<f64>, Error> {
+    let rows = flow_dir.rows();
+    let columns = flow_dir.columns();
+    let nodata = flow_dir.nodata();
+    let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if flow_dir.get_value(row, col) != nodata {
+                num_inflowing.set_value(row, col, 0);
+            }
+        }
+    }
+    for row in 0..rows {
+        for col in 0..columns {
+            if flow_dir.get_value(row, col) == nodata {
+                continue;
+            }
+            if let Some(receivers) = dinf_receivers(flow_dir.get_value(row, col), row, col) {
+                for &(rn, cn, p) in receivers.iter() {
+                    if p > 0f64 && flow_dir.get_value(rn, cn) != nodata {
+                        num_inflowing.increment(rn, cn, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut area: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut stack = vec![];
+    for row in 0..rows {
+        for col in 0..columns {
+            if num_inflowing.get_value(row, col) >= 0 {
+                area.set_value(row, col, cell_area);
+                if num_inflowing.get_value(row, col) == 0 {
+                    stack.push((row, col));
+                }
+            }
+        }
+    }
+    while let Some((row, col)) = stack.pop() {
+        let a = area.get_value(row, col);
+        if let Some(receivers) = dinf_receivers(flow_dir.get_value(row, col), row, col) {
+            for &(rn, cn, p) in receivers.iter() {
+                if p > 0f64 && flow_dir.get_value(rn, cn) != nodata {
+                    area.increment(rn, cn, a * p);
+                    num_inflowing.decrement(rn, cn, 1);
+                    if num_inflowing.get_value(rn, cn) == 0 {
+                        stack.push((rn, cn));
+                    }
+                }
+            }
+        }
+    }
+    Ok(area)
+}
@@ -196,7 +196,8 @@ impl WhiteboxTool for DownslopeDistanceToStream {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -212,16 +213,6 @@ impl WhiteboxTool for DownslopeDistanceToStream {
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
         let inflowing_vals = [4i8, 5i8, 6i8, 7i8, 0i8, 1i8, 2i8, 3i8];
 
-        // make sure the input files have the same size
-        if dem.configs.rows != streams.configs.rows
-            || dem.configs.columns != streams.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         /////////////////////////////////////////////
         // Perform the D8 flow pointer calculation //
         /////////////////////////////////////////////
@@ -265,10 +265,8 @@ impl WhiteboxTool for DownslopeFlowpathLength {
             true => {
                 // if verbose { println!("Reading watershed data...") };
                 let r = Raster::new(&watersheds_file, "r")?;
-                if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // make sure the inputs share the same grid and CRS
+                let r = align_raster_input(&pntr, r)?;
                 r.get_data_as_array2d()
             }
         };
@@ -282,10 +280,8 @@ impl WhiteboxTool for DownslopeFlowpathLength {
             true => {
                 // if verbose { println!("Reading weights data...") };
                 let r = Raster::new(&weights_file, "r")?;
-                if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // make sure the inputs share the same grid and CRS
+                let r = align_raster_input(&pntr, r)?;
                 r.get_data_as_array2d()
             }
         };
@@ -0,0 +1,583 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::{Array2D, Point2D};
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool measures, for each grid cell in a digital elevation model (DEM; `--dem`), the length
+/// of the downslope D8 flowpath (O'Callaghan and Mark, 1984) leading from the cell to the channel
+/// network or, if no channel network is specified, to the outlet at which the flowpath ends, as
+/// well as the total drop in elevation along that flowpath. Both properties are calculated in a
+/// single pass from the flowpath termini upslope, such that each flowpath is only traced once.
+///
+/// If a streams raster (`--streams`) is specified, in which stream cells are designated by all
+/// positive, non-NoData values, flowpaths terminate at the first stream cell that they encounter,
+/// and the outputs are equivalent to those of the `DownslopeDistanceToStream` and
+/// `ElevationAboveStream` tools; cells whose flowpaths leave the DEM, or end in a pit, without
+/// reaching a stream are assigned NoData. Otherwise, flowpaths terminate at the outlets of the DEM,
+/// i.e. at cells without a downslope neighbour, which are typically located on the edges of the DEM
+/// or of NoData areas. The DEM must have been pre-processed to remove artifact topographic
+/// depressions and flat areas (see `BreachDepressionsLeastCost`).
+///
+/// The flowpath length is written to the output raster (`--output`), in the horizontal units of the
+/// DEM, or in metres for DEMs in geographic coordinates, and the elevation drop, i.e. the elevation
+/// of each cell less that of its flowpath terminus, may be written to an optional raster
+/// (`--out_drop`). If a vector of seed points (`--seed_pts`) and an output vector
+/// (`--out_flowpaths`) are specified, the full downslope flowpath leading from each seed point to
+/// its terminus is written as a polyline, with attributes for the seed point's record number
+/// (SEED), the length of the flowpath (LENGTH), its elevation drop (DROP), and whether it
+/// terminates at a stream or an outlet (TERMINUS).
+///
+/// # See Also
+/// `DownslopeDistanceToStream`, `ElevationAboveStream`, `TraceDownslopeFlowpaths`,
+/// `DownslopeFlowpathLength`, `D8Pointer`
+pub struct DownslopeFlowpathMetrics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DownslopeFlowpathMetrics {
+    pub fn new() -> DownslopeFlowpathMetrics {
+        // public constructor
+        let name = "DownslopeFlowpathMetrics".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Measures the downslope flowpath length and elevation drop to the nearest stream or outlet."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File (optional)".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Optional input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Flowpath Length File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of downslope flowpath lengths.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Elevation Drop File (optional)".to_owned(),
+            flags: vec!["--out_drop".to_owned()],
+            description: "Optional output raster file of downslope elevation drops.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Seed Points File (optional)".to_owned(),
+            flags: vec!["--seed_pts".to_owned()],
+            description: "Optional input vector seed points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Flowpaths File (optional)".to_owned(),
+            flags: vec!["--out_flowpaths".to_owned()],
+            description:
+                "Output vector polyline file of the flowpaths leading from the seed points."
+                    .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --streams=streams.tif -o=flowpath_length.tif --out_drop=drop.tif --seed_pts=sites.shp --out_flowpaths=flowpaths.shp",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DownslopeFlowpathMetrics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DownslopeFlowpathMetrics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut streams_file = String::new();
+        let mut output_file = String::new();
+        let mut drop_file = String::new();
+        let mut seeds_file = String::new();
+        let mut flowpaths_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-streams" {
+                streams_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_drop" {
+                drop_file = value;
+            } else if flag_val == "-seed_pts" {
+                seeds_file = value;
+            } else if flag_val == "-out_flowpaths" {
+                flowpaths_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !streams_file.is_empty() && !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !drop_file.is_empty() && !drop_file.contains(&sep) && !drop_file.contains("/") {
+            drop_file = format!("{}{}", working_directory, drop_file);
+        }
+        if !seeds_file.is_empty() {
+            if flowpaths_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An output flowpaths file (--out_flowpaths) must be specified along with the seed points file.",
+                ));
+            }
+            if !seeds_file.contains(&sep) && !seeds_file.contains("/") {
+                seeds_file = format!("{}{}", working_directory, seeds_file);
+            }
+            if !flowpaths_file.contains(&sep) && !flowpaths_file.contains("/") {
+                flowpaths_file = format!("{}{}", working_directory, flowpaths_file);
+            }
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let streams = if !streams_file.is_empty() {
+            // make sure the inputs share the same grid and CRS
+            Some(align_raster_input(
+                &input,
+                Raster::new(&streams_file, "r")?,
+            )?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // the grid resolution, in metres for geographic coordinates
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let diag_cell_size = (res_x * res_x + res_y * res_y).sqrt();
+        let grid_lengths = [
+            diag_cell_size,
+            res_x,
+            diag_cell_size,
+            res_y,
+            diag_cell_size,
+            res_x,
+            diag_cell_size,
+            res_y,
+        ];
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
+
+        let is_stream = |row: isize, col: isize| -> bool {
+            match streams {
+                Some(ref s) => {
+                    let v = s.get_value(row, col);
+                    v > 0f64 && v != s.configs.nodata
+                }
+                None => false,
+            }
+        };
+
+        // calculate the D8 flow directions, with -1 for no-flow cells and -2 for NoData cells
+        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                let mut dir = -1i8;
+                let mut max_slope = 0f64;
+                for i in 0..8 {
+                    let z_n = input.get_value(row + dy[i], col + dx[i]);
+                    if z_n != nodata {
+                        let slope = (z - z_n) / grid_lengths[i];
+                        if slope > max_slope {
+                            max_slope = slope;
+                            dir = i as i8;
+                        }
+                    }
+                }
+                flow_dir.set_value(row, col, dir);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Flow directions: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // find the flowpath termini, i.e. stream cells, or outlets if there are no streams
+        let mut length: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut terminus_z: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                let dir = flow_dir.get_value(row, col);
+                let terminus = if streams.is_some() {
+                    is_stream(row, col)
+                } else {
+                    dir == -1i8
+                };
+                if dir != -2i8 && terminus {
+                    length.set_value(row, col, 0f64);
+                    terminus_z.set_value(row, col, input.get_value(row, col));
+                    stack.push((row, col));
+                }
+            }
+        }
+
+        // work upslope from the termini
+        let mut num_solved_cells = 0usize;
+        let num_cells = (rows * columns) as usize;
+        while let Some((row, col)) = stack.pop() {
+            let l = length.get_value(row, col);
+            let z_t = terminus_z.get_value(row, col);
+            for i in 0..8 {
+                let (row_n, col_n) = (row + dy[i], col + dx[i]);
+                if flow_dir.get_value(row_n, col_n) == inflowing_vals[i]
+                    && length.get_value(row_n, col_n) == nodata
+                {
+                    length.set_value(row_n, col_n, l + grid_lengths[i]);
+                    terminus_z.set_value(row_n, col_n, z_t);
+                    stack.push((row_n, col_n));
+                }
+            }
+            if verbose {
+                num_solved_cells += 1;
+                progress = (100.0_f64 * num_solved_cells as f64 / num_cells as f64) as usize;
+                if progress != old_progress {
+                    println!("Tracing flowpaths: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        let mut drop_output = if !drop_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&drop_file, &input);
+            r.configs.data_type = DataType::F32;
+            r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            r.configs.palette = "spectrum.plt".to_string();
+            Some(r)
+        } else {
+            None
+        };
+        for row in 0..rows {
+            output.set_row_data(row, length.get_row_data(row));
+            if let Some(ref mut r) = drop_output {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    let z_t = terminus_z.get_value(row, col);
+                    if z_t != nodata {
+                        data[col as usize] = input.get_value(row, col) - z_t;
+                    }
+                }
+                r.set_row_data(row, data);
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+        if !streams_file.is_empty() {
+            output.add_metadata_entry(format!("Input streams file: {}", streams_file));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(mut r) = drop_output {
+            r.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            r.add_metadata_entry(format!("Input DEM file: {}", input_file));
+            let _ = match r.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Elevation drop file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !seeds_file.is_empty() {
+            if verbose {
+                println!("Tracing the seed point flowpaths...");
+            }
+            let seeds = Shapefile::read(&seeds_file)?;
+            if seeds.header.shape_type.base_shape_type() != ShapeType::Point {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input seed points vector data must be of point base shape type.",
+                ));
+            }
+            let mut out = Shapefile::new(&flowpaths_file, ShapeType::PolyLine)?;
+            out.projection = input.configs.coordinate_ref_system_wkt.clone();
+            out.attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+            out.attributes
+                .add_field(&AttributeField::new("SEED", FieldDataType::Int, 7u8, 0u8));
+            out.attributes.add_field(&AttributeField::new(
+                "LENGTH",
+                FieldDataType::Real,
+                12u8,
+                3u8,
+            ));
+            out.attributes
+                .add_field(&AttributeField::new("DROP", FieldDataType::Real, 12u8, 3u8));
+            out.attributes.add_field(&AttributeField::new(
+                "TERMINUS",
+                FieldDataType::Text,
+                6u8,
+                0u8,
+            ));
+
+            let mut fid = 0i32;
+            for record_num in 0..seeds.num_records {
+                let record = seeds.get_record(record_num);
+                let mut row = input.get_row_from_y(record.points[0].y);
+                let mut col = input.get_column_from_x(record.points[0].x);
+                if flow_dir.get_value(row, col) == -2i8 {
+                    if verbose {
+                        println!(
+                            "Warning: seed point {} lies outside of the valid data.",
+                            record_num + 1
+                        );
+                    }
+                    continue;
+                }
+                let z_seed = input.get_value(row, col);
+                let mut points = vec![Point2D::new(record.points[0].x, record.points[0].y)];
+                let mut path_length = 0f64;
+                let mut num_steps = 0usize;
+                // streams are only reached if the path starts on, or flows into, a stream cell
+                let mut reached_stream = is_stream(row, col);
+                while !reached_stream && num_steps < num_cells {
+                    let dir = flow_dir.get_value(row, col);
+                    if dir < 0 {
+                        break;
+                    }
+                    path_length += grid_lengths[dir as usize];
+                    row += dy[dir as usize];
+                    col += dx[dir as usize];
+                    points.push(Point2D::new(
+                        input.get_x_from_column(col),
+                        input.get_y_from_row(row),
+                    ));
+                    reached_stream = is_stream(row, col);
+                    num_steps += 1;
+                }
+                if points.len() < 2 {
+                    // the seed point lies on its terminus
+                    points.push(Point2D::new(
+                        input.get_x_from_column(col),
+                        input.get_y_from_row(row),
+                    ));
+                }
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&points);
+                out.add_record(sfg);
+                fid += 1;
+                out.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(record_num as i32 + 1),
+                        FieldData::Real(path_length),
+                        FieldData::Real(z_seed - input.get_value(row, col)),
+                        FieldData::Text(
+                            if reached_stream { "stream" } else { "outlet" }.to_string(),
+                        ),
+                    ],
+                    false,
+                );
+
+                if verbose {
+                    progress = (100.0_f64 * record_num as f64
+                        / (seeds.num_records - 1).max(1) as f64)
+                        as usize;
+                    if progress != old_progress {
+                        println!("Seed point flowpaths: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            let _ = match out.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Flowpaths file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,400 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::dinf_routing::{dinf_flow_directions, dinf_receivers};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool identifies the grid cells of a digital elevation model (DEM; `--dem`) that are
+/// affected by edge contamination, i.e. cells whose upslope contributing areas may be truncated
+/// because they extend beyond the edges of the DEM, or into its interior NoData areas. Flow
+/// accumulation values for such cells, and derived indices such as the wetness index
+/// (`WetnessIndex`) and stream power index (`StreamPowerIndex`), are likely to be underestimated
+/// and unreliable. Edge contamination is a common source of error in terrain analysis of DEMs
+/// clipped to rectangular extents that do not coincide with drainage divides.
+///
+/// A DEM edge cell, i.e. a cell with at least one NoData or off-grid neighbour, that drains towards
+/// the interior of the DEM indicates that the terrain continues to rise beyond the edge, and that
+/// the cell may therefore receive flow from outside of the DEM. Each of these cells, and all of the
+/// cells located downslope of them, are flagged as edge contaminated. Edge cells without any lower
+/// neighbours within the DEM are assumed to drain off of its edge and are not flagged. Because the
+/// contaminated area depends on the flow algorithm, the user must specify the method
+/// (`--flow_type`) used to calculate the flow accumulation values that are to be masked; options
+/// include 'd8', 'mfd' (the multiple flow direction method, in which flow is passed to all lower
+/// neighbours), and 'dinf' (D-infinity). The dispersive methods produce more extensive
+/// contaminated areas than D8.
+///
+/// The output raster (`--output`) contains a value of 1 for edge-contaminated cells and 0 for all
+/// other valid cells, and may be used to mask flow accumulation rasters. The DEM should have been
+/// pre-processed to remove artifact topographic depressions and flat areas (see
+/// `BreachDepressionsLeastCost`) before running this tool.
+///
+/// # See Also
+/// `D8FlowAccumulation`, `FD8FlowAccumulation`, `DInfFlowAccumulation`, `WetnessIndex`
+pub struct EdgeContamination {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl EdgeContamination {
+    pub fn new() -> EdgeContamination {
+        // public constructor
+        let name = "EdgeContamination".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Identifies cells whose upslope areas may be truncated by the DEM edge or NoData areas."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Flow Type".to_owned(),
+            flags: vec!["--flow_type".to_owned()],
+            description: "Flow algorithm type; one of 'd8' (default), 'mfd', and 'dinf'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "d8".to_owned(),
+                "mfd".to_owned(),
+                "dinf".to_owned(),
+            ]),
+            default_value: Some("d8".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=edge_contamination.tif --flow_type=mfd",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        EdgeContamination {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for EdgeContamination {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut flow_type = "d8".to_string();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-flow_type" {
+                flow_type = value.trim().to_lowercase();
+            }
+        }
+
+        flow_type = if flow_type.contains("mfd") || flow_type.contains("fd8") {
+            "mfd".to_string()
+        } else if flow_type.contains("dinf") {
+            "dinf".to_string()
+        } else if flow_type.contains("d8") {
+            "d8".to_string()
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized flow type; the --flow_type parameter must be one of 'd8', 'mfd', and 'dinf'.",
+            ));
+        };
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // the grid resolution, in metres for geographic coordinates
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let diag_cell_size = (res_x * res_x + res_y * res_y).sqrt();
+        let grid_lengths = [
+            diag_cell_size,
+            res_x,
+            diag_cell_size,
+            res_y,
+            diag_cell_size,
+            res_x,
+            diag_cell_size,
+            res_y,
+        ];
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+        let dinf_dir = if flow_type == "dinf" {
+            Some(dinf_flow_directions(&input, res_x, res_y)?.0)
+        } else {
+            None
+        };
+
+        // the downslope neighbours of a cell, according to the flow algorithm
+        let receivers = |row: isize, col: isize| -> Vec<(isize, isize)> {
+            let z = input.get_value(row, col);
+            let mut cells = vec![];
+            if let Some(ref dir) = dinf_dir {
+                if let Some(r) = dinf_receivers(dir.get_value(row, col), row, col) {
+                    for &(row_n, col_n, p) in r.iter() {
+                        if p > 0f64 && input.get_value(row_n, col_n) != nodata {
+                            cells.push((row_n, col_n));
+                        }
+                    }
+                }
+            } else if flow_type == "mfd" {
+                for i in 0..8 {
+                    let z_n = input.get_value(row + dy[i], col + dx[i]);
+                    if z_n != nodata && z_n < z {
+                        cells.push((row + dy[i], col + dx[i]));
+                    }
+                }
+            } else {
+                let mut max_slope = 0f64;
+                let mut dir = 8;
+                for i in 0..8 {
+                    let z_n = input.get_value(row + dy[i], col + dx[i]);
+                    if z_n != nodata {
+                        let slope = (z - z_n) / grid_lengths[i];
+                        if slope > max_slope {
+                            max_slope = slope;
+                            dir = i;
+                        }
+                    }
+                }
+                if dir < 8 {
+                    cells.push((row + dy[dir], col + dx[dir]));
+                }
+            }
+            cells
+        };
+
+        // find the edge cells that drain into the interior of the DEM
+        let mut contaminated: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row, col) == nodata {
+                    contaminated.set_value(row, col, -1);
+                    continue;
+                }
+                let mut is_edge = false;
+                for i in 0..8 {
+                    if input.get_value(row + dy[i], col + dx[i]) == nodata {
+                        is_edge = true;
+                        break;
+                    }
+                }
+                if is_edge && !receivers(row, col).is_empty() {
+                    contaminated.set_value(row, col, 1);
+                    stack.push((row, col));
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding edge cells: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // flag all of the cells downslope of the contaminated edge cells
+        if verbose {
+            println!("Tracing contaminated flowpaths...");
+        }
+        while let Some((row, col)) = stack.pop() {
+            for (row_n, col_n) in receivers(row, col) {
+                if contaminated.get_value(row_n, col_n) == 0 {
+                    contaminated.set_value(row_n, col_n, 1);
+                    stack.push((row_n, col_n));
+                }
+            }
+        }
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::I16;
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        let mut num_contaminated = 0usize;
+        let mut num_valid = 0usize;
+        for row in 0..rows {
+            let mut data = vec![out_nodata; columns as usize];
+            for col in 0..columns {
+                let v = contaminated.get_value(row, col);
+                if v >= 0 {
+                    data[col as usize] = v as f64;
+                    num_valid += 1;
+                    if v == 1 {
+                        num_contaminated += 1;
+                    }
+                }
+            }
+            output.set_row_data(row, data);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+        output.add_metadata_entry(format!("Flow type: {}", flow_type));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!(
+                "Edge-contaminated cells: {} ({:.2}% of valid cells)",
+                num_contaminated,
+                100f64 * num_contaminated as f64 / num_valid.max(1) as f64
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 9, 2017
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -37,7 +37,7 @@ use std::thread;
 /// Remote Sensing of Environment, 112(9), 3469-3481.
 ///
 /// # See Also
-/// `ElevationAboveStreamEuclidean`, `DownslopeDistanceToStream`, `ElevAbovePit`, `BreachDepressions`
+/// `ElevationAboveStreamEuclidean`, `DownslopeDistanceToStream`, `ElevAbovePit`, `BreachDepressions`, `ExtractValleyBottoms`
 pub struct ElevationAboveStream {
     name: String,
     description: String,
@@ -209,7 +209,8 @@ impl WhiteboxTool for ElevationAboveStream {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -225,16 +226,6 @@ impl WhiteboxTool for ElevationAboveStream {
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
         let inflowing_vals = [4i8, 5i8, 6i8, 7i8, 0i8, 1i8, 2i8, 3i8];
 
-        // make sure the input files have the same size
-        if dem.configs.rows != streams.configs.rows
-            || dem.configs.columns != streams.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
@@ -202,7 +202,8 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         };
 
         let dem = Raster::new(&dem_file, "r")?;
-        let input = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let input = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
 
         let nodata = input.configs.nodata;
         let rows = input.configs.rows as isize;
@@ -211,13 +212,6 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         let mut r_y: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
         let mut distance: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
 
-        if dem.configs.rows as isize != rows || dem.configs.columns as isize != columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Input DEM and streams file must have the same extent (rows and columns).",
-            ));
-        }
-
         let start = Instant::now();
 
         let mut allocation = Raster::initialize_using_file(&output_file, &dem);
@@ -0,0 +1,1034 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::spatial_ref_system::CoordinateReferenceSystem;
+use crate::structures::Array2D;
+use crate::tools::*;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::env;
+use std::f64;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path;
+
+/// This tool packages the outputs of a watershed delineation, along with land use, soil and slope
+/// overlays, into the inputs required to set up a semi-distributed hydrological model. The user
+/// must specify a depressionless DEM (`--dem`), a D8 pointer raster (`--d8_pntr`), a streams
+/// raster (`--streams`), a sub-basins raster (`--subbasins`), such as that produced by the
+/// `Subbasins` tool, and categorical land use (`--landuse`) and soil (`--soil`) rasters, all of
+/// which must share the same grid. If the pointer file contains ESRI flow direction values, the
+/// `--esri_pntr` parameter must be specified.
+///
+/// Each sub-basin is associated with one channel, made up of the stream cells within the sub-basin.
+/// The outlet of a sub-basin is its cell of greatest flow accumulation, and the sub-basin (and
+/// channel) downstream is that containing the cell to which the outlet drains. The channel Strahler
+/// orders are derived from this sub-basin topology. Hydrologic response units (HRUs) are formed
+/// from each unique combination of sub-basin, land use class, soil class and slope class, where the
+/// slope classes are defined by an optional list of slope breaks, in percent (`--slope_classes`,
+/// e.g. '5,15'). The land use and soil class values are written as-is and must be related to the
+/// model's land use and soil databases by the user.
+///
+/// Two output formats (`--format`) are available. With the 'swat' format, the tables used by the
+/// SWAT+ Editor to build a SWAT+ project (gis_subbasins.csv, gis_channels.csv, gis_lsus.csv,
+/// gis_hrus.csv, gis_routing.csv and gis_points.csv) are written to the output directory
+/// (`--output`). Each sub-basin contains a single landscape unit (LSU), with an identifier of ten
+/// times the channel identifier. Channel widths and depths are estimated from the drainage area
+/// (A, in square kilometres) as 1.29A^0.6 and 0.13A^0.4 metres respectively. Areas are reported in
+/// hectares and slopes in percent. With the 'json' format, a single model_setup.json file,
+/// containing the sub-basins, their channels, HRUs and routing, is written to the output
+/// directory instead.
+///
+/// Latitudes and longitudes are calculated from the DEM's coordinate reference system; if this is
+/// not defined, map coordinates are written instead. The DEM is expected to be in a projected
+/// coordinate system with linear units of metres.
+///
+/// # See Also
+/// `Subbasins`, `D8Pointer`, `ExtractStreams`, `StreamNetworkToVector`
+pub struct ExportHydrologicModel {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportHydrologicModel {
+    pub fn new() -> ExportHydrologicModel {
+        // public constructor
+        let name = "ExportHydrologicModel".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Exports sub-basins, channels, HRUs and routing topology for setting up a SWAT+ or other hydrological model.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Input depressionless raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Sub-basins File".to_owned(),
+            flags: vec!["--subbasins".to_owned()],
+            description: "Input raster sub-basins file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Land Use File".to_owned(),
+            flags: vec!["--landuse".to_owned()],
+            description: "Input categorical land use raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Soil File".to_owned(),
+            flags: vec!["--soil".to_owned()],
+            description: "Input categorical soil raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Slope Class Breaks (%)".to_owned(),
+            flags: vec!["--slope_classes".to_owned()],
+            description:
+                "Optional comma-separated list of slope class breaks, in percent (e.g. '5,15')."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Format".to_owned(),
+            flags: vec!["--format".to_owned()],
+            description: "Output format; options include 'swat' and 'json'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["swat".to_owned(), "json".to_owned()]),
+            default_value: Some("swat".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output directory.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_pntr".to_owned()],
+            description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=dem.tif --d8_pntr=D8.tif --streams=streams.tif --subbasins=subbasins.tif --landuse=landuse.tif --soil=soil.tif --slope_classes='5,15' -o=swat_project
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=dem.tif --d8_pntr=D8.tif --streams=streams.tif --subbasins=subbasins.tif --landuse=landuse.tif --soil=soil.tif --format=json -o=model", short_exe, name).replace("*", &sep);
+
+        ExportHydrologicModel {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportHydrologicModel {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut dem_file = String::new();
+        let mut d8_file = String::new();
+        let mut streams_file = String::new();
+        let mut subbasins_file = String::new();
+        let mut landuse_file = String::new();
+        let mut soil_file = String::new();
+        let mut slope_breaks: Vec<f64> = vec![];
+        let mut json_format = false;
+        let mut output_dir = String::new();
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-d8_pntr" {
+                d8_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-streams" {
+                streams_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-subbasins" {
+                subbasins_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-landuse" {
+                landuse_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-soil" {
+                soil_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-slope_classes" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                for s in val.split(|c| c == ',' || c == ';' || c == ' ') {
+                    if !s.trim().is_empty() {
+                        slope_breaks.push(
+                            s.trim()
+                                .parse::<f64>()
+                                .expect(&format!("Error parsing {}", flag_val)),
+                        );
+                    }
+                }
+            } else if flag_val == "-format" {
+                let val = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                json_format = val.contains("json");
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_dir = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    esri_style = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [
+            &mut dem_file,
+            &mut d8_file,
+            &mut streams_file,
+            &mut subbasins_file,
+            &mut landuse_file,
+            &mut soil_file,
+            &mut output_dir,
+        ]
+        .iter_mut()
+        {
+            if !file.contains(&sep) && !file.contains("/") {
+                **file = format!("{}{}", working_directory, file);
+            }
+        }
+        if !output_dir.ends_with(&sep) && !output_dir.ends_with("/") {
+            output_dir.push_str(&sep);
+        }
+
+        slope_breaks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        slope_breaks.dedup();
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        // make sure the layers share the grid and CRS of the DEM
+        let pntr = align_raster_input(&dem, Raster::new(&d8_file, "r")?)?;
+        let streams = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
+        let subbasins = align_raster_input(&dem, Raster::new(&subbasins_file, "r")?)?;
+        let landuse = align_raster_input(&dem, Raster::new(&landuse_file, "r")?)?;
+        let soil = align_raster_input(&dem, Raster::new(&soil_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+
+        let crs = if dem.configs.epsg_code != 0 {
+            CoordinateReferenceSystem::from_epsg(dem.configs.epsg_code).ok()
+        } else if !dem.configs.coordinate_ref_system_wkt.is_empty() {
+            CoordinateReferenceSystem::from_wkt(&dem.configs.coordinate_ref_system_wkt).ok()
+        } else {
+            CoordinateReferenceSystem::from_wkt(&dem.configs.projection).ok()
+        };
+        if crs.is_none() && verbose {
+            println!("Warning: The DEM's coordinate reference system could not be determined. Map coordinates will be output in place of latitudes and longitudes.");
+        }
+        let to_lat_lon = |x: f64, y: f64| -> (f64, f64) {
+            match &crs {
+                Some(crs) => {
+                    let (lon, lat) = crs.unproject(x, y);
+                    (lat, lon)
+                }
+                None => (y, x),
+            }
+        };
+
+        let dem_nodata = dem.configs.nodata;
+        let pntr_nodata = pntr.configs.nodata;
+        let streams_nodata = streams.configs.nodata;
+        let subbasins_nodata = subbasins.configs.nodata;
+        let landuse_nodata = landuse.configs.nodata;
+        let soil_nodata = soil.configs.nodata;
+        let res_x = dem.configs.resolution_x;
+        let res_y = dem.configs.resolution_y;
+        let cell_area_ha = res_x * res_y / 10000f64;
+        let diag = (res_x * res_x + res_y * res_y).sqrt();
+
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let step_lengths = [diag, res_x, diag, res_y, diag, res_x, diag, res_y];
+        let inflowing_vals = if esri_style {
+            [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64]
+        } else {
+            [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64]
+        };
+
+        // Create a mapping from the pointer values to cells offsets.
+        let mut pntr_matches: [usize; 129] = [999usize; 129];
+        if !esri_style {
+            // This maps Whitebox-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 0usize;
+            pntr_matches[2] = 1usize;
+            pntr_matches[4] = 2usize;
+            pntr_matches[8] = 3usize;
+            pntr_matches[16] = 4usize;
+            pntr_matches[32] = 5usize;
+            pntr_matches[64] = 6usize;
+            pntr_matches[128] = 7usize;
+        } else {
+            // This maps Esri-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 1usize;
+            pntr_matches[2] = 2usize;
+            pntr_matches[4] = 3usize;
+            pntr_matches[8] = 4usize;
+            pntr_matches[16] = 5usize;
+            pntr_matches[32] = 6usize;
+            pntr_matches[64] = 7usize;
+            pntr_matches[128] = 0usize;
+        }
+
+        // returns the index of the flow direction of a cell, if it has one
+        let flow_dir = |row: isize, col: isize| -> Result<Option<usize>, Error> {
+            let z = pntr.get_value(row, col);
+            if z == pntr_nodata || z <= 0f64 {
+                return Ok(None);
+            }
+            let dir = z as usize;
+            if dir > 128 || pntr_matches[dir] == 999 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools.",
+                ));
+            }
+            Ok(Some(pntr_matches[dir]))
+        };
+        let subbasin_id = |row: isize, col: isize| -> Option<i32> {
+            let z = subbasins.get_value(row, col);
+            if z != subbasins_nodata && z > 0f64 {
+                Some(z as i32)
+            } else {
+                None
+            }
+        };
+
+        // Calculate the flow accumulation, in grid cells, used to locate the sub-basin outlets.
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut accum: Array2D<f64> = Array2D::new(rows, columns, 1f64, -1f64)?;
+        let mut queue = VecDeque::new();
+        for row in 0..rows {
+            for col in 0..columns {
+                if pntr.get_value(row, col) != pntr_nodata {
+                    let mut count = 0i8;
+                    for i in 0..8 {
+                        if pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i] {
+                            count += 1;
+                        }
+                    }
+                    num_inflowing.set_value(row, col, count);
+                    if count == 0 {
+                        queue.push_back((row, col));
+                    }
+                }
+            }
+        }
+        while let Some((row, col)) = queue.pop_front() {
+            if let Some(c) = flow_dir(row, col)? {
+                let (rn, cn) = (row + dy[c], col + dx[c]);
+                if num_inflowing.get_value(rn, cn) > 0 {
+                    let a = accum.get_value(row, col);
+                    accum.increment(rn, cn, a);
+                    num_inflowing.decrement(rn, cn, 1);
+                    if num_inflowing.get_value(rn, cn) == 0 {
+                        queue.push_back((rn, cn));
+                    }
+                }
+            }
+        }
+        drop(num_inflowing);
+
+        // Gather the sub-basin, channel and HRU statistics.
+        let mut basins: BTreeMap<i32, Subbasin> = BTreeMap::new();
+        let mut hrus: BTreeMap<(i32, i32, i32, usize), Hru> = BTreeMap::new();
+        let mut landuse_areas: HashMap<(i32, i32), f64> = HashMap::new();
+        let mut soil_areas: HashMap<(i32, i32), f64> = HashMap::new();
+        let mut slope_areas: HashMap<(i32, usize), f64> = HashMap::new();
+        for row in 0..rows {
+            for col in 0..columns {
+                let id = match subbasin_id(row, col) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let z = dem.get_value(row, col);
+                if z == dem_nodata {
+                    continue;
+                }
+                let x = dem.get_x_from_column(col);
+                let y = dem.get_y_from_row(row);
+                let slope = cell_slope(&dem, row, col, res_x, res_y);
+                let a = accum.get_value(row, col);
+                let basin = basins.entry(id).or_insert(Subbasin::new());
+                basin.num_cells += 1;
+                basin.sum_elev += z;
+                basin.min_elev = basin.min_elev.min(z);
+                basin.max_elev = basin.max_elev.max(z);
+                basin.sum_slope += slope;
+                basin.sum_x += x;
+                basin.sum_y += y;
+                if a > basin.outlet_accum {
+                    basin.outlet_accum = a;
+                    basin.outlet = (row, col);
+                }
+                let s = streams.get_value(row, col);
+                if s > 0f64 && s != streams_nodata {
+                    basin.channel_cells += 1;
+                    basin.channel_min_elev = basin.channel_min_elev.min(z);
+                    basin.channel_max_elev = basin.channel_max_elev.max(z);
+                    basin.channel_sum_x += x;
+                    basin.channel_sum_y += y;
+                    if let Some(c) = flow_dir(row, col)? {
+                        basin.channel_length += step_lengths[c];
+                    }
+                }
+
+                let lu = landuse.get_value(row, col);
+                let so = soil.get_value(row, col);
+                if lu == landuse_nodata || so == soil_nodata {
+                    continue;
+                }
+                let slope_class = slope_breaks.iter().filter(|b| slope >= **b).count();
+                let hru = hrus
+                    .entry((id, lu as i32, so as i32, slope_class))
+                    .or_insert(Hru::new());
+                hru.num_cells += 1;
+                hru.sum_slope += slope;
+                hru.sum_elev += z;
+                hru.sum_x += x;
+                hru.sum_y += y;
+                *landuse_areas.entry((id, lu as i32)).or_insert(0f64) += cell_area_ha;
+                *soil_areas.entry((id, so as i32)).or_insert(0f64) += cell_area_ha;
+                *slope_areas.entry((id, slope_class)).or_insert(0f64) += cell_area_ha;
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if basins.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No sub-basins were found in the sub-basins raster.",
+            ));
+        }
+
+        // Find the sub-basin downstream of each outlet, and then the channel Strahler orders.
+        let ids: Vec<i32> = basins.keys().cloned().collect();
+        for id in &ids {
+            let (row, col) = basins[id].outlet;
+            let ds = match flow_dir(row, col)? {
+                Some(c) => subbasin_id(row + dy[c], col + dx[c]).filter(|ds| ds != id),
+                None => None,
+            };
+            basins.get_mut(id).unwrap().downstream = ds.filter(|ds| basins.contains_key(ds));
+        }
+        let mut num_upstream: HashMap<i32, usize> = ids.iter().map(|id| (*id, 0)).collect();
+        for id in &ids {
+            if let Some(ds) = basins[id].downstream {
+                *num_upstream.get_mut(&ds).unwrap() += 1;
+            }
+        }
+        let mut order: Vec<i32> = ids
+            .iter()
+            .filter(|id| num_upstream[id] == 0)
+            .cloned()
+            .collect();
+        let mut i = 0;
+        while i < order.len() {
+            if let Some(ds) = basins[&order[i]].downstream {
+                let n = num_upstream.get_mut(&ds).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    order.push(ds);
+                }
+            }
+            i += 1;
+        }
+        if order.len() < ids.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The sub-basin topology contains a cycle; check that the D8 pointer and sub-basins rasters are consistent.",
+            ));
+        }
+        let mut upstream_orders: HashMap<i32, Vec<i32>> = HashMap::new();
+        for id in &order {
+            let strahler = match upstream_orders.get(id) {
+                Some(orders) => {
+                    let max_order = *orders.iter().max().unwrap();
+                    if orders.iter().filter(|o| **o == max_order).count() > 1 {
+                        max_order + 1
+                    } else {
+                        max_order
+                    }
+                }
+                None => 1,
+            };
+            basins.get_mut(id).unwrap().strahler = strahler;
+            if let Some(ds) = basins[id].downstream {
+                upstream_orders.entry(ds).or_insert(vec![]).push(strahler);
+            }
+        }
+
+        let slope_labels: Vec<String> = (0..=slope_breaks.len())
+            .map(|c| {
+                let lower = if c == 0 { 0f64 } else { slope_breaks[c - 1] };
+                if c < slope_breaks.len() {
+                    format!("{}-{}", lower, slope_breaks[c])
+                } else {
+                    format!("{}-9999", lower)
+                }
+            })
+            .collect();
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of sub-basins: {}", basins.len());
+            println!("Number of HRUs: {}", hrus.len());
+            println!("Saving data...")
+        };
+        fs::create_dir_all(&output_dir)?;
+
+        if json_format {
+            let mut subbasin_values = vec![];
+            let mut routing_values = vec![];
+            let mut hru_id = 0;
+            for (id, basin) in &basins {
+                let (outlet_x, outlet_y) = (
+                    dem.get_x_from_column(basin.outlet.1),
+                    dem.get_y_from_row(basin.outlet.0),
+                );
+                let (centroid_x, centroid_y) = basin.centroid();
+                let (lat, lon) = to_lat_lon(centroid_x, centroid_y);
+                let area = basin.num_cells as f64 * cell_area_ha;
+                let drainage_area = basin.outlet_accum * cell_area_ha;
+                let mut hru_values = vec![];
+                for (key, hru) in
+                    hrus.range((*id, i32::MIN, i32::MIN, 0)..=(*id, i32::MAX, i32::MAX, usize::MAX))
+                {
+                    hru_id += 1;
+                    hru_values.push(json!({
+                        "id": hru_id,
+                        "landuse": key.1,
+                        "soil": key.2,
+                        "slope_class": slope_labels[key.3],
+                        "area": hru.num_cells as f64 * cell_area_ha,
+                        "fraction": hru.num_cells as f64 / basin.num_cells as f64,
+                        "slope": hru.sum_slope / hru.num_cells as f64,
+                        "elevation": hru.sum_elev / hru.num_cells as f64,
+                    }));
+                }
+                subbasin_values.push(json!({
+                    "id": id,
+                    "downstream": basin.downstream.unwrap_or(0),
+                    "area": area,
+                    "drainage_area": drainage_area,
+                    "slope": basin.sum_slope / basin.num_cells as f64,
+                    "elevation": {
+                        "min": basin.min_elev,
+                        "mean": basin.sum_elev / basin.num_cells as f64,
+                        "max": basin.max_elev,
+                    },
+                    "centroid": { "x": centroid_x, "y": centroid_y, "lat": lat, "lon": lon },
+                    "outlet": { "x": outlet_x, "y": outlet_y, "elevation": dem.get_value(basin.outlet.0, basin.outlet.1) },
+                    "channel": {
+                        "length": basin.channel_length,
+                        "slope": basin.channel_slope(),
+                        "strahler": basin.strahler,
+                        "width": channel_width(drainage_area),
+                        "depth": channel_depth(drainage_area),
+                    },
+                    "hrus": hru_values,
+                }));
+                routing_values.push(json!({
+                    "from": id,
+                    "to": basin.downstream.unwrap_or(0),
+                }));
+            }
+            let model = json!({
+                "units": {
+                    "area": "ha",
+                    "length": "m",
+                    "slope": "percent",
+                },
+                "slope_classes": slope_labels,
+                "subbasins": subbasin_values,
+                "routing": routing_values,
+            });
+            let f = File::create(format!("{}model_setup.json", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            let s = serde_json::to_string_pretty(&model)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            writer.write_all(s.as_bytes())?;
+        } else {
+            let f = File::create(format!("{}gis_subbasins.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(
+                writer,
+                "id,area,slo1,len1,sll,lat,lon,elev,elevmin,elevmax,waterid"
+            )?;
+            for (id, basin) in &basins {
+                let (x, y) = basin.centroid();
+                let (lat, lon) = to_lat_lon(x, y);
+                writeln!(
+                    writer,
+                    "{},{:.4},{:.4},{:.4},{:.4},{:.6},{:.6},{:.4},{:.4},{:.4},0",
+                    id,
+                    basin.num_cells as f64 * cell_area_ha,
+                    basin.sum_slope / basin.num_cells as f64,
+                    basin.channel_length,
+                    basin.slope_length(res_x, res_y),
+                    lat,
+                    lon,
+                    basin.sum_elev / basin.num_cells as f64,
+                    basin.min_elev,
+                    basin.max_elev
+                )?;
+            }
+
+            let f = File::create(format!("{}gis_channels.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(
+                writer,
+                "id,subbasin,areac,strahler,len2,slo2,wid2,dep2,elevmin,elevmax,midlat,midlon"
+            )?;
+            for (id, basin) in &basins {
+                let drainage_area = basin.outlet_accum * cell_area_ha;
+                let (x, y) = basin.channel_midpoint();
+                let (lat, lon) = to_lat_lon(x, y);
+                writeln!(
+                    writer,
+                    "{},{},{:.4},{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4},{:.6},{:.6}",
+                    id,
+                    id,
+                    drainage_area,
+                    basin.strahler,
+                    basin.channel_length,
+                    basin.channel_slope(),
+                    channel_width(drainage_area),
+                    channel_depth(drainage_area),
+                    basin.channel_min_elev(),
+                    basin.channel_max_elev(),
+                    lat,
+                    lon
+                )?;
+            }
+
+            let f = File::create(format!("{}gis_lsus.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(
+                writer,
+                "id,category,channel,area,slope,len1,csl,wid1,dep1,lat,lon,elev"
+            )?;
+            for (id, basin) in &basins {
+                let drainage_area = basin.outlet_accum * cell_area_ha;
+                let (x, y) = basin.centroid();
+                let (lat, lon) = to_lat_lon(x, y);
+                writeln!(
+                    writer,
+                    "{},0,{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4},{:.6},{:.6},{:.4}",
+                    id * 10,
+                    id,
+                    basin.num_cells as f64 * cell_area_ha,
+                    basin.sum_slope / basin.num_cells as f64,
+                    basin.channel_length,
+                    basin.channel_slope(),
+                    channel_width(drainage_area),
+                    channel_depth(drainage_area),
+                    lat,
+                    lon,
+                    basin.sum_elev / basin.num_cells as f64
+                )?;
+            }
+
+            let f = File::create(format!("{}gis_hrus.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(
+                writer,
+                "id,lsu,arsub,arlsu,landuse,arland,soil,arso,slp,arslp,slope,lat,lon,elev"
+            )?;
+            let mut hru_lsus = vec![];
+            for (key, hru) in &hrus {
+                let basin_area = basins[&key.0].num_cells as f64 * cell_area_ha;
+                let (lat, lon) = to_lat_lon(
+                    hru.sum_x / hru.num_cells as f64,
+                    hru.sum_y / hru.num_cells as f64,
+                );
+                hru_lsus.push(key.0 * 10);
+                writeln!(
+                    writer,
+                    "{},{},{:.4},{:.4},{},{:.4},{},{:.4},{},{:.4},{:.4},{:.6},{:.6},{:.4}",
+                    hru_lsus.len(),
+                    key.0 * 10,
+                    basin_area,
+                    basin_area,
+                    key.1,
+                    landuse_areas[&(key.0, key.1)],
+                    key.2,
+                    soil_areas[&(key.0, key.2)],
+                    slope_labels[key.3],
+                    slope_areas[&(key.0, key.3)],
+                    hru.sum_slope / hru.num_cells as f64,
+                    lat,
+                    lon,
+                    hru.sum_elev / hru.num_cells as f64
+                )?;
+            }
+
+            let f = File::create(format!("{}gis_points.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(writer, "id,subbasin,ptype,xpr,ypr,lat,lon,elev")?;
+            for (id, basin) in &basins {
+                let (x, y) = (
+                    dem.get_x_from_column(basin.outlet.1),
+                    dem.get_y_from_row(basin.outlet.0),
+                );
+                let (lat, lon) = to_lat_lon(x, y);
+                writeln!(
+                    writer,
+                    "{},{},O,{:.4},{:.4},{:.6},{:.6},{:.4}",
+                    id,
+                    id,
+                    x,
+                    y,
+                    lat,
+                    lon,
+                    dem.get_value(basin.outlet.0, basin.outlet.1)
+                )?;
+            }
+
+            // HRUs drain to their landscape unit, which drains to its channel. Channels drain to
+            // the channel downstream or, at the watershed outlets, to the outlet points.
+            let f = File::create(format!("{}gis_routing.csv", output_dir))?;
+            let mut writer = BufWriter::new(f);
+            writeln!(writer, "sourceid,sourcecat,hyd_typ,sinkid,sinkcat,percent")?;
+            for (i, lsu) in hru_lsus.iter().enumerate() {
+                writeln!(writer, "{},HRU,tot,{},LSU,100", i + 1, lsu)?;
+            }
+            for (id, basin) in &basins {
+                writeln!(writer, "{},LSU,tot,{},CH,100", id * 10, id)?;
+                match basin.downstream {
+                    Some(ds) => writeln!(writer, "{},CH,tot,{},CH,100", id, ds)?,
+                    None => {
+                        writeln!(writer, "{},CH,tot,{},PT,100", id, id)?;
+                        writeln!(writer, "{},PT,tot,0,X,100", id)?;
+                    }
+                }
+            }
+        }
+
+        if verbose {
+            println!("Output files written");
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+struct Subbasin {
+    num_cells: usize,
+    sum_elev: f64,
+    min_elev: f64,
+    max_elev: f64,
+    sum_slope: f64,
+    sum_x: f64,
+    sum_y: f64,
+    outlet: (isize, isize),
+    outlet_accum: f64,
+    downstream: Option<i32>,
+    strahler: i32,
+    channel_cells: usize,
+    channel_length: f64,
+    channel_min_elev: f64,
+    channel_max_elev: f64,
+    channel_sum_x: f64,
+    channel_sum_y: f64,
+}
+
+impl Subbasin {
+    fn new() -> Subbasin {
+        Subbasin {
+            num_cells: 0,
+            sum_elev: 0f64,
+            min_elev: f64::INFINITY,
+            max_elev: f64::NEG_INFINITY,
+            sum_slope: 0f64,
+            sum_x: 0f64,
+            sum_y: 0f64,
+            outlet: (0, 0),
+            outlet_accum: 0f64,
+            downstream: None,
+            strahler: 1,
+            channel_cells: 0,
+            channel_length: 0f64,
+            channel_min_elev: f64::INFINITY,
+            channel_max_elev: f64::NEG_INFINITY,
+            channel_sum_x: 0f64,
+            channel_sum_y: 0f64,
+        }
+    }
+
+    fn centroid(&self) -> (f64, f64) {
+        (
+            self.sum_x / self.num_cells as f64,
+            self.sum_y / self.num_cells as f64,
+        )
+    }
+
+    /// The mean location of the channel cells, or the centroid if there is no channel.
+    fn channel_midpoint(&self) -> (f64, f64) {
+        if self.channel_cells > 0 {
+            (
+                self.channel_sum_x / self.channel_cells as f64,
+                self.channel_sum_y / self.channel_cells as f64,
+            )
+        } else {
+            self.centroid()
+        }
+    }
+
+    fn channel_min_elev(&self) -> f64 {
+        if self.channel_cells > 0 {
+            self.channel_min_elev
+        } else {
+            self.min_elev
+        }
+    }
+
+    fn channel_max_elev(&self) -> f64 {
+        if self.channel_cells > 0 {
+            self.channel_max_elev
+        } else {
+            self.max_elev
+        }
+    }
+
+    /// The channel slope, in percent.
+    fn channel_slope(&self) -> f64 {
+        if self.channel_length > 0f64 {
+            100f64 * (self.channel_max_elev() - self.channel_min_elev()) / self.channel_length
+        } else {
+            0f64
+        }
+    }
+
+    /// An estimate of the mean overland flow slope length, taken as half of the ratio of the
+    /// sub-basin area to its channel length, limited to between one cell and 120 m.
+    fn slope_length(&self, res_x: f64, res_y: f64) -> f64 {
+        let area = self.num_cells as f64 * res_x * res_y;
+        let length = if self.channel_length > 0f64 {
+            area / (2f64 * self.channel_length)
+        } else {
+            area.sqrt()
+        };
+        length.min(120f64).max(res_x.min(res_y))
+    }
+}
+
+struct Hru {
+    num_cells: usize,
+    sum_slope: f64,
+    sum_elev: f64,
+    sum_x: f64,
+    sum_y: f64,
+}
+
+impl Hru {
+    fn new() -> Hru {
+        Hru {
+            num_cells: 0,
+            sum_slope: 0f64,
+            sum_elev: 0f64,
+            sum_x: 0f64,
+            sum_y: 0f64,
+        }
+    }
+}
+
+/// Returns the slope of a DEM cell, in percent, using the 3 x 3 finite difference method
+/// of Horn (1981). Neighbouring NoData cells are replaced by the centre cell elevation.
+fn cell_slope(dem: &Raster, row: isize, col: isize, res_x: f64, res_y: f64) -> f64 {
+    let nodata = dem.configs.nodata;
+    let z = dem.get_value(row, col);
+    let mut n = [0f64; 9];
+    for r in 0..3 {
+        for c in 0..3 {
+            let zn = dem.get_value(row + r as isize - 1, col + c as isize - 1);
+            n[r * 3 + c] = if zn != nodata { zn } else { z };
+        }
+    }
+    let dz_dx = ((n[2] + 2f64 * n[5] + n[8]) - (n[0] + 2f64 * n[3] + n[6])) / (8f64 * res_x);
+    let dz_dy = ((n[0] + 2f64 * n[1] + n[2]) - (n[6] + 2f64 * n[7] + n[8])) / (8f64 * res_y);
+    100f64 * (dz_dx * dz_dx + dz_dy * dz_dy).sqrt()
+}
+
+/// Channel bankfull width, in metres, from the drainage area in hectares.
+fn channel_width(area_ha: f64) -> f64 {
+    1.29 * (area_ha / 100f64).powf(0.6)
+}
+
+/// Channel bankfull depth, in metres, from the drainage area in hectares.
+fn channel_depth(area_ha: f64) -> f64 {
+    0.13 * (area_ha / 100f64).powf(0.4)
+}
@@ -0,0 +1,692 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::mrvbf::{fuzzy_membership, mrvbf, slope_percent};
+use crate::algorithms::{polygon_area, raster_to_polygons};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool maps the extent of valley bottoms and floodplains by combining the height above
+/// the nearest drainage (HAND), local slope gradient, and drainage area. The user must specify the
+/// names of an input digital elevation model (`--dem`) and streams raster (`--streams`). The DEM
+/// must have been pre-processed to remove artifact topographic depressions and flat areas (see
+/// `BreachDepressions`), and stream cells are designated in the streams raster as all positive,
+/// non-zero values.
+///
+/// D8 flow directions and the upslope drainage area of each cell are first derived from the DEM.
+/// The HAND of each cell is then measured as its elevation above the stream cell that it drains
+/// to, as with the `ElevationAboveStream` tool. Streams with a drainage area smaller than
+/// `--min_area` (in squared map units) are ignored. Because larger rivers build wider and higher
+/// floodplains, the HAND threshold may optionally be scaled by the drainage area (*A*) of the stream
+/// cell that each cell drains to (Nardi et al., 2006):
+///
+/// > *h<sub>t</sub>* = `--max_hand` &times; (*A* / `--ref_area`)<sup>`--area_exponent`</sup>
+///
+/// The default exponent of zero applies the same HAND threshold (`--max_hand`) throughout; values of
+/// about 0.3 are typical of the scaling of floodplain depth with drainage area.
+///
+/// Each criterion is converted into a fuzzy membership value ranging from zero to one using the
+/// function 1 / (1 + (*x* / *t*)<sup>4</sup>), where *x* is the HAND or slope of the cell and *t* is
+/// the corresponding threshold, such that cells at the threshold have a membership of 0.5. The slope
+/// threshold is specified in degrees (`--max_slope`). Alternatively, if the `--mrvbf` flag is
+/// specified, the slope membership is replaced by a membership based on the multi-resolution valley
+/// bottom flatness (MRVBF) index of Gallant and Dowling (2003), which identifies flat, low-lying
+/// areas at a range of progressively coarser scales. In this case, the membership function is
+/// 1 / (1 + (0.5 / MRVBF)<sup>4</sup>), since MRVBF values less than 0.5 are not considered valley
+/// bottoms. MRVBF is better suited to mapping the broad valley floors of large rivers, whose local
+/// slope gradients can be noisy in fine-resolution DEMs.
+///
+/// The output raster (`--output`) contains the product of the HAND and terrain memberships, which
+/// can be interpreted as the probability that each cell is part of a valley bottom. Cells that do not
+/// drain to a stream have a probability of zero. The valley-bottom extent is defined as the set of
+/// cells with a probability of at least `--threshold` (default 0.5) that are connected to the
+/// stream network through other valley-bottom cells. The extent may optionally be output as a
+/// vector polygon file (`--out_polygons`), with *FID* and *AREA* attributes.
+///
+/// # References
+/// Gallant, J. C., and Dowling, T. I. (2003). A multiresolution index of valley bottom flatness
+/// for mapping depositional areas. *Water Resources Research*, 39(12), 1347.
+///
+/// Nardi, F., Vivoni, E. R., and Grimaldi, S. (2006). Investigating a floodplain scaling relation
+/// using a hydrogeomorphic delineation method. *Water Resources Research*, 42(9), W09409.
+///
+/// # See Also
+/// `ElevationAboveStream`, `ExtractValleys`, `BreachDepressions`
+pub struct ExtractValleyBottoms {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractValleyBottoms {
+    pub fn new() -> ExtractValleyBottoms {
+        // public constructor
+        let name = "ExtractValleyBottoms".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Maps valley bottoms and floodplains using HAND, slope, and drainage area.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Probability File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of valley-bottom probability.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Extent Polygons File".to_owned(),
+            flags: vec!["--out_polygons".to_owned()],
+            description: "Optional output vector polygon file of the valley-bottom extent."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "HAND Threshold".to_owned(),
+            flags: vec!["--max_hand".to_owned()],
+            description:
+                "Height above the nearest drainage at which membership is 0.5, in elevation units."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Slope Threshold (degrees)".to_owned(),
+            flags: vec!["--max_slope".to_owned()],
+            description: "Slope gradient at which membership is 0.5, in degrees.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Stream Drainage Area".to_owned(),
+            flags: vec!["--min_area".to_owned()],
+            description: "Minimum drainage area of the streams, in squared map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Drainage Area Scaling Exponent".to_owned(),
+            flags: vec!["--area_exponent".to_owned()],
+            description: "Exponent used to scale the HAND threshold by stream drainage area."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference Drainage Area".to_owned(),
+            flags: vec!["--ref_area".to_owned()],
+            description: "Drainage area at which the HAND threshold applies, in squared map units."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1000000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Use MRVBF?".to_owned(),
+            flags: vec!["--mrvbf".to_owned()],
+            description: "Use the multi-resolution valley bottom flatness index instead of slope."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Probability Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Minimum probability of cells in the valley-bottom extent.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --streams=streams.tif -o=valley_prob.tif --out_polygons=valley_bottoms.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --streams=streams.tif -o=valley_prob.tif --max_hand=3.0 --area_exponent=0.3 --min_area=100000.0 --mrvbf", short_exe, name).replace("*", &sep);
+
+        ExtractValleyBottoms {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractValleyBottoms {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut dem_file = String::new();
+        let mut streams_file = String::new();
+        let mut output_file = String::new();
+        let mut polygons_file = String::new();
+        let mut max_hand = 5f64;
+        let mut max_slope = 5f64;
+        let mut min_area = 0f64;
+        let mut area_exponent = 0f64;
+        let mut ref_area = 1_000_000f64;
+        let mut use_mrvbf = false;
+        let mut threshold = 0.5f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-streams" {
+                streams_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_polygons" {
+                polygons_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-mrvbf" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    use_mrvbf = true;
+                }
+            } else if flag_val == "-max_hand"
+                || flag_val == "-max_slope"
+                || flag_val == "-min_area"
+                || flag_val == "-area_exponent"
+                || flag_val == "-ref_area"
+                || flag_val == "-threshold"
+            {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-max_hand" => max_hand = val,
+                    "-max_slope" => max_slope = val,
+                    "-min_area" => min_area = val,
+                    "-area_exponent" => area_exponent = val,
+                    "-ref_area" => ref_area = val,
+                    _ => threshold = val,
+                }
+            }
+        }
+
+        if max_hand <= 0f64 || max_slope <= 0f64 || ref_area <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --max_hand, --max_slope, and --ref_area parameters must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !polygons_file.is_empty()
+            && !polygons_file.contains(&sep)
+            && !polygons_file.contains("/")
+        {
+            polygons_file = format!("{}{}", working_directory, polygons_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        // make sure the streams raster shares the grid and CRS of the DEM
+        let streams = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let streams_nodata = streams.configs.nodata;
+        let mut cell_size_x = dem.configs.resolution_x;
+        let mut cell_size_y = dem.configs.resolution_y;
+        if dem.is_in_geographic_coordinates() {
+            let mid_lat = ((dem.configs.north + dem.configs.south) / 2.0).to_radians();
+            cell_size_x *= 111_320.0 * mid_lat.cos();
+            cell_size_y *= 111_320.0;
+        }
+        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+        let cell_area = cell_size_x * cell_size_y;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let grid_lengths = [
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+        ];
+
+        let mut z_data: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            z_data.set_row_data(row, dem.get_row_data(row));
+        }
+        let z_data = Arc::new(z_data);
+
+        // D8 flow directions; -1 indicates a cell without a downslope neighbour
+        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, 0i8, -1i8)?;
+        let (mut z, mut z_n): (f64, f64);
+        let (mut max_slope_n, mut slope): (f64, f64);
+        for row in 0..rows {
+            for col in 0..columns {
+                z = z_data.get_value(row, col);
+                if z != nodata {
+                    max_slope_n = 0f64;
+                    for n in 0..8 {
+                        z_n = z_data.get_value(row + dy[n], col + dx[n]);
+                        if z_n != nodata {
+                            slope = (z - z_n) / grid_lengths[n];
+                            if slope > max_slope_n {
+                                max_slope_n = slope;
+                                flow_dir.set_value(row, col, n as i8);
+                            }
+                        }
+                    }
+                    let dir = flow_dir.get_value(row, col);
+                    if dir >= 0 {
+                        num_inflowing.increment(
+                            row + dy[dir as usize],
+                            col + dx[dir as usize],
+                            1i8,
+                        );
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Flow directions: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // upslope drainage area
+        let mut area: Array2D<f64> = Array2D::new(rows, columns, cell_area, nodata)?;
+        let mut queue = VecDeque::new();
+        for row in 0..rows {
+            for col in 0..columns {
+                if z_data.get_value(row, col) != nodata && num_inflowing.get_value(row, col) == 0 {
+                    queue.push_back((row, col));
+                }
+            }
+        }
+        while let Some((row, col)) = queue.pop_front() {
+            let dir = flow_dir.get_value(row, col);
+            if dir >= 0 {
+                let (row_n, col_n) = (row + dy[dir as usize], col + dx[dir as usize]);
+                area.increment(row_n, col_n, area.get_value(row, col));
+                num_inflowing.decrement(row_n, col_n, 1i8);
+                if num_inflowing.get_value(row_n, col_n) == 0 {
+                    queue.push_back((row_n, col_n));
+                }
+            }
+        }
+        drop(num_inflowing);
+
+        // height above the nearest drainage, and the drainage area of that stream cell
+        let mut hand: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut stream_area: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut is_stream: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                let s = streams.get_value(row, col);
+                if s > 0f64
+                    && s != streams_nodata
+                    && z_data.get_value(row, col) != nodata
+                    && area.get_value(row, col) >= min_area
+                {
+                    is_stream.set_value(row, col, 1u8);
+                    hand.set_value(row, col, 0f64);
+                    stream_area.set_value(row, col, area.get_value(row, col));
+                    stack.push((row, col));
+                }
+            }
+        }
+        drop(streams);
+        let stream_cells = stack.clone();
+        while let Some((row, col)) = stack.pop() {
+            let z_stream = z_data.get_value(row, col) - hand.get_value(row, col);
+            let a_stream = stream_area.get_value(row, col);
+            for n in 0..8 {
+                let (row_n, col_n) = (row + dy[n], col + dx[n]);
+                if flow_dir.get_value(row_n, col_n) == ((n + 4) % 8) as i8
+                    && is_stream.get_value(row_n, col_n) == 0u8
+                {
+                    hand.set_value(row_n, col_n, z_data.get_value(row_n, col_n) - z_stream);
+                    stream_area.set_value(row_n, col_n, a_stream);
+                    stack.push((row_n, col_n));
+                }
+            }
+        }
+        drop(flow_dir);
+        drop(area);
+
+        // the terrain membership, based on either slope or MRVBF
+        let terrain_membership: Array2D<f64> = if use_mrvbf {
+            if verbose {
+                println!("Calculating MRVBF...")
+            };
+            let mut mrvbf_data = mrvbf(&z_data, cell_size_x, cell_size_y, verbose)?;
+            for row in 0..rows {
+                let mut data = mrvbf_data.get_row_data(row);
+                for col in 0..columns as usize {
+                    if data[col] != nodata {
+                        data[col] = if data[col] > 0f64 {
+                            fuzzy_membership(0.5, data[col])
+                        } else {
+                            0f64
+                        };
+                    }
+                }
+                mrvbf_data.set_row_data(row, data);
+            }
+            mrvbf_data
+        } else {
+            let slope_data = slope_percent(&z_data, cell_size_x, cell_size_y)?;
+            let slope_threshold = max_slope.to_radians().tan() * 100f64;
+            let mut membership: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+            for row in 0..rows {
+                for col in 0..columns {
+                    let s = slope_data.get_value(row, col);
+                    if s != nodata {
+                        membership.set_value(row, col, fuzzy_membership(s, slope_threshold));
+                    }
+                }
+            }
+            membership
+        };
+
+        let mut output = Raster::initialize_using_file(&output_file, &dem);
+        output.configs.data_type = DataType::F32;
+        for row in 0..rows {
+            let mut data = vec![nodata; columns as usize];
+            for col in 0..columns {
+                if z_data.get_value(row, col) != nodata {
+                    let h = hand.get_value(row, col);
+                    data[col as usize] = if h != nodata {
+                        let h_t = max_hand
+                            * (stream_area.get_value(row, col) / ref_area).powf(area_exponent);
+                        fuzzy_membership(h.max(0f64), h_t) * terrain_membership.get_value(row, col)
+                    } else {
+                        0f64
+                    };
+                }
+            }
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Calculating probability: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut num_polygons = 0;
+        if !polygons_file.is_empty() {
+            // the extent is grown outwards from the stream cells through cells above the threshold
+            let mut extent = Raster::initialize_using_file(&output_file, &dem);
+            extent.reinitialize_values(0f64);
+            let mut stack = vec![];
+            for &(row, col) in &stream_cells {
+                if output.get_value(row, col) >= threshold && extent.get_value(row, col) == 0f64 {
+                    extent.set_value(row, col, 1f64);
+                    stack.push((row, col));
+                }
+            }
+            while let Some((row, col)) = stack.pop() {
+                for n in 0..8 {
+                    let (row_n, col_n) = (row + dy[n], col + dx[n]);
+                    let p = output.get_value(row_n, col_n);
+                    if p != nodata && p >= threshold && extent.get_value(row_n, col_n) == 0f64 {
+                        extent.set_value(row_n, col_n, 1f64);
+                        stack.push((row_n, col_n));
+                    }
+                }
+            }
+
+            if verbose {
+                println!("Tracing valley-bottom polygons...")
+            };
+            let (geometries, _) = raster_to_polygons(&extent, 0f64, verbose)?;
+            drop(extent);
+
+            let mut polygons = Shapefile::new(&polygons_file, ShapeType::Polygon)?;
+            polygons.projection = dem.configs.coordinate_ref_system_wkt.clone();
+            polygons.attributes.add_field(&AttributeField::new(
+                "FID",
+                FieldDataType::Int,
+                10u8,
+                0u8,
+            ));
+            polygons.attributes.add_field(&AttributeField::new(
+                "AREA",
+                FieldDataType::Real,
+                14u8,
+                4u8,
+            ));
+            for geometry in geometries {
+                // the first part is the hull and any others are holes
+                let mut poly_area = 0f64;
+                for part in 0..geometry.num_parts as usize {
+                    let start_point = geometry.parts[part] as usize;
+                    let end_point = if part < geometry.num_parts as usize - 1 {
+                        geometry.parts[part + 1] as usize
+                    } else {
+                        geometry.num_points as usize
+                    };
+                    let a = polygon_area(&geometry.points[start_point..end_point]);
+                    poly_area += if part == 0 { a } else { -a };
+                }
+                num_polygons += 1;
+                polygons.add_record(geometry);
+                polygons.attributes.add_record(
+                    vec![FieldData::Int(num_polygons), FieldData::Real(poly_area)],
+                    false,
+                );
+            }
+
+            let _ = match polygons.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Polygons file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "spectrum.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("DEM file: {}", dem_file));
+        output.add_metadata_entry(format!("Streams file: {}", streams_file));
+        output.add_metadata_entry(format!("HAND threshold: {}", max_hand));
+        if use_mrvbf {
+            output.add_metadata_entry("Terrain criterion: MRVBF".to_string());
+        } else {
+            output.add_metadata_entry(format!("Slope threshold: {}", max_slope));
+        }
+        output.add_metadata_entry(format!("Minimum stream drainage area: {}", min_area));
+        output.add_metadata_entry(format!("Drainage area exponent: {}", area_exponent));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            if !polygons_file.is_empty() {
+                println!("Number of valley-bottom polygons: {}", num_polygons);
+            }
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
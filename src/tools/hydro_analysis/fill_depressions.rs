@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 28/06/2017
-Last Modified: 12/12/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -11,7 +11,7 @@ use crate::structures::Array2D;
 use crate::tools::*;
 use std::cmp::Ordering;
 use std::cmp::Ordering::Equal;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::env;
 use std::f64;
 use std::i32;
@@ -54,6 +54,19 @@ use std::thread;
 /// Note that this tool will not fill in NoData regions within the DEM. It is advisable to remove such regions using the
 /// `FillMissingData` tool prior to application.
 ///
+/// For very large (e.g. continental-scale) DEMs, the optional `--parallel_tiled` mode replaces the pit-based filling
+/// described above with the parallel tiled priority-flood algorithm of Barnes (2016). The DEM is divided into square
+/// tiles (`--tile_size`, 1024 cells by default), each of which is flooded independently and concurrently from its own
+/// perimeter. Only the spill elevations between tile-perimeter watersheds are retained from this first pass, which are
+/// then resolved into a global spill graph and used in a second concurrent pass to produce the final filled tiles. The
+/// working memory of each pass is therefore bounded by the tile size rather than the DEM size. In this mode, grid cells
+/// along the DEM edge or adjacent to NoData cells are treated as outlets, and the `--max_depth` option is not supported.
+/// The flat-fixing step (`--fix_flats`) is applied to the result in the same way as in the default mode.
+///
+/// # Reference
+/// Barnes, R. (2016). Parallel priority-flood depression filling for trillion cell digital elevation models on desktops
+/// or clusters. *Computers & Geosciences*, 96, 56-68.
+///
 /// # See Also
 /// `BreachDepressionsLeastCost`, `BreachDepressions`, `Sink`, `DepthInSink`, `FillMissingData`
 pub struct FillDepressions {
@@ -119,6 +132,27 @@ impl FillDepressions {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Use parallel tiled priority-flood?".to_owned(),
+            flags: vec!["--parallel_tiled".to_owned()],
+            description:
+                "Optional flag indicating whether to use the parallel tiled priority-flood algorithm (Barnes, 2016)."
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tile size (cells)".to_owned(),
+            flags: vec!["--tile_size".to_owned()],
+            description: "Optional tile size, in grid cells, used in parallel tiled mode."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1024".to_string()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -131,7 +165,8 @@ impl FillDepressions {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif --fix_flats",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif --fix_flats
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif --fix_flats --parallel_tiled --tile_size=2048",
             short_exe, name
         )
         .replace("*", &sep);
@@ -185,6 +220,8 @@ impl WhiteboxTool for FillDepressions {
         let mut fix_flats = false;
         let mut flat_increment = f64::NAN;
         let mut max_depth = f64::INFINITY;
+        let mut parallel_tiled = false;
+        let mut tile_size = 1024isize;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -230,6 +267,22 @@ impl WhiteboxTool for FillDepressions {
                         .parse::<f64>()
                         .expect(&format!("Error parsing {}", flag_val))
                 };
+            } else if flag_val == "-parallel_tiled" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    parallel_tiled = true;
+                }
+            } else if flag_val == "-tile_size" {
+                tile_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                };
             } else if flag_val == "-max_depth" {
                 max_depth = if keyval {
                     vec[1]
@@ -245,6 +298,19 @@ impl WhiteboxTool for FillDepressions {
             }
         }
 
+        if parallel_tiled && max_depth != f64::INFINITY {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --max_depth option is not supported in --parallel_tiled mode.",
+            ));
+        }
+        if tile_size < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --tile_size must be at least 3 grid cells.",
+            ));
+        }
+
         if verbose {
             println!("***************{}", "*".repeat(self.get_tool_name().len()));
             println!("* Welcome to {} *", self.get_tool_name());
@@ -267,7 +333,7 @@ impl WhiteboxTool for FillDepressions {
             println!("Reading data...")
         };
 
-        let input = Raster::new(&input_file, "r")?;
+        let input = Arc::new(Raster::new(&input_file, "r")?);
 
         let start = Instant::now();
         let rows = input.configs.rows as isize;
@@ -301,61 +367,80 @@ impl WhiteboxTool for FillDepressions {
         let dx = [1, 1, 1, 0, -1, -1, -1, 0];
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
 
-        // Find pit cells. This step is parallelized.
         let num_procs = num_cpus::get() as isize;
-        let output2 = Arc::new(output);
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let output2 = output2.clone();
-            let tx = tx.clone();
-            thread::spawn(move || {
-                let mut z: f64;
-                let mut zn: f64;
-                let mut flag: bool;
-                let mut pits = vec![];
-                for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
-                    for col in 1..columns - 1 {
-                        z = output2.get_value(row, col);
-                        if z != nodata {
-                            flag = true;
-                            for n in 0..8 {
-                                zn = output2.get_value(row + dy[n], col + dx[n]);
-                                if zn < z || zn == nodata {
-                                    // It either has a lower neighbour or is an edge cell.
-                                    flag = false;
-                                    break;
-                                }
-                            }
-                            if flag {
-                                // it's a cell with undefined flow
-                                pits.push((row, col, z));
+        let mut undefined_flow_cells = vec![];
+        let mut possible_outlets = vec![];
+        if parallel_tiled {
+            // Barnes (2016) parallel tiled priority-flood. The pit-based filling below is skipped
+            // because there are no undefined flow cells left to solve.
+            fill_tiled(&input, &mut output, tile_size, num_procs, verbose)?;
+
+            // Cells that were raised to the level of an unraised neighbour drain through that neighbour,
+            // which are the candidate outlets for the flat-fixing step.
+            for row in 0..rows {
+                for col in 0..columns {
+                    z = output.get_value(row, col);
+                    if z != nodata && z > input.get_value(row, col) {
+                        for n in 0..8 {
+                            rn = row + dy[n];
+                            cn = col + dx[n];
+                            if output.get_value(rn, cn) == z && input.get_value(rn, cn) == z {
+                                possible_outlets.push((rn, cn));
                             }
                         }
                     }
                 }
-                tx.send(pits).unwrap();
-            });
-        }
+            }
+        } else {
+            // Find pit cells. This step is parallelized. The output is an unmodified copy of the
+            // input at this stage, so the shared input is searched.
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let mut z: f64;
+                    let mut zn: f64;
+                    let mut flag: bool;
+                    let mut pits = vec![];
+                    for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
+                        for col in 1..columns - 1 {
+                            z = input.get_value(row, col);
+                            if z != nodata {
+                                flag = true;
+                                for n in 0..8 {
+                                    zn = input.get_value(row + dy[n], col + dx[n]);
+                                    if zn < z || zn == nodata {
+                                        // It either has a lower neighbour or is an edge cell.
+                                        flag = false;
+                                        break;
+                                    }
+                                }
+                                if flag {
+                                    // it's a cell with undefined flow
+                                    pits.push((row, col, z));
+                                }
+                            }
+                        }
+                    }
+                    tx.send(pits).unwrap();
+                });
+            }
 
-        let mut undefined_flow_cells = vec![];
-        for p in 0..num_procs {
-            let mut pits = rx.recv().expect("Error receiving data from thread.");
-            undefined_flow_cells.append(&mut pits);
+            for p in 0..num_procs {
+                let mut pits = rx.recv().expect("Error receiving data from thread.");
+                undefined_flow_cells.append(&mut pits);
 
-            if verbose {
-                progress = (100.0_f64 * (p + 1) as f64 / num_procs as f64) as usize;
-                if progress != old_progress {
-                    println!("Finding pit cells: {}%", progress);
-                    old_progress = progress;
+                if verbose {
+                    progress = (100.0_f64 * (p + 1) as f64 / num_procs as f64) as usize;
+                    if progress != old_progress {
+                        println!("Finding pit cells: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
         }
 
-        output = match Arc::try_unwrap(output2) {
-            Ok(val) => val,
-            Err(_) => panic!("Error unwrapping 'output'"),
-        };
-
         let num_deps = undefined_flow_cells.len();
 
         // Now we need to perform an in-place depression filling
@@ -363,7 +448,6 @@ impl WhiteboxTool for FillDepressions {
         let mut minheap2 = BinaryHeap::new();
         let mut visited: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
         let mut flats: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
-        let mut possible_outlets = vec![];
         // solve from highest to lowest
         undefined_flow_cells.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Equal));
         let mut pit_id = 1;
@@ -785,3 +869,370 @@ impl Ord for GridCell2 {
         self.partial_cmp(other).unwrap()
     }
 }
+
+/// Label of the cells that drain directly to the DEM edge or to NoData cells.
+const OCEAN: u64 = u64::MAX - 1;
+/// Label of NoData cells and of cells that have not yet been reached by the flood.
+const UNLABELLED: u64 = u64::MAX;
+
+/// Fills the depressions in `output` using the parallel tiled priority-flood method of Barnes (2016).
+/// The first pass floods each tile from its perimeter and retains only the spill elevations between
+/// the perimeter-cell watersheds. These are resolved into the elevation at which each watershed spills
+/// to an outlet, which the second pass applies to the re-flooded tiles.
+fn fill_tiled(
+    input: &Arc<Raster>,
+    output: &mut Raster,
+    tile_size: isize,
+    num_procs: isize,
+    verbose: bool,
+) -> Result<(), Error> {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let tiles_x = (columns + tile_size - 1) / tile_size;
+    let tiles_y = (rows + tile_size - 1) / tile_size;
+    let num_tiles = tiles_x * tiles_y;
+    let tile_bounds = move |tile: isize| {
+        let r0 = (tile / tiles_x) * tile_size;
+        let c0 = (tile % tiles_x) * tile_size;
+        (
+            r0,
+            c0,
+            (r0 + tile_size).min(rows),
+            (c0 + tile_size).min(columns),
+        )
+    };
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    // First pass: flood each tile and collect the spill graph.
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
+                let (r0, c0, r1, c1) = tile_bounds(tile);
+                let (_, _, edges) = flood_tile(&input, r0, c0, r1, c1);
+                tx.send(edges).unwrap();
+            }
+        });
+    }
+
+    let mut graph: HashMap<u64, Vec<(u64, f64)>> = HashMap::new();
+    for t in 0..num_tiles {
+        let edges = rx.recv().expect("Error receiving data from thread.");
+        for ((a, b), z) in edges {
+            graph.entry(a).or_insert(vec![]).push((b, z));
+            graph.entry(b).or_insert(vec![]).push((a, z));
+        }
+        if verbose {
+            progress = (100.0_f64 * (t + 1) as f64 / num_tiles as f64) as usize;
+            if progress != old_progress {
+                println!("Flooding tiles: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    // Resolve the lowest elevation at which each watershed can spill to an outlet.
+    if verbose {
+        println!("Resolving spill graph ({} watersheds)...", graph.len());
+    }
+    let mut spill: HashMap<u64, f64> = HashMap::new();
+    let mut minheap = BinaryHeap::new();
+    spill.insert(OCEAN, f64::NEG_INFINITY);
+    minheap.push(LabelSpill {
+        label: OCEAN,
+        priority: f64::NEG_INFINITY,
+    });
+    while let Some(cell) = minheap.pop() {
+        if cell.priority > spill[&cell.label] {
+            continue; // a lower spill has already been found for this watershed
+        }
+        if let Some(neighbours) = graph.get(&cell.label) {
+            for &(label, z) in neighbours {
+                let z = z.max(cell.priority);
+                if z < *spill.get(&label).unwrap_or(&f64::INFINITY) {
+                    spill.insert(label, z);
+                    minheap.push(LabelSpill {
+                        label: label,
+                        priority: z,
+                    });
+                }
+            }
+        }
+    }
+    drop(graph);
+
+    // Second pass: re-flood each tile and raise each watershed to its spill elevation.
+    let spill = Arc::new(spill);
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let spill = spill.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
+                let (r0, c0, r1, c1) = tile_bounds(tile);
+                let (mut filled, labels, _) = flood_tile(&input, r0, c0, r1, c1);
+                for i in 0..filled.len() {
+                    if labels[i] != UNLABELLED {
+                        if let Some(z) = spill.get(&labels[i]) {
+                            if *z > filled[i] {
+                                filled[i] = *z;
+                            }
+                        }
+                    }
+                }
+                tx.send((tile, filled, labels)).unwrap();
+            }
+        });
+    }
+
+    for t in 0..num_tiles {
+        let (tile, filled, labels) = rx.recv().expect("Error receiving data from thread.");
+        let (r0, c0, r1, c1) = tile_bounds(tile);
+        let width = c1 - c0;
+        for row in r0..r1 {
+            for col in c0..c1 {
+                let i = ((row - r0) * width + col - c0) as usize;
+                if labels[i] != UNLABELLED {
+                    output.set_value(row, col, filled[i]);
+                }
+            }
+        }
+        if verbose {
+            progress = (100.0_f64 * (t + 1) as f64 / num_tiles as f64) as usize;
+            if progress != old_progress {
+                println!("Filling tiles: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Priority-floods the tile spanning rows `r0..r1` and columns `c0..c1` from its perimeter cells and
+/// from cells bordering NoData. Returns the tile-filled elevations, the label of the perimeter
+/// watershed of each cell, and the minimum spill elevation between each pair of adjacent watersheds,
+/// including those of neighbouring tiles.
+fn flood_tile(
+    input: &Raster,
+    r0: isize,
+    c0: isize,
+    r1: isize,
+    c1: isize,
+) -> (Vec<f64>, Vec<u64>, HashMap<(u64, u64), f64>) {
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let width = c1 - c0;
+    let num_cells = ((r1 - r0) * width) as usize;
+    let mut filled = vec![nodata; num_cells];
+    let mut labels = vec![UNLABELLED; num_cells];
+    let mut edges: HashMap<(u64, u64), f64> = HashMap::new();
+    let mut add_edge = |a: u64, b: u64, z: f64| {
+        if a != b {
+            let key = if a < b { (a, b) } else { (b, a) };
+            let e = edges.entry(key).or_insert(z);
+            if z < *e {
+                *e = z;
+            }
+        }
+    };
+    let seed_label = |row: isize, col: isize| {
+        for n in 0..8 {
+            if input.get_value(row + dy[n], col + dx[n]) == nodata {
+                return OCEAN;
+            }
+        }
+        (row * columns + col) as u64
+    };
+
+    let mut minheap = BinaryHeap::new();
+    let (mut z, mut zn): (f64, f64);
+    for row in r0..r1 {
+        for col in c0..c1 {
+            z = input.get_value(row, col);
+            if z == nodata {
+                continue;
+            }
+            let label = seed_label(row, col);
+            let on_perimeter = row == r0 || row == r1 - 1 || col == c0 || col == c1 - 1;
+            if on_perimeter || label == OCEAN {
+                let i = ((row - r0) * width + col - c0) as usize;
+                filled[i] = z;
+                labels[i] = label;
+                minheap.push(GridCell {
+                    row: row,
+                    column: col,
+                    priority: z,
+                });
+                if on_perimeter {
+                    // connect to the perimeter cells of the neighbouring tiles
+                    for n in 0..8 {
+                        let (rn, cn) = (row + dy[n], col + dx[n]);
+                        if rn < r0 || rn >= r1 || cn < c0 || cn >= c1 {
+                            zn = input.get_value(rn, cn);
+                            if zn != nodata {
+                                add_edge(label, seed_label(rn, cn), z.max(zn));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    while let Some(cell) = minheap.pop() {
+        let i = ((cell.row - r0) * width + cell.column - c0) as usize;
+        z = filled[i];
+        for n in 0..8 {
+            let (rn, cn) = (cell.row + dy[n], cell.column + dx[n]);
+            if rn < r0 || rn >= r1 || cn < c0 || cn >= c1 {
+                continue;
+            }
+            let j = ((rn - r0) * width + cn - c0) as usize;
+            if labels[j] == UNLABELLED {
+                zn = input.get_value(rn, cn);
+                if zn != nodata {
+                    labels[j] = labels[i];
+                    filled[j] = zn.max(z);
+                    minheap.push(GridCell {
+                        row: rn,
+                        column: cn,
+                        priority: filled[j],
+                    });
+                }
+            } else if labels[j] != labels[i] {
+                add_edge(labels[i], labels[j], z.max(filled[j]));
+            }
+        }
+    }
+
+    (filled, labels, edges)
+}
+
+#[derive(PartialEq, Debug)]
+struct LabelSpill {
+    label: u64,
+    priority: f64,
+}
+
+impl Eq for LabelSpill {}
+
+impl PartialOrd for LabelSpill {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for LabelSpill {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FillDepressions;
+    use crate::raster::{DataType, Raster, RasterConfigs};
+    use crate::tools::WhiteboxTool;
+    use std::env;
+    use std::fs;
+
+    /// Writes a tilted plane containing several bowl-shaped depressions, most of which straddle
+    /// the seams between 5 x 5 tiles, and a NoData hole that spans a tile corner.
+    fn write_dem(file: &str) -> Raster {
+        let configs = RasterConfigs {
+            rows: 21,
+            columns: 23,
+            north: 21.0,
+            south: 0.0,
+            east: 23.0,
+            west: 0.0,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            nodata: -32768.0,
+            data_type: DataType::F64,
+            ..Default::default()
+        };
+        let bowls = [
+            (5.0, 5.0, 3.5, 4.0),
+            (10.0, 10.0, 4.5, 6.0),
+            (12.0, 17.0, 2.5, 3.0),
+            (4.0, 14.0, 3.0, 5.0),
+            (17.0, 6.0, 2.5, 4.0),
+        ];
+        let mut dem = Raster::initialize_using_config(file, &configs);
+        for row in 0..21isize {
+            for col in 0..23isize {
+                let mut z = 100.0 + 0.5 * col as f64 + 0.25 * row as f64;
+                for &(r, c, radius, depth) in &bowls {
+                    let dist = (row as f64 - r).hypot(col as f64 - c);
+                    if dist < radius {
+                        z -= depth * (1.0 - dist / radius);
+                    }
+                }
+                // keep the elevations exactly representable
+                dem.set_value(row, col, (z * 8.0).floor() / 8.0);
+            }
+        }
+        for &(row, col) in &[(14, 9), (14, 10), (15, 9), (15, 10)] {
+            dem.set_value(row, col, -32768.0);
+        }
+        dem.write().unwrap();
+        dem
+    }
+
+    #[test]
+    fn test_parallel_tiled_matches_serial_fill() {
+        let dir = env::temp_dir().join(format!("wbt_fill_tiled_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let wd = format!("{}/", dir.display());
+        let dem = write_dem(&format!("{}dem.tif", wd));
+        let tool = FillDepressions::new();
+        tool.run(
+            vec![
+                "--dem=dem.tif".to_string(),
+                "--output=serial.tif".to_string(),
+            ],
+            &wd,
+            false,
+        )
+        .unwrap();
+        tool.run(
+            vec![
+                "--dem=dem.tif".to_string(),
+                "--output=tiled.tif".to_string(),
+                "--parallel_tiled".to_string(),
+                "--tile_size=5".to_string(),
+            ],
+            &wd,
+            false,
+        )
+        .unwrap();
+        let serial = Raster::new(&format!("{}serial.tif", wd), "r").unwrap();
+        let tiled = Raster::new(&format!("{}tiled.tif", wd), "r").unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut num_filled = 0;
+        for row in 0..21isize {
+            for col in 0..23isize {
+                assert_eq!(
+                    tiled.get_value(row, col),
+                    serial.get_value(row, col),
+                    "cell ({}, {})",
+                    row,
+                    col
+                );
+                if serial.get_value(row, col) != dem.get_value(row, col) {
+                    num_filled += 1;
+                }
+            }
+        }
+        assert!(num_filled > 0);
+    }
+}
@@ -0,0 +1,580 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::depression_tree::{Depression, DepressionTree, OCEAN};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::cmp::Ordering::Equal;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+// the tolerance, in elevation units times cells, below which a depression is considered full
+const EPSILON: f64 = 1e-9;
+
+/// This tool redistributes a depth of runoff over a digital elevation model (DEM; `--dem`) into the
+/// DEM's depressions using the Fill-Spill-Merge algorithm of Barnes et al. (2021). The runoff depth
+/// raster (`--runoff`), which must have the same dimensions as the DEM and use the same units as
+/// the elevations, may represent a uniform or spatially variable rainfall excess, snowmelt, or the
+/// water surplus of a hydrological model. Runoff flows downslope to the pit of each cell's
+/// depression, which fills until it reaches its spill elevation. The excess water then spills over
+/// the depression's outlet into the neighbouring depression; once two neighbouring depressions are
+/// both full, they merge into a single lake that continues to fill to its own spill elevation.
+/// Water that spills over the edge of the DEM, or that falls on cells draining to the edge, is lost
+/// from the system. The tool thereby accounts for the fill-and-spill behaviour of depressional
+/// landscapes, such as prairie pothole regions, in which depressions only become connected to the
+/// drainage network once they, and the depressions upstream of them, are filled.
+///
+/// Rather than simulating the flow of water through time, the tool traverses the hierarchy of
+/// nested depressions (see `DepressionHierarchy`), such that the final distribution of water is
+/// found without repeatedly flooding the DEM. The outputs are rasters of the final water depth
+/// (`--output`) and of the water surface elevation (`--out_surface`), the latter being equal to the
+/// DEM elevation where cells are dry. The volumes of runoff, of water stored in depressions, and of
+/// water lost at the DEM edge are reported.
+///
+/// # Reference
+/// Barnes, R., Callaghan, K. L., and Wickert, A. D. (2021). Computing water flow through complex
+/// landscapes - Part 3: Fill-Spill-Merge: flow routing in depression hierarchies. *Earth Surface
+/// Dynamics*, 9(1), 105-121.
+///
+/// # See Also
+/// `DepressionHierarchy`, `DepthInSink`, `FillDepressions`, `StochasticDepressionAnalysis`,
+/// `CoastalFlooding`
+pub struct FillSpillMerge {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FillSpillMerge {
+    pub fn new() -> FillSpillMerge {
+        // public constructor
+        let name = "FillSpillMerge".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Redistributes a depth of runoff into the depressions of a DEM using the Fill-Spill-Merge algorithm.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Runoff Depth File".to_owned(),
+            flags: vec!["--runoff".to_owned()],
+            description: "Input raster file of runoff depths, in elevation units.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Water Depth File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of water depths.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Water Surface File".to_owned(),
+            flags: vec!["--out_surface".to_owned()],
+            description: "Output raster file of water surface elevations.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --runoff=runoff.tif -o=depth.tif --out_surface=surface.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        FillSpillMerge {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FillSpillMerge {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut runoff_file = String::new();
+        let mut output_file = String::new();
+        let mut surface_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-runoff" {
+                runoff_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_surface" {
+                surface_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !runoff_file.contains(&sep) && !runoff_file.contains("/") {
+            runoff_file = format!("{}{}", working_directory, runoff_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if surface_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output water surface file (--out_surface) must be specified.",
+            ));
+        }
+        if !surface_file.contains(&sep) && !surface_file.contains("/") {
+            surface_file = format!("{}{}", working_directory, surface_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let runoff = Raster::new(&runoff_file, "r")?;
+
+        // make sure the inputs share the same grid and CRS
+        let runoff = align_raster_input(&input, runoff)?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let runoff_nodata = runoff.configs.nodata;
+        let cell_area = input.configs.resolution_x * input.configs.resolution_y;
+
+        let mut dem: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            dem.set_row_data(row, input.get_row_data(row));
+        }
+
+        if verbose {
+            println!("Building the depression hierarchy...");
+        }
+        let tree = DepressionTree::new(
+            &dem,
+            nodata,
+            input.configs.resolution_x,
+            input.configs.resolution_y,
+        )?;
+
+        // Runoff flows by steepest descent to the pit of the cell's leaf depression, or is lost
+        // if the cell drains to the ocean. Volumes are in elevation units times cells.
+        let n = tree.depressions.len();
+        let mut water = vec![0f64; n];
+        let mut total_runoff = 0f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                let label = tree.labels.get_value(row, col);
+                let r = runoff.get_value(row, col);
+                if label == OCEAN || label >= 0 {
+                    if r != runoff_nodata && r > 0f64 {
+                        total_runoff += r;
+                        if label >= 0 {
+                            water[label as usize] += r;
+                        }
+                    }
+                }
+            }
+        }
+
+        if verbose {
+            println!("Filling, spilling, and merging...");
+        }
+        let levels = fill_spill_merge(&tree, &dem, nodata, water);
+        let mut stored = 0f64;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.palette = "blueyellow.plt".to_string();
+        let mut surface = Raster::initialize_using_file(&surface_file, &input);
+        surface.configs.data_type = DataType::F32;
+        for row in 0..rows {
+            let mut depths = vec![nodata; columns as usize];
+            let mut elevs = vec![nodata; columns as usize];
+            for col in 0..columns {
+                let z = dem.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                let label = tree.labels.get_value(row, col);
+                let depth = if label >= 0 {
+                    (levels[label as usize] - z).max(0f64)
+                } else {
+                    0f64
+                };
+                stored += depth;
+                depths[col as usize] = depth;
+                elevs[col as usize] = z + depth;
+            }
+            output.set_row_data(row, depths);
+            surface.set_row_data(row, elevs);
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Runoff volume: {:.3}", total_runoff * cell_area);
+            println!("Volume stored in depressions: {:.3}", stored * cell_area);
+            println!(
+                "Volume lost at the DEM edge: {:.3}",
+                (total_runoff - stored).max(0f64) * cell_area
+            );
+        }
+        for r in [&mut output, &mut surface].iter_mut() {
+            r.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            r.add_metadata_entry(format!("Input DEM file: {}", input_file));
+            r.add_metadata_entry(format!("Input runoff file: {}", runoff_file));
+            r.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        let _ = match surface.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Water surface file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Distributes the water collected at the pits of the leaf depressions through the depression
+/// hierarchy and returns the water surface elevation of each leaf depression.
+fn fill_spill_merge(
+    tree: &DepressionTree,
+    dem: &Array2D<f64>,
+    nodata: f64,
+    water: Vec<f64>,
+) -> Vec<f64> {
+    let deps = &tree.depressions;
+    let n = deps.len();
+    let capacity: Vec<f64> = deps
+        .iter()
+        .map(|d| {
+            if d.spill_elevation.is_finite() {
+                d.volume
+            } else {
+                f64::INFINITY // a depression without an outlet never overflows
+            }
+        })
+        .collect();
+
+    // the water contained by each depression and its descendants; children always precede
+    // their parents
+    let mut total = water;
+    for i in 0..n {
+        if let Some(p) = deps[i].parent {
+            total[p] += total[i];
+        }
+    }
+    let is_full = |total: &Vec<f64>, i: usize| total[i] >= capacity[i] - EPSILON;
+
+    // Once both of a meta-depression's children have been processed, the excess water of each
+    // child spills into its sibling, where it fills the sibling's depressions, and whatever
+    // remains once both are full forms a lake within the meta-depression.
+    let excess = |total: &mut Vec<f64>, i: usize, target: Option<usize>, stop: usize| {
+        let extra = total[i] - capacity[i];
+        if extra > 0f64 {
+            add_water(deps, total, i, -extra);
+            if let Some(leaf) = target {
+                overflow_into(deps, &capacity, total, leaf, stop, extra);
+            }
+        }
+    };
+    for i in 0..n {
+        if deps[i].is_leaf() {
+            continue;
+        }
+        for &c in &deps[i].children {
+            excess(&mut total, c, deps[c].overflow_leaf, i);
+        }
+    }
+
+    // Depressions that overflow to the ocean may spill into another depression tree whose root
+    // overflowed at a lower elevation, which may not be full, so the roots are processed from
+    // the highest spill elevation to the lowest.
+    let mut roots: Vec<usize> = (0..n).filter(|&i| deps[i].parent.is_none()).collect();
+    roots.sort_by(|&a, &b| {
+        deps[b]
+            .spill_elevation
+            .partial_cmp(&deps[a].spill_elevation)
+            .unwrap_or(Equal)
+    });
+    for &r in &roots {
+        let stop = deps[r].overflow_leaf.map(|leaf| root_of(deps, leaf));
+        match stop {
+            Some(s) => excess(&mut total, r, deps[r].overflow_leaf, s),
+            None => excess(&mut total, r, None, r),
+        }
+    }
+
+    // Each leaf lies within the body of water of its lowest ancestor that is not full or, if
+    // the water spilled out of a full depression into a sibling that is not full, within that
+    // full depression.
+    let mut body = vec![0usize; tree.num_leaves];
+    for leaf in 0..tree.num_leaves {
+        let mut node = leaf;
+        loop {
+            if !is_full(&total, node) {
+                break;
+            }
+            match deps[node].parent {
+                Some(p) => {
+                    let sibling = if deps[p].children[0] == node {
+                        deps[p].children[1]
+                    } else {
+                        deps[p].children[0]
+                    };
+                    if !is_full(&total, sibling) {
+                        break;
+                    }
+                    node = p;
+                }
+                None => break,
+            }
+        }
+        body[leaf] = node;
+    }
+
+    // Full bodies of water stand at their spill elevations; the levels of the others are found
+    // from the elevations of their cells below the spill elevation.
+    let mut level: Vec<f64> = (0..n).map(|i| deps[i].spill_elevation).collect();
+    let mut elevations: Vec<Vec<f64>> = vec![vec![]; n];
+    for row in 0..dem.rows {
+        for col in 0..dem.columns {
+            let label = tree.labels.get_value(row, col);
+            let z = dem.get_value(row, col);
+            if label < 0 || z == nodata {
+                continue;
+            }
+            let b = body[label as usize];
+            if !is_full(&total, b) && z < deps[b].spill_elevation {
+                elevations[b].push(z);
+            }
+        }
+    }
+    for b in 0..n {
+        if elevations[b].is_empty() {
+            continue;
+        }
+        let z = &mut elevations[b];
+        z.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+        // the level at which the volume above the k lowest cells equals the water volume
+        let mut sum = 0f64;
+        for k in 0..z.len() {
+            sum += z[k];
+            level[b] = (total[b] + sum) / (k + 1) as f64;
+            if k + 1 == z.len() || level[b] <= z[k + 1] {
+                break;
+            }
+        }
+    }
+
+    (0..tree.num_leaves)
+        .map(|leaf| {
+            let b = body[leaf];
+            if is_full(&total, b) || !elevations[b].is_empty() {
+                level[b]
+            } else {
+                f64::NEG_INFINITY
+            }
+        })
+        .collect()
+}
+
+/// Moves a volume of water into the depression `start`, which lies within the depression
+/// `stop`, and from there to the depressions into which it overflows when full.
+fn overflow_into(
+    deps: &Vec<Depression>,
+    capacity: &Vec<f64>,
+    total: &mut Vec<f64>,
+    start: usize,
+    stop: usize,
+    mut extra: f64,
+) {
+    let mut node = start;
+    loop {
+        if node == stop {
+            add_water(deps, total, node, extra);
+            return;
+        }
+        let room = capacity[node] - total[node];
+        if room > 0f64 {
+            let amount = room.min(extra);
+            add_water(deps, total, node, amount);
+            extra -= amount;
+            if extra <= 0f64 {
+                return;
+            }
+        }
+        // the depression is full; its water spills into its sibling unless that is also full,
+        // in which case the two have merged
+        match deps[node].parent {
+            Some(p) => {
+                let sibling = if deps[p].children[0] == node {
+                    deps[p].children[1]
+                } else {
+                    deps[p].children[0]
+                };
+                node = match deps[node].overflow_leaf {
+                    Some(leaf) if total[sibling] < capacity[sibling] - EPSILON => leaf,
+                    _ => p,
+                };
+            }
+            None => {
+                add_water(deps, total, node, extra);
+                return;
+            }
+        }
+    }
+}
+
+// adds water to a depression and each of its ancestors
+fn add_water(deps: &Vec<Depression>, total: &mut Vec<f64>, i: usize, amount: f64) {
+    let mut node = Some(i);
+    while let Some(j) = node {
+        total[j] += amount;
+        node = deps[j].parent;
+    }
+}
+
+fn root_of(deps: &Vec<Depression>, i: usize) -> usize {
+    let mut node = i;
+    while let Some(p) = deps[node].parent {
+        node = p;
+    }
+    node
+}
@@ -210,10 +210,8 @@ impl WhiteboxTool for FindParallelFlow {
                     println!("Reading streams data...")
                 };
                 let r = Raster::new(&streams_file, "r")?;
-                if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
-                }
+                // make sure the inputs share the same grid and CRS
+                let r = align_raster_input(&pntr, r)?;
                 streams_nodata = r.configs.nodata;
                 r.get_data_as_array2d()
             }
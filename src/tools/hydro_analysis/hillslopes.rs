@@ -219,7 +219,8 @@ impl WhiteboxTool for Hillslopes {
         };
 
         let pntr = Raster::new(&d8_file, "r")?;
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -229,16 +230,6 @@ impl WhiteboxTool for Hillslopes {
         let nodata = streams.configs.nodata;
         let pntr_nodata = pntr.configs.nodata;
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         // First assign each stream link a unique identifier
         let mut pourpts: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
         let mut stack = Vec::with_capacity((rows * columns) as usize);
@@ -2,13 +2,16 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 28/05/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::structures::Array2D;
 use crate::tools::*;
+use crate::utils::checkpoint::{
+    file_signature, Checkpoint, CheckpointReader, CheckpointWriter, DEFAULT_CHECKPOINT_INTERVAL,
+};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
 use std::env;
@@ -28,12 +31,17 @@ use std::path::Path;
 /// applied to very large DEMs. It is not necessary to pre-process the input DEM (`--dem`) to remove topographic depressions
 /// and flat areas. The internal flow-accumulation operation will not be confounded by the presence of these features.
 ///
+/// Because runs on large DEMs can take many hours, the tool can optionally save its intermediate state to a checkpoint
+/// file (`--checkpoint`) every `--checkpoint_interval` minutes (15 by default). If a run is interrupted, e.g. by a crash
+/// or reboot, running the tool again with the same inputs, parameters, and checkpoint file resumes the calculation from
+/// the last checkpoint. The checkpoint file is deleted once the tool completes successfully.
+///
 /// # Reference
 /// Lindsay, JB (2015) Modelling the spatial pattern of potential impoundment size from DEMs.
 /// Online resource: [Whitebox Blog](https://whiteboxgeospatial.wordpress.com/2015/04/29/modelling-the-spatial-pattern-of-potential-impoundment-size-from-dems/)
 ///
 /// # See Also
-/// `InsertDams`, `StochasticDepressionAnalysis`
+/// `InsertDams`, `StochasticDepressionAnalysis`, `BreachEmbankments`
 pub struct ImpoundmentSizeIndex {
     name: String,
     description: String,
@@ -91,6 +99,24 @@ impl ImpoundmentSizeIndex {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Checkpoint File (optional)".to_owned(),
+            flags: vec!["--checkpoint".to_owned()],
+            description: "Optional file used to periodically save progress, allowing an interrupted run to be resumed.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Checkpoint Interval (minutes)".to_owned(),
+            flags: vec!["--checkpoint_interval".to_owned()],
+            description: "Time between checkpoints, in minutes.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("15.0".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -159,6 +185,8 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut output_file = String::new();
         let mut out_type = 0; // 0 = area; 1 = volume
         let mut dam_length = 111f64;
+        let mut checkpoint_file = String::new();
+        let mut checkpoint_interval = DEFAULT_CHECKPOINT_INTERVAL;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -213,6 +241,24 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                         .parse::<f64>()
                         .expect(&format!("Error parsing {}", flag_val))
                 };
+            } else if flag_val == "-checkpoint" {
+                checkpoint_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-checkpoint_interval" {
+                checkpoint_interval = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             }
         }
 
@@ -233,6 +279,12 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         if !output_file.contains(&sep) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
         }
+        if !checkpoint_file.is_empty()
+            && !checkpoint_file.contains(&sep)
+            && !checkpoint_file.contains("/")
+        {
+            checkpoint_file = format!("{}{}", working_directory, checkpoint_file);
+        }
 
         /*
         There are three stages to the calculation of the impoundment index:
@@ -265,6 +317,31 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let nodata = input.configs.nodata;
         let grid_area = input.configs.resolution_x * input.configs.resolution_y;
 
+        // The checkpoint records the stage reached: 1) dam heights, 2) priority flood, 3) index.
+        let mut checkpoint = if checkpoint_file.is_empty() {
+            None
+        } else {
+            let key = format!(
+                "{} {} {} {} {}",
+                self.get_tool_name(),
+                input_file,
+                file_signature(&input_file),
+                out_type,
+                dam_length
+            );
+            Some(Checkpoint::new(&checkpoint_file, &key, checkpoint_interval))
+        };
+        let (resume_stage, mut resume) = match &checkpoint {
+            Some(cp) => match cp.load()? {
+                Some((stage, reader)) => (stage, Some(reader)),
+                None => (0u8, None),
+            },
+            None => (0u8, None),
+        };
+        if verbose && resume.is_some() {
+            println!("Resuming from checkpoint {}...", checkpoint_file);
+        }
+
         // Calculate dam heights
         /*
         Each cell will be assigned the altitude (ASL) of the highest dam that
@@ -291,7 +368,12 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut z: f64;
         let mut z_n: f64;
         let (mut r_n, mut c_n, mut r_n2, mut c_n2): (isize, isize, isize, isize);
-        for row in 0..rows {
+        let mut start_row = 0isize;
+        if let Some(reader) = resume.as_mut() {
+            reader.read_array2d(&mut crest_elev)?;
+            start_row = reader.read()?;
+        }
+        for row in start_row..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
                 if z != nodata {
@@ -397,6 +479,17 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                     old_progress = progress;
                 }
             }
+            if let Some(cp) = checkpoint.as_mut() {
+                if cp.is_due() {
+                    if verbose {
+                        println!("Saving checkpoint...");
+                    }
+                    cp.save(1, |w| {
+                        w.write_array2d(&crest_elev)?;
+                        w.write(row + 1)
+                    })?;
+                }
+            }
         }
 
         /*
@@ -418,6 +511,8 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let background_val = (i32::min_value() + 1) as f64;
         let mut filled_dem: Array2D<f64> = Array2D::new(rows, columns, background_val, nodata)?;
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut stack = Vec::with_capacity((rows * columns) as usize);
 
         /*
         Find the data edges. This is complicated by the fact that DEMs frequently
@@ -461,6 +556,20 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let (mut row, mut col): (isize, isize);
         let (mut row_n, mut col_n): (isize, isize);
         let mut num_nodata_cells = 0;
+        if resume_stage >= 2 {
+            // the data edges were found, and the priority flood begun, before the checkpoint
+            queue.clear();
+            let (solved, nodata_cells) = read_flow_state(
+                resume.as_mut().unwrap(),
+                &mut filled_dem,
+                &mut flow_dir,
+                &mut num_inflowing,
+                &mut minheap,
+                &mut stack,
+            )?;
+            num_solved_cells = solved;
+            num_nodata_cells = nodata_cells;
+        }
         while !queue.is_empty() {
             let cell = queue.pop_front().unwrap();
             row = cell.0;
@@ -503,8 +612,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let back_link = [4i8, 5i8, 6i8, 7i8, 0i8, 1i8, 2i8, 3i8];
         let mut dir: i8;
         let mut count: i8;
-        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut stack = Vec::with_capacity((rows * columns) as usize);
+        let mut num_popped = 0usize;
         while !minheap.is_empty() {
             let cell = minheap.pop().expect("Error during pop operation.");
             row = cell.row;
@@ -550,6 +658,28 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                     old_progress = progress;
                 }
             }
+
+            num_popped += 1;
+            if let Some(cp) = checkpoint.as_mut() {
+                if num_popped % 65536 == 0 && cp.is_due() {
+                    if verbose {
+                        println!("Saving checkpoint...");
+                    }
+                    cp.save(2, |w| {
+                        w.write_array2d(&crest_elev)?;
+                        w.write(rows)?;
+                        write_flow_state(
+                            w,
+                            &filled_dem,
+                            &flow_dir,
+                            &num_inflowing,
+                            (num_solved_cells, num_nodata_cells),
+                            &minheap,
+                            &stack,
+                        )
+                    })?;
+                }
+            }
         }
 
         /*
@@ -567,7 +697,6 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         */
         let mut upslope_elevs: Vec<Vec<Vec<f64>>> =
             vec![vec![vec![]; columns as usize]; rows as usize];
-        num_solved_cells = num_nodata_cells;
         let mut z: f64;
         let mut cutoff_z: f64;
         let mut threshold: f64;
@@ -575,6 +704,22 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut vol: f64;
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.reinitialize_values(0.0);
+        if resume_stage >= 3 {
+            let reader = resume.as_mut().unwrap();
+            let num_lists: usize = reader.read()?;
+            for _ in 0..num_lists {
+                let r: isize = reader.read()?;
+                let c: isize = reader.read()?;
+                upslope_elevs[r as usize][c as usize] = reader.read_values()?;
+            }
+            for r in 0..rows {
+                output.set_row_data(r, reader.read_values()?);
+            }
+        } else {
+            num_solved_cells = num_nodata_cells;
+        }
+        drop(resume);
+        num_popped = 0;
         while !stack.is_empty() {
             let cell = stack.pop().expect("Error during pop operation.");
             row = cell.0;
@@ -630,6 +775,47 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                     old_progress = progress;
                 }
             }
+
+            num_popped += 1;
+            if let Some(cp) = checkpoint.as_mut() {
+                if num_popped % 65536 == 0 && cp.is_due() {
+                    if verbose {
+                        println!("Saving checkpoint...");
+                    }
+                    cp.save(3, |w| {
+                        w.write_array2d(&crest_elev)?;
+                        w.write(rows)?;
+                        write_flow_state(
+                            w,
+                            &filled_dem,
+                            &flow_dir,
+                            &num_inflowing,
+                            (num_solved_cells, num_nodata_cells),
+                            &minheap,
+                            &stack,
+                        )?;
+                        let num_lists = upslope_elevs
+                            .iter()
+                            .map(|r| r.iter().filter(|l| !l.is_empty()).count())
+                            .sum::<usize>();
+                        w.write(num_lists)?;
+                        for r in 0..rows {
+                            for c in 0..columns {
+                                let list = &upslope_elevs[r as usize][c as usize];
+                                if !list.is_empty() {
+                                    w.write(r)?;
+                                    w.write(c)?;
+                                    w.write_values(list)?;
+                                }
+                            }
+                        }
+                        for r in 0..rows {
+                            w.write_values(&output.get_row_data(r))?;
+                        }
+                        Ok(())
+                    })?;
+                }
+            }
         }
 
         // Output the dam height above the ground elevation
@@ -719,6 +905,10 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
             Err(e) => return Err(e),
         };
 
+        if let Some(cp) = &checkpoint {
+            cp.remove();
+        }
+
         if verbose {
             println!(
                 "{}",
@@ -730,6 +920,67 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
     }
 }
 
+/// Writes the state of the priority flood operation to a checkpoint.
+fn write_flow_state(
+    w: &mut CheckpointWriter,
+    filled_dem: &Array2D<f64>,
+    flow_dir: &Array2D<i8>,
+    num_inflowing: &Array2D<i8>,
+    (num_solved_cells, num_nodata_cells): (usize, usize),
+    minheap: &BinaryHeap<GridCell>,
+    stack: &[(isize, isize)],
+) -> Result<(), Error> {
+    w.write_array2d(filled_dem)?;
+    w.write_array2d(flow_dir)?;
+    w.write_array2d(num_inflowing)?;
+    w.write(num_solved_cells)?;
+    w.write(num_nodata_cells)?;
+    w.write(minheap.len())?;
+    for cell in minheap.iter() {
+        w.write(cell.row)?;
+        w.write(cell.column)?;
+        w.write(cell.priority)?;
+    }
+    w.write(stack.len())?;
+    for &(row, col) in stack {
+        w.write(row)?;
+        w.write(col)?;
+    }
+    Ok(())
+}
+
+/// Reads the state of the priority flood operation from a checkpoint, returning
+/// the numbers of solved and nodata cells.
+fn read_flow_state(
+    reader: &mut CheckpointReader,
+    filled_dem: &mut Array2D<f64>,
+    flow_dir: &mut Array2D<i8>,
+    num_inflowing: &mut Array2D<i8>,
+    minheap: &mut BinaryHeap<GridCell>,
+    stack: &mut Vec<(isize, isize)>,
+) -> Result<(usize, usize), Error> {
+    reader.read_array2d(filled_dem)?;
+    reader.read_array2d(flow_dir)?;
+    reader.read_array2d(num_inflowing)?;
+    let num_solved_cells: usize = reader.read()?;
+    let num_nodata_cells: usize = reader.read()?;
+    let heap_len: usize = reader.read()?;
+    for _ in 0..heap_len {
+        minheap.push(GridCell {
+            row: reader.read()?,
+            column: reader.read()?,
+            priority: reader.read()?,
+        });
+    }
+    let stack_len: usize = reader.read()?;
+    for _ in 0..stack_len {
+        let row: isize = reader.read()?;
+        let col: isize = reader.read()?;
+        stack.push((row, col));
+    }
+    Ok((num_solved_cells, num_nodata_cells))
+}
+
 #[derive(PartialEq, Debug)]
 struct GridCell {
     row: isize,
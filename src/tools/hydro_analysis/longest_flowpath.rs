@@ -220,16 +220,8 @@ impl WhiteboxTool for LongestFlowpath {
         };
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
-        let basins = Arc::new(Raster::new(&basins_file, "r")?);
-
-        if input.configs.rows != basins.configs.rows
-            || input.configs.columns != basins.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input rasters must have the same spatial extent (i.e. number of rows and columns).",
-            ));
-        }
+        // make sure the inputs share the same grid and CRS
+        let basins = Arc::new(align_raster_input(&input, Raster::new(&basins_file, "r")?)?);
 
         // calculate the flow direction
         let start = Instant::now();
@@ -4,19 +4,33 @@ mod average_upslope_flowpath_length;
 mod basins;
 mod breach_depressions;
 mod breach_depressions_least_cost;
+mod breach_embankments;
 mod breach_pits;
 mod burn_streams_at_roads;
+mod calibrate_channel_threshold;
+mod coastal_flooding;
+mod convert_d8_pointer;
+mod convert_dinf_pointer;
+mod curve_number_runoff;
 mod d8_flow_accum;
 mod d8_mass_flux;
 mod d8_pointer;
+mod degree_day_snowmelt;
+mod depression_hierarchy;
+mod depression_tree;
 mod depth_in_sink;
 mod dinf_flow_accum;
 mod dinf_mass_flux;
 mod dinf_pointer;
+pub(crate) mod dinf_routing;
 mod downslope_distance_to_stream;
+mod downslope_flowpath_metrics;
 mod downslope_flowpath_length;
+mod edge_contamination;
 mod elevation_above_stream;
 mod elevation_above_stream_euclidean;
+mod export_hydrologic_model;
+mod extract_valley_bottoms;
 mod fd8_flow_accum;
 mod fd8_pointer;
 mod fill_burn;
@@ -24,6 +38,7 @@ mod fill_depressions;
 mod fill_depressions_planchon_and_darboux;
 mod fill_depressions_wang_and_liu;
 mod fill_pits;
+mod fill_spill_merge;
 mod find_noflow_cells;
 mod find_parallel_flow;
 mod flatten_lakes;
@@ -38,9 +53,15 @@ mod jenson_snap_pour_points;
 mod longest_flowpath;
 mod max_upslope_flowpath;
 mod mdinf_flow_accum;
+mod modflow;
+mod modflow_ibound;
+mod modflow_layer_elevations;
+mod modflow_river_cells;
+pub(crate) mod mrvbf;
 mod num_inflowing_neighbours;
 mod raise_walls;
 mod rho8_pointer;
+mod sediment_connectivity_index;
 mod sink;
 mod snap_pour_points;
 mod stochastic_depression_analysis;
@@ -49,6 +70,7 @@ mod subbasins;
 mod trace_downslope_flowpaths;
 mod unnest_basins;
 mod upslope_depression_storage;
+mod upslope_statistics;
 mod watershed;
 
 // exports identifiers from private sub-modules in the current module namespace
@@ -57,19 +79,31 @@ pub use self::average_upslope_flowpath_length::AverageUpslopeFlowpathLength;
 pub use self::basins::Basins;
 pub use self::breach_depressions::BreachDepressions;
 pub use self::breach_depressions_least_cost::BreachDepressionsLeastCost;
+pub use self::breach_embankments::BreachEmbankments;
 pub use self::breach_pits::BreachSingleCellPits;
 pub use self::burn_streams_at_roads::BurnStreamsAtRoads;
+pub use self::calibrate_channel_threshold::CalibrateChannelThreshold;
+pub use self::coastal_flooding::CoastalFlooding;
+pub use self::convert_d8_pointer::ConvertD8Pointer;
+pub use self::convert_dinf_pointer::ConvertDinfPointer;
+pub use self::curve_number_runoff::CurveNumberRunoff;
 pub use self::d8_flow_accum::D8FlowAccumulation;
 pub use self::d8_mass_flux::D8MassFlux;
 pub use self::d8_pointer::D8Pointer;
+pub use self::degree_day_snowmelt::DegreeDaySnowmelt;
+pub use self::depression_hierarchy::DepressionHierarchy;
 pub use self::depth_in_sink::DepthInSink;
 pub use self::dinf_flow_accum::DInfFlowAccumulation;
 pub use self::dinf_mass_flux::DInfMassFlux;
 pub use self::dinf_pointer::DInfPointer;
 pub use self::downslope_distance_to_stream::DownslopeDistanceToStream;
+pub use self::downslope_flowpath_metrics::DownslopeFlowpathMetrics;
 pub use self::downslope_flowpath_length::DownslopeFlowpathLength;
+pub use self::edge_contamination::EdgeContamination;
 pub use self::elevation_above_stream::ElevationAboveStream;
 pub use self::elevation_above_stream_euclidean::ElevationAboveStreamEuclidean;
+pub use self::export_hydrologic_model::ExportHydrologicModel;
+pub use self::extract_valley_bottoms::ExtractValleyBottoms;
 pub use self::fd8_flow_accum::FD8FlowAccumulation;
 pub use self::fd8_pointer::FD8Pointer;
 pub use self::fill_burn::FillBurn;
@@ -77,6 +111,7 @@ pub use self::fill_depressions::FillDepressions;
 pub use self::fill_depressions_planchon_and_darboux::FillDepressionsPlanchonAndDarboux;
 pub use self::fill_depressions_wang_and_liu::FillDepressionsWangAndLiu;
 pub use self::fill_pits::FillSingleCellPits;
+pub use self::fill_spill_merge::FillSpillMerge;
 pub use self::find_noflow_cells::FindNoFlowCells;
 pub use self::find_parallel_flow::FindParallelFlow;
 pub use self::flatten_lakes::FlattenLakes;
@@ -91,9 +126,13 @@ pub use self::jenson_snap_pour_points::JensonSnapPourPoints;
 pub use self::longest_flowpath::LongestFlowpath;
 pub use self::max_upslope_flowpath::MaxUpslopeFlowpathLength;
 pub use self::mdinf_flow_accum::MDInfFlowAccumulation;
+pub use self::modflow_ibound::ModflowIbound;
+pub use self::modflow_layer_elevations::ModflowLayerElevations;
+pub use self::modflow_river_cells::ModflowRiverCells;
 pub use self::num_inflowing_neighbours::NumInflowingNeighbours;
 pub use self::raise_walls::RaiseWalls;
 pub use self::rho8_pointer::Rho8Pointer;
+pub use self::sediment_connectivity_index::SedimentConnectivityIndex;
 pub use self::sink::Sink;
 pub use self::snap_pour_points::SnapPourPoints;
 pub use self::stochastic_depression_analysis::StochasticDepressionAnalysis;
@@ -102,4 +141,5 @@ pub use self::subbasins::Subbasins;
 pub use self::trace_downslope_flowpaths::TraceDownslopeFlowpaths;
 pub use self::unnest_basins::UnnestBasins;
 pub use self::upslope_depression_storage::UpslopeDepressionStorage;
+pub use self::upslope_statistics::UpslopeStatistics;
 pub use self::watershed::Watershed;
@@ -0,0 +1,80 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Writers for the MODFLOW text formats shared by the ModflowIbound, ModflowLayerElevations,
+and ModflowRiverCells tools. Arrays are written in free format, one model row after another, with
+each row starting on a new line and wrapped at VALUES_PER_LINE values, such that they can be read
+with an OPEN/CLOSE array control record, e.g. 'OPEN/CLOSE ibound.txt 1 (FREE) -1'. List files
+contain one line per boundary cell, with one-based layer, row, and column indices followed by the
+cell's values, and can be read with an OPEN/CLOSE record in a stress period's data.
+*/
+
+use crate::structures::Array2D;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error};
+
+/// The maximum number of values on each line of an array file.
+pub const VALUES_PER_LINE: usize = 10;
+
+/// The value written to real arrays for cells outside of the active model domain.
+pub const INACTIVE_VALUE: f64 = -999.0;
+
+/// Writes an integer array, such as IBOUND, in MODFLOW free format.
+pub fn write_int_array(file_name: &str, data: &Array2D<i32>) -> Result<(), Error> {
+    write_array(file_name, data.rows, data.columns, |row, col| {
+        format!("{:>3}", data.get_value(row, col))
+    })
+}
+
+/// Writes a real array, such as a layer's top or bottom elevations, in MODFLOW free format.
+pub fn write_real_array(file_name: &str, data: &Array2D<f64>) -> Result<(), Error> {
+    write_array(file_name, data.rows, data.columns, |row, col| {
+        format!("{:>12.4}", data.get_value(row, col))
+    })
+}
+
+/// Writes a list of boundary cells, each with its layer, row, and column (zero-based; written
+/// one-based) and its values.
+pub fn write_cell_list(
+    file_name: &str,
+    cells: &[(usize, isize, isize, Vec<f64>)],
+) -> Result<(), Error> {
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+    for (layer, row, col, values) in cells {
+        let mut s = format!("{:>5} {:>7} {:>7}", layer + 1, row + 1, col + 1);
+        for v in values {
+            s.push_str(&format!(" {:>14.6}", v));
+        }
+        writer.write_all(format!("{}\n", s).as_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_array<F: Fn(isize, isize) -> String>(
+    file_name: &str,
+    rows: isize,
+    columns: isize,
+    value: F,
+) -> Result<(), Error> {
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+    for row in 0..rows {
+        let mut line = vec![];
+        for col in 0..columns {
+            line.push(value(row, col));
+            if line.len() == VALUES_PER_LINE || col == columns - 1 {
+                writer.write_all(format!("{}\n", line.join(" ")).as_bytes())?;
+                line.clear();
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,267 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::modflow::write_int_array;
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool derives the MODFLOW boundary (IBOUND) array of a groundwater model from a watershed
+/// mask raster (`--input`), such as that produced by the `Watershed` or `Basins` tools, whose grid
+/// is used as the model grid. Cells within the mask, i.e. cells that are neither zero nor NoData,
+/// are active (1) and all other cells are inactive (0). Optionally, a raster of constant-head cells
+/// (`--constant_head`), e.g. lakes, the sea, or rivers with a fixed stage, may be specified; its
+/// non-zero, non-NoData cells are assigned a value of -1, regardless of the mask.
+///
+/// The array is written to a text file (`--output`) in MODFLOW free format, with each model row
+/// starting on a new line and wrapped at ten values per line, such that it can be read with a
+/// control record such as 'OPEN/CLOSE ibound.txt 1 (FREE) -1' in the Basic (BAS6) package. The same
+/// array may be used for each model layer. Rows and columns follow the raster, with row 1 being the
+/// northernmost row.
+///
+/// # See Also
+/// `ModflowLayerElevations`, `ModflowRiverCells`, `Watershed`
+pub struct ModflowIbound {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ModflowIbound {
+    pub fn new() -> ModflowIbound {
+        // public constructor
+        let name = "ModflowIbound".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Derives a MODFLOW IBOUND array from a watershed mask and optional constant-head cells."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Watershed Mask File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster watershed mask file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Constant-Head Cells File (optional)".to_owned(),
+            flags: vec!["--constant_head".to_owned()],
+            description: "Optional input raster file of constant-head cells.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output IBOUND Array File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output MODFLOW array text file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=watershed.tif --constant_head=lake.tif -o=ibound.txt",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ModflowIbound {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ModflowIbound {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut ch_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-constant_head" {
+                ch_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !ch_file.is_empty() && !ch_file.contains(&sep) && !ch_file.contains("/") {
+            ch_file = format!("{}{}", working_directory, ch_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let constant_head = if !ch_file.is_empty() {
+            let ch = Raster::new(&ch_file, "r")?;
+            Some(align_raster_input(&input, ch)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut ibound: Array2D<i32> = Array2D::new(rows, columns, 0i32, 0i32)?;
+        let (mut num_active, mut num_ch) = (0usize, 0usize);
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if let Some(ref ch) = constant_head {
+                    let v = ch.get_value(row, col);
+                    if v != ch.configs.nodata && v != 0f64 {
+                        ibound.set_value(row, col, -1i32);
+                        num_ch += 1;
+                        continue;
+                    }
+                }
+                if z != nodata && z != 0f64 {
+                    ibound.set_value(row, col, 1i32);
+                    num_active += 1;
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if num_active == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The watershed mask does not contain any active cells.",
+            ));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Saving data...")
+        };
+        write_int_array(&output_file, &ibound)?;
+
+        if verbose {
+            println!("Grid: {} rows x {} columns", rows, columns);
+            println!("Active cells: {}", num_active);
+            println!("Constant-head cells: {}", num_ch);
+            println!(
+                "Inactive cells: {}",
+                (rows * columns) as usize - num_active - num_ch
+            );
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::modflow::{write_real_array, INACTIVE_VALUE};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool derives the top and bottom elevation arrays of the layers of a MODFLOW groundwater
+/// model from a digital elevation model (DEM; `--dem`), which defines the top of the uppermost
+/// layer, and a list of layer thickness rasters (`--thickness`), ordered from the uppermost layer
+/// downward. The bottom of each layer is the bottom of the layer above it less the layer's
+/// thickness, and the thickness rasters must use the same units as the DEM elevations. Because
+/// MODFLOW does not permit cells of zero thickness, thicknesses smaller than a minimum value
+/// (`--min_thickness`), including NoData thicknesses, are increased to the minimum thickness.
+///
+/// The arrays are written in MODFLOW free format, with each model row starting on a new line and
+/// wrapped at ten values per line, to the output directory (`--output`). The top of the model is
+/// written to top.txt and the bottom of each layer to botm_1.txt, botm_2.txt, etc., such that they
+/// can be read by the Discretization (DIS) package with control records such as
+/// 'OPEN/CLOSE top.txt 1.0 (FREE) -1'. Cells with NoData in the DEM, which should be inactive in
+/// the model's IBOUND array (see `ModflowIbound`), are assigned a value of -999 in each array.
+///
+/// # See Also
+/// `ModflowIbound`, `ModflowRiverCells`
+pub struct ModflowLayerElevations {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ModflowLayerElevations {
+    pub fn new() -> ModflowLayerElevations {
+        // public constructor
+        let name = "ModflowLayerElevations".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Derives MODFLOW layer top and bottom elevation arrays from a DEM and layer thickness rasters."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Layer Thickness Files".to_owned(),
+            flags: vec!["--thickness".to_owned()],
+            description:
+                "Input raster files of layer thicknesses, from the uppermost layer downward."
+                    .to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Layer Thickness".to_owned(),
+            flags: vec!["--min_thickness".to_owned()],
+            description: "Minimum layer thickness, in elevation units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output directory.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --thickness='aquifer1.tif;aquitard.tif;aquifer2.tif' --min_thickness=0.5 -o=modflow",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ModflowLayerElevations {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ModflowLayerElevations {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut thickness_files = String::new();
+        let mut min_thickness = 0.1f64;
+        let mut output_dir = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-thickness" {
+                thickness_files = value;
+            } else if flag_val == "-min_thickness" {
+                min_thickness = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_dir = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_dir.contains(&sep) && !output_dir.contains("/") {
+            output_dir = format!("{}{}", working_directory, output_dir);
+        }
+        if !output_dir.ends_with(&sep) && !output_dir.ends_with("/") {
+            output_dir.push_str(&sep);
+        }
+        if min_thickness <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The minimum layer thickness must be greater than zero.",
+            ));
+        }
+
+        let mut cmd = thickness_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = thickness_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let mut layer_files = vec![];
+        for value in vec {
+            let mut file = value.trim().to_string();
+            if file.is_empty() {
+                continue;
+            }
+            if !file.contains(&sep) && !file.contains("/") {
+                file = format!("{}{}", working_directory, file);
+            }
+            layer_files.push(file);
+        }
+        if layer_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one layer thickness file (--thickness) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut top: Array2D<f64> = Array2D::new(rows, columns, INACTIVE_VALUE, INACTIVE_VALUE)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z != nodata {
+                    top.set_value(row, col, z);
+                }
+            }
+        }
+
+        fs::create_dir_all(&output_dir)?;
+        write_real_array(&format!("{}top.txt", output_dir), &top)?;
+
+        let mut bottom = top.duplicate();
+        let num_layers = layer_files.len();
+        for (k, file) in layer_files.iter().enumerate() {
+            if verbose {
+                println!("Processing layer {} of {}...", k + 1, num_layers);
+            }
+            let thickness = align_raster_input(&input, Raster::new(file, "r")?)?;
+            let thickness_nodata = thickness.configs.nodata;
+            let mut num_adjusted = 0usize;
+            for row in 0..rows {
+                for col in 0..columns {
+                    if input.get_value(row, col) == nodata {
+                        continue;
+                    }
+                    let mut t = thickness.get_value(row, col);
+                    if t == thickness_nodata || t < min_thickness {
+                        t = min_thickness;
+                        num_adjusted += 1;
+                    }
+                    let z = bottom.get_value(row, col);
+                    bottom.set_value(row, col, z - t);
+                }
+            }
+            if verbose && num_adjusted > 0 {
+                println!(
+                    "Layer {}: {} cells increased to the minimum thickness",
+                    k + 1,
+                    num_adjusted
+                );
+            }
+            write_real_array(&format!("{}botm_{}.txt", output_dir, k + 1), &bottom)?;
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!(
+                "Wrote the top and {} layer bottom arrays to {}",
+                num_layers, output_dir
+            );
+            println!("Elapsed Time (including I/O): {}", elapsed_time);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,447 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::modflow::write_cell_list;
+use crate::raster::*;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool derives the cells of the River (RIV) package of a MODFLOW groundwater model from a
+/// raster stream network (`--streams`), such as that produced by the `ExtractStreams` tool, and a
+/// digital elevation model (DEM; `--dem`), whose grid is used as the model grid. Each stream cell,
+/// i.e. each cell that is neither zero nor NoData in the streams raster, becomes a river cell in the
+/// specified model layer (`--layer`). The river stage is taken as the DEM elevation of the stream
+/// cell, the bottom of the river (RBOT) lies a specified water depth (`--depth`) below the stage,
+/// and the riverbed conductance is:
+///
+/// > C = K L W / M
+///
+/// where K is the hydraulic conductivity of the riverbed sediments (`--conductivity`), W is the
+/// river width (`--width`), M is the thickness of the riverbed sediments (`--bed_thickness`), and L
+/// is the length of the river within the cell. The length is estimated as half of the summed
+/// distances to the neighbouring stream cells, such that a cell in the middle of a straight reach
+/// has a length of one grid cell, and is one grid cell for isolated stream cells. The units of the
+/// conductance are those of the conductivity times the squared linear units of the DEM.
+///
+/// The river cells are written to a text file (`--output`) with one line per cell, containing the
+/// one-based layer, row, and column, followed by the stage, conductance, and river bottom
+/// elevation, which can be read by the RIV package with an 'OPEN/CLOSE' record in the stress period
+/// data; the number of river cells, which is required by the package's ITMP and MXACTR values, is
+/// reported. Optionally, the river cells may also be output as a vector points file
+/// (`--out_points`) with the ROW, COLUMN, STAGE, COND, RBOT, and LENGTH attributes, for mapping and
+/// checking the conductance values.
+///
+/// # See Also
+/// `ModflowIbound`, `ModflowLayerElevations`, `ExtractStreams`
+pub struct ModflowRiverCells {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ModflowRiverCells {
+    pub fn new() -> ModflowRiverCells {
+        // public constructor
+        let name = "ModflowRiverCells".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Derives MODFLOW River package cells, with stage and conductance, from a stream network."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output River Cells File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output MODFLOW river cell list text file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output River Points File (optional)".to_owned(),
+            flags: vec!["--out_points".to_owned()],
+            description: "Optional output vector points file of the river cells.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Model Layer".to_owned(),
+            flags: vec!["--layer".to_owned()],
+            description: "Model layer containing the river cells (1 is the uppermost layer)."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "River Width".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "River width, in the linear units of the DEM.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Water Depth".to_owned(),
+            flags: vec!["--depth".to_owned()],
+            description: "Depth of water between the stage and the river bottom.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Riverbed Thickness".to_owned(),
+            flags: vec!["--bed_thickness".to_owned()],
+            description: "Thickness of the riverbed sediments.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Riverbed Hydraulic Conductivity".to_owned(),
+            flags: vec!["--conductivity".to_owned()],
+            description: "Hydraulic conductivity of the riverbed sediments.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --streams=streams.tif --dem=DEM.tif -o=riv.txt --out_points=riv.shp --width=8.0 --depth=1.5 --bed_thickness=0.5 --conductivity=0.2",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ModflowRiverCells {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ModflowRiverCells {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut streams_file = String::new();
+        let mut dem_file = String::new();
+        let mut output_file = String::new();
+        let mut points_file = String::new();
+        let mut layer = 1usize;
+        let mut width = 5.0f64;
+        let mut depth = 1.0f64;
+        let mut bed_thickness = 1.0f64;
+        let mut conductivity = 1.0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-streams" {
+                streams_file = value;
+            } else if flag_val == "-i" || flag_val == "-dem" {
+                dem_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_points" {
+                points_file = value;
+            } else if flag_val == "-layer" {
+                layer = value
+                    .parse::<usize>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-width" {
+                width = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-depth" {
+                depth = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-bed_thickness" {
+                bed_thickness = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-conductivity" {
+                conductivity = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !points_file.is_empty() && !points_file.contains(&sep) && !points_file.contains("/") {
+            points_file = format!("{}{}", working_directory, points_file);
+        }
+        if layer < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The model layer must be 1 or greater.",
+            ));
+        }
+        if width <= 0f64 || bed_thickness <= 0f64 || conductivity < 0f64 || depth < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The river width and riverbed thickness must be greater than zero, and the depth and conductivity must not be negative.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let streams = align_raster_input(&dem, Raster::new(&streams_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let streams_nodata = streams.configs.nodata;
+        let cell_size_x = dem.configs.resolution_x;
+        let cell_size_y = dem.configs.resolution_y;
+        let diag = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let lengths = [
+            diag,
+            cell_size_x,
+            diag,
+            cell_size_y,
+            diag,
+            cell_size_x,
+            diag,
+            cell_size_y,
+        ];
+        let is_stream = |row: isize, col: isize| {
+            let v = streams.get_value(row, col);
+            v != streams_nodata && v != 0f64 && dem.get_value(row, col) != nodata
+        };
+
+        let mut cells = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if !is_stream(row, col) {
+                    continue;
+                }
+                let mut length = 0f64;
+                for n in 0..8 {
+                    if is_stream(row + dy[n], col + dx[n]) {
+                        length += lengths[n] / 2f64;
+                    }
+                }
+                if length == 0f64 {
+                    length = (cell_size_x + cell_size_y) / 2f64;
+                }
+                let stage = dem.get_value(row, col);
+                let cond = conductivity * length * width / bed_thickness;
+                cells.push((
+                    layer - 1,
+                    row,
+                    col,
+                    vec![stage, cond, stage - depth, length],
+                ));
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The streams raster does not contain any stream cells within the DEM.",
+            ));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        // the cell length is only written to the points file
+        let list: Vec<(usize, isize, isize, Vec<f64>)> = cells
+            .iter()
+            .map(|(k, r, c, v)| (*k, *r, *c, v[0..3].to_vec()))
+            .collect();
+        write_cell_list(&output_file, &list)?;
+
+        if !points_file.is_empty() {
+            let mut output = Shapefile::new(&points_file, ShapeType::Point)?;
+            output.projection = dem.configs.coordinate_ref_system_wkt.clone();
+            output
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+            output
+                .attributes
+                .add_field(&AttributeField::new("ROW", FieldDataType::Int, 7u8, 0u8));
+            output.attributes.add_field(&AttributeField::new(
+                "COLUMN",
+                FieldDataType::Int,
+                7u8,
+                0u8,
+            ));
+            for name in ["STAGE", "COND", "RBOT", "LENGTH"].iter() {
+                output.attributes.add_field(&AttributeField::new(
+                    name,
+                    FieldDataType::Real,
+                    14u8,
+                    4u8,
+                ));
+            }
+            for (i, (_, row, col, v)) in cells.iter().enumerate() {
+                output.add_point_record(dem.get_x_from_column(*col), dem.get_y_from_row(*row));
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(i as i32 + 1),
+                        FieldData::Int(*row as i32 + 1),
+                        FieldData::Int(*col as i32 + 1),
+                        FieldData::Real(v[0]),
+                        FieldData::Real(v[1]),
+                        FieldData::Real(v[2]),
+                        FieldData::Real(v[3]),
+                    ],
+                    false,
+                );
+            }
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Points file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!("Number of river cells (ITMP): {}", cells.len());
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
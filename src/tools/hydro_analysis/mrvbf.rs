@@ -0,0 +1,278 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The multi-resolution valley bottom flatness (MRVBF) index of Gallant and Dowling (2003) and
+the slope and elevation percentile grids from which it is built. These were originally part of the
+ExtractValleyBottoms tool and are shared with the tools that derive terrain covariates from a DEM.
+*/
+
+use crate::structures::Array2D;
+use num_cpus;
+use std::io::Error;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// The fuzzy membership of a value `x` in the set of values below a threshold `t`; the
+/// membership is 0.5 at the threshold.
+pub fn fuzzy_membership(x: f64, t: f64) -> f64 {
+    1f64 / (1f64 + (x / t).powi(4))
+}
+
+/// Calculates the slope gradient, in percent, of a DEM using the 3 x 3 finite difference method
+/// of Horn (1981). NoData neighbours are replaced with the elevation of the centre cell.
+pub fn slope_percent(dem: &Array2D<f64>, res_x: f64, res_y: f64) -> Result<Array2D<f64>, Error> {
+    let rows = dem.rows();
+    let columns = dem.columns();
+    let nodata = dem.nodata();
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut n = [0f64; 8];
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = dem.get_value(row, col);
+            if z != nodata {
+                for i in 0..8 {
+                    n[i] = dem.get_value(row + dy[i], col + dx[i]);
+                    if n[i] == nodata {
+                        n[i] = z;
+                    }
+                }
+                // the neighbours are ordered clockwise from the north-east
+                let fx = (n[2] - n[4] + 2f64 * (n[1] - n[5]) + n[0] - n[6]) / (8f64 * res_x);
+                let fy = (n[6] - n[4] + 2f64 * (n[7] - n[3]) + n[0] - n[2]) / (8f64 * res_y);
+                output.set_value(row, col, 100f64 * (fx * fx + fy * fy).sqrt());
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Calculates the proportion of the cells within a circular neighbourhood of the given radius
+/// that are lower than each cell.
+pub fn elevation_percentile(dem: &Arc<Array2D<f64>>, radius: isize) -> Result<Array2D<f64>, Error> {
+    let rows = dem.rows();
+    let columns = dem.columns();
+    let nodata = dem.nodata();
+    let mut offsets = vec![];
+    for r in -radius..=radius {
+        for c in -radius..=radius {
+            if r * r + c * c <= radius * radius && !(r == 0 && c == 0) {
+                offsets.push((r, c));
+            }
+        }
+    }
+    let offsets = Arc::new(offsets);
+    let num_procs = num_cpus::get() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let dem = dem.clone();
+        let offsets = offsets.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    let z = dem.get_value(row, col);
+                    if z != nodata {
+                        let mut n = 0f64;
+                        let mut n_lower = 0f64;
+                        for &(r, c) in offsets.iter() {
+                            let z_n = dem.get_value(row + r, col + c);
+                            if z_n != nodata {
+                                n += 1f64;
+                                if z_n < z {
+                                    n_lower += 1f64;
+                                }
+                            }
+                        }
+                        data[col as usize] = if n > 0f64 { n_lower / n } else { 0f64 };
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    for _ in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(row, data);
+    }
+    Ok(output)
+}
+
+/// Reduces the resolution of a grid by averaging blocks of `factor` x `factor` cells.
+fn aggregate(dem: &Array2D<f64>, factor: isize) -> Result<Array2D<f64>, Error> {
+    let nodata = dem.nodata();
+    let rows = (dem.rows() + factor - 1) / factor;
+    let columns = (dem.columns() + factor - 1) / factor;
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let mut total = 0f64;
+            let mut n = 0f64;
+            for r in row * factor..(row + 1) * factor {
+                for c in col * factor..(col + 1) * factor {
+                    let z = dem.get_value(r, c);
+                    if z != nodata {
+                        total += z;
+                        n += 1f64;
+                    }
+                }
+            }
+            if n > 0f64 {
+                output.set_value(row, col, total / n);
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Smooths a grid using a 3 x 3 mean filter.
+fn smooth(dem: &Array2D<f64>) -> Result<Array2D<f64>, Error> {
+    let rows = dem.rows();
+    let columns = dem.columns();
+    let nodata = dem.nodata();
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if dem.get_value(row, col) != nodata {
+                let mut total = 0f64;
+                let mut n = 0f64;
+                for r in row - 1..=row + 1 {
+                    for c in col - 1..=col + 1 {
+                        let z = dem.get_value(r, c);
+                        if z != nodata {
+                            total += z;
+                            n += 1f64;
+                        }
+                    }
+                }
+                output.set_value(row, col, total / n);
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Bilinearly interpolates the value of a grid that was aggregated by `factor` at a cell of the
+/// original grid. NoData cells are excluded from the interpolation.
+fn refine(coarse: &Array2D<f64>, factor: isize, row: isize, col: isize) -> f64 {
+    let nodata = coarse.nodata();
+    let half = (factor as f64 - 1f64) / 2f64;
+    let r = ((row as f64 - half) / factor as f64).max(0f64);
+    let c = ((col as f64 - half) / factor as f64).max(0f64);
+    let (r0, c0) = (r.floor() as isize, c.floor() as isize);
+    let (fr, fc) = (r - r0 as f64, c - c0 as f64);
+    let mut total = 0f64;
+    let mut total_weight = 0f64;
+    for &(dr, dc, w) in &[
+        (0, 0, (1f64 - fr) * (1f64 - fc)),
+        (0, 1, (1f64 - fr) * fc),
+        (1, 0, fr * (1f64 - fc)),
+        (1, 1, fr * fc),
+    ] {
+        let z = coarse.get_value(r0 + dr, c0 + dc);
+        if z != nodata && w > 0f64 {
+            total += z * w;
+            total_weight += w;
+        }
+    }
+    if total_weight > 0f64 {
+        total / total_weight
+    } else {
+        coarse.get_value(r0, c0)
+    }
+}
+
+/// Calculates the multi-resolution valley bottom flatness index (Gallant and Dowling, 2003).
+/// The first two steps are carried out at the resolution of the DEM, after which each step
+/// aggregates the DEM by a further factor of three and halves the slope threshold. Steps
+/// continue until the aggregated DEM becomes too small for the elevation percentile
+/// neighbourhood.
+pub fn mrvbf(
+    dem: &Arc<Array2D<f64>>,
+    res_x: f64,
+    res_y: f64,
+    verbose: bool,
+) -> Result<Array2D<f64>, Error> {
+    let rows = dem.rows();
+    let columns = dem.columns();
+    let nodata = dem.nodata();
+    let initial_slope_threshold = 16f64; // percent
+
+    // step 1
+    let slope = slope_percent(dem, res_x, res_y)?;
+    let pctl = elevation_percentile(dem, 3)?;
+    let mut combined_flatness: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut output: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            let s = slope.get_value(row, col);
+            if s != nodata {
+                let flatness = fuzzy_membership(s, initial_slope_threshold);
+                let lowness = 1f64 / (1f64 + (pctl.get_value(row, col) / 0.4).powi(3));
+                combined_flatness.set_value(row, col, flatness);
+                output.set_value(row, col, flatness * lowness);
+            }
+        }
+    }
+
+    // subsequent steps
+    let mut step_dem = smooth(dem)?;
+    let mut factor = 1isize;
+    let mut step = 2;
+    while step_dem.rows() >= 13 && step_dem.columns() >= 13 {
+        if verbose {
+            println!("MRVBF step {}...", step);
+        }
+        let slope_threshold = initial_slope_threshold / 2f64.powi(step - 1);
+        let step_dem_arc = Arc::new(step_dem);
+        let slope = slope_percent(&step_dem_arc, res_x * factor as f64, res_y * factor as f64)?;
+        let pctl = elevation_percentile(&step_dem_arc, 6)?;
+        let shape = ((step as f64 - 0.5) / 0.1).ln() / 1.5f64.ln();
+        for row in 0..rows {
+            for col in 0..columns {
+                let previous = output.get_value(row, col);
+                if previous == nodata {
+                    continue;
+                }
+                let (s, p) = if factor == 1 {
+                    (slope.get_value(row, col), pctl.get_value(row, col))
+                } else {
+                    (
+                        refine(&slope, factor, row, col),
+                        refine(&pctl, factor, row, col),
+                    )
+                };
+                if s == nodata || p == nodata {
+                    continue;
+                }
+                let flatness =
+                    fuzzy_membership(s, slope_threshold) * combined_flatness.get_value(row, col);
+                combined_flatness.set_value(row, col, flatness);
+                let vf = flatness / (1f64 + (p / 0.4).powi(3));
+                let w = 1f64 - 1f64 / (1f64 + (vf / 0.4).powf(shape));
+                output.set_value(
+                    row,
+                    col,
+                    w * (step as f64 - 1f64 + vf) + (1f64 - w) * previous,
+                );
+            }
+        }
+        let step_dem_arc = match Arc::try_unwrap(step_dem_arc) {
+            Ok(a) => a,
+            Err(a) => (*a).clone(),
+        };
+        step_dem = aggregate(&step_dem_arc, 3)?;
+        factor *= 3;
+        step += 1;
+    }
+
+    Ok(output)
+}
@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 16/07/2017
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::random;
 use num_cpus;
 use rand::prelude::*;
 use std::env;
@@ -238,9 +239,8 @@ impl WhiteboxTool for Rho8Pointer {
                     false => [1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64, 128f64],
                 };
                 let (mut z, mut z_n, mut slope): (f64, f64, f64);
-                // let between = Range::new(0f64, 1f64);
-                let mut rng = thread_rng();
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut rng = random::rng(row as u64);
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
@@ -0,0 +1,562 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::dinf_routing::{dinf_flow_directions, dinf_receivers};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// The lower bound placed on the slope gradient (m/m), after Cavalli et al. (2013).
+const MIN_SLOPE: f64 = 0.005;
+/// The upper bound placed on the slope gradient (m/m).
+const MAX_SLOPE: f64 = 1.0;
+/// The lower bound placed on the weighting factor, which avoids infinite impedances.
+const MIN_WEIGHT: f64 = 0.001;
+
+/// This tool calculates the index of connectivity (IC) of Borselli et al. (2008), as modified by
+/// Cavalli et al. (2013), which describes the potential for the sediment produced at each point in
+/// a catchment to reach a target, such as the stream network or the catchment outlet. It is widely
+/// used in sediment-budget studies to identify the sediment source areas that are well coupled with
+/// the channel network and those that are buffered from it. The index is defined as:
+///
+/// > IC = log<sub>10</sub>(D<sub>up</sub> / D<sub>dn</sub>)
+///
+/// where the upslope component, D<sub>up</sub> = *W̄* *S̄* √*A*, is the potential for the upslope
+/// area to route sediment downslope, *W̄* and *S̄* are the average weighting factor and slope
+/// gradient of the upslope contributing area, and *A* is the contributing area (m<sup>2</sup>). The
+/// downslope component, D<sub>dn</sub> = Σ *d*<sub>i</sub> / (*W*<sub>i</sub> *S*<sub>i</sub>), is
+/// the impedance of the flow path leading from the cell to the nearest target, where
+/// *d*<sub>i</sub> is the length of the flow path across the *i*th cell. IC values range from
+/// negative infinity to positive infinity, with connectivity increasing with IC.
+///
+/// The user must specify an input digital elevation model (DEM; `--dem`), which should be
+/// hydrologically conditioned, e.g. using `BreachDepressionsLeastCost`, and in which the elevations
+/// are in metres. Flow is routed over the DEM using the D-infinity method (Tarboton, 1997), in the
+/// same manner as the `DInfFlowAccumulation` tool, such that both the upslope and downslope
+/// components are weighted by the proportions of flow that are divided between neighbouring cells.
+/// The weighting factor raster (`--weights`) represents the impedance to sediment fluxes and is
+/// commonly derived from the RUSLE cover-management factor (Borselli et al., 2008) or from a
+/// surface-roughness index (Cavalli et al., 2013). NoData weights are replaced by the lower bound
+/// of the weighting factor (0.001), which prevents the impedance from becoming infinite, and slope
+/// gradients are bounded to the range 0.005-1 m/m, following Cavalli et al. (2013).
+///
+/// The targets may be specified with an optional raster (`--targets`), in which all non-zero,
+/// non-NoData cells, e.g. those of a stream network or a catchment outlet, are targets. Flow paths
+/// terminate at the targets, which are assigned NoData in the output. Flow paths also terminate
+/// where they leave the DEM, or at cells without a downslope neighbour, such that in the absence of
+/// a targets raster the index describes the connectivity of each cell with the edge of the DEM.
+/// The upslope and downslope components may be output (`--out_dup` and `--out_ddn`) for further
+/// analysis. For DEMs in geographic coordinates, the grid resolution is converted to metres.
+///
+/// # References
+/// Borselli, L., Cassi, P., and Torri, D. (2008). Prolegomena to sediment and flow connectivity in
+/// the landscape: A GIS and field numerical assessment. *Catena*, 75(3), 268-277.
+///
+/// Cavalli, M., Trevisani, S., Comiti, F., and Marchi, L. (2013). Geomorphometric assessment of
+/// spatial sediment connectivity in small Alpine catchments. *Geomorphology*, 188, 31-41.
+///
+/// Tarboton, D. G. (1997). A new method for the determination of flow directions and upslope areas
+/// in grid digital elevation models. *Water Resources Research*, 33(2), 309-319.
+///
+/// # See Also
+/// `DInfFlowAccumulation`, `DInfPointer`, `RusleLsFactor`, `SedimentTransportIndex`
+pub struct SedimentConnectivityIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SedimentConnectivityIndex {
+    pub fn new() -> SedimentConnectivityIndex {
+        // public constructor
+        let name = "SedimentConnectivityIndex".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Calculates the index of sediment connectivity of Borselli et al. (2008) from a DEM."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Weighting Factor File".to_owned(),
+            flags: vec!["--weights".to_owned()],
+            description: "Input raster of the weighting factor, e.g. the RUSLE C-factor."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Targets File (optional)".to_owned(),
+            flags: vec!["--targets".to_owned()],
+            description: "Optional input raster in which non-zero cells are targets, e.g. streams."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file of the index of connectivity.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Upslope Component File (optional)".to_owned(),
+            flags: vec!["--out_dup".to_owned()],
+            description: "Optional output raster of the upslope component (Dup).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Downslope Component File (optional)".to_owned(),
+            flags: vec!["--out_ddn".to_owned()],
+            description: "Optional output raster of the downslope component (Ddn).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --weights=c_factor.tif --targets=streams.tif -o=ic.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SedimentConnectivityIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SedimentConnectivityIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut weights_file = String::new();
+        let mut targets_file = String::new();
+        let mut output_file = String::new();
+        let mut dup_file = String::new();
+        let mut ddn_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-weights" {
+                weights_file = value;
+            } else if flag_val == "-targets" {
+                targets_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_dup" {
+                dup_file = value;
+            } else if flag_val == "-out_ddn" {
+                ddn_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !weights_file.contains(&sep) && !weights_file.contains("/") {
+            weights_file = format!("{}{}", working_directory, weights_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !targets_file.is_empty() && !targets_file.contains(&sep) && !targets_file.contains("/") {
+            targets_file = format!("{}{}", working_directory, targets_file);
+        }
+        if !dup_file.is_empty() && !dup_file.contains(&sep) && !dup_file.contains("/") {
+            dup_file = format!("{}{}", working_directory, dup_file);
+        }
+        if !ddn_file.is_empty() && !ddn_file.contains(&sep) && !ddn_file.contains("/") {
+            ddn_file = format!("{}{}", working_directory, ddn_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let weights = align_raster_input(&input, Raster::new(&weights_file, "r")?)?;
+        let targets = if !targets_file.is_empty() {
+            Some(align_raster_input(
+                &input,
+                Raster::new(&targets_file, "r")?,
+            )?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // the grid resolution, in metres
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let diag_cell_size = (res_x * res_x + res_y * res_y).sqrt();
+        let cell_area = res_x * res_y;
+
+        if verbose {
+            println!("Calculating flow directions...");
+        }
+        let (flow_dir, slope) = dinf_flow_directions(&input, res_x, res_y)?;
+
+        let is_target = |row: isize, col: isize| -> bool {
+            match targets {
+                Some(ref t) => {
+                    let v = t.get_value(row, col);
+                    v != 0f64 && v != t.configs.nodata
+                }
+                None => false,
+            }
+        };
+
+        // the bounded weighting factor and slope of each cell
+        let mut w_vals: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut s_vals: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row, col) == nodata {
+                    continue;
+                }
+                let w = weights.get_value(row, col);
+                let w = if w == weights.configs.nodata {
+                    MIN_WEIGHT
+                } else {
+                    w.max(MIN_WEIGHT)
+                };
+                w_vals.set_value(row, col, w);
+                s_vals.set_value(
+                    row,
+                    col,
+                    slope.get_value(row, col).max(MIN_SLOPE).min(MAX_SLOPE),
+                );
+                if num_inflowing.get_value(row, col) == -1 {
+                    num_inflowing.set_value(row, col, 0);
+                }
+                if let Some(receivers) = dinf_receivers(flow_dir.get_value(row, col), row, col) {
+                    for &(rn, cn, p) in receivers.iter() {
+                        if p > 0f64 && input.get_value(rn, cn) != nodata {
+                            if num_inflowing.get_value(rn, cn) == -1 {
+                                num_inflowing.set_value(rn, cn, 0);
+                            }
+                            num_inflowing.increment(rn, cn, 1);
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Num. inflowing neighbours: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // upslope pass, accumulating the contributing area and the area-weighted W and S
+        let mut acc_area: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        let mut acc_w: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        let mut acc_s: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        let mut stack = Vec::with_capacity((rows * columns) as usize);
+        let mut num_valid_cells = 0usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row, col) != nodata {
+                    num_valid_cells += 1;
+                    if num_inflowing.get_value(row, col) == 0 {
+                        stack.push((row, col));
+                    }
+                }
+            }
+        }
+
+        // cells are recorded in topological order, i.e. each cell precedes its receivers
+        let mut order: Vec<(isize, isize)> = Vec::with_capacity(num_valid_cells);
+        while let Some((row, col)) = stack.pop() {
+            order.push((row, col));
+            let area = acc_area.get_value(row, col) + cell_area;
+            let w = acc_w.get_value(row, col) + w_vals.get_value(row, col) * cell_area;
+            let s = acc_s.get_value(row, col) + s_vals.get_value(row, col) * cell_area;
+            acc_area.set_value(row, col, area);
+            acc_w.set_value(row, col, w);
+            acc_s.set_value(row, col, s);
+            if let Some(receivers) = dinf_receivers(flow_dir.get_value(row, col), row, col) {
+                for &(rn, cn, p) in receivers.iter() {
+                    if p > 0f64 && input.get_value(rn, cn) != nodata {
+                        acc_area.increment(rn, cn, area * p);
+                        acc_w.increment(rn, cn, w * p);
+                        acc_s.increment(rn, cn, s * p);
+                        num_inflowing.decrement(rn, cn, 1);
+                        if num_inflowing.get_value(rn, cn) == 0 {
+                            stack.push((rn, cn));
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress =
+                    (100.0_f64 * order.len() as f64 / num_valid_cells.max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Upslope component: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // downslope pass, accumulating the flow-path impedance from each cell to the targets
+        let mut d_dn: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for (i, &(row, col)) in order.iter().rev().enumerate() {
+            if is_target(row, col) {
+                d_dn.set_value(row, col, 0f64);
+                continue;
+            }
+            let impedance = 1f64 / (w_vals.get_value(row, col) * s_vals.get_value(row, col));
+            let mut dist = (res_x + res_y) / 2.0;
+            let mut downslope = 0f64;
+            if let Some(receivers) = dinf_receivers(flow_dir.get_value(row, col), row, col) {
+                dist = 0f64;
+                for &(rn, cn, p) in receivers.iter() {
+                    let d = if rn != row && cn != col {
+                        diag_cell_size
+                    } else if rn != row {
+                        res_y
+                    } else {
+                        res_x
+                    };
+                    dist += p * d;
+                    // flow that leaves the DEM contributes no further impedance
+                    let v = d_dn.get_value(rn, cn);
+                    if p > 0f64 && v != nodata {
+                        downslope += p * v;
+                    }
+                }
+            }
+            d_dn.set_value(row, col, dist * impedance + downslope);
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (order.len() - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Downslope component: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.reinitialize_values(nodata);
+        let mut dup_output = if !dup_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&dup_file, &input);
+            r.configs.data_type = DataType::F32;
+            r.reinitialize_values(nodata);
+            Some(r)
+        } else {
+            None
+        };
+        let mut ddn_output = if !ddn_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&ddn_file, &input);
+            r.configs.data_type = DataType::F32;
+            r.reinitialize_values(nodata);
+            Some(r)
+        } else {
+            None
+        };
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row, col) == nodata {
+                    continue;
+                }
+                let area = acc_area.get_value(row, col);
+                let dup = (acc_w.get_value(row, col) / area)
+                    * (acc_s.get_value(row, col) / area)
+                    * area.sqrt();
+                let ddn = d_dn.get_value(row, col);
+                if let Some(ref mut r) = dup_output {
+                    r.set_value(row, col, dup);
+                }
+                if let Some(ref mut r) = ddn_output {
+                    r.set_value(row, col, ddn);
+                }
+                if ddn > 0f64 {
+                    output.set_value(row, col, (dup / ddn).log10());
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "spectrum.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+        output.add_metadata_entry(format!("Input weighting factor file: {}", weights_file));
+        if !targets_file.is_empty() {
+            output.add_metadata_entry(format!("Input targets file: {}", targets_file));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        for (r, label) in vec![(dup_output, "Upslope"), (ddn_output, "Downslope")] {
+            if let Some(mut r) = r {
+                r.configs.palette = "spectrum.plt".to_string();
+                r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+                r.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                r.add_metadata_entry(format!("{} component of the index of connectivity", label));
+                let _ = match r.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("{} component file written", label)
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,15 +2,16 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 11/05/2018
-Last Modified: 29/03/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::structures::Array2D;
 use crate::tools::*;
+use crate::utils::checkpoint::{file_signature, Checkpoint, DEFAULT_CHECKPOINT_INTERVAL};
+use crate::utils::random;
 use rand::prelude::*;
-use rand::rngs::SmallRng;
 use rand_distr::StandardNormal;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
@@ -45,16 +46,36 @@ use std::thread;
 ///    filter method is highly efficient. This results in a significant performance
 ///    increase compared with the original tool.
 ///
-/// 3. Parts of the tool's workflow utilize parallel processing. However, the depression
-///    filling operation, which is the most time-consuming part of the workflow, is
-///    not parallelized.
+/// 3. The iterations of the simulation are independent of one another and are run in
+///    parallel, with each processor simulating a separate realization of the error-added DEM.
 ///
 /// In addition to the input DEM (`--dem`) and output p<sub>dep</sub> file name (`--output`), the user
 /// must specify the nature of the error model, including the root-mean-square error (`--rmse`) and
 /// the error field correlation length (`--range`). These parameters determine the statistical frequency
 /// distribution and spatial characteristics of the modeled error fields added to the DEM in each
-/// iteration of the simulation. The user must also specify the number of iterations (`--iterations`).
-/// A larger number of iterations will produce a smoother p<sub>dep</sub> raster.
+/// iteration of the simulation. The RMSE may either be a constant value or a raster of spatially
+/// variable DEM error, e.g. derived from land-cover specific accuracy assessments, of the same
+/// dimensions as the DEM. Each error field is first matched to a standard normal distribution and then
+/// scaled by the local RMSE. Grid cells with **NoData** in an error raster are assigned no error.
+///
+/// The user must also specify the maximum number of iterations (`--iterations`). A larger number of
+/// iterations will produce a smoother p<sub>dep</sub> raster. Optionally, a convergence tolerance
+/// (`--tolerance`) may be specified, in which case the simulation stops before the maximum number of
+/// iterations once the largest change in p<sub>dep</sub> between consecutive batches of parallel
+/// iterations is no greater than the tolerance. The number of iterations that were actually performed
+/// is reported and recorded in the output metadata.
+///
+/// Long simulations can optionally save their progress to a checkpoint file (`--checkpoint`) every
+/// `--checkpoint_interval` minutes (15 by default). Running the tool again with the same DEM, error
+/// model, and checkpoint file, e.g. after a crash or reboot, resumes the simulation from the iterations
+/// completed at the last checkpoint. Because the iterations are independent, the maximum number of
+/// iterations may also be raised when resuming. The checkpoint file is deleted once the tool completes.
+///
+/// The error field of each iteration is generated from its own random number stream. When the global
+/// `--seed` flag is used, the output is therefore reproducible for a given number of iterations, regardless
+/// of the number of processors. However, because convergence is tested between batches of parallel
+/// iterations, the number of iterations performed when a `--tolerance` is specified may depend on the
+/// number of processors.
 ///
 /// This tool creates several temporary rasters in memory and, as a result, is very memory hungry.
 /// This will necessarily limit the size of DEMs that can be processed on more memory-constrained
@@ -105,8 +126,8 @@ impl StochasticDepressionAnalysis {
         parameters.push(ToolParameter{
             name: "DEM root-mean-square-error (z units)".to_owned(), 
             flags: vec!["--rmse".to_owned()], 
-            description: "The DEM's root-mean-square-error (RMSE), in z units. This determines error magnitude.".to_owned(),
-            parameter_type: ParameterType::Float,
+            description: "The DEM's root-mean-square-error (RMSE), in z units, or a raster of spatially variable RMSE. This determines error magnitude.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
             default_value: None,
             optional: false
         });
@@ -123,12 +144,39 @@ impl StochasticDepressionAnalysis {
         parameters.push(ToolParameter {
             name: "Iterations".to_owned(),
             flags: vec!["--iterations".to_owned()],
-            description: "The number of iterations.".to_owned(),
+            description: "The maximum number of iterations.".to_owned(),
             parameter_type: ParameterType::Integer,
             default_value: Some("100".to_owned()),
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Convergence Tolerance (optional)".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Optional maximum change in pdep between batches of iterations at which the simulation stops.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Checkpoint File (optional)".to_owned(),
+            flags: vec!["--checkpoint".to_owned()],
+            description: "Optional file used to periodically save progress, allowing an interrupted run to be resumed.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Checkpoint Interval (minutes)".to_owned(),
+            flags: vec!["--checkpoint_interval".to_owned()],
+            description: "Time between checkpoints, in minutes.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("15.0".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -140,7 +188,8 @@ impl StochasticDepressionAnalysis {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=out.tif --rmse=10.0 --range=850.0 --iterations=2500", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=out.tif --rmse=10.0 --range=850.0 --iterations=2500
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=out.tif --rmse=error.tif --range=850.0 --iterations=5000 --tolerance=0.001", short_exe, name).replace("*", &sep);
 
         StochasticDepressionAnalysis {
             name: name,
@@ -195,9 +244,12 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
     ) -> Result<(), Error> {
         let mut input_file = String::new();
         let mut output_file = String::new();
-        let mut rmse = 1f64;
+        let mut rmse_str = String::from("1.0");
         let mut range = 1f64;
         let mut iterations = 100;
+        let mut tolerance = 0f64;
+        let mut checkpoint_file = String::new();
+        let mut checkpoint_interval = DEFAULT_CHECKPOINT_INTERVAL;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -228,7 +280,13 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                     args[i + 1].to_string()
                 };
             } else if flag_val == "-rmse" {
-                rmse = if keyval {
+                rmse_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-tolerance" {
+                tolerance = if keyval {
                     vec[1]
                         .to_string()
                         .parse::<f64>()
@@ -263,6 +321,24 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                         .parse::<f32>()
                         .expect(&format!("Error parsing {}", flag_val)) as usize
                 };
+            } else if flag_val == "-checkpoint" {
+                checkpoint_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-checkpoint_interval" {
+                checkpoint_interval = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             }
         }
 
@@ -283,15 +359,30 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         if !output_file.contains(&sep) && !output_file.contains("/") {
             output_file = format!("{}{}", working_directory, output_file);
         }
+        let (rmse_is_constant, rmse) = match rmse_str.parse::<f64>() {
+            Ok(val) => (true, val),
+            Err(_) => (false, f64::NAN),
+        };
+        if !rmse_is_constant && !rmse_str.contains(&sep) && !rmse_str.contains("/") {
+            rmse_str = format!("{}{}", working_directory, rmse_str);
+        }
+        if !checkpoint_file.is_empty()
+            && !checkpoint_file.contains(&sep)
+            && !checkpoint_file.contains("/")
+        {
+            checkpoint_file = format!("{}{}", working_directory, checkpoint_file);
+        }
 
+        // The error fields are matched to a standard normal distribution and scaled by the RMSE afterwards.
+        let sd = 1f64;
         let mut reference_cdf: Vec<Vec<f64>> = vec![];
         let mu = 0f64; // assume the mean error is zero
-        let p_step = 6.0 * rmse / 99.0;
+        let p_step = 6.0 * sd / 99.0;
         for a in 0..100 {
-            let x = -3.0 * rmse + a as f64 * p_step;
+            let x = -3.0 * sd + a as f64 * p_step;
             // (1 / sqrt(2σ^2 * π)) * e^(-(x - μ)^2 / 2σ^2)
-            let p = (1.0 / (2.0 * PI * rmse.powi(2)).sqrt())
-                * (-(x - mu).powi(2) / (2.0 * rmse.powi(2))).exp();
+            let p = (1.0 / (2.0 * PI * sd.powi(2)).sqrt())
+                * (-(x - mu).powi(2) / (2.0 * sd.powi(2))).exp();
             reference_cdf.push(vec![x, p]);
         }
 
@@ -356,6 +447,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         let start = Instant::now();
 
         let rows = input1.configs.rows as isize;
+        let mut z: f64;
         let columns = input1.configs.columns as isize;
         let nodata = input1.configs.nodata;
         let sigma = range / input1.configs.resolution_x;
@@ -366,9 +458,25 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         output_config.data_type = DataType::F32;
         let mut freq_dep: Array2D<i16> = Array2D::new(rows, columns, 0i16, -1i16).unwrap();
 
+        let mut rmse_field: Array2D<f32> = Array2D::new(rows, columns, rmse as f32, -1f32)?;
+        if !rmse_is_constant {
+            // make sure the inputs share the same grid and CRS
+            let error_raster = align_raster_input(&input1, Raster::new(&rmse_str, "r")?)?;
+            let error_nodata = error_raster.configs.nodata;
+            for row in 0..rows {
+                for col in 0..columns {
+                    z = error_raster.get_value(row, col);
+                    if z != error_nodata {
+                        rmse_field.set_value(row, col, z as f32);
+                    } else {
+                        rmse_field.set_value(row, col, 0f32);
+                    }
+                }
+            }
+        }
+
         let nodata_i32 = i32::min_value();
         let mut input: Array2D<i32> = Array2D::new(rows, columns, nodata_i32, nodata_i32).unwrap();
-        let mut z: f64;
         let multiplier = 1000f64;
         let mut num_nodata = 0usize;
         for row in 0..rows {
@@ -389,353 +497,138 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             num_nodata = ((rows + 2) * 2 + (columns + 2) * 2) as usize;
         }
 
-        // let mut error_model: Array2D<i32> = Array2D::new(rows, columns, nodata_i32, nodata_i32).unwrap();
-        let background_val = i32::min_value() + 1;
-        let num_procs = num_cpus::get() as isize;
-        let numcells: f64 = (rows * columns) as f64; // used by the histogram matching
-        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-
-        for iter_num in 0..iterations {
-            if verbose {
-                println!("Iteration {}...", iter_num + 1);
+        let num_procs = num_cpus::get() as usize;
+        let input = Arc::new(input);
+        let rmse_field = Arc::new(rmse_field);
+        let reference_cdf = Arc::new(reference_cdf);
+        let mut batch_freq: Array2D<i16> = Array2D::new(rows, columns, 0i16, -1i16)?;
+        let mut num_iterations = 0usize;
+        let mut converged = false;
+
+        let mut checkpoint = if checkpoint_file.is_empty() {
+            None
+        } else {
+            let key = format!(
+                "{} {} {} {} {} {}",
+                self.get_tool_name(),
+                input_file,
+                file_signature(&input_file),
+                rmse_str,
+                file_signature(&rmse_str),
+                range
+            );
+            Some(Checkpoint::new(&checkpoint_file, &key, checkpoint_interval))
+        };
+        if let Some(cp) = &checkpoint {
+            if let Some((_, mut reader)) = cp.load()? {
+                reader.read_array2d(&mut freq_dep)?;
+                num_iterations = reader.read()?;
+                if verbose {
+                    println!(
+                        "Resuming from checkpoint {} after {} iterations...",
+                        checkpoint_file, num_iterations
+                    );
+                }
             }
-
-            /////////////////////////////
-            // Generate a random field //
-            /////////////////////////////
-
+        }
+        let (mut f, mut d): (i16, i16);
+        let mut max_change: f64;
+        while num_iterations < iterations {
+            // Each iteration in the batch simulates an independent error-added DEM.
+            let batch_size = num_procs.min(iterations - num_iterations);
             let (tx, rx) = mpsc::channel();
-            for tid in 0..num_procs {
+            for i in 0..batch_size {
+                let iteration = (num_iterations + i) as u64;
+                let input = input.clone();
+                let rmse_field = rmse_field.clone();
+                let reference_cdf = reference_cdf.clone();
                 let tx = tx.clone();
                 thread::spawn(move || {
-                    let mut rng = SmallRng::from_entropy();
-                    let mut sn_val: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![0i32; columns as usize];
-                        for col in 0..columns {
-                            sn_val = rng.sample(StandardNormal);
-                            data[col as usize] =
-                                (sn_val * multiplier * range_in_cells * 2f64) as i32;
-                        }
-
-                        tx.send((row, data)).unwrap();
-                    }
+                    let dep_cells = simulate_depressions(
+                        &input,
+                        &rmse_field,
+                        &reference_cdf,
+                        &starting_vals,
+                        sigma,
+                        range_in_cells,
+                        multiplier,
+                        num_nodata,
+                        iteration,
+                    );
+                    tx.send(dep_cells).unwrap();
                 });
             }
 
-            let mut error_model: Array2D<i32> =
-                Array2D::new(rows, columns, nodata_i32, nodata_i32).unwrap();
-            for _ in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                error_model.set_row_data(row, data);
-            }
-
-            ////////////////////////////////////////
-            // Perform a FastAlmostGaussianFilter //
-            ////////////////////////////////////////
-            let n = 5;
-            let w_ideal = (12f64 * sigma * sigma / n as f64 + 1f64).sqrt();
-            let mut wl = w_ideal.floor() as isize;
-            if wl % 2 == 0 {
-                wl -= 1;
-            } // must be an odd integer
-            let wu = wl + 2;
-            let m = ((12f64 * sigma * sigma
-                - (n * wl * wl) as f64
-                - (4 * n * wl) as f64
-                - (3 * n) as f64)
-                / (-4 * wl - 4) as f64)
-                .round() as isize;
-
-            let mut val: i32;
-            let mut sum: i32;
-            let mut i_prev: i32;
-
-            // Find the min and max values.
-            let mut min_value = i32::max_value();
-            let mut max_value = i32::min_value();
-            let mut z: i32;
-
-            for iteration_num in 0..n {
-                let midpoint = if iteration_num <= m {
-                    (wl as f64 / 2f64).floor() as isize
-                } else {
-                    (wu as f64 / 2f64).floor() as isize
-                };
-
-                // Create the integral image.
-                let mut integral: Array2D<i32> =
-                    Array2D::new(rows, columns, 0, nodata_i32).unwrap();
-                for row in 0..rows {
-                    sum = 0;
-                    for col in 0..columns {
-                        val = error_model.get_value(row, col);
-                        sum += val;
-                        if row > 0 {
-                            i_prev = integral.get_value(row - 1, col);
-                            integral.set_value(row, col, sum + i_prev);
-                        } else {
-                            integral.set_value(row, col, sum);
-                        }
-                    }
-                }
-
-                // Perform Filter
-                let integral = Arc::new(integral);
-                let (tx, rx) = mpsc::channel();
-                for tid in 0..num_procs {
-                    let tx = tx.clone();
-                    let integral = integral.clone();
-                    thread::spawn(move || {
-                        let mut z: i32;
-                        let mut sum: i32;
-                        let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                        let mut num_cells: i32;
-                        for row in (0..rows).filter(|r| r % num_procs == tid) {
-                            y1 = row - midpoint - 1;
-                            if y1 < 0 {
-                                y1 = 0;
-                            }
-                            y2 = row + midpoint;
-                            if y2 >= rows {
-                                y2 = rows - 1;
-                            }
-                            let mut data = vec![0i32; columns as usize];
-                            let mut min_value = i32::max_value();
-                            let mut max_value = i32::min_value();
-                            for col in 0..columns {
-                                x1 = col - midpoint - 1;
-                                if x1 < 0 {
-                                    x1 = 0;
-                                }
-                                x2 = col + midpoint;
-                                if x2 >= columns {
-                                    x2 = columns - 1;
-                                }
-
-                                num_cells = ((y2 - y1) * (x2 - x1)) as i32;
-                                if num_cells > 0 {
-                                    sum = integral[(y2, x2)] + integral[(y1, x1)]
-                                        - integral[(y1, x2)]
-                                        - integral[(y2, x1)];
-
-                                    z = sum / num_cells;
-                                    data[col as usize] = z;
-                                    if z < min_value {
-                                        min_value = z;
-                                    }
-                                    if z > max_value {
-                                        max_value = z;
-                                    }
-                                }
-                            }
-
-                            tx.send((row, data, min_value, max_value)).unwrap();
-                        }
-                    });
+            batch_freq.reinitialize_values(0i16);
+            for _ in 0..batch_size {
+                let dep_cells = rx.recv().expect("Error receiving data from thread.");
+                for id in dep_cells {
+                    batch_freq.increment(id / columns, id % columns, 1i16);
                 }
-
-                for _ in 0..rows {
-                    let (row, data, val1, val2) =
-                        rx.recv().expect("Error receiving data from thread.");
-                    error_model.set_row_data(row, data);
-                    if val1 < min_value {
-                        min_value = val1;
-                    }
-                    if val2 > max_value {
-                        max_value = val2;
-                    }
-                }
-
-                drop(integral);
             }
 
-            ////////////////////////////////////////////
-            // Perform a histogram matching operation //
-            ////////////////////////////////////////////
-
-            let num_bins = (max_value - min_value + 1) as usize;
-            let mut histogram = vec![0f64; num_bins];
-            let mut bin_num: usize;
+            let n_old = num_iterations as f64;
+            num_iterations += batch_size;
+            let n_new = num_iterations as f64;
+            max_change = 0f64;
             for row in 0..rows {
                 for col in 0..columns {
-                    z = error_model.get_value(row, col);
-                    bin_num = (z - min_value) as usize;
-                    histogram[bin_num] += 1f64;
-                }
-            }
-
-            let mut cdf = vec![0f64; num_bins];
-            cdf[0] = histogram[0];
-            for i in 1..num_bins {
-                cdf[i] = cdf[i - 1] + histogram[i];
-            }
-            for i in 0..num_bins {
-                cdf[i] = cdf[i] / numcells;
-            }
-
-            drop(histogram);
-
-            let mut j: usize;
-            let mut x_val = 0f64;
-            let mut p_val: f64;
-            let (mut x1, mut x2, mut p1, mut p2): (f64, f64, f64, f64);
-            for row in 0..rows {
-                for col in 0..columns {
-                    z = error_model.get_value(row, col);
-                    bin_num = (z - min_value) as usize;
-                    p_val = cdf[bin_num];
-                    j = ((p_val * 10f64).floor()) as usize;
-                    for i in starting_vals[j]..num_lines {
-                        if reference_cdf[i][1] > p_val {
-                            if i > 0 {
-                                x1 = reference_cdf[i - 1][0];
-                                x2 = reference_cdf[i][0];
-                                p1 = reference_cdf[i - 1][1];
-                                p2 = reference_cdf[i][1];
-                                x_val = if p1 != p2 {
-                                    x1 + ((x2 - x1) * ((p_val - p1) / (p2 - p1)))
-                                } else {
-                                    x1
-                                };
-                            } else {
-                                x_val = reference_cdf[i][0];
-                            }
-                            break;
-                        }
+                    d = batch_freq.get_value(row, col);
+                    f = freq_dep.get_value(row, col);
+                    if n_old > 0f64 {
+                        max_change =
+                            max_change.max(((f + d) as f64 / n_new - f as f64 / n_old).abs());
                     }
-                    error_model.set_value(row, col, (x_val * multiplier) as i32);
-                }
-            }
-
-            drop(cdf);
-
-            /////////////////////////////////////
-            // Add the DEM to the error model. //
-            /////////////////////////////////////
-            let mut e: i32;
-            for row in 0..rows {
-                for col in 0..columns {
-                    z = input.get_value(row, col);
-                    if z != nodata_i32 {
-                        e = error_model.get_value(row, col);
-                        error_model.set_value(row, col, z + e);
-                    } else {
-                        error_model.set_value(row, col, nodata_i32);
+                    if d > 0 {
+                        freq_dep.set_value(row, col, f + d);
                     }
                 }
             }
 
-            /////////////////////////////////////////////////
-            // Fill the depressions in the error-added DEM //
-            /////////////////////////////////////////////////
-
-            /*
-            Find the data edges. This is complicated by the fact that DEMs frequently
-            have nodata edges, whereby the DEM does not occupy the full extent of
-            the raster. One approach to doing this would be simply to scan the
-            raster, looking for cells that neighbour nodata values. However, this
-            assumes that there are no interior nodata holes in the dataset. Instead,
-            the approach used here is to perform a region-growing operation, looking
-            for nodata values along the raster's edges.
-            */
-
-            let mut queue: VecDeque<(isize, isize)> = VecDeque::with_capacity(num_nodata);
-            for row in 0..rows {
-                /*
-                Note that this is only possible because Whitebox rasters
-                allow you to address cells beyond the raster extent but
-                return the nodata value for these regions.
-                */
-                queue.push_back((row, -1));
-                queue.push_back((row, columns));
-            }
-
-            for col in 0..columns {
-                queue.push_back((-1, col));
-                queue.push_back((rows, col));
-            }
-
-            let mut dep_filled: Array2D<i32> =
-                Array2D::new(rows, columns, background_val, nodata_i32).unwrap();
-            let mut minheap = BinaryHeap::with_capacity((rows * columns) as usize - num_nodata);
-            let mut zin_n: i32; // value of neighbour of row, col in input raster
-            let mut zout: i32; // value of row, col in output raster
-            let mut zout_n: i32; // value of neighbour of row, col in output raster
-            let (mut row, mut col): (isize, isize);
-            let (mut row_n, mut col_n): (isize, isize);
-            while !queue.is_empty() {
-                let cell = queue.pop_front().unwrap();
-                row = cell.0;
-                col = cell.1;
-                for n in 0..8 {
-                    row_n = row + dy[n];
-                    col_n = col + dx[n];
-                    zin_n = error_model.get_value(row_n, col_n);
-                    zout_n = dep_filled.get_value(row_n, col_n);
-                    if zout_n == background_val {
-                        if zin_n == nodata_i32 {
-                            dep_filled.set_value(row_n, col_n, nodata_i32);
-                            queue.push_back((row_n, col_n));
-                        } else {
-                            dep_filled.set_value(row_n, col_n, zin_n);
-                            // Push it onto the priority queue for the priority flood operation
-                            minheap.push(GridCell {
-                                id: row_n * columns + col_n,
-                                priority: zin_n,
-                            });
-                        }
-                    }
+            if verbose {
+                if n_old > 0f64 {
+                    println!(
+                        "Iterations completed: {} (max. change in pdep: {:.6})",
+                        num_iterations, max_change
+                    );
+                }
+                progress = (100.0_f64 * num_iterations as f64 / iterations as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
 
-            drop(queue);
-
-            // Perform the priority flood operation.
-            while !minheap.is_empty() {
-                let cell = minheap.pop().expect("Error during pop operation.");
-                row = cell.id / columns;
-                col = cell.id % columns;
-                zout = dep_filled.get_value(row, col);
-                for n in 0..8 {
-                    row_n = row + dy[n];
-                    col_n = col + dx[n];
-                    zout_n = dep_filled.get_value(row_n, col_n);
-                    if zout_n == background_val {
-                        zin_n = error_model.get_value(row_n, col_n);
-                        if zin_n != nodata_i32 {
-                            if zin_n < zout {
-                                zin_n = zout;
-                                // Depression cell; increase its value in output
-                                freq_dep.increment(row_n, col_n, 1i16);
-                            } // We're in a depression. Raise the elevation.
-                            dep_filled.set_value(row_n, col_n, zin_n);
-                            minheap.push(GridCell {
-                                id: row_n * columns + col_n,
-                                priority: zin_n,
-                            });
-                        } else {
-                            // Interior nodata cells are still treated as nodata and are not filled.
-                            dep_filled.set_value(row_n, col_n, nodata_i32);
-                        }
+            if let Some(cp) = checkpoint.as_mut() {
+                if cp.is_due() {
+                    if verbose {
+                        println!("Saving checkpoint...");
                     }
+                    cp.save(1, |w| {
+                        w.write_array2d(&freq_dep)?;
+                        w.write(num_iterations)
+                    })?;
                 }
             }
 
-            drop(minheap);
-            drop(dep_filled);
+            if tolerance > 0f64 && n_old > 0f64 && max_change <= tolerance {
+                converged = true;
+                break;
+            }
+        }
 
-            if verbose {
-                progress = (100.0_f64 * (iter_num + 1) as f64 / iterations as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
+        if verbose && tolerance > 0f64 {
+            if converged {
+                println!("pdep converged after {} iterations.", num_iterations);
+            } else {
+                println!(
+                    "Warning: pdep did not converge within {} iterations.",
+                    num_iterations
+                );
             }
         }
 
-        let iters = iterations as f64;
+        let iters = num_iterations as f64;
         let mut output = Raster::initialize_using_config(&output_file, &output_config);
         for row in 0..rows {
             for col in 0..columns {
@@ -754,9 +647,17 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             self.get_tool_name()
         ));
         output.add_metadata_entry(format!("Input base raster file: {}", input_file));
-        output.add_metadata_entry(format!("RMSE: {}", rmse));
+        if rmse_is_constant {
+            output.add_metadata_entry(format!("RMSE: {}", rmse));
+        } else {
+            output.add_metadata_entry(format!("RMSE raster file: {}", rmse_str));
+        }
         output.add_metadata_entry(format!("Range: {}", range));
-        output.add_metadata_entry(format!("Iterations: {}", iterations));
+        output.add_metadata_entry(format!("Iterations: {}", num_iterations));
+        if tolerance > 0f64 {
+            output.add_metadata_entry(format!("Convergence tolerance: {}", tolerance));
+            output.add_metadata_entry(format!("Converged: {}", converged));
+        }
         let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
@@ -771,6 +672,9 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             }
             Err(e) => return Err(e),
         };
+        if let Some(cp) = &checkpoint {
+            cp.remove();
+        }
         if verbose {
             println!(
                 "{}",
@@ -806,3 +710,316 @@ impl PartialEq for GridCell {
         self.priority == other.priority
     }
 }
+
+/// Simulates a single realization of the error-added DEM and returns the ids (`row * columns + col`)
+/// of the grid cells that are located within depressions. The error field is drawn from the random
+/// number stream of the specified iteration.
+fn simulate_depressions(
+    input: &Array2D<i32>,
+    rmse_field: &Array2D<f32>,
+    reference_cdf: &Vec<Vec<f64>>,
+    starting_vals: &[usize; 11],
+    sigma: f64,
+    range_in_cells: f64,
+    multiplier: f64,
+    num_nodata: usize,
+    iteration: u64,
+) -> Vec<isize> {
+    let rows = input.rows as isize;
+    let columns = input.columns as isize;
+    let nodata_i32 = i32::min_value();
+    let background_val = i32::min_value() + 1;
+    let numcells: f64 = (rows * columns) as f64; // used by the histogram matching
+    let num_lines = reference_cdf.len();
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+    /////////////////////////////
+    // Generate a random field //
+    /////////////////////////////
+
+    let mut rng = random::rng(iteration);
+    let mut sn_val: f64;
+    let mut error_model: Array2D<i32> =
+        Array2D::new(rows, columns, nodata_i32, nodata_i32).unwrap();
+    for row in 0..rows {
+        for col in 0..columns {
+            sn_val = rng.sample(StandardNormal);
+            error_model.set_value(
+                row,
+                col,
+                (sn_val * multiplier * range_in_cells * 2f64) as i32,
+            );
+        }
+    }
+
+    ////////////////////////////////////////
+    // Perform a FastAlmostGaussianFilter //
+    ////////////////////////////////////////
+    let n = 5;
+    let w_ideal = (12f64 * sigma * sigma / n as f64 + 1f64).sqrt();
+    let mut wl = w_ideal.floor() as isize;
+    if wl % 2 == 0 {
+        wl -= 1;
+    } // must be an odd integer
+    let wu = wl + 2;
+    let m = ((12f64 * sigma * sigma - (n * wl * wl) as f64 - (4 * n * wl) as f64 - (3 * n) as f64)
+        / (-4 * wl - 4) as f64)
+        .round() as isize;
+
+    let mut val: i32;
+    let mut sum: i32;
+    let mut i_prev: i32;
+
+    // Find the min and max values.
+    let mut min_value = i32::max_value();
+    let mut max_value = i32::min_value();
+    let mut z: i32;
+    let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+    let mut num_cells: i32;
+
+    for iteration_num in 0..n {
+        let midpoint = if iteration_num <= m {
+            (wl as f64 / 2f64).floor() as isize
+        } else {
+            (wu as f64 / 2f64).floor() as isize
+        };
+
+        // Create the integral image.
+        let mut integral: Array2D<i32> = Array2D::new(rows, columns, 0, nodata_i32).unwrap();
+        for row in 0..rows {
+            sum = 0;
+            for col in 0..columns {
+                val = error_model.get_value(row, col);
+                sum += val;
+                if row > 0 {
+                    i_prev = integral.get_value(row - 1, col);
+                    integral.set_value(row, col, sum + i_prev);
+                } else {
+                    integral.set_value(row, col, sum);
+                }
+            }
+        }
+
+        // Perform Filter
+        for row in 0..rows {
+            y1 = row - midpoint - 1;
+            if y1 < 0 {
+                y1 = 0;
+            }
+            y2 = row + midpoint;
+            if y2 >= rows {
+                y2 = rows - 1;
+            }
+            for col in 0..columns {
+                x1 = col - midpoint - 1;
+                if x1 < 0 {
+                    x1 = 0;
+                }
+                x2 = col + midpoint;
+                if x2 >= columns {
+                    x2 = columns - 1;
+                }
+
+                num_cells = ((y2 - y1) * (x2 - x1)) as i32;
+                z = if num_cells > 0 {
+                    sum = integral[(y2, x2)] + integral[(y1, x1)]
+                        - integral[(y1, x2)]
+                        - integral[(y2, x1)];
+                    sum / num_cells
+                } else {
+                    0
+                };
+                error_model.set_value(row, col, z);
+                if num_cells > 0 {
+                    if z < min_value {
+                        min_value = z;
+                    }
+                    if z > max_value {
+                        max_value = z;
+                    }
+                }
+            }
+        }
+    }
+
+    ////////////////////////////////////////////
+    // Perform a histogram matching operation //
+    ////////////////////////////////////////////
+
+    let num_bins = (max_value - min_value + 1) as usize;
+    let mut histogram = vec![0f64; num_bins];
+    let mut bin_num: usize;
+    for row in 0..rows {
+        for col in 0..columns {
+            z = error_model.get_value(row, col);
+            bin_num = (z - min_value) as usize;
+            histogram[bin_num] += 1f64;
+        }
+    }
+
+    let mut cdf = vec![0f64; num_bins];
+    cdf[0] = histogram[0];
+    for i in 1..num_bins {
+        cdf[i] = cdf[i - 1] + histogram[i];
+    }
+    for i in 0..num_bins {
+        cdf[i] = cdf[i] / numcells;
+    }
+
+    drop(histogram);
+
+    let mut j: usize;
+    let mut x_val = 0f64;
+    let mut p_val: f64;
+    let (mut x1, mut x2, mut p1, mut p2): (f64, f64, f64, f64);
+    for row in 0..rows {
+        for col in 0..columns {
+            z = error_model.get_value(row, col);
+            bin_num = (z - min_value) as usize;
+            p_val = cdf[bin_num];
+            j = ((p_val * 10f64).floor()) as usize;
+            for i in starting_vals[j]..num_lines {
+                if reference_cdf[i][1] > p_val {
+                    if i > 0 {
+                        x1 = reference_cdf[i - 1][0];
+                        x2 = reference_cdf[i][0];
+                        p1 = reference_cdf[i - 1][1];
+                        p2 = reference_cdf[i][1];
+                        x_val = if p1 != p2 {
+                            x1 + ((x2 - x1) * ((p_val - p1) / (p2 - p1)))
+                        } else {
+                            x1
+                        };
+                    } else {
+                        x_val = reference_cdf[i][0];
+                    }
+                    break;
+                }
+            }
+            // scale the standard normal error by the local RMSE
+            error_model.set_value(
+                row,
+                col,
+                (x_val * rmse_field.get_value(row, col) as f64 * multiplier) as i32,
+            );
+        }
+    }
+
+    drop(cdf);
+
+    /////////////////////////////////////
+    // Add the DEM to the error model. //
+    /////////////////////////////////////
+    let mut e: i32;
+    for row in 0..rows {
+        for col in 0..columns {
+            z = input.get_value(row, col);
+            if z != nodata_i32 {
+                e = error_model.get_value(row, col);
+                error_model.set_value(row, col, z + e);
+            } else {
+                error_model.set_value(row, col, nodata_i32);
+            }
+        }
+    }
+
+    /////////////////////////////////////////////////
+    // Fill the depressions in the error-added DEM //
+    /////////////////////////////////////////////////
+
+    /*
+    Find the data edges. This is complicated by the fact that DEMs frequently
+    have nodata edges, whereby the DEM does not occupy the full extent of
+    the raster. One approach to doing this would be simply to scan the
+    raster, looking for cells that neighbour nodata values. However, this
+    assumes that there are no interior nodata holes in the dataset. Instead,
+    the approach used here is to perform a region-growing operation, looking
+    for nodata values along the raster's edges.
+    */
+
+    let mut queue: VecDeque<(isize, isize)> = VecDeque::with_capacity(num_nodata);
+    for row in 0..rows {
+        /*
+        Note that this is only possible because Whitebox rasters
+        allow you to address cells beyond the raster extent but
+        return the nodata value for these regions.
+        */
+        queue.push_back((row, -1));
+        queue.push_back((row, columns));
+    }
+
+    for col in 0..columns {
+        queue.push_back((-1, col));
+        queue.push_back((rows, col));
+    }
+
+    let mut dep_filled: Array2D<i32> =
+        Array2D::new(rows, columns, background_val, nodata_i32).unwrap();
+    let mut minheap = BinaryHeap::with_capacity((rows * columns) as usize - num_nodata);
+    let mut dep_cells = vec![];
+    let mut zin_n: i32; // value of neighbour of row, col in input raster
+    let mut zout: i32; // value of row, col in output raster
+    let mut zout_n: i32; // value of neighbour of row, col in output raster
+    let (mut row, mut col): (isize, isize);
+    let (mut row_n, mut col_n): (isize, isize);
+    while !queue.is_empty() {
+        let cell = queue.pop_front().unwrap();
+        row = cell.0;
+        col = cell.1;
+        for n in 0..8 {
+            row_n = row + dy[n];
+            col_n = col + dx[n];
+            zin_n = error_model.get_value(row_n, col_n);
+            zout_n = dep_filled.get_value(row_n, col_n);
+            if zout_n == background_val {
+                if zin_n == nodata_i32 {
+                    dep_filled.set_value(row_n, col_n, nodata_i32);
+                    queue.push_back((row_n, col_n));
+                } else {
+                    dep_filled.set_value(row_n, col_n, zin_n);
+                    // Push it onto the priority queue for the priority flood operation
+                    minheap.push(GridCell {
+                        id: row_n * columns + col_n,
+                        priority: zin_n,
+                    });
+                }
+            }
+        }
+    }
+
+    drop(queue);
+
+    // Perform the priority flood operation.
+    while !minheap.is_empty() {
+        let cell = minheap.pop().expect("Error during pop operation.");
+        row = cell.id / columns;
+        col = cell.id % columns;
+        zout = dep_filled.get_value(row, col);
+        for n in 0..8 {
+            row_n = row + dy[n];
+            col_n = col + dx[n];
+            zout_n = dep_filled.get_value(row_n, col_n);
+            if zout_n == background_val {
+                zin_n = error_model.get_value(row_n, col_n);
+                if zin_n != nodata_i32 {
+                    if zin_n < zout {
+                        zin_n = zout;
+                        // Depression cell
+                        dep_cells.push(row_n * columns + col_n);
+                    } // We're in a depression. Raise the elevation.
+                    dep_filled.set_value(row_n, col_n, zin_n);
+                    minheap.push(GridCell {
+                        id: row_n * columns + col_n,
+                        priority: zin_n,
+                    });
+                } else {
+                    // Interior nodata cells are still treated as nodata and are not filled.
+                    dep_filled.set_value(row_n, col_n, nodata_i32);
+                }
+            }
+        }
+    }
+
+    dep_cells
+}
@@ -212,7 +212,8 @@ impl WhiteboxTool for StrahlerOrderBasins {
         };
 
         let pntr = Raster::new(&d8_file, "r")?;
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -221,16 +222,6 @@ impl WhiteboxTool for StrahlerOrderBasins {
         let nodata = streams.configs.nodata;
         let pntr_nodata = pntr.configs.nodata;
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let dx = [1, 1, 1, 0, -1, -1, -1, 0];
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
 
@@ -0,0 +1,573 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates a statistic of the values of an attribute raster (`--values`) within the
+/// upslope contributing area of each grid cell, e.g. the maximum upslope road density, the mean
+/// upslope rainfall, or the median upslope soil erodibility. It generalizes D8 flow accumulation,
+/// in which the values of the upslope cells are summed, to other statistics: the attribute values
+/// are propagated downstream along D8 flow paths (O'Callaghan and Mark, 1984) and each cell records
+/// the maximum (`max`), minimum (`min`), mean (`mean`), or a percentile (`percentile`) of the
+/// values of the cells that drain to it (`--stat`), including itself.
+///
+/// The user must specify the name of the input digital elevation model (DEM) or D8 flow pointer
+/// raster (`--input`). If an input DEM is used, it must have been hydrologically corrected to
+/// remove all spurious depressions and flat areas, e.g. using the `BreachDepressionsLeastCost`
+/// tool. If a D8 pointer raster is input, the user must also specify the `--pntr` flag, and the
+/// `--esri_pntr` flag if the pointer follows the Esri pointer scheme. The attribute raster must
+/// share the grid of the DEM/pointer. Cells with **NoData** attribute values do not contribute to
+/// the statistic of their downslope cells, and cells without any valid upslope values are assigned
+/// **NoData**, as are the **NoData** cells of the DEM/pointer.
+///
+/// Percentiles (`--percentile`, e.g. 50 for the median) are estimated from histograms of the
+/// upslope values, which are divided into a number of equal-width bins (`--bins`) spanning the
+/// range of the attribute raster, and are linearly interpolated within the bin containing the
+/// percentile. Increasing the number of bins improves the precision of the estimate at the expense
+/// of memory and processing time.
+///
+/// # See Also
+/// `D8FlowAccumulation`, `D8Pointer`, `DownslopeFlowpathLength`, `BreachDepressionsLeastCost`
+pub struct UpslopeStatistics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl UpslopeStatistics {
+    pub fn new() -> UpslopeStatistics {
+        // public constructor
+        let name = "UpslopeStatistics".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description =
+            "Calculates a statistic of an attribute raster within the upslope area of each cell."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM or D8 Pointer File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster DEM or D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Values File".to_owned(),
+            flags: vec!["--values".to_owned()],
+            description: "Input raster of the attribute values.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistic".to_owned(),
+            flags: vec!["--stat".to_owned()],
+            description: "Statistic of the upslope values.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "max".to_owned(),
+                "min".to_owned(),
+                "mean".to_owned(),
+                "percentile".to_owned(),
+            ]),
+            default_value: Some("mean".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Percentile".to_owned(),
+            flags: vec!["--percentile".to_owned()],
+            description: "Percentile (0-100) calculated when the statistic is 'percentile'."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("50.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Histogram Bins".to_owned(),
+            flags: vec!["--bins".to_owned()],
+            description: "Number of histogram bins used to estimate percentiles.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("256".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Is the input raster a D8 flow pointer?".to_owned(),
+            flags: vec!["--pntr".to_owned()],
+            description: "Is the input raster a D8 flow pointer rather than a DEM?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "If a pointer is input, does it use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_pntr".to_owned()],
+            description: "Input D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --input=DEM.tif --values=rainfall.tif -o=upslope_rainfall.tif --stat=mean
+>>.*{} -r={} -v --wd=\"*path*to*data*\" --input=D8.tif --pntr --values=road_density.tif -o=output.tif --stat=percentile --percentile=90",
+            short_exe, name, short_exe, name
+        )
+        .replace("*", &sep);
+
+        UpslopeStatistics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for UpslopeStatistics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut values_file = String::new();
+        let mut output_file = String::new();
+        let mut stat = "mean".to_string();
+        let mut percentile = 50f64;
+        let mut num_bins = 256usize;
+        let mut pntr_input = false;
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-values" {
+                values_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-stat" {
+                stat = value.trim().to_lowercase();
+            } else if flag_val == "-percentile" {
+                percentile = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-bins" {
+                num_bins = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-pntr" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    pntr_input = true;
+                }
+            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    esri_style = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !values_file.contains(&sep) && !values_file.contains("/") {
+            values_file = format!("{}{}", working_directory, values_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if stat != "max" && stat != "min" && stat != "mean" && stat != "percentile" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The statistic (--stat) must be one of 'max', 'min', 'mean', or 'percentile'.",
+            ));
+        }
+        if stat == "percentile" {
+            if percentile < 0f64 || percentile > 100f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The percentile (--percentile) must be between 0 and 100.",
+                ));
+            }
+            if num_bins < 2 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The number of histogram bins (--bins) must be at least two.",
+                ));
+            }
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let values = align_raster_input(&input, Raster::new(&values_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let values_nodata = values.configs.nodata;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+        // calculate the flow directions, with -1 for no-flow cells and -2 for NoData cells
+        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
+        if !pntr_input {
+            let cell_size_x = input.configs.resolution_x;
+            let cell_size_y = input.configs.resolution_y;
+            let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+            let grid_lengths = [
+                diag_cell_size,
+                cell_size_x,
+                diag_cell_size,
+                cell_size_y,
+                diag_cell_size,
+                cell_size_x,
+                diag_cell_size,
+                cell_size_y,
+            ];
+            for row in 0..rows {
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if z == nodata {
+                        continue;
+                    }
+                    let mut dir = -1i8;
+                    let mut max_slope = 0f64;
+                    for i in 0..8 {
+                        let z_n = input.get_value(row + dy[i], col + dx[i]);
+                        if z_n != nodata {
+                            let slope = (z - z_n) / grid_lengths[i];
+                            if slope > max_slope {
+                                max_slope = slope;
+                                dir = i as i8;
+                            }
+                        }
+                    }
+                    flow_dir.set_value(row, col, dir);
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Flow directions: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            // map the pointer values onto the cell offsets in dx and dy
+            let mut pntr_matches: [i8; 129] = [-1i8; 129];
+            let pntr_vals = if esri_style {
+                [128, 1, 2, 4, 8, 16, 32, 64]
+            } else {
+                [1, 2, 4, 8, 16, 32, 64, 128]
+            };
+            for i in 0..8 {
+                pntr_matches[pntr_vals[i]] = i as i8;
+            }
+            for row in 0..rows {
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if z == nodata {
+                        continue;
+                    }
+                    let dir = if z > 0f64 && z <= 128f64 {
+                        pntr_matches[z as usize]
+                    } else {
+                        -1i8
+                    };
+                    flow_dir.set_value(row, col, dir);
+                }
+            }
+        }
+
+        // calculate the number of inflowing cells
+        let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut stack = Vec::with_capacity((rows * columns) as usize);
+        let mut num_cells = 0usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                if flow_dir.get_value(row, col) != -2i8 {
+                    let count = (0..8)
+                        .filter(|&i| {
+                            flow_dir.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
+                        })
+                        .count() as i8;
+                    num_inflowing.set_value(row, col, count);
+                    num_cells += 1;
+                    if count == 0 {
+                        stack.push((row, col));
+                    }
+                }
+            }
+        }
+
+        // the histogram bins used to estimate percentiles
+        let (mut min_val, mut max_val) = (f64::INFINITY, f64::NEG_INFINITY);
+        if stat == "percentile" {
+            for row in 0..rows {
+                for col in 0..columns {
+                    let v = values.get_value(row, col);
+                    if v != values_nodata {
+                        min_val = min_val.min(v);
+                        max_val = max_val.max(v);
+                    }
+                }
+            }
+        }
+        let bin_width = if max_val > min_val {
+            (max_val - min_val) / num_bins as f64
+        } else {
+            1f64
+        };
+        let bin_of = |v: f64| -> usize { (((v - min_val) / bin_width) as usize).min(num_bins - 1) };
+
+        // propagate the values downstream. Each cell's accumulator holds the number of valid
+        // upslope values and their sum, minimum, and maximum; histograms are only held until
+        // they are passed on to the downslope cell.
+        let mut count: Array2D<f64> = Array2D::new(rows, columns, 0f64, -1f64)?;
+        let mut acc: Array2D<f64> = Array2D::new(rows, columns, f64::NAN, f64::NAN)?;
+        let mut histograms: HashMap<(isize, isize), Vec<u32>> = HashMap::new();
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = values.configs.palette.clone();
+        output.reinitialize_values(nodata);
+        let mut num_solved_cells = 0usize;
+        while let Some((row, col)) = stack.pop() {
+            let v = values.get_value(row, col);
+            let mut n = count.get_value(row, col);
+            let mut a = acc.get_value(row, col);
+            let mut histo = if stat == "percentile" {
+                histograms
+                    .remove(&(row, col))
+                    .unwrap_or_else(|| vec![0u32; num_bins])
+            } else {
+                vec![]
+            };
+            if v != values_nodata {
+                n += 1f64;
+                a = if a.is_nan() {
+                    v
+                } else {
+                    match stat.as_str() {
+                        "max" => a.max(v),
+                        "min" => a.min(v),
+                        "mean" => a + v,
+                        _ => a,
+                    }
+                };
+                if stat == "percentile" {
+                    histo[bin_of(v)] += 1;
+                }
+            }
+
+            if n > 0f64 {
+                let z = match stat.as_str() {
+                    "mean" => a / n,
+                    "percentile" => {
+                        // the rank of the percentile among the upslope values
+                        let target = percentile / 100f64 * n;
+                        let mut cumulative = 0f64;
+                        let mut z = max_val;
+                        for (bin, &freq) in histo.iter().enumerate() {
+                            if freq > 0 && cumulative + freq as f64 >= target {
+                                let frac = (target - cumulative) / freq as f64;
+                                z = min_val + (bin as f64 + frac) * bin_width;
+                                break;
+                            }
+                            cumulative += freq as f64;
+                        }
+                        z.max(min_val).min(max_val)
+                    }
+                    _ => a,
+                };
+                output.set_value(row, col, z);
+            }
+
+            let dir = flow_dir.get_value(row, col);
+            if dir >= 0 {
+                let (row_n, col_n) = (row + dy[dir as usize], col + dx[dir as usize]);
+                if flow_dir.get_value(row_n, col_n) != -2i8 {
+                    count.increment(row_n, col_n, n);
+                    if !a.is_nan() {
+                        let a_n = acc.get_value(row_n, col_n);
+                        let merged = if a_n.is_nan() {
+                            a
+                        } else {
+                            match stat.as_str() {
+                                "max" => a_n.max(a),
+                                "min" => a_n.min(a),
+                                "mean" => a_n + a,
+                                _ => a_n,
+                            }
+                        };
+                        acc.set_value(row_n, col_n, merged);
+                    }
+                    if stat == "percentile" && n > 0f64 {
+                        let entry = histograms
+                            .entry((row_n, col_n))
+                            .or_insert_with(|| vec![0u32; num_bins]);
+                        for (b, freq) in histo.iter().enumerate() {
+                            entry[b] += freq;
+                        }
+                    }
+                    num_inflowing.decrement(row_n, col_n, 1i8);
+                    if num_inflowing.get_value(row_n, col_n) == 0i8 {
+                        stack.push((row_n, col_n));
+                    }
+                }
+            }
+
+            if verbose {
+                num_solved_cells += 1;
+                progress = (100.0_f64 * num_solved_cells as f64 / num_cells.max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Upslope statistics: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Input values file: {}", values_file));
+        if stat == "percentile" {
+            output.add_metadata_entry(format!("Statistic: percentile ({})", percentile));
+            output.add_metadata_entry(format!("Histogram bins: {}", num_bins));
+        } else {
+            output.add_metadata_entry(format!("Statistic: {}", stat));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -350,13 +350,8 @@ impl WhiteboxTool for Watershed {
             // it's a raster
             let pourpts = Raster::new(&pourpts_file, "r")?;
             output.configs.palette = pourpts.configs.palette.clone();
-            // make sure the input files have the same size
-            if pourpts.configs.rows != pntr.configs.rows
-                || pourpts.configs.columns != pntr.configs.columns
-            {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
-            }
+            // make sure the inputs share the same grid and CRS
+            let pourpts = align_raster_input(&pntr, pourpts)?;
 
             for row in 0..rows {
                 for col in 0..columns {
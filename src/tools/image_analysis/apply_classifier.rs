@@ -0,0 +1,397 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::ClassifierModel;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool applies a supervised classification model, created using the `TrainClassifier` tool
+/// (`--model`), to a stack of input rasters (`--inputs`), producing a classified raster (`--output`).
+/// The input rasters must be specified in the same order as they were when the model was trained, and
+/// must each have the same number of rows and columns and spatial extent. Each grid cell in the output
+/// raster is assigned the class value with the highest predicted probability. Grid cells containing
+/// NoData in any of the input rasters are assigned NoData in the output.
+///
+/// If the `--probabilities` flag is specified, an additional raster is created for each class, containing
+/// the predicted probability (0-1) of the class. For a random forest model, this is the mean of the class
+/// probabilities of the individual trees, while for a k-nearest neighbour model, it is the proportion of
+/// the k nearest training samples belonging to the class. The probability rasters are named by appending
+/// '_prob' and the class value to the output file name, e.g. *classes_prob3.tif*. Low maximum class
+/// probabilities are indicative of areas of classification uncertainty.
+///
+/// # See Also
+/// `TrainClassifier`
+pub struct ApplyClassifier {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ApplyClassifier {
+    pub fn new() -> ApplyClassifier {
+        // public constructor
+        let name = "ApplyClassifier".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Applies a trained supervised classification model to a raster stack.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Model File".to_owned(),
+            flags: vec!["--model".to_owned()],
+            description: "Input classification model file created by TrainClassifier.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output classified raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output class probabilities?".to_owned(),
+            flags: vec!["--probabilities".to_owned()],
+            description: "Optional flag indicating whether to output a probability raster for each class.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd='*path*to*data*' -i='band1.tif;band2.tif;band3.tif' --model=model.json -o=classes.tif --probabilities", short_exe, name).replace("*", &sep);
+
+        ApplyClassifier {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ApplyClassifier {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files_str = String::new();
+        let mut model_file = String::new();
+        let mut output_file = String::new();
+        let mut output_probabilities = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                input_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-model" {
+                model_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-probabilities" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    output_probabilities = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !model_file.contains(&sep) && !model_file.contains("/") {
+            model_file = format!("{}{}", working_directory, model_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading model...");
+        }
+        let model = Arc::new(ClassifierModel::read(&model_file)?);
+
+        let mut cmd = input_files_str.split(";");
+        let mut input_files = cmd.collect::<Vec<&str>>();
+        if input_files.len() == 1 {
+            cmd = input_files_str.split(",");
+            input_files = cmd.collect::<Vec<&str>>();
+        }
+        let input_files: Vec<&str> = input_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+        let num_files = input_files.len();
+        if num_files != model.num_features {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The model was trained using {} input rasters but {} were specified.",
+                    model.num_features, num_files
+                ),
+            ));
+        }
+
+        let mut inputs: Vec<Raster> = Vec::with_capacity(num_files);
+        for i in 0..num_files {
+            if verbose {
+                println!("Reading file {} of {}", i + 1, num_files);
+            }
+            let mut input_file = input_files[i].trim().to_owned();
+            if !input_file.contains(&sep) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            let input = Raster::new(&input_file, "r")?;
+            if i == 0 {
+                inputs.push(input);
+            } else {
+                // make sure the inputs share the same grid and CRS
+                let input = align_raster_input(&inputs[0], input)?;
+                inputs.push(input);
+            }
+        }
+        let inputs = Arc::new(inputs);
+
+        let start = Instant::now();
+        let rows = inputs[0].configs.rows as isize;
+        let columns = inputs[0].configs.columns as isize;
+        let num_classes = model.num_classes();
+        let out_nodata = -32768f64;
+
+        let mut output = Raster::initialize_using_file(&output_file, &inputs[0]);
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        output.configs.data_type = if model.class_values.iter().all(|v| v.fract() == 0f64) {
+            DataType::I16
+        } else {
+            DataType::F32
+        };
+
+        let mut probability_rasters = vec![];
+        if output_probabilities {
+            let p = path::Path::new(&output_file);
+            let mut extension = String::from(".");
+            let ext = p.extension().unwrap().to_str().unwrap();
+            extension.push_str(ext);
+            for c in 0..num_classes {
+                let prob_file = output_file.replace(
+                    &extension,
+                    &format!("_prob{}{}", model.class_values[c], extension),
+                );
+                let mut r = Raster::initialize_using_file(&prob_file, &inputs[0]);
+                r.configs.nodata = out_nodata;
+                r.configs.data_type = DataType::F32;
+                r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+                r.configs.palette = "spectrum.plt".to_string();
+                probability_rasters.push(r);
+            }
+        }
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let inputs = inputs.clone();
+            let model = model.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut x = vec![0f64; num_files];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut classes = vec![out_nodata; columns as usize];
+                    let mut probabilities = if output_probabilities {
+                        vec![vec![out_nodata; columns as usize]; num_classes]
+                    } else {
+                        vec![]
+                    };
+                    'col_loop: for col in 0..columns {
+                        for i in 0..num_files {
+                            x[i] = inputs[i].get_value(row, col);
+                            if x[i] == inputs[i].configs.nodata {
+                                continue 'col_loop;
+                            }
+                        }
+                        let p = model.predict_proba(&x);
+                        let mut class_index = 0;
+                        for c in 1..num_classes {
+                            if p[c] > p[class_index] {
+                                class_index = c;
+                            }
+                        }
+                        classes[col as usize] = model.class_values[class_index];
+                        if output_probabilities {
+                            for c in 0..num_classes {
+                                probabilities[c][col as usize] = p[c];
+                            }
+                        }
+                    }
+                    tx.send((row, classes, probabilities)).unwrap();
+                }
+            });
+        }
+
+        for r in 0..rows {
+            let (row, classes, probabilities) =
+                rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, classes);
+            for (c, data) in probabilities.into_iter().enumerate() {
+                probability_rasters[c].set_row_data(row, data);
+            }
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input files: {}", input_files_str));
+        output.add_metadata_entry(format!("Model file: {}", model_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        for (c, mut r) in probability_rasters.into_iter().enumerate() {
+            r.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            r.add_metadata_entry(format!("Model file: {}", model_file));
+            r.add_metadata_entry(format!("Class: {}", model.class_values[c]));
+            let _ = match r.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Class {} probability file written", model.class_values[c])
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,582 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool performs an image-based atmospheric correction of optical multispectral imagery, converting
+/// the image values into an approximation of surface reflectance using either the dark-object subtraction
+/// (DOS; Chavez, 1988) or the cosine of the solar zenith angle (COST; Chavez, 1996) method. Both methods assume
+/// that some grid cells within the scene (e.g. deep water bodies or dense shadows) have a very low surface
+/// reflectance, such that the signal measured over these dark objects is due to atmospheric scattering (path
+/// radiance, or haze). The dark-object value of each band is taken as the lowest value for which at least
+/// `--dark_count` grid cells (default 1000) have lower or equal values, which guards against noise and
+/// defective cells. Assuming that dark objects have a surface reflectance of 1%, the surface reflectance is:
+///
+/// > ρ = (ρ<sub>TOA</sub> - ρ<sub>dark</sub>) / τ + 0.01
+///
+/// where ρ<sub>TOA</sub> is the top-of-atmosphere reflectance, ρ<sub>dark</sub> is the top-of-atmosphere
+/// reflectance of the dark object, and τ is the atmospheric transmittance along the path from the sun to the
+/// surface. The DOS method (`--method=dos`) assumes a transmittance of one, while the COST method
+/// (`--method=cost`) approximates it with the cosine of the solar zenith angle, which partially accounts for
+/// atmospheric absorption and generally gives better estimates in the visible and near-infrared bands. Negative
+/// reflectance values are set to zero. The corrections are approximations, and are no substitute for a physically
+/// based correction where one is available.
+///
+/// The input image (`--input`) may be a single-band or multi-band raster. Landsat Collection 2 Level-1 scenes and
+/// Sentinel-2 Level-1C SAFE products, named by their directories or metadata files, are read as top-of-atmosphere
+/// reflectance, and the sun elevation and band roles are taken from their metadata. Thermal bands of scenes are
+/// copied to the output without correction. For other images of raw digital numbers (DN), the sun elevation
+/// (`--sun_elevation`, in degrees) and the per-band gains (`--gains`) and offsets (`--offsets`) converting DN into
+/// either top-of-atmosphere reflectance or, when the per-band exo-atmospheric solar irradiances (`--esun`, in
+/// W/(m<sup>2</sup> µm)) are given, spectral radiance (W/(m<sup>2</sup> sr µm)) must be specified, as comma-separated
+/// lists with one value per band (or a single value for all bands). Radiance, L, is converted to top-of-atmosphere
+/// reflectance as:
+///
+/// > ρ<sub>TOA</sub> = π L d<sup>2</sup> / (ESUN cos θ<sub>z</sub>)
+///
+/// where d is the Earth-Sun distance, in astronomical units (`--distance`), and θ<sub>z</sub> is the solar zenith
+/// angle. When gains convert DN into reflectance, the reflectance is divided by cos θ<sub>z</sub>, following the
+/// convention of the Landsat metadata. The output is a raster with the same number of bands as the input, and
+/// must therefore be written in the GeoTIFF or Whitebox v2 (.wbr) formats for multi-band inputs.
+///
+/// # References
+/// Chavez, P. S. (1988). An improved dark-object subtraction technique for atmospheric scattering correction of
+/// multispectral data. *Remote Sensing of Environment*, 24(3), 459-479.
+///
+/// Chavez, P. S. (1996). Image-based atmospheric corrections - revisited and improved. *Photogrammetric
+/// Engineering and Remote Sensing*, 62(9), 1025-1036.
+///
+/// # See Also
+/// `SpectralIndex`, `NormalizedDifferenceIndex`
+pub struct AtmosphericCorrection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl AtmosphericCorrection {
+    pub fn new() -> AtmosphericCorrection {
+        // public constructor
+        let name = "AtmosphericCorrection".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Approximates surface reflectance from optical imagery using dark-object subtraction (DOS) or the COST method.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Image".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input image, multi-band image, or satellite scene.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Correction Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Correction method; one of 'dos' and 'cost'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["dos".to_owned(), "cost".to_owned()]),
+            default_value: Some("cost".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Dark Object Cell Count".to_owned(),
+            flags: vec!["--dark_count".to_owned()],
+            description:
+                "Minimum number of grid cells darker than or equal to the dark-object value."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1000".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sun Elevation (degrees)".to_owned(),
+            flags: vec!["--sun_elevation".to_owned()],
+            description:
+                "Sun elevation angle, in degrees; read from the metadata of satellite scenes."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Gains".to_owned(),
+            flags: vec!["--gains".to_owned()],
+            description:
+                "Comma-separated list of the gains converting DN into reflectance or radiance."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Offsets".to_owned(),
+            flags: vec!["--offsets".to_owned()],
+            description:
+                "Comma-separated list of the offsets converting DN into reflectance or radiance."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Solar Irradiances (ESUN)".to_owned(),
+            flags: vec!["--esun".to_owned()],
+            description: "Comma-separated list of the exo-atmospheric solar irradiance of each band, for radiance gains."
+                .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Earth-Sun Distance (AU)".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Earth-Sun distance, in astronomical units, used with radiance gains."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=LC08_L1TP_018030_20210720_20210729_02_T1_MTL.txt -o=surface_reflectance.tif --method=cost
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=surface_reflectance.tif --method=dos --sun_elevation=52.3 --gains=2.0E-05 --offsets=-0.1", short_exe, name).replace("*", &sep);
+
+        AtmosphericCorrection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for AtmosphericCorrection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut use_cost = true;
+        let mut dark_count = 1000usize;
+        let mut sun_elevation = f64::NAN;
+        let mut gains_str = String::new();
+        let mut offsets_str = String::new();
+        let mut esun_str = String::new();
+        let mut distance = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                let method = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                use_cost = !method.to_lowercase().contains("dos");
+            } else if flag_val == "-dark_count" {
+                dark_count = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-sun_elevation" {
+                sun_elevation = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-gains" {
+                gains_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-offsets" {
+                offsets_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-esun" {
+                esun_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if dark_count < 1 {
+            dark_count = 1;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let num_bands = input.configs.bands.max(1);
+        let in_nodata = input.configs.nodata;
+        let is_scene = input.get_metadata_value("Scene type").is_some();
+
+        if is_scene {
+            if let Some(values) = input.get_metadata_value("Values") {
+                if values.starts_with("surface reflectance") {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The input scene is a surface reflectance product and has already been atmospherically corrected.",
+                    ));
+                }
+            }
+        }
+        if sun_elevation.is_nan() {
+            sun_elevation = input
+                .get_metadata_value("Sun elevation")
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(f64::NAN);
+        }
+        if !(sun_elevation > 0f64 && sun_elevation <= 90f64) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A sun elevation between 0 and 90 degrees must be specified (--sun_elevation).",
+            ));
+        }
+        if distance.is_nan() {
+            distance = input
+                .get_metadata_value("Earth-Sun distance")
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1f64);
+        }
+        let cos_zenith = sun_elevation.to_radians().sin();
+
+        // the conversion of image values into top-of-atmosphere reflectance
+        let parse_list = |s: &str, default: f64| -> Result<Vec<f64>, Error> {
+            if s.trim().is_empty() {
+                return Ok(vec![default; num_bands]);
+            }
+            let values = s
+                .split(|c| c == ',' || c == ';')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| {
+                    Error::new(ErrorKind::InvalidInput, format!("Error parsing '{}'.", s))
+                })?;
+            if values.len() == 1 {
+                return Ok(vec![values[0]; num_bands]);
+            }
+            if values.len() != num_bands {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The list '{}' must contain one value, or one value for each of the {} bands.",
+                        s, num_bands
+                    ),
+                ));
+            }
+            Ok(values)
+        };
+        let has_gains = !gains_str.trim().is_empty();
+        if !is_scene && !has_gains {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The gains (--gains) converting the image values into reflectance or radiance must be specified for images that are not satellite scenes.",
+            ));
+        }
+        let gains = parse_list(&gains_str, 1f64)?;
+        let offsets = parse_list(&offsets_str, 0f64)?;
+        let esun = if esun_str.trim().is_empty() {
+            None
+        } else {
+            Some(parse_list(&esun_str, 0f64)?)
+        };
+        let mut scale = vec![1f64; num_bands];
+        let mut shift = vec![0f64; num_bands];
+        if has_gains {
+            for b in 0..num_bands {
+                let factor = match &esun {
+                    Some(esun) => PI * distance * distance / (esun[b] * cos_zenith),
+                    None => 1f64 / cos_zenith,
+                };
+                scale[b] = gains[b] * factor;
+                shift[b] = offsets[b] * factor;
+            }
+        }
+
+        let tau = if use_cost { cos_zenith } else { 1f64 };
+
+        let start = Instant::now();
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        let out_nodata = -32768f64;
+        output.configs.nodata = out_nodata;
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "grey.plt".to_string();
+        if num_bands > 1 {
+            output.set_num_bands(num_bands);
+        }
+
+        let mut dark_values = vec![f64::NAN; num_bands];
+        let num_procs = num_cpus::get() as isize;
+        for band in 0..num_bands {
+            // thermal bands are copied to the output without correction
+            let role = input
+                .get_metadata_value(&format!("Band {} role", band + 1))
+                .unwrap_or_default();
+            let is_thermal = role.starts_with("tir");
+
+            // find the dark-object value
+            let mut dark = 0f64;
+            if !is_thermal {
+                let mut values = Vec::with_capacity(input.num_cells());
+                for row in 0..rows {
+                    for col in 0..columns {
+                        let z = input.get_value_band(row, col, band);
+                        if z != in_nodata {
+                            values.push(z * scale[band] + shift[band]);
+                        }
+                    }
+                }
+                if !values.is_empty() {
+                    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    dark = values[(dark_count - 1).min(values.len() - 1)];
+                    dark_values[band] = dark;
+                }
+            }
+
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let tx = tx.clone();
+                let (scale, shift) = (scale[band], shift[band]);
+                thread::spawn(move || {
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![out_nodata; columns as usize];
+                        for col in 0..columns {
+                            let z = input.get_value_band(row, col, band);
+                            if z != in_nodata {
+                                data[col as usize] = if is_thermal {
+                                    z
+                                } else {
+                                    ((z * scale + shift - dark) / tau + 0.01).max(0f64)
+                                };
+                            }
+                        }
+                        tx.send((row, data)).unwrap();
+                    }
+                });
+            }
+
+            for r in 0..rows {
+                let (row, data) = rx.recv().expect("Error receiving data from thread.");
+                if num_bands > 1 {
+                    output.set_row_data_band(row, band, data);
+                } else {
+                    output.set_row_data(row, data);
+                }
+
+                if verbose {
+                    progress = (100.0_f64 * (band as f64 + r as f64 / (rows - 1).max(1) as f64)
+                        / num_bands as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if is_scene {
+            // carry the descriptive metadata of the scene, e.g. the band roles, through to the output
+            for entry in &input.configs.metadata {
+                if !entry.starts_with("Values:") {
+                    output.add_metadata_entry(entry.clone());
+                }
+            }
+        }
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Values: surface reflectance ({} approximation)",
+            if use_cost { "COST" } else { "DOS" }
+        ));
+        output.add_metadata_entry(format!("Sun elevation used: {}", sun_elevation));
+        for band in 0..num_bands {
+            if !dark_values[band].is_nan() {
+                output.add_metadata_entry(format!(
+                    "Band {} dark-object reflectance: {}",
+                    band + 1,
+                    dark_values[band]
+                ));
+            }
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
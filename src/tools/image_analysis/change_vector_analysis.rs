@@ -2,10 +2,11 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 29/04/2018
-Last Modified: 29/04/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
+use crate::algorithms::otsu_threshold;
 use crate::raster::*;
 use crate::structures::Array2D;
 use crate::tools::*;
@@ -38,12 +39,23 @@ use std::path;
 /// determine 'actual' change (i.e. change above some assumed level of error). The type
 /// of change (qualitatively) is then defined according to the corresponding sector code.
 /// Jensen (2015) provides a useful description of this approach to change detection.
+/// If the optional binary change output (`--out_change`) is specified, the tool will
+/// perform this thresholding, assigning 1 to pixels with a magnitude greater than the
+/// threshold and 0 elsewhere. The user may specify the magnitude threshold (`--threshold`)
+/// directly, or otherwise it will be determined automatically using Otsu's (1979) method,
+/// which selects the value that best separates the magnitude histogram into two classes.
+/// Notice that when a single band is input for each date, the analysis is equivalent to
+/// simple image differencing, with the magnitude being the absolute difference and the
+/// direction indicating whether the band value increased (1) or decreased (0).
 ///
 /// # Reference
 /// Jensen, J. R. (2015). Introductory Digital Image Processing: A Remote Sensing Perspective.
 ///
+/// Otsu, N. (1979). A threshold selection method from gray-level histograms. IEEE Transactions
+/// on Systems, Man, and Cybernetics, 9(1), 62-66.
+///
 /// # See Also
-/// `WriteFunctionMemoryInsertion`
+/// `SelectivePcaChangeDetection`, `WriteFunctionMemoryInsertion`
 pub struct ChangeVectorAnalysis {
     name: String,
     description: String,
@@ -97,6 +109,24 @@ impl ChangeVectorAnalysis {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Output Binary Change File (optional)".to_owned(),
+            flags: vec!["--out_change".to_owned()],
+            description: "Optional output binary change raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Change Magnitude Threshold (optional; Otsu if unspecified)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Magnitude threshold for the binary change output. Determined using Otsu's method if unspecified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -108,7 +138,7 @@ impl ChangeVectorAnalysis {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --date1='d1_band1.tif;d1_band2.tif;d1_band3.tif' --date2='d2_band1.tif;d2_band2.tif;d2_band3.tif' --magnitude=mag_out.tif --direction=dir_out.tif", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --date1='d1_band1.tif;d1_band2.tif;d1_band3.tif' --date2='d2_band1.tif;d2_band2.tif;d2_band3.tif' --magnitude=mag_out.tif --direction=dir_out.tif --out_change=change.tif", short_exe, name).replace("*", &sep);
 
         ChangeVectorAnalysis {
             name: name,
@@ -158,6 +188,8 @@ impl WhiteboxTool for ChangeVectorAnalysis {
         let mut input2_files_str = String::new();
         let mut magnitude_file = String::new();
         let mut direction_file = String::new();
+        let mut change_file = String::new();
+        let mut threshold = f64::NAN;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -199,6 +231,24 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                 } else {
                     args[i + 1].to_string()
                 };
+            } else if flag_val == "-out_change" {
+                change_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             }
         }
 
@@ -221,6 +271,9 @@ impl WhiteboxTool for ChangeVectorAnalysis {
         if !direction_file.contains(&sep) && !direction_file.contains("/") {
             direction_file = format!("{}{}", working_directory, direction_file);
         }
+        if !change_file.is_empty() && !change_file.contains(&sep) && !change_file.contains("/") {
+            change_file = format!("{}{}", working_directory, change_file);
+        }
 
         let mut cmd = input1_files_str.split(";");
         let mut input1_files = cmd.collect::<Vec<&str>>();
@@ -296,20 +349,9 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                     input2_file = format!("{}{}", working_directory, input2_file);
                 }
 
-                let input1 = Raster::new(&input1_file, "r")?;
-                let input2 = Raster::new(&input2_file, "r")?;
-
-                // make sure the images have the right rows and columns
-                if input1.configs.rows as isize != rows
-                    || input1.configs.columns as isize != columns
-                    || input2.configs.rows as isize != rows
-                    || input1.configs.columns as isize != columns
-                {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All of the input files must share the same extent (rows and columns).",
-                    ));
-                }
+                // make sure the inputs share the same grid and CRS
+                let input1 = align_raster_input(&out_magnitude, Raster::new(&input1_file, "r")?)?;
+                let input2 = align_raster_input(&out_magnitude, Raster::new(&input2_file, "r")?)?;
 
                 let nodata1 = input1.configs.nodata;
                 let nodata2 = input2.configs.nodata;
@@ -361,6 +403,44 @@ impl WhiteboxTool for ChangeVectorAnalysis {
             }
         }
 
+        let mut out_change = if !change_file.is_empty() {
+            if threshold.is_nan() {
+                let mut values = Vec::with_capacity((rows * columns) as usize);
+                for row in 0..rows {
+                    for col in 0..columns {
+                        if nodata_detected.get_value(row, col) < 0 {
+                            values.push(out_magnitude.get_value(row, col));
+                        }
+                    }
+                }
+                threshold = otsu_threshold(&values, 1024);
+                if verbose {
+                    println!("Otsu magnitude threshold: {:.4}", threshold);
+                }
+            }
+            let mut out_change = Raster::initialize_using_file(&change_file, &input1);
+            out_change.configs.data_type = DataType::I16;
+            out_change.configs.nodata = -32768f64;
+            out_change.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            out_change.configs.palette = String::from("qual.plt");
+            for row in 0..rows {
+                for col in 0..columns {
+                    if nodata_detected.get_value(row, col) < 0 {
+                        if out_magnitude.get_value(row, col) > threshold {
+                            out_change.set_value(row, col, 1f64);
+                        } else {
+                            out_change.set_value(row, col, 0f64);
+                        }
+                    } else {
+                        out_change.set_value(row, col, -32768f64);
+                    }
+                }
+            }
+            Some(out_change)
+        } else {
+            None
+        };
+
         if verbose {
             println!("Saving data...")
         };
@@ -398,6 +478,24 @@ impl WhiteboxTool for ChangeVectorAnalysis {
             Err(e) => return Err(e),
         };
 
+        if let Some(ref mut out_change) = out_change {
+            out_change.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            out_change.add_metadata_entry(format!("Magnitude threshold: {}", threshold));
+            out_change.add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
+
+            let _ = match out_change.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
         // print out a key for interpreting the direction image
         let mut s = "Key For Interpreting The CVA Direction Image:\n\n\tDirection of Change (+ or -)\nValue".to_string();
         for i in 0..num_files {
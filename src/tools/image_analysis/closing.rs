@@ -27,7 +27,7 @@ use std::thread;
 /// window in both the x and y directions (`--filterx` and `--filtery`).
 ///
 /// # See Also
-/// `Opening`, `TophatTransform`
+/// `Opening`, `TophatTransform`, `MorphologicalFilter`
 pub struct Closing {
     name: String,
     description: String,
@@ -0,0 +1,832 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::satellite_scene::find_band_by_role;
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// The roles of the bands used by the tool; the thermal band is optional.
+const ROLES: [&str; 7] = ["blue", "green", "red", "nir", "swir1", "swir2", "tir1"];
+
+// the classes of the output mask, following the convention of Fmask
+const CLEAR_LAND: f64 = 0f64;
+const WATER: f64 = 1f64;
+const SHADOW: f64 = 2f64;
+const SNOW: f64 = 3f64;
+const CLOUD: f64 = 4f64;
+const FILL: f64 = 255f64;
+
+/// This tool derives a cloud, cloud shadow, snow, and water mask from optical multispectral imagery, such as
+/// Landsat and Sentinel-2 scenes, using a simplified version of the Fmask algorithm (Zhu and Woodcock, 2012).
+/// The output mask uses the class values of Fmask:
+///
+/// | Value | Class |
+/// |-------|-------|
+/// | 0 | Clear land |
+/// | 1 | Water |
+/// | 2 | Cloud shadow |
+/// | 3 | Snow |
+/// | 4 | Cloud |
+/// | 255 | No observation (NoData) |
+///
+/// Potential cloud cells are first identified using a series of spectral tests, i.e. the basic, whiteness, haze
+/// optimized transformation (HOT), and NIR/SWIR1 ratio tests. The probability that each cell is cloud is then
+/// estimated from its spectral variability and, when a thermal band is available, its brightness temperature
+/// relative to the temperatures of the clear-sky land or water cells of the scene. Potential cloud cells with a
+/// probability exceeding the 82.5th percentile of the probabilities of the clear-sky cells plus a margin
+/// (`--cloud_probability`, default 0.2) are classed as cloud. Water cells are identified from their low NDVI and
+/// near-infrared (NIR) reflectance, and snow cells from their high normalized difference snow index (NDSI).
+///
+/// Cloud shadows darken the NIR band. Potential shadow cells are identified as those that are more than 0.02
+/// darker than the NIR band after its local depressions have been filled (i.e. a flood-fill transformation). Of
+/// these, the cells that lie in the shadow of a cloud, i.e. those from which a cloud cell is found in the direction
+/// of the sun at a distance corresponding to a cloud height between `--min_cloud_height` (default 200 m) and
+/// `--max_cloud_height` (default 12000 m), are classed as cloud shadow. This geometric test is a simplification of
+/// the cloud-shadow matching of Fmask, which additionally estimates the height of each cloud object.
+///
+/// Finally, the cloud, shadow, and snow classes are dilated by `--cloud_buffer` (default 3), `--shadow_buffer`
+/// (default 3), and `--snow_buffer` (default 0) grid cells respectively, to capture the thin cloud edges and
+/// penumbrae that the tests miss. Where buffered classes overlap, cloud takes precedence over shadow, and
+/// shadow over snow.
+///
+/// The input image (`--input`) should contain top-of-atmosphere or surface reflectance values, with an optional
+/// thermal band of brightness temperature, in kelvin. Landsat Collection 2 scenes and Sentinel-2 SAFE products,
+/// named by their directories or metadata files, can be used directly, in which case the bands, and the sun
+/// elevation and azimuth required by the shadow test, are identified from the scene's metadata. Sentinel-2 does
+/// not have a thermal band, and its cloud probabilities are based on spectral variability alone. For other
+/// multi-band images, the band mapping (`--bands`) lists the (one-based) band numbers of the blue, green, red,
+/// nir, swir1, swir2, and optionally tir1 bands, e.g. `--bands="blue=2;green=3;red=4;nir=5;swir1=6;swir2=7"`, and
+/// the `--scale` parameter converts scaled integer values into reflectance. The sun elevation and azimuth, in
+/// degrees, must then be specified with `--sun_elevation` and `--sun_azimuth`.
+///
+/// The mask can be used to remove cloud-contaminated observations from time series. Within the text file listing
+/// the rasters of a time series (see `TimeSeriesGapFill`), a mask file may follow the date of each raster, in which
+/// case the cells of the raster that are cloud, cloud shadow, or unobserved in the mask are treated as missing.
+///
+/// # Reference
+/// Zhu, Z., & Woodcock, C. E. (2012). Object-based cloud and cloud shadow detection in Landsat imagery. *Remote
+/// Sensing of Environment*, 118, 83-94.
+///
+/// # See Also
+/// `AtmosphericCorrection`, `TimeSeriesGapFill`, `SpectralIndex`
+pub struct CloudMask {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CloudMask {
+    pub fn new() -> CloudMask {
+        // public constructor
+        let name = "CloudMask".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Derives cloud, cloud shadow, snow, and water masks from multispectral imagery using an Fmask-style algorithm.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Image".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input multi-band image or satellite scene.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Mapping".to_owned(),
+            flags: vec!["--bands".to_owned()],
+            description: "Semicolon-separated list of role=number pairs for the blue, green, red, nir, swir1, swir2, and optional tir1 bands; optional for satellite scenes.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output mask raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reflectance Scale Factor".to_owned(),
+            flags: vec!["--scale".to_owned()],
+            description: "Factor converting the image values into reflectance (e.g. 0.0001)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sun Elevation (degrees)".to_owned(),
+            flags: vec!["--sun_elevation".to_owned()],
+            description:
+                "Sun elevation angle, in degrees; read from the metadata of satellite scenes."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sun Azimuth (degrees)".to_owned(),
+            flags: vec!["--sun_azimuth".to_owned()],
+            description: "Sun azimuth angle, in degrees clockwise from north; read from the metadata of satellite scenes."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cloud Probability Margin".to_owned(),
+            flags: vec!["--cloud_probability".to_owned()],
+            description:
+                "Margin added to the clear-sky cloud probability to give the cloud threshold."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Cloud Height (m)".to_owned(),
+            flags: vec!["--min_cloud_height".to_owned()],
+            description: "Minimum height of the clouds casting shadows, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("200.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Cloud Height (m)".to_owned(),
+            flags: vec!["--max_cloud_height".to_owned()],
+            description: "Maximum height of the clouds casting shadows, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("12000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cloud Buffer (cells)".to_owned(),
+            flags: vec!["--cloud_buffer".to_owned()],
+            description: "Number of grid cells by which the cloud class is dilated.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Shadow Buffer (cells)".to_owned(),
+            flags: vec!["--shadow_buffer".to_owned()],
+            description: "Number of grid cells by which the cloud shadow class is dilated."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Snow Buffer (cells)".to_owned(),
+            flags: vec!["--snow_buffer".to_owned()],
+            description: "Number of grid cells by which the snow class is dilated.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=LC08_L2SP_018030_20210720_20210729_02_T1_MTL.txt -o=qa_mask.tif --cloud_buffer=5
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=stack.tif --bands=\"blue=1;green=2;red=3;nir=4;swir1=5;swir2=6\" --scale=0.0001 --sun_elevation=48.2 --sun_azimuth=141.7 -o=qa_mask.tif", short_exe, name).replace("*", &sep);
+
+        CloudMask {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CloudMask {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut band_mapping = String::new();
+        let mut output_file = String::new();
+        let mut scale = 1f64;
+        let mut sun_elevation = f64::NAN;
+        let mut sun_azimuth = f64::NAN;
+        let mut prob_margin = 0.2f64;
+        let mut min_height = 200f64;
+        let mut max_height = 12000f64;
+        let mut cloud_buffer = 3isize;
+        let mut shadow_buffer = 3isize;
+        let mut snow_buffer = 0isize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let parse_f64 = |i: usize| -> f64 {
+                if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                }
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-bands" {
+                // the band mapping itself contains equals signs
+                band_mapping = if keyval {
+                    arg.splitn(2, '=').nth(1).unwrap_or("").to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-scale" {
+                scale = parse_f64(i);
+            } else if flag_val == "-sun_elevation" {
+                sun_elevation = parse_f64(i);
+            } else if flag_val == "-sun_azimuth" {
+                sun_azimuth = parse_f64(i);
+            } else if flag_val == "-cloud_probability" {
+                prob_margin = parse_f64(i);
+            } else if flag_val == "-min_cloud_height" {
+                min_height = parse_f64(i);
+            } else if flag_val == "-max_cloud_height" {
+                max_height = parse_f64(i);
+            } else if flag_val == "-cloud_buffer" {
+                cloud_buffer = parse_f64(i) as isize;
+            } else if flag_val == "-shadow_buffer" {
+                shadow_buffer = parse_f64(i) as isize;
+            } else if flag_val == "-snow_buffer" {
+                snow_buffer = parse_f64(i) as isize;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let num_cells = input.num_cells();
+
+        // identify the bands
+        let mut band_nums: Vec<Option<usize>> = vec![None; ROLES.len()];
+        for entry in band_mapping.split(|c| c == ';' || c == ',') {
+            if entry.trim().is_empty() {
+                continue;
+            }
+            let mut parts = entry.splitn(2, '=');
+            let role = parts.next().unwrap_or("").trim().to_lowercase();
+            let value = parts.next().unwrap_or("").trim();
+            let j = ROLES.iter().position(|r| *r == role).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unrecognized band role '{}' in the band mapping.", role),
+                )
+            })?;
+            match value.parse::<usize>() {
+                Ok(b) if b >= 1 && b <= input.num_bands() => band_nums[j] = Some(b - 1),
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The {} band '{}' is not a band number of the input image.",
+                            role, value
+                        ),
+                    ))
+                }
+            }
+        }
+        for j in 0..ROLES.len() {
+            if band_nums[j].is_none() {
+                band_nums[j] = find_band_by_role(&input, ROLES[j]);
+            }
+            if band_nums[j].is_none() && ROLES[j] != "tir1" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The {} band could not be identified; specify it with the band mapping (--bands).",
+                        ROLES[j]
+                    ),
+                ));
+            }
+        }
+        // scenes are already in units of reflectance
+        if input.get_metadata_value("Scene type").is_some() {
+            scale = 1f64;
+        }
+        if sun_elevation.is_nan() {
+            sun_elevation = input
+                .get_metadata_value("Sun elevation")
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(f64::NAN);
+        }
+        if sun_azimuth.is_nan() {
+            sun_azimuth = input
+                .get_metadata_value("Sun azimuth")
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(f64::NAN);
+        }
+        if !(sun_elevation > 0f64 && sun_elevation <= 90f64) || !sun_azimuth.is_finite() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The sun elevation (--sun_elevation) and azimuth (--sun_azimuth), in degrees, are required to detect cloud shadows.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // read the bands, with missing values as NaN
+        let read_band = |band: usize, factor: f64| -> Vec<f64> {
+            let mut values = vec![f64::NAN; num_cells];
+            for row in 0..rows {
+                for col in 0..columns {
+                    let z = input.get_value_band(row, col, band);
+                    if z != nodata {
+                        values[(row * columns + col) as usize] = z * factor;
+                    }
+                }
+            }
+            values
+        };
+        let blue = read_band(band_nums[0].unwrap(), scale);
+        let green = read_band(band_nums[1].unwrap(), scale);
+        let red = read_band(band_nums[2].unwrap(), scale);
+        let nir = read_band(band_nums[3].unwrap(), scale);
+        let swir1 = read_band(band_nums[4].unwrap(), scale);
+        let swir2 = read_band(band_nums[5].unwrap(), scale);
+        let thermal = band_nums[6].map(|b| read_band(b, 1f64));
+        let has_thermal = thermal.is_some();
+        let bt = |i: usize| -> f64 {
+            match &thermal {
+                Some(t) => t[i],
+                None => 0f64,
+            }
+        };
+
+        if verbose {
+            println!("Identifying potential clouds...");
+        }
+        let mut valid = vec![false; num_cells];
+        let mut pcp = vec![false; num_cells];
+        let mut water = vec![false; num_cells];
+        let mut snow = vec![false; num_cells];
+        let mut variability = vec![0f64; num_cells];
+        for i in 0..num_cells {
+            if !(blue[i].is_finite()
+                && green[i].is_finite()
+                && red[i].is_finite()
+                && nir[i].is_finite()
+                && swir1[i].is_finite()
+                && swir2[i].is_finite()
+                && bt(i).is_finite())
+            {
+                continue;
+            }
+            valid[i] = true;
+            let ndvi = normalized_difference(nir[i], red[i]);
+            let ndsi = normalized_difference(green[i], swir1[i]);
+            let mean_vis = (blue[i] + green[i] + red[i]) / 3f64;
+            let whiteness = if mean_vis > 0f64 {
+                ((blue[i] - mean_vis).abs()
+                    + (green[i] - mean_vis).abs()
+                    + (red[i] - mean_vis).abs())
+                    / mean_vis
+            } else {
+                f64::INFINITY
+            };
+            let basic =
+                swir2[i] > 0.03 && ndsi < 0.8 && ndvi < 0.8 && (!has_thermal || bt(i) < 300.15);
+            let hot = blue[i] - 0.5 * red[i] - 0.08 > 0f64;
+            let ratio = swir1[i] > 0f64 && nir[i] / swir1[i] > 0.75;
+            pcp[i] = basic && whiteness < 0.7 && hot && ratio;
+            water[i] =
+                (ndvi < 0.01 && nir[i] < 0.11) || (ndvi < 0.1 && ndvi > 0f64 && nir[i] < 0.05);
+            snow[i] =
+                ndsi > 0.15 && nir[i] > 0.11 && green[i] > 0.1 && (!has_thermal || bt(i) < 283.0);
+            variability[i] = 1f64 - ndvi.abs().max(ndsi.abs()).max(whiteness.min(1f64));
+        }
+
+        // the cloud probabilities, relative to the clear-sky cells
+        let num_valid = valid.iter().filter(|v| **v).count();
+        let clear_land: Vec<usize> = (0..num_cells)
+            .filter(|&i| valid[i] && !pcp[i] && !water[i])
+            .collect();
+        let clear_water: Vec<usize> = (0..num_cells)
+            .filter(|&i| valid[i] && !pcp[i] && water[i])
+            .collect();
+        let mut cloud = vec![false; num_cells];
+        if clear_land.len() <= (num_valid / 1000).max(1) {
+            // the scene is almost entirely cloud covered
+            for i in 0..num_cells {
+                cloud[i] = pcp[i];
+            }
+        } else {
+            let (t_low, t_high) = if has_thermal {
+                let temps: Vec<f64> = clear_land.iter().map(|&i| bt(i)).collect();
+                (percentile(temps.clone(), 17.5), percentile(temps, 82.5))
+            } else {
+                (0f64, 0f64)
+            };
+            let t_water = if has_thermal && !clear_water.is_empty() {
+                percentile(clear_water.iter().map(|&i| bt(i)).collect(), 82.5)
+            } else {
+                0f64
+            };
+            let land_prob = |i: usize| -> f64 {
+                if has_thermal {
+                    let temp_prob = ((t_high + 4f64 - bt(i)) / (t_high - t_low + 8f64)).max(0f64);
+                    temp_prob * variability[i]
+                } else {
+                    variability[i]
+                }
+            };
+            let water_prob = |i: usize| -> f64 {
+                let brightness = swir1[i].min(0.11).max(0f64) / 0.11;
+                if has_thermal {
+                    ((t_water - bt(i)) / 4f64).max(0f64) * brightness
+                } else {
+                    brightness
+                }
+            };
+            let land_threshold =
+                percentile(clear_land.iter().map(|&i| land_prob(i)).collect(), 82.5) + prob_margin;
+            for i in 0..num_cells {
+                if !valid[i] {
+                    continue;
+                }
+                let lp = land_prob(i);
+                cloud[i] = (pcp[i]
+                    && ((water[i] && water_prob(i) > 0.5) || (!water[i] && lp > land_threshold)))
+                    || (!water[i] && lp > 0.99)
+                    || (has_thermal && bt(i) < t_low - 35f64);
+            }
+        }
+
+        if verbose {
+            println!("Identifying cloud shadows...");
+        }
+        // potential shadows are darker than the flood-filled NIR band
+        let mut filled = nir.clone();
+        let mut visited = vec![false; num_cells];
+        let mut queue = BinaryHeap::with_capacity(num_cells);
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        for row in 0..rows {
+            for col in 0..columns {
+                let i = (row * columns + col) as usize;
+                if !valid[i] {
+                    continue;
+                }
+                let is_edge = (0..8).any(|k| {
+                    let (r, c) = (row + dy[k], col + dx[k]);
+                    r < 0
+                        || c < 0
+                        || r >= rows
+                        || c >= columns
+                        || !valid[(r * columns + c) as usize]
+                });
+                if is_edge {
+                    visited[i] = true;
+                    queue.push(GridCell {
+                        row: row,
+                        column: col,
+                        priority: filled[i],
+                    });
+                }
+            }
+        }
+        while let Some(cell) = queue.pop() {
+            let z = cell.priority;
+            for k in 0..8 {
+                let (r, c) = (cell.row + dy[k], cell.column + dx[k]);
+                if r < 0 || c < 0 || r >= rows || c >= columns {
+                    continue;
+                }
+                let j = (r * columns + c) as usize;
+                if visited[j] || !valid[j] {
+                    continue;
+                }
+                visited[j] = true;
+                if filled[j] < z {
+                    filled[j] = z;
+                }
+                queue.push(GridCell {
+                    row: r,
+                    column: c,
+                    priority: filled[j],
+                });
+            }
+        }
+        let potential_shadow: Vec<bool> = (0..num_cells)
+            .map(|i| valid[i] && !cloud[i] && filled[i] - nir[i] > 0.02)
+            .collect();
+
+        // shadows lie away from the sun, at a distance determined by the cloud height
+        let cloud = Arc::new(cloud);
+        let potential_shadow = Arc::new(potential_shadow);
+        let tan_zenith = (90f64 - sun_elevation).to_radians().tan();
+        let (sin_az, cos_az) = sun_azimuth.to_radians().sin_cos();
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+        let step = res_x.min(res_y);
+        let min_dist = min_height * tan_zenith;
+        let max_dist = max_height * tan_zenith;
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cloud = cloud.clone();
+            let potential_shadow = potential_shadow.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![false; columns as usize];
+                    for col in 0..columns {
+                        if !potential_shadow[(row * columns + col) as usize] {
+                            continue;
+                        }
+                        let mut dist = min_dist;
+                        while dist <= max_dist {
+                            let r = row - (dist * cos_az / res_y).round() as isize;
+                            let c = col + (dist * sin_az / res_x).round() as isize;
+                            if r < 0 || c < 0 || r >= rows || c >= columns {
+                                break;
+                            }
+                            if cloud[(r * columns + c) as usize] {
+                                data[col as usize] = true;
+                                break;
+                            }
+                            dist += step;
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+        let mut shadow = vec![false; num_cells];
+        for _ in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for col in 0..columns as usize {
+                shadow[row as usize * columns as usize + col] = data[col];
+            }
+        }
+
+        if verbose {
+            println!("Buffering masks...");
+        }
+        let cloud = dilate(&cloud, rows, columns, cloud_buffer);
+        let shadow = dilate(&shadow, rows, columns, shadow_buffer);
+        let snow = dilate(&snow, rows, columns, snow_buffer);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.nodata = FILL;
+        output.configs.data_type = DataType::U8;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        let (mut num_cloud, mut num_shadow, mut num_snow) = (0usize, 0usize, 0usize);
+        for row in 0..rows {
+            let mut data = vec![FILL; columns as usize];
+            for col in 0..columns {
+                let i = (row * columns + col) as usize;
+                if !valid[i] {
+                    continue;
+                }
+                data[col as usize] = if cloud[i] {
+                    num_cloud += 1;
+                    CLOUD
+                } else if shadow[i] {
+                    num_shadow += 1;
+                    SHADOW
+                } else if snow[i] {
+                    num_snow += 1;
+                    SNOW
+                } else if water[i] {
+                    WATER
+                } else {
+                    CLEAR_LAND
+                };
+            }
+            output.set_row_data(row, data);
+        }
+
+        let pct = |count: usize| 100f64 * count as f64 / num_valid.max(1) as f64;
+        if verbose {
+            println!("Cloud cover: {:.2}%", pct(num_cloud));
+            println!("Cloud shadow cover: {:.2}%", pct(num_shadow));
+            println!("Snow cover: {:.2}%", pct(num_snow));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(
+            "Classes: 0 = clear land, 1 = water, 2 = cloud shadow, 3 = snow, 4 = cloud".to_string(),
+        );
+        output.add_metadata_entry(format!("Thermal band used: {}", has_thermal));
+        output.add_metadata_entry(format!("Cloud cover (%): {:.2}", pct(num_cloud)));
+        output.add_metadata_entry(format!("Cloud shadow cover (%): {:.2}", pct(num_shadow)));
+        output.add_metadata_entry(format!("Snow cover (%): {:.2}", pct(num_snow)));
+        if let Some(date) = input.get_metadata_value("Acquisition date") {
+            output.add_metadata_entry(format!("Acquisition date: {}", date));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn normalized_difference(a: f64, b: f64) -> f64 {
+    if a + b != 0f64 {
+        (a - b) / (a + b)
+    } else {
+        0f64
+    }
+}
+
+/// Returns the p-th percentile of a set of values.
+fn percentile(mut values: Vec<f64>, p: f64) -> f64 {
+    if values.is_empty() {
+        return 0f64;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let idx = ((p / 100f64) * (values.len() - 1) as f64).round() as usize;
+    values[idx.min(values.len() - 1)]
+}
+
+/// Dilates a mask by a circular neighbourhood with a radius of `radius` grid cells.
+fn dilate(mask: &[bool], rows: isize, columns: isize, radius: isize) -> Vec<bool> {
+    if radius < 1 {
+        return mask.to_vec();
+    }
+    let offsets: Vec<(isize, isize)> = (-radius..=radius)
+        .flat_map(|dy| (-radius..=radius).map(move |dx| (dy, dx)))
+        .filter(|(dy, dx)| dy * dy + dx * dx <= radius * radius)
+        .collect();
+    let mut output = mask.to_vec();
+    for row in 0..rows {
+        for col in 0..columns {
+            if !mask[(row * columns + col) as usize] {
+                continue;
+            }
+            for (dy, dx) in &offsets {
+                let (r, c) = (row + dy, col + dx);
+                if r >= 0 && c >= 0 && r < rows && c < columns {
+                    output[(r * columns + c) as usize] = true;
+                }
+            }
+        }
+    }
+    output
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
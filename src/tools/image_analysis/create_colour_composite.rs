@@ -283,11 +283,18 @@ impl WhiteboxTool for CreateColourComposite {
         if verbose {
             println!("Reading green band data...")
         };
-        let input_g = Arc::new(Raster::new(&input2_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let input_g = Arc::new(align_raster_input(
+            &input_r,
+            Raster::new(&input2_file, "r")?,
+        )?);
         if verbose {
             println!("Reading blue band data...")
         };
-        let input_b = Arc::new(Raster::new(&input3_file, "r")?);
+        let input_b = Arc::new(align_raster_input(
+            &input_r,
+            Raster::new(&input3_file, "r")?,
+        )?);
 
         let rows = input_r.configs.rows as isize;
         let columns = input_r.configs.columns as isize;
@@ -312,15 +319,9 @@ impl WhiteboxTool for CreateColourComposite {
                 if verbose {
                     println!("Reading opacity data...")
                 };
-                let opacity = Raster::new(&input4_file, "r")?;
+                let opacity = align_raster_input(&input_r, Raster::new(&input4_file, "r")?)?;
                 a_min = opacity.configs.display_min;
                 a_range = opacity.configs.display_max - a_min;
-                if input_r.configs.rows != opacity.configs.rows
-                    || input_r.configs.columns != opacity.configs.columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                          "The input files must have the same number of rows and columns and spatial extent."));
-                }
                 Arc::new(opacity.get_data_as_array2d())
             }
             false => {
@@ -333,24 +334,6 @@ impl WhiteboxTool for CreateColourComposite {
 
         let start = Instant::now();
 
-        // make sure the input files have the same size
-        if input_r.configs.rows != input_g.configs.rows
-            || input_r.configs.columns != input_g.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if input_r.configs.rows != input_b.configs.rows
-            || input_r.configs.columns != input_b.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
@@ -0,0 +1,119 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::morphology::{morphology_tool_parameters, run_morphology, MorphOperation};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool performs a mathematical morphology [dilation](https://en.wikipedia.org/wiki/Dilation_(morphology)) of
+/// an input image (`--input`). In a grey-scale dilation, each grid cell is assigned the maximum value of the cells
+/// that are covered by the reflected structuring element centred on it. Dilation expands bright features and
+/// shrinks dark features. When the `--binary` flag is specified, positive input values are treated as foreground
+/// (1) and all other values as background (0), and the dilation adds the background cells that neighbour the
+/// foreground.
+///
+/// The structuring element is specified by its shape (`--shape`), either a `disk` or a `square`, and its size
+/// (`--size`), i.e. the disk's diameter or the square's width, in grid cells. Alternatively, an arbitrary
+/// structuring element can be read from a kernel file (`--kernel`), a text file with one row of comma- or
+/// space-separated values per line, with an odd number of rows and columns, in which the non-zero values are
+/// the members of the element and the centre of the kernel is its origin (i.e. the same format as the kernel
+/// files of the `UserDefinedWeightsFilter` tool).
+///
+/// NoData cells are ignored within the structuring element and remain NoData in the output.
+///
+/// # See Also
+/// `Erosion`, `MorphologicalFilter`, `MaximumFilter`, `Opening`, `Closing`
+pub struct Dilation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Dilation {
+    pub fn new() -> Dilation {
+        // public constructor
+        let name = "Dilation".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Performs a morphological dilation of an image using a disk, square, or custom structuring element.".to_string();
+
+        let parameters = morphology_tool_parameters(Some(MorphOperation::Dilate));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --shape=square --size=3 --binary",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        Dilation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Dilation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_morphology(
+            &self.get_tool_name(),
+            Some(MorphOperation::Dilate),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
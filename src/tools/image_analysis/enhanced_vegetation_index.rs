@@ -0,0 +1,124 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the enhanced vegetation index (EVI) of Huete et al. (2002):
+///
+/// > EVI = 2.5 (NIR - Red) / (NIR + 6 Red - 7.5 Blue + 1)
+///
+/// The EVI uses the blue band to correct the red band for aerosol scattering and includes a canopy background
+/// adjustment, making it less prone to saturation than the NDVI over dense vegetation and less sensitive to
+/// atmospheric conditions. Unlike simple normalized differences, the EVI requires surface reflectance values in
+/// the range 0-1. Images storing scaled reflectance must therefore be converted during the calculation using the
+/// `--scale` and `--offset` parameters (e.g. `--scale=0.0001` for Sentinel-2 Level-2A imagery).
+///
+/// The NIR, red and blue bands are specified with the band mapping parameter (`--bands`), either as single-band
+/// raster files, e.g. `--bands="nir=B08.tif;red=B04.tif;blue=B02.tif"`, or as the (one-based) numbers of bands
+/// within a multi-band image (`--input`), e.g. `--bands="nir=5;red=4;blue=2"`. Grid cells that are NoData in any
+/// band, or for which the denominator is zero, are assigned NoData in the output.
+///
+/// # Reference
+/// Huete, A., Didan, K., Miura, T., Rodriguez, E. P., Gao, X., & Ferreira, L. G. (2002). Overview of the
+/// radiometric and biophysical performance of the MODIS vegetation indices. *Remote Sensing of Environment*,
+/// 83(1-2), 195-213.
+///
+/// # See Also
+/// `SoilAdjustedVegetationIndex`, `NormalizedDifferenceIndex`, `SpectralIndex`
+pub struct EnhancedVegetationIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl EnhancedVegetationIndex {
+    pub fn new() -> EnhancedVegetationIndex {
+        // public constructor
+        let name = "EnhancedVegetationIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the enhanced vegetation index (EVI) from near-infrared, red, and blue image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("evi"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --bands=\"nir=B08.tif;red=B04.tif;blue=B02.tif\" --scale=0.0001 -o=evi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        EnhancedVegetationIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for EnhancedVegetationIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("evi"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
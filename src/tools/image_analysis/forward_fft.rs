@@ -0,0 +1,354 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{fft2d, fft_pad_image};
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the two-dimensional discrete Fourier transform of an input image (`--input`)
+/// using the fast Fourier transform (FFT) algorithm. The FFT requires that the image dimensions are powers
+/// of two, and so the image is first padded to the next power of two in each dimension by reflecting the
+/// image across its bottom and right edges, which reduces the spectral artifacts caused by the image edges.
+/// **NoData** cells are replaced by the image mean prior to the transform.
+///
+/// The main output (`--output`) is the log-amplitude spectrum, i.e. ln(1 + |F|), which is a useful means
+/// of visualizing the frequency content of an image and of identifying periodic noise, such as the striping
+/// and banding that is common in scanned aerial photographs and some satellite imagery. Periodic noise
+/// appears as isolated bright spots, or as bright lines in the case of striping, within the spectrum. The
+/// spectrum is centred, i.e. the zero-frequency (DC) component is located at the centre of the output image,
+/// with frequency increasing away from the centre. The output spectrum shares the north-west corner and cell
+/// size of the input image, but has the padded dimensions.
+///
+/// The real (`--out_real`) and imaginary (`--out_imag`) components of the transform may also be output,
+/// in the same centred arrangement. These may be modified (e.g. using `RasterCalculator`) and transformed
+/// back into the spatial domain using the `InverseFft` tool.
+///
+/// # See Also
+/// `InverseFft`, `FrequencyDomainFilter`, `RemovePeriodicNoise`
+pub struct ForwardFft {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ForwardFft {
+    pub fn new() -> ForwardFft {
+        // public constructor
+        let name = "ForwardFft".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the Fourier transform of an image using the fast Fourier transform."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Log-Amplitude Spectrum File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output log-amplitude spectrum raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Real Component File (optional)".to_owned(),
+            flags: vec!["--out_real".to_owned()],
+            description: "Optional output real component raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Imaginary Component File (optional)".to_owned(),
+            flags: vec!["--out_imag".to_owned()],
+            description: "Optional output imaginary component raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=image.tif -o=spectrum.tif --out_real=real.tif --out_imag=imag.tif", short_exe, name).replace("*", &sep);
+
+        ForwardFft {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ForwardFft {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut real_file = String::new();
+        let mut imag_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_real" {
+                real_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_imag" {
+                imag_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !real_file.is_empty() && !real_file.contains(&sep) && !real_file.contains("/") {
+            real_file = format!("{}{}", working_directory, real_file);
+        }
+        if !imag_file.is_empty() && !imag_file.contains(&sep) && !imag_file.contains("/") {
+            imag_file = format!("{}{}", working_directory, imag_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows;
+        let columns = input.configs.columns;
+        let nodata = input.configs.nodata;
+
+        // replace NoData cells with the image mean
+        let (mut sum, mut n) = (0f64, 0f64);
+        let mut data = vec![0f64; rows * columns];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row as isize, col as isize);
+                if z != nodata {
+                    data[row * columns + col] = z;
+                    sum += z;
+                    n += 1f64;
+                }
+            }
+        }
+        if n == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image does not contain any valid data.",
+            ));
+        }
+        let mean = sum / n;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row as isize, col as isize) == nodata {
+                    data[row * columns + col] = mean;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Performing transform...")
+        };
+        let (mut re, padded_rows, padded_columns) = fft_pad_image(&data, rows, columns);
+        let mut im = vec![0f64; padded_rows * padded_columns];
+        fft2d(&mut re, &mut im, padded_rows, padded_columns, false);
+
+        let mut configs = input.configs.clone();
+        configs.rows = padded_rows;
+        configs.columns = padded_columns;
+        configs.south = configs.north - padded_rows as f64 * configs.resolution_y;
+        configs.east = configs.west + padded_columns as f64 * configs.resolution_x;
+        configs.nodata = -32768f64;
+        configs.data_type = DataType::F64;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        output.configs.data_type = DataType::F32;
+        output.configs.palette = "grey.plt".to_string();
+        let mut out_real = if !real_file.is_empty() {
+            Some(Raster::initialize_using_config(&real_file, &configs))
+        } else {
+            None
+        };
+        let mut out_imag = if !imag_file.is_empty() {
+            Some(Raster::initialize_using_config(&imag_file, &configs))
+        } else {
+            None
+        };
+
+        // centre the spectrum on the zero-frequency component
+        let (half_rows, half_columns) = (padded_rows / 2, padded_columns / 2);
+        for row in 0..padded_rows {
+            let src_row = (row + half_rows) % padded_rows;
+            for col in 0..padded_columns {
+                let src_col = (col + half_columns) % padded_columns;
+                let idx = src_row * padded_columns + src_col;
+                let amplitude = (re[idx] * re[idx] + im[idx] * im[idx]).sqrt();
+                output.set_value(row as isize, col as isize, amplitude.ln_1p());
+                if let Some(ref mut r) = out_real {
+                    r.set_value(row as isize, col as isize, re[idx]);
+                }
+                if let Some(ref mut r) = out_imag {
+                    r.set_value(row as isize, col as isize, im[idx]);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Original rows: {}", rows));
+        output.add_metadata_entry(format!("Original columns: {}", columns));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        for out in vec![out_real, out_imag] {
+            if let Some(mut out) = out {
+                out.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                out.add_metadata_entry(format!("Input file: {}", input_file));
+                out.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+                let _ = match out.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Output file written")
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{fft2d, fft_frequency, fft_pad_image};
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool performs a low-pass, high-pass, band-pass, or band-stop filtering operation (`--filter`)
+/// on an input image (`--input`) in the frequency domain. The image is transformed using the fast Fourier
+/// transform (FFT), the spectrum is multiplied by the transfer function of a Butterworth filter, and the
+/// result is transformed back into the spatial domain. Unlike the ideal filter, which has a sharp cutoff
+/// and introduces ringing artifacts into the filtered image, the Butterworth filter has a smooth transition
+/// between the pass and stop bands, the steepness of which is controlled by the filter order (`--order`).
+/// The low-pass Butterworth transfer function is:
+///
+/// > H(f) = 1 / (1 + (f / f<sub>c</sub>)<sup>2n</sup>)
+///
+/// where *f* is the radial frequency, *f*<sub>c</sub> is the cutoff frequency (`--cutoff`), and *n* is the
+/// filter order. The high-pass filter is 1 - H(f). The band-pass filter passes frequencies between the
+/// lower cutoff (`--cutoff`) and upper cutoff (`--cutoff2`) frequencies, and the band-stop filter removes
+/// them. Frequencies are measured in cycles per cell and range from 0 to 0.5 (the Nyquist frequency), e.g.
+/// a cutoff of 0.1 corresponds to a wavelength of 10 cells. Notice that high-pass and band-pass filtering
+/// removes the zero-frequency component, such that the output image values will be centred on zero.
+///
+/// The image is padded to dimensions that are powers of two by reflection prior to the transform, and
+/// **NoData** cells are replaced by the image mean; **NoData** cells are preserved in the output image.
+///
+/// # See Also
+/// `ForwardFft`, `InverseFft`, `RemovePeriodicNoise`, `GaussianFilter`, `HighPassFilter`
+pub struct FrequencyDomainFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FrequencyDomainFilter {
+    pub fn new() -> FrequencyDomainFilter {
+        // public constructor
+        let name = "FrequencyDomainFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description =
+            "Performs a Butterworth low-pass, high-pass, band-pass, or band-stop filter in the frequency domain.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Type".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Filter type; options include 'lowpass', 'highpass', 'bandpass', and 'bandstop'"
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "lowpass".to_owned(),
+                "highpass".to_owned(),
+                "bandpass".to_owned(),
+                "bandstop".to_owned(),
+            ]),
+            default_value: Some("lowpass".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cutoff Frequency (cycles per cell)".to_owned(),
+            flags: vec!["--cutoff".to_owned()],
+            description: "Cutoff frequency, or lower cutoff for band filters, in cycles per cell (0-0.5)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Upper Cutoff Frequency (cycles per cell)".to_owned(),
+            flags: vec!["--cutoff2".to_owned()],
+            description: "Upper cutoff frequency for band filters, in cycles per cell (0-0.5)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.25".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Order".to_owned(),
+            flags: vec!["--order".to_owned()],
+            description: "Butterworth filter order.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --filter=bandpass --cutoff=0.05 --cutoff2=0.2 --order=3", short_exe, name).replace("*", &sep);
+
+        FrequencyDomainFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FrequencyDomainFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut filter_type = String::from("lowpass");
+        let mut cutoff = 0.1f64;
+        let mut cutoff2 = 0.25f64;
+        let mut order = 2i32;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-filter" {
+                filter_type = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                filter_type = if filter_type.contains("low") {
+                    String::from("lowpass")
+                } else if filter_type.contains("high") {
+                    String::from("highpass")
+                } else if filter_type.contains("stop") || filter_type.contains("reject") {
+                    String::from("bandstop")
+                } else {
+                    String::from("bandpass")
+                };
+            } else if flag_val == "-cutoff" {
+                cutoff = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-cutoff2" {
+                cutoff2 = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-order" {
+                order = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as i32
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as i32
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if cutoff <= 0f64 || cutoff > 0.5f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The cutoff frequency must be greater than 0 and less than or equal to 0.5.",
+            ));
+        }
+        if (filter_type == "bandpass" || filter_type == "bandstop")
+            && (cutoff2 <= cutoff || cutoff2 > 0.5f64)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The upper cutoff frequency must be greater than the lower cutoff and less than or equal to 0.5.",
+            ));
+        }
+        if order < 1 {
+            order = 1;
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows;
+        let columns = input.configs.columns;
+        let nodata = input.configs.nodata;
+
+        // replace NoData cells with the image mean
+        let (mut sum, mut n) = (0f64, 0f64);
+        let mut data = vec![0f64; rows * columns];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row as isize, col as isize);
+                if z != nodata {
+                    data[row * columns + col] = z;
+                    sum += z;
+                    n += 1f64;
+                }
+            }
+        }
+        if n == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image does not contain any valid data.",
+            ));
+        }
+        let mean = sum / n;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row as isize, col as isize) == nodata {
+                    data[row * columns + col] = mean;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Performing forward transform...")
+        };
+        let (mut re, padded_rows, padded_columns) = fft_pad_image(&data, rows, columns);
+        let mut im = vec![0f64; padded_rows * padded_columns];
+        fft2d(&mut re, &mut im, padded_rows, padded_columns, false);
+
+        if verbose {
+            println!("Filtering...")
+        };
+        let lowpass = |f: f64, fc: f64| -> f64 { 1f64 / (1f64 + (f / fc).powi(2 * order)) };
+        for row in 0..padded_rows {
+            let fv = fft_frequency(row, padded_rows);
+            for col in 0..padded_columns {
+                let fu = fft_frequency(col, padded_columns);
+                let f = (fu * fu + fv * fv).sqrt();
+                let h = match filter_type.as_str() {
+                    "lowpass" => lowpass(f, cutoff),
+                    "highpass" => 1f64 - lowpass(f, cutoff),
+                    "bandpass" => lowpass(f, cutoff2) * (1f64 - lowpass(f, cutoff)),
+                    _ => 1f64 - lowpass(f, cutoff2) * (1f64 - lowpass(f, cutoff)),
+                };
+                re[row * padded_columns + col] *= h;
+                im[row * padded_columns + col] *= h;
+            }
+        }
+
+        if verbose {
+            println!("Performing inverse transform...")
+        };
+        fft2d(&mut re, &mut im, padded_rows, padded_columns, true);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row as isize, col as isize) != nodata {
+                    output.set_value(row as isize, col as isize, re[row * padded_columns + col]);
+                } else {
+                    output.set_value(row as isize, col as isize, nodata);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Filter type: {}", filter_type));
+        output.add_metadata_entry(format!("Cutoff frequency: {}", cutoff));
+        if filter_type == "bandpass" || filter_type == "bandstop" {
+            output.add_metadata_entry(format!("Upper cutoff frequency: {}", cutoff2));
+        }
+        output.add_metadata_entry(format!("Filter order: {}", order));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
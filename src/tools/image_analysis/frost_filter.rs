@@ -0,0 +1,124 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::speckle_filters::{run_speckle_filter, speckle_filter_parameters, SpeckleFilterType};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool applies the Frost et al. (1982) filter to reduce the speckle noise of a synthetic aperture radar
+/// (SAR) backscatter image (`--input`). The Frost filter is an adaptive, exponentially weighted mean filter. The
+/// weight of each cell within the filter window (`--filter`, default 7 x 7 cells) is:
+///
+/// > *w* = exp(-*D* *C*<sub>i</sub><sup>2</sup> *d*)
+///
+/// where *D* is the damping factor (`--damping`, default 2.0), *C*<sub>i</sub> is the coefficient of variation
+/// (the standard deviation divided by the mean) of the window, and *d* is the distance of the cell from the
+/// window centre, in grid cells. In homogeneous areas, the coefficient of variation is low and the filter
+/// approaches a mean filter, while near edges and point targets, where it is high, the weights are concentrated
+/// on the centre cell, which is therefore preserved. Higher damping factors preserve more detail.
+///
+/// The filter assumes that the image values are backscatter intensities, i.e. in linear power units. Images in
+/// decibels can be filtered using the `--db` flag, in which case they are converted into intensities before
+/// filtering and back into decibels afterwards.
+///
+/// # Reference
+/// Frost, V. S., Stiles, J. A., Shanmugan, K. S., & Holtzman, J. C. (1982). A model for radar images and its
+/// application to adaptive digital filtering of multiplicative noise. *IEEE Transactions on Pattern Analysis and
+/// Machine Intelligence*, 4(2), 157-166.
+///
+/// # See Also
+/// `LeeSpeckleFilter`, `RefinedLeeFilter`, `GammaMapFilter`
+pub struct FrostFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FrostFilter {
+    pub fn new() -> FrostFilter {
+        // public constructor
+        let name = "FrostFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description = "Performs a Frost adaptive speckle filter on a SAR image.".to_string();
+
+        let parameters = speckle_filter_parameters(SpeckleFilterType::Frost);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=sentinel1_vv.tif -o=output.tif --filter=5 --damping=1.5",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        FrostFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FrostFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_speckle_filter(
+            &self.get_tool_name(),
+            SpeckleFilterType::Frost,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -0,0 +1,125 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::speckle_filters::{run_speckle_filter, speckle_filter_parameters, SpeckleFilterType};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool applies the Gamma maximum a posteriori (Gamma-MAP) filter (Lopes et al., 1990) to reduce the speckle
+/// noise of a synthetic aperture radar (SAR) backscatter image (`--input`). The filter assumes that both the
+/// speckle and the underlying scene reflectivity are gamma distributed, and replaces each grid cell with the
+/// maximum a posteriori estimate of its reflectivity, given the mean and variance of the filter window (`--filter`,
+/// default 7 x 7 cells). Cells are treated differently depending on the coefficient of variation of the window,
+/// *C*<sub>i</sub>, relative to that of the speckle, *C*<sub>u</sub> = 1 / sqrt(L), where L is the equivalent
+/// number of looks of the image (`--looks`):
+///
+/// - where *C*<sub>i</sub> <= *C*<sub>u</sub>, the area is homogeneous and the cell is replaced by the window mean;
+/// - where *C*<sub>i</sub> >= sqrt(2) *C*<sub>u</sub>, the cell is a point target and is left unchanged;
+/// - otherwise, the cell is replaced by the Gamma-MAP estimate.
+///
+/// The filter assumes that the image values are backscatter intensities, i.e. in linear power units. Images in
+/// decibels can be filtered using the `--db` flag, in which case they are converted into intensities before
+/// filtering and back into decibels afterwards.
+///
+/// # Reference
+/// Lopes, A., Touzi, R., & Nezry, E. (1990). Adaptive speckle filters and scene heterogeneity. *IEEE Transactions
+/// on Geoscience and Remote Sensing*, 28(6), 992-1000.
+///
+/// # See Also
+/// `LeeSpeckleFilter`, `RefinedLeeFilter`, `FrostFilter`
+pub struct GammaMapFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GammaMapFilter {
+    pub fn new() -> GammaMapFilter {
+        // public constructor
+        let name = "GammaMapFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description =
+            "Performs a Gamma maximum a posteriori (Gamma-MAP) speckle filter on a SAR image."
+                .to_string();
+
+        let parameters = speckle_filter_parameters(SpeckleFilterType::GammaMap);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=sentinel1_vv.tif -o=output.tif --filter=7 --looks=4.4",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        GammaMapFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GammaMapFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_speckle_filter(
+            &self.get_tool_name(),
+            SpeckleFilterType::GammaMap,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
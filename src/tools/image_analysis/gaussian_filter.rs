@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::{gpu, simd, thread};
 use num_cpus;
 use std::env;
 use std::f64;
@@ -16,7 +17,6 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool can be used to perform a Gaussian filter on a raster image. A Gaussian filter
 /// can be used to emphasize the longer-range variability in an image, effectively acting to
@@ -40,6 +40,9 @@ use std::thread;
 /// the `FastAlmostGaussianFilter` tool, which offers a fast-running approximatation to a
 /// Gaussian filter for larger kernel sizes.
 ///
+/// Greyscale images may be filtered on a GPU, where one is available, by running the tool with
+/// the `--device=gpu` flag; this requires a build of WhiteboxTools with the `gpu` feature.
+///
 /// # See Also
 /// `FastAlmostGaussianFilter`, `MeanFilter`, `MedianFilter`, `RgbToIhs`
 pub struct GaussianFilter {
@@ -86,8 +89,8 @@ impl GaussianFilter {
         });
 
         let sep: String = path::MAIN_SEPARATOR.to_string();
-        let p = format!("{}", env::current_dir().unwrap().display());
-        let e = format!("{}", env::current_exe().unwrap().display());
+        let p = format!("{}", env::current_dir().unwrap_or_default().display());
+        let e = format!("{}", env::current_exe().unwrap_or_default().display());
         let mut short_exe = e
             .replace(&p, "")
             .replace(".exe", "")
@@ -289,78 +292,141 @@ impl WhiteboxTool for GaussianFilter {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let num_procs = num_cpus::get() as isize;
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let d_x = d_x.clone();
-            let d_y = d_y.clone();
-            let weights = weights.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
+        // when requested, the convolution is run on the GPU, falling back to the CPU
+        // if the GPU is unavailable
+        let mut gpu_output = None;
+        if gpu::use_gpu() && !is_rgb_image {
+            let mut data = Vec::with_capacity((rows * columns) as usize);
+            for row in 0..rows {
+                data.extend(input.get_row_data(row));
+            }
+            match gpu::convolve(
+                &data,
+                rows as usize,
+                columns as usize,
+                nodata,
+                &d_x,
+                &d_y,
+                &weights,
+                true,
+            ) {
+                Ok(result) => gpu_output = Some(result),
+                Err(e) => {
+                    if verbose {
+                        println!("{} Running on the CPU instead.", e);
+                    }
+                }
+            }
+        }
 
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
-                        }
-                        nodata
-                    })
-                };
-
-                let (mut sum, mut z_final): (f64, f64);
-                let mut z: f64;
-                let mut zn: f64;
-                let (mut x, mut y): (isize, isize);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_fn(row, col);
-                        if z != nodata {
-                            sum = 0.0;
-                            z_final = 0.0;
+        if let Some(result) = gpu_output {
+            for row in 0..rows {
+                let a = (row * columns) as usize;
+                output.set_row_data(row, result[a..a + columns as usize].to_vec());
+            }
+        } else {
+            let num_procs = num_cpus::get() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let d_x = d_x.clone();
+                let d_y = d_y.clone();
+                let weights = weights.clone();
+                let tx1 = tx.clone();
+                thread::spawn(move || {
+                    let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+                        Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+                    } else {
+                        Box::new(|row: isize, col: isize| -> f64 {
+                            let value = input.get_value(row, col);
+                            if value != nodata {
+                                return value2i(value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
+                        // simply return the value.
+                        Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
+                    } else {
+                        // convert it back into an rgb value, using the modified intensity value.
+                        Box::new(|row: isize, col: isize, value: f64| -> f64 {
+                            if value != nodata {
+                                let (h, s, _) = value2hsi(input.get_value(row, col));
+                                return hsi2value(h, s, value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let (mut sum, mut z_final): (f64, f64);
+                    let mut z: f64;
+                    let mut zn: f64;
+                    let (mut x, mut y): (isize, isize);
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![nodata; columns as usize];
+                        if !is_rgb_image {
+                            // greyscale images are convolved a row at a time, accumulating
+                            // each kernel cell's contribution across the row
+                            let window: Vec<Vec<f64>> = (row - midpoint..=row + midpoint)
+                                .map(|y| input.get_row_data(y))
+                                .collect();
+                            let mut sums = vec![0f64; columns as usize];
+                            let mut z_finals = vec![0f64; columns as usize];
                             for a in 0..num_pixels_in_filter {
-                                x = col + d_x[a];
-                                y = row + d_y[a];
-                                zn = input_fn(y, x);
-                                if zn != nodata {
-                                    sum += weights[a];
-                                    z_final += weights[a] * zn;
+                                let start = (-d_x[a]).max(0);
+                                let end = columns - d_x[a].max(0);
+                                if start < end {
+                                    let values = &window[(d_y[a] + midpoint) as usize];
+                                    simd::accumulate_weighted(
+                                        &values[(start + d_x[a]) as usize..(end + d_x[a]) as usize],
+                                        nodata,
+                                        weights[a],
+                                        &mut z_finals[start as usize..end as usize],
+                                        &mut sums[start as usize..end as usize],
+                                    );
+                                }
+                            }
+                            for col in 0..columns as usize {
+                                if window[midpoint as usize][col] != nodata {
+                                    data[col] = z_finals[col] / sums[col];
+                                }
+                            }
+                        } else {
+                            for col in 0..columns {
+                                z = input_fn(row, col);
+                                if z != nodata {
+                                    sum = 0.0;
+                                    z_final = 0.0;
+                                    for a in 0..num_pixels_in_filter {
+                                        x = col + d_x[a];
+                                        y = row + d_y[a];
+                                        zn = input_fn(y, x);
+                                        if zn != nodata {
+                                            sum += weights[a];
+                                            z_final += weights[a] * zn;
+                                        }
+                                    }
+                                    data[col as usize] = output_fn(row, col, z_final / sum);
                                 }
                             }
-                            data[col as usize] = output_fn(row, col, z_final / sum);
                         }
-                    }
 
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+                        tx1.send((row, data)).unwrap();
+                    }
+                });
+            }
 
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+            for row in 0..rows {
+                let data = rx.recv().expect("Error receiving data from thread.");
+                output.set_row_data(data.0, data.1);
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
         }
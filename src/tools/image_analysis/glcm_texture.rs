@@ -0,0 +1,556 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// The texture statistics calculated by the tool, in the order of the output bands.
+const STATISTICS: [&str; 5] = ["contrast", "entropy", "homogeneity", "correlation", "asm"];
+
+/// This tool calculates image texture measures from the grey-level co-occurrence matrix (GLCM) of Haralick et
+/// al. (1973) within a moving window. The GLCM of a window records how often pairs of grey levels occur at a
+/// given offset, i.e. a distance (`--distance`, default 1 cell) and direction (`--directions`), from one another.
+/// The directions are any combination of 0 (east), 45 (north-east), 90 (north), and 135 (north-west) degrees,
+/// specified as a comma-separated list (default `0,45,90,135`). The co-occurrences of all of the specified
+/// directions are combined into a single symmetric matrix, which makes the measures rotation invariant when all
+/// four directions are used.
+///
+/// Each of the following texture statistics (`--stats`, default all) is written to a separate band of the
+/// output raster, in this order:
+///
+/// | Statistic | Formula | Description |
+/// |-----------|---------|-------------|
+/// | contrast | Σ *P*(*i*,*j*)(*i* - *j*)<sup>2</sup> | Local grey-level variation |
+/// | entropy | -Σ *P*(*i*,*j*) ln *P*(*i*,*j*) | Disorder of the grey-level pairs |
+/// | homogeneity | Σ *P*(*i*,*j*) / (1 + (*i* - *j*)<sup>2</sup>) | Closeness of the pairs to the GLCM diagonal |
+/// | correlation | Σ (*i* - μ)(*j* - μ)*P*(*i*,*j*) / σ<sup>2</sup> | Linear dependency of the grey levels |
+/// | asm | Σ *P*(*i*,*j*)<sup>2</sup> | Angular second moment, or uniformity |
+///
+/// where *P*(*i*,*j*) is the normalized co-occurrence frequency of grey levels *i* and *j*, and μ and σ<sup>2</sup>
+/// are the mean and variance of the grey levels of the matrix. The correlation of a window with a single grey
+/// level is taken to be 1. The statistics are calculated in a single pass over the image, with the rows of the
+/// image processed in parallel, and the band names are recorded in the output raster's metadata.
+///
+/// The input image (`--input`) values are quantized into a number of grey levels (`--levels`, default 32) between
+/// the image's minimum and maximum values prior to calculating the co-occurrences. Fewer grey levels produce
+/// more stable statistics in small windows. The size of the moving window is specified by the `--filter`
+/// parameter (default 7 x 7 cells). Pairs of cells involving NoData are ignored, and cells that are NoData in the
+/// input, or whose windows contain no valid pairs, are NoData in the output.
+///
+/// # Reference
+/// Haralick, R. M., Shanmugam, K., & Dinstein, I. (1973). Textural features for image classification. *IEEE
+/// Transactions on Systems, Man, and Cybernetics*, 3(6), 610-621.
+///
+/// # See Also
+/// `StandardDeviationFilter`, `RangeFilter`, `DiversityFilter`
+pub struct GlcmTexture {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GlcmTexture {
+    pub fn new() -> GlcmTexture {
+        // public constructor
+        let name = "GlcmTexture".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Calculates grey-level co-occurrence matrix (GLCM) texture statistics within a moving window.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Size".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the (square) moving window, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("7".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Grey Levels".to_owned(),
+            flags: vec!["--levels".to_owned()],
+            description: "Number of grey levels into which the image is quantized.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("32".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Offset Distance".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Distance between the cells of co-occurring pairs, in grid cells."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Offset Directions".to_owned(),
+            flags: vec!["--directions".to_owned()],
+            description: "Comma-separated list of the directions (0, 45, 90, 135 degrees) of co-occurring pairs.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("0,45,90,135".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistics".to_owned(),
+            flags: vec!["--stats".to_owned()],
+            description: "Comma-separated list of the statistics (contrast, entropy, homogeneity, correlation, asm) to calculate.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("contrast,entropy,homogeneity,correlation,asm".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=texture.tif --filter=9 --levels=16
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=texture.tif --distance=2 --directions=\"0,90\" --stats=\"contrast,entropy\"", short_exe, name).replace("*", &sep);
+
+        GlcmTexture {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GlcmTexture {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut filter_size = 7usize;
+        let mut levels = 32usize;
+        let mut distance = 1isize;
+        let mut directions_str = "0,45,90,135".to_string();
+        let mut stats_str = STATISTICS.join(",");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-filter" {
+                filter_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-levels" {
+                levels = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-distance" {
+                distance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                };
+            } else if flag_val == "-directions" {
+                directions_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-stats" {
+                stats_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        // The filter dimensions must be odd numbers such that there is a middle pixel
+        if filter_size < 3 {
+            filter_size = 3;
+        }
+        if filter_size % 2 == 0 {
+            filter_size += 1;
+        }
+        if levels < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of grey levels must be at least two.",
+            ));
+        }
+        if distance < 1 || distance as usize >= filter_size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The offset distance must be at least one and less than the filter size.",
+            ));
+        }
+
+        // the offsets (dy, dx) of the co-occurring pairs, with north up
+        let mut offsets: Vec<(isize, isize)> = vec![];
+        for s in directions_str.split(|c| c == ',' || c == ';') {
+            let s = s.trim();
+            if s.is_empty() {
+                continue;
+            }
+            let offset = match s.parse::<f64>().ok().map(|a| a.round() as isize) {
+                Some(0) => (0, distance),
+                Some(45) => (-distance, distance),
+                Some(90) => (-distance, 0),
+                Some(135) => (-distance, -distance),
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Unrecognized direction '{}'; directions must be 0, 45, 90, or 135 degrees.",
+                            s
+                        ),
+                    ))
+                }
+            };
+            if !offsets.contains(&offset) {
+                offsets.push(offset);
+            }
+        }
+        if offsets.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one offset direction must be specified.",
+            ));
+        }
+
+        let mut stats: Vec<usize> = vec![];
+        for s in stats_str.split(|c| c == ',' || c == ';') {
+            let s = s.trim().to_lowercase();
+            if s.is_empty() {
+                continue;
+            }
+            match STATISTICS.iter().position(|name| *name == s) {
+                Some(k) => {
+                    if !stats.contains(&k) {
+                        stats.push(k);
+                    }
+                }
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unrecognized texture statistic '{}'.", s),
+                    ))
+                }
+            }
+        }
+        if stats.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one texture statistic must be specified.",
+            ));
+        }
+        // bands are written in the order of the statistics table
+        stats.sort();
+        let num_stats = stats.len();
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let min_val = input.configs.minimum;
+        let range = input.configs.maximum - min_val;
+        let midpoint = (filter_size / 2) as isize;
+
+        let (tx, rx) = mpsc::channel();
+        let num_procs = num_cpus::get() as isize;
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let offsets = offsets.clone();
+            let stats = stats.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let grey_level = |row: isize, col: isize| -> Option<usize> {
+                    let z = input.get_value(row, col);
+                    if z == nodata {
+                        None
+                    } else if range > 0f64 {
+                        Some((((z - min_val) / range * levels as f64) as usize).min(levels - 1))
+                    } else {
+                        Some(0)
+                    }
+                };
+                // the co-occurrence counts are reset after each window, using the list of
+                // the matrix entries that were visited
+                let mut glcm = vec![0f64; levels * levels];
+                let mut visited: Vec<usize> = vec![];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; num_stats];
+                    for col in 0..columns {
+                        if input.get_value(row, col) == nodata {
+                            continue;
+                        }
+                        let mut total = 0f64;
+                        for r in row - midpoint..=row + midpoint {
+                            for c in col - midpoint..=col + midpoint {
+                                let i = match grey_level(r, c) {
+                                    Some(i) => i,
+                                    None => continue,
+                                };
+                                for (dy, dx) in &offsets {
+                                    let (r2, c2) = (r + dy, c + dx);
+                                    if (r2 - row).abs() > midpoint || (c2 - col).abs() > midpoint {
+                                        continue;
+                                    }
+                                    let j = match grey_level(r2, c2) {
+                                        Some(j) => j,
+                                        None => continue,
+                                    };
+                                    // the matrix is symmetric
+                                    for idx in [i * levels + j, j * levels + i].iter() {
+                                        if glcm[*idx] == 0f64 {
+                                            visited.push(*idx);
+                                        }
+                                        glcm[*idx] += 1f64;
+                                    }
+                                    total += 2f64;
+                                }
+                            }
+                        }
+                        if total == 0f64 {
+                            continue;
+                        }
+
+                        let mut mean = 0f64;
+                        for idx in &visited {
+                            mean += (idx / levels) as f64 * glcm[*idx] / total;
+                        }
+                        let mut variance = 0f64;
+                        let mut contrast = 0f64;
+                        let mut entropy = 0f64;
+                        let mut homogeneity = 0f64;
+                        let mut covariance = 0f64;
+                        let mut asm = 0f64;
+                        for idx in &visited {
+                            let p = glcm[*idx] / total;
+                            let i = (idx / levels) as f64;
+                            let j = (idx % levels) as f64;
+                            variance += p * (i - mean) * (i - mean);
+                            contrast += p * (i - j) * (i - j);
+                            entropy -= p * p.ln();
+                            homogeneity += p / (1f64 + (i - j) * (i - j));
+                            covariance += p * (i - mean) * (j - mean);
+                            asm += p * p;
+                        }
+                        let correlation = if variance > 0f64 {
+                            covariance / variance
+                        } else {
+                            1f64
+                        };
+                        for (band, k) in stats.iter().enumerate() {
+                            data[band][col as usize] = match k {
+                                0 => contrast,
+                                1 => entropy,
+                                2 => homogeneity,
+                                3 => correlation,
+                                _ => asm,
+                            };
+                        }
+
+                        for idx in visited.drain(..) {
+                            glcm[idx] = 0f64;
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "grey.plt".to_string();
+        output.set_num_bands(num_stats);
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for row in 0..rows {
+            let (r, data) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                output.set_row_data_band(r, band, values);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Filter size: {}", filter_size));
+        output.add_metadata_entry(format!("Grey levels: {}", levels));
+        output.add_metadata_entry(format!("Offset distance: {}", distance));
+        output.add_metadata_entry(format!("Offset directions: {}", directions_str));
+        for (band, k) in stats.iter().enumerate() {
+            output.add_metadata_entry(format!("Band {}: {}", band + 1, STATISTICS[*k]));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
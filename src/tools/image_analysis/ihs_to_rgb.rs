@@ -310,11 +310,15 @@ impl WhiteboxTool for IhsToRgb {
         if verbose {
             println!("Reading hue band data...")
         };
-        let input_h = Arc::new(Raster::new(&hue_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let input_h = Arc::new(align_raster_input(&input_i, Raster::new(&hue_file, "r")?)?);
         if verbose {
             println!("Reading saturation band data...")
         };
-        let input_s = Arc::new(Raster::new(&saturation_file, "r")?);
+        let input_s = Arc::new(align_raster_input(
+            &input_i,
+            Raster::new(&saturation_file, "r")?,
+        )?);
 
         let rows = input_i.configs.rows as isize;
         let columns = input_i.configs.columns as isize;
@@ -324,24 +328,6 @@ impl WhiteboxTool for IhsToRgb {
 
         let start = Instant::now();
 
-        // make sure the input files have the same size
-        if input_i.configs.rows != input_h.configs.rows
-            || input_i.configs.columns != input_h.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if input_i.configs.rows != input_s.configs.rows
-            || input_i.configs.columns != input_s.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let num_procs = num_cpus::get() as isize;
         if !use_composite {
             let (tx, rx) = mpsc::channel();
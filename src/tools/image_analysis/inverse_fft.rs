@@ -0,0 +1,314 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::fft2d;
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool transforms the real (`--real`) and imaginary (`--imaginary`) components of a Fourier
+/// transform, such as those created by the `ForwardFft` tool, back into the spatial domain using the
+/// inverse fast Fourier transform. The input components must be centred, i.e. with the zero-frequency
+/// component at the centre of the image, as output by `ForwardFft`, and must have dimensions that are
+/// powers of two. Only the real part of the inverse transform is output (`--output`).
+///
+/// Because the forward transform pads the image to dimensions that are powers of two, the user may
+/// optionally specify the original image (`--base`). In this case, the output will be cropped to the
+/// dimensions of the base image, will share its georeferencing, and any cells that are **NoData** in the
+/// base image will be **NoData** in the output. Otherwise, the full padded grid is output.
+///
+/// # See Also
+/// `ForwardFft`, `FrequencyDomainFilter`, `RemovePeriodicNoise`
+pub struct InverseFft {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl InverseFft {
+    pub fn new() -> InverseFft {
+        // public constructor
+        let name = "InverseFft".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Transforms the real and imaginary components of a Fourier transform back into an image."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Real Component File".to_owned(),
+            flags: vec!["--real".to_owned()],
+            description: "Input real component raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Imaginary Component File".to_owned(),
+            flags: vec!["--imaginary".to_owned()],
+            description: "Input imaginary component raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Base Image File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optional original image used to crop the output and define NoData cells."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --real=real.tif --imaginary=imag.tif --base=image.tif -o=output.tif", short_exe, name).replace("*", &sep);
+
+        InverseFft {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for InverseFft {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut real_file = String::new();
+        let mut imag_file = String::new();
+        let mut base_file = String::new();
+        let mut output_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-real" {
+                real_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-imaginary" || flag_val == "-imag" {
+                imag_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-base" {
+                base_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !real_file.contains(&sep) && !real_file.contains("/") {
+            real_file = format!("{}{}", working_directory, real_file);
+        }
+        if !imag_file.contains(&sep) && !imag_file.contains("/") {
+            imag_file = format!("{}{}", working_directory, imag_file);
+        }
+        if !base_file.is_empty() && !base_file.contains(&sep) && !base_file.contains("/") {
+            base_file = format!("{}{}", working_directory, base_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input_real = Raster::new(&real_file, "r")?;
+        // make sure the components share the same grid and CRS
+        let input_imag = align_raster_input(&input_real, Raster::new(&imag_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = input_real.configs.rows;
+        let columns = input_real.configs.columns;
+        if !rows.is_power_of_two() || !columns.is_power_of_two() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The dimensions of the input components must be powers of two.",
+            ));
+        }
+
+        // un-centre the spectrum
+        let mut re = vec![0f64; rows * columns];
+        let mut im = vec![0f64; rows * columns];
+        let nodata_real = input_real.configs.nodata;
+        let nodata_imag = input_imag.configs.nodata;
+        let (half_rows, half_columns) = (rows / 2, columns / 2);
+        for row in 0..rows {
+            let dst_row = (row + half_rows) % rows;
+            for col in 0..columns {
+                let dst_col = (col + half_columns) % columns;
+                let idx = dst_row * columns + dst_col;
+                let z_re = input_real.get_value(row as isize, col as isize);
+                let z_im = input_imag.get_value(row as isize, col as isize);
+                re[idx] = if z_re != nodata_real { z_re } else { 0f64 };
+                im[idx] = if z_im != nodata_imag { z_im } else { 0f64 };
+            }
+        }
+
+        if verbose {
+            println!("Performing transform...")
+        };
+        fft2d(&mut re, &mut im, rows, columns, true);
+
+        let mut output = if !base_file.is_empty() {
+            let base = Raster::new(&base_file, "r")?;
+            if base.configs.rows > rows || base.configs.columns > columns {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The base image is larger than the input components.",
+                ));
+            }
+            let mut output = Raster::initialize_using_file(&output_file, &base);
+            output.configs.data_type = DataType::F32;
+            let nodata = base.configs.nodata;
+            for row in 0..base.configs.rows {
+                for col in 0..base.configs.columns {
+                    if base.get_value(row as isize, col as isize) != nodata {
+                        output.set_value(row as isize, col as isize, re[row * columns + col]);
+                    } else {
+                        output.set_value(row as isize, col as isize, nodata);
+                    }
+                }
+            }
+            output
+        } else {
+            let mut output = Raster::initialize_using_file(&output_file, &input_real);
+            output.configs.data_type = DataType::F32;
+            for row in 0..rows {
+                for col in 0..columns {
+                    output.set_value(row as isize, col as isize, re[row * columns + col]);
+                }
+            }
+            output
+        };
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input real component file: {}", real_file));
+        output.add_metadata_entry(format!("Input imaginary component file: {}", imag_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
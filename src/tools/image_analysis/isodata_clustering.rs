@@ -0,0 +1,922 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::rendering::LineGraph;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool performs an unsupervised classification of two or more input images (`--inputs`),
+/// typically several bands of multi-spectral satellite imagery, using the ISODATA (iterative
+/// self-organizing data analysis technique) clustering algorithm (Ball and Hall, 1965). ISODATA is an
+/// extension of the k-means method (`KMeansClustering`) in which the number of clusters is allowed to
+/// change during the analysis. Beginning with the user-specified target number of clusters (`--classes`),
+/// with centres spaced along the multi-dimensional diagonal of spectral space, each iteration assigns every
+/// pixel to the nearest cluster centre and updates the centres. Clusters containing fewer than the minimum
+/// number of pixels (`--min_class_size`) are then discarded. On odd-numbered iterations, clusters with a
+/// standard deviation exceeding a threshold (`--max_stdev`) in any band are split in two, provided the number
+/// of clusters is less than twice the target number. On even-numbered iterations, pairs of clusters with
+/// centres separated by less than a threshold distance (`--merge_dist`) are merged, provided the number of
+/// clusters exceeds half of the target number. If unspecified, the splitting and merging thresholds default to
+/// one half and one quarter, respectively, of the mean standard deviation of the input images. The algorithm
+/// terminates when the maximum number of iterations (`--max_iterations`) is reached or when the percentage
+/// of pixels changing cluster between consecutive iterations, without any splitting or merging, falls below a
+/// threshold (`--class_change`).
+///
+/// The tool creates two outputs, the classified image (`--output`) and an optional HTML report (`--out_html`)
+/// containing the spectral signature of each cluster, i.e. its size and the mean and standard deviation of each
+/// input band, as well as the inter-centroid distances and a convergence plot. Note, each of the input images
+/// must have the same number of rows and columns and the same spatial extent because the analysis is performed
+/// on a pixel-by-pixel basis. **NoData** values in any of the input images will result in the removal of the
+/// corresponding pixel from the analysis.
+/// Multi-band images, including RGB colour composites, may also be input, in which case each band is
+/// treated as a separate input image.
+///
+/// # Reference
+/// Ball, G. H., and Hall, D. J. (1965). ISODATA, a novel method of data analysis and pattern classification.
+/// Stanford Research Institute, Menlo Park, CA.
+///
+/// # See Also
+/// `KMeansClustering`, `ModifiedKMeansClustering`, `TrainClassifier`
+pub struct IsodataClustering {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl IsodataClustering {
+    pub fn new() -> IsodataClustering {
+        // public constructor
+        let name = "IsodataClustering".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Performs an ISODATA clustering operation on a multi-spectral dataset.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML Report File".to_owned(),
+            flags: vec!["--out_html".to_owned()],
+            description: "Output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target Num. Classes".to_owned(),
+            flags: vec!["--classes".to_owned()],
+            description: "Target number of classes".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Max. Iterations".to_owned(),
+            flags: vec!["--max_iterations".to_owned()],
+            description: "Maximum number of iterations".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("20".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Percent Class Change Threshold".to_owned(),
+            flags: vec!["--class_change".to_owned()],
+            description: "Minimum percent of cells changed between iterations before completion"
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Min. Class Size".to_owned(),
+            flags: vec!["--min_class_size".to_owned()],
+            description: "Minimum class size, in pixels".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Max. Cluster Standard Deviation (optional)".to_owned(),
+            flags: vec!["--max_stdev".to_owned()],
+            description: "Cluster standard deviation above which clusters are split".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Min. Cluster Centre Distance (optional)".to_owned(),
+            flags: vec!["--merge_dist".to_owned()],
+            description: "Cluster centre distance below which clusters are merged".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' -o=output.tif --out_html=report.html --classes=15 --max_iterations=25 --class_change=1.5 --min_class_size=500 --max_stdev=12.0 --merge_dist=8.0", short_exe, name).replace("*", &sep);
+
+        IsodataClustering {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for IsodataClustering {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files_str = String::new();
+        let mut output_file = String::new();
+        let mut output_html_file = String::new();
+        let mut num_classes = 0usize;
+        let mut max_iterations = 20usize;
+        let mut percent_changed_threshold = 2f64;
+        let mut min_class_size = 10usize;
+        let mut max_stdev = f64::NAN;
+        let mut merge_dist = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                input_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_html" {
+                output_html_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-classes" {
+                num_classes = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-max_iterations" {
+                max_iterations = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-class_change" {
+                percent_changed_threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-min_class_size" {
+                min_class_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-max_stdev" {
+                max_stdev = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-merge_dist" {
+                merge_dist = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if !output_html_file.trim().is_empty() {
+            if !output_html_file.contains(&sep) && !output_html_file.contains("/") {
+                output_html_file = format!("{}{}", working_directory, output_html_file);
+            }
+            if !output_html_file.ends_with(".html") {
+                output_html_file.push_str(".html");
+            }
+        }
+
+        let mut cmd = input_files_str.split(";");
+        let mut input_files = cmd.collect::<Vec<&str>>();
+        if input_files.len() == 1 {
+            cmd = input_files_str.split(",");
+            input_files = cmd.collect::<Vec<&str>>();
+        }
+        let input_files: Vec<&str> = input_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+        if input_files.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
+        }
+
+        if num_classes < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The target number of classes should be at least 2.",
+            ));
+        }
+
+        if max_iterations < 2 || max_iterations > 250 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Maximum iterations should be between 2 and 250.",
+            ));
+        }
+
+        if percent_changed_threshold < 0f64 || percent_changed_threshold > 25f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "class_change flag should be between 0.0 and 25.0.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // multi-band images contribute each of their bands as a separate input
+        let mut input_raster: Vec<Raster> = vec![];
+        let mut band_names: Vec<String> = vec![];
+        for i in 0..input_files.len() {
+            if verbose {
+                println!("Reading file {} of {}", i + 1, input_files.len());
+            }
+            let mut input_file = input_files[i].trim().to_owned();
+            if !input_file.contains(&sep) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            let raster = Raster::new(&input_file, "r")?;
+            let raster = if input_raster.is_empty() {
+                raster
+            } else {
+                // make sure the inputs share the same grid and CRS
+                align_raster_input(&input_raster[0], raster)?
+            };
+            let num_bands = raster.num_bands();
+            if num_bands > 1 {
+                for b in 0..num_bands {
+                    input_raster.push(raster.get_band(b));
+                    band_names.push(format!("{} (band {})", input_files[i].trim(), b + 1));
+                }
+            } else {
+                input_raster.push(raster);
+                band_names.push(input_files[i].trim().to_owned());
+            }
+        }
+        let num_files = input_raster.len();
+        if num_files < 2 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
+        }
+
+        let mut nodata: Vec<f64> = Vec::with_capacity(num_files);
+        for i in 0..num_files {
+            nodata.push(input_raster[i].configs.nodata);
+        }
+        let rows = input_raster[0].configs.rows as isize;
+        let columns = input_raster[0].configs.columns as isize;
+
+        // default splitting and merging thresholds
+        let mut mean_stdev = 0f64;
+        for i in 0..num_files {
+            let (_, stdev) = input_raster[i].calculate_mean_and_stdev();
+            mean_stdev += stdev / num_files as f64;
+        }
+        if max_stdev.is_nan() {
+            max_stdev = mean_stdev / 2f64;
+        }
+        if merge_dist.is_nan() {
+            merge_dist = mean_stdev / 4f64;
+        }
+
+        // initialize the cluster centres along the diagonal of spectral space
+        let mut centres = vec![vec![0f64; num_files]; num_classes];
+        for a in 0..num_classes {
+            for i in 0..num_files {
+                let min_val = input_raster[i].configs.minimum;
+                let range = input_raster[i].configs.maximum - min_val;
+                centres[a][i] = min_val + range * (a as f64 + 0.5) / num_classes as f64;
+            }
+        }
+
+        let input_raster = Arc::new(input_raster);
+        let nodata = Arc::new(nodata);
+        let num_cells = (rows * columns) as usize;
+        let mut labels = vec![-1isize; num_cells];
+        let mut class_n: Vec<usize> = vec![];
+        let mut class_stdev: Vec<Vec<f64>> = vec![];
+        let mut xdata = vec![vec![]];
+        let mut ydata = vec![vec![]];
+        let mut structure_changed = true;
+        let mut num_iterations = 0;
+        for iteration in 1..=max_iterations {
+            num_iterations = iteration;
+            let k = centres.len();
+
+            // assign each pixel to the nearest cluster centre
+            let num_procs = num_cpus::get() as isize;
+            let centres_arc = Arc::new(centres.clone());
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input_raster = input_raster.clone();
+                let centres = centres_arc.clone();
+                let nodata = nodata.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let mut value = vec![0f64; num_files];
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![-1isize; columns as usize];
+                        let mut sum = vec![vec![0f64; num_files]; k];
+                        let mut sum_sqr = vec![vec![0f64; num_files]; k];
+                        let mut count = vec![0usize; k];
+                        'col_loop: for col in 0..columns {
+                            for i in 0..num_files {
+                                value[i] = input_raster[i].get_value(row, col);
+                                if value[i] == nodata[i] {
+                                    continue 'col_loop;
+                                }
+                            }
+                            let mut min_dist = f64::INFINITY;
+                            let mut which_class = 0;
+                            for a in 0..k {
+                                let mut dist = 0f64;
+                                for i in 0..num_files {
+                                    dist += (value[i] - centres[a][i]) * (value[i] - centres[a][i]);
+                                }
+                                if dist < min_dist {
+                                    min_dist = dist;
+                                    which_class = a;
+                                }
+                            }
+                            data[col as usize] = which_class as isize;
+                            count[which_class] += 1;
+                            for i in 0..num_files {
+                                sum[which_class][i] += value[i];
+                                sum_sqr[which_class][i] += value[i] * value[i];
+                            }
+                        }
+                        tx.send((row, data, sum, sum_sqr, count)).unwrap();
+                    }
+                });
+            }
+
+            let mut sum = vec![vec![0f64; num_files]; k];
+            let mut sum_sqr = vec![vec![0f64; num_files]; k];
+            class_n = vec![0usize; k];
+            let mut cells_changed = 0f64;
+            let mut n = 0f64;
+            for r in 0..rows {
+                let (row, data, s, ss, c) = rx.recv().expect("Error receiving data from thread.");
+                for col in 0..columns as usize {
+                    if data[col] >= 0 {
+                        n += 1f64;
+                        let idx = row as usize * columns as usize + col;
+                        if labels[idx] != data[col] {
+                            cells_changed += 1f64;
+                            labels[idx] = data[col];
+                        }
+                    }
+                }
+                for a in 0..k {
+                    class_n[a] += c[a];
+                    for i in 0..num_files {
+                        sum[a][i] += s[a][i];
+                        sum_sqr[a][i] += ss[a][i];
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!(
+                            "Progress (loop {} of {}): {}%",
+                            iteration, max_iterations, progress
+                        );
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            // update the cluster centres and standard deviations
+            class_stdev = vec![vec![0f64; num_files]; k];
+            for a in 0..k {
+                if class_n[a] > 0 {
+                    let na = class_n[a] as f64;
+                    for i in 0..num_files {
+                        centres[a][i] = sum[a][i] / na;
+                        class_stdev[a][i] = (sum_sqr[a][i] / na - centres[a][i] * centres[a][i])
+                            .max(0f64)
+                            .sqrt();
+                    }
+                }
+            }
+
+            let percent_changed = if n > 0f64 {
+                100f64 * cells_changed / n
+            } else {
+                0f64
+            };
+            xdata[0].push(iteration as f64);
+            ydata[0].push(percent_changed);
+            if verbose {
+                println!(
+                    "Clusters: {}; cells changed {} ({:.4} percent)",
+                    k, cells_changed, percent_changed
+                );
+            }
+
+            if iteration == max_iterations
+                || (!structure_changed && percent_changed < percent_changed_threshold)
+            {
+                break;
+            }
+
+            // discard small clusters
+            let mut new_centres: Vec<Vec<f64>> = vec![];
+            let mut new_n: Vec<usize> = vec![];
+            let mut new_stdev: Vec<Vec<f64>> = vec![];
+            for a in 0..k {
+                if class_n[a] >= min_class_size {
+                    new_centres.push(centres[a].clone());
+                    new_n.push(class_n[a]);
+                    new_stdev.push(class_stdev[a].clone());
+                }
+            }
+            if new_centres.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "All clusters are smaller than the minimum class size.",
+                ));
+            }
+            structure_changed = new_centres.len() != k;
+
+            if iteration % 2 == 1 && new_centres.len() < 2 * num_classes {
+                // split heterogeneous clusters
+                let mut split_centres = vec![];
+                for a in 0..new_centres.len() {
+                    let mut max_band = 0;
+                    for i in 1..num_files {
+                        if new_stdev[a][i] > new_stdev[a][max_band] {
+                            max_band = i;
+                        }
+                    }
+                    if new_stdev[a][max_band] > max_stdev
+                        && new_n[a] > 2 * (min_class_size + 1)
+                        && new_centres.len() + split_centres.len() < 2 * num_classes
+                    {
+                        let mut c = new_centres[a].clone();
+                        c[max_band] += new_stdev[a][max_band];
+                        new_centres[a][max_band] -= new_stdev[a][max_band];
+                        split_centres.push(c);
+                    }
+                }
+                if !split_centres.is_empty() {
+                    structure_changed = true;
+                    new_centres.extend(split_centres);
+                }
+            } else if iteration % 2 == 0 && new_centres.len() > num_classes / 2 {
+                // merge clusters with nearby centres, closest pairs first
+                let mut pairs = vec![];
+                for a in 0..new_centres.len() {
+                    for b in (a + 1)..new_centres.len() {
+                        let mut dist = 0f64;
+                        for i in 0..num_files {
+                            dist += (new_centres[a][i] - new_centres[b][i])
+                                * (new_centres[a][i] - new_centres[b][i]);
+                        }
+                        let dist = dist.sqrt();
+                        if dist < merge_dist {
+                            pairs.push((dist, a, b));
+                        }
+                    }
+                }
+                pairs.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap());
+                let mut merged = vec![false; new_centres.len()];
+                let mut num_remaining = new_centres.len();
+                for (_, a, b) in pairs {
+                    if merged[a] || merged[b] || num_remaining <= num_classes / 2 {
+                        continue;
+                    }
+                    let (na, nb) = (new_n[a] as f64, new_n[b] as f64);
+                    for i in 0..num_files {
+                        new_centres[a][i] =
+                            (new_centres[a][i] * na + new_centres[b][i] * nb) / (na + nb);
+                    }
+                    new_n[a] += new_n[b];
+                    merged[b] = true;
+                    num_remaining -= 1;
+                }
+                if num_remaining < new_centres.len() {
+                    structure_changed = true;
+                    new_centres = new_centres
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(a, _)| !merged[*a])
+                        .map(|(_, c)| c)
+                        .collect();
+                }
+            }
+
+            if structure_changed {
+                // cluster indices are no longer comparable between iterations
+                for l in labels.iter_mut() {
+                    *l = -1;
+                }
+            }
+            centres = new_centres;
+        }
+
+        let num_clusters = centres.len();
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &input_raster[0]);
+        output.configs.nodata = out_nodata;
+        output.reinitialize_values(out_nodata);
+        for row in 0..rows {
+            for col in 0..columns {
+                let l = labels[(row * columns + col) as usize];
+                if l >= 0 {
+                    output.set_value(row, col, (l + 1) as f64);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.data_type = DataType::I16;
+        output.configs.palette = "qual.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Target num. clusters: {}", num_classes));
+        output.add_metadata_entry(format!("Num. clusters: {}", num_clusters));
+        output.add_metadata_entry(format!("Num. bands: {}", num_files));
+        output.add_metadata_entry(format!("max_iterations: {}", max_iterations));
+        output.add_metadata_entry(format!("class_change: {}", percent_changed_threshold));
+        output.add_metadata_entry(format!("min_class_size: {}", min_class_size));
+        output.add_metadata_entry(format!("max_stdev: {}", max_stdev));
+        output.add_metadata_entry(format!("merge_dist: {}", merge_dist));
+        output.add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        if !output_html_file.trim().is_empty() {
+            let f = File::create(output_html_file.clone())?;
+            let mut writer = BufWriter::new(f);
+
+            writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+            <html>
+                <head>
+                    <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                    <title>ISODATA Clustering</title>"#.as_bytes())?;
+
+            // get the style sheet
+            writer.write_all(&get_css().as_bytes())?;
+
+            writer.write_all(
+                &r#"
+                </head>
+                <body>
+                    <h1>ISODATA Clustering Report</h1>
+                    <p>"#
+                    .as_bytes(),
+            )?;
+
+            writer
+                .write_all(&format!("<strong>Num. bands</strong>: {}<br>", num_files).as_bytes())?;
+            for i in 0..num_files {
+                writer.write_all(
+                    &format!("<strong>Image {}</strong>: {}<br>", i + 1, band_names[i]).as_bytes(),
+                )?;
+            }
+            writer.write_all(
+                &format!("<strong>Target num. clusters</strong>: {}<br>", num_classes).as_bytes(),
+            )?;
+            writer.write_all(
+                &format!("<strong>Final num. clusters</strong>: {}<br>", num_clusters).as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Iterations</strong>: {} (max. {})<br>",
+                    num_iterations, max_iterations
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Percent change threshold</strong>: {:.3}%<br>",
+                    percent_changed_threshold
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                &format!("<strong>Min. cluster size</strong>: {}<br>", min_class_size).as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Split std. dev. threshold</strong>: {:.3}<br>",
+                    max_stdev
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Merge distance threshold</strong>: {:.3}<br>",
+                    merge_dist
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all("</p>".as_bytes())?;
+
+            ///////////////////////////////
+            // Cluster Signatures table  //
+            ///////////////////////////////
+            writer.write_all("<p><table>".as_bytes())?;
+            writer
+                .write_all("<caption>Cluster Signatures (Mean / Std. Dev.)</caption>".as_bytes())?;
+            let mut s = String::from("<tr><th>Cluster</th><th>Num. Pixels</th>");
+            for i in 0..num_files {
+                s.push_str(&format!("<th>Image {}</th>", i + 1));
+            }
+            s.push_str("</tr>");
+            writer.write_all(s.as_bytes())?;
+            for a in 0..num_clusters {
+                let mut s = format!(
+                    "<tr><td>{}</td><td class=\"numberCell\">{}</td>",
+                    a + 1,
+                    class_n[a]
+                );
+                for i in 0..num_files {
+                    s.push_str(&format!(
+                        "<td class=\"numberCell\">{:.3} / {:.3}</td>",
+                        centres[a][i], class_stdev[a][i]
+                    ));
+                }
+                s.push_str("</tr>");
+                writer.write_all(s.as_bytes())?;
+            }
+            writer.write_all("</table></p>".as_bytes())?;
+
+            ////////////////////////////////////////
+            // Cluster Centroid Distance Analysis //
+            ////////////////////////////////////////
+            writer.write_all("<p><table>".as_bytes())?;
+            writer.write_all("<caption>Cluster Centroid Distance Analysis</caption>".as_bytes())?;
+            let mut s = String::from("<tr><th></th>");
+            for a in 0..num_clusters {
+                s.push_str(&format!("<th>Cluster {}</th>", a + 1));
+            }
+            s.push_str("</tr>");
+            writer.write_all(s.as_bytes())?;
+
+            for a in 0..num_clusters {
+                let mut s = format!("<tr><td class=\"header\">Cluster {}</td>", a + 1);
+                for b in 0..num_clusters {
+                    if b >= a {
+                        let mut dist = 0f64;
+                        for i in 0..num_files {
+                            dist +=
+                                (centres[a][i] - centres[b][i]) * (centres[a][i] - centres[b][i]);
+                        }
+                        s.push_str(&format!("<td class=\"numberCell\">{:.3}</td>", dist.sqrt()));
+                    } else {
+                        s.push_str("<td></td>");
+                    }
+                }
+                s.push_str("</tr>");
+                writer.write_all(s.as_bytes())?;
+            }
+            writer.write_all("</table></p>".as_bytes())?;
+
+            //////////////////////
+            // convergence plot //
+            //////////////////////
+            writer.write_all("<br><br><h2>Convergence Plot</h2>".as_bytes())?;
+            let graph = LineGraph {
+                parent_id: "graph".to_string(),
+                width: 500f64,
+                height: 450f64,
+                data_x: xdata.clone(),
+                data_y: ydata.clone(),
+                series_labels: vec!["Line 1".to_string()].clone(),
+                x_axis_label: "Iteration".to_string(),
+                y_axis_label: "Cells with class values changed (%)".to_string(),
+                draw_points: true,
+                draw_gridlines: true,
+                draw_legend: false,
+                draw_grey_background: false,
+            };
+
+            writer.write_all(
+                &format!("<div id='graph' align=\"center\">{}</div>", graph.get_svg()).as_bytes(),
+            )?;
+
+            writer.write_all("</body>".as_bytes())?;
+            writer.write_all("</html>".as_bytes())?;
+
+            let _ = writer.flush();
+
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+
+                println!("Complete! Please see {} for output.", output_html_file);
+            }
+        }
+
+        Ok(())
+    }
+}
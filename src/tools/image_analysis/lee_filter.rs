@@ -27,7 +27,7 @@ use std::thread;
 /// processing, 24(2), 255-269.
 ///
 /// # See Also
-/// `MeanFilter`, `GaussianFilter`
+/// `MeanFilter`, `GaussianFilter`, `LeeSpeckleFilter`, `RefinedLeeFilter`
 pub struct LeeSigmaFilter {
     name: String,
     description: String,
@@ -0,0 +1,123 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::speckle_filters::{run_speckle_filter, speckle_filter_parameters, SpeckleFilterType};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool applies the adaptive Lee (1980) filter to reduce the speckle noise of a synthetic aperture radar
+/// (SAR) backscatter image (`--input`). Speckle is modelled as multiplicative noise, with a coefficient of
+/// variation of 1 / sqrt(L), where L is the equivalent number of looks of the image (`--looks`). Each grid cell
+/// is replaced by the minimum mean square error estimate of its noise-free value:
+///
+/// > *R* = *m* + *k*(*z* - *m*)
+///
+/// where *z* is the cell's value, *m* is the mean of the filter window (`--filter`, default 7 x 7 cells), and the
+/// weight *k* approaches zero in homogeneous areas, where the window's variability is explained by speckle alone,
+/// and one in heterogeneous areas, such as edges and point targets, which are therefore preserved.
+///
+/// The filter assumes that the image values are backscatter intensities, i.e. in linear power units. Images in
+/// decibels can be filtered using the `--db` flag, in which case they are converted into intensities before
+/// filtering and back into decibels afterwards. This filter differs from the `LeeSigmaFilter`, which averages
+/// the values of the window within a specified range of the centre value.
+///
+/// # Reference
+/// Lee, J. S. (1980). Digital image enhancement and noise filtering by use of local statistics. *IEEE
+/// Transactions on Pattern Analysis and Machine Intelligence*, 2, 165-168.
+///
+/// # See Also
+/// `RefinedLeeFilter`, `FrostFilter`, `GammaMapFilter`, `LeeSigmaFilter`
+pub struct LeeSpeckleFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LeeSpeckleFilter {
+    pub fn new() -> LeeSpeckleFilter {
+        // public constructor
+        let name = "LeeSpeckleFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description = "Performs a Lee adaptive speckle filter on a SAR image.".to_string();
+
+        let parameters = speckle_filter_parameters(SpeckleFilterType::Lee);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=sentinel1_vv.tif -o=output.tif --filter=7 --looks=4.4",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        LeeSpeckleFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LeeSpeckleFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_speckle_filter(
+            &self.get_tool_name(),
+            SpeckleFilterType::Lee,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 06/07/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -34,11 +34,18 @@ use std::thread;
 /// NoData values in the input image are ignored during filtering. When the neighbourhood around a grid cell extends
 /// beyond the edge of the grid, NoData values are assigned to these sites. In the event of multiple modes, i.e.
 /// neighbourhoods for which there is more than one class with tied and maximal frequency within the neighbourhood,
-/// the tool will report the first-discovered class value in the output raster. This is unlikely to be an issue
-/// for larger filter windows, but may be more problematic at smaller window sizes.
+/// the class of the centre cell is preserved if it is one of the tied classes; otherwise the lowest tied class
+/// value is output.
+///
+/// Class boundaries can be further preserved by specifying a replacement threshold (`--threshold`), i.e. the
+/// minimum proportion of the valid cells within the window that the modal class must occupy before the centre
+/// cell is replaced. For example, a threshold of 0.5 requires a strict majority of the window to belong to the
+/// modal class, preventing the erosion of the corners of class regions. The default threshold of 0.0 yields a
+/// conventional mode filter. Small, isolated class patches that survive filtering can be removed using the
+/// `EliminateSmallPatches` tool.
 ///
 /// # See Also
-/// `MedianFilter`
+/// `MedianFilter`, `EliminateSmallPatches`
 pub struct MajorityFilter {
     name: String,
     description: String,
@@ -91,6 +98,15 @@ impl MajorityFilter {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Replacement Threshold (0-1)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Minimum proportion of the window that the modal class must occupy to replace the centre cell.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -103,7 +119,7 @@ impl MajorityFilter {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --filter=25",
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --filter=25 --threshold=0.5",
             short_exe, name
         )
         .replace("*", &sep);
@@ -156,6 +172,7 @@ impl WhiteboxTool for MajorityFilter {
         let mut output_file = String::new();
         let mut filter_size_x = 11usize;
         let mut filter_size_y = 11usize;
+        let mut threshold = 0f64;
         if args.len() == 0 {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -213,6 +230,18 @@ impl WhiteboxTool for MajorityFilter {
                         .expect(&format!("Error parsing {}", flag_val))
                         as usize;
                 }
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             } else if flag_val == "-filtery" {
                 if keyval {
                     filter_size_y = vec[1]
@@ -253,6 +282,13 @@ impl WhiteboxTool for MajorityFilter {
             filter_size_y += 1;
         }
 
+        if threshold < 0f64 || threshold >= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The replacement threshold must be in the range 0.0 to 1.0.",
+            ));
+        }
+
         let midpoint_x = (filter_size_x as f64 / 2f64).floor() as isize;
         let midpoint_y = (filter_size_y as f64 / 2f64).floor() as isize;
         let mut progress: usize;
@@ -308,24 +344,23 @@ impl WhiteboxTool for MajorityFilter {
                     start_row = row - midpoint_y;
                     end_row = row + midpoint_y;
                     let mut data = vec![nodata; columns as usize];
-                    let mut histo = vec![0; num_bins];
+                    let mut histo = vec![0usize; num_bins];
                     let mut set = HashSet::new();
-                    // I realize that the above two lines could be combined
-                    // to use a HashMap instead of a Vec and a HashSet. Trouble is
-                    // Rust's HashMap is painful to use.
-                    let mut mode_bin = 0usize;
-                    let mut mode_freq = 0usize;
+                    let mut num_valid = 0usize;
+                    let mut mode_bin: usize;
+                    let mut mode_freq: usize;
                     let mut z: f64;
                     for col in 0..columns {
+                        start_col = col - midpoint_x;
+                        end_col = col + midpoint_x;
                         if col > 0 {
-                            start_col = col - midpoint_x;
-                            end_col = col + midpoint_x;
                             // remove the trailing column from the histo
                             for row2 in start_row..end_row + 1 {
                                 z = input.get_value(row2, start_col - 1);
                                 if z != nodata {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] -= 1;
+                                    num_valid -= 1;
                                     if histo[bin_val] == 0 {
                                         set.remove(&bin_val);
                                     }
@@ -338,39 +373,21 @@ impl WhiteboxTool for MajorityFilter {
                                 if z != nodata {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] += 1;
-                                    if histo[bin_val] > histo[mode_bin] {
-                                        mode_freq = histo[bin_val];
-                                        mode_bin = bin_val;
-                                    }
+                                    num_valid += 1;
                                     if histo[bin_val] == 1 {
                                         set.insert(bin_val);
                                     }
                                 }
                             }
-
-                            if histo[mode_bin] < mode_freq {
-                                mode_freq = histo[mode_bin];
-                                for x in &set {
-                                    if histo[*x] > mode_freq {
-                                        mode_freq = histo[*x];
-                                        mode_bin = *x;
-                                    }
-                                }
-                            }
                         } else {
                             // initialize the filter histo
-                            start_col = col - midpoint_x;
-                            end_col = col + midpoint_x;
                             for col2 in start_col..end_col + 1 {
                                 for row2 in start_row..end_row + 1 {
                                     z = input.get_value(row2, col2);
                                     if z != nodata {
                                         bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                         histo[bin_val] += 1;
-                                        if histo[bin_val] > mode_freq {
-                                            mode_freq = histo[bin_val];
-                                            mode_bin = bin_val;
-                                        }
+                                        num_valid += 1;
                                         if histo[bin_val] == 1 {
                                             set.insert(bin_val);
                                         }
@@ -378,8 +395,29 @@ impl WhiteboxTool for MajorityFilter {
                                 }
                             }
                         }
-                        if input.get_value(row, col) != nodata {
-                            data[col as usize] = (mode_bin as f64 + min_val_mult) / multiplier;
+
+                        z = input.get_value(row, col);
+                        if z != nodata {
+                            // find the mode, breaking ties in favour of the centre cell's class
+                            // and then the lowest class value
+                            bin_val = (z * multiplier - min_val_mult).floor() as usize;
+                            mode_bin = bin_val;
+                            mode_freq = histo[bin_val];
+                            for x in &set {
+                                if histo[*x] > mode_freq
+                                    || (histo[*x] == mode_freq && mode_bin != bin_val && *x < mode_bin)
+                                {
+                                    mode_freq = histo[*x];
+                                    mode_bin = *x;
+                                }
+                            }
+                            if mode_bin != bin_val
+                                && mode_freq as f64 > threshold * num_valid as f64
+                            {
+                                data[col as usize] = (mode_bin as f64 + min_val_mult) / multiplier;
+                            } else {
+                                data[col as usize] = z;
+                            }
                         }
                     }
                     tx1.send((row, data)).unwrap();
@@ -407,6 +445,7 @@ impl WhiteboxTool for MajorityFilter {
         output.add_metadata_entry(format!("Input file: {}", input_file));
         output.add_metadata_entry(format!("Filter size x: {}", filter_size_x));
         output.add_metadata_entry(format!("Filter size y: {}", filter_size_y));
+        output.add_metadata_entry(format!("Replacement threshold: {}", threshold));
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/06/2017
-Last Modified: 22/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -17,11 +17,11 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 // use tools::ParameterFileType;
 // use tools::ParameterType;
 // use tools::ToolParameter;
 use crate::tools::*;
+use crate::utils::thread;
 
 /// This tool performs a mean filter operation on a raster image. A mean filter, a type of low-pass filter, can be
 /// used to emphasize the longer-range variability in an image, effectively acting to smooth the image. This can be
@@ -42,6 +42,9 @@ use crate::tools::*;
 /// channel. NoData values in the input image are ignored during filtering. NoData values are assigned to all sites beyond
 /// the raster.
 ///
+/// When the input raster has a hexagonal grid, the filter is instead applied to the cells within a radius of
+/// `--filterx` / 2 cells (or `--filtery` / 2, if larger) of each cell, i.e. to a hexagonal neighbourhood.
+///
 /// # Reference
 /// Crow, F. C. (1984, January). Summed-area tables for texture mapping. In ACM SIGGRAPH computer graphics (Vol. 18, No.
 /// 3, pp. 207-212). ACM.
@@ -101,8 +104,8 @@ impl MeanFilter {
         });
 
         let sep: String = path::MAIN_SEPARATOR.to_string();
-        let p = format!("{}", env::current_dir().unwrap().display());
-        let e = format!("{}", env::current_exe().unwrap().display());
+        let p = format!("{}", env::current_dir().unwrap_or_default().display());
+        let e = format!("{}", env::current_exe().unwrap_or_default().display());
         let mut short_exe = e
             .replace(&p, "")
             .replace(".exe", "")
@@ -291,147 +294,158 @@ impl WhiteboxTool for MeanFilter {
             0f64
         };
 
-        // create the integral images
-        let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
-        let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
-
-        let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-            Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if input.configs.grid_type == GridType::Hexagonal {
+            // the neighbourhood of a hexagonal grid cell is the cells within a radius of it
+            hexagonal_mean_filter(
+                &input,
+                &mut output,
+                midpoint_x.max(midpoint_y),
+                is_rgb_image,
+                verbose,
+            );
         } else {
-            Box::new(|row: isize, col: isize| -> f64 {
-                let value = input.get_value(row, col);
-                if value != nodata {
-                    return value2i(value);
-                }
-                nodata
-            })
-        };
-
-        let mut val: f64;
-        let mut sum: f64;
-        let mut sum_n: i32;
-        let mut i_prev: f64;
-        let mut n_prev: i32;
-        for row in 0..rows {
-            sum = 0f64;
-            sum_n = 0;
-            for col in 0..columns {
-                val = input_fn(row, col);
-                if val == nodata {
-                    val = 0f64;
-                } else {
-                    val -= min_val;
-                    sum_n += 1;
-                }
-                sum += val;
-                if row > 0 {
-                    i_prev = integral[(row - 1, col)];
-                    n_prev = integral_n[(row - 1, col)];
-                    integral[(row, col)] = sum + i_prev;
-                    integral_n[(row, col)] = sum_n + n_prev;
-                } else {
-                    integral[(row, col)] = sum;
-                    integral_n[(row, col)] = sum_n;
+            // create the integral images
+            let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+            let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
+
+            let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+                Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+            } else {
+                Box::new(|row: isize, col: isize| -> f64 {
+                    let value = input.get_value(row, col);
+                    if value != nodata {
+                        return value2i(value);
+                    }
+                    nodata
+                })
+            };
+
+            let mut val: f64;
+            let mut sum: f64;
+            let mut sum_n: i32;
+            let mut i_prev: f64;
+            let mut n_prev: i32;
+            for row in 0..rows {
+                sum = 0f64;
+                sum_n = 0;
+                for col in 0..columns {
+                    val = input_fn(row, col);
+                    if val == nodata {
+                        val = 0f64;
+                    } else {
+                        val -= min_val;
+                        sum_n += 1;
+                    }
+                    sum += val;
+                    if row > 0 {
+                        i_prev = integral[(row - 1, col)];
+                        n_prev = integral_n[(row - 1, col)];
+                        integral[(row, col)] = sum + i_prev;
+                        integral_n[(row, col)] = sum_n + n_prev;
+                    } else {
+                        integral[(row, col)] = sum;
+                        integral_n[(row, col)] = sum_n;
+                    }
                 }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
-                    old_progress = progress;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Creating integral images: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
-        }
 
-        let i = Arc::new(integral); // wrap integral in an Arc
-        let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
-        let mut output = Raster::initialize_using_file(&output_file, &input);
-        let (tx, rx) = mpsc::channel();
-        let num_procs = num_cpus::get() as isize;
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let i = i.clone();
-            let i_n = i_n.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
+            let i = Arc::new(integral); // wrap integral in an Arc
+            let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
+            let (tx, rx) = mpsc::channel();
+            let num_procs = num_cpus::get() as isize;
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let i = i.clone();
+                let i_n = i_n.clone();
+                let tx1 = tx.clone();
+                thread::spawn(move || {
+                    let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+                        Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+                    } else {
+                        Box::new(|row: isize, col: isize| -> f64 {
+                            let value = input.get_value(row, col);
+                            if value != nodata {
+                                return value2i(value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
+                        // simply return the value.
+                        Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
+                    } else {
+                        // convert it back into an rgb value, using the modified intensity value.
+                        Box::new(|row: isize, col: isize, value: f64| -> f64 {
+                            if value != nodata {
+                                let (h, s, _) = value2hsi(input.get_value(row, col));
+                                return hsi2value(h, s, value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+                    let mut n: i32;
+                    let mut sum: f64;
+                    let mut mean: f64;
+                    let mut z: f64;
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        y1 = row - midpoint_y - 1;
+                        if y1 < 0 {
+                            y1 = 0;
                         }
-                        nodata
-                    })
-                };
 
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
+                        y2 = row + midpoint_y;
+                        if y2 >= rows {
+                            y2 = rows - 1;
                         }
-                        nodata
-                    })
-                };
-
-                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                let mut n: i32;
-                let mut sum: f64;
-                let mut mean: f64;
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    y1 = row - midpoint_y - 1;
-                    if y1 < 0 {
-                        y1 = 0;
-                    }
-
-                    y2 = row + midpoint_y;
-                    if y2 >= rows {
-                        y2 = rows - 1;
-                    }
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_fn(row, col);
-                        if z != nodata {
-                            x1 = col - midpoint_x - 1;
-                            if x1 < 0 {
-                                x1 = 0;
-                            }
-
-                            x2 = col + midpoint_x;
-                            if x2 >= columns {
-                                x2 = columns - 1;
-                            }
-                            n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
-                            if n > 0 {
-                                sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
-                                mean = sum / n as f64 + min_val;
-                                data[col as usize] = output_fn(row, col, mean);
-                            } else {
-                                data[col as usize] = output_fn(row, col, 0f64);
+                        let mut data = vec![nodata; columns as usize];
+                        for col in 0..columns {
+                            z = input_fn(row, col);
+                            if z != nodata {
+                                x1 = col - midpoint_x - 1;
+                                if x1 < 0 {
+                                    x1 = 0;
+                                }
+
+                                x2 = col + midpoint_x;
+                                if x2 >= columns {
+                                    x2 = columns - 1;
+                                }
+                                n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
+                                if n > 0 {
+                                    sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
+                                    mean = sum / n as f64 + min_val;
+                                    data[col as usize] = output_fn(row, col, mean);
+                                } else {
+                                    data[col as usize] = output_fn(row, col, 0f64);
+                                }
                             }
                         }
-                    }
 
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+                        tx1.send((row, data)).unwrap();
+                    }
+                });
+            }
 
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+            for row in 0..rows {
+                let data = rx.recv().expect("Error receiving data from thread.");
+                output.set_row_data(data.0, data.1);
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
         }
@@ -469,6 +483,80 @@ impl WhiteboxTool for MeanFilter {
     }
 }
 
+/// Assigns each cell of a hexagonal grid the mean of the valid cells within `radius`
+/// cells of it.
+fn hexagonal_mean_filter(
+    input: &Arc<Raster>,
+    output: &mut Raster,
+    radius: isize,
+    is_rgb_image: bool,
+    verbose: bool,
+) {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    // the neighbourhoods of cells in even and odd rows differ
+    let offsets = Arc::new([
+        grid::hex_neighbourhood(0, radius),
+        grid::hex_neighbourhood(1, radius),
+    ]);
+    let (tx, rx) = mpsc::channel();
+    let num_procs = num_cpus::get() as isize;
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let offsets = offsets.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let input_fn = |row: isize, col: isize| -> f64 {
+                let value = input.get_value(row, col);
+                if is_rgb_image && value != nodata {
+                    return value2i(value);
+                }
+                value
+            };
+            let (mut sum, mut n): (f64, f64);
+            let mut z: f64;
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    if input.get_value(row, col) != nodata {
+                        sum = 0f64;
+                        n = 0f64;
+                        for (dr, dc) in &offsets[(row & 1) as usize] {
+                            z = input_fn(row + dr, col + dc);
+                            if z != nodata {
+                                sum += z;
+                                n += 1f64;
+                            }
+                        }
+                        data[col as usize] = if is_rgb_image {
+                            let (h, s, _) = value2hsi(input.get_value(row, col));
+                            hsi2value(h, s, sum / n)
+                        } else {
+                            sum / n
+                        };
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for row in 0..rows {
+        let data = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(data.0, data.1);
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+}
+
 fn value2i(value: f64) -> f64 {
     let r = (value as u32 & 0xFF) as f64 / 255f64;
     let g = ((value as u32 >> 8) & 0xFF) as f64 / 255f64;
@@ -1,23 +1,34 @@
 // private sub-module defined in other files
 mod adaptive_filter;
+mod apply_classifier;
+mod atmospheric_correction;
 mod balance_contrast_enhancement;
 mod bilateral_filter;
 mod change_vector_analysis;
 mod closing;
+mod cloud_mask;
 mod conservative_smoothing_filter;
 mod corner_detection;
 mod correct_vignetting;
 mod create_colour_composite;
+mod dilation;
 mod direct_decorrelation_stretch;
 mod diversity_filter;
 mod dog_filter;
 mod edge_preserving_mean_filter;
 mod emboss_filter;
+mod enhanced_vegetation_index;
+mod erosion;
 mod fast_almost_gaussian_filter;
 mod flip_image;
+mod forward_fft;
+mod frost_filter;
+mod frequency_domain_filter;
 mod gamma_correction;
+mod gamma_map_filter;
 mod gaussian_contrast_stretch;
 mod gaussian_filter;
+mod glcm_texture;
 mod highpass_filter;
 mod highpass_median_filter;
 mod histogram_equalization;
@@ -26,10 +37,13 @@ mod histogram_matching_two_images;
 mod ihs_to_rgb;
 mod image_stack_profile;
 mod integral_image;
+mod inverse_fft;
+mod isodata_clustering;
 mod k_means_clustering;
 mod k_nearest_mean_filter;
 mod laplacian_filter;
 mod lee_filter;
+mod lee_speckle_filter;
 mod line_detection_filter;
 mod line_thin;
 mod log_filter;
@@ -40,9 +54,15 @@ mod median_filter;
 mod min_filter;
 mod min_max_contrast_stretch;
 mod modified_k_means_clustering;
+mod modified_normalized_difference_water_index;
+mod morphological_filter;
+mod morphology;
 mod mosaic;
 mod mosaic_with_feathering;
+mod normalized_burn_ratio;
 mod normalized_difference_index;
+mod normalized_difference_snow_index;
+mod normalized_difference_water_index;
 mod olympic_filter;
 mod opening;
 mod pan_sharpening;
@@ -50,43 +70,63 @@ mod percentage_contrast_stretch;
 mod percentile_filter;
 mod prewitt_filter;
 mod range_filter;
+mod refined_lee_filter;
+mod remove_periodic_noise;
 mod remove_spurs;
 mod resample;
 mod rgb_to_ihs;
 mod roberts_filter;
 mod scharr_filter;
+mod selective_pca_change_detection;
 mod sigmoidal_contrast_stretch;
+mod slic_segmentation;
 mod sobel_filter;
+mod soil_adjusted_vegetation_index;
+mod spectral_index;
+mod speckle_filters;
+mod spectral_indices;
 mod split_colour_composite;
 mod stdev_contrast_stretch;
 mod stdev_filter;
 mod thicken_line;
 mod tophat;
 mod total_filter;
+mod train_classifier;
 mod unsharp_masking;
 mod user_defined_weights_filter;
 mod write_func_memory_insertion;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::adaptive_filter::AdaptiveFilter;
+pub use self::apply_classifier::ApplyClassifier;
+pub use self::atmospheric_correction::AtmosphericCorrection;
 pub use self::balance_contrast_enhancement::BalanceContrastEnhancement;
 pub use self::bilateral_filter::BilateralFilter;
 pub use self::change_vector_analysis::ChangeVectorAnalysis;
 pub use self::closing::Closing;
+pub use self::cloud_mask::CloudMask;
 pub use self::conservative_smoothing_filter::ConservativeSmoothingFilter;
 pub use self::corner_detection::CornerDetection;
 pub use self::correct_vignetting::CorrectVignetting;
 pub use self::create_colour_composite::CreateColourComposite;
+pub use self::dilation::Dilation;
 pub use self::direct_decorrelation_stretch::DirectDecorrelationStretch;
 pub use self::diversity_filter::DiversityFilter;
 pub use self::dog_filter::DiffOfGaussianFilter;
 pub use self::edge_preserving_mean_filter::EdgePreservingMeanFilter;
 pub use self::emboss_filter::EmbossFilter;
+pub use self::enhanced_vegetation_index::EnhancedVegetationIndex;
+pub use self::erosion::Erosion;
 pub use self::fast_almost_gaussian_filter::FastAlmostGaussianFilter;
 pub use self::flip_image::FlipImage;
+pub use self::forward_fft::ForwardFft;
+pub use self::frost_filter::FrostFilter;
+pub use self::frequency_domain_filter::FrequencyDomainFilter;
 pub use self::gamma_correction::GammaCorrection;
+pub use self::gamma_map_filter::GammaMapFilter;
 pub use self::gaussian_contrast_stretch::GaussianContrastStretch;
 pub use self::gaussian_filter::GaussianFilter;
+pub use self::glcm_texture::GlcmTexture;
 pub use self::highpass_filter::HighPassFilter;
 pub use self::highpass_median_filter::HighPassMedianFilter;
 pub use self::histogram_equalization::HistogramEqualization;
@@ -95,10 +135,13 @@ pub use self::histogram_matching_two_images::HistogramMatchingTwoImages;
 pub use self::ihs_to_rgb::IhsToRgb;
 pub use self::image_stack_profile::ImageStackProfile;
 pub use self::integral_image::IntegralImage;
+pub use self::inverse_fft::InverseFft;
+pub use self::isodata_clustering::IsodataClustering;
 pub use self::k_means_clustering::KMeansClustering;
 pub use self::k_nearest_mean_filter::KNearestMeanFilter;
 pub use self::laplacian_filter::LaplacianFilter;
 pub use self::lee_filter::LeeSigmaFilter;
+pub use self::lee_speckle_filter::LeeSpeckleFilter;
 pub use self::line_detection_filter::LineDetectionFilter;
 pub use self::line_thin::LineThinning;
 pub use self::log_filter::LaplacianOfGaussianFilter;
@@ -109,9 +152,14 @@ pub use self::median_filter::MedianFilter;
 pub use self::min_filter::MinimumFilter;
 pub use self::min_max_contrast_stretch::MinMaxContrastStretch;
 pub use self::modified_k_means_clustering::ModifiedKMeansClustering;
+pub use self::modified_normalized_difference_water_index::ModifiedNormalizedDifferenceWaterIndex;
+pub use self::morphological_filter::MorphologicalFilter;
 pub use self::mosaic::Mosaic;
 pub use self::mosaic_with_feathering::MosaicWithFeathering;
+pub use self::normalized_burn_ratio::NormalizedBurnRatio;
 pub use self::normalized_difference_index::NormalizedDifferenceIndex;
+pub use self::normalized_difference_snow_index::NormalizedDifferenceSnowIndex;
+pub use self::normalized_difference_water_index::NormalizedDifferenceWaterIndex;
 pub use self::olympic_filter::OlympicFilter;
 pub use self::opening::Opening;
 pub use self::pan_sharpening::PanchromaticSharpening;
@@ -119,19 +167,26 @@ pub use self::percentage_contrast_stretch::PercentageContrastStretch;
 pub use self::percentile_filter::PercentileFilter;
 pub use self::prewitt_filter::PrewittFilter;
 pub use self::range_filter::RangeFilter;
+pub use self::refined_lee_filter::RefinedLeeFilter;
+pub use self::remove_periodic_noise::RemovePeriodicNoise;
 pub use self::remove_spurs::RemoveSpurs;
 pub use self::resample::Resample;
 pub use self::rgb_to_ihs::RgbToIhs;
 pub use self::roberts_filter::RobertsCrossFilter;
 pub use self::scharr_filter::ScharrFilter;
+pub use self::selective_pca_change_detection::SelectivePcaChangeDetection;
 pub use self::sigmoidal_contrast_stretch::SigmoidalContrastStretch;
+pub use self::slic_segmentation::SlicSegmentation;
 pub use self::sobel_filter::SobelFilter;
+pub use self::soil_adjusted_vegetation_index::SoilAdjustedVegetationIndex;
+pub use self::spectral_index::SpectralIndex;
 pub use self::split_colour_composite::SplitColourComposite;
 pub use self::stdev_contrast_stretch::StandardDeviationContrastStretch;
 pub use self::stdev_filter::StandardDeviationFilter;
 pub use self::thicken_line::ThickenRasterLine;
 pub use self::tophat::TophatTransform;
 pub use self::total_filter::TotalFilter;
+pub use self::train_classifier::TrainClassifier;
 pub use self::unsharp_masking::UnsharpMasking;
 pub use self::user_defined_weights_filter::UserDefinedWeightsFilter;
 pub use self::write_func_memory_insertion::WriteFunctionMemoryInsertion;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 30/12/2017
-Last Modified: 24/02/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -10,6 +10,7 @@ use crate::raster::*;
 use crate::rendering::html::*;
 use crate::rendering::LineGraph;
 use crate::tools::*;
+use crate::utils::random;
 use num_cpus;
 use rand::prelude::*;
 use std::env;
@@ -32,12 +33,15 @@ use std::thread;
 /// than a user-defined threshold. The main difference between this algorithm and the ISODATA
 /// technique is that clusters can not be broken apart into two smaller clusters.
 ///
+/// Multi-band images, including RGB colour composites, may be input (`--inputs`), in which case
+/// each band is treated as a separate input image.
+///
 /// # Reference
 /// Mather, P. M., & Koch, M. (2011). Computer processing of remotely-sensed images: an
 /// introduction. John Wiley & Sons.
 ///
 /// # See Also
-/// `KMeansClustering`
+/// `KMeansClustering`, `IsodataClustering`
 pub struct ModifiedKMeansClustering {
     name: String,
     description: String,
@@ -340,10 +344,15 @@ impl WhiteboxTool for ModifiedKMeansClustering {
             cmd = input_files_str.split(",");
             input_files = cmd.collect::<Vec<&str>>();
         }
-        let num_files = input_files.len();
-        if num_files < 2 {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                                "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
+        let input_files: Vec<&str> = input_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+        if input_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There is something incorrect about the input files. At least two inputs are required to operate this tool.",
+            ));
         }
 
         if max_iterations < 2 || max_iterations > 250 {
@@ -372,44 +381,60 @@ impl WhiteboxTool for ModifiedKMeansClustering {
         let mut rows = -1isize;
         let mut columns = -1isize;
 
+        // multi-band images contribute each of their bands as a separate input
+        let mut input_raster: Vec<Raster> = vec![];
+        let mut band_names: Vec<String> = vec![];
+        for i in 0..input_files.len() {
+            println!("Reading file {} of {}", i + 1, input_files.len());
+            let mut input_file = input_files[i].trim().to_owned();
+            if !input_file.contains(&sep) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            let raster = Raster::new(&input_file, "r")?;
+            let raster = if input_raster.is_empty() {
+                raster
+            } else {
+                // make sure the inputs share the same grid and CRS
+                align_raster_input(&input_raster[0], raster)?
+            };
+            let num_bands = raster.num_bands();
+            if num_bands > 1 {
+                for b in 0..num_bands {
+                    input_raster.push(raster.get_band(b));
+                    band_names.push(format!("{} (band {})", input_files[i].trim(), b + 1));
+                }
+            } else {
+                input_raster.push(raster);
+                band_names.push(input_files[i].trim().to_owned());
+            }
+        }
+        let num_files = input_raster.len();
+        if num_files < 2 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
+        }
+
         let mut nodata: Vec<f64> = Vec::with_capacity(num_files);
         let mut minimum: Vec<f64> = Vec::with_capacity(num_files);
         let mut maximum: Vec<f64> = Vec::with_capacity(num_files);
-        let mut input_raster: Vec<Raster> = Vec::with_capacity(num_files);
-
         for i in 0..num_files {
-            println!("Reading file {} of {}", i + 1, num_files);
-            if !input_files[i].trim().is_empty() {
-                let mut input_file = input_files[i].trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
-                input_raster.push(Raster::new(&input_file, "r")?);
-                nodata.push(input_raster[i].configs.nodata);
-                minimum.push(input_raster[i].configs.minimum);
-                maximum.push(input_raster[i].configs.maximum);
-
-                if rows == -1 || columns == -1 {
-                    rows = input_raster[i].configs.rows as isize;
-                    columns = input_raster[i].configs.columns as isize;
-                    if num_classes < 2 || num_classes as isize > (rows * columns) {
-                        return Err(Error::new(
-                            ErrorKind::InvalidInput,
-                            "Number of classes should be between 2 and rows x columns.",
-                        ));
-                    }
-                // if min_class_size > ((rows * columns) as usize / num_classes) {
-                //     return Err(Error::new(ErrorKind::InvalidInput,
-                //         "Min class size should be less than rows x columns / num_classes."));
-                // }
-                } else {
-                    if input_raster[i].configs.rows as isize != rows
-                        || input_raster[i].configs.columns as isize != columns
-                    {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
-                    }
+            nodata.push(input_raster[i].configs.nodata);
+            minimum.push(input_raster[i].configs.minimum);
+            maximum.push(input_raster[i].configs.maximum);
+
+            if rows == -1 || columns == -1 {
+                rows = input_raster[i].configs.rows as isize;
+                columns = input_raster[i].configs.columns as isize;
+                if num_classes < 2 || num_classes as isize > (rows * columns) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Number of classes should be between 2 and rows x columns.",
+                    ));
                 }
+            // if min_class_size > ((rows * columns) as usize / num_classes) {
+            //     return Err(Error::new(ErrorKind::InvalidInput,
+            //         "Min class size should be less than rows x columns / num_classes."));
+            // }
             }
         }
 
@@ -426,7 +451,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
 
         // if initialization_mode == 0 {
         // initialize the class centres randomly
-        let mut rng = thread_rng();
+        let mut rng = random::rng(0);
         for a in 0..num_classes {
             let row = rng.gen_range(0, rows); // Range::new(0, rows).ind_sample(&mut rng);
             let col = rng.gen_range(0, columns); // Range::new(0, columns).ind_sample(&mut rng);
@@ -686,7 +711,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                     &format!(
                         "<strong>Image {}</strong>: {}<br>",
                         i + 1,
-                        input_files[i].clone()
+                        band_names[i].clone()
                     )
                     .as_bytes(),
                 )?;
@@ -0,0 +1,123 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the modified normalized difference water index (MNDWI) of Xu (2006):
+///
+/// > MNDWI = (Green - SWIR1) / (Green + SWIR1)
+///
+/// where SWIR1 is the first shortwave infrared band (e.g. Sentinel-2 band 11 or Landsat 8 OLI band 6). By
+/// replacing the near-infrared band of the `NormalizedDifferenceWaterIndex` with a shortwave infrared band, the
+/// MNDWI better suppresses the signal of built-up land, which is commonly confused with open water in the NDWI,
+/// and is therefore preferred for mapping water in urban areas. Open water generally has positive values.
+///
+/// The green and SWIR1 bands are specified with the band mapping parameter (`--bands`), either as single-band
+/// raster files, e.g. `--bands="green=B03.tif;swir1=B11.tif"`, or as the (one-based) numbers of bands within a
+/// multi-band image (`--input`), e.g. `--bands="green=3;swir1=6"`. The bands must share the same grid; use the
+/// `--resample_inputs` flag to resample the 20 m shortwave infrared bands of Sentinel-2 onto the 10 m grid of the
+/// green band. Grid cells that are NoData in either band are assigned NoData in the output.
+///
+/// # Reference
+/// Xu, H. (2006). Modification of normalised difference water index (NDWI) to enhance open water features in
+/// remotely sensed imagery. *International Journal of Remote Sensing*, 27(14), 3025-3033.
+///
+/// # See Also
+/// `NormalizedDifferenceWaterIndex`, `NormalizedDifferenceSnowIndex`, `SpectralIndex`
+pub struct ModifiedNormalizedDifferenceWaterIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ModifiedNormalizedDifferenceWaterIndex {
+    pub fn new() -> ModifiedNormalizedDifferenceWaterIndex {
+        // public constructor
+        let name = "ModifiedNormalizedDifferenceWaterIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the modified normalized difference water index (MNDWI) from green and shortwave infrared image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("mndwi"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --bands=\"green=B03.tif;swir1=B11.tif\" -o=mndwi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ModifiedNormalizedDifferenceWaterIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ModifiedNormalizedDifferenceWaterIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("mndwi"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -0,0 +1,133 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::morphology::{morphology_tool_parameters, run_morphology};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool performs grey-scale and binary mathematical morphology operations on an input image (`--input`)
+/// using an arbitrary structuring element. The operation (`--operation`) is one of:
+///
+/// | Operation | Definition |
+/// |-----------|------------|
+/// | erode | The minimum of the values under the structuring element |
+/// | dilate | The maximum of the values under the (reflected) structuring element |
+/// | open | The dilation of the erosion; removes bright features smaller than the element |
+/// | close | The erosion of the dilation; removes dark features smaller than the element |
+/// | white_tophat | The image minus its opening; isolates bright features smaller than the element |
+/// | black_tophat | The closing minus the image; isolates dark features smaller than the element |
+/// | gradient | The dilation minus the erosion; highlights edges |
+///
+/// The structuring element is specified by its shape (`--shape`), either a `disk` or a `square`, and its size
+/// (`--size`), i.e. the disk's diameter or the square's width, in grid cells. Alternatively, an arbitrary
+/// structuring element can be read from a kernel file (`--kernel`), a text file with one row of comma- or
+/// space-separated values per line, with an odd number of rows and columns, in which the non-zero values are
+/// the members of the element and the centre of the kernel is its origin (i.e. the same format as the kernel
+/// files of the `UserDefinedWeightsFilter` tool).
+///
+/// When the `--binary` flag is specified, positive input values are treated as foreground (1) and all other
+/// values as background (0), which is useful for cleaning classified images and masks, e.g. removing isolated
+/// cells with an opening or filling small gaps with a closing. Applied to a digital elevation model, the
+/// operations are useful for geomorphometric feature extraction; the white top-hat transform highlights
+/// positive relief features smaller than the structuring element, such as hummocks, ridges, and mounds, while
+/// the black top-hat transform highlights negative features, such as valleys, channels, and depressions. A disk
+/// structuring element avoids the directional bias of a square element in these applications.
+///
+/// NoData cells are ignored within the structuring element and remain NoData in the output.
+///
+/// # See Also
+/// `Erosion`, `Dilation`, `Opening`, `Closing`, `TophatTransform`, `UserDefinedWeightsFilter`
+pub struct MorphologicalFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MorphologicalFilter {
+    pub fn new() -> MorphologicalFilter {
+        // public constructor
+        let name = "MorphologicalFilter".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Performs a grey-scale or binary morphological operation using a disk, square, or custom structuring element.".to_string();
+
+        let parameters = morphology_tool_parameters(None);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=dem.tif -o=output.tif --operation=white_tophat --shape=disk --size=21",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        MorphologicalFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for MorphologicalFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_morphology(
+            &self.get_tool_name(),
+            None,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -0,0 +1,546 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The mathematical morphology tools (Erosion, Dilation, and MorphologicalFilter) are thin
+wrappers around this module. All of the operations are composed of two elementary passes, an
+erosion (the minimum of the cell values under the structuring element) and a dilation (the
+maximum of the values under the reflected structuring element), each of which is parallelized
+across rows. Binary images are handled as grey-scale images with the values 0 and 1, for which
+the minimum and maximum reduce to the binary set operations.
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MorphOperation {
+    Erode,
+    Dilate,
+    Open,
+    Close,
+    WhiteTophat,
+    BlackTophat,
+    Gradient,
+}
+
+impl MorphOperation {
+    fn from_str(s: &str) -> Option<MorphOperation> {
+        match s.trim().to_lowercase().replace("-", "_").as_str() {
+            "erode" | "erosion" => Some(MorphOperation::Erode),
+            "dilate" | "dilation" => Some(MorphOperation::Dilate),
+            "open" | "opening" => Some(MorphOperation::Open),
+            "close" | "closing" => Some(MorphOperation::Close),
+            "white_tophat" | "tophat" => Some(MorphOperation::WhiteTophat),
+            "black_tophat" => Some(MorphOperation::BlackTophat),
+            "gradient" => Some(MorphOperation::Gradient),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            MorphOperation::Erode => "erode",
+            MorphOperation::Dilate => "dilate",
+            MorphOperation::Open => "open",
+            MorphOperation::Close => "close",
+            MorphOperation::WhiteTophat => "white_tophat",
+            MorphOperation::BlackTophat => "black_tophat",
+            MorphOperation::Gradient => "gradient",
+        }
+    }
+}
+
+/// The (row, column) offsets of the cells of a structuring element from its centre.
+type StructuringElement = Vec<(isize, isize)>;
+
+/// Returns the parameters shared by the morphology tools. The `--operation` parameter is
+/// included for the generic tool only, i.e. when `operation` is `None`.
+pub fn morphology_tool_parameters(operation: Option<MorphOperation>) -> Vec<ToolParameter> {
+    let mut parameters = vec![];
+    parameters.push(ToolParameter {
+        name: "Input File".to_owned(),
+        flags: vec!["-i".to_owned(), "--input".to_owned()],
+        description: "Input raster file.".to_owned(),
+        parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: false,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Output File".to_owned(),
+        flags: vec!["-o".to_owned(), "--output".to_owned()],
+        description: "Output raster file.".to_owned(),
+        parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: false,
+    });
+
+    if operation.is_none() {
+        parameters.push(ToolParameter {
+            name: "Operation".to_owned(),
+            flags: vec!["--operation".to_owned()],
+            description: "Morphological operation to perform.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "erode".to_owned(),
+                "dilate".to_owned(),
+                "open".to_owned(),
+                "close".to_owned(),
+                "white_tophat".to_owned(),
+                "black_tophat".to_owned(),
+                "gradient".to_owned(),
+            ]),
+            default_value: Some("open".to_owned()),
+            optional: false,
+        });
+    }
+
+    parameters.push(ToolParameter {
+        name: "Structuring Element Shape".to_owned(),
+        flags: vec!["--shape".to_owned()],
+        description:
+            "Shape of the structuring element; options are 'disk', 'square', and 'custom'."
+                .to_owned(),
+        parameter_type: ParameterType::OptionList(vec![
+            "disk".to_owned(),
+            "square".to_owned(),
+            "custom".to_owned(),
+        ]),
+        default_value: Some("disk".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Structuring Element Size".to_owned(),
+        flags: vec!["--size".to_owned()],
+        description: "Diameter (disk) or width (square) of the structuring element, in grid cells."
+            .to_owned(),
+        parameter_type: ParameterType::Integer,
+        default_value: Some("3".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Structuring Element File (optional)".to_owned(),
+        flags: vec!["--kernel".to_owned()],
+        description:
+            "Text file of a custom structuring element, in which non-zero values are members."
+                .to_owned(),
+        parameter_type: ParameterType::ExistingFile(ParameterFileType::Text),
+        default_value: None,
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Treat input as binary?".to_owned(),
+        flags: vec!["--binary".to_owned()],
+        description:
+            "Flag indicating that positive values are foreground and all other values background."
+                .to_owned(),
+        parameter_type: ParameterType::Boolean,
+        default_value: Some("false".to_owned()),
+        optional: true,
+    });
+
+    parameters
+}
+
+/// Runs a morphology tool. The operation is either given (for the Erosion and Dilation
+/// tools) or read from the `--operation` argument (for the MorphologicalFilter tool).
+pub fn run_morphology(
+    tool_name: &str,
+    operation: Option<MorphOperation>,
+    args: Vec<String>,
+    working_directory: &str,
+    verbose: bool,
+) -> Result<(), Error> {
+    let mut input_file = String::new();
+    let mut output_file = String::new();
+    let mut operation_str = String::new();
+    let mut shape = "disk".to_string();
+    let mut size = 3usize;
+    let mut kernel_file = String::new();
+    let mut binary = false;
+
+    if args.len() == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Tool run with no parameters.",
+        ));
+    }
+    for i in 0..args.len() {
+        let mut arg = args[i].replace("\"", "");
+        arg = arg.replace("\'", "");
+        let cmd = arg.split("="); // in case an equals sign was used
+        let vec = cmd.collect::<Vec<&str>>();
+        let mut keyval = false;
+        if vec.len() > 1 {
+            keyval = true;
+        }
+        let flag_val = vec[0].to_lowercase().replace("--", "-");
+        if flag_val == "-i" || flag_val == "-input" {
+            input_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-o" || flag_val == "-output" {
+            output_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-operation" {
+            operation_str = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-shape" {
+            shape = if keyval {
+                vec[1].to_lowercase()
+            } else {
+                args[i + 1].to_lowercase()
+            };
+        } else if flag_val == "-size" {
+            size = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize
+            };
+        } else if flag_val == "-kernel" {
+            kernel_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-binary" {
+            if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                binary = true;
+            }
+        }
+    }
+
+    if verbose {
+        println!("***************{}", "*".repeat(tool_name.len()));
+        println!("* Welcome to {} *", tool_name);
+        println!("***************{}", "*".repeat(tool_name.len()));
+    }
+
+    let sep: String = path::MAIN_SEPARATOR.to_string();
+
+    if !input_file.contains(&sep) && !input_file.contains("/") {
+        input_file = format!("{}{}", working_directory, input_file);
+    }
+    if !output_file.contains(&sep) && !output_file.contains("/") {
+        output_file = format!("{}{}", working_directory, output_file);
+    }
+
+    let operation = match operation {
+        Some(op) => op,
+        None => MorphOperation::from_str(&operation_str).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized morphological operation '{}'.",
+                    operation_str.trim()
+                ),
+            )
+        })?,
+    };
+
+    if !kernel_file.trim().is_empty() {
+        shape = "custom".to_string();
+    }
+    let element: StructuringElement = if shape.contains("custom") {
+        if kernel_file.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A custom structuring element requires a kernel file (--kernel).",
+            ));
+        }
+        if !kernel_file.contains(&sep) && !kernel_file.contains("/") {
+            kernel_file = format!("{}{}", working_directory, kernel_file);
+        }
+        read_kernel_file(&kernel_file)?
+    } else if shape.contains("square") {
+        square_element(size.max(1))
+    } else {
+        disk_element(size.max(1))
+    };
+
+    if verbose {
+        println!("Reading data...")
+    };
+    let input = Raster::new(&input_file, "r")?;
+
+    let start = Instant::now();
+
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+
+    // the image, with NoData represented by NaN
+    let mut image = vec![f64::NAN; (rows * columns) as usize];
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = input.get_value(row, col);
+            if z != nodata {
+                image[(row * columns + col) as usize] = if binary {
+                    if z > 0f64 {
+                        1f64
+                    } else {
+                        0f64
+                    }
+                } else {
+                    z
+                };
+            }
+        }
+    }
+    let image = Arc::new(image);
+    let element = Arc::new(element);
+
+    let result = match operation {
+        MorphOperation::Erode => elementary_pass(&image, &element, rows, columns, false),
+        MorphOperation::Dilate => elementary_pass(&image, &element, rows, columns, true),
+        MorphOperation::Open | MorphOperation::WhiteTophat => {
+            let eroded = Arc::new(elementary_pass(&image, &element, rows, columns, false));
+            elementary_pass(&eroded, &element, rows, columns, true)
+        }
+        MorphOperation::Close | MorphOperation::BlackTophat => {
+            let dilated = Arc::new(elementary_pass(&image, &element, rows, columns, true));
+            elementary_pass(&dilated, &element, rows, columns, false)
+        }
+        MorphOperation::Gradient => {
+            let dilated = elementary_pass(&image, &element, rows, columns, true);
+            let eroded = elementary_pass(&image, &element, rows, columns, false);
+            dilated
+                .iter()
+                .zip(eroded.iter())
+                .map(|(d, e)| d - e)
+                .collect()
+        }
+    };
+
+    let mut output = Raster::initialize_using_file(&output_file, &input);
+    if binary {
+        output.configs.data_type = DataType::U8;
+        output.configs.nodata = 255f64;
+        output.configs.photometric_interp = PhotometricInterpretation::Boolean;
+    } else if operation == MorphOperation::Gradient
+        || operation == MorphOperation::WhiteTophat
+        || operation == MorphOperation::BlackTophat
+    {
+        output.configs.data_type = DataType::F32;
+    }
+    output.configs.palette = input.configs.palette.clone();
+    let out_nodata = output.configs.nodata;
+    for row in 0..rows {
+        let mut data = vec![out_nodata; columns as usize];
+        for col in 0..columns {
+            let i = (row * columns + col) as usize;
+            if image[i].is_nan() || result[i].is_nan() {
+                continue;
+            }
+            data[col as usize] = match operation {
+                MorphOperation::WhiteTophat => image[i] - result[i],
+                MorphOperation::BlackTophat => result[i] - image[i],
+                _ => result[i],
+            };
+        }
+        output.set_row_data(row, data);
+    }
+
+    let elapsed_time = get_formatted_elapsed_time(start);
+    output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", tool_name));
+    output.add_metadata_entry(format!("Input file: {}", input_file));
+    output.add_metadata_entry(format!("Operation: {}", operation.name()));
+    if shape.contains("custom") {
+        output.add_metadata_entry(format!("Structuring element file: {}", kernel_file));
+    } else {
+        output.add_metadata_entry(format!("Structuring element: {} {}", shape, size));
+    }
+    output.add_metadata_entry(format!("Binary: {}", binary));
+    output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+    if verbose {
+        println!("Saving data...")
+    };
+    let _ = match output.write() {
+        Ok(_) => {
+            if verbose {
+                println!("Output file written")
+            }
+        }
+        Err(e) => return Err(e),
+    };
+    if verbose {
+        println!(
+            "{}",
+            &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+        );
+    }
+
+    Ok(())
+}
+
+/// Erodes (the minimum of the values under the structuring element) or dilates (the maximum
+/// of the values under the reflected structuring element) an image, ignoring NaN values.
+fn elementary_pass(
+    image: &Arc<Vec<f64>>,
+    element: &Arc<StructuringElement>,
+    rows: isize,
+    columns: isize,
+    dilate: bool,
+) -> Vec<f64> {
+    let (tx, rx) = mpsc::channel();
+    let num_procs = num_cpus::get() as isize;
+    for tid in 0..num_procs {
+        let image = image.clone();
+        let element = element.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![f64::NAN; columns as usize];
+                for col in 0..columns {
+                    if image[(row * columns + col) as usize].is_nan() {
+                        continue;
+                    }
+                    let mut value = if dilate {
+                        f64::NEG_INFINITY
+                    } else {
+                        f64::INFINITY
+                    };
+                    for (dy, dx) in element.iter() {
+                        let (r, c) = if dilate {
+                            (row - dy, col - dx)
+                        } else {
+                            (row + dy, col + dx)
+                        };
+                        if r < 0 || c < 0 || r >= rows || c >= columns {
+                            continue;
+                        }
+                        let z = image[(r * columns + c) as usize];
+                        if z.is_nan() {
+                            continue;
+                        }
+                        if (dilate && z > value) || (!dilate && z < value) {
+                            value = z;
+                        }
+                    }
+                    if value.is_finite() {
+                        data[col as usize] = value;
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+    let mut output = vec![f64::NAN; (rows * columns) as usize];
+    for _ in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        let start = (row * columns) as usize;
+        output[start..start + columns as usize].copy_from_slice(&data);
+    }
+    output
+}
+
+fn square_element(size: usize) -> StructuringElement {
+    let lower = -((size as isize - 1) / 2);
+    let upper = lower + size as isize - 1;
+    let mut element = vec![];
+    for dy in lower..=upper {
+        for dx in lower..=upper {
+            element.push((dy, dx));
+        }
+    }
+    element
+}
+
+fn disk_element(size: usize) -> StructuringElement {
+    let radius = size as f64 / 2f64;
+    let r = (size / 2) as isize;
+    let mut element = vec![];
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if ((dy * dy + dx * dx) as f64).sqrt() <= radius {
+                element.push((dy, dx));
+            }
+        }
+    }
+    element
+}
+
+/// Reads a structuring element from a text file with one row of comma- or space-separated
+/// values per line, in the format of the UserDefinedWeightsFilter kernel files. Non-zero
+/// values are members of the element, and the centre of the kernel is its origin.
+fn read_kernel_file(file_name: &str) -> Result<StructuringElement, Error> {
+    let f = BufReader::new(File::open(file_name)?);
+    let mut values: Vec<Vec<f64>> = vec![];
+    for line in f.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut row = vec![];
+        for s in line.split(|c: char| c == ',' || c.is_whitespace()) {
+            if s.trim().is_empty() {
+                continue;
+            }
+            row.push(s.trim().parse::<f64>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Unrecognized value '{}' in the kernel file.", s.trim()),
+                )
+            })?);
+        }
+        values.push(row);
+    }
+    let kernel_rows = values.len();
+    let kernel_columns = values.first().map(|r| r.len()).unwrap_or(0);
+    if kernel_rows == 0
+        || kernel_rows % 2 == 0
+        || kernel_columns % 2 == 0
+        || values.iter().any(|r| r.len() != kernel_columns)
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The kernel file must contain an odd number of rows and columns of equal length.",
+        ));
+    }
+    let (mid_y, mid_x) = ((kernel_rows / 2) as isize, (kernel_columns / 2) as isize);
+    let mut element = vec![];
+    for (r, row) in values.iter().enumerate() {
+        for (c, v) in row.iter().enumerate() {
+            if *v != 0f64 {
+                element.push((r as isize - mid_y, c as isize - mid_x));
+            }
+        }
+    }
+    if element.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The kernel file does not contain any non-zero values.",
+        ));
+    }
+    Ok(element)
+}
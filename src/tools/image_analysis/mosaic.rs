@@ -2,11 +2,12 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 02/01/2018
-Last Modified: 02/01/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
+use crate::structures::Array2D;
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -23,9 +24,27 @@ use std::thread;
 /// cells in the output image will be assigned the corresponding value determined from the
 /// first image found in the list to possess an overlapping coordinate.
 ///
+/// The way in which areas of overlap are resolved can be changed using the `--overlap`
+/// parameter. The options are 'first' (the default), in which the first image in the list
+/// with valid data takes precedence, 'last', in which the last such image takes precedence,
+/// 'min' and 'max', in which the lowest or highest of the overlapping values is output, and
+/// 'blend'. The blend method outputs the weighted average of the overlapping values, where
+/// each image's weight is its distance, in grid cells, to the nearest edge of its valid data,
+/// up to the feathering width (`--feather_width`). Seamlines are therefore feathered over a
+/// band of this width, beyond which overlapping images are weighted equally. A feathering width
+/// of zero results in a simple average of the overlapping values.
+///
+/// Images acquired on different dates or by different sensors often differ in their overall
+/// brightness and contrast, resulting in visible seams. When the `--balance` flag is specified,
+/// each image after the first is colour balanced by histogram matching, i.e. its values are
+/// transformed such that, within the area that it overlaps the preceding images in the list,
+/// its distribution of values matches that of the (already balanced) preceding images. Images
+/// that do not overlap any preceding image are left unchanged. Colour-composite (RGB) images
+/// must be split into their component bands before they can be blended or balanced.
+///
 /// This is the preferred mosaicing tool to use when appending multiple images with
-/// little to no overlapping areas, e.g. tiled data. When images have significant overlap
-/// areas, users are advised to use the `MosaicWithFeathering` tool instead.
+/// little to no overlapping areas, e.g. tiled data. `MosaicWithFeathering` offers an
+/// alternative for blending pairs of images with significant overlap areas.
 ///
 /// Resample is very similar in operation to the Mosaic tool. The Resample tool should be
 /// used when there is an existing image into which you would like to dump information from
@@ -40,7 +59,7 @@ use std::thread;
 /// assigned the NoData value.
 ///
 /// # See Also
-/// `MosaicWithFeathering`
+/// `MosaicWithFeathering`, `HistogramMatching`, `Resample`
 pub struct Mosaic {
     name: String,
     description: String,
@@ -84,6 +103,33 @@ impl Mosaic {
             optional: true
         });
 
+        parameters.push(ToolParameter{
+            name: "Overlap Method".to_owned(),
+            flags: vec!["--overlap".to_owned()],
+            description: "Method for resolving areas of overlap; options include 'first', 'last', 'min', 'max', and 'blend'".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["first".to_owned(), "last".to_owned(), "min".to_owned(), "max".to_owned(), "blend".to_owned()]),
+            default_value: Some("first".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter {
+            name: "Feathering Width (cells)".to_owned(),
+            flags: vec!["--feather_width".to_owned()],
+            description: "Width, in grid cells, over which seamlines are feathered when blending.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("25".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Perform colour balancing?".to_owned(),
+            flags: vec!["--balance".to_owned()],
+            description: "Optional flag indicating whether to colour balance the images using histogram matching.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -95,7 +141,7 @@ impl Mosaic {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' -o=dest.tif --method='cc'", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' -o=dest.tif --method='cc' --overlap=blend --feather_width=50 --balance", short_exe, name).replace("*", &sep);
 
         Mosaic {
             name: name,
@@ -144,6 +190,9 @@ impl WhiteboxTool for Mosaic {
         let mut input_files = String::new();
         let mut output_file = String::new();
         let mut method = String::from("cc");
+        let mut overlap = String::from("first");
+        let mut feather_width = 25f64;
+        let mut balance = false;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -191,6 +240,37 @@ impl WhiteboxTool for Mosaic {
                 {
                     method = "cc".to_string();
                 }
+            } else if flag_val == "-overlap" {
+                overlap = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                if !["first", "last", "min", "max", "blend"].contains(&overlap.as_str()) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The overlap method must be one of 'first', 'last', 'min', 'max', or 'blend'.",
+                    ));
+                }
+            } else if flag_val == "-feather_width" {
+                feather_width = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+                if feather_width < 0f64 {
+                    feather_width = 0f64;
+                }
+            } else if flag_val == "-balance" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    balance = true;
+                }
             }
         }
 
@@ -340,226 +420,138 @@ impl WhiteboxTool for Mosaic {
             y.push(output.get_y_from_row(row));
         }
 
+        let is_rgb = inputs
+            .iter()
+            .any(|r| r.configs.photometric_interp == PhotometricInterpretation::RGB);
+        if is_rgb && (balance || (overlap != "first" && overlap != "last")) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "Colour-composite (RGB) inputs can only be mosaicked using the 'first' or 'last' overlap methods, without colour balancing. Split the composites into their component bands first."));
+        }
+        if method != "nn" || overlap == "blend" || balance {
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.data_type = DataType::F32;
+        }
+
+        // the order in which the images are visited when resolving overlaps
+        let order: Vec<usize> = if overlap == "last" {
+            (0..num_files).rev().collect()
+        } else {
+            (0..num_files).collect()
+        };
+
+        // edge-distance rasters, used to feather the seamlines
+        let mut edge_dist: Vec<Array2D<f32>> = Vec::with_capacity(num_files);
+        if overlap == "blend" && feather_width > 0f64 {
+            for i in 0..num_files {
+                edge_dist.push(edge_distance(&inputs[i], nodata_vals[i], feather_width)?);
+                if verbose {
+                    progress = (100.0_f64 * (i + 1) as f64 / num_files as f64) as usize;
+                    println!("Calculating edge distances: {}%", progress);
+                }
+            }
+        }
+
+        // colour balancing; each image is matched to the images that precede it
+        let mut transfer: Vec<Vec<(f64, f64)>> = vec![vec![]; num_files];
+        if balance {
+            for i in 1..num_files {
+                transfer[i] = match_to_preceding(&inputs, &nodata_vals, &transfer, i);
+                if verbose {
+                    progress = (100.0_f64 * i as f64 / (num_files - 1) as f64) as usize;
+                    println!("Balancing images: {}%", progress);
+                }
+            }
+        }
+
         let x = Arc::new(x);
         let y = Arc::new(y);
         let inputs = Arc::new(inputs);
         let nodata_vals = Arc::new(nodata_vals);
+        let order = Arc::new(order);
+        let edge_dist = Arc::new(edge_dist);
+        let transfer = Arc::new(transfer);
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
-        if method == "nn" {
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let (mut col_src, mut row_src): (isize, isize);
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            for i in 0..num_files {
-                                row_src = inputs[i].get_row_from_y(y[row as usize]);
-                                col_src = inputs[i].get_column_from_x(x[col as usize]);
-                                // row_src = ((inputs[i].configs.north - y[row as usize]) / inputs[i].configs.resolution_y).round() as isize;
-                                //col_src = ((x[col as usize] - inputs[i].configs.west) / inputs[i].configs.resolution_x).round() as isize;
-                                z = inputs[i].get_value(row_src, col_src);
+        for tid in 0..num_procs {
+            let inputs = inputs.clone();
+            let nodata_vals = nodata_vals.clone();
+            let order = order.clone();
+            let edge_dist = edge_dist.clone();
+            let transfer = transfer.clone();
+            let x = x.clone();
+            let y = y.clone();
+            let method = method.clone();
+            let overlap = overlap.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut z: f64;
+                let (mut sum_w, mut sum_z, mut w): (f64, f64, f64);
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let (xc, yc) = (x[col as usize], y[row as usize]);
+                        if overlap == "first" || overlap == "last" {
+                            for &i in order.iter() {
+                                z = sample_value(&inputs[i], nodata_vals[i], xc, yc, &method);
                                 if z != nodata_vals[i] {
-                                    data[col as usize] = z;
+                                    data[col as usize] = apply_transfer(&transfer[i], z);
                                     break;
                                 }
                             }
-                        }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns {
-                    if data[col as usize] != nodata {
-                        output.set_value(row, col, data[col as usize]);
-                    }
-                }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
-                }
-            }
-        } else if method == "cc" {
-            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
-            output.configs.data_type = DataType::F32;
-
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let shift_x = [-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2];
-                    let shift_y = [-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2];
-                    let num_neighbours = 16;
-                    let mut neighbour = [[0f64; 2]; 16];
-                    let (mut col_src, mut row_src): (f64, f64);
-                    let (mut col_n, mut row_n): (isize, isize);
-                    let (mut origin_row, mut origin_col): (isize, isize);
-                    let (mut dx, mut dy): (f64, f64);
-                    let mut sum_dist: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            let mut flag = true;
-                            for i in 0..num_files {
-                                if !flag {
-                                    break;
-                                }
-                                // row_src = inputs[i].get_row_from_y(y[row as usize]);
-                                // col_src = inputs[i].get_column_from_x(x[col as usize]);
-                                row_src = (inputs[i].configs.north - y[row as usize])
-                                    / inputs[i].configs.resolution_y;
-                                col_src = (x[col as usize] - inputs[i].configs.west)
-                                    / inputs[i].configs.resolution_x;
-                                origin_row = row_src.floor() as isize;
-                                origin_col = col_src.floor() as isize;
-                                // if origin_row < 0 || origin_col < 0 { break; }
-                                // if origin_row > inputs[i].configs.rows as isize || origin_col > inputs[i].configs.columns as isize { break; }
-                                sum_dist = 0f64;
-                                for n in 0..num_neighbours {
-                                    row_n = origin_row + shift_y[n];
-                                    col_n = origin_col + shift_x[n];
-                                    neighbour[n][0] = inputs[i].get_value(row_n, col_n);
-                                    dy = row_n as f64 - row_src;
-                                    dx = col_n as f64 - col_src;
-
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
-                                        neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
-                                        sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
-                                        neighbour[n][1] = 0f64;
-                                    } else {
-                                        data[col as usize] = neighbour[n][0];
-                                        flag = false;
-                                    }
-                                }
-
-                                if sum_dist > 0f64 {
-                                    z = 0f64;
-                                    for n in 0..num_neighbours {
-                                        z += (neighbour[n][0] * neighbour[n][1]) / sum_dist;
+                        } else if overlap == "min" || overlap == "max" {
+                            for &i in order.iter() {
+                                z = sample_value(&inputs[i], nodata_vals[i], xc, yc, &method);
+                                if z != nodata_vals[i] {
+                                    z = apply_transfer(&transfer[i], z);
+                                    if data[col as usize] == nodata
+                                        || (overlap == "min" && z < data[col as usize])
+                                        || (overlap == "max" && z > data[col as usize])
+                                    {
+                                        data[col as usize] = z;
                                     }
-                                    data[col as usize] = z;
-                                    flag = false;
                                 }
                             }
-                        }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns as usize {
-                    if data[col] != nodata {
-                        output.set_value(row, col as isize, data[col]);
-                    }
-                }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
-                }
-            }
-        } else {
-            // bilinear
-            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
-            output.configs.data_type = DataType::F32;
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let shift_x = [0, 1, 0, 1];
-                    let shift_y = [0, 0, 1, 1];
-                    let num_neighbours = 4;
-                    let mut neighbour = [[0f64; 2]; 4];
-                    let (mut col_src, mut row_src): (f64, f64);
-                    let (mut col_n, mut row_n): (isize, isize);
-                    let (mut origin_col, mut origin_row): (isize, isize);
-                    let (mut dx, mut dy): (f64, f64);
-                    let mut sum_dist: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            let mut flag = true;
-                            for i in 0..num_files {
-                                if !flag {
-                                    break;
-                                }
-                                row_src = (inputs[i].configs.north - y[row as usize])
-                                    / inputs[i].configs.resolution_y;
-                                col_src = (x[col as usize] - inputs[i].configs.west)
-                                    / inputs[i].configs.resolution_x;
-                                origin_row = row_src.floor() as isize;
-                                origin_col = col_src.floor() as isize;
-                                // if origin_row < 0 || origin_col < 0 { break; }
-                                // if origin_row > inputs[i].configs.rows as isize || origin_col > inputs[i].configs.columns as isize { break; }
-                                sum_dist = 0f64;
-                                for n in 0..num_neighbours {
-                                    row_n = origin_row + shift_y[n];
-                                    col_n = origin_col + shift_x[n];
-                                    neighbour[n][0] = inputs[i].get_value(row_n, col_n);
-                                    dy = row_n as f64 - row_src;
-                                    dx = col_n as f64 - col_src;
-
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
-                                        neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
-                                        sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
-                                        neighbour[n][1] = 0f64;
+                        } else {
+                            // blend
+                            sum_w = 0f64;
+                            sum_z = 0f64;
+                            for &i in order.iter() {
+                                z = sample_value(&inputs[i], nodata_vals[i], xc, yc, &method);
+                                if z != nodata_vals[i] {
+                                    w = if edge_dist.is_empty() {
+                                        1f64
                                     } else {
-                                        data[col as usize] = neighbour[n][0];
-                                        flag = false;
-                                    }
-                                }
-
-                                if sum_dist > 0f64 {
-                                    z = 0f64;
-                                    for n in 0..num_neighbours {
-                                        z += (neighbour[n][0] * neighbour[n][1]) / sum_dist;
-                                    }
-                                    data[col as usize] = z;
-                                    flag = false;
+                                        (edge_dist[i].get_value(
+                                            inputs[i].get_row_from_y(yc),
+                                            inputs[i].get_column_from_x(xc),
+                                        ) as f64)
+                                            .max(0.5)
+                                    };
+                                    sum_w += w;
+                                    sum_z += w * apply_transfer(&transfer[i], z);
                                 }
                             }
+                            if sum_w > 0f64 {
+                                data[col as usize] = sum_z / sum_w;
+                            }
                         }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns as usize {
-                    if data[col] != nodata {
-                        output.set_value(row, col as isize, data[col]);
                     }
+                    tx.send((row, data)).unwrap();
                 }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
+            });
+        }
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for col in 0..columns as usize {
+                if data[col] != nodata {
+                    output.set_value(row, col as isize, data[col]);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
         }
@@ -570,6 +562,11 @@ impl WhiteboxTool for Mosaic {
             self.get_tool_name()
         ));
         output.add_metadata_entry(format!("Resampling method: {}", method));
+        output.add_metadata_entry(format!("Overlap method: {}", overlap));
+        if overlap == "blend" {
+            output.add_metadata_entry(format!("Feathering width: {}", feather_width));
+        }
+        output.add_metadata_entry(format!("Colour balancing: {}", balance));
 
         if verbose {
             println!("Saving data...")
@@ -592,3 +589,162 @@ impl WhiteboxTool for Mosaic {
         Ok(())
     }
 }
+
+/// Samples an image at a map coordinate, returning the image's NoData value where
+/// the coordinate does not overlap valid data. The 'bilinear' and 'cc' methods use the
+/// inverse-distance weighting of the 4 and 16 surrounding cells respectively.
+fn sample_value(input: &Raster, nodata: f64, x: f64, y: f64, method: &str) -> f64 {
+    if method == "nn" {
+        return input.get_value(input.get_row_from_y(y), input.get_column_from_x(x));
+    }
+    let (shift_x, shift_y): (&[isize], &[isize]) = if method == "cc" {
+        (
+            &[-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2],
+            &[-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2],
+        )
+    } else {
+        (&[0, 1, 0, 1], &[0, 0, 1, 1])
+    };
+    // fractional row and column, relative to the cell centres
+    let row_src = (input.configs.north - y) / input.configs.resolution_y - 0.5;
+    let col_src = (x - input.configs.west) / input.configs.resolution_x - 0.5;
+    let origin_row = row_src.floor() as isize;
+    let origin_col = col_src.floor() as isize;
+    let (mut sum_w, mut sum_z) = (0f64, 0f64);
+    for n in 0..shift_x.len() {
+        let row_n = origin_row + shift_y[n];
+        let col_n = origin_col + shift_x[n];
+        let z = input.get_value(row_n, col_n);
+        if z != nodata {
+            let dy = row_n as f64 - row_src;
+            let dx = col_n as f64 - col_src;
+            let dist = dx * dx + dy * dy;
+            if dist == 0f64 {
+                return z;
+            }
+            sum_w += 1f64 / dist;
+            sum_z += z / dist;
+        }
+    }
+    if sum_w > 0f64 {
+        sum_z / sum_w
+    } else {
+        nodata
+    }
+}
+
+/// Calculates the chamfer distance, in cells, from each valid cell of an image to the
+/// nearest NoData cell or image edge, truncated at `max_dist`.
+fn edge_distance(input: &Raster, nodata: f64, max_dist: f64) -> Result<Array2D<f32>, Error> {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let max_dist = max_dist as f32;
+    let mut dist: Array2D<f32> = Array2D::new(rows, columns, max_dist, 0f32)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if input.get_value(row, col) == nodata {
+                dist.set_value(row, col, 0f32);
+            }
+        }
+    }
+    let diag = 2f32.sqrt();
+    let dx = [-1, -1, 0, 1];
+    let dy = [0, -1, -1, -1];
+    let cost = [1f32, diag, 1f32, diag];
+    for row in 0..rows {
+        for col in 0..columns {
+            let mut d = dist.get_value(row, col);
+            if d > 0f32 {
+                for n in 0..4 {
+                    // cells beyond the image edge are returned as the nodata value, zero
+                    d = d.min(dist.get_value(row + dy[n], col + dx[n]) + cost[n]);
+                }
+                dist.set_value(row, col, d);
+            }
+        }
+    }
+    for row in (0..rows).rev() {
+        for col in (0..columns).rev() {
+            let mut d = dist.get_value(row, col);
+            if d > 0f32 {
+                for n in 0..4 {
+                    d = d.min(dist.get_value(row - dy[n], col - dx[n]) + cost[n]);
+                }
+                dist.set_value(row, col, d);
+            }
+        }
+    }
+    Ok(dist)
+}
+
+/// Derives a histogram-matching transfer function for image `i`, mapping the quantiles
+/// of its values within the area that overlaps the preceding (already balanced) images
+/// onto the quantiles of those images' values. Returns an empty function, i.e. the
+/// identity, if the overlap is too small.
+fn match_to_preceding(
+    inputs: &[Raster],
+    nodata_vals: &[f64],
+    transfer: &[Vec<(f64, f64)>],
+    i: usize,
+) -> Vec<(f64, f64)> {
+    let rows = inputs[i].configs.rows as isize;
+    let columns = inputs[i].configs.columns as isize;
+    // limit the sample to roughly one million cells
+    let stride = (((rows * columns) as f64 / 1_000_000f64).sqrt().ceil() as isize).max(1);
+    let mut src = vec![];
+    let mut reference = vec![];
+    for row in (0..rows).step_by(stride as usize) {
+        let y = inputs[i].get_y_from_row(row);
+        for col in (0..columns).step_by(stride as usize) {
+            let z = inputs[i].get_value(row, col);
+            if z != nodata_vals[i] {
+                let x = inputs[i].get_x_from_column(col);
+                for j in 0..i {
+                    let zj = sample_value(&inputs[j], nodata_vals[j], x, y, "nn");
+                    if zj != nodata_vals[j] {
+                        src.push(z);
+                        reference.push(apply_transfer(&transfer[j], zj));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if src.len() < 100 {
+        return vec![];
+    }
+    src.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    reference.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = src.len() - 1;
+    let num_quantiles = 256;
+    let mut function: Vec<(f64, f64)> = Vec::with_capacity(num_quantiles + 1);
+    for q in 0..=num_quantiles {
+        let k = (q * n) / num_quantiles;
+        if function.is_empty() || src[k] > function[function.len() - 1].0 {
+            function.push((src[k], reference[k]));
+        }
+    }
+    function
+}
+
+/// Applies a piecewise-linear transfer function to a value. Values beyond the range
+/// of the function are shifted by the offset at the nearest end.
+fn apply_transfer(function: &[(f64, f64)], z: f64) -> f64 {
+    if function.is_empty() {
+        return z;
+    }
+    let last = function.len() - 1;
+    if z <= function[0].0 {
+        return z + function[0].1 - function[0].0;
+    }
+    if z >= function[last].0 {
+        return z + function[last].1 - function[last].0;
+    }
+    let k = match function.binary_search_by(|p| p.0.partial_cmp(&z).unwrap()) {
+        Ok(k) => return function[k].1,
+        Err(k) => k,
+    };
+    let (x0, y0) = function[k - 1];
+    let (x1, y1) = function[k];
+    y0 + (z - x0) / (x1 - x0) * (y1 - y0)
+}
@@ -0,0 +1,125 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the normalized burn ratio (NBR), which is used to map burned areas and the severity
+/// of wildfires:
+///
+/// > NBR = (NIR - SWIR2) / (NIR + SWIR2)
+///
+/// where NIR is the near-infrared band and SWIR2 is the second shortwave infrared band (e.g. Sentinel-2 band 12
+/// or Landsat 8 OLI band 7). Healthy vegetation has high NIR and low SWIR2 reflectance, while recently burned
+/// areas have the opposite, such that fire lowers the NBR. Burn severity is commonly estimated from the
+/// difference between the NBR of images acquired before and after a fire (dNBR; Key and Benson, 2006), which
+/// can be calculated with the `Subtract` tool.
+///
+/// The NIR and SWIR2 bands are specified with the band mapping parameter (`--bands`), either as single-band
+/// raster files, e.g. `--bands="nir=B8A.tif;swir2=B12.tif"`, or as the (one-based) numbers of bands within a
+/// multi-band image (`--input`), e.g. `--bands="nir=5;swir2=7"`. Grid cells that are NoData in either band are
+/// assigned NoData in the output.
+///
+/// # Reference
+/// Key, C. H., & Benson, N. C. (2006). Landscape assessment (LA): Sampling and analysis methods. In *FIREMON:
+/// Fire effects monitoring and inventory system* (Gen. Tech. Rep. RMRS-GTR-164-CD). USDA Forest Service,
+/// Rocky Mountain Research Station.
+///
+/// # See Also
+/// `SpectralIndex`, `Subtract`
+pub struct NormalizedBurnRatio {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl NormalizedBurnRatio {
+    pub fn new() -> NormalizedBurnRatio {
+        // public constructor
+        let name = "NormalizedBurnRatio".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the normalized burn ratio (NBR) from near-infrared and shortwave infrared image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("nbr"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=landsat8.tif --bands=\"nir=5;swir2=7\" -o=nbr.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        NormalizedBurnRatio {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for NormalizedBurnRatio {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("nbr"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -57,7 +57,7 @@ use std::thread;
 /// in this case, will result in an output NoData value.
 ///
 /// # See Also
-/// `Divide`
+/// `Divide`, `SpectralIndex`
 pub struct NormalizedDifferenceIndex {
     name: String,
     description: String,
@@ -303,17 +303,10 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
         let columns = nir.configs.columns as isize;
         let nir_nodata = nir.configs.nodata;
 
-        let red = Arc::new(Raster::new(&input2_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let red = Arc::new(align_raster_input(&nir, Raster::new(&input2_file, "r")?)?);
         let red_nodata = red.configs.nodata;
 
-        // make sure the input files have the same size
-        if nir.configs.rows != red.configs.rows || nir.configs.columns != red.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let start = Instant::now();
 
         let mut output = Raster::initialize_using_file(&output_file, &nir);
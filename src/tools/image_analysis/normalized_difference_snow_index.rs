@@ -0,0 +1,124 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the normalized difference snow index (NDSI) of Hall et al. (1995), which is used to
+/// map snow and ice cover:
+///
+/// > NDSI = (Green - SWIR1) / (Green + SWIR1)
+///
+/// Snow and ice are highly reflective in the visible spectrum but strongly absorb shortwave infrared (SWIR)
+/// radiation, unlike most clouds, such that snow-covered areas have high NDSI values. A threshold of
+/// approximately 0.4 is commonly used to identify snow-covered grid cells. Because open water also has
+/// positive NDSI values, snow maps are commonly refined using a near-infrared reflectance threshold or a water
+/// mask. Note that the index is numerically identical to the `ModifiedNormalizedDifferenceWaterIndex`.
+///
+/// The green and SWIR1 bands are specified with the band mapping parameter (`--bands`), either as single-band
+/// raster files, e.g. `--bands="green=B03.tif;swir1=B11.tif"`, or as the (one-based) numbers of bands within a
+/// multi-band image (`--input`), e.g. `--bands="green=3;swir1=6"`. Grid cells that are NoData in either band
+/// are assigned NoData in the output.
+///
+/// # Reference
+/// Hall, D. K., Riggs, G. A., & Salomonson, V. V. (1995). Development of methods for mapping global snow cover
+/// using moderate resolution imaging spectroradiometer data. *Remote Sensing of Environment*, 54(2), 127-140.
+///
+/// # See Also
+/// `ModifiedNormalizedDifferenceWaterIndex`, `SpectralIndex`
+pub struct NormalizedDifferenceSnowIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl NormalizedDifferenceSnowIndex {
+    pub fn new() -> NormalizedDifferenceSnowIndex {
+        // public constructor
+        let name = "NormalizedDifferenceSnowIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the normalized difference snow index (NDSI) from green and shortwave infrared image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("ndsi"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=landsat8.tif --bands=\"green=3;swir1=6\" -o=ndsi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        NormalizedDifferenceSnowIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for NormalizedDifferenceSnowIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("ndsi"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
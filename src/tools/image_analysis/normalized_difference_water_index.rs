@@ -0,0 +1,123 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the normalized difference water index (NDWI) of McFeeters (1996), which is used to
+/// delineate open water features in multispectral imagery:
+///
+/// > NDWI = (Green - NIR) / (Green + NIR)
+///
+/// Open water, which reflects more green than near-infrared (NIR) radiation, generally has positive NDWI
+/// values, while vegetation and soils have negative values. Note that the index of the same name proposed by
+/// Gao (1996), which is sensitive to vegetation water content, uses the NIR and shortwave infrared bands instead,
+/// and is available as the normalized difference moisture index (`--index=ndmi`) of the `SpectralIndex` tool.
+///
+/// The green and NIR bands are specified with the band mapping parameter (`--bands`), either as single-band
+/// raster files, e.g. `--bands="green=B03.tif;nir=B08.tif"`, or as the (one-based) numbers of bands within a
+/// multi-band image (`--input`), e.g. `--bands="green=3;nir=5"` for a Landsat 8 OLI stack. Grid cells that are
+/// NoData in either band are assigned NoData in the output.
+///
+/// # Reference
+/// McFeeters, S. K. (1996). The use of the Normalized Difference Water Index (NDWI) in the delineation of open
+/// water features. *International Journal of Remote Sensing*, 17(7), 1425-1432.
+///
+/// # See Also
+/// `ModifiedNormalizedDifferenceWaterIndex`, `SpectralIndex`, `NormalizedDifferenceIndex`
+pub struct NormalizedDifferenceWaterIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl NormalizedDifferenceWaterIndex {
+    pub fn new() -> NormalizedDifferenceWaterIndex {
+        // public constructor
+        let name = "NormalizedDifferenceWaterIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the normalized difference water index (NDWI) from green and near-infrared image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("ndwi"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --bands=\"green=B03.tif;nir=B08.tif\" -o=ndwi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        NormalizedDifferenceWaterIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for NormalizedDifferenceWaterIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("ndwi"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -27,7 +27,7 @@ use std::thread;
 /// (`--filterx` and `--filtery`).
 ///
 /// # See Also
-/// `Closing`, `TophatTransform`
+/// `Closing`, `TophatTransform`, `MorphologicalFilter`
 pub struct Opening {
     name: String,
     description: String,
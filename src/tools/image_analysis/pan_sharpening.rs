@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 27/07/2017
-Last Modified: 11/02/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::structures::Array2D;
+use crate::na::{DMatrix, DVector};
 use crate::tools::*;
 use num_cpus;
 use std::env;
@@ -31,9 +32,32 @@ use std::thread;
 /// For Landsat ETM+ data, the panchromatic band is sensitive to EMR in the range of 0.52-0.90 micrometres. This
 /// corresponds closely to the green (band 2), red (band 3), and near-infrared (band 4).
 ///
+/// A third method, component substitution (`--method=cs`), is an adaptive Gram-Schmidt-style technique (Aiazzi
+/// et al., 2007). An intensity component is estimated as the weighted sum of the multi-spectral bands that best
+/// predicts the panchromatic image, by least-squares regression. The panchromatic image, histogram-matched to this
+/// intensity, is then substituted for it, with the spatial detail injected into each band in proportion to the
+/// band's covariance with the intensity component. This method generally preserves the spectral properties of the
+/// input imagery better than the Brovey and IHS transformations.
+///
+/// The multi-spectral data can be input either as a colour composite (`--composite`), as individual red, green,
+/// and blue bands (`--red`, `--green`, `--blue`), or as a list of any number of bands (`--inputs`). In the
+/// first two cases, the output is a fused colour composite. In the latter case, the fusion is performed on the
+/// original band values, using a generalized form of each of the three methods (Tu et al., 2001), and one fused
+/// image is output for each input band, named by appending the band number to the output file name (e.g.
+/// *pan_sharp_1.tif*, *pan_sharp_2.tif*, etc.). The multi-spectral bands are co-registered to the grid of the
+/// panchromatic image automatically, using bilinear interpolation in the `--inputs` and `cs` modes, and
+/// nearest-neighbour resampling otherwise. Output cells that fall outside of the multi-spectral image extent
+/// are assigned **NoData**.
+///
 /// # Reference
+/// Aiazzi, B., Baronti, S., and Selva, M. (2007). Improving component substitution pansharpening through
+/// multivariate regression of MS+Pan data. IEEE Transactions on Geoscience and Remote Sensing, 45(10), 3230-3239.
+///
 /// Jensen, J. R. (2015). Introductory Digital Image Processing: A Remote Sensing Perspective.
 ///
+/// Tu, T. M., Su, S. C., Shyu, H. C., and Huang, P. S. (2001). A new look at IHS-like image fusion methods.
+/// Information Fusion, 2(3), 177-186.
+///
 /// # See Also
 /// `CreateColourComposite`
 pub struct PanchromaticSharpening {
@@ -91,6 +115,15 @@ impl PanchromaticSharpening {
             optional: true
         });
 
+        parameters.push(ToolParameter{
+            name: "Input Multispectral Band Files (optional; only if colour-composite and individual bands not specified)".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input multispectral band files. One fused output image is created for each band.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
         parameters.push(ToolParameter {
             name: "Input Panchromatic Band File".to_owned(),
             flags: vec!["--pan".to_owned()],
@@ -112,8 +145,13 @@ impl PanchromaticSharpening {
         parameters.push(ToolParameter {
             name: "Pan-Sharpening Method".to_owned(),
             flags: vec!["--method".to_owned()],
-            description: "Options include 'brovey' (default) and 'ihs'".to_owned(),
-            parameter_type: ParameterType::OptionList(vec!["brovey".to_owned(), "ihs".to_owned()]),
+            description: "Options include 'brovey' (default), 'ihs', and 'cs' (component substitution)"
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "brovey".to_owned(),
+                "ihs".to_owned(),
+                "cs".to_owned(),
+            ]),
             default_value: Some("brovey".to_owned()),
             optional: true,
         });
@@ -130,7 +168,8 @@ impl PanchromaticSharpening {
             short_exe += ".exe";
         }
         let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=red.tif --green=green.tif --blue=blue.tif --pan=pan.tif --output=pan_sharp.tif --method='brovey'
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" --composite=image.tif --pan=pan.tif --output=pan_sharp.tif --method='ihs'", short_exe, name).replace("*", &sep);
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --composite=image.tif --pan=pan.tif --output=pan_sharp.tif --method='ihs'
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='band1.tif;band2.tif;band3.tif;band4.tif' --pan=pan.tif --output=pan_sharp.tif --method='cs'", short_exe, name).replace("*", &sep);
 
         PanchromaticSharpening {
             name: name,
@@ -188,6 +227,7 @@ impl WhiteboxTool for PanchromaticSharpening {
         let mut blue_file = String::new();
         let mut composite_file = String::new();
         let mut use_composite = false;
+        let mut input_files_str = String::new();
         let mut pan_file = String::new();
         let mut output_file = String::new();
         let mut fusion_method = String::from("brovey");
@@ -239,6 +279,12 @@ impl WhiteboxTool for PanchromaticSharpening {
                     args[i + 1].to_string()
                 };
                 use_composite = true;
+            } else if flag_val == "-i" || flag_val == "-inputs" {
+                input_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
             } else if flag_val == "-o" || flag_val == "-output" {
                 output_file = if keyval {
                     vec[1].to_string()
@@ -253,6 +299,10 @@ impl WhiteboxTool for PanchromaticSharpening {
                 };
                 fusion_method = if fusion_method.to_lowercase().contains("bro") {
                     String::from("brovey")
+                } else if fusion_method.to_lowercase().contains("cs")
+                    || fusion_method.to_lowercase().contains("comp")
+                {
+                    String::from("cs")
                 } else {
                     String::from("ihs")
                 };
@@ -289,6 +339,135 @@ impl WhiteboxTool for PanchromaticSharpening {
             output_file = format!("{}{}", working_directory, output_file);
         }
 
+        if !input_files_str.trim().is_empty() {
+            // multi-band mode; each band is fused individually and output as a separate image
+            let mut cmd = input_files_str.split(";");
+            let mut input_files = cmd.collect::<Vec<&str>>();
+            if input_files.len() == 1 {
+                cmd = input_files_str.split(",");
+                input_files = cmd.collect::<Vec<&str>>();
+            }
+            let input_files: Vec<String> = input_files
+                .into_iter()
+                .filter(|f| !f.trim().is_empty())
+                .map(|f| {
+                    let f = f.trim().to_owned();
+                    if !f.contains(&sep) && !f.contains("/") {
+                        format!("{}{}", working_directory, f)
+                    } else {
+                        f
+                    }
+                })
+                .collect();
+            let num_bands = input_files.len();
+            if num_bands < 2 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "At least two multispectral bands must be specified with the --inputs flag.",
+                ));
+            }
+
+            let mut bands: Vec<Array2D<f64>> = Vec::with_capacity(num_bands);
+            let mut nodata_ms: Vec<f64> = Vec::with_capacity(num_bands);
+            let mut grid = (0f64, 0f64, 0f64, 0f64);
+            let (mut rows_ms, mut columns_ms) = (0isize, 0isize);
+            let mut first_band: Option<Raster> = None;
+            for i in 0..num_bands {
+                if verbose {
+                    println!("Reading multispec band {} of {}...", i + 1, num_bands)
+                };
+                let input_band = Raster::new(&input_files[i], "r")?;
+                let input_band = match first_band {
+                    // make sure the inputs share the same grid and CRS
+                    Some(ref base) => align_raster_input(base, input_band)?,
+                    None => input_band,
+                };
+                nodata_ms.push(input_band.configs.nodata);
+                bands.push(input_band.get_data_as_array2d());
+                if i == 0 {
+                    rows_ms = input_band.configs.rows as isize;
+                    columns_ms = input_band.configs.columns as isize;
+                    grid = (
+                        input_band.configs.north,
+                        input_band.configs.west,
+                        input_band.configs.resolution_x,
+                        input_band.configs.resolution_y,
+                    );
+                    first_band = Some(input_band);
+                }
+            }
+
+            if verbose {
+                println!("Reading pan image data...")
+            };
+            let pan = Arc::new(Raster::new(&pan_file, "r")?);
+
+            let start = Instant::now();
+
+            let nodata_out = -32768f64;
+            let fused = fuse_bands(
+                Arc::new(bands),
+                Arc::new(nodata_ms),
+                grid,
+                pan.clone(),
+                &fusion_method,
+                nodata_out,
+                verbose,
+            )?;
+
+            let elapsed_time = get_formatted_elapsed_time(start);
+
+            let extension: String = match path::Path::new(&output_file).extension() {
+                Some(ext) => format!(".{}", ext.to_str().unwrap_or("tif")),
+                None => String::new(),
+            };
+            for k in 0..num_bands {
+                let out_file = if extension.is_empty() {
+                    format!("{}_{}", output_file, k + 1)
+                } else {
+                    output_file.replace(&extension, &format!("_{}{}", k + 1, extension))
+                };
+                let mut output = Raster::initialize_using_file(&out_file, &pan);
+                output.configs.nodata = nodata_out;
+                output.configs.data_type = DataType::F32;
+                output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+                output.configs.palette = "grey.plt".to_string();
+                for row in 0..pan.configs.rows as isize {
+                    for col in 0..pan.configs.columns as isize {
+                        output.set_value(row, col, fused[k].get_value(row, col));
+                    }
+                }
+                output.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                output.add_metadata_entry(format!("Input multispec band file: {}", input_files[k]));
+                output.add_metadata_entry(format!("Input panchromatic file: {}", pan_file));
+                output.add_metadata_entry(format!("Pan-sharpening fusion method: {}", fusion_method));
+                output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+                if verbose {
+                    println!("Saving band {} of {}...", k + 1, num_bands)
+                };
+                let _ = match output.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Output file written")
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+            if verbose {
+                println!(
+                    "{}",
+                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                );
+            }
+
+            return Ok(());
+        }
+
         let num_procs = num_cpus::get() as isize;
 
         let mut input: Array2D<f64>;
@@ -324,25 +503,12 @@ impl WhiteboxTool for PanchromaticSharpening {
             if verbose {
                 println!("Reading green band data...")
             };
-            let input_g = Raster::new(&green_file, "r")?;
+            // make sure the inputs share the same grid and CRS
+            let input_g = align_raster_input(&input_r, Raster::new(&green_file, "r")?)?;
             if verbose {
                 println!("Reading blue band data...")
             };
-            let input_b = Raster::new(&blue_file, "r")?;
-
-            // make sure the input files have the same size
-            if input_r.configs.rows != input_g.configs.rows
-                || input_r.configs.columns != input_g.configs.columns
-            {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                      "The input files must have the same number of rows and columns and spatial extent."));
-            }
-            if input_r.configs.rows != input_b.configs.rows
-                || input_r.configs.columns != input_b.configs.columns
-            {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                      "The input files must have the same number of rows and columns and spatial extent."));
-            }
+            let input_b = align_raster_input(&input_r, Raster::new(&blue_file, "r")?)?;
 
             let nodata_r = input_r.configs.nodata;
             let nodata_g = input_g.configs.nodata;
@@ -502,6 +668,45 @@ impl WhiteboxTool for PanchromaticSharpening {
                     }
                 }
             }
+        } else if fusion_method == String::from("cs") {
+            // unpack the colour composite into separate bands and fuse them with the general method
+            let band_nodata = -1f64;
+            let mut bands = vec![Array2D::new(rows_ms, columns_ms, band_nodata, band_nodata)?; 3];
+            let mut z: f64;
+            for row in 0..rows_ms {
+                for col in 0..columns_ms {
+                    z = input[(row, col)];
+                    if z != nodata_ms {
+                        bands[0][(row, col)] = (z as u32 & 0xFF) as f64;
+                        bands[1][(row, col)] = ((z as u32 >> 8) & 0xFF) as f64;
+                        bands[2][(row, col)] = ((z as u32 >> 16) & 0xFF) as f64;
+                    }
+                }
+            }
+            let fused = fuse_bands(
+                Arc::new(bands),
+                Arc::new(vec![band_nodata; 3]),
+                (north, west, resolution_x, resolution_y),
+                pan.clone(),
+                &fusion_method,
+                band_nodata,
+                verbose,
+            )?;
+            let (mut r_out, mut g_out, mut b_out): (u32, u32, u32);
+            for row in 0..rows_pan {
+                for col in 0..columns_pan {
+                    if fused[0][(row, col)] != band_nodata {
+                        r_out = fused[0][(row, col)].max(0f64).min(255f64) as u32;
+                        g_out = fused[1][(row, col)].max(0f64).min(255f64) as u32;
+                        b_out = fused[2][(row, col)].max(0f64).min(255f64) as u32;
+                        output.set_value(
+                            row,
+                            col,
+                            ((255 << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64,
+                        );
+                    }
+                }
+            }
         } else {
             // ihs
 
@@ -716,3 +921,246 @@ impl WhiteboxTool for PanchromaticSharpening {
         Ok(())
     }
 }
+
+/// Samples the multi-spectral bands at the point (x, y) using bilinear interpolation, falling
+/// back to the nearest cell where any of the neighbouring cells are NoData. Returns false if
+/// the nearest cell is NoData in any band or lies outside of the multi-spectral grid.
+fn sample_bands(
+    bands: &[Array2D<f64>],
+    nodata: &[f64],
+    grid: (f64, f64, f64, f64),
+    x: f64,
+    y: f64,
+    values: &mut [f64],
+) -> bool {
+    let (north, west, resolution_x, resolution_y) = grid;
+    let c = (x - west) / resolution_x;
+    let r = (north - y) / resolution_y;
+    let (col_n, row_n) = (c.floor() as isize, r.floor() as isize);
+    let (c, r) = (c - 0.5, r - 0.5);
+    let (c0, r0) = (c.floor() as isize, r.floor() as isize);
+    let (fc, fr) = (c - c0 as f64, r - r0 as f64);
+    for k in 0..bands.len() {
+        let z = bands[k].get_value(row_n, col_n);
+        if z == nodata[k] {
+            return false;
+        }
+        let z00 = bands[k].get_value(r0, c0);
+        let z01 = bands[k].get_value(r0, c0 + 1);
+        let z10 = bands[k].get_value(r0 + 1, c0);
+        let z11 = bands[k].get_value(r0 + 1, c0 + 1);
+        values[k] = if z00 != nodata[k] && z01 != nodata[k] && z10 != nodata[k] && z11 != nodata[k]
+        {
+            (z00 * (1f64 - fc) + z01 * fc) * (1f64 - fr) + (z10 * (1f64 - fc) + z11 * fc) * fr
+        } else {
+            z
+        };
+    }
+    true
+}
+
+/// Fuses the multi-spectral bands with the panchromatic image, returning one fused band, on the
+/// panchromatic grid, for each input band. The generalized Brovey and IHS methods use the band mean
+/// as the intensity component, while the component substitution method estimates the intensity by
+/// regressing the panchromatic values on the bands. In each case, the panchromatic image is matched
+/// to the mean and standard deviation of the intensity component before it is substituted.
+fn fuse_bands(
+    bands: Arc<Vec<Array2D<f64>>>,
+    nodata_ms: Arc<Vec<f64>>,
+    grid: (f64, f64, f64, f64),
+    pan: Arc<Raster>,
+    method: &str,
+    nodata_out: f64,
+    verbose: bool,
+) -> Result<Vec<Array2D<f64>>, Error> {
+    let num_procs = num_cpus::get() as isize;
+    let num_bands = bands.len();
+    let rows = pan.configs.rows as isize;
+    let columns = pan.configs.columns as isize;
+    let nodata_pan = pan.configs.nodata;
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    // accumulate the means and cross-products of the bands and the panchromatic image
+    let m = num_bands + 1;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let bands = bands.clone();
+        let nodata_ms = nodata_ms.clone();
+        let pan = pan.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut values = vec![0f64; m];
+            for row in (0..rows).filter(|row_val| row_val % num_procs == tid) {
+                let mut n = 0f64;
+                let mut sum = vec![0f64; m];
+                let mut cross = vec![0f64; m * m];
+                let y = pan.get_y_from_row(row);
+                for col in 0..columns {
+                    let z_pan = pan.get_value(row, col);
+                    if z_pan == nodata_pan {
+                        continue;
+                    }
+                    let x = pan.get_x_from_column(col);
+                    if !sample_bands(&bands, &nodata_ms, grid, x, y, &mut values[0..num_bands]) {
+                        continue;
+                    }
+                    values[num_bands] = z_pan;
+                    n += 1f64;
+                    for j in 0..m {
+                        sum[j] += values[j];
+                        for k in j..m {
+                            cross[j * m + k] += values[j] * values[k];
+                        }
+                    }
+                }
+                tx.send((n, sum, cross)).unwrap();
+            }
+        });
+    }
+
+    let mut n = 0f64;
+    let mut sum = vec![0f64; m];
+    let mut cross = vec![0f64; m * m];
+    for row in 0..rows {
+        let (n_row, sum_row, cross_row) = rx.recv().expect("Error receiving data from thread.");
+        n += n_row;
+        for j in 0..m {
+            sum[j] += sum_row[j];
+        }
+        for j in 0..m * m {
+            cross[j] += cross_row[j];
+        }
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress (1 of 2): {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    if n < 2f64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The multispectral and panchromatic images do not overlap.",
+        ));
+    }
+
+    let mean: Vec<f64> = sum.iter().map(|s| s / n).collect();
+    let cov = |j: usize, k: usize| -> f64 {
+        let (a, b) = if j <= k { (j, k) } else { (k, j) };
+        cross[a * m + b] / n - mean[a] * mean[b]
+    };
+
+    // intensity component weights
+    let mut weights = vec![1f64 / num_bands as f64; num_bands];
+    let mut intercept = 0f64;
+    if method == "cs" {
+        let a = DMatrix::from_fn(num_bands, num_bands, |j, k| cov(j, k));
+        let b = DVector::from_fn(num_bands, |j, _| cov(j, num_bands));
+        if let Some(w) = a.lu().solve(&b) {
+            if w.iter().all(|v| v.is_finite()) {
+                for j in 0..num_bands {
+                    weights[j] = w[j];
+                }
+                intercept = mean[num_bands];
+                for j in 0..num_bands {
+                    intercept -= weights[j] * mean[j];
+                }
+            }
+        }
+    }
+
+    let mut mean_i = intercept;
+    let mut var_i = 0f64;
+    let mut gains = vec![1f64; num_bands];
+    for j in 0..num_bands {
+        mean_i += weights[j] * mean[j];
+        let mut cov_ji = 0f64;
+        for k in 0..num_bands {
+            cov_ji += weights[k] * cov(j, k);
+        }
+        gains[j] = cov_ji;
+        var_i += weights[j] * cov_ji;
+    }
+    let stdev_i = var_i.max(0f64).sqrt();
+    let stdev_pan = cov(num_bands, num_bands).max(0f64).sqrt();
+    let pan_scale = if stdev_pan > 0f64 {
+        stdev_i / stdev_pan
+    } else {
+        0f64
+    };
+    if method == "cs" && var_i > 0f64 {
+        for j in 0..num_bands {
+            gains[j] /= var_i;
+        }
+    } else {
+        gains = vec![1f64; num_bands];
+    }
+    let mean_pan = mean[num_bands];
+    let weights = Arc::new(weights);
+    let gains = Arc::new(gains);
+    let is_brovey = method == "brovey";
+
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let bands = bands.clone();
+        let nodata_ms = nodata_ms.clone();
+        let pan = pan.clone();
+        let weights = weights.clone();
+        let gains = gains.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut values = vec![0f64; num_bands];
+            for row in (0..rows).filter(|row_val| row_val % num_procs == tid) {
+                let mut data = vec![vec![nodata_out; columns as usize]; num_bands];
+                let y = pan.get_y_from_row(row);
+                for col in 0..columns {
+                    let z_pan = pan.get_value(row, col);
+                    if z_pan == nodata_pan {
+                        continue;
+                    }
+                    let x = pan.get_x_from_column(col);
+                    if !sample_bands(&bands, &nodata_ms, grid, x, y, &mut values) {
+                        continue;
+                    }
+                    let mut intensity = intercept;
+                    for j in 0..num_bands {
+                        intensity += weights[j] * values[j];
+                    }
+                    let p = (z_pan - mean_pan) * pan_scale + mean_i;
+                    for j in 0..num_bands {
+                        data[j][col as usize] = if is_brovey {
+                            if intensity != 0f64 {
+                                values[j] * p / intensity
+                            } else {
+                                values[j]
+                            }
+                        } else {
+                            values[j] + gains[j] * (p - intensity)
+                        };
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    let mut fused = vec![Array2D::new(rows, columns, nodata_out, nodata_out)?; num_bands];
+    for r in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        for j in 0..num_bands {
+            fused[j].set_row_data(row, data[j].clone());
+        }
+        if verbose {
+            progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress (2 of 2): {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(fused)
+}
@@ -0,0 +1,122 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::speckle_filters::{run_speckle_filter, speckle_filter_parameters, SpeckleFilterType};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool applies the refined Lee (1981) filter to reduce the speckle noise of a synthetic aperture radar
+/// (SAR) backscatter image (`--input`). The refined Lee filter improves the preservation of edges by the adaptive
+/// Lee filter (see `LeeSpeckleFilter`) by estimating the local statistics within an edge-aligned window. The filter
+/// window is divided into 3 x 3 overlapping sub-windows, e.g. a 7 x 7 window into 3 x 3 cell sub-windows, and the
+/// direction of the strongest edge is determined from the gradients of the sub-window means. The local mean and
+/// variance are then calculated from the half of the window lying on the side of the edge most similar to the
+/// window centre, and the cell is replaced by the Lee minimum mean square error estimate of its noise-free value.
+///
+/// The filter size (`--filter`) must be of the form 2*n* + 1, where *n* is an odd number (i.e. 7, 11, 15, ...);
+/// other values are rounded up to the next valid size. The speckle is modelled as multiplicative noise, with a
+/// coefficient of variation of 1 / sqrt(L), where L is the equivalent number of looks of the image (`--looks`).
+/// The filter assumes that the image values are backscatter intensities, i.e. in linear power units. Images in
+/// decibels can be filtered using the `--db` flag.
+///
+/// # Reference
+/// Lee, J. S. (1981). Refined filtering of image noise using local statistics. *Computer Graphics and Image
+/// Processing*, 15(4), 380-389.
+///
+/// # See Also
+/// `LeeSpeckleFilter`, `FrostFilter`, `GammaMapFilter`, `LeeSigmaFilter`
+pub struct RefinedLeeFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RefinedLeeFilter {
+    pub fn new() -> RefinedLeeFilter {
+        // public constructor
+        let name = "RefinedLeeFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description =
+            "Performs a refined Lee edge-aligned speckle filter on a SAR image.".to_string();
+
+        let parameters = speckle_filter_parameters(SpeckleFilterType::RefinedLee);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=sentinel1_vv.tif -o=output.tif --filter=7 --looks=4.4",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        RefinedLeeFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RefinedLeeFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        run_speckle_filter(
+            &self.get_tool_name(),
+            SpeckleFilterType::RefinedLee,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
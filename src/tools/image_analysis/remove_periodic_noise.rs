@@ -0,0 +1,599 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{fft2d, fft_pad_image};
+use crate::raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool removes periodic noise, such as the striping and banding that is common in scanned aerial
+/// photographs and in imagery acquired by some satellite sensors, from an input image (`--input`). The image
+/// is transformed into the frequency domain using the fast Fourier transform (FFT), where periodic noise is
+/// concentrated into a small number of frequencies, the noise frequencies are suppressed using Gaussian notch
+/// filters, and the result is transformed back into the spatial domain.
+///
+/// Three methods (`--method`) are available for identifying the noise frequencies. The 'auto' method detects
+/// isolated spikes in the power spectrum, i.e. frequencies that are local maxima and whose log-power exceeds
+/// the local average log-power of the spectrum, within a window of `--filter_size` spectral cells, by more
+/// than a factor of `--threshold` in power. The 'horizontal' method is intended for destriping images with
+/// horizontal stripes (i.e. row-wise noise, common with line-scanning sensors), the energy of which lies along
+/// the vertical frequency axis of the spectrum; this axis is suppressed. Similarly, the 'vertical' method removes
+/// vertical stripes by suppressing the horizontal frequency axis. In each case, frequencies lower than
+/// `--min_freq` (in cycles per cell, 0 to 0.5) are protected, because these contain the bulk of the image's
+/// information, and the notch size (`--notch_size`), in spectral cells, is the standard deviation of the Gaussian
+/// notch; smaller notches (e.g. 1.0) are often appropriate for the 'horizontal' and 'vertical' methods, because
+/// the frequency axes also carry some of the image's own information. The user may optionally output the notch
+/// filter transfer function (`--out_mask`), in the centred arrangement of the `ForwardFft` tool, to inspect the
+/// frequencies that were removed; it can be useful to examine the log-amplitude spectrum created by `ForwardFft`
+/// when setting the parameters.
+///
+/// The image is padded to dimensions that are powers of two by reflection prior to the transform, and
+/// **NoData** cells are replaced by the image mean; **NoData** cells are preserved in the output image.
+///
+/// # See Also
+/// `ForwardFft`, `InverseFft`, `FrequencyDomainFilter`
+pub struct RemovePeriodicNoise {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RemovePeriodicNoise {
+    pub fn new() -> RemovePeriodicNoise {
+        // public constructor
+        let name = "RemovePeriodicNoise".to_string();
+        let toolbox = "Image Processing Tools/Image Enhancement".to_string();
+        let description =
+            "Removes periodic noise, including striping, from an image using frequency-domain notch filtering.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Notch Filter File (optional)".to_owned(),
+            flags: vec!["--out_mask".to_owned()],
+            description: "Optional output notch filter transfer function raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Noise Detection Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Noise detection method; options include 'auto', 'horizontal', and 'vertical'"
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "auto".to_owned(),
+                "horizontal".to_owned(),
+                "vertical".to_owned(),
+            ]),
+            default_value: Some("auto".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spike Power Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Ratio of spike power to local average power used by the 'auto' method."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spectral Window Size (cells)".to_owned(),
+            flags: vec!["--filter_size".to_owned()],
+            description: "Size of the window used to estimate the local average power by the 'auto' method.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("11".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Noise Frequency (cycles per cell)".to_owned(),
+            flags: vec!["--min_freq".to_owned()],
+            description: "Frequencies below this value, in cycles per cell, are not filtered.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.02".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Notch Size (cells)".to_owned(),
+            flags: vec!["--notch_size".to_owned()],
+            description: "Standard deviation of the Gaussian notch, in spectral cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --method=auto --threshold=200.0 --notch_size=1.5
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=output.tif --method=horizontal --min_freq=0.01", short_exe, name).replace("*", &sep);
+
+        RemovePeriodicNoise {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RemovePeriodicNoise {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut mask_file = String::new();
+        let mut method = String::from("auto");
+        let mut threshold = 100f64;
+        let mut filter_size = 11usize;
+        let mut min_freq = 0.02f64;
+        let mut notch_size = 2f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_mask" {
+                mask_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                method = if method.contains("hor") {
+                    String::from("horizontal")
+                } else if method.contains("ver") {
+                    String::from("vertical")
+                } else {
+                    String::from("auto")
+                };
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-filter_size" {
+                filter_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-min_freq" {
+                min_freq = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-notch_size" {
+                notch_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if threshold <= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The spike power threshold must be greater than 1.0.",
+            ));
+        }
+        if notch_size <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The notch size must be greater than zero.",
+            ));
+        }
+        // the filter size must be an odd integer
+        if filter_size < 3 {
+            filter_size = 3;
+        }
+        if filter_size % 2 == 0 {
+            filter_size += 1;
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !mask_file.is_empty() && !mask_file.contains(&sep) && !mask_file.contains("/") {
+            mask_file = format!("{}{}", working_directory, mask_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows;
+        let columns = input.configs.columns;
+        let nodata = input.configs.nodata;
+
+        // replace NoData cells with the image mean
+        let (mut sum, mut n) = (0f64, 0f64);
+        let mut data = vec![0f64; rows * columns];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row as isize, col as isize);
+                if z != nodata {
+                    data[row * columns + col] = z;
+                    sum += z;
+                    n += 1f64;
+                }
+            }
+        }
+        if n == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image does not contain any valid data.",
+            ));
+        }
+        let mean = sum / n;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row as isize, col as isize) == nodata {
+                    data[row * columns + col] = mean;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Performing forward transform...")
+        };
+        let (mut re, padded_rows, padded_columns) = fft_pad_image(&data, rows, columns);
+        let mut im = vec![0f64; padded_rows * padded_columns];
+        fft2d(&mut re, &mut im, padded_rows, padded_columns, false);
+
+        // The notch filter is built in the centred arrangement of the spectrum, where the
+        // zero frequency is located at (half_rows, half_columns).
+        let (half_rows, half_columns) = (padded_rows / 2, padded_columns / 2);
+        let centred_index = |row: usize, col: usize| -> usize {
+            ((row + half_rows) % padded_rows) * padded_columns + (col + half_columns) % padded_columns
+        };
+        let radial_freq = |row: usize, col: usize| -> f64 {
+            let fv = (row as f64 - half_rows as f64) / padded_rows as f64;
+            let fu = (col as f64 - half_columns as f64) / padded_columns as f64;
+            (fu * fu + fv * fv).sqrt()
+        };
+        let mut h = vec![1f64; padded_rows * padded_columns];
+        let two_sigma_sqr = 2f64 * notch_size * notch_size;
+        let mut num_notches = 0usize;
+
+        if method == "auto" {
+            if verbose {
+                println!("Detecting noise spikes...")
+            };
+            let mut log_power = vec![0f64; padded_rows * padded_columns];
+            for row in 0..padded_rows {
+                for col in 0..padded_columns {
+                    let idx = centred_index(row, col);
+                    log_power[row * padded_columns + col] =
+                        (re[idx] * re[idx] + im[idx] * im[idx]).ln_1p();
+                }
+            }
+
+            // integral image of the log power, for the local average
+            let mut integral = vec![0f64; (padded_rows + 1) * (padded_columns + 1)];
+            for row in 0..padded_rows {
+                let mut row_sum = 0f64;
+                for col in 0..padded_columns {
+                    row_sum += log_power[row * padded_columns + col];
+                    integral[(row + 1) * (padded_columns + 1) + col + 1] =
+                        integral[row * (padded_columns + 1) + col + 1] + row_sum;
+                }
+            }
+
+            let half_window = (filter_size / 2) as isize;
+            let log_threshold = threshold.ln();
+            let notch_radius = (4f64 * notch_size).ceil() as isize;
+            let mut spikes = vec![];
+            for row in 0..padded_rows {
+                for col in 0..padded_columns {
+                    if radial_freq(row, col) < min_freq {
+                        continue;
+                    }
+                    let z = log_power[row * padded_columns + col];
+                    let r1 = (row as isize - half_window).max(0) as usize;
+                    let r2 = ((row as isize + half_window) as usize).min(padded_rows - 1) + 1;
+                    let c1 = (col as isize - half_window).max(0) as usize;
+                    let c2 = ((col as isize + half_window) as usize).min(padded_columns - 1) + 1;
+                    let w = (padded_columns + 1) as usize;
+                    let local_sum = integral[r2 * w + c2] - integral[r1 * w + c2]
+                        - integral[r2 * w + c1]
+                        + integral[r1 * w + c1];
+                    let local_mean = local_sum / ((r2 - r1) * (c2 - c1)) as f64;
+                    if z - local_mean <= log_threshold {
+                        continue;
+                    }
+                    // spikes must be local maxima
+                    let mut is_max = true;
+                    'neighbours: for dr in -1isize..=1 {
+                        for dc in -1isize..=1 {
+                            let (rn, cn) = (row as isize + dr, col as isize + dc);
+                            if (dr != 0 || dc != 0)
+                                && rn >= 0
+                                && cn >= 0
+                                && rn < padded_rows as isize
+                                && cn < padded_columns as isize
+                                && log_power[rn as usize * padded_columns + cn as usize] > z
+                            {
+                                is_max = false;
+                                break 'neighbours;
+                            }
+                        }
+                    }
+                    if is_max {
+                        spikes.push((row as isize, col as isize));
+                    }
+                }
+            }
+
+            for (row, col) in spikes {
+                for rn in (row - notch_radius).max(0)
+                    ..=(row + notch_radius).min(padded_rows as isize - 1)
+                {
+                    for cn in (col - notch_radius).max(0)
+                        ..=(col + notch_radius).min(padded_columns as isize - 1)
+                    {
+                        let d2 = ((rn - row) * (rn - row) + (cn - col) * (cn - col)) as f64;
+                        h[rn as usize * padded_columns + cn as usize] *=
+                            1f64 - (-d2 / two_sigma_sqr).exp();
+                    }
+                }
+                num_notches += 1;
+            }
+        } else {
+            // horizontal stripes occupy the vertical frequency axis and vice versa
+            for row in 0..padded_rows {
+                for col in 0..padded_columns {
+                    let d = if method == "horizontal" {
+                        col as f64 - half_columns as f64
+                    } else {
+                        row as f64 - half_rows as f64
+                    };
+                    let f_along = if method == "horizontal" {
+                        ((row as f64 - half_rows as f64) / padded_rows as f64).abs()
+                    } else {
+                        ((col as f64 - half_columns as f64) / padded_columns as f64).abs()
+                    };
+                    if f_along >= min_freq {
+                        h[row * padded_columns + col] *= 1f64 - (-d * d / two_sigma_sqr).exp();
+                    }
+                }
+            }
+            num_notches = 1;
+        }
+
+        if verbose {
+            println!("Number of notches: {}", num_notches);
+            println!("Performing inverse transform...")
+        };
+        for row in 0..padded_rows {
+            for col in 0..padded_columns {
+                let idx = centred_index(row, col);
+                re[idx] *= h[row * padded_columns + col];
+                im[idx] *= h[row * padded_columns + col];
+            }
+        }
+        fft2d(&mut re, &mut im, padded_rows, padded_columns, true);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row as isize, col as isize) != nodata {
+                    output.set_value(row as isize, col as isize, re[row * padded_columns + col]);
+                } else {
+                    output.set_value(row as isize, col as isize, nodata);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if method == "auto" {
+            output.add_metadata_entry(format!("Spike power threshold: {}", threshold));
+            output.add_metadata_entry(format!("Spectral window size: {}", filter_size));
+        }
+        output.add_metadata_entry(format!("Minimum noise frequency: {}", min_freq));
+        output.add_metadata_entry(format!("Notch size: {}", notch_size));
+        output.add_metadata_entry(format!("Number of notches: {}", num_notches));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !mask_file.is_empty() {
+            let mut configs = input.configs.clone();
+            configs.rows = padded_rows;
+            configs.columns = padded_columns;
+            configs.south = configs.north - padded_rows as f64 * configs.resolution_y;
+            configs.east = configs.west + padded_columns as f64 * configs.resolution_x;
+            configs.nodata = -32768f64;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            let mut out_mask = Raster::initialize_using_config(&mask_file, &configs);
+            for row in 0..padded_rows {
+                for col in 0..padded_columns {
+                    out_mask.set_value(row as isize, col as isize, h[row * padded_columns + col]);
+                }
+            }
+            out_mask.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            out_mask.add_metadata_entry(format!("Input file: {}", input_file));
+            let _ = match out_mask.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -30,7 +30,7 @@ use std::thread;
 /// the output raster must be read and written to during the same loop.
 ///
 /// # See Also
-/// `LineThinning`
+/// `LineThinning`, `SkeletonizeRaster`
 pub struct RemoveSpurs {
     name: String,
     description: String,
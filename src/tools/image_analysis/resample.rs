@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/01/2018
-Last Modified: 13/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -17,17 +17,38 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
-/// Resample is very similar in operation to the Mosaic tool. The Resample tool should
-/// be used when there is an existing image into which you would like to dump information
-/// from one or more source images. If the source images are more extensive than the
-/// destination image, i.e. there are areas that extend beyond the destination image
+/// This tool resamples one or more input images (`--inputs`) onto a new grid. The grid cell
+/// values are determined using one of six methods (`--method`). The nearest neighbour ('nn'),
+/// bilinear interpolation ('bilinear'), and cubic convolution ('cc') methods interpolate the
+/// input values at the centre of each output cell and are best suited to resampling to a similar
+/// or finer resolution; nearest neighbour should be used for categorical data. The 'average',
+/// 'mode', and 'sum' methods aggregate the values of all input cells with centres that fall within
+/// each output cell and are intended for resampling to a coarser resolution, e.g. averaging a
+/// continuous surface, taking the majority class of a categorical map, or totalling counts or
+/// areas. The mode method is suitable for categorical data. Where an output cell is smaller than
+/// the input cells, the aggregation methods fall back to the nearest neighbour value.
+///
+/// The output grid can be defined in several ways. When a base raster (`--base`) is specified
+/// without a cell size, the output (`--output`) will have exactly the same grid as the base
+/// raster, i.e. the same extent, rows, columns, and cell size. This is the best way to ensure that
+/// derivative layers align perfectly, cell for cell, for subsequent map algebra. When a cell size
+/// (`--cell_size`) is specified, the output grid will cover the extent of the inputs at the
+/// specified resolution. If a base raster is also provided, the output grid lines are snapped to
+/// those of the base raster, such that the output shares its grid origin. If neither is specified,
+/// the output has the extent of the inputs and the finest of the input resolutions.
+///
+/// Alternatively, an existing destination raster (`--destination`) may be specified, into which
+/// the information from the source images will be dumped. If the source images are more extensive
+/// than the destination image, i.e. there are areas that extend beyond the destination image
 /// boundaries, these areas will not be represented in the updated image. Grid cells in the
-/// destination image that are not overlapping with any of the input source images will not
-/// be updated, i.e. they will possess the same value as before the resampling operation. The
-/// Mosaic tool is used when there is no existing destination image. In this case, a new
-/// image is created that represents the bounding rectangle of each of the two or more input
-/// images. Grid cells in the output image that do not overlap with any of the input images
-/// will be assigned the NoData value.
+/// destination image that are not overlapping with any of the input source images will not be
+/// updated, i.e. they will possess the same value as before the resampling operation.
+///
+/// Where multiple inputs overlap, the output value is taken from the first image in the list
+/// possessing valid data. The Mosaic tool offers more control over the handling of overlaps.
+///
+/// # See Also
+/// `Mosaic`, `AggregateRaster`
 pub struct Resample {
     name: String,
     description: String,
@@ -60,14 +81,41 @@ impl Resample {
             description: "Destination raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
-            optional: false,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file, used when no destination raster is specified.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of the output raster.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified raster defining, or providing the grid origin of, the output grid.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
         });
 
         parameters.push(ToolParameter{
             name: "Resampling Method".to_owned(), 
             flags: vec!["--method".to_owned()], 
-            description: "Resampling method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution)".to_owned(),
-            parameter_type: ParameterType::OptionList(vec!["nn".to_owned(), "bilinear".to_owned(), "cc".to_owned()]),
+            description: "Resampling method; options include 'nn' (nearest neighbour), 'bilinear', 'cc' (cubic convolution), 'average', 'mode', and 'sum'".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["nn".to_owned(), "bilinear".to_owned(), "cc".to_owned(), "average".to_owned(), "mode".to_owned(), "sum".to_owned()]),
             default_value: Some("cc".to_owned()),
             optional: true
         });
@@ -83,7 +131,8 @@ impl Resample {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' --destination=dest.tif --method='cc", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' --destination=dest.tif --method='cc'
+>>.*{0} -r={1} -v --wd='*path*to*data*' -i=landcover.tif -o=output.tif --base=dem.tif --method=mode", short_exe, name).replace("*", &sep);
 
         Resample {
             name: name,
@@ -131,6 +180,9 @@ impl WhiteboxTool for Resample {
     ) -> Result<(), Error> {
         let mut input_files = String::new();
         let mut destination_file = String::new();
+        let mut output_file = String::new();
+        let mut base_file = String::new();
+        let mut cell_size = 0f64;
         let mut method = String::from("cc");
 
         if args.len() == 0 {
@@ -161,13 +213,45 @@ impl WhiteboxTool for Resample {
                 } else {
                     args[i + 1].to_string()
                 };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-base" {
+                base_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-cell_size" {
+                cell_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             } else if flag_val == "-method" {
                 method = if keyval {
                     vec[1].to_string()
                 } else {
                     args[i + 1].to_string()
                 };
-                if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
+                if method.to_lowercase().contains("av") || method.to_lowercase().contains("mean") {
+                    method = "average".to_string();
+                } else if method.to_lowercase().contains("mode")
+                    || method.to_lowercase().contains("maj")
+                {
+                    method = "mode".to_string();
+                } else if method.to_lowercase().contains("sum") {
+                    method = "sum".to_string();
+                } else if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
                 {
                     method = "nn".to_string();
                 } else if method.to_lowercase().contains("bilinear")
@@ -193,14 +277,26 @@ impl WhiteboxTool for Resample {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !destination_file.contains(&sep) && !destination_file.contains("/") {
-            destination_file = format!("{}{}", working_directory, destination_file);
-        }
+        if !destination_file.is_empty() {
+            if !destination_file.contains(&sep) && !destination_file.contains("/") {
+                destination_file = format!("{}{}", working_directory, destination_file);
+            }
 
-        // see if the destination file exists.
-        if !path::Path::new(&destination_file).exists() {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "The destination raster file does not exist. If you want to create a new file, try the Mosaic tool rather than Resample."));
+            // see if the destination file exists.
+            if !path::Path::new(&destination_file).exists() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "The destination raster file does not exist. If you want to create a new file, specify an output file instead."));
+            }
+        } else if output_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a destination or an output raster file must be specified.",
+            ));
+        } else if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !base_file.is_empty() && !base_file.contains(&sep) && !base_file.contains("/") {
+            base_file = format!("{}{}", working_directory, base_file);
         }
 
         let mut cmd = input_files.split(";");
@@ -217,12 +313,6 @@ impl WhiteboxTool for Resample {
 
         let start = Instant::now();
 
-        // Open the destination raster.
-        let mut destination = Raster::new(&destination_file, "rw")?;
-        let rows = destination.configs.rows as isize;
-        let columns = destination.configs.columns as isize;
-        let nodata = destination.configs.nodata;
-
         // read the input files
         if verbose {
             println!("Reading data...")
@@ -244,15 +334,104 @@ impl WhiteboxTool for Resample {
             }
         }
 
+        if inputs
+            .iter()
+            .any(|r| r.configs.photometric_interp == PhotometricInterpretation::RGB)
+            && method != "nn"
+            && method != "mode"
+        {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                "Colour-composite (RGB) inputs can only be resampled using the 'nn' or 'mode' methods."));
+        }
+
+        let mut output = if !destination_file.is_empty() {
+            // Open the destination raster.
+            Raster::new(&destination_file, "rw")?
+        } else if !base_file.is_empty() && cell_size <= 0f64 {
+            // the output grid is identical to that of the base raster
+            let base = Raster::new(&base_file, "r")?;
+            let mut output = Raster::initialize_using_file(&output_file, &base);
+            output.configs.data_type = inputs[0].configs.data_type;
+            output.configs.photometric_interp = inputs[0].configs.photometric_interp;
+            output.configs.palette = inputs[0].configs.palette.clone();
+            if method == "nn" || method == "mode" {
+                output.reinitialize_values(nodata_vals[0]);
+                output.configs.nodata = nodata_vals[0];
+            }
+            output
+        } else {
+            // the output grid covers the extent of the inputs
+            let mut north = f64::NEG_INFINITY;
+            let mut south = f64::INFINITY;
+            let mut east = f64::NEG_INFINITY;
+            let mut west = f64::INFINITY;
+            let mut resolution_x = f64::INFINITY;
+            let mut resolution_y = f64::INFINITY;
+            for input in inputs.iter() {
+                north = north.max(input.configs.north);
+                south = south.min(input.configs.south);
+                east = east.max(input.configs.east);
+                west = west.min(input.configs.west);
+                resolution_x = resolution_x.min(input.configs.resolution_x);
+                resolution_y = resolution_y.min(input.configs.resolution_y);
+            }
+            if cell_size > 0f64 {
+                resolution_x = cell_size;
+                resolution_y = cell_size;
+            }
+            if !base_file.is_empty() {
+                // snap the grid lines to those of the base raster
+                let base = Raster::new(&base_file, "r")?;
+                west = base.configs.west
+                    + ((west - base.configs.west) / resolution_x).floor() * resolution_x;
+                north = base.configs.north
+                    - ((base.configs.north - north) / resolution_y).floor() * resolution_y;
+            }
+            let rows = ((north - south) / resolution_y).ceil() as usize;
+            let columns = ((east - west) / resolution_x).ceil() as usize;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows;
+            configs.columns = columns;
+            configs.north = north;
+            configs.south = north - rows as f64 * resolution_y;
+            configs.east = west + columns as f64 * resolution_x;
+            configs.west = west;
+            configs.resolution_x = resolution_x;
+            configs.resolution_y = resolution_y;
+            configs.nodata = if method == "nn" || method == "mode" {
+                nodata_vals[0]
+            } else {
+                -32768f64
+            };
+            configs.data_type = inputs[0].configs.data_type;
+            configs.photometric_interp = inputs[0].configs.photometric_interp;
+            configs.palette = inputs[0].configs.palette.clone();
+            configs.projection = inputs[0].configs.projection.clone();
+            configs.epsg_code = inputs[0].configs.epsg_code;
+            Raster::initialize_using_config(&output_file, &configs)
+        };
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let nodata = output.configs.nodata;
+        if method != "nn" && method != "mode" {
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.data_type = DataType::F32;
+        }
+        let half_x = output.configs.resolution_x / 2f64;
+        let half_y = output.configs.resolution_y / 2f64;
+
         // create the x and y arrays
         let mut x: Vec<f64> = Vec::with_capacity(columns as usize);
         for col in 0..columns {
-            x.push(destination.get_x_from_column(col));
+            x.push(output.get_x_from_column(col));
         }
 
         let mut y: Vec<f64> = Vec::with_capacity(rows as usize);
         for row in 0..rows {
-            y.push(destination.get_y_from_row(row));
+            y.push(output.get_y_from_row(row));
         }
 
         let x = Arc::new(x);
@@ -261,233 +440,68 @@ impl WhiteboxTool for Resample {
         let nodata_vals = Arc::new(nodata_vals);
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
-        if method == "nn" {
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let (mut col_src, mut row_src): (isize, isize);
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            for i in 0..num_files {
-                                row_src = inputs[i].get_row_from_y(y[row as usize]);
-                                col_src = inputs[i].get_column_from_x(x[col as usize]);
-                                // row_src = ((inputs[i].configs.north - y[row as usize]) / inputs[i].configs.resolution_y).round() as isize;
-                                // col_src = ((x[col as usize] - inputs[i].configs.west) / inputs[i].configs.resolution_x).round() as isize;
-                                z = inputs[i].get_value(row_src, col_src);
-                                if z != nodata_vals[i] {
-                                    data[col as usize] = z;
-                                    break;
-                                }
-                            }
-                        }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns {
-                    if data[col as usize] != nodata {
-                        destination.set_value(row, col, data[col as usize]);
-                    }
-                }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
-                }
-            }
-        } else if method == "cc" {
-            destination.configs.photometric_interp = PhotometricInterpretation::Continuous;
-            destination.configs.data_type = DataType::F32;
-
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let shift_x = [-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2];
-                    let shift_y = [-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2];
-                    let num_neighbours = 16;
-                    let mut neighbour = [[0f64; 2]; 16];
-                    let (mut col_src, mut row_src): (f64, f64);
-                    let (mut col_n, mut row_n): (isize, isize);
-                    let (mut origin_row, mut origin_col): (isize, isize);
-                    let (mut dx, mut dy): (f64, f64);
-                    let mut sum_dist: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            let mut flag = true;
-                            for i in 0..num_files {
-                                if !flag {
-                                    break;
-                                }
-                                // row_src = inputs[i].get_row_from_y(y[row as usize]);
-                                // col_src = inputs[i].get_column_from_x(x[col as usize]);
-                                row_src = (inputs[i].configs.north - y[row as usize])
-                                    / inputs[i].configs.resolution_y;
-                                col_src = (x[col as usize] - inputs[i].configs.west)
-                                    / inputs[i].configs.resolution_x;
-                                origin_row = row_src.floor() as isize;
-                                origin_col = col_src.floor() as isize;
-                                sum_dist = 0f64;
-                                for n in 0..num_neighbours {
-                                    row_n = origin_row + shift_y[n];
-                                    col_n = origin_col + shift_x[n];
-                                    neighbour[n][0] = inputs[i].get_value(row_n, col_n);
-                                    dy = row_n as f64 - row_src;
-                                    dx = col_n as f64 - col_src;
-
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
-                                        neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
-                                        sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
-                                        neighbour[n][1] = 0f64;
-                                    } else {
-                                        data[col as usize] = neighbour[n][0];
-                                        flag = false;
-                                    }
-                                }
-
-                                if sum_dist > 0f64 {
-                                    z = 0f64;
-                                    for n in 0..num_neighbours {
-                                        z += (neighbour[n][0] * neighbour[n][1]) / sum_dist;
-                                    }
-                                    data[col as usize] = z;
-                                    flag = false;
-                                }
+        for tid in 0..num_procs {
+            let inputs = inputs.clone();
+            let nodata_vals = nodata_vals.clone();
+            let x = x.clone();
+            let y = y.clone();
+            let method = method.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut z: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        for i in 0..num_files {
+                            z = resample_value(
+                                &inputs[i],
+                                nodata_vals[i],
+                                x[col as usize],
+                                y[row as usize],
+                                half_x,
+                                half_y,
+                                &method,
+                            );
+                            if z != nodata_vals[i] {
+                                data[col as usize] = z;
+                                break;
                             }
                         }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns as usize {
-                    if data[col] != nodata {
-                        destination.set_value(row, col as isize, data[col]);
                     }
+                    tx.send((row, data)).unwrap();
                 }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
+            });
+        }
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for col in 0..columns as usize {
+                if data[col] != nodata {
+                    output.set_value(row, col as isize, data[col]);
                 }
             }
-        } else {
-            // bilinear
-            destination.configs.photometric_interp = PhotometricInterpretation::Continuous;
-            destination.configs.data_type = DataType::F32;
-            for tid in 0..num_procs {
-                let inputs = inputs.clone();
-                let nodata_vals = nodata_vals.clone();
-                let x = x.clone();
-                let y = y.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z: f64;
-                    let shift_x = [0, 1, 0, 1];
-                    let shift_y = [0, 0, 1, 1];
-                    let num_neighbours = 4;
-                    let mut neighbour = [[0f64; 2]; 4];
-                    let (mut col_src, mut row_src): (f64, f64);
-                    let (mut col_n, mut row_n): (isize, isize);
-                    let (mut origin_col, mut origin_row): (isize, isize);
-                    let (mut dx, mut dy): (f64, f64);
-                    let mut sum_dist: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            let mut flag = true;
-                            for i in 0..num_files {
-                                if !flag {
-                                    break;
-                                }
-                                row_src = (inputs[i].configs.north - y[row as usize])
-                                    / inputs[i].configs.resolution_y;
-                                col_src = (x[col as usize] - inputs[i].configs.west)
-                                    / inputs[i].configs.resolution_x;
-                                origin_row = row_src.floor() as isize;
-                                origin_col = col_src.floor() as isize;
-                                sum_dist = 0f64;
-                                for n in 0..num_neighbours {
-                                    row_n = origin_row + shift_y[n];
-                                    col_n = origin_col + shift_x[n];
-                                    neighbour[n][0] = inputs[i].get_value(row_n, col_n);
-                                    dy = row_n as f64 - row_src;
-                                    dx = col_n as f64 - col_src;
-
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
-                                        neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
-                                        sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
-                                        neighbour[n][1] = 0f64;
-                                    } else {
-                                        data[col as usize] = neighbour[n][0];
-                                        flag = false;
-                                    }
-                                }
-
-                                if sum_dist > 0f64 {
-                                    z = 0f64;
-                                    for n in 0..num_neighbours {
-                                        z += (neighbour[n][0] * neighbour[n][1]) / sum_dist;
-                                    }
-                                    data[col as usize] = z;
-                                    flag = false;
-                                }
-                            }
-                        }
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns as usize {
-                    if data[col] != nodata {
-                        destination.set_value(row, col as isize, data[col]);
-                    }
-                }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
-        destination.add_metadata_entry(format!(
+        output.add_metadata_entry(format!(
             "Modified by whitebox_tools\' {} tool",
             self.get_tool_name()
         ));
+        output.add_metadata_entry(format!("Resampling method: {}", method));
 
         if verbose {
             println!("Saving data...")
         };
-        let _ = match destination.write() {
+        let _ = match output.write() {
             Ok(_) => {
                 if verbose {
-                    println!("Destination file written")
+                    println!("Output file written")
                 }
             }
             Err(e) => return Err(e),
@@ -502,3 +516,100 @@ impl WhiteboxTool for Resample {
         Ok(())
     }
 }
+
+/// Determines the value of an image at a map coordinate using the specified resampling
+/// method, returning the image's NoData value where there is no valid data. The
+/// aggregation methods ('average', 'mode', and 'sum') operate on the source cells whose
+/// centres fall within the output cell, which extends `half_x` and `half_y` either side of
+/// the coordinate, and fall back to the nearest neighbour where the output cell contains
+/// no source cell centres, i.e. when the output grid is finer than the source.
+fn resample_value(
+    input: &Raster,
+    nodata: f64,
+    x: f64,
+    y: f64,
+    half_x: f64,
+    half_y: f64,
+    method: &str,
+) -> f64 {
+    if method == "average" || method == "mode" || method == "sum" {
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+        let col_start = ((x - half_x - input.configs.west) / res_x - 0.5).ceil() as isize;
+        let col_end = ((x + half_x - input.configs.west) / res_x - 0.5).ceil() as isize - 1;
+        let row_start = ((input.configs.north - y - half_y) / res_y - 0.5).ceil() as isize;
+        let row_end = ((input.configs.north - y + half_y) / res_y - 0.5).ceil() as isize - 1;
+        if col_end >= col_start && row_end >= row_start {
+            let mut values = vec![];
+            for row in row_start.max(0)..=row_end.min(input.configs.rows as isize - 1) {
+                for col in col_start.max(0)..=col_end.min(input.configs.columns as isize - 1) {
+                    let z = input.get_value(row, col);
+                    if z != nodata {
+                        values.push(z);
+                    }
+                }
+            }
+            if values.is_empty() {
+                return nodata;
+            }
+            if method == "sum" {
+                return values.iter().sum();
+            } else if method == "average" {
+                return values.iter().sum::<f64>() / values.len() as f64;
+            }
+            // mode; ties are resolved in favour of the lowest value
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let (mut mode, mut mode_count) = (values[0], 0);
+            let mut k = 0;
+            while k < values.len() {
+                let mut j = k;
+                while j < values.len() && values[j] == values[k] {
+                    j += 1;
+                }
+                if j - k > mode_count {
+                    mode_count = j - k;
+                    mode = values[k];
+                }
+                k = j;
+            }
+            return mode;
+        }
+    }
+    if method != "bilinear" && method != "cc" {
+        return input.get_value(input.get_row_from_y(y), input.get_column_from_x(x));
+    }
+    let (shift_x, shift_y): (&[isize], &[isize]) = if method == "cc" {
+        (
+            &[-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2],
+            &[-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2],
+        )
+    } else {
+        (&[0, 1, 0, 1], &[0, 0, 1, 1])
+    };
+    // fractional row and column, relative to the cell centres
+    let row_src = (input.configs.north - y) / input.configs.resolution_y - 0.5;
+    let col_src = (x - input.configs.west) / input.configs.resolution_x - 0.5;
+    let origin_row = row_src.floor() as isize;
+    let origin_col = col_src.floor() as isize;
+    let (mut sum_w, mut sum_z) = (0f64, 0f64);
+    for n in 0..shift_x.len() {
+        let row_n = origin_row + shift_y[n];
+        let col_n = origin_col + shift_x[n];
+        let z = input.get_value(row_n, col_n);
+        if z != nodata {
+            let dy = row_n as f64 - row_src;
+            let dx = col_n as f64 - col_src;
+            let dist = dx * dx + dy * dy;
+            if dist == 0f64 {
+                return z;
+            }
+            sum_w += 1f64 / dist;
+            sum_z += z / dist;
+        }
+    }
+    if sum_w > 0f64 {
+        sum_z / sum_w
+    } else {
+        nodata
+    }
+}
@@ -0,0 +1,563 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::otsu_threshold;
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool performs a change detection analysis on a two-date multi-spectral dataset using the
+/// selective principal component analysis (PCA) method (Chavez and Kwarteng, 1989). Rather than
+/// performing a PCA on the full stack of bands from both dates, selective PCA transforms each pair of
+/// corresponding bands (i.e. the same band from each of the two dates) separately. Because most of the
+/// landscape is unchanged between dates, the two images of a band pair are usually highly correlated, and
+/// the first principal component of the pair captures this shared, unchanged information (including overall
+/// radiometric differences between the dates, e.g. due to differences in illumination or atmospheric
+/// conditions). The second, minor component captures the departures from this relation, i.e. change.
+///
+/// The minor component of each band pair is standardized by its standard deviation, and the tool outputs the
+/// magnitude of the resulting vector of standardized minor components (`--magnitude`), which is analogous to the
+/// change vector magnitude of `ChangeVectorAnalysis`, but is relatively insensitive to date-wide radiometric
+/// shifts. The optional direction output (`--direction`) codes the sign of each band's minor component in the
+/// same way as the direction image of `ChangeVectorAnalysis`, i.e. the value is the sum of 2<sup>i</sup> for each
+/// band *i* (starting at 0) with a positive minor component, meaning a band value that increased relative to
+/// that predicted by the dominant inter-date relation. If the optional binary change output (`--out_change`) is
+/// specified, pixels with a magnitude greater than a threshold are assigned 1, and 0 otherwise. The magnitude
+/// threshold may be specified by the user (`--threshold`) or otherwise is determined automatically using Otsu's
+/// (1979) method.
+///
+/// There must be the same number of image files (bands) for the two dates and they must be entered in the same
+/// order. All images must share the same number of rows and columns and spatial extent. **NoData** values in any
+/// of the input images will result in **NoData** in the outputs.
+///
+/// # Reference
+/// Chavez, P. S., and Kwarteng, A. Y. (1989). Extracting spectral contrast in Landsat Thematic Mapper image data
+/// using selective principal component analysis. Photogrammetric Engineering and Remote Sensing, 55(3), 339-348.
+///
+/// Otsu, N. (1979). A threshold selection method from gray-level histograms. IEEE Transactions on Systems, Man,
+/// and Cybernetics, 9(1), 62-66.
+///
+/// # See Also
+/// `ChangeVectorAnalysis`, `PrincipalComponentAnalysis`
+pub struct SelectivePcaChangeDetection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SelectivePcaChangeDetection {
+    pub fn new() -> SelectivePcaChangeDetection {
+        // public constructor
+        let name = "SelectivePcaChangeDetection".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Performs a selective principal component change detection on a two-date multi-spectral dataset.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Earlier Date Input Files".to_owned(),
+            flags: vec!["--date1".to_owned()],
+            description: "Input raster files for the earlier date.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Later Date Input Files".to_owned(),
+            flags: vec!["--date2".to_owned()],
+            description: "Input raster files for the later date.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Change Magnitude File".to_owned(),
+            flags: vec!["--magnitude".to_owned()],
+            description: "Output change magnitude raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Change Direction File (optional)".to_owned(),
+            flags: vec!["--direction".to_owned()],
+            description: "Optional output change direction raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Binary Change File (optional)".to_owned(),
+            flags: vec!["--out_change".to_owned()],
+            description: "Optional output binary change raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Change Magnitude Threshold (optional; Otsu if unspecified)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Magnitude threshold for the binary change output. Determined using Otsu's method if unspecified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --date1='d1_band1.tif;d1_band2.tif;d1_band3.tif' --date2='d2_band1.tif;d2_band2.tif;d2_band3.tif' --magnitude=mag_out.tif --direction=dir_out.tif --out_change=change.tif", short_exe, name).replace("*", &sep);
+
+        SelectivePcaChangeDetection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SelectivePcaChangeDetection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input1_files_str = String::new();
+        let mut input2_files_str = String::new();
+        let mut magnitude_file = String::new();
+        let mut direction_file = String::new();
+        let mut change_file = String::new();
+        let mut threshold = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-date1" {
+                input1_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-date2" {
+                input2_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-magnitude" {
+                magnitude_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-direction" {
+                direction_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_change" {
+                change_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let start = Instant::now();
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !magnitude_file.contains(&sep) && !magnitude_file.contains("/") {
+            magnitude_file = format!("{}{}", working_directory, magnitude_file);
+        }
+        if !direction_file.is_empty()
+            && !direction_file.contains(&sep)
+            && !direction_file.contains("/")
+        {
+            direction_file = format!("{}{}", working_directory, direction_file);
+        }
+        if !change_file.is_empty() && !change_file.contains(&sep) && !change_file.contains("/") {
+            change_file = format!("{}{}", working_directory, change_file);
+        }
+
+        let mut cmd = input1_files_str.split(";");
+        let mut input1_files = cmd.collect::<Vec<&str>>();
+        if input1_files.len() == 1 {
+            cmd = input1_files_str.split(",");
+            input1_files = cmd.collect::<Vec<&str>>();
+        }
+        let input1_files: Vec<&str> = input1_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+
+        cmd = input2_files_str.split(";");
+        let mut input2_files = cmd.collect::<Vec<&str>>();
+        if input2_files.len() == 1 {
+            cmd = input2_files_str.split(",");
+            input2_files = cmd.collect::<Vec<&str>>();
+        }
+        let input2_files: Vec<&str> = input2_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+
+        let num_files = input1_files.len();
+        if num_files == 0 || input2_files.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input for each date are required to operate this tool.",
+            ));
+        }
+        if num_files != input2_files.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There must be the same number of input files for each date.",
+            ));
+        }
+
+        let mut input1: Vec<Raster> = Vec::with_capacity(num_files);
+        let mut input2: Vec<Raster> = Vec::with_capacity(num_files);
+        for i in 0..num_files {
+            if verbose {
+                println!("Reading band {} of {}", i + 1, num_files);
+            }
+            let mut input1_file = input1_files[i].trim().to_owned();
+            if !input1_file.contains(&sep) && !input1_file.contains("/") {
+                input1_file = format!("{}{}", working_directory, input1_file);
+            }
+            let mut input2_file = input2_files[i].trim().to_owned();
+            if !input2_file.contains(&sep) && !input2_file.contains("/") {
+                input2_file = format!("{}{}", working_directory, input2_file);
+            }
+            let band1 = Raster::new(&input1_file, "r")?;
+            let band2 = Raster::new(&input2_file, "r")?;
+            if i == 0 {
+                input1.push(band1);
+            } else {
+                // make sure the inputs share the same grid and CRS
+                let band1 = align_raster_input(&input1[0], band1)?;
+                input1.push(band1);
+            }
+            let band2 = align_raster_input(&input1[0], band2)?;
+            input2.push(band2);
+        }
+
+        let rows = input1[0].configs.rows as isize;
+        let columns = input1[0].configs.columns as isize;
+        let nodata = -32768f64;
+
+        // find the cells that are valid in every band of both dates
+        let mut valid: Array2D<i8> = Array2D::new(rows, columns, 1i8, 0i8)?;
+        for i in 0..num_files {
+            let nodata1 = input1[i].configs.nodata;
+            let nodata2 = input2[i].configs.nodata;
+            for row in 0..rows {
+                for col in 0..columns {
+                    if input1[i].get_value(row, col) == nodata1
+                        || input2[i].get_value(row, col) == nodata2
+                    {
+                        valid.set_value(row, col, 0i8);
+                    }
+                }
+            }
+        }
+
+        // perform a PCA on each band pair, retaining the minor component
+        let mut minor_axis = vec![(0f64, 0f64); num_files];
+        let mut means = vec![(0f64, 0f64); num_files];
+        let mut minor_stdev = vec![1f64; num_files];
+        for i in 0..num_files {
+            let (mut n, mut sum1, mut sum2) = (0f64, 0f64, 0f64);
+            let (mut sum11, mut sum22, mut sum12) = (0f64, 0f64, 0f64);
+            let (mut z1, mut z2): (f64, f64);
+            for row in 0..rows {
+                for col in 0..columns {
+                    if valid.get_value(row, col) == 1i8 {
+                        z1 = input1[i].get_value(row, col);
+                        z2 = input2[i].get_value(row, col);
+                        n += 1f64;
+                        sum1 += z1;
+                        sum2 += z2;
+                        sum11 += z1 * z1;
+                        sum22 += z2 * z2;
+                        sum12 += z1 * z2;
+                    }
+                }
+            }
+            if n < 2f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input images do not contain enough valid data.",
+                ));
+            }
+            let (m1, m2) = (sum1 / n, sum2 / n);
+            let a = sum11 / n - m1 * m1;
+            let c = sum22 / n - m2 * m2;
+            let b = sum12 / n - m1 * m2;
+
+            // the smaller eigenvalue of the 2 x 2 covariance matrix and its eigenvector
+            let half_trace = (a + c) / 2f64;
+            let lambda1 = half_trace + (((a - c) / 2f64).powi(2) + b * b).sqrt();
+            let lambda2 = (half_trace - (((a - c) / 2f64).powi(2) + b * b).sqrt()).max(0f64);
+            let (mut v1, mut v2) = if b.abs() > 0f64 {
+                (b, lambda2 - a)
+            } else if a <= c {
+                (1f64, 0f64)
+            } else {
+                (0f64, 1f64)
+            };
+            let len = (v1 * v1 + v2 * v2).sqrt();
+            v1 /= len;
+            v2 /= len;
+            // orient the axis such that positive values indicate an increase in the later date
+            if v2 - v1 < 0f64 {
+                v1 = -v1;
+                v2 = -v2;
+            }
+            minor_axis[i] = (v1, v2);
+            means[i] = (m1, m2);
+            if lambda2 > 0f64 {
+                minor_stdev[i] = lambda2.sqrt();
+            }
+
+            if verbose {
+                println!(
+                    "Band {}: inter-date correlation = {:.4}, minor component variance = {:.4}%",
+                    i + 1,
+                    if a > 0f64 && c > 0f64 {
+                        b / (a * c).sqrt()
+                    } else {
+                        0f64
+                    },
+                    if lambda1 + lambda2 > 0f64 {
+                        100f64 * lambda2 / (lambda1 + lambda2)
+                    } else {
+                        0f64
+                    }
+                );
+            }
+        }
+
+        let mut out_magnitude = Raster::initialize_using_file(&magnitude_file, &input1[0]);
+        out_magnitude.configs.data_type = DataType::F32;
+        out_magnitude.configs.nodata = nodata;
+        out_magnitude.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        out_magnitude.reinitialize_values(nodata);
+        let mut out_direction = Raster::initialize_using_file(&magnitude_file, &input1[0]);
+        out_direction.configs.data_type = DataType::I32;
+        out_direction.configs.nodata = nodata;
+        out_direction.reinitialize_values(nodata);
+        let mut magnitudes = vec![];
+        let (mut z, mut score, mut direction): (f64, f64, f64);
+        for row in 0..rows {
+            for col in 0..columns {
+                if valid.get_value(row, col) == 1i8 {
+                    z = 0f64;
+                    direction = 0f64;
+                    for i in 0..num_files {
+                        score = (minor_axis[i].0 * (input1[i].get_value(row, col) - means[i].0)
+                            + minor_axis[i].1 * (input2[i].get_value(row, col) - means[i].1))
+                            / minor_stdev[i];
+                        z += score * score;
+                        if score >= 0f64 {
+                            direction += 2f64.powi(i as i32);
+                        }
+                    }
+                    z = z.sqrt();
+                    out_magnitude.set_value(row, col, z);
+                    out_direction.set_value(row, col, direction);
+                    magnitudes.push(z);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        drop(input2);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+
+        out_magnitude.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        out_magnitude.add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
+        let _ = match out_magnitude.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !direction_file.is_empty() {
+            out_direction.file_name = direction_file.clone();
+            out_direction.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            out_direction.configs.palette = String::from("qual.plt");
+            out_direction.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            out_direction
+                .add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
+            let _ = match out_direction.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !change_file.is_empty() {
+            if threshold.is_nan() {
+                threshold = otsu_threshold(&magnitudes, 1024);
+                if verbose {
+                    println!("Otsu magnitude threshold: {:.4}", threshold);
+                }
+            }
+            let mut out_change = Raster::initialize_using_file(&change_file, &input1[0]);
+            out_change.configs.data_type = DataType::I16;
+            out_change.configs.nodata = nodata;
+            out_change.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            out_change.configs.palette = String::from("qual.plt");
+            for row in 0..rows {
+                for col in 0..columns {
+                    if valid.get_value(row, col) == 1i8 {
+                        if out_magnitude.get_value(row, col) > threshold {
+                            out_change.set_value(row, col, 1f64);
+                        } else {
+                            out_change.set_value(row, col, 0f64);
+                        }
+                    } else {
+                        out_change.set_value(row, col, nodata);
+                    }
+                }
+            }
+            out_change.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            out_change.add_metadata_entry(format!("Magnitude threshold: {}", threshold));
+            out_change.add_metadata_entry(format!("Elapsed Time (including I/O): {}", elapsed_time));
+            let _ = match out_change.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
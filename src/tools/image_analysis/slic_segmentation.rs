@@ -0,0 +1,673 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool segments multi-band imagery into spatially compact, spectrally homogeneous objects
+/// (superpixels) using the simple linear iterative clustering (SLIC) method of Achanta et al. (2012).
+/// SLIC is a localized k-means clustering in the combined spectral and image space. Cluster centres
+/// are initialized on a regular grid with an interval of *S* grid cells (`--size`), shifted to the
+/// position of lowest spectral gradient within their 3 x 3 neighbourhood, and each grid cell is then
+/// iteratively assigned to the nearest cluster centre within a 2*S* x 2*S* search window, using the
+/// distance:
+///
+/// > *D* = [*d<sub>c</sub>*<sup>2</sup> + (*d<sub>s</sub>* / *S*)<sup>2</sup> *m*<sup>2</sup>]<sup>0.5</sup>
+///
+/// where *d<sub>c</sub>* is the spectral distance, *d<sub>s</sub>* is the spatial distance, in grid cells,
+/// and *m* is the compactness parameter (`--compactness`). Prior to calculating spectral distances, each
+/// input band is rescaled to the range 0-100, using its minimum and maximum values, so that each band
+/// contributes equally to the segmentation. Larger compactness values produce more regularly shaped
+/// segments while smaller values produce segments that adhere more closely to image edges. After the
+/// final iteration (`--iterations`), connectivity is enforced by merging disjoint fragments smaller than
+/// one quarter of the nominal segment area into an adjacent segment.
+///
+/// The user must specify two or more input images (`--inputs`), typically the bands of a multi-spectral
+/// image, and the output segment raster (`--output`), in which each segment is assigned a unique
+/// identifier. Optionally, a points vector file (`--out_segments`) may also be created, containing the
+/// centroid of each segment and an attribute table with the segment identifier (`SEG_ID`), the number of
+/// grid cells within the segment (`NUM_CELLS`), and the mean value of each input band (`MEAN_1`, `MEAN_2`,
+/// etc.), enabling object-based image analysis. Each of the input images must have the same number of
+/// rows and columns and the same spatial extent. NoData values in any of the input images are excluded
+/// from the segmentation and are assigned NoData in the output.
+///
+/// # Reference
+/// Achanta, R., Shaji, A., Smith, K., Lucchi, A., Fua, P., and Süsstrunk, S. (2012). SLIC superpixels
+/// compared to state-of-the-art superpixel methods. *IEEE Transactions on Pattern Analysis and Machine
+/// Intelligence*, 34(11), 2274-2282.
+///
+/// # See Also
+/// `KMeansClustering`, `ModifiedKMeansClustering`, `Clump`
+pub struct SlicSegmentation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SlicSegmentation {
+    pub fn new() -> SlicSegmentation {
+        // public constructor
+        let name = "SlicSegmentation".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Segments a multi-spectral image into objects using SLIC superpixels.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output segment raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Segment Attributes File (optional)".to_owned(),
+            flags: vec!["--out_segments".to_owned()],
+            description: "Optional output points vector file of segment centroids and mean band values.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Segment Size (grid cells)".to_owned(),
+            flags: vec!["--size".to_owned()],
+            description: "Nominal segment width, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Compactness".to_owned(),
+            flags: vec!["--compactness".to_owned()],
+            description: "Compactness parameter; larger values produce more regular segments."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Iterations".to_owned(),
+            flags: vec!["--iterations".to_owned()],
+            description: "Number of clustering iterations.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' -o=segments.tif --out_segments=segments.shp --size=15 --compactness=20.0 --iterations=10", short_exe, name).replace("*", &sep);
+
+        SlicSegmentation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SlicSegmentation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files_str = String::new();
+        let mut output_file = String::new();
+        let mut segments_file = String::new();
+        let mut size = 10isize;
+        let mut compactness = 10f64;
+        let mut iterations = 10usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                input_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_segments" {
+                segments_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-size" {
+                size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as isize
+                };
+            } else if flag_val == "-compactness" {
+                compactness = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-iterations" {
+                iterations = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !segments_file.is_empty() && !segments_file.contains(&sep) && !segments_file.contains("/")
+        {
+            segments_file = format!("{}{}", working_directory, segments_file);
+        }
+
+        if size < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The segment size must be at least two grid cells.",
+            ));
+        }
+        if compactness <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The compactness parameter must be greater than zero.",
+            ));
+        }
+        if iterations < 1 {
+            iterations = 1;
+        }
+
+        let mut cmd = input_files_str.split(";");
+        let mut input_files = cmd.collect::<Vec<&str>>();
+        if input_files.len() == 1 {
+            cmd = input_files_str.split(",");
+            input_files = cmd.collect::<Vec<&str>>();
+        }
+        let input_files: Vec<&str> = input_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+        let num_files = input_files.len();
+        if num_files < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There is something incorrect about the input files. At least one input is required to operate this tool.",
+            ));
+        }
+
+        let mut input_raster: Vec<Raster> = Vec::with_capacity(num_files);
+        for i in 0..num_files {
+            if verbose {
+                println!("Reading file {} of {}", i + 1, num_files);
+            }
+            let mut input_file = input_files[i].trim().to_owned();
+            if !input_file.contains(&sep) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            let input = Raster::new(&input_file, "r")?;
+            if i == 0 {
+                input_raster.push(input);
+            } else {
+                // make sure the inputs share the same grid and CRS
+                let input = align_raster_input(&input_raster[0], input)?;
+                input_raster.push(input);
+            }
+        }
+        let rows = input_raster[0].configs.rows as isize;
+        let columns = input_raster[0].configs.columns as isize;
+
+        let start = Instant::now();
+
+        // read the rescaled band values into a single cell-interleaved array
+        let num_cells = (rows * columns) as usize;
+        let mut values = vec![0f64; num_cells * num_files];
+        let mut valid = vec![true; num_cells];
+        for i in 0..num_files {
+            let nodata = input_raster[i].configs.nodata;
+            let min_val = input_raster[i].configs.minimum;
+            let range = input_raster[i].configs.maximum - min_val;
+            let scale = if range > 0f64 { 100f64 / range } else { 0f64 };
+            for row in 0..rows {
+                for col in 0..columns {
+                    let idx = (row * columns + col) as usize;
+                    let z = input_raster[i].get_value(row, col);
+                    if z != nodata {
+                        values[idx * num_files + i] = (z - min_val) * scale;
+                    } else {
+                        valid[idx] = false;
+                    }
+                }
+            }
+        }
+
+        let spectral_dist_sqr = |a: usize, centre: &[f64]| -> f64 {
+            let mut d = 0f64;
+            for i in 0..num_files {
+                let diff = values[a * num_files + i] - centre[i];
+                d += diff * diff;
+            }
+            d
+        };
+
+        // initialize the cluster centres on a regular grid, moving each to the lowest
+        // gradient position in its 3 x 3 neighbourhood
+        let half = size / 2;
+        let mut centre_row: Vec<f64> = vec![];
+        let mut centre_col: Vec<f64> = vec![];
+        let mut centre_val: Vec<Vec<f64>> = vec![];
+        let mut row = half;
+        while row < rows {
+            let mut col = half;
+            while col < columns {
+                let mut best: Option<(isize, isize)> = None;
+                let mut min_gradient = f64::INFINITY;
+                for r in (row - 1)..(row + 2) {
+                    for c in (col - 1)..(col + 2) {
+                        if r < 1 || r >= rows - 1 || c < 1 || c >= columns - 1 {
+                            continue;
+                        }
+                        let idx = (r * columns + c) as usize;
+                        let n = ((r - 1) * columns + c) as usize;
+                        let s = ((r + 1) * columns + c) as usize;
+                        let e = (r * columns + c + 1) as usize;
+                        let w = (r * columns + c - 1) as usize;
+                        if !valid[idx] || !valid[n] || !valid[s] || !valid[e] || !valid[w] {
+                            continue;
+                        }
+                        let mut gradient = 0f64;
+                        for i in 0..num_files {
+                            let gx = values[e * num_files + i] - values[w * num_files + i];
+                            let gy = values[s * num_files + i] - values[n * num_files + i];
+                            gradient += gx * gx + gy * gy;
+                        }
+                        if gradient < min_gradient {
+                            min_gradient = gradient;
+                            best = Some((r, c));
+                        }
+                    }
+                }
+                if best.is_none() && valid[(row * columns + col) as usize] {
+                    best = Some((row, col));
+                }
+                if let Some((r, c)) = best {
+                    let idx = (r * columns + c) as usize;
+                    centre_row.push(r as f64);
+                    centre_col.push(c as f64);
+                    centre_val.push(values[idx * num_files..(idx + 1) * num_files].to_vec());
+                }
+                col += size;
+            }
+            row += size;
+        }
+        let num_centres = centre_row.len();
+        if num_centres == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No valid cluster centres could be located; the segment size may be too large.",
+            ));
+        }
+
+        // iteratively assign cells to the nearest cluster centre within a 2S x 2S window
+        const NO_LABEL: usize = usize::max_value();
+        let mut labels = vec![NO_LABEL; num_cells];
+        let mut distances = vec![f64::INFINITY; num_cells];
+        let spatial_weight = (compactness / size as f64) * (compactness / size as f64);
+        for iteration in 0..iterations {
+            for d in distances.iter_mut() {
+                *d = f64::INFINITY;
+            }
+            for k in 0..num_centres {
+                let cr = centre_row[k].round() as isize;
+                let cc = centre_col[k].round() as isize;
+                let row_start = (cr - size).max(0);
+                let row_end = (cr + size).min(rows - 1);
+                let col_start = (cc - size).max(0);
+                let col_end = (cc + size).min(columns - 1);
+                for r in row_start..=row_end {
+                    for c in col_start..=col_end {
+                        let idx = (r * columns + c) as usize;
+                        if !valid[idx] {
+                            continue;
+                        }
+                        let dr = r as f64 - centre_row[k];
+                        let dc = c as f64 - centre_col[k];
+                        let d = spectral_dist_sqr(idx, &centre_val[k])
+                            + (dr * dr + dc * dc) * spatial_weight;
+                        if d < distances[idx] {
+                            distances[idx] = d;
+                            labels[idx] = k;
+                        }
+                    }
+                }
+            }
+
+            // update the cluster centres
+            let mut sum_row = vec![0f64; num_centres];
+            let mut sum_col = vec![0f64; num_centres];
+            let mut sum_val = vec![vec![0f64; num_files]; num_centres];
+            let mut count = vec![0usize; num_centres];
+            for r in 0..rows {
+                for c in 0..columns {
+                    let idx = (r * columns + c) as usize;
+                    let k = labels[idx];
+                    if k != NO_LABEL && valid[idx] {
+                        sum_row[k] += r as f64;
+                        sum_col[k] += c as f64;
+                        for i in 0..num_files {
+                            sum_val[k][i] += values[idx * num_files + i];
+                        }
+                        count[k] += 1;
+                    }
+                }
+            }
+            for k in 0..num_centres {
+                if count[k] > 0 {
+                    let n = count[k] as f64;
+                    centre_row[k] = sum_row[k] / n;
+                    centre_col[k] = sum_col[k] / n;
+                    for i in 0..num_files {
+                        centre_val[k][i] = sum_val[k][i] / n;
+                    }
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * (iteration + 1) as f64 / iterations as f64) as usize;
+                if progress != old_progress {
+                    println!("Clustering: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // enforce connectivity, merging small fragments into an adjacent segment
+        let min_fragment_size = ((size * size) / 4).max(1) as usize;
+        let dx = [0, 1, 0, -1];
+        let dy = [-1, 0, 1, 0];
+        let mut segment: Array2D<i32> = Array2D::new(rows, columns, -1, -1)?;
+        let mut num_segments = 0i32;
+        let mut stack = vec![];
+        let mut fragment = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                let idx = (row * columns + col) as usize;
+                if !valid[idx] || labels[idx] == NO_LABEL || segment.get_value(row, col) != -1 {
+                    continue;
+                }
+                let k = labels[idx];
+                let seg_id = num_segments + 1;
+                let mut adjacent_id = -1i32;
+                fragment.clear();
+                segment.set_value(row, col, seg_id);
+                stack.push((row, col));
+                while let Some((r, c)) = stack.pop() {
+                    fragment.push((r, c));
+                    for n in 0..4 {
+                        let rn = r + dy[n];
+                        let cn = c + dx[n];
+                        if rn < 0 || rn >= rows || cn < 0 || cn >= columns {
+                            continue;
+                        }
+                        let idx_n = (rn * columns + cn) as usize;
+                        let sn = segment.get_value(rn, cn);
+                        if sn == -1 && valid[idx_n] && labels[idx_n] == k {
+                            segment.set_value(rn, cn, seg_id);
+                            stack.push((rn, cn));
+                        } else if sn > 0 && sn != seg_id {
+                            adjacent_id = sn;
+                        }
+                    }
+                }
+                if fragment.len() < min_fragment_size && adjacent_id > 0 {
+                    for &(r, c) in &fragment {
+                        segment.set_value(r, c, adjacent_id);
+                    }
+                } else {
+                    num_segments += 1;
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Enforcing connectivity: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // per-segment attributes
+        let n_seg = num_segments as usize;
+        let mut seg_count = vec![0usize; n_seg];
+        let mut seg_x = vec![0f64; n_seg];
+        let mut seg_y = vec![0f64; n_seg];
+        let mut seg_mean = vec![vec![0f64; num_files]; n_seg];
+        let mut output = Raster::initialize_using_file(&output_file, &input_raster[0]);
+        let out_nodata = -32768f64;
+        output.configs.nodata = out_nodata;
+        output.configs.data_type = DataType::I32;
+        output.configs.palette = "qual.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.reinitialize_values(out_nodata);
+        for row in 0..rows {
+            for col in 0..columns {
+                let s = segment.get_value(row, col);
+                if s > 0 {
+                    output.set_value(row, col, s as f64);
+                    let k = (s - 1) as usize;
+                    seg_count[k] += 1;
+                    seg_x[k] += input_raster[0].get_x_from_column(col);
+                    seg_y[k] += input_raster[0].get_y_from_row(row);
+                    for i in 0..num_files {
+                        seg_mean[k][i] += input_raster[i].get_value(row, col);
+                    }
+                }
+            }
+        }
+
+        if verbose {
+            println!("Number of segments: {}", num_segments);
+        }
+
+        if !segments_file.is_empty() {
+            let mut segments = Shapefile::new(&segments_file, ShapeType::Point)?;
+            segments.projection = input_raster[0].configs.coordinate_ref_system_wkt.clone();
+            segments
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+            segments
+                .attributes
+                .add_field(&AttributeField::new("SEG_ID", FieldDataType::Int, 7u8, 0u8));
+            segments.attributes.add_field(&AttributeField::new(
+                "NUM_CELLS",
+                FieldDataType::Int,
+                9u8,
+                0u8,
+            ));
+            for i in 0..num_files {
+                segments.attributes.add_field(&AttributeField::new(
+                    &format!("MEAN_{}", i + 1),
+                    FieldDataType::Real,
+                    12u8,
+                    4u8,
+                ));
+            }
+            for k in 0..n_seg {
+                if seg_count[k] == 0 {
+                    continue;
+                }
+                let n = seg_count[k] as f64;
+                segments.add_point_record(seg_x[k] / n, seg_y[k] / n);
+                let mut atts = vec![
+                    FieldData::Int(k as i32 + 1),
+                    FieldData::Int(k as i32 + 1),
+                    FieldData::Int(seg_count[k] as i32),
+                ];
+                for i in 0..num_files {
+                    atts.push(FieldData::Real(seg_mean[k][i] / n));
+                }
+                segments.attributes.add_record(atts, false);
+            }
+            if verbose {
+                println!("Saving segment attributes...")
+            };
+            let _ = match segments.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Segment attributes file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input files: {}", input_files_str));
+        output.add_metadata_entry(format!("Segment size: {}", size));
+        output.add_metadata_entry(format!("Compactness: {}", compactness));
+        output.add_metadata_entry(format!("Iterations: {}", iterations));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, find_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates the soil adjusted vegetation index (SAVI) of Huete (1988):
+///
+/// > SAVI = (1 + L)(NIR - Red) / (NIR + Red + L)
+///
+/// where the soil brightness correction factor, L, is 0.5. The SAVI minimizes the influence of the soil
+/// background on the vegetation signal, and is therefore preferred to the NDVI in areas of sparse vegetation
+/// cover. Because the correction factor is expressed in units of reflectance, the SAVI requires surface
+/// reflectance values in the range 0-1; images storing scaled reflectance must be converted during the
+/// calculation using the `--scale` and `--offset` parameters (e.g. `--scale=0.0001` for Sentinel-2 Level-2A
+/// imagery). The optimized SAVI (OSAVI), with L = 0.16, can be calculated using the `--correction` parameter of
+/// the `NormalizedDifferenceIndex` tool.
+///
+/// The NIR and red bands are specified with the band mapping parameter (`--bands`), either as single-band raster
+/// files, e.g. `--bands="nir=B08.tif;red=B04.tif"`, or as the (one-based) numbers of bands within a multi-band
+/// image (`--input`), e.g. `--bands="nir=5;red=4"`. Grid cells that are NoData in either band are assigned
+/// NoData in the output.
+///
+/// # Reference
+/// Huete, A. R. (1988). A soil-adjusted vegetation index (SAVI). *Remote Sensing of Environment*, 25(3),
+/// 295-309.
+///
+/// # See Also
+/// `EnhancedVegetationIndex`, `NormalizedDifferenceIndex`, `SpectralIndex`
+pub struct SoilAdjustedVegetationIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SoilAdjustedVegetationIndex {
+    pub fn new() -> SoilAdjustedVegetationIndex {
+        // public constructor
+        let name = "SoilAdjustedVegetationIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates the soil adjusted vegetation index (SAVI) from near-infrared and red image bands.".to_string();
+
+        let parameters = index_tool_parameters(find_index("savi"));
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --bands=\"nir=B08.tif;red=B04.tif\" --scale=0.0001 -o=savi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SoilAdjustedVegetationIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SoilAdjustedVegetationIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            find_index("savi"),
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
@@ -0,0 +1,518 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The SAR speckle filter tools (LeeSpeckleFilter, RefinedLeeFilter, FrostFilter, and
+GammaMapFilter) are thin wrappers around this module, which parses their shared parameters
+and applies the filter estimators. All of the filters assume a multiplicative speckle model,
+with the image values being backscatter intensities (i.e. linear power units), for which the
+coefficient of variation of the speckle is 1 / sqrt(L), where L is the equivalent number of
+looks. Images in decibels are converted into intensities before filtering and back afterwards.
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SpeckleFilterType {
+    Lee,
+    RefinedLee,
+    Frost,
+    GammaMap,
+}
+
+/// Returns the parameters shared by the speckle filter tools. The Frost filter takes a
+/// damping factor, and the other filters the equivalent number of looks.
+pub fn speckle_filter_parameters(filter: SpeckleFilterType) -> Vec<ToolParameter> {
+    let mut parameters = vec![];
+    parameters.push(ToolParameter {
+        name: "Input File".to_owned(),
+        flags: vec!["-i".to_owned(), "--input".to_owned()],
+        description: "Input SAR backscatter raster file.".to_owned(),
+        parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: false,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Output File".to_owned(),
+        flags: vec!["-o".to_owned(), "--output".to_owned()],
+        description: "Output raster file.".to_owned(),
+        parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: false,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Filter Size".to_owned(),
+        flags: vec!["--filter".to_owned()],
+        description: "Size of the (square) filter window, in grid cells.".to_owned(),
+        parameter_type: ParameterType::Integer,
+        default_value: Some("7".to_owned()),
+        optional: true,
+    });
+
+    if filter == SpeckleFilterType::Frost {
+        parameters.push(ToolParameter {
+            name: "Damping Factor".to_owned(),
+            flags: vec!["--damping".to_owned()],
+            description:
+                "Damping factor controlling the decay of the filter weights with distance."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+    } else {
+        parameters.push(ToolParameter {
+            name: "Number of Looks".to_owned(),
+            flags: vec!["--looks".to_owned()],
+            description: "Equivalent number of looks of the SAR image.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+    }
+
+    parameters.push(ToolParameter {
+        name: "Input values are in decibels?".to_owned(),
+        flags: vec!["--db".to_owned()],
+        description: "Flag indicating that the input backscatter values are in decibels."
+            .to_owned(),
+        parameter_type: ParameterType::Boolean,
+        default_value: Some("false".to_owned()),
+        optional: true,
+    });
+
+    parameters
+}
+
+/// Runs a speckle filter tool.
+pub fn run_speckle_filter(
+    tool_name: &str,
+    filter: SpeckleFilterType,
+    args: Vec<String>,
+    working_directory: &str,
+    verbose: bool,
+) -> Result<(), Error> {
+    let mut input_file = String::new();
+    let mut output_file = String::new();
+    let mut filter_size = 7usize;
+    let mut looks = 1f64;
+    let mut damping = 2f64;
+    let mut in_db = false;
+
+    if args.len() == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Tool run with no parameters.",
+        ));
+    }
+    for i in 0..args.len() {
+        let mut arg = args[i].replace("\"", "");
+        arg = arg.replace("\'", "");
+        let cmd = arg.split("="); // in case an equals sign was used
+        let vec = cmd.collect::<Vec<&str>>();
+        let mut keyval = false;
+        if vec.len() > 1 {
+            keyval = true;
+        }
+        let flag_val = vec[0].to_lowercase().replace("--", "-");
+        if flag_val == "-i" || flag_val == "-input" {
+            input_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-o" || flag_val == "-output" {
+            output_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-filter" {
+            filter_size = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize
+            };
+        } else if flag_val == "-looks" {
+            looks = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            };
+        } else if flag_val == "-damping" {
+            damping = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            };
+        } else if flag_val == "-db" {
+            if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                in_db = true;
+            }
+        }
+    }
+
+    if verbose {
+        println!("***************{}", "*".repeat(tool_name.len()));
+        println!("* Welcome to {} *", tool_name);
+        println!("***************{}", "*".repeat(tool_name.len()));
+    }
+
+    let sep: String = path::MAIN_SEPARATOR.to_string();
+
+    if !input_file.contains(&sep) && !input_file.contains("/") {
+        input_file = format!("{}{}", working_directory, input_file);
+    }
+    if !output_file.contains(&sep) && !output_file.contains("/") {
+        output_file = format!("{}{}", working_directory, output_file);
+    }
+
+    if looks <= 0f64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The number of looks must be greater than zero.",
+        ));
+    }
+    if damping < 0f64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The damping factor must not be negative.",
+        ));
+    }
+
+    // The filter dimensions must be odd numbers such that there is a middle pixel
+    if filter_size < 3 {
+        filter_size = 3;
+    }
+    if filter_size % 2 == 0 {
+        filter_size += 1;
+    }
+    // the refined Lee filter divides its window into 3 x 3 overlapping sub-windows of
+    // half its size, which must also have odd dimensions, e.g. 7 x 7 into 3 x 3 windows
+    let mut sub_size = 1usize;
+    if filter == SpeckleFilterType::RefinedLee {
+        sub_size = ((filter_size - 1) / 2).max(3);
+        if sub_size % 2 == 0 {
+            sub_size += 1;
+        }
+        filter_size = 2 * sub_size + 1;
+    }
+    let midpoint = (filter_size / 2) as isize;
+
+    if verbose {
+        println!("Reading data...")
+    };
+    let input = Arc::new(Raster::new(&input_file, "r")?);
+
+    let start = Instant::now();
+
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+
+    // the squared coefficient of variation of the speckle
+    let cu2 = 1f64 / looks;
+
+    let (tx, rx) = mpsc::channel();
+    let num_procs = num_cpus::get() as isize;
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let get_intensity = |row: isize, col: isize| -> Option<f64> {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    None
+                } else if in_db {
+                    Some(10f64.powf(z / 10f64))
+                } else {
+                    Some(z)
+                }
+            };
+            let mut window = vec![];
+            for dy in -midpoint..=midpoint {
+                for dx in -midpoint..=midpoint {
+                    window.push((dy, dx));
+                }
+            }
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    let z = match get_intensity(row, col) {
+                        Some(z) => z,
+                        None => continue,
+                    };
+                    let value = match filter {
+                        SpeckleFilterType::Lee => {
+                            let (mean, variance) = window_stats(
+                                window
+                                    .iter()
+                                    .filter_map(|(dy, dx)| get_intensity(row + dy, col + dx)),
+                            );
+                            lee_estimate(z, mean, variance, cu2)
+                        }
+                        SpeckleFilterType::RefinedLee => {
+                            let (ny, nx) = edge_normal(
+                                |dy: isize, dx: isize| get_intensity(row + dy, col + dx),
+                                sub_size as isize,
+                            );
+                            let (mean, variance) = window_stats(
+                                window
+                                    .iter()
+                                    .filter(|(dy, dx)| dy * ny + dx * nx >= 0)
+                                    .filter_map(|(dy, dx)| get_intensity(row + dy, col + dx)),
+                            );
+                            lee_estimate(z, mean, variance, cu2)
+                        }
+                        SpeckleFilterType::Frost => {
+                            let (mean, variance) = window_stats(
+                                window
+                                    .iter()
+                                    .filter_map(|(dy, dx)| get_intensity(row + dy, col + dx)),
+                            );
+                            let ci2 = if mean > 0f64 {
+                                variance / (mean * mean)
+                            } else {
+                                0f64
+                            };
+                            let mut sum = 0f64;
+                            let mut weights = 0f64;
+                            for (dy, dx) in &window {
+                                if let Some(zn) = get_intensity(row + dy, col + dx) {
+                                    let dist = ((dy * dy + dx * dx) as f64).sqrt();
+                                    let w = (-damping * ci2 * dist).exp();
+                                    sum += w * zn;
+                                    weights += w;
+                                }
+                            }
+                            sum / weights
+                        }
+                        SpeckleFilterType::GammaMap => {
+                            let (mean, variance) = window_stats(
+                                window
+                                    .iter()
+                                    .filter_map(|(dy, dx)| get_intensity(row + dy, col + dx)),
+                            );
+                            gamma_map_estimate(z, mean, variance, looks)
+                        }
+                    };
+                    data[col as usize] = if in_db {
+                        if value > 0f64 {
+                            10f64 * value.log10()
+                        } else {
+                            input.get_value(row, col)
+                        }
+                    } else {
+                        value
+                    };
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    let mut output = Raster::initialize_using_file(&output_file, &input);
+    output.configs.data_type = DataType::F32;
+    output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    output.configs.palette = input.configs.palette.clone();
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for row in 0..rows {
+        let data = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(data.0, data.1);
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let elapsed_time = get_formatted_elapsed_time(start);
+    output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", tool_name));
+    output.add_metadata_entry(format!("Input file: {}", input_file));
+    output.add_metadata_entry(format!("Filter size: {}", filter_size));
+    if filter == SpeckleFilterType::Frost {
+        output.add_metadata_entry(format!("Damping factor: {}", damping));
+    } else {
+        output.add_metadata_entry(format!("Number of looks: {}", looks));
+    }
+    output.add_metadata_entry(format!("Input in decibels: {}", in_db));
+    output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+    if verbose {
+        println!("Saving data...")
+    };
+    let _ = match output.write() {
+        Ok(_) => {
+            if verbose {
+                println!("Output file written")
+            }
+        }
+        Err(e) => return Err(e),
+    };
+    if verbose {
+        println!(
+            "{}",
+            &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the mean and variance of a set of values.
+fn window_stats<I: Iterator<Item = f64>>(values: I) -> (f64, f64) {
+    let mut n = 0f64;
+    let mut sum = 0f64;
+    let mut sq_sum = 0f64;
+    for z in values {
+        n += 1f64;
+        sum += z;
+        sq_sum += z * z;
+    }
+    if n == 0f64 {
+        return (0f64, 0f64);
+    }
+    let mean = sum / n;
+    (mean, (sq_sum / n - mean * mean).max(0f64))
+}
+
+/// The minimum mean square error estimate of the Lee filter, given the local mean and
+/// variance and the squared coefficient of variation of the speckle.
+fn lee_estimate(z: f64, mean: f64, variance: f64, cu2: f64) -> f64 {
+    if mean <= 0f64 || variance <= 0f64 {
+        return mean.max(0f64);
+    }
+    let var_x = (variance - mean * mean * cu2) / (1f64 + cu2);
+    let k = (var_x / variance).max(0f64).min(1f64);
+    mean + k * (z - mean)
+}
+
+/// The Gamma maximum a posteriori estimate, which assumes gamma-distributed scene
+/// reflectivity. Homogeneous areas are replaced by the local mean and strong scatterers
+/// are preserved.
+fn gamma_map_estimate(z: f64, mean: f64, variance: f64, looks: f64) -> f64 {
+    if mean <= 0f64 {
+        return z;
+    }
+    let cu2 = 1f64 / looks;
+    let cmax2 = 2f64 * cu2;
+    let ci2 = variance / (mean * mean);
+    if ci2 <= cu2 {
+        return mean;
+    }
+    if ci2 >= cmax2 {
+        return z;
+    }
+    let alpha = (1f64 + cu2) / (ci2 - cu2);
+    let b = alpha - looks - 1f64;
+    let d = mean * mean * b * b + 4f64 * alpha * looks * mean * z;
+    (b * mean + d.sqrt()) / (2f64 * alpha)
+}
+
+/// Estimates the direction of the dominant edge within the refined Lee filter's window from
+/// the means of its 3 x 3 overlapping sub-windows, and returns the normal (dy, dx) pointing
+/// towards the side of the edge that is most similar to the centre of the window.
+fn edge_normal<F: Fn(isize, isize) -> Option<f64>>(
+    get_value: F,
+    sub_size: isize,
+) -> (isize, isize) {
+    let half = sub_size / 2;
+    let stride = half + 1;
+    let sub_window_mean = |cy: isize, cx: isize| -> Option<f64> {
+        let mut n = 0f64;
+        let mut sum = 0f64;
+        for dy in -half..=half {
+            for dx in -half..=half {
+                if let Some(z) = get_value(cy + dy, cx + dx) {
+                    n += 1f64;
+                    sum += z;
+                }
+            }
+        }
+        if n > 0f64 {
+            Some(sum / n)
+        } else {
+            None
+        }
+    };
+    // the centre sub-window always contains the centre cell; sub-windows lying entirely
+    // outside of the data take its mean
+    let centre = sub_window_mean(0, 0).unwrap_or(0f64);
+    let mut m = [[centre; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            let (cy, cx) = ((i as isize - 1) * stride, (j as isize - 1) * stride);
+            m[i][j] = sub_window_mean(cy, cx).unwrap_or(centre);
+        }
+    }
+    // the gradients across vertical, horizontal, and the two diagonal edges, with the
+    // sub-window means on either side of each edge and the corresponding normals
+    let candidates = [
+        (
+            (m[0][2] + m[1][2] + m[2][2] - m[0][0] - m[1][0] - m[2][0]).abs(),
+            (m[1][2], (0, 1)),
+            (m[1][0], (0, -1)),
+        ),
+        (
+            (m[2][0] + m[2][1] + m[2][2] - m[0][0] - m[0][1] - m[0][2]).abs(),
+            (m[2][1], (1, 0)),
+            (m[0][1], (-1, 0)),
+        ),
+        (
+            (m[1][2] + m[2][1] + m[2][2] - m[0][0] - m[0][1] - m[1][0]).abs(),
+            (m[2][2], (1, 1)),
+            (m[0][0], (-1, -1)),
+        ),
+        (
+            (m[0][1] + m[0][2] + m[1][2] - m[1][0] - m[2][0] - m[2][1]).abs(),
+            (m[0][2], (-1, 1)),
+            (m[2][0], (1, -1)),
+        ),
+    ];
+    let mut best = 0;
+    for k in 1..candidates.len() {
+        if candidates[k].0 > candidates[best].0 {
+            best = k;
+        }
+    }
+    let (_, (m1, n1), (m2, n2)) = candidates[best];
+    if (m1 - centre).abs() <= (m2 - centre).abs() {
+        n1
+    } else {
+        n2
+    }
+}
@@ -0,0 +1,140 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::spectral_indices::{calculate_index, index_tool_parameters};
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool calculates a spectral index from the bands of multispectral image data. The index (`--index`)
+/// is chosen from a registry of common indices, which currently includes:
+///
+/// | Index | Name | Bands | Formula |
+/// |-------|------|-------|---------|
+/// | ndvi | Normalized Difference Vegetation Index | nir, red | (NIR - Red) / (NIR + Red) |
+/// | evi | Enhanced Vegetation Index | nir, red, blue | 2.5(NIR - Red) / (NIR + 6Red - 7.5Blue + 1) |
+/// | savi | Soil Adjusted Vegetation Index | nir, red | 1.5(NIR - Red) / (NIR + Red + 0.5) |
+/// | ndwi | Normalized Difference Water Index | green, nir | (Green - NIR) / (Green + NIR) |
+/// | mndwi | Modified Normalized Difference Water Index | green, swir1 | (Green - SWIR1) / (Green + SWIR1) |
+/// | ndmi | Normalized Difference Moisture Index | nir, swir1 | (NIR - SWIR1) / (NIR + SWIR1) |
+/// | ndsi | Normalized Difference Snow Index | green, swir1 | (Green - SWIR1) / (Green + SWIR1) |
+/// | nbr | Normalized Burn Ratio | nir, swir2 | (NIR - SWIR2) / (NIR + SWIR2) |
+/// | nbr2 | Normalized Burn Ratio 2 | swir1, swir2 | (SWIR1 - SWIR2) / (SWIR1 + SWIR2) |
+///
+/// The image bands used by the index are specified with the band mapping parameter (`--bands`), a
+/// semicolon-separated list of band=source pairs, where each source is either a single-band raster file,
+/// e.g. `--bands="nir=B08.tif;red=B04.tif"`, or, when a multi-band image is specified (`--input`), the
+/// (one-based) number of a band within that image, e.g. `--bands="nir=5;red=4"` for a Landsat 8 OLI stack.
+///
+/// The input image may also be a Landsat Collection 2 scene or a Sentinel-2 SAFE product, named by its
+/// directory or metadata file, in which case the band mapping may be omitted; the bands required by the index
+/// are identified by their roles in the scene's metadata, and the scene's values are already reflectance.
+///
+/// Indices that are not simple normalized differences, such as the EVI and SAVI, assume that the image
+/// values are surface reflectance in the range 0-1. Images that store scaled reflectance, such as Sentinel-2
+/// Level-2A and Landsat Collection 2 surface reflectance products, can be converted during the calculation
+/// using the `--scale` and `--offset` parameters (e.g. `--scale=0.0001` for Sentinel-2 and `--scale=0.0000275
+/// --offset=-0.2` for Landsat Collection 2). Grid cells that are NoData in any of the bands, or for which
+/// the index is undefined (e.g. division by zero), are assigned NoData in the output.
+///
+/// The most commonly used indices are also available as separate tools, e.g. `NormalizedBurnRatio`, which
+/// differ from this tool only in not requiring the `--index` parameter.
+///
+/// # See Also
+/// `NormalizedDifferenceIndex`, `EnhancedVegetationIndex`, `SoilAdjustedVegetationIndex`,
+/// `NormalizedDifferenceWaterIndex`, `ModifiedNormalizedDifferenceWaterIndex`, `NormalizedDifferenceSnowIndex`,
+/// `NormalizedBurnRatio`
+pub struct SpectralIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SpectralIndex {
+    pub fn new() -> SpectralIndex {
+        // public constructor
+        let name = "SpectralIndex".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates a spectral index, chosen from a registry of common indices, from multispectral image bands.".to_string();
+
+        let parameters = index_tool_parameters(None);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=landsat8.tif --index=mndwi --bands=\"green=3;swir1=6\" -o=mndwi.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SpectralIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SpectralIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        calculate_index(
+            &self.get_tool_name(),
+            None,
+            args,
+            working_directory,
+            verbose,
+        )
+    }
+}
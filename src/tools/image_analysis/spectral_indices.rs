@@ -0,0 +1,464 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: The spectral index tools (e.g. SpectralIndex, NormalizedBurnRatio) are thin wrappers
+around the registry in this module. Each index is described by an entry in SPECTRAL_INDICES,
+giving the names of the bands it requires and a formula over the band values, in that order.
+Adding an index is therefore a matter of adding an entry to the registry, after which it is
+available through the SpectralIndex tool's --index parameter.
+*/
+
+use crate::raster::satellite_scene::find_band_by_role;
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// A spectral index, described by the bands it requires and a formula over the
+/// (reflectance) values of those bands, in the order that they are listed.
+pub struct SpectralIndexDef {
+    pub name: &'static str,
+    pub long_name: &'static str,
+    pub bands: &'static [&'static str],
+    pub formula: fn(&[f64]) -> f64,
+}
+
+/// The registry of spectral indices.
+pub static SPECTRAL_INDICES: &[SpectralIndexDef] = &[
+    SpectralIndexDef {
+        name: "ndvi",
+        long_name: "Normalized Difference Vegetation Index",
+        bands: &["nir", "red"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "evi",
+        long_name: "Enhanced Vegetation Index",
+        bands: &["nir", "red", "blue"],
+        formula: |b| 2.5 * (b[0] - b[1]) / (b[0] + 6.0 * b[1] - 7.5 * b[2] + 1.0),
+    },
+    SpectralIndexDef {
+        name: "savi",
+        long_name: "Soil Adjusted Vegetation Index",
+        bands: &["nir", "red"],
+        formula: |b| 1.5 * (b[0] - b[1]) / (b[0] + b[1] + 0.5),
+    },
+    SpectralIndexDef {
+        name: "ndwi",
+        long_name: "Normalized Difference Water Index",
+        bands: &["green", "nir"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "mndwi",
+        long_name: "Modified Normalized Difference Water Index",
+        bands: &["green", "swir1"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "ndmi",
+        long_name: "Normalized Difference Moisture Index",
+        bands: &["nir", "swir1"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "ndsi",
+        long_name: "Normalized Difference Snow Index",
+        bands: &["green", "swir1"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "nbr",
+        long_name: "Normalized Burn Ratio",
+        bands: &["nir", "swir2"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+    SpectralIndexDef {
+        name: "nbr2",
+        long_name: "Normalized Burn Ratio 2",
+        bands: &["swir1", "swir2"],
+        formula: |b| (b[0] - b[1]) / (b[0] + b[1]),
+    },
+];
+
+/// Returns the registry entry for an index name (case insensitive).
+pub fn find_index(name: &str) -> Option<&'static SpectralIndexDef> {
+    let name = name.trim().to_lowercase();
+    SPECTRAL_INDICES.iter().find(|ind| ind.name == name)
+}
+
+/// Returns the parameters shared by the spectral index tools. The `--index` parameter
+/// is included for the generic tool only, i.e. when `index` is `None`.
+pub fn index_tool_parameters(index: Option<&SpectralIndexDef>) -> Vec<ToolParameter> {
+    let mut parameters = vec![];
+    parameters.push(ToolParameter {
+        name: "Input Multi-band Image (optional)".to_owned(),
+        flags: vec!["-i".to_owned(), "--input".to_owned()],
+        description:
+            "Optional input multi-band image, to which band numbers in the band mapping refer."
+                .to_owned(),
+        parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: true,
+    });
+
+    if index.is_none() {
+        parameters.push(ToolParameter {
+            name: "Spectral Index".to_owned(),
+            flags: vec!["--index".to_owned()],
+            description: "Name of the spectral index to calculate.".to_owned(),
+            parameter_type: ParameterType::OptionList(
+                SPECTRAL_INDICES
+                    .iter()
+                    .map(|ind| ind.name.to_owned())
+                    .collect(),
+            ),
+            default_value: Some("ndvi".to_owned()),
+            optional: false,
+        });
+    }
+
+    let required = match index {
+        Some(ind) => ind.bands.join(", "),
+        None => "as required by the index".to_owned(),
+    };
+    parameters.push(ToolParameter {
+        name: "Band Mapping".to_owned(),
+        flags: vec!["--bands".to_owned()],
+        description: format!(
+            "Semicolon-separated list of band=file (or band=number, for a multi-band input) pairs for the bands {}; optional for satellite scenes.",
+            required
+        ),
+        parameter_type: ParameterType::String,
+        default_value: None,
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Output File".to_owned(),
+        flags: vec!["-o".to_owned(), "--output".to_owned()],
+        description: "Output raster file.".to_owned(),
+        parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+        default_value: None,
+        optional: false,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Reflectance Scale Factor".to_owned(),
+        flags: vec!["--scale".to_owned()],
+        description: "Factor converting the image values into reflectance (e.g. 0.0001)."
+            .to_owned(),
+        parameter_type: ParameterType::Float,
+        default_value: Some("1.0".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Reflectance Offset".to_owned(),
+        flags: vec!["--offset".to_owned()],
+        description: "Offset added to the scaled image values to give reflectance.".to_owned(),
+        parameter_type: ParameterType::Float,
+        default_value: Some("0.0".to_owned()),
+        optional: true,
+    });
+
+    parameters
+}
+
+/// Runs a spectral index tool. The index is either given (for the prebuilt index tools)
+/// or read from the `--index` argument (for the generic SpectralIndex tool).
+pub fn calculate_index(
+    tool_name: &str,
+    index: Option<&SpectralIndexDef>,
+    args: Vec<String>,
+    working_directory: &str,
+    verbose: bool,
+) -> Result<(), Error> {
+    let mut input_file = String::new();
+    let mut index_name = String::new();
+    let mut band_mapping = String::new();
+    let mut output_file = String::new();
+    let mut scale = 1f64;
+    let mut offset = 0f64;
+
+    if args.len() == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Tool run with no parameters.",
+        ));
+    }
+    for i in 0..args.len() {
+        let mut arg = args[i].replace("\"", "");
+        arg = arg.replace("\'", "");
+        let cmd = arg.split("="); // in case an equals sign was used
+        let vec = cmd.collect::<Vec<&str>>();
+        let mut keyval = false;
+        if vec.len() > 1 {
+            keyval = true;
+        }
+        let flag_val = vec[0].to_lowercase().replace("--", "-");
+        if flag_val == "-i" || flag_val == "-input" {
+            input_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-index" {
+            index_name = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-bands" {
+            // the band mapping itself contains equals signs
+            band_mapping = if keyval {
+                arg.splitn(2, '=').nth(1).unwrap_or("").to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-o" || flag_val == "-output" {
+            output_file = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            };
+        } else if flag_val == "-scale" {
+            scale = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            };
+        } else if flag_val == "-offset" {
+            offset = if keyval {
+                vec[1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            } else {
+                args[i + 1]
+                    .to_string()
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            };
+        }
+    }
+
+    if verbose {
+        println!("***************{}", "*".repeat(tool_name.len()));
+        println!("* Welcome to {} *", tool_name);
+        println!("***************{}", "*".repeat(tool_name.len()));
+    }
+
+    let index = match index {
+        Some(ind) => ind,
+        None => find_index(&index_name).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized spectral index '{}'. Available indices are: {}.",
+                    index_name,
+                    SPECTRAL_INDICES
+                        .iter()
+                        .map(|ind| ind.name)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ),
+            )
+        })?,
+    };
+
+    let sep: String = path::MAIN_SEPARATOR.to_string();
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    if !input_file.is_empty() && !input_file.contains(&sep) && !input_file.contains("/") {
+        input_file = format!("{}{}", working_directory, input_file);
+    }
+    if !output_file.contains(&sep) && !output_file.contains("/") {
+        output_file = format!("{}{}", working_directory, output_file);
+    }
+
+    // parse the band mapping
+    let mut sources = vec![String::new(); index.bands.len()];
+    for entry in band_mapping.split(|c| c == ';' || c == ',') {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let mut parts = entry.splitn(2, '=');
+        let band = parts.next().unwrap_or("").trim().to_lowercase();
+        let source = parts.next().unwrap_or("").trim().to_string();
+        if let Some(j) = index.bands.iter().position(|b| *b == band) {
+            sources[j] = source;
+        }
+    }
+    if verbose {
+        println!("Reading data...")
+    };
+
+    let multiband = if !input_file.is_empty() {
+        Some(Raster::new(&input_file, "r")?)
+    } else {
+        None
+    };
+    for j in 0..index.bands.len() {
+        if sources[j].is_empty() {
+            // satellite scenes record the role of each of their bands
+            if let Some(b) = multiband
+                .as_ref()
+                .and_then(|image| find_band_by_role(image, index.bands[j]))
+            {
+                sources[j] = format!("{}", b + 1);
+                continue;
+            }
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The band mapping (--bands) must specify the {} band(s) for the {} ({}).",
+                    index.bands.join(", "),
+                    index.long_name,
+                    index.name.to_uppercase()
+                ),
+            ));
+        }
+    }
+
+    let mut layers: Vec<Raster> = Vec::with_capacity(sources.len());
+    let mut source_names: Vec<String> = Vec::with_capacity(sources.len());
+    for j in 0..sources.len() {
+        let layer = match (&multiband, sources[j].parse::<usize>()) {
+            (Some(image), Ok(band_num)) => {
+                if band_num < 1 || band_num > image.num_bands() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Band {} of the input image does not exist (the image has {} bands).",
+                            band_num,
+                            image.num_bands()
+                        ),
+                    ));
+                }
+                source_names.push(format!("{} band {}", input_file, band_num));
+                image.get_band(band_num - 1)
+            }
+            _ => {
+                let mut file = sources[j].clone();
+                if !file.contains(&sep) && !file.contains("/") {
+                    file = format!("{}{}", working_directory, file);
+                }
+                source_names.push(file.clone());
+                Raster::new(&file, "r")?
+            }
+        };
+        let layer = if j > 0 {
+            align_raster_input(&layers[0], layer)?
+        } else {
+            layer
+        };
+        layers.push(layer);
+    }
+    drop(multiband);
+
+    let start = Instant::now();
+
+    let rows = layers[0].configs.rows as isize;
+    let columns = layers[0].configs.columns as isize;
+    let nodata = -32768f64;
+    let formula = index.formula;
+
+    let mut output = Raster::initialize_using_file(&output_file, &layers[0]);
+    output.configs.nodata = nodata;
+    output.configs.data_type = DataType::F32;
+    output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    output.configs.palette = "grey.plt".to_string();
+
+    let layers = Arc::new(layers);
+    let num_procs = num_cpus::get() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let layers = layers.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let mut values = vec![0f64; layers.len()];
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                'col: for col in 0..columns {
+                    for j in 0..layers.len() {
+                        let z = layers[j].get_value(row, col);
+                        if z == layers[j].configs.nodata {
+                            continue 'col;
+                        }
+                        values[j] = z * scale + offset;
+                    }
+                    let z = formula(&values);
+                    if z.is_finite() {
+                        data[col as usize] = z;
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    for r in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(row, data);
+
+        if verbose {
+            progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let elapsed_time = get_formatted_elapsed_time(start);
+    output.add_metadata_entry(format!("Created by whitebox_tools\' {} tool", tool_name));
+    output.add_metadata_entry(format!(
+        "Index: {} ({})",
+        index.long_name,
+        index.name.to_uppercase()
+    ));
+    for j in 0..index.bands.len() {
+        output.add_metadata_entry(format!("{} band: {}", index.bands[j], source_names[j]));
+    }
+    output.add_metadata_entry(format!("Reflectance scale: {}", scale));
+    output.add_metadata_entry(format!("Reflectance offset: {}", offset));
+    output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+    if verbose {
+        println!("Saving data...")
+    };
+    let _ = match output.write() {
+        Ok(_) => {
+            if verbose {
+                println!("Output file written")
+            }
+        }
+        Err(e) => return Err(e),
+    };
+    if verbose {
+        println!(
+            "{}",
+            &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+        );
+    }
+
+    Ok(())
+}
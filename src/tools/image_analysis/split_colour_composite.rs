@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 15/07/2017
-Last Modified: 12/04/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -20,6 +20,11 @@ use std::thread;
 /// multi-spectral imagery. The user must specify the input image (`--input`) and output red, green, blue images
 /// (`--red`, `--green`, `--blue`).
 ///
+/// The tool can also be used to split a multi-band image, e.g. a multi-band GeoTIFF, into its individual
+/// bands. In this case, the first three bands are written to the red, green, and blue output files respectively,
+/// if specified, and every other band is written to a file named after the input image with a `_bandN` suffix,
+/// where N is the one-based band number.
+///
 /// # See Also
 /// `CreateColourComposite`
 pub struct SplitColourComposite {
@@ -205,6 +210,11 @@ impl WhiteboxTool for SplitColourComposite {
         if !input_file.contains(&sep) && !input_file.contains("/") {
             input_file = format!("{}{}", working_directory, input_file);
         }
+        let specified_files = vec![
+            !red_file.trim().is_empty(),
+            !green_file.trim().is_empty(),
+            !blue_file.trim().is_empty(),
+        ];
         if !red_file.contains(&sep) && !red_file.contains("/") {
             red_file = format!("{}{}", working_directory, red_file);
         }
@@ -223,6 +233,63 @@ impl WhiteboxTool for SplitColourComposite {
 
         let start = Instant::now();
 
+        if input.configs.bands > 1 {
+            let num_bands = input.configs.bands;
+            let input_path = path::Path::new(&input_file);
+            let stem = input_path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let extension = input_path
+                .extension()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            for band in 0..num_bands {
+                let band_file = if band < 3 && specified_files[band] {
+                    vec![&red_file, &green_file, &blue_file][band].clone()
+                } else {
+                    input_path
+                        .with_file_name(format!("{}_band{}.{}", stem, band + 1, extension))
+                        .to_string_lossy()
+                        .to_string()
+                };
+                let band_raster = input.get_band(band);
+                let mut output = Raster::initialize_using_file(&band_file, &band_raster);
+                for row in 0..band_raster.configs.rows as isize {
+                    output.set_row_data(row, band_raster.get_row_data(row));
+                }
+                output.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                output.add_metadata_entry(format!("Input file: {}", input_file));
+                output.add_metadata_entry(format!("Band: {}", band + 1));
+                if verbose {
+                    println!("Saving band {} of {}...", band + 1, num_bands)
+                };
+                let _ = match output.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Output file written: {}", band_file)
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+            if verbose {
+                println!(
+                    "{}",
+                    &format!(
+                        "Elapsed Time (excluding I/O): {}",
+                        get_formatted_elapsed_time(start)
+                    )
+                );
+            }
+            return Ok(());
+        }
+
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
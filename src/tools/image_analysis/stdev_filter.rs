@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -23,7 +23,9 @@ use std::thread;
 /// (`--output`) the [standard deviation](https://en.wikipedia.org/wiki/Standard_deviation), a measure of dispersion, of the values contained within a moving window centred on each grid cell.
 ///
 /// Neighbourhood size, or filter size, is specified in the x and y dimensions using the `--filterx` and `--filtery`
-/// flags. These dimensions should be odd, positive integer values (e.g. 3, 5, 7, 9, etc.).
+/// flags. These dimensions should be odd, positive integer values (e.g. 3, 5, 7, 9, etc.). When the input raster has a
+/// hexagonal grid, the filter is instead applied to the cells within a radius of `--filterx` / 2 cells (or `--filtery` / 2,
+/// if larger) of each cell, i.e. to a hexagonal neighbourhood.
 ///
 /// # See Also
 /// `RangeFilter`, `TotalFilter`
@@ -272,136 +274,142 @@ impl WhiteboxTool for StandardDeviationFilter {
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
 
-        // create the integral images
-        let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
-        let mut integral2: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
-        let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
-
-        let mut val: f64;
-        let mut sum: f64;
-        let mut sum_sqr: f64;
-        let mut sum_n: i32;
-        let (mut i_prev, mut i2_prev): (f64, f64);
-        let mut n_prev: i32;
-        for row in 0..rows {
-            sum = 0f64;
-            sum_sqr = 0f64;
-            sum_n = 0;
-            for col in 0..columns {
-                val = input[(row, col)];
-                if val == nodata {
-                    val = 0f64;
-                } else {
-                    sum_n += 1;
-                }
-                sum += val;
-                sum_sqr += val * val;
-                if row > 0 {
-                    i_prev = integral[(row - 1, col)];
-                    i2_prev = integral2[(row - 1, col)];
-                    n_prev = integral_n[(row - 1, col)];
-                    integral[(row, col)] = sum + i_prev;
-                    integral2[(row, col)] = sum_sqr + i2_prev;
-                    integral_n[(row, col)] = sum_n + n_prev;
-                } else {
-                    integral[(row, col)] = sum;
-                    integral2[(row, col)] = sum_sqr;
-                    integral_n[(row, col)] = sum_n;
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if input.configs.grid_type == GridType::Hexagonal {
+            // the neighbourhood of a hexagonal grid cell is the cells within a radius of it
+            hexagonal_stdev_filter(&input, &mut output, midpoint_x.max(midpoint_y), verbose);
+        } else {
+            // create the integral images
+            let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+            let mut integral2: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+            let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
+
+            let mut val: f64;
+            let mut sum: f64;
+            let mut sum_sqr: f64;
+            let mut sum_n: i32;
+            let (mut i_prev, mut i2_prev): (f64, f64);
+            let mut n_prev: i32;
+            for row in 0..rows {
+                sum = 0f64;
+                sum_sqr = 0f64;
+                sum_n = 0;
+                for col in 0..columns {
+                    val = input[(row, col)];
+                    if val == nodata {
+                        val = 0f64;
+                    } else {
+                        sum_n += 1;
+                    }
+                    sum += val;
+                    sum_sqr += val * val;
+                    if row > 0 {
+                        i_prev = integral[(row - 1, col)];
+                        i2_prev = integral2[(row - 1, col)];
+                        n_prev = integral_n[(row - 1, col)];
+                        integral[(row, col)] = sum + i_prev;
+                        integral2[(row, col)] = sum_sqr + i2_prev;
+                        integral_n[(row, col)] = sum_n + n_prev;
+                    } else {
+                        integral[(row, col)] = sum;
+                        integral2[(row, col)] = sum_sqr;
+                        integral_n[(row, col)] = sum_n;
+                    }
                 }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
-                    old_progress = progress;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Creating integral images: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
-        }
 
-        let i = Arc::new(integral); // wrap integral in an Arc
-        let i2 = Arc::new(integral2); // wrap integral2 in an Arc
-        let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
-
-        let num_procs = num_cpus::get() as isize;
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input_data = input.clone();
-            let i = i.clone();
-            let i2 = i2.clone();
-            let i_n = i_n.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                let mut n: i32;
-                let (mut sum, mut sum_sqr): (f64, f64);
-                let (mut v, mut s): (f64, f64);
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    y1 = row - midpoint_y - 1;
-                    if y1 < 0 {
-                        y1 = 0;
-                    }
-                    if y1 >= rows {
-                        y1 = rows - 1;
-                    }
+            let i = Arc::new(integral); // wrap integral in an Arc
+            let i2 = Arc::new(integral2); // wrap integral2 in an Arc
+            let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
+
+            let num_procs = num_cpus::get() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input_data = input.clone();
+                let i = i.clone();
+                let i2 = i2.clone();
+                let i_n = i_n.clone();
+                let tx1 = tx.clone();
+                thread::spawn(move || {
+                    let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+                    let mut n: i32;
+                    let (mut sum, mut sum_sqr): (f64, f64);
+                    let (mut v, mut s): (f64, f64);
+                    let mut z: f64;
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        y1 = row - midpoint_y - 1;
+                        if y1 < 0 {
+                            y1 = 0;
+                        }
+                        if y1 >= rows {
+                            y1 = rows - 1;
+                        }
 
-                    y2 = row + midpoint_y;
-                    if y2 < 0 {
-                        y2 = 0;
-                    }
-                    if y2 >= rows {
-                        y2 = rows - 1;
-                    }
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_data[(row, col)];
-                        if z != nodata {
-                            x1 = col - midpoint_x - 1;
-                            if x1 < 0 {
-                                x1 = 0;
-                            }
-                            if x1 >= columns {
-                                x1 = columns - 1;
-                            }
+                        y2 = row + midpoint_y;
+                        if y2 < 0 {
+                            y2 = 0;
+                        }
+                        if y2 >= rows {
+                            y2 = rows - 1;
+                        }
+                        let mut data = vec![nodata; columns as usize];
+                        for col in 0..columns {
+                            z = input_data[(row, col)];
+                            if z != nodata {
+                                x1 = col - midpoint_x - 1;
+                                if x1 < 0 {
+                                    x1 = 0;
+                                }
+                                if x1 >= columns {
+                                    x1 = columns - 1;
+                                }
 
-                            x2 = col + midpoint_x;
-                            if x2 < 0 {
-                                x2 = 0;
-                            }
-                            if x2 >= columns {
-                                x2 = columns - 1;
-                            }
-                            n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
-                            if n > 0 {
-                                sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
-                                sum_sqr = i2[(y2, x2)] + i2[(y1, x1)] - i2[(y1, x2)] - i2[(y2, x1)];
-                                v = (sum_sqr - (sum * sum) / n as f64) / n as f64;
-                                if v > 0f64 {
-                                    s = v.sqrt();
-                                    data[col as usize] = s;
+                                x2 = col + midpoint_x;
+                                if x2 < 0 {
+                                    x2 = 0;
+                                }
+                                if x2 >= columns {
+                                    x2 = columns - 1;
+                                }
+                                n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
+                                if n > 0 {
+                                    sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
+                                    sum_sqr =
+                                        i2[(y2, x2)] + i2[(y1, x1)] - i2[(y1, x2)] - i2[(y2, x1)];
+                                    v = (sum_sqr - (sum * sum) / n as f64) / n as f64;
+                                    if v > 0f64 {
+                                        s = v.sqrt();
+                                        data[col as usize] = s;
+                                    } else {
+                                        data[col as usize] = 0f64;
+                                    }
                                 } else {
                                     data[col as usize] = 0f64;
                                 }
-                            } else {
-                                data[col as usize] = 0f64;
                             }
                         }
-                    }
 
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+                        tx1.send((row, data)).unwrap();
+                    }
+                });
+            }
 
-        let mut output = Raster::initialize_using_file(&output_file, &input);
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+            for row in 0..rows {
+                let data = rx.recv().expect("Error receiving data from thread.");
+                output.set_row_data(data.0, data.1);
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
         }
@@ -439,3 +447,62 @@ impl WhiteboxTool for StandardDeviationFilter {
         Ok(())
     }
 }
+
+/// Assigns each cell of a hexagonal grid the standard deviation of the valid cells
+/// within `radius` cells of it.
+fn hexagonal_stdev_filter(input: &Arc<Raster>, output: &mut Raster, radius: isize, verbose: bool) {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    // the neighbourhoods of cells in even and odd rows differ
+    let offsets = Arc::new([
+        grid::hex_neighbourhood(0, radius),
+        grid::hex_neighbourhood(1, radius),
+    ]);
+    let (tx, rx) = mpsc::channel();
+    let num_procs = num_cpus::get() as isize;
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let offsets = offsets.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let (mut sum, mut sum_sqr, mut n): (f64, f64, f64);
+            let (mut v, mut z): (f64, f64);
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    if input.get_value(row, col) != nodata {
+                        sum = 0f64;
+                        sum_sqr = 0f64;
+                        n = 0f64;
+                        for (dr, dc) in &offsets[(row & 1) as usize] {
+                            z = input.get_value(row + dr, col + dc);
+                            if z != nodata {
+                                sum += z;
+                                sum_sqr += z * z;
+                                n += 1f64;
+                            }
+                        }
+                        v = (sum_sqr - (sum * sum) / n) / n;
+                        data[col as usize] = if v > 0f64 { v.sqrt() } else { 0f64 };
+                    }
+                }
+                tx.send((row, data)).unwrap();
+            }
+        });
+    }
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for row in 0..rows {
+        let data = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(data.0, data.1);
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                println!("Progress: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+}
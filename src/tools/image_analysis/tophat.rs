@@ -33,7 +33,7 @@ use std::thread;
 /// flavours of top-hat transform the tool should perform by specifying either 'white' or 'black' with the `--variant` flag.
 ///
 /// # See Also:
-/// `Closing`, `Opening`, `MaximumFilter`, `MinimumFilter`
+/// `Closing`, `Opening`, `MaximumFilter`, `MinimumFilter`, `MorphologicalFilter`
 pub struct TophatTransform {
     name: String,
     description: String,
@@ -0,0 +1,574 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::point_in_poly;
+use crate::raster::*;
+use crate::structures::{Classifier, ClassifierModel, KnnClassifier, Point2D, RandomForest};
+use crate::tools::*;
+use crate::utils::random;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool trains a supervised classification model using a stack of co-registered input rasters
+/// (`--inputs`), typically the bands of a multi-spectral image, and a set of labelled training sites
+/// (`--training`). The training sites may be either points or polygons, with the class of each site
+/// specified by a numeric attribute field (`--field`). For point training data, the grid cell containing
+/// each point is sampled, while for polygon training data, all grid cells with centres that fall within
+/// the polygon (excluding holes) are sampled. Grid cells containing NoData in any of the input rasters are
+/// excluded from the training data.
+///
+/// Two classification methods are available (`--method`). The *random_forest* method fits an ensemble of
+/// decision trees (`--num_trees`), each grown on a bootstrap sample of the training data using the Gini
+/// impurity splitting criterion and a random subset of the input bands at each split (Breiman, 2001). The
+/// maximum tree depth (`--max_depth`) and the minimum number of samples required to split a node
+/// (`--min_samples_split`) can be used to limit the growth of the trees. The out-of-bag estimate of the
+/// classification accuracy is reported after training. The *knn* method is a k-nearest neighbour classifier
+/// (`-k`) applied to the z-score standardized band values.
+///
+/// The trained model is saved to a JSON file (`--output`), which can then be applied to the same, or a
+/// different but equivalent, set of input rasters using the `ApplyClassifier` tool. The input rasters must be
+/// specified in the same order in both tools.
+///
+/// # Reference
+/// Breiman, L. (2001). Random forests. *Machine Learning*, 45(1), 5-32.
+///
+/// # See Also
+/// `ApplyClassifier`, `KMeansClustering`, `SlicSegmentation`
+pub struct TrainClassifier {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TrainClassifier {
+    pub fn new() -> TrainClassifier {
+        // public constructor
+        let name = "TrainClassifier".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description = "Trains a random forest or k-nearest neighbour supervised classification model on a raster stack.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Training Sites File".to_owned(),
+            flags: vec!["--training".to_owned()],
+            description: "Input training sites points or polygons vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Class Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Numeric field in the training sites attribute table containing the class values.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--training".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Model File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output classification model (JSON) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Classification Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Classification method; options include 'random_forest' and 'knn'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "random_forest".to_owned(),
+                "knn".to_owned(),
+            ]),
+            default_value: Some("random_forest".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Trees".to_owned(),
+            flags: vec!["--num_trees".to_owned()],
+            description: "Number of trees in the random forest.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("100".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Tree Depth (optional)".to_owned(),
+            flags: vec!["--max_depth".to_owned()],
+            description: "Optional maximum depth of the random forest trees.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Samples to Split".to_owned(),
+            flags: vec!["--min_samples_split".to_owned()],
+            description: "Minimum number of samples required to split a random forest tree node."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours (k)".to_owned(),
+            flags: vec!["-k".to_owned()],
+            description: "Number of neighbours used by the k-nearest neighbour classifier."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("5".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd='*path*to*data*' -i='band1.tif;band2.tif;band3.tif' --training=sites.shp --field=CLASS -o=model.json --method=random_forest --num_trees=200
+>>.*{0} -r={1} -v --wd='*path*to*data*' -i='band1.tif;band2.tif;band3.tif' --training=sites.shp --field=CLASS -o=model.json --method=knn -k=7", short_exe, name).replace("*", &sep);
+
+        TrainClassifier {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TrainClassifier {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files_str = String::new();
+        let mut training_file = String::new();
+        let mut field_name = String::new();
+        let mut output_file = String::new();
+        let mut use_knn = false;
+        let mut num_trees = 100usize;
+        let mut max_depth = 0usize;
+        let mut min_samples_split = 2usize;
+        let mut k = 5usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                input_files_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-training" {
+                training_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-field" {
+                field_name = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                let method = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                use_knn = method.to_lowercase().contains("knn")
+                    || method.to_lowercase().contains("neighbour")
+                    || method.to_lowercase().contains("neighbor");
+            } else if flag_val == "-num_trees" {
+                num_trees = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-max_depth" {
+                max_depth = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-min_samples_split" {
+                min_samples_split = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-k" {
+                k = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !training_file.contains(&sep) && !training_file.contains("/") {
+            training_file = format!("{}{}", working_directory, training_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if num_trees < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of trees must be at least one.",
+            ));
+        }
+        if k < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of neighbours (k) must be at least one.",
+            ));
+        }
+
+        let mut cmd = input_files_str.split(";");
+        let mut input_files = cmd.collect::<Vec<&str>>();
+        if input_files.len() == 1 {
+            cmd = input_files_str.split(",");
+            input_files = cmd.collect::<Vec<&str>>();
+        }
+        let input_files: Vec<&str> = input_files
+            .into_iter()
+            .filter(|f| !f.trim().is_empty())
+            .collect();
+        let num_files = input_files.len();
+        if num_files < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There is something incorrect about the input files. At least one input is required to operate this tool.",
+            ));
+        }
+
+        let mut input_raster: Vec<Raster> = Vec::with_capacity(num_files);
+        for i in 0..num_files {
+            if verbose {
+                println!("Reading file {} of {}", i + 1, num_files);
+            }
+            let mut input_file = input_files[i].trim().to_owned();
+            if !input_file.contains(&sep) && !input_file.contains("/") {
+                input_file = format!("{}{}", working_directory, input_file);
+            }
+            let input = Raster::new(&input_file, "r")?;
+            if i == 0 {
+                input_raster.push(input);
+            } else {
+                // make sure the inputs share the same grid and CRS
+                let input = align_raster_input(&input_raster[0], input)?;
+                input_raster.push(input);
+            }
+        }
+        let rows = input_raster[0].configs.rows as isize;
+        let columns = input_raster[0].configs.columns as isize;
+
+        let training = Shapefile::read(&training_file)?;
+        let base_shape_type = training.header.shape_type.base_shape_type();
+        if base_shape_type != ShapeType::Point
+            && base_shape_type != ShapeType::MultiPoint
+            && base_shape_type != ShapeType::Polygon
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The training sites vector data must be of a point or polygon base shape type.",
+            ));
+        }
+        let field_index = match training.attributes.get_field_num(&field_name) {
+            Some(i) => i,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The class field was not found in the training sites attribute table.",
+                ));
+            }
+        };
+        if !training.attributes.is_field_numeric(field_index) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The class field must be numeric.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // sample the input rasters at the training sites
+        let mut features: Vec<Vec<f64>> = vec![];
+        let mut sample_classes: Vec<f64> = vec![];
+        let sample_cell = |row: isize, col: isize| -> Option<Vec<f64>> {
+            if row < 0 || row >= rows || col < 0 || col >= columns {
+                return None;
+            }
+            let mut x = Vec::with_capacity(num_files);
+            for i in 0..num_files {
+                let z = input_raster[i].get_value(row, col);
+                if z == input_raster[i].configs.nodata {
+                    return None;
+                }
+                x.push(z);
+            }
+            Some(x)
+        };
+        for record_num in 0..training.num_records {
+            let class_value = match training.attributes.get_value(record_num, &field_name) {
+                FieldData::Int(val) => val as f64,
+                FieldData::Real(val) => val,
+                _ => continue, // likely a null field
+            };
+            let record = training.get_record(record_num);
+            if base_shape_type == ShapeType::Polygon {
+                let row_start = input_raster[0].get_row_from_y(record.y_max).max(0);
+                let row_end = input_raster[0].get_row_from_y(record.y_min).min(rows - 1);
+                let col_start = input_raster[0].get_column_from_x(record.x_min).max(0);
+                let col_end = input_raster[0].get_column_from_x(record.x_max).min(columns - 1);
+                for row in row_start..=row_end {
+                    let y = input_raster[0].get_y_from_row(row);
+                    for col in col_start..=col_end {
+                        let p = Point2D::new(input_raster[0].get_x_from_column(col), y);
+                        let mut inside = false;
+                        for part in 0..record.num_parts as usize {
+                            let start_point = record.parts[part] as usize;
+                            let end_point = if part < record.num_parts as usize - 1 {
+                                record.parts[part + 1] as usize
+                            } else {
+                                record.num_points as usize
+                            };
+                            if point_in_poly(&p, &record.points[start_point..end_point]) {
+                                if record.is_hole(part as i32) {
+                                    inside = false;
+                                    break;
+                                }
+                                inside = true;
+                            }
+                        }
+                        if inside {
+                            if let Some(x) = sample_cell(row, col) {
+                                features.push(x);
+                                sample_classes.push(class_value);
+                            }
+                        }
+                    }
+                }
+            } else {
+                for p in &record.points {
+                    let row = input_raster[0].get_row_from_y(p.y);
+                    let col = input_raster[0].get_column_from_x(p.x);
+                    if let Some(x) = sample_cell(row, col) {
+                        features.push(x);
+                        sample_classes.push(class_value);
+                    }
+                }
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / training.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Sampling training sites: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // map the class values onto class indices
+        let mut class_values = sample_classes.clone();
+        class_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        class_values.dedup();
+        if class_values.len() < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The training data must contain valid samples of at least two classes.",
+            ));
+        }
+        let num_classes = class_values.len();
+        let labels: Vec<usize> = sample_classes
+            .iter()
+            .map(|v| class_values.iter().position(|c| c == v).unwrap())
+            .collect();
+
+        if verbose {
+            println!("Number of training samples: {}", labels.len());
+            for c in 0..num_classes {
+                println!(
+                    "  Class {}: {} samples",
+                    class_values[c],
+                    labels.iter().filter(|&&l| l == c).count()
+                );
+            }
+            println!("Training the classifier...");
+        }
+
+        let classifier = if use_knn {
+            Classifier::KNearestNeighbour(KnnClassifier::fit(&features, &labels, num_classes, k)?)
+        } else {
+            let rf = RandomForest::fit(
+                &features,
+                &labels,
+                num_classes,
+                num_trees,
+                0,
+                min_samples_split,
+                max_depth,
+                random::random_seed(0),
+            )?;
+            if verbose {
+                println!("Out-of-bag accuracy: {:.2}%", rf.oob_accuracy * 100f64);
+            }
+            Classifier::RandomForest(rf)
+        };
+
+        let model = ClassifierModel {
+            class_values,
+            num_features: num_files,
+            classifier,
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match model.write(&output_file) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
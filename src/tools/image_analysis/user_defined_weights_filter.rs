@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/04/2018
-Last Modified: 22/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::gpu;
 use num_cpus;
 use std::env;
 use std::f64;
@@ -35,6 +36,9 @@ use std::thread;
 /// that this implies that the sum of weights should equal one. In some cases, alternative
 /// sums (e.g. zero) are more appropriate, and as such normalization should not be applied
 /// in these cases.
+///
+/// Large kernels applied to greyscale images may be run on a GPU, where one is available, using
+/// the `--device=gpu` flag; this requires a build of WhiteboxTools with the `gpu` feature.
 pub struct UserDefinedWeightsFilter {
     name: String,
     description: String,
@@ -306,99 +310,135 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
         let d_x = Arc::new(d_x);
         let d_y = Arc::new(d_y);
         let weights = Arc::new(weights);
-        let num_procs = num_cpus::get() as isize;
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let d_x = d_x.clone();
-            let d_y = d_y.clone();
-            let weights = weights.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
+        let mut output = Raster::initialize_using_file(&output_file, &input);
 
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
-                        }
-                        nodata
-                    })
-                };
+        // when requested, the convolution is run on the GPU, falling back to the CPU
+        // if the GPU is unavailable
+        let mut gpu_output = None;
+        if gpu::use_gpu() && !is_rgb_image {
+            let mut data = Vec::with_capacity((rows * columns) as usize);
+            for row in 0..rows {
+                data.extend(input.get_row_data(row));
+            }
+            match gpu::convolve(
+                &data,
+                rows as usize,
+                columns as usize,
+                nodata,
+                &d_x,
+                &d_y,
+                &weights,
+                normalize,
+            ) {
+                Ok(result) => gpu_output = Some(result),
+                Err(e) => {
+                    if verbose {
+                        println!("{} Running on the CPU instead.", e);
+                    }
+                }
+            }
+        }
 
-                let (mut sum_weights, mut z_final): (f64, f64);
-                let mut z: f64;
-                let mut zn: f64;
-                let (mut x, mut y): (isize, isize);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![nodata; columns as usize];
-                    if normalize {
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                sum_weights = 0.0;
-                                z_final = 0.0;
-                                for a in 0..num_pixels_in_filter {
-                                    x = col + d_x[a];
-                                    y = row + d_y[a];
-                                    zn = input_fn(y, x);
-                                    if zn != nodata {
-                                        sum_weights += weights[a];
-                                        z_final += weights[a] * zn;
+        if let Some(result) = gpu_output {
+            for row in 0..rows {
+                let a = (row * columns) as usize;
+                output.set_row_data(row, result[a..a + columns as usize].to_vec());
+            }
+        } else {
+            let num_procs = num_cpus::get() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let d_x = d_x.clone();
+                let d_y = d_y.clone();
+                let weights = weights.clone();
+                let tx1 = tx.clone();
+                thread::spawn(move || {
+                    let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
+                        Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
+                    } else {
+                        Box::new(|row: isize, col: isize| -> f64 {
+                            let value = input.get_value(row, col);
+                            if value != nodata {
+                                return value2i(value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
+                        // simply return the value.
+                        Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
+                    } else {
+                        // convert it back into an rgb value, using the modified intensity value.
+                        Box::new(|row: isize, col: isize, value: f64| -> f64 {
+                            if value != nodata {
+                                let (h, s, _) = value2hsi(input.get_value(row, col));
+                                return hsi2value(h, s, value);
+                            }
+                            nodata
+                        })
+                    };
+
+                    let (mut sum_weights, mut z_final): (f64, f64);
+                    let mut z: f64;
+                    let mut zn: f64;
+                    let (mut x, mut y): (isize, isize);
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![nodata; columns as usize];
+                        if normalize {
+                            for col in 0..columns {
+                                z = input_fn(row, col);
+                                if z != nodata {
+                                    sum_weights = 0.0;
+                                    z_final = 0.0;
+                                    for a in 0..num_pixels_in_filter {
+                                        x = col + d_x[a];
+                                        y = row + d_y[a];
+                                        zn = input_fn(y, x);
+                                        if zn != nodata {
+                                            sum_weights += weights[a];
+                                            z_final += weights[a] * zn;
+                                        }
+                                    }
+                                    if sum_weights > 0f64 {
+                                        data[col as usize] =
+                                            output_fn(row, col, z_final / sum_weights);
                                     }
-                                }
-                                if sum_weights > 0f64 {
-                                    data[col as usize] = output_fn(row, col, z_final / sum_weights);
                                 }
                             }
-                        }
-                    } else {
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                z_final = 0.0;
-                                for a in 0..num_pixels_in_filter {
-                                    x = col + d_x[a];
-                                    y = row + d_y[a];
-                                    zn = input_fn(y, x);
-                                    if zn != nodata {
-                                        z_final += weights[a] * zn;
+                        } else {
+                            for col in 0..columns {
+                                z = input_fn(row, col);
+                                if z != nodata {
+                                    z_final = 0.0;
+                                    for a in 0..num_pixels_in_filter {
+                                        x = col + d_x[a];
+                                        y = row + d_y[a];
+                                        zn = input_fn(y, x);
+                                        if zn != nodata {
+                                            z_final += weights[a] * zn;
+                                        }
                                     }
+                                    data[col as usize] = output_fn(row, col, z_final);
                                 }
-                                data[col as usize] = output_fn(row, col, z_final);
                             }
                         }
-                    }
 
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+                        tx1.send((row, data)).unwrap();
+                    }
+                });
+            }
 
-        let mut output = Raster::initialize_using_file(&output_file, &input);
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+            for row in 0..rows {
+                let data = rx.recv().expect("Error receiving data from thread.");
+                output.set_row_data(data.0, data.1);
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
                 }
             }
         }
@@ -236,32 +236,21 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
         };
 
         let input_r = Arc::new(Raster::new(&input1_file, "r")?);
-        let input_g = Arc::new(Raster::new(&input2_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let input_g = Arc::new(align_raster_input(
+            &input_r,
+            Raster::new(&input2_file, "r")?,
+        )?);
         let input_b = match input3_used {
-            true => Arc::new(Raster::new(&input3_file, "r")?),
-            false => Arc::new(Raster::new(&input2_file, "r")?),
+            true => Arc::new(align_raster_input(
+                &input_r,
+                Raster::new(&input3_file, "r")?,
+            )?),
+            false => input_g.clone(),
         };
 
         let start = Instant::now();
 
-        // make sure the input files have the same size
-        if input_r.configs.rows != input_g.configs.rows
-            || input_r.configs.columns != input_g.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if input_r.configs.rows != input_b.configs.rows
-            || input_r.configs.columns != input_b.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let rows = input_r.configs.rows as isize;
         let columns = input_r.configs.columns as isize;
         let nodata_r = input_r.configs.nodata;
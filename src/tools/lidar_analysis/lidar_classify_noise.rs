@@ -0,0 +1,685 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::lidar::*;
+use crate::rendering::html::*;
+use crate::structures::{DistanceMetric, FixedRadiusSearch3D};
+use crate::tools::*;
+use num_cpus;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool classifies the noise points within a LiDAR point cloud, assigning outlier points
+/// below the surrounding points to the low noise class (7) and those above the surrounding points
+/// to the high noise class (18). Unlike `LidarRemoveOutliers`, which removes outliers from the point
+/// cloud, points are not deleted, and their classification may be reviewed or reversed later. Two
+/// criteria are used to identify noise points:
+///
+/// 1. **Statistical outlier removal** (SOR). The mean distance from each point to its *k* nearest
+///    neighbours (`--num_neighbours`) in three dimensions is calculated, along with the mean (&mu;)
+///    and standard deviation (&sigma;) of these distances over the point cloud. Points with a mean
+///    neighbour distance greater than &mu; + `--std_dev_mult` &times; &sigma; are classified as noise.
+///
+/// 2. **Isolation**. The point cloud is divided into cubic voxels with a side length of
+///    `--voxel_size`. Points with fewer than `--min_voxel_points` other points in the 3 &times; 3
+///    &times; 3 block of voxels centred on the voxel containing the point are classified as noise.
+///
+/// A noise point is classified as low noise if it lies below the mean elevation of its *k* nearest
+/// neighbours, and as high noise otherwise. Points that are already classified as noise, and
+/// withheld points, are excluded from the neighbourhoods of the other points and retain their
+/// classification.
+///
+/// If the `--dry_run` flag is specified, the number of points identified by each criterion is
+/// reported, but the output LiDAR file (`--output`) is not written, which is useful for selecting
+/// suitable parameter values before classifying a large dataset. An HTML report of the
+/// classification may optionally be output (`--out_html`).
+///
+/// # See Also
+/// `LidarRemoveOutliers`, `FilterLidarClasses`, `LidarClassifySubset`
+pub struct LidarClassifyNoise {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarClassifyNoise {
+    pub fn new() -> LidarClassifyNoise {
+        // public constructor
+        let name = "LidarClassifyNoise".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description =
+            "Classifies isolated points and low and high outliers in a LiDAR point cloud as noise."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file; not required for a dry run.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours".to_owned(),
+            flags: vec!["--num_neighbours".to_owned()],
+            description: "Number of nearest neighbours used in statistical outlier removal."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Standard Deviation Multiplier".to_owned(),
+            flags: vec!["--std_dev_mult".to_owned()],
+            description: "Number of standard deviations above the mean neighbour distance at which points are outliers.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("3.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Voxel Size".to_owned(),
+            flags: vec!["--voxel_size".to_owned()],
+            description: "Side length of the voxels used to identify isolated points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Voxel Neighbourhood Points".to_owned(),
+            flags: vec!["--min_voxel_points".to_owned()],
+            description:
+                "Minimum number of other points in the voxel neighbourhood of non-isolated points."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Dry run?".to_owned(),
+            flags: vec!["--dry_run".to_owned()],
+            description: "Report the number of noise points without writing the output file."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML Report File".to_owned(),
+            flags: vec!["--out_html".to_owned()],
+            description: "Optional output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=output.las --num_neighbours=10 --std_dev_mult=2.5 --voxel_size=2.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las --dry_run --out_html=noise_report.html", short_exe, name).replace("*", &sep);
+
+        LidarClassifyNoise {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarClassifyNoise {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file: String = "".to_string();
+        let mut output_file: String = "".to_string();
+        let mut html_file: String = "".to_string();
+        let mut num_neighbours = 8usize;
+        let mut std_dev_mult = 3f64;
+        let mut voxel_size = 1f64;
+        let mut min_voxel_points = 2usize;
+        let mut dry_run = false;
+
+        // read the arguments
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_html" {
+                html_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-num_neighbours" {
+                num_neighbours = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-std_dev_mult" {
+                std_dev_mult = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-voxel_size" {
+                voxel_size = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-min_voxel_points" {
+                min_voxel_points = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-dry_run" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    dry_run = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if num_neighbours == 0 || voxel_size <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --num_neighbours and --voxel_size parameters must be greater than zero.",
+            ));
+        }
+        if output_file.is_empty() && !dry_run {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output file must be specified unless the --dry_run flag is used.",
+            ));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.is_empty() && !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !html_file.is_empty() && !html_file.contains(sep) && !html_file.contains("/") {
+            html_file = format!("{}{}", working_directory, html_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let input = LasFile::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (input.header.number_of_points as f64 - 1f64).max(1f64); // used for progress calculation only
+
+        // find the points that are candidates for classification, and count the points in
+        // each voxel
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+        let mut frs: FixedRadiusSearch3D<usize> =
+            FixedRadiusSearch3D::new(voxel_size, DistanceMetric::Euclidean);
+        let mut voxel_counts: HashMap<(i64, i64, i64), usize> = HashMap::new();
+        let mut is_candidate = vec![false; n_points];
+        let mut num_candidates = 0usize;
+        let mut p: PointData;
+        for i in 0..n_points {
+            p = input.get_point_info(i);
+            if !p.is_classified_noise() && !p.withheld() {
+                is_candidate[i] = true;
+                num_candidates += 1;
+                frs.insert(p.x, p.y, p.z, i);
+                *voxel_counts
+                    .entry(voxel_of(p.x, p.y, p.z, voxel_size))
+                    .or_insert(0) += 1;
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Adding points to search tree: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if num_candidates <= num_neighbours {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file contains too few points for the number of neighbours.",
+            ));
+        }
+
+        // the mean distance to, and mean elevation of, the nearest neighbours of each point
+        let frs = Arc::new(frs);
+        let input = Arc::new(input);
+        let is_candidate = Arc::new(is_candidate);
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let frs = frs.clone();
+            let input = input.clone();
+            let is_candidate = is_candidate.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut p: PointData;
+                for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    if !is_candidate[point_num] {
+                        tx.send((point_num, 0f64, 0f64)).unwrap();
+                        continue;
+                    }
+                    p = input.get_point_info(point_num);
+                    let ret = frs.knn_search(p.x, p.y, p.z, num_neighbours + 1);
+                    let mut total_dist = 0f64;
+                    let mut total_z = 0f64;
+                    let mut n = 0f64;
+                    for &(j, dist) in &ret {
+                        if j != point_num {
+                            total_dist += dist;
+                            total_z += input.get_point_info(j).z;
+                            n += 1f64;
+                        }
+                    }
+                    if n > 0f64 {
+                        tx.send((point_num, total_dist / n, total_z / n)).unwrap();
+                    } else {
+                        tx.send((point_num, 0f64, p.z)).unwrap();
+                    }
+                }
+            });
+        }
+
+        let mut mean_dist = vec![0f64; n_points];
+        let mut mean_z = vec![0f64; n_points];
+        for i in 0..n_points {
+            let (point_num, dist, z) = rx.recv().expect("Error receiving data from thread.");
+            mean_dist[point_num] = dist;
+            mean_z[point_num] = z;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Finding neighbours: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // the statistical outlier threshold
+        let mut total = 0f64;
+        let mut total_sqrd = 0f64;
+        for i in 0..n_points {
+            if is_candidate[i] {
+                total += mean_dist[i];
+                total_sqrd += mean_dist[i] * mean_dist[i];
+            }
+        }
+        let n = num_candidates as f64;
+        let dist_mean = total / n;
+        let dist_std_dev = (total_sqrd / n - dist_mean * dist_mean).max(0f64).sqrt();
+        let dist_threshold = dist_mean + std_dev_mult * dist_std_dev;
+
+        let mut class_vals = vec![0u8; n_points];
+        let mut num_sor = 0usize;
+        let mut num_isolated = 0usize;
+        let mut num_low = 0usize;
+        let mut num_high = 0usize;
+        let mut num_existing = 0usize;
+        for i in 0..n_points {
+            p = input.get_point_info(i);
+            if !is_candidate[i] {
+                if p.is_classified_noise() {
+                    num_existing += 1;
+                }
+                continue;
+            }
+            let outlier = mean_dist[i] > dist_threshold;
+            let (vx, vy, vz) = voxel_of(p.x, p.y, p.z, voxel_size);
+            let mut num_neighbouring = 0usize;
+            for x in vx - 1..=vx + 1 {
+                for y in vy - 1..=vy + 1 {
+                    for z in vz - 1..=vz + 1 {
+                        if let Some(count) = voxel_counts.get(&(x, y, z)) {
+                            num_neighbouring += count;
+                        }
+                    }
+                }
+            }
+            let isolated = num_neighbouring - 1 < min_voxel_points; // excluding the point itself
+            if outlier {
+                num_sor += 1;
+            }
+            if isolated {
+                num_isolated += 1;
+            }
+            if outlier || isolated {
+                if p.z < mean_z[i] {
+                    class_vals[i] = 7;
+                    num_low += 1;
+                } else {
+                    class_vals[i] = 18;
+                    num_high += 1;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Mean neighbour distance: {:.4}", dist_mean);
+            println!("Outlier distance threshold: {:.4}", dist_threshold);
+            println!("Statistical outliers: {}", num_sor);
+            println!("Isolated points: {}", num_isolated);
+            println!("Points classified as low noise (7): {}", num_low);
+            println!("Points classified as high noise (18): {}", num_high);
+            println!("Points already classified as noise: {}", num_existing);
+        }
+
+        if !dry_run {
+            let mut output = LasFile::initialize_using_file(&output_file, &input);
+            output.header.system_id = "EXTRACTION".to_string();
+            for point_num in 0..n_points {
+                let mut pr = input.get_record(point_num);
+                if class_vals[point_num] != 0u8 {
+                    pr.set_classification(class_vals[point_num]);
+                }
+                output.add_point_record(pr);
+                if verbose {
+                    progress = (100.0_f64 * point_num as f64 / num_points) as i32;
+                    if progress != old_progress {
+                        println!("Saving data: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            if verbose {
+                println!("Writing output LAS file...");
+            }
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Complete!")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        } else if verbose {
+            println!("Dry run; the output file was not written.");
+        }
+
+        if !html_file.is_empty() {
+            let f = File::create(html_file.clone())?;
+            let mut writer = BufWriter::new(f);
+
+            writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>LiDAR Noise Classification</title>"#.as_bytes())?;
+
+            // get the style sheet
+            writer.write_all(&get_css().as_bytes())?;
+
+            writer.write_all(
+                &r#"</head>
+            <body>
+                <h1>LiDAR Noise Classification</h1>"#
+                    .as_bytes(),
+            )?;
+
+            writer
+                .write_all(&format!("<p><strong>Input</strong>: {}<br>", input_file).as_bytes())?;
+            if dry_run {
+                writer.write_all("<strong>Output</strong>: None (dry run)<br>".as_bytes())?;
+            } else {
+                writer.write_all(
+                    &format!("<strong>Output</strong>: {}<br>", output_file).as_bytes(),
+                )?;
+            }
+            writer.write_all(
+                &format!(
+                    "<strong>Number of neighbours</strong>: {}<br>",
+                    num_neighbours
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Standard deviation multiplier</strong>: {}<br>",
+                    std_dev_mult
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                &format!("<strong>Voxel size</strong>: {}<br>", voxel_size).as_bytes(),
+            )?;
+            writer.write_all(
+                &format!(
+                    "<strong>Minimum voxel neighbourhood points</strong>: {}</p>",
+                    min_voxel_points
+                )
+                .as_bytes(),
+            )?;
+
+            writer.write_all("<div><table align=\"center\">".as_bytes())?;
+            writer.write_all("<caption>Noise Classification Summary</caption>".as_bytes())?;
+            let rows = vec![
+                ("Number of points", format!("{}", n_points)),
+                (
+                    "Points already classified as noise",
+                    format!("{}", num_existing),
+                ),
+                ("Mean neighbour distance", format!("{:.4}", dist_mean)),
+                (
+                    "Standard deviation of neighbour distance",
+                    format!("{:.4}", dist_std_dev),
+                ),
+                (
+                    "Outlier distance threshold",
+                    format!("{:.4}", dist_threshold),
+                ),
+                ("Statistical outliers", format!("{}", num_sor)),
+                ("Isolated points", format!("{}", num_isolated)),
+                ("Low noise (class 7)", format!("{}", num_low)),
+                ("High noise (class 18)", format!("{}", num_high)),
+                (
+                    "Percentage of points classified as noise",
+                    format!(
+                        "{:.3}%",
+                        100f64 * (num_low + num_high) as f64 / n_points as f64
+                    ),
+                ),
+            ];
+            for (label, value) in rows {
+                writer.write_all(
+                    &format!(
+                        "<tr><td>{}</td><td class=\"numberCell\">{}</td></tr>",
+                        label, value
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            writer.write_all("</table></div>".as_bytes())?;
+            writer.write_all("</body>".as_bytes())?;
+            let _ = writer.flush();
+
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+
+                println!("Complete! Please see {} for output.", html_file);
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the indices of the voxel containing the point (x, y, z).
+fn voxel_of(x: f64, y: f64, z: f64, voxel_size: f64) -> (i64, i64, i64) {
+    (
+        (x / voxel_size).floor() as i64,
+        (y / voxel_size).floor() as i64,
+        (z / voxel_size).floor() as i64,
+    )
+}
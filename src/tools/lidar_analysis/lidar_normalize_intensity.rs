@@ -0,0 +1,715 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::lidar::*;
+use crate::raster::*;
+use crate::tools::*;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool normalizes the intensity values of a LiDAR point cloud, removing the variation in
+/// intensity that is caused by the acquisition geometry and by differences in the receiver gain
+/// between flightlines, rather than by the reflectance of the surface. The normalized intensity
+/// values replace the original values in the output LiDAR file (`--output`). Three corrections are
+/// applied:
+///
+/// 1. **Range**. The returned energy decreases with the square of the range (*R*) from the sensor
+///    to the target, and intensities are normalized to a reference range (*R*<sub>ref</sub>,
+///    `--ref_range`) as *I* (*R* / *R*<sub>ref</sub>)<sup>2</sup>. Since the sensor trajectory is
+///    not stored in LAS files, the range is estimated from the scan angle (&theta;) and the
+///    flying height of the sensor (`--flying_height`) above the mean elevation of the point cloud,
+///    as *R* = (*H* - *z*) / cos &theta;, where *H* is the sensor altitude. By default, the reference
+///    range is the flying height.
+///
+/// 2. **Incidence angle**. If a ground surface raster (`--dem`) is specified, the intensities are
+///    also divided by the cosine of the angle of incidence (&alpha;) between the laser beam and the
+///    surface normal, calculated from the slope of the ground surface. The direction of flight
+///    of each flightline, which determines the direction of the beam for a given scan angle, is
+///    estimated from the principal axis of its points, oriented using the GPS times of the points
+///    where available. Angles of incidence greater than `--max_incidence` degrees are truncated to
+///    avoid amplifying the intensities of points on steep surfaces excessively.
+///
+/// 3. **Flightline gain**. If the `--balance_flightlines` flag is specified, differences in the
+///    automatic gain control of the receiver between flightlines, as identified by the point source
+///    ID of each point, are removed. The mean range- and incidence-corrected intensity of the first
+///    returns of each flightline is calculated within grid cells of size `--resolution`, and the
+///    gain of each flightline is estimated by least-squares from the ratios of the means of
+///    overlapping flightlines within the cells that they share. Intensities are scaled to the gain
+///    of the flightline containing the most points.
+///
+/// Normalized intensities are rounded and truncated to the range of 16-bit intensity values. An
+/// optional raster of the mean normalized intensity of the first returns within each grid cell
+/// of size `--resolution` may also be output (`--out_raster`).
+///
+/// # See Also
+/// `LidarHillshade`, `FlightlineOverlap`, `LidarIdwInterpolation`
+pub struct LidarNormalizeIntensity {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarNormalizeIntensity {
+    pub fn new() -> LidarNormalizeIntensity {
+        // public constructor
+        let name = "LidarNormalizeIntensity".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description =
+            "Corrects LiDAR intensity for range, incidence angle, and flightline gain differences."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Flying Height".to_owned(),
+            flags: vec!["--flying_height".to_owned()],
+            description: "Height of the sensor above the mean elevation of the points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference Range".to_owned(),
+            flags: vec!["--ref_range".to_owned()],
+            description: "Range to which intensities are normalized; default is the flying height."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Ground Surface File (optional)".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description:
+                "Optional input ground surface raster used for the incidence angle correction."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Incidence Angle (degrees)".to_owned(),
+            flags: vec!["--max_incidence".to_owned()],
+            description: "Angle of incidence at which the incidence correction is truncated."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("75.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Balance flightline gains?".to_owned(),
+            flags: vec!["--balance_flightlines".to_owned()],
+            description: "Remove gain differences between overlapping flightlines.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Intensity Raster File (optional)".to_owned(),
+            flags: vec!["--out_raster".to_owned()],
+            description: "Optional output raster of mean normalized intensity.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Grid Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description:
+                "Grid cell size of the output raster and of the flightline gain estimation."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=normalized.las --flying_height=1200.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=normalized.las --flying_height=1200.0 --dem=ground.tif --balance_flightlines --out_raster=intensity.tif --resolution=2.0", short_exe, name).replace("*", &sep);
+
+        LidarNormalizeIntensity {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarNormalizeIntensity {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut dem_file = String::new();
+        let mut raster_file = String::new();
+        let mut flying_height = 1000f64;
+        let mut ref_range = f64::NAN;
+        let mut max_incidence = 75f64;
+        let mut balance_flightlines = false;
+        let mut resolution = 1f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_raster" {
+                raster_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-balance_flightlines" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    balance_flightlines = true;
+                }
+            } else if flag_val == "-flying_height"
+                || flag_val == "-ref_range"
+                || flag_val == "-max_incidence"
+                || flag_val == "-resolution"
+            {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-flying_height" => flying_height = val,
+                    "-ref_range" => ref_range = val,
+                    "-max_incidence" => max_incidence = val,
+                    _ => resolution = val,
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if flying_height <= 0f64 || resolution <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --flying_height and --resolution parameters must be greater than zero.",
+            ));
+        }
+        if ref_range.is_nan() {
+            ref_range = flying_height;
+        }
+        let min_cos_incidence = max_incidence.max(0f64).min(89f64).to_radians().cos();
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !dem_file.is_empty() && !dem_file.contains(sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !raster_file.is_empty() && !raster_file.contains(sep) && !raster_file.contains("/") {
+            raster_file = format!("{}{}", working_directory, raster_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let input = LasFile::new(&input_file, "r")?;
+        let dem = if !dem_file.is_empty() {
+            if verbose {
+                println!("Reading ground surface...");
+            }
+            Some(Raster::new(&dem_file, "r")?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        if n_points == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file does not contain any points.",
+            ));
+        }
+        let num_points: f64 = (n_points as f64 - 1f64).max(1f64); // used for progress calculation only
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        // the sensor altitude, and the direction of flight of each flightline
+        let mut mean_z = 0f64;
+        for i in 0..n_points {
+            mean_z += input[i].z;
+        }
+        mean_z /= n_points as f64;
+        let sensor_altitude = mean_z + flying_height;
+        let flight_directions = if dem.is_some() {
+            flightline_directions(&input)
+        } else {
+            HashMap::new()
+        };
+
+        // range and incidence angle corrections
+        let mut corrected = vec![0f64; n_points];
+        let mut num_truncated = 0usize;
+        for i in 0..n_points {
+            let p = input[i];
+            let scan_angle = if p.is_64bit {
+                p.scan_angle as f64 * 0.006
+            } else {
+                p.scan_angle as f64
+            }
+            .to_radians();
+            let range = ((sensor_altitude - p.z) / scan_angle.cos()).max(f64::EPSILON);
+            let mut value = p.intensity as f64 * (range / ref_range).powi(2);
+            if let Some(dem) = &dem {
+                if let Some(normal) = surface_normal(dem, p.x, p.y) {
+                    // the unit vector of the beam, from the sensor towards the point
+                    let (dx, dy) = match flight_directions.get(&p.point_source_id) {
+                        Some(&(ux, uy)) => (uy, -ux), // to the right of the direction of flight
+                        None => (0f64, 0f64),
+                    };
+                    let beam = (
+                        scan_angle.sin() * dx,
+                        scan_angle.sin() * dy,
+                        -scan_angle.cos(),
+                    );
+                    let mut cos_incidence =
+                        -(beam.0 * normal.0 + beam.1 * normal.1 + beam.2 * normal.2);
+                    if cos_incidence < min_cos_incidence {
+                        cos_incidence = min_cos_incidence;
+                        num_truncated += 1;
+                    }
+                    value /= cos_incidence;
+                }
+            }
+            corrected[i] = value;
+
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Correcting intensity: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        drop(dem);
+
+        let (min_x, min_y) = (input.header.min_x, input.header.min_y);
+        let cell_of = |x: f64, y: f64| -> (isize, isize) {
+            (
+                ((x - min_x) / resolution).floor() as isize,
+                ((y - min_y) / resolution).floor() as isize,
+            )
+        };
+
+        // flightline gains
+        let mut gains: HashMap<u16, f64> = HashMap::new();
+        if balance_flightlines {
+            if verbose {
+                println!("Estimating flightline gains...");
+            }
+            // the mean log intensity of the first returns of each flightline in each cell
+            let mut cells: HashMap<(isize, isize), BTreeMap<u16, (f64, usize)>> = HashMap::new();
+            let mut flightline_sizes: BTreeMap<u16, usize> = BTreeMap::new();
+            for i in 0..n_points {
+                let p = input[i];
+                *flightline_sizes.entry(p.point_source_id).or_insert(0) += 1;
+                if p.is_first_return() && corrected[i] > 0f64 {
+                    let entry = cells
+                        .entry(cell_of(p.x, p.y))
+                        .or_insert(BTreeMap::new())
+                        .entry(p.point_source_id)
+                        .or_insert((0f64, 0));
+                    entry.0 += corrected[i].ln();
+                    entry.1 += 1;
+                }
+            }
+            let mut pairs: HashMap<u16, Vec<(u16, f64)>> = HashMap::new();
+            for flightlines in cells.values() {
+                if flightlines.len() < 2 {
+                    continue;
+                }
+                let means: Vec<(u16, f64)> = flightlines
+                    .iter()
+                    .map(|(&id, &(total, n))| (id, total / n as f64))
+                    .collect();
+                for &(a, mean_a) in &means {
+                    for &(b, mean_b) in &means {
+                        if a != b {
+                            pairs.entry(a).or_insert(vec![]).push((b, mean_b - mean_a));
+                        }
+                    }
+                }
+            }
+            let reference = flightline_sizes
+                .iter()
+                .max_by_key(|entry| *entry.1)
+                .map(|(&id, _)| id)
+                .unwrap_or(0);
+            let log_gains = solve_log_gains(&pairs, reference);
+            for (&id, &log_gain) in &log_gains {
+                gains.insert(id, log_gain.exp());
+            }
+            if verbose {
+                println!("Flightline gains (reference flightline {}):", reference);
+                for (id, _) in &flightline_sizes {
+                    println!("  {}: {:.4}", id, gains.get(id).unwrap_or(&1f64));
+                }
+            }
+        }
+
+        // output the normalized intensities
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        output.header.system_id = "EXTRACTION".to_string();
+        for i in 0..n_points {
+            let gain = *gains.get(&input[i].point_source_id).unwrap_or(&1f64);
+            corrected[i] = (corrected[i] * gain).round().max(0f64).min(u16::MAX as f64);
+            let mut pr = input.get_record(i);
+            pr.set_intensity(corrected[i] as u16);
+            output.add_point_record(pr);
+
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Saving data: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            if num_truncated > 0 {
+                println!(
+                    "Incidence angles truncated at {} degrees for {} points",
+                    max_incidence, num_truncated
+                );
+            }
+            println!("Writing output LAS file...");
+        }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !raster_file.is_empty() {
+            let west = min_x;
+            let south = min_y;
+            let columns = (((input.header.max_x - west) / resolution).floor() as usize) + 1;
+            let rows = (((input.header.max_y - south) / resolution).floor() as usize) + 1;
+            let north = south + rows as f64 * resolution;
+            let nodata = -32768f64;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows;
+            configs.columns = columns;
+            configs.north = north;
+            configs.south = south;
+            configs.east = west + columns as f64 * resolution;
+            configs.west = west;
+            configs.resolution_x = resolution;
+            configs.resolution_y = resolution;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            configs.palette = "grey.plt".to_string();
+
+            let mut totals = vec![0f64; rows * columns];
+            let mut counts = vec![0usize; rows * columns];
+            for i in 0..n_points {
+                let p = input[i];
+                if p.is_first_return() {
+                    let (col, row_from_south) = cell_of(p.x, p.y);
+                    let row = rows as isize - 1 - row_from_south;
+                    let index = row as usize * columns + col as usize;
+                    totals[index] += corrected[i];
+                    counts[index] += 1;
+                }
+            }
+            let mut raster = Raster::initialize_using_config(&raster_file, &configs);
+            for row in 0..rows {
+                for col in 0..columns {
+                    let index = row * columns + col;
+                    if counts[index] > 0 {
+                        raster.set_value(
+                            row as isize,
+                            col as isize,
+                            totals[index] / counts[index] as f64,
+                        );
+                    }
+                }
+            }
+            raster.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            raster.add_metadata_entry(format!("Input file: {}", input_file));
+            raster.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving intensity raster...")
+            };
+            let _ = match raster.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Estimates the direction of flight of each flightline, identified by point source ID, as the
+/// principal axis of the coordinates of its points. Where the points have GPS times, the direction
+/// is oriented towards the later points.
+fn flightline_directions(input: &LasFile) -> HashMap<u16, (f64, f64)> {
+    let n_points = input.header.number_of_points as usize;
+    let has_gps_time = input.has_gps_time();
+    // sums of x, y, xx, yy, xy, and n for each flightline
+    let mut sums: HashMap<u16, [f64; 6]> = HashMap::new();
+    for i in 0..n_points {
+        let p = input[i];
+        let s = sums.entry(p.point_source_id).or_insert([0f64; 6]);
+        let (x, y) = (p.x - input.header.min_x, p.y - input.header.min_y);
+        s[0] += x;
+        s[1] += y;
+        s[2] += x * x;
+        s[3] += y * y;
+        s[4] += x * y;
+        s[5] += 1f64;
+    }
+    let mut directions = HashMap::new();
+    for (&id, s) in &sums {
+        let n = s[5];
+        let (mx, my) = (s[0] / n, s[1] / n);
+        let sxx = s[2] / n - mx * mx;
+        let syy = s[3] / n - my * my;
+        let sxy = s[4] / n - mx * my;
+        // the orientation of the major axis of the covariance matrix
+        let theta = 0.5 * (2f64 * sxy).atan2(sxx - syy);
+        directions.insert(id, (theta.cos(), theta.sin(), mx, my));
+    }
+
+    // orient each direction towards the points with later GPS times
+    let mut covariances: HashMap<u16, (f64, f64, f64, f64)> = HashMap::new();
+    if has_gps_time {
+        for i in 0..n_points {
+            let p = input[i];
+            if let (Some(&(ux, uy, mx, my)), Ok(t)) =
+                (directions.get(&p.point_source_id), input.get_gps_time(i))
+            {
+                let along =
+                    (p.x - input.header.min_x - mx) * ux + (p.y - input.header.min_y - my) * uy;
+                let c = covariances
+                    .entry(p.point_source_id)
+                    .or_insert((0f64, 0f64, 0f64, 0f64));
+                c.0 += along * t;
+                c.1 += along;
+                c.2 += t;
+                c.3 += 1f64;
+            }
+        }
+    }
+    directions
+        .into_iter()
+        .map(|(id, (ux, uy, _, _))| {
+            let sign = match covariances.get(&id) {
+                Some(&(sum_at, sum_a, sum_t, n)) if sum_at / n - sum_a * sum_t / (n * n) < 0f64 => {
+                    -1f64
+                }
+                _ => 1f64,
+            };
+            (id, (sign * ux, sign * uy))
+        })
+        .collect()
+}
+
+/// Returns the upward unit normal of the ground surface at (x, y), calculated using the 3 x 3
+/// finite difference method of Horn (1981), or `None` if the point lies outside of the surface.
+fn surface_normal(dem: &Raster, x: f64, y: f64) -> Option<(f64, f64, f64)> {
+    let row = dem.get_row_from_y(y);
+    let col = dem.get_column_from_x(x);
+    let nodata = dem.configs.nodata;
+    let z = dem.get_value(row, col);
+    if z == nodata {
+        return None;
+    }
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut n = [0f64; 8];
+    for i in 0..8 {
+        n[i] = dem.get_value(row + dy[i], col + dx[i]);
+        if n[i] == nodata {
+            n[i] = z;
+        }
+    }
+    // the neighbours are ordered clockwise from the north-east
+    let fx = (n[2] - n[4] + 2f64 * (n[1] - n[5]) + n[0] - n[6]) / (8f64 * dem.configs.resolution_x);
+    let fy = (n[6] - n[4] + 2f64 * (n[7] - n[3]) + n[0] - n[2]) / (8f64 * dem.configs.resolution_y);
+    let length = (fx * fx + fy * fy + 1f64).sqrt();
+    Some((-fx / length, -fy / length, 1f64 / length))
+}
+
+/// Solves for the log gain of each flightline, with that of the `reference` flightline fixed at
+/// zero, that minimizes the squared differences between the gain-adjusted mean log intensities of
+/// overlapping flightlines. `pairs` lists, for each flightline, the overlapping flightlines and
+/// the difference in mean log intensity between them in each shared cell.
+fn solve_log_gains(pairs: &HashMap<u16, Vec<(u16, f64)>>, reference: u16) -> HashMap<u16, f64> {
+    let mut log_gains: HashMap<u16, f64> = pairs.keys().map(|&id| (id, 0f64)).collect();
+    // Gauss-Seidel iterations
+    for _ in 0..500 {
+        let mut max_change = 0f64;
+        for (&id, neighbours) in pairs {
+            if id == reference {
+                continue;
+            }
+            let mut total = 0f64;
+            for &(other, diff) in neighbours {
+                total += log_gains[&other] + diff;
+            }
+            let value = total / neighbours.len() as f64;
+            max_change = max_change.max((value - log_gains[&id]).abs());
+            log_gains.insert(id, value);
+        }
+        if max_change < 1e-8 {
+            break;
+        }
+    }
+    log_gains
+}
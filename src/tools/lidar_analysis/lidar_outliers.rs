@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 06/02/2018
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -22,6 +22,9 @@ use std::thread;
 /// This tool will filter out points from a LiDAR point cloud if the absolute elevation
 /// difference between a point and the averge elevation of its neighbourhood, calculated
 /// without the point, exceeds a threshold (elev_diff).
+///
+/// # See Also
+/// `LidarClassifyNoise`
 pub struct LidarRemoveOutliers {
     name: String,
     description: String,
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 18/02/2018
-Last Modified: 31/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -42,6 +42,13 @@ use std::thread;
 /// When the input/output parameters are not specified, the tool works on all LAS files contained within
 /// the working directory.
 ///
+/// By default, points are binned into a north-up grid of square cells. The `--grid_type` flag may be
+/// set to `hexagonal` to bin points into a grid of regular hexagons instead, in which case the grid
+/// resolution is the distance between the centres of neighbouring hexagons. The `--rotation` flag
+/// rotates the grid counter-clockwise by the specified number of degrees, e.g. to align it with a
+/// survey's flight lines. Hexagonal and rotated grids can only be saved in the GeoTIFF and Whitebox
+/// raster formats.
+///
 /// **Notes**:
 /// 1. The num_pulses output is actually the number of pulses with at lease one return; specifically it is
 ///    the sum of the early returns (first and only) in a grid cell. In areas of low reflectance, such as
@@ -87,6 +94,30 @@ impl LidarPointStats {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Grid Type".to_owned(),
+            flags: vec!["--grid_type".to_owned()],
+            description:
+                "Shape of the output raster's grid cells; options are 'square' and 'hexagonal'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "square".to_owned(),
+                "hexagonal".to_owned(),
+            ]),
+            default_value: Some("square".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Grid Rotation (degrees)".to_owned(),
+            flags: vec!["--rotation".to_owned()],
+            description: "Counter-clockwise rotation of the output raster's grid, in degrees."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
         parameters.push(ToolParameter {
             name: "Output number of points?".to_owned(),
             flags: vec!["--num_points".to_owned()],
@@ -225,6 +256,8 @@ impl WhiteboxTool for LidarPointStats {
         let mut z_range = false;
         let mut intensity_range = false;
         let mut predominant_class = false;
+        let mut grid_type = GridType::Square;
+        let mut rotation = 0f64;
 
         // read the arguments
         if args.len() == 0 {
@@ -261,6 +294,33 @@ impl WhiteboxTool for LidarPointStats {
                         .parse::<f64>()
                         .expect(&format!("Error parsing {}", flag_val))
                 };
+            } else if flag_val == "-grid_type" {
+                let v = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                grid_type = match GridType::from_str(&v) {
+                    Some(g) => g,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unrecognized grid type '{}'.", v),
+                        ))
+                    }
+                };
+            } else if flag_val == "-rotation" {
+                rotation = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             } else if flag_val == "-num_points" {
                 if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
                     num_points = true;
@@ -376,8 +436,10 @@ impl WhiteboxTool for LidarPointStats {
 
                     let west: f64 = input.header.min_x; // - 0.5 * grid_res;
                     let north: f64 = input.header.max_y; // + 0.5 * grid_res;
-                    let rows: usize = (((north - input.header.min_y) / grid_res).ceil()) as usize;
-                    let columns: usize = (((input.header.max_x - west) / grid_res).ceil()) as usize;
+                    let mut rows: usize =
+                        (((north - input.header.min_y) / grid_res).ceil()) as usize;
+                    let mut columns: usize =
+                        (((input.header.max_x - west) / grid_res).ceil()) as usize;
                     let south: f64 = north - rows as f64 * grid_res;
                     let east = west + columns as f64 * grid_res;
                     let nodata = -32768.0f64;
@@ -399,6 +461,34 @@ impl WhiteboxTool for LidarPointStats {
                     configs.nodata = nodata;
                     configs.data_type = DataType::F64;
                     configs.photometric_interp = PhotometricInterpretation::Continuous;
+                    configs.grid_type = grid_type;
+                    configs.rotation = rotation;
+                    if !configs.is_north_up_square_grid() {
+                        if grid_type == GridType::Hexagonal {
+                            configs.resolution_y = grid::hex_row_spacing(grid_res);
+                        }
+                        grid::fit_grid_to_extent(
+                            &mut configs,
+                            input.header.min_x,
+                            input.header.min_y,
+                            input.header.max_x,
+                            input.header.max_y,
+                        );
+                        rows = configs.rows;
+                        columns = configs.columns;
+                    }
+                    let get_cell = |x: f64, y: f64| -> (isize, isize) {
+                        if configs.is_north_up_square_grid() {
+                            (
+                                (((rows - 1) as f64 * (north - half_grid_res - y) / ns_range)
+                                    .round()) as isize,
+                                (((columns - 1) as f64 * (x - west - half_grid_res) / ew_range)
+                                    .round()) as isize,
+                            )
+                        } else {
+                            configs.get_cell_from_point(x, y)
+                        }
+                    };
 
                     let n_points = input.header.number_of_points as usize;
                     let num_points_float: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
@@ -417,13 +507,9 @@ impl WhiteboxTool for LidarPointStats {
 
                         let start_run = Instant::now();
 
-                        let (mut row, mut col): (isize, isize);
                         for i in 0..n_points {
                             let p: PointData = input.get_point_info(i);
-                            col = (((columns - 1) as f64 * (p.x - west - half_grid_res) / ew_range)
-                                .round()) as isize;
-                            row = (((rows - 1) as f64 * (north - half_grid_res - p.y) / ns_range)
-                                .round()) as isize;
+                            let (row, col) = get_cell(p.x, p.y);
 
                             out_num_pnts.increment(row, col, 1f64);
 
@@ -550,13 +636,9 @@ impl WhiteboxTool for LidarPointStats {
 
                         let mut new_min_max_z: bool;
                         let mut new_min_max_i: bool;
-                        let (mut row, mut col): (isize, isize);
                         for i in 0..n_points {
                             let p: PointData = input.get_point_info(i);
-                            col = (((columns - 1) as f64 * (p.x - west - half_grid_res) / ew_range)
-                                .round()) as isize;
-                            row = (((rows - 1) as f64 * (north - half_grid_res - p.y) / ns_range)
-                                .round()) as isize;
+                            let (row, col) = get_cell(p.x, p.y);
 
                             new_min_max_z = false;
                             if p.z < min_z.get_value(row, col) {
@@ -665,13 +747,9 @@ impl WhiteboxTool for LidarPointStats {
 
                         let mut class: u8;
                         let mut freq: u16;
-                        let (mut row, mut col): (isize, isize);
                         for i in 0..n_points {
                             let p: PointData = input.get_point_info(i);
-                            col = (((columns - 1) as f64 * (p.x - west - half_grid_res) / ew_range)
-                                .round()) as isize;
-                            row = (((rows - 1) as f64 * (north - half_grid_res - p.y) / ns_range)
-                                .round()) as isize;
+                            let (row, col) = get_cell(p.x, p.y);
 
                             class = p.classification();
                             class_histo[class as usize].increment(row, col, 1u16);
@@ -0,0 +1,721 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use self::na::{Matrix3, Vector3};
+use crate::lidar::*;
+use crate::na;
+use crate::structures::{DistanceMetric, FixedRadiusSearch2D, FixedRadiusSearch3D, Point2D};
+use crate::tools::*;
+use crate::vector::*;
+use num_cpus;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool detects the conductors of powerlines and transmission lines in a classified LiDAR
+/// point cloud, labels the conductor points, and outputs three-dimensional polylines that
+/// approximate the conductors, along with the clearance between the conductors and the
+/// surrounding vegetation. The input point cloud (`--input`) must contain classified ground points
+/// (class 2), e.g. from the `LidarGroundPointFilter` tool, which are used to measure the height of
+/// points above the ground.
+///
+/// Conductors are identified as linear, elevated features. Candidate points are those that are at
+/// least `--min_height` above the nearest ground point, excluding ground, building, and noise
+/// points. The linearity of each candidate point is measured from the eigenvalues
+/// (&lambda;<sub>1</sub> &ge; &lambda;<sub>2</sub> &ge; &lambda;<sub>3</sub>) of the covariance
+/// matrix of the candidate points within a distance of `--radius`, as
+/// (&lambda;<sub>1</sub> - &lambda;<sub>2</sub>) / &lambda;<sub>1</sub>. Points with a linearity of
+/// at least `--min_linearity`, and with a principal direction inclined less than 30 degrees from
+/// the horizontal, are grouped into conductor segments by region growing, linking neighbouring
+/// linear points with principal directions within 20 degrees of one another. Segments with a
+/// horizontal length less than `--min_length` are discarded, which removes most of the linear
+/// features of vegetation and building edges.
+///
+/// Conductor points are assigned the wire conductor class (14) in the output LiDAR file
+/// (`--output`). If an output vector file (`--out_lines`) is specified, each conductor segment is
+/// output as a 3D polyline (PolyLineZ) whose vertices are the mean positions of the segment's points
+/// within intervals of length `--radius` along the segment, which follows the catenary sag of the
+/// conductor. The attributes of the polylines include the number of points (*NUM_PTS*), the 3D length
+/// (*LENGTH*), the minimum and maximum height above ground (*MIN_HAG* and *MAX_HAG*), the minimum
+/// distance to a vegetation point (classes 3-5) within the `--clearance` distance (*MIN_CLR*), and
+/// the number of vegetation points within the clearance distance (*NUM_VEG*). Conductors without
+/// any vegetation within the clearance distance have a *MIN_CLR* of zero and a *NUM_VEG* of zero.
+///
+/// # See Also
+/// `LidarGroundPointFilter`, `ClassifyBuildingsInLidar`, `LidarClassifyNoise`, `HeightAboveGround`
+pub struct LidarPowerlineDetection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarPowerlineDetection {
+    pub fn new() -> LidarPowerlineDetection {
+        // public constructor
+        let name = "LidarPowerlineDetection".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Detects powerline conductors in a classified LiDAR point cloud and measures their clearance to vegetation.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Conductor Lines File (optional)".to_owned(),
+            flags: vec!["--out_lines".to_owned()],
+            description: "Optional output 3D polyline file of the conductors.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Height Above Ground".to_owned(),
+            flags: vec!["--min_height".to_owned()],
+            description: "Minimum height above ground of conductor points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Radius".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Search radius used to measure linearity and to link conductor points."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Linearity".to_owned(),
+            flags: vec!["--min_linearity".to_owned()],
+            description: "Minimum linearity (0-1) of conductor points.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.9".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Conductor Length".to_owned(),
+            flags: vec!["--min_length".to_owned()],
+            description: "Minimum horizontal length of conductor segments.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("20.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Vegetation Clearance Distance".to_owned(),
+            flags: vec!["--clearance".to_owned()],
+            description:
+                "Distance within which vegetation points are counted as clearance encroachments."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=classified.las -o=powerlines.las --out_lines=conductors.shp --min_height=6.0 --radius=2.5 --clearance=4.0", short_exe, name).replace("*", &sep);
+
+        LidarPowerlineDetection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarPowerlineDetection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut lines_file = String::new();
+        let mut min_height = 5f64;
+        let mut radius = 2f64;
+        let mut min_linearity = 0.9f64;
+        let mut min_length = 20f64;
+        let mut clearance = 5f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_lines" {
+                lines_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-min_height"
+                || flag_val == "-radius"
+                || flag_val == "-min_linearity"
+                || flag_val == "-min_length"
+                || flag_val == "-clearance"
+            {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-min_height" => min_height = val,
+                    "-radius" => radius = val,
+                    "-min_linearity" => min_linearity = val,
+                    "-min_length" => min_length = val,
+                    _ => clearance = val,
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if radius <= 0f64 || clearance <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --radius and --clearance parameters must be greater than zero.",
+            ));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !lines_file.is_empty() && !lines_file.contains(sep) && !lines_file.contains("/") {
+            lines_file = format!("{}{}", working_directory, lines_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let mut input = LasFile::new(&input_file, "r")?;
+        let wkt = input.get_wkt();
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (n_points as f64 - 1f64).max(1f64); // used for progress calculation only
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        // height above the nearest ground point
+        let mut ground: FixedRadiusSearch2D<f64> =
+            FixedRadiusSearch2D::new(radius * 5f64, DistanceMetric::SquaredEuclidean);
+        let mut num_ground = 0usize;
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            if p.classification() == 2 && !p.withheld() {
+                ground.insert(p.x, p.y, p.z);
+                num_ground += 1;
+            }
+        }
+        if num_ground == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file does not contain any ground points (class 2); classify the ground points first, e.g. using LidarGroundPointFilter.",
+            ));
+        }
+        let mut hag = vec![f64::NAN; n_points];
+        let mut candidates: FixedRadiusSearch3D<usize> =
+            FixedRadiusSearch3D::new(radius, DistanceMetric::SquaredEuclidean);
+        let mut is_candidate = vec![false; n_points];
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            let class = p.classification();
+            if class != 2 && class != 6 && !p.is_classified_noise() && !p.withheld() {
+                let ret = ground.knn_search(p.x, p.y, 1);
+                if !ret.is_empty() {
+                    hag[i] = p.z - ret[0].0;
+                    if hag[i] >= min_height {
+                        is_candidate[i] = true;
+                        candidates.insert(p.x, p.y, p.z, i);
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Finding elevated points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        drop(ground);
+
+        // the linearity and principal direction of each candidate point
+        let input = Arc::new(input);
+        let candidates = Arc::new(candidates);
+        let is_candidate = Arc::new(is_candidate);
+        let max_inclination = 30f64.to_radians().sin();
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let candidates = candidates.clone();
+            let is_candidate = is_candidate.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    let mut direction = None;
+                    if is_candidate[point_num] {
+                        let p = input.get_point_info(point_num);
+                        let ret = candidates.search(p.x, p.y, p.z);
+                        if ret.len() >= 3 {
+                            let neighbours: Vec<Vector3<f64>> = ret
+                                .iter()
+                                .map(|&(j, _)| {
+                                    let q = input.get_point_info(j);
+                                    Vector3::new(q.x - p.x, q.y - p.y, q.z - p.z)
+                                })
+                                .collect();
+                            let (linearity, dir) = principal_direction(&neighbours);
+                            if linearity >= min_linearity && dir.z.abs() <= max_inclination {
+                                direction = Some(dir);
+                            }
+                        }
+                    }
+                    tx.send((point_num, direction)).unwrap();
+                }
+            });
+        }
+        let mut directions: Vec<Option<Vector3<f64>>> = vec![None; n_points];
+        for i in 0..n_points {
+            let (point_num, direction) = rx.recv().expect("Error receiving data from thread.");
+            directions[point_num] = direction;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Measuring linearity: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // group the linear points into conductor segments
+        let min_direction_similarity = 20f64.to_radians().cos();
+        let mut segment_of = vec![usize::MAX; n_points];
+        let mut segments: Vec<Vec<usize>> = vec![];
+        for i in 0..n_points {
+            if directions[i].is_none() || segment_of[i] != usize::MAX {
+                continue;
+            }
+            let segment_id = segments.len();
+            let mut members = vec![i];
+            segment_of[i] = segment_id;
+            let mut queue = VecDeque::new();
+            queue.push_back(i);
+            while let Some(j) = queue.pop_front() {
+                let p = input.get_point_info(j);
+                let dir_j = directions[j].unwrap();
+                for (k, _) in candidates.search(p.x, p.y, p.z) {
+                    if segment_of[k] == usize::MAX {
+                        if let Some(dir_k) = directions[k] {
+                            if dir_j.dot(&dir_k).abs() >= min_direction_similarity {
+                                segment_of[k] = segment_id;
+                                members.push(k);
+                                queue.push_back(k);
+                            }
+                        }
+                    }
+                }
+            }
+            segments.push(members);
+        }
+
+        // discard the short segments, and find the axis of each of the remaining segments
+        let mut conductors: Vec<Conductor> = vec![];
+        for members in segments {
+            let (mut mx, mut my) = (0f64, 0f64);
+            for &j in &members {
+                let p = input.get_point_info(j);
+                mx += p.x;
+                my += p.y;
+            }
+            mx /= members.len() as f64;
+            my /= members.len() as f64;
+            let (mut sxx, mut syy, mut sxy) = (0f64, 0f64, 0f64);
+            for &j in &members {
+                let p = input.get_point_info(j);
+                sxx += (p.x - mx) * (p.x - mx);
+                syy += (p.y - my) * (p.y - my);
+                sxy += (p.x - mx) * (p.y - my);
+            }
+            let theta = 0.5 * (2f64 * sxy).atan2(sxx - syy);
+            let (ux, uy) = (theta.cos(), theta.sin());
+            let mut positions: Vec<(f64, usize)> = members
+                .iter()
+                .map(|&j| {
+                    let p = input.get_point_info(j);
+                    ((p.x - mx) * ux + (p.y - my) * uy, j)
+                })
+                .collect();
+            positions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            let length = positions[positions.len() - 1].0 - positions[0].0;
+            if length >= min_length {
+                conductors.push(Conductor {
+                    positions: positions,
+                    min_clearance: f64::INFINITY,
+                    num_vegetation: 0,
+                });
+            }
+        }
+        let mut conductor_of = vec![usize::MAX; n_points];
+        let mut conductor_points: FixedRadiusSearch3D<usize> =
+            FixedRadiusSearch3D::new(clearance, DistanceMetric::Euclidean);
+        for (c, conductor) in conductors.iter().enumerate() {
+            for &(_, j) in &conductor.positions {
+                conductor_of[j] = c;
+                let p = input.get_point_info(j);
+                conductor_points.insert(p.x, p.y, p.z, c);
+            }
+        }
+
+        // clearance between the conductors and the vegetation
+        if !conductors.is_empty() {
+            for i in 0..n_points {
+                let p = input.get_point_info(i);
+                let class = p.classification();
+                if (class == 3 || class == 4 || class == 5) && conductor_of[i] == usize::MAX {
+                    let mut nearest: HashMap<usize, f64> = HashMap::new();
+                    for (c, dist) in conductor_points.search(p.x, p.y, p.z) {
+                        let d = nearest.entry(c).or_insert(dist);
+                        if dist < *d {
+                            *d = dist;
+                        }
+                    }
+                    for (c, dist) in nearest {
+                        conductors[c].num_vegetation += 1;
+                        if dist < conductors[c].min_clearance {
+                            conductors[c].min_clearance = dist;
+                        }
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * i as f64 / num_points) as i32;
+                    if progress != old_progress {
+                        println!("Measuring vegetation clearance: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        // output the classified points
+        let mut num_conductor_points = 0usize;
+        let mut output = LasFile::initialize_using_file(&output_file, &input);
+        output.header.system_id = "EXTRACTION".to_string();
+        for i in 0..n_points {
+            let mut pr = input.get_record(i);
+            if conductor_of[i] != usize::MAX {
+                pr.set_classification(14);
+                num_conductor_points += 1;
+            }
+            output.add_point_record(pr);
+        }
+        if verbose {
+            println!("Writing output LAS file...");
+        }
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Complete!")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !lines_file.is_empty() {
+            let mut lines = Shapefile::new(&lines_file, ShapeType::PolyLineZ)?;
+            lines.projection = wkt;
+            lines
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+            lines.attributes.add_field(&AttributeField::new(
+                "NUM_PTS",
+                FieldDataType::Int,
+                9u8,
+                0u8,
+            ));
+            lines.attributes.add_field(&AttributeField::new(
+                "LENGTH",
+                FieldDataType::Real,
+                12u8,
+                3u8,
+            ));
+            lines.attributes.add_field(&AttributeField::new(
+                "MIN_HAG",
+                FieldDataType::Real,
+                10u8,
+                3u8,
+            ));
+            lines.attributes.add_field(&AttributeField::new(
+                "MAX_HAG",
+                FieldDataType::Real,
+                10u8,
+                3u8,
+            ));
+            lines.attributes.add_field(&AttributeField::new(
+                "MIN_CLR",
+                FieldDataType::Real,
+                10u8,
+                3u8,
+            ));
+            lines.attributes.add_field(&AttributeField::new(
+                "NUM_VEG",
+                FieldDataType::Int,
+                9u8,
+                0u8,
+            ));
+
+            for (c, conductor) in conductors.iter().enumerate() {
+                // the vertices are the mean positions of the points within each interval
+                let s_start = conductor.positions[0].0;
+                let mut points = vec![];
+                let mut z_values = vec![];
+                let (mut min_hag, mut max_hag) = (f64::INFINITY, f64::NEG_INFINITY);
+                let mut bin = 0isize;
+                let (mut sx, mut sy, mut sz, mut n) = (0f64, 0f64, 0f64, 0f64);
+                for &(s, j) in &conductor.positions {
+                    let p = input.get_point_info(j);
+                    min_hag = min_hag.min(hag[j]);
+                    max_hag = max_hag.max(hag[j]);
+                    let b = ((s - s_start) / radius).floor() as isize;
+                    if b != bin && n > 0f64 {
+                        points.push(Point2D::new(sx / n, sy / n));
+                        z_values.push(sz / n);
+                        sx = 0f64;
+                        sy = 0f64;
+                        sz = 0f64;
+                        n = 0f64;
+                    }
+                    bin = b;
+                    sx += p.x;
+                    sy += p.y;
+                    sz += p.z;
+                    n += 1f64;
+                }
+                if n > 0f64 {
+                    points.push(Point2D::new(sx / n, sy / n));
+                    z_values.push(sz / n);
+                }
+                if points.len() < 2 {
+                    continue;
+                }
+                let mut length = 0f64;
+                for k in 1..points.len() {
+                    let (dx, dy, dz) = (
+                        points[k].x - points[k - 1].x,
+                        points[k].y - points[k - 1].y,
+                        z_values[k] - z_values[k - 1],
+                    );
+                    length += (dx * dx + dy * dy + dz * dz).sqrt();
+                }
+                let measures = vec![0f64; points.len()];
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLineZ);
+                sfg.add_partz(&points, &measures, &z_values);
+                lines.add_record(sfg);
+                lines.attributes.add_record(
+                    vec![
+                        FieldData::Int(c as i32 + 1),
+                        FieldData::Int(conductor.positions.len() as i32),
+                        FieldData::Real(length),
+                        FieldData::Real(min_hag),
+                        FieldData::Real(max_hag),
+                        FieldData::Real(if conductor.num_vegetation > 0 {
+                            conductor.min_clearance
+                        } else {
+                            0f64
+                        }),
+                        FieldData::Int(conductor.num_vegetation as i32),
+                    ],
+                    false,
+                );
+            }
+
+            if verbose {
+                println!("Saving conductor lines...");
+            }
+            let _ = match lines.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!("Number of conductor segments: {}", conductors.len());
+            println!("Number of conductor points: {}", num_conductor_points);
+            let encroached = conductors.iter().filter(|c| c.num_vegetation > 0).count();
+            println!(
+                "Segments with vegetation within {} of the conductor: {}",
+                clearance, encroached
+            );
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+struct Conductor {
+    // the position of each point along the axis of the conductor, and the point number
+    positions: Vec<(f64, usize)>,
+    min_clearance: f64,
+    num_vegetation: usize,
+}
+
+/// Returns the linearity, (l1 - l2) / l1, of a set of points, where l1 and l2 are the largest
+/// eigenvalues of their covariance matrix, along with the principal direction of the points.
+fn principal_direction(points: &[Vector3<f64>]) -> (f64, Vector3<f64>) {
+    let n = points.len() as f64;
+    let mut centroid = Vector3::new(0f64, 0f64, 0f64);
+    for p in points {
+        centroid += p;
+    }
+    centroid /= n;
+    let mut cov = Matrix3::zeros();
+    for p in points {
+        let d = p - centroid;
+        cov += d * d.transpose();
+    }
+    cov /= n;
+    let eig = cov.symmetric_eigen();
+    let mut order = [0usize, 1, 2];
+    order.sort_by(|&a, &b| {
+        eig.eigenvalues[b]
+            .partial_cmp(&eig.eigenvalues[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let l1 = eig.eigenvalues[order[0]];
+    let l2 = eig.eigenvalues[order[1]];
+    let direction = Vector3::new(
+        eig.eigenvectors[(0, order[0])],
+        eig.eigenvectors[(1, order[0])],
+        eig.eigenvectors[(2, order[0])],
+    );
+    let linearity = if l1 > 0f64 { (l1 - l2) / l1 } else { 0f64 };
+    (linearity, direction.normalize())
+}
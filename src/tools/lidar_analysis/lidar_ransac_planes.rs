@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 23/07/2019
-Last Modified: 29/12/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 // extern crate kdtree;
@@ -12,6 +12,7 @@ use crate::lidar::*;
 use crate::na;
 use crate::structures::{DistanceMetric, FixedRadiusSearch3D};
 use crate::tools::*;
+use crate::utils::random;
 use rand::seq::SliceRandom;
 // use kdtree::distance::squared_euclidean;
 // use kdtree::KdTree;
@@ -431,7 +432,6 @@ impl WhiteboxTool for LidarRansacPlanes {
                 let mut p1: PointData;
                 let mut p2: PointData;
                 let mut index: usize;
-                let mut rng = &mut rand::thread_rng();
                 let mut model: Plane;
                 let mut better_model: Plane;
                 let mut center_point: Vector3<f64>;
@@ -439,6 +439,9 @@ impl WhiteboxTool for LidarRansacPlanes {
                 let mut min_rmse: f64;
                 let mut model_contains_center_point: bool;
                 for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    // each point has its own stream, so the planes found do not depend on
+                    // the number of threads
+                    let mut rng = random::rng(point_num as u64);
                     // find the best fitting planar model that contains this point
                     p1 = input.get_point_info(point_num);
                     center_point = Vector3::new(p1.x, p1.y, p1.z);
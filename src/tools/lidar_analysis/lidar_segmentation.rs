@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 05/12/2017
-Last Modified: 12/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 // extern crate kdtree;
@@ -12,6 +12,7 @@ use crate::lidar::*;
 use crate::na;
 use crate::structures::{DistanceMetric, FixedRadiusSearch3D};
 use crate::tools::*;
+use crate::utils::random;
 use rand::seq::SliceRandom;
 // use kdtree::distance::squared_euclidean;
 // use kdtree::KdTree;
@@ -500,7 +501,6 @@ impl WhiteboxTool for LidarSegmentation {
                 let mut p1: PointData;
                 let mut p2: PointData;
                 let mut index: usize;
-                let mut rng = &mut rand::thread_rng();
                 let mut model: Plane;
                 let mut better_model: Plane;
                 let mut center_point: Vector3<f64>;
@@ -508,6 +508,9 @@ impl WhiteboxTool for LidarSegmentation {
                 let mut min_rmse = f64::MAX;
                 let mut model_contains_center_point: bool;
                 for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    // each point has its own stream, so the planes found do not depend on
+                    // the number of threads
+                    let mut rng = random::rng(point_num as u64);
                     let mut best_model: Plane = Plane::zero();
                     // find the best fitting planar model that contains this point
                     p1 = input.get_point_info(point_num);
@@ -722,7 +725,7 @@ impl WhiteboxTool for LidarSegmentation {
         }
 
         let mut clrs: Vec<(u16, u16, u16)> = Vec::new();
-        let mut rng = rand::thread_rng();
+        let mut rng = random::rng(n_points as u64);
         let (mut r, mut g, mut b): (u16, u16, u16); // = (0u16, 0u16, 0u16);
         let range: Vec<u32> = (0..16777215).collect();
         let raw_clrs: Vec<u32> = range
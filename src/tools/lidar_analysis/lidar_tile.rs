@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 05/02/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 use crate::lidar::*;
@@ -22,7 +22,7 @@ use std::path::Path;
 /// station) can result in poorly populated tiles containing relatively few points.
 ///
 /// # See Also
-/// `LidarJoin`, `LidarTileFootprint`
+/// `LidarJoin`, `LidarTileFootprint`, `LidarTiledWorkflow`
 pub struct LidarTile {
     name: String,
     description: String,
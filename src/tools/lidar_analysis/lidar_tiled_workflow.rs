@@ -0,0 +1,757 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::lidar::*;
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use serde_json;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool runs another LiDAR tool on a large LiDAR dataset by splitting the dataset into
+/// buffered tiles, running the tool on each of the tiles in parallel, and merging the tiled
+/// outputs into a single output file. Tools that depend upon the neighbourhood of each point,
+/// such as gridding and ground-point filtering tools, produce artifacts along the edges of
+/// tiles when they are run on tiles that do not overlap. Each tile is therefore extended by a
+/// buffer (`--buffer`), in map units, containing the points of the neighbouring tiles, and the
+/// buffer is removed from each tile's output before merging, so that the merged output is
+/// seamless. The buffer should be at least as large as the search radius used by the tool.
+///
+/// The user must specify the input LAS files (`--inputs`), which together form the dataset; the
+/// name of the tool to run (`--tool`), e.g. `LidarTINGridding` or `LidarGroundPointFilter`; any
+/// further arguments of the tool (`--tool_args`), e.g. `"--resolution=1.0 --returns=last"`; and the
+/// output file (`--output`). The input and output files of the tool are set by this tool and must
+/// not be included in `--tool_args`. The tool must have a LiDAR input and either a raster or a LiDAR
+/// output. Raster outputs are merged onto a grid with the resolution of the tiled outputs, and
+/// with cells aligned with the tile edges where the tile dimensions are multiples of the
+/// resolution. LiDAR outputs are merged by retaining the points of each tile's output that fall
+/// within the unbuffered tile. LAZ files are not supported and must be decompressed before
+/// processing.
+///
+/// Tiles are defined by a grid with its origin at (0, 0) and a tile width (`--width`) and height
+/// (`--height`) in map units. Tiles are written to a temporary directory within the working
+/// directory, which is removed once the outputs are merged unless the `--keep_tiles` flag is
+/// specified. The number of tiles that are processed concurrently (`--threads`) defaults to the
+/// number of available processors; as many LiDAR tools are themselves multi-threaded, a smaller
+/// number may be more efficient, and will require less memory.
+///
+/// # See Also
+/// `LidarTile`, `LidarJoin`, `LidarTileFootprint`
+pub struct LidarTiledWorkflow {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarTiledWorkflow {
+    pub fn new() -> LidarTiledWorkflow {
+        // public constructor
+        let name = "LidarTiledWorkflow".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description =
+            "Runs a LiDAR tool on buffered tiles of a dataset in parallel and merges the outputs."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input LiDAR files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tool Name".to_owned(),
+            flags: vec!["--tool".to_owned()],
+            description: "Name of the LiDAR tool to run on each tile.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tool Arguments".to_owned(),
+            flags: vec!["--tool_args".to_owned()],
+            description: "Space-separated arguments of the tool, excluding its input and output."
+                .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster or LiDAR file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tile Width".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "Width of tiles in the X dimension.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tile Height".to_owned(),
+            flags: vec!["--height".to_owned()],
+            description: "Height of tiles in the Y dimension.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tile Buffer".to_owned(),
+            flags: vec!["--buffer".to_owned()],
+            description: "Width of the buffer added to each side of the tiles.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("20.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Threads".to_owned(),
+            flags: vec!["--threads".to_owned()],
+            description:
+                "Number of tiles processed concurrently; default is the number of processors."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Keep tiles?".to_owned(),
+            flags: vec!["--keep_tiles".to_owned()],
+            description: "Keep the tiles and the tiled outputs after merging.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"file1.las;file2.las\" --tool=LidarTINGridding --tool_args=\"--parameter=elevation --returns=last --resolution=1.0\" -o=dem.tif --width=500.0 --height=500.0 --buffer=25.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"file1.las;file2.las\" --tool=LidarGroundPointFilter --tool_args=\"--radius=2.0 --classify\" -o=classified.las --buffer=10.0 --threads=4", short_exe, name).replace("*", &sep);
+
+        LidarTiledWorkflow {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarTiledWorkflow {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_files = String::new();
+        let mut tool_name = String::new();
+        let mut tool_args = String::new();
+        let mut output_file = String::new();
+        let mut width = 1000f64;
+        let mut height = 1000f64;
+        let mut buffer = 20f64;
+        let mut num_threads = num_cpus::get();
+        let mut keep_tiles = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-inputs" {
+                input_files = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-tool" {
+                tool_name = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-tool_args" {
+                // the tool arguments themselves contain equals signs
+                tool_args = if keyval {
+                    arg[arg.find('=').unwrap() + 1..].to_string()
+                } else {
+                    args[i + 1].replace("\"", "").replace("\'", "")
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-width" {
+                width = if keyval {
+                    vec[1].to_string().parse::<f64>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<f64>().unwrap()
+                };
+            } else if flag_val == "-height" {
+                height = if keyval {
+                    vec[1].to_string().parse::<f64>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<f64>().unwrap()
+                };
+            } else if flag_val == "-buffer" {
+                buffer = if keyval {
+                    vec[1].to_string().parse::<f64>().unwrap()
+                } else {
+                    args[i + 1].to_string().parse::<f64>().unwrap()
+                };
+            } else if flag_val == "-threads" {
+                num_threads = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-keep_tiles" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    keep_tiles = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if width <= 0f64 || height <= 0f64 || buffer < 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The tile width and height must be greater than zero, and the buffer must not be negative.",
+            ));
+        }
+        if num_threads == 0 {
+            num_threads = 1;
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let mut inputs = vec![];
+        let mut cmd = input_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut input_file = value.trim().to_owned();
+                if !input_file.contains(&sep) && !input_file.contains("/") {
+                    input_file = format!("{}{}", working_directory, input_file);
+                }
+                if input_file.to_lowercase().ends_with(".laz") {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "LAZ files are not supported and must be decompressed ({}).",
+                            input_file
+                        ),
+                    ));
+                }
+                inputs.push(input_file);
+            }
+        }
+        if inputs.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input LiDAR file must be specified.",
+            ));
+        }
+
+        // find the LiDAR input and the raster or LiDAR output of the tool
+        let tile_dir = format!(
+            "{}wbt_lidar_tiles_{}{}",
+            working_directory,
+            std::process::id(),
+            sep
+        );
+        let tm = ToolManager::new(&tile_dir, &false)?;
+        let o: Value = serde_json::from_str(&tm.get_tool_parameters(&tool_name)?)?;
+        let tool_parameters: Vec<ToolParameter> = serde_json::from_value(o["parameters"].clone())?;
+        let mut input_flag = String::new();
+        let mut output_flag = String::new();
+        let mut raster_output = false;
+        for p in &tool_parameters {
+            let flag = p.flags[p.flags.len() - 1].clone();
+            match p.parameter_type {
+                ParameterType::ExistingFile(ParameterFileType::Lidar) if input_flag.is_empty() => {
+                    input_flag = flag
+                }
+                ParameterType::NewFile(ParameterFileType::Raster) if output_flag.is_empty() => {
+                    output_flag = flag;
+                    raster_output = true;
+                }
+                ParameterType::NewFile(ParameterFileType::Lidar) if output_flag.is_empty() => {
+                    output_flag = flag
+                }
+                _ => {}
+            }
+        }
+        if input_flag.is_empty() || output_flag.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} must have a LiDAR input and a raster or LiDAR output to be run on tiles.",
+                    tool_name
+                ),
+            ));
+        }
+        let tool_args: Vec<String> = tool_args
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+
+        let start = Instant::now();
+
+        fs::create_dir_all(&tile_dir)?;
+        let result = (|| {
+            // split each of the input files into buffered tiles; a tile's points are held in
+            // one part file for each of the input files that overlaps it
+            let mut tile_parts: BTreeMap<(isize, isize), Vec<String>> = BTreeMap::new();
+            let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+            let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+            let mut point_format = -1i32;
+            for (file_num, input_file) in inputs.iter().enumerate() {
+                if verbose {
+                    println!(
+                        "Tiling file {} of {} ({})...",
+                        file_num + 1,
+                        inputs.len(),
+                        input_file
+                    );
+                }
+                let input = LasFile::new(input_file, "r")?;
+                if point_format == -1 {
+                    point_format = input.header.point_format as i32;
+                } else if input.header.point_format as i32 != point_format {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        "All input files must be of the same LAS Point Format.",
+                    ));
+                }
+                let n_points = input.header.number_of_points as usize;
+                if n_points == 0 {
+                    continue;
+                }
+                min_x = min_x.min(input.header.min_x);
+                max_x = max_x.max(input.header.max_x);
+                min_y = min_y.min(input.header.min_y);
+                max_y = max_y.max(input.header.max_y);
+
+                let mut parts: HashMap<(isize, isize), (String, LasFile)> = HashMap::new();
+                for i in 0..n_points {
+                    let p: PointData = input[i];
+                    let col_start = ((p.x - buffer) / width).floor() as isize;
+                    let col_end = ((p.x + buffer) / width).floor() as isize;
+                    let row_start = ((p.y - buffer) / height).floor() as isize;
+                    let row_end = ((p.y + buffer) / height).floor() as isize;
+                    for row in row_start..=row_end {
+                        for col in col_start..=col_end {
+                            let (_, part) = parts.entry((row, col)).or_insert_with(|| {
+                                let file = format!(
+                                    "{}tile_r{}_c{}_part{}.las",
+                                    tile_dir, row, col, file_num
+                                );
+                                let mut las = LasFile::initialize_using_file(&file, &input);
+                                las.header.system_id = "EXTRACTION".to_string();
+                                (file, las)
+                            });
+                            part.add_point_record(input.get_record(i));
+                        }
+                    }
+                }
+                for (key, (file, mut part)) in parts {
+                    part.write()?;
+                    tile_parts.entry(key).or_insert(vec![]).push(file);
+                }
+            }
+            if tile_parts.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The input files do not contain any points.",
+                ));
+            }
+
+            // run the tool on each of the tiles
+            let tiles: Vec<((isize, isize), Vec<String>)> = tile_parts.into_iter().collect();
+            let num_tiles = tiles.len();
+            if verbose {
+                println!("Running {} on {} tiles...", tool_name, num_tiles);
+            }
+            let job = Arc::new(TileJob {
+                tool_name: tool_name.clone(),
+                tool_args: tool_args.clone(),
+                input_flag: input_flag.clone(),
+                output_flag: output_flag.clone(),
+                tile_dir: tile_dir.clone(),
+                out_ext: if raster_output { "tif" } else { "las" }.to_string(),
+            });
+            let tiles = Arc::new(tiles);
+            let next_tile = Arc::new(AtomicUsize::new(0));
+            let (tx, rx) = mpsc::channel();
+            for _ in 0..num_threads.min(num_tiles) {
+                let job = job.clone();
+                let tiles = tiles.clone();
+                let next_tile = next_tile.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let tm = match ToolManager::new(&job.tile_dir, &false) {
+                        Ok(tm) => tm,
+                        Err(e) => {
+                            let _ = tx.send(Err(e));
+                            return;
+                        }
+                    };
+                    loop {
+                        let index = next_tile.fetch_add(1, Ordering::SeqCst);
+                        if index >= tiles.len() {
+                            break;
+                        }
+                        let ((row, col), parts) = &tiles[index];
+                        let result = job.run(&tm, (*row, *col), parts);
+                        let failed = result.is_err();
+                        if tx.send(result.map(|f| (index, f))).is_err() || failed {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut tile_outputs = vec![String::new(); num_tiles];
+            for n in 0..num_tiles {
+                match rx.recv() {
+                    Ok(Ok((index, file))) => tile_outputs[index] = file,
+                    Ok(Err(e)) => {
+                        // stop the remaining workers from taking further tiles
+                        next_tile.store(num_tiles, Ordering::SeqCst);
+                        return Err(e);
+                    }
+                    Err(_) => {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            "The tile workers stopped unexpectedly.",
+                        ))
+                    }
+                }
+                if verbose {
+                    println!("Processed tile {} of {}", n + 1, num_tiles);
+                }
+            }
+
+            // remove the buffers and merge the tiled outputs
+            if verbose {
+                println!("Merging the tiled outputs...");
+            }
+            let keys: Vec<(isize, isize)> = tiles.iter().map(|(key, _)| *key).collect();
+            if raster_output {
+                merge_raster_tiles(
+                    &output_file,
+                    &keys,
+                    &tile_outputs,
+                    width,
+                    height,
+                    (min_x, max_x, min_y, max_y),
+                    verbose,
+                )
+            } else {
+                merge_lidar_tiles(&output_file, &keys, &tile_outputs, width, height, verbose)
+            }
+        })();
+
+        if !keep_tiles {
+            let _ = fs::remove_dir_all(&tile_dir);
+        }
+        result?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The tool run on each of the tiles.
+struct TileJob {
+    tool_name: String,
+    tool_args: Vec<String>,
+    input_flag: String,
+    output_flag: String,
+    tile_dir: String,
+    out_ext: String,
+}
+
+impl TileJob {
+    /// Joins the parts of a tile, runs the tool on the tile, and returns the name of the
+    /// tile's output file.
+    fn run(
+        &self,
+        tm: &ToolManager,
+        key: (isize, isize),
+        parts: &[String],
+    ) -> Result<String, Error> {
+        let (row, col) = key;
+        let input_file = if parts.len() == 1 {
+            parts[0].clone()
+        } else {
+            let file = format!("{}tile_r{}_c{}.las", self.tile_dir, row, col);
+            let mut output = LasFile::new(&file, "w")?;
+            for (i, part) in parts.iter().enumerate() {
+                let input = LasFile::new(part, "r")?;
+                if i == 0 {
+                    output = LasFile::initialize_using_file(&file, &input);
+                }
+                for j in 0..input.header.number_of_points as usize {
+                    output.add_point_record(input.get_record(j));
+                }
+            }
+            output.write()?;
+            file
+        };
+        let output_file = format!(
+            "{}tile_r{}_c{}_out.{}",
+            self.tile_dir, row, col, self.out_ext
+        );
+        let mut args = self.tool_args.clone();
+        args.push(format!("{}={}", self.input_flag, input_file));
+        args.push(format!("{}={}", self.output_flag, output_file));
+        match tm.run_tool(self.tool_name.clone(), args) {
+            Ok(()) => Ok(output_file),
+            Err(e) => Err(Error::new(
+                e.kind(),
+                format!(
+                    "{} failed on the tile at row {}, column {}: {}",
+                    self.tool_name, row, col, e
+                ),
+            )),
+        }
+    }
+}
+
+/// Merges the points of the LiDAR tile outputs that lie within their unbuffered tiles.
+fn merge_lidar_tiles(
+    output_file: &str,
+    keys: &[(isize, isize)],
+    tile_outputs: &[String],
+    width: f64,
+    height: f64,
+    verbose: bool,
+) -> Result<(), Error> {
+    let mut output = LasFile::new(output_file, "w")?;
+    for (i, ((row, col), file)) in keys.iter().zip(tile_outputs).enumerate() {
+        let input = LasFile::new(file, "r")?;
+        if i == 0 {
+            output = LasFile::initialize_using_file(output_file, &input);
+        }
+        for j in 0..input.header.number_of_points as usize {
+            let p: PointData = input[j];
+            if (p.x / width).floor() as isize == *col && (p.y / height).floor() as isize == *row {
+                output.add_point_record(input.get_record(j));
+            }
+        }
+        if verbose {
+            println!("Merged tile {} of {}", i + 1, keys.len());
+        }
+    }
+    if verbose {
+        println!("Writing output LAS file...");
+    }
+    output.write()
+}
+
+/// Merges the raster tile outputs onto a single grid covering the extent of the input points,
+/// taking the value of each cell from the tile output whose unbuffered tile contains the cell.
+fn merge_raster_tiles(
+    output_file: &str,
+    keys: &[(isize, isize)],
+    tile_outputs: &[String],
+    width: f64,
+    height: f64,
+    extent: (f64, f64, f64, f64),
+    verbose: bool,
+) -> Result<(), Error> {
+    let (min_x, max_x, min_y, max_y) = extent;
+    let mut output: Option<Raster> = None;
+    for (i, ((row, col), file)) in keys.iter().zip(tile_outputs).enumerate() {
+        let input = Raster::new(file, "r")?;
+        if output.is_none() {
+            let res_x = input.configs.resolution_x;
+            let res_y = input.configs.resolution_y;
+            let west = (min_x / res_x).floor() * res_x;
+            let north = (max_y / res_y).ceil() * res_y;
+            let columns = (((max_x - west) / res_x).ceil() as usize).max(1);
+            let rows = (((north - min_y) / res_y).ceil() as usize).max(1);
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows;
+            configs.columns = columns;
+            configs.north = north;
+            configs.south = north - rows as f64 * res_y;
+            configs.east = west + columns as f64 * res_x;
+            configs.west = west;
+            configs.resolution_x = res_x;
+            configs.resolution_y = res_y;
+            configs.nodata = input.configs.nodata;
+            configs.data_type = input.configs.data_type;
+            configs.photometric_interp = input.configs.photometric_interp;
+            configs.palette = input.configs.palette.clone();
+            configs.projection = input.configs.projection.clone();
+            configs.xy_units = input.configs.xy_units.clone();
+            configs.z_units = input.configs.z_units.clone();
+            configs.epsg_code = input.configs.epsg_code;
+            configs.coordinate_ref_system_wkt = input.configs.coordinate_ref_system_wkt.clone();
+            output = Some(Raster::initialize_using_config(output_file, &configs));
+        }
+        let out = output.as_mut().unwrap();
+        let out_nodata = out.configs.nodata;
+        let in_nodata = input.configs.nodata;
+
+        // the cells of the output whose centres lie within the unbuffered tile
+        let tile_west = *col as f64 * width;
+        let tile_south = *row as f64 * height;
+        let col_start = out.get_column_from_x(tile_west).max(0);
+        let col_end = out
+            .get_column_from_x(tile_west + width)
+            .min(out.configs.columns as isize - 1);
+        let row_start = out.get_row_from_y(tile_south + height).max(0);
+        let row_end = out
+            .get_row_from_y(tile_south)
+            .min(out.configs.rows as isize - 1);
+        for r in row_start..=row_end {
+            let y = out.get_y_from_row(r);
+            if y < tile_south || y >= tile_south + height {
+                continue;
+            }
+            let row_in = input.get_row_from_y(y);
+            for c in col_start..=col_end {
+                let x = out.get_x_from_column(c);
+                if x < tile_west || x >= tile_west + width {
+                    continue;
+                }
+                let z = input.get_value(row_in, input.get_column_from_x(x));
+                if z != in_nodata {
+                    out.set_value(r, c, z);
+                } else {
+                    out.set_value(r, c, out_nodata);
+                }
+            }
+        }
+        if verbose {
+            println!("Merged tile {} of {}", i + 1, keys.len());
+        }
+    }
+
+    let mut output = output.unwrap();
+    output.add_metadata_entry("Created by whitebox_tools\' LidarTiledWorkflow tool".to_string());
+    if verbose {
+        println!("Saving data...")
+    };
+    output.write()
+}
@@ -0,0 +1,498 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::triangulate;
+use crate::lidar::*;
+use crate::rendering::Mesh;
+use crate::spatial_ref_system;
+use crate::structures::Point2D;
+use crate::tools::*;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool builds a triangulated surface mesh from the points of selected classes in a LiDAR
+/// point cloud (`--input`), by default the ground (class 2) and building (class 6) points, and
+/// exports it for 3D visualization. The format of the output file (`--output`) is determined by
+/// its extension:
+///
+/// | Extension | Format |
+/// | :--- | :--- |
+/// | .obj | Wavefront OBJ, with vertex colours written as 'v x y z r g b' records |
+/// | .ply | Binary Stanford PLY, with double-precision coordinates and vertex colours |
+/// | .stl | Binary STL, positioned at the origin; colours are not stored |
+/// | .gltf | glTF 2.0, with an embedded buffer and positions relative to the centre of the mesh |
+/// | .glb | Binary glTF 2.0, with positions relative to the centre of the mesh |
+/// | .json | Cesium 3D Tiles tileset, with a single Batched 3D Model (.b3dm) tile of the same name |
+///
+/// The OBJ and PLY outputs retain the coordinates of the input point cloud. For 3D Tiles
+/// outputs, the point cloud's coordinate reference system is used to place the tileset on the
+/// globe; if the input file does not have a recognized coordinate reference system, the tileset
+/// is written in local coordinates.
+///
+/// The mesh is a greedy triangulation of the selected points, i.e. a 2.5D Delaunay triangulation
+/// from which the triangles with any horizontal edge longer than `--max_edge` are removed. This
+/// removes the long, thin triangles that would otherwise bridge gaps in the point cloud, such as
+/// the areas of water, vegetation, or other excluded classes. Because the triangulation is
+/// two-and-a-half dimensional, building walls are represented by the steep triangles between the
+/// roof and ground points, rather than by true vertical surfaces. Dense point clouds may be thinned
+/// prior to triangulation by specifying a grid `--resolution`, in which case only the highest
+/// selected point within each grid cell is retained.
+///
+/// The mesh vertices are coloured (`--colour`) by the point RGB values ('rgb'), by class
+/// ('class'), or by elevation ('elevation'), or are left uncoloured ('none'). The default, 'auto',
+/// uses the RGB values if the point cloud contains them and the class colours otherwise. Noise
+/// and withheld points are always excluded.
+///
+/// # See Also
+/// `RasterToMesh`, `LidarConstructVectorTIN`, `LidarTINGridding`, `LidarGroundPointFilter`, `ClassifyBuildingsInLidar`
+pub struct LidarToMesh {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarToMesh {
+    pub fn new() -> LidarToMesh {
+        // public constructor
+        let name = "LidarToMesh".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Builds a triangulated surface mesh from classified LiDAR points and exports it as OBJ, PLY, STL, glTF, or 3D Tiles.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output mesh file (*.obj, *.ply, *.stl, *.gltf, *.glb, or a 3D Tiles *.json tileset)."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Mesh Classes".to_owned(),
+            flags: vec!["--classes".to_owned()],
+            description: "Classes of the points included in the mesh, e.g. '2,6'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("2,6".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Triangle Edge Length (optional)".to_owned(),
+            flags: vec!["--max_edge".to_owned()],
+            description: "Optional maximum horizontal triangle edge length.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Thinning Resolution (optional)".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Optional grid resolution used to thin the points before triangulation."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Vertex Colours".to_owned(),
+            flags: vec!["--colour".to_owned()],
+            description:
+                "Vertex colouring; options are 'auto', 'rgb', 'class', 'elevation', and 'none'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "auto".to_owned(),
+                "rgb".to_owned(),
+                "class".to_owned(),
+                "elevation".to_owned(),
+                "none".to_owned(),
+            ]),
+            default_value: Some("auto".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=classified.las -o=surface.ply --classes='2,6' --max_edge=5.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=classified.las -o=tileset.json --max_edge=5.0 --resolution=1.0 --colour=class", short_exe, name).replace("*", &sep);
+
+        LidarToMesh {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarToMesh {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut classes_str = String::from("2,6");
+        let mut max_edge = f64::INFINITY;
+        let mut resolution = 0f64;
+        let mut colour_mode = String::from("auto");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-classes" {
+                classes_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-colour" || flag_val == "-color" {
+                colour_mode = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-max_edge" {
+                max_edge = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                if max_edge <= 0f64 {
+                    max_edge = f64::INFINITY;
+                }
+            } else if flag_val == "-resolution" {
+                resolution = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let mut include_class_vals = vec![false; 256];
+        let mut cmd = classes_str.split(",");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = classes_str.split(";");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        for value in vec {
+            if !value.trim().is_empty() {
+                let c = value
+                    .trim()
+                    .parse::<usize>()
+                    .expect("Error parsing --classes");
+                if c < 256 {
+                    include_class_vals[c] = true;
+                }
+            }
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let mut input = LasFile::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (n_points as f64 - 1f64).max(1f64); // used for progress calculation only
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        // select the points, keeping the highest point within each grid cell, or at each
+        // location if the points are not thinned
+        let extent = input.get_extent();
+        let mut selected: HashMap<(i64, i64), usize> = HashMap::new();
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            if include_class_vals[p.classification() as usize]
+                && !p.withheld()
+                && !p.is_classified_noise()
+            {
+                let key = if resolution > 0f64 {
+                    (
+                        ((p.x - extent.min_x) / resolution).floor() as i64,
+                        ((p.y - extent.min_y) / resolution).floor() as i64,
+                    )
+                } else {
+                    (p.x.to_bits() as i64, p.y.to_bits() as i64)
+                };
+                let entry = selected.entry(key).or_insert(i);
+                if input.get_point_info(*entry).z < p.z {
+                    *entry = i;
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Selecting points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        let mut point_nums: Vec<usize> = selected.into_iter().map(|(_, i)| i).collect();
+        point_nums.sort();
+        if point_nums.len() < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Fewer than three points of the selected classes were found in the input file.",
+            ));
+        }
+
+        if verbose {
+            println!("Performing triangulation...");
+        }
+        let points: Vec<Point2D> = point_nums
+            .iter()
+            .map(|&i| {
+                let p = input.get_point_info(i);
+                Point2D::new(p.x, p.y)
+            })
+            .collect();
+        let result = match triangulate(&points) {
+            Some(t) => t,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "No triangulation exists for the selected points.",
+                ))
+            }
+        };
+
+        let mut mesh = Mesh::new();
+        for &i in &point_nums {
+            let p = input.get_point_info(i);
+            mesh.add_vertex(p.x, p.y, p.z);
+        }
+        let max_edge_sqrd = max_edge * max_edge;
+        for t in (0..result.triangles.len()).step_by(3) {
+            let (a, b, c) = (
+                result.triangles[t],
+                result.triangles[t + 1],
+                result.triangles[t + 2],
+            );
+            if points[a].distance_squared(&points[b]) <= max_edge_sqrd
+                && points[b].distance_squared(&points[c]) <= max_edge_sqrd
+                && points[c].distance_squared(&points[a]) <= max_edge_sqrd
+            {
+                mesh.add_triangle(a as u32, b as u32, c as u32);
+            }
+        }
+
+        // vertex colours
+        if colour_mode == "auto" {
+            colour_mode = if input.has_rgb() {
+                "rgb".to_string()
+            } else {
+                "class".to_string()
+            };
+        }
+        if colour_mode.contains("rgb") {
+            if !input.has_rgb() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input file does not contain RGB colour data.",
+                ));
+            }
+            // colours may be stored as either 8-bit or 16-bit values
+            let mut max_value = 0u16;
+            for &i in &point_nums {
+                let c = input.get_rgb(i)?;
+                max_value = max_value.max(c.red).max(c.green).max(c.blue);
+            }
+            let shift = if max_value > 255 { 8 } else { 0 };
+            for &i in &point_nums {
+                let c = input.get_rgb(i)?;
+                mesh.colours.push([
+                    (c.red >> shift) as u8,
+                    (c.green >> shift) as u8,
+                    (c.blue >> shift) as u8,
+                ]);
+            }
+        } else if colour_mode.contains("class") {
+            for &i in &point_nums {
+                mesh.colours
+                    .push(class_colour(input.get_point_info(i).classification()));
+            }
+        } else if colour_mode.contains("elev") {
+            let (min, max) = mesh.bounds();
+            let range = (max[2] - min[2]).max(f64::EPSILON);
+            for v in mesh.vertices.clone() {
+                mesh.colours.push(elevation_colour((v[2] - min[2]) / range));
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let crs = spatial_ref_system::CoordinateReferenceSystem::from_wkt(&input.get_wkt()).ok();
+        if verbose {
+            if crs.is_none() && output_file.to_lowercase().ends_with(".json") {
+                println!("Warning: The input file's coordinate reference system is unknown; the tileset will be written in local coordinates.");
+            }
+            println!("Saving data...")
+        };
+        let _ = match mesh.write(&output_file, crs.as_ref()) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!("Number of vertices: {}", mesh.vertices.len());
+            println!("Number of triangles: {}", mesh.triangles.len());
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn class_colour(class: u8) -> [u8; 3] {
+    match class {
+        2 => [166, 134, 98],   // ground
+        3 => [180, 210, 120],  // low vegetation
+        4 => [110, 170, 80],   // medium vegetation
+        5 => [50, 120, 50],    // high vegetation
+        6 => [205, 92, 70],    // building
+        9 => [70, 130, 200],   // water
+        17 => [150, 150, 160], // bridge deck
+        _ => [190, 190, 190],
+    }
+}
+
+/// Returns a colour along a green-yellow-brown-white ramp for a relative elevation (0-1).
+fn elevation_colour(t: f64) -> [u8; 3] {
+    let ramp = [
+        [56f64, 128f64, 64f64],
+        [222f64, 214f64, 120f64],
+        [150f64, 100f64, 60f64],
+        [250f64, 250f64, 250f64],
+    ];
+    let t = t.max(0f64).min(1f64) * (ramp.len() - 1) as f64;
+    let k = (t.floor() as usize).min(ramp.len() - 2);
+    let w = t - k as f64;
+    [
+        (ramp[k][0] + w * (ramp[k + 1][0] - ramp[k][0])) as u8,
+        (ramp[k][1] + w * (ramp[k + 1][1] - ramp[k][1])) as u8,
+        (ramp[k][2] + w * (ramp[k + 1][2] - ramp[k][2])) as u8,
+    ]
+}
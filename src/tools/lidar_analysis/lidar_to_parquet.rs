@@ -0,0 +1,382 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::lidar::*;
+use crate::spatial_ref_system;
+use crate::tools::*;
+use crate::vector::{geoparquet_metadata, point_to_wkb, ParquetColumn, ParquetTable};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool exports the points of a LiDAR file (`--input`), or a subset of them, to an Apache
+/// Parquet file (`--output`), a compressed columnar format that can be read directly by pyarrow,
+/// pandas (`pandas.read_parquet`), polars, DuckDB, and most other data analytics tools. Each point
+/// becomes a row of the table, with the columns x, y, z, intensity, return_number,
+/// number_of_returns, classification, scan_angle, user_data, and point_source_id, as well as
+/// gps_time and red, green, and blue, if the point format includes them.
+///
+/// The exported points may be restricted to those of selected `--classes` (e.g. '2,6'; by default
+/// all classes are exported) and to the first or last returns (`--returns`). Withheld points are
+/// never exported. If the `--geometry` flag is specified, the table also includes a 'geometry'
+/// column containing each point as a WKB Point Z, and the file is written as GeoParquet, along
+/// with the coordinate reference system of the input file, such that it may be read by GeoPandas
+/// (`geopandas.read_parquet`) and other GIS software.
+///
+/// # See Also
+/// `ExportTableToParquet`, `LasToShapefile`, `LidarPointStats`
+pub struct LidarToParquet {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarToParquet {
+    pub fn new() -> LidarToParquet {
+        // public constructor
+        let name = "LidarToParquet".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description =
+            "Exports the points of a LiDAR file, or a subset of them, to a Parquet or GeoParquet table."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output Parquet file (*.parquet).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Exported Classes (optional)".to_owned(),
+            flags: vec!["--classes".to_owned()],
+            description: "Optional classes of the exported points, e.g. '2,6'; by default, all classes are exported.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Point Returns Included".to_owned(),
+            flags: vec!["--returns".to_owned()],
+            description:
+                "Point return types to include; options are 'all' (default), 'last', 'first'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "all".to_owned(),
+                "last".to_owned(),
+                "first".to_owned(),
+            ]),
+            default_value: Some("all".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Include point geometries (GeoParquet)?".to_owned(),
+            flags: vec!["--geometry".to_owned()],
+            description: "Include a WKB point geometry column and write the file as GeoParquet."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=points.parquet
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.las -o=ground.parquet --classes='2' --returns=last --geometry", short_exe, name).replace("*", &sep);
+
+        LidarToParquet {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarToParquet {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut classes_str = String::new();
+        let mut return_type = "all".to_string();
+        let mut include_geometry = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-classes" {
+                classes_str = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-returns" {
+                return_type = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-geometry" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    include_geometry = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        // an empty class list includes all classes
+        let mut include_class_vals = vec![classes_str.trim().is_empty(); 256];
+        let mut cmd = classes_str.split(",");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = classes_str.split(";");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        for value in vec {
+            if !value.trim().is_empty() {
+                let c = value
+                    .trim()
+                    .parse::<usize>()
+                    .expect("Error parsing --classes");
+                if c < 256 {
+                    include_class_vals[c] = true;
+                }
+            }
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let mut input = LasFile::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (n_points as f64 - 1f64).max(1f64); // used for progress calculation only
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        let mut selected = vec![];
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            if include_class_vals[p.classification() as usize]
+                && !p.withheld()
+                && (!return_type.contains("last") || p.is_late_return())
+                && (!return_type.contains("first") || p.is_early_return())
+            {
+                selected.push(i);
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Selecting points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        if selected.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the input points meet the selection criteria.",
+            ));
+        }
+
+        if verbose {
+            println!("Building table...");
+        }
+        let points: Vec<PointData> = selected.iter().map(|&i| input.get_point_info(i)).collect();
+        let mut table = ParquetTable::new();
+        let double = |f: &dyn Fn(&PointData) -> f64| -> ParquetColumn {
+            ParquetColumn::Double(points.iter().map(|p| Some(f(p))).collect())
+        };
+        let int = |f: &dyn Fn(&PointData) -> i32| -> ParquetColumn {
+            ParquetColumn::Int32(points.iter().map(|p| Some(f(p))).collect())
+        };
+        table.add_column("x", double(&|p| p.x))?;
+        table.add_column("y", double(&|p| p.y))?;
+        table.add_column("z", double(&|p| p.z))?;
+        table.add_column("intensity", int(&|p| p.intensity as i32))?;
+        table.add_column("return_number", int(&|p| p.return_number() as i32))?;
+        table.add_column("number_of_returns", int(&|p| p.number_of_returns() as i32))?;
+        table.add_column("classification", int(&|p| p.classification() as i32))?;
+        table.add_column("scan_angle", int(&|p| p.scan_angle as i32))?;
+        table.add_column("user_data", int(&|p| p.user_data as i32))?;
+        table.add_column("point_source_id", int(&|p| p.point_source_id as i32))?;
+        if input.has_gps_time() {
+            table.add_column(
+                "gps_time",
+                ParquetColumn::Double(
+                    selected
+                        .iter()
+                        .map(|&i| input.get_gps_time(i).ok())
+                        .collect(),
+                ),
+            )?;
+        }
+        if input.has_rgb() {
+            let colours: Vec<Option<ColourData>> =
+                selected.iter().map(|&i| input.get_rgb(i).ok()).collect();
+            table.add_column(
+                "red",
+                ParquetColumn::Int32(colours.iter().map(|c| c.map(|c| c.red as i32)).collect()),
+            )?;
+            table.add_column(
+                "green",
+                ParquetColumn::Int32(colours.iter().map(|c| c.map(|c| c.green as i32)).collect()),
+            )?;
+            table.add_column(
+                "blue",
+                ParquetColumn::Int32(colours.iter().map(|c| c.map(|c| c.blue as i32)).collect()),
+            )?;
+        }
+
+        if include_geometry {
+            table.add_column(
+                "geometry",
+                ParquetColumn::Binary(
+                    points
+                        .iter()
+                        .map(|p| Some(point_to_wkb(p.x, p.y, Some(p.z))))
+                        .collect(),
+                ),
+            )?;
+            let crs =
+                spatial_ref_system::CoordinateReferenceSystem::from_wkt(&input.get_wkt()).ok();
+            let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+            let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+            for p in &points {
+                min_x = min_x.min(p.x);
+                min_y = min_y.min(p.y);
+                max_x = max_x.max(p.x);
+                max_y = max_y.max(p.y);
+            }
+            table.add_metadata(
+                "geo",
+                &geoparquet_metadata(
+                    "geometry",
+                    &["Point Z".to_string()],
+                    [min_x, min_y, max_x, max_y],
+                    crs.as_ref(),
+                ),
+            );
+        }
+
+        if verbose {
+            println!("Saving data...");
+        }
+        table.write(&output_file)?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Exported {} of {} points", points.len(), n_points);
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
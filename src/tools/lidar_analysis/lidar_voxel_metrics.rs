@@ -0,0 +1,593 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::lidar::*;
+use crate::raster::*;
+use crate::structures::{DistanceMetric, FixedRadiusSearch2D};
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool voxelizes a LiDAR point cloud (`--input`) and outputs a three-dimensional grid of
+/// vertical structure metrics as a multi-band raster (`--output`), with one band for each height
+/// bin. The bands are ordered from the bottom up, such that band one contains the values of the
+/// layer of voxels from `--min_height` to `--min_height` + `--vertical_resolution`, and the
+/// sequence of band values at a grid cell is the vertical profile of the metric for the column of
+/// voxels above the cell. Because of this, the output must be a GeoTIFF (.tif) file whenever it
+/// contains more than one band. The horizontal and vertical sizes of the voxels are set by the
+/// `--resolution` and `--vertical_resolution` parameters.
+///
+/// The following voxel metrics (`--metric`) are available:
+///
+/// | Metric | Description |
+/// | :--- | :--- |
+/// | count | The number of points in the voxel. |
+/// | occupancy | One if the voxel contains at least one point, zero otherwise. |
+/// | density | The proportion of the column's points contained in the voxel, i.e. the relative vertical point density. |
+/// | pad | The plant area density (m<sup>2</sup>/m<sup>3</sup>) estimated using the MacArthur-Horn method. |
+///
+/// The plant area density (PAD) of a voxel is estimated from the gap fractions, P, at the top and
+/// base of the voxel, measured as the proportion of the column's points that lie below each
+/// height, as PAD = ln(P<sub>top</sub> / P<sub>base</sub>) / (k &Delta;z), where k is the
+/// extinction coefficient (`--extinction`) and &Delta;z is the vertical resolution. Points below
+/// `--min_height` count towards the gap fractions but are not assigned to a voxel; setting
+/// `--min_height` to about 1.0 m therefore excludes the ground returns from the profiles while
+/// still using them as the transmitted pulses. PAD values are undefined, and are output as NoData,
+/// for voxels beneath which no points are recorded.
+///
+/// Heights are measured from the point elevations by default, which is appropriate for point
+/// clouds that have been height-normalized, e.g. using the `HeightAboveGround` tool. Alternatively,
+/// the `--normalize` flag measures the height of each point above the nearest ground point
+/// (class 2). The top of the grid is set by `--max_height`, or by the highest point if this
+/// parameter is unspecified. Points of excluded classes (`--exclude_cls`), as well as withheld and
+/// noise points, are ignored. Grid cells that do not contain any points are assigned NoData in
+/// all bands.
+///
+/// An optional CSV file (`--out_profile`) can also be output, containing the average vertical
+/// profile of the metric over all of the grid cells that contain points, along with the total
+/// number of points in each height bin.
+///
+/// # See Also
+/// `LidarPointStats`, `LidarPointDensity`, `HeightAboveGround`
+pub struct LidarVoxelMetrics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LidarVoxelMetrics {
+    pub fn new() -> LidarVoxelMetrics {
+        // public constructor
+        let name = "LidarVoxelMetrics".to_string();
+        let toolbox = "LiDAR Tools".to_string();
+        let description = "Calculates voxel-based occupancy, density, and plant area density profiles from a LiDAR point cloud as a multi-band raster.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input LiDAR file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Lidar),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file, with one band per height bin.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Voxel Metric".to_owned(),
+            flags: vec!["--metric".to_owned()],
+            description: "Voxel metric; options are 'count', 'occupancy', 'density', and 'pad' (plant area density).".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "count".to_owned(),
+                "occupancy".to_owned(),
+                "density".to_owned(),
+                "pad".to_owned(),
+            ]),
+            default_value: Some("density".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Horizontal Resolution".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Horizontal size of the voxels and output grid cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Vertical Resolution".to_owned(),
+            flags: vec!["--vertical_resolution".to_owned()],
+            description: "Vertical size of the voxels, i.e. the height of each bin.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Height".to_owned(),
+            flags: vec!["--min_height".to_owned()],
+            description: "Height of the base of the lowest bin.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Height (optional)".to_owned(),
+            flags: vec!["--max_height".to_owned()],
+            description: "Optional height of the top of the highest bin; the highest point is used by default.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Measure heights above the nearest ground point?".to_owned(),
+            flags: vec!["--normalize".to_owned()],
+            description: "Measure heights above the nearest ground point (class 2) rather than using elevations.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Extinction Coefficient".to_owned(),
+            flags: vec!["--extinction".to_owned()],
+            description: "Extinction coefficient used to estimate plant area density.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter{
+            name: "Exclusion Classes (0-18, based on LAS spec; e.g. 3,4,5,6,7)".to_owned(),
+            flags: vec!["--exclude_cls".to_owned()],
+            description: "Optional exclude classes from the voxel grid; Valid class values range from 0 to 18, based on LAS specifications. Example, --exclude_cls='6,7,18'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Profile File (optional)".to_owned(),
+            flags: vec!["--out_profile".to_owned()],
+            description: "Optional output CSV file of the average vertical profile.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=normalized.las -o=density_profile.tif --metric=density --resolution=10.0 --vertical_resolution=2.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las -o=pad.tif --metric=pad --normalize --min_height=1.0 --max_height=40.0 --exclude_cls='6,7,18' --out_profile=pad_profile.csv", short_exe, name).replace("*", &sep);
+
+        LidarVoxelMetrics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LidarVoxelMetrics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut profile_file = String::new();
+        let mut metric = VoxelMetric::Density;
+        let mut resolution = 5f64;
+        let mut vertical_resolution = 1f64;
+        let mut min_height = 0f64;
+        let mut max_height = f64::NAN;
+        let mut normalize = false;
+        let mut extinction = 0.5f64;
+        let mut include_class_vals = vec![true; 256];
+        let mut exclude_cls_str = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_profile" {
+                profile_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-metric" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                metric = match VoxelMetric::from_str(&val) {
+                    Some(m) => m,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unrecognized voxel metric '{}'.", val),
+                        ))
+                    }
+                };
+            } else if flag_val == "-normalize" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    normalize = true;
+                }
+            } else if flag_val == "-resolution"
+                || flag_val == "-vertical_resolution"
+                || flag_val == "-min_height"
+                || flag_val == "-max_height"
+                || flag_val == "-extinction"
+            {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                }
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+                match flag_val.as_str() {
+                    "-resolution" => resolution = val,
+                    "-vertical_resolution" => vertical_resolution = val,
+                    "-min_height" => min_height = val,
+                    "-max_height" => max_height = val,
+                    _ => extinction = val,
+                }
+            } else if flag_val == "-exclude_cls" {
+                if keyval {
+                    exclude_cls_str = vec[1].to_string();
+                } else {
+                    exclude_cls_str = args[i + 1].to_string();
+                }
+                let mut cmd = exclude_cls_str.split(",");
+                let mut vec = cmd.collect::<Vec<&str>>();
+                if vec.len() == 1 {
+                    cmd = exclude_cls_str.split(";");
+                    vec = cmd.collect::<Vec<&str>>();
+                }
+                for value in vec {
+                    if !value.trim().is_empty() {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
+                    }
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        if resolution <= 0f64 || vertical_resolution <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --resolution and --vertical_resolution parameters must be greater than zero.",
+            ));
+        }
+        if metric == VoxelMetric::PlantAreaDensity && extinction <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --extinction parameter must be greater than zero.",
+            ));
+        }
+
+        let sep = path::MAIN_SEPARATOR;
+        if !input_file.contains(sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !profile_file.is_empty() && !profile_file.contains(sep) && !profile_file.contains("/") {
+            profile_file = format!("{}{}", working_directory, profile_file);
+        }
+
+        if verbose {
+            println!("Reading input LAS file...");
+        }
+        let input = LasFile::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let n_points = input.header.number_of_points as usize;
+        let num_points: f64 = (n_points as f64 - 1f64).max(1f64); // used for progress calculation only
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        // measure the height of each included point
+        let ground = if normalize {
+            let mut frs: FixedRadiusSearch2D<f64> =
+                FixedRadiusSearch2D::new(resolution, DistanceMetric::SquaredEuclidean);
+            let mut num_ground = 0usize;
+            for i in 0..n_points {
+                let p = input.get_point_info(i);
+                if p.classification() == 2 && !p.withheld() {
+                    frs.insert(p.x, p.y, p.z);
+                    num_ground += 1;
+                }
+            }
+            if num_ground == 0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --normalize flag requires classified ground points (class 2), but the input file does not contain any.",
+                ));
+            }
+            Some(frs)
+        } else {
+            None
+        };
+        let mut heights = vec![f64::NAN; n_points];
+        let mut highest = f64::NEG_INFINITY;
+        for i in 0..n_points {
+            let p = input.get_point_info(i);
+            if include_class_vals[p.classification() as usize]
+                && !p.withheld()
+                && !p.is_classified_noise()
+            {
+                heights[i] = match ground {
+                    Some(ref frs) => {
+                        let ret = frs.knn_search(p.x, p.y, 1);
+                        if ret.is_empty() {
+                            f64::NAN
+                        } else {
+                            p.z - ret[0].0
+                        }
+                    }
+                    None => p.z,
+                };
+                if heights[i] > highest {
+                    highest = heights[i];
+                }
+            }
+        }
+        drop(ground);
+        if max_height.is_nan() {
+            max_height = highest;
+        }
+        if max_height.is_nan() || max_height <= min_height {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum height must be greater than the minimum height.",
+            ));
+        }
+
+        let mut grid = VoxelGrid::new(
+            input.get_extent(),
+            min_height,
+            max_height,
+            resolution,
+            vertical_resolution,
+        );
+        for i in 0..n_points {
+            if !heights[i].is_nan() {
+                let p = input.get_point_info(i);
+                grid.insert(p.x, p.y, heights[i]);
+            }
+            if verbose {
+                progress = (100.0_f64 * i as f64 / num_points) as i32;
+                if progress != old_progress {
+                    println!("Voxelizing points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let nodata = -32768f64;
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        configs.rows = grid.rows;
+        configs.columns = grid.columns;
+        configs.north = grid.north;
+        configs.south = grid.north - grid.rows as f64 * resolution;
+        configs.east = grid.west + grid.columns as f64 * resolution;
+        configs.west = grid.west;
+        configs.resolution_x = resolution;
+        configs.resolution_y = resolution;
+        configs.nodata = nodata;
+        configs.data_type = DataType::F32;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+        configs.palette = "spectrum.pal".to_string();
+
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        if grid.layers > 1 && !output.raster_type.supports_multiple_bands() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Multi-band outputs must be GeoTIFF or Whitebox v2 files; use a .tif or .wbr output file.",
+            ));
+        }
+        output.set_num_bands(grid.layers);
+
+        let mut profile_sums = vec![0f64; grid.layers];
+        let mut profile_counts = vec![0usize; grid.layers];
+        let mut layer_points = vec![0usize; grid.layers];
+        for row in 0..grid.rows {
+            for col in 0..grid.columns {
+                if let Some(profile) = grid.profile(row, col, metric, extinction) {
+                    for (layer, &value) in profile.iter().enumerate() {
+                        layer_points[layer] += grid.count(row, col, layer) as usize;
+                        if !value.is_nan() {
+                            output.set_value_band(row as isize, col as isize, layer, value);
+                            profile_sums[layer] += value;
+                            profile_counts[layer] += 1;
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (grid.rows - 1).max(1) as f64) as i32;
+                if progress != old_progress {
+                    println!("Calculating profiles: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Metric: {:?}", metric));
+        output.add_metadata_entry(format!("Vertical resolution: {}", vertical_resolution));
+        output.add_metadata_entry(format!(
+            "Band 1 base height: {}; number of bands: {}",
+            min_height, grid.layers
+        ));
+        if !exclude_cls_str.is_empty() {
+            output.add_metadata_entry(format!("Excluded classes: {}", exclude_cls_str));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !profile_file.is_empty() {
+            let f = File::create(&profile_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("BAND,BASE_HEIGHT,TOP_HEIGHT,NUM_POINTS,MEAN_VALUE\n".as_bytes())?;
+            for layer in 0..grid.layers {
+                let mean = if profile_counts[layer] > 0 {
+                    format!("{}", profile_sums[layer] / profile_counts[layer] as f64)
+                } else {
+                    String::new()
+                };
+                writer.write_all(
+                    format!(
+                        "{},{},{},{},{}\n",
+                        layer + 1,
+                        grid.layer_base(layer),
+                        grid.layer_base(layer + 1),
+                        layer_points[layer],
+                        mean
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            let _ = writer.flush();
+            if verbose {
+                println!("Profile written to {}", profile_file);
+            }
+        }
+
+        if verbose {
+            println!("Number of height bins: {}", grid.layers);
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
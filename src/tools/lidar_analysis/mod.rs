@@ -14,6 +14,7 @@ mod height_above_ground;
 mod las_to_ascii;
 mod las_to_multipoint_shapefile;
 mod las_to_shapefile;
+mod lidar_classify_noise;
 mod lidar_classify_subset;
 mod lidar_colourize;
 mod lidar_construct_vector_tin;
@@ -27,9 +28,11 @@ mod lidar_info;
 mod lidar_join;
 mod lidar_kappa;
 mod lidar_nn_gridding;
+mod lidar_normalize_intensity;
 mod lidar_outliers;
 mod lidar_point_density;
 mod lidar_point_stats;
+mod lidar_powerline_detection;
 mod lidar_radial_basis_function_interpolation;
 mod lidar_ransac_planes;
 mod lidar_segmentation;
@@ -38,8 +41,12 @@ mod lidar_thin;
 mod lidar_thin_high_density;
 mod lidar_tile;
 mod lidar_tile_footprint;
+mod lidar_tiled_workflow;
 mod lidar_tin_gridding;
+mod lidar_to_mesh;
+mod lidar_to_parquet;
 mod lidar_tophat_transform;
+mod lidar_voxel_metrics;
 mod normal_vectors;
 mod remove_duplicates;
 mod select_tiles_by_polygon;
@@ -60,6 +67,7 @@ pub use self::height_above_ground::HeightAboveGround;
 pub use self::las_to_ascii::LasToAscii;
 pub use self::las_to_multipoint_shapefile::LasToMultipointShapefile;
 pub use self::las_to_shapefile::LasToShapefile;
+pub use self::lidar_classify_noise::LidarClassifyNoise;
 pub use self::lidar_classify_subset::LidarClassifySubset;
 pub use self::lidar_colourize::LidarColourize;
 pub use self::lidar_construct_vector_tin::LidarConstructVectorTIN;
@@ -73,9 +81,11 @@ pub use self::lidar_info::LidarInfo;
 pub use self::lidar_join::LidarJoin;
 pub use self::lidar_kappa::LidarKappaIndex;
 pub use self::lidar_nn_gridding::LidarNearestNeighbourGridding;
+pub use self::lidar_normalize_intensity::LidarNormalizeIntensity;
 pub use self::lidar_outliers::LidarRemoveOutliers;
 pub use self::lidar_point_density::LidarPointDensity;
 pub use self::lidar_point_stats::LidarPointStats;
+pub use self::lidar_powerline_detection::LidarPowerlineDetection;
 pub use self::lidar_radial_basis_function_interpolation::LidarRbfInterpolation;
 pub use self::lidar_ransac_planes::LidarRansacPlanes;
 pub use self::lidar_segmentation::LidarSegmentation;
@@ -84,8 +94,12 @@ pub use self::lidar_thin::LidarThin;
 pub use self::lidar_thin_high_density::LidarThinHighDensity;
 pub use self::lidar_tile::LidarTile;
 pub use self::lidar_tile_footprint::LidarTileFootprint;
+pub use self::lidar_tiled_workflow::LidarTiledWorkflow;
 pub use self::lidar_tin_gridding::LidarTINGridding;
+pub use self::lidar_to_mesh::LidarToMesh;
+pub use self::lidar_to_parquet::LidarToParquet;
 pub use self::lidar_tophat_transform::LidarTophatTransform;
+pub use self::lidar_voxel_metrics::LidarVoxelMetrics;
 pub use self::normal_vectors::NormalVectors;
 pub use self::remove_duplicates::LidarRemoveDuplicates;
 pub use self::select_tiles_by_polygon::SelectTilesByPolygon;
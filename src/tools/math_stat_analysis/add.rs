@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 05/07/2017
-Last Modified: 24/01/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::simd::{self, BinaryOp};
 use num_cpus;
 use std::env;
 use std::f64;
@@ -385,7 +386,8 @@ impl WhiteboxTool for Add {
                 println!("Reading data...")
             };
             let in1 = Arc::new(Raster::new(&input1, "r")?);
-            let in2 = Arc::new(Raster::new(&input2, "r")?);
+            // make sure the input files share the same grid and CRS
+            let in2 = Arc::new(align_raster_input(&in1, Raster::new(&input2, "r")?)?);
 
             let start = Instant::now();
             let rows = in1.configs.rows as isize;
@@ -393,12 +395,6 @@ impl WhiteboxTool for Add {
             let nodata1 = in1.configs.nodata;
             let nodata2 = in2.configs.nodata;
 
-            // make sure the input files have the same size
-            if in1.configs.rows != in2.configs.rows || in1.configs.columns != in2.configs.columns {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
-            }
-
             let num_procs = num_cpus::get() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -406,19 +402,16 @@ impl WhiteboxTool for Add {
                 let in2 = in2.clone();
                 let tx = tx.clone();
                 thread::spawn(move || {
-                    let mut z1: f64;
-                    let mut z2: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
-                        for col in 0..columns {
-                            z1 = in1[(row, col)];
-                            z2 = in2[(row, col)];
-                            if z1 != nodata1 && z2 != nodata2 {
-                                data[col as usize] = z1 + z2;
-                            } else {
-                                data[col as usize] = nodata1;
-                            }
-                        }
+                        simd::binary_op(
+                            BinaryOp::Add,
+                            &in1.get_row_data(row),
+                            nodata1,
+                            &in2.get_row_data(row),
+                            nodata2,
+                            &mut data,
+                        );
                         tx.send((row, data)).unwrap();
                     }
                 });
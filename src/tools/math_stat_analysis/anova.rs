@@ -291,11 +291,12 @@ impl WhiteboxTool for Anova {
         );
         writer.write_all(s1.as_bytes())?;
 
-        let features = Arc::new(Raster::new(&feature_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let features = Arc::new(align_raster_input(
+            &input,
+            Raster::new(&feature_file, "r")?,
+        )?);
         let nodata_features = features.configs.nodata;
-        if features.configs.columns as isize != columns || features.configs.rows as isize != rows {
-            panic!(Error::new(ErrorKind::InvalidInput, "The input and feature definition rasters must have the same number of rows and columns."));
-        }
         // How many features/classes are there?
         // let mut z: f64;
         // let mut id: f64;
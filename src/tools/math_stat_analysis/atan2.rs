@@ -375,7 +375,8 @@ impl WhiteboxTool for Atan2 {
                 println!("Reading data...")
             };
             let in1 = Arc::new(Raster::new(&input1, "r")?);
-            let in2 = Arc::new(Raster::new(&input2, "r")?);
+            // make sure the input files share the same grid and CRS
+            let in2 = Arc::new(align_raster_input(&in1, Raster::new(&input2, "r")?)?);
 
             let start = Instant::now();
             let rows = in1.configs.rows as isize;
@@ -383,12 +384,6 @@ impl WhiteboxTool for Atan2 {
             let nodata1 = in1.configs.nodata;
             let nodata2 = in2.configs.nodata;
 
-            // make sure the input files have the same size
-            if in1.configs.rows != in2.configs.rows || in1.configs.columns != in2.configs.columns {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
-            }
-
             let num_procs = num_cpus::get() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
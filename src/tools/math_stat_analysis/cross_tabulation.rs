@@ -209,7 +209,8 @@ impl WhiteboxTool for CrossTabulation {
         let columns = input1.configs.columns as isize;
         let nodata1 = input1.configs.nodata;
 
-        let input2 = Raster::new(&input_file2, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let input2 = align_raster_input(&input1, Raster::new(&input_file2, "r")?)?;
         let nodata2 = input2.configs.nodata;
 
         let min1 = input1.configs.minimum.round() as isize;
@@ -0,0 +1,1036 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::na::{DMatrix, DVector};
+use crate::raster::*;
+use crate::structures::{EmpiricalSemivariogram, Point2D, Semivariogram, VariogramModel};
+use crate::tools::*;
+use crate::utils::random;
+use crate::vector::{FieldData, ShapeType, ShapeTypeDimension, Shapefile};
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use num_cpus;
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+use rand_distr::StandardNormal;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool generates multiple equiprobable realizations (`--num_realizations`) of a continuous,
+/// multi-Gaussian random surface with a user-specified semivariogram, optionally conditioned on a set of
+/// sample points (`--input`). Unlike an interpolated (e.g. kriged) surface, which is smooth and represents
+/// the expected value at each location, each realization reproduces the spatial variability of the data,
+/// and the set of realizations characterizes the uncertainty in the surface. Realizations are commonly used
+/// as inputs to Monte Carlo analyses of uncertainty propagation.
+///
+/// Unconditional realizations are generated using the turning bands method (Matheron, 1973), with the line
+/// processes generated using the spectral method (Mantoglou and Wilson, 1982). Each realization is the sum
+/// of `--num_lines` random cosine waves, each travelling along a randomly oriented line, with frequencies
+/// drawn from the spectral density of the semivariogram model (`--model`; 'spherical', 'exponential', or
+/// 'gaussian'). More lines yield realizations that more closely follow a Gaussian distribution and the
+/// specified semivariogram, at the cost of longer run times. The nugget is added as uncorrelated noise.
+/// Unconditional realizations have a mean of `--mean` (0 by default).
+///
+/// When sample points are provided, either with an attribute field (`--field`) or z-values (`--use_z`),
+/// each realization is conditioned on the samples by kriging: the ordinary kriging estimate of the
+/// differences between the sample values and the unconditional realization at the sample locations, using
+/// the `--num_neighbours` nearest samples, is added to the unconditional realization. The conditioned
+/// realizations honour the sample values at the sample locations and their variance approaches the kriging
+/// variance. In this case the semivariogram is, by default, fitted to the empirical semivariogram of the
+/// samples (see `OrdinaryKriging` for a description of the `--lag_size` and `--num_lags` parameters), unless
+/// the range (`--range`) is specified. Without sample points, the range must be specified and the partial
+/// sill (`--sill`) and nugget (`--nugget`) default to 1 and 0 respectively.
+///
+/// The output grid is defined by a base raster (`--base`) or, for conditional simulations, by a cell size
+/// (`--cell_size`) and the extent of the sample points. By default, the realizations are written as the
+/// bands of a single multi-band raster (`--output`), which must be in the GeoTIFF format. Alternatively,
+/// the `--separate_files` flag writes each realization to its own numbered file, e.g. 'sim_1.tif',
+/// 'sim_2.tif', etc. for an output file named 'sim.tif'.
+///
+/// Use the global `--seed` flag to generate the same realizations in repeated runs.
+///
+/// # References
+/// Mantoglou, A., and Wilson, J. L. (1982). The turning bands method for simulation of random fields using
+/// line generation by a spectral method. Water Resources Research, 18(5), 1379-1394.
+///
+/// Matheron, G. (1973). The intrinsic random functions and their applications. Advances in Applied
+/// Probability, 5(3), 439-468.
+///
+/// # See Also
+/// `TurningBandsSimulation`, `OrdinaryKriging`, `SemivariogramAnalysis`, `RandomField`
+pub struct GaussianSimulation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GaussianSimulation {
+    pub fn new() -> GaussianSimulation {
+        // public constructor
+        let name = "GaussianSimulation".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description =
+            "Generates equiprobable realizations of a Gaussian random surface, optionally conditioned on point data."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Conditioning Points File (optional)".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Optional input vector points file of conditioning data.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Use Shapefile 'z' values?".to_owned(),
+            flags: vec!["--use_z".to_owned()],
+            description:
+                "Use the 'z' dimension of the Shapefile's geometry instead of an attribute field?"
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Realizations".to_owned(),
+            flags: vec!["--num_realizations".to_owned()],
+            description: "Number of realizations to generate.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Write realizations to separate files?".to_owned(),
+            flags: vec!["--separate_files".to_owned()],
+            description:
+                "Write each realization to a numbered file rather than a multi-band raster."
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Semivariogram Model".to_owned(),
+            flags: vec!["--model".to_owned()],
+            description:
+                "Semivariogram model; options include 'spherical', 'exponential', and 'gaussian'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "spherical".to_owned(),
+                "exponential".to_owned(),
+                "gaussian".to_owned(),
+            ]),
+            default_value: Some("spherical".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Lag Size (optional)".to_owned(),
+            flags: vec!["--lag_size".to_owned()],
+            description: "Width of the empirical semivariogram lag classes.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Lags".to_owned(),
+            flags: vec!["--num_lags".to_owned()],
+            description: "Number of empirical semivariogram lag classes.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("15".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Nugget (optional)".to_owned(),
+            flags: vec!["--nugget".to_owned()],
+            description: "Semivariogram nugget; only used when the range is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Partial Sill (optional)".to_owned(),
+            flags: vec!["--sill".to_owned()],
+            description: "Semivariogram partial sill; only used when the range is specified."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Range (optional)".to_owned(),
+            flags: vec!["--range".to_owned()],
+            description: "Semivariogram range; required for unconditional simulations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Mean".to_owned(),
+            flags: vec!["--mean".to_owned()],
+            description: "Mean of unconditional realizations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Turning Bands Lines".to_owned(),
+            flags: vec!["--num_lines".to_owned()],
+            description: "Number of turning bands lines used to generate each realization."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1000".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours".to_owned(),
+            flags: vec!["--num_neighbours".to_owned()],
+            description: "Number of nearest sample points used to condition each cell.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified input base raster file. Not used when a cell size is specified.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --base=dem.tif -o=sims.tif --num_realizations=50 --model=exponential --sill=0.25 --range=500.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=ELEV -o=sim.tif --num_realizations=100 --separate_files --cell_size=10.0",
+            short_exe, name
+        ).replace("*", &sep);
+
+        GaussianSimulation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GaussianSimulation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut use_z = false;
+        let mut use_field = false;
+        let mut output_file = String::new();
+        let mut num_realizations = 10usize;
+        let mut separate_files = false;
+        let mut model_name = String::from("spherical");
+        let mut lag_size = 0f64;
+        let mut num_lags = 15usize;
+        let mut nugget: Option<f64> = None;
+        let mut sill: Option<f64> = None;
+        let mut range = 0f64;
+        let mut mean = 0f64;
+        let mut num_lines = 1000usize;
+        let mut num_neighbours = 16usize;
+        let mut grid_res = 0f64;
+        let mut base_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-field" {
+                field_name = val;
+                use_field = true;
+            } else if flag_val.contains("use_z") {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    use_z = true;
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-num_realizations" {
+                num_realizations = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-separate_files" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    separate_files = true;
+                }
+            } else if flag_val == "-model" {
+                model_name = val.to_lowercase();
+            } else if flag_val == "-lag_size" {
+                lag_size = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-num_lags" {
+                num_lags = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-nugget" {
+                nugget = Some(
+                    val.parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)),
+                );
+            } else if flag_val == "-sill" {
+                sill = Some(
+                    val.parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)),
+                );
+            } else if flag_val == "-range" {
+                range = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-mean" {
+                mean = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-num_lines" {
+                num_lines = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-num_neighbours" || flag_val == "-num_neighbors" {
+                num_neighbours = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-resolution" || flag_val == "-cell_size" {
+                grid_res = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-base" {
+                base_file = val;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        let conditional = !input_file.trim().is_empty();
+        if conditional && !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !base_file.trim().is_empty() && !base_file.contains(&sep) && !base_file.contains("/") {
+            base_file = format!("{}{}", working_directory, base_file);
+        }
+
+        let model = match VariogramModel::from_name(&model_name) {
+            Some(m) => m,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Unrecognized semivariogram model. Options include 'spherical', 'exponential', and 'gaussian'.",
+                ))
+            }
+        };
+        if num_realizations == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of realizations must be greater than zero.",
+            ));
+        }
+        if num_lines < 1 {
+            num_lines = 1;
+        }
+        if num_lags < 2 {
+            num_lags = 2;
+        }
+        if num_neighbours < 3 {
+            num_neighbours = 3;
+        }
+
+        // read the conditioning data
+        let mut points: Vec<Point2D> = vec![];
+        let mut z_values: Vec<f64> = vec![];
+        let mut extent = (0f64, 0f64, 0f64, 0f64); // west, south, east, north
+        if conditional {
+            if verbose {
+                println!("Reading data...")
+            };
+
+            let input = Shapefile::read(&input_file)?;
+
+            // make sure the input vector file is of points type
+            if input.header.shape_type.base_shape_type() != ShapeType::Point
+                && input.header.shape_type.base_shape_type() != ShapeType::MultiPoint
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input vector data must be of POINT base shape type.",
+                ));
+            }
+
+            if !use_z && !use_field {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "If vector data 'Z' data are unavailable (--use_z), an attribute field must be specified (--field=).",
+                ));
+            }
+
+            if use_z && input.header.shape_type.dimension() != ShapeTypeDimension::Z {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input vector data must be of 'POINTZ' or 'MULTIPOINTZ' ShapeType to use the --use_z flag.",
+                ));
+            } else if use_field {
+                let field_index = match input.attributes.get_field_num(&field_name) {
+                    Some(i) => i,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "The specified field name does not exist in input shapefile.",
+                        ))
+                    }
+                };
+                if !input.attributes.is_field_numeric(field_index) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The specified attribute field is non-numeric.",
+                    ));
+                }
+            }
+
+            for record_num in 0..input.num_records {
+                let record = input.get_record(record_num);
+                let field_value = if use_z {
+                    None
+                } else {
+                    match input.attributes.get_value(record_num, &field_name) {
+                        FieldData::Int(val) => Some(val as f64),
+                        FieldData::Real(val) => Some(val),
+                        _ => None, // likely a null field
+                    }
+                };
+                for i in 0..record.num_points as usize {
+                    let z = if use_z {
+                        record.z_array[i]
+                    } else {
+                        match field_value {
+                            Some(v) => v,
+                            None => continue,
+                        }
+                    };
+                    points.push(Point2D::new(record.points[i].x, record.points[i].y));
+                    z_values.push(z);
+                }
+            }
+
+            if points.len() < 3 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "At least three points with valid values are required for conditioning.",
+                ));
+            }
+            extent = (
+                input.header.x_min,
+                input.header.y_min,
+                input.header.x_max,
+                input.header.y_max,
+            );
+        }
+        let n = points.len();
+        if num_neighbours > n {
+            num_neighbours = n;
+        }
+
+        // the semivariogram model
+        let semivariogram = if range > 0f64 {
+            let sill = match sill {
+                Some(s) => s,
+                None if conditional => {
+                    let m = z_values.iter().sum::<f64>() / n as f64;
+                    z_values.iter().map(|z| (z - m) * (z - m)).sum::<f64>() / n as f64
+                }
+                None => 1f64,
+            };
+            Semivariogram::new(model, nugget.unwrap_or(0f64), sill, range)
+        } else if conditional {
+            if verbose {
+                println!("Fitting semivariogram model...");
+            }
+            if lag_size <= 0f64 {
+                let width = extent.2 - extent.0;
+                let height = extent.3 - extent.1;
+                lag_size = (width * width + height * height).sqrt() / 2f64 / num_lags as f64;
+            }
+            let empirical = EmpiricalSemivariogram::new(&points, &z_values, lag_size, num_lags);
+            match Semivariogram::fit(model, &empirical) {
+                Some(s) => s,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The semivariogram model could not be fitted; too few lag classes contain point pairs. Try increasing the lag size.",
+                    ))
+                }
+            }
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The semivariogram range (--range) must be specified for unconditional simulations.",
+            ));
+        };
+        if semivariogram.sill < 0f64
+            || semivariogram.nugget < 0f64
+            || semivariogram.sill + semivariogram.nugget <= 0f64
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The semivariogram sill must be greater than zero and the nugget must not be negative.",
+            ));
+        }
+        if conditional {
+            // the unconditional realizations are centred on the sample mean; the
+            // conditioning step is unaffected by the mean, but this keeps the
+            // realizations near the samples' mean away from the samples
+            mean = z_values.iter().sum::<f64>() / n as f64;
+        }
+
+        if verbose {
+            println!(
+                "Semivariogram: {} model, nugget = {:.4}, partial sill = {:.4}, range = {:.4}",
+                semivariogram.model, semivariogram.nugget, semivariogram.sill, semivariogram.range
+            );
+        }
+
+        let nodata = -32768.0f64;
+        let mut output = if !base_file.trim().is_empty() {
+            let mut base = Raster::new(&base_file, "r")?;
+            base.configs.nodata = nodata;
+            Raster::initialize_using_file(&output_file, &base)
+        } else if conditional && grid_res > 0f64 {
+            // base the output raster on the grid_res and the
+            // extent of the input vector.
+            let (west, south, east, north) = extent;
+            let rows: isize = (((north - south) / grid_res).ceil()) as isize;
+            let columns: isize = (((east - west) / grid_res).ceil()) as isize;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows.max(1) as usize;
+            configs.columns = columns.max(1) as usize;
+            configs.north = north;
+            configs.south = north - configs.rows as f64 * grid_res;
+            configs.east = west + configs.columns as f64 * grid_res;
+            configs.west = west;
+            configs.resolution_x = grid_res;
+            configs.resolution_y = grid_res;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+            Raster::initialize_using_config(&output_file, &configs)
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a base raster (--base) or, for conditional simulations, a non-zero grid resolution (--cell_size) must be specified.",
+            ));
+        };
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        output.configs.nodata = nodata; // in case a base image is used with a different nodata value.
+        output.configs.palette = "spectrum.pal".to_string();
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        if !separate_files && num_realizations > 1 && !output.raster_type.supports_multiple_bands()
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Multi-band outputs must be GeoTIFF or Whitebox v2 files; use a .tif or .wbr output file or the --separate_files flag.",
+            ));
+        }
+
+        // Draw the turning bands lines of each realization. The line directions and
+        // frequencies are drawn from the spectral density of the covariance model and
+        // the phases are uniformly distributed.
+        let spherical_cdf = if model == VariogramModel::Spherical {
+            spherical_spectral_cdf()
+        } else {
+            vec![]
+        };
+        let mut lines: Vec<Vec<(f64, f64, f64)>> = Vec::with_capacity(num_realizations);
+        for r in 0..num_realizations {
+            let mut rng = random::rng(r as u64);
+            let mut l = Vec::with_capacity(num_lines);
+            for _ in 0..num_lines {
+                let (wx, wy) = sample_frequency(
+                    &mut rng,
+                    semivariogram.model,
+                    semivariogram.range,
+                    &spherical_cdf,
+                );
+                l.push((wx, wy, rng.gen_range(0f64, 2f64 * PI)));
+            }
+            lines.push(l);
+        }
+        let amplitude = (2f64 * semivariogram.sill / num_lines as f64).sqrt();
+        let nugget_sd = semivariogram.nugget.sqrt();
+        let num_realizations_u64 = num_realizations as u64;
+
+        // the unconditional realizations at the sample locations
+        let mut point_values: Vec<Vec<f64>> = vec![vec![0f64; num_realizations]; n];
+        for r in 0..num_realizations {
+            let mut rng = random::rng(num_realizations_u64 + r as u64);
+            for i in 0..n {
+                let mut z = mean;
+                for &(wx, wy, phase) in &lines[r] {
+                    z += amplitude * (wx * points[i].x + wy * points[i].y + phase).cos();
+                }
+                if nugget_sd > 0f64 {
+                    z += nugget_sd * rng.sample::<f64, _>(StandardNormal);
+                }
+                point_values[i][r] = z;
+            }
+        }
+
+        const DIMENSIONS: usize = 2;
+        const CAPACITY_PER_NODE: usize = 64;
+        let mut tree = KdTree::with_capacity(DIMENSIONS, CAPACITY_PER_NODE);
+        for p in 0..n {
+            tree.add([points[p].x, points[p].y], p).unwrap();
+        }
+
+        if verbose {
+            println!("Simulating...");
+        }
+        let configs = Arc::new(output.configs.clone());
+        let lines = Arc::new(lines);
+        let points = Arc::new(points);
+        let z_values = Arc::new(z_values);
+        let point_values = Arc::new(point_values);
+        let tree = Arc::new(tree);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let configs = configs.clone();
+            let lines = lines.clone();
+            let points = points.clone();
+            let z_values = z_values.clone();
+            let point_values = point_values.clone();
+            let tree = tree.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    // the cell centres of a row are evenly spaced for every grid geometry
+                    let (x0, y0) = configs.get_cell_centre(row, 0);
+                    let (x1, y1) = configs.get_cell_centre(row, 1);
+                    let mut data = vec![vec![mean; columns as usize]; num_realizations];
+                    for r in 0..num_realizations {
+                        for &(wx, wy, phase) in &lines[r] {
+                            add_cosine_wave(
+                                &mut data[r],
+                                amplitude,
+                                wx * x0 + wy * y0 + phase,
+                                wx * (x1 - x0) + wy * (y1 - y0),
+                            );
+                        }
+                        if nugget_sd > 0f64 {
+                            let mut rng: SmallRng = random::rng(
+                                2 * num_realizations_u64 + r as u64 * rows as u64 + row as u64,
+                            );
+                            for col in 0..columns as usize {
+                                data[r][col] += nugget_sd * rng.sample::<f64, _>(StandardNormal);
+                            }
+                        }
+                    }
+
+                    if conditional {
+                        for col in 0..columns {
+                            let (px, py) = configs.get_cell_centre(row, col);
+                            let neighbours: Vec<usize> =
+                                match tree.nearest(&[px, py], num_neighbours, &squared_euclidean) {
+                                    Ok(ret) => ret.into_iter().map(|(_, &p)| p).collect(),
+                                    Err(_) => continue,
+                                };
+                            let k = neighbours.len();
+                            if k == 0 {
+                                continue;
+                            }
+
+                            // Ordinary kriging system in terms of semivariance, with the
+                            // Lagrange multiplier enforcing unbiasedness in the last row.
+                            let a = DMatrix::from_fn(k + 1, k + 1, |i, j| {
+                                if i == k && j == k {
+                                    0f64
+                                } else if i == k || j == k {
+                                    1f64
+                                } else {
+                                    semivariogram.value(
+                                        points[neighbours[i]].distance(&points[neighbours[j]]),
+                                    )
+                                }
+                            });
+                            let p = Point2D::new(px, py);
+                            let b = DVector::from_fn(k + 1, |i, _| {
+                                if i == k {
+                                    1f64
+                                } else {
+                                    semivariogram.value(points[neighbours[i]].distance(&p))
+                                }
+                            });
+                            let w: Vec<f64> = match a.lu().solve(&b) {
+                                Some(w) => (0..k).map(|i| w[i]).collect(),
+                                None => {
+                                    // singular system, e.g. due to coincident points; honour
+                                    // the nearest sample
+                                    let mut w = vec![0f64; k];
+                                    w[0] = 1f64;
+                                    w
+                                }
+                            };
+
+                            // add the kriged residuals of the samples from the realization
+                            for r in 0..num_realizations {
+                                let mut residual = 0f64;
+                                for i in 0..k {
+                                    let s = neighbours[i];
+                                    residual += w[i] * (z_values[s] - point_values[s][r]);
+                                }
+                                data[r][col as usize] += residual;
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut separate_outputs: Vec<Raster> = vec![];
+        if separate_files {
+            let extension = match output_file.rfind('.') {
+                Some(i) if !output_file[i..].contains(&sep) && !output_file[i..].contains('/') => {
+                    output_file[i..].to_string()
+                }
+                _ => String::new(),
+            };
+            let stem = output_file[..output_file.len() - extension.len()].to_string();
+            for r in 0..num_realizations {
+                separate_outputs.push(Raster::initialize_using_file(
+                    &format!("{}_{}{}", stem, r + 1, extension),
+                    &output,
+                ));
+            }
+        } else {
+            output.set_num_bands(num_realizations);
+        }
+        for row in 0..rows {
+            let (r, data) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                if separate_files {
+                    separate_outputs[band].set_row_data(r, values);
+                } else {
+                    output.set_row_data_band(r, band, values);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Simulating: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let mut metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Number of realizations: {}", num_realizations),
+            format!("Semivariogram model: {}", semivariogram.model),
+            format!("Nugget: {}", semivariogram.nugget),
+            format!("Partial sill: {}", semivariogram.sill),
+            format!("Range: {}", semivariogram.range),
+            format!("Num. turning bands lines: {}", num_lines),
+        ];
+        if conditional {
+            metadata.push(format!("Input file: {}", input_file));
+            metadata.push(format!("Field name: {}", field_name));
+            metadata.push(format!("Use z-field: {}", use_z));
+            metadata.push(format!("Num. neighbours: {}", num_neighbours));
+        } else {
+            metadata.push(format!("Mean: {}", mean));
+        }
+        if !base_file.trim().is_empty() {
+            metadata.push(format!("Base file: {}", base_file));
+        } else {
+            metadata.push(format!("Grid resolution: {}", grid_res));
+        }
+        if let Some(seed) = random::seed() {
+            metadata.push(format!("Seed: {}", seed));
+        }
+        metadata.push(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        if separate_files {
+            for mut o in separate_outputs {
+                for entry in &metadata {
+                    o.add_metadata_entry(entry.clone());
+                }
+                o.write()?;
+            }
+        } else {
+            for entry in &metadata {
+                output.add_metadata_entry(entry.clone());
+            }
+            output.write()?;
+        }
+        if verbose {
+            println!("Output file written")
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The extent and resolution of the tabulated radial distribution of the spherical
+/// model's spectral density, in units of the reciprocal of half the range.
+const SPHERICAL_CDF_MAX: f64 = 2000f64;
+const SPHERICAL_CDF_STEPS: usize = 100_000;
+
+/// Tabulates the cumulative distribution of the radial frequency of the spectral density
+/// of the (3-D) spherical covariance, which is the autocorrelation of a ball of diameter
+/// equal to the range. In units of the reciprocal of the ball's radius R, the radial density
+/// is proportional to x^2 j(x)^2, where j(x) = (sin x - x cos x) / x^3.
+fn spherical_spectral_cdf() -> Vec<f64> {
+    let dx = SPHERICAL_CDF_MAX / SPHERICAL_CDF_STEPS as f64;
+    let density = |x: f64| {
+        if x < 1e-3 {
+            x * x / 9f64
+        } else {
+            let j = (x.sin() - x * x.cos()) / (x * x * x);
+            x * x * j * j
+        }
+    };
+    let mut cdf = vec![0f64; SPHERICAL_CDF_STEPS + 1];
+    let mut previous = density(0f64);
+    for i in 1..=SPHERICAL_CDF_STEPS {
+        let d = density(i as f64 * dx);
+        cdf[i] = cdf[i - 1] + 0.5 * (previous + d) * dx;
+        previous = d;
+    }
+    let total = cdf[SPHERICAL_CDF_STEPS];
+    for v in cdf.iter_mut() {
+        *v /= total;
+    }
+    cdf
+}
+
+/// Draws a frequency vector from the spectral density of a unit-sill covariance model
+/// with the specified (practical) range, such that the expected value of cos(w . h) is the
+/// covariance at lag h.
+fn sample_frequency(
+    rng: &mut SmallRng,
+    model: VariogramModel,
+    range: f64,
+    spherical_cdf: &[f64],
+) -> (f64, f64) {
+    match model {
+        VariogramModel::Gaussian => {
+            // C(h) = exp(-3 h^2 / a^2) has a Gaussian spectral density
+            let sd = (6f64).sqrt() / range;
+            (
+                sd * rng.sample::<f64, _>(StandardNormal),
+                sd * rng.sample::<f64, _>(StandardNormal),
+            )
+        }
+        VariogramModel::Exponential => {
+            // C(h) = exp(-3 h / a) has a bivariate Cauchy spectral density
+            let scale = 3f64 / range;
+            let w = rng
+                .sample::<f64, _>(StandardNormal)
+                .abs()
+                .max(f64::MIN_POSITIVE);
+            (
+                scale * rng.sample::<f64, _>(StandardNormal) / w,
+                scale * rng.sample::<f64, _>(StandardNormal) / w,
+            )
+        }
+        VariogramModel::Spherical => {
+            // draw the 3-D radial frequency from the tabulated distribution and a
+            // uniformly distributed direction, then project onto the plane
+            let u: f64 = rng.gen();
+            let i = match spherical_cdf
+                .binary_search_by(|v| v.partial_cmp(&u).unwrap_or(std::cmp::Ordering::Less))
+            {
+                Ok(i) => i,
+                Err(i) => i.max(1).min(spherical_cdf.len() - 1),
+            };
+            let dx = SPHERICAL_CDF_MAX / SPHERICAL_CDF_STEPS as f64;
+            let (c0, c1) = (spherical_cdf[i.max(1) - 1], spherical_cdf[i]);
+            let t = if c1 > c0 { (u - c0) / (c1 - c0) } else { 0f64 };
+            let x = ((i.max(1) - 1) as f64 + t) * dx;
+            let rho = x / (range / 2f64);
+            let cos_theta = rng.gen_range(-1f64, 1f64);
+            let phi = rng.gen_range(0f64, 2f64 * PI);
+            let r = rho * (1f64 - cos_theta * cos_theta).sqrt();
+            (r * phi.cos(), r * phi.sin())
+        }
+    }
+}
+
+/// Adds `amplitude * cos(phase + i * step)` to each value of a row. The cosines are
+/// evaluated by rotation, periodically resynchronized to limit the accumulated error.
+fn add_cosine_wave(values: &mut [f64], amplitude: f64, phase: f64, step: f64) {
+    const RESYNC_INTERVAL: usize = 256;
+    let (sin_step, cos_step) = step.sin_cos();
+    let (mut s, mut c) = (0f64, 0f64);
+    for (i, v) in values.iter_mut().enumerate() {
+        if i % RESYNC_INTERVAL == 0 {
+            let (a, b) = (phase + i as f64 * step).sin_cos();
+            s = a;
+            c = b;
+        } else {
+            let c_next = c * cos_step - s * sin_step;
+            s = s * cos_step + c * sin_step;
+            c = c_next;
+        }
+        *v += amplitude * c;
+    }
+}
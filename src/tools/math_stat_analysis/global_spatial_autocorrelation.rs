@@ -0,0 +1,860 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use self::statrs::distribution::{Normal, Univariate};
+use crate::raster::grid::hex_neighbour_offsets;
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::structures::{
+    gearys_c, gearys_c_test, morans_i, morans_i_test, pseudo_p_value, AutocorrelationTest,
+    GridWeights, Moments, Point2D, SpatialWeights, WeightsList, WeightsSummary,
+};
+use crate::tools::*;
+use crate::utils::random;
+use crate::vector::{FieldData, ShapeType, Shapefile, ShapefileGeometry};
+use num_cpus;
+use rand::seq::SliceRandom;
+use statrs;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool measures the global spatial autocorrelation of a raster, or of a numeric attribute (`--field`)
+/// of a vector, using Moran's *I* and Geary's *C* statistics. Spatial autocorrelation describes the extent
+/// to which similar values are clustered (positive autocorrelation) or dispersed (negative autocorrelation)
+/// through space. Moran's *I* is greater than its expected value, -1 / (n - 1), and Geary's *C* is less than
+/// its expected value of 1, for clustered patterns.
+///
+/// The neighbours of each grid cell or vector feature are defined by the spatial weights type (`--weights`):
+///
+/// - 'queen': features that share a vertex (vector polygons) or the eight adjacent cells (rasters);
+/// - 'rook': features that share an edge (vector polygons) or the four cells sharing an edge (rasters);
+/// - 'bishop': the four diagonally adjacent cells (rasters only);
+/// - 'distance': features, or cells, with centroids within a distance (`--distance`) of one another; and
+/// - 'knn': the k nearest features (`--num_neighbours`; vectors only).
+///
+/// For hexagonal grids, the queen's and rook's neighbours are the six adjacent cells. Contiguity weights require
+/// that shared polygon vertices have identical coordinates; distance-based weights may be used with any vector
+/// geometry type and are based on the feature centroids. The weights of each feature may be row-standardized
+/// (`--row_standardize`), such that they sum to one.
+///
+/// The tool outputs an HTML report (`--output`) containing each statistic, its expected value, variance, and
+/// z-score under the randomization assumption, and the corresponding two-sided p-value. In addition, a pseudo
+/// p-value is estimated from the statistics of `--permutations` random permutations of the values among the
+/// features (999 by default; 0 to skip). **NoData** cells and features with null attribute values are excluded
+/// from the analysis. Features without neighbours (islands) are included in the number of features but do not
+/// otherwise contribute to the statistics; consider a larger distance or k-nearest neighbour weights if the
+/// report lists many islands. Use the global `--seed` flag for reproducible pseudo p-values.
+///
+/// Use the `LocalSpatialAutocorrelation` tool to map local clusters and outliers.
+///
+/// # References
+/// Cliff, A. D., and Ord, J. K. (1981). Spatial Processes: Models and Applications. Pion, London.
+///
+/// # See Also
+/// `LocalSpatialAutocorrelation`, `ImageAutocorrelation`, `SemivariogramAnalysis`
+pub struct GlobalSpatialAutocorrelation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GlobalSpatialAutocorrelation {
+    pub fn new() -> GlobalSpatialAutocorrelation {
+        // public constructor
+        let name = "GlobalSpatialAutocorrelation".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description =
+            "Measures the global spatial autocorrelation of a raster or vector attribute using Moran's I and Geary's C."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster or Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster or vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table (vector inputs only).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        push_weights_parameters(&mut parameters, 999);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=report.html --weights=queen
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=counties.shp --field=INCOME -o=report.html --weights=rook --row_standardize --permutations=9999",
+            short_exe, name
+        ).replace("*", &sep);
+
+        GlobalSpatialAutocorrelation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GlobalSpatialAutocorrelation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut output_file = String::new();
+        let mut options = WeightsOptions::new(999);
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-field" {
+                field_name = val;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else {
+                options.parse_arg(&flag_val, &vec, &val);
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let is_vector = input_file.to_lowercase().ends_with(".shp");
+        let (weights, values) = if is_vector {
+            let input = Shapefile::read(&input_file)?;
+            let (weights, values, _) = vector_weights(&input, &field_name, &options)?;
+            (SpatialData::Vector(Arc::new(weights)), values)
+        } else {
+            let input = Raster::new(&input_file, "r")?;
+            let (weights, values) = raster_weights(&input, &options)?;
+            (SpatialData::Raster(Arc::new(weights)), values)
+        };
+
+        let start = Instant::now();
+
+        let n = values.len();
+        if n < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least four cells or features with valid values are required.",
+            ));
+        }
+        let moments = Moments::new(&values);
+        if moments.sum2 <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input values are constant; spatial autocorrelation is undefined.",
+            ));
+        }
+
+        if verbose {
+            println!("Calculating statistics...");
+        }
+        let values = Arc::new(values);
+        let (moran, geary, num_islands, simulated) = match &weights {
+            SpatialData::Raster(w) => {
+                global_tests(w.clone(), values, options.permutations, verbose)
+            }
+            SpatialData::Vector(w) => {
+                global_tests(w.clone(), values, options.permutations, verbose)
+            }
+        };
+        if num_islands == n {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the cells or features have neighbours; try different spatial weights.",
+            ));
+        }
+
+        let distribution = Normal::new(0.0, 1.0).unwrap();
+        let p_value = |z: f64| 2f64 * (1f64 - distribution.cdf(z.abs()));
+        let moran_pseudo_p = if simulated.0.is_empty() {
+            None
+        } else {
+            Some(pseudo_p_value(moran.statistic, &simulated.0))
+        };
+        let geary_pseudo_p = if simulated.1.is_empty() {
+            None
+        } else {
+            Some(pseudo_p_value(geary.statistic, &simulated.1))
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let f = File::create(&output_file)?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <head>
+            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+            <title>Global Spatial Autocorrelation</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"</head>
+        <body>
+            <h1>Global Spatial Autocorrelation Report</h1>"#
+                .as_bytes(),
+        )?;
+
+        writer.write_all(&format!("<p><strong>Input</strong>: {}</p>", input_file).as_bytes())?;
+        if is_vector {
+            writer
+                .write_all(&format!("<p><strong>Field</strong>: {}</p>", field_name).as_bytes())?;
+        }
+        writer.write_all(
+            &format!(
+                "<p><strong>Spatial weights</strong>: {}</p>",
+                options.description()
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(
+            &format!(
+                "<p><strong>Number of {}</strong>: {} ({} without neighbours)</p>",
+                if is_vector { "features" } else { "cells" },
+                n,
+                num_islands
+            )
+            .as_bytes(),
+        )?;
+
+        writer.write_all("<br><table align=\"center\">".as_bytes())?;
+        writer.write_all(
+            "<tr><th>Statistic</th><th>Moran's I</th><th>Geary's C</th></tr>".as_bytes(),
+        )?;
+        let rows = [
+            ("Value", moran.statistic, geary.statistic),
+            ("Expected value", moran.expected, geary.expected),
+            ("Variance", moran.variance, geary.variance),
+            ("z-score", moran.z_score(), geary.z_score()),
+            (
+                "p-value",
+                p_value(moran.z_score()),
+                p_value(geary.z_score()),
+            ),
+        ];
+        for (label, a, b) in &rows {
+            writer.write_all(
+                &format!(
+                    "<tr><td>{}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td></tr>",
+                    label, 4, a, 4, b
+                )
+                .as_bytes(),
+            )?;
+        }
+        if let (Some(a), Some(b)) = (moran_pseudo_p, geary_pseudo_p) {
+            writer.write_all(
+                &format!(
+                    "<tr><td>Pseudo p-value ({} permutations)</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td></tr>",
+                    options.permutations, 4, a, 4, b
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table>".as_bytes())?;
+        writer.write_all(
+            "<p>Variances, z-scores, and p-values are based on the randomization assumption.</p>"
+                .as_bytes(),
+        )?;
+        writer.write_all("</body>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            println!(
+                "Moran's I: {:.4} (z = {:.4})",
+                moran.statistic,
+                moran.z_score()
+            );
+            println!(
+                "Geary's C: {:.4} (z = {:.4})",
+                geary.statistic,
+                geary.z_score()
+            );
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+            println!("Complete! Please see {} for output.", output_file);
+        }
+
+        Ok(())
+    }
+}
+
+/// The spatial weights of a raster's cells or a vector's features.
+enum SpatialData {
+    Raster(Arc<GridWeights>),
+    Vector(Arc<WeightsList>),
+}
+
+/// Calculates the global Moran's I and Geary's C tests, the number of features without
+/// neighbours, and the statistics of `permutations` random permutations of the values.
+fn global_tests<W: SpatialWeights + Send + Sync + 'static>(
+    weights: Arc<W>,
+    values: Arc<Vec<f64>>,
+    permutations: usize,
+    verbose: bool,
+) -> (
+    AutocorrelationTest,
+    AutocorrelationTest,
+    usize,
+    (Vec<f64>, Vec<f64>),
+) {
+    let summary = WeightsSummary::new(&*weights);
+    let moran = morans_i_test(&*weights, &summary, &values);
+    let geary = gearys_c_test(&*weights, &summary, &values);
+    let mut num_islands = 0;
+    for i in 0..weights.len() {
+        let mut has_neighbours = false;
+        weights.for_each_neighbour(i, |_, _| has_neighbours = true);
+        if !has_neighbours {
+            num_islands += 1;
+        }
+    }
+
+    let mut simulated_i = vec![0f64; permutations];
+    let mut simulated_c = vec![0f64; permutations];
+    if permutations > 0 {
+        let moments = Moments::new(&values);
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let weights = weights.clone();
+            let values = values.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut permuted = (*values).clone();
+                for p in (0..permutations).filter(|p| p % num_procs == tid) {
+                    let mut rng = random::rng(p as u64);
+                    permuted.shuffle(&mut rng);
+                    tx.send((
+                        p,
+                        morans_i(&*weights, &permuted, &moments),
+                        gearys_c(&*weights, &permuted, &moments),
+                    ))
+                    .unwrap();
+                }
+            });
+        }
+
+        let mut old_progress: usize = 1;
+        for a in 0..permutations {
+            let (p, i, c) = rx.recv().expect("Error receiving data from thread.");
+            simulated_i[p] = i;
+            simulated_c[p] = c;
+            if verbose {
+                let progress = (100.0_f64 * (a + 1) as f64 / permutations as f64) as usize;
+                if progress != old_progress {
+                    println!("Permutations: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+    }
+    (moran, geary, num_islands, (simulated_i, simulated_c))
+}
+
+/// The spatial weights types of the spatial autocorrelation tools.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum WeightsType {
+    Queen,
+    Rook,
+    Bishop,
+    Distance,
+    KNearest,
+}
+
+/// The spatial weights parameters shared by the spatial autocorrelation tools.
+pub(super) struct WeightsOptions {
+    pub weights_type: WeightsType,
+    pub distance: f64,
+    pub num_neighbours: usize,
+    pub row_standardize: bool,
+    pub permutations: usize,
+}
+
+impl WeightsOptions {
+    pub fn new(permutations: usize) -> WeightsOptions {
+        WeightsOptions {
+            weights_type: WeightsType::Queen,
+            distance: 0f64,
+            num_neighbours: 8,
+            row_standardize: false,
+            permutations: permutations,
+        }
+    }
+
+    /// Parses a spatial weights argument; other arguments are ignored.
+    pub fn parse_arg(&mut self, flag_val: &str, vec: &[&str], val: &str) {
+        if flag_val == "-weights" {
+            let val = val.to_lowercase();
+            self.weights_type = if val.contains("rook") {
+                WeightsType::Rook
+            } else if val.contains("bishop") {
+                WeightsType::Bishop
+            } else if val.contains("dist") {
+                WeightsType::Distance
+            } else if val.contains("knn") || val.contains("near") {
+                WeightsType::KNearest
+            } else {
+                WeightsType::Queen
+            };
+        } else if flag_val == "-distance" {
+            self.distance = val
+                .parse::<f64>()
+                .expect(&format!("Error parsing {}", flag_val));
+        } else if flag_val == "-num_neighbours" || flag_val == "-num_neighbors" {
+            self.num_neighbours =
+                val.parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize;
+        } else if flag_val == "-row_standardize" {
+            if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                self.row_standardize = true;
+            }
+        } else if flag_val == "-permutations" {
+            self.permutations =
+                val.parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val)) as usize;
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let s = match self.weights_type {
+            WeightsType::Queen => "queen's contiguity".to_string(),
+            WeightsType::Rook => "rook's contiguity".to_string(),
+            WeightsType::Bishop => "bishop's contiguity".to_string(),
+            WeightsType::Distance => format!("distance band ({})", self.distance),
+            WeightsType::KNearest => format!("{} nearest neighbours", self.num_neighbours),
+        };
+        if self.row_standardize {
+            format!("{}, row-standardized", s)
+        } else {
+            s
+        }
+    }
+}
+
+/// Adds the spatial weights parameters to a tool's parameters.
+pub(super) fn push_weights_parameters(parameters: &mut Vec<ToolParameter>, permutations: usize) {
+    parameters.push(ToolParameter {
+        name: "Spatial Weights Type".to_owned(),
+        flags: vec!["--weights".to_owned()],
+        description: "Spatial weights type; options include 'queen', 'rook', 'bishop' (rasters only), 'distance', and 'knn' (vectors only).".to_owned(),
+        parameter_type: ParameterType::OptionList(vec![
+            "queen".to_owned(),
+            "rook".to_owned(),
+            "bishop".to_owned(),
+            "distance".to_owned(),
+            "knn".to_owned(),
+        ]),
+        default_value: Some("queen".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Distance Band (optional)".to_owned(),
+        flags: vec!["--distance".to_owned()],
+        description: "Neighbourhood distance, in map units, for distance weights.".to_owned(),
+        parameter_type: ParameterType::Float,
+        default_value: None,
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Number of Neighbours".to_owned(),
+        flags: vec!["--num_neighbours".to_owned()],
+        description: "Number of nearest neighbours for knn weights.".to_owned(),
+        parameter_type: ParameterType::Integer,
+        default_value: Some("8".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Row-standardize weights?".to_owned(),
+        flags: vec!["--row_standardize".to_owned()],
+        description: "Scale the weights of each feature such that they sum to one.".to_owned(),
+        parameter_type: ParameterType::Boolean,
+        default_value: Some("false".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Number of Permutations".to_owned(),
+        flags: vec!["--permutations".to_owned()],
+        description: "Number of random permutations used to estimate pseudo p-values (0 to skip)."
+            .to_owned(),
+        parameter_type: ParameterType::Integer,
+        default_value: Some(format!("{}", permutations)),
+        optional: true,
+    });
+}
+
+/// Creates the spatial weights of the valid cells of a raster, returning the weights and
+/// the cell values in feature order.
+pub(super) fn raster_weights(
+    input: &Raster,
+    options: &WeightsOptions,
+) -> Result<(GridWeights, Vec<f64>), Error> {
+    let hex = input.configs.grid_type == GridType::Hexagonal;
+    let square = |offsets: &[(isize, isize)]| (offsets.to_vec(), offsets.to_vec());
+    let (even_row_offsets, odd_row_offsets) = match options.weights_type {
+        WeightsType::Queen | WeightsType::Rook if hex => (
+            hex_neighbour_offsets(0).to_vec(),
+            hex_neighbour_offsets(1).to_vec(),
+        ),
+        WeightsType::Queen => square(&[
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+            (1, 0),
+            (1, -1),
+            (0, -1),
+        ]),
+        WeightsType::Rook => square(&[(-1, 0), (0, 1), (1, 0), (0, -1)]),
+        WeightsType::Bishop if hex => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Bishop's contiguity is undefined for hexagonal grids.",
+            ))
+        }
+        WeightsType::Bishop => square(&[(-1, -1), (-1, 1), (1, 1), (1, -1)]),
+        WeightsType::Distance => {
+            if options.distance <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A positive distance (--distance) must be specified for distance weights.",
+                ));
+            }
+            (
+                distance_offsets(&input.configs, options.distance, 0),
+                distance_offsets(&input.configs, options.distance, 1),
+            )
+        }
+        WeightsType::KNearest => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "k-nearest neighbour weights are not supported for rasters; use distance weights instead.",
+            ))
+        }
+    };
+
+    let nodata = input.configs.nodata;
+    let weights = GridWeights::new(
+        input.configs.rows as isize,
+        input.configs.columns as isize,
+        |row, col| input.get_value(row, col) != nodata,
+        even_row_offsets,
+        odd_row_offsets,
+        options.row_standardize,
+    );
+    let values = (0..weights.len())
+        .map(|i| {
+            let (row, col) = weights.cell(i);
+            input.get_value(row, col)
+        })
+        .collect();
+    Ok((weights, values))
+}
+
+/// Returns the (row, column) offsets of the cells with centres within `distance` of the
+/// centre of a cell in an even (`parity` = 0) or odd row.
+fn distance_offsets(configs: &RasterConfigs, distance: f64, parity: isize) -> Vec<(isize, isize)> {
+    let hex = configs.grid_type == GridType::Hexagonal;
+    // the half-cell eastward shift of the odd rows of hexagonal grids
+    let shift = |row: isize| if hex && row & 1 == 1 { 0.5 } else { 0f64 };
+    let max_dr = (distance / configs.resolution_y).floor() as isize;
+    let max_dc = (distance / configs.resolution_x).ceil() as isize + 1;
+    let mut offsets = vec![];
+    for dr in -max_dr..=max_dr {
+        for dc in -max_dc..=max_dc {
+            if dr == 0 && dc == 0 {
+                continue;
+            }
+            let dx = (dc as f64 + shift(parity + dr) - shift(parity)) * configs.resolution_x;
+            let dy = dr as f64 * configs.resolution_y;
+            if dx * dx + dy * dy <= distance * distance {
+                offsets.push((dr, dc));
+            }
+        }
+    }
+    offsets
+}
+
+/// Reads a numeric attribute and creates the spatial weights of the features with
+/// non-null values, returning the weights, the values in feature order, and the feature
+/// number of each record.
+pub(super) fn vector_weights(
+    input: &Shapefile,
+    field_name: &str,
+    options: &WeightsOptions,
+) -> Result<(WeightsList, Vec<f64>, Vec<Option<usize>>), Error> {
+    let field_index = match input.attributes.get_field_num(field_name) {
+        Some(i) => i,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The specified field name does not exist in input shapefile.",
+            ))
+        }
+    };
+    if !input.attributes.is_field_numeric(field_index) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The specified attribute field is non-numeric.",
+        ));
+    }
+    let is_polygon = input.header.shape_type.base_shape_type() == ShapeType::Polygon;
+
+    let mut values = vec![];
+    let mut feature_nums = vec![None; input.num_records];
+    let mut records = vec![];
+    for record_num in 0..input.num_records {
+        let value = match input.attributes.get_value(record_num, field_name) {
+            FieldData::Int(val) => val as f64,
+            FieldData::Real(val) => val,
+            _ => continue, // likely a null field
+        };
+        let record = input.get_record(record_num);
+        if record.num_points == 0 {
+            continue;
+        }
+        feature_nums[record_num] = Some(values.len());
+        values.push(value);
+        records.push(record);
+    }
+
+    let mut weights = match options.weights_type {
+        WeightsType::Queen | WeightsType::Rook => {
+            if !is_polygon {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Contiguity weights require POLYGON vector data; use distance or knn weights instead.",
+                ));
+            }
+            let features: Vec<Vec<Vec<Point2D>>> = records
+                .iter()
+                .map(|record| {
+                    (0..record.num_parts as usize)
+                        .map(|part| {
+                            let part_start = record.parts[part] as usize;
+                            let part_end = if part < record.num_parts as usize - 1 {
+                                record.parts[part + 1] as usize
+                            } else {
+                                record.num_points as usize
+                            };
+                            record.points[part_start..part_end].to_vec()
+                        })
+                        .collect()
+                })
+                .collect();
+            WeightsList::from_polygon_contiguity(
+                &features,
+                options.weights_type == WeightsType::Rook,
+            )
+        }
+        WeightsType::Bishop => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Bishop's contiguity is only defined for rasters.",
+            ))
+        }
+        WeightsType::Distance => {
+            if options.distance <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A positive distance (--distance) must be specified for distance weights.",
+                ));
+            }
+            let points: Vec<Point2D> = records.iter().map(|r| centroid(r, is_polygon)).collect();
+            WeightsList::from_distance_band(&points, options.distance)
+        }
+        WeightsType::KNearest => {
+            let points: Vec<Point2D> = records.iter().map(|r| centroid(r, is_polygon)).collect();
+            WeightsList::from_k_nearest(&points, options.num_neighbours.max(1))
+        }
+    };
+    if options.row_standardize {
+        weights.row_standardize();
+    }
+    Ok((weights, values, feature_nums))
+}
+
+/// Returns the area-weighted centroid of a polygon, or the mean vertex of other
+/// geometries. Holes, which are wound in the opposite direction to the outer rings,
+/// have negative signed areas and are thereby subtracted.
+fn centroid(record: &ShapefileGeometry, is_polygon: bool) -> Point2D {
+    let num_points = record.num_points as usize;
+    if is_polygon {
+        let (mut area, mut cx, mut cy) = (0f64, 0f64, 0f64);
+        for part in 0..record.num_parts as usize {
+            let part_start = record.parts[part] as usize;
+            let part_end = if part < record.num_parts as usize - 1 {
+                record.parts[part + 1] as usize
+            } else {
+                num_points
+            };
+            for i in part_start..part_end.saturating_sub(1) {
+                let (p, q) = (record.points[i], record.points[i + 1]);
+                let cross = p.x * q.y - q.x * p.y;
+                area += cross;
+                cx += (p.x + q.x) * cross;
+                cy += (p.y + q.y) * cross;
+            }
+        }
+        if area.abs() > 0f64 {
+            return Point2D::new(cx / (3f64 * area), cy / (3f64 * area));
+        }
+    }
+    let (mut x, mut y) = (0f64, 0f64);
+    for p in &record.points {
+        x += p.x;
+        y += p.y;
+    }
+    Point2D::new(x / num_points as f64, y / num_points as f64)
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 16/12/2017
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -68,7 +68,7 @@ use std::thread;
 /// **NoData **values in the input image are ignored during the analysis.
 ///
 /// # See Also
-/// `ImageCorrelation`, `ImageCorrelationNeighbourhoodAnalysis`
+/// `ImageCorrelation`, `ImageCorrelationNeighbourhoodAnalysis`, `GlobalSpatialAutocorrelation`, `LocalSpatialAutocorrelation`
 pub struct ImageAutocorrelation {
     name: String,
     description: String,
@@ -306,22 +306,21 @@ impl WhiteboxTool for ImageAutocorrelation {
         if verbose {
             println!("Calculating image averages...");
         }
+        let mut base_image: Option<Arc<Raster>> = None;
         for a in 0..num_files {
             let value = &file_names[a]; //files_vec[a];
             let input_file = value.trim(); //.to_owned();
-            let input = Arc::new(Raster::new(&input_file, "r")?);
+            let input = Raster::new(&input_file, "r")?;
+            let input = match base_image {
+                // make sure the inputs share the same grid and CRS
+                Some(ref base) => Arc::new(align_raster_input(base, input)?),
+                None => Arc::new(input),
+            };
             let nodata = input.configs.nodata;
             if a == 0 {
                 rows = input.configs.rows as isize;
                 columns = input.configs.columns as isize;
-            } else {
-                if input.configs.columns as isize != columns || input.configs.rows as isize != rows
-                {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All input images must have the same dimensions (rows and columns).",
-                    ));
-                }
+                base_image = Some(input.clone());
             }
 
             for tid in 0..num_procs {
@@ -243,22 +243,21 @@ impl WhiteboxTool for ImageCorrelation {
         if verbose {
             println!("Calculating image averages...");
         }
+        let mut base_image: Option<Arc<Raster>> = None;
         for a in 0..num_files {
             let value = &file_names[a]; //files_vec[a];
             let input_file = value.trim(); //.to_owned();
-            let input = Arc::new(Raster::new(&input_file, "r")?);
+            let input = Raster::new(&input_file, "r")?;
+            let input = match base_image {
+                // make sure the inputs share the same grid and CRS
+                Some(ref base) => Arc::new(align_raster_input(base, input)?),
+                None => Arc::new(input),
+            };
             let nodata = input.configs.nodata;
             if a == 0 {
                 rows = input.configs.rows as isize;
                 columns = input.configs.columns as isize;
-            } else {
-                if input.configs.columns as isize != columns || input.configs.rows as isize != rows
-                {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All input images must have the same dimensions (rows and columns).",
-                    ));
-                }
+                base_image = Some(input.clone());
             }
 
             for tid in 0..num_procs {
@@ -303,6 +302,15 @@ impl WhiteboxTool for ImageCorrelation {
             }
         }
         let image_averages = Arc::new(image_averages);
+        let base_image = match base_image {
+            Some(image) => image,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "No valid input images were specified.",
+                ))
+            }
+        };
 
         if verbose {
             println!("Calculating the correlation matrix:");
@@ -310,13 +318,16 @@ impl WhiteboxTool for ImageCorrelation {
         let mut i = 0;
         for a in 0..num_files {
             let value = &file_names[a];
-            let image1 = Arc::new(Raster::new(&value, "r")?);
+            let image1 = Arc::new(align_raster_input(&base_image, Raster::new(&value, "r")?)?);
             let nodata1 = image1.configs.nodata;
             for b in 0..(i + 1) {
                 if a == b {
                     correlation_matrix[a][b] = 1.0;
                 } else {
-                    let image2 = Arc::new(Raster::new(&file_names[b], "r")?);
+                    let image2 = Arc::new(align_raster_input(
+                        &base_image,
+                        Raster::new(&file_names[b], "r")?,
+                    )?);
                     let nodata2 = image2.configs.nodata;
 
                     let (tx, rx) = mpsc::channel();
@@ -295,10 +295,11 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
         let columns = image1.configs.columns as isize;
         let nodata1 = image1.configs.nodata;
 
-        let image2 = Arc::new(Raster::new(&input_file2, "r")?);
-        if image2.configs.rows as isize != rows || image2.configs.columns as isize != columns {
-            panic!("Error: The input files do not contain the same raster extent.");
-        }
+        // make sure the inputs share the same grid and CRS
+        let image2 = Arc::new(align_raster_input(
+            &image1,
+            Raster::new(&input_file2, "r")?,
+        )?);
         let nodata2 = image2.configs.nodata;
 
         // The r-value output
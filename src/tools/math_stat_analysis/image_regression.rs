@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 27/09/2017
-Last Modified: 18/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -10,6 +10,7 @@ use self::statrs::distribution::{FisherSnedecor, StudentsT, Univariate};
 use crate::raster::*;
 use crate::rendering::Scattergram;
 use crate::tools::*;
+use crate::utils::random;
 use num_cpus;
 use rand::prelude::*;
 use statrs;
@@ -302,10 +303,11 @@ impl WhiteboxTool for ImageRegression {
         let columns = input1.configs.columns as isize;
         let nodata1 = input1.configs.nodata;
 
-        let input2 = Arc::new(Raster::new(&input_file2, "r")?);
-        if input2.configs.rows as isize != rows || input2.configs.columns as isize != columns {
-            panic!("Error: The input files do not contain the same raster extent.");
-        }
+        // make sure the inputs share the same grid and CRS
+        let input2 = Arc::new(align_raster_input(
+            &input1,
+            Raster::new(&input_file2, "r")?,
+        )?);
         let nodata2 = input2.configs.nodata;
 
         let start = Instant::now();
@@ -741,7 +743,7 @@ impl WhiteboxTool for ImageRegression {
             let mut series_xdata = vec![];
             let mut series_ydata = vec![];
             let mut series_names = vec![];
-            let mut rng = thread_rng();
+            let mut rng = random::rng(0);
             let mut sample_num = 0usize;
             let (mut x, mut y): (f64, f64);
             while sample_num < num_samples {
@@ -227,17 +227,12 @@ impl WhiteboxTool for InPlaceAdd {
             if verbose {
                 println!("Reading data...")
             };
-            let in2 = Raster::new(&input2, "r")?;
+            // make sure the input files share the same grid and CRS
+            let in2 = align_raster_input(&in1, Raster::new(&input2, "r")?)?;
 
             start = Instant::now();
             let nodata2 = in2.configs.nodata;
 
-            // make sure the input files have the same size
-            if in1.configs.rows != in2.configs.rows || in1.configs.columns != in2.configs.columns {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                    "The input files must have the same number of rows and columns and spatial extent."));
-            }
-
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = in1.get_value(row, col);
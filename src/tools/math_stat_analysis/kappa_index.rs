@@ -223,10 +223,8 @@ impl WhiteboxTool for KappaIndex {
         let min1 = input1.configs.minimum.round() as i32;
         let max1 = input1.configs.maximum.round() as i32;
 
-        let input2 = Raster::new(&input_file2, "r")?;
-        if input2.configs.rows as isize != rows || input2.configs.columns as isize != columns {
-            panic!("Error: The input files do not contain the same raster extent.");
-        }
+        // make sure the inputs share the same grid and CRS
+        let input2 = align_raster_input(&input1, Raster::new(&input_file2, "r")?)?;
         let nodata2 = input2.configs.nodata;
         let min2 = input2.configs.minimum.round() as i32;
         let max2 = input2.configs.maximum.round() as i32;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 02/01/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -10,6 +10,7 @@ use crate::raster::*;
 use crate::rendering::html::*;
 use crate::rendering::Histogram;
 use crate::tools::*;
+use crate::utils::random;
 use rand::prelude::*;
 use std::env;
 use std::f64;
@@ -283,7 +284,7 @@ impl WhiteboxTool for KsTestForNormality {
         } else {
             // Calculate the mean and total_deviation from a random sample.
             // Note that this is sampling with replacement.
-            let mut rng = thread_rng();
+            let mut rng = random::rng(0);
             // let row_rng = Range::new(0, rows as isize);
             // let col_rng = Range::new(0, columns as isize);
             let (mut row, mut col, mut cell_index): (isize, isize, isize);
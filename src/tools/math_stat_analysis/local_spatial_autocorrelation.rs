@@ -0,0 +1,533 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::global_spatial_autocorrelation::{
+    push_weights_parameters, raster_weights, vector_weights, WeightsOptions,
+};
+use crate::raster::*;
+use crate::structures::{local_test, LocalStatistic, LocalTest, Moments, SpatialWeights};
+use crate::tools::*;
+use crate::utils::random;
+use crate::vector::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// The number of features processed with each random number stream.
+const BLOCK_SIZE: usize = 1024;
+
+/// This tool calculates a local indicator of spatial association (LISA) for each grid cell of a raster, or
+/// for each feature of a vector with a numeric attribute (`--field`), identifying statistically significant
+/// clusters of similar values (hot and cold spots) and spatial outliers. The local statistic (`--statistic`)
+/// may be one of:
+///
+/// - 'moran': the local Moran's *I* (Anselin, 1995). Positive values indicate that a feature is surrounded by
+///   similar values (a cluster of high or low values) and negative values indicate a spatial outlier, i.e. a
+///   high value surrounded by low values, or vice versa;
+/// - 'geary': the local Geary's *c* (Anselin, 1995), the weighted sum of the squared differences between a
+///   feature's value and its neighbours', in units of the variance. Small values indicate positive local
+///   autocorrelation; or
+/// - 'gi_star': the Getis-Ord *Gi** statistic (Getis and Ord, 1992; Ord and Getis, 1995), the ratio of the
+///   weighted sum of the values of the feature and its neighbours to the sum of all values. Large positive
+///   z-scores indicate clusters of high values (hot spots) and large negative z-scores indicate clusters of
+///   low values (cold spots). The feature itself is included, with the largest of its neighbours' weights.
+///
+/// The neighbours of each feature are defined by the spatial weights (`--weights`, `--distance`,
+/// `--num_neighbours`, and `--row_standardize`), as described for the `GlobalSpatialAutocorrelation` tool.
+///
+/// For each feature, the tool outputs the statistic, its z-score, and a pseudo p-value. The z-scores of
+/// the local Moran's *I* and Geary's *c* are based on the moments of the statistics under conditional
+/// randomization, in which the feature's value is held fixed and its neighbours' values are drawn at random
+/// from those of the other features; the z-score of the *Gi** statistic is that of Ord and Getis (1995).
+/// Pseudo p-values are estimated from the statistics of `--permutations` (99 by default) conditional
+/// permutations and are folded, i.e. they measure the significance of the statistic in the direction of the
+/// nearer tail. Set `--permutations` to 0 to skip this relatively time-consuming step. Note that because a
+/// test is performed for each feature, the individual p-values overstate the significance of the pattern
+/// as a whole. Use the global `--seed` flag for reproducible pseudo p-values.
+///
+/// For raster inputs, the statistic, z-scores, and pseudo p-values are written to the output raster
+/// (`--output`) and to two rasters with the same name and '_z' and '_p' suffixes, respectively (e.g.
+/// 'lisa.tif', 'lisa_z.tif', and 'lisa_p.tif'). For vector inputs, the output vector contains the input
+/// features and attributes with three additional fields, e.g. LMI, LMI_Z, and LMI_P for the local Moran's
+/// *I*, LGC for Geary's *c*, and GI for *Gi**. **NoData** cells, features with null attribute values, and
+/// features without neighbours are assigned NoData and null values.
+///
+/// # References
+/// Anselin, L. (1995). Local indicators of spatial association—LISA. Geographical Analysis, 27(2), 93-115.
+///
+/// Getis, A., and Ord, J. K. (1992). The analysis of spatial association by use of distance statistics.
+/// Geographical Analysis, 24(3), 189-206.
+///
+/// Ord, J. K., and Getis, A. (1995). Local spatial autocorrelation statistics: distributional issues and an
+/// application. Geographical Analysis, 27(4), 286-306.
+///
+/// # See Also
+/// `GlobalSpatialAutocorrelation`, `ImageAutocorrelation`
+pub struct LocalSpatialAutocorrelation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LocalSpatialAutocorrelation {
+    pub fn new() -> LocalSpatialAutocorrelation {
+        // public constructor
+        let name = "LocalSpatialAutocorrelation".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description =
+            "Calculates local Moran's I, Geary's c, or Getis-Ord Gi* statistics for a raster or vector attribute."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster or Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster or vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table (vector inputs only).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster or vector file, of the same type as the input.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Local Statistic".to_owned(),
+            flags: vec!["--statistic".to_owned()],
+            description: "Local statistic; options include 'moran', 'geary', and 'gi_star'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "moran".to_owned(),
+                "geary".to_owned(),
+                "gi_star".to_owned(),
+            ]),
+            default_value: Some("moran".to_owned()),
+            optional: true,
+        });
+
+        push_weights_parameters(&mut parameters, 99);
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=ndvi.tif -o=lisa.tif --statistic=moran --weights=queen
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=tracts.shp --field=RATE -o=hotspots.shp --statistic=gi_star --weights=distance --distance=5000.0 --permutations=999",
+            short_exe, name
+        ).replace("*", &sep);
+
+        LocalSpatialAutocorrelation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LocalSpatialAutocorrelation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut output_file = String::new();
+        let mut statistic_name = String::from("moran");
+        let mut options = WeightsOptions::new(99);
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-field" {
+                field_name = val;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-statistic" {
+                statistic_name = val;
+            } else {
+                options.parse_arg(&flag_val, &vec, &val);
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let statistic = match LocalStatistic::from_name(&statistic_name) {
+            Some(s) => s,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Unrecognized statistic. Options include 'moran', 'geary', and 'gi_star'.",
+                ))
+            }
+        };
+        let is_vector = input_file.to_lowercase().ends_with(".shp");
+        if is_vector != output_file.to_lowercase().ends_with(".shp") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The output file must be of the same type (raster or vector) as the input file.",
+            ));
+        }
+        let num_outputs = if options.permutations > 0 { 3 } else { 2 };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        if is_vector {
+            let input = Shapefile::read(&input_file)?;
+
+            let start = Instant::now();
+
+            let (weights, values, feature_nums) = vector_weights(&input, &field_name, &options)?;
+            let results = local_tests(
+                Arc::new(weights),
+                Arc::new(values),
+                statistic,
+                options.permutations,
+                verbose,
+            )?;
+
+            // create output file
+            let mut output = Shapefile::initialize_using_file(
+                &output_file,
+                &input,
+                input.header.shape_type,
+                true,
+            )?;
+            let prefix = match statistic {
+                LocalStatistic::MoransI => "LMI",
+                LocalStatistic::GearysC => "LGC",
+                LocalStatistic::GetisOrdGiStar => "GI",
+            };
+            output.attributes.add_field(&AttributeField::new(
+                prefix,
+                FieldDataType::Real,
+                12u8,
+                6u8,
+            ));
+            output.attributes.add_field(&AttributeField::new(
+                &format!("{}_Z", prefix),
+                FieldDataType::Real,
+                12u8,
+                6u8,
+            ));
+            if num_outputs == 3 {
+                output.attributes.add_field(&AttributeField::new(
+                    &format!("{}_P", prefix),
+                    FieldDataType::Real,
+                    12u8,
+                    6u8,
+                ));
+            }
+
+            for record_num in 0..input.num_records {
+                let record = input.get_record(record_num);
+                output.add_record(record.clone());
+
+                let mut atts = input.attributes.get_record(record_num);
+                match feature_nums[record_num].and_then(|i| results[i]) {
+                    Some(result) => {
+                        atts.push(FieldData::Real(result.statistic));
+                        atts.push(FieldData::Real(result.z_score));
+                        if num_outputs == 3 {
+                            atts.push(FieldData::Real(result.pseudo_p));
+                        }
+                    }
+                    None => {
+                        for _ in 0..num_outputs {
+                            atts.push(FieldData::Null);
+                        }
+                    }
+                }
+                output.attributes.add_record(atts, false);
+            }
+
+            let elapsed_time = get_formatted_elapsed_time(start);
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+            if verbose {
+                println!(
+                    "{}",
+                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                );
+            }
+        } else {
+            let input = Raster::new(&input_file, "r")?;
+
+            let start = Instant::now();
+
+            let (weights, values) = raster_weights(&input, &options)?;
+            let weights = Arc::new(weights);
+            let results = local_tests(
+                weights.clone(),
+                Arc::new(values),
+                statistic,
+                options.permutations,
+                verbose,
+            )?;
+
+            let extension = match output_file.rfind('.') {
+                Some(i) if !output_file[i..].contains(&sep) && !output_file[i..].contains('/') => {
+                    output_file[i..].to_string()
+                }
+                _ => String::new(),
+            };
+            let stem = output_file[..output_file.len() - extension.len()].to_string();
+            let file_names = [
+                output_file.clone(),
+                format!("{}_z{}", stem, extension),
+                format!("{}_p{}", stem, extension),
+            ];
+            let nodata = -32768f64;
+            let mut outputs = vec![];
+            for a in 0..num_outputs {
+                let mut output = Raster::initialize_using_file(&file_names[a], &input);
+                output.configs.nodata = nodata;
+                output.configs.data_type = DataType::F32;
+                output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+                output.configs.palette = "spectrum.plt".to_string();
+                output.reinitialize_values(nodata);
+                outputs.push(output);
+            }
+            for (i, result) in results.iter().enumerate() {
+                if let Some(result) = result {
+                    let (row, col) = weights.cell(i);
+                    outputs[0].set_value(row, col, result.statistic);
+                    outputs[1].set_value(row, col, result.z_score);
+                    if num_outputs == 3 {
+                        outputs[2].set_value(row, col, result.pseudo_p);
+                    }
+                }
+            }
+
+            let elapsed_time = get_formatted_elapsed_time(start);
+            if verbose {
+                println!("Saving data...")
+            };
+            let labels = ["statistic", "z-score", "pseudo p-value"];
+            for (a, mut output) in outputs.into_iter().enumerate() {
+                output.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                output.add_metadata_entry(format!("Input file: {}", input_file));
+                output.add_metadata_entry(format!("Statistic: {}", statistic_name));
+                output.add_metadata_entry(format!("Values: {}", labels[a]));
+                output.add_metadata_entry(format!("Spatial weights: {}", options.description()));
+                if a == 2 {
+                    output.add_metadata_entry(format!("Permutations: {}", options.permutations));
+                }
+                output
+                    .add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+                output.write()?;
+                if verbose {
+                    progress = (100.0_f64 * (a + 1) as f64 / num_outputs as f64) as usize;
+                    if progress != old_progress {
+                        println!("Saving: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            if verbose {
+                println!("Output files written");
+                println!(
+                    "{}",
+                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Calculates and tests the local statistic of each feature, returning `None` for features
+/// without neighbours. Blocks of features are processed in parallel, each with its own
+/// random number stream.
+fn local_tests<W: SpatialWeights + Send + Sync + 'static>(
+    weights: Arc<W>,
+    values: Arc<Vec<f64>>,
+    statistic: LocalStatistic,
+    permutations: usize,
+    verbose: bool,
+) -> Result<Vec<Option<LocalTest>>, Error> {
+    let n = values.len();
+    if n < 3 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "At least three cells or features with valid values are required.",
+        ));
+    }
+    let moments = Moments::new(&values);
+    if moments.sum2 <= 0f64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The input values are constant; spatial autocorrelation is undefined.",
+        ));
+    }
+
+    let num_blocks = (n + BLOCK_SIZE - 1) / BLOCK_SIZE;
+    let num_procs = num_cpus::get();
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let weights = weights.clone();
+        let values = values.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for block in (0..num_blocks).filter(|b| b % num_procs == tid) {
+                let mut rng = random::rng(block as u64);
+                let results: Vec<Option<LocalTest>> = (block * BLOCK_SIZE
+                    ..((block + 1) * BLOCK_SIZE).min(n))
+                    .map(|i| {
+                        local_test(
+                            &*weights,
+                            &values,
+                            &moments,
+                            i,
+                            statistic,
+                            permutations,
+                            &mut rng,
+                        )
+                    })
+                    .collect();
+                tx.send((block, results)).unwrap();
+            }
+        });
+    }
+
+    let mut output = vec![None; n];
+    let mut old_progress: usize = 1;
+    for b in 0..num_blocks {
+        let (block, results) = rx.recv().expect("Error receiving data from thread.");
+        for (k, result) in results.into_iter().enumerate() {
+            output[block * BLOCK_SIZE + k] = result;
+        }
+        if verbose {
+            let progress = (100.0_f64 * (b + 1) as f64 / num_blocks as f64) as usize;
+            if progress != old_progress {
+                println!("Progress: {}%", progress);
+                old_progress = progress;
+            }
+        }
+    }
+    Ok(output)
+}
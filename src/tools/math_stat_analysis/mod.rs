@@ -26,6 +26,8 @@ mod equal_to;
 mod exp;
 mod exp2;
 mod floor;
+mod gaussian_simulation;
+mod global_spatial_autocorrelation;
 mod greater_than;
 mod image_autocorrelation;
 mod image_correlation;
@@ -42,6 +44,7 @@ mod kappa_index;
 mod ks_normality_test;
 mod less_than;
 mod list_unique_values;
+mod local_spatial_autocorrelation;
 mod ln;
 mod log10;
 mod log2;
@@ -53,6 +56,7 @@ mod negate;
 mod not;
 mod not_equal_to;
 mod or;
+mod ordinary_kriging;
 mod paired_sample_t_test;
 mod power;
 mod principal_component_analysis;
@@ -65,6 +69,7 @@ mod reciprocal;
 mod rescale_value_range;
 mod root_mean_square_error;
 mod round;
+mod semivariogram_analysis;
 mod sin;
 mod sinh;
 mod sqrt;
@@ -112,6 +117,8 @@ pub use self::equal_to::EqualTo;
 pub use self::exp::Exp;
 pub use self::exp2::Exp2;
 pub use self::floor::Floor;
+pub use self::gaussian_simulation::GaussianSimulation;
+pub use self::global_spatial_autocorrelation::GlobalSpatialAutocorrelation;
 pub use self::greater_than::GreaterThan;
 pub use self::image_autocorrelation::ImageAutocorrelation;
 pub use self::image_correlation::ImageCorrelation;
@@ -128,6 +135,7 @@ pub use self::kappa_index::KappaIndex;
 pub use self::ks_normality_test::KsTestForNormality;
 pub use self::less_than::LessThan;
 pub use self::list_unique_values::ListUniqueValues;
+pub use self::local_spatial_autocorrelation::LocalSpatialAutocorrelation;
 pub use self::ln::Ln;
 pub use self::log10::Log10;
 pub use self::log2::Log2;
@@ -139,6 +147,7 @@ pub use self::negate::Negate;
 pub use self::not::Not;
 pub use self::not_equal_to::NotEqualTo;
 pub use self::or::Or;
+pub use self::ordinary_kriging::OrdinaryKriging;
 pub use self::paired_sample_t_test::PairedSampleTTest;
 pub use self::power::Power;
 pub use self::principal_component_analysis::PrincipalComponentAnalysis;
@@ -151,6 +160,7 @@ pub use self::reciprocal::Reciprocal;
 pub use self::rescale_value_range::RescaleValueRange;
 pub use self::root_mean_square_error::RootMeanSquareError;
 pub use self::round::Round;
+pub use self::semivariogram_analysis::SemivariogramAnalysis;
 pub use self::sin::Sin;
 pub use self::sinh::Sinh;
 pub use self::sqrt::SquareRoot;
@@ -0,0 +1,760 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::na::{DMatrix, DVector};
+use crate::raster::*;
+use crate::structures::{EmpiricalSemivariogram, Point2D, Semivariogram, VariogramModel};
+use crate::tools::*;
+use crate::vector::{FieldData, ShapeType, ShapeTypeDimension, Shapefile};
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool interpolates a set of input vector points (`--input`) onto a raster grid (`--output`) using
+/// [ordinary kriging](https://en.wikipedia.org/wiki/Kriging). Kriging is a geostatistical interpolation
+/// method in which the interpolated value at each grid cell is a weighted average of nearby sample values,
+/// with the weights chosen to minimize the estimation variance given a model of the spatial autocorrelation
+/// of the data, i.e. the semivariogram. Ordinary kriging assumes that the mean of the data is constant but
+/// unknown within the local neighbourhood of each estimated location.
+///
+/// The user must specify the attribute field containing point values (`--field`). Alternatively, if the input
+/// Shapefile contains z-values, the interpolation may be based on these values (`--use_z`). Either an output grid
+/// resolution (`--cell_size`) must be specified or alternatively an existing base file (`--base`) can be used to
+/// determine the output raster's resolution and spatial extent.
+///
+/// By default, a semivariogram model (`--model`; 'spherical', 'exponential', or 'gaussian') is fitted to the
+/// empirical semivariogram of the data by weighted least-squares. The empirical semivariogram is calculated using
+/// `--num_lags` lag classes of width `--lag_size`; if the lag size is unspecified, the lags span one-half of the
+/// diagonal length of the data's bounding box. Alternatively, the semivariogram may be fully specified by the user
+/// by providing the model range (`--range`), in which case the partial sill (`--sill`; defaults to the sample
+/// variance) and nugget (`--nugget`; defaults to zero) are also used without fitting. The `SemivariogramAnalysis`
+/// tool can be used to examine the empirical semivariogram and fitted models prior to interpolation.
+///
+/// The estimate at each grid cell is based on the `--num_neighbours` nearest sample points (default 16). The
+/// kriging variance, which is a measure of the estimation uncertainty, can optionally be output as a second
+/// raster (`--variance`). Note that the empirical semivariogram calculation considers all pairs of points, and
+/// therefore this tool is best suited to interpolating sparse samples rather than very dense point clouds, such
+/// as LiDAR data.
+///
+/// # Reference
+/// Isaaks, E. H., and Srivastava, R. M. (1989). An Introduction to Applied Geostatistics. Oxford University
+/// Press, New York.
+///
+/// # See Also
+/// `SemivariogramAnalysis`, `NaturalNeighbourInterpolation`, `IdwInterpolation`, `TrendSurfaceVectorPoints`
+pub struct OrdinaryKriging {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl OrdinaryKriging {
+    pub fn new() -> OrdinaryKriging {
+        // public constructor
+        let name = "OrdinaryKriging".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description =
+            "Interpolates vector points onto a raster grid using ordinary kriging.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Use Shapefile 'z' values?".to_owned(),
+            flags: vec!["--use_z".to_owned()],
+            description:
+                "Use the 'z' dimension of the Shapefile's geometry instead of an attribute field?"
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Kriging Variance Raster File (optional)".to_owned(),
+            flags: vec!["--variance".to_owned()],
+            description: "Optional output kriging variance raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Semivariogram Model".to_owned(),
+            flags: vec!["--model".to_owned()],
+            description: "Semivariogram model; options include 'spherical', 'exponential', and 'gaussian'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "spherical".to_owned(),
+                "exponential".to_owned(),
+                "gaussian".to_owned(),
+            ]),
+            default_value: Some("spherical".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Lag Size (optional)".to_owned(),
+            flags: vec!["--lag_size".to_owned()],
+            description: "Width of the empirical semivariogram lag classes.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Lags".to_owned(),
+            flags: vec!["--num_lags".to_owned()],
+            description: "Number of empirical semivariogram lag classes.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("15".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Nugget (optional)".to_owned(),
+            flags: vec!["--nugget".to_owned()],
+            description: "Semivariogram nugget; only used when the range is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Partial Sill (optional)".to_owned(),
+            flags: vec!["--sill".to_owned()],
+            description: "Semivariogram partial sill; only used when the range is specified."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Range (optional)".to_owned(),
+            flags: vec!["--range".to_owned()],
+            description: "Semivariogram range; if specified, the model is not fitted to the data."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours".to_owned(),
+            flags: vec!["--num_neighbours".to_owned()],
+            description: "Number of nearest sample points used in each estimate.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter{
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true
+        });
+
+        parameters.push(ToolParameter{
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified input base raster file. Not used when a cell size is specified.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=ELEV -o=surface.tif --model=exponential --cell_size=10.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --use_z -o=surface.tif --variance=variance.tif --nugget=0.5 --sill=12.0 --range=850.0 --num_neighbours=24 --base=dem.tif",
+            short_exe, name
+        ).replace("*", &sep);
+
+        OrdinaryKriging {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for OrdinaryKriging {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut use_z = false;
+        let mut use_field = false;
+        let mut output_file = String::new();
+        let mut variance_file = String::new();
+        let mut model_name = String::from("spherical");
+        let mut lag_size = 0f64;
+        let mut num_lags = 15usize;
+        let mut nugget: Option<f64> = None;
+        let mut sill: Option<f64> = None;
+        let mut range = 0f64;
+        let mut num_neighbours = 16usize;
+        let mut grid_res = 0f64;
+        let mut base_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-field" {
+                field_name = val;
+                use_field = true;
+            } else if flag_val.contains("use_z") {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    use_z = true;
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-variance" {
+                variance_file = val;
+            } else if flag_val == "-model" {
+                model_name = val.to_lowercase();
+            } else if flag_val == "-lag_size" {
+                lag_size = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-num_lags" {
+                num_lags = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-nugget" {
+                nugget = Some(
+                    val.parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)),
+                );
+            } else if flag_val == "-sill" {
+                sill = Some(
+                    val.parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)),
+                );
+            } else if flag_val == "-range" {
+                range = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-num_neighbours" || flag_val == "-num_neighbors" {
+                num_neighbours = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-resolution" || flag_val == "-cell_size" {
+                grid_res = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-base" {
+                base_file = val;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !variance_file.is_empty() && !variance_file.contains(&sep) && !variance_file.contains("/")
+        {
+            variance_file = format!("{}{}", working_directory, variance_file);
+        }
+
+        let model = match VariogramModel::from_name(&model_name) {
+            Some(m) => m,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Unrecognized semivariogram model. Options include 'spherical', 'exponential', and 'gaussian'.",
+                ))
+            }
+        };
+        if num_lags < 2 {
+            num_lags = 2;
+        }
+        if num_neighbours < 3 {
+            num_neighbours = 3;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        // make sure the input vector file is of points type
+        if input.header.shape_type.base_shape_type() != ShapeType::Point
+            && input.header.shape_type.base_shape_type() != ShapeType::MultiPoint
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of POINT base shape type.",
+            ));
+        }
+
+        if !use_z && !use_field {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "If vector data 'Z' data are unavailable (--use_z), an attribute field must be specified (--field=).",
+            ));
+        }
+
+        if use_z && input.header.shape_type.dimension() != ShapeTypeDimension::Z {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of 'POINTZ' or 'MULTIPOINTZ' ShapeType to use the --use_z flag.",
+            ));
+        } else if use_field {
+            // What is the index of the field to be analyzed?
+            let field_index = match input.attributes.get_field_num(&field_name) {
+                Some(i) => i,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The specified field name does not exist in input shapefile.",
+                    ))
+                }
+            };
+
+            // Is the field numeric?
+            if !input.attributes.is_field_numeric(field_index) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified attribute field is non-numeric.",
+                ));
+            }
+        }
+
+        let mut points: Vec<Point2D> = Vec::with_capacity(input.get_total_num_points());
+        let mut z_values: Vec<f64> = Vec::with_capacity(input.get_total_num_points());
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let field_value = if use_z {
+                None
+            } else {
+                match input.attributes.get_value(record_num, &field_name) {
+                    FieldData::Int(val) => Some(val as f64),
+                    FieldData::Real(val) => Some(val),
+                    _ => None, // likely a null field
+                }
+            };
+            for i in 0..record.num_points as usize {
+                let z = if use_z {
+                    record.z_array[i]
+                } else {
+                    match field_value {
+                        Some(v) => v,
+                        None => continue,
+                    }
+                };
+                points.push(Point2D::new(record.points[i].x, record.points[i].y));
+                z_values.push(z);
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Reading points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let n = points.len();
+        if n < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least three points with valid values are required for kriging.",
+            ));
+        }
+        if num_neighbours > n {
+            num_neighbours = n;
+        }
+
+        // the semivariogram model
+        let semivariogram = if range > 0f64 {
+            let sill = match sill {
+                Some(s) => s,
+                None => {
+                    let mean = z_values.iter().sum::<f64>() / n as f64;
+                    z_values.iter().map(|z| (z - mean) * (z - mean)).sum::<f64>() / n as f64
+                }
+            };
+            Semivariogram::new(model, nugget.unwrap_or(0f64), sill, range)
+        } else {
+            if verbose {
+                println!("Fitting semivariogram model...");
+            }
+            if lag_size <= 0f64 {
+                let width = input.header.x_max - input.header.x_min;
+                let height = input.header.y_max - input.header.y_min;
+                lag_size = (width * width + height * height).sqrt() / 2f64 / num_lags as f64;
+            }
+            let empirical = EmpiricalSemivariogram::new(&points, &z_values, lag_size, num_lags);
+            match Semivariogram::fit(model, &empirical) {
+                Some(s) => s,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The semivariogram model could not be fitted; too few lag classes contain point pairs. Try increasing the lag size.",
+                    ))
+                }
+            }
+        };
+        if semivariogram.sill + semivariogram.nugget <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The semivariogram sill must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!(
+                "Semivariogram: {} model, nugget = {:.4}, partial sill = {:.4}, range = {:.4}",
+                semivariogram.model, semivariogram.nugget, semivariogram.sill, semivariogram.range
+            );
+        }
+
+        if base_file.trim().is_empty() && grid_res <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The specified grid resolution is incorrect. Either a non-zero grid resolution \nor an input existing base file name must be used.",
+            ));
+        }
+
+        let nodata = -32768.0f64;
+        let mut output = if !base_file.trim().is_empty() {
+            if !base_file.contains(&sep) && !base_file.contains("/") {
+                base_file = format!("{}{}", working_directory, base_file);
+            }
+            let mut base = Raster::new(&base_file, "r")?;
+            base.configs.nodata = nodata;
+            Raster::initialize_using_file(&output_file, &base)
+        } else {
+            // base the output raster on the grid_res and the
+            // extent of the input vector.
+            let west: f64 = input.header.x_min;
+            let north: f64 = input.header.y_max;
+            let rows: isize = (((north - input.header.y_min) / grid_res).ceil()) as isize;
+            let columns: isize = (((input.header.x_max - west) / grid_res).ceil()) as isize;
+            let south: f64 = north - rows as f64 * grid_res;
+            let east = west + columns as f64 * grid_res;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows as usize;
+            configs.columns = columns as usize;
+            configs.north = north;
+            configs.south = south;
+            configs.east = east;
+            configs.west = west;
+            configs.resolution_x = grid_res;
+            configs.resolution_y = grid_res;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+            Raster::initialize_using_config(&output_file, &configs)
+        };
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let west = output.configs.west;
+        let north = output.configs.north;
+        let res_x = output.configs.resolution_x;
+        let res_y = output.configs.resolution_y;
+        output.configs.nodata = nodata; // in case a base image is used with a different nodata value.
+        output.configs.palette = "spectrum.pal".to_string();
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        const DIMENSIONS: usize = 2;
+        const CAPACITY_PER_NODE: usize = 64;
+        let mut tree = KdTree::with_capacity(DIMENSIONS, CAPACITY_PER_NODE);
+        for p in 0..n {
+            tree.add([points[p].x, points[p].y], p).unwrap();
+        }
+
+        if verbose {
+            println!("Interpolating...");
+        }
+        let points = Arc::new(points);
+        let z_values = Arc::new(z_values);
+        let tree = Arc::new(tree);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let points = points.clone();
+            let z_values = z_values.clone();
+            let tree = tree.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let (mut px, mut py): (f64, f64);
+                let mut z: f64;
+                let mut var: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    let mut variance = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        px = west + (col as f64 + 0.5) * res_x;
+                        py = north - (row as f64 + 0.5) * res_y;
+                        let neighbours: Vec<usize> =
+                            match tree.nearest(&[px, py], num_neighbours, &squared_euclidean) {
+                                Ok(ret) => ret.into_iter().map(|(_, &p)| p).collect(),
+                                Err(_) => continue,
+                            };
+                        let k = neighbours.len();
+                        if k == 0 {
+                            continue;
+                        }
+
+                        // Ordinary kriging system in terms of semivariance, with the
+                        // Lagrange multiplier enforcing unbiasedness in the last row.
+                        let a = DMatrix::from_fn(k + 1, k + 1, |i, j| {
+                            if i == k && j == k {
+                                0f64
+                            } else if i == k || j == k {
+                                1f64
+                            } else {
+                                semivariogram
+                                    .value(points[neighbours[i]].distance(&points[neighbours[j]]))
+                            }
+                        });
+                        let p = Point2D::new(px, py);
+                        let gamma0: Vec<f64> = neighbours
+                            .iter()
+                            .map(|&i| semivariogram.value(points[i].distance(&p)))
+                            .collect();
+                        let b = DVector::from_fn(k + 1, |i, _| if i == k { 1f64 } else { gamma0[i] });
+                        match a.lu().solve(&b) {
+                            Some(w) => {
+                                z = 0f64;
+                                var = w[k];
+                                for i in 0..k {
+                                    z += w[i] * z_values[neighbours[i]];
+                                    var += w[i] * gamma0[i];
+                                }
+                                data[col as usize] = z;
+                                variance[col as usize] = var.max(0f64);
+                            }
+                            None => {
+                                // singular system, e.g. due to coincident points; use the nearest sample
+                                data[col as usize] = z_values[neighbours[0]];
+                            }
+                        }
+                    }
+                    tx.send((row, data, variance)).unwrap();
+                }
+            });
+        }
+
+        let mut variance_output = if !variance_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&variance_file, &output);
+            r.configs.palette = "grey.pal".to_string();
+            Some(r)
+        } else {
+            None
+        };
+        for row in 0..rows {
+            let (r, data, variance) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(r, data);
+            if let Some(ref mut vo) = variance_output {
+                vo.set_row_data(r, variance);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Interpolating: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let mut metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Input file: {}", input_file),
+            format!("Field name: {}", field_name),
+            format!("Use z-field: {}", use_z),
+            format!("Semivariogram model: {}", semivariogram.model),
+            format!("Nugget: {}", semivariogram.nugget),
+            format!("Partial sill: {}", semivariogram.sill),
+            format!("Range: {}", semivariogram.range),
+            format!("Num. neighbours: {}", num_neighbours),
+        ];
+        if grid_res > 0f64 && base_file.trim().is_empty() {
+            metadata.push(format!("Grid resolution: {}", grid_res));
+        } else {
+            metadata.push(format!("Base file: {}", base_file));
+        }
+        metadata.push(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        for entry in &metadata {
+            output.add_metadata_entry(entry.clone());
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(mut vo) = variance_output {
+            for entry in &metadata {
+                vo.add_metadata_entry(entry.clone());
+            }
+            let _ = match vo.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Variance file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -299,8 +299,14 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
                 }
 
                 // read the image
-                // let input = Raster::new(&input_file, "r")?;
-                input_raster.push(Raster::new(&input_file, "r")?);
+                let input = Raster::new(&input_file, "r")?;
+                if input_raster.is_empty() {
+                    input_raster.push(input);
+                } else {
+                    // make sure the inputs share the same grid and CRS
+                    let input = align_raster_input(&input_raster[0], input)?;
+                    input_raster.push(input);
+                }
 
                 // get the nodata value, the number of valid cells, and the average
                 nodata[i] = input_raster[i].configs.nodata;
@@ -308,17 +314,10 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
                 average[i] = input_raster[i].calculate_mean();
                 file_names.push(input_raster[i].get_short_filename());
 
-                // initialize the rows and column and check that each image has the same dimensions
+                // initialize the rows and columns
                 if rows == -1 || columns == -1 {
                     rows = input_raster[i].configs.rows as isize;
                     columns = input_raster[i].configs.columns as isize;
-                } else {
-                    if input_raster[i].configs.rows as isize != rows
-                        || input_raster[i].configs.columns as isize != columns
-                    {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
-                    }
                 }
             } else {
                 return Err(Error::new(ErrorKind::InvalidInput,
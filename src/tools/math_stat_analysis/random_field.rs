@@ -2,15 +2,15 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 11/07/2017
-Last Modified: 13/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::random;
 use num_cpus;
 use rand::prelude::*;
-use rand::rngs::SmallRng;
 use rand_distr::StandardNormal;
 use std::env;
 use std::f64;
@@ -27,6 +27,9 @@ use std::thread;
 /// the `FastAlmostGaussianFilter` tool may be used to force spatial autocorrelation onto the distribution of the
 /// `RandomField` tool.
 ///
+/// Each run produces a different field unless the global `--seed` flag is specified, in which case
+/// the same seed always yields the same field.
+///
 /// # See Also
 /// `TurningBandsSimulation`, `FastAlmostGaussianFilter`
 pub struct RandomField {
@@ -199,10 +202,10 @@ impl WhiteboxTool for RandomField {
         for tid in 0..num_procs {
             let tx = tx.clone();
             thread::spawn(move || {
-                // let mut rng = rand::thread_rng();
-                // let normal = Normal::new(0.0, 1.0);
-                let mut rng = SmallRng::from_entropy();
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    // each row has its own stream, so the output does not depend on
+                    // the number of threads
+                    let mut rng = random::rng(row as u64);
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         data[col as usize] = rng.sample(StandardNormal); //normal.ind_sample(&mut rng);
@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: January 2, 2018
-Last Modified: 13/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::random;
 use rand::prelude::*;
 use std::env;
 use std::f64;
@@ -21,7 +22,8 @@ use std::path;
 /// raster grid, and a background value of zero. This tool is useful when performing
 /// statistical analyses on raster images when you wish to obtain a random sample of data.
 ///
-/// Only valid, non-nodata, cells in the base raster will be sampled.
+/// Only valid, non-nodata, cells in the base raster will be sampled. Use the global `--seed` flag to
+/// draw the same sample in repeated runs.
 pub struct RandomSample {
     name: String,
     description: String,
@@ -218,7 +220,7 @@ impl WhiteboxTool for RandomSample {
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.reinitialize_values(0f64);
 
-        let mut rng = thread_rng();
+        let mut rng = random::rng(0);
         // let row_rng = Range::new(0, rows as isize);
         // let col_rng = Range::new(0, columns as isize);
         let mut sample_num = 0usize;
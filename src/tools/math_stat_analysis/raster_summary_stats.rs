@@ -2,24 +2,37 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/07/2017
-Last Modified: 13/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
+use super::zonal_statistics::{percentile, percentile_field_name};
 use crate::raster::*;
+use crate::rendering::html::*;
+use crate::rendering::Histogram;
 use crate::tools::*;
 use num_cpus;
+use serde_json::json;
+use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
 use std::io::{Error, ErrorKind};
 use std::path;
+use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
+/// The number of bins used to approximate percentiles when `--approx` is specified.
+const APPROX_BINS: usize = 100_000;
+
 /// This tool outputs distribution summary statistics for input raster images (`--input`).
 /// The distribution statistics include the raster minimum, maximum, range, total, mean,
-/// variance, and standard deviation. These summary statistics are output to the system `stdout`.
+/// variance, standard deviation, skewness, kurtosis, and a set of percentiles. These summary
+/// statistics are output to the system `stdout`.
 ///
 /// The following is an example of the summary report:
 ///
@@ -37,6 +50,32 @@ use std::thread;
 /// > Image average: 406.13745012929786 <br/>
 /// > Image variance: 31.370027239143383 <br/>
 /// > Image standard deviation: 5.600895217654351 <br/>
+/// > Image skewness: 0.2132860384150581 <br/>
+/// > Image kurtosis: -0.6113029484418112 <br/>
+/// > Image 25th percentile: 401.6712341308594 <br/>
+/// > Image 50th percentile: 405.8817138671875 <br/>
+/// > Image 75th percentile: 410.3076171875 <br/>
+///
+/// Skewness is the third standardized moment of the distribution and kurtosis is the excess
+/// kurtosis (i.e. the fourth standardized moment minus three), such that a normal distribution
+/// has a skewness and kurtosis of zero.
+///
+/// The percentiles to report are specified using the `--percentiles` parameter, as a comma- or
+/// semicolon-separated list of values (0-100), e.g. '5;25;50;75;95'. By default, percentiles are
+/// calculated exactly, using linear interpolation between ranked values, which requires that all
+/// of the valid values of a band are held in memory and sorted. For very large rasters, the
+/// `--approx` flag may be used to instead estimate percentiles from a fine-grained (100,000 bin)
+/// histogram that is accumulated in a single streaming pass through the data; the error of these
+/// estimates is less than 1/100,000th of the data range.
+///
+/// In addition to the `stdout` report, the statistics may be written to an HTML report (`--output`),
+/// which also contains a histogram of each band, a JSON file (`--out_json`), and/or a CSV file
+/// (`--out_csv`). The JSON report includes the histogram counts, while the CSV file contains a
+/// single row of statistics for each band. The number of histogram bins, which evenly divide the
+/// range of the data, is set using `--num_bins`.
+///
+/// For multi-band images, including RGB colour composites, the statistics are reported
+/// separately for each band.
 ///
 /// # See Also
 /// `RasterHistogram`, `ZonalStatistics`
@@ -54,7 +93,7 @@ impl RasterSummaryStats {
         let name = "RasterSummaryStats".to_string();
         let toolbox = "Math and Stats Tools".to_string();
         let description =
-            "Measures a rasters min, max, average, standard deviation, num. non-nodata cells, and total."
+            "Measures a rasters min, max, average, standard deviation, skewness, kurtosis, percentiles, num. non-nodata cells, and total."
                 .to_string();
 
         let mut parameters = vec![];
@@ -67,6 +106,63 @@ impl RasterSummaryStats {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Optional output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output JSON File".to_owned(),
+            flags: vec!["--out_json".to_owned()],
+            description: "Optional output JSON report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Optional output CSV file of per-band statistics.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Percentiles".to_owned(),
+            flags: vec!["--percentiles".to_owned()],
+            description: "List of percentiles (0-100) to calculate, e.g. '5;25;50;75;95'."
+                .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("25;50;75".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Approximate percentiles?".to_owned(),
+            flags: vec!["--approx".to_owned()],
+            description:
+                "Estimate percentiles from a streaming histogram rather than sorting the data."
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Histogram Bins".to_owned(),
+            flags: vec!["--num_bins".to_owned()],
+            description: "Number of bins in the histogram of the output reports.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("50".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -79,7 +175,8 @@ impl RasterSummaryStats {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=report.html --out_json=stats.json --percentiles='5;50;95' --approx --num_bins=100",
             short_exe, name
         )
         .replace("*", &sep);
@@ -136,6 +233,12 @@ impl WhiteboxTool for RasterSummaryStats {
         verbose: bool,
     ) -> Result<(), Error> {
         let mut input_file = String::new();
+        let mut output_html_file = String::new();
+        let mut output_json_file = String::new();
+        let mut output_csv_file = String::new();
+        let mut percentiles: Vec<f64> = vec![25f64, 50f64, 75f64];
+        let mut approx = false;
+        let mut num_bins = 50usize;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -152,15 +255,81 @@ impl WhiteboxTool for RasterSummaryStats {
             if vec.len() > 1 {
                 keyval = true;
             }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_html_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_json" {
+                output_json_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_csv" {
+                output_csv_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-percentiles" {
+                let val = if keyval {
+                    vec[1].to_string()
                 } else {
-                    input_file = args[i + 1].to_string();
+                    args[i + 1].to_string()
+                };
+                percentiles.clear();
+                for p in val.split(|c| c == ';' || c == ',') {
+                    if !p.trim().is_empty() {
+                        let p = p.trim().parse::<f64>().map_err(|_| {
+                            Error::new(
+                                ErrorKind::InvalidInput,
+                                format!("Error parsing percentile value '{}'.", p),
+                            )
+                        })?;
+                        if p < 0f64 || p > 100f64 {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                "Percentiles must be within the range 0-100.",
+                            ));
+                        }
+                        percentiles.push(p);
+                    }
                 }
+            } else if flag_val == "-approx" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    approx = true;
+                }
+            } else if flag_val == "-num_bins" {
+                num_bins = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<usize>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             }
         }
 
+        if num_bins == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of histogram bins must be greater than zero.",
+            ));
+        }
+
         if verbose {
             println!("***************{}", "*".repeat(self.get_tool_name().len()));
             println!("* Welcome to {} *", self.get_tool_name());
@@ -175,6 +344,24 @@ impl WhiteboxTool for RasterSummaryStats {
         if !input_file.contains(&sep) && !input_file.contains("/") {
             input_file = format!("{}{}", working_directory, input_file);
         }
+        if !output_html_file.is_empty()
+            && !output_html_file.contains(&sep)
+            && !output_html_file.contains("/")
+        {
+            output_html_file = format!("{}{}", working_directory, output_html_file);
+        }
+        if !output_json_file.is_empty()
+            && !output_json_file.contains(&sep)
+            && !output_json_file.contains("/")
+        {
+            output_json_file = format!("{}{}", working_directory, output_json_file);
+        }
+        if !output_csv_file.is_empty()
+            && !output_csv_file.contains(&sep)
+            && !output_csv_file.contains("/")
+        {
+            output_csv_file = format!("{}{}", working_directory, output_csv_file);
+        }
 
         if verbose {
             println!("Reading data...")
@@ -187,84 +374,229 @@ impl WhiteboxTool for RasterSummaryStats {
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
 
-        //if verbose { println!("Calculating image mean and standard deviation...") };
-        //let (mean, stdev) = input.calculate_mean_and_stdev();
-
-        let num_procs = num_cpus::get() as isize;
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let tx = tx.clone();
-            thread::spawn(move || {
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut n = 0;
-                    let mut s = 0.0;
-                    let mut sq = 0.0;
-                    let mut minz = f64::INFINITY;
-                    let mut maxz = f64::NEG_INFINITY;
-                    for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            n += 1;
-                            s += z;
-                            sq += z * z;
-                            if z < minz {
-                                minz = z;
+        let mut band_stats: Vec<BandStats> = vec![];
+        let num_bands = input.num_bands();
+        for band in 0..num_bands {
+            let num_procs = num_cpus::get() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let mut z: f64;
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut n = 0;
+                        let mut s = 0.0;
+                        let mut minz = f64::INFINITY;
+                        let mut maxz = f64::NEG_INFINITY;
+                        for col in 0..columns {
+                            z = input.get_value_band(row, col, band);
+                            if z != nodata {
+                                n += 1;
+                                s += z;
+                                if z < minz {
+                                    minz = z;
+                                }
+                                if z > maxz {
+                                    maxz = z;
+                                }
                             }
-                            if z > maxz {
-                                maxz = z;
+                        }
+                        tx.send((n, s, minz, maxz)).unwrap();
+                    }
+                });
+            }
+
+            let mut num_cells = 0;
+            let mut sum = 0.0;
+            let mut minz = f64::INFINITY;
+            let mut maxz = f64::NEG_INFINITY;
+            for row in 0..rows {
+                let (a, b, d, e) = rx.recv().expect("Error receiving data from thread.");
+                num_cells += a;
+                sum += b;
+                if d < minz {
+                    minz = d;
+                }
+                if e > maxz {
+                    maxz = e;
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        if num_bands > 1 {
+                            println!(
+                                "Progress (band {} of {}): {}%",
+                                band + 1,
+                                num_bands,
+                                progress
+                            );
+                        } else {
+                            println!("Progress: {}%", progress);
+                        }
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            let mut stats = BandStats {
+                band: band + 1,
+                num_cells: num_cells,
+                num_nodata: input.num_cells() - num_cells,
+                min: minz,
+                max: maxz,
+                total: sum,
+                mean: f64::NAN,
+                variance: f64::NAN,
+                skewness: f64::NAN,
+                kurtosis: f64::NAN,
+                percentiles: vec![f64::NAN; percentiles.len()],
+                histogram: vec![0usize; num_bins],
+                bin_width: f64::NAN,
+            };
+
+            if num_cells > 0 {
+                // A second pass calculates the central moments and histograms, which
+                // depend on the mean and range found by the first pass.
+                let mean = sum / num_cells as f64;
+                let range = maxz - minz;
+                let bin_width = if range > 0f64 {
+                    range / num_bins as f64
+                } else {
+                    1f64
+                };
+                let approx_bin_width = if range > 0f64 {
+                    range / APPROX_BINS as f64
+                } else {
+                    1f64
+                };
+                let (tx, rx) = mpsc::channel();
+                for tid in 0..num_procs {
+                    let input = input.clone();
+                    let tx = tx.clone();
+                    thread::spawn(move || {
+                        let mut z: f64;
+                        let (mut m2, mut m3, mut m4) = (0f64, 0f64, 0f64);
+                        let mut histogram = vec![0usize; num_bins];
+                        let mut approx_histogram = if approx {
+                            vec![0usize; APPROX_BINS]
+                        } else {
+                            vec![]
+                        };
+                        let mut values = vec![];
+                        for row in (0..rows).filter(|r| r % num_procs == tid) {
+                            for col in 0..columns {
+                                z = input.get_value_band(row, col, band);
+                                if z != nodata {
+                                    let d = z - mean;
+                                    let d2 = d * d;
+                                    m2 += d2;
+                                    m3 += d2 * d;
+                                    m4 += d2 * d2;
+                                    let bin = ((z - minz) / bin_width).floor() as usize;
+                                    histogram[bin.min(num_bins - 1)] += 1;
+                                    if approx {
+                                        let bin = ((z - minz) / approx_bin_width).floor() as usize;
+                                        approx_histogram[bin.min(APPROX_BINS - 1)] += 1;
+                                    } else {
+                                        values.push(z);
+                                    }
+                                }
                             }
                         }
+                        tx.send((m2, m3, m4, histogram, approx_histogram, values))
+                            .unwrap();
+                    });
+                }
+
+                let (mut m2, mut m3, mut m4) = (0f64, 0f64, 0f64);
+                let mut approx_histogram = if approx {
+                    vec![0usize; APPROX_BINS]
+                } else {
+                    vec![]
+                };
+                let mut values: Vec<f64> = if approx {
+                    vec![]
+                } else {
+                    Vec::with_capacity(num_cells)
+                };
+                for _ in 0..num_procs {
+                    let (a, b, c, h, ah, v) = rx.recv().expect("Error receiving data from thread.");
+                    m2 += a;
+                    m3 += b;
+                    m4 += c;
+                    for i in 0..num_bins {
+                        stats.histogram[i] += h[i];
                     }
-                    tx.send((n, s, sq, minz, maxz)).unwrap();
+                    for i in 0..ah.len() {
+                        approx_histogram[i] += ah[i];
+                    }
+                    values.extend_from_slice(&v);
                 }
-            });
-        }
+                m2 /= num_cells as f64;
+                m3 /= num_cells as f64;
+                m4 /= num_cells as f64;
 
-        let mut num_cells = 0;
-        let mut sum = 0.0;
-        let mut sq_sum = 0.0;
-        let mut minz = f64::INFINITY;
-        let mut maxz = f64::NEG_INFINITY;
-        for row in 0..rows {
-            let (a, b, c, d, e) = rx.recv().expect("Error receiving data from thread.");
-            num_cells += a;
-            sum += b;
-            sq_sum += c;
-            if d < minz {
-                minz = d;
+                stats.mean = mean;
+                stats.variance = m2;
+                if m2 > 0f64 {
+                    stats.skewness = m3 / m2.powf(1.5);
+                    stats.kurtosis = m4 / (m2 * m2) - 3f64;
+                }
+                stats.bin_width = bin_width;
+                stats.percentiles = if approx {
+                    percentiles
+                        .iter()
+                        .map(|p| {
+                            histogram_percentile(
+                                &approx_histogram,
+                                minz,
+                                maxz,
+                                approx_bin_width,
+                                num_cells,
+                                *p,
+                            )
+                        })
+                        .collect()
+                } else {
+                    if verbose {
+                        println!("Sorting values...");
+                    }
+                    values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+                    percentiles
+                        .iter()
+                        .map(|p| percentile(&values, *p))
+                        .collect()
+                };
             }
-            if e > maxz {
-                maxz = e;
+
+            if num_bands > 1 {
+                println!("\nBand {}:", band + 1);
             }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
+            println!("\nNumber of non-nodata grid cells: {}", stats.num_cells);
+            println!("Number of nodata grid cells: {}", stats.num_nodata);
+            println!("Image minimum: {}", stats.min);
+            println!("Image maximum: {}", stats.max);
+            println!("Image range: {}", stats.max - stats.min);
+            println!("Image total: {}", stats.total);
+            println!("Image average: {}", stats.mean);
+            println!("Image variance: {}", stats.variance);
+            println!("Image standard deviation: {}", stats.variance.sqrt());
+            println!("Image skewness: {}", stats.skewness);
+            println!("Image kurtosis: {}", stats.kurtosis);
+            for i in 0..percentiles.len() {
+                println!(
+                    "Image {} percentile{}: {}",
+                    ordinal(percentiles[i]),
+                    if approx { " (approx.)" } else { "" },
+                    stats.percentiles[i]
+                );
             }
-        }
 
-        let mean = sum / num_cells as f64;
-        let variance = sq_sum / num_cells as f64 - mean * mean;
-        let std_dev = variance.sqrt();
+            band_stats.push(stats);
+        }
 
         let elapsed_time = get_formatted_elapsed_time(start);
-
-        println!("\nNumber of non-nodata grid cells: {}", num_cells);
-        println!(
-            "Number of nodata grid cells: {}",
-            input.num_cells() - num_cells
-        );
-        println!("Image minimum: {}", minz);
-        println!("Image maximum: {}", maxz);
-        println!("Image range: {}", maxz - minz);
-        println!("Image total: {}", sum);
-        println!("Image average: {}", mean);
-        println!("Image variance: {}", variance);
-        println!("Image standard deviation: {}", std_dev);
         if verbose {
             println!(
                 "\n{}",
@@ -272,6 +604,287 @@ impl WhiteboxTool for RasterSummaryStats {
             );
         }
 
+        if !output_json_file.is_empty() {
+            let bands: Vec<serde_json::Value> = band_stats
+                .iter()
+                .map(|s| {
+                    let p: Vec<serde_json::Value> = percentiles
+                        .iter()
+                        .zip(s.percentiles.iter())
+                        .map(|(p, v)| json!({ "percentile": p, "value": v }))
+                        .collect();
+                    json!({
+                        "band": s.band,
+                        "num_cells": s.num_cells,
+                        "num_nodata": s.num_nodata,
+                        "min": s.min,
+                        "max": s.max,
+                        "range": s.max - s.min,
+                        "total": s.total,
+                        "mean": s.mean,
+                        "variance": s.variance,
+                        "std_dev": s.variance.sqrt(),
+                        "skewness": s.skewness,
+                        "kurtosis": s.kurtosis,
+                        "percentiles": p,
+                        "histogram": {
+                            "min": s.min,
+                            "bin_width": s.bin_width,
+                            "counts": s.histogram,
+                        },
+                    })
+                })
+                .collect();
+            let report = json!({
+                "input": input_file,
+                "percentile_method": if approx { "approximate" } else { "exact" },
+                "bands": bands,
+            });
+            let f = File::create(&output_json_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all(
+                serde_json::to_string_pretty(&report)
+                    .map_err(|e| Error::new(ErrorKind::Other, e))?
+                    .as_bytes(),
+            )?;
+            writer.flush()?;
+            if verbose {
+                println!("JSON report written to {}", output_json_file);
+            }
+        }
+
+        if !output_csv_file.is_empty() {
+            let f = File::create(&output_csv_file)?;
+            let mut writer = BufWriter::new(f);
+            let mut header = String::from(
+                "BAND,COUNT,NODATA,MIN,MAX,RANGE,TOTAL,MEAN,VARIANCE,STDEV,SKEWNESS,KURTOSIS",
+            );
+            for p in &percentiles {
+                header.push_str(&format!(",{}", percentile_field_name(*p)));
+            }
+            writer.write_all(format!("{}\n", header).as_bytes())?;
+            for s in &band_stats {
+                let mut line = format!("{},{},{}", s.band, s.num_cells, s.num_nodata);
+                for v in s.values().iter().chain(s.percentiles.iter()) {
+                    if v.is_finite() {
+                        line.push_str(&format!(",{}", v));
+                    } else {
+                        line.push_str(",");
+                    }
+                }
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            writer.flush()?;
+            if verbose {
+                println!("CSV report written to {}", output_csv_file);
+            }
+        }
+
+        if !output_html_file.is_empty() {
+            let f = File::create(&output_html_file)?;
+            let mut writer = BufWriter::new(f);
+
+            writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Raster Summary Statistics</title>"#.as_bytes())?;
+
+            // get the style sheet
+            writer.write_all(&get_css().as_bytes())?;
+
+            writer.write_all(
+                &r#"</head>
+            <body>
+                <h1>Raster Summary Statistics</h1>"#
+                    .as_bytes(),
+            )?;
+
+            writer.write_all(
+                &format!("<p><strong>Image</strong>: {}</p>", input_file.clone()).as_bytes(),
+            )?;
+
+            let labels = [
+                "Minimum",
+                "Maximum",
+                "Range",
+                "Total",
+                "Mean",
+                "Variance",
+                "Std. Dev.",
+                "Skewness",
+                "Kurtosis",
+            ];
+            for s in &band_stats {
+                if num_bands > 1 {
+                    writer.write_all(&format!("<h2>Band {}</h2>", s.band).as_bytes())?;
+                }
+                writer.write_all("<br><table align=\"center\">".as_bytes())?;
+                writer.write_all("<tr><th>Statistic</th><th>Value</th></tr>".as_bytes())?;
+                writer.write_all(
+                    &format!(
+                        "<tr><td>Non-NoData Cells</td><td class=\"numberCell\">{}</td></tr>",
+                        s.num_cells
+                    )
+                    .as_bytes(),
+                )?;
+                writer.write_all(
+                    &format!(
+                        "<tr><td>NoData Cells</td><td class=\"numberCell\">{}</td></tr>",
+                        s.num_nodata
+                    )
+                    .as_bytes(),
+                )?;
+                for (label, v) in labels.iter().zip(s.values().iter()) {
+                    writer.write_all(
+                        &format!(
+                            "<tr><td>{}</td><td class=\"numberCell\">{:.*}</td></tr>",
+                            label, 4, v
+                        )
+                        .as_bytes(),
+                    )?;
+                }
+                for (p, v) in percentiles.iter().zip(s.percentiles.iter()) {
+                    writer.write_all(
+                        &format!(
+                            "<tr><td>{} Percentile{}</td><td class=\"numberCell\">{:.*}</td></tr>",
+                            ordinal(*p),
+                            if approx { " (approx.)" } else { "" },
+                            4,
+                            v
+                        )
+                        .as_bytes(),
+                    )?;
+                }
+                writer.write_all("</table>".as_bytes())?;
+
+                if s.num_cells > 0 {
+                    let histo = Histogram {
+                        parent_id: format!("histo{}", s.band),
+                        width: 700f64,
+                        height: 500f64,
+                        freq_data: s.histogram.clone(),
+                        min_bin_val: s.min,
+                        bin_width: s.bin_width,
+                        x_axis_label: "Image Value (X)".to_owned(),
+                        cumulative: false,
+                    };
+                    writer.write_all(
+                        &format!(
+                            "<br><div id='histo{}' align=\"center\">{}</div>",
+                            s.band,
+                            histo.get_svg()
+                        )
+                        .as_bytes(),
+                    )?;
+                }
+            }
+
+            writer.write_all("</body>".as_bytes())?;
+
+            let _ = writer.flush();
+
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+                println!("Complete! Please see {} for output.", output_html_file);
+            }
+        }
+
         Ok(())
     }
 }
+
+/// The summary statistics of a single band.
+struct BandStats {
+    band: usize,
+    num_cells: usize,
+    num_nodata: usize,
+    min: f64,
+    max: f64,
+    total: f64,
+    mean: f64,
+    variance: f64,
+    skewness: f64,
+    kurtosis: f64,
+    percentiles: Vec<f64>,
+    histogram: Vec<usize>,
+    bin_width: f64,
+}
+
+impl BandStats {
+    /// Returns the statistics in report order: min, max, range, total, mean, variance,
+    /// std. dev., skewness, and kurtosis.
+    fn values(&self) -> Vec<f64> {
+        vec![
+            self.min,
+            self.max,
+            self.max - self.min,
+            self.total,
+            self.mean,
+            self.variance,
+            self.variance.sqrt(),
+            self.skewness,
+            self.kurtosis,
+        ]
+    }
+}
+
+/// Estimates the p-th percentile (0-100) of `n` values from a histogram of equal-width
+/// bins, assuming that the values within each bin are evenly spread across it. The rank
+/// of the percentile is defined as in `percentile`.
+fn histogram_percentile(
+    counts: &[usize],
+    min: f64,
+    max: f64,
+    bin_width: f64,
+    n: usize,
+    p: f64,
+) -> f64 {
+    let rank = p / 100f64 * (n - 1) as f64;
+    let mut cumulative = 0usize;
+    for (bin, count) in counts.iter().enumerate() {
+        if *count > 0 && (cumulative + count) as f64 > rank {
+            let k = rank - cumulative as f64;
+            let z = min + (bin as f64 + (k + 0.5) / *count as f64) * bin_width;
+            return z.max(min).min(max);
+        }
+        cumulative += count;
+    }
+    max
+}
+
+/// Formats a percentile as an ordinal, e.g. '25th' or '2.5th'.
+fn ordinal(p: f64) -> String {
+    let suffix = if p.fract() != 0f64 {
+        "th"
+    } else {
+        match (p as usize % 100, p as usize % 10) {
+            (11..=13, _) => "th",
+            (_, 1) => "st",
+            (_, 2) => "nd",
+            (_, 3) => "rd",
+            _ => "th",
+        }
+    };
+    format!("{}{}", p, suffix)
+}
@@ -19,8 +19,9 @@ use std::sync::Arc;
 use std::thread;
 
 /// This tool calculates the root-mean-square-error (RMSE) or root-mean-square-difference (RMSD) from two
-/// input rasters. If the two input rasters possess the same number of rows and columns, the RMSE is
-/// calucated on a cell-by-cell basis, otherwise bilinear resampling is used. In addition to RMSE,
+/// input rasters. If the two input rasters share the same grid, the RMSE is calucated on a
+/// cell-by-cell basis, otherwise bilinear resampling is used. The two rasters must be in the same
+/// coordinate reference system, unless the `--resample_inputs` flag is used. In addition to RMSE,
 /// the tool also reports other common accuracy statistics including the mean verical error, the
 /// 95% confidence limit (RMSE x 1.96), and the 90% linear error (LE90), which is the 90% percentile of
 /// the residuals between two raster surfaces. The LE90 is the most robust of the reported accuracy
@@ -187,7 +188,13 @@ impl WhiteboxTool for RootMeanSquareError {
         };
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
-        let base_raster = Arc::new(Raster::new(&base_file, "r")?);
+        let base_raster = Raster::new(&base_file, "r")?;
+        // differing grids are handled by resampling below, but the inputs must share the same CRS
+        let base_raster = if base_raster.has_same_crs(&input) {
+            Arc::new(base_raster)
+        } else {
+            Arc::new(align_raster_input(&input, base_raster)?)
+        };
 
         let start = Instant::now();
         let rows = input.configs.rows as isize;
@@ -199,10 +206,8 @@ impl WhiteboxTool for RootMeanSquareError {
         // the 90th percentile lies at the bottom of the top 10% highest absolute residual values.
         // let target_num_cells = (0.1 * num_valid_cells as f64) as usize;
 
-        if base_raster.configs.rows as isize == rows
-            && base_raster.configs.columns as isize == columns
-        {
-            // The two grids are the same resolution. This simplifies the calculation greatly.
+        if base_raster.is_grid_aligned_with(&input) {
+            // The two grids are the same. This simplifies the calculation greatly.
             let num_procs = num_cpus::get() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -277,7 +282,7 @@ impl WhiteboxTool for RootMeanSquareError {
             );
             println!("LE90: {:.5}", le90);
         } else {
-            /* The two grids are not of the same resolution. Bilinear resampling will have to be
+            /* The two grids are not the same. Bilinear resampling will have to be
             carried out to estimate z-values. Base image = source; input image = destination */
             let num_procs = num_cpus::get() as isize;
             let (tx, rx) = mpsc::channel();
@@ -0,0 +1,587 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::rendering::html::*;
+use crate::rendering::LineGraph;
+use crate::structures::{EmpiricalSemivariogram, Point2D, Semivariogram, VariogramModel};
+use crate::tools::*;
+use crate::vector::{FieldData, ShapeType, ShapeTypeDimension, Shapefile};
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// This tool calculates the empirical (experimental) semivariogram of a set of vector points (`--input`) and fits
+/// one or more theoretical semivariogram models to it, outputting the results as an HTML report (`--output`). The
+/// semivariogram describes how the dissimilarity of values, measured as one-half of the mean squared difference
+/// between pairs of points, changes with the separation distance (lag) between the points. It is the basis of
+/// geostatistical interpolation methods such as kriging (`OrdinaryKriging`).
+///
+/// The user must specify the attribute field containing point values (`--field`). Alternatively, if the input
+/// Shapefile contains z-values, the analysis may be based on these values (`--use_z`). Point pairs are binned into
+/// `--num_lags` lag classes (default 15) of width `--lag_size`. If the lag size is unspecified, the lag classes
+/// span one-half of the diagonal length of the data's bounding box.
+///
+/// The spherical, exponential, and Gaussian models are fitted by weighted least-squares, with weights equal to the
+/// number of point pairs in each lag class divided by the squared lag distance, which emphasizes the better-estimated
+/// short-range structure. The nugget, partial sill, and (practical) range of each model are reported, along with
+/// the weighted sum of squared residuals, and the best fitting model is highlighted. Alternatively, the user may
+/// specify a single model to fit (`--model`). The reported parameters may be used directly as inputs to the
+/// `OrdinaryKriging` tool.
+///
+/// Note that all pairs of points are considered and therefore the analysis is best suited to sparse samples.
+///
+/// # See Also
+/// `OrdinaryKriging`, `ImageAutocorrelation`
+pub struct SemivariogramAnalysis {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SemivariogramAnalysis {
+    pub fn new() -> SemivariogramAnalysis {
+        // public constructor
+        let name = "SemivariogramAnalysis".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description =
+            "Calculates the empirical semivariogram of vector points and fits semivariogram models."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field name in attribute table.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Use Shapefile 'z' values?".to_owned(),
+            flags: vec!["--use_z".to_owned()],
+            description:
+                "Use the 'z' dimension of the Shapefile's geometry instead of an attribute field?"
+                    .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Lag Size (optional)".to_owned(),
+            flags: vec!["--lag_size".to_owned()],
+            description: "Width of the lag classes.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Lags".to_owned(),
+            flags: vec!["--num_lags".to_owned()],
+            description: "Number of lag classes.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("15".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Semivariogram Model (optional)".to_owned(),
+            flags: vec!["--model".to_owned()],
+            description: "Optional single model to fit; options include 'spherical', 'exponential', and 'gaussian'. By default, all models are fitted.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "spherical".to_owned(),
+                "exponential".to_owned(),
+                "gaussian".to_owned(),
+            ]),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=points.shp --field=ELEV -o=semivariogram.html --lag_size=50.0 --num_lags=20",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SemivariogramAnalysis {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SemivariogramAnalysis {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut field_name = String::new();
+        let mut use_z = false;
+        let mut use_field = false;
+        let mut output_file = String::new();
+        let mut lag_size = 0f64;
+        let mut num_lags = 15usize;
+        let mut model_name = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-field" {
+                field_name = val;
+                use_field = true;
+            } else if flag_val.contains("use_z") {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    use_z = true;
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-lag_size" {
+                lag_size = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-num_lags" {
+                num_lags = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-model" {
+                model_name = val.to_lowercase();
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !output_file.to_lowercase().ends_with(".html") {
+            output_file.push_str(".html");
+        }
+
+        let models = if model_name.is_empty() {
+            vec![
+                VariogramModel::Spherical,
+                VariogramModel::Exponential,
+                VariogramModel::Gaussian,
+            ]
+        } else {
+            match VariogramModel::from_name(&model_name) {
+                Some(m) => vec![m],
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Unrecognized semivariogram model. Options include 'spherical', 'exponential', and 'gaussian'.",
+                    ))
+                }
+            }
+        };
+        if num_lags < 2 {
+            num_lags = 2;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        let start = Instant::now();
+
+        // make sure the input vector file is of points type
+        if input.header.shape_type.base_shape_type() != ShapeType::Point
+            && input.header.shape_type.base_shape_type() != ShapeType::MultiPoint
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of POINT base shape type.",
+            ));
+        }
+
+        if !use_z && !use_field {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "If vector data 'Z' data are unavailable (--use_z), an attribute field must be specified (--field=).",
+            ));
+        }
+
+        if use_z && input.header.shape_type.dimension() != ShapeTypeDimension::Z {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of 'POINTZ' or 'MULTIPOINTZ' ShapeType to use the --use_z flag.",
+            ));
+        } else if use_field {
+            // What is the index of the field to be analyzed?
+            let field_index = match input.attributes.get_field_num(&field_name) {
+                Some(i) => i,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The specified field name does not exist in input shapefile.",
+                    ))
+                }
+            };
+
+            // Is the field numeric?
+            if !input.attributes.is_field_numeric(field_index) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified attribute field is non-numeric.",
+                ));
+            }
+        }
+
+        let mut points: Vec<Point2D> = Vec::with_capacity(input.get_total_num_points());
+        let mut z_values: Vec<f64> = Vec::with_capacity(input.get_total_num_points());
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            let field_value = if use_z {
+                None
+            } else {
+                match input.attributes.get_value(record_num, &field_name) {
+                    FieldData::Int(val) => Some(val as f64),
+                    FieldData::Real(val) => Some(val),
+                    _ => None, // likely a null field
+                }
+            };
+            for i in 0..record.num_points as usize {
+                let z = if use_z {
+                    record.z_array[i]
+                } else {
+                    match field_value {
+                        Some(v) => v,
+                        None => continue,
+                    }
+                };
+                points.push(Point2D::new(record.points[i].x, record.points[i].y));
+                z_values.push(z);
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Reading points: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let n = points.len();
+        if n < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least three points with valid values are required.",
+            ));
+        }
+
+        if lag_size <= 0f64 {
+            let width = input.header.x_max - input.header.x_min;
+            let height = input.header.y_max - input.header.y_min;
+            lag_size = (width * width + height * height).sqrt() / 2f64 / num_lags as f64;
+        }
+
+        if verbose {
+            println!("Calculating the empirical semivariogram...");
+        }
+        let empirical = EmpiricalSemivariogram::new(&points, &z_values, lag_size, num_lags);
+        if empirical.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No point pairs were found within the lag classes. Try increasing the lag size.",
+            ));
+        }
+
+        if verbose {
+            println!("Fitting models...");
+        }
+        let fitted: Vec<(Semivariogram, f64)> = models
+            .iter()
+            .filter_map(|&m| Semivariogram::fit(m, &empirical))
+            .map(|s| {
+                let sse = s.weighted_sse(&empirical);
+                (s, sse)
+            })
+            .collect();
+        let mut best = 0;
+        for i in 1..fitted.len() {
+            if fitted[i].1 < fitted[best].1 {
+                best = i;
+            }
+        }
+
+        let mean = z_values.iter().sum::<f64>() / n as f64;
+        let variance = z_values.iter().map(|z| (z - mean) * (z - mean)).sum::<f64>() / n as f64;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+
+        let f = File::create(output_file.clone())?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <html>
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Semivariogram Analysis Report</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"
+            </head>
+            <body>
+                <h1>Semivariogram Analysis Report</h1>
+                "#
+            .as_bytes(),
+        )?;
+
+        writer.write_all((format!("<p><strong>Input</strong>: {}</p>", input_file)).as_bytes())?;
+        if use_z {
+            writer.write_all("<p><strong>Values</strong>: z values</p>".as_bytes())?;
+        } else {
+            writer.write_all(
+                (format!("<p><strong>Field</strong>: {}</p>", field_name)).as_bytes(),
+            )?;
+        }
+        writer.write_all((format!("<p><strong>Num. points</strong>: {}</p>", n)).as_bytes())?;
+        writer.write_all(
+            (format!("<p><strong>Sample variance</strong>: {:.*}</p>", 5, variance)).as_bytes(),
+        )?;
+        writer.write_all(
+            (format!(
+                "<p><strong>Lag size</strong>: {:.*} ({} lags)</p>",
+                5, lag_size, num_lags
+            ))
+            .as_bytes(),
+        )?;
+
+        ////////////////////////////////
+        // Fitted model summary table //
+        ////////////////////////////////
+        if fitted.is_empty() {
+            writer.write_all(
+                "<p>Too few lag classes contain point pairs to fit a model. Try increasing the lag size.</p>"
+                    .as_bytes(),
+            )?;
+        } else {
+            writer.write_all("<p><table>".as_bytes())?;
+            writer.write_all("<caption>Fitted Semivariogram Models</caption>".as_bytes())?;
+            writer.write_all("<tr><th>Model</th><th>Nugget</th><th>Partial Sill</th><th>Sill</th><th>Range</th><th>Weighted SSE</th></tr>".as_bytes())?;
+            for i in 0..fitted.len() {
+                let (s, sse) = fitted[i];
+                let model = if i == best && fitted.len() > 1 {
+                    format!("<strong>{} (best fit)</strong>", s.model)
+                } else {
+                    format!("{}", s.model)
+                };
+                writer.write_all(&format!("<tr><td>{}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*e}</td></tr>",
+                    model, 5, s.nugget, 5, s.sill, 5, s.nugget + s.sill, 5, s.range, 4, sse).as_bytes())?;
+            }
+            writer.write_all("</table></p>".as_bytes())?;
+        }
+
+        ///////////
+        // Graph //
+        ///////////
+        let mut xdata = vec![empirical.distances.clone()];
+        let mut ydata = vec![empirical.gamma.clone()];
+        let mut series_names = vec!["Empirical".to_string()];
+        let max_lag = lag_size * num_lags as f64;
+        const NUM_SAMPLES: usize = 100;
+        for (s, _) in &fitted {
+            let x: Vec<f64> = (1..=NUM_SAMPLES)
+                .map(|i| max_lag * i as f64 / NUM_SAMPLES as f64)
+                .collect();
+            ydata.push(x.iter().map(|&h| s.value(h)).collect());
+            xdata.push(x);
+            series_names.push(format!("{}", s.model));
+        }
+        writer.write_all("<h2>Semivariogram</h2>".as_bytes())?;
+        let graph = LineGraph {
+            parent_id: "graph".to_string(),
+            width: 700f64,
+            height: 500f64,
+            data_x: xdata,
+            data_y: ydata,
+            series_labels: series_names,
+            x_axis_label: "Lag Distance".to_string(),
+            y_axis_label: "Semivariance".to_string(),
+            draw_points: false,
+            draw_gridlines: true,
+            draw_legend: true,
+            draw_grey_background: false,
+        };
+        writer.write_all(
+            &format!("<div id='graph' align=\"center\">{}</div>", graph.get_svg()).as_bytes(),
+        )?;
+
+        ///////////////////////
+        // Lag classes table //
+        ///////////////////////
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Empirical Semivariogram</caption>".as_bytes())?;
+        writer.write_all(
+            "<tr><th>Mean Lag Distance</th><th>Semivariance</th><th>Num. Pairs</th></tr>"
+                .as_bytes(),
+        )?;
+        for i in 0..empirical.len() {
+            writer.write_all(&format!("<tr><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{}</td></tr>",
+                5, empirical.distances[i], 5, empirical.gamma[i], empirical.num_pairs[i]).as_bytes())?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        writer.write_all("</body>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+
+            println!("Please see {} for output report.", output_file);
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,16 +2,15 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 14/07/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::random;
 use num_cpus;
 use rand::prelude::*;
-// use rand::{Rng, SeedableRng};
-use rand::thread_rng;
 use rand_distr::StandardNormal;
 use std::env;
 use std::f64;
@@ -30,7 +29,8 @@ use std::thread;
 /// natural stopping condition in this process, so the user must specify the number of bands to create
 /// (`--iterations`). The default value of 1000 iterations is reasonable. The fewer iterations used, the
 /// more prevalent the 1-D simulations will be in the output error image, effectively creating artifacts.
-/// Run time increases with the number of iterations.
+/// Run time increases with the number of iterations. The simulation can be repeated exactly by specifying
+/// the same global `--seed` value in each run.
 ///
 /// Turning bands simulation is a commonly applied technique in Monte Carlo style simulations of uncertainty.
 /// As such, it is frequently run many times during a simulation (often 1000s of times). When this is the
@@ -266,8 +266,9 @@ impl WhiteboxTool for TurningBandsSimulation {
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.reinitialize_values(0.0);
 
-        let mut rng = thread_rng();
-        let mut rng2 = thread_rng();
+        // the band values and the band orientations are drawn from separate streams
+        let mut rng = random::rng(0);
+        let mut rng2 = random::rng(1);
         // let normal = Normal::new(0.0, 1.0);
         // let between = Range::new(0, 4);
         // let between_rows = Range::new(0f64, rows as f64);
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/10/2019
-Last Modified: 25/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -11,6 +11,7 @@ use crate::raster::*;
 use crate::rendering::html::*;
 use crate::rendering::LineGraph;
 use crate::tools::*;
+use crate::utils::random;
 use rand::prelude::*;
 use statrs;
 use std::cmp::Ordering::Equal;
@@ -239,18 +240,13 @@ impl WhiteboxTool for WilcoxonSignedRankTest {
 
         let input1 = Arc::new(Raster::new(&input_file1, "r")?);
         let input1_name = input1.get_short_filename();
-        let input2 = Arc::new(Raster::new(&input_file2, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let input2 = Arc::new(align_raster_input(
+            &input1,
+            Raster::new(&input_file2, "r")?,
+        )?);
         let input2_name = input2.get_short_filename();
 
-        if input1.configs.rows != input2.configs.rows
-            || input1.configs.columns != input2.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let start = Instant::now();
         let mut progress: i32;
         let mut old_progress: i32 = -1;
@@ -334,7 +330,7 @@ impl WhiteboxTool for WilcoxonSignedRankTest {
             let mut diff: f64;
             let mut sum = 0.0;
             let mut sq_sum = 0.0;
-            let mut rng = thread_rng();
+            let mut rng = random::rng(0);
             let (mut row, mut col): (isize, isize);
             let mut sample_num = 0usize;
             while sample_num < num_samples {
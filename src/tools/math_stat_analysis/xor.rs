@@ -199,7 +199,8 @@ impl WhiteboxTool for Xor {
             println!("Reading data...")
         };
         let in1 = Arc::new(Raster::new(&input1, "r")?);
-        let in2 = Arc::new(Raster::new(&input2, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let in2 = Arc::new(align_raster_input(&in1, Raster::new(&input2, "r")?)?);
 
         let start = Instant::now();
         let rows = in1.configs.rows as isize;
@@ -207,14 +208,6 @@ impl WhiteboxTool for Xor {
         let nodata1 = in1.configs.nodata;
         let nodata2 = in2.configs.nodata;
 
-        // make sure the input files have the same size
-        if in1.configs.rows != in2.configs.rows || in1.configs.columns != in2.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         // calculate the number of downslope cells
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
@@ -2,16 +2,21 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: Dec. 15, 2017
-Last Modified: 17/07/2019
+Last Modified: 15/10/2026
 License: MIT
 
 Notes: Compared with the original Whitebox GAT tool, this will output a table
 with each of the mean, min, max, range, std dev, and total. The output raster can
-only represent one statistic, given by the --stat flag.
+only represent one statistic, given by the --stat flag. Zones may also be supplied
+as a polygon vector, in which case statistics are written to the polygon attribute
+table (or a CSV file) rather than to a raster.
 */
 
+use crate::algorithms::point_in_poly;
 use crate::raster::*;
+use crate::spatial_ref_system::CoordinateReferenceSystem;
 use crate::tools::*;
+use crate::vector::*;
 use num_cpus;
 use std::cmp::Ordering::Equal;
 use std::env;
@@ -44,11 +49,29 @@ use std::thread;
 /// spatial data which can then be interrogated using statistical analyses. At least one output type
 /// (image or text) must be specified for the tool to operate.
 ///
+/// Zones can alternatively be defined by a polygon vector file (`--features`), in which case
+/// statistics are calculated for all grid cells with centres that fall within each polygon,
+/// excluding cells within polygon holes. Polygons are processed in parallel. In this mode, the
+/// mean, median, minimum, maximum, range, standard deviation, total, and cell count of each zone,
+/// along with any requested percentiles, are appended to the polygon's attribute table, unless
+/// a CSV output file (`--out_csv`) is specified, in which case the table is written to the CSV file
+/// instead and the input vector is left unmodified. Note that overlapping polygons are each
+/// treated as independent zones.
+///
+/// The optional `--percentiles` parameter accepts a comma- or semicolon-separated list of
+/// percentiles (0-100), e.g. '10;25;75;90', which are calculated using linear interpolation
+/// between ranked values. Percentiles are reported in the CSV and vector attribute outputs.
+/// A CSV output can also be requested when zones are defined by a feature definition raster.
+///
+/// Hexagonal and rotated input grids are supported. With polygon zones, each hexagonal cell is
+/// assigned to the polygons containing its centre. With a feature definition raster, the two
+/// rasters must share the same grid geometry.
+///
 /// NoData values in either of the two input images are ignored during the calculation of the
 /// descriptive statistic.
 ///
 /// # See Also
-/// `RasterSummaryStats`
+/// `RasterSummaryStats`, `ClipRasterToPolygon`
 pub struct ZonalStatistics {
     name: String,
     description: String,
@@ -78,8 +101,10 @@ impl ZonalStatistics {
         parameters.push(ToolParameter {
             name: "Input Feature Definition File".to_owned(),
             flags: vec!["--features".to_owned()],
-            description: "Input feature definition raster file.".to_owned(),
-            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            description: "Input feature definition raster file or polygon vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Polygon,
+            )),
             default_value: None,
             optional: false,
         });
@@ -119,6 +144,24 @@ impl ZonalStatistics {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Output CSV file of per-zone statistics.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Percentiles".to_owned(),
+            flags: vec!["--percentiles".to_owned()],
+            description: "Optional list of percentiles (0-100) to calculate, e.g. '25;75'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -131,7 +174,8 @@ impl ZonalStatistics {
             short_exe += ".exe";
         }
         let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='input.tif' --features='groups.tif' -o='output.tif' --stat='minimum'
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='input.tif' --features='groups.tif' --out_table='output.html'", short_exe, name).replace("*", &sep);
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='input.tif' --features='groups.tif' --out_table='output.html'
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='input.tif' --features='watersheds.shp' --out_csv='stats.csv' --percentiles='10;90'", short_exe, name).replace("*", &sep);
 
         ZonalStatistics {
             name: name,
@@ -141,6 +185,227 @@ impl ZonalStatistics {
             example_usage: usage,
         }
     }
+
+    /// Calculates zonal statistics for zones defined by the polygons in a vector file.
+    fn polygon_zonal_statistics(
+        &self,
+        input_file: &str,
+        polygons_file: &str,
+        output_csv_file: &str,
+        percentiles: &[f64],
+        verbose: bool,
+    ) -> Result<(), Error> {
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(input_file, "r")?);
+        let mut polygons = Shapefile::read(polygons_file)?;
+        if output_csv_file.is_empty() {
+            polygons.file_mode = "rw".to_string(); // we need to be able to modify the attributes table
+        }
+
+        // make sure the input vector file is of polygon type
+        if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of polygon base shape type.",
+            ));
+        }
+
+        if let Some(raster_crs) = input.get_crs() {
+            if !polygons.projection.trim().is_empty() {
+                if let Ok(vector_crs) = CoordinateReferenceSystem::from_wkt(&polygons.projection) {
+                    if !raster_crs.is_equivalent(&vector_crs) {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "The input raster ({}) and polygon zones ({}) do not share the same coordinate reference system. Use ReprojectVector to transform the polygons first.",
+                                raster_crs.name, vector_crs.name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let start = Instant::now();
+        let num_records = polygons.num_records;
+        let records = Arc::new(
+            (0..num_records)
+                .map(|record_num| polygons.get_record(record_num).clone())
+                .collect::<Vec<ShapefileGeometry>>(),
+        );
+        let percentiles = Arc::new(percentiles.to_vec());
+
+        let num_procs = num_cpus::get();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let records = records.clone();
+            let percentiles = percentiles.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let rows = input.configs.rows as isize;
+                let columns = input.configs.columns as isize;
+                let nodata = input.configs.nodata;
+                for record_num in (0..records.len()).filter(|r| r % num_procs == tid) {
+                    let record = &records[record_num];
+                    let mut values = vec![];
+                    if record.num_points > 0 {
+                        // find the range of rows and columns covered by the polygon
+                        let mut min_row = isize::max_value();
+                        let mut max_row = isize::min_value();
+                        let mut min_col = isize::max_value();
+                        let mut max_col = isize::min_value();
+                        for p in &record.points {
+                            let (row, col) = input.get_cell_from_point(p.x, p.y);
+                            min_row = min_row.min(row);
+                            max_row = max_row.max(row);
+                            min_col = min_col.min(col);
+                            max_col = max_col.max(col);
+                        }
+                        // hexagonal cells containing the polygon's edges may extend beyond
+                        // the cells containing its vertices
+                        let pad = if input.configs.grid_type == GridType::Hexagonal {
+                            1
+                        } else {
+                            0
+                        };
+                        min_row = (min_row - pad).max(0);
+                        max_row = (max_row + pad).min(rows - 1);
+                        min_col = (min_col - pad).max(0);
+                        max_col = (max_col + pad).min(columns - 1);
+
+                        let num_parts = record.num_parts as usize;
+                        let mut part_bounds = Vec::with_capacity(num_parts);
+                        for part in 0..num_parts {
+                            let start_point = record.parts[part] as usize;
+                            let end_point = if part < num_parts - 1 {
+                                record.parts[part + 1] as usize
+                            } else {
+                                record.num_points as usize
+                            };
+                            // the first part is always treated as a hull
+                            let is_hole = part > 0 && record.is_hole(part as i32);
+                            part_bounds.push((start_point, end_point, is_hole));
+                        }
+
+                        for row in min_row..=max_row {
+                            for col in min_col..=max_col {
+                                let z = input.get_value(row, col);
+                                if z == nodata {
+                                    continue;
+                                }
+                                let (x, y) = input.get_cell_centre(row, col);
+                                let point = Point2D::new(x, y);
+                                let mut inside = false;
+                                for &(start_point, end_point, is_hole) in &part_bounds {
+                                    if point_in_poly(&point, &record.points[start_point..end_point]) {
+                                        if is_hole {
+                                            inside = false;
+                                            break;
+                                        }
+                                        inside = true;
+                                    }
+                                }
+                                if inside {
+                                    values.push(z);
+                                }
+                            }
+                        }
+                    }
+                    let stats = ZoneStats::from_values(&mut values, &percentiles);
+                    tx.send((record_num, stats)).unwrap();
+                }
+            });
+        }
+
+        let mut zone_stats = vec![ZoneStats::default(); num_records];
+        for record_num in 0..num_records {
+            let (rec, stats) = rx.recv().expect("Error receiving data from thread.");
+            zone_stats[rec] = stats;
+            if verbose {
+                let progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                println!("Progress: {}%", progress);
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        if !output_csv_file.is_empty() {
+            let ids = (0..num_records)
+                .map(|record_num| format!("{}", record_num + 1))
+                .collect::<Vec<String>>();
+            write_zone_stats_csv(output_csv_file, &ids, &zone_stats, &percentiles)?;
+            if verbose {
+                println!("CSV file written");
+            }
+        } else {
+            let mut field_names = vec![
+                "COUNT".to_string(),
+                "MIN".to_string(),
+                "MAX".to_string(),
+                "MEAN".to_string(),
+                "MEDIAN".to_string(),
+                "STDEV".to_string(),
+                "RANGE".to_string(),
+                "TOTAL".to_string(),
+            ];
+            for p in percentiles.iter() {
+                field_names.push(percentile_field_name(*p));
+            }
+            for name in &field_names {
+                if name == "COUNT" {
+                    polygons
+                        .attributes
+                        .add_field(&AttributeField::new(name, FieldDataType::Int, 9u8, 0u8));
+                } else {
+                    polygons
+                        .attributes
+                        .add_field(&AttributeField::new(name, FieldDataType::Real, 12u8, 6u8));
+                }
+            }
+            for record_num in 0..num_records {
+                let stats = &zone_stats[record_num];
+                polygons.attributes.set_value(
+                    record_num,
+                    "COUNT",
+                    FieldData::Int(stats.n as i32),
+                );
+                let values = stats.values();
+                for i in 1..field_names.len() {
+                    let val = if stats.n > 0 {
+                        FieldData::Real(values[i - 1])
+                    } else {
+                        FieldData::Null
+                    };
+                    polygons
+                        .attributes
+                        .set_value(record_num, &field_names[i], val);
+                }
+            }
+            let _ = match polygons.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
 }
 
 impl WhiteboxTool for ZonalStatistics {
@@ -190,6 +455,8 @@ impl WhiteboxTool for ZonalStatistics {
         // let mut out_table = false;
         let mut output_html_file = String::new();
         let mut stat_type = String::from("mean");
+        let mut output_csv_file = String::new();
+        let mut percentiles: Vec<f64> = vec![];
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -238,6 +505,35 @@ impl WhiteboxTool for ZonalStatistics {
                 } else {
                     args[i + 1].to_string().to_lowercase()
                 };
+            } else if flag_val == "-out_csv" {
+                output_csv_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-percentiles" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                for p in val.split(|c| c == ';' || c == ',') {
+                    if !p.trim().is_empty() {
+                        let p = p.trim().parse::<f64>().map_err(|_| {
+                            Error::new(
+                                ErrorKind::InvalidInput,
+                                format!("Error parsing percentile value '{}'.", p),
+                            )
+                        })?;
+                        if p < 0f64 || p > 100f64 {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                "Percentiles must be within the range 0-100.",
+                            ));
+                        }
+                        percentiles.push(p);
+                    }
+                }
             }
         }
 
@@ -268,10 +564,26 @@ impl WhiteboxTool for ZonalStatistics {
                 output_html_file = format!("{}{}", working_directory, output_html_file);
             }
         }
-        if output_file.is_empty() && output_html_file.is_empty() {
+        if !output_csv_file.is_empty() {
+            if !output_csv_file.contains(&sep) && !output_csv_file.contains("/") {
+                output_csv_file = format!("{}{}", working_directory, output_csv_file);
+            }
+        }
+
+        if features_file.to_lowercase().ends_with(".shp") {
+            return self.polygon_zonal_statistics(
+                &input_file,
+                &features_file,
+                &output_csv_file,
+                &percentiles,
+                verbose,
+            );
+        }
+
+        if output_file.is_empty() && output_html_file.is_empty() && output_csv_file.is_empty() {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
-                "At least one of --output or --out_table must be specified.",
+                "At least one of --output, --out_table, or --out_csv must be specified.",
             ));
         }
 
@@ -279,7 +591,11 @@ impl WhiteboxTool for ZonalStatistics {
             println!("Reading data...")
         };
         let input = Arc::new(Raster::new(&input_file, "r")?);
-        let features = Arc::new(Raster::new(&features_file, "r")?);
+        // make sure the feature definition raster shares the grid and CRS of the input
+        let features = Arc::new(align_raster_input(
+            &input,
+            Raster::new(&features_file, "r")?,
+        )?);
 
         let start = Instant::now();
         let rows = input.configs.rows as isize;
@@ -287,13 +603,6 @@ impl WhiteboxTool for ZonalStatistics {
         let nodata = input.configs.nodata;
         let features_nodata = features.configs.nodata;
 
-        if features.configs.rows as isize != rows || features.configs.columns as isize != columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Input data and features definition raster must have the same dimensions.",
-            ));
-        }
-
         // How many features are there?
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
@@ -437,6 +746,21 @@ impl WhiteboxTool for ZonalStatistics {
             }
         }
 
+        if !output_csv_file.is_empty() {
+            let mut ids = vec![];
+            let mut stats = vec![];
+            for id in 0..num_features {
+                if features_n[id] > 0f64 {
+                    ids.push(format!("{}", id as isize + min_id));
+                    stats.push(ZoneStats::from_values(&mut features_data[id], &percentiles));
+                }
+            }
+            write_zone_stats_csv(&output_csv_file, &ids, &stats, &percentiles)?;
+            if verbose {
+                println!("CSV file written");
+            }
+        }
+
         // output the raster, if specified.
         if !output_file.is_empty() {
             let mut output = Raster::initialize_using_file(&output_file, &input);
@@ -667,3 +991,111 @@ impl WhiteboxTool for ZonalStatistics {
         Ok(())
     }
 }
+
+/// Descriptive statistics for the values within a single zone.
+#[derive(Clone, Debug, Default)]
+struct ZoneStats {
+    n: usize,
+    min: f64,
+    max: f64,
+    mean: f64,
+    median: f64,
+    std_dev: f64,
+    range: f64,
+    total: f64,
+    percentiles: Vec<f64>,
+}
+
+impl ZoneStats {
+    /// Calculates the statistics of a set of values. The values are sorted in place.
+    fn from_values(values: &mut Vec<f64>, percentiles: &[f64]) -> ZoneStats {
+        let n = values.len();
+        if n == 0 {
+            return ZoneStats {
+                percentiles: vec![f64::NAN; percentiles.len()],
+                ..Default::default()
+            };
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+        let total: f64 = values.iter().sum();
+        let mean = total / n as f64;
+        let std_dev = if n > 1 {
+            (values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0f64
+        };
+        let min = values[0];
+        let max = values[n - 1];
+        ZoneStats {
+            n: n,
+            min: min,
+            max: max,
+            mean: mean,
+            median: percentile(values, 50f64),
+            std_dev: std_dev,
+            range: max - min,
+            total: total,
+            percentiles: percentiles.iter().map(|p| percentile(values, *p)).collect(),
+        }
+    }
+
+    /// Returns the statistics in output order: min, max, mean, median, std. dev., range,
+    /// total, followed by the percentiles.
+    fn values(&self) -> Vec<f64> {
+        let mut ret = vec![
+            self.min,
+            self.max,
+            self.mean,
+            self.median,
+            self.std_dev,
+            self.range,
+            self.total,
+        ];
+        ret.extend_from_slice(&self.percentiles);
+        ret
+    }
+}
+
+/// Returns the p-th percentile (0-100) of a sorted slice, using linear interpolation
+/// between the closest ranks.
+pub(super) fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = p / 100f64 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+pub(super) fn percentile_field_name(p: f64) -> String {
+    format!("P{}", p).replace(".", "_")
+}
+
+fn write_zone_stats_csv(
+    file_name: &str,
+    ids: &[String],
+    stats: &[ZoneStats],
+    percentiles: &[f64],
+) -> Result<(), Error> {
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+    let mut header = String::from("ID,COUNT,MIN,MAX,MEAN,MEDIAN,STDEV,RANGE,TOTAL");
+    for p in percentiles {
+        header.push_str(&format!(",{}", percentile_field_name(*p)));
+    }
+    writer.write_all(format!("{}\n", header).as_bytes())?;
+    for i in 0..ids.len() {
+        let mut line = format!("{},{}", ids[i], stats[i].n);
+        for v in stats[i].values() {
+            if v.is_nan() || stats[i].n == 0 {
+                line.push_str(",");
+            } else {
+                line.push_str(&format!(",{}", v));
+            }
+        }
+        writer.write_all(format!("{}\n", line).as_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -1,18 +1,68 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod daemon;
 pub mod data_tools;
+#[cfg(not(target_arch = "wasm32"))]
+mod distributed;
 pub mod gis_analysis;
 pub mod hydro_analysis;
 pub mod image_analysis;
 pub mod lidar_analysis;
 pub mod math_stat_analysis;
+mod pipeline;
+#[cfg(not(target_arch = "wasm32"))]
+mod server;
+pub mod soil_analysis;
 pub mod stream_network_analysis;
 pub mod terrain_analysis;
+pub mod time_series_analysis;
 
-use crate::utils::get_formatted_elapsed_time;
+use crate::dataset::{remove_dataset, store_dataset, Dataset, MEMORY_PREFIX};
+use crate::raster::{set_default_compression, Raster, RasterCompression, RasterPredictor};
+use crate::utils::gpu::{set_device, Device};
+use crate::utils::memory::{parse_memory_size, set_memory_budget};
+use crate::utils::random::set_seed;
+use crate::utils::{get_formatted_elapsed_time, Instant};
 use serde_json;
 use std::io::{Error, ErrorKind};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 // use tools;
 
+/// Set by the `--resample_inputs` flag. When true, raster inputs that do not share
+/// the grid and CRS of a tool's base input are resampled rather than rejected.
+static RESAMPLE_INPUTS: AtomicBool = AtomicBool::new(false);
+
+/// Used to give the datasets of each in-memory tool run unique names.
+static IN_MEMORY_RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Checks that a raster `input` shares the coordinate reference system and grid
+/// (rows, columns, resolution, and extent) of the `base` raster. If it does not,
+/// either an error describing the mismatch is returned or, when the user has
+/// opted in with the `--resample_inputs` flag, the input is resampled onto the
+/// base grid (reprojecting if necessary) using nearest-neighbour interpolation.
+pub fn align_raster_input(base: &Raster, input: Raster) -> Result<Raster, Error> {
+    let same_crs = base.has_same_crs(&input);
+    if same_crs && base.is_grid_aligned_with(&input) {
+        return Ok(input);
+    }
+    if RESAMPLE_INPUTS.load(Ordering::SeqCst) {
+        return Ok(input.resample_to_grid(base));
+    }
+    let problem = if !same_crs {
+        "are in different coordinate reference systems"
+    } else {
+        "do not share the same grid (rows, columns, resolution and extent)"
+    };
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "The input rasters {}:\n  {}\n  {}\nUse the --resample_inputs flag to resample inputs onto the grid of the first input automatically.",
+            problem,
+            base.describe_grid(),
+            input.describe_grid()
+        ),
+    ))
+}
+
 #[derive(Default)]
 pub struct ToolManager {
     pub working_dir: String,
@@ -31,10 +81,14 @@ impl ToolManager {
         tool_names.push("CleanVector".to_string());
         tool_names.push("ConvertNodataToZero".to_string());
         tool_names.push("ConvertRasterFormat".to_string());
+        tool_names.push("ConvertToWhiteboxRaster".to_string());
         tool_names.push("CsvPointsToVector".to_string());
         tool_names.push("ExportTableToCsv".to_string());
+        tool_names.push("ExportTableToParquet".to_string());
+        tool_names.push("FieldCalculator".to_string());
         tool_names.push("JoinTables".to_string());
         tool_names.push("LinesToPolygons".to_string());
+        tool_names.push("ListDatabaseLayers".to_string());
         tool_names.push("MergeTableWithCsv".to_string());
         tool_names.push("MergeVectors".to_string());
         tool_names.push("ModifyNoDataValue".to_string());
@@ -42,16 +96,20 @@ impl ToolManager {
         tool_names.push("NewRasterFromBase".to_string());
         tool_names.push("PolygonsToLines".to_string());
         tool_names.push("PrintGeoTiffTags".to_string());
+        tool_names.push("RasterToMesh".to_string());
         tool_names.push("RasterToVectorLines".to_string());
         tool_names.push("RasterToVectorPoints".to_string());
         tool_names.push("RasterToVectorPolygons".to_string());
         tool_names.push("ReinitializeAttributeTable".to_string());
         tool_names.push("RemovePolygonHoles".to_string());
+        tool_names.push("ReprojectRaster".to_string());
+        tool_names.push("ReprojectVector".to_string());
         tool_names.push("SetNodataValue".to_string());
         tool_names.push("SinglePartToMultiPart".to_string());
         tool_names.push("VectorLinesToRaster".to_string());
         tool_names.push("VectorPointsToRaster".to_string());
         tool_names.push("VectorPolygonsToRaster".to_string());
+        tool_names.push("VectorToRaster".to_string());
 
         // gis_analysis
         tool_names.push("AggregateRaster".to_string());
@@ -60,7 +118,7 @@ impl ToolManager {
         tool_names.push("BlockMinimumGridding".to_string());
         tool_names.push("BoundaryShapeComplexity".to_string());
         tool_names.push("BufferRaster".to_string());
-        // tool_names.push("BufferVector".to_string());
+        tool_names.push("BufferVector".to_string());
         tool_names.push("Centroid".to_string());
         tool_names.push("CentroidVector".to_string());
         tool_names.push("Clip".to_string());
@@ -79,21 +137,25 @@ impl ToolManager {
         tool_names.push("Dissolve".to_string());
         tool_names.push("EdgeProportion".to_string());
         tool_names.push("EliminateCoincidentPoints".to_string());
+        tool_names.push("EliminateSmallPatches".to_string());
         tool_names.push("ElongationRatio".to_string());
         tool_names.push("Erase".to_string());
         tool_names.push("ErasePolygonFromRaster".to_string());
         tool_names.push("EuclideanAllocation".to_string());
         tool_names.push("EuclideanDistance".to_string());
         tool_names.push("ExtendVectorLines".to_string());
+        tool_names.push("ExtractByAttribute".to_string());
         tool_names.push("ExtractNodes".to_string());
         tool_names.push("ExtractRasterValuesAtPoints".to_string());
         tool_names.push("FindLowestOrHighestPoints".to_string());
         tool_names.push("FindPatchOrClassEdgeCells".to_string());
+        tool_names.push("GeodesicDistance".to_string());
         tool_names.push("HighestPosition".to_string());
         tool_names.push("HoleProportion".to_string());
         tool_names.push("IdwInterpolation".to_string());
         tool_names.push("Intersect".to_string());
         tool_names.push("LayerFootprint".to_string());
+        tool_names.push("LeastCostPath".to_string());
         tool_names.push("LinearityIndex".to_string());
         tool_names.push("LineIntersections".to_string());
         tool_names.push("LowestPosition".to_string());
@@ -118,6 +180,7 @@ impl ToolManager {
         tool_names.push("PickFromList".to_string());
         tool_names.push("PolygonArea".to_string());
         tool_names.push("PolygonLongAxis".to_string());
+        tool_names.push("PolygonMedialAxis".to_string());
         tool_names.push("PolygonPerimeter".to_string());
         tool_names.push("PolygonShortAxis".to_string());
         tool_names.push("Polygonize".to_string());
@@ -132,7 +195,10 @@ impl ToolManager {
         tool_names.push("RelatedCircumscribingCircle".to_string());
         tool_names.push("ShapeComplexityIndex".to_string());
         tool_names.push("ShapeComplexityIndexRaster".to_string());
+        tool_names.push("SimplifyVectors".to_string());
+        tool_names.push("SkeletonizeRaster".to_string());
         tool_names.push("SmoothVectors".to_string());
+        tool_names.push("SpatialJoin".to_string());
         tool_names.push("SplitWithLines".to_string());
         tool_names.push("SumOverlay".to_string());
         tool_names.push("SymmetricalDifference".to_string());
@@ -149,19 +215,31 @@ impl ToolManager {
         tool_names.push("Basins".to_string());
         tool_names.push("BreachDepressions".to_string());
         tool_names.push("BreachDepressionsLeastCost".to_string());
+        tool_names.push("BreachEmbankments".to_string());
         tool_names.push("BreachSingleCellPits".to_string());
         tool_names.push("BurnStreamsAtRoads".to_string());
+        tool_names.push("CalibrateChannelThreshold".to_string());
+        tool_names.push("CoastalFlooding".to_string());
+        tool_names.push("ConvertD8Pointer".to_string());
+        tool_names.push("ConvertDinfPointer".to_string());
+        tool_names.push("CurveNumberRunoff".to_string());
         tool_names.push("D8FlowAccumulation".to_string());
         tool_names.push("D8MassFlux".to_string());
         tool_names.push("D8Pointer".to_string());
+        tool_names.push("DegreeDaySnowmelt".to_string());
+        tool_names.push("DepressionHierarchy".to_string());
         tool_names.push("DepthInSink".to_string());
         tool_names.push("DInfFlowAccumulation".to_string());
         tool_names.push("DInfMassFlux".to_string());
         tool_names.push("DInfPointer".to_string());
         tool_names.push("DownslopeDistanceToStream".to_string());
+        tool_names.push("DownslopeFlowpathMetrics".to_string());
         tool_names.push("DownslopeFlowpathLength".to_string());
+        tool_names.push("EdgeContamination".to_string());
         tool_names.push("ElevationAboveStream".to_string());
         tool_names.push("ElevationAboveStreamEuclidean".to_string());
+        tool_names.push("ExportHydrologicModel".to_string());
+        tool_names.push("ExtractValleyBottoms".to_string());
         tool_names.push("FD8FlowAccumulation".to_string());
         tool_names.push("FD8Pointer".to_string());
         tool_names.push("FillBurn".to_string());
@@ -169,6 +247,7 @@ impl ToolManager {
         tool_names.push("FillDepressionsPlanchonAndDarboux".to_string());
         tool_names.push("FillDepressionsWangAndLiu".to_string());
         tool_names.push("FillSingleCellPits".to_string());
+        tool_names.push("FillSpillMerge".to_string());
         tool_names.push("FindNoFlowCells".to_string());
         tool_names.push("FindParallelFlow".to_string());
         tool_names.push("FlattenLakes".to_string());
@@ -183,9 +262,13 @@ impl ToolManager {
         tool_names.push("LongestFlowpath".to_string());
         tool_names.push("MaxUpslopeFlowpathLength".to_string());
         tool_names.push("MDInfFlowAccumulation".to_string());
+        tool_names.push("ModflowIbound".to_string());
+        tool_names.push("ModflowLayerElevations".to_string());
+        tool_names.push("ModflowRiverCells".to_string());
         tool_names.push("NumInflowingNeighbours".to_string());
         tool_names.push("RaiseWalls".to_string());
         tool_names.push("Rho8Pointer".to_string());
+        tool_names.push("SedimentConnectivityIndex".to_string());
         tool_names.push("Sink".to_string());
         tool_names.push("SnapPourPoints".to_string());
         tool_names.push("StochasticDepressionAnalysis".to_string());
@@ -194,28 +277,40 @@ impl ToolManager {
         tool_names.push("TraceDownslopeFlowpaths".to_string());
         tool_names.push("UnnestBasins".to_string());
         tool_names.push("UpslopeDepressionStorage".to_string());
+        tool_names.push("UpslopeStatistics".to_string());
         tool_names.push("Watershed".to_string());
 
         // image_analysis
         tool_names.push("AdaptiveFilter".to_string());
+        tool_names.push("ApplyClassifier".to_string());
+        tool_names.push("AtmosphericCorrection".to_string());
         tool_names.push("BalanceContrastEnhancement".to_string());
         tool_names.push("BilateralFilter".to_string());
         tool_names.push("ChangeVectorAnalysis".to_string());
         tool_names.push("Closing".to_string());
+        tool_names.push("CloudMask".to_string());
         tool_names.push("ConservativeSmoothingFilter".to_string());
         tool_names.push("CornerDetection".to_string());
         tool_names.push("CorrectVignetting".to_string());
         tool_names.push("CreateColourComposite".to_string());
+        tool_names.push("Dilation".to_string());
         tool_names.push("DirectDecorrelationStretch".to_string());
         tool_names.push("DiversityFilter".to_string());
         tool_names.push("DiffOfGaussianFilter".to_string());
         tool_names.push("EdgePreservingMeanFilter".to_string());
         tool_names.push("EmbossFilter".to_string());
+        tool_names.push("EnhancedVegetationIndex".to_string());
+        tool_names.push("Erosion".to_string());
         tool_names.push("FastAlmostGaussianFilter".to_string());
         tool_names.push("FlipImage".to_string());
+        tool_names.push("ForwardFft".to_string());
+        tool_names.push("FrequencyDomainFilter".to_string());
+        tool_names.push("FrostFilter".to_string());
         tool_names.push("GammaCorrection".to_string());
+        tool_names.push("GammaMapFilter".to_string());
         tool_names.push("GaussianContrastStretch".to_string());
         tool_names.push("GaussianFilter".to_string());
+        tool_names.push("GlcmTexture".to_string());
         tool_names.push("HighPassFilter".to_string());
         tool_names.push("HighPassMedianFilter".to_string());
         tool_names.push("HistogramEqualization".to_string());
@@ -224,11 +319,14 @@ impl ToolManager {
         tool_names.push("IhsToRgb".to_string());
         tool_names.push("ImageStackProfile".to_string());
         tool_names.push("IntegralImage".to_string());
+        tool_names.push("InverseFft".to_string());
+        tool_names.push("IsodataClustering".to_string());
         tool_names.push("KMeansClustering".to_string());
         tool_names.push("KNearestMeanFilter".to_string());
         tool_names.push("LaplacianFilter".to_string());
         tool_names.push("LaplacianOfGaussianFilter".to_string());
         tool_names.push("LeeSigmaFilter".to_string());
+        tool_names.push("LeeSpeckleFilter".to_string());
         tool_names.push("LineDetectionFilter".to_string());
         tool_names.push("LineThinning".to_string());
         tool_names.push("MajorityFilter".to_string());
@@ -238,9 +336,14 @@ impl ToolManager {
         tool_names.push("MinMaxContrastStretch".to_string());
         tool_names.push("MinimumFilter".to_string());
         tool_names.push("ModifiedKMeansClustering".to_string());
+        tool_names.push("ModifiedNormalizedDifferenceWaterIndex".to_string());
+        tool_names.push("MorphologicalFilter".to_string());
         tool_names.push("Mosaic".to_string());
         tool_names.push("MosaicWithFeathering".to_string());
+        tool_names.push("NormalizedBurnRatio".to_string());
         tool_names.push("NormalizedDifferenceIndex".to_string());
+        tool_names.push("NormalizedDifferenceSnowIndex".to_string());
+        tool_names.push("NormalizedDifferenceWaterIndex".to_string());
         tool_names.push("OlympicFilter".to_string());
         tool_names.push("Opening".to_string());
         tool_names.push("PanchromaticSharpening".to_string());
@@ -248,19 +351,26 @@ impl ToolManager {
         tool_names.push("PercentileFilter".to_string());
         tool_names.push("PrewittFilter".to_string());
         tool_names.push("RangeFilter".to_string());
+        tool_names.push("RefinedLeeFilter".to_string());
+        tool_names.push("RemovePeriodicNoise".to_string());
         tool_names.push("RemoveSpurs".to_string());
         tool_names.push("Resample".to_string());
         tool_names.push("RgbToIhs".to_string());
         tool_names.push("RobertsCrossFilter".to_string());
         tool_names.push("ScharrFilter".to_string());
+        tool_names.push("SelectivePcaChangeDetection".to_string());
         tool_names.push("SigmoidalContrastStretch".to_string());
+        tool_names.push("SlicSegmentation".to_string());
         tool_names.push("SobelFilter".to_string());
+        tool_names.push("SoilAdjustedVegetationIndex".to_string());
+        tool_names.push("SpectralIndex".to_string());
         tool_names.push("SplitColourComposite".to_string());
         tool_names.push("StandardDeviationContrastStretch".to_string());
         tool_names.push("StandardDeviationFilter".to_string());
         tool_names.push("ThickenRasterLine".to_string());
         tool_names.push("TophatTransform".to_string());
         tool_names.push("TotalFilter".to_string());
+        tool_names.push("TrainClassifier".to_string());
         tool_names.push("UnsharpMasking".to_string());
         tool_names.push("UserDefinedWeightsFilter".to_string());
         tool_names.push("WriteFunctionMemoryInsertion".to_string());
@@ -281,6 +391,7 @@ impl ToolManager {
         tool_names.push("LasToAscii".to_string());
         tool_names.push("LasToMultipointShapefile".to_string());
         tool_names.push("LasToShapefile".to_string());
+        tool_names.push("LidarClassifyNoise".to_string());
         tool_names.push("LidarClassifySubset".to_string());
         tool_names.push("LidarColourize".to_string());
         tool_names.push("LidarConstructVectorTIN".to_string());
@@ -294,8 +405,10 @@ impl ToolManager {
         tool_names.push("LidarJoin".to_string());
         tool_names.push("LidarKappaIndex".to_string());
         tool_names.push("LidarNearestNeighbourGridding".to_string());
+        tool_names.push("LidarNormalizeIntensity".to_string());
         tool_names.push("LidarPointDensity".to_string());
         tool_names.push("LidarPointStats".to_string());
+        tool_names.push("LidarPowerlineDetection".to_string());
         tool_names.push("LidarRbfInterpolation".to_string());
         tool_names.push("LidarRansacPlanes".to_string());
         tool_names.push("LidarRemoveDuplicates".to_string());
@@ -306,8 +419,12 @@ impl ToolManager {
         tool_names.push("LidarThinHighDensity".to_string());
         tool_names.push("LidarTile".to_string());
         tool_names.push("LidarTileFootprint".to_string());
+        tool_names.push("LidarTiledWorkflow".to_string());
         tool_names.push("LidarTINGridding".to_string());
+        tool_names.push("LidarToMesh".to_string());
+        tool_names.push("LidarToParquet".to_string());
         tool_names.push("LidarTophatTransform".to_string());
+        tool_names.push("LidarVoxelMetrics".to_string());
         tool_names.push("NormalVectors".to_string());
         tool_names.push("SelectTilesByPolygon".to_string());
 
@@ -338,8 +455,12 @@ impl ToolManager {
         tool_names.push("EqualTo".to_string());
         tool_names.push("Exp".to_string());
         tool_names.push("Exp2".to_string());
+        tool_names.push("OrdinaryKriging".to_string());
+        tool_names.push("SemivariogramAnalysis".to_string());
         tool_names.push("ZonalStatistics".to_string());
         tool_names.push("Floor".to_string());
+        tool_names.push("GaussianSimulation".to_string());
+        tool_names.push("GlobalSpatialAutocorrelation".to_string());
         tool_names.push("GreaterThan".to_string());
         tool_names.push("ImageAutocorrelation".to_string());
         tool_names.push("ImageCorrelation".to_string());
@@ -356,6 +477,7 @@ impl ToolManager {
         tool_names.push("KsTestForNormality".to_string());
         tool_names.push("LessThan".to_string());
         tool_names.push("ListUniqueValues".to_string());
+        tool_names.push("LocalSpatialAutocorrelation".to_string());
         tool_names.push("Log10".to_string());
         tool_names.push("Log2".to_string());
         tool_names.push("Ln".to_string());
@@ -397,7 +519,14 @@ impl ToolManager {
         tool_names.push("Xor".to_string());
         tool_names.push("ZScores".to_string());
 
+        // soil_analysis
+        tool_names.push("RusleLsFactor".to_string());
+        tool_names.push("RusleSoilLoss".to_string());
+        tool_names.push("SoilMappingCovariates".to_string());
+
         // stream_network_analysis
+        tool_names.push("ChannelHydraulicGeometry".to_string());
+        tool_names.push("ChiIndex".to_string());
         tool_names.push("DistanceToOutlet".to_string());
         tool_names.push("ExtractStreams".to_string());
         tool_names.push("ExtractValleys".to_string());
@@ -405,6 +534,7 @@ impl ToolManager {
         tool_names.push("FindMainStem".to_string());
         tool_names.push("HackStreamOrder".to_string());
         tool_names.push("HortonStreamOrder".to_string());
+        tool_names.push("KnickpointDetection".to_string());
         tool_names.push("LengthOfUpstreamChannels".to_string());
         tool_names.push("LongProfile".to_string());
         tool_names.push("LongProfileFromPoints".to_string());
@@ -417,6 +547,7 @@ impl ToolManager {
         tool_names.push("StreamLinkIdentifier".to_string());
         tool_names.push("StreamLinkLength".to_string());
         tool_names.push("StreamLinkSlope".to_string());
+        tool_names.push("StreamNetworkToVector".to_string());
         tool_names.push("StreamSlopeContinuous".to_string());
         tool_names.push("TopologicalStreamOrder".to_string());
         tool_names.push("TributaryIdentifier".to_string());
@@ -426,6 +557,8 @@ impl ToolManager {
         tool_names.push("AverageNormalVectorAngularDeviation".to_string());
         tool_names.push("CircularVarianceOfAspect".to_string());
         tool_names.push("ContoursFromRaster".to_string());
+        tool_names.push("CoregisterDems".to_string());
+        tool_names.push("DemQualityReport".to_string());
         tool_names.push("DevFromMeanElev".to_string());
         tool_names.push("DiffFromMeanElev".to_string());
         tool_names.push("DirectionalRelief".to_string());
@@ -436,6 +569,7 @@ impl ToolManager {
         tool_names.push("ElevPercentile".to_string());
         tool_names.push("ElevRelativeToMinMax".to_string());
         tool_names.push("ElevRelativeToWatershedMinMax".to_string());
+        tool_names.push("ExtractProfiles".to_string());
         tool_names.push("FeaturePreservingSmoothing".to_string());
         tool_names.push("FetchAnalysis".to_string());
         tool_names.push("FillMissingData".to_string());
@@ -460,12 +594,16 @@ impl ToolManager {
         tool_names.push("MultiscaleTopographicPositionImage".to_string());
         tool_names.push("NumDownslopeNeighbours".to_string());
         tool_names.push("NumUpslopeNeighbours".to_string());
+        tool_names.push("Openness".to_string());
         tool_names.push("PennockLandformClass".to_string());
         tool_names.push("PercentElevRange".to_string());
         tool_names.push("PlanCurvature".to_string());
         tool_names.push("ProfileCurvature".to_string());
         tool_names.push("Profile".to_string());
         tool_names.push("RelativeAspect".to_string());
+        tool_names.push("SagaWetnessIndex".to_string());
+        tool_names.push("SkyViewFactor".to_string());
+        tool_names.push("SolarRadiation".to_string());
         tool_names.push("StreamPowerIndex".to_string());
         tool_names.push("RelativeTopographicPosition".to_string());
         tool_names.push("RemoveOffTerrainObjects".to_string());
@@ -480,7 +618,22 @@ impl ToolManager {
         tool_names.push("TotalCurvature".to_string());
         tool_names.push("Viewshed".to_string());
         tool_names.push("VisibilityIndex".to_string());
+        tool_names.push("WaterFetch".to_string());
+        tool_names.push("WatershedHypsometry".to_string());
         tool_names.push("WetnessIndex".to_string());
+        tool_names.push("WindExpositionIndex".to_string());
+        tool_names.push("WinstralShelterIndex".to_string());
+
+        // time_series_analysis
+        tool_names.push("ExtractShorelines".to_string());
+        tool_names.push("PhenologyMetrics".to_string());
+        tool_names.push("ShorelineChangeAnalysis".to_string());
+        tool_names.push("TimeSeriesAggregate".to_string());
+        tool_names.push("TimeSeriesAnomalies".to_string());
+        tool_names.push("TimeSeriesGapFill".to_string());
+        tool_names.push("TimeSeriesSavitzkyGolay".to_string());
+        tool_names.push("TimeSeriesTrend".to_string());
+        tool_names.push("TimeSeriesWhittaker".to_string());
 
         tool_names.sort();
 
@@ -501,10 +654,16 @@ impl ToolManager {
             "cleanvector" => Some(Box::new(data_tools::CleanVector::new())),
             "convertnodatatozero" => Some(Box::new(data_tools::ConvertNodataToZero::new())),
             "convertrasterformat" => Some(Box::new(data_tools::ConvertRasterFormat::new())),
+            "converttowhiteboxraster" => {
+                Some(Box::new(data_tools::ConvertToWhiteboxRaster::new()))
+            }
             "csvpointstovector" => Some(Box::new(data_tools::CsvPointsToVector::new())),
             "exporttabletocsv" => Some(Box::new(data_tools::ExportTableToCsv::new())),
+            "exporttabletoparquet" => Some(Box::new(data_tools::ExportTableToParquet::new())),
+            "fieldcalculator" => Some(Box::new(data_tools::FieldCalculator::new())),
             "jointables" => Some(Box::new(data_tools::JoinTables::new())),
             "linestopolygons" => Some(Box::new(data_tools::LinesToPolygons::new())),
+            "listdatabaselayers" => Some(Box::new(data_tools::ListDatabaseLayers::new())),
             "mergetablewithcsv" => Some(Box::new(data_tools::MergeTableWithCsv::new())),
             "mergevectors" => Some(Box::new(data_tools::MergeVectors::new())),
             "modifynodatavalue" => Some(Box::new(data_tools::ModifyNoDataValue::new())),
@@ -512,6 +671,7 @@ impl ToolManager {
             "newrasterfrombase" => Some(Box::new(data_tools::NewRasterFromBase::new())),
             "polygonstolines" => Some(Box::new(data_tools::PolygonsToLines::new())),
             "printgeotifftags" => Some(Box::new(data_tools::PrintGeoTiffTags::new())),
+            "rastertomesh" => Some(Box::new(data_tools::RasterToMesh::new())),
             "rastertovectorlines" => Some(Box::new(data_tools::RasterToVectorLines::new())),
             "rastertovectorpoints" => Some(Box::new(data_tools::RasterToVectorPoints::new())),
             "rastertovectorpolygons" => Some(Box::new(data_tools::RasterToVectorPolygons::new())),
@@ -519,11 +679,14 @@ impl ToolManager {
                 Some(Box::new(data_tools::ReinitializeAttributeTable::new()))
             }
             "removepolygonholes" => Some(Box::new(data_tools::RemovePolygonHoles::new())),
+            "reprojectraster" => Some(Box::new(data_tools::ReprojectRaster::new())),
+            "reprojectvector" => Some(Box::new(data_tools::ReprojectVector::new())),
             "setnodatavalue" => Some(Box::new(data_tools::SetNodataValue::new())),
             "singleparttomultipart" => Some(Box::new(data_tools::SinglePartToMultiPart::new())),
             "vectorlinestoraster" => Some(Box::new(data_tools::VectorLinesToRaster::new())),
             "vectorpointstoraster" => Some(Box::new(data_tools::VectorPointsToRaster::new())),
             "vectorpolygonstoraster" => Some(Box::new(data_tools::VectorPolygonsToRaster::new())),
+            "vectortoraster" => Some(Box::new(data_tools::VectorToRaster::new())),
 
             // gis_analysis
             "aggregateraster" => Some(Box::new(gis_analysis::AggregateRaster::new())),
@@ -534,7 +697,7 @@ impl ToolManager {
                 Some(Box::new(gis_analysis::BoundaryShapeComplexity::new()))
             }
             "bufferraster" => Some(Box::new(gis_analysis::BufferRaster::new())),
-            // "buffervector" => Some(Box::new(gis_analysis::BufferVector::new())),
+            "buffervector" => Some(Box::new(gis_analysis::BufferVector::new())),
             "centroid" => Some(Box::new(gis_analysis::Centroid::new())),
             "centroidvector" => Some(Box::new(gis_analysis::CentroidVector::new())),
             "clip" => Some(Box::new(gis_analysis::Clip::new())),
@@ -559,12 +722,14 @@ impl ToolManager {
             "eliminatecoincidentpoints" => {
                 Some(Box::new(gis_analysis::EliminateCoincidentPoints::new()))
             }
+            "eliminatesmallpatches" => Some(Box::new(gis_analysis::EliminateSmallPatches::new())),
             "elongationratio" => Some(Box::new(gis_analysis::ElongationRatio::new())),
             "erase" => Some(Box::new(gis_analysis::Erase::new())),
             "erasepolygonfromraster" => Some(Box::new(gis_analysis::ErasePolygonFromRaster::new())),
             "euclideanallocation" => Some(Box::new(gis_analysis::EuclideanAllocation::new())),
             "euclideandistance" => Some(Box::new(gis_analysis::EuclideanDistance::new())),
             "extendvectorlines" => Some(Box::new(gis_analysis::ExtendVectorLines::new())),
+            "extractbyattribute" => Some(Box::new(gis_analysis::ExtractByAttribute::new())),
             "extractnodes" => Some(Box::new(gis_analysis::ExtractNodes::new())),
             "extractrastervaluesatpoints" => {
                 Some(Box::new(gis_analysis::ExtractRasterValuesAtPoints::new()))
@@ -575,11 +740,13 @@ impl ToolManager {
             "findpatchorclassedgecells" => {
                 Some(Box::new(gis_analysis::FindPatchOrClassEdgeCells::new()))
             }
+            "geodesicdistance" => Some(Box::new(gis_analysis::GeodesicDistance::new())),
             "highestposition" => Some(Box::new(gis_analysis::HighestPosition::new())),
             "holeproportion" => Some(Box::new(gis_analysis::HoleProportion::new())),
             "idwinterpolation" => Some(Box::new(gis_analysis::IdwInterpolation::new())),
             "intersect" => Some(Box::new(gis_analysis::Intersect::new())),
             "layerfootprint" => Some(Box::new(gis_analysis::LayerFootprint::new())),
+            "leastcostpath" => Some(Box::new(gis_analysis::LeastCostPath::new())),
             "lineintersections" => Some(Box::new(gis_analysis::LineIntersections::new())),
             "linearityindex" => Some(Box::new(gis_analysis::LinearityIndex::new())),
             "lowestposition" => Some(Box::new(gis_analysis::LowestPosition::new())),
@@ -610,6 +777,7 @@ impl ToolManager {
             "pickfromlist" => Some(Box::new(gis_analysis::PickFromList::new())),
             "polygonarea" => Some(Box::new(gis_analysis::PolygonArea::new())),
             "polygonlongaxis" => Some(Box::new(gis_analysis::PolygonLongAxis::new())),
+            "polygonmedialaxis" => Some(Box::new(gis_analysis::PolygonMedialAxis::new())),
             "polygonperimeter" => Some(Box::new(gis_analysis::PolygonPerimeter::new())),
             "polygonshortaxis" => Some(Box::new(gis_analysis::PolygonShortAxis::new())),
             "polygonize" => Some(Box::new(gis_analysis::Polygonize::new())),
@@ -630,10 +798,15 @@ impl ToolManager {
             "shapecomplexityindexraster" => {
                 Some(Box::new(gis_analysis::ShapeComplexityIndexRaster::new()))
             }
+            "simplifyvectors" => Some(Box::new(gis_analysis::SimplifyVectors::new())),
+            "skeletonizeraster" => Some(Box::new(gis_analysis::SkeletonizeRaster::new())),
             "smoothvectors" => Some(Box::new(gis_analysis::SmoothVectors::new())),
+            "spatialjoin" => Some(Box::new(gis_analysis::SpatialJoin::new())),
             "splitwithlines" => Some(Box::new(gis_analysis::SplitWithLines::new())),
             "sumoverlay" => Some(Box::new(gis_analysis::SumOverlay::new())),
-            "symmetricaldifference" => Some(Box::new(gis_analysis::SymmetricalDifference::new())),
+            "symmetricaldifference" | "symmetricdifference" => {
+                Some(Box::new(gis_analysis::SymmetricalDifference::new()))
+            }
             "tingridding" => Some(Box::new(gis_analysis::TINGridding::new())),
             "union" => Some(Box::new(gis_analysis::Union::new())),
             "vectorhexbinning" => Some(Box::new(gis_analysis::VectorHexBinning::new())),
@@ -651,11 +824,21 @@ impl ToolManager {
             "breachdepressionsleastcost" => {
                 Some(Box::new(hydro_analysis::BreachDepressionsLeastCost::new()))
             }
+            "breachembankments" => Some(Box::new(hydro_analysis::BreachEmbankments::new())),
             "breachsinglecellpits" => Some(Box::new(hydro_analysis::BreachSingleCellPits::new())),
             "burnstreamsatroads" => Some(Box::new(hydro_analysis::BurnStreamsAtRoads::new())),
+            "calibratechannelthreshold" => {
+                Some(Box::new(hydro_analysis::CalibrateChannelThreshold::new()))
+            }
+            "coastalflooding" => Some(Box::new(hydro_analysis::CoastalFlooding::new())),
+            "convertd8pointer" => Some(Box::new(hydro_analysis::ConvertD8Pointer::new())),
+            "convertdinfpointer" => Some(Box::new(hydro_analysis::ConvertDinfPointer::new())),
+            "curvenumberrunoff" => Some(Box::new(hydro_analysis::CurveNumberRunoff::new())),
             "d8flowaccumulation" => Some(Box::new(hydro_analysis::D8FlowAccumulation::new())),
             "d8massflux" => Some(Box::new(hydro_analysis::D8MassFlux::new())),
             "d8pointer" => Some(Box::new(hydro_analysis::D8Pointer::new())),
+            "degreedaysnowmelt" => Some(Box::new(hydro_analysis::DegreeDaySnowmelt::new())),
+            "depressionhierarchy" => Some(Box::new(hydro_analysis::DepressionHierarchy::new())),
             "depthinsink" => Some(Box::new(hydro_analysis::DepthInSink::new())),
             "dinfflowaccumulation" => Some(Box::new(hydro_analysis::DInfFlowAccumulation::new())),
             "dinfmassflux" => Some(Box::new(hydro_analysis::DInfMassFlux::new())),
@@ -663,13 +846,19 @@ impl ToolManager {
             "downslopedistancetostream" => {
                 Some(Box::new(hydro_analysis::DownslopeDistanceToStream::new()))
             }
+            "downslopeflowpathmetrics" => {
+                Some(Box::new(hydro_analysis::DownslopeFlowpathMetrics::new()))
+            }
             "downslopeflowpathlength" => {
                 Some(Box::new(hydro_analysis::DownslopeFlowpathLength::new()))
             }
+            "edgecontamination" => Some(Box::new(hydro_analysis::EdgeContamination::new())),
             "elevationabovestream" => Some(Box::new(hydro_analysis::ElevationAboveStream::new())),
             "elevationabovestreameuclidean" => Some(Box::new(
                 hydro_analysis::ElevationAboveStreamEuclidean::new(),
             )),
+            "exporthydrologicmodel" => Some(Box::new(hydro_analysis::ExportHydrologicModel::new())),
+            "extractvalleybottoms" => Some(Box::new(hydro_analysis::ExtractValleyBottoms::new())),
             "fd8flowaccumulation" => Some(Box::new(hydro_analysis::FD8FlowAccumulation::new())),
             "fd8pointer" => Some(Box::new(hydro_analysis::FD8Pointer::new())),
             "fillburn" => Some(Box::new(hydro_analysis::FillBurn::new())),
@@ -681,6 +870,7 @@ impl ToolManager {
                 Some(Box::new(hydro_analysis::FillDepressionsWangAndLiu::new()))
             }
             "fillsinglecellpits" => Some(Box::new(hydro_analysis::FillSingleCellPits::new())),
+            "fillspillmerge" => Some(Box::new(hydro_analysis::FillSpillMerge::new())),
             "findnoflowcells" => Some(Box::new(hydro_analysis::FindNoFlowCells::new())),
             "findparallelflow" => Some(Box::new(hydro_analysis::FindParallelFlow::new())),
             "flattenlakes" => Some(Box::new(hydro_analysis::FlattenLakes::new())),
@@ -699,11 +889,19 @@ impl ToolManager {
                 Some(Box::new(hydro_analysis::MaxUpslopeFlowpathLength::new()))
             }
             "mdinfflowaccumulation" => Some(Box::new(hydro_analysis::MDInfFlowAccumulation::new())),
+            "modflowibound" => Some(Box::new(hydro_analysis::ModflowIbound::new())),
+            "modflowlayerelevations" => {
+                Some(Box::new(hydro_analysis::ModflowLayerElevations::new()))
+            }
+            "modflowrivercells" => Some(Box::new(hydro_analysis::ModflowRiverCells::new())),
             "numinflowingneighbours" => {
                 Some(Box::new(hydro_analysis::NumInflowingNeighbours::new()))
             }
             "raisewalls" => Some(Box::new(hydro_analysis::RaiseWalls::new())),
             "rho8pointer" => Some(Box::new(hydro_analysis::Rho8Pointer::new())),
+            "sedimentconnectivityindex" => {
+                Some(Box::new(hydro_analysis::SedimentConnectivityIndex::new()))
+            }
             "sink" => Some(Box::new(hydro_analysis::Sink::new())),
             "snappourpoints" => Some(Box::new(hydro_analysis::SnapPourPoints::new())),
             "stochasticdepressionanalysis" => {
@@ -718,22 +916,29 @@ impl ToolManager {
             "upslopedepressionstorage" => {
                 Some(Box::new(hydro_analysis::UpslopeDepressionStorage::new()))
             }
+            "upslopestatistics" => Some(Box::new(hydro_analysis::UpslopeStatistics::new())),
             "watershed" => Some(Box::new(hydro_analysis::Watershed::new())),
 
             // image_analysis
             "adaptivefilter" => Some(Box::new(image_analysis::AdaptiveFilter::new())),
+            "applyclassifier" => Some(Box::new(image_analysis::ApplyClassifier::new())),
+            "atmosphericcorrection" => {
+                Some(Box::new(image_analysis::AtmosphericCorrection::new()))
+            }
             "balancecontrastenhancement" => {
                 Some(Box::new(image_analysis::BalanceContrastEnhancement::new()))
             }
             "bilateralfilter" => Some(Box::new(image_analysis::BilateralFilter::new())),
             "changevectoranalysis" => Some(Box::new(image_analysis::ChangeVectorAnalysis::new())),
             "closing" => Some(Box::new(image_analysis::Closing::new())),
+            "cloudmask" => Some(Box::new(image_analysis::CloudMask::new())),
             "cornerdetection" => Some(Box::new(image_analysis::CornerDetection::new())),
             "correctvignetting" => Some(Box::new(image_analysis::CorrectVignetting::new())),
             "conservativesmoothingfilter" => {
                 Some(Box::new(image_analysis::ConservativeSmoothingFilter::new()))
             }
             "createcolourcomposite" => Some(Box::new(image_analysis::CreateColourComposite::new())),
+            "dilation" => Some(Box::new(image_analysis::Dilation::new())),
             "directdecorrelationstretch" => {
                 Some(Box::new(image_analysis::DirectDecorrelationStretch::new()))
             }
@@ -743,15 +948,24 @@ impl ToolManager {
                 Some(Box::new(image_analysis::EdgePreservingMeanFilter::new()))
             }
             "embossfilter" => Some(Box::new(image_analysis::EmbossFilter::new())),
+            "enhancedvegetationindex" => {
+                Some(Box::new(image_analysis::EnhancedVegetationIndex::new()))
+            }
+            "erosion" => Some(Box::new(image_analysis::Erosion::new())),
             "fastalmostgaussianfilter" => {
                 Some(Box::new(image_analysis::FastAlmostGaussianFilter::new()))
             }
             "flipimage" => Some(Box::new(image_analysis::FlipImage::new())),
+            "forwardfft" => Some(Box::new(image_analysis::ForwardFft::new())),
+            "frequencydomainfilter" => Some(Box::new(image_analysis::FrequencyDomainFilter::new())),
+            "frostfilter" => Some(Box::new(image_analysis::FrostFilter::new())),
             "gammacorrection" => Some(Box::new(image_analysis::GammaCorrection::new())),
+            "gammamapfilter" => Some(Box::new(image_analysis::GammaMapFilter::new())),
             "gaussiancontraststretch" => {
                 Some(Box::new(image_analysis::GaussianContrastStretch::new()))
             }
             "gaussianfilter" => Some(Box::new(image_analysis::GaussianFilter::new())),
+            "glcmtexture" => Some(Box::new(image_analysis::GlcmTexture::new())),
             "highpassfilter" => Some(Box::new(image_analysis::HighPassFilter::new())),
             "highpassmedianfilter" => Some(Box::new(image_analysis::HighPassMedianFilter::new())),
             "histogramequalization" => Some(Box::new(image_analysis::HistogramEqualization::new())),
@@ -762,6 +976,8 @@ impl ToolManager {
             "ihstorgb" => Some(Box::new(image_analysis::IhsToRgb::new())),
             "imagestackprofile" => Some(Box::new(image_analysis::ImageStackProfile::new())),
             "integralimage" => Some(Box::new(image_analysis::IntegralImage::new())),
+            "inversefft" => Some(Box::new(image_analysis::InverseFft::new())),
+            "isodataclustering" => Some(Box::new(image_analysis::IsodataClustering::new())),
             "kmeansclustering" => Some(Box::new(image_analysis::KMeansClustering::new())),
             "knearestmeanfilter" => Some(Box::new(image_analysis::KNearestMeanFilter::new())),
             "laplacianfilter" => Some(Box::new(image_analysis::LaplacianFilter::new())),
@@ -769,6 +985,7 @@ impl ToolManager {
                 Some(Box::new(image_analysis::LaplacianOfGaussianFilter::new()))
             }
             "leesigmafilter" => Some(Box::new(image_analysis::LeeSigmaFilter::new())),
+            "leespecklefilter" => Some(Box::new(image_analysis::LeeSpeckleFilter::new())),
             "linedetectionfilter" => Some(Box::new(image_analysis::LineDetectionFilter::new())),
             "linethinning" => Some(Box::new(image_analysis::LineThinning::new())),
             "majorityfilter" => Some(Box::new(image_analysis::MajorityFilter::new())),
@@ -780,11 +997,22 @@ impl ToolManager {
             "modifiedkmeansclustering" => {
                 Some(Box::new(image_analysis::ModifiedKMeansClustering::new()))
             }
+            "modifiednormalizeddifferencewaterindex" => {
+                Some(Box::new(image_analysis::ModifiedNormalizedDifferenceWaterIndex::new()))
+            }
+            "morphologicalfilter" => Some(Box::new(image_analysis::MorphologicalFilter::new())),
             "mosaic" => Some(Box::new(image_analysis::Mosaic::new())),
             "mosaicwithfeathering" => Some(Box::new(image_analysis::MosaicWithFeathering::new())),
+            "normalizedburnratio" => Some(Box::new(image_analysis::NormalizedBurnRatio::new())),
             "normalizeddifferenceindex" => {
                 Some(Box::new(image_analysis::NormalizedDifferenceIndex::new()))
             }
+            "normalizeddifferencesnowindex" => {
+                Some(Box::new(image_analysis::NormalizedDifferenceSnowIndex::new()))
+            }
+            "normalizeddifferencewaterindex" => {
+                Some(Box::new(image_analysis::NormalizedDifferenceWaterIndex::new()))
+            }
             "olympicfilter" => Some(Box::new(image_analysis::OlympicFilter::new())),
             "opening" => Some(Box::new(image_analysis::Opening::new())),
             "panchromaticsharpening" => {
@@ -796,15 +1024,25 @@ impl ToolManager {
             "percentilefilter" => Some(Box::new(image_analysis::PercentileFilter::new())),
             "prewittfilter" => Some(Box::new(image_analysis::PrewittFilter::new())),
             "rangefilter" => Some(Box::new(image_analysis::RangeFilter::new())),
+            "refinedleefilter" => Some(Box::new(image_analysis::RefinedLeeFilter::new())),
+            "removeperiodicnoise" => Some(Box::new(image_analysis::RemovePeriodicNoise::new())),
             "removespurs" => Some(Box::new(image_analysis::RemoveSpurs::new())),
             "resample" => Some(Box::new(image_analysis::Resample::new())),
             "rgbtoihs" => Some(Box::new(image_analysis::RgbToIhs::new())),
             "robertscrossfilter" => Some(Box::new(image_analysis::RobertsCrossFilter::new())),
             "scharrfilter" => Some(Box::new(image_analysis::ScharrFilter::new())),
+            "selectivepcachangedetection" => {
+                Some(Box::new(image_analysis::SelectivePcaChangeDetection::new()))
+            }
             "sigmoidalcontraststretch" => {
                 Some(Box::new(image_analysis::SigmoidalContrastStretch::new()))
             }
+            "slicsegmentation" => Some(Box::new(image_analysis::SlicSegmentation::new())),
             "sobelfilter" => Some(Box::new(image_analysis::SobelFilter::new())),
+            "soiladjustedvegetationindex" => {
+                Some(Box::new(image_analysis::SoilAdjustedVegetationIndex::new()))
+            }
+            "spectralindex" => Some(Box::new(image_analysis::SpectralIndex::new())),
             "splitcolourcomposite" => Some(Box::new(image_analysis::SplitColourComposite::new())),
             "standarddeviationcontraststretch" => Some(Box::new(
                 image_analysis::StandardDeviationContrastStretch::new(),
@@ -815,6 +1053,7 @@ impl ToolManager {
             "thickenrasterline" => Some(Box::new(image_analysis::ThickenRasterLine::new())),
             "tophattransform" => Some(Box::new(image_analysis::TophatTransform::new())),
             "totalfilter" => Some(Box::new(image_analysis::TotalFilter::new())),
+            "trainclassifier" => Some(Box::new(image_analysis::TrainClassifier::new())),
             "unsharpmasking" => Some(Box::new(image_analysis::UnsharpMasking::new())),
             "userdefinedweightsfilter" => {
                 Some(Box::new(image_analysis::UserDefinedWeightsFilter::new()))
@@ -845,6 +1084,7 @@ impl ToolManager {
                 Some(Box::new(lidar_analysis::LasToMultipointShapefile::new()))
             }
             "lastoshapefile" => Some(Box::new(lidar_analysis::LasToShapefile::new())),
+            "lidarclassifynoise" => Some(Box::new(lidar_analysis::LidarClassifyNoise::new())),
             "lidarclassifysubset" => Some(Box::new(lidar_analysis::LidarClassifySubset::new())),
             "lidarcolourize" => Some(Box::new(lidar_analysis::LidarColourize::new())),
             "lidarconstructvectortin" => {
@@ -864,8 +1104,14 @@ impl ToolManager {
             "lidarnearestneighbourgridding" => Some(Box::new(
                 lidar_analysis::LidarNearestNeighbourGridding::new(),
             )),
+            "lidarnormalizeintensity" => {
+                Some(Box::new(lidar_analysis::LidarNormalizeIntensity::new()))
+            }
             "lidarpointdensity" => Some(Box::new(lidar_analysis::LidarPointDensity::new())),
             "lidarpointstats" => Some(Box::new(lidar_analysis::LidarPointStats::new())),
+            "lidarpowerlinedetection" => {
+                Some(Box::new(lidar_analysis::LidarPowerlineDetection::new()))
+            }
             "lidarrbfinterpolation" => Some(Box::new(lidar_analysis::LidarRbfInterpolation::new())),
             "lidarransacplanes" => Some(Box::new(lidar_analysis::LidarRansacPlanes::new())),
             "lidarremoveduplicates" => Some(Box::new(lidar_analysis::LidarRemoveDuplicates::new())),
@@ -878,8 +1124,12 @@ impl ToolManager {
             "lidarthinhighdensity" => Some(Box::new(lidar_analysis::LidarThinHighDensity::new())),
             "lidartile" => Some(Box::new(lidar_analysis::LidarTile::new())),
             "lidartilefootprint" => Some(Box::new(lidar_analysis::LidarTileFootprint::new())),
+            "lidartiledworkflow" => Some(Box::new(lidar_analysis::LidarTiledWorkflow::new())),
             "lidartingridding" => Some(Box::new(lidar_analysis::LidarTINGridding::new())),
+            "lidartomesh" => Some(Box::new(lidar_analysis::LidarToMesh::new())),
+            "lidartoparquet" => Some(Box::new(lidar_analysis::LidarToParquet::new())),
             "lidartophattransform" => Some(Box::new(lidar_analysis::LidarTophatTransform::new())),
+            "lidarvoxelmetrics" => Some(Box::new(lidar_analysis::LidarVoxelMetrics::new())),
             "normalvectors" => Some(Box::new(lidar_analysis::NormalVectors::new())),
             "selecttilesbypolygon" => Some(Box::new(lidar_analysis::SelectTilesByPolygon::new())),
 
@@ -918,8 +1168,16 @@ impl ToolManager {
             "equalto" => Some(Box::new(math_stat_analysis::EqualTo::new())),
             "exp" => Some(Box::new(math_stat_analysis::Exp::new())),
             "exp2" => Some(Box::new(math_stat_analysis::Exp2::new())),
+            "ordinarykriging" => Some(Box::new(math_stat_analysis::OrdinaryKriging::new())),
+            "semivariogramanalysis" => {
+                Some(Box::new(math_stat_analysis::SemivariogramAnalysis::new()))
+            }
             "zonalstatistics" => Some(Box::new(math_stat_analysis::ZonalStatistics::new())),
             "floor" => Some(Box::new(math_stat_analysis::Floor::new())),
+            "gaussiansimulation" => Some(Box::new(math_stat_analysis::GaussianSimulation::new())),
+            "globalspatialautocorrelation" => {
+                Some(Box::new(math_stat_analysis::GlobalSpatialAutocorrelation::new()))
+            }
             "greaterthan" => Some(Box::new(math_stat_analysis::GreaterThan::new())),
             "imageautocorrelation" => {
                 Some(Box::new(math_stat_analysis::ImageAutocorrelation::new()))
@@ -940,6 +1198,9 @@ impl ToolManager {
             "kstestfornormality" => Some(Box::new(math_stat_analysis::KsTestForNormality::new())),
             "lessthan" => Some(Box::new(math_stat_analysis::LessThan::new())),
             "listuniquevalues" => Some(Box::new(math_stat_analysis::ListUniqueValues::new())),
+            "localspatialautocorrelation" => {
+                Some(Box::new(math_stat_analysis::LocalSpatialAutocorrelation::new()))
+            }
             "log10" => Some(Box::new(math_stat_analysis::Log10::new())),
             "log2" => Some(Box::new(math_stat_analysis::Log2::new())),
             "ln" => Some(Box::new(math_stat_analysis::Ln::new())),
@@ -989,7 +1250,18 @@ impl ToolManager {
             "xor" => Some(Box::new(math_stat_analysis::Xor::new())),
             "zscores" => Some(Box::new(math_stat_analysis::ZScores::new())),
 
+            // soil_analysis
+            "ruslelsfactor" => Some(Box::new(soil_analysis::RusleLsFactor::new())),
+            "ruslesoilloss" => Some(Box::new(soil_analysis::RusleSoilLoss::new())),
+            "soilmappingcovariates" => {
+                Some(Box::new(soil_analysis::SoilMappingCovariates::new()))
+            }
+
             // stream_network_analysis
+            "channelhydraulicgeometry" => Some(Box::new(
+                stream_network_analysis::ChannelHydraulicGeometry::new(),
+            )),
+            "chiindex" => Some(Box::new(stream_network_analysis::ChiIndex::new())),
             "distancetooutlet" => Some(Box::new(stream_network_analysis::DistanceToOutlet::new())),
             "extractstreams" => Some(Box::new(stream_network_analysis::ExtractStreams::new())),
             "extractvalleys" => Some(Box::new(stream_network_analysis::ExtractValleys::new())),
@@ -1001,6 +1273,9 @@ impl ToolManager {
             "hortonstreamorder" => {
                 Some(Box::new(stream_network_analysis::HortonStreamOrder::new()))
             }
+            "knickpointdetection" => {
+                Some(Box::new(stream_network_analysis::KnickpointDetection::new()))
+            }
             "lengthofupstreamchannels" => Some(Box::new(
                 stream_network_analysis::LengthOfUpstreamChannels::new(),
             )),
@@ -1027,6 +1302,9 @@ impl ToolManager {
             )),
             "streamlinklength" => Some(Box::new(stream_network_analysis::StreamLinkLength::new())),
             "streamlinkslope" => Some(Box::new(stream_network_analysis::StreamLinkSlope::new())),
+            "streamnetworktovector" => {
+                Some(Box::new(stream_network_analysis::StreamNetworkToVector::new()))
+            }
             "streamslopecontinuous" => Some(Box::new(
                 stream_network_analysis::StreamSlopeContinuous::new(),
             )),
@@ -1046,6 +1324,8 @@ impl ToolManager {
                 Some(Box::new(terrain_analysis::CircularVarianceOfAspect::new()))
             }
             "contoursfromraster" => Some(Box::new(terrain_analysis::ContoursFromRaster::new())),
+            "coregisterdems" => Some(Box::new(terrain_analysis::CoregisterDems::new())),
+            "demqualityreport" => Some(Box::new(terrain_analysis::DemQualityReport::new())),
             "devfrommeanelev" => Some(Box::new(terrain_analysis::DevFromMeanElev::new())),
             "difffrommeanelev" => Some(Box::new(terrain_analysis::DiffFromMeanElev::new())),
             "directionalrelief" => Some(Box::new(terrain_analysis::DirectionalRelief::new())),
@@ -1060,6 +1340,7 @@ impl ToolManager {
             "elevrelativetowatershedminmax" => Some(Box::new(
                 terrain_analysis::ElevRelativeToWatershedMinMax::new(),
             )),
+            "extractprofiles" => Some(Box::new(terrain_analysis::ExtractProfiles::new())),
             "featurepreservingsmoothing" => {
                 Some(Box::new(terrain_analysis::FeaturePreservingSmoothing::new()))
             }
@@ -1108,12 +1389,16 @@ impl ToolManager {
                 Some(Box::new(terrain_analysis::NumDownslopeNeighbours::new()))
             }
             "numupslopeneighbours" => Some(Box::new(terrain_analysis::NumUpslopeNeighbours::new())),
+            "openness" => Some(Box::new(terrain_analysis::Openness::new())),
             "pennocklandformclass" => Some(Box::new(terrain_analysis::PennockLandformClass::new())),
             "percentelevrange" => Some(Box::new(terrain_analysis::PercentElevRange::new())),
             "plancurvature" => Some(Box::new(terrain_analysis::PlanCurvature::new())),
             "profilecurvature" => Some(Box::new(terrain_analysis::ProfileCurvature::new())),
             "profile" => Some(Box::new(terrain_analysis::Profile::new())),
             "relativeaspect" => Some(Box::new(terrain_analysis::RelativeAspect::new())),
+            "sagawetnessindex" => Some(Box::new(terrain_analysis::SagaWetnessIndex::new())),
+            "skyviewfactor" => Some(Box::new(terrain_analysis::SkyViewFactor::new())),
+            "solarradiation" => Some(Box::new(terrain_analysis::SolarRadiation::new())),
             "streampowerindex" => Some(Box::new(terrain_analysis::StreamPowerIndex::new())),
             "relativetopographicposition" => Some(Box::new(
                 terrain_analysis::RelativeTopographicPosition::new(),
@@ -1138,7 +1423,34 @@ impl ToolManager {
             "totalcurvature" => Some(Box::new(terrain_analysis::TotalCurvature::new())),
             "viewshed" => Some(Box::new(terrain_analysis::Viewshed::new())),
             "visibilityindex" => Some(Box::new(terrain_analysis::VisibilityIndex::new())),
+            "waterfetch" => Some(Box::new(terrain_analysis::WaterFetch::new())),
+            "watershedhypsometry" => Some(Box::new(terrain_analysis::WatershedHypsometry::new())),
             "wetnessindex" => Some(Box::new(terrain_analysis::WetnessIndex::new())),
+            "windexpositionindex" => Some(Box::new(terrain_analysis::WindExpositionIndex::new())),
+            "winstralshelterindex" => {
+                Some(Box::new(terrain_analysis::WinstralShelterIndex::new()))
+            }
+
+            // time_series_analysis
+            "extractshorelines" => Some(Box::new(time_series_analysis::ExtractShorelines::new())),
+            "phenologymetrics" => Some(Box::new(time_series_analysis::PhenologyMetrics::new())),
+            "shorelinechangeanalysis" => {
+                Some(Box::new(time_series_analysis::ShorelineChangeAnalysis::new()))
+            }
+            "timeseriesaggregate" => {
+                Some(Box::new(time_series_analysis::TimeSeriesAggregate::new()))
+            }
+            "timeseriesanomalies" => {
+                Some(Box::new(time_series_analysis::TimeSeriesAnomalies::new()))
+            }
+            "timeseriesgapfill" => Some(Box::new(time_series_analysis::TimeSeriesGapFill::new())),
+            "timeseriessavitzkygolay" => {
+                Some(Box::new(time_series_analysis::TimeSeriesSavitzkyGolay::new()))
+            }
+            "timeseriestrend" => Some(Box::new(time_series_analysis::TimeSeriesTrend::new())),
+            "timeserieswhittaker" => {
+                Some(Box::new(time_series_analysis::TimeSeriesWhittaker::new()))
+            }
 
             _ => None,
         }
@@ -1149,6 +1461,8 @@ impl ToolManager {
         //     tool_args_vec.insert(0, format!("--wd={}", working_dir));
         // }
 
+        let args = apply_framework_flags(args)?;
+
         match self.get_tool(tool_name.as_ref()) {
             Some(tool) => return tool.run(args, &self.working_dir, self.verbose),
             None => {
@@ -1160,6 +1474,25 @@ impl ToolManager {
         }
     }
 
+    /// Runs a tool on in-memory datasets, returning the tool's outputs as
+    /// in-memory datasets rather than writing them to disk. See
+    /// `WhiteboxTool::run_in_memory` for how the inputs are assigned.
+    pub fn run_tool_in_memory(
+        &self,
+        tool_name: String,
+        inputs: &[Dataset],
+        args: Vec<String>,
+    ) -> Result<Vec<Dataset>, Error> {
+        let args = apply_framework_flags(args)?;
+        match self.get_tool(tool_name.as_ref()) {
+            Some(tool) => tool.run_in_memory(inputs, args, &self.working_dir, self.verbose),
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Unrecognized tool name {}.", tool_name),
+            )),
+        }
+    }
+
     pub fn tool_help(&self, tool_name: String) -> Result<(), Error> {
         if !tool_name.is_empty() {
             match self.get_tool(tool_name.as_ref()) {
@@ -1216,6 +1549,31 @@ impl ToolManager {
         Ok(())
     }
 
+    /// Returns the parameters (in json form) for a specific tool.
+    pub fn get_tool_parameters(&self, tool_name: &str) -> Result<String, Error> {
+        match self.get_tool(tool_name) {
+            Some(tool) => Ok(tool.get_tool_parameters()),
+            None => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Unrecognized tool name {}.", tool_name),
+            )),
+        }
+    }
+
+    /// Returns the name, description, and toolbox of each of the available tools.
+    pub fn get_tool_details(&self) -> Vec<(String, String, String)> {
+        let mut tool_details = Vec::with_capacity(self.tool_names.len());
+        for val in &self.tool_names {
+            let tool = self
+                .get_tool(&val)
+                .expect(&format!("Unrecognized tool name {}.", val));
+            let toolbox = tool.get_toolbox();
+            let (name, description) = get_name_and_description(tool);
+            tool_details.push((name, description, toolbox));
+        }
+        tool_details
+    }
+
     pub fn list_tools(&self) {
         let mut tool_details: Vec<(String, String)> = Vec::new();
 
@@ -1292,6 +1650,293 @@ pub trait WhiteboxTool {
         working_directory: &'a str,
         verbose: bool,
     ) -> Result<(), Error>;
+
+    /// Runs the tool on in-memory datasets rather than files, allowing tools to be
+    /// chained together without writing intermediate results to disk. The `inputs`
+    /// are assigned, in order, to the tool's raster and vector input parameters that
+    /// are not already specified within `args`; a file-list parameter receives all
+    /// of the remaining inputs. Each required raster or vector output is captured in
+    /// memory and returned, in the order of the tool's parameters. All other
+    /// parameters are specified within `args` as usual.
+    fn run_in_memory<'a>(
+        &self,
+        inputs: &[Dataset],
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<Vec<Dataset>, Error> {
+        let o: serde_json::Value = serde_json::from_str(&self.get_tool_parameters())?;
+        let parameters: Vec<ToolParameter> = serde_json::from_value(o["parameters"].clone())?;
+        let specified_flags: Vec<String> = args
+            .iter()
+            .map(|a| {
+                a.to_lowercase()
+                    .replace("--", "-")
+                    .splitn(2, '=')
+                    .next()
+                    .unwrap_or("")
+                    .to_string()
+            })
+            .collect();
+        let run_id = IN_MEMORY_RUN_ID.fetch_add(1, Ordering::SeqCst);
+        let mut args = args;
+        let mut input_names: Vec<String> = vec![];
+        let mut output_names: Vec<String> = vec![];
+        for p in &parameters {
+            if p.flags
+                .iter()
+                .any(|f| specified_flags.contains(&f.to_lowercase().replace("--", "-")))
+            {
+                continue;
+            }
+            let flag = match p.flags.last() {
+                Some(f) => f.clone(),
+                None => continue,
+            };
+            match &p.parameter_type {
+                ParameterType::ExistingFile(ParameterFileType::Raster)
+                | ParameterType::ExistingFile(ParameterFileType::Vector(_))
+                | ParameterType::ExistingFile(ParameterFileType::RasterAndVector(_))
+                | ParameterType::FileList(ParameterFileType::Raster)
+                | ParameterType::FileList(ParameterFileType::Vector(_))
+                | ParameterType::FileList(ParameterFileType::RasterAndVector(_)) => {
+                    let num_inputs = match p.parameter_type {
+                        ParameterType::FileList(_) => inputs.len() - input_names.len(),
+                        _ => 1.min(inputs.len() - input_names.len()),
+                    };
+                    if num_inputs == 0 {
+                        continue;
+                    }
+                    let mut names = vec![];
+                    for dataset in &inputs[input_names.len()..input_names.len() + num_inputs] {
+                        names.push(format!(
+                            "{}{}/input{}.{}",
+                            MEMORY_PREFIX,
+                            run_id,
+                            input_names.len() + names.len() + 1,
+                            dataset.extension()
+                        ));
+                        store_dataset(&names[names.len() - 1], dataset.clone());
+                    }
+                    args.push(format!("{}={}", flag, names.join(";")));
+                    input_names.extend(names);
+                }
+                ParameterType::NewFile(ParameterFileType::Raster) if !p.optional => {
+                    let name = format!(
+                        "{}{}/output{}.tif",
+                        MEMORY_PREFIX,
+                        run_id,
+                        output_names.len() + 1
+                    );
+                    args.push(format!("{}={}", flag, name));
+                    output_names.push(name);
+                }
+                ParameterType::NewFile(ParameterFileType::Vector(_)) if !p.optional => {
+                    let name = format!(
+                        "{}{}/output{}.shp",
+                        MEMORY_PREFIX,
+                        run_id,
+                        output_names.len() + 1
+                    );
+                    args.push(format!("{}={}", flag, name));
+                    output_names.push(name);
+                }
+                _ => {}
+            }
+        }
+
+        let result = if input_names.len() < inputs.len() {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} in-memory input datasets were provided but the tool only accepts {}.",
+                    inputs.len(),
+                    input_names.len()
+                ),
+            ))
+        } else {
+            self.run(args, working_directory, verbose)
+        };
+        for name in &input_names {
+            remove_dataset(name);
+        }
+        let mut outputs = vec![];
+        for name in &output_names {
+            outputs.push(remove_dataset(name));
+        }
+        result?;
+
+        let mut datasets = vec![];
+        for (name, output) in output_names.iter().zip(outputs) {
+            match output {
+                Some(dataset) => datasets.push(dataset),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        format!("The tool did not produce the in-memory output {}.", name),
+                    ))
+                }
+            }
+        }
+        Ok(datasets)
+    }
+}
+
+/// The framework-level flags handled by `apply_framework_flags`.
+const FRAMEWORK_FLAGS: [&str; 7] = [
+    "-resample_inputs",
+    "-compress_level",
+    "-compress",
+    "-predictor",
+    "-max_mem",
+    "-device",
+    "-seed",
+];
+
+/// Returns true if an argument is one of the framework-level flags, which set process-wide
+/// options rather than being fed to a tool.
+fn is_framework_flag(arg: &str) -> bool {
+    let flag = arg.to_lowercase().replace("--", "-");
+    FRAMEWORK_FLAGS
+        .iter()
+        .any(|name| flag == *name || flag.starts_with(&format!("{}=", name)))
+}
+
+/// Handles the framework-level flags (`--resample_inputs`, `--max_mem`, `--device`, `--seed`,
+/// and the raster compression flags), returning the remaining arguments to be fed to the tool.
+fn apply_framework_flags(args: Vec<String>) -> Result<Vec<String>, Error> {
+    // --resample_inputs, --max_mem, --device, --seed, and the raster compression flags
+    // are framework-level flags, handled here rather than by the tool
+    let mut resample_inputs = false;
+    let mut max_mem = String::new();
+    let mut device = String::from("cpu");
+    let mut seed = String::new();
+    let mut compression = String::from("none");
+    let mut compression_level = String::from("6");
+    let mut predictor = String::from("auto");
+    let args: Vec<String> = args
+        .into_iter()
+        .filter(|a| {
+            let flag = a.to_lowercase().replace("--", "-");
+            let value = flag.splitn(2, '=').nth(1).unwrap_or("").replace("\"", "");
+            // flags are matched exactly, so as not to catch tool flags that share a prefix,
+            // e.g. --compression_level, --predictor_vars, --devices, or --seed_pts
+            let is_flag = |name: &str| flag == name || flag.starts_with(&format!("{}=", name));
+            if is_flag("-resample_inputs") {
+                resample_inputs = !flag.contains("false");
+                return false;
+            } else if is_flag("-compress_level") {
+                compression_level = value;
+                return false;
+            } else if is_flag("-compress") {
+                compression = if value.is_empty() {
+                    "deflate".to_string()
+                } else {
+                    value
+                };
+                return false;
+            } else if is_flag("-predictor") {
+                predictor = value;
+                return false;
+            } else if is_flag("-max_mem") {
+                max_mem = value;
+                return false;
+            } else if is_flag("-device") {
+                device = value;
+                return false;
+            } else if is_flag("-seed") {
+                seed = value;
+                return false;
+            }
+            true
+        })
+        .collect();
+    RESAMPLE_INPUTS.store(resample_inputs, Ordering::SeqCst);
+    if max_mem.is_empty() {
+        set_memory_budget(0);
+    } else {
+        match parse_memory_size(&max_mem) {
+            Some(bytes) => set_memory_budget(bytes),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "Unrecognized --max_mem value '{}'; use a size such as 512M or 16G.",
+                        max_mem
+                    ),
+                ))
+            }
+        }
+    }
+    match Device::from_str(&device) {
+        Some(d) => set_device(d),
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized --device value '{}'; options are 'cpu' and 'gpu'.",
+                    device
+                ),
+            ))
+        }
+    }
+    if seed.is_empty() {
+        set_seed(None);
+    } else {
+        match seed.trim().parse::<u64>() {
+            Ok(v) => set_seed(Some(v)),
+            Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "Unrecognized --seed value '{}'; the seed must be a non-negative integer.",
+                        seed
+                    ),
+                ))
+            }
+        }
+    }
+    let compression = match RasterCompression::from_str(&compression) {
+        Some(RasterCompression::Zstd) if !cfg!(feature = "zstd") => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "This build of WhiteboxTools does not include ZSTD support; rebuild with --features zstd, or use --compress=deflate or --compress=lzw instead.",
+            ))
+        }
+        Some(c) => c,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized --compress value '{}'; options are 'none', 'deflate', 'lzw', and 'zstd'.",
+                    compression
+                ),
+            ))
+        }
+    };
+    let compression_level = match compression_level.parse::<u8>() {
+        Ok(level) if level >= 1 && level <= 9 => level,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --compress_level value must be an integer from 1 to 9.",
+            ))
+        }
+    };
+    let predictor = match RasterPredictor::from_str(&predictor) {
+        Some(p) => p,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Unrecognized --predictor value '{}'; options are 'auto', 'none', 'horizontal', and 'float'.",
+                    predictor
+                ),
+            ))
+        }
+    };
+    set_default_compression(compression, compression_level, predictor);
+    Ok(args)
 }
 
 fn get_help<'a>(wt: Box<dyn WhiteboxTool + 'a>) -> String {
@@ -1418,3 +2063,85 @@ enum AttributeType {
     Boolean,
     Date,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{apply_framework_flags, ToolManager};
+    use crate::utils::memory::BUDGET_TEST_LOCK;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_framework_flags_keep_tool_flags_with_shared_prefixes() {
+        // apply_framework_flags resets the memory budget
+        let _guard = BUDGET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let args: Vec<String> = vec![
+            "--compression_level=4",
+            "--compress=deflate",
+            "--predictor_vars=a;b",
+            "--devices=2",
+            "--seed_pts=pts.shp",
+            "--max_mem_per_tile=1",
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+        let remaining = apply_framework_flags(args).unwrap();
+        assert_eq!(
+            remaining,
+            vec![
+                "--compression_level=4",
+                "--predictor_vars=a;b",
+                "--devices=2",
+                "--seed_pts=pts.shp",
+                "--max_mem_per_tile=1",
+            ]
+        );
+        // reset the global compression settings for the other tests
+        apply_framework_flags(vec![]).unwrap();
+    }
+
+    #[test]
+    fn test_convert_to_whitebox_raster_compression_level() {
+        let _guard = BUDGET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dem = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/DEM.tif");
+        let dir = env::temp_dir().join(format!("wbt_compression_level_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut sizes = vec![];
+        for level in &[1, 9] {
+            let input = dir.join(format!("DEM_{}.tif", level));
+            fs::copy(&dem, &input).unwrap();
+            let tm = ToolManager::new(&format!("{}/", dir.display()), &false).unwrap();
+            tm.run_tool(
+                "ConvertToWhiteboxRaster".to_string(),
+                vec![
+                    format!("--inputs={}", input.display()),
+                    format!("--compression_level={}", level),
+                ],
+            )
+            .unwrap();
+            sizes.push(fs::metadata(input.with_extension("wbr")).unwrap().len());
+        }
+        let _ = fs::remove_dir_all(&dir);
+        assert!(sizes[1] <= sizes[0]);
+    }
+
+    #[test]
+    fn test_pipeline_rejects_step_framework_flags() {
+        let _guard = BUDGET_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = env::temp_dir().join(format!("wbt_pipeline_flags_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let pipeline = dir.join("pipeline.json");
+        fs::write(
+            &pipeline,
+            r#"{ "steps": [ { "id": "sim", "tool": "RandomField", "args": { "base": "DEM.tif", "output": "rf.tif", "seed": 4 } } ] }"#,
+        )
+        .unwrap();
+        let tm = ToolManager::new(&format!("{}/", dir.display()), &false).unwrap();
+        let result = tm.run_pipeline(&pipeline.to_string_lossy(), vec![], false);
+        let _ = fs::remove_dir_all(&dir);
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("--seed"));
+    }
+}
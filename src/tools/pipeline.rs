@@ -0,0 +1,652 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Runs a workflow of tools described in a JSON pipeline file. For example:
+
+{
+    "in_memory": true,
+    "steps": [
+        { "id": "fill", "tool": "FillDepressions", "args": { "dem": "DEM.tif" } },
+        { "id": "slope", "tool": "Slope", "args": { "input": "@fill.output", "output": "slope.tif" } },
+        { "id": "accum", "tool": "D8FlowAccumulation", "args": { "dem": "@fill.output", "output": "accum.tif" } }
+    ]
+}
+
+Argument values of the form `@step.flag` refer to an output of an earlier step and
+imply a dependency between the two steps; further dependencies may be listed in a
+step's `depends_on` array. Required outputs that are not named explicitly are given
+intermediate names based on the step id and, when `in_memory` is true, intermediate
+rasters and vectors that are consumed by later steps are passed in memory rather
+than through the file system. Independent branches of the workflow are run in
+parallel, up to `max_parallel` tools at a time. The ids of completed steps are
+recorded in a state file (the pipeline file name with a `.state` extension) so that
+a failed pipeline can be resumed with the `--resume` flag. Framework-level flags
+(e.g. `--compress`, `--max_mem`, `--resample_inputs`, and `--seed`) apply to every
+step and must be given on the command line rather than in a step's `args`.
+*/
+
+use super::{
+    apply_framework_flags, is_framework_flag, ParameterFileType, ParameterType, ToolManager,
+    ToolParameter,
+};
+use crate::dataset::{is_in_memory, remove_dataset, MEMORY_PREFIX};
+use crate::utils::get_formatted_elapsed_time;
+use num_cpus;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+/// Used to give the in-memory intermediates of each pipeline run unique names.
+static PIPELINE_RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+struct Step {
+    id: String,
+    tool: String,
+    args: Vec<(String, String)>,
+    parameters: Vec<ToolParameter>,
+    // (flag, file name) of each of the step's outputs
+    outputs: Vec<(String, String)>,
+    depends_on: Vec<usize>,
+}
+
+impl Step {
+    /// Returns the parameter matching a flag, which may be specified with or without
+    /// leading dashes and using any of the parameter's flag aliases.
+    fn find_parameter(&self, flag: &str) -> Option<&ToolParameter> {
+        let flag = trim_flag(flag);
+        self.parameters
+            .iter()
+            .find(|p| p.flags.iter().any(|f| trim_flag(f) == flag))
+    }
+
+    fn find_output(&self, flag: &str) -> Option<String> {
+        let p = self.find_parameter(flag)?;
+        self.outputs
+            .iter()
+            .find(|(f, _)| p.flags.iter().any(|pf| trim_flag(pf) == *f))
+            .map(|(_, file)| file.clone())
+    }
+}
+
+impl ToolManager {
+    /// Runs the workflow of tools described in a JSON pipeline file. When `resume` is
+    /// true, steps that were completed by an earlier, failed run of the pipeline are
+    /// skipped. The `args` may contain the framework-level flags (e.g. `--compress`),
+    /// which then apply to every step.
+    pub fn run_pipeline(
+        &self,
+        pipeline_file: &str,
+        args: Vec<String>,
+        resume: bool,
+    ) -> Result<(), Error> {
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut pipeline_file = pipeline_file.trim().to_string();
+        if !pipeline_file.contains(&sep) && !pipeline_file.contains("/") {
+            pipeline_file = format!("{}{}", self.working_dir, pipeline_file);
+        }
+        let lowercase_file = pipeline_file.to_lowercase();
+        if lowercase_file.ends_with(".yaml") || lowercase_file.ends_with(".yml") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "YAML pipeline files are not supported by this build of WhiteboxTools; please describe the pipeline in JSON.",
+            ));
+        }
+        let _ = apply_framework_flags(args)?;
+
+        let start = Instant::now();
+
+        let contents = fs::read_to_string(&pipeline_file)?;
+        let pipeline: Value = serde_json::from_str(&contents).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing pipeline file {}: {}", pipeline_file, e),
+            )
+        })?;
+        let in_memory = pipeline["in_memory"].as_bool().unwrap_or(false);
+        let max_parallel = match pipeline["max_parallel"].as_u64() {
+            Some(n) if n > 0 => n as usize,
+            _ => num_cpus::get(),
+        };
+        let step_values = match pipeline["steps"].as_array() {
+            Some(steps) if !steps.is_empty() => steps.clone(),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The pipeline file must contain a non-empty 'steps' array.",
+                ))
+            }
+        };
+
+        // read the steps
+        let mut steps: Vec<Step> = Vec::with_capacity(step_values.len());
+        for (i, v) in step_values.iter().enumerate() {
+            let id = match v["id"].as_str() {
+                Some(id) => id.to_string(),
+                None => format!("step{}", i + 1),
+            };
+            if steps.iter().any(|s| s.id == id) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The step id '{}' is used more than once.", id),
+                ));
+            }
+            let tool = match v["tool"].as_str() {
+                Some(tool) => tool.to_string(),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Step '{}' does not specify a tool.", id),
+                    ))
+                }
+            };
+            let parameters: Vec<ToolParameter> = match self.get_tool(&tool) {
+                Some(t) => {
+                    let o: Value = serde_json::from_str(&t.get_tool_parameters())?;
+                    serde_json::from_value(o["parameters"].clone())?
+                }
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        format!("Unrecognized tool name {} in step '{}'.", tool, id),
+                    ))
+                }
+            };
+            let args = parse_step_args(&v["args"]).map_err(|e| {
+                Error::new(
+                    e.kind(),
+                    format!("Error reading the args of step '{}': {}", id, e),
+                )
+            })?;
+            // framework-level flags set process-wide options, which cannot differ between
+            // steps that run in parallel, and so they apply to the pipeline as a whole
+            if let Some((flag, _)) = args
+                .iter()
+                .find(|(f, _)| is_framework_flag(&format!("-{}", f)))
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "Step '{}' sets the framework-level flag --{}, which applies to the whole pipeline; specify it on the command line alongside --run_pipeline instead.",
+                        id, flag
+                    ),
+                ));
+            }
+            steps.push(Step {
+                id,
+                tool,
+                args,
+                parameters,
+                outputs: vec![],
+                depends_on: vec![],
+            });
+        }
+
+        // explicit dependencies and output references
+        let num_steps = steps.len();
+        let mut references: Vec<Vec<(usize, usize)>> = vec![vec![]; num_steps]; // (arg, producer)
+        for i in 0..num_steps {
+            if let Some(deps) = step_values[i]["depends_on"].as_array() {
+                for d in deps {
+                    let d = d.as_str().unwrap_or("");
+                    match steps.iter().position(|s| s.id == d) {
+                        Some(j) => steps[i].depends_on.push(j),
+                        None => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                format!(
+                                    "Step '{}' depends on an unknown step '{}'.",
+                                    steps[i].id, d
+                                ),
+                            ))
+                        }
+                    }
+                }
+            }
+            for a in 0..steps[i].args.len() {
+                let arg_value = steps[i].args[a].1.clone();
+                for value in arg_value.split(";") {
+                    if let Some((step_id, _)) = parse_reference(value) {
+                        match steps.iter().position(|s| s.id == step_id) {
+                            Some(j) if j != i => {
+                                references[i].push((a, j));
+                                steps[i].depends_on.push(j);
+                            }
+                            _ => {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidInput,
+                                    format!(
+                                        "Step '{}' refers to an unknown step in '{}'.",
+                                        steps[i].id, value
+                                    ),
+                                ))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // order the steps so that each follows the steps it depends on
+        let order = topological_order(&steps)?;
+
+        // name the outputs, passing referenced intermediates in memory if requested
+        let run_id = PIPELINE_RUN_ID.fetch_add(1, Ordering::SeqCst);
+        for i in 0..num_steps {
+            // the output flags of this step that are referred to by other steps
+            let mut referenced: Vec<String> = vec![];
+            for k in 0..num_steps {
+                for (a, j) in &references[k] {
+                    if *j != i {
+                        continue;
+                    }
+                    for value in steps[k].args[*a].1.split(";") {
+                        if let Some((step_id, flag)) = parse_reference(value) {
+                            if step_id == steps[i].id {
+                                referenced.push(trim_flag(&flag));
+                            }
+                        }
+                    }
+                }
+            }
+            let mut outputs = vec![];
+            let mut new_args = vec![];
+            for p in &steps[i].parameters {
+                let file_type = match &p.parameter_type {
+                    ParameterType::NewFile(ft) => ft,
+                    _ => continue,
+                };
+                let flag = match p.flags.last() {
+                    Some(f) => trim_flag(f),
+                    None => continue,
+                };
+                let is_referenced = p.flags.iter().any(|f| referenced.contains(&trim_flag(f)));
+                let specified = steps[i]
+                    .args
+                    .iter()
+                    .find(|(f, _)| p.flags.iter().any(|pf| trim_flag(pf) == *f))
+                    .map(|(_, v)| v.clone());
+                match specified {
+                    Some(file) => outputs.push((flag, file)),
+                    None if !p.optional || is_referenced => {
+                        let extension = match file_type {
+                            ParameterFileType::Raster => "tif",
+                            ParameterFileType::Vector(_) => "shp",
+                            ParameterFileType::RasterAndVector(_) => "tif",
+                            ParameterFileType::Lidar => "las",
+                            ParameterFileType::Html => "html",
+                            ParameterFileType::Csv => "csv",
+                            _ => "txt",
+                        };
+                        let memory_capable = match file_type {
+                            ParameterFileType::Raster | ParameterFileType::Vector(_) => true,
+                            _ => false,
+                        };
+                        let file = if in_memory && is_referenced && memory_capable {
+                            format!(
+                                "{}pipeline{}/{}/{}.{}",
+                                MEMORY_PREFIX, run_id, steps[i].id, flag, extension
+                            )
+                        } else {
+                            format!("{}{}_{}.{}", self.working_dir, steps[i].id, flag, extension)
+                        };
+                        new_args.push((flag.clone(), file.clone()));
+                        outputs.push((flag, file));
+                    }
+                    None => {}
+                }
+            }
+            steps[i].args.extend(new_args);
+            steps[i].outputs = outputs;
+        }
+
+        // resolve the output references
+        for i in 0..num_steps {
+            for (a, j) in references[i].clone() {
+                let mut values = vec![];
+                for value in steps[i].args[a].1.split(";") {
+                    match parse_reference(value) {
+                        Some((step_id, flag)) if step_id == steps[j].id => {
+                            match steps[j].find_output(&flag) {
+                                Some(file) => values.push(file),
+                                None => return Err(Error::new(
+                                    ErrorKind::InvalidInput,
+                                    format!(
+                                        "Step '{}' refers to '{}', which is not an output of {}.",
+                                        steps[i].id, value, steps[j].tool
+                                    ),
+                                )),
+                            }
+                        }
+                        _ => values.push(value.to_string()),
+                    }
+                }
+                steps[i].args[a].1 = values.join(";");
+            }
+        }
+
+        // determine which steps need to be run
+        let state_file = format!("{}.state", pipeline_file);
+        let mut completed: HashSet<String> = HashSet::new();
+        if resume {
+            if let Ok(state) = fs::read_to_string(&state_file) {
+                let state: Value = serde_json::from_str(&state).unwrap_or(Value::Null);
+                if let Some(ids) = state["completed"].as_array() {
+                    for id in ids {
+                        if let Some(id) = id.as_str() {
+                            completed.insert(id.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        let mut to_run = vec![true; num_steps];
+        for &i in order.iter().rev() {
+            let outputs_exist = steps[i]
+                .outputs
+                .iter()
+                .all(|(_, file)| is_in_memory(file) || path::Path::new(file).exists());
+            // in-memory outputs do not survive between runs, so their producer must be
+            // run again if any of their consumers are to be run
+            let needed_in_memory = (0..num_steps).any(|k| {
+                to_run[k]
+                    && steps[k].depends_on.contains(&i)
+                    && steps[i].outputs.iter().any(|(_, file)| is_in_memory(file))
+            });
+            to_run[i] = !(completed.contains(&steps[i].id) && outputs_exist) || needed_in_memory;
+        }
+        let num_to_run = to_run.iter().filter(|r| **r).count();
+        completed = (0..num_steps)
+            .filter(|i| !to_run[*i])
+            .map(|i| steps[i].id.clone())
+            .collect();
+        if self.verbose {
+            println!(
+                "Running {} of the {} pipeline steps ({} completed previously)...",
+                num_to_run,
+                num_steps,
+                num_steps - num_to_run
+            );
+        }
+
+        // run the steps, in parallel where they are independent of one another
+        let mut num_waiting_on: Vec<usize> = (0..num_steps)
+            .map(|i| {
+                steps[i]
+                    .depends_on
+                    .iter()
+                    .collect::<HashSet<&usize>>()
+                    .into_iter()
+                    .filter(|j| to_run[**j])
+                    .count()
+            })
+            .collect();
+        let mut num_consumers: Vec<usize> = (0..num_steps)
+            .map(|i| {
+                (0..num_steps)
+                    .filter(|k| to_run[*k] && steps[*k].depends_on.contains(&i))
+                    .count()
+            })
+            .collect();
+        let mut ready: Vec<usize> = order
+            .iter()
+            .filter(|i| to_run[**i] && num_waiting_on[**i] == 0)
+            .cloned()
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        let mut num_running = 0usize;
+        let mut num_finished = 0usize;
+        let mut failure: Option<(usize, Error)> = None;
+        while num_finished < num_to_run {
+            while failure.is_none() && num_running < max_parallel && !ready.is_empty() {
+                let i = ready.remove(0);
+                if self.verbose {
+                    println!("Starting step '{}' ({})...", steps[i].id, steps[i].tool);
+                }
+                let tool = steps[i].tool.clone();
+                let args: Vec<String> = steps[i]
+                    .args
+                    .iter()
+                    .map(|(f, v)| {
+                        if v.is_empty() {
+                            format!("--{}", f)
+                        } else {
+                            format!("--{}={}", f, v)
+                        }
+                    })
+                    .collect();
+                let working_dir = self.working_dir.clone();
+                let verbose = self.verbose;
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let result = match ToolManager::new(&working_dir, &verbose) {
+                        Ok(tm) => match tm.get_tool(&tool) {
+                            Some(t) => t.run(args, &working_dir, verbose),
+                            None => Err(Error::new(
+                                ErrorKind::NotFound,
+                                format!("Unrecognized tool name {}.", tool),
+                            )),
+                        },
+                        Err(e) => Err(e),
+                    };
+                    tx.send((i, result)).unwrap();
+                });
+                num_running += 1;
+            }
+            if num_running == 0 {
+                break;
+            }
+            let (i, result) = rx.recv().expect("Error receiving data from thread.");
+            num_running -= 1;
+            num_finished += 1;
+            match result {
+                Ok(_) => {
+                    if self.verbose {
+                        println!("Completed step '{}' ({})", steps[i].id, steps[i].tool);
+                    }
+                    completed.insert(steps[i].id.clone());
+                    write_state(&state_file, &steps, &completed)?;
+                    for k in 0..num_steps {
+                        if to_run[k] && steps[k].depends_on.contains(&i) {
+                            num_waiting_on[k] -= 1;
+                            if num_waiting_on[k] == 0 {
+                                ready.push(k);
+                            }
+                        }
+                    }
+                    // release any in-memory intermediates that are no longer needed
+                    for &j in steps[i].depends_on.iter().collect::<HashSet<&usize>>() {
+                        num_consumers[j] -= 1;
+                        if num_consumers[j] == 0 {
+                            for (_, file) in &steps[j].outputs {
+                                if is_in_memory(file) {
+                                    remove_dataset(file);
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    if failure.is_none() {
+                        failure = Some((i, e));
+                    }
+                }
+            }
+        }
+
+        for step in &steps {
+            for (_, file) in &step.outputs {
+                if is_in_memory(file) {
+                    remove_dataset(file);
+                }
+            }
+        }
+
+        if let Some((i, e)) = failure {
+            return Err(Error::new(
+                e.kind(),
+                format!(
+                    "Pipeline step '{}' ({}) failed: {}. Completed steps have been recorded in {}; re-run with the --resume flag to continue the pipeline.",
+                    steps[i].id, steps[i].tool, e, state_file
+                ),
+            ));
+        }
+        if path::Path::new(&state_file).exists() {
+            fs::remove_file(&state_file)?;
+        }
+
+        if self.verbose {
+            println!(
+                "{}",
+                &format!(
+                    "Pipeline completed. Elapsed Time: {}",
+                    get_formatted_elapsed_time(start)
+                )
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Strips the leading dashes from a flag and converts it to lowercase.
+pub(super) fn trim_flag(flag: &str) -> String {
+    flag.trim().trim_start_matches('-').to_lowercase()
+}
+
+/// Parses a reference to a step output, of the form `@step.flag`, returning the
+/// step id and output flag.
+fn parse_reference(value: &str) -> Option<(String, String)> {
+    let value = value.trim();
+    if !value.starts_with("@") {
+        return None;
+    }
+    let mut parts = value[1..].rsplitn(2, '.');
+    let flag = parts.next()?;
+    let step = parts.next()?;
+    Some((step.to_string(), flag.to_string()))
+}
+
+/// Converts the `args` of a step, either an object of flag/value pairs or an array
+/// of command-line style arguments, into (flag, value) pairs. Boolean flags that are
+/// set take an empty value and list values are joined with semicolons.
+pub(super) fn parse_step_args(args: &Value) -> Result<Vec<(String, String)>, Error> {
+    let mut ret = vec![];
+    match args {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (flag, value) in map {
+                let flag = trim_flag(flag);
+                match value {
+                    Value::Bool(true) => ret.push((flag, String::new())),
+                    Value::Bool(false) => ret.push((flag, "false".to_string())),
+                    Value::String(s) => ret.push((flag, s.clone())),
+                    Value::Number(n) => ret.push((flag, n.to_string())),
+                    Value::Array(a) => {
+                        let values: Vec<String> = a
+                            .iter()
+                            .map(|v| match v {
+                                Value::String(s) => s.clone(),
+                                _ => v.to_string(),
+                            })
+                            .collect();
+                        ret.push((flag, values.join(";")));
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unsupported value for argument '{}'.", flag),
+                        ))
+                    }
+                }
+            }
+        }
+        Value::Array(a) => {
+            for v in a {
+                let arg = match v.as_str() {
+                    Some(s) => s.trim(),
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Argument arrays must contain strings, e.g. \"--input=DEM.tif\".",
+                        ))
+                    }
+                };
+                let mut parts = arg.splitn(2, '=');
+                let flag = trim_flag(parts.next().unwrap_or(""));
+                let value = parts.next().unwrap_or("").replace("\"", "");
+                ret.push((flag, value));
+            }
+        }
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A step's 'args' must be an object or an array.",
+            ))
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns the step indices ordered such that each step follows its dependencies,
+/// or an error if the dependencies contain a cycle.
+fn topological_order(steps: &[Step]) -> Result<Vec<usize>, Error> {
+    let n = steps.len();
+    let mut in_degree: Vec<usize> = (0..n)
+        .map(|i| {
+            steps[i]
+                .depends_on
+                .iter()
+                .collect::<HashSet<&usize>>()
+                .len()
+        })
+        .collect();
+    let mut order = Vec::with_capacity(n);
+    let mut stack: Vec<usize> = (0..n).rev().filter(|i| in_degree[*i] == 0).collect();
+    while let Some(i) = stack.pop() {
+        order.push(i);
+        for k in (0..n).rev() {
+            if steps[k].depends_on.contains(&i) {
+                in_degree[k] -= 1;
+                if in_degree[k] == 0 {
+                    stack.push(k);
+                }
+            }
+        }
+    }
+    if order.len() < n {
+        let cycle: Vec<&str> = (0..n)
+            .filter(|i| !order.contains(i))
+            .map(|i| steps[i].id.as_str())
+            .collect();
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "The pipeline steps contain a circular dependency involving: {}.",
+                cycle.join(", ")
+            ),
+        ));
+    }
+    Ok(order)
+}
+
+/// Records the ids of the completed steps, in pipeline order, within the state file.
+fn write_state(state_file: &str, steps: &[Step], completed: &HashSet<String>) -> Result<(), Error> {
+    let ids: Vec<Value> = steps
+        .iter()
+        .filter(|s| completed.contains(&s.id))
+        .map(|s| Value::String(s.id.clone()))
+        .collect();
+    let mut state = serde_json::Map::new();
+    state.insert("completed".to_string(), Value::Array(ids));
+    fs::write(state_file, Value::Object(state).to_string())
+}
@@ -0,0 +1,628 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Runs WhiteboxTools as an HTTP (REST) service, so that web GIS applications may
+use it as a processing backend. The following endpoints are recognized:
+
+GET  /tools                      Lists the available tools, with descriptions and toolboxes.
+GET  /tools/{name}               Returns the parameters (in json form) of a tool.
+POST /jobs                       Submits a job, e.g. { "tool": "Slope", "args": { "dem": "DEM.tif", "output": "slope.tif" } }.
+GET  /jobs                       Lists the submitted jobs.
+GET  /jobs/{id}                  Returns a job's status, progress, recent messages, and outputs.
+GET  /files/{path}               Downloads a file, e.g. a job output, from the working directory.
+PUT  /files/{path}               Uploads a file, e.g. a job input, to the working directory.
+
+A job's `args` may be an object of flag/value pairs or an array of command-line style
+arguments, as with the steps of a pipeline. All file arguments are resolved relative
+to the server's working directory and may not refer to files outside of it. Jobs are
+queued and run, at most `max_jobs` at a time, in separate processes of the
+whitebox_tools executable so that each job's progress messages can be captured.
+*/
+
+use super::pipeline::{parse_step_args, trim_flag};
+use super::{apply_framework_flags, ParameterType, ToolManager, ToolParameter};
+use crate::utils::{get_formatted_elapsed_time, parse_progress, Instant};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// The number of recent tool messages retained for each job.
+const MAX_JOB_MESSAGES: usize = 100;
+
+/// The largest accepted request body, other than for file uploads.
+const MAX_REQUEST_BODY: usize = 1_048_576;
+
+#[derive(Clone, Copy, PartialEq)]
+enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+struct Job {
+    id: usize,
+    tool: String,
+    args: Vec<String>,
+    // (flag, file name) of each of the job's outputs
+    outputs: Vec<(String, String)>,
+    status: JobStatus,
+    progress: Option<usize>,
+    messages: VecDeque<String>,
+    error: Option<String>,
+    elapsed_time: Option<String>,
+}
+
+impl Job {
+    fn to_json(&self, detailed: bool) -> Value {
+        let mut v = json!({
+            "id": self.id,
+            "tool": self.tool,
+            "status": self.status.as_str(),
+            "progress": self.progress,
+        });
+        if detailed {
+            v["args"] = json!(self.args);
+            v["messages"] = json!(self.messages);
+            v["error"] = json!(self.error);
+            v["elapsed_time"] = json!(self.elapsed_time);
+            v["outputs"] = Value::Array(
+                self.outputs
+                    .iter()
+                    .map(|(flag, file)| {
+                        json!({
+                            "flag": flag,
+                            "file": file,
+                            "url": format!("/files/{}", file.replace("\\", "/")),
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        v
+    }
+}
+
+struct ServerState {
+    tm: ToolManager,
+    // the framework-level flags (e.g. `--compress`) applied to every job
+    framework_args: Vec<String>,
+    jobs: Mutex<Vec<Job>>,
+    queue: Mutex<mpsc::Sender<usize>>,
+}
+
+struct Request {
+    method: String,
+    path: Vec<String>,
+    content_length: usize,
+}
+
+impl ToolManager {
+    /// Runs an HTTP service, listening on `address` (e.g. `127.0.0.1:8080`), that
+    /// exposes the tools to web clients. At most `max_jobs` jobs are run concurrently.
+    /// The `args` may contain the framework-level flags (e.g. `--compress`), which
+    /// then apply to every job. This function only returns if the service fails to start.
+    pub fn serve(&self, address: &str, max_jobs: usize, args: Vec<String>) -> Result<(), Error> {
+        let _ = apply_framework_flags(args.clone())?;
+        let listener = TcpListener::bind(address)?;
+        let exe = env::current_exe()?;
+        let max_jobs = max_jobs.max(1);
+
+        let (tx, rx) = mpsc::channel::<usize>();
+        let state = Arc::new(ServerState {
+            tm: ToolManager::new(&self.working_dir, &self.verbose)?,
+            framework_args: args,
+            jobs: Mutex::new(vec![]),
+            queue: Mutex::new(tx),
+        });
+        let rx = Arc::new(Mutex::new(rx));
+        for _ in 0..max_jobs {
+            let state = state.clone();
+            let rx = rx.clone();
+            let exe = exe.clone();
+            thread::spawn(move || loop {
+                let id = match rx.lock().unwrap().recv() {
+                    Ok(id) => id,
+                    Err(_) => break,
+                };
+                run_job(&state, &exe, id);
+            });
+        }
+
+        println!(
+            "WhiteboxTools is serving on http://{} (working directory: {}, max jobs: {})",
+            address, self.working_dir, max_jobs
+        );
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+            let state = state.clone();
+            thread::spawn(move || {
+                let _ = handle_connection(&state, stream);
+            });
+        }
+        Ok(())
+    }
+}
+
+fn handle_connection(state: &ServerState, stream: TcpStream) -> Result<(), Error> {
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let request = match read_request(&mut reader) {
+        Ok(r) => r,
+        Err(e) => return send_error(&mut writer, &e),
+    };
+    if state.tm.verbose {
+        println!("{} /{}", request.method, request.path.join("/"));
+    }
+    let segments: Vec<&str> = request.path.iter().map(|s| s.as_str()).collect();
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("OPTIONS", _) => return send_response(&mut writer, 204, "text/plain", b""),
+        ("GET", ["tools"]) => Ok(list_tools(state)),
+        ("GET", ["tools", name]) => tool_parameters(state, name),
+        ("GET", ["jobs"]) => {
+            let jobs = state.jobs.lock().unwrap();
+            Ok(Value::Array(jobs.iter().map(|j| j.to_json(false)).collect()))
+        }
+        ("POST", ["jobs"]) => {
+            if request.content_length > MAX_REQUEST_BODY {
+                Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The request body is too large.",
+                ))
+            } else {
+                let mut body = vec![0u8; request.content_length];
+                reader.read_exact(&mut body)?;
+                submit_job(state, &body)
+            }
+        }
+        ("GET", ["jobs", id]) => {
+            let jobs = state.jobs.lock().unwrap();
+            match find_job(&jobs, id) {
+                Some(job) => Ok(job.to_json(true)),
+                None => Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("Unrecognized job id {}.", id),
+                )),
+            }
+        }
+        ("GET", ["files", ..]) => {
+            return match resolve_file(&state.tm.working_dir, &request.path[1..]) {
+                Ok(file) => send_file(&mut writer, &file),
+                Err(e) => send_error(&mut writer, &e),
+            };
+        }
+        ("PUT", ["files", ..]) => {
+            match resolve_file(&state.tm.working_dir, &request.path[1..]) {
+                Ok(file) => receive_file(&mut reader, request.content_length, &file)
+                    .map(|_| json!({ "file": request.path[1..].join("/") })),
+                Err(e) => Err(e),
+            }
+        }
+        _ => Err(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "Unrecognized request {} /{}.",
+                request.method,
+                request.path.join("/")
+            ),
+        )),
+    };
+    match result {
+        Ok(v) => {
+            let status = if request.method == "POST" { 202 } else { 200 };
+            send_response(
+                &mut writer,
+                status,
+                "application/json",
+                v.to_string().as_bytes(),
+            )
+        }
+        Err(e) => send_error(&mut writer, &e),
+    }
+}
+
+fn list_tools(state: &ServerState) -> Value {
+    Value::Array(
+        state
+            .tm
+            .get_tool_details()
+            .into_iter()
+            .map(|(name, description, toolbox)| {
+                json!({ "name": name, "description": description, "toolbox": toolbox })
+            })
+            .collect(),
+    )
+}
+
+fn tool_parameters(state: &ServerState, tool_name: &str) -> Result<Value, Error> {
+    let parameters = state.tm.get_tool_parameters(tool_name)?;
+    Ok(serde_json::from_str(&parameters)?)
+}
+
+/// Validates a job request and adds it to the queue, returning the new job's id.
+fn submit_job(state: &ServerState, body: &[u8]) -> Result<Value, Error> {
+    let request: Value = serde_json::from_slice(body).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Error parsing the job request: {}", e),
+        )
+    })?;
+    let tool = match request["tool"].as_str() {
+        Some(tool) => tool.to_string(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The job request does not specify a tool.",
+            ))
+        }
+    };
+    let parameters: Vec<ToolParameter> = match state.tm.get_tool(&tool) {
+        Some(t) => {
+            let o: Value = serde_json::from_str(&t.get_tool_parameters())?;
+            serde_json::from_value(o["parameters"].clone())?
+        }
+        None => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Unrecognized tool name {}.", tool),
+            ))
+        }
+    };
+
+    let mut args = vec![];
+    let mut outputs = vec![];
+    for (flag, mut value) in parse_step_args(&request["args"])? {
+        let p = parameters
+            .iter()
+            .find(|p| p.flags.iter().any(|f| trim_flag(f) == flag));
+        if let Some(p) = p {
+            // file arguments are resolved within the working directory, since tools
+            // only prepend the working directory to bare file names
+            let is_file_list = match &p.parameter_type {
+                ParameterType::ExistingFile(_)
+                | ParameterType::NewFile(_)
+                | ParameterType::Directory => Some(false),
+                ParameterType::ExistingFileOrFloat(_) if value.parse::<f64>().is_err() => {
+                    Some(false)
+                }
+                ParameterType::FileList(_) => Some(true),
+                _ => None,
+            };
+            if let Some(is_file_list) = is_file_list {
+                if let ParameterType::NewFile(_) = p.parameter_type {
+                    outputs.push((flag.clone(), value.trim().to_string()));
+                }
+                let files: Vec<&str> = if is_file_list {
+                    value
+                        .split(|c: char| c == ';' || c == ',')
+                        .filter(|f| !f.trim().is_empty())
+                        .collect()
+                } else {
+                    vec![value.as_str()]
+                };
+                let mut resolved = vec![];
+                for file in files {
+                    let file = file.trim();
+                    let parts: Vec<String> = file
+                        .split(|c: char| c == '/' || c == '\\')
+                        .map(|s| s.to_string())
+                        .collect();
+                    let r = if file.starts_with('/') || file.starts_with('\\') {
+                        Err(outside_working_dir())
+                    } else {
+                        resolve_file(&state.tm.working_dir, &parts)
+                    };
+                    resolved.push(r.map_err(|e| {
+                        Error::new(
+                            e.kind(),
+                            format!("Invalid value for argument '{}': {}", flag, e),
+                        )
+                    })?);
+                }
+                value = resolved.join(";");
+            }
+        }
+        if value.is_empty() {
+            args.push(format!("--{}", flag));
+        } else {
+            args.push(format!("--{}={}", flag, value));
+        }
+    }
+
+    let mut jobs = state.jobs.lock().unwrap();
+    let id = jobs.len() + 1;
+    jobs.push(Job {
+        id,
+        tool,
+        args,
+        outputs,
+        status: JobStatus::Queued,
+        progress: None,
+        messages: VecDeque::new(),
+        error: None,
+        elapsed_time: None,
+    });
+    drop(jobs);
+    state
+        .queue
+        .lock()
+        .unwrap()
+        .send(id)
+        .map_err(|_| Error::new(ErrorKind::Other, "The job queue is unavailable."))?;
+    Ok(json!({ "id": id, "status": JobStatus::Queued.as_str(), "url": format!("/jobs/{}", id) }))
+}
+
+/// Runs a queued job in a separate whitebox_tools process, recording its progress.
+fn run_job(state: &ServerState, exe: &path::Path, id: usize) {
+    let (tool, args) = {
+        let mut jobs = state.jobs.lock().unwrap();
+        let job = &mut jobs[id - 1];
+        job.status = JobStatus::Running;
+        (job.tool.clone(), job.args.clone())
+    };
+    let update = |f: &mut dyn FnMut(&mut Job)| {
+        let mut jobs = state.jobs.lock().unwrap();
+        f(&mut jobs[id - 1]);
+    };
+
+    let start = Instant::now();
+    let child = Command::new(exe)
+        .arg(format!("--run={}", tool))
+        .arg(format!("--wd={}", state.tm.working_dir))
+        .arg("-v")
+        .args(&args)
+        .args(&state.framework_args)
+        .current_dir(&state.tm.working_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(c) => c,
+        Err(e) => {
+            update(&mut |job| {
+                job.status = JobStatus::Failed;
+                job.error = Some(format!("Error starting the tool: {}", e));
+            });
+            return;
+        }
+    };
+    let stderr = child.stderr.take().map(|mut stderr| {
+        thread::spawn(move || {
+            let mut s = String::new();
+            let _ = stderr.read_to_string(&mut s);
+            s
+        })
+    });
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            update(&mut |job| {
+                if let Some(p) = parse_progress(&line) {
+                    job.progress = Some(p);
+                }
+                if job.messages.len() == MAX_JOB_MESSAGES {
+                    job.messages.pop_front();
+                }
+                job.messages.push_back(line.clone());
+            });
+        }
+    }
+    let stderr = stderr
+        .and_then(|h| h.join().ok())
+        .unwrap_or_default();
+    let success = match child.wait() {
+        Ok(status) => status.success(),
+        Err(_) => false,
+    };
+    let elapsed_time = get_formatted_elapsed_time(start);
+    update(&mut |job| {
+        job.elapsed_time = Some(elapsed_time.clone());
+        if success {
+            job.status = JobStatus::Completed;
+            job.progress = Some(100);
+        } else {
+            job.status = JobStatus::Failed;
+            let message: Vec<&str> = stderr
+                .lines()
+                .filter(|l| !l.trim().is_empty() && !l.starts_with("note:"))
+                .collect();
+            job.error = Some(if message.is_empty() {
+                "The tool did not complete successfully.".to_string()
+            } else {
+                message.join("\n")
+            });
+        }
+    });
+}
+
+fn find_job<'a>(jobs: &'a [Job], id: &str) -> Option<&'a Job> {
+    match id.parse::<usize>() {
+        Ok(id) if id >= 1 && id <= jobs.len() => Some(&jobs[id - 1]),
+        _ => None,
+    }
+}
+
+/// Returns the path of a file within the working directory, given the segments of
+/// its relative path. Absolute paths and paths leading outside of the working
+/// directory are rejected.
+fn resolve_file(working_dir: &str, segments: &[String]) -> Result<String, Error> {
+    let segments: Vec<&str> = segments
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect();
+    if segments.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "No file name was specified."));
+    }
+    if segments.iter().any(|s| *s == ".." || s.contains(':')) {
+        return Err(outside_working_dir());
+    }
+    let sep = path::MAIN_SEPARATOR.to_string();
+    Ok(format!("{}{}", working_dir, segments.join(&sep)))
+}
+
+fn outside_working_dir() -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        "Files must be located within the server's working directory.",
+    )
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Error> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_uppercase();
+    let target = parts.next().unwrap_or("");
+    if method.is_empty() || !target.starts_with('/') {
+        return Err(Error::new(ErrorKind::InvalidInput, "Malformed HTTP request."));
+    }
+    let target = target.split('?').next().unwrap_or("");
+    let path: Vec<String> = target
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| percent_decode(s))
+        .collect::<Result<_, _>>()?;
+
+    let mut content_length = 0usize;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let header = line.trim();
+        if header.is_empty() {
+            break;
+        }
+        let mut kv = header.splitn(2, ':');
+        let key = kv.next().unwrap_or("").trim().to_lowercase();
+        let value = kv.next().unwrap_or("").trim();
+        if key == "content-length" {
+            content_length = value.parse().map_err(|_| {
+                Error::new(ErrorKind::InvalidInput, "Invalid Content-Length header.")
+            })?;
+        }
+    }
+    Ok(Request {
+        method,
+        path,
+        content_length,
+    })
+}
+
+fn percent_decode(s: &str) -> Result<String, Error> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            match u8::from_str_radix(hex, 16) {
+                Ok(b) => {
+                    decoded.push(b);
+                    i += 3;
+                    continue;
+                }
+                Err(_) => {}
+            }
+        }
+        decoded.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+        i += 1;
+    }
+    String::from_utf8(decoded)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "The request path is not valid UTF-8."))
+}
+
+fn receive_file<R: Read>(reader: &mut R, length: usize, file: &str) -> Result<(), Error> {
+    if let Some(parent) = path::Path::new(file).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut f = fs::File::create(file)?;
+    let copied = std::io::copy(&mut reader.take(length as u64), &mut f)?;
+    if copied != length as u64 {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "The upload ended before the full file was received.",
+        ));
+    }
+    Ok(())
+}
+
+fn send_file<W: Write>(writer: &mut W, file: &str) -> Result<(), Error> {
+    let mut f = fs::File::open(file)?;
+    let length = f.metadata()?.len();
+    write!(
+        writer,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        length
+    )?;
+    std::io::copy(&mut f, writer)?;
+    writer.flush()
+}
+
+fn send_error<W: Write>(writer: &mut W, e: &Error) -> Result<(), Error> {
+    let status = match e.kind() {
+        ErrorKind::NotFound => 404,
+        ErrorKind::InvalidInput | ErrorKind::InvalidData | ErrorKind::UnexpectedEof => 400,
+        ErrorKind::PermissionDenied => 403,
+        _ => 500,
+    };
+    let body = json!({ "error": e.to_string() }).to_string();
+    send_response(writer, status, "application/json", body.as_bytes())
+}
+
+fn send_response<W: Write>(
+    writer: &mut W,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), Error> {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, PUT, OPTIONS\r\nAccess-Control-Allow-Headers: Content-Type\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )?;
+    writer.write_all(body)?;
+    writer.flush()
+}
@@ -0,0 +1,9 @@
+// private sub-module defined in other files
+mod rusle_ls_factor;
+mod rusle_soil_loss;
+mod soil_mapping_covariates;
+
+// exports identifiers from private sub-modules in the current module namespace
+pub use self::rusle_ls_factor::RusleLsFactor;
+pub use self::rusle_soil_loss::RusleSoilLoss;
+pub use self::soil_mapping_covariates::SoilMappingCovariates;
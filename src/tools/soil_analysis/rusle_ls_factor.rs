@@ -0,0 +1,439 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the slope length and steepness factor (*LS*) of the Revised Universal Soil
+/// Loss Equation (RUSLE; Renard et al., 1997) from a specific contributing area (*A<sub>s</sub>*,
+/// i.e. the upslope contributing area per unit contour length; `--sca`) raster and a slope gradient
+/// (&theta;, measured in degrees; `--slope`) raster. Because the upslope area is used in place of
+/// the slope length, the factor accounts for the convergence and divergence of overland flow in
+/// complex terrain. *A<sub>s</sub>* can be derived using any of the flow accumulation tools (with
+/// the specific contributing area output type), although better results usually result from
+/// multiple-flow-direction algorithms such as `FD8FlowAccumulation`, and must not be
+/// log-transformed. The slope raster can be created from the DEM using the `Slope` tool.
+///
+/// Two methods are available (`--method`). The default 'desmet' method uses the two-dimensional
+/// slope length factor of Desmet and Govers (1996):
+///
+/// > *L* = ((*A* + *D*<sup>2</sup>)<sup>*m*+1</sup> - *A*<sup>*m*+1</sup>) / (*D*<sup>*m*+2</sup> 22.13<sup>*m*</sup>)
+///
+/// where *A* is the area draining into the grid cell, estimated as *A<sub>s</sub> D* -
+/// *D*<sup>2</sup>, and *D* is the grid resolution in metres. The slope length exponent, *m* =
+/// &beta; / (1 + &beta;), varies with the ratio of rill to interrill erosion, &beta; = *r* (sin
+/// &theta; / 0.0896) / (3 sin<sup>0.8</sup> &theta; + 0.56) (McCool et al., 1989), where *r*
+/// (`--rill_ratio`; default 1.0) is greater than one for soils that are highly susceptible to rill
+/// erosion and less than one for soils that are not. The slope steepness factor is that of McCool
+/// et al. (1987), i.e. *S* = 10.8 sin &theta; + 0.03 for slope gradients less than 9% and *S* =
+/// 16.8 sin &theta; - 0.50 otherwise. The 'moore' method uses the unit stream-power based factor of
+/// Moore and Burch (1986):
+///
+/// > *LS* = 1.4 (*A<sub>s</sub>* / 22.13)<sup>0.4</sup> (sin &theta; / 0.0896)<sup>1.3</sup>
+///
+/// (see also `SedimentTransportIndex`), which is best suited to slope lengths of less than 100 m
+/// and slopes of less than 14&deg;.
+///
+/// Because the RUSLE describes sheet and rill erosion, two cutoffs are available. Overland flow
+/// rarely remains unconcentrated over long distances, and the maximum slope length (`--max_length`,
+/// in metres) caps the upslope area of each cell at that of a slope of the given length (RUSLE
+/// guidance suggests that slope lengths rarely exceed 300 m). Cells whose specific contributing
+/// area exceeds a channel threshold (`--channel_threshold`) are considered to be channels, in which
+/// erosion is not described by the RUSLE, and are assigned an *LS* value of zero. The output may be
+/// combined with the other RUSLE factors using the `RusleSoilLoss` tool.
+///
+/// # Reference
+/// Desmet, P. J. J., and Govers, G. (1996). A GIS procedure for automatically calculating the USLE
+/// LS factor on topographically complex landscape units. *Journal of Soil and Water Conservation*,
+/// 51(5), 427-433.
+///
+/// McCool, D. K., Brown, L. C., Foster, G. R., Mutchler, C. K., and Meyer, L. D. (1987). Revised
+/// slope steepness factor for the Universal Soil Loss Equation. *Transactions of the ASAE*, 30(5),
+/// 1387-1396.
+///
+/// McCool, D. K., Foster, G. R., Mutchler, C. K., and Meyer, L. D. (1989). Revised slope length
+/// factor for the Universal Soil Loss Equation. *Transactions of the ASAE*, 32(5), 1571-1576.
+///
+/// Moore, I. D., and Burch, G. J. (1986). Physical basis of the length-slope factor in the
+/// Universal Soil Loss Equation. *Soil Science Society of America Journal*, 50(5), 1294-1298.
+///
+/// Renard, K. G., Foster, G. R., Weesies, G. A., McCool, D. K., and Yoder, D. C. (1997).
+/// *Predicting soil erosion by water: a guide to conservation planning with the Revised Universal
+/// Soil Loss Equation (RUSLE)*. Agriculture Handbook 703, USDA.
+///
+/// # See Also
+/// `RusleSoilLoss`, `SedimentTransportIndex`, `FD8FlowAccumulation`, `Slope`
+pub struct RusleLsFactor {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RusleLsFactor {
+    pub fn new() -> RusleLsFactor {
+        // public constructor
+        let name = "RusleLsFactor".to_string();
+        let toolbox = "Agriculture/Soils".to_string();
+        let description =
+            "Calculates the RUSLE slope length and steepness (LS) factor from contributing area and slope."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Specific Contributing Area (SCA) File".to_owned(),
+            flags: vec!["--sca".to_owned()],
+            description: "Input raster specific contributing area (SCA) file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Slope File".to_owned(),
+            flags: vec!["--slope".to_owned()],
+            description: "Input raster slope file, in degrees.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "LS factor method; one of 'desmet' and 'moore'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "desmet".to_owned(),
+                "moore".to_owned(),
+            ]),
+            default_value: Some("desmet".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Rill to Interrill Erosion Ratio".to_owned(),
+            flags: vec!["--rill_ratio".to_owned()],
+            description: "Ratio of rill to interrill erosion, used by the 'desmet' method."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Slope Length (m; optional)".to_owned(),
+            flags: vec!["--max_length".to_owned()],
+            description: "Optional maximum slope length, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Channel Threshold (optional)".to_owned(),
+            flags: vec!["--channel_threshold".to_owned()],
+            description: "Optional SCA above which cells are channels and are assigned zero."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --sca=sca.tif --slope=slope.tif -o=ls.tif --method=desmet --max_length=300.0 --channel_threshold=10000.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        RusleLsFactor {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RusleLsFactor {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut sca_file = String::new();
+        let mut slope_file = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("desmet");
+        let mut rill_ratio = 1f64;
+        let mut max_length = f64::INFINITY;
+        let mut channel_threshold = f64::INFINITY;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-sca" {
+                sca_file = value;
+            } else if flag_val == "-slope" {
+                slope_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-method" {
+                method = if value.to_lowercase().contains("moore") {
+                    "moore".to_string()
+                } else {
+                    "desmet".to_string()
+                };
+            } else if flag_val == "-rill_ratio" {
+                rill_ratio = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_length" {
+                max_length = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-channel_threshold" {
+                channel_threshold = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !sca_file.contains(&sep) && !sca_file.contains("/") {
+            sca_file = format!("{}{}", working_directory, sca_file);
+        }
+        if !slope_file.contains(&sep) && !slope_file.contains("/") {
+            slope_file = format!("{}{}", working_directory, slope_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if max_length <= 0f64 {
+            max_length = f64::INFINITY;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let sca = Arc::new(Raster::new(&sca_file, "r")?);
+        let slope = Arc::new(align_raster_input(&sca, Raster::new(&slope_file, "r")?)?);
+
+        let start = Instant::now();
+        let rows = sca.configs.rows as isize;
+        let columns = sca.configs.columns as isize;
+        let sca_nodata = sca.configs.nodata;
+        let slope_nodata = slope.configs.nodata;
+
+        // the grid resolution, in metres
+        let mut res_x = sca.configs.resolution_x;
+        let mut res_y = sca.configs.resolution_y;
+        if sca.is_in_geographic_coordinates() {
+            let mid_lat = ((sca.configs.north + sca.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let cell_size = (res_x * res_y).sqrt();
+        let desmet = method == "desmet";
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let sca = sca.clone();
+            let slope = slope.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let d = cell_size;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![sca_nodata; columns as usize];
+                    for col in 0..columns {
+                        let a_s = sca.get_value(row, col);
+                        let theta = slope.get_value(row, col);
+                        if a_s == sca_nodata || theta == slope_nodata {
+                            continue;
+                        }
+                        if a_s > channel_threshold {
+                            data[col as usize] = 0f64;
+                            continue;
+                        }
+                        let sin_theta = theta.to_radians().sin();
+                        data[col as usize] = if desmet {
+                            // the area draining into the cell, capped at the maximum slope length
+                            let area = (a_s * d - d * d)
+                                .max(0f64)
+                                .min((max_length - d).max(0f64) * d);
+                            let beta = rill_ratio * (sin_theta / 0.0896)
+                                / (3f64 * sin_theta.powf(0.8) + 0.56);
+                            let m = beta / (1f64 + beta);
+                            let l = ((area + d * d).powf(m + 1f64) - area.powf(m + 1f64))
+                                / (d.powf(m + 2f64) * 22.13f64.powf(m));
+                            let s = if theta.to_radians().tan() < 0.09 {
+                                10.8 * sin_theta + 0.03
+                            } else {
+                                16.8 * sin_theta - 0.50
+                            };
+                            l * s
+                        } else {
+                            1.4 * (a_s.min(max_length) / 22.13).powf(0.4)
+                                * (sin_theta / 0.0896).powf(1.3)
+                        };
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &sca);
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.data_type = DataType::F32;
+        output.configs.palette = "grey.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.clip_display_min_max(1.0);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("SCA raster: {}", sca_file));
+        output.add_metadata_entry(format!("Slope raster: {}", slope_file));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if desmet {
+            output.add_metadata_entry(format!("Rill ratio: {}", rill_ratio));
+        }
+        if max_length.is_finite() {
+            output.add_metadata_entry(format!("Max slope length: {}", max_length));
+        }
+        if channel_threshold.is_finite() {
+            output.add_metadata_entry(format!("Channel threshold: {}", channel_threshold));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if sca.configs.maximum < 100.0 {
+            println!("WARNING: The input SCA data layer contained only low values. It is likely that it has been
+            log-transformed. This tool requires non-transformed SCA as an input.")
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
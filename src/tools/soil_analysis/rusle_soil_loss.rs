@@ -0,0 +1,494 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use std::collections::BTreeMap;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+use std::path;
+
+/// This tool estimates the mean annual soil loss (*A*) due to sheet and rill erosion using the
+/// Revised Universal Soil Loss Equation (RUSLE; Renard et al., 1997):
+///
+/// > *A* = *R K LS C P*
+///
+/// where *R* is the rainfall-runoff erosivity factor (`--r_factor`), *K* is the soil erodibility
+/// factor (`--k_factor`), *LS* is the slope length and steepness factor (`--ls`), *C* is the
+/// cover-management factor (`--c_factor`), and *P* is the support practice factor (`--p_factor`;
+/// default 1.0, i.e. no support practices). The *LS* factor raster, which may be created using the
+/// `RusleLsFactor` tool, defines the output grid; each of the other factors may be either a raster,
+/// which must share the grid of the *LS* raster (see the `--resample_inputs` flag), or a constant
+/// value, which is convenient for the *R* factor of small study areas. When *R* is in MJ mm
+/// ha<sup>-1</sup> h<sup>-1</sup> yr<sup>-1</sup> and *K* is in t ha h ha<sup>-1</sup>
+/// MJ<sup>-1</sup> mm<sup>-1</sup>, the output soil loss raster (`--output`) is in t
+/// ha<sup>-1</sup> yr<sup>-1</sup>. Grid cells that are NoData in any of the factor rasters are
+/// NoData in the output.
+///
+/// The soil loss may also be summarized by zone, e.g. by watershed or field, using an optional
+/// zones raster (`--zones`) in which each zone has a unique integer identifier, such as that
+/// created by the `Watershed` or `Subbasins` tools. The summary is written to a CSV file
+/// (`--out_table`) containing, for each zone, the number of grid cells (CELLS), the area in
+/// hectares (AREA_HA), the mean and maximum soil loss rates (MEAN_LOSS and MAX_LOSS, in t
+/// ha<sup>-1</sup> yr<sup>-1</sup>), and the total annual soil loss (TOTAL_LOSS, in t
+/// yr<sup>-1</sup>). Cell areas are calculated in square metres, with the resolution of grids in
+/// geographic coordinates converted at the grid's mid-latitude. The mean rate and total soil loss
+/// of the entire grid are also reported.
+///
+/// # Reference
+/// Renard, K. G., Foster, G. R., Weesies, G. A., McCool, D. K., and Yoder, D. C. (1997).
+/// *Predicting soil erosion by water: a guide to conservation planning with the Revised Universal
+/// Soil Loss Equation (RUSLE)*. Agriculture Handbook 703, USDA.
+///
+/// # See Also
+/// `RusleLsFactor`, `SedimentTransportIndex`, `Watershed`, `ZonalStatistics`
+pub struct RusleSoilLoss {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RusleSoilLoss {
+    pub fn new() -> RusleSoilLoss {
+        // public constructor
+        let name = "RusleSoilLoss".to_string();
+        let toolbox = "Agriculture/Soils".to_string();
+        let description =
+            "Estimates mean annual soil loss using the RUSLE, with optional summaries by zone."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Rainfall Erosivity (R) File Or Constant".to_owned(),
+            flags: vec!["--r_factor".to_owned()],
+            description: "Input R factor raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Soil Erodibility (K) File Or Constant".to_owned(),
+            flags: vec!["--k_factor".to_owned()],
+            description: "Input K factor raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Slope Length and Steepness (LS) File".to_owned(),
+            flags: vec!["--ls".to_owned()],
+            description: "Input LS factor raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Cover-Management (C) File Or Constant".to_owned(),
+            flags: vec!["--c_factor".to_owned()],
+            description: "Input C factor raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Support Practice (P) File Or Constant".to_owned(),
+            flags: vec!["--p_factor".to_owned()],
+            description: "Input P factor raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Soil Loss File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output soil loss raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Zones File (optional)".to_owned(),
+            flags: vec!["--zones".to_owned()],
+            description: "Optional input raster of zone identifiers, e.g. watersheds.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Zone Summary File (optional)".to_owned(),
+            flags: vec!["--out_table".to_owned()],
+            description: "Output CSV file of the soil loss of each zone.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --r_factor=850.0 --k_factor=k.tif --ls=ls.tif --c_factor=c.tif -o=soil_loss.tif --zones=watersheds.tif --out_table=soil_loss_by_watershed.csv",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        RusleSoilLoss {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RusleSoilLoss {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut r_input = String::new();
+        let mut k_input = String::new();
+        let mut ls_file = String::new();
+        let mut c_input = String::new();
+        let mut p_input = String::from("1.0");
+        let mut output_file = String::new();
+        let mut zones_file = String::new();
+        let mut table_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-r_factor" {
+                r_input = value;
+            } else if flag_val == "-k_factor" {
+                k_input = value;
+            } else if flag_val == "-ls" {
+                ls_file = value;
+            } else if flag_val == "-c_factor" {
+                c_input = value;
+            } else if flag_val == "-p_factor" {
+                p_input = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-zones" {
+                zones_file = value;
+            } else if flag_val == "-out_table" {
+                table_file = value;
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !ls_file.contains(&sep) && !ls_file.contains("/") {
+            ls_file = format!("{}{}", working_directory, ls_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !zones_file.is_empty() {
+            if table_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An output zone summary file (--out_table) must be specified along with the zones file.",
+                ));
+            }
+            if !zones_file.contains(&sep) && !zones_file.contains("/") {
+                zones_file = format!("{}{}", working_directory, zones_file);
+            }
+            if !table_file.contains(&sep) && !table_file.contains("/") {
+                table_file = format!("{}{}", working_directory, table_file);
+            }
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let ls = Raster::new(&ls_file, "r")?;
+        let mut factors: Vec<(&str, Factor)> = vec![];
+        for (name, input) in [
+            ("R", &r_input),
+            ("K", &k_input),
+            ("C", &c_input),
+            ("P", &p_input),
+        ]
+        .iter()
+        {
+            if input.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The {} factor must be specified.", name),
+                ));
+            }
+            factors.push((*name, Factor::read(input.as_str(), &ls, working_directory)?));
+        }
+        let zones = if !zones_file.is_empty() {
+            Some(align_raster_input(&ls, Raster::new(&zones_file, "r")?)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = ls.configs.rows as isize;
+        let columns = ls.configs.columns as isize;
+        let nodata = -32768f64;
+        let ls_nodata = ls.configs.nodata;
+
+        // the area of a grid cell, in hectares
+        let mut res_x = ls.configs.resolution_x;
+        let mut res_y = ls.configs.resolution_y;
+        if ls.is_in_geographic_coordinates() {
+            let mid_lat = ((ls.configs.north + ls.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let cell_area = res_x * res_y / 10000f64;
+
+        let mut output = Raster::initialize_using_file(&output_file, &ls);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = nodata;
+        // zone ID -> (cells, sum of rates, maximum rate)
+        let mut zone_stats: BTreeMap<i64, (usize, f64, f64)> = BTreeMap::new();
+        let (mut num_cells, mut sum_rates) = (0usize, 0f64);
+        for row in 0..rows {
+            let mut data = vec![nodata; columns as usize];
+            'col: for col in 0..columns {
+                let mut a = ls.get_value(row, col);
+                if a == ls_nodata {
+                    continue;
+                }
+                for (_, factor) in factors.iter() {
+                    match factor.get_value(row, col) {
+                        Some(v) => a *= v,
+                        None => continue 'col,
+                    }
+                }
+                data[col as usize] = a;
+                num_cells += 1;
+                sum_rates += a;
+                if let Some(ref zones) = zones {
+                    let z = zones.get_value(row, col);
+                    if z != zones.configs.nodata {
+                        let entry = zone_stats.entry(z.round() as i64).or_insert((
+                            0,
+                            0f64,
+                            f64::NEG_INFINITY,
+                        ));
+                        entry.0 += 1;
+                        entry.1 += a;
+                        entry.2 = entry.2.max(a);
+                    }
+                }
+            }
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let mean_rate = if num_cells > 0 {
+            sum_rates / num_cells as f64
+        } else {
+            0f64
+        };
+        let total_loss = sum_rates * cell_area;
+        if verbose {
+            println!("Mean soil loss rate: {:.4}", mean_rate);
+            println!("Total soil loss: {:.3}", total_loss);
+        }
+
+        output.configs.palette = "spectrum.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.clip_display_min_max(1.0);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("LS raster: {}", ls_file));
+        for (name, factor) in factors.iter() {
+            output.add_metadata_entry(format!("{} factor: {}", name, factor.description()));
+        }
+        output.add_metadata_entry(format!("Mean soil loss rate: {}", mean_rate));
+        output.add_metadata_entry(format!("Total soil loss: {}", total_loss));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if zones.is_some() {
+            let f = File::create(&table_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("ZONE,CELLS,AREA_HA,MEAN_LOSS,MAX_LOSS,TOTAL_LOSS\n".as_bytes())?;
+            for (zone, (n, sum, max)) in zone_stats.iter() {
+                writer.write_all(
+                    format!(
+                        "{},{},{:.4},{:.4},{:.4},{:.4}\n",
+                        zone,
+                        n,
+                        *n as f64 * cell_area,
+                        sum / *n as f64,
+                        max,
+                        sum * cell_area
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            let _ = writer.flush();
+            if verbose {
+                println!("Zone summary written ({} zones)", zone_stats.len());
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A RUSLE factor, which is either a raster sharing the grid of the LS factor or a constant.
+enum Factor {
+    Grid(Raster),
+    Constant(f64),
+}
+
+impl Factor {
+    fn read(input: &str, base: &Raster, working_directory: &str) -> Result<Factor, Error> {
+        if let Ok(v) = input.parse::<f64>() {
+            return Ok(Factor::Constant(v));
+        }
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let file = if !input.contains(&sep) && !input.contains("/") {
+            format!("{}{}", working_directory, input)
+        } else {
+            input.to_string()
+        };
+        Ok(Factor::Grid(align_raster_input(
+            base,
+            Raster::new(&file, "r")?,
+        )?))
+    }
+
+    fn get_value(&self, row: isize, col: isize) -> Option<f64> {
+        match self {
+            Factor::Grid(r) => {
+                let v = r.get_value(row, col);
+                if v == r.configs.nodata {
+                    None
+                } else {
+                    Some(v)
+                }
+            }
+            Factor::Constant(v) => Some(*v),
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Factor::Grid(r) => r.file_name.clone(),
+            Factor::Constant(v) => format!("{}", v),
+        }
+    }
+}
@@ -0,0 +1,600 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::hydro_analysis::dinf_routing::{dinf_contributing_area, dinf_flow_directions};
+use crate::tools::hydro_analysis::mrvbf::{elevation_percentile, mrvbf};
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// The names of the covariates, in band order.
+const COVARIATES: [&str; 8] = [
+    "Slope (degrees)",
+    "Profile curvature (degrees x 100)",
+    "Plan curvature (degrees x 100)",
+    "Topographic wetness index",
+    "Multi-resolution valley bottom flatness",
+    "Relative elevation percentile",
+    "Distance to streams (m)",
+    "Distance to ridges (m)",
+];
+
+/// The lower bound placed on the slope gradient (m/m) in the wetness index, which keeps the index
+/// finite in flat areas.
+const MIN_TAN_SLOPE: f64 = 0.001;
+
+/// This tool derives a standard stack of terrain covariates for digital soil mapping (DSM) from a
+/// digital elevation model (DEM; `--dem`). Soil properties vary with landscape position, and these
+/// covariates are among the most commonly used predictors in DSM models, e.g. random forests or
+/// regression kriging. Rather than running a separate tool for each covariate, which reads the DEM
+/// and builds the flow routing many times over, this tool calculates all of the covariates from a
+/// single reading of the DEM and writes them to the bands of one multi-band raster (`--output`),
+/// which must be in the GeoTIFF or Whitebox v2 (.wbr) format. The bands are:
+///
+/// | Band | Covariate |
+/// |------|-----------|
+/// | 1 | Slope gradient, in degrees |
+/// | 2 | Profile curvature, in degrees x 100 (as `ProfileCurvature`) |
+/// | 3 | Plan curvature, in degrees x 100 (as `PlanCurvature`) |
+/// | 4 | Topographic wetness index, ln(SCA / tan β) |
+/// | 5 | Multi-resolution valley bottom flatness (MRVBF; Gallant and Dowling, 2003) |
+/// | 6 | Relative elevation percentile, i.e. the proportion of lower cells within a radius |
+/// | 7 | Euclidean distance to the nearest stream cell, in metres |
+/// | 8 | Euclidean distance to the nearest ridge cell, in metres |
+///
+/// Slope and curvatures are calculated from the 3 x 3 neighbourhood of each cell using the same
+/// finite differences as the `ProfileCurvature` tool. The wetness index is based on the
+/// D-infinity (Tarboton, 1997) specific contributing area (SCA) and slope gradient, the latter
+/// of which is bounded below by 0.001 to keep the index finite in flat areas; the DEM should
+/// therefore be hydrologically conditioned, e.g. using `BreachDepressionsLeastCost`. The relative
+/// elevation percentile is measured within a circular neighbourhood of the given radius
+/// (`--radius`, in cells), which should be chosen to reflect the scale of the hillslopes in the
+/// landscape.
+///
+/// Streams are the cells whose D-infinity contributing area is at least the given threshold
+/// (`--stream_threshold`, in square metres), unless a streams raster (`--streams`), in which all
+/// non-zero, non-NoData cells are streams, is specified. Ridges are extracted in the same manner
+/// from the inverted DEM, i.e. they are the cells that would be channels if the landscape were
+/// turned upside down, using the same threshold. For DEMs in geographic coordinates, the grid
+/// resolution is converted to metres, and the elevations are assumed to be in metres.
+///
+/// # References
+/// Gallant, J. C., and Dowling, T. I. (2003). A multiresolution index of valley bottom flatness for
+/// mapping depositional areas. *Water Resources Research*, 39(12), 1347.
+///
+/// McBratney, A. B., Mendonça Santos, M. L., and Minasny, B. (2003). On digital soil mapping.
+/// *Geoderma*, 117(1-2), 3-52.
+///
+/// Tarboton, D. G. (1997). A new method for the determination of flow directions and upslope areas
+/// in grid digital elevation models. *Water Resources Research*, 33(2), 309-319.
+///
+/// # See Also
+/// `Slope`, `ProfileCurvature`, `PlanCurvature`, `WetnessIndex`, `ExtractValleyBottoms`,
+/// `ElevPercentile`
+pub struct SoilMappingCovariates {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SoilMappingCovariates {
+    pub fn new() -> SoilMappingCovariates {
+        // public constructor
+        let name = "SoilMappingCovariates".to_string();
+        let toolbox = "Agriculture/Soils".to_string();
+        let description =
+            "Derives a multi-band stack of terrain covariates for digital soil mapping from a DEM."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file of covariates (GeoTIFF or .wbr)."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Relative Position Radius (cells)".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description:
+                "Radius of the neighbourhood of the relative elevation percentile, in cells."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Stream Threshold (m^2)".to_owned(),
+            flags: vec!["--stream_threshold".to_owned()],
+            description: "Contributing area of the stream and ridge networks, in square metres."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100000.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File (optional)".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Optional input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=covariates.tif --radius=15 --stream_threshold=50000.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SoilMappingCovariates {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SoilMappingCovariates {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut streams_file = String::new();
+        let mut radius = 10isize;
+        let mut stream_threshold = 100000f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-streams" {
+                streams_file = value;
+            } else if flag_val == "-radius" {
+                radius = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as isize;
+            } else if flag_val == "-stream_threshold" {
+                stream_threshold = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !streams_file.is_empty() && !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if radius < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The relative position radius (--radius) must be at least one cell.",
+            ));
+        }
+        if stream_threshold <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The stream threshold (--stream_threshold) must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let streams = if !streams_file.is_empty() {
+            // make sure the inputs share the same grid and CRS
+            Some(align_raster_input(
+                &input,
+                Raster::new(&streams_file, "r")?,
+            )?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // the grid resolution, in metres
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let cell_size = (res_x + res_y) / 2.0;
+        let cell_area = res_x * res_y;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "grey.plt".to_string();
+        output.set_num_bands(COVARIATES.len());
+
+        // slope and curvatures
+        let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+        let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let mut n = [0f64; 8];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                for c in 0..8 {
+                    n[c] = input.get_value(row + d_y[c], col + d_x[c]);
+                    if n[c] == nodata {
+                        n[c] = z;
+                    }
+                }
+                let zx = (n[1] - n[5]) / (2f64 * res_x);
+                let zy = (n[7] - n[3]) / (2f64 * res_y);
+                let zxx = (n[1] - 2f64 * z + n[5]) / (res_x * res_x);
+                let zyy = (n[7] - 2f64 * z + n[3]) / (res_y * res_y);
+                let zxy = (-n[6] + n[0] + n[4] - n[2]) / (4f64 * res_x * res_y);
+                let zx2 = zx * zx;
+                let zy2 = zy * zy;
+                let p = zx2 + zy2;
+                let q = p + 1f64;
+                output.set_value_band(row, col, 0, p.sqrt().atan().to_degrees());
+                let (prof, plan) = if p > 0f64 {
+                    (
+                        ((zxx * zx2 + 2f64 * zxy * zx * zy + zyy * zy2) / (p * q.powf(1.5f64)))
+                            .to_degrees()
+                            * 100f64,
+                        ((zxx * zy2 - 2f64 * zxy * zx * zy + zyy * zx2) / p.powf(1.5f64))
+                            .to_degrees()
+                            * 100f64,
+                    )
+                } else {
+                    (0f64, 0f64)
+                };
+                output.set_value_band(row, col, 1, prof);
+                output.set_value_band(row, col, 2, plan);
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Slope and curvatures: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // wetness index and streams
+        if verbose {
+            println!("Calculating the wetness index...");
+        }
+        let (flow_dir, dinf_slope) = dinf_flow_directions(&input, res_x, res_y)?;
+        let area = dinf_contributing_area(&flow_dir, cell_area)?;
+        drop(flow_dir);
+        let mut is_stream: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let a = area.get_value(row, col);
+                if a == nodata {
+                    continue;
+                }
+                let tan_slope = dinf_slope.get_value(row, col).max(MIN_TAN_SLOPE);
+                output.set_value_band(row, col, 3, (a / cell_size / tan_slope).ln());
+                let stream = match streams {
+                    Some(ref s) => {
+                        let v = s.get_value(row, col);
+                        v != 0f64 && v != s.configs.nodata
+                    }
+                    None => a >= stream_threshold,
+                };
+                if stream {
+                    is_stream.set_value(row, col, 1u8);
+                }
+            }
+        }
+        drop(dinf_slope);
+        drop(area);
+
+        // ridges, i.e. the channels of the inverted DEM
+        if verbose {
+            println!("Extracting ridges...");
+        }
+        let mut inverted = input.clone();
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z != nodata {
+                    inverted.set_value(row, col, -z);
+                }
+            }
+        }
+        let (flow_dir, _) = dinf_flow_directions(&inverted, res_x, res_y)?;
+        drop(inverted);
+        let area = dinf_contributing_area(&flow_dir, cell_area)?;
+        drop(flow_dir);
+        let mut is_ridge: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let a = area.get_value(row, col);
+                if a != nodata && a >= stream_threshold {
+                    is_ridge.set_value(row, col, 1u8);
+                }
+            }
+        }
+        drop(area);
+
+        if verbose {
+            println!("Calculating distances to streams and ridges...");
+        }
+        let stream_dist = euclidean_distance(&is_stream, res_x, res_y)?;
+        let ridge_dist = euclidean_distance(&is_ridge, res_x, res_y)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                if input.get_value(row, col) != nodata {
+                    let d = stream_dist.get_value(row, col);
+                    if d.is_finite() {
+                        output.set_value_band(row, col, 6, d);
+                    }
+                    let d = ridge_dist.get_value(row, col);
+                    if d.is_finite() {
+                        output.set_value_band(row, col, 7, d);
+                    }
+                }
+            }
+        }
+        drop(stream_dist);
+        drop(ridge_dist);
+
+        // valley bottom flatness and relative position
+        if verbose {
+            println!("Calculating MRVBF...");
+        }
+        let z_data = Arc::new(input.get_data_as_array2d());
+        let mrvbf_data = mrvbf(&z_data, res_x, res_y, verbose)?;
+        if verbose {
+            println!("Calculating the relative elevation percentile...");
+        }
+        let percentile = elevation_percentile(&z_data, radius)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let v = mrvbf_data.get_value(row, col);
+                if v != nodata {
+                    output.set_value_band(row, col, 4, v);
+                }
+                let v = percentile.get_value(row, col);
+                if v != nodata {
+                    output.set_value_band(row, col, 5, v);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input DEM file: {}", input_file));
+        if !streams_file.is_empty() {
+            output.add_metadata_entry(format!("Input streams file: {}", streams_file));
+        }
+        output.add_metadata_entry(format!("Relative position radius: {}", radius));
+        output.add_metadata_entry(format!("Stream threshold: {}", stream_threshold));
+        for (band, covariate) in COVARIATES.iter().enumerate() {
+            output.add_metadata_entry(format!("Band {}: {}", band + 1, covariate));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Calculates the Euclidean distance from each cell to the nearest feature (non-zero) cell, using
+/// the two-pass algorithm of Shih and Wu (2004) as in the `EuclideanDistance` tool. Distances are
+/// infinite if there are no feature cells.
+fn euclidean_distance(
+    features: &Array2D<u8>,
+    res_x: f64,
+    res_y: f64,
+) -> Result<Array2D<f64>, Error> {
+    let rows = features.rows();
+    let columns = features.columns();
+    let outside = -1f64;
+    let mut dist: Array2D<f64> = Array2D::new(rows, columns, f64::INFINITY, outside)?;
+    let mut rx: Array2D<f64> = Array2D::new(rows, columns, 0f64, outside)?;
+    let mut ry: Array2D<f64> = Array2D::new(rows, columns, 0f64, outside)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if features.get_value(row, col) != 0u8 {
+                dist.set_value(row, col, 0f64);
+            }
+        }
+    }
+
+    let dx = [-1, -1, 0, 1, 1, 1, 0, -1];
+    let dy = [0, -1, -1, -1, 0, 1, 1, 1];
+    let gx = [1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0];
+    let gy = [0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0];
+    let mut update = |row: isize, col: isize, neighbours: &[usize]| {
+        let z = dist.get_value(row, col);
+        if z == 0f64 {
+            return;
+        }
+        let mut z_min = f64::INFINITY;
+        let mut which_cell = 0;
+        for &i in neighbours {
+            let (x, y) = (col + dx[i], row + dy[i]);
+            let z2 = dist.get_value(y, x);
+            if z2 != outside {
+                let h = match i {
+                    0 | 4 => 2.0 * rx.get_value(y, x) + 1.0,
+                    2 | 6 => 2.0 * ry.get_value(y, x) + 1.0,
+                    _ => 2.0 * (rx.get_value(y, x) + ry.get_value(y, x) + 1.0),
+                };
+                if z2 + h < z_min {
+                    z_min = z2 + h;
+                    which_cell = i;
+                }
+            }
+        }
+        if z_min < z {
+            dist.set_value(row, col, z_min);
+            let (x, y) = (col + dx[which_cell], row + dy[which_cell]);
+            rx.set_value(row, col, rx.get_value(y, x) + gx[which_cell]);
+            ry.set_value(row, col, ry.get_value(y, x) + gy[which_cell]);
+        }
+    };
+    for row in 0..rows {
+        for col in 0..columns {
+            update(row, col, &[0, 1, 2, 3]);
+        }
+    }
+    for row in (0..rows).rev() {
+        for col in (0..columns).rev() {
+            update(row, col, &[4, 5, 6, 7]);
+        }
+    }
+
+    // convert the squared cell distances to metres
+    for row in 0..rows {
+        for col in 0..columns {
+            let (x, y) = (
+                rx.get_value(row, col) * res_x,
+                ry.get_value(row, col) * res_y,
+            );
+            if dist.get_value(row, col).is_finite() {
+                dist.set_value(row, col, (x * x + y * y).sqrt());
+            }
+        }
+    }
+    Ok(dist)
+}
@@ -0,0 +1,646 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool estimates the bankfull hydraulic geometry of a channel network, i.e. the width, depth,
+/// cross-sectional area, and hydraulic radius of the channel at bankfull stage, from a high-resolution
+/// digital elevation model (`--dem`), e.g. a lidar DEM. The channels are specified by a vector stream
+/// network (`--streams`) of the POLYLINE ShapeType. Stations are placed along each stream line at a
+/// regular interval (`--spacing`), in map units, and a cross-section of a specified length
+/// (`--xs_length`) is extracted from the DEM perpendicular to the line at each station, with elevations
+/// sampled at the DEM's resolution by bilinear interpolation.
+///
+/// The thalweg of each cross-section is its lowest point within the central third of the section,
+/// such that the vector channel does not need to follow the channel precisely. The bankfull stage is
+/// then identified as the water surface elevation, between the thalweg and a maximum depth above it
+/// (`--max_depth`), at which the ratio of channel width to mean depth is minimized (Riley, 1972). Below
+/// bankfull stage, the width of flow increases slowly with stage; once the water surface reaches the
+/// top of the banks and spills onto the floodplain, width increases abruptly, which produces a minimum
+/// in the width-depth ratio. Flow at each stage is confined to the part of the cross-section that
+/// is connected to the thalweg. Stages at which the flow would reach the ends of the cross-section
+/// are not considered, and the cross-section should therefore be long enough to span the channel and
+/// its banks.
+///
+/// Lidar does not generally penetrate water and the DEM surface within a wetted channel is therefore
+/// the water surface rather than the channel bed. A raster of water surface elevations
+/// (`--water_surface`), e.g. interpolated from lidar water returns, can optionally be provided, in which
+/// case the bankfull stage is constrained to lie above the water surface at the thalweg, and the
+/// water surface elevation of each station is reported.
+///
+/// The output vector (`--output`) contains a point at the thalweg of each cross-section, with the
+/// attributes: FID; PARENT_ID, the FID of the stream line; STATION, the distance along the line; THALWEG,
+/// the thalweg elevation; WS_ELEV, the water surface elevation (if specified); BANKFULL, the bankfull stage
+/// elevation; WIDTH, the bankfull width; MAX_DEPTH, the bankfull stage less the thalweg elevation;
+/// MEAN_DEPTH, the cross-sectional area divided by width; AREA, the bankfull cross-sectional area;
+/// HYD_RADIUS, the area divided by the wetted perimeter; and WD_RATIO, the width-depth ratio. Stations
+/// at which no bankfull stage could be identified are not output. The bankfull cross-sections, between
+/// the left and right banks, can optionally be output as a vector of lines (`--out_sections`) with the
+/// same attributes.
+///
+/// The DEM and stream network must share a projected coordinate system, with elevations and horizontal
+/// distances in the same units.
+///
+/// # Reference
+/// Riley, S. J. (1972). A comparison of morphometric measures of bankfull. *Journal of Hydrology*, 17(1-2),
+/// 23-31.
+///
+/// # See Also
+/// `LongProfile`, `ExtractValleys`, `StreamSlopeContinuous`
+pub struct ChannelHydraulicGeometry {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ChannelHydraulicGeometry {
+    pub fn new() -> ChannelHydraulicGeometry {
+        // public constructor
+        let name = "ChannelHydraulicGeometry".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description =
+            "Estimates bankfull channel width, depth, and area along a vector stream network from DEM cross-sections.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-d".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Vector Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input vector stream network lines file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Water Surface File (optional)".to_owned(),
+            flags: vec!["--water_surface".to_owned()],
+            description: "Optional input raster of water surface elevations.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Stations File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file of cross-section stations.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Cross-Sections File (optional)".to_owned(),
+            flags: vec!["--out_sections".to_owned()],
+            description: "Optional output vector lines file of bankfull cross-sections.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Station Spacing".to_owned(),
+            flags: vec!["--spacing".to_owned()],
+            description: "Distance between cross-section stations along the streams, in map units."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cross-Section Length".to_owned(),
+            flags: vec!["--xs_length".to_owned()],
+            description: "Total length of each cross-section, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("50.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Bankfull Depth".to_owned(),
+            flags: vec!["--max_depth".to_owned()],
+            description:
+                "Maximum depth of the bankfull stage above the thalweg, in elevation units."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=lidar_dem.tif --streams=channels.shp -o=stations.shp --out_sections=sections.shp --spacing=20.0 --xs_length=80.0 --max_depth=3.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ChannelHydraulicGeometry {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ChannelHydraulicGeometry {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut dem_file = String::new();
+        let mut streams_file = String::new();
+        let mut ws_file = String::new();
+        let mut output_file = String::new();
+        let mut sections_file = String::new();
+        let mut spacing = 10f64;
+        let mut xs_length = 50f64;
+        let mut max_depth = 5f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-d" || flag_val == "-dem" {
+                dem_file = value;
+            } else if flag_val == "-streams" {
+                streams_file = value;
+            } else if flag_val == "-water_surface" {
+                ws_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-out_sections" {
+                sections_file = value;
+            } else if flag_val == "-spacing" {
+                spacing = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-xs_length" {
+                xs_length = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_depth" {
+                max_depth = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !ws_file.is_empty() && !ws_file.contains(&sep) && !ws_file.contains("/") {
+            ws_file = format!("{}{}", working_directory, ws_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !sections_file.is_empty()
+            && !sections_file.contains(&sep)
+            && !sections_file.contains("/")
+        {
+            sections_file = format!("{}{}", working_directory, sections_file);
+        }
+        if spacing <= 0f64 || xs_length <= 0f64 || max_depth <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The station spacing, cross-section length, and maximum depth must be positive.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let streams = Shapefile::read(&streams_file)?;
+        let water_surface = if !ws_file.is_empty() {
+            let water_surface = Raster::new(&ws_file, "r")?;
+            // the water surface is sampled at the thalweg coordinates and may have a different
+            // resolution, but it must share the CRS of the DEM
+            if water_surface.has_same_crs(&dem) {
+                Some(water_surface)
+            } else {
+                Some(align_raster_input(&dem, water_surface)?)
+            }
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        if streams.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input streams file must be of a POLYLINE base shape type.",
+            ));
+        }
+
+        let nodata = dem.configs.nodata;
+        let ds = dem.configs.resolution_x.min(dem.configs.resolution_y);
+        let half_samples = (xs_length / 2f64 / ds).ceil() as isize;
+        let num_samples = (2 * half_samples + 1) as usize;
+        // the thalweg is sought within the central third of the cross-section
+        let search = (half_samples / 3).max(1);
+        let sample_dem = |p: &Point2D| -> f64 {
+            let row = (dem.configs.north - p.y) / dem.configs.resolution_y - 0.5;
+            let col = (p.x - dem.configs.west) / dem.configs.resolution_x - 0.5;
+            dem.get_interpolated_value(row, col, InterpolationMethod::Bilinear)
+        };
+
+        let mut output = Shapefile::new(&output_file, ShapeType::Point)?;
+        output.projection = dem.configs.coordinate_ref_system_wkt.clone();
+        let mut sections = Shapefile::new(&sections_file, ShapeType::PolyLine)?;
+        sections.projection = dem.configs.coordinate_ref_system_wkt.clone();
+        for shp in [&mut output, &mut sections].iter_mut() {
+            shp.attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            shp.attributes.add_field(&AttributeField::new(
+                "PARENT_ID",
+                FieldDataType::Int,
+                10u8,
+                0u8,
+            ));
+            for name in &[
+                "STATION",
+                "THALWEG",
+                "WS_ELEV",
+                "BANKFULL",
+                "WIDTH",
+                "MAX_DEPTH",
+                "MEAN_DEPTH",
+                "AREA",
+                "HYD_RADIUS",
+                "WD_RATIO",
+            ] {
+                shp.attributes.add_field(&AttributeField::new(
+                    name,
+                    FieldDataType::Real,
+                    12u8,
+                    4u8,
+                ));
+            }
+        }
+
+        let mut fid = 1i32;
+        let mut z = vec![nodata; num_samples];
+        let mut points = vec![Point2D::new(0f64, 0f64); num_samples];
+        for record_num in 0..streams.num_records {
+            let record = streams.get_record(record_num);
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                let line = &record.points[part_start..part_end];
+                if line.len() < 2 {
+                    continue;
+                }
+                let mut distances = vec![0f64; line.len()];
+                for i in 1..line.len() {
+                    distances[i] = distances[i - 1] + line[i].distance(&line[i - 1]);
+                }
+                let length = distances[line.len() - 1];
+                let mut station = spacing / 2f64;
+                while station < length {
+                    // the local direction of the line, over a distance of one spacing
+                    let p1 = point_along(line, &distances, (station - spacing / 2f64).max(0f64));
+                    let p2 = point_along(line, &distances, (station + spacing / 2f64).min(length));
+                    let centre = point_along(line, &distances, station);
+                    let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+                    let d = (dx * dx + dy * dy).sqrt();
+                    if d == 0f64 {
+                        station += spacing;
+                        continue;
+                    }
+                    // the unit normal, pointing to the left of the line
+                    let (nx, ny) = (-dy / d, dx / d);
+                    for k in 0..num_samples {
+                        let offset = (k as isize - half_samples) as f64 * ds;
+                        points[k] = Point2D::new(centre.x + offset * nx, centre.y + offset * ny);
+                        z[k] = sample_dem(&points[k]);
+                    }
+
+                    // find the thalweg
+                    let mut thalweg = usize::max_value();
+                    let mut zt = f64::INFINITY;
+                    for k in (half_samples - search) as usize..=(half_samples + search) as usize {
+                        if z[k] != nodata && z[k] < zt {
+                            zt = z[k];
+                            thalweg = k;
+                        }
+                    }
+                    if thalweg == usize::max_value() {
+                        station += spacing;
+                        continue;
+                    }
+                    let ws = match &water_surface {
+                        Some(r) => {
+                            let row = (r.configs.north - points[thalweg].y)
+                                / r.configs.resolution_y
+                                - 0.5;
+                            let col =
+                                (points[thalweg].x - r.configs.west) / r.configs.resolution_x - 0.5;
+                            let v =
+                                r.get_interpolated_value(row, col, InterpolationMethod::Bilinear);
+                            if v != r.configs.nodata {
+                                Some(v)
+                            } else {
+                                None
+                            }
+                        }
+                        None => None,
+                    };
+
+                    if let Some(g) = bankfull_geometry(&z, nodata, ds, thalweg, ws, max_depth) {
+                        let atts = vec![
+                            FieldData::Int(fid),
+                            FieldData::Int(record_num as i32 + 1),
+                            FieldData::Real(station),
+                            FieldData::Real(zt),
+                            match ws {
+                                Some(v) => FieldData::Real(v),
+                                None => FieldData::Null,
+                            },
+                            FieldData::Real(g.stage),
+                            FieldData::Real(g.width),
+                            FieldData::Real(g.stage - zt),
+                            FieldData::Real(g.area / g.width),
+                            FieldData::Real(g.area),
+                            FieldData::Real(g.area / g.wetted_perimeter),
+                            FieldData::Real(g.width * g.width / g.area),
+                        ];
+                        let mut sfg = ShapefileGeometry::new(ShapeType::Point);
+                        sfg.add_point(points[thalweg]);
+                        output.add_record(sfg);
+                        output.attributes.add_record(atts.clone(), false);
+
+                        let left = -(half_samples as f64) * ds + g.left;
+                        let right = -(half_samples as f64) * ds + g.right;
+                        let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                        sfg.add_part(&[
+                            Point2D::new(centre.x + left * nx, centre.y + left * ny),
+                            Point2D::new(centre.x + right * nx, centre.y + right * ny),
+                        ]);
+                        sections.add_record(sfg);
+                        sections.attributes.add_record(atts, false);
+                        fid += 1;
+                    }
+                    station += spacing;
+                }
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / streams.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if !sections_file.is_empty() {
+            let _ = match sections.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Cross-sections file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The bankfull geometry of a cross-section. The bank positions are distances from the start of
+/// the cross-section.
+struct BankfullGeometry {
+    stage: f64,
+    width: f64,
+    area: f64,
+    wetted_perimeter: f64,
+    left: f64,
+    right: f64,
+}
+
+/// Returns the point at a distance along a line.
+fn point_along(line: &[Point2D], distances: &[f64], distance: f64) -> Point2D {
+    for i in 1..line.len() {
+        if distances[i] >= distance {
+            let segment = distances[i] - distances[i - 1];
+            let t = if segment > 0f64 {
+                (distance - distances[i - 1]) / segment
+            } else {
+                0f64
+            };
+            return Point2D::new(
+                line[i - 1].x + t * (line[i].x - line[i - 1].x),
+                line[i - 1].y + t * (line[i].y - line[i - 1].y),
+            );
+        }
+    }
+    line[line.len() - 1]
+}
+
+/// Identifies the bankfull stage of a cross-section of elevations, sampled at an interval of `ds`,
+/// as the stage of minimum width-depth ratio between the thalweg (or water surface) and `max_depth`
+/// above the thalweg. Returns `None` if there is no such stage at which flow is confined within
+/// the cross-section.
+fn bankfull_geometry(
+    z: &[f64],
+    nodata: f64,
+    ds: f64,
+    thalweg: usize,
+    water_surface: Option<f64>,
+    max_depth: f64,
+) -> Option<BankfullGeometry> {
+    let zt = z[thalweg];
+    let base = match water_surface {
+        Some(ws) if ws > zt => ws,
+        _ => zt,
+    };
+    let mut stages: Vec<f64> = z
+        .iter()
+        .filter(|&&v| v != nodata && v > base && v <= zt + max_depth)
+        .cloned()
+        .collect();
+    stages.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    stages.dedup();
+
+    let n = z.len();
+    let mut best: Option<BankfullGeometry> = None;
+    let mut best_ratio = f64::INFINITY;
+    for stage in stages {
+        // the extent of flow connected to the thalweg
+        let mut left = thalweg;
+        while left > 0 && z[left - 1] != nodata && z[left - 1] <= stage {
+            left -= 1;
+        }
+        let mut right = thalweg;
+        while right < n - 1 && z[right + 1] != nodata && z[right + 1] <= stage {
+            right += 1;
+        }
+        if left == 0 || right == n - 1 || z[left - 1] == nodata || z[right + 1] == nodata {
+            break; // flow is no longer confined within the cross-section
+        }
+
+        // the banks are interpolated between the last wet and the first dry samples
+        let left_bank = (left as f64 - (stage - z[left]) / (z[left - 1] - z[left])) * ds;
+        let right_bank = (right as f64 + (stage - z[right]) / (z[right + 1] - z[right])) * ds;
+        let width = right_bank - left_bank;
+        let mut area = 0f64;
+        let mut wetted_perimeter = 0f64;
+        for k in left..=right {
+            area += (stage - z[k]) * ds;
+            if k > left {
+                wetted_perimeter += (ds * ds + (z[k] - z[k - 1]).powi(2)).sqrt();
+            }
+        }
+        let edge_left = left as f64 * ds - left_bank;
+        let edge_right = right_bank - right as f64 * ds;
+        wetted_perimeter += (edge_left * edge_left + (stage - z[left]).powi(2)).sqrt();
+        wetted_perimeter += (edge_right * edge_right + (stage - z[right]).powi(2)).sqrt();
+        if area <= 0f64 || width <= 0f64 {
+            continue;
+        }
+        let ratio = width * width / area;
+        if ratio < best_ratio {
+            best_ratio = ratio;
+            best = Some(BankfullGeometry {
+                stage: stage,
+                width: width,
+                area: area,
+                wetted_perimeter: wetted_perimeter,
+                left: left_bank,
+                right: right_bank,
+            });
+        }
+    }
+    best
+}
@@ -0,0 +1,598 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the chi (&chi;) coordinate (Perron and Royden, 2013) of each grid cell in a
+/// raster stream network. Chi is an upstream integral of drainage area along the flow path from
+/// the outlet of the network:
+///
+/// > &chi; = &int; (*A*<sub>0</sub> / *A*(*x*))<sup>&theta;</sup> d*x*
+///
+/// where *A* is the upslope drainage area, *A*<sub>0</sub> is a reference drainage area
+/// (`--ref_area`), &theta; is the reference concavity index (`--theta`), and *x* is the distance
+/// upstream from the outlet. Chi transforms the horizontal coordinate of a river long profile such
+/// that a channel in steady state, obeying the stream-power incision law, plots as a straight line
+/// in chi-elevation space, with a slope equal to the normalized channel steepness index
+/// (*k<sub>sn</sub>*) when *A*<sub>0</sub> = 1. Chi maps are commonly used to identify divide
+/// migration, since the chi values on either side of a drainage divide should be equal when the
+/// divide is stable, and chi-elevation plots are used to identify knickpoints and transient
+/// channel segments (see `KnickpointDetection`).
+///
+/// The user must specify the names of a D8 pointer raster (`--d8_pntr`), a raster stream network
+/// (`--streams`), in which stream grid cells are denoted by all positive non-zero values, and a
+/// flow accumulation raster (`--flow_accum`). The accumulation type (`--accum_type`) specifies
+/// whether the flow accumulation raster contains the number of upslope grid cells ('cells'), as
+/// is the default output of `D8FlowAccumulation`, or the upslope catchment area ('catchment area').
+/// Chi is integrated upstream, using the trapezoidal rule, from each outlet of the stream network,
+/// i.e. each stream cell that does not flow into another stream cell, where it is set to zero.
+/// Chi values are therefore only comparable between drainage basins that share a common base level.
+///
+/// Background cells will be assigned the NoData value in the output image, unless the
+/// `--zero_background` parameter is used, in which case non-stream cells will be assigned zero
+/// values in the output.
+///
+/// By default, the pointer raster is assumed to use the clockwise indexing method used by WhiteboxTools.
+/// If the pointer file contains ESRI flow direction values instead, the `--esri_pntr` parameter must be specified.
+///
+/// # Reference
+/// Perron, J. T., and Royden, L. (2013). An integral approach to bedrock river profile analysis.
+/// *Earth Surface Processes and Landforms*, 38(6), 570-576.
+///
+/// # See Also
+/// `KnickpointDetection`, `DistanceToOutlet`, `D8FlowAccumulation`
+pub struct ChiIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ChiIndex {
+    pub fn new() -> ChiIndex {
+        // public constructor
+        let name = "ChiIndex".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description =
+            "Calculates the chi coordinate of each grid cell in a stream network.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Flow Accumulation File".to_owned(),
+            flags: vec!["--flow_accum".to_owned()],
+            description: "Input raster flow accumulation file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        push_chi_parameters(&mut parameters);
+
+        parameters.push(ToolParameter {
+            name: "Should a background value of zero be used?".to_owned(),
+            flags: vec!["--zero_background".to_owned()],
+            description: "Flag indicating whether a background value of zero should be used."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --d8_pntr=D8.tif --streams=streams.tif --flow_accum=accum.tif -o=chi.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --d8_pntr=D8.tif --streams=streams.tif --flow_accum=area.tif --accum_type='catchment area' -o=chi.tif --theta=0.5 --ref_area=1000000.0 --esri_pntr --zero_background", short_exe, name).replace("*", &sep);
+
+        ChiIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ChiIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut d8_file = String::new();
+        let mut streams_file = String::new();
+        let mut accum_file = String::new();
+        let mut output_file = String::new();
+        let mut options = ChiOptions::default();
+        let mut background_val = f64::NEG_INFINITY;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-d8_pntr" {
+                d8_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-streams" {
+                streams_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-flow_accum" {
+                accum_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-zero_background" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    background_val = 0f64;
+                }
+            } else {
+                options.parse_arg(&flag_val, &vec, &args, i);
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !d8_file.contains(&sep) && !d8_file.contains("/") {
+            d8_file = format!("{}{}", working_directory, d8_file);
+        }
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !accum_file.contains(&sep) && !accum_file.contains("/") {
+            accum_file = format!("{}{}", working_directory, accum_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let pntr = align_raster_input(&streams, Raster::new(&d8_file, "r")?)?;
+        let accum = align_raster_input(&streams, Raster::new(&accum_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = streams.configs.rows as isize;
+        let columns = streams.configs.columns as isize;
+        let nodata = streams.configs.nodata;
+        if background_val == f64::NEG_INFINITY {
+            background_val = nodata;
+        }
+
+        let (chi, _) = chi_transform(&pntr, &streams, &accum, &options)?;
+
+        let mut output = Raster::initialize_using_file(&output_file, &streams);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = nodata;
+        let chi_nodata = chi.nodata();
+        for row in 0..rows {
+            let mut data = vec![background_val; columns as usize];
+            for col in 0..columns {
+                let val = chi.get_value(row, col);
+                if val != chi_nodata {
+                    data[col as usize] = val;
+                } else if streams.get_value(row, col) == nodata {
+                    data[col as usize] = nodata;
+                }
+            }
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if background_val == 0.0f64 {
+            output.configs.palette = "spectrum_black_background.plt".to_string();
+        } else {
+            output.configs.palette = "spectrum.plt".to_string();
+        }
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input d8 pointer file: {}", d8_file));
+        output.add_metadata_entry(format!("Input streams file: {}", streams_file));
+        output.add_metadata_entry(format!("Input flow accumulation file: {}", accum_file));
+        output.add_metadata_entry(format!("Theta: {}", options.theta));
+        output.add_metadata_entry(format!("Reference area: {}", options.ref_area));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The parameters of the chi transformation that are shared by the chi-based tools.
+pub(super) struct ChiOptions {
+    pub theta: f64,
+    pub ref_area: f64,
+    pub accum_is_cells: bool,
+    pub esri_style: bool,
+}
+
+impl Default for ChiOptions {
+    fn default() -> ChiOptions {
+        ChiOptions {
+            theta: 0.45,
+            ref_area: 1.0,
+            accum_is_cells: true,
+            esri_style: false,
+        }
+    }
+}
+
+impl ChiOptions {
+    /// Parses one of the chi transformation flags, ignoring any other flags.
+    pub fn parse_arg(&mut self, flag_val: &str, vec: &[&str], args: &[String], i: usize) {
+        let keyval = vec.len() > 1;
+        if flag_val == "-theta" || flag_val == "-ref_area" {
+            let val = if keyval {
+                vec[1].to_string()
+            } else {
+                args[i + 1].to_string()
+            }
+            .parse::<f64>()
+            .expect(&format!("Error parsing {}", flag_val));
+            if flag_val == "-theta" {
+                self.theta = val;
+            } else {
+                self.ref_area = val;
+            }
+        } else if flag_val == "-accum_type" {
+            let val = if keyval {
+                vec[1].to_lowercase()
+            } else {
+                args[i + 1].to_lowercase()
+            };
+            self.accum_is_cells = val.contains("cells");
+        } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+            if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                self.esri_style = true;
+            }
+        }
+    }
+
+    /// Converts a flow accumulation value into a drainage area, in squared map units. Missing
+    /// accumulation values are treated as a single grid cell.
+    pub fn drainage_area(&self, accum: f64, accum_nodata: f64, cell_area: f64) -> f64 {
+        if accum == accum_nodata || accum <= 0f64 {
+            cell_area
+        } else if self.accum_is_cells {
+            accum * cell_area
+        } else {
+            accum
+        }
+    }
+}
+
+/// Adds the parameters of the chi transformation to a tool's parameter list.
+pub(super) fn push_chi_parameters(parameters: &mut Vec<ToolParameter>) {
+    parameters.push(ToolParameter {
+        name: "Flow Accumulation Type".to_owned(),
+        flags: vec!["--accum_type".to_owned()],
+        description: "Type of flow accumulation input; options are 'cells' and 'catchment area'."
+            .to_owned(),
+        parameter_type: ParameterType::OptionList(vec![
+            "cells".to_owned(),
+            "catchment area".to_owned(),
+        ]),
+        default_value: Some("cells".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Reference Concavity Index (Theta)".to_owned(),
+        flags: vec!["--theta".to_owned()],
+        description: "Reference concavity index (m/n ratio).".to_owned(),
+        parameter_type: ParameterType::Float,
+        default_value: Some("0.45".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Reference Drainage Area".to_owned(),
+        flags: vec!["--ref_area".to_owned()],
+        description: "Reference drainage area (A0), in squared map units.".to_owned(),
+        parameter_type: ParameterType::Float,
+        default_value: Some("1.0".to_owned()),
+        optional: true,
+    });
+
+    parameters.push(ToolParameter {
+        name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+        flags: vec!["--esri_pntr".to_owned()],
+        description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+        parameter_type: ParameterType::Boolean,
+        default_value: Some("false".to_owned()),
+        optional: true,
+    });
+}
+
+/// Calculates the chi coordinate and the flow distance to the outlet of each cell in a raster
+/// stream network. Both are integrated upstream from each outlet, i.e. each stream cell that does
+/// not flow into another stream cell. Non-stream cells are assigned the nodata value of the
+/// returned arrays.
+pub(super) fn chi_transform(
+    pntr: &Raster,
+    streams: &Raster,
+    accum: &Raster,
+    options: &ChiOptions,
+) -> Result<(Array2D<f64>, Array2D<f64>), Error> {
+    let rows = streams.configs.rows as isize;
+    let columns = streams.configs.columns as isize;
+
+    // the inputs are aligned with the streams by the calling tool (see align_raster_input)
+    for r in [pntr, accum].iter() {
+        if !r.has_same_crs(streams) || !r.is_grid_aligned_with(streams) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input files must share the same grid (rows, columns, resolution and extent) and coordinate reference system.",
+            ));
+        }
+    }
+
+    let (cell_size_x, cell_size_y) = cell_sizes(streams);
+    let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+    let cell_area = cell_size_x * cell_size_y;
+
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let inflowing_vals = if options.esri_style {
+        [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64]
+    } else {
+        [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64]
+    };
+    let grid_lengths = [
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+    ];
+    let pntr_matches = d8_pointer_matches(options.esri_style);
+    let pntr_nodata = pntr.configs.nodata;
+    let streams_nodata = streams.configs.nodata;
+    let accum_nodata = accum.configs.nodata;
+
+    let is_stream = |row: isize, col: isize| -> bool {
+        let z = streams.get_value(row, col);
+        z > 0f64 && z != streams_nodata
+    };
+    // the chi integrand, (A0 / A)^theta
+    let integrand = |row: isize, col: isize| -> f64 {
+        let area = options.drainage_area(accum.get_value(row, col), accum_nodata, cell_area);
+        (options.ref_area / area).powf(options.theta)
+    };
+
+    let nodata = -32768f64;
+    let mut chi: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut dist: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+
+    // find the outlets
+    let mut stack = vec![];
+    for row in 0..rows {
+        for col in 0..columns {
+            if is_stream(row, col) {
+                let dir = pntr.get_value(row, col);
+                if dir > 0f64 && dir != pntr_nodata && dir <= 128f64 {
+                    let c = pntr_matches[dir as usize];
+                    if c < 8 && is_stream(row + dy[c], col + dx[c]) {
+                        continue;
+                    }
+                }
+                chi.set_value(row, col, 0f64);
+                dist.set_value(row, col, 0f64);
+                stack.push((row, col));
+            }
+        }
+    }
+
+    // integrate upstream
+    while let Some((row, col)) = stack.pop() {
+        let chi_dn = chi.get_value(row, col);
+        let dist_dn = dist.get_value(row, col);
+        let f_dn = integrand(row, col);
+        for n in 0..8 {
+            let (row_n, col_n) = (row + dy[n], col + dx[n]);
+            if is_stream(row_n, col_n) && pntr.get_value(row_n, col_n) == inflowing_vals[n] {
+                let f = integrand(row_n, col_n);
+                chi.set_value(row_n, col_n, chi_dn + 0.5 * (f + f_dn) * grid_lengths[n]);
+                dist.set_value(row_n, col_n, dist_dn + grid_lengths[n]);
+                stack.push((row_n, col_n));
+            }
+        }
+    }
+
+    Ok((chi, dist))
+}
+
+/// Returns the grid cell sizes of a raster in the x and y directions. The sizes of cells in
+/// geographic coordinates are approximated in metres.
+pub(super) fn cell_sizes(raster: &Raster) -> (f64, f64) {
+    let mut cell_size_x = raster.configs.resolution_x;
+    let mut cell_size_y = raster.configs.resolution_y;
+    if raster.is_in_geographic_coordinates() {
+        let mid_lat = ((raster.configs.north + raster.configs.south) / 2.0).to_radians();
+        cell_size_x *= 111_320.0 * mid_lat.cos();
+        cell_size_y *= 111_320.0;
+    }
+    (cell_size_x, cell_size_y)
+}
+
+/// Maps D8 pointer values onto the cell offsets used by the chi-based tools. Unrecognized
+/// pointer values map onto 8.
+pub(super) fn d8_pointer_matches(esri_style: bool) -> [usize; 129] {
+    let mut pntr_matches: [usize; 129] = [8usize; 129];
+    if !esri_style {
+        // This maps Whitebox-style D8 pointer values
+        // onto the cell offsets in d_x and d_y.
+        pntr_matches[1] = 0usize;
+        pntr_matches[2] = 1usize;
+        pntr_matches[4] = 2usize;
+        pntr_matches[8] = 3usize;
+        pntr_matches[16] = 4usize;
+        pntr_matches[32] = 5usize;
+        pntr_matches[64] = 6usize;
+        pntr_matches[128] = 7usize;
+    } else {
+        // This maps Esri-style D8 pointer values
+        // onto the cell offsets in d_x and d_y.
+        pntr_matches[1] = 1usize;
+        pntr_matches[2] = 2usize;
+        pntr_matches[4] = 3usize;
+        pntr_matches[8] = 4usize;
+        pntr_matches[16] = 5usize;
+        pntr_matches[32] = 6usize;
+        pntr_matches[64] = 7usize;
+        pntr_matches[128] = 0usize;
+    }
+    pntr_matches
+}
@@ -241,7 +241,8 @@ impl WhiteboxTool for FarthestChannelHead {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -256,16 +257,6 @@ impl WhiteboxTool for FarthestChannelHead {
         let cell_size_y = streams.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let mut output = Raster::initialize_using_file(&output_file, &streams);
         output.configs.photometric_interp = PhotometricInterpretation::Continuous;
         output.configs.data_type = DataType::F32;
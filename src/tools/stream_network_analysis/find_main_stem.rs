@@ -249,7 +249,8 @@ impl WhiteboxTool for FindMainStem {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -264,16 +265,6 @@ impl WhiteboxTool for FindMainStem {
         let cell_size_y = streams.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let mut output = Raster::initialize_using_file(&output_file, &streams);
         let mut stack = Vec::with_capacity((rows * columns) as usize);
 
@@ -0,0 +1,634 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::chi_index::{
+    cell_sizes, chi_transform, d8_pointer_matches, push_chi_parameters, ChiOptions,
+};
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use crate::vector::*;
+use std::cmp::Ordering::Equal;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool identifies knickpoints, i.e. abrupt changes in channel steepness, along the channels
+/// of a raster stream network using chi-transformed long profiles (Perron and Royden, 2013). The
+/// stream network (`--streams`) is first decomposed into a set of profiles; starting with the
+/// channel head with the greatest chi value, each profile is traced downstream until it reaches
+/// the outlet or joins a previously traced profile. Each profile therefore represents a trunk
+/// stream or one of its tributaries. The elevation of each profile cell, taken from the input
+/// digital elevation model (`--dem`), is then plotted against its chi coordinate (see `ChiIndex`),
+/// in which space a channel with uniform steepness plots as a straight line.
+///
+/// Each chi-elevation profile is fit with a series of linear segments by recursively splitting it
+/// at the point of greatest vertical deviation from the straight line joining the segment ends,
+/// until all points lie within a vertical tolerance (`--tolerance`) of their segment's line, in
+/// DEM elevation units. No segment is allowed to contain fewer than a minimum number of grid cells
+/// (`--min_length`), which prevents short-wavelength noise in the DEM from being identified as
+/// knickpoints. The normalized channel steepness index (*k<sub>sn</sub>*) of each segment is
+/// estimated by least-squares regression of elevation on chi, multiplied by
+/// *A*<sub>0</sub><sup>&theta;</sup>, where *A*<sub>0</sub> is the reference drainage area
+/// (`--ref_area`) and &theta; is the reference concavity index (`--theta`).
+///
+/// The breaks between segments are output as a vector points file (`--output`) of knickpoints,
+/// with the attribute fields *FID*, *PROFILE*, *ELEV*, *CHI*, *DIST* (the flow distance to the
+/// outlet), *UP_AREA* (the upslope drainage area), *KSN_UP* and *KSN_DN* (the steepness indices of
+/// the segments upstream and downstream of the knickpoint). Knickpoints where *KSN_UP* is less than
+/// *KSN_DN* are slope-break knickpoints at the top of a steepened reach, commonly associated with
+/// an upstream-migrating wave of incision, while the reverse indicates the base of a steep reach,
+/// e.g. a resistant lithology. The chi-transformed long profiles can optionally be written to a
+/// CSV file (`--out_csv`), with one row per profile cell, including the steepness index of the
+/// segment that each cell belongs to, for plotting in other software.
+///
+/// The user must also specify the names of a D8 pointer raster (`--d8_pntr`) and a flow accumulation
+/// raster (`--flow_accum`), as with the `ChiIndex` tool. All input rasters must share the same
+/// grid. By default, the pointer raster is assumed to use the clockwise indexing method used by
+/// WhiteboxTools. If the pointer file contains ESRI flow direction values instead, the
+/// `--esri_pntr` parameter must be specified.
+///
+/// # Reference
+/// Perron, J. T., and Royden, L. (2013). An integral approach to bedrock river profile analysis.
+/// *Earth Surface Processes and Landforms*, 38(6), 570-576.
+///
+/// # See Also
+/// `ChiIndex`, `LongProfile`, `StreamSlopeContinuous`
+pub struct KnickpointDetection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl KnickpointDetection {
+    pub fn new() -> KnickpointDetection {
+        // public constructor
+        let name = "KnickpointDetection".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description =
+            "Identifies knickpoints in a stream network from segmented chi-elevation profiles."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Flow Accumulation File".to_owned(),
+            flags: vec!["--flow_accum".to_owned()],
+            description: "Input raster flow accumulation file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Knickpoints File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file of knickpoints.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Optional output CSV file of chi-transformed long profiles.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        push_chi_parameters(&mut parameters);
+
+        parameters.push(ToolParameter {
+            name: "Vertical Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Maximum vertical deviation of a profile from its fitted segments, in elevation units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Segment Length (cells)".to_owned(),
+            flags: vec!["--min_length".to_owned()],
+            description: "Minimum number of grid cells in a fitted segment.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --d8_pntr=D8.tif --streams=streams.tif --dem=dem.tif --flow_accum=accum.tif -o=knickpoints.shp --out_csv=chi_profiles.csv --theta=0.45 --tolerance=10.0 --min_length=15", short_exe, name).replace("*", &sep);
+
+        KnickpointDetection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for KnickpointDetection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut d8_file = String::new();
+        let mut streams_file = String::new();
+        let mut dem_file = String::new();
+        let mut accum_file = String::new();
+        let mut output_file = String::new();
+        let mut output_csv_file = String::new();
+        let mut options = ChiOptions::default();
+        let mut tolerance = 5f64;
+        let mut min_length = 10usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-d8_pntr" {
+                d8_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-streams" {
+                streams_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-i" || flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-flow_accum" {
+                accum_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_csv" {
+                output_csv_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-tolerance" {
+                tolerance = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-min_length" {
+                min_length = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else {
+                options.parse_arg(&flag_val, &vec, &args, i);
+            }
+        }
+
+        if min_length < 2 {
+            min_length = 2;
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !d8_file.contains(&sep) && !d8_file.contains("/") {
+            d8_file = format!("{}{}", working_directory, d8_file);
+        }
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !accum_file.contains(&sep) && !accum_file.contains("/") {
+            accum_file = format!("{}{}", working_directory, accum_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !output_csv_file.is_empty()
+            && !output_csv_file.contains(&sep)
+            && !output_csv_file.contains("/")
+        {
+            output_csv_file = format!("{}{}", working_directory, output_csv_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let pntr = align_raster_input(&streams, Raster::new(&d8_file, "r")?)?;
+        let dem = align_raster_input(&streams, Raster::new(&dem_file, "r")?)?;
+        let accum = align_raster_input(&streams, Raster::new(&accum_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = streams.configs.rows as isize;
+        let columns = streams.configs.columns as isize;
+        let dem_nodata = dem.configs.nodata;
+        let accum_nodata = accum.configs.nodata;
+        let pntr_nodata = pntr.configs.nodata;
+        let (cell_size_x, cell_size_y) = cell_sizes(&streams);
+        let cell_area = cell_size_x * cell_size_y;
+        let ksn_multiplier = options.ref_area.powf(options.theta);
+
+        if verbose {
+            println!("Calculating chi...")
+        };
+        let (chi, dist) = chi_transform(&pntr, &streams, &accum, &options)?;
+        let chi_nodata = chi.nodata();
+
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let inflowing_vals = if options.esri_style {
+            [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64]
+        } else {
+            [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64]
+        };
+        let pntr_matches = d8_pointer_matches(options.esri_style);
+
+        // find the channel heads, i.e. network cells without inflowing network cells
+        let mut heads = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                let chi_val = chi.get_value(row, col);
+                if chi_val != chi_nodata {
+                    let mut is_head = true;
+                    for n in 0..8 {
+                        if chi.get_value(row + dy[n], col + dx[n]) != chi_nodata
+                            && pntr.get_value(row + dy[n], col + dx[n]) == inflowing_vals[n]
+                        {
+                            is_head = false;
+                            break;
+                        }
+                    }
+                    if is_head {
+                        heads.push((chi_val, row, col));
+                    }
+                }
+            }
+        }
+        heads.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Equal));
+
+        let mut output = Shapefile::new(&output_file, ShapeType::Point)?;
+        output.projection = dem.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "PROFILE",
+            FieldDataType::Int,
+            10u8,
+            0u8,
+        ));
+        for name in &["ELEV", "CHI", "DIST", "UP_AREA", "KSN_UP", "KSN_DN"] {
+            output
+                .attributes
+                .add_field(&AttributeField::new(name, FieldDataType::Real, 12u8, 6u8));
+        }
+
+        let mut csv_lines = vec![];
+        let mut visited: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut fid = 1i32;
+        let num_heads = heads.len();
+        for h in 0..num_heads {
+            // trace the profile downstream, until it reaches the outlet or a traced profile
+            let (_, mut row, mut col) = heads[h];
+            let mut cells = vec![];
+            loop {
+                cells.push((row, col));
+                if visited.get_value(row, col) == 1u8 {
+                    break; // a confluence with a previously traced profile
+                }
+                visited.set_value(row, col, 1u8);
+                let dir = pntr.get_value(row, col);
+                if dir <= 0f64 || dir == pntr_nodata || dir > 128f64 {
+                    break;
+                }
+                let c = pntr_matches[dir as usize];
+                if c > 7 || chi.get_value(row + dy[c], col + dx[c]) == chi_nodata {
+                    break;
+                }
+                row += dy[c];
+                col += dx[c];
+            }
+            cells.retain(|&(row, col)| dem.get_value(row, col) != dem_nodata);
+            let profile_chi = cells
+                .iter()
+                .map(|&(row, col)| chi.get_value(row, col))
+                .collect::<Vec<f64>>();
+            let profile_z = cells
+                .iter()
+                .map(|&(row, col)| dem.get_value(row, col))
+                .collect::<Vec<f64>>();
+            let n = cells.len();
+            if n < 2 {
+                continue;
+            }
+
+            // fit the segments
+            let mut breaks = vec![0usize];
+            split_profile(
+                &profile_chi,
+                &profile_z,
+                0,
+                n - 1,
+                tolerance,
+                min_length,
+                &mut breaks,
+            );
+            breaks.push(n - 1);
+            let ksn = (0..breaks.len() - 1)
+                .map(|s| {
+                    ksn_multiplier
+                        * segment_slope(&profile_chi, &profile_z, breaks[s], breaks[s + 1])
+                })
+                .collect::<Vec<f64>>();
+
+            // the profile runs downstream, so the segment preceding a break lies upstream of it
+            for s in 1..breaks.len() - 1 {
+                let (row, col) = cells[breaks[s]];
+                let (x, y) = dem.get_cell_centre(row, col);
+                output.add_point_record(x, y);
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(h as i32 + 1),
+                        FieldData::Real(profile_z[breaks[s]]),
+                        FieldData::Real(profile_chi[breaks[s]]),
+                        FieldData::Real(dist.get_value(row, col)),
+                        FieldData::Real(options.drainage_area(
+                            accum.get_value(row, col),
+                            accum_nodata,
+                            cell_area,
+                        )),
+                        FieldData::Real(ksn[s - 1]),
+                        FieldData::Real(ksn[s]),
+                    ],
+                    false,
+                );
+                fid += 1;
+            }
+
+            if !output_csv_file.is_empty() {
+                let mut s = 0;
+                for i in 0..n {
+                    while s < ksn.len() - 1 && i > breaks[s + 1] {
+                        s += 1;
+                    }
+                    let (row, col) = cells[i];
+                    let (x, y) = dem.get_cell_centre(row, col);
+                    csv_lines.push(format!(
+                        "{},{},{},{},{},{},{},{}",
+                        h + 1,
+                        x,
+                        y,
+                        dist.get_value(row, col),
+                        profile_chi[i],
+                        profile_z[i],
+                        options.drainage_area(accum.get_value(row, col), accum_nodata, cell_area),
+                        ksn[s]
+                    ));
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * (h + 1) as f64 / num_heads as f64) as usize;
+                if progress != old_progress {
+                    println!("Fitting profiles: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of knickpoints: {}", fid - 1);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !output_csv_file.is_empty() {
+            let f = File::create(&output_csv_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("PROFILE,X,Y,DIST,CHI,ELEV,UP_AREA,KSN\n".as_bytes())?;
+            for line in &csv_lines {
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            writer.flush()?;
+            if verbose {
+                println!("CSV file written to {}", output_csv_file);
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Recursively splits the profile between the `start` and `end` points at the point of greatest
+/// vertical deviation from the chord joining them, until all points are within `tolerance` of
+/// a chord. Splits that would create segments of fewer than `min_length` cells are not made.
+/// The break points are appended to `breaks` in increasing order.
+fn split_profile(
+    chi: &[f64],
+    z: &[f64],
+    start: usize,
+    end: usize,
+    tolerance: f64,
+    min_length: usize,
+    breaks: &mut Vec<usize>,
+) {
+    if end < start + 2 * min_length {
+        return;
+    }
+    let slope = if chi[end] != chi[start] {
+        (z[end] - z[start]) / (chi[end] - chi[start])
+    } else {
+        0f64
+    };
+    let mut max_dev = 0f64;
+    let mut split = 0usize;
+    for i in start + min_length..=end - min_length {
+        let dev = (z[i] - (z[start] + slope * (chi[i] - chi[start]))).abs();
+        if dev > max_dev {
+            max_dev = dev;
+            split = i;
+        }
+    }
+    if max_dev > tolerance {
+        split_profile(chi, z, start, split, tolerance, min_length, breaks);
+        breaks.push(split);
+        split_profile(chi, z, split, end, tolerance, min_length, breaks);
+    }
+}
+
+/// Returns the least-squares slope of elevation on chi for the profile points from `start` to
+/// `end`, inclusive.
+fn segment_slope(chi: &[f64], z: &[f64], start: usize, end: usize) -> f64 {
+    let n = (end - start + 1) as f64;
+    let mean_chi = chi[start..=end].iter().sum::<f64>() / n;
+    let mean_z = z[start..=end].iter().sum::<f64>() / n;
+    let mut sxy = 0f64;
+    let mut sxx = 0f64;
+    for i in start..=end {
+        sxy += (chi[i] - mean_chi) * (z[i] - mean_z);
+        sxx += (chi[i] - mean_chi) * (chi[i] - mean_chi);
+    }
+    if sxx > 0f64 {
+        sxy / sxx
+    } else {
+        0f64
+    }
+}
@@ -257,35 +257,18 @@ impl WhiteboxTool for LongProfile {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
         if verbose {
             println!("Reading DEM data...")
         };
-        let dem = Raster::new(&dem_file, "r")?;
+        let dem = align_raster_input(&pntr, Raster::new(&dem_file, "r")?)?;
 
         let start = Instant::now();
 
         let rows = pntr.configs.rows as isize;
         let columns = pntr.configs.columns as isize;
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
-        // make sure the input files have the same size
-        if dem.configs.rows != pntr.configs.rows || dem.configs.columns != pntr.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let cell_size_x = pntr.configs.resolution_x;
         let cell_size_y = pntr.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
@@ -253,21 +253,14 @@ impl WhiteboxTool for LongProfileFromPoints {
         if verbose {
             println!("Reading DEM data...")
         };
-        let dem = Raster::new(&dem_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let dem = align_raster_input(&pntr, Raster::new(&dem_file, "r")?)?;
 
         let start = Instant::now();
 
         let rows = pntr.configs.rows as isize;
         let columns = pntr.configs.columns as isize;
 
-        // make sure the input files have the same size
-        if dem.configs.rows != pntr.configs.rows || dem.configs.columns != pntr.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         // make sure the input vector file is of points type
         if points.header.shape_type.base_shape_type() != ShapeType::Point {
             return Err(Error::new(
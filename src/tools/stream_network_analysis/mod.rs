@@ -1,4 +1,6 @@
 // private sub-module defined in other files
+mod channel_hydraulic_geometry;
+mod chi_index;
 mod dist_to_outlet;
 mod extract_streams;
 mod extract_valleys;
@@ -6,6 +8,7 @@ mod farthest_channel_head;
 mod find_main_stem;
 mod hack_order;
 mod horton_order;
+mod knickpoint_detection;
 mod long_profile;
 mod long_profile_from_points;
 mod raster_streams_to_vector;
@@ -17,12 +20,15 @@ mod stream_link_class;
 mod stream_link_id;
 mod stream_link_length;
 mod stream_link_slope;
+mod stream_network_to_vector;
 mod stream_slope_continuous;
 mod topological_stream_order;
 mod total_length_channels;
 mod tributary_id;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::channel_hydraulic_geometry::ChannelHydraulicGeometry;
+pub use self::chi_index::ChiIndex;
 pub use self::dist_to_outlet::DistanceToOutlet;
 pub use self::extract_streams::ExtractStreams;
 pub use self::extract_valleys::ExtractValleys;
@@ -30,6 +36,7 @@ pub use self::farthest_channel_head::FarthestChannelHead;
 pub use self::find_main_stem::FindMainStem;
 pub use self::hack_order::HackStreamOrder;
 pub use self::horton_order::HortonStreamOrder;
+pub use self::knickpoint_detection::KnickpointDetection;
 pub use self::long_profile::LongProfile;
 pub use self::long_profile_from_points::LongProfileFromPoints;
 pub use self::raster_streams_to_vector::RasterStreamsToVector;
@@ -41,6 +48,7 @@ pub use self::stream_link_class::StreamLinkClass;
 pub use self::stream_link_id::StreamLinkIdentifier;
 pub use self::stream_link_length::StreamLinkLength;
 pub use self::stream_link_slope::StreamLinkSlope;
+pub use self::stream_network_to_vector::StreamNetworkToVector;
 pub use self::stream_slope_continuous::StreamSlopeContinuous;
 pub use self::topological_stream_order::TopologicalStreamOrder;
 pub use self::total_length_channels::LengthOfUpstreamChannels;
@@ -221,7 +221,8 @@ impl WhiteboxTool for RasterStreamsToVector {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -231,16 +232,6 @@ impl WhiteboxTool for RasterStreamsToVector {
         let nodata = streams.configs.nodata;
         let pntr_nodata = pntr.configs.nodata;
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         // create output file
         let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
 
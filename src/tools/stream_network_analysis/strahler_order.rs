@@ -250,7 +250,8 @@ impl WhiteboxTool for StrahlerStreamOrder {
         if verbose {
             println!("Reading streams data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -262,16 +263,6 @@ impl WhiteboxTool for StrahlerStreamOrder {
             background_val = streams_nodata;
         }
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
         let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
 
@@ -261,11 +261,12 @@ impl WhiteboxTool for StreamLinkSlope {
         if verbose {
             println!("Reading link ID data...")
         };
-        let streams = Raster::new(&streams_file, "r")?;
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
         if verbose {
             println!("Reading DEM data...")
         };
-        let dem = Raster::new(&dem_file, "r")?;
+        let dem = align_raster_input(&pntr, Raster::new(&dem_file, "r")?)?;
 
         let start = Instant::now();
 
@@ -279,24 +280,6 @@ impl WhiteboxTool for StreamLinkSlope {
         let cell_size_y = streams.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if streams.configs.rows != dem.configs.rows
-            || streams.configs.columns != dem.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let max_id = streams.configs.maximum as usize + 1;
         let mut min_elev = vec![f64::INFINITY; max_id];
         let mut max_elev = vec![f64::NEG_INFINITY; max_id];
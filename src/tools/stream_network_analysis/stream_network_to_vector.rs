@@ -0,0 +1,687 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::structures::{Array2D, Point2D};
+use crate::tools::*;
+use crate::vector::*;
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a raster stream network into a topologically connected vector network of
+/// POLYLINE features, one for each stream link, with the link attributes that are commonly required
+/// by hydrological models. The user must specify the names of a streams raster (`--streams`), a D8
+/// pointer raster (`--d8_pntr`), a digital elevation model (`--dem`), and the output vector
+/// (`--output`). Stream cells are designated in the streams raster as all positive, non-zero
+/// values. The pointer raster is used to traverse the stream network and should only be created
+/// using the D8 algorithm (`D8Pointer`) from a DEM with all topographic depressions and flat areas
+/// removed. If the pointer file contains ESRI flow direction values, the `--esri_pntr` parameter
+/// must be specified.
+///
+/// The network is divided into links at each channel head and confluence. Each link ends at the
+/// centre of the confluence grid cell at which it joins its downstream link, which begins at the
+/// same point, such that the features of the output network are connected end-to-end. Unlike
+/// the `RasterStreamsToVector` tool, changes in the value of the streams raster do not divide
+/// links. The attribute table contains the following fields for each link:
+///
+/// | Field    | Description                                                                  |
+/// |----------|------------------------------------------------------------------------------|
+/// | FID      | The link identifier.                                                         |
+/// | DS_LINK  | The FID of the downstream link, or zero for outlet links.                    |
+/// | OUTLET   | The FID of the outlet link of the network containing the link.               |
+/// | STRAHLER | The Strahler stream order.                                                   |
+/// | SHREVE   | The Shreve stream magnitude.                                                 |
+/// | HORTON   | The Horton stream order.                                                     |
+/// | LENGTH   | The link length, in map units.                                               |
+/// | US_ELEV  | The elevation at the upstream end of the link.                               |
+/// | DS_ELEV  | The elevation at the downstream end of the link.                             |
+/// | SLOPE    | The average slope gradient of the link, in degrees.                          |
+/// | UP_AREA  | The area draining to the downstream-most cell of the link, in map units squared. |
+///
+/// For the Horton order, the main stem above each confluence is taken to be the upstream link of
+/// the highest Strahler order, with ties broken by the longest upstream flow path.
+///
+/// # See Also
+/// `RasterStreamsToVector`, `StrahlerStreamOrder`, `ShreveStreamMagnitude`, `HortonStreamOrder`, `StreamLinkSlope`
+pub struct StreamNetworkToVector {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl StreamNetworkToVector {
+    pub fn new() -> StreamNetworkToVector {
+        // public constructor
+        let name = "StreamNetworkToVector".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description =
+            "Converts a raster stream network into a connected vector network with link attributes."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_pntr".to_owned()],
+            description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --streams=streams.tif --d8_pntr=D8.tif --dem=dem.tif -o=output.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --streams=streams.tif --d8_pntr=D8.tif --dem=dem.tif -o=output.shp --esri_pntr", short_exe, name).replace("*", &sep);
+
+        StreamNetworkToVector {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for StreamNetworkToVector {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut streams_file = String::new();
+        let mut d8_file = String::new();
+        let mut dem_file = String::new();
+        let mut output_file = String::new();
+        let mut esri_style = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-d8_pntr" {
+                d8_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-streams" {
+                streams_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    esri_style = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !d8_file.contains(&sep) && !d8_file.contains("/") {
+            d8_file = format!("{}{}", working_directory, d8_file);
+        }
+        if !streams_file.contains(&sep) && !streams_file.contains("/") {
+            streams_file = format!("{}{}", working_directory, streams_file);
+        }
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading pointer data...")
+        };
+        let pntr = Raster::new(&d8_file, "r")?;
+        if verbose {
+            println!("Reading streams data...")
+        };
+        // make sure the inputs share the same grid and CRS
+        let streams = align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?;
+        if verbose {
+            println!("Reading DEM data...")
+        };
+        let dem = align_raster_input(&pntr, Raster::new(&dem_file, "r")?)?;
+
+        let start = Instant::now();
+
+        let rows = pntr.configs.rows as isize;
+        let columns = pntr.configs.columns as isize;
+        let nodata = streams.configs.nodata;
+        let pntr_nodata = pntr.configs.nodata;
+        let cell_area = pntr.configs.resolution_x * pntr.configs.resolution_y;
+
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let inflowing_vals = if esri_style {
+            [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64]
+        } else {
+            [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64]
+        };
+
+        // Create a mapping from the pointer values to cells offsets.
+        let mut pntr_matches: [usize; 129] = [999usize; 129];
+        if !esri_style {
+            // This maps Whitebox-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 0usize;
+            pntr_matches[2] = 1usize;
+            pntr_matches[4] = 2usize;
+            pntr_matches[8] = 3usize;
+            pntr_matches[16] = 4usize;
+            pntr_matches[32] = 5usize;
+            pntr_matches[64] = 6usize;
+            pntr_matches[128] = 7usize;
+        } else {
+            // This maps Esri-style D8 pointer values
+            // onto the cell offsets in dx and dy.
+            pntr_matches[1] = 1usize;
+            pntr_matches[2] = 2usize;
+            pntr_matches[4] = 3usize;
+            pntr_matches[8] = 4usize;
+            pntr_matches[16] = 5usize;
+            pntr_matches[32] = 6usize;
+            pntr_matches[64] = 7usize;
+            pntr_matches[128] = 0usize;
+        }
+
+        // returns the index of the flow direction of a cell, if it has one
+        let flow_dir = |row: isize, col: isize| -> Result<Option<usize>, Error> {
+            let z = pntr.get_value(row, col);
+            if z == pntr_nodata || z <= 0f64 {
+                return Ok(None);
+            }
+            let dir = z as usize;
+            if dir > 128 || pntr_matches[dir] == 999 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools.",
+                ));
+            }
+            Ok(Some(pntr_matches[dir]))
+        };
+        let is_stream = |row: isize, col: isize| -> bool {
+            let z = streams.get_value(row, col);
+            z > 0f64 && z != nodata
+        };
+
+        // Calculate the upslope contributing area of each cell, in grid cells, and the
+        // number of inflowing stream cells of each stream cell.
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut num_inflowing_streams: Array2D<i8> = Array2D::new(rows, columns, 0, 0)?;
+        let mut accum: Array2D<f64> = Array2D::new(rows, columns, 1f64, -1f64)?;
+        let mut queue = VecDeque::new();
+        let mut headwaters = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if pntr.get_value(row, col) != pntr_nodata {
+                    let mut count = 0i8;
+                    let mut stream_count = 0i8;
+                    for i in 0..8 {
+                        if pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i] {
+                            count += 1;
+                            if is_stream(row + dy[i], col + dx[i]) {
+                                stream_count += 1;
+                            }
+                        }
+                    }
+                    num_inflowing.set_value(row, col, count);
+                    num_inflowing_streams.set_value(row, col, stream_count);
+                    if count == 0 {
+                        queue.push_back((row, col));
+                    }
+                    if stream_count == 0 && is_stream(row, col) {
+                        headwaters.push((row, col));
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Finding headwaters: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        while let Some((row, col)) = queue.pop_front() {
+            if let Some(c) = flow_dir(row, col)? {
+                let (rn, cn) = (row + dy[c], col + dx[c]);
+                if num_inflowing.get_value(rn, cn) > 0 {
+                    let a = accum.get_value(row, col);
+                    accum.increment(rn, cn, a);
+                    num_inflowing.decrement(rn, cn, 1);
+                    if num_inflowing.get_value(rn, cn) == 0 {
+                        queue.push_back((rn, cn));
+                    }
+                }
+            }
+        }
+        drop(num_inflowing);
+
+        // Trace the stream links, from each channel head and confluence to the next
+        // confluence or outlet downstream.
+        let mut link_id: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
+        let mut links: Vec<StreamLink> = vec![];
+        let mut stack = headwaters;
+        let num_headwaters = stack.len();
+        while let Some((start_row, start_col)) = stack.pop() {
+            if link_id.get_value(start_row, start_col) != 0 {
+                continue;
+            }
+            let id = links.len() as i32 + 1;
+            let mut cells = vec![];
+            let mut ds_cell = None;
+            let (mut row, mut col) = (start_row, start_col);
+            loop {
+                link_id.set_value(row, col, id);
+                cells.push((row, col));
+                let c = match flow_dir(row, col)? {
+                    Some(c) => c,
+                    None => break,
+                };
+                let (rn, cn) = (row + dy[c], col + dx[c]);
+                if !is_stream(rn, cn) || link_id.get_value(rn, cn) == id {
+                    break;
+                }
+                if num_inflowing_streams.get_value(rn, cn) > 1 || link_id.get_value(rn, cn) != 0 {
+                    // it's a confluence
+                    ds_cell = Some((rn, cn));
+                    stack.push((rn, cn));
+                    break;
+                }
+                row = rn;
+                col = cn;
+            }
+
+            let last_cell = cells[cells.len() - 1];
+            let up_area = accum.get_value(last_cell.0, last_cell.1) * cell_area;
+            let us_elev = dem.get_value(start_row, start_col);
+            if let Some(cell) = ds_cell {
+                cells.push(cell);
+            }
+            let ds_elev = dem.get_value(cells[cells.len() - 1].0, cells[cells.len() - 1].1);
+
+            let mut points = Vec::with_capacity(cells.len());
+            let mut length = 0f64;
+            let mut prev_point = Point2D::new(0f64, 0f64);
+            for i in 0..cells.len() {
+                let p = Point2D::new(
+                    pntr.get_x_from_column(cells[i].1),
+                    pntr.get_y_from_row(cells[i].0),
+                );
+                if i > 0 {
+                    length += p.distance(&prev_point);
+                }
+                prev_point = p;
+                // only the vertices at which the flow direction changes are needed
+                if i == 0
+                    || i == cells.len() - 1
+                    || cells[i].0 - cells[i - 1].0 != cells[i + 1].0 - cells[i].0
+                    || cells[i].1 - cells[i - 1].1 != cells[i + 1].1 - cells[i].1
+                {
+                    points.push(p);
+                }
+            }
+
+            links.push(StreamLink {
+                points: points,
+                ds_cell: ds_cell,
+                ds_link: None,
+                length: length,
+                us_elev: us_elev,
+                ds_elev: ds_elev,
+                up_area: up_area,
+            });
+
+            if verbose {
+                progress = (100.0_f64 * links.len() as f64
+                    / (2 * num_headwaters).max(links.len()) as f64)
+                    as usize;
+                if progress != old_progress {
+                    println!("Tracing links: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Connect each link to its downstream link and remove degenerate single-cell links,
+        // which occur at outlets that are also confluences.
+        for i in 0..links.len() {
+            if let Some((row, col)) = links[i].ds_cell {
+                let ds = link_id.get_value(row, col) as usize - 1;
+                if links[ds].points.len() > 1 {
+                    links[i].ds_link = Some(ds);
+                }
+            }
+        }
+        let mut new_index = vec![0usize; links.len()];
+        let mut num_links = 0usize;
+        for i in 0..links.len() {
+            if links[i].points.len() > 1 {
+                new_index[i] = num_links;
+                num_links += 1;
+            }
+        }
+        links.retain(|l| l.points.len() > 1);
+        for link in links.iter_mut() {
+            link.ds_link = link.ds_link.map(|ds| new_index[ds]);
+        }
+
+        // Calculate the stream orders, visiting the links in topological order.
+        let mut upstream_links: Vec<Vec<usize>> = vec![vec![]; num_links];
+        for i in 0..num_links {
+            if let Some(ds) = links[i].ds_link {
+                upstream_links[ds].push(i);
+            }
+        }
+        let mut num_upstream: Vec<usize> = upstream_links.iter().map(|u| u.len()).collect();
+        let mut order: Vec<usize> = (0..num_links).filter(|i| num_upstream[*i] == 0).collect();
+        let mut i = 0;
+        while i < order.len() {
+            if let Some(ds) = links[order[i]].ds_link {
+                num_upstream[ds] -= 1;
+                if num_upstream[ds] == 0 {
+                    order.push(ds);
+                }
+            }
+            i += 1;
+        }
+
+        let mut strahler = vec![1i32; num_links];
+        let mut shreve = vec![1i32; num_links];
+        let mut max_path_length = vec![0f64; num_links];
+        for &link in &order {
+            max_path_length[link] = links[link].length;
+            if !upstream_links[link].is_empty() {
+                let max_order = upstream_links[link]
+                    .iter()
+                    .map(|u| strahler[*u])
+                    .max()
+                    .unwrap();
+                let num_max = upstream_links[link]
+                    .iter()
+                    .filter(|u| strahler[**u] == max_order)
+                    .count();
+                strahler[link] = if num_max > 1 {
+                    max_order + 1
+                } else {
+                    max_order
+                };
+                shreve[link] = upstream_links[link].iter().map(|u| shreve[*u]).sum();
+                max_path_length[link] += upstream_links[link]
+                    .iter()
+                    .map(|u| max_path_length[*u])
+                    .fold(0f64, f64::max);
+            }
+        }
+
+        // The Horton order and outlet are passed upstream, from the outlets, along the main stem.
+        let mut horton = strahler.clone();
+        let mut outlet: Vec<usize> = (0..num_links).collect();
+        for &link in order.iter().rev() {
+            if let Some(ds) = links[link].ds_link {
+                outlet[link] = outlet[ds];
+            }
+            let main_stem = upstream_links[link].iter().max_by(|a, b| {
+                strahler[**a].cmp(&strahler[**b]).then(
+                    max_path_length[**a]
+                        .partial_cmp(&max_path_length[**b])
+                        .unwrap(),
+                )
+            });
+            if let Some(&u) = main_stem {
+                horton[u] = horton[link];
+            }
+        }
+
+        // create output file
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+
+        // set the projection information
+        output.projection = streams.configs.coordinate_ref_system_wkt.clone();
+
+        // add the attributes
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "DS_LINK",
+            FieldDataType::Int,
+            7u8,
+            0u8,
+        ));
+        output
+            .attributes
+            .add_field(&AttributeField::new("OUTLET", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "STRAHLER",
+            FieldDataType::Int,
+            4u8,
+            0u8,
+        ));
+        output
+            .attributes
+            .add_field(&AttributeField::new("SHREVE", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("HORTON", FieldDataType::Int, 4u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "LENGTH",
+            FieldDataType::Real,
+            14u8,
+            3u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "US_ELEV",
+            FieldDataType::Real,
+            12u8,
+            3u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "DS_ELEV",
+            FieldDataType::Real,
+            12u8,
+            3u8,
+        ));
+        output
+            .attributes
+            .add_field(&AttributeField::new("SLOPE", FieldDataType::Real, 9u8, 4u8));
+        output.attributes.add_field(&AttributeField::new(
+            "UP_AREA",
+            FieldDataType::Real,
+            18u8,
+            3u8,
+        ));
+
+        for i in 0..num_links {
+            let link = &links[i];
+            let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+            sfg.add_part(&link.points);
+            output.add_record(sfg);
+            let slope = ((link.us_elev - link.ds_elev) / link.length)
+                .atan()
+                .to_degrees();
+            output.attributes.add_record(
+                vec![
+                    FieldData::Int(i as i32 + 1),
+                    FieldData::Int(link.ds_link.map_or(0, |ds| ds as i32 + 1)),
+                    FieldData::Int(outlet[i] as i32 + 1),
+                    FieldData::Int(strahler[i]),
+                    FieldData::Int(shreve[i]),
+                    FieldData::Int(horton[i]),
+                    FieldData::Real(link.length),
+                    FieldData::Real(link.us_elev),
+                    FieldData::Real(link.ds_elev),
+                    FieldData::Real(slope),
+                    FieldData::Real(link.up_area),
+                ],
+                false,
+            );
+
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_links as f64) as usize;
+                if progress != old_progress {
+                    println!("Creating features: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of links: {}", num_links);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+struct StreamLink {
+    points: Vec<Point2D>,
+    ds_cell: Option<(isize, isize)>,
+    ds_link: Option<usize>,
+    length: f64,
+    us_elev: f64,
+    ds_elev: f64,
+    up_area: f64,
+}
@@ -262,11 +262,12 @@ impl WhiteboxTool for StreamSlopeContinuous {
         if verbose {
             println!("Reading link ID data...")
         };
-        let streams = Arc::new(Raster::new(&streams_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let streams = Arc::new(align_raster_input(&pntr, Raster::new(&streams_file, "r")?)?);
         if verbose {
             println!("Reading DEM data...")
         };
-        let dem = Arc::new(Raster::new(&dem_file, "r")?);
+        let dem = Arc::new(align_raster_input(&pntr, Raster::new(&dem_file, "r")?)?);
 
         let start = Instant::now();
 
@@ -294,24 +295,6 @@ impl WhiteboxTool for StreamSlopeContinuous {
             }
         }
 
-        // make sure the input files have the same size
-        if streams.configs.rows != pntr.configs.rows
-            || streams.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if streams.configs.rows != dem.configs.rows
-            || streams.configs.columns != dem.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
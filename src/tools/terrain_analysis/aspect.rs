@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/06/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::{simd, thread};
 use num_cpus;
 use std::env;
 use std::f64;
@@ -15,7 +16,6 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool calculates slope aspect (i.e. slope orientation in degrees clockwise from north) for each grid cell
 /// in an input digital elevation model (DEM). The user must specify the name of the input
@@ -100,8 +100,8 @@ impl Aspect {
         });
 
         let sep: String = path::MAIN_SEPARATOR.to_string();
-        let p = format!("{}", env::current_dir().unwrap().display());
-        let e = format!("{}", env::current_exe().unwrap().display());
+        let p = format!("{}", env::current_dir().unwrap_or_default().display());
+        let e = format!("{}", env::current_exe().unwrap_or_default().display());
         let mut short_exe = e
             .replace(&p, "")
             .replace(".exe", "")
@@ -258,32 +258,29 @@ impl WhiteboxTool for Aspect {
             let input = input.clone();
             let tx = tx.clone();
             thread::spawn(move || {
-                let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-                let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-                let mut n: [f64; 8] = [0.0; 8];
-                let mut z: f64;
+                // the 3 x 3 finite-difference weights of the x and y gradients
+                let k = z_factor / eight_grid_res;
+                let weights_x = [-k, 0.0, k, -2.0 * k, 0.0, 2.0 * k, -k, 0.0, k];
+                let weights_y = [k, 2.0 * k, k, 0.0, 0.0, 0.0, -k, -2.0 * k, -k];
+                let mut gradient_x = vec![0f64; columns as usize];
+                let mut gradient_y = vec![0f64; columns as usize];
                 let (mut fx, mut fy): (f64, f64);
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let above = input.get_row_data(row - 1);
+                    let center = input.get_row_data(row);
+                    let below = input.get_row_data(row + 1);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_x, &mut gradient_x);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_y, &mut gradient_y);
                     let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            for c in 0..8 {
-                                n[c] = input[(row + dy[c], col + dx[c])];
-                                if n[c] != nodata {
-                                    n[c] = n[c] * z_factor;
-                                } else {
-                                    n[c] = z * z_factor;
-                                }
-                            }
-                            fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / eight_grid_res;
+                    for col in 0..columns as usize {
+                        if center[col] != nodata {
+                            fx = gradient_x[col];
                             if fx > 0f64 {
-                                fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2])
-                                    / eight_grid_res;
-                                data[col as usize] = 180f64 - ((fy / fx).atan()).to_degrees()
+                                fy = gradient_y[col];
+                                data[col] = 180f64 - ((fy / fx).atan()).to_degrees()
                                     + 90f64 * (fx / (fx).abs());
                             } else {
-                                data[col as usize] = -1f64;
+                                data[col] = -1f64;
                             }
                         }
                     }
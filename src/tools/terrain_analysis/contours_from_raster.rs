@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/02/2020
-Last Modified: 03/03/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -22,7 +22,26 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
-/// Converts a
+/// This tool can be used to derive a vector contour coverage from a raster surface, such as a digital
+/// elevation model (DEM). Contours are traced at a regular interval (`--interval`) starting from a base
+/// contour height (`--base`). The output vector (`--output`) contains a polyline for each contour and
+/// may be written either as a Shapefile or, if the output file has a *.geojson* or *.json* extension,
+/// as a GeoJSON file.
+///
+/// Contours that are traced directly from the raster grid have a jagged appearance, and are therefore
+/// smoothed using one of several methods (`--smoothing_method`). The default 'average' method applies a
+/// moving-average filter to the contour vertices, with a filter size (`--smooth`) specified in number
+/// of vertices. The 'bspline' method replaces each line with a cubic B-spline approximating the line's
+/// vertices, while the 'bezier' method fits a piecewise cubic Bezier curve that passes through each
+/// vertex. Smoothing can be turned off entirely using the 'none' option. The `--tolerance` parameter
+/// determines the level of line generalization, i.e. the removal of vertices along nearly straight
+/// sections of line, and is applied prior to B-spline and Bezier smoothing.
+///
+/// The output attribute table contains the contour height (*HEIGHT*). If an index contour interval
+/// (`--index_interval`) is specified, every *n*th contour is flagged as an index contour, for
+/// labelling and symbolizing purposes. For example, an index interval of 5 used with a 10 m contour
+/// interval will result in index contours every 50 m. The *INDEX* field is set to 1 for index contours
+/// and 0 otherwise, and the *LABEL* field contains the contour height label for index contours.
 ///
 /// # See Also
 /// `RasterToVectorPoints`
@@ -100,6 +119,30 @@ impl ContoursFromRaster {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Smoothing Method".to_owned(),
+            flags: vec!["--smoothing_method".to_owned()],
+            description: "Line smoothing method; options include 'average', 'bspline', 'bezier', and 'none'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "average".to_owned(),
+                "bspline".to_owned(),
+                "bezier".to_owned(),
+                "none".to_owned(),
+            ]),
+            default_value: Some("average".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Index Contour Interval".to_owned(),
+            flags: vec!["--index_interval".to_owned()],
+            description: "Optional index contour interval, in number of contours (e.g. 5).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -112,7 +155,8 @@ impl ContoursFromRaster {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=contours.shp --interval=100.0",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=contours.shp --interval=100.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input=DEM.tif -o=contours.geojson --interval=10.0 --smoothing_method=bspline --index_interval=5",
             short_exe, name
         )
         .replace("*", &sep);
@@ -174,6 +218,8 @@ impl WhiteboxTool for ContoursFromRaster {
         let mut base_contour = 0f64;
         let mut deflection_tolerance = 10f64;
         let mut filter_size = 11;
+        let mut smoothing_method = String::from("average");
+        let mut index_interval = 0i64;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -260,6 +306,36 @@ impl WhiteboxTool for ContoursFromRaster {
                 if filter_size > 21 {
                     filter_size = 21;
                 }
+            } else if flag_val == "-smoothing_method" {
+                smoothing_method = if keyval {
+                    vec[1].to_string().to_lowercase()
+                } else {
+                    args[i + 1].to_string().to_lowercase()
+                };
+                if smoothing_method.contains("bs") || smoothing_method.contains("spline") {
+                    smoothing_method = "bspline".to_string();
+                } else if smoothing_method.contains("bez") {
+                    smoothing_method = "bezier".to_string();
+                } else if smoothing_method.contains("none") {
+                    smoothing_method = "none".to_string();
+                } else {
+                    smoothing_method = "average".to_string();
+                }
+            } else if flag_val == "-index_interval" {
+                index_interval = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<i64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<i64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+                if index_interval < 0 {
+                    index_interval = 0;
+                }
             }
         }
 
@@ -322,6 +398,27 @@ impl WhiteboxTool for ContoursFromRaster {
             12u8,
             5u8,
         ));
+        output
+            .attributes
+            .add_field(&AttributeField::new("INDEX", FieldDataType::Int, 1u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("LABEL", FieldDataType::Text, 20u8, 0u8));
+
+        let contour_attributes = |fid: i32, z: f64| -> Vec<FieldData> {
+            let height = base_contour + z * contour_interval;
+            let is_index = index_interval > 0 && (z as i64).rem_euclid(index_interval) == 0;
+            vec![
+                FieldData::Int(fid),
+                FieldData::Real(height),
+                FieldData::Int(is_index as i32),
+                FieldData::Text(if is_index {
+                    format!("{}", (height * 1.0e6).round() / 1.0e6)
+                } else {
+                    String::new()
+                }),
+            ]
+        };
 
         let dx = [0, 1, 0, -1, 1, 1, -1, -1];
         let dy = [-1, 0, 1, 0, -1, 1, 1, -1];
@@ -611,7 +708,7 @@ impl WhiteboxTool for ContoursFromRaster {
 
                     if points.len() > 1 {
                         // Smooth the points
-                        if points.len() > filter_size {
+                        if smoothing_method == "average" && points.len() > filter_size {
                             for a in 0..points.len() {
                                 x = 0f64;
                                 y = 0f64;
@@ -653,6 +750,10 @@ impl WhiteboxTool for ContoursFromRaster {
                             }
                         }
 
+                        if smoothing_method == "bspline" || smoothing_method == "bezier" {
+                            points = edge_midpoints(&points, false);
+                        }
+
                         if deflection_tolerance > 0f64 {
                             for a in (1..points.len() - 1).rev() {
                                 p1 = points[a - 1];
@@ -666,16 +767,18 @@ impl WhiteboxTool for ContoursFromRaster {
                             }
                         }
 
+                        if smoothing_method == "bspline" {
+                            points = bspline_smooth(&points, false);
+                        } else if smoothing_method == "bezier" {
+                            points = bezier_smooth(&points, false);
+                        }
+
                         let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                         sfg.add_part(&points);
                         output.add_record(sfg);
-                        output.attributes.add_record(
-                            vec![
-                                FieldData::Int(fid as i32 + 1),
-                                FieldData::Real(base_contour + z * contour_interval),
-                            ],
-                            false,
-                        );
+                        output
+                            .attributes
+                            .add_record(contour_attributes(fid as i32 + 1, z), false);
                         fid += 1;
                     }
                 }
@@ -786,7 +889,7 @@ impl WhiteboxTool for ContoursFromRaster {
 
                 num_line_points = points.len();
                 if num_line_points > 1 {
-                    if points.len() > filter_size {
+                    if smoothing_method == "average" && points.len() > filter_size {
                         for a in 0..num_line_points {
                             x = 0f64;
                             y = 0f64;
@@ -836,6 +939,10 @@ impl WhiteboxTool for ContoursFromRaster {
                         points[num_line_points - 1].y = points[0].y;
                     }
 
+                    if smoothing_method == "bspline" || smoothing_method == "bezier" {
+                        points = edge_midpoints(&points, true);
+                    }
+
                     if deflection_tolerance > 0f64 {
                         for a in (1..points.len() - 1).rev() {
                             p1 = points[a - 1];
@@ -849,6 +956,12 @@ impl WhiteboxTool for ContoursFromRaster {
                         }
                     }
 
+                    if smoothing_method == "bspline" {
+                        points = bspline_smooth(&points, true);
+                    } else if smoothing_method == "bezier" {
+                        points = bezier_smooth(&points, true);
+                    }
+
                     // make sure the line is big enough to warrant writing to file.
                     let mut min_x = f64::MAX;
                     let mut max_x = f64::MIN;
@@ -873,13 +986,9 @@ impl WhiteboxTool for ContoursFromRaster {
                         let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                         sfg.add_part(&points);
                         output.add_record(sfg);
-                        output.attributes.add_record(
-                            vec![
-                                FieldData::Int(fid as i32 + 1),
-                                FieldData::Real(base_contour + z * contour_interval),
-                            ],
-                            false,
-                        );
+                        output
+                            .attributes
+                            .add_record(contour_attributes(fid as i32 + 1, z), false);
                         fid += 1;
                     }
                 }
@@ -901,7 +1010,12 @@ impl WhiteboxTool for ContoursFromRaster {
         if verbose {
             println!("Saving data...")
         };
-        let _ = match output.write() {
+        let result = if is_geojson_file(&output_file) {
+            write_geojson(&output, &output_file)
+        } else {
+            output.write()
+        };
+        let _ = match result {
             Ok(_) => {
                 if verbose {
                     println!("Output file written")
@@ -957,3 +1071,147 @@ pub fn path_deflection(previous: Point2D, current: Point2D, next: Point2D) -> f6
     let p2 = next - current;
     ((p1 * p2) / (p1.magnitude() * p2.magnitude())).abs()
 }
+
+/// The number of output vertices per span of a smoothed B-spline or Bezier curve.
+const SPLINE_SAMPLES: usize = 4;
+
+/// Reduces a traced contour to the mid-points of the cell edges that it crosses, removing
+/// the stair-stepped cell corners, for use as spline control points. Open lines retain
+/// their end points.
+fn edge_midpoints(points: &[Point2D], closed: bool) -> Vec<Point2D> {
+    let mut pts: Vec<Point2D> = Vec::with_capacity(points.len());
+    for p in points {
+        if pts.is_empty() || pts[pts.len() - 1].distance(p) > EPSILON * 10f64 {
+            pts.push(*p);
+        }
+    }
+    // traced vertices alternate between cell corners and edge mid-points
+    let mut ret: Vec<Point2D> = pts.iter().skip(1).step_by(2).copied().collect();
+    if ret.len() < 2 {
+        return points.to_vec();
+    }
+    if closed {
+        ret.push(ret[0]);
+    } else {
+        ret.insert(0, pts[0]);
+        if pts.len() % 2 == 0 {
+            // the last vertex is a mid-point and is already included
+        } else {
+            ret.push(pts[pts.len() - 1]);
+        }
+    }
+    ret
+}
+
+/// Returns the line's vertices for a closed line, without the duplicated end point.
+fn closed_vertices(points: &[Point2D]) -> Vec<Point2D> {
+    let n = points.len();
+    if n > 1 && points[0].distance(&points[n - 1]) < EPSILON * 10f64 {
+        points[0..n - 1].to_vec()
+    } else {
+        points.to_vec()
+    }
+}
+
+/// Smooths a line by replacing it with a uniform cubic B-spline, using the line's
+/// vertices as control points. The end points of open lines are preserved.
+fn bspline_smooth(points: &[Point2D], closed: bool) -> Vec<Point2D> {
+    let ctrl = if closed {
+        closed_vertices(points)
+    } else {
+        points.to_vec()
+    };
+    let n = ctrl.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let get_ctrl = |i: isize| -> Point2D {
+        if closed {
+            ctrl[i.rem_euclid(n as isize) as usize]
+        } else {
+            ctrl[i.max(0).min(n as isize - 1) as usize]
+        }
+    };
+    // open lines are clamped by repeating the end points
+    let (first_span, last_span) = if closed {
+        (0isize, n as isize)
+    } else {
+        (-2isize, n as isize - 1)
+    };
+    let mut ret = vec![];
+    for i in first_span..last_span {
+        let (p0, p1, p2, p3) = (get_ctrl(i), get_ctrl(i + 1), get_ctrl(i + 2), get_ctrl(i + 3));
+        for s in 0..SPLINE_SAMPLES {
+            let t = s as f64 / SPLINE_SAMPLES as f64;
+            let b0 = (1f64 - t).powi(3) / 6f64;
+            let b1 = (3f64 * t.powi(3) - 6f64 * t * t + 4f64) / 6f64;
+            let b2 = (-3f64 * t.powi(3) + 3f64 * t * t + 3f64 * t + 1f64) / 6f64;
+            let b3 = t.powi(3) / 6f64;
+            ret.push(Point2D::new(
+                b0 * p0.x + b1 * p1.x + b2 * p2.x + b3 * p3.x,
+                b0 * p0.y + b1 * p1.y + b2 * p2.y + b3 * p3.y,
+            ));
+        }
+    }
+    if closed {
+        ret.push(ret[0]);
+    } else {
+        ret.push(ctrl[n - 1]);
+    }
+    ret
+}
+
+/// Smooths a line by fitting a piecewise cubic Bezier curve that passes through each of
+/// the line's vertices. Control points are derived from the Catmull-Rom tangents.
+fn bezier_smooth(points: &[Point2D], closed: bool) -> Vec<Point2D> {
+    let pts = if closed {
+        closed_vertices(points)
+    } else {
+        points.to_vec()
+    };
+    let n = pts.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let get_point = |i: isize| -> Point2D {
+        if closed {
+            pts[i.rem_euclid(n as isize) as usize]
+        } else {
+            pts[i.max(0).min(n as isize - 1) as usize]
+        }
+    };
+    let num_spans = if closed { n } else { n - 1 };
+    let mut ret = vec![];
+    for i in 0..num_spans as isize {
+        let p0 = get_point(i);
+        let p3 = get_point(i + 1);
+        let prev = get_point(i - 1);
+        let next = get_point(i + 2);
+        let c1 = Point2D::new(
+            p0.x + (p3.x - prev.x) / 6f64,
+            p0.y + (p3.y - prev.y) / 6f64,
+        );
+        let c2 = Point2D::new(
+            p3.x - (next.x - p0.x) / 6f64,
+            p3.y - (next.y - p0.y) / 6f64,
+        );
+        for s in 0..SPLINE_SAMPLES {
+            let t = s as f64 / SPLINE_SAMPLES as f64;
+            let mt = 1f64 - t;
+            let b0 = mt * mt * mt;
+            let b1 = 3f64 * mt * mt * t;
+            let b2 = 3f64 * mt * t * t;
+            let b3 = t * t * t;
+            ret.push(Point2D::new(
+                b0 * p0.x + b1 * c1.x + b2 * c2.x + b3 * p3.x,
+                b0 * p0.y + b1 * c1.y + b2 * c2.y + b3 * p3.y,
+            ));
+        }
+    }
+    if closed {
+        ret.push(ret[0]);
+    } else {
+        ret.push(pts[n - 1]);
+    }
+    ret
+}
@@ -0,0 +1,713 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::na::{DMatrix, DVector};
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::tools::*;
+use num_cpus;
+use std::cmp::Ordering::Equal;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool co-registers a digital elevation model (DEM; `--input`) to an overlapping reference DEM
+/// (`--reference`), estimating and removing the horizontal and vertical shifts between them using the method of
+/// Nuth and Kääb (2011). Misregistration between DEMs, even by a fraction of a grid cell, produces elevation
+/// differences that are correlated with terrain slope and aspect, which bias the measurement of elevation
+/// change. Co-registration is therefore an essential step before DEM differencing and change detection.
+///
+/// A horizontal shift of a DEM produces elevation differences, *dh*, with the reference that vary with the
+/// slope, *α*, and aspect, *ψ*, of the terrain as:
+///
+/// > *dh* / tan(*α*) = *Δx* sin(*ψ*) + *Δy* cos(*ψ*) + *c*
+///
+/// where *Δx* and *Δy* are the eastward and northward components of the shift. The tool fits this relation to the
+/// elevation differences at each reference grid cell by least squares and shifts the input DEM by the fitted
+/// components. Because the relation is a linear approximation, the fit is repeated with the shifted DEM until
+/// the magnitude of the shift increment falls below a tolerance (`--tolerance`; by default, 1% of the reference
+/// grid resolution) or a maximum number of iterations (`--max_iterations`) is reached. The vertical shift is the
+/// median elevation difference after the horizontal shift is applied.
+///
+/// Only grid cells with a reference slope of at least `--min_slope` degrees (3 by default) are used to estimate
+/// the horizontal shift, since the elevation differences of flat terrain are insensitive to horizontal shifts and
+/// dividing by their small slope amplifies noise. Elevation differences more than three normalized median absolute
+/// deviations (NMAD) from the median are excluded as outliers. Areas of elevation change (e.g. glaciers, landslides,
+/// or vegetation), which would otherwise bias the estimates, can be excluded using an optional stable terrain mask
+/// (`--mask`), a raster with the same dimensions as the reference DEM in which cells with NoData or zero values are
+/// excluded.
+///
+/// The output DEM (`--output`) is the input DEM resampled, using bilinear interpolation, on its own grid after the
+/// horizontal shift is applied, and with the vertical shift subtracted. The HTML report (`--report`) describes
+/// the convergence of the method, listing for each iteration the cumulative shift and the median, NMAD, and root
+/// mean square of the elevation differences. The DEMs may have different resolutions and extents, but must share
+/// a projected coordinate system with vertical units that are the same as the horizontal units.
+///
+/// # Reference
+/// Nuth, C., and Kääb, A. (2011). Co-registration and bias corrections of satellite elevation data sets for
+/// quantifying glacier thickness change. The Cryosphere, 5(1), 271-290.
+///
+/// # See Also
+/// `Slope`, `Aspect`, `Resample`, `Subtract`
+pub struct CoregisterDems {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CoregisterDems {
+    pub fn new() -> CoregisterDems {
+        // public constructor
+        let name = "CoregisterDems".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Co-registers a DEM to a reference DEM by removing horizontal and vertical shifts (Nuth and Kääb, 2011)."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster DEM file to be co-registered.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference DEM File".to_owned(),
+            flags: vec!["--reference".to_owned()],
+            description: "Input reference raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Stable Terrain Mask File (optional)".to_owned(),
+            flags: vec!["--mask".to_owned()],
+            description:
+                "Optional stable terrain mask raster; NoData and zero-valued cells are excluded."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output co-registered raster DEM file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Report File".to_owned(),
+            flags: vec!["--report".to_owned()],
+            description: "Output HTML convergence report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Slope (degrees)".to_owned(),
+            flags: vec!["--min_slope".to_owned()],
+            description: "Minimum reference slope, in degrees, of cells used to estimate the horizontal shift."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("3.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Iterations".to_owned(),
+            flags: vec!["--max_iterations".to_owned()],
+            description: "Maximum number of iterations.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Convergence Tolerance (optional)".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Shift increment, in map units, below which the method has converged."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem_2019.tif --reference=dem_2010.tif --mask=stable.tif -o=dem_2019_coreg.tif --report=coreg.html --min_slope=5.0",
+                            short_exe, name).replace("*", &sep);
+
+        CoregisterDems {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CoregisterDems {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut reference_file = String::new();
+        let mut mask_file = String::new();
+        let mut output_file = String::new();
+        let mut report_file = String::new();
+        let mut min_slope = 3f64;
+        let mut max_iterations = 10usize;
+        let mut tolerance = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = val;
+            } else if flag_val == "-reference" {
+                reference_file = val;
+            } else if flag_val == "-mask" {
+                mask_file = val;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-report" {
+                report_file = val;
+            } else if flag_val == "-min_slope" {
+                min_slope = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_iterations" {
+                max_iterations = val
+                    .parse::<f32>()
+                    .expect(&format!("Error parsing {}", flag_val))
+                    as usize;
+            } else if flag_val == "-tolerance" {
+                tolerance = val
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !reference_file.contains(&sep) && !reference_file.contains("/") {
+            reference_file = format!("{}{}", working_directory, reference_file);
+        }
+        if !mask_file.trim().is_empty() && !mask_file.contains(&sep) && !mask_file.contains("/") {
+            mask_file = format!("{}{}", working_directory, mask_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if report_file.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output report file (--report) must be specified.",
+            ));
+        }
+        if !report_file.contains(&sep) && !report_file.contains("/") {
+            report_file = format!("{}{}", working_directory, report_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+        let reference = Arc::new(Raster::new(&reference_file, "r")?);
+        let mask = if !mask_file.trim().is_empty() {
+            // make sure the mask shares the grid and CRS of the reference DEM
+            let mask = align_raster_input(&reference, Raster::new(&mask_file, "r")?)?;
+            Some(Arc::new(mask))
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        if tolerance <= 0f64 {
+            tolerance = 0.01
+                * reference
+                    .configs
+                    .resolution_x
+                    .min(reference.configs.resolution_y);
+        }
+        let min_tan_slope = min_slope.max(0f64).to_radians().tan();
+        if max_iterations < 1 {
+            max_iterations = 1;
+        }
+
+        // Each iteration samples the shifted DEM at the reference cell centres, measures
+        // the elevation differences, and fits the slope/aspect relation to estimate the
+        // increment to the horizontal shift.
+        let mut shift_x = 0f64;
+        let mut shift_y = 0f64;
+        let mut history: Vec<IterationStats> = vec![];
+        let mut converged = false;
+        for iteration in 0..=max_iterations {
+            let samples = sample_differences(
+                input.clone(),
+                reference.clone(),
+                mask.clone(),
+                shift_x,
+                shift_y,
+            );
+            if samples.len() < 3 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The DEMs do not overlap sufficiently to be co-registered.",
+                ));
+            }
+            let mut dh: Vec<f64> = samples.iter().map(|s| s.dh).collect();
+            let median_dh = median(&mut dh);
+            let mut abs_dev: Vec<f64> = dh.iter().map(|v| (v - median_dh).abs()).collect();
+            let nmad = 1.4826 * median(&mut abs_dev);
+            let rmse = (dh.iter().map(|v| v * v).sum::<f64>() / dh.len() as f64).sqrt();
+            history.push(IterationStats {
+                shift_x: shift_x,
+                shift_y: shift_y,
+                median: median_dh,
+                nmad: nmad,
+                rmse: rmse,
+                num_cells: dh.len(),
+            });
+            if verbose {
+                println!(
+                    "Iteration {}: shift = ({:.4}, {:.4}), median dh = {:.4}, NMAD = {:.4}",
+                    iteration, shift_x, shift_y, median_dh, nmad
+                );
+            }
+            if converged || iteration == max_iterations {
+                break;
+            }
+
+            // least-squares fit of (dh - median) / tan(slope) = dx sin(aspect) + dy cos(aspect) + c
+            let max_dev = 3f64 * nmad;
+            let mut ata = DMatrix::from_element(3, 3, 0f64);
+            let mut atb = DVector::from_element(3, 0f64);
+            let mut n = 0usize;
+            for s in &samples {
+                if s.tan_slope < min_tan_slope
+                    || (nmad > 0f64 && (s.dh - median_dh).abs() > max_dev)
+                {
+                    continue;
+                }
+                let y = (s.dh - median_dh) / s.tan_slope;
+                let row = [s.sin_aspect, s.cos_aspect, 1f64];
+                for a in 0..3 {
+                    for b in 0..3 {
+                        ata[(a, b)] += row[a] * row[b];
+                    }
+                    atb[a] += row[a] * y;
+                }
+                n += 1;
+            }
+            let solution = if n >= 3 { ata.lu().solve(&atb) } else { None };
+            let (dx, dy) = match solution {
+                Some(x) => (x[0], x[1]),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Too few sloping cells to estimate the horizontal shift; try a lower minimum slope.",
+                    ))
+                }
+            };
+            shift_x += dx;
+            shift_y += dy;
+            if (dx * dx + dy * dy).sqrt() < tolerance {
+                // sample once more to report the statistics of the final shift
+                converged = true;
+            }
+        }
+        let final_stats = history[history.len() - 1];
+        let shift_z = final_stats.median;
+
+        if verbose {
+            println!(
+                "Shift: dx = {:.4}, dy = {:.4}, dz = {:.4}",
+                shift_x, shift_y, shift_z
+            );
+        }
+
+        // resample the shifted input DEM on its own grid
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        if input.get_value(row, col) == nodata {
+                            continue;
+                        }
+                        let (x, y) = input.get_cell_centre(row, col);
+                        let (r, c) = input.get_grid_position(x + shift_x, y + shift_y);
+                        let z = input.get_interpolated_value(r, c, InterpolationMethod::Bilinear);
+                        if z != nodata {
+                            data[col as usize] = z - shift_z;
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Resampling: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Reference file: {}", reference_file));
+        output.add_metadata_entry(format!("Shift x: {}", shift_x));
+        output.add_metadata_entry(format!("Shift y: {}", shift_y));
+        output.add_metadata_entry(format!("Shift z: {}", shift_z));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let f = File::create(&report_file)?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <head>
+            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+            <title>DEM Co-registration</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"</head>
+        <body>
+            <h1>DEM Co-registration Report</h1>"#
+                .as_bytes(),
+        )?;
+        writer
+            .write_all(&format!("<p><strong>Input DEM</strong>: {}</p>", input_file).as_bytes())?;
+        writer.write_all(
+            &format!("<p><strong>Reference DEM</strong>: {}</p>", reference_file).as_bytes(),
+        )?;
+        if mask.is_some() {
+            writer.write_all(
+                &format!("<p><strong>Stable terrain mask</strong>: {}</p>", mask_file).as_bytes(),
+            )?;
+        }
+        writer.write_all(
+            &format!(
+                "<p><strong>Shift</strong>: dx = {:.4}, dy = {:.4}, dz = {:.4} (magnitude of horizontal shift = {:.4})</p>",
+                shift_x,
+                shift_y,
+                shift_z,
+                (shift_x * shift_x + shift_y * shift_y).sqrt()
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(
+            &format!(
+                "<p><strong>Convergence</strong>: {} after {} iterations (tolerance = {})</p>",
+                if converged {
+                    "converged"
+                } else {
+                    "did not converge"
+                },
+                history.len() - 1,
+                tolerance
+            )
+            .as_bytes(),
+        )?;
+
+        writer.write_all("<br><table align=\"center\">".as_bytes())?;
+        writer.write_all(
+            "<caption>Elevation differences (input - reference) by iteration</caption>".as_bytes(),
+        )?;
+        writer.write_all(
+            "<tr><th>Iteration</th><th>Shift x</th><th>Shift y</th><th>Median</th><th>NMAD</th><th>RMSE</th><th>Num. Cells</th></tr>"
+                .as_bytes(),
+        )?;
+        for (i, s) in history.iter().enumerate() {
+            writer.write_all(
+                &format!(
+                    "<tr><td class=\"numberCell\">{}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{}</td></tr>",
+                    i, s.shift_x, s.shift_y, s.median, s.nmad, s.rmse, s.num_cells
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table>".as_bytes())?;
+        writer.write_all("<p>The statistics of the final iteration are measured after the final horizontal shift, and before the vertical shift, is applied. NMAD is the normalized median absolute deviation.</p>".as_bytes())?;
+        writer.write_all("</body>".as_bytes())?;
+        let _ = writer.flush();
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(report_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct IterationStats {
+    shift_x: f64,
+    shift_y: f64,
+    median: f64,
+    nmad: f64,
+    rmse: f64,
+    num_cells: usize,
+}
+
+/// The elevation difference and reference terrain slope and aspect at a grid cell.
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    dh: f64,
+    tan_slope: f64,
+    sin_aspect: f64,
+    cos_aspect: f64,
+}
+
+/// Samples the input DEM, shifted by (shift_x, shift_y), at the centre of each valid
+/// reference cell and measures the elevation difference. The slope and aspect of the
+/// reference DEM are calculated using Horn's (1981) method.
+fn sample_differences(
+    input: Arc<Raster>,
+    reference: Arc<Raster>,
+    mask: Option<Arc<Raster>>,
+    shift_x: f64,
+    shift_y: f64,
+) -> Vec<Sample> {
+    let rows = reference.configs.rows as isize;
+    let columns = reference.configs.columns as isize;
+    let nodata = reference.configs.nodata;
+    let input_nodata = input.configs.nodata;
+    let eight_res_x = 8f64 * reference.configs.resolution_x;
+    let eight_res_y = 8f64 * reference.configs.resolution_y;
+    let num_procs = num_cpus::get() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let reference = reference.clone();
+        let mask = mask.clone();
+        let tx = tx.clone();
+        thread::spawn(move || {
+            // neighbours, clockwise from the north-west
+            let dx = [-1, 0, 1, 1, 1, 0, -1, -1];
+            let dy = [-1, -1, -1, 0, 1, 1, 1, 0];
+            let mut n = [0f64; 8];
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut samples = vec![];
+                'cells: for col in 0..columns {
+                    let z = reference.get_value(row, col);
+                    if z == nodata {
+                        continue;
+                    }
+                    if let Some(mask) = &mask {
+                        let m = mask.get_value(row, col);
+                        if m == mask.configs.nodata || m == 0f64 {
+                            continue;
+                        }
+                    }
+                    for k in 0..8 {
+                        n[k] = reference.get_value(row + dy[k], col + dx[k]);
+                        if n[k] == nodata {
+                            continue 'cells;
+                        }
+                    }
+                    // gradient, positive to the east and north
+                    let p =
+                        ((n[2] + 2f64 * n[3] + n[4]) - (n[0] + 2f64 * n[7] + n[6])) / eight_res_x;
+                    let q =
+                        ((n[0] + 2f64 * n[1] + n[2]) - (n[6] + 2f64 * n[5] + n[4])) / eight_res_y;
+                    let tan_slope = (p * p + q * q).sqrt();
+                    if tan_slope <= 0f64 {
+                        continue;
+                    }
+
+                    let (x, y) = reference.get_cell_centre(row, col);
+                    let (r, c) = input.get_grid_position(x + shift_x, y + shift_y);
+                    let zi = input.get_interpolated_value(r, c, InterpolationMethod::Bilinear);
+                    if zi == input_nodata {
+                        continue;
+                    }
+                    // the aspect is the downslope direction, clockwise from north
+                    samples.push(Sample {
+                        dh: zi - z,
+                        tan_slope: tan_slope,
+                        sin_aspect: -p / tan_slope,
+                        cos_aspect: -q / tan_slope,
+                    });
+                }
+                tx.send((row, samples)).unwrap();
+            }
+        });
+    }
+
+    // gather the samples in row order, so that results do not depend on thread scheduling
+    let mut row_samples: Vec<Vec<Sample>> = vec![vec![]; rows as usize];
+    for _ in 0..rows {
+        let (row, samples) = rx.recv().expect("Error receiving data from thread.");
+        row_samples[row as usize] = samples;
+    }
+    row_samples.into_iter().flatten().collect()
+}
+
+/// Returns the median of a set of values, which are reordered.
+fn median(values: &mut [f64]) -> f64 {
+    let n = values.len();
+    if n == 0 {
+        return f64::NAN;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+    if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / 2f64
+    }
+}
@@ -0,0 +1,903 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::{fft2d, fft_frequency, fft_pad_image};
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::rendering::Histogram;
+use crate::structures::Array2D;
+use crate::tools::*;
+use serde_json::json;
+use std::cmp::Ordering::Equal;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// The maximum number of rows and columns in the window used to assess striping.
+const MAX_FFT_SIZE: usize = 2048;
+
+/// Frequencies (in cycles per cell) lower than this value are excluded from the striping ratios.
+const MIN_STRIPE_FREQ: f64 = 0.05;
+
+/// Striping ratios greater than this value are reported as possible striping.
+const STRIPE_RATIO_THRESHOLD: f64 = 2.0;
+
+/// This tool assesses the quality of a digital elevation model (DEM; `--dem`) and summarizes the
+/// results in an HTML report (`--output`) and/or a JSON file (`--out_json`), which is convenient
+/// for automated quality-assurance workflows. A brief summary is also output to the system
+/// `stdout`. The report includes the following measures:
+///
+/// - **Voids**: The percentage of grid cells that are NoData, as well as the number and total area
+///   of interior voids, i.e. NoData areas that are not connected to the edges of the grid. Interior
+///   voids commonly result from data gaps (e.g. water bodies, radar shadow, or dense vegetation)
+///   and may need to be filled (see `FillMissingData`), whereas NoData areas that are connected to
+///   the grid edges usually reflect the irregular footprint of the data.
+/// - **Pits and flats**: The number of single-cell pits, i.e. cells with eight valid neighbours
+///   that are all higher, reported as a density (pits per square kilometre), and the percentage of
+///   interior cells that are flat, i.e. cells without a lower neighbour but with at least one
+///   neighbour of equal elevation. High pit densities and extensive flats are characteristic of
+///   noisy or heavily quantized elevation data and of interpolation artifacts.
+/// - **Striping**: Striping and banding artifacts, which are common in DEMs derived from
+///   line-scanning sensors and from interferometric and photogrammetric processing, are detected in
+///   the frequency domain using the fast Fourier transform (FFT). Stripes that are parallel to the
+///   rows (i.e. horizontal stripes) concentrate their energy along the vertical frequency axis of
+///   the power spectrum, and vertical stripes along the horizontal frequency axis. The striping
+///   ratio of each orientation is the geometric mean of the power along the corresponding axis
+///   relative to that of the nearby off-axis frequencies, excluding frequencies lower than 0.05
+///   cycles per cell.
+///   Natural terrain has ratios near 1.0, and ratios greater than 2.0 are flagged as possible
+///   striping. For large DEMs, striping is assessed within a central window of 2048 x 2048 cells,
+///   and NoData cells are replaced by the DEM mean prior to the transform.
+/// - **Elevation distribution**: The minimum, maximum, mean, and standard deviation of elevation,
+///   and a histogram with a user-specified number of bins (`--num_bins`).
+/// - **Reference comparison**: If a reference DEM (`--reference`), e.g. a higher-accuracy LiDAR
+///   DEM, is specified, the elevation differences (DEM less reference) are summarized using the
+///   mean error (bias), mean absolute error, root-mean-square error (RMSE), standard deviation,
+///   normalized median absolute deviation (NMAD), the 90th percentile of the absolute error (LE90),
+///   and the correlation coefficient. The NMAD and LE90 are robust to the outliers that are common
+///   in DEM errors. The reference DEM must share the grid of the input DEM.
+///
+/// Horizontal distances are measured in metres for DEMs in geographic coordinates.
+///
+/// # See Also
+/// `RasterSummaryStats`, `FindNoFlowCells`, `RemovePeriodicNoise`, `CoregisterDems`
+pub struct DemQualityReport {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DemQualityReport {
+    pub fn new() -> DemQualityReport {
+        // public constructor
+        let name = "DemQualityReport".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Reports DEM quality measures, including voids, pits, flats, striping, and the error relative to a reference DEM."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Optional output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output JSON File".to_owned(),
+            flags: vec!["--out_json".to_owned()],
+            description: "Optional output JSON report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference DEM File (optional)".to_owned(),
+            flags: vec!["--reference".to_owned()],
+            description: "Optional input reference raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Histogram Bins".to_owned(),
+            flags: vec!["--num_bins".to_owned()],
+            description: "Number of bins in the elevation histogram.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("50".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=report.html --out_json=report.json --reference=lidar_DEM.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DemQualityReport {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DemQualityReport {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_html_file = String::new();
+        let mut output_json_file = String::new();
+        let mut reference_file = String::new();
+        let mut num_bins = 50usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_html_file = value;
+            } else if flag_val == "-out_json" {
+                output_json_file = value;
+            } else if flag_val == "-reference" {
+                reference_file = value;
+            } else if flag_val == "-num_bins" {
+                num_bins = value
+                    .parse::<usize>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if num_bins == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of histogram bins must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_html_file.is_empty()
+            && !output_html_file.contains(&sep)
+            && !output_html_file.contains("/")
+        {
+            output_html_file = format!("{}{}", working_directory, output_html_file);
+        }
+        if !output_json_file.is_empty()
+            && !output_json_file.contains(&sep)
+            && !output_json_file.contains("/")
+        {
+            output_json_file = format!("{}{}", working_directory, output_json_file);
+        }
+        if !reference_file.is_empty()
+            && !reference_file.contains(&sep)
+            && !reference_file.contains("/")
+        {
+            reference_file = format!("{}{}", working_directory, reference_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let reference = if !reference_file.is_empty() {
+            // make sure the inputs share the same grid and CRS
+            Some(align_raster_input(
+                &input,
+                Raster::new(&reference_file, "r")?,
+            )?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let num_cells = (rows * columns) as usize;
+
+        // the grid resolution, in metres for geographic coordinates
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+            res_x = res_x * 113200.0 * mid_lat.cos();
+            res_y = res_y * 113200.0;
+        }
+        let cell_area = res_x * res_y;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+        // elevation statistics, pits and flats
+        let mut num_valid = 0usize;
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut total = 0f64;
+        let mut total_sqr = 0f64;
+        let mut num_interior = 0usize;
+        let mut num_pits = 0usize;
+        let mut num_flats = 0usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z == nodata {
+                    continue;
+                }
+                num_valid += 1;
+                if z < min {
+                    min = z;
+                }
+                if z > max {
+                    max = z;
+                }
+                total += z;
+                total_sqr += z * z;
+
+                let mut is_interior = true;
+                let mut num_lower = 0;
+                let mut num_equal = 0;
+                for i in 0..8 {
+                    let z_n = input.get_value(row + dy[i], col + dx[i]);
+                    if z_n == nodata {
+                        is_interior = false;
+                        break;
+                    }
+                    if z_n < z {
+                        num_lower += 1;
+                    } else if z_n == z {
+                        num_equal += 1;
+                    }
+                }
+                if is_interior {
+                    num_interior += 1;
+                    if num_lower == 0 {
+                        if num_equal == 0 {
+                            num_pits += 1;
+                        } else {
+                            num_flats += 1;
+                        }
+                    }
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Assessing elevations: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if num_valid == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input DEM does not contain any valid cells.",
+            ));
+        }
+
+        let mean = total / num_valid as f64;
+        let std_dev = (total_sqr / num_valid as f64 - mean * mean)
+            .max(0f64)
+            .sqrt();
+        let num_nodata = num_cells - num_valid;
+        let void_percent = 100f64 * num_nodata as f64 / num_cells as f64;
+        let valid_area_km2 = num_valid as f64 * cell_area / 1_000_000f64;
+        let pit_density = if valid_area_km2 > 0f64 {
+            num_pits as f64 / valid_area_km2
+        } else {
+            0f64
+        };
+        let flat_percent = 100f64 * num_flats as f64 / num_interior.max(1) as f64;
+
+        // interior voids are NoData areas that are not connected to the grid edges
+        if verbose {
+            println!("Finding interior voids...");
+        }
+        let mut void_id: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if (row == 0 || row == rows - 1 || col == 0 || col == columns - 1)
+                    && input.get_value(row, col) == nodata
+                {
+                    void_id.set_value(row, col, -1);
+                    stack.push((row, col));
+                }
+            }
+        }
+        while let Some((row, col)) = stack.pop() {
+            for i in 0..8 {
+                let (row_n, col_n) = (row + dy[i], col + dx[i]);
+                if void_id.get_value(row_n, col_n) == 0 && input.get_value(row_n, col_n) == nodata {
+                    void_id.set_value(row_n, col_n, -1);
+                    stack.push((row_n, col_n));
+                }
+            }
+        }
+        let mut num_voids = 0i32;
+        let mut num_void_cells = 0usize;
+        let mut largest_void = 0usize;
+        for row in 0..rows {
+            for col in 0..columns {
+                if void_id.get_value(row, col) == 0 && input.get_value(row, col) == nodata {
+                    num_voids += 1;
+                    let mut void_size = 1usize;
+                    void_id.set_value(row, col, num_voids);
+                    stack.push((row, col));
+                    while let Some((r, c)) = stack.pop() {
+                        for i in 0..8 {
+                            let (row_n, col_n) = (r + dy[i], c + dx[i]);
+                            if void_id.get_value(row_n, col_n) == 0
+                                && input.get_value(row_n, col_n) == nodata
+                            {
+                                void_id.set_value(row_n, col_n, num_voids);
+                                void_size += 1;
+                                stack.push((row_n, col_n));
+                            }
+                        }
+                    }
+                    num_void_cells += void_size;
+                    if void_size > largest_void {
+                        largest_void = void_size;
+                    }
+                }
+            }
+        }
+        drop(void_id);
+        let interior_void_percent = 100f64 * num_void_cells as f64 / num_cells as f64;
+
+        // elevation histogram
+        let range = max - min;
+        let bin_width = if range > 0f64 {
+            range / num_bins as f64
+        } else {
+            1f64
+        };
+        let mut histogram = vec![0usize; num_bins];
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if z != nodata {
+                    let bin = (((z - min) / bin_width).floor() as usize).min(num_bins - 1);
+                    histogram[bin] += 1;
+                }
+            }
+        }
+
+        // striping, assessed in the frequency domain within a central window
+        if verbose {
+            println!("Assessing striping...");
+        }
+        let win_rows = (rows as usize).min(MAX_FFT_SIZE);
+        let win_columns = (columns as usize).min(MAX_FFT_SIZE);
+        let start_row = (rows as usize - win_rows) / 2;
+        let start_col = (columns as usize - win_columns) / 2;
+        let (horizontal_ratio, vertical_ratio) = if win_rows >= 8 && win_columns >= 8 {
+            // deviations from the mean, such that NoData cells take the mean elevation
+            let mut data = vec![0f64; win_rows * win_columns];
+            for r in 0..win_rows {
+                for c in 0..win_columns {
+                    let z = input.get_value((start_row + r) as isize, (start_col + c) as isize);
+                    if z != nodata {
+                        data[r * win_columns + c] = z - mean;
+                    }
+                }
+            }
+            let (mut re, padded_rows, padded_columns) = fft_pad_image(&data, win_rows, win_columns);
+            drop(data);
+            let mut im = vec![0f64; re.len()];
+            fft2d(&mut re, &mut im, padded_rows, padded_columns, false);
+            let log_power = |r: usize, c: usize| -> f64 {
+                let k = r * padded_columns + c;
+                (re[k] * re[k] + im[k] * im[k]).max(f64::MIN_POSITIVE).ln()
+            };
+
+            // horizontal stripes lie along the vertical frequency axis (column frequency of zero)
+            let mut axis_total = 0f64;
+            let mut off_axis_total = 0f64;
+            let mut n = 0usize;
+            let ref_cols = [2, 3, padded_columns - 2, padded_columns - 3];
+            for r in 0..padded_rows {
+                if fft_frequency(r, padded_rows).abs() >= MIN_STRIPE_FREQ {
+                    axis_total += log_power(r, 0);
+                    off_axis_total += ref_cols.iter().map(|&c| log_power(r, c)).sum::<f64>() / 4f64;
+                    n += 1;
+                }
+            }
+            let horizontal_ratio = if n > 0 {
+                ((axis_total - off_axis_total) / n as f64).exp()
+            } else {
+                f64::NAN
+            };
+
+            // vertical stripes lie along the horizontal frequency axis (row frequency of zero)
+            axis_total = 0f64;
+            off_axis_total = 0f64;
+            n = 0;
+            let ref_rows = [2, 3, padded_rows - 2, padded_rows - 3];
+            for c in 0..padded_columns {
+                if fft_frequency(c, padded_columns).abs() >= MIN_STRIPE_FREQ {
+                    axis_total += log_power(0, c);
+                    off_axis_total += ref_rows.iter().map(|&r| log_power(r, c)).sum::<f64>() / 4f64;
+                    n += 1;
+                }
+            }
+            let vertical_ratio = if n > 0 {
+                ((axis_total - off_axis_total) / n as f64).exp()
+            } else {
+                f64::NAN
+            };
+            (horizontal_ratio, vertical_ratio)
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+        let horizontal_striping = horizontal_ratio > STRIPE_RATIO_THRESHOLD;
+        let vertical_striping = vertical_ratio > STRIPE_RATIO_THRESHOLD;
+
+        // comparison with the reference DEM
+        let comparison = match reference {
+            Some(ref reference) => {
+                if verbose {
+                    println!("Comparing with the reference DEM...");
+                }
+                let ref_nodata = reference.configs.nodata;
+                let mut diffs = vec![];
+                let (mut sum_z, mut sum_r, mut sum_zz, mut sum_rr, mut sum_zr) =
+                    (0f64, 0f64, 0f64, 0f64, 0f64);
+                for row in 0..rows {
+                    for col in 0..columns {
+                        let z = input.get_value(row, col);
+                        let zr = reference.get_value(row, col);
+                        if z != nodata && zr != ref_nodata {
+                            diffs.push(z - zr);
+                            sum_z += z;
+                            sum_r += zr;
+                            sum_zz += z * z;
+                            sum_rr += zr * zr;
+                            sum_zr += z * zr;
+                        }
+                    }
+                }
+                let n = diffs.len();
+                if n == 0 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The input and reference DEMs do not have any valid cells in common.",
+                    ));
+                }
+                let nf = n as f64;
+                let bias = diffs.iter().sum::<f64>() / nf;
+                let mae = diffs.iter().map(|d| d.abs()).sum::<f64>() / nf;
+                let rmse = (diffs.iter().map(|d| d * d).sum::<f64>() / nf).sqrt();
+                let sd = (diffs.iter().map(|d| (d - bias) * (d - bias)).sum::<f64>() / nf).sqrt();
+                let cov = sum_zr / nf - (sum_z / nf) * (sum_r / nf);
+                let var_z = sum_zz / nf - (sum_z / nf) * (sum_z / nf);
+                let var_r = sum_rr / nf - (sum_r / nf) * (sum_r / nf);
+                let correlation = if var_z > 0f64 && var_r > 0f64 {
+                    cov / (var_z * var_r).sqrt()
+                } else {
+                    f64::NAN
+                };
+                diffs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+                let min_diff = diffs[0];
+                let max_diff = diffs[n - 1];
+                let median_diff = percentile(&diffs, 50f64);
+                let mut abs_dev: Vec<f64> = diffs.iter().map(|d| (d - median_diff).abs()).collect();
+                abs_dev.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+                let nmad = 1.4826 * percentile(&abs_dev, 50f64);
+                let mut abs_err: Vec<f64> = diffs.iter().map(|d| d.abs()).collect();
+                drop(diffs);
+                abs_err.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+                let le90 = percentile(&abs_err, 90f64);
+                Some(vec![
+                    ("Num. Compared Cells", n as f64),
+                    ("Mean Error (Bias)", bias),
+                    ("Mean Absolute Error", mae),
+                    ("RMSE", rmse),
+                    ("Std. Dev. of Error", sd),
+                    ("Median Error", median_diff),
+                    ("NMAD", nmad),
+                    ("LE90", le90),
+                    ("Minimum Error", min_diff),
+                    ("Maximum Error", max_diff),
+                    ("Correlation Coefficient", correlation),
+                ])
+            }
+            None => None,
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let void_stats = vec![
+            ("Total Cells", num_cells as f64),
+            ("NoData Cells", num_nodata as f64),
+            ("Void Percentage", void_percent),
+            ("Num. Interior Voids", num_voids as f64),
+            ("Interior Void Cells", num_void_cells as f64),
+            ("Interior Void Percentage", interior_void_percent),
+            ("Largest Interior Void (Cells)", largest_void as f64),
+        ];
+        let surface_stats = vec![
+            ("Num. Pits", num_pits as f64),
+            ("Pit Density (per sq. km)", pit_density),
+            ("Num. Flat Cells", num_flats as f64),
+            ("Flat Area Percentage", flat_percent),
+        ];
+        let striping_stats = vec![
+            ("Horizontal Striping Ratio", horizontal_ratio),
+            ("Vertical Striping Ratio", vertical_ratio),
+        ];
+        let elev_stats = vec![
+            ("Minimum", min),
+            ("Maximum", max),
+            ("Range", range),
+            ("Mean", mean),
+            ("Std. Dev.", std_dev),
+        ];
+
+        if verbose {
+            println!("\nVoid percentage: {:.4}%", void_percent);
+            println!(
+                "Interior voids: {} ({:.4}% of cells)",
+                num_voids, interior_void_percent
+            );
+            println!("Pit density: {:.4} per sq. km", pit_density);
+            println!("Flat area percentage: {:.4}%", flat_percent);
+            println!(
+                "Striping ratios: horizontal = {:.4}{}, vertical = {:.4}{}",
+                horizontal_ratio,
+                if horizontal_striping {
+                    " (possible striping)"
+                } else {
+                    ""
+                },
+                vertical_ratio,
+                if vertical_striping {
+                    " (possible striping)"
+                } else {
+                    ""
+                }
+            );
+            if let Some(ref c) = comparison {
+                for (label, v) in c {
+                    println!("{}: {:.4}", label, v);
+                }
+            }
+            println!(
+                "\n{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        if !output_json_file.is_empty() {
+            let mut report = json!({
+                "input": input_file,
+                "rows": rows,
+                "columns": columns,
+                "voids": {
+                    "num_cells": num_cells,
+                    "num_nodata": num_nodata,
+                    "void_percent": void_percent,
+                    "num_interior_voids": num_voids,
+                    "num_interior_void_cells": num_void_cells,
+                    "interior_void_percent": interior_void_percent,
+                    "largest_interior_void_cells": largest_void,
+                },
+                "pits_and_flats": {
+                    "num_pits": num_pits,
+                    "pit_density_per_km2": pit_density,
+                    "num_flat_cells": num_flats,
+                    "flat_percent": flat_percent,
+                },
+                "striping": {
+                    "horizontal_ratio": json_number(horizontal_ratio),
+                    "vertical_ratio": json_number(vertical_ratio),
+                    "horizontal_striping": horizontal_striping,
+                    "vertical_striping": vertical_striping,
+                    "threshold": STRIPE_RATIO_THRESHOLD,
+                },
+                "elevation": {
+                    "min": min,
+                    "max": max,
+                    "range": range,
+                    "mean": mean,
+                    "std_dev": std_dev,
+                    "histogram": {
+                        "min": min,
+                        "bin_width": bin_width,
+                        "counts": histogram,
+                    },
+                },
+            });
+            if let Some(ref c) = comparison {
+                report["reference_comparison"] = json!({
+                    "reference": reference_file,
+                    "num_cells": c[0].1 as usize,
+                    "bias": c[1].1,
+                    "mae": c[2].1,
+                    "rmse": c[3].1,
+                    "std_dev": c[4].1,
+                    "median": c[5].1,
+                    "nmad": c[6].1,
+                    "le90": c[7].1,
+                    "min": c[8].1,
+                    "max": c[9].1,
+                    "correlation": json_number(c[10].1),
+                });
+            }
+            let f = File::create(&output_json_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all(
+                serde_json::to_string_pretty(&report)
+                    .map_err(|e| Error::new(ErrorKind::Other, e))?
+                    .as_bytes(),
+            )?;
+            writer.flush()?;
+            if verbose {
+                println!("JSON report written to {}", output_json_file);
+            }
+        }
+
+        if !output_html_file.is_empty() {
+            let f = File::create(&output_html_file)?;
+            let mut writer = BufWriter::new(f);
+
+            writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>DEM Quality Report</title>"#.as_bytes())?;
+
+            // get the style sheet
+            writer.write_all(&get_css().as_bytes())?;
+
+            writer.write_all(
+                &r#"</head>
+            <body>
+                <h1>DEM Quality Report</h1>"#
+                    .as_bytes(),
+            )?;
+
+            writer.write_all(&format!("<p><strong>DEM</strong>: {}</p>", input_file).as_bytes())?;
+            writer.write_all(
+                &format!(
+                    "<p><strong>Rows</strong>: {}, <strong>Columns</strong>: {}</p>",
+                    rows, columns
+                )
+                .as_bytes(),
+            )?;
+
+            write_html_table(&mut writer, "Voids", &void_stats)?;
+            write_html_table(&mut writer, "Pits and Flats", &surface_stats)?;
+            write_html_table(&mut writer, "Striping", &striping_stats)?;
+            let mut notes = vec![];
+            if horizontal_striping {
+                notes.push("horizontal");
+            }
+            if vertical_striping {
+                notes.push("vertical");
+            }
+            if !notes.is_empty() {
+                writer.write_all(
+                    &format!(
+                        "<p><strong>Warning</strong>: possible {} striping was detected (ratio > {}).</p>",
+                        notes.join(" and "),
+                        STRIPE_RATIO_THRESHOLD
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            write_html_table(&mut writer, "Elevation Distribution", &elev_stats)?;
+
+            let histo = Histogram {
+                parent_id: "histo".to_string(),
+                width: 700f64,
+                height: 500f64,
+                freq_data: histogram.clone(),
+                min_bin_val: min,
+                bin_width: bin_width,
+                x_axis_label: "Elevation".to_owned(),
+                cumulative: false,
+            };
+            writer.write_all(
+                &format!(
+                    "<br><div id='histo' align=\"center\">{}</div>",
+                    histo.get_svg()
+                )
+                .as_bytes(),
+            )?;
+
+            if let Some(ref c) = comparison {
+                write_html_table(&mut writer, "Reference DEM Comparison", c)?;
+                writer.write_all(
+                    &format!("<p><strong>Reference DEM</strong>: {}</p>", reference_file)
+                        .as_bytes(),
+                )?;
+            }
+
+            writer.write_all("</body>".as_bytes())?;
+
+            let _ = writer.flush();
+
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+                println!("Complete! Please see {} for output.", output_html_file);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a titled table of labelled statistics to an HTML report.
+fn write_html_table<W: Write>(
+    writer: &mut W,
+    title: &str,
+    stats: &[(&str, f64)],
+) -> Result<(), Error> {
+    writer.write_all(&format!("<h2>{}</h2>", title).as_bytes())?;
+    writer.write_all("<table align=\"center\">".as_bytes())?;
+    writer.write_all("<tr><th>Measure</th><th>Value</th></tr>".as_bytes())?;
+    for (label, v) in stats {
+        let value = if !v.is_finite() {
+            "N/A".to_string()
+        } else if v.fract() == 0f64 {
+            format!("{}", v)
+        } else {
+            format!("{:.4}", v)
+        };
+        writer.write_all(
+            &format!(
+                "<tr><td>{}</td><td class=\"numberCell\">{}</td></tr>",
+                label, value
+            )
+            .as_bytes(),
+        )?;
+    }
+    writer.write_all("</table>".as_bytes())?;
+    Ok(())
+}
+
+/// Returns a JSON number, or null for values that are not finite.
+fn json_number(v: f64) -> serde_json::Value {
+    if v.is_finite() {
+        json!(v)
+    } else {
+        serde_json::Value::Null
+    }
+}
+
+/// Returns the p-th percentile (0-100) of a sorted slice, using linear interpolation
+/// between the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = p / 100f64 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
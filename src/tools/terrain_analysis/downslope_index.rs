@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 17, 2017
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -47,6 +47,9 @@ use std::thread;
 /// Hjerdt, K.N., McDonnell, J.J., Seibert, J. Rodhe, A. (2004) *A new topographic index to
 /// quantify downslope controls on local drainage*, **Water Resources Research**, 40, W05602,
 /// doi:10.1029/2004WR003130.
+///
+/// # See Also
+/// `SagaWetnessIndex`, `WetnessIndex`
 pub struct DownslopeIndex {
     name: String,
     description: String,
@@ -207,19 +207,13 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
         let nodata = input.configs.nodata;
         // let min_val = input.configs.minimum;
 
-        let watersheds = Arc::new(Raster::new(&watersheds_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let watersheds = Arc::new(align_raster_input(
+            &input,
+            Raster::new(&watersheds_file, "r")?,
+        )?);
         let watershed_nodata = watersheds.configs.nodata;
 
-        // make sure the input files have the same size
-        if watersheds.configs.rows != input.configs.rows
-            || watersheds.configs.columns != input.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let start = Instant::now();
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
@@ -0,0 +1,636 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::rendering::LineGraph;
+use crate::tools::*;
+use crate::vector::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// This tool samples one or more rasters (`--inputs`), e.g. a digital elevation model (DEM) and
+/// any of its derivatives, along a set of vector lines (`--lines`). Each line part is sampled at a
+/// regular distance interval (`--interval`), measured in map units along the line, beginning at
+/// the first vertex; the final vertex of each part is always sampled, so the last spacing may be
+/// shorter than the interval. The default interval is the mean cell size of the first input raster.
+/// Typical applications include the extraction of river long-profiles from digitized channel
+/// lines and the extraction of valley or hillslope cross-sections along transects.
+///
+/// The samples are output as a vector points file (`--output`) with the attribute fields *FID*,
+/// *LINE_ID* (the record number of the source line), *PART*, *DIST* (the distance along the
+/// line part), and *VALUE1*, *VALUE2*, *VALUE3*, etc., each corresponding to the order of input
+/// rasters. Multi-band inputs produce one field for each band, named *VALUE1_B1*, *VALUE1_B2*,
+/// etc. Samples falling on NoData cells are assigned null values. The same table of
+/// distance-value pairs, along with sample coordinates, may optionally be written to a CSV file
+/// (`--out_csv`), and an interactive HTML document (`--out_html`) containing one profile plot per
+/// input raster may also be produced.
+///
+/// By default, raster values are estimated at the sample locations using bilinear interpolation.
+/// The interpolation method (`--method`) may alternatively be set to nearest neighbour ('nn') or
+/// cubic convolution ('cc'). Input rasters need not share the same grid.
+///
+/// # See Also
+/// `Profile`, `LongProfile`, `ExtractRasterValuesAtPoints`
+pub struct ExtractProfiles {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractProfiles {
+    pub fn new() -> ExtractProfiles {
+        // public constructor
+        let name = "ExtractProfiles".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Samples one or more rasters at a regular interval along vector lines.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector Line File".to_owned(),
+            flags: vec!["--lines".to_owned()],
+            description: "Input vector line file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector Points File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sampling Interval".to_owned(),
+            flags: vec!["--interval".to_owned()],
+            description: "Distance between samples along lines, in map units; defaults to the mean cell size of the first input.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Interpolation method; options include 'nn' (nearest neighbour), 'bilinear', and 'cc' (cubic convolution)".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "nn".to_owned(),
+                "bilinear".to_owned(),
+                "cc".to_owned(),
+            ]),
+            default_value: Some("bilinear".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Optional output CSV file of distance-value pairs.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["--out_html".to_owned()],
+            description: "Optional output HTML file of profile plots.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --lines=streams.shp -i='dem.tif;slope.tif' -o=samples.shp --interval=10.0 --out_csv=profiles.csv --out_html=profiles.html", short_exe, name).replace("*", &sep);
+
+        ExtractProfiles {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractProfiles {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut lines_file = String::new();
+        let mut input_files = String::new();
+        let mut output_file = String::new();
+        let mut interval = 0f64;
+        let mut method = InterpolationMethod::Bilinear;
+        let mut output_csv_file = String::new();
+        let mut output_html_file = String::new();
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-lines" {
+                lines_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-i" || flag_val == "-inputs" {
+                input_files = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-interval" {
+                interval = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-method" {
+                let val = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+                method = match InterpolationMethod::from_name(&val) {
+                    Some(m) => m,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unrecognized interpolation method '{}'.", val),
+                        ))
+                    }
+                };
+            } else if flag_val == "-out_csv" {
+                output_csv_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_html" {
+                output_html_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !lines_file.contains(&sep) && !lines_file.contains("/") {
+            lines_file = format!("{}{}", working_directory, lines_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !output_csv_file.is_empty()
+            && !output_csv_file.contains(&sep)
+            && !output_csv_file.contains("/")
+        {
+            output_csv_file = format!("{}{}", working_directory, output_csv_file);
+        }
+        if !output_html_file.is_empty()
+            && !output_html_file.contains(&sep)
+            && !output_html_file.contains("/")
+        {
+            output_html_file = format!("{}{}", working_directory, output_html_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let lines = Shapefile::read(&lines_file)?;
+
+        // make sure the input vector file is of lines type
+        if lines.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of polyline base shape type.",
+            ));
+        }
+
+        let mut cmd = input_files.split(";");
+        let mut v = cmd.collect::<Vec<&str>>();
+        if v.len() == 1 {
+            cmd = input_files.split(",");
+            v = cmd.collect::<Vec<&str>>();
+        }
+        let mut inputs: Vec<Raster> = vec![];
+        for value in v {
+            if !value.trim().is_empty() {
+                let mut input_file = value.trim().to_owned();
+                if !input_file.contains(&sep) && !input_file.contains("/") {
+                    input_file = format!("{}{}", working_directory, input_file);
+                }
+                inputs.push(Raster::new(&input_file, "r")?);
+            }
+        }
+        if inputs.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect about the input files. At least one input is required to operate this tool."));
+        }
+
+        let start = Instant::now();
+
+        if interval <= 0f64 {
+            interval = (inputs[0].configs.resolution_x + inputs[0].configs.resolution_y) / 2f64;
+        }
+
+        // one field (and plot) per raster band
+        let mut field_names = vec![];
+        let mut field_sources = vec![]; // (input, band)
+        for i in 0..inputs.len() {
+            let num_bands = inputs[i].num_bands();
+            for band in 0..num_bands {
+                field_names.push(if num_bands == 1 {
+                    format!("VALUE{}", i + 1)
+                } else {
+                    format!("VALUE{}_B{}", i + 1, band + 1)
+                });
+                field_sources.push((i, band));
+            }
+        }
+        let num_fields = field_names.len();
+
+        let mut output = Shapefile::new(&output_file, ShapeType::Point)?;
+        output.projection = lines.projection.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "LINE_ID",
+            FieldDataType::Int,
+            10u8,
+            0u8,
+        ));
+        output
+            .attributes
+            .add_field(&AttributeField::new("PART", FieldDataType::Int, 6u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("DIST", FieldDataType::Real, 12u8, 4u8));
+        for name in &field_names {
+            output
+                .attributes
+                .add_field(&AttributeField::new(name, FieldDataType::Real, 12u8, 6u8));
+        }
+
+        let mut csv_lines = vec![];
+        let mut xdata = vec![vec![]; num_fields];
+        let mut ydata = vec![vec![]; num_fields];
+        let mut series_names = vec![];
+        let mut fid = 1i32;
+        let (mut start_point_in_part, mut end_point_in_part): (usize, usize);
+        let (mut x, mut y, mut z): (f64, f64, f64);
+        let (mut seg_length, mut seg_start_dist, mut next_dist): (f64, f64, f64);
+        for record_num in 0..lines.num_records {
+            let record = lines.get_record(record_num);
+            for part in 0..record.num_parts as usize {
+                start_point_in_part = record.parts[part] as usize;
+                end_point_in_part = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize - 1
+                } else {
+                    record.num_points as usize - 1
+                };
+
+                // find the sample locations and their distances along the part
+                let mut samples: Vec<(f64, f64, f64)> = vec![];
+                seg_start_dist = 0f64;
+                next_dist = 0f64;
+                for i in start_point_in_part..end_point_in_part {
+                    let p1 = record.points[i];
+                    let p2 = record.points[i + 1];
+                    seg_length = p1.distance(&p2);
+                    while next_dist <= seg_start_dist + seg_length {
+                        let t = if seg_length > 0f64 {
+                            (next_dist - seg_start_dist) / seg_length
+                        } else {
+                            0f64
+                        };
+                        samples.push((
+                            p1.x + t * (p2.x - p1.x),
+                            p1.y + t * (p2.y - p1.y),
+                            next_dist,
+                        ));
+                        next_dist += interval;
+                    }
+                    seg_start_dist += seg_length;
+                }
+                if end_point_in_part > start_point_in_part
+                    && samples.last().map_or(true, |s| s.2 < seg_start_dist)
+                {
+                    let p = record.points[end_point_in_part];
+                    samples.push((p.x, p.y, seg_start_dist));
+                }
+                if samples.is_empty() {
+                    continue;
+                }
+
+                let mut part_xdata = vec![vec![]; num_fields];
+                let mut part_ydata = vec![vec![]; num_fields];
+                for s in &samples {
+                    x = s.0;
+                    y = s.1;
+                    let mut atts = vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(record_num as i32 + 1),
+                        FieldData::Int(part as i32 + 1),
+                        FieldData::Real(s.2),
+                    ];
+                    let mut csv_line =
+                        format!("{},{},{},{},{}", record_num + 1, part + 1, s.2, x, y);
+                    for f in 0..num_fields {
+                        let (i, band) = field_sources[f];
+                        let (row, col) = inputs[i].get_grid_position(x, y);
+                        z = inputs[i].get_interpolated_band_value(row, col, band, method);
+                        if z != inputs[i].configs.nodata {
+                            atts.push(FieldData::Real(z));
+                            csv_line.push_str(&format!(",{}", z));
+                            part_xdata[f].push(s.2);
+                            part_ydata[f].push(z);
+                        } else {
+                            atts.push(FieldData::Null);
+                            csv_line.push_str(",");
+                        }
+                    }
+                    output.add_point_record(x, y);
+                    output.attributes.add_record(atts, false);
+                    csv_lines.push(csv_line);
+                    fid += 1;
+                }
+
+                for f in 0..num_fields {
+                    xdata[f].push(part_xdata[f].clone());
+                    ydata[f].push(part_ydata[f].clone());
+                }
+                if record.num_parts > 1 {
+                    series_names.push(format!("Profile {} Part {}", record_num + 1, part + 1));
+                } else {
+                    series_names.push(format!("Profile {}", record_num + 1));
+                }
+            }
+
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / lines.num_records as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !output_csv_file.is_empty() {
+            let f = File::create(&output_csv_file)?;
+            let mut writer = BufWriter::new(f);
+            let mut header = String::from("LINE_ID,PART,DIST,X,Y");
+            for name in &field_names {
+                header.push_str(&format!(",{}", name));
+            }
+            writer.write_all(format!("{}\n", header).as_bytes())?;
+            for line in &csv_lines {
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            writer.flush()?;
+            if verbose {
+                println!("CSV file written to {}", output_csv_file);
+            }
+        }
+
+        if !output_html_file.is_empty() {
+            let f = File::create(&output_html_file)?;
+            let mut writer = BufWriter::new(f);
+
+            writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Extract Profiles</title>"#.as_bytes())?;
+
+            // get the style sheet
+            writer.write_all(&get_css().as_bytes())?;
+
+            writer.write_all(
+                &r#"</head>
+            <body>
+                <h1>Extract Profiles</h1>"#
+                    .as_bytes(),
+            )?;
+
+            writer.write_all(
+                (format!(
+                    "<p><strong>Input Lines</strong>: {}<br><strong>Sampling Interval</strong>: {}<br><strong>Number of Samples</strong>: {}</p>",
+                    path::Path::new(&lines_file)
+                        .file_stem()
+                        .unwrap()
+                        .to_str()
+                        .unwrap(),
+                    interval,
+                    csv_lines.len()
+                ))
+                .as_bytes(),
+            )?;
+
+            let multiples = series_names.len() > 1 && series_names.len() < 12;
+            for f in 0..num_fields {
+                let (i, band) = field_sources[f];
+                let label = if inputs[i].num_bands() == 1 {
+                    inputs[i].get_short_filename()
+                } else {
+                    format!("{} (band {})", inputs[i].get_short_filename(), band + 1)
+                };
+                writer.write_all(format!("<h2>{}</h2>", label).as_bytes())?;
+
+                let graph = LineGraph {
+                    parent_id: format!("graph{}", f + 1),
+                    width: 700f64,
+                    height: 500f64,
+                    data_x: xdata[f].clone(),
+                    data_y: ydata[f].clone(),
+                    series_labels: series_names.clone(),
+                    x_axis_label: "Distance".to_string(),
+                    y_axis_label: label.clone(),
+                    draw_points: false,
+                    draw_gridlines: true,
+                    draw_legend: multiples,
+                    draw_grey_background: false,
+                };
+
+                writer.write_all(
+                    &format!(
+                        "<div id='graph{}' align=\"center\">{}</div>",
+                        f + 1,
+                        graph.get_svg()
+                    )
+                    .as_bytes(),
+                )?;
+            }
+
+            writer.write_all("</body>".as_bytes())?;
+
+            let _ = writer.flush();
+
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_html_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+
+                println!("Please see {} for the profile plots.", output_html_file);
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
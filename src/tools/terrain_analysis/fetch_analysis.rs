@@ -47,7 +47,7 @@ use std::thread;
 /// from raster digital elevation models. Computers & Geosciences, 19(6), 769-779.
 ///
 /// # See Also
-/// `DirectionalRelief`, `HorizonAngle`, `RelativeAspect`
+/// `DirectionalRelief`, `HorizonAngle`, `RelativeAspect`, `WinstralShelterIndex`, `WaterFetch`
 pub struct FetchAnalysis {
     name: String,
     description: String,
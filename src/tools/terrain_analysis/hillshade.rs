@@ -2,12 +2,14 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/06/2017
-Last Modified: 30/01/2020
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
+use crate::structures::Array2D;
 use crate::tools::*;
+use crate::utils::thread;
 use num_cpus;
 use std::env;
 use std::f64;
@@ -16,7 +18,6 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool performs a hillshade operation (also called shaded relief) on an input digital elevation model (DEM).
 /// The user must specify the  name of the input DEM and the output hillshade image name. Other parameters that must
@@ -40,12 +41,33 @@ use std::thread;
 /// are the illumination source altitude and azimuth respectively. Slope and aspect are calculated using
 /// Horn's (1981) 3rd-order finate difference method.
 ///
+/// The illumination `--mode` may be one of 'single' (the default), 'multidirectional', or 'multiazimuth'.
+/// The *multidirectional* mode uses the weighted multi-directional method of Mark (1992), in which four
+/// hillshades are calculated using illumination azimuths of 225, 270, 315, and 360 degrees and combined
+/// using weights of sin<sup>2</sup>(*a* - *Az*), i.e. each cell is weighted towards the light sources
+/// that strike it most obliquely. This reduces the loss of detail on slopes that face towards or away from
+/// a single light source. The *multiazimuth* mode averages the hillshades from a number (`--num_azimuths`)
+/// of evenly spaced azimuths, with shadowed directions contributing zero illumination. The result is similar
+/// to a diffuse, ambient-occlusion-like sky illumination. The `--azimuth` parameter is ignored in both of
+/// these modes.
+///
+/// By default, the output is a 16-bit integer raster with hillshade values in the range 0 to 32767. The
+/// `--bit_depth` parameter can be used to instead output an 8-bit raster (values 1-255, with zero used
+/// as NoData) or a 32-bit floating-point raster (values 0.0-1.0). Alternatively, when the `--hypsometric`
+/// flag is specified, the hillshade is blended with a hypsometric tint, i.e. a colour ramp of elevation
+/// running from greens in the lowlands through tans and browns to white at the highest elevations, and the
+/// output is a 24-bit RGB colour composite. The `--shade_weight` parameter (0.0-1.0) determines the
+/// relative strength of the shading in this blend. The bit depth is ignored for hypsometric outputs.
+///
 /// # Reference
 /// Gallant, J. C., and J. P. Wilson, 2000, Primary topographic attributes, in Terrain Analysis: Principles
 /// and Applications, edited by J. P. Wilson and J. C. Gallant pp. 51-86, John Wiley, Hoboken, N.J.
 ///
+/// Mark, R. K. (1992). Multidirectional, oblique-weighted, shaded-relief image of the Island of Hawaii.
+/// US Geological Survey Open-File Report 92-422.
+///
 /// # See Also
-/// `Aspect`, `Slope`
+/// `Aspect`, `Slope`, `HypsometricAnalysis`
 pub struct Hillshade {
     name: String,
     description: String,
@@ -109,9 +131,67 @@ impl Hillshade {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Illumination Mode".to_owned(),
+            flags: vec!["--mode".to_owned()],
+            description:
+                "Illumination mode; options are 'single', 'multidirectional', and 'multiazimuth'."
+                    .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "single".to_owned(),
+                "multidirectional".to_owned(),
+                "multiazimuth".to_owned(),
+            ]),
+            default_value: Some("single".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Azimuths".to_owned(),
+            flags: vec!["--num_azimuths".to_owned()],
+            description:
+                "Number of evenly spaced illumination azimuths used by the multiazimuth mode."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Bit Depth".to_owned(),
+            flags: vec!["--bit_depth".to_owned()],
+            description: "Output bit depth; options are '8', '16', and '32' (floating-point)."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "8".to_owned(),
+                "16".to_owned(),
+                "32".to_owned(),
+            ]),
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Blend with hypsometric tint?".to_owned(),
+            flags: vec!["--hypsometric".to_owned()],
+            description: "Optional flag indicating whether to blend the hillshade with a hypsometric tint, producing an RGB output.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Shade Weight".to_owned(),
+            flags: vec!["--shade_weight".to_owned()],
+            description: "Relative strength (0.0-1.0) of the shading when blending with the hypsometric tint.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
-        let p = format!("{}", env::current_dir().unwrap().display());
-        let e = format!("{}", env::current_exe().unwrap().display());
+        let p = format!("{}", env::current_dir().unwrap_or_default().display());
+        let e = format!("{}", env::current_exe().unwrap_or_default().display());
         let mut short_exe = e
             .replace(&p, "")
             .replace(".exe", "")
@@ -120,7 +200,8 @@ impl Hillshade {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --azimuth=315.0 --altitude=30.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --azimuth=315.0 --altitude=30.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --mode=multidirectional --altitude=45.0 --hypsometric --shade_weight=0.6", short_exe, name).replace("*", &sep);
 
         Hillshade {
             name: name,
@@ -178,6 +259,11 @@ impl WhiteboxTool for Hillshade {
         let mut azimuth = 315.0f64;
         let mut altitude = 30.0f64;
         let mut z_factor = 1f64;
+        let mut mode = String::from("single");
+        let mut num_azimuths = 16usize;
+        let mut bit_depth = 16usize;
+        let mut hypsometric = false;
+        let mut shade_weight = 0.5f64;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -243,9 +329,81 @@ impl WhiteboxTool for Hillshade {
                         .parse::<f64>()
                         .expect(&format!("Error parsing {}", flag_val));
                 }
+            } else if flag_val == "-mode" {
+                let val = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                mode = if val.contains("multid") {
+                    "multidirectional".to_string()
+                } else if val.contains("multia") || val.contains("ambient") {
+                    "multiazimuth".to_string()
+                } else {
+                    "single".to_string()
+                };
+            } else if flag_val == "-num_azimuths" {
+                num_azimuths = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-bit_depth" {
+                bit_depth = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-hypsometric" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    hypsometric = true;
+                }
+            } else if flag_val == "-shade_weight" {
+                shade_weight = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
             }
         }
 
+        if bit_depth != 8 && bit_depth != 16 && bit_depth != 32 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The bit depth must be one of 8, 16, or 32.",
+            ));
+        }
+        if mode == "multiazimuth" && num_azimuths < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of azimuths must be at least 2.",
+            ));
+        }
+        if shade_weight < 0f64 {
+            shade_weight = 0f64;
+        }
+        if shade_weight > 1f64 {
+            shade_weight = 1f64;
+        }
+
         if verbose {
             println!("***************{}", "*".repeat(self.get_tool_name().len()));
             println!("* Welcome to {} *", self.get_tool_name());
@@ -272,6 +430,22 @@ impl WhiteboxTool for Hillshade {
 
         let start = Instant::now();
 
+        // the illumination azimuths (in compass degrees) and their relative weights; weights of
+        // None indicate the aspect-dependent weighting of Mark's (1992) multidirectional method.
+        let azimuths: Vec<f64> = match mode.as_str() {
+            "multidirectional" => vec![225f64, 270f64, 315f64, 360f64],
+            "multiazimuth" => (0..num_azimuths)
+                .map(|a| 360f64 * a as f64 / num_azimuths as f64)
+                .collect(),
+            _ => vec![azimuth],
+        };
+        let azimuths = Arc::new(
+            azimuths
+                .iter()
+                .map(|az| (az - 90f64).to_radians())
+                .collect::<Vec<f64>>(),
+        );
+        let mode = Arc::new(mode);
         azimuth = (azimuth - 90f64).to_radians();
         altitude = altitude.to_radians();
         let sin_theta = altitude.sin();
@@ -287,17 +461,16 @@ impl WhiteboxTool for Hillshade {
             }
         }
 
-        let mut configs = input.configs.clone();
-        configs.data_type = DataType::I16;
-        configs.nodata = -32768f64;
-        let mut output = Raster::initialize_using_config(&output_file, &configs);
-        let out_nodata = output.configs.nodata;
+        let out_nodata = -32768f64;
         let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
 
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
+            let azimuths = azimuths.clone();
+            let mode = mode.clone();
             let tx1 = tx.clone();
             thread::spawn(move || {
                 let nodata = input.configs.nodata;
@@ -306,7 +479,10 @@ impl WhiteboxTool for Hillshade {
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut n: [f64; 8] = [0.0; 8];
                 let mut z: f64;
-                let (mut term1, mut term2, mut term3): (f64, f64, f64);
+                let (mut term1, mut term2): (f64, f64);
+                let mut hs: f64;
+                let mut weight: f64;
+                let mut sum_weights: f64;
                 let (mut fx, mut fy): (f64, f64);
                 let mut tan_slope: f64;
                 let mut aspect: f64;
@@ -333,8 +509,28 @@ impl WhiteboxTool for Hillshade {
                                 aspect = PI - ((fy / fx).atan()) + half_pi * (fx / (fx).abs());
                                 term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
                                 term2 = sin_theta / tan_slope;
-                                term3 = cos_theta * (azimuth - aspect).sin();
-                                z = term1 * (term2 - term3);
+                                z = 0f64;
+                                sum_weights = 0f64;
+                                for az in azimuths.iter() {
+                                    hs = term1 * (term2 - cos_theta * (az - aspect).sin());
+                                    weight = match mode.as_str() {
+                                        // sin^2 of the angle between the aspect and the compass
+                                        // azimuth, which is 90 degrees greater than az.
+                                        "multidirectional" => (az - aspect).cos().powi(2),
+                                        "multiazimuth" => {
+                                            if hs < 0f64 {
+                                                hs = 0f64;
+                                            }
+                                            1f64
+                                        }
+                                        _ => 1f64,
+                                    };
+                                    z += weight * hs;
+                                    sum_weights += weight;
+                                }
+                                if sum_weights > 0f64 {
+                                    z /= sum_weights;
+                                }
                             } else {
                                 z = 0.5;
                             }
@@ -350,6 +546,7 @@ impl WhiteboxTool for Hillshade {
             });
         }
 
+        let mut shade: Array2D<f64> = Array2D::new(rows, columns, out_nodata, out_nodata)?;
         let mut histo: [f64; 32768] = [0.0; 32768];
         let mut num_cells = 0.0;
         for row in 0..rows {
@@ -362,12 +559,12 @@ impl WhiteboxTool for Hillshade {
                     num_cells += 1.0;
                 }
             }
-            output.set_row_data(data.0, data.1);
+            shade.set_row_data(data.0, data.1);
 
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    println!("Performing analysis (Loop 1 of 2): {}%", progress);
                     old_progress = progress;
                 }
             }
@@ -395,13 +592,91 @@ impl WhiteboxTool for Hillshade {
             }
         }
 
-        if new_max > new_min {
-            output.configs.display_min = new_min as f64;
-            output.configs.display_max = new_max as f64;
+        let mut configs = input.configs.clone();
+        if hypsometric {
+            configs.data_type = DataType::RGBA32;
+            configs.photometric_interp = PhotometricInterpretation::RGB;
+            configs.nodata = 0f64;
+        } else if bit_depth == 8 {
+            configs.data_type = DataType::U8;
+            configs.nodata = 0f64;
+        } else if bit_depth == 32 {
+            configs.data_type = DataType::F32;
+            configs.nodata = out_nodata;
+        } else {
+            configs.data_type = DataType::I16;
+            configs.nodata = out_nodata;
+        }
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let nodata = input.configs.nodata;
+        let min_elev = input.configs.minimum;
+        let elev_range = input.configs.maximum - input.configs.minimum;
+        let (shade_min, shade_range) = if new_max > new_min {
+            (new_min as f64, (new_max - new_min) as f64)
+        } else {
+            (0f64, 32767f64)
+        };
+        let mut z: f64;
+        let mut s: f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                z = shade.get_value(row, col);
+                if z != out_nodata {
+                    if hypsometric {
+                        // stretch the shading between the display clip values and blend it with the tint
+                        s = ((z - shade_min) / shade_range).max(0f64).min(1f64);
+                        s = 1f64 - shade_weight + shade_weight * s;
+                        let elev = input.get_value(row, col);
+                        let t = if elev != nodata && elev_range > 0f64 {
+                            (elev - min_elev) / elev_range
+                        } else {
+                            0f64
+                        };
+                        let (r, g, b) = hypsometric_tint(t);
+                        let r = (r * s).round().max(0f64).min(255f64) as u32;
+                        let g = (g * s).round().max(0f64).min(255f64) as u32;
+                        let b = (b * s).round().max(0f64).min(255f64) as u32;
+                        output.set_value(
+                            row,
+                            col,
+                            ((255u32 << 24) | (b << 16) | (g << 8) | r) as f64,
+                        );
+                    } else if bit_depth == 8 {
+                        output.set_value(row, col, 1f64 + (z * 254f64 / 32767f64).round());
+                    } else if bit_depth == 32 {
+                        output.set_value(row, col, z / 32767f64);
+                    } else {
+                        output.set_value(row, col, z);
+                    }
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Performing analysis (Loop 2 of 2): {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if new_max > new_min && !hypsometric {
+            if bit_depth == 8 {
+                output.configs.display_min = 1f64 + (new_min as f64 * 254f64 / 32767f64).round();
+                output.configs.display_max = 1f64 + (new_max as f64 * 254f64 / 32767f64).round();
+            } else if bit_depth == 32 {
+                output.configs.display_min = new_min as f64 / 32767f64;
+                output.configs.display_max = new_max as f64 / 32767f64;
+            } else {
+                output.configs.display_min = new_min as f64;
+                output.configs.display_max = new_max as f64;
+            }
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
-        output.configs.palette = "grey.plt".to_string();
+        if !hypsometric {
+            output.configs.palette = "grey.plt".to_string();
+        }
         output.add_metadata_entry(format!(
             "Created by whitebox_tools\' {} tool",
             self.get_tool_name()
@@ -410,6 +685,12 @@ impl WhiteboxTool for Hillshade {
         output.add_metadata_entry(format!("Azimuth: {}", azimuth));
         output.add_metadata_entry(format!("Altitude: {}", altitude));
         output.add_metadata_entry(format!("Z-factor: {}", z_factor));
+        output.add_metadata_entry(format!("Mode: {}", mode));
+        if hypsometric {
+            output.add_metadata_entry(format!("Hypsometric tint shade weight: {}", shade_weight));
+        } else {
+            output.add_metadata_entry(format!("Bit depth: {}", bit_depth));
+        }
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
@@ -433,3 +714,29 @@ impl WhiteboxTool for Hillshade {
         Ok(())
     }
 }
+
+/// Returns the (red, green, blue) colour of a hypsometric tint for an elevation that has been
+/// normalized to the range 0.0-1.0.
+fn hypsometric_tint(t: f64) -> (f64, f64, f64) {
+    let stops: [(f64, f64, f64, f64); 7] = [
+        (0.0, 0.0, 97.0, 71.0),
+        (0.15, 16.0, 122.0, 47.0),
+        (0.3, 232.0, 215.0, 125.0),
+        (0.5, 161.0, 67.0, 0.0),
+        (0.7, 130.0, 30.0, 30.0),
+        (0.85, 161.0, 161.0, 161.0),
+        (1.0, 255.0, 255.0, 255.0),
+    ];
+    let t = t.max(0f64).min(1f64);
+    for i in 1..stops.len() {
+        if t <= stops[i].0 {
+            let w = (t - stops[i - 1].0) / (stops[i].0 - stops[i - 1].0);
+            return (
+                stops[i - 1].1 + w * (stops[i].1 - stops[i - 1].1),
+                stops[i - 1].2 + w * (stops[i].2 - stops[i - 1].2),
+                stops[i - 1].3 + w * (stops[i].3 - stops[i - 1].3),
+            );
+        }
+    }
+    (255f64, 255f64, 255f64)
+}
@@ -41,6 +41,9 @@ use std::thread;
 ///
 /// Ray-tracing is a highly computationally intensive task and therefore this tool may take considerable time to operate for
 /// larger sized DEMs. Maximum upwind slope is best displayed using a Grey scale palette that is inverted.
+///
+/// # See Also
+/// `SkyViewFactor`, `Openness`
 pub struct HorizonAngle {
     name: String,
     description: String,
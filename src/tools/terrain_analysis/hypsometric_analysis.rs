@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 30/01/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -24,7 +24,7 @@ use std::process::Command;
 /// distribution function of elevations in a geographical area.
 ///
 /// # See Also
-/// `SlopeVsElevationPlot`
+/// `SlopeVsElevationPlot`, `WatershedHypsometry`
 pub struct HypsometricAnalysis {
     name: String,
     description: String,
@@ -340,12 +340,8 @@ impl WhiteboxTool for HypsometricAnalysis {
                 }
                 let watershed = Raster::new(&watershed_file, "r")?;
                 let ws_nodata = watershed.configs.nodata;
-                if watershed.configs.rows as isize != rows
-                    || watershed.configs.columns as isize != columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                            "The input DEM and watershed rasters should have the same extents (rows and columns)."));
-                }
+                // make sure the inputs share the same grid and CRS
+                let watershed = align_raster_input(&input, watershed)?;
 
                 let watershed_min = watershed.configs.minimum;
                 let watershed_max = watershed.configs.maximum;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 28/08/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -18,7 +18,28 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
-/// Calculates the maximum difference from mean elevation over a range of spatial scales.
+/// This tool can be used to calculate the maximum difference from mean elevation, *DIFFmax*, for each grid cell
+/// in a digital elevation model (DEM) across a range of spatial scales. The difference from mean elevation, also
+/// known as the topographic position index (TPI), is the difference between the elevation of a grid cell and the
+/// mean elevation of the surrounding neighbourhood. Unlike the deviation from mean elevation (*DEV*), *DIFF* is
+/// not normalized by local relief and is therefore expressed in elevation units. The multi-scaled calculation
+/// of *DIFFmax* uses an integral image approach (Crow, 1984), such that the cost of each evaluated scale is
+/// independent of the filter size, and all scales are evaluated in a single pass over the DEM.
+///
+/// The user must specify the name (`--dem`) of the input DEM. The range of scales that are evaluated are determined
+/// by the user-specified `--min_scale`, `--max_scale`, and `--step` parameters. The scale parameters are in units of
+/// grid cells and specify kernel size "radii" (*r*), such that the filter dimension is *d* = 2*r* + 1. Every grid
+/// cell is assigned the *DIFF* value with the largest absolute magnitude across the evaluated scales.
+///
+/// The user must specify the file names of two output rasters, including the magnitude (*DIFFmax*; `--out_mag`) and
+/// a second raster that assigns each pixel the scale at which *DIFFmax* is encountered (`--out_scale`).
+///
+/// # Reference
+/// Lindsay J, Cockburn J, Russell H. 2015. An integral image approach to performing multi-scale
+/// topographic position analysis. Geomorphology, 245: 51-61.
+///
+/// # See Also
+/// `DiffFromMeanElev`, `MaxElevationDeviation`, `MultiscaleRoughness`
 pub struct MaxDifferenceFromMean {
     name: String,
     description: String,
@@ -303,88 +324,80 @@ impl WhiteboxTool for MaxDifferenceFromMean {
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         let mut output_scale = Raster::initialize_using_file(&output_scale_file, &input);
 
-        let num_loops = (max_scale - min_scale) / step;
-        let mut loop_num = 0;
-        for midpoint in (min_scale..max_scale).filter(|s| (s - min_scale) % step == 0) {
-            // .step_by(step) { once step_by is stabilized
-            loop_num += 1;
-            let (tx, rx) = mpsc::channel();
-            for tid in 0..num_procs {
-                let input_data = input.clone();
-                let i = i.clone();
-                let i_n = i_n.clone();
-                let tx1 = tx.clone();
-                thread::spawn(move || {
-                    let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                    let mut n: i32;
-                    let (mut mean, mut sum): (f64, f64);
-                    let mut z: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        y1 = row - midpoint - 1;
-                        if y1 < 0 {
-                            y1 = 0;
-                        }
-
-                        y2 = row + midpoint;
-                        if y2 >= rows {
-                            y2 = rows - 1;
-                        }
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+        let scales: Vec<isize> = (min_scale..max_scale)
+            .filter(|s| (s - min_scale) % step == 0)
+            .collect();
+        let scales = Arc::new(scales);
+
+        // All scales are evaluated for each row in a single pass over the data, since
+        // the integral images make the cost of each evaluation independent of scale.
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input_data = input.clone();
+            let i = i.clone();
+            let i_n = i_n.clone();
+            let scales = scales.clone();
+            let tx1 = tx.clone();
+            thread::spawn(move || {
+                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+                let mut n: i32;
+                let (mut mean, mut sum): (f64, f64);
+                let mut z: f64;
+                let mut val: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut mag = vec![nodata; columns as usize];
+                    let mut scale = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input_data[(row, col)];
+                        if z != nodata {
+                            for &midpoint in scales.iter() {
+                                y1 = row - midpoint - 1;
+                                if y1 < 0 {
+                                    y1 = 0;
+                                }
+                                y2 = row + midpoint;
+                                if y2 >= rows {
+                                    y2 = rows - 1;
+                                }
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
                                 }
-
                                 x2 = col + midpoint;
                                 if x2 >= columns {
                                     x2 = columns - 1;
                                 }
                                 n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
+                                val = 0f64;
                                 if n > 0 {
                                     sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
                                     mean = sum / n as f64;
-                                    data[col as usize] = z - mean;
-                                } else {
-                                    data[col as usize] = 0f64;
+                                    val = z - mean;
+                                }
+                                if mag[col as usize] == nodata
+                                    || val * val > mag[col as usize] * mag[col as usize]
+                                {
+                                    mag[col as usize] = val;
+                                    scale[col as usize] = midpoint as f64;
                                 }
                             }
                         }
-
-                        tx1.send((row, data)).unwrap();
                     }
-                });
-            }
 
-            let (mut z1, mut z2): (f64, f64);
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns {
-                    z2 = data[col as usize];
-                    if z2 != nodata {
-                        z1 = output_mag[(row, col)];
-                        if z1 != nodata {
-                            if z2 * z2 > z1 * z1 {
-                                output_mag[(row, col)] = z2;
-                                output_scale[(row, col)] = midpoint as f64;
-                            }
-                        } else {
-                            output_mag[(row, col)] = z2;
-                            output_scale[(row, col)] = midpoint as f64;
-                        }
-                    }
+                    tx1.send((row, mag, scale)).unwrap();
                 }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!(
-                            "Progress (Loop {} of {}): {}%",
-                            loop_num, num_loops, progress
-                        );
-                        old_progress = progress;
-                    }
+            });
+        }
+
+        for r in 0..rows {
+            let (row, mag, scale) = rx.recv().expect("Error receiving data from thread.");
+            output_mag.set_row_data(row, mag);
+            output_scale.set_row_data(row, scale);
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
         }
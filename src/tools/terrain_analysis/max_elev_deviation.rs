@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: July 20, 2017
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -25,7 +25,7 @@ use std::thread;
 /// local relief, and so is normalized to the local surface roughness. The multi-scaled calculation
 /// of *DEVmax* utilizes an integral image approach (Crow, 1984) to ensure highly efficient
 /// filtering that is invariant with filter size, which is the algorithm characteristic that allows for
-/// this densely sampled multi-scale analysis. In this way, `MaxElevationDeviation` allows users
+/// this densely sampled multi-scale analysis. All scales are evaluated in a single pass over the DEM. In this way, `MaxElevationDeviation` allows users
 /// to estimate the locally optimal scale with which to estimate *DEV* on a pixel-by-pixel basis.
 /// This multi-scaled version of local topographic position can reveal significant terrain
 /// characteristics and can aid with soil, vegetation, landform, and other mapping applications
@@ -52,7 +52,7 @@ use std::thread;
 /// topographic position analysis. Geomorphology, 245: 51-61.
 ///
 /// # See Also
-/// `DevFromMeanElev`, `MaxDifferenceFromMean`, `MultiscaleElevationPercentile`
+/// `DevFromMeanElev`, `MaxDifferenceFromMean`, `MultiscaleElevationPercentile`, `MultiscaleRoughness`
 pub struct MaxElevationDeviation {
     name: String,
     description: String,
@@ -353,48 +353,53 @@ impl WhiteboxTool for MaxElevationDeviation {
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         let mut output_scale = Raster::initialize_using_file(&output_scale_file, &input);
 
-        let num_loops = (max_scale - min_scale) / step;
-        let mut loop_num = 0;
-        for midpoint in (min_scale..max_scale).filter(|s| (s - min_scale) % step == 0) {
-            // .step_by(step) { once step_by is stabilized
-            loop_num += 1;
-            let (tx, rx) = mpsc::channel();
-            for tid in 0..num_procs {
-                let input_data = input.clone();
-                let i = i.clone();
-                let i2 = i2.clone();
-                let i_n = i_n.clone();
-                let tx1 = tx.clone();
-                thread::spawn(move || {
-                    let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                    let mut n: i32;
-                    let (mut mean, mut sum, mut sum_sqr): (f64, f64, f64);
-                    let (mut v, mut s): (f64, f64);
-                    let mut z: f64;
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        y1 = row - midpoint - 1;
-                        if y1 < 0 {
-                            y1 = 0;
-                        }
-
-                        y2 = row + midpoint;
-                        if y2 >= rows {
-                            y2 = rows - 1;
-                        }
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z = input_data[(row, col)];
-                            if z != nodata {
+        let scales: Vec<isize> = (min_scale..max_scale)
+            .filter(|s| (s - min_scale) % step == 0)
+            .collect();
+        let scales = Arc::new(scales);
+
+        // All scales are evaluated for each row in a single pass over the data, since
+        // the integral images make the cost of each evaluation independent of scale.
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input_data = input.clone();
+            let i = i.clone();
+            let i2 = i2.clone();
+            let i_n = i_n.clone();
+            let scales = scales.clone();
+            let tx1 = tx.clone();
+            thread::spawn(move || {
+                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
+                let mut n: i32;
+                let (mut mean, mut sum, mut sum_sqr): (f64, f64, f64);
+                let (mut v, mut s): (f64, f64);
+                let mut z: f64;
+                let mut val: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut mag = vec![nodata; columns as usize];
+                    let mut scale = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input_data[(row, col)];
+                        if z != nodata {
+                            for &midpoint in scales.iter() {
+                                y1 = row - midpoint - 1;
+                                if y1 < 0 {
+                                    y1 = 0;
+                                }
+                                y2 = row + midpoint;
+                                if y2 >= rows {
+                                    y2 = rows - 1;
+                                }
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
                                 }
-
                                 x2 = col + midpoint;
                                 if x2 >= columns {
                                     x2 = columns - 1;
                                 }
                                 n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
+                                val = 0f64;
                                 if n > 0 {
                                     sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
                                     sum_sqr =
@@ -403,48 +408,33 @@ impl WhiteboxTool for MaxElevationDeviation {
                                     if v > 0f64 {
                                         s = v.sqrt();
                                         mean = sum / n as f64;
-                                        data[col as usize] = (z - mean) / s;
-                                    } else {
-                                        data[col as usize] = 0f64;
+                                        val = (z - mean) / s;
                                     }
-                                } else {
-                                    data[col as usize] = 0f64;
+                                }
+                                if mag[col as usize] == nodata
+                                    || val * val > mag[col as usize] * mag[col as usize]
+                                {
+                                    mag[col as usize] = val;
+                                    scale[col as usize] = midpoint as f64;
                                 }
                             }
                         }
-
-                        tx1.send((row, data)).unwrap();
                     }
-                });
-            }
 
-            let (mut z1, mut z2): (f64, f64);
-            for r in 0..rows {
-                let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                for col in 0..columns {
-                    z2 = data[col as usize];
-                    if z2 != nodata {
-                        z1 = output_mag[(row, col)];
-                        if z1 != nodata {
-                            if z2 * z2 > z1 * z1 {
-                                output_mag[(row, col)] = z2;
-                                output_scale[(row, col)] = midpoint as f64;
-                            }
-                        } else {
-                            output_mag[(row, col)] = z2;
-                            output_scale[(row, col)] = midpoint as f64;
-                        }
-                    }
+                    tx1.send((row, mag, scale)).unwrap();
                 }
-                if verbose {
-                    progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!(
-                            "Progress (Loop {} of {}): {}%",
-                            loop_num, num_loops, progress
-                        );
-                        old_progress = progress;
-                    }
+            });
+        }
+
+        for r in 0..rows {
+            let (row, mag, scale) = rx.recv().expect("Error receiving data from thread.");
+            output_mag.set_row_data(row, mag);
+            output_scale.set_row_data(row, scale);
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
                 }
             }
         }
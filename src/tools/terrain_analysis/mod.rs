@@ -3,6 +3,8 @@ mod aspect;
 mod average_normal_vector_angular_deviation;
 mod circular_variance_of_aspect;
 mod contours_from_raster;
+mod coregister_dems;
+mod dem_quality_report;
 mod dev_from_mean_elev;
 mod diff_from_mean_elev;
 mod directional_relief;
@@ -13,6 +15,7 @@ mod elev_above_pit;
 mod elev_percentile;
 mod elev_relative_to_min_max;
 mod elev_relative_to_watershed_min_max;
+mod extract_profiles;
 mod feature_preserving_smoothing;
 mod fetch_analysis;
 mod fill_missing_data;
@@ -37,6 +40,7 @@ mod multiscale_std_dev_normals_signature;
 mod multiscale_topographic_position_image;
 mod num_downslope_neighbours;
 mod num_upslope_neighbours;
+mod openness;
 mod pennock_landform_class;
 mod percent_elev_range;
 mod plan_curvature;
@@ -47,9 +51,12 @@ mod relative_stream_power_index;
 mod relative_topographic_position;
 mod remove_off_terrain_objects;
 mod ruggedness_index;
+mod saga_wetness_index;
 mod sediment_transport_index;
+mod sky_view_factor;
 mod slope;
 mod slope_vs_elev_plot;
+mod solar_radiation;
 mod spherical_std_dev_of_normals;
 mod standard_deviation_of_slope;
 mod surface_area_ratio;
@@ -57,13 +64,20 @@ mod tan_curvature;
 mod total_curvature;
 mod viewshed;
 mod visibility_index;
+mod water_fetch;
+mod watershed_hypsometry;
 mod wetness_index;
+mod wind_exposition_index;
+mod wind_exposure;
+mod winstral_shelter_index;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::aspect::Aspect;
 pub use self::average_normal_vector_angular_deviation::AverageNormalVectorAngularDeviation;
 pub use self::circular_variance_of_aspect::CircularVarianceOfAspect;
 pub use self::contours_from_raster::ContoursFromRaster;
+pub use self::coregister_dems::CoregisterDems;
+pub use self::dem_quality_report::DemQualityReport;
 pub use self::dev_from_mean_elev::DevFromMeanElev;
 pub use self::diff_from_mean_elev::DiffFromMeanElev;
 pub use self::directional_relief::DirectionalRelief;
@@ -74,6 +88,7 @@ pub use self::elev_above_pit::ElevAbovePit;
 pub use self::elev_percentile::ElevPercentile;
 pub use self::elev_relative_to_min_max::ElevRelativeToMinMax;
 pub use self::elev_relative_to_watershed_min_max::ElevRelativeToWatershedMinMax;
+pub use self::extract_profiles::ExtractProfiles;
 pub use self::feature_preserving_smoothing::FeaturePreservingSmoothing;
 pub use self::fetch_analysis::FetchAnalysis;
 pub use self::fill_missing_data::FillMissingData;
@@ -98,6 +113,7 @@ pub use self::multiscale_std_dev_normals_signature::MultiscaleStdDevNormalsSigna
 pub use self::multiscale_topographic_position_image::MultiscaleTopographicPositionImage;
 pub use self::num_downslope_neighbours::NumDownslopeNeighbours;
 pub use self::num_upslope_neighbours::NumUpslopeNeighbours;
+pub use self::openness::Openness;
 pub use self::pennock_landform_class::PennockLandformClass;
 pub use self::percent_elev_range::PercentElevRange;
 pub use self::plan_curvature::PlanCurvature;
@@ -108,9 +124,12 @@ pub use self::relative_stream_power_index::StreamPowerIndex;
 pub use self::relative_topographic_position::RelativeTopographicPosition;
 pub use self::remove_off_terrain_objects::RemoveOffTerrainObjects;
 pub use self::ruggedness_index::RuggednessIndex;
+pub use self::saga_wetness_index::SagaWetnessIndex;
 pub use self::sediment_transport_index::SedimentTransportIndex;
+pub use self::sky_view_factor::SkyViewFactor;
 pub use self::slope::Slope;
 pub use self::slope_vs_elev_plot::SlopeVsElevationPlot;
+pub use self::solar_radiation::SolarRadiation;
 pub use self::spherical_std_dev_of_normals::SphericalStdDevOfNormals;
 pub use self::standard_deviation_of_slope::StandardDeviationOfSlope;
 pub use self::surface_area_ratio::SurfaceAreaRatio;
@@ -118,4 +137,8 @@ pub use self::tan_curvature::TangentialCurvature;
 pub use self::total_curvature::TotalCurvature;
 pub use self::viewshed::Viewshed;
 pub use self::visibility_index::VisibilityIndex;
+pub use self::water_fetch::WaterFetch;
+pub use self::watershed_hypsometry::WatershedHypsometry;
 pub use self::wetness_index::WetnessIndex;
+pub use self::wind_exposition_index::WindExpositionIndex;
+pub use self::winstral_shelter_index::WinstralShelterIndex;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/02/2018
-Last Modified: 22/10/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -20,6 +20,24 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 
+/// This tool calculates surface roughness over a range of spatial scales (Lindsay et al., 2019). Roughness is
+/// measured as the average angular deviation (in degrees) between the surface normals of the original DEM and
+/// those of a smoothed DEM, calculated within a local neighbourhood. The smoothing is performed using an integral
+/// image-based mean filter of the same size as the neighbourhood, such that roughness at each scale characterizes
+/// the topographic complexity at finer scales than the filter size.
+///
+/// The user must specify the name (`--dem`) of the input DEM. The range of scales that are evaluated are determined
+/// by the user-specified `--min_scale`, `--max_scale`, and `--step` parameters, which are in units of grid cells and
+/// specify kernel size "radii" (*r*), such that the filter dimension is *d* = 2*r* + 1. Every grid cell is assigned
+/// the maximum roughness value across the evaluated scales (`--out_mag`), and a second raster (`--out_scale`) records
+/// the scale at which the maximum roughness is encountered.
+///
+/// # Reference
+/// Lindsay, J. B., Newman, D. R., & Francioni, A. (2019). Scale-optimized surface roughness for topographic
+/// analysis. Geosciences, 9(7), 322.
+///
+/// # See Also
+/// `MultiscaleRoughnessSignature`, `MaxElevationDeviation`, `MaxDifferenceFromMean`
 pub struct MultiscaleRoughness {
     name: String,
     description: String,
@@ -399,13 +417,15 @@ impl WhiteboxTool for MultiscaleRoughness {
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         let mut output_scale = Raster::initialize_using_file(&output_scale_file, &input);
 
-        // let num_loops = (max_scale - min_scale) / step;
-        // let mut loop_num = 0;
+        let num_loops = (max_scale - min_scale + step - 1) / step;
+        let mut loop_num = 0;
         for midpoint in (min_scale..max_scale).filter(|s| (s - min_scale) % step == 0) {
             // .step_by(step) { once step_by is stabilized
-            // loop_num += 1;
-
-            println!("Loop {} / {}", midpoint - min_scale, max_scale - min_scale);
+            loop_num += 1;
+            if verbose {
+                progress = (100.0_f64 * loop_num as f64 / num_loops as f64) as usize;
+                println!("Loop {} of {}: {}%", loop_num, num_loops, progress);
+            }
 
             ////////////////////////////////////////////////////////////////////////////
             // Use the integral image to smooth the DEM at a scale of the filter size //
@@ -256,11 +256,18 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
         if verbose {
             println!("Reading meso-scale DEV data...")
         };
-        let input_g = Arc::new(Raster::new(&input2_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let input_g = Arc::new(align_raster_input(
+            &input_r,
+            Raster::new(&input2_file, "r")?,
+        )?);
         if verbose {
             println!("Reading local-scale DEV data...")
         };
-        let input_b = Arc::new(Raster::new(&input3_file, "r")?);
+        let input_b = Arc::new(align_raster_input(
+            &input_r,
+            Raster::new(&input3_file, "r")?,
+        )?);
 
         let rows = input_r.configs.rows as isize;
         let columns = input_r.configs.columns as isize;
@@ -276,24 +283,6 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
 
         let start = Instant::now();
 
-        // make sure the input files have the same size
-        if input_r.configs.rows != input_g.configs.rows
-            || input_r.configs.columns != input_g.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-        if input_r.configs.rows != input_b.configs.rows
-            || input_r.configs.columns != input_b.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
@@ -0,0 +1,432 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the positive and negative topographic openness (Yokoyama et al., 2002) for each grid cell
+/// in an input digital elevation model (DEM). Openness is an angular measure of the relation between surface relief
+/// and horizontal distance. Positive openness (`--pos_output`) is the mean, over a number of azimuth directions, of
+/// the zenith angle to the horizon, i.e. 90 degrees minus the maximum elevation angle along each direction. It is
+/// high on convex landforms, such as ridges and peaks, and low within valleys and depressions. Negative openness
+/// (`--neg_output`) is the mean nadir angle, i.e. 90 degrees minus the maximum depression angle along each direction,
+/// and is high within concave landforms. Both measures are expressed in degrees and, unlike hillshading, do not
+/// depend on an illumination direction, making them useful for relief visualization, e.g. in archaeological
+/// prospection, and for landform classification. At least one of the two outputs must be specified.
+///
+/// The algorithm traces rays from each grid cell along a number of evenly spaced azimuth directions
+/// (`--num_directions`; default 8) out to a maximum search distance (`--max_dist`; in xy units), sampling the DEM
+/// surface at one grid cell intervals along each ray using bilinear interpolation. If the maximum search distance is
+/// unspecified, a distance of 100 grid cells is used. Rays are terminated at the edge of the DEM and at NoData
+/// cells, and therefore values near the edges of the DEM should be interpreted with caution.
+///
+/// # Reference
+/// Yokoyama, R., Shirasawa, M., and Pike, R. J. (2002). Visualizing topography by openness: a new application of
+/// image processing to digital elevation models. Photogrammetric Engineering and Remote Sensing, 68(3), 257-266.
+///
+/// # See Also
+/// `Openness`, `HorizonAngle`, `VisibilityIndex`
+pub struct Openness {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Openness {
+    /// public constructor
+    pub fn new() -> Openness {
+        let name = "Openness".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates positive and negative topographic openness for each grid cell in an input DEM."
+            .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Positive Openness File".to_owned(),
+            flags: vec!["--pos_output".to_owned()],
+            description: "Output positive openness raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Negative Openness File".to_owned(),
+            flags: vec!["--neg_output".to_owned()],
+            description: "Output negative openness raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Search Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of evenly spaced azimuth directions to search.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Optional maximum search distance (in xy units; 100 grid cells if unspecified)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif --pos_output=pos_openness.tif --neg_output=neg_openness.tif --num_directions=16 --max_dist=250.0", short_exe, name).replace("*", &sep);
+
+        Openness {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Openness {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut pos_output_file = String::new();
+        let mut neg_output_file = String::new();
+        let mut num_directions = 8usize;
+        let mut max_dist = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-pos_output" {
+                if keyval {
+                    pos_output_file = vec[1].to_string();
+                } else {
+                    pos_output_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-neg_output" {
+                if keyval {
+                    neg_output_file = vec[1].to_string();
+                } else {
+                    neg_output_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-num_directions" {
+                if keyval {
+                    num_directions = vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize;
+                } else {
+                    num_directions = args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize;
+                }
+            } else if flag_val == "-max_dist" {
+                if keyval {
+                    max_dist = vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val));
+                } else {
+                    max_dist = args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val));
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if pos_output_file.is_empty() && neg_output_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of the positive (--pos_output) and negative (--neg_output) openness outputs must be specified.",
+            ));
+        }
+        if !pos_output_file.is_empty()
+            && !pos_output_file.contains(&sep)
+            && !pos_output_file.contains("/")
+        {
+            pos_output_file = format!("{}{}", working_directory, pos_output_file);
+        }
+        if !neg_output_file.is_empty()
+            && !neg_output_file.contains(&sep)
+            && !neg_output_file.contains("/")
+        {
+            neg_output_file = format!("{}{}", working_directory, neg_output_file);
+        }
+
+        if num_directions < 4 {
+            num_directions = 4;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mut mid_lat = (input.configs.north - input.configs.south) / 2.0;
+            if mid_lat <= 90.0 && mid_lat >= -90.0 {
+                mid_lat = mid_lat.to_radians();
+                res_x = res_x * (113200.0 * mid_lat.cos());
+                res_y = res_y * (113200.0 * mid_lat.cos());
+            }
+        }
+        let cell_size = (res_x + res_y) / 2.0;
+        if !(max_dist > 0f64) {
+            max_dist = 100f64 * cell_size;
+        }
+        let num_steps = (max_dist / cell_size).ceil().max(1f64) as usize;
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                // unit steps (in grid cells) along each search direction
+                let directions: Vec<(f64, f64)> = (0..num_directions)
+                    .map(|d| {
+                        let azimuth = (d as f64 * 360f64 / num_directions as f64).to_radians();
+                        (-azimuth.cos(), azimuth.sin())
+                    })
+                    .collect();
+                let (mut r, mut c): (f64, f64);
+                let (mut dist, mut z, mut z0): (f64, f64, f64);
+                let (mut tan_max, mut tan_min): (f64, f64);
+                let (mut sum_pos, mut sum_neg): (f64, f64);
+                let mut n: usize;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut pos_data: Vec<f64> = vec![nodata; columns as usize];
+                    let mut neg_data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z0 = input[(row, col)];
+                        if z0 != nodata {
+                            sum_pos = 0f64;
+                            sum_neg = 0f64;
+                            n = 0;
+                            for &(dr, dc) in &directions {
+                                tan_max = f64::NEG_INFINITY;
+                                tan_min = f64::INFINITY;
+                                let mut found = false;
+                                for s in 1..=num_steps {
+                                    r = row as f64 + dr * s as f64;
+                                    c = col as f64 + dc * s as f64;
+                                    if r < 0f64
+                                        || c < 0f64
+                                        || r > (rows - 1) as f64
+                                        || c > (columns - 1) as f64
+                                    {
+                                        break;
+                                    }
+                                    z = input.get_interpolated_value(
+                                        r,
+                                        c,
+                                        InterpolationMethod::Bilinear,
+                                    );
+                                    if z == nodata {
+                                        break;
+                                    }
+                                    found = true;
+                                    dist = ((dr * s as f64 * res_y).powi(2)
+                                        + (dc * s as f64 * res_x).powi(2))
+                                    .sqrt();
+                                    tan_max = tan_max.max((z - z0) / dist);
+                                    tan_min = tan_min.min((z - z0) / dist);
+                                }
+                                if found {
+                                    sum_pos += 90f64 - tan_max.atan().to_degrees();
+                                    sum_neg += 90f64 + tan_min.atan().to_degrees();
+                                    n += 1;
+                                }
+                            }
+                            if n > 0 {
+                                pos_data[col as usize] = sum_pos / n as f64;
+                                neg_data[col as usize] = sum_neg / n as f64;
+                            }
+                        }
+                    }
+                    tx.send((row, pos_data, neg_data)).unwrap();
+                }
+            });
+        }
+
+        let mut pos_output = Raster::initialize_using_file(&pos_output_file, &input);
+        let mut neg_output = Raster::initialize_using_file(&neg_output_file, &input);
+        for r in 0..rows {
+            let (row, pos_data, neg_data) = rx.recv().expect("Error receiving data from thread.");
+            pos_output.set_row_data(row, pos_data);
+            neg_output.set_row_data(row, neg_data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        for (mut output, output_file) in vec![
+            (pos_output, pos_output_file.clone()),
+            (neg_output, neg_output_file.clone()),
+        ] {
+            if output_file.is_empty() {
+                continue;
+            }
+            output.configs.data_type = DataType::F32;
+            output.configs.palette = "grey.plt".to_string();
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Num. directions: {}", num_directions));
+            output.add_metadata_entry(format!("Max dist: {}", max_dist));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
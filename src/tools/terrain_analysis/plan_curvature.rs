@@ -2,12 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/06/2017
-Last Modified: 30/01/2019
+Last Modified: 15/10/2026
 License: MIT
 */
 
 use crate::raster::*;
 use crate::tools::*;
+use crate::utils::simd;
 use num_cpus;
 use std::env;
 use std::f64;
@@ -256,11 +257,21 @@ impl WhiteboxTool for PlanCurvature {
             let tx1 = tx.clone();
             thread::spawn(move || {
                 let nodata = input.configs.nodata;
-                let columns = input.configs.columns as isize;
-                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
-                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
-                let mut n: [f64; 8] = [0.0; 8];
-                let mut z: f64;
+                let columns = input.configs.columns;
+                // the 3 x 3 finite-difference weights of the first and second derivatives
+                let k1 = z_factor / cell_size_times2;
+                let k2 = z_factor / cell_size_sqrd;
+                let k4 = z_factor / four_times_cell_size_sqrd;
+                let weights_zx = [0.0, 0.0, 0.0, -k1, 0.0, k1, 0.0, 0.0, 0.0];
+                let weights_zy = [0.0, k1, 0.0, 0.0, 0.0, 0.0, 0.0, -k1, 0.0];
+                let weights_zxx = [0.0, 0.0, 0.0, k2, -2.0 * k2, k2, 0.0, 0.0, 0.0];
+                let weights_zyy = [0.0, k2, 0.0, 0.0, -2.0 * k2, 0.0, 0.0, k2, 0.0];
+                let weights_zxy = [-k4, 0.0, k4, 0.0, 0.0, 0.0, k4, 0.0, -k4];
+                let mut d_zx = vec![0f64; columns];
+                let mut d_zy = vec![0f64; columns];
+                let mut d_zxx = vec![0f64; columns];
+                let mut d_zyy = vec![0f64; columns];
+                let mut d_zxy = vec![0f64; columns];
                 let (mut zx, mut zy, mut zxx, mut zyy, mut zxy, mut zx2, mut zy2): (
                     f64,
                     f64,
@@ -272,31 +283,28 @@ impl WhiteboxTool for PlanCurvature {
                 );
                 let mut p: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![nodata; columns as usize];
+                    let above = input.get_row_data(row - 1);
+                    let center = input.get_row_data(row);
+                    let below = input.get_row_data(row + 1);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_zx, &mut d_zx);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_zy, &mut d_zy);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_zxx, &mut d_zxx);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_zyy, &mut d_zyy);
+                    simd::window3x3(&above, &center, &below, nodata, &weights_zxy, &mut d_zxy);
+                    let mut data = vec![nodata; columns];
                     for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            z = z * z_factor;
-                            for c in 0..8 {
-                                n[c] = input[(row + d_y[c], col + d_x[c])];
-                                if n[c] != nodata {
-                                    n[c] = n[c] * z_factor;
-                                } else {
-                                    n[c] = z;
-                                }
-                            }
+                        if center[col] != nodata {
                             // calculate curvature
-                            zx = (n[1] - n[5]) / cell_size_times2;
-                            zy = (n[7] - n[3]) / cell_size_times2;
-                            zxx = (n[1] - 2.0f64 * z + n[5]) / cell_size_sqrd;
-                            zyy = (n[7] - 2.0f64 * z + n[3]) / cell_size_sqrd;
-                            zxy = (-n[6] + n[0] + n[4] - n[2]) / four_times_cell_size_sqrd;
+                            zx = d_zx[col];
+                            zy = d_zy[col];
+                            zxx = d_zxx[col];
+                            zyy = d_zyy[col];
+                            zxy = d_zxy[col];
                             zx2 = zx * zx;
                             zy2 = zy * zy;
                             p = zx2 + zy2;
                             if p > 0.0f64 {
-                                data[col as usize] = ((zxx * zy2 - 2.0f64 * zxy * zx * zy
-                                    + zyy * zx2)
+                                data[col] = ((zxx * zy2 - 2.0f64 * zxy * zx * zy + zyy * zx2)
                                     / p.powf(1.5f64))
                                 .to_degrees()
                                     * 100f64;
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/02/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -30,7 +30,7 @@ use std::process::Command;
 /// use the `LongProfile` tool instead.
 ///
 /// # See Also
-/// `LongProfile`, `HypsometricAnalysis`
+/// `LongProfile`, `HypsometricAnalysis`, `ExtractProfiles`
 pub struct Profile {
     name: String,
     description: String,
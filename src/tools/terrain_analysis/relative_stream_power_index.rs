@@ -233,7 +233,8 @@ impl WhiteboxTool for StreamPowerIndex {
             println!("Reading data...")
         };
         let sca = Arc::new(Raster::new(&sca_file, "r")?);
-        let slope = Arc::new(Raster::new(&slope_file, "r")?);
+        // make sure the inputs share the same grid and CRS
+        let slope = Arc::new(align_raster_input(&sca, Raster::new(&slope_file, "r")?)?);
 
         let start = Instant::now();
         let rows = sca.configs.rows as isize;
@@ -241,14 +242,6 @@ impl WhiteboxTool for StreamPowerIndex {
         let sca_nodata = sca.configs.nodata;
         let slope_nodata = slope.configs.nodata;
 
-        // make sure the input files have the same size
-        if sca.configs.rows != slope.configs.rows || sca.configs.columns != slope.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
         // calculate the number of downslope cells
         let num_procs = num_cpus::get() as isize;
         let (tx, rx) = mpsc::channel();
@@ -0,0 +1,521 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::mfd_flow_accumulation;
+use crate::raster::*;
+use crate::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the SAGA wetness index (SWI) of Böhner et al. (2002), a variant of the
+/// topographic wetness index (`WetnessIndex`), ln(*a* / tan(β)), that replaces the specific contributing
+/// area (*a*) with a *modified catchment area*. The modified catchment area assumes that grid cells
+/// situated in valley floors with small vertical distances to a channel possess a more realistic,
+/// higher potential soil moisture than is predicted by the standard wetness index. It is calculated by
+/// iteratively assigning each grid cell the larger of its own catchment area and the maximum catchment
+/// area of its eight neighbours, weighted by the factor (1 / *t*)<sup>exp(*t*<sup>β</sup>)</sup>, until no
+/// further changes occur. Here, β is the local slope gradient (in radians) and *t* is the suction
+/// parameter (`--suction`, 10 by default). Lower suction values spread the high catchment areas of
+/// valley bottoms further onto the adjacent low-gradient terrain.
+///
+/// The user must specify the input digital elevation model (DEM; `--dem`), which should have been
+/// hydrologically conditioned to remove depressions (e.g. using `BreachDepressionsLeastCost`). The
+/// catchment area is calculated internally using the multiple-flow-direction algorithm of Freeman (1991),
+/// in which the flow leaving a cell is apportioned among all of its downslope neighbours in proportion to
+/// their slope gradient raised to a power (`--exponent`, 1.1 by default). The catchment area used in the
+/// index (`--area_type`) may be the specific contributing area (`sca`, the default), i.e. the catchment
+/// area divided by the grid resolution, the total catchment area (`ca`), or the square root of the
+/// catchment area (`sqrt`). The local slope is calculated using the method of Horn (1981) and the optional
+/// Z conversion factor (`--zfactor`) may be used when the vertical and horizontal units differ. To avoid
+/// division by zero, the slope tangent is not allowed to fall below 0.0001. The modified catchment area
+/// may optionally be output (`--out_mca`).
+///
+/// Grid cells possessing the **NoData** value in the input DEM are assigned the **NoData** value in the
+/// output images.
+///
+/// # Reference
+/// Böhner, J., Köthe, R., Conrad, O., Gross, J., Ringeler, A., and Selige, T. (2002). Soil regionalisation
+/// by means of terrain analysis and process parameterisation. In: Micheli, E., Nachtergaele, F., and
+/// Montanarella, L. (eds.), *Soil Classification 2001*. European Soil Bureau, Research Report No. 7,
+/// 213-222.
+///
+/// Freeman, T. G. (1991). Calculating catchment area with divergent flow based on a regular grid.
+/// *Computers & Geosciences*, 17(3), 413-422.
+///
+/// # See Also
+/// `WetnessIndex`, `DownslopeIndex`, `FD8FlowAccumulation`
+pub struct SagaWetnessIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SagaWetnessIndex {
+    pub fn new() -> SagaWetnessIndex {
+        // public constructor
+        let name = "SagaWetnessIndex".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates the SAGA wetness index, based on a modified catchment area.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Suction Parameter (t)".to_owned(),
+            flags: vec!["--suction".to_owned()],
+            description:
+                "Suction parameter controlling the modified catchment area; default is 10."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Flow Dispersion Exponent".to_owned(),
+            flags: vec!["--exponent".to_owned()],
+            description: "Optional multiple-flow-direction exponent parameter; default is 1.1."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Catchment Area Type".to_owned(),
+            flags: vec!["--area_type".to_owned()],
+            description: "Catchment area type; one of 'sca' (default), 'ca', and 'sqrt'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "sca".to_owned(),
+                "ca".to_owned(),
+                "sqrt".to_owned(),
+            ]),
+            default_value: Some("sca".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Z Conversion Factor".to_owned(),
+            flags: vec!["--zfactor".to_owned()],
+            description:
+                "Optional multiplier for when the vertical and horizontal units are not the same."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Modified Catchment Area File (optional)".to_owned(),
+            flags: vec!["--out_mca".to_owned()],
+            description: "Optional output modified catchment area raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=swi.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=swi.tif --suction=5.0 --area_type=ca --out_mca=mca.tif", short_exe, name).replace("*", &sep);
+
+        SagaWetnessIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SagaWetnessIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut mca_file = String::new();
+        let mut suction = 10f64;
+        let mut exponent = 1.1f64;
+        let mut area_type = String::from("sca");
+        let mut z_factor = 1f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_mca" {
+                mca_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-suction" {
+                suction = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-exponent" {
+                exponent = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-area_type" {
+                area_type = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+                if area_type.contains("sqrt") || area_type.contains("square") {
+                    area_type = String::from("sqrt");
+                } else if area_type.contains("sca") || area_type.contains("specific") {
+                    area_type = String::from("sca");
+                } else {
+                    area_type = String::from("ca");
+                }
+            } else if flag_val == "-zfactor" {
+                z_factor = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            }
+        }
+
+        if suction <= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The suction parameter must be greater than 1.0.",
+            ));
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !mca_file.is_empty() && !mca_file.contains(&sep) && !mca_file.contains("/") {
+            mca_file = format!("{}{}", working_directory, mca_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+        let dem = input.get_data_as_array2d();
+
+        if verbose {
+            println!("Calculating the catchment area...");
+        }
+        let accum = mfd_flow_accumulation(&dem, res_x, res_y, exponent)?;
+
+        // Catchment areas and the suction weights, (1 / t)^exp(t^β), of each cell.
+        let cell_area = res_x * res_y;
+        let avg_res = (res_x + res_y) / 2f64;
+        let mut mca: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut weights: Array2D<f64> = Array2D::new(rows, columns, 0f64, 0f64)?;
+        let mut tan_slope: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                if dem.get_value(row, col) != nodata {
+                    let ca = accum.get_value(row, col) * cell_area;
+                    let area = match area_type.as_str() {
+                        "sca" => ca / avg_res,
+                        "sqrt" => ca.sqrt(),
+                        _ => ca,
+                    };
+                    mca.set_value(row, col, area);
+                    let slope = horn_slope(&dem, row, col, res_x, res_y, z_factor);
+                    tan_slope.set_value(row, col, slope.tan().max(0.0001));
+                    weights.set_value(row, col, (1f64 / suction).powf(suction.powf(slope).exp()));
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Calculating slope: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        drop(accum);
+
+        // Iteratively modify the catchment area until it no longer changes. Alternating the scan
+        // direction speeds the propagation of high catchment areas in all directions.
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let mut num_changes = 1usize;
+        let mut pass = 0usize;
+        while num_changes > 0 {
+            num_changes = 0;
+            for r in 0..rows {
+                let row = if pass % 2 == 0 { r } else { rows - 1 - r };
+                for c in 0..columns {
+                    let col = if pass % 2 == 0 { c } else { columns - 1 - c };
+                    let z = mca.get_value(row, col);
+                    if z != nodata {
+                        let mut max_n = f64::NEG_INFINITY;
+                        for n in 0..8 {
+                            let zn = mca.get_value(row + dy[n], col + dx[n]);
+                            if zn != nodata && zn > max_n {
+                                max_n = zn;
+                            }
+                        }
+                        let z_mod = max_n * weights.get_value(row, col);
+                        if z_mod > z {
+                            mca.set_value(row, col, z_mod);
+                            num_changes += 1;
+                        }
+                    }
+                }
+            }
+            pass += 1;
+            if verbose {
+                println!(
+                    "Modifying catchment area, pass {}: {} cells changed",
+                    pass, num_changes
+                );
+            }
+        }
+        drop(weights);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = mca.get_value(row, col);
+                if z != nodata {
+                    output.set_value(row, col, (z / tan_slope.get_value(row, col)).ln());
+                } else {
+                    output.set_value(row, col, nodata);
+                }
+            }
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "blueyellow.plt".to_string();
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Suction: {}", suction));
+        output.add_metadata_entry(format!("Exponent: {}", exponent));
+        output.add_metadata_entry(format!("Area type: {}", area_type));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !mca_file.is_empty() {
+            let mut out_mca = Raster::initialize_using_file(&mca_file, &input);
+            out_mca.configs.data_type = DataType::F32;
+            out_mca.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            out_mca.configs.palette = "blueyellow.plt".to_string();
+            for row in 0..rows {
+                for col in 0..columns {
+                    out_mca.set_value(row, col, mca.get_value(row, col));
+                }
+            }
+            out_mca.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            out_mca.add_metadata_entry(format!("Input file: {}", input_file));
+            let _ = match out_mca.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Modified catchment area file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Local slope gradient, in radians, using the method of Horn (1981). Missing neighbours
+/// are assigned the elevation of the centre cell.
+fn horn_slope(
+    dem: &Array2D<f64>,
+    row: isize,
+    col: isize,
+    res_x: f64,
+    res_y: f64,
+    z_factor: f64,
+) -> f64 {
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let z = dem.get_value(row, col);
+    let mut n = [0f64; 8];
+    for i in 0..8 {
+        let zn = dem.get_value(row + dy[i], col + dx[i]);
+        n[i] = if zn != dem.nodata {
+            zn * z_factor
+        } else {
+            z * z_factor
+        };
+    }
+    // n: 0=NE, 1=E, 2=SE, 3=S, 4=SW, 5=W, 6=NW, 7=N
+    let fx = (n[0] + 2f64 * n[1] + n[2] - n[6] - 2f64 * n[5] - n[4]) / (8f64 * res_x);
+    let fy = (n[6] + 2f64 * n[7] + n[0] - n[4] - 2f64 * n[3] - n[2]) / (8f64 * res_y);
+    (fx * fx + fy * fy).sqrt().atan()
+}
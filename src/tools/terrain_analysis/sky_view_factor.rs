@@ -0,0 +1,391 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the sky-view factor (SVF) for each grid cell in an input digital elevation model (DEM).
+/// The sky-view factor is the proportion of the visible sky hemisphere above a location that is not obstructed
+/// by the surrounding topography (Zakšek et al., 2011). Values range from 0 to 1, with values of 1 indicating an
+/// unobstructed view of the sky (e.g. on peaks and ridges or on flat, open plains) and lower values indicating
+/// locations, such as valley bottoms and depressions, that are shielded by the surrounding terrain. SVF is widely
+/// used as a relief visualization technique for archaeological prospection, since unlike hillshading it does not
+/// depend on a single illumination direction, and it is also used to estimate the diffuse component of solar
+/// radiation and longwave radiation exchange.
+///
+/// The algorithm traces rays from each grid cell along a number of evenly spaced azimuth directions
+/// (`--num_directions`; default 16), determining the horizon elevation angle (γ) in each direction, i.e. the maximum
+/// elevation angle to any point on the ray within the maximum search distance (`--max_dist`; in xy units). The DEM
+/// surface is sampled at one grid cell intervals along each ray using bilinear interpolation. The sky-view factor
+/// is then:
+///
+/// > SVF = 1 - Σ sin(γ<sub>i</sub>) / n
+///
+/// where *n* is the number of directions and negative horizon angles are treated as zero. If the maximum search
+/// distance is unspecified, a distance of 100 grid cells is used. Larger search distances capture broader
+/// landscape-scale structure but substantially increase processing time. Rays are terminated at the edge of the
+/// DEM and at NoData cells, and therefore values near the edges of the DEM should be interpreted with caution.
+///
+/// # Reference
+/// Zakšek, K., Oštir, K., and Kokalj, Ž. (2011). Sky-view factor as a relief visualization technique. Remote
+/// Sensing, 3(2), 398-415.
+///
+/// # See Also
+/// `Openness`, `HorizonAngle`, `VisibilityIndex`, `Hillshade`
+pub struct SkyViewFactor {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SkyViewFactor {
+    /// public constructor
+    pub fn new() -> SkyViewFactor {
+        let name = "SkyViewFactor".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates the sky-view factor for each grid cell in an input DEM."
+            .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Search Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of evenly spaced azimuth directions to search.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Optional maximum search distance (in xy units; 100 grid cells if unspecified)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=svf.tif --num_directions=32 --max_dist=250.0", short_exe, name).replace("*", &sep);
+
+        SkyViewFactor {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SkyViewFactor {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut num_directions = 16usize;
+        let mut max_dist = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                if keyval {
+                    input_file = vec[1].to_string();
+                } else {
+                    input_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-o" || flag_val == "-output" {
+                if keyval {
+                    output_file = vec[1].to_string();
+                } else {
+                    output_file = args[i + 1].to_string();
+                }
+            } else if flag_val == "-num_directions" {
+                if keyval {
+                    num_directions = vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize;
+                } else {
+                    num_directions = args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                        as usize;
+                }
+            } else if flag_val == "-max_dist" {
+                if keyval {
+                    max_dist = vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val));
+                } else {
+                    max_dist = args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val));
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if num_directions < 4 {
+            num_directions = 4;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mut mid_lat = (input.configs.north - input.configs.south) / 2.0;
+            if mid_lat <= 90.0 && mid_lat >= -90.0 {
+                mid_lat = mid_lat.to_radians();
+                res_x = res_x * (113200.0 * mid_lat.cos());
+                res_y = res_y * (113200.0 * mid_lat.cos());
+            }
+        }
+        let cell_size = (res_x + res_y) / 2.0;
+        if !(max_dist > 0f64) {
+            max_dist = 100f64 * cell_size;
+        }
+        let num_steps = (max_dist / cell_size).ceil().max(1f64) as usize;
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                // unit steps (in grid cells) along each search direction
+                let directions: Vec<(f64, f64)> = (0..num_directions)
+                    .map(|d| {
+                        let azimuth = (d as f64 * 360f64 / num_directions as f64).to_radians();
+                        (-azimuth.cos(), azimuth.sin())
+                    })
+                    .collect();
+                let (mut r, mut c): (f64, f64);
+                let (mut dist, mut z, mut z0): (f64, f64, f64);
+                let mut tan_max: f64;
+                let mut sum: f64;
+                let mut n: usize;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z0 = input[(row, col)];
+                        if z0 != nodata {
+                            sum = 0f64;
+                            n = 0;
+                            for &(dr, dc) in &directions {
+                                tan_max = 0f64;
+                                let mut found = false;
+                                for s in 1..=num_steps {
+                                    r = row as f64 + dr * s as f64;
+                                    c = col as f64 + dc * s as f64;
+                                    if r < 0f64
+                                        || c < 0f64
+                                        || r > (rows - 1) as f64
+                                        || c > (columns - 1) as f64
+                                    {
+                                        break;
+                                    }
+                                    z = input.get_interpolated_value(
+                                        r,
+                                        c,
+                                        InterpolationMethod::Bilinear,
+                                    );
+                                    if z == nodata {
+                                        break;
+                                    }
+                                    found = true;
+                                    dist = ((dr * s as f64 * res_y).powi(2)
+                                        + (dc * s as f64 * res_x).powi(2))
+                                    .sqrt();
+                                    if (z - z0) / dist > tan_max {
+                                        tan_max = (z - z0) / dist;
+                                    }
+                                }
+                                if found {
+                                    sum += tan_max.atan().sin();
+                                    n += 1;
+                                }
+                            }
+                            data[col as usize] = if n > 0 { 1f64 - sum / n as f64 } else { 1f64 };
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "grey.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Num. directions: {}", num_directions));
+        output.add_metadata_entry(format!("Max dist: {}", max_dist));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -480,12 +480,8 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 }
                 let watershed = Raster::new(&watershed_file, "r")?;
                 let ws_nodata = watershed.configs.nodata;
-                if watershed.configs.rows as isize != rows
-                    || watershed.configs.columns as isize != columns
-                {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                            "The input DEM and watershed rasters should have the same extents (rows and columns)."));
-                }
+                // make sure the inputs share the same grid and CRS
+                let watershed = align_raster_input(&input, watershed)?;
 
                 let watershed_min = watershed.configs.minimum;
                 let watershed_max = watershed.configs.maximum;
@@ -0,0 +1,694 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool estimates the incoming shortwave (solar) radiation received by each grid cell of an input
+/// digital elevation model (DEM) over a period of time, accounting for the effects of latitude, day of the
+/// year, surface slope and aspect, atmospheric attenuation, and shading by the surrounding topography. The
+/// total (global) radiation is written to the output raster (`--output`), and the direct (beam) and diffuse
+/// components may optionally be written to separate rasters (`--out_direct` and `--out_diffuse`). All outputs are
+/// in units of kWh/m<sup>2</sup>, integrated over the period between the start (`--start_day`) and end (`--end_day`)
+/// days of the year (1-365). Thus, a daily total can be modelled by setting the start and end days equal, while
+/// seasonal and annual totals are modelled with a longer period.
+///
+/// The position of the sun is calculated at regular time steps (`--time_step`, in hours) throughout each modelled
+/// day, sampled every `--day_interval` days. At each time step, the extraterrestrial irradiance (solar constant,
+/// corrected for the Earth-Sun distance) is attenuated by the atmosphere according to the user-specified
+/// atmospheric transmissivity (`--transmissivity`, the proportion of radiation transmitted through the atmosphere
+/// at zenith, typically 0.5-0.75 for clear skies) and the optical air mass, which is adjusted for the elevation of
+/// the grid cell. The direct radiation on the sloping surface depends on the angle of incidence between the solar
+/// beam and the surface normal, and is zero when the sun is below the local horizon. Diffuse radiation on a
+/// horizontal surface is estimated from the transmissivity using the relation of Liu and Jordan (1960) and is
+/// scaled by the proportion of the sky hemisphere that is visible from the grid cell (see `SkyViewFactor`),
+/// assuming an isotropic sky.
+///
+/// Topographic shading is modelled using the horizon angle in each of a number of evenly spaced azimuth directions
+/// (`--num_directions`; default 16), out to a maximum search distance (`--max_dist`; in xy units; 100 grid cells if
+/// unspecified). The horizon angle at the solar azimuth is linearly interpolated between the two nearest search
+/// directions.
+///
+/// The latitude of the site (`--latitude`, in decimal degrees) is used for the calculation of sun positions. If
+/// it is unspecified, the latitude of the DEM's centre is determined from its coordinate reference system. A single
+/// latitude is used for the entire DEM, which is appropriate for areas that are not excessively extensive in the
+/// north-south direction. Elevations are assumed to be in metres.
+///
+/// # Reference
+/// Fu, P., and Rich, P. M. (2002). A geometric solar radiation model with applications in agriculture and forestry.
+/// Computers and Electronics in Agriculture, 37(1-3), 25-35.
+///
+/// Liu, B. Y., and Jordan, R. C. (1960). The interrelationship and characteristic distribution of direct, diffuse
+/// and total solar radiation. Solar Energy, 4(3), 1-19.
+///
+/// # See Also
+/// `SkyViewFactor`, `HorizonAngle`, `Hillshade`
+pub struct SolarRadiation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SolarRadiation {
+    /// public constructor
+    pub fn new() -> SolarRadiation {
+        let name = "SolarRadiation".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Estimates incoming direct, diffuse, and total solar radiation over a period of time from a DEM."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Total Radiation File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output total (global) radiation raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Direct Radiation File (optional)".to_owned(),
+            flags: vec!["--out_direct".to_owned()],
+            description: "Optional output direct radiation raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Diffuse Radiation File (optional)".to_owned(),
+            flags: vec!["--out_diffuse".to_owned()],
+            description: "Optional output diffuse radiation raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Latitude (optional)".to_owned(),
+            flags: vec!["--latitude".to_owned()],
+            description: "Site latitude in decimal degrees; determined from the DEM's coordinate reference system if unspecified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Start Day of Year".to_owned(),
+            flags: vec!["--start_day".to_owned()],
+            description: "Start day of the year (1-365).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "End Day of Year".to_owned(),
+            flags: vec!["--end_day".to_owned()],
+            description: "End day of the year (1-365).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("365".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Day Interval".to_owned(),
+            flags: vec!["--day_interval".to_owned()],
+            description: "Interval, in days, between modelled days.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("14".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Time Step (hours)".to_owned(),
+            flags: vec!["--time_step".to_owned()],
+            description: "Time step, in hours, used to calculate sun positions.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Atmospheric Transmissivity".to_owned(),
+            flags: vec!["--transmissivity".to_owned()],
+            description: "Proportion of radiation transmitted through the atmosphere at zenith (0-1)."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.6".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Horizon Search Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of evenly spaced azimuth directions used to model horizon shading."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Horizon Search Distance".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Optional maximum horizon search distance (in xy units; 100 grid cells if unspecified).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=total.tif --out_direct=direct.tif --out_diffuse=diffuse.tif --start_day=152 --end_day=243 --day_interval=7 --transmissivity=0.65", short_exe, name).replace("*", &sep);
+
+        SolarRadiation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SolarRadiation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut direct_file = String::new();
+        let mut diffuse_file = String::new();
+        let mut latitude = f64::NAN;
+        let mut start_day = 1isize;
+        let mut end_day = 365isize;
+        let mut day_interval = 14isize;
+        let mut time_step = 0.5f64;
+        let mut transmissivity = 0.6f64;
+        let mut num_directions = 16usize;
+        let mut max_dist = f64::NAN;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let val = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            let parse_f64 = |v: &str| -> f64 {
+                v.parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val))
+            };
+            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
+                input_file = val;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = val;
+            } else if flag_val == "-out_direct" {
+                direct_file = val;
+            } else if flag_val == "-out_diffuse" {
+                diffuse_file = val;
+            } else if flag_val == "-latitude" {
+                latitude = parse_f64(&val);
+            } else if flag_val == "-start_day" {
+                start_day = parse_f64(&val) as isize;
+            } else if flag_val == "-end_day" {
+                end_day = parse_f64(&val) as isize;
+            } else if flag_val == "-day_interval" {
+                day_interval = parse_f64(&val) as isize;
+            } else if flag_val == "-time_step" {
+                time_step = parse_f64(&val);
+            } else if flag_val == "-transmissivity" {
+                transmissivity = parse_f64(&val);
+            } else if flag_val == "-num_directions" {
+                num_directions = parse_f64(&val) as usize;
+            } else if flag_val == "-max_dist" {
+                max_dist = parse_f64(&val);
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !direct_file.is_empty() && !direct_file.contains(&sep) && !direct_file.contains("/") {
+            direct_file = format!("{}{}", working_directory, direct_file);
+        }
+        if !diffuse_file.is_empty() && !diffuse_file.contains(&sep) && !diffuse_file.contains("/")
+        {
+            diffuse_file = format!("{}{}", working_directory, diffuse_file);
+        }
+
+        if start_day < 1 || start_day > 365 || end_day < 1 || end_day > 365 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The start and end days must be within the range 1-365.",
+            ));
+        }
+        if day_interval < 1 {
+            day_interval = 1;
+        }
+        if time_step <= 0f64 || time_step > 6f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The time step must be greater than zero and no larger than 6 hours.",
+            ));
+        }
+        if transmissivity <= 0f64 || transmissivity > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The atmospheric transmissivity must be within the range 0-1.",
+            ));
+        }
+        if num_directions < 4 {
+            num_directions = 4;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        if latitude.is_nan() {
+            let x = (input.configs.west + input.configs.east) / 2.0;
+            let y = (input.configs.north + input.configs.south) / 2.0;
+            latitude = match input.get_crs() {
+                Some(crs) => crs.unproject(x, y).1,
+                None => {
+                    if input.is_in_geographic_coordinates() {
+                        y
+                    } else {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "The latitude could not be determined from the DEM's coordinate reference system; please specify it (--latitude).",
+                        ));
+                    }
+                }
+            };
+        }
+        if latitude < -90f64 || latitude > 90f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The latitude must be within the range -90 to 90 degrees.",
+            ));
+        }
+
+        let mut res_x = input.configs.resolution_x;
+        let mut res_y = input.configs.resolution_y;
+        if input.is_in_geographic_coordinates() {
+            let mid_lat = latitude.to_radians();
+            res_x = res_x * (113200.0 * mid_lat.cos());
+            res_y = res_y * (113200.0 * mid_lat.cos());
+        }
+        let cell_size = (res_x + res_y) / 2.0;
+        if !(max_dist > 0f64) {
+            max_dist = 100f64 * cell_size;
+        }
+        let num_steps = (max_dist / cell_size).ceil().max(1f64) as usize;
+
+        // Calculate the sun positions. Each entry holds the cosine and sine of the solar zenith
+        // angle, the solar azimuth (radians clockwise from north), and the extraterrestrial
+        // irradiance (W/m2) multiplied by the duration (hours) that the entry represents.
+        const SOLAR_CONSTANT: f64 = 1367f64;
+        let phi = latitude.to_radians();
+        let mut days = vec![];
+        let mut day = start_day;
+        let num_days = if end_day >= start_day {
+            end_day - start_day + 1
+        } else {
+            365 - start_day + end_day + 1
+        };
+        let mut remaining = num_days;
+        while remaining > 0 {
+            let weight = remaining.min(day_interval);
+            // sample the middle of the interval of days
+            let mut d = day + weight / 2;
+            if d > 365 {
+                d -= 365;
+            }
+            days.push((d, weight));
+            day += weight;
+            if day > 365 {
+                day -= 365;
+            }
+            remaining -= weight;
+        }
+        let mut sun_positions: Vec<(f64, f64, f64, f64)> = vec![];
+        let num_time_steps = (24f64 / time_step).ceil() as usize;
+        for &(d, weight) in &days {
+            let day_angle = 2f64 * PI * d as f64 / 365f64;
+            let declination = (23.45f64).to_radians() * (2f64 * PI * (284 + d) as f64 / 365f64).sin();
+            let eccentricity = 1f64 + 0.033 * day_angle.cos();
+            for t in 0..num_time_steps {
+                let hours = time_step.min(24f64 - t as f64 * time_step);
+                let solar_time = t as f64 * time_step + hours / 2f64;
+                let hour_angle = (15f64 * (solar_time - 12f64)).to_radians();
+                let cos_z = phi.sin() * declination.sin()
+                    + phi.cos() * declination.cos() * hour_angle.cos();
+                if cos_z <= 0f64 {
+                    continue; // the sun is below the horizon
+                }
+                let sin_z = (1f64 - cos_z * cos_z).max(0f64).sqrt();
+                let mut azimuth = if phi.cos() * sin_z > 1e-9 {
+                    ((declination.sin() - phi.sin() * cos_z) / (phi.cos() * sin_z))
+                        .max(-1f64)
+                        .min(1f64)
+                        .acos()
+                } else {
+                    PI
+                };
+                if hour_angle > 0f64 {
+                    azimuth = 2f64 * PI - azimuth;
+                }
+                sun_positions.push((
+                    cos_z,
+                    sin_z,
+                    azimuth,
+                    SOLAR_CONSTANT * eccentricity * hours * weight as f64,
+                ));
+            }
+        }
+        let sun_positions = Arc::new(sun_positions);
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let sun_positions = sun_positions.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+                let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+                // unit steps (in grid cells) along each horizon search direction
+                let directions: Vec<(f64, f64)> = (0..num_directions)
+                    .map(|d| {
+                        let azimuth = (d as f64 * 360f64 / num_directions as f64).to_radians();
+                        (-azimuth.cos(), azimuth.sin())
+                    })
+                    .collect();
+                let direction_width = 2f64 * PI / num_directions as f64;
+                let mut horizon = vec![0f64; num_directions];
+                let mut n = [0f64; 8];
+                let (mut r, mut c): (f64, f64);
+                let (mut dist, mut z, mut z0): (f64, f64, f64);
+                let mut tan_max: f64;
+                let (mut fx, mut fy): (f64, f64);
+                let (mut slope, mut aspect): (f64, f64);
+                let mut sky_view: f64;
+                let mut pressure_ratio: f64;
+                let (mut air_mass, mut beam_transmittance): (f64, f64);
+                let (mut cos_i, mut sun_elevation, mut horizon_angle): (f64, f64, f64);
+                let (mut direct, mut diffuse): (f64, f64);
+                let mut f: f64;
+                let mut d0: usize;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut direct_data: Vec<f64> = vec![nodata; columns as usize];
+                    let mut diffuse_data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z0 = input[(row, col)];
+                        if z0 == nodata {
+                            continue;
+                        }
+
+                        // slope and aspect
+                        for i in 0..8 {
+                            n[i] = input[(row + dy[i], col + dx[i])];
+                            if n[i] == nodata {
+                                n[i] = z0;
+                            }
+                        }
+                        // n: 0=NE, 1=E, 2=SE, 3=S, 4=SW, 5=W, 6=NW, 7=N
+                        fx = (n[0] + 2f64 * n[1] + n[2] - n[6] - 2f64 * n[5] - n[4])
+                            / (8f64 * res_x);
+                        fy = (n[6] + 2f64 * n[7] + n[0] - n[4] - 2f64 * n[3] - n[2])
+                            / (8f64 * res_y);
+                        slope = (fx * fx + fy * fy).sqrt().atan();
+                        aspect = (-fx).atan2(-fy); // downslope direction, clockwise from north
+                        if aspect < 0f64 {
+                            aspect += 2f64 * PI;
+                        }
+
+                        // horizon angles and sky view factor
+                        sky_view = 0f64;
+                        for d in 0..num_directions {
+                            let (dr, dc) = directions[d];
+                            tan_max = f64::NEG_INFINITY;
+                            for s in 1..=num_steps {
+                                r = row as f64 + dr * s as f64;
+                                c = col as f64 + dc * s as f64;
+                                if r < 0f64
+                                    || c < 0f64
+                                    || r > (rows - 1) as f64
+                                    || c > (columns - 1) as f64
+                                {
+                                    break;
+                                }
+                                z = input.get_interpolated_value(
+                                    r,
+                                    c,
+                                    InterpolationMethod::Bilinear,
+                                );
+                                if z == nodata {
+                                    break;
+                                }
+                                dist = ((dr * s as f64 * res_y).powi(2)
+                                    + (dc * s as f64 * res_x).powi(2))
+                                .sqrt();
+                                tan_max = tan_max.max((z - z0) / dist);
+                            }
+                            horizon[d] = if tan_max > f64::NEG_INFINITY {
+                                tan_max.atan()
+                            } else {
+                                -PI / 2f64
+                            };
+                            sky_view += horizon[d].max(0f64).sin();
+                        }
+                        sky_view = 1f64 - sky_view / num_directions as f64;
+
+                        // elevation correction of the optical air mass
+                        pressure_ratio = ((288f64 - 0.0065 * z0.max(0f64)) / 288f64)
+                            .max(0f64)
+                            .powf(5.256);
+
+                        direct = 0f64;
+                        diffuse = 0f64;
+                        for &(cos_z, sin_z, sun_azimuth, energy) in sun_positions.iter() {
+                            air_mass = pressure_ratio / cos_z;
+                            beam_transmittance = transmissivity.powf(air_mass);
+
+                            // diffuse radiation on a horizontal surface (Liu and Jordan, 1960)
+                            diffuse += energy
+                                * cos_z
+                                * (0.271 - 0.294 * beam_transmittance).max(0f64)
+                                * sky_view;
+
+                            // is the sun above the local horizon?
+                            f = sun_azimuth / direction_width;
+                            d0 = f.floor() as usize % num_directions;
+                            f -= f.floor();
+                            horizon_angle = horizon[d0] * (1f64 - f)
+                                + horizon[(d0 + 1) % num_directions] * f;
+                            sun_elevation = PI / 2f64 - cos_z.acos();
+                            if sun_elevation <= horizon_angle {
+                                continue;
+                            }
+
+                            cos_i = cos_z * slope.cos()
+                                + sin_z * slope.sin() * (sun_azimuth - aspect).cos();
+                            if cos_i > 0f64 {
+                                direct += energy * beam_transmittance * cos_i;
+                            }
+                        }
+
+                        // convert from Wh/m2 to kWh/m2
+                        direct_data[col as usize] = direct / 1000f64;
+                        diffuse_data[col as usize] = diffuse / 1000f64;
+                    }
+                    tx.send((row, direct_data, diffuse_data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        let mut direct_output = Raster::initialize_using_file(&direct_file, &input);
+        let mut diffuse_output = Raster::initialize_using_file(&diffuse_file, &input);
+        for r in 0..rows {
+            let (row, direct_data, diffuse_data) =
+                rx.recv().expect("Error receiving data from thread.");
+            let total_data: Vec<f64> = direct_data
+                .iter()
+                .zip(diffuse_data.iter())
+                .map(|(&a, &b)| if a != nodata { a + b } else { nodata })
+                .collect();
+            output.set_row_data(row, total_data);
+            direct_output.set_row_data(row, direct_data);
+            diffuse_output.set_row_data(row, diffuse_data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        for (mut output, output_file) in vec![
+            (output, output_file.clone()),
+            (direct_output, direct_file.clone()),
+            (diffuse_output, diffuse_file.clone()),
+        ] {
+            if output_file.is_empty() {
+                continue;
+            }
+            output.configs.data_type = DataType::F32;
+            output.configs.palette = "spectrum.plt".to_string();
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry("Units: kWh/m2".to_string());
+            output.add_metadata_entry(format!("Latitude: {}", latitude));
+            output.add_metadata_entry(format!("Start day: {}", start_day));
+            output.add_metadata_entry(format!("End day: {}", end_day));
+            output.add_metadata_entry(format!("Day interval: {}", day_interval));
+            output.add_metadata_entry(format!("Time step (hours): {}", time_step));
+            output.add_metadata_entry(format!("Transmissivity: {}", transmissivity));
+            output.add_metadata_entry(format!("Num. directions: {}", num_directions));
+            output.add_metadata_entry(format!("Max dist: {}", max_dist));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
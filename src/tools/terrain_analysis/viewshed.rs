@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 10/01/2018
-Last Modified: 12/10/2018
+Last Modified: 15/10/2026
 License: MIT
 */
 
@@ -24,10 +24,26 @@ use std::thread;
 /// by an input digital elevation model (DEM). The user must specify the name of the input
 /// DEM (`--dem`), a viewing station input vector file (`--stations`), the output file name
 /// (`--output`), and the viewing height (`--height`).
-/// Viewing station locations are specified as points within an input shapefile. The output
-/// image indicates the number of stations visible from each grid cell. The viewing height
-/// is in the same units as the elevations of the DEM and represent a height above the ground
-/// elevation from which the viewshed is calculated.
+/// Viewing station locations are specified as points (or multi-points) within an input
+/// shapefile. The output image indicates the number of stations visible from each grid cell,
+/// i.e. a cumulative viewshed. The viewing height is in the same units as the elevations of
+/// the DEM and represent a height above the ground elevation from which the viewshed is
+/// calculated. Optionally, per-station observer heights may be read from a numeric field
+/// within the stations attribute table (`--height_field`); stations with a null value in this
+/// field use the `--height` value.
+///
+/// The target height (`--target_height`) is an offset added to the ground elevation of each
+/// target grid cell, such that a cell is deemed visible if an object of this height located
+/// within the cell could be seen from the station. The maximum viewing distance
+/// (`--max_dist`), in the horizontal units of the DEM, limits the viewshed to a radius around
+/// each station; cells beyond this distance are deemed not visible. When the `--curvature`
+/// flag is specified, target elevations are lowered by (1 - *k*)*d*<sup>2</sup> / 2*R*,
+/// where *d* is the distance from the station, *R* is the radius of the Earth (6,371 km), and
+/// *k* is the atmospheric refraction coefficient (`--refraction`, default 0.13). This
+/// correction requires a DEM in a projected coordinate system with horizontal units of
+/// metres. If the `--per_station` flag is specified, a boolean viewshed raster (1 = visible,
+/// 0 = not visible) is also written for each station, with the station number appended to
+/// the output file name (e.g. *output_1.tif*).
 ///
 /// `Viewshed` should be used when there are a relatively small number of target sites
 /// for which visibility needs to be assessed. If you need to assess general landscape
@@ -36,10 +52,7 @@ use std::thread;
 ///
 /// Viewshed analysis is a very
 /// computationally intensive task. Depending on the size of the input DEM grid and the
-/// number of viewing stations, this operation may take considerable time to complete. Also,
-/// this implementation of the viewshed algorithm does not account for the curvature of the
-/// Earth. This should be accounted for if viewsheds are being calculated over very
-/// extensive areas.
+/// number of viewing stations, this operation may take considerable time to complete.
 ///
 /// # See Also
 /// `VisibilityIndex`
@@ -97,6 +110,63 @@ impl Viewshed {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Station Height Field (optional)".to_owned(),
+            flags: vec!["--height_field".to_owned()],
+            description: "Optional numeric field in the stations attribute table containing per-station viewing heights.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--stations".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target Height (in z units)".to_owned(),
+            flags: vec!["--target_height".to_owned()],
+            description: "Height of targets above the ground surface, in z units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Viewing Distance (xy units)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Optional maximum viewing distance, in xy units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Correct for Earth curvature and refraction?".to_owned(),
+            flags: vec!["--curvature".to_owned()],
+            description: "Optional flag indicating whether to correct for Earth curvature and atmospheric refraction.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Refraction Coefficient".to_owned(),
+            flags: vec!["--refraction".to_owned()],
+            description: "Atmospheric refraction coefficient, used with the curvature correction.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.13".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output per-station viewsheds?".to_owned(),
+            flags: vec!["--per_station".to_owned()],
+            description: "Optional flag indicating whether to output a boolean viewshed raster for each station.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let p = format!("{}", env::current_dir().unwrap().display());
         let e = format!("{}", env::current_exe().unwrap().display());
@@ -108,7 +178,8 @@ impl Viewshed {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem='dem.tif' --stations='stations.shp' -o=output.tif --height=10.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem='dem.tif' --stations='stations.shp' -o=output.tif --height=10.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem='dem.tif' --stations='stations.shp' -o=output.tif --height_field=HEIGHT --target_height=1.5 --max_dist=5000.0 --curvature --per_station", short_exe, name).replace("*", &sep);
 
         Viewshed {
             name: name,
@@ -165,6 +236,12 @@ impl WhiteboxTool for Viewshed {
         let mut stations_file = String::new();
         let mut output_file = String::new();
         let mut height = 2.0;
+        let mut height_field = String::new();
+        let mut target_height = 0f64;
+        let mut max_dist = f64::INFINITY;
+        let mut curvature = false;
+        let mut refraction = 0.13f64;
+        let mut per_station = false;
 
         if args.len() == 0 {
             return Err(Error::new(
@@ -212,6 +289,56 @@ impl WhiteboxTool for Viewshed {
                         .parse::<f64>()
                         .expect(&format!("Error parsing {}", flag_val))
                 };
+            } else if flag_val == "-height_field" {
+                height_field = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-target_height" {
+                target_height = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-max_dist" {
+                max_dist = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-curvature" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    curvature = true;
+                }
+            } else if flag_val == "-refraction" {
+                refraction = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-per_station" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    per_station = true;
+                }
             }
         }
 
@@ -250,6 +377,21 @@ impl WhiteboxTool for Viewshed {
             height = 0f64;
         }
 
+        if max_dist <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum viewing distance must be greater than zero.",
+            ));
+        }
+
+        // the drop of the Earth's surface below the tangent plane, reduced by refraction
+        const EARTH_RADIUS: f64 = 6_371_000f64;
+        let curvature_factor = if curvature {
+            (1f64 - refraction) / (2f64 * EARTH_RADIUS)
+        } else {
+            0f64
+        };
+
         let rows = dem.configs.rows as isize;
         let columns = dem.configs.columns as isize;
         let nodata = dem.configs.nodata;
@@ -268,36 +410,49 @@ impl WhiteboxTool for Viewshed {
 
         let mut output = Raster::initialize_using_file(&output_file, &dem);
 
-        // scan the stations raster and place each non-zero grid cell into Vecs
-        // let mut z: f64;
+        let use_field = !height_field.trim().is_empty();
+        if use_field {
+            let field_index = match stations.attributes.get_field_num(&height_field) {
+                Some(i) => i,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The station height field was not found in the attribute table.",
+                    ));
+                }
+            };
+            if !stations.attributes.is_field_numeric(field_index) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The station height field must be numeric.",
+                ));
+            }
+        }
+
+        // place each station point, and its viewing height, into Vecs
         let mut station_x = vec![];
         let mut station_y = vec![];
-        // for row in 0..rows {
-        //     for col in 0..columns {
-        //         z = stations.get_value(row, col);
-        //         if z > 0f64 && dem.get_value(row, col) != nodata {
-        //             station_x.push(stations.get_x_from_column(col));
-        //             station_y.push(stations.get_y_from_row(row));
-        //         }
-        //     }
-
-        //     if verbose {
-        //         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-        //         if progress != old_progress {
-        //             println!("Locating stations: {}%", progress);
-        //             old_progress = progress;
-        //         }
-        //     }
-        // }
-
+        let mut station_height = vec![];
         for record_num in 0..stations.num_records {
             let record = stations.get_record(record_num);
-            station_y.push(record.points[0].y);
-            station_x.push(record.points[0].x);
+            let stn_height = if use_field {
+                match stations.attributes.get_value(record_num, &height_field) {
+                    FieldData::Int(val) => val as f64,
+                    FieldData::Real(val) => val,
+                    _ => height, // likely a null field
+                }
+            } else {
+                height
+            };
+            for i in 0..record.num_points as usize {
+                station_x.push(record.points[i].x);
+                station_y.push(record.points[i].y);
+                station_height.push(stn_height.max(0f64));
+            }
 
             if verbose {
-                progress =
-                    (100.0_f64 * record_num as f64 / (stations.num_records - 1) as f64) as usize;
+                progress = (100.0_f64 * (record_num + 1) as f64 / stations.num_records as f64)
+                    as usize;
                 if progress != old_progress {
                     println!("Locating view stations: {}%", progress);
                     old_progress = progress;
@@ -309,24 +464,33 @@ impl WhiteboxTool for Viewshed {
         let mut stn_z: f64;
         let (mut stn_row, mut stn_col): (isize, isize);
         let mut view_angle: Array2D<f32> = Array2D::new(rows, columns, -32768f32, -32768f32)?;
-        let mut stn_num = 0;
         let num_stn = station_x.len();
-        while !station_x.is_empty() {
-            stn_num += 1;
-            println!("Station {} of {}", stn_num, num_stn);
+        for stn_num in 1..=num_stn {
+            if verbose {
+                println!("Station {} of {}", stn_num, num_stn);
+            }
 
-            stn_x = station_x.pop().expect("Error during pop operation.");
+            stn_x = station_x[stn_num - 1];
             stn_col = dem.get_column_from_x(stn_x);
-            stn_y = station_y.pop().expect("Error during pop operation.");
+            stn_y = station_y[stn_num - 1];
             stn_row = dem.get_row_from_y(stn_y);
-            stn_z = dem.get_value(stn_row, stn_col) + height;
 
-            if (stn_col < 0 || stn_col >= columns) && (stn_row < 0 || stn_row >= rows) {
+            if stn_col < 0 || stn_col >= columns || stn_row < 0 || stn_row >= rows {
                 return Err(Error::new(
                     ErrorKind::InvalidInput,
-                    "The input stations is not located within the footprint of the DEM.",
+                    format!(
+                        "Station {} is not located within the footprint of the DEM.",
+                        stn_num
+                    ),
                 ));
             }
+            if dem.get_value(stn_row, stn_col) == nodata {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Station {} is located on a nodata cell in the DEM.", stn_num),
+                ));
+            }
+            stn_z = dem.get_value(stn_row, stn_col) + station_height[stn_num - 1];
 
             // now calculate the view angle
             let (tx, rx) = mpsc::channel();
@@ -345,9 +509,9 @@ impl WhiteboxTool for Viewshed {
                             if z != nodata {
                                 x = dem.get_x_from_column(col);
                                 y = dem.get_y_from_row(row);
-                                dz = z - stn_z;
                                 dist =
                                     ((x - stn_x) * (x - stn_x) + (y - stn_y) * (y - stn_y)).sqrt();
+                                dz = z - curvature_factor * dist * dist - stn_z;
                                 if dist != 0.0 {
                                     data[col as usize] = (dz / dist * 1000f64) as f32;
                                 } else {
@@ -645,24 +809,48 @@ impl WhiteboxTool for Viewshed {
                 }
             }
 
+            let mut station_output = if per_station {
+                let p = path::Path::new(&output_file);
+                let mut extension = String::from(".");
+                let ext = p.extension().unwrap().to_str().unwrap();
+                extension.push_str(ext);
+                let station_file =
+                    output_file.replace(&extension, &format!("_{}{}", stn_num, extension));
+                let mut r = Raster::initialize_using_file(&station_file, &dem);
+                r.configs.data_type = DataType::I16;
+                r.configs.palette = "qual.plt".to_string();
+                r.configs.photometric_interp = PhotometricInterpretation::Categorical;
+                Some(r)
+            } else {
+                None
+            };
+
             let mut value: f64;
+            let (mut x, mut y, mut dist): (f64, f64, f64);
+            let mut target_va: f32;
             for row in 0..rows {
                 for col in 0..columns {
-                    // z = max_view_angle.get_value(row, col);
-                    // if z > -32768f32 {
-                    //     output.set_value(row, col, z as f64);
-                    // } else {
-                    //     output.set_value(row, col, nodata);
-                    // }
                     if dem.get_value(row, col) != nodata {
-                        value = if max_view_angle.get_value(row, col)
-                            > view_angle.get_value(row, col)
+                        x = dem.get_x_from_column(col);
+                        y = dem.get_y_from_row(row);
+                        dist = ((x - stn_x) * (x - stn_x) + (y - stn_y) * (y - stn_y)).sqrt();
+                        target_va = if dist != 0.0 {
+                            view_angle.get_value(row, col)
+                                + (target_height / dist * 1000f64) as f32
+                        } else {
+                            0f32
+                        };
+                        value = if dist > max_dist
+                            || max_view_angle.get_value(row, col) > target_va
                         {
                             0f64
                         } else {
                             1f64
                         };
                         output.increment(row, col, value);
+                        if let Some(ref mut r) = station_output {
+                            r.set_value(row, col, value);
+                        }
                     }
                 }
 
@@ -677,6 +865,23 @@ impl WhiteboxTool for Viewshed {
                     }
                 }
             }
+
+            if let Some(mut r) = station_output {
+                r.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                r.add_metadata_entry(format!("DEM file: {}", input_file));
+                r.add_metadata_entry(format!("Station: {} of {}", stn_num, num_stn));
+                let _ = match r.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Station {} viewshed written", stn_num)
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
@@ -685,6 +890,17 @@ impl WhiteboxTool for Viewshed {
             self.get_tool_name()
         ));
         output.add_metadata_entry(format!("DEM file: {}", input_file));
+        output.add_metadata_entry(format!("Stations file: {}", stations_file));
+        output.add_metadata_entry(format!("Target height: {}", target_height));
+        if max_dist.is_finite() {
+            output.add_metadata_entry(format!("Maximum viewing distance: {}", max_dist));
+        }
+        if curvature {
+            output.add_metadata_entry(format!(
+                "Curvature corrected; refraction coefficient: {}",
+                refraction
+            ));
+        }
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
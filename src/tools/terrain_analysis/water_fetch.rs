@@ -0,0 +1,379 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::wind_exposure::{resolution_in_metres, trace_ray};
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the fetch, i.e. the distance over open water across which the wind blows
+/// before reaching a point, for each cell of a water body raster (`--input`), in which water cells
+/// have non-zero values and land cells have values of zero, for wind blowing from a specified
+/// direction (`--azimuth`, in degrees clockwise from north). Fetch controls the height and period
+/// of wind-generated waves and is therefore used in the modelling of shoreline erosion, sediment
+/// resuspension, and the exposure of aquatic habitats. The fetch is the distance from the cell to
+/// the first land cell upwind. Where the search reaches the edge of the raster, or NoData, before
+/// reaching land, the distance to the edge is used and, as in the `FetchAnalysis` tool, the value
+/// is made negative to distinguish these truncated fetches. The search may optionally be limited to
+/// a maximum distance (`--max_dist`, in metres); fetches that reach this distance are not
+/// truncated.
+///
+/// Because waves are generated by winds over a range of directions, the effective fetch of the
+/// Shore Protection Manual (CERC, 1984) may be calculated instead (`--effective`). The effective
+/// fetch is the cosine-weighted average of the fetches along 15 radials at 6° intervals within
+/// 42° of the wind direction; it is negative if any of the radials is truncated.
+///
+/// Land cells are assigned zero in the output and NoData cells are assigned NoData. The water
+/// raster may be derived from a land-cover classification or, for a lake, by thresholding a DEM at
+/// the water level. Distances are in metres; if the raster is in geographic coordinates, its
+/// resolution is converted to metres at its mid-latitude.
+///
+/// # Reference
+/// Coastal Engineering Research Center (CERC) (1984). *Shore Protection Manual*. U.S. Army Corps of
+/// Engineers, Vicksburg, MS.
+///
+/// # See Also
+/// `FetchAnalysis`, `WinstralShelterIndex`, `WindExpositionIndex`
+pub struct WaterFetch {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl WaterFetch {
+    pub fn new() -> WaterFetch {
+        // public constructor
+        let name = "WaterFetch".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates the fetch over water bodies for a given wind direction.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Water Body File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file of water bodies (non-zero) and land (zero).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Wind Direction (degrees)".to_owned(),
+            flags: vec!["--azimuth".to_owned()],
+            description: "Direction from which the wind blows, in degrees clockwise from north."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("270.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance (m)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Optional maximum search distance, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Calculate the effective fetch?".to_owned(),
+            flags: vec!["--effective".to_owned()],
+            description: "Calculate the Shore Protection Manual effective fetch.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=lake.tif -o=fetch.tif --azimuth=225.0 --effective",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        WaterFetch {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for WaterFetch {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut azimuth = 270.0f64;
+        let mut max_dist = f64::INFINITY;
+        let mut effective = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-azimuth" {
+                azimuth = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_dist" {
+                max_dist = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-effective" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    effective = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if max_dist <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let res = resolution_in_metres(&input);
+        let cell_size = res.0.max(res.1);
+
+        // the radials along which the fetch is measured, and their weights
+        let radials: Arc<Vec<(f64, f64)>> = Arc::new(if effective {
+            (-7..=7)
+                .map(|i| {
+                    let offset = i as f64 * 6f64;
+                    (
+                        (azimuth + offset).rem_euclid(360f64),
+                        offset.to_radians().cos(),
+                    )
+                })
+                .collect()
+        } else {
+            vec![(azimuth.rem_euclid(360f64), 1f64)]
+        });
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let radials = radials.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let z0 = input.get_value(row, col);
+                        if z0 == nodata {
+                            continue;
+                        }
+                        if z0 == 0f64 {
+                            data[col as usize] = 0f64;
+                            continue;
+                        }
+                        let (mut sum, mut sum_weights) = (0f64, 0f64);
+                        let mut truncated = false;
+                        for &(a, weight) in radials.iter() {
+                            let mut reached_land = false;
+                            let mut reached_nodata = false;
+                            let dist = trace_ray(
+                                (rows, columns),
+                                (row, col),
+                                a,
+                                max_dist,
+                                res,
+                                |r, c, _| {
+                                    let z = input.get_value(r.round() as isize, c.round() as isize);
+                                    if z == nodata {
+                                        reached_nodata = true;
+                                        return false;
+                                    }
+                                    if z == 0f64 {
+                                        reached_land = true;
+                                        return false;
+                                    }
+                                    true
+                                },
+                            );
+                            // the ray stopped short of the maximum distance without reaching land
+                            if reached_nodata
+                                || (!reached_land && dist + 1.5 * cell_size < max_dist)
+                            {
+                                truncated = true;
+                            }
+                            sum += dist * weight;
+                            sum_weights += weight;
+                        }
+                        let fetch = sum / sum_weights;
+                        data[col as usize] = if truncated { -fetch } else { fetch };
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "blueyellow.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Azimuth: {}", azimuth));
+        output.add_metadata_entry(format!("Max dist: {}", max_dist));
+        output.add_metadata_entry(format!("Effective fetch: {}", effective));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,753 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use crate::algorithms::point_in_poly;
+use crate::raster::*;
+use crate::rendering::html::*;
+use crate::rendering::LineGraph;
+use crate::spatial_ref_system::CoordinateReferenceSystem;
+use crate::tools::*;
+use crate::vector::*;
+use num_cpus;
+use std::cmp::Ordering::Equal;
+use std::collections::BTreeMap;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::isize;
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the hypsometric curve, or area-altitude curve, and the hypsometric integral
+/// of each zone, e.g. each watershed, in a zones layer (`--zones`), based on an input digital
+/// elevation model (DEM; `--dem`). Zones may be defined either by a polygon vector file, in which
+/// case all grid cells with centres that fall within each polygon (excluding holes) are included, or
+/// by a categorical raster, such as the output of the `Watershed` or `Basins` tools, in which case
+/// each unique non-zero value defines a zone. Overlapping polygons are treated as independent zones.
+///
+/// The hypsometric curve of a zone (Strahler, 1952) plots relative height (*h*/*H*), the height
+/// above the zone's lowest cell divided by the zone's total relief, against relative area
+/// (*a*/*A*), the proportion of the zone's area lying above that height. Each curve is sampled
+/// at a user-specified number of evenly spaced relative heights (`--num_points`). The hypsometric
+/// integral (HI), i.e. the area beneath the curve, is calculated using the elevation-relief ratio
+/// of Pike and Wilson (1971), which is equivalent to the integral of the curve:
+///
+/// > HI = (*z_mean* - *z_min*) / (*z_max* - *z_min*)
+///
+/// High HI values (convex curves) are associated with youthful, weakly dissected landscapes, while
+/// low values (concave curves) characterize more eroded, mature landscapes.
+///
+/// The tool outputs an HTML report (`--output`) containing a table of the cell count, area, minimum,
+/// maximum and mean elevation, relief, and HI of each zone, along with a plot of the hypsometric
+/// curves. When zones are defined by polygons, the *COUNT*, *AREA*, *MIN_ELEV*, *MAX_ELEV*,
+/// *MEAN_ELEV*, *RELIEF*, and *HI* fields are also appended to the polygon attribute table. The
+/// curves can optionally be written to a CSV file (`--out_csv`), with one row per zone and curve
+/// point, for use in other plotting software. Zones without any valid DEM cells are omitted from
+/// the report and plot, and are assigned null values in the attribute table.
+///
+/// # References
+/// Pike, R. J., and Wilson, S. E. (1971). Elevation-relief ratio, hypsometric integral, and
+/// geomorphic area-altitude analysis. *Geological Society of America Bulletin*, 82(4), 1079-1084.
+///
+/// Strahler, A. N. (1952). Hypsometric (area-altitude) analysis of erosional topography.
+/// *Geological Society of America Bulletin*, 63(11), 1117-1142.
+///
+/// # See Also
+/// `HypsometricAnalysis`, `ZonalStatistics`, `Watershed`
+pub struct WatershedHypsometry {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl WatershedHypsometry {
+    pub fn new() -> WatershedHypsometry {
+        // public constructor
+        let name = "WatershedHypsometry".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates hypsometric curves and integrals for each zone, e.g. watershed, in a zones layer."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Zones File".to_owned(),
+            flags: vec!["--zones".to_owned()],
+            description: "Input zones (e.g. watersheds) raster file or polygon vector file."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output HTML report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output CSV File".to_owned(),
+            flags: vec!["--out_csv".to_owned()],
+            description: "Optional output CSV file of hypsometric curves.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Curve Points".to_owned(),
+            flags: vec!["--num_points".to_owned()],
+            description: "Number of relative heights at which each curve is sampled.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("50".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --zones=watersheds.shp -o=hypsometry.html
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --zones=basins.tif -o=hypsometry.html --out_csv=curves.csv --num_points=100", short_exe, name).replace("*", &sep);
+
+        WatershedHypsometry {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for WatershedHypsometry {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut dem_file = String::new();
+        let mut zones_file = String::new();
+        let mut output_file = String::new();
+        let mut output_csv_file = String::new();
+        let mut num_points = 50usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-dem" {
+                dem_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-zones" {
+                zones_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-out_csv" {
+                output_csv_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-num_points" {
+                num_points = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f32>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if num_points < 2 {
+            num_points = 2;
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !dem_file.contains(&sep) && !dem_file.contains("/") {
+            dem_file = format!("{}{}", working_directory, dem_file);
+        }
+        if !zones_file.contains(&sep) && !zones_file.contains("/") {
+            zones_file = format!("{}{}", working_directory, zones_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !output_csv_file.is_empty()
+            && !output_csv_file.contains(&sep)
+            && !output_csv_file.contains("/")
+        {
+            output_csv_file = format!("{}{}", working_directory, output_csv_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Arc::new(Raster::new(&dem_file, "r")?);
+        let cell_area = dem.configs.resolution_x * dem.configs.resolution_y;
+
+        let start: Instant;
+        let mut zone_ids: Vec<String> = vec![];
+        let mut zones: Vec<ZoneHypsometry> = vec![];
+        if zones_file.to_lowercase().ends_with(".shp") {
+            let mut polygons = Shapefile::read(&zones_file)?;
+            polygons.file_mode = "rw".to_string(); // we need to be able to modify the attributes table
+
+            // make sure the input vector file is of polygon type
+            if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input vector data must be of polygon base shape type.",
+                ));
+            }
+
+            if let Some(raster_crs) = dem.get_crs() {
+                if !polygons.projection.trim().is_empty() {
+                    if let Ok(vector_crs) =
+                        CoordinateReferenceSystem::from_wkt(&polygons.projection)
+                    {
+                        if !raster_crs.is_equivalent(&vector_crs) {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                format!(
+                                    "The input DEM ({}) and polygon zones ({}) do not share the same coordinate reference system. Use ReprojectVector to transform the polygons first.",
+                                    raster_crs.name, vector_crs.name
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+
+            start = Instant::now();
+            let num_records = polygons.num_records;
+            let records = Arc::new(
+                (0..num_records)
+                    .map(|record_num| polygons.get_record(record_num).clone())
+                    .collect::<Vec<ShapefileGeometry>>(),
+            );
+
+            let num_procs = num_cpus::get();
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let dem = dem.clone();
+                let records = records.clone();
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    let rows = dem.configs.rows as isize;
+                    let columns = dem.configs.columns as isize;
+                    let nodata = dem.configs.nodata;
+                    for record_num in (0..records.len()).filter(|r| r % num_procs == tid) {
+                        let record = &records[record_num];
+                        let mut values = vec![];
+                        if record.num_points > 0 {
+                            // find the range of rows and columns covered by the polygon
+                            let mut min_row = isize::max_value();
+                            let mut max_row = isize::min_value();
+                            let mut min_col = isize::max_value();
+                            let mut max_col = isize::min_value();
+                            for p in &record.points {
+                                let (row, col) = dem.get_cell_from_point(p.x, p.y);
+                                min_row = min_row.min(row);
+                                max_row = max_row.max(row);
+                                min_col = min_col.min(col);
+                                max_col = max_col.max(col);
+                            }
+                            min_row = min_row.max(0);
+                            max_row = max_row.min(rows - 1);
+                            min_col = min_col.max(0);
+                            max_col = max_col.min(columns - 1);
+
+                            let num_parts = record.num_parts as usize;
+                            let mut part_bounds = Vec::with_capacity(num_parts);
+                            for part in 0..num_parts {
+                                let start_point = record.parts[part] as usize;
+                                let end_point = if part < num_parts - 1 {
+                                    record.parts[part + 1] as usize
+                                } else {
+                                    record.num_points as usize
+                                };
+                                // the first part is always treated as a hull
+                                let is_hole = part > 0 && record.is_hole(part as i32);
+                                part_bounds.push((start_point, end_point, is_hole));
+                            }
+
+                            for row in min_row..=max_row {
+                                for col in min_col..=max_col {
+                                    let z = dem.get_value(row, col);
+                                    if z == nodata {
+                                        continue;
+                                    }
+                                    let (x, y) = dem.get_cell_centre(row, col);
+                                    let point = Point2D::new(x, y);
+                                    let mut inside = false;
+                                    for &(start_point, end_point, is_hole) in &part_bounds {
+                                        if point_in_poly(
+                                            &point,
+                                            &record.points[start_point..end_point],
+                                        ) {
+                                            if is_hole {
+                                                inside = false;
+                                                break;
+                                            }
+                                            inside = true;
+                                        }
+                                    }
+                                    if inside {
+                                        values.push(z);
+                                    }
+                                }
+                            }
+                        }
+                        let zone = ZoneHypsometry::from_values(&mut values, num_points);
+                        tx.send((record_num, zone)).unwrap();
+                    }
+                });
+            }
+
+            let mut polygon_zones = vec![ZoneHypsometry::default(); num_records];
+            for record_num in 0..num_records {
+                let (rec, zone) = rx.recv().expect("Error receiving data from thread.");
+                polygon_zones[rec] = zone;
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            // append the integral attributes to the polygons
+            polygons.attributes.add_field(&AttributeField::new(
+                "COUNT",
+                FieldDataType::Int,
+                9u8,
+                0u8,
+            ));
+            let field_names = ["AREA", "MIN_ELEV", "MAX_ELEV", "MEAN_ELEV", "RELIEF", "HI"];
+            for name in &field_names {
+                polygons.attributes.add_field(&AttributeField::new(
+                    name,
+                    FieldDataType::Real,
+                    12u8,
+                    6u8,
+                ));
+            }
+            for record_num in 0..num_records {
+                let zone = &polygon_zones[record_num];
+                polygons
+                    .attributes
+                    .set_value(record_num, "COUNT", FieldData::Int(zone.n as i32));
+                let values = [
+                    zone.n as f64 * cell_area,
+                    zone.min,
+                    zone.max,
+                    zone.mean,
+                    zone.relief(),
+                    zone.integral(),
+                ];
+                for i in 0..field_names.len() {
+                    let val = if zone.n > 0 && values[i].is_finite() {
+                        FieldData::Real(values[i])
+                    } else {
+                        FieldData::Null
+                    };
+                    polygons
+                        .attributes
+                        .set_value(record_num, field_names[i], val);
+                }
+            }
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match polygons.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Polygon attributes updated")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+
+            for record_num in 0..num_records {
+                if polygon_zones[record_num].n > 0 {
+                    zone_ids.push(format!("{}", record_num + 1));
+                    zones.push(polygon_zones[record_num].clone());
+                }
+            }
+        } else {
+            // make sure the zones raster shares the grid and CRS of the DEM
+            let zones_raster = align_raster_input(&dem, Raster::new(&zones_file, "r")?)?;
+            start = Instant::now();
+            let rows = dem.configs.rows as isize;
+            let columns = dem.configs.columns as isize;
+            let nodata = dem.configs.nodata;
+            let zones_nodata = zones_raster.configs.nodata;
+            let mut zone_values: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+            let (mut z, mut zone): (f64, f64);
+            for row in 0..rows {
+                for col in 0..columns {
+                    z = dem.get_value(row, col);
+                    zone = zones_raster.get_value(row, col);
+                    if z != nodata && zone != zones_nodata && zone != 0f64 {
+                        zone_values
+                            .entry(zone.round() as i64)
+                            .or_insert(vec![])
+                            .push(z);
+                    }
+                }
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                    if progress != old_progress {
+                        println!("Progress: {}%", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            for (id, mut values) in zone_values {
+                zone_ids.push(format!("{}", id));
+                zones.push(ZoneHypsometry::from_values(&mut values, num_points));
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if zones.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the zones contain valid DEM cells.",
+            ));
+        }
+
+        if !output_csv_file.is_empty() {
+            let f = File::create(&output_csv_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("ZONE,REL_HEIGHT,REL_AREA,ELEV,AREA\n".as_bytes())?;
+            for i in 0..zones.len() {
+                let zone = &zones[i];
+                for k in 0..=num_points {
+                    let rel_height = k as f64 / num_points as f64;
+                    writer.write_all(
+                        format!(
+                            "{},{},{},{},{}\n",
+                            zone_ids[i],
+                            rel_height,
+                            zone.curve[k],
+                            zone.min + rel_height * zone.relief(),
+                            zone.curve[k] * zone.n as f64 * cell_area
+                        )
+                        .as_bytes(),
+                    )?;
+                }
+            }
+            writer.flush()?;
+            if verbose {
+                println!("CSV file written to {}", output_csv_file);
+            }
+        }
+
+        let f = File::create(output_file.clone())?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <head>
+            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+            <title>Watershed Hypsometry</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"</head>
+        <body>
+            <h1>Watershed Hypsometry</h1>"#
+                .as_bytes(),
+        )?;
+
+        writer.write_all(
+            (format!(
+                "<p><strong>Input DEM</strong>: {}<br><strong>Input Zones</strong>: {}<br><strong>Number of Zones</strong>: {}</p>",
+                dem.get_short_filename(),
+                path::Path::new(&zones_file)
+                    .file_stem()
+                    .unwrap()
+                    .to_str()
+                    .unwrap(),
+                zones.len()
+            ))
+            .as_bytes(),
+        )?;
+
+        let mut xdata = vec![];
+        let mut ydata = vec![];
+        let mut series_names = vec![];
+        for i in 0..zones.len() {
+            // plot relative area on the x-axis, in increasing order
+            let mut x = zones[i].curve.clone();
+            x.reverse();
+            xdata.push(x);
+            ydata.push(
+                (0..=num_points)
+                    .rev()
+                    .map(|k| k as f64 / num_points as f64)
+                    .collect::<Vec<f64>>(),
+            );
+            series_names.push(format!("Zone {}", zone_ids[i]));
+        }
+
+        let graph = LineGraph {
+            parent_id: "graph".to_string(),
+            width: 700f64,
+            height: 500f64,
+            data_x: xdata,
+            data_y: ydata,
+            series_labels: series_names,
+            x_axis_label: "Relative Area (a/A)".to_string(),
+            y_axis_label: "Relative Height (h/H)".to_string(),
+            draw_points: false,
+            draw_gridlines: true,
+            draw_legend: zones.len() > 1 && zones.len() < 12,
+            draw_grey_background: false,
+        };
+
+        writer.write_all(
+            &format!("<div id='graph' align=\"center\">{}</div>", graph.get_svg()).as_bytes(),
+        )?;
+
+        writer.write_all("<br><table align=\"center\">".as_bytes())?;
+        writer.write_all("<caption>Zone Hypsometry</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Zone</th><th>Count</th><th>Area</th><th>Min. Elev.</th><th>Max. Elev.</th><th>Mean Elev.</th><th>Relief</th><th>HI</th></tr>".as_bytes())?;
+        for i in 0..zones.len() {
+            let zone = &zones[i];
+            writer.write_all(
+                format!(
+                    "<tr><td>{}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td></tr>",
+                    zone_ids[i],
+                    zone.n,
+                    zone.n as f64 * cell_area,
+                    zone.min,
+                    zone.max,
+                    zone.mean,
+                    zone.relief(),
+                    zone.integral()
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table>".as_bytes())?;
+
+        writer.write_all("</body>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(output_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+
+            println!("Complete! Please see {} for output.", output_file);
+        }
+
+        Ok(())
+    }
+}
+
+/// The hypsometry of a single zone.
+#[derive(Default, Clone)]
+struct ZoneHypsometry {
+    n: usize,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// The proportion of the zone's area above each of the evenly spaced relative heights.
+    curve: Vec<f64>,
+}
+
+impl ZoneHypsometry {
+    /// Calculates the hypsometry of a set of elevations. The values are sorted in place.
+    fn from_values(values: &mut Vec<f64>, num_points: usize) -> ZoneHypsometry {
+        let n = values.len();
+        if n == 0 {
+            return ZoneHypsometry {
+                curve: vec![f64::NAN; num_points + 1],
+                ..Default::default()
+            };
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
+        let min = values[0];
+        let max = values[n - 1];
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let mut curve = Vec::with_capacity(num_points + 1);
+        curve.push(1f64);
+        let mut index = 0usize;
+        for k in 1..=num_points {
+            let z = min + (max - min) * k as f64 / num_points as f64;
+            // the values are sorted, so the number of cells below z never decreases
+            while index < n && values[index] < z {
+                index += 1;
+            }
+            curve.push((n - index) as f64 / n as f64);
+        }
+        if max > min {
+            // only cells at the zone's maximum elevation lie at h/H = 1; the curve must close at zero
+            curve[num_points] = 0f64;
+        }
+        ZoneHypsometry {
+            n,
+            min,
+            max,
+            mean,
+            curve,
+        }
+    }
+
+    fn relief(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// The hypsometric integral, calculated as the elevation-relief ratio.
+    fn integral(&self) -> f64 {
+        if self.max > self.min {
+            (self.mean - self.min) / (self.max - self.min)
+        } else {
+            f64::NAN
+        }
+    }
+}
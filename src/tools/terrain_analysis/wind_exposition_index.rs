@@ -0,0 +1,351 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::wind_exposure::{resolution_in_metres, trace_ray};
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates a wind exposition index for each grid cell of a digital elevation model
+/// (DEM; `--dem`), describing its exposure to, or shelter from, winds blowing from any direction.
+/// Following Gerlitz et al. (2015), the index is the average of a directional wind effect over a
+/// number of evenly spaced directions (`--num_directions`; default 36). The directional wind effect
+/// is derived from the terrain upwind of the cell, within a maximum search distance (`--max_dist`,
+/// in metres; default 1000 m), using the distance-weighted windward horizon of Böhner and Antonić
+/// (2009):
+///
+/// > H = Σ(tan<sup>-1</sup>((z<sub>i</sub> - z<sub>0</sub>) / d<sub>i</sub>) / d<sub>i</sub>) / Σ(1 / d<sub>i</sub>)
+///
+/// where z<sub>0</sub> is the elevation of the grid cell and z<sub>i</sub> is the elevation of the
+/// terrain at a distance d<sub>i</sub> upwind, such that nearby terrain has the greatest influence.
+/// The directional wind effect is 1 - 2H/π, which is one on a plain, less than one where the
+/// upwind terrain rises above the cell (wind shadow), and greater than one where the cell rises
+/// above the upwind terrain. Thus, index values smaller than one indicate sheltered cells, e.g. in
+/// valleys and on lee slopes, and values greater than one indicate exposed cells, e.g. on ridges
+/// and summits. Unlike `WinstralShelterIndex`, which describes the shelter from a prevailing wind
+/// direction, the index describes the exposure to winds from all directions.
+///
+/// Upwind elevations are estimated using bilinear interpolation, and the search along a direction
+/// stops at the edge of the DEM or at NoData. Distances are in metres; if the DEM is in geographic
+/// coordinates, its resolution is converted to metres at its mid-latitude.
+///
+/// # Reference
+/// Böhner, J., and Antonić, O. (2009). Land-surface parameters specific to topo-climatology. In
+/// Hengl, T., and Reuter, H. I. (Eds.), *Geomorphometry: Concepts, Software, Applications*,
+/// Developments in Soil Science, 33, 195-226.
+///
+/// Gerlitz, L., Conrad, O., and Böhner, J. (2015). Large-scale atmospheric forcing and topographic
+/// modification of precipitation rates over High Asia - a neural-network-based approach. *Earth
+/// System Dynamics*, 6(1), 61-81.
+///
+/// # See Also
+/// `WinstralShelterIndex`, `WaterFetch`, `SkyViewFactor`, `Openness`
+pub struct WindExpositionIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl WindExpositionIndex {
+    pub fn new() -> WindExpositionIndex {
+        // public constructor
+        let name = "WindExpositionIndex".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates a wind exposition index averaging the wind effect over all directions."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Wind Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of evenly spaced wind directions that are averaged.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("36".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance (m)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum upwind search distance, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1000.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=exposition.tif --num_directions=72 --max_dist=2000.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        WindExpositionIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for WindExpositionIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut num_directions = 36usize;
+        let mut max_dist = 1000.0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-num_directions" {
+                num_directions = value
+                    .parse::<usize>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_dist" {
+                max_dist = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if num_directions < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least four wind directions are required.",
+            ));
+        }
+        if max_dist <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let res = resolution_in_metres(&input);
+
+        let directions: Arc<Vec<f64>> = Arc::new(
+            (0..num_directions)
+                .map(|d| d as f64 * 360f64 / num_directions as f64)
+                .collect(),
+        );
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let directions = directions.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let z0 = input.get_value(row, col);
+                        if z0 == nodata {
+                            continue;
+                        }
+                        let mut sum = 0f64;
+                        for &a in directions.iter() {
+                            let (mut sum_angles, mut sum_weights) = (0f64, 0f64);
+                            trace_ray((rows, columns), (row, col), a, max_dist, res, |r, c, d| {
+                                let z = input.get_interpolated_value(
+                                    r,
+                                    c,
+                                    InterpolationMethod::Bilinear,
+                                );
+                                if z == nodata {
+                                    return false;
+                                }
+                                sum_angles += ((z - z0) / d).atan() / d;
+                                sum_weights += 1f64 / d;
+                                true
+                            });
+                            let horizon = if sum_weights > 0f64 {
+                                sum_angles / sum_weights
+                            } else {
+                                0f64
+                            };
+                            sum += 1f64 - 2f64 * horizon / f64::consts::PI;
+                        }
+                        data[col as usize] = sum / num_directions as f64;
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "grey.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Num. directions: {}", num_directions));
+        output.add_metadata_entry(format!("Max dist: {}", max_dist));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
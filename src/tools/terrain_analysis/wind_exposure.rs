@@ -0,0 +1,66 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Ray tracing shared by the WinstralShelterIndex, WindExpositionIndex, and WaterFetch tools.
+Rays leave a grid cell in a compass direction and are sampled at steps of one grid cell, in the
+manner of the SkyViewFactor and SolarRadiation tools. Distances are measured in metres, with the
+grid resolution of DEMs in geographic coordinates converted at the DEM's mid-latitude.
+*/
+
+use crate::raster::Raster;
+
+/// Returns the x and y grid resolutions of a raster in metres.
+pub fn resolution_in_metres(input: &Raster) -> (f64, f64) {
+    let mut res_x = input.configs.resolution_x;
+    let mut res_y = input.configs.resolution_y;
+    if input.is_in_geographic_coordinates() {
+        let mid_lat = (input.configs.north + input.configs.south) / 2.0;
+        if mid_lat <= 90.0 && mid_lat >= -90.0 {
+            res_x = res_x * (113200.0 * mid_lat.to_radians().cos());
+            res_y = res_y * 113200.0;
+        }
+    }
+    (res_x, res_y)
+}
+
+/// Steps along a ray leaving the cell `(row, col)` of a grid with dimensions `(rows, columns)` in
+/// the direction `azimuth` (in degrees clockwise from north), calling `visit` with the fractional
+/// row and column of each sample and its distance from the cell, until the edge of the grid or
+/// `max_dist` is reached, or `visit` returns false. `res` is the grid resolution in metres. Returns
+/// the distance of the last sample visited.
+pub fn trace_ray<F: FnMut(f64, f64, f64) -> bool>(
+    (rows, columns): (isize, isize),
+    (row, col): (isize, isize),
+    azimuth: f64,
+    max_dist: f64,
+    (res_x, res_y): (f64, f64),
+    mut visit: F,
+) -> f64 {
+    let a = azimuth.to_radians();
+    let (dr, dc) = (-a.cos(), a.sin());
+    // the distance covered by a step of one grid cell in this direction
+    let step = ((dr * res_y).powi(2) + (dc * res_x).powi(2)).sqrt();
+    let mut last = 0f64;
+    let mut s = 1f64;
+    loop {
+        let dist = s * step;
+        if dist > max_dist {
+            break;
+        }
+        let r = row as f64 + dr * s;
+        let c = col as f64 + dc * s;
+        if r < 0f64 || c < 0f64 || r > (rows - 1) as f64 || c > (columns - 1) as f64 {
+            break;
+        }
+        last = dist;
+        if !visit(r, c, dist) {
+            break;
+        }
+        s += 1f64;
+    }
+    last
+}
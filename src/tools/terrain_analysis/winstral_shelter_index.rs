@@ -0,0 +1,362 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::wind_exposure::{resolution_in_metres, trace_ray};
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the maximum upwind slope index, S<sub>x</sub>, of Winstral et al. (2002),
+/// which characterizes the degree to which each grid cell of a digital elevation model (DEM;
+/// `--dem`) is sheltered from, or exposed to, the wind blowing from a specified direction
+/// (`--azimuth`, in degrees clockwise from north). S<sub>x</sub> is the maximum elevation angle,
+/// in degrees, from the grid cell to the terrain upwind of it, within a maximum search distance
+/// (`--max_dist`, in metres; default 100 m):
+///
+/// > S<sub>x</sub> = max[tan<sup>-1</sup>((z<sub>i</sub> - z<sub>0</sub>) / d<sub>i</sub>)]
+///
+/// where z<sub>0</sub> is the elevation of the grid cell and z<sub>i</sub> is the elevation of the
+/// terrain at a distance d<sub>i</sub> upwind. Positive values indicate cells that are sheltered by
+/// upwind terrain, such as lee slopes, where wind-blown snow tends to be deposited, and negative
+/// values indicate cells that are exposed to the wind, such as ridges, which tend to be scoured.
+/// Because wind directions vary, S<sub>x</sub> is averaged over the directions within a window
+/// (`--window`, in degrees; default 30°) centred on the wind direction, at increments of 5°.
+///
+/// Upwind elevations are estimated using bilinear interpolation, and the search along a direction
+/// stops at the edge of the DEM or at NoData. Distances are in metres; if the DEM is in geographic
+/// coordinates, its resolution is converted to metres at its mid-latitude.
+///
+/// # Reference
+/// Winstral, A., Elder, K., and Davis, R. E. (2002). Spatial snow modeling of wind-redistributed
+/// snow using terrain-based parameters. *Journal of Hydrometeorology*, 3(5), 524-538.
+///
+/// # See Also
+/// `WindExpositionIndex`, `WaterFetch`, `FetchAnalysis`, `HorizonAngle`
+pub struct WinstralShelterIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl WinstralShelterIndex {
+    pub fn new() -> WinstralShelterIndex {
+        // public constructor
+        let name = "WinstralShelterIndex".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates the maximum upwind slope (Sx) wind shelter index of Winstral et al. (2002)."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Wind Direction (degrees)".to_owned(),
+            flags: vec!["--azimuth".to_owned()],
+            description: "Direction from which the wind blows, in degrees clockwise from north."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("270.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance (m)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum upwind search distance, in metres.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Direction Window (degrees)".to_owned(),
+            flags: vec!["--window".to_owned()],
+            description: "Width of the window of wind directions that are averaged, in degrees."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("30.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=sx.tif --azimuth=225.0 --max_dist=150.0 --window=40.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        WinstralShelterIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for WinstralShelterIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut output_file = String::new();
+        let mut azimuth = 270.0f64;
+        let mut max_dist = 100.0f64;
+        let mut window = 30.0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-dem" {
+                input_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-azimuth" {
+                azimuth = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-max_dist" {
+                max_dist = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-window" {
+                window = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if max_dist <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let res = resolution_in_metres(&input);
+
+        // the wind directions within the window, at 5-degree increments
+        let window = window.max(0f64).min(360f64);
+        let num_directions = (window / 5f64).floor() as usize + 1;
+        let directions: Arc<Vec<f64>> = Arc::new(
+            (0..num_directions)
+                .map(|d| {
+                    let offset = if num_directions > 1 {
+                        -window / 2f64 + d as f64 * window / (num_directions - 1) as f64
+                    } else {
+                        0f64
+                    };
+                    (azimuth + offset).rem_euclid(360f64)
+                })
+                .collect(),
+        );
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let directions = directions.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let z0 = input.get_value(row, col);
+                        if z0 == nodata {
+                            continue;
+                        }
+                        let mut sum = 0f64;
+                        let mut n = 0f64;
+                        for &a in directions.iter() {
+                            let mut tan_max = f64::NEG_INFINITY;
+                            trace_ray((rows, columns), (row, col), a, max_dist, res, |r, c, d| {
+                                let z = input.get_interpolated_value(
+                                    r,
+                                    c,
+                                    InterpolationMethod::Bilinear,
+                                );
+                                if z == nodata {
+                                    return false;
+                                }
+                                tan_max = tan_max.max((z - z0) / d);
+                                true
+                            });
+                            if tan_max.is_finite() {
+                                sum += tan_max.atan().to_degrees();
+                                n += 1f64;
+                            }
+                        }
+                        data[col as usize] = if n > 0f64 { sum / n } else { 0f64 };
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "blue_white_red.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Azimuth: {}", azimuth));
+        output.add_metadata_entry(format!("Max dist: {}", max_dist));
+        output.add_metadata_entry(format!("Window: {}", window));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
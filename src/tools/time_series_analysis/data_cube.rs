@@ -0,0 +1,337 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: A data cube is a stack of co-registered raster layers representing a time series.
+It is read either from a multi-band raster, with one band per time step, or from a text file
+listing one single-band raster per line, each optionally followed by its acquisition date,
+e.g. `ndvi_2020_06.tif, 2020-06-15`. The date may in turn be followed by a quality mask raster, in
+the class values of the CloudMask tool, e.g. `ndvi_2020_06.tif, 2020-06-15, qa_2020_06.tif`, in which
+case the layer's cells that are cloud, cloud shadow, or unobserved in the mask are treated as missing.
+The dates of a multi-band raster are taken from the
+`--dates` argument, a comma-separated list, or else from the band dates recorded in the
+raster's metadata by the time-series tools. Dated layers are sorted chronologically.
+*/
+
+use crate::raster::*;
+use crate::tools::align_raster_input;
+use chrono::prelude::*;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, Error, ErrorKind};
+use std::path;
+
+const DATE_METADATA_PREFIX: &str = "Date of band ";
+
+// the quality mask classes that are treated as missing observations
+const QA_CLOUD_SHADOW: f64 = 2f64;
+const QA_CLOUD: f64 = 4f64;
+const QA_FILL: f64 = 255f64;
+
+pub struct DataCube {
+    /// The grid of the cube's layers.
+    pub configs: RasterConfigs,
+    /// The date of each layer, if the time series is dated.
+    pub dates: Option<Vec<NaiveDate>>,
+    /// A descriptive label for each layer.
+    pub labels: Vec<String>,
+    layers: Vec<Raster>,
+}
+
+impl DataCube {
+    /// Reads a data cube from either a multi-band raster or a text (.txt or .csv) file
+    /// listing the layers' rasters. `dates` is an optional comma-separated list of the
+    /// dates of a multi-band raster's bands.
+    pub fn read(input_file: &str, dates: &str, working_directory: &str) -> Result<DataCube, Error> {
+        let lower = input_file.to_lowercase();
+        let (layers, labels, layer_dates) = if lower.ends_with(".txt") || lower.ends_with(".csv") {
+            if !dates.trim().is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --dates argument only applies to multi-band inputs; list the dates of each raster in the input file instead.",
+                ));
+            }
+            read_file_list(input_file, working_directory)?
+        } else {
+            let input = Raster::new(input_file, "r")?;
+            let num_bands = input.num_bands();
+            let band_dates = if !dates.trim().is_empty() {
+                let mut v = vec![];
+                for s in dates.split(",") {
+                    v.push(parse_date(s).ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "Unrecognized date '{}'; dates must be formatted as YYYY-MM-DD.",
+                                s.trim()
+                            ),
+                        )
+                    })?);
+                }
+                if v.len() != num_bands {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The number of dates ({}) does not match the number of bands in the input raster ({}).",
+                            v.len(),
+                            num_bands
+                        ),
+                    ));
+                }
+                Some(v)
+            } else {
+                read_date_metadata(&input.configs.metadata, num_bands)
+            };
+            let mut layers = Vec::with_capacity(num_bands);
+            let mut labels = Vec::with_capacity(num_bands);
+            for band in 0..num_bands {
+                layers.push(input.get_band(band));
+                labels.push(format!("Band {}", band + 1));
+            }
+            (layers, labels, band_dates)
+        };
+
+        if layers.len() < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A time series must contain at least two layers.",
+            ));
+        }
+
+        let mut order: Vec<usize> = (0..layers.len()).collect();
+        if let Some(ref d) = layer_dates {
+            order.sort_by(|a, b| d[*a].cmp(&d[*b]));
+        }
+        let mut slots: Vec<Option<Raster>> = layers.into_iter().map(|r| Some(r)).collect();
+        let mut sorted_layers: Vec<Raster> = Vec::with_capacity(order.len());
+        for &i in &order {
+            let layer = slots[i].take().unwrap();
+            let layer = if sorted_layers.is_empty() {
+                layer
+            } else {
+                align_raster_input(&sorted_layers[0], layer)?
+            };
+            sorted_layers.push(layer);
+        }
+        let mut configs = sorted_layers[0].configs.clone();
+        configs.bands = 1;
+        configs.metadata = vec![];
+
+        Ok(DataCube {
+            configs: configs,
+            dates: layer_dates.map(|d| order.iter().map(|&i| d[i]).collect()),
+            labels: order.iter().map(|&i| labels[i].clone()).collect(),
+            layers: sorted_layers,
+        })
+    }
+
+    /// Returns the number of layers (time steps) in the cube.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns true if the acquisition date of each layer is known.
+    pub fn is_dated(&self) -> bool {
+        self.dates.is_some()
+    }
+
+    /// Returns the time of each layer as a decimal year, if the cube is dated, or
+    /// otherwise as the layer's (zero-based) index.
+    pub fn times(&self) -> Vec<f64> {
+        match self.dates {
+            Some(ref d) => d.iter().map(|date| decimal_year(date)).collect(),
+            None => (0..self.layers.len()).map(|i| i as f64).collect(),
+        }
+    }
+
+    /// Returns the series of values at a grid cell, with missing (NoData) values
+    /// represented by NaN.
+    pub fn get_series(&self, row: isize, column: isize) -> Vec<f64> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let z = layer.get_value(row, column);
+                if z == layer.configs.nodata {
+                    f64::NAN
+                } else {
+                    z
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the value of a grid cell in one layer, with missing (NoData) values
+    /// represented by NaN.
+    pub fn get_value(&self, layer: usize, row: isize, column: isize) -> f64 {
+        let z = self.layers[layer].get_value(row, column);
+        if z == self.layers[layer].configs.nodata {
+            f64::NAN
+        } else {
+            z
+        }
+    }
+
+    /// Creates a new 32-bit floating point output raster, with the grid of the cube
+    /// and the specified number of bands.
+    pub fn new_output(&self, file_name: &str, bands: usize) -> Raster {
+        let mut configs = self.configs.clone();
+        configs.data_type = DataType::F32;
+        configs.nodata = -32768f64;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+        configs.palette = "grey.plt".to_string();
+        let mut output = Raster::initialize_using_config(file_name, &configs);
+        output.set_num_bands(bands);
+        output
+    }
+
+    /// Records the dates of the cube's layers in the metadata of an output raster that
+    /// has one band per layer, allowing the time series to be read back as a data cube.
+    pub fn add_date_metadata(&self, output: &mut Raster) {
+        if let Some(ref d) = self.dates {
+            add_band_dates(output, d);
+        }
+    }
+}
+
+/// Records the date of each band of a multi-band raster in its metadata, allowing the
+/// raster to be read as a dated data cube.
+pub fn add_band_dates(output: &mut Raster, dates: &[NaiveDate]) {
+    for (band, date) in dates.iter().enumerate() {
+        output.add_metadata_entry(format!(
+            "{}{}: {}",
+            DATE_METADATA_PREFIX,
+            band + 1,
+            date.format("%Y-%m-%d")
+        ));
+    }
+}
+
+/// Parses a date formatted as YYYY-MM-DD, YYYY/MM/DD, or YYYYMMDD. Any time of day
+/// following the date is ignored.
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+    let s = s.split(|c: char| c == 'T' || c == ' ').next().unwrap_or("");
+    for fmt in &["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"] {
+        if let Ok(d) = NaiveDate::parse_from_str(s, fmt) {
+            return Some(d);
+        }
+    }
+    None
+}
+
+/// Converts a date into a decimal year, e.g. 2020-07-02 is 2020.5.
+pub fn decimal_year(date: &NaiveDate) -> f64 {
+    let days_in_year = if NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some() {
+        366f64
+    } else {
+        365f64
+    };
+    date.year() as f64 + (date.ordinal() - 1) as f64 / days_in_year
+}
+
+fn read_file_list(
+    input_file: &str,
+    working_directory: &str,
+) -> Result<(Vec<Raster>, Vec<String>, Option<Vec<NaiveDate>>), Error> {
+    let sep: String = path::MAIN_SEPARATOR.to_string();
+    let f = File::open(input_file)?;
+    let f = BufReader::new(f);
+    let mut layers = vec![];
+    let mut labels = vec![];
+    let mut dates = vec![];
+    for line in f.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#") {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c == ';' || c == '\t')
+            .map(|s| s.trim())
+            .collect();
+        let mut file_name = fields[0].replace("\"", "");
+        if fields.len() > 1 && !fields[1].is_empty() {
+            match parse_date(fields[1]) {
+                Some(d) => dates.push(d),
+                None => {
+                    if layers.is_empty() {
+                        continue; // a header line
+                    }
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Unrecognized date '{}'; dates must be formatted as YYYY-MM-DD.",
+                            fields[1]
+                        ),
+                    ));
+                }
+            }
+        }
+        if !file_name.contains(&sep) && !file_name.contains("/") {
+            file_name = format!("{}{}", working_directory, file_name);
+        }
+        let mut raster = Raster::new(&file_name, "r")?;
+        if fields.len() > 2 && !fields[2].is_empty() {
+            let mut mask_file = fields[2].replace("\"", "");
+            if !mask_file.contains(&sep) && !mask_file.contains("/") {
+                mask_file = format!("{}{}", working_directory, mask_file);
+            }
+            apply_quality_mask(&mut raster, Raster::new(&mask_file, "r")?)?;
+        }
+        labels.push(raster.get_short_filename());
+        layers.push(raster);
+    }
+    let dates = if dates.is_empty() {
+        None
+    } else if dates.len() == layers.len() {
+        Some(dates)
+    } else {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Either all or none of the rasters listed in the input file must have a date.",
+        ));
+    };
+    Ok((layers, labels, dates))
+}
+
+/// Sets the cells of a layer to NoData where a quality mask, with the class values of the
+/// CloudMask tool, is cloud, cloud shadow, or unobserved.
+fn apply_quality_mask(layer: &mut Raster, mask: Raster) -> Result<(), Error> {
+    let mask = align_raster_input(layer, mask)?;
+    let nodata = layer.configs.nodata;
+    let mask_nodata = mask.configs.nodata;
+    for row in 0..layer.configs.rows as isize {
+        for col in 0..layer.configs.columns as isize {
+            let m = mask.get_value(row, col);
+            if m == mask_nodata || m == QA_CLOUD_SHADOW || m == QA_CLOUD || m == QA_FILL {
+                layer.set_value(row, col, nodata);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_date_metadata(metadata: &[String], num_bands: usize) -> Option<Vec<NaiveDate>> {
+    let mut dates: Vec<Option<NaiveDate>> = vec![None; num_bands];
+    for entry in metadata {
+        if entry.starts_with(DATE_METADATA_PREFIX) {
+            let rest = &entry[DATE_METADATA_PREFIX.len()..];
+            let mut parts = rest.splitn(2, ':');
+            let band = parts.next().and_then(|b| b.trim().parse::<usize>().ok());
+            let date = parts.next().and_then(|d| parse_date(d));
+            if let (Some(band), Some(date)) = (band, date) {
+                if band >= 1 && band <= num_bands {
+                    dates[band - 1] = Some(date);
+                }
+            }
+        }
+    }
+    if dates.iter().all(|d| d.is_some()) {
+        Some(dates.into_iter().map(|d| d.unwrap()).collect())
+    } else {
+        None
+    }
+}
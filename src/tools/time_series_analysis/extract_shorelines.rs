@@ -0,0 +1,426 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::DataCube;
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use chrono::prelude::*;
+use std::collections::HashMap;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool extracts shorelines from a time series of water masks, such as those derived by
+/// thresholding the `NormalizedDifferenceWaterIndex` or `ModifiedNormalizedDifferenceWaterIndex`
+/// of a series of satellite images. The time series (`--input`) may be either a multi-band raster,
+/// with one band per date, or a text file (.txt or .csv) listing one raster per line, each followed
+/// by its acquisition date (YYYY-MM-DD), e.g. `water_2015_07.tif, 2015-07-12`. The dates of the
+/// bands of a multi-band raster may be specified with the `--dates` parameter, as a comma-separated
+/// list. In each water mask, non-zero cells are water, zero cells are land, and NoData cells are
+/// unobserved, e.g. because of clouds.
+///
+/// Shorelines are traced along the boundaries between the land and water cells of each mask using
+/// the marching squares algorithm, with vertices placed midway between the centres of neighbouring
+/// land and water cells, and no shoreline is traced next to unobserved cells. Diagonally adjacent
+/// water cells are treated as connected. Shorelines shorter than a minimum length (`--min_length`,
+/// in map units), which are often the edges of small ponds or of misclassified cells, may be
+/// removed.
+///
+/// The output polyline vector (`--output`) contains one feature per shoreline, with the index of
+/// its layer in the time series (LAYER), its acquisition date (DATE), and the date as a decimal
+/// year (YEAR). If the layers are not dated, the YEAR field contains the zero-based layer index.
+/// The output may be used as the input of the `ShorelineChangeAnalysis` tool.
+///
+/// # See Also
+/// `ShorelineChangeAnalysis`, `ContoursFromRaster`, `NormalizedDifferenceWaterIndex`
+pub struct ExtractShorelines {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExtractShorelines {
+    pub fn new() -> ExtractShorelines {
+        // public constructor
+        let name = "ExtractShorelines".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Extracts dated shoreline polylines from a time series of water masks.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Water Mask Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one water mask (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Shorelines File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polyline file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Shoreline Length".to_owned(),
+            flags: vec!["--min_length".to_owned()],
+            description: "Minimum length of output shorelines, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=water_masks.txt -o=shorelines.shp --min_length=500.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ExtractShorelines {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExtractShorelines {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut min_length = 0f64;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-dates" {
+                dates = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-min_length" {
+                min_length = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = DataCube::read(&input_file, &dates, working_directory)?;
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+        if verbose {
+            println!(
+                "Number of layers: {}{}",
+                num_layers,
+                if cube.is_dated() { "" } else { " (undated)" }
+            );
+        }
+
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = cube.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("LAYER", FieldDataType::Int, 4u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("DATE", FieldDataType::Date, 8u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("YEAR", FieldDataType::Real, 12u8, 4u8));
+
+        // the position of the midpoint of a grid edge between two cell centres; horizontal edges
+        // have even IDs and vertical edges odd IDs
+        let res_x = cube.configs.resolution_x;
+        let res_y = cube.configs.resolution_y;
+        let west = cube.configs.west;
+        let north = cube.configs.north;
+        let edge_point = |id: usize| -> Point2D {
+            let cell = id / 2;
+            let (row, col) = (
+                (cell / columns as usize) as f64,
+                (cell % columns as usize) as f64,
+            );
+            let x = west + (col + 0.5) * res_x;
+            let y = north - (row + 0.5) * res_y;
+            if id % 2 == 0 {
+                Point2D::new(x + res_x / 2f64, y)
+            } else {
+                Point2D::new(x, y - res_y / 2f64)
+            }
+        };
+
+        let times = cube.times();
+        let mut fid = 0;
+        for layer in 0..num_layers {
+            let is_water = |row: isize, col: isize| -> Option<bool> {
+                let z = cube.get_value(layer, row, col);
+                if z.is_nan() {
+                    None
+                } else {
+                    Some(z != 0f64)
+                }
+            };
+
+            // trace the shoreline segments of each square of four cell centres
+            let mut segments: Vec<(usize, usize)> = vec![];
+            for row in 0..rows - 1 {
+                for col in 0..columns - 1 {
+                    let corners = [
+                        is_water(row, col),
+                        is_water(row, col + 1),
+                        is_water(row + 1, col + 1),
+                        is_water(row + 1, col),
+                    ];
+                    if corners.iter().any(|c| c.is_none()) {
+                        continue;
+                    }
+                    let case = corners
+                        .iter()
+                        .fold(0usize, |acc, c| (acc << 1) | c.unwrap() as usize);
+                    let top = 2 * (row * columns + col) as usize;
+                    let bottom = 2 * ((row + 1) * columns + col) as usize;
+                    let left = 2 * (row * columns + col) as usize + 1;
+                    let right = 2 * (row * columns + col + 1) as usize + 1;
+                    match case {
+                        1 | 14 => segments.push((left, bottom)),
+                        2 | 13 => segments.push((bottom, right)),
+                        3 | 12 => segments.push((left, right)),
+                        4 | 11 => segments.push((top, right)),
+                        6 | 9 => segments.push((top, bottom)),
+                        7 | 8 => segments.push((left, top)),
+                        5 => {
+                            // a saddle, with the diagonal water cells connected
+                            segments.push((left, top));
+                            segments.push((bottom, right));
+                        }
+                        10 => {
+                            segments.push((top, right));
+                            segments.push((left, bottom));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            // chain the segments into lines, starting with the open lines
+            let mut at_edge: HashMap<usize, Vec<usize>> = HashMap::new();
+            for (i, s) in segments.iter().enumerate() {
+                at_edge.entry(s.0).or_insert(vec![]).push(i);
+                at_edge.entry(s.1).or_insert(vec![]).push(i);
+            }
+            let mut used = vec![false; segments.len()];
+            let mut starts: Vec<usize> = segments
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| at_edge[&s.0].len() == 1 || at_edge[&s.1].len() == 1)
+                .map(|(i, _)| i)
+                .collect();
+            starts.extend(0..segments.len());
+            for i in starts {
+                if used[i] {
+                    continue;
+                }
+                used[i] = true;
+                let (a, b) = segments[i];
+                // lines are traced from an open end; closed lines end where they start
+                let (first, mut current) = if at_edge[&a].len() == 1 {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                let mut line = vec![edge_point(first), edge_point(current)];
+                loop {
+                    let next = at_edge[&current].iter().find(|&&j| !used[j]).cloned();
+                    match next {
+                        Some(j) => {
+                            used[j] = true;
+                            let (a, b) = segments[j];
+                            current = if a == current { b } else { a };
+                            line.push(edge_point(current));
+                        }
+                        None => break,
+                    }
+                }
+                let length: f64 = (1..line.len())
+                    .map(|k| line[k - 1].distance(&line[k]))
+                    .sum();
+                if length < min_length {
+                    continue;
+                }
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&line);
+                output.add_record(sfg);
+                fid += 1;
+                let date = match cube.dates {
+                    Some(ref d) => FieldData::Date(DateData {
+                        year: d[layer].year() as u16,
+                        month: d[layer].month() as u8,
+                        day: d[layer].day() as u8,
+                    }),
+                    None => FieldData::Null,
+                };
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(layer as i32 + 1),
+                        date,
+                        FieldData::Real(times[layer]),
+                    ],
+                    false,
+                );
+            }
+
+            if verbose {
+                progress = (100.0_f64 * layer as f64 / (num_layers - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Number of shorelines: {}", fid);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+// private sub-module defined in other files
+mod data_cube;
+mod extract_shorelines;
+mod phenology_metrics;
+mod shoreline_change_analysis;
+mod time_series_aggregate;
+mod time_series_anomalies;
+mod time_series_gap_fill;
+mod time_series_savitzky_golay;
+mod time_series_trend;
+mod time_series_whittaker;
+
+// exports identifiers from private sub-modules in the current module namespace
+pub use self::extract_shorelines::ExtractShorelines;
+pub use self::phenology_metrics::PhenologyMetrics;
+pub use self::shoreline_change_analysis::ShorelineChangeAnalysis;
+pub use self::time_series_aggregate::TimeSeriesAggregate;
+pub use self::time_series_anomalies::TimeSeriesAnomalies;
+pub use self::time_series_gap_fill::TimeSeriesGapFill;
+pub use self::time_series_savitzky_golay::TimeSeriesSavitzkyGolay;
+pub use self::time_series_trend::TimeSeriesTrend;
+pub use self::time_series_whittaker::TimeSeriesWhittaker;
@@ -0,0 +1,639 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::{add_band_dates, DataCube};
+use crate::algorithms::{double_logistic, fit_double_logistic, savitzky_golay};
+use crate::raster::*;
+use crate::tools::*;
+use chrono::prelude::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool extracts land surface phenology metrics from a vegetation index (e.g. NDVI or EVI) time
+/// series, i.e. a data cube of co-registered images. For each grid cell and growing season, a smooth
+/// curve is fitted to the valid observations using either a double logistic function (`--method=logistic`,
+/// the default), fitted with the Levenberg-Marquardt algorithm, or a second-degree Savitzky-Golay filter
+/// (`--method=savitzky_golay`) with a half-window of `--window` observations. Missing (NoData)
+/// observations, such as those masked for clouds, are ignored in the fitting.
+///
+/// The following metrics are extracted from the fitted curve using the seasonal amplitude threshold
+/// method. The start of season (SOS) is the time at which the curve rises above its minimum before the
+/// peak by a fraction (`--threshold`, default 0.2) of the rise in values, and the end of season (EOS) is
+/// the time at which it falls below its minimum after the peak by the same fraction of the decline.
+///
+/// | Output suffix | Metric                                                            |
+/// |---------------|-------------------------------------------------------------------|
+/// | `_sos`        | Start of season                                                   |
+/// | `_eos`        | End of season                                                     |
+/// | `_los`        | Length of season, i.e. EOS minus SOS                              |
+/// | `_peak`       | Peak (maximum) value of the fitted curve                          |
+/// | `_peak_time`  | Time of the peak value                                            |
+/// | `_amplitude`  | Seasonal amplitude, i.e. the peak value minus the mean of the minima before and after the peak |
+///
+/// The output files are named by appending these suffixes to the base name of `--output`. When the time
+/// series is dated, each calendar year is treated as one growing season, and times are expressed as the
+/// number of days since the start of the season year (i.e. the day of year). For locations in which the
+/// growing season spans the new year, such as the southern hemisphere, the first month of the season year
+/// may be set with `--start_month` (e.g. 7 for July). Time series spanning several years produce outputs
+/// with one band per season year, which must be written in the GeoTIFF or Whitebox v2 (.wbr) formats; these
+/// outputs are themselves dated time series, such that trends in phenology may be estimated using the
+/// `TimeSeriesTrend` tool. When the time series is undated, the entire series is treated as a single
+/// season and times are expressed in units of time steps (i.e. fractional layer numbers, starting at zero).
+///
+/// Seasons with fewer than `--min_obs` valid observations, and those for which the curve does not rise and
+/// fall through the thresholds, are assigned NoData.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a text
+/// file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition date
+/// (YYYY-MM-DD). The dates of the bands of a multi-band raster may be specified with the `--dates` parameter.
+///
+/// # Reference
+/// Jönsson, P., & Eklundh, L. (2004). TIMESAT—a program for analyzing time-series of satellite sensor data.
+/// *Computers & Geosciences*, 30(8), 833-845.
+///
+/// # See Also
+/// `TimeSeriesGapFill`, `TimeSeriesSavitzkyGolay`, `TimeSeriesWhittaker`, `TimeSeriesTrend`, `NormalizedDifferenceIndex`
+pub struct PhenologyMetrics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl PhenologyMetrics {
+    pub fn new() -> PhenologyMetrics {
+        // public constructor
+        let name = "PhenologyMetrics".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Extracts start, end, and length of season and peak value and timing from a vegetation index time series."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Base File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster base file name; a suffix is appended for each metric."
+                .to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Curve-Fitting Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Curve-fitting method; options are 'logistic' and 'savitzky_golay'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "logistic".to_owned(),
+                "savitzky_golay".to_owned(),
+            ]),
+            default_value: Some("logistic".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Amplitude Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description:
+                "Fraction of the seasonal amplitude defining the start and end of season (0-1)."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Savitzky-Golay Half-Window".to_owned(),
+            flags: vec!["--window".to_owned()],
+            description: "Number of observations on either side of each observation in the Savitzky-Golay filter."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Season Start Month".to_owned(),
+            flags: vec!["--start_month".to_owned()],
+            description: "First month (1-12) of each season year.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Number of Observations".to_owned(),
+            flags: vec!["--min_obs".to_owned()],
+            description: "Minimum number of valid observations in a season.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("7".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=ndvi_series.txt -o=pheno.tif --method=logistic --threshold=0.2",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        PhenologyMetrics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for PhenologyMetrics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("logistic");
+        let mut threshold = 0.2f64;
+        let mut half_window = 3usize;
+        let mut start_month = 1u32;
+        let mut min_obs = 7usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-threshold" {
+                threshold = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-window" {
+                half_window = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-start_month" {
+                start_month = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as u32
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as u32
+                };
+            } else if flag_val == "-min_obs" {
+                min_obs = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let use_logistic = if method.contains("log") {
+            true
+        } else if method.contains("sav") || method.contains("sg") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized --method; options are 'logistic' and 'savitzky_golay'.",
+            ));
+        };
+        if !(threshold > 0f64 && threshold < 1f64) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --threshold must be greater than 0 and less than 1.",
+            ));
+        }
+        if start_month < 1 || start_month > 12 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --start_month must be between 1 and 12.",
+            ));
+        }
+        half_window = half_window.max(1);
+        // a double logistic function has six parameters
+        min_obs = min_obs.max(if use_logistic { 7 } else { 3 });
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+
+        // assign the layers to seasons, and express their times relative to the season start
+        let mut layer_season = vec![0usize; num_layers];
+        let mut layer_times: Vec<f64> = (0..num_layers).map(|t| t as f64).collect();
+        let mut season_starts: Vec<NaiveDate> = vec![];
+        let mut season_lengths: Vec<f64> = vec![(num_layers - 1) as f64];
+        if let Some(ref d) = cube.dates {
+            let season_year = |date: &NaiveDate| -> i32 {
+                if date.month() >= start_month {
+                    date.year()
+                } else {
+                    date.year() - 1
+                }
+            };
+            let mut years: Vec<i32> = d.iter().map(|date| season_year(date)).collect();
+            years.dedup(); // the layers are in chronological order
+            season_starts = years
+                .iter()
+                .map(|y| NaiveDate::from_ymd(*y, start_month, 1))
+                .collect();
+            season_lengths = years
+                .iter()
+                .map(|y| {
+                    NaiveDate::from_ymd(*y + 1, start_month, 1)
+                        .signed_duration_since(NaiveDate::from_ymd(*y, start_month, 1))
+                        .num_days() as f64
+                })
+                .collect();
+            for t in 0..num_layers {
+                let s = years.binary_search(&season_year(&d[t])).unwrap();
+                layer_season[t] = s;
+                // day one is the first day of the season year
+                layer_times[t] =
+                    d[t].signed_duration_since(season_starts[s]).num_days() as f64 + 1f64;
+            }
+        }
+        let num_seasons = season_lengths.len();
+        if verbose {
+            println!("Number of seasons: {}", num_seasons);
+        }
+
+        let layer_season = Arc::new(layer_season);
+        let layer_times = Arc::new(layer_times);
+        let season_lengths = Arc::new(season_lengths);
+        let is_dated = cube.is_dated();
+        let num_metrics = 6;
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let layer_season = layer_season.clone();
+            let layer_times = layer_times.clone();
+            let season_lengths = season_lengths.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    // data[metric][season][col]
+                    let mut data =
+                        vec![vec![vec![nodata; columns as usize]; num_seasons]; num_metrics];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        for s in 0..num_seasons {
+                            let mut times = vec![];
+                            let mut values = vec![];
+                            for t in 0..num_layers {
+                                if layer_season[t] == s {
+                                    times.push(layer_times[t]);
+                                    values.push(series[t]);
+                                }
+                            }
+                            if values.iter().filter(|v| v.is_finite()).count() < min_obs {
+                                continue;
+                            }
+                            let (curve_times, curve_values) = if use_logistic {
+                                let params = match fit_double_logistic(&times, &values) {
+                                    Some(p) => p,
+                                    None => continue,
+                                };
+                                // evaluate the fitted curve daily, or every tenth of a time step
+                                let (first, step) = if is_dated {
+                                    (1f64, 1f64)
+                                } else {
+                                    (0f64, 0.1f64)
+                                };
+                                let num_steps =
+                                    ((season_lengths[s] - first) / step).round() as usize;
+                                let ct: Vec<f64> =
+                                    (0..=num_steps).map(|k| first + k as f64 * step).collect();
+                                let cv: Vec<f64> =
+                                    ct.iter().map(|t| double_logistic(&params, *t)).collect();
+                                (ct, cv)
+                            } else {
+                                let smoothed = savitzky_golay(&times, &values, half_window, 2);
+                                let mut ct = vec![];
+                                let mut cv = vec![];
+                                for k in 0..times.len() {
+                                    if smoothed[k].is_finite() {
+                                        ct.push(times[k]);
+                                        cv.push(smoothed[k]);
+                                    }
+                                }
+                                (ct, cv)
+                            };
+                            if let Some(m) = season_metrics(&curve_times, &curve_values, threshold)
+                            {
+                                for (metric, value) in m.iter().enumerate() {
+                                    data[metric][s][col as usize] = *value;
+                                }
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let extension = match output_file.rfind('.') {
+            Some(i) if !output_file[i..].contains(&sep) && !output_file[i..].contains('/') => {
+                output_file[i..].to_string()
+            }
+            _ => String::new(),
+        };
+        let stem = output_file[..output_file.len() - extension.len()].to_string();
+        let suffixes = ["sos", "eos", "los", "peak", "peak_time", "amplitude"];
+        let mut outputs: Vec<Raster> = suffixes
+            .iter()
+            .map(|suffix| {
+                cube.new_output(&format!("{}_{}{}", stem, suffix, extension), num_seasons)
+            })
+            .collect();
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for (metric, seasons) in data.into_iter().enumerate() {
+                for (band, values) in seasons.into_iter().enumerate() {
+                    outputs[metric].set_row_data_band(row, band, values);
+                }
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Saving data...")
+        };
+        for (metric, output) in outputs.iter_mut().enumerate() {
+            output.configs.palette = if metric == 3 || metric == 5 {
+                "grey.plt".to_string()
+            } else {
+                "spectrum.plt".to_string()
+            };
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Metric: {}", suffixes[metric]));
+            output.add_metadata_entry(format!("Method: {}", method));
+            output.add_metadata_entry(format!("Threshold: {}", threshold));
+            if metric != 3 && metric != 5 {
+                output.add_metadata_entry(format!(
+                    "Time units: {}",
+                    if is_dated {
+                        "days since the start of the season year"
+                    } else {
+                        "time steps"
+                    }
+                ));
+            }
+            if !season_starts.is_empty() {
+                add_band_dates(output, &season_starts);
+            }
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written: {}", output.get_short_filename())
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts the start, end, and length of season, the peak value and its time, and the
+/// seasonal amplitude from a fitted seasonal curve, using the amplitude threshold method.
+fn season_metrics(times: &[f64], values: &[f64], threshold: f64) -> Option<[f64; 6]> {
+    let n = values.len();
+    if n < 3 {
+        return None;
+    }
+    let mut peak = 0;
+    for i in 1..n {
+        if values[i] > values[peak] {
+            peak = i;
+        }
+    }
+    if peak == 0 || peak == n - 1 {
+        return None;
+    }
+    let base_left = values[..peak].iter().cloned().fold(f64::INFINITY, f64::min);
+    let base_right = values[peak + 1..]
+        .iter()
+        .cloned()
+        .fold(f64::INFINITY, f64::min);
+    if !(values[peak] > base_left && values[peak] > base_right) {
+        return None;
+    }
+    let left_threshold = base_left + threshold * (values[peak] - base_left);
+    let right_threshold = base_right + threshold * (values[peak] - base_right);
+
+    // search outwards from the peak for the threshold crossings
+    let mut sos = f64::NAN;
+    for i in (1..=peak).rev() {
+        if values[i - 1] < left_threshold && values[i] >= left_threshold {
+            let w = (left_threshold - values[i - 1]) / (values[i] - values[i - 1]);
+            sos = times[i - 1] + w * (times[i] - times[i - 1]);
+            break;
+        }
+    }
+    let mut eos = f64::NAN;
+    for i in peak..n - 1 {
+        if values[i] >= right_threshold && values[i + 1] < right_threshold {
+            let w = (values[i] - right_threshold) / (values[i] - values[i + 1]);
+            eos = times[i] + w * (times[i + 1] - times[i]);
+            break;
+        }
+    }
+    if !sos.is_finite() || !eos.is_finite() {
+        return None;
+    }
+    Some([
+        sos,
+        eos,
+        eos - sos,
+        values[peak],
+        times[peak],
+        values[peak] - (base_left + base_right) / 2f64,
+    ])
+}
@@ -0,0 +1,627 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::{decimal_year, parse_date};
+use crate::structures::Point2D;
+use crate::tools::*;
+use crate::vector::*;
+use chrono::prelude::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates rates of shoreline change along transects that are cast at regular
+/// intervals along a baseline, in the manner of the Digital Shoreline Analysis System (DSAS;
+/// Himmelstoss et al., 2018). The input polyline vector (`--input`) contains the shorelines of
+/// several dates, such as those output by the `ExtractShorelines` tool, with the date of each
+/// shoreline stored in an attribute field (`--date_field`; default DATE). The field may be a date
+/// field, a text field containing dates formatted as YYYY-MM-DD, or a numeric field containing
+/// decimal years.
+///
+/// Transects are cast perpendicular to the baseline (`--baseline`), a polyline vector that roughly
+/// parallels the shorelines, at a regular spacing along the baseline (`--spacing`, in map units).
+/// Each transect is centred on the baseline and has a specified length (`--length`), such that it
+/// extends half of its length on either side of the baseline. The orientation of the transects may
+/// be smoothed (`--smoothing`, in map units), in which case the baseline direction is measured
+/// between the points lying half of the smoothing distance before and after the transect, which
+/// prevents neighbouring transects from crossing where the baseline bends sharply.
+///
+/// The position of each shoreline along a transect is its intersection with the transect closest
+/// to the baseline, measured as a signed distance from the baseline. Distances are positive on the
+/// left-hand side of the baseline, as digitized; thus, if the baseline is digitized with the water
+/// on its left (or with the `--reverse` flag if the water is on its right), positive rates of
+/// change indicate accretion and negative rates indicate erosion. The output vector (`--output`)
+/// contains the transects, with the following DSAS statistics:
+///
+/// | Field | Description |
+/// |-------|-------------|
+/// | BASE_DIST | The distance of the transect along its baseline |
+/// | N_SHORES | The number of shoreline dates intersecting the transect |
+/// | SCE | Shoreline change envelope, the range of the shoreline positions |
+/// | NSM | Net shoreline movement, between the oldest and youngest shorelines |
+/// | EPR | End point rate, the NSM divided by the time elapsed between the two shorelines |
+/// | LRR | Linear regression rate, the least-squares slope of position against time |
+/// | LR2 | The coefficient of determination (R<sup>2</sup>) of the linear regression |
+///
+/// The change statistics require at least two shoreline dates, and the regression statistics at
+/// least three; transects with too few shorelines are assigned null values. Rates are expressed in
+/// map units per year.
+///
+/// # Reference
+/// Himmelstoss, E. A., Henderson, R. E., Kratzmann, M. G., and Farris, A. S. (2018). *Digital
+/// Shoreline Analysis System (DSAS) version 5.0 user guide*. U.S. Geological Survey Open-File
+/// Report 2018-1179.
+///
+/// # See Also
+/// `ExtractShorelines`, `TimeSeriesTrend`
+pub struct ShorelineChangeAnalysis {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ShorelineChangeAnalysis {
+    pub fn new() -> ShorelineChangeAnalysis {
+        // public constructor
+        let name = "ShorelineChangeAnalysis".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Calculates DSAS-style shoreline change rates (EPR, LRR) along transects cast from a baseline."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Shorelines File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polyline file of dated shorelines.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Date Field Name".to_owned(),
+            flags: vec!["--date_field".to_owned()],
+            description: "Input field containing the date of each shoreline.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: Some("DATE".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Baseline File".to_owned(),
+            flags: vec!["--baseline".to_owned()],
+            description: "Input vector polyline file of the baseline.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Transects File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polyline file of transects.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Transect Spacing".to_owned(),
+            flags: vec!["--spacing".to_owned()],
+            description: "Spacing of the transects along the baseline, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("50.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Transect Length".to_owned(),
+            flags: vec!["--length".to_owned()],
+            description: "Length of the transects, centred on the baseline, in map units."
+                .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("500.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing Distance".to_owned(),
+            flags: vec!["--smoothing".to_owned()],
+            description: "Distance along the baseline over which the transect orientation is smoothed, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reverse the sign of distances?".to_owned(),
+            flags: vec!["--reverse".to_owned()],
+            description: "Measure positive distances on the right-hand side of the baseline."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=shorelines.shp --baseline=baseline.shp -o=transects.shp --spacing=25.0 --length=800.0 --smoothing=200.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ShorelineChangeAnalysis {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ShorelineChangeAnalysis {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut date_field = String::from("DATE");
+        let mut baseline_file = String::new();
+        let mut output_file = String::new();
+        let mut spacing = 50f64;
+        let mut length = 500f64;
+        let mut smoothing = 0f64;
+        let mut reverse = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            let value = if keyval {
+                vec[1].to_string()
+            } else if i + 1 < args.len() {
+                args[i + 1].to_string()
+            } else {
+                String::new()
+            };
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = value;
+            } else if flag_val == "-date_field" {
+                date_field = value;
+            } else if flag_val == "-baseline" {
+                baseline_file = value;
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = value;
+            } else if flag_val == "-spacing" {
+                spacing = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-length" {
+                length = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-smoothing" {
+                smoothing = value
+                    .parse::<f64>()
+                    .expect(&format!("Error parsing {}", flag_val));
+            } else if flag_val == "-reverse" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    reverse = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !baseline_file.contains(&sep) && !baseline_file.contains("/") {
+            baseline_file = format!("{}{}", working_directory, baseline_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if spacing <= 0f64 || length <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The transect spacing and length must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Shapefile::read(&input_file)?;
+        let baseline = Shapefile::read(&baseline_file)?;
+        if input.header.shape_type.base_shape_type() != ShapeType::PolyLine
+            || baseline.header.shape_type.base_shape_type() != ShapeType::PolyLine
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input shorelines and baseline must be of a POLYLINE base shape type.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // read the date of each shoreline as a decimal year
+        let field_num = input.attributes.get_field_num(&date_field).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The date field '{}' does not exist in the attribute table.",
+                    date_field
+                ),
+            )
+        })?;
+        let mut shore_times: Vec<f64> = Vec::with_capacity(input.num_records);
+        for record_num in 0..input.num_records {
+            let time = match input.attributes.get_record(record_num)[field_num] {
+                FieldData::Date(ref d) => {
+                    NaiveDate::from_ymd_opt(d.year as i32, d.month as u32, d.day as u32)
+                        .map(|date| decimal_year(&date))
+                }
+                FieldData::Text(ref s) => match parse_date(s) {
+                    Some(date) => Some(decimal_year(&date)),
+                    None => s.trim().parse::<f64>().ok(),
+                },
+                FieldData::Int(v) => Some(v as f64),
+                FieldData::Int64(v) => Some(v as f64),
+                FieldData::Real(v) => Some(v),
+                _ => None,
+            };
+            shore_times.push(time.unwrap_or(f64::NAN));
+        }
+        let mut dates: Vec<f64> = shore_times
+            .iter()
+            .filter(|t| !t.is_nan())
+            .cloned()
+            .collect();
+        dates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dates.dedup();
+        if dates.len() < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input must contain shorelines of at least two dates.",
+            ));
+        }
+        if verbose {
+            println!("Number of shoreline dates: {}", dates.len());
+        }
+
+        // cast the transects along each part of the baseline
+        let sign = if reverse { -1f64 } else { 1f64 };
+        let mut transects: Vec<(Point2D, Point2D, f64)> = vec![];
+        for record_num in 0..baseline.num_records {
+            let record = baseline.get_record(record_num);
+            for part in 0..record.num_parts as usize {
+                let start_pt = record.parts[part] as usize;
+                let end_pt = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                let line = &record.points[start_pt..end_pt];
+                let total = line_length(line);
+                let mut s = 0f64;
+                while s <= total && total > 0f64 {
+                    let p = point_along(line, s);
+                    let (p1, p2) = if smoothing > 0f64 {
+                        (
+                            point_along(line, (s - smoothing / 2f64).max(0f64)),
+                            point_along(line, (s + smoothing / 2f64).min(total)),
+                        )
+                    } else {
+                        let d = (total / 1e6).max(f64::EPSILON);
+                        (
+                            point_along(line, (s - d).max(0f64)),
+                            point_along(line, (s + d).min(total)),
+                        )
+                    };
+                    let (dx, dy) = (p2.x - p1.x, p2.y - p1.y);
+                    let d = (dx * dx + dy * dy).sqrt();
+                    if d > 0f64 {
+                        // the unit normal pointing to the left of the baseline
+                        let (nx, ny) = (-dy / d * sign, dx / d * sign);
+                        let half = length / 2f64;
+                        transects.push((
+                            Point2D::new(p.x - nx * half, p.y - ny * half),
+                            Point2D::new(p.x + nx * half, p.y + ny * half),
+                            s,
+                        ));
+                    }
+                    s += spacing;
+                }
+            }
+        }
+        if transects.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No transects could be cast along the baseline.",
+            ));
+        }
+
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = input.projection.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "BASE_DIST",
+            FieldDataType::Real,
+            12u8,
+            3u8,
+        ));
+        output.attributes.add_field(&AttributeField::new(
+            "N_SHORES",
+            FieldDataType::Int,
+            4u8,
+            0u8,
+        ));
+        for name in ["SCE", "NSM", "EPR", "LRR", "LR2"].iter() {
+            output
+                .attributes
+                .add_field(&AttributeField::new(name, FieldDataType::Real, 12u8, 4u8));
+        }
+
+        let num_transects = transects.len();
+        let (mut sum_epr, mut num_epr) = (0f64, 0usize);
+        let (mut sum_lrr, mut num_lrr) = (0f64, 0usize);
+        for (i, (a, b, base_dist)) in transects.iter().enumerate() {
+            // the intersection closest to the baseline, for each shoreline date
+            let mut positions: Vec<f64> = vec![f64::NAN; dates.len()];
+            for record_num in 0..input.num_records {
+                if shore_times[record_num].is_nan() {
+                    continue;
+                }
+                let record = input.get_record(record_num);
+                if record.x_max < a.x.min(b.x)
+                    || record.x_min > a.x.max(b.x)
+                    || record.y_max < a.y.min(b.y)
+                    || record.y_min > a.y.max(b.y)
+                {
+                    continue;
+                }
+                let date = dates
+                    .iter()
+                    .position(|&t| t == shore_times[record_num])
+                    .unwrap();
+                for part in 0..record.num_parts as usize {
+                    let start_pt = record.parts[part] as usize;
+                    let end_pt = if part < record.num_parts as usize - 1 {
+                        record.parts[part + 1] as usize
+                    } else {
+                        record.num_points as usize
+                    };
+                    for j in start_pt + 1..end_pt {
+                        if let Some(u) = intersect(a, b, &record.points[j - 1], &record.points[j]) {
+                            let dist = (u - 0.5) * length;
+                            if positions[date].is_nan() || dist.abs() < positions[date].abs() {
+                                positions[date] = dist;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let (times, dists): (Vec<f64>, Vec<f64>) = dates
+                .iter()
+                .zip(positions.iter())
+                .filter(|(_, d)| !d.is_nan())
+                .map(|(t, d)| (*t, *d))
+                .unzip();
+            let n = times.len();
+            let mut atts = vec![
+                FieldData::Int(i as i32 + 1),
+                FieldData::Real(*base_dist),
+                FieldData::Int(n as i32),
+            ];
+            if n >= 2 {
+                let max = dists.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let min = dists.iter().cloned().fold(f64::INFINITY, f64::min);
+                let nsm = dists[n - 1] - dists[0];
+                let epr = nsm / (times[n - 1] - times[0]);
+                sum_epr += epr;
+                num_epr += 1;
+                atts.push(FieldData::Real(max - min));
+                atts.push(FieldData::Real(nsm));
+                atts.push(FieldData::Real(epr));
+            } else {
+                atts.push(FieldData::Null);
+                atts.push(FieldData::Null);
+                atts.push(FieldData::Null);
+            }
+            if n >= 3 {
+                let (lrr, r2) = linear_regression(&times, &dists);
+                sum_lrr += lrr;
+                num_lrr += 1;
+                atts.push(FieldData::Real(lrr));
+                atts.push(FieldData::Real(r2));
+            } else {
+                atts.push(FieldData::Null);
+                atts.push(FieldData::Null);
+            }
+
+            let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+            sfg.add_part(&[*a, *b]);
+            output.add_record(sfg);
+            output.attributes.add_record(atts, false);
+
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (num_transects - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Number of transects: {}", num_transects);
+            if num_epr > 0 {
+                println!("Mean EPR: {:.4}", sum_epr / num_epr as f64);
+            }
+            if num_lrr > 0 {
+                println!("Mean LRR: {:.4}", sum_lrr / num_lrr as f64);
+            }
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn line_length(line: &[Point2D]) -> f64 {
+    (1..line.len())
+        .map(|i| line[i - 1].distance(&line[i]))
+        .sum()
+}
+
+/// Returns the point lying a distance `s` along a line.
+fn point_along(line: &[Point2D], s: f64) -> Point2D {
+    let mut remaining = s;
+    for i in 1..line.len() {
+        let d = line[i - 1].distance(&line[i]);
+        if remaining <= d && d > 0f64 {
+            let t = remaining / d;
+            return Point2D::new(
+                line[i - 1].x + t * (line[i].x - line[i - 1].x),
+                line[i - 1].y + t * (line[i].y - line[i - 1].y),
+            );
+        }
+        remaining -= d;
+    }
+    line[line.len() - 1]
+}
+
+/// Returns the position of the intersection of the segments a-b and c-d as a fraction of the
+/// distance from a to b, if the segments intersect.
+fn intersect(a: &Point2D, b: &Point2D, c: &Point2D, d: &Point2D) -> Option<f64> {
+    let (rx, ry) = (b.x - a.x, b.y - a.y);
+    let (sx, sy) = (d.x - c.x, d.y - c.y);
+    let denom = rx * sy - ry * sx;
+    if denom == 0f64 {
+        return None; // parallel
+    }
+    let u = ((c.x - a.x) * sy - (c.y - a.y) * sx) / denom;
+    let v = ((c.x - a.x) * ry - (c.y - a.y) * rx) / denom;
+    if u >= 0f64 && u <= 1f64 && v >= 0f64 && v <= 1f64 {
+        Some(u)
+    } else {
+        None
+    }
+}
+
+/// Returns the slope and coefficient of determination of the least-squares regression of y on x.
+fn linear_regression(x: &[f64], y: &[f64]) -> (f64, f64) {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (mut sxy, mut sxx, mut syy) = (0f64, 0f64, 0f64);
+    for i in 0..x.len() {
+        sxy += (x[i] - mean_x) * (y[i] - mean_y);
+        sxx += (x[i] - mean_x) * (x[i] - mean_x);
+        syy += (y[i] - mean_y) * (y[i] - mean_y);
+    }
+    let slope = sxy / sxx;
+    let r2 = if syy > 0f64 {
+        sxy * sxy / (sxx * syy)
+    } else {
+        1f64
+    };
+    (slope, r2)
+}
@@ -0,0 +1,496 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::{add_band_dates, DataCube};
+use crate::raster::*;
+use crate::tools::*;
+use chrono::prelude::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool aggregates the observations of a raster time series (i.e. a data cube) over time periods,
+/// calculating a summary statistic (`--stat`) of the valid observations of each grid cell within each
+/// period. Supported statistics include the `mean`, `median`, `minimum`, `maximum`, `sum`, `range`,
+/// standard deviation (`stdev`), and the `count` of valid observations. Aggregation periods (`--period`)
+/// include:
+///
+/// | Period          | Description                                                              |
+/// |-----------------|--------------------------------------------------------------------------|
+/// | `all`           | The entire series, producing a single-band output                        |
+/// | `year`          | Each calendar year                                                       |
+/// | `season`        | Each meteorological season (DJF, MAM, JJA, SON); December is assigned to the following year's winter |
+/// | `month`         | Each calendar month of each year                                         |
+/// | `month_of_year` | Each calendar month, pooling all years (i.e. a monthly climatology)      |
+///
+/// For example, the maximum of each year of a series of NDVI images is a common annual greenness
+/// composite, and the mean of each month of year of a series of temperature rasters is a climatology.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a
+/// text file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition
+/// date (YYYY-MM-DD). The dates of the bands of a multi-band raster may be specified with the `--dates`
+/// parameter. Periods other than `all` require a dated time series. The output is a raster with one band
+/// per period, in chronological order, and the label of each band is recorded in the output's metadata.
+/// Outputs with more than one band must be written in the GeoTIFF or Whitebox v2 (.wbr) formats. The
+/// yearly, seasonal, and monthly outputs are themselves dated time series, with each band dated at the
+/// start of its period, and may be used as the input to the other time-series tools.
+///
+/// # See Also
+/// `TimeSeriesTrend`, `TimeSeriesAnomalies`, `TimeSeriesGapFill`
+pub struct TimeSeriesAggregate {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TimeSeriesAggregate {
+    pub fn new() -> TimeSeriesAggregate {
+        // public constructor
+        let name = "TimeSeriesAggregate".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Aggregates a raster time series over years, seasons, or months.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file, with one band per period.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Aggregation Period".to_owned(),
+            flags: vec!["--period".to_owned()],
+            description: "Aggregation period; options are 'all', 'year', 'season', 'month', and 'month_of_year'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "all".to_owned(),
+                "year".to_owned(),
+                "season".to_owned(),
+                "month".to_owned(),
+                "month_of_year".to_owned(),
+            ]),
+            default_value: Some("year".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistic".to_owned(),
+            flags: vec!["--stat".to_owned()],
+            description: "Summary statistic; options are 'mean', 'median', 'minimum', 'maximum', 'sum', 'range', 'stdev', and 'count'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "mean".to_owned(),
+                "median".to_owned(),
+                "minimum".to_owned(),
+                "maximum".to_owned(),
+                "sum".to_owned(),
+                "range".to_owned(),
+                "stdev".to_owned(),
+                "count".to_owned(),
+            ]),
+            default_value: Some("mean".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=ndvi_series.txt -o=ndvi_annual_max.tif --period=year --stat=maximum",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TimeSeriesAggregate {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TimeSeriesAggregate {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut period = String::from("year");
+        let mut stat = String::from("mean");
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-period" {
+                period = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-stat" {
+                stat = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        let stat_code = match stat.as_str() {
+            "mean" | "average" => 0,
+            "median" => 1,
+            "minimum" | "min" => 2,
+            "maximum" | "max" => 3,
+            "sum" | "total" => 4,
+            "range" => 5,
+            "stdev" | "sd" | "standard deviation" => 6,
+            "count" => 7,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Unrecognized --stat; options are 'mean', 'median', 'minimum', 'maximum', 'sum', 'range', 'stdev', and 'count'.",
+                ))
+            }
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+
+        // assign each layer to a period; each period is identified by a sortable key
+        let season_names = ["DJF", "MAM", "JJA", "SON"];
+        let month_names = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let mut keys: Vec<(i32, u32)> = vec![(0, 0); num_layers];
+        match period.as_str() {
+            "all" | "total" => {}
+            "year" | "season" | "month" | "month_of_year" => {
+                let d = match cube.dates {
+                    Some(ref d) => d,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Aggregation by year, season, or month requires a dated time series.",
+                        ))
+                    }
+                };
+                for t in 0..num_layers {
+                    keys[t] = match period.as_str() {
+                        "year" => (d[t].year(), 0),
+                        "season" => {
+                            if d[t].month() == 12 {
+                                (d[t].year() + 1, 0)
+                            } else {
+                                (d[t].year(), d[t].month() / 3)
+                            }
+                        }
+                        "month" => (d[t].year(), d[t].month0()),
+                        _ => (0, d[t].month0()),
+                    };
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Unrecognized --period; options are 'all', 'year', 'season', 'month', and 'month_of_year'.",
+                ))
+            }
+        }
+        let mut periods = keys.clone();
+        periods.sort();
+        periods.dedup();
+        let num_periods = periods.len();
+        let layer_period: Vec<usize> = keys
+            .iter()
+            .map(|k| periods.binary_search(k).unwrap())
+            .collect();
+        let labels: Vec<String> = periods
+            .iter()
+            .map(|&(year, sub)| match period.as_str() {
+                "year" => format!("{}", year),
+                "season" => format!("{} {}", year, season_names[sub as usize]),
+                "month" => format!("{}-{:02}", year, sub + 1),
+                "month_of_year" => month_names[sub as usize].to_string(),
+                _ => "All".to_string(),
+            })
+            .collect();
+        let period_dates: Option<Vec<NaiveDate>> = match period.as_str() {
+            "year" | "season" | "month" => Some(
+                periods
+                    .iter()
+                    .map(|&(year, sub)| match period.as_str() {
+                        "year" => NaiveDate::from_ymd(year, 1, 1),
+                        "season" => {
+                            if sub == 0 {
+                                NaiveDate::from_ymd(year - 1, 12, 1)
+                            } else {
+                                NaiveDate::from_ymd(year, sub * 3, 1)
+                            }
+                        }
+                        _ => NaiveDate::from_ymd(year, sub + 1, 1),
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        };
+        if verbose {
+            println!("Number of periods: {}", num_periods);
+        }
+
+        let layer_period = Arc::new(layer_period);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let layer_period = layer_period.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; num_periods];
+                    let mut values: Vec<Vec<f64>> = vec![vec![]; num_periods];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        for p in 0..num_periods {
+                            values[p].clear();
+                        }
+                        for t in 0..num_layers {
+                            if series[t].is_finite() {
+                                values[layer_period[t]].push(series[t]);
+                            }
+                        }
+                        for p in 0..num_periods {
+                            let v = &mut values[p];
+                            let n = v.len() as f64;
+                            if v.is_empty() {
+                                if stat_code == 7 {
+                                    data[p][col as usize] = 0f64;
+                                }
+                                continue;
+                            }
+                            data[p][col as usize] = match stat_code {
+                                0 => v.iter().sum::<f64>() / n,
+                                1 => {
+                                    v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                                    let m = v.len() / 2;
+                                    if v.len() % 2 == 0 {
+                                        (v[m - 1] + v[m]) / 2f64
+                                    } else {
+                                        v[m]
+                                    }
+                                }
+                                2 => v.iter().cloned().fold(f64::INFINITY, f64::min),
+                                3 => v.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                                4 => v.iter().sum::<f64>(),
+                                5 => {
+                                    v.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+                                        - v.iter().cloned().fold(f64::INFINITY, f64::min)
+                                }
+                                6 => {
+                                    if v.len() < 2 {
+                                        nodata
+                                    } else {
+                                        let mean = v.iter().sum::<f64>() / n;
+                                        let ss: f64 =
+                                            v.iter().map(|z| (z - mean) * (z - mean)).sum();
+                                        (ss / (n - 1f64)).sqrt()
+                                    }
+                                }
+                                _ => n,
+                            };
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = cube.new_output(&output_file, num_periods);
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                output.set_row_data_band(row, band, values);
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Period: {}", period));
+        output.add_metadata_entry(format!("Statistic: {}", stat));
+        for (band, label) in labels.iter().enumerate() {
+            output.add_metadata_entry(format!("Band {}: {}", band + 1, label));
+        }
+        if let Some(ref d) = period_dates {
+            add_band_dates(&mut output, d);
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
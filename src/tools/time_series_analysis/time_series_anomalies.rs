@@ -0,0 +1,447 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::DataCube;
+use crate::raster::*;
+use crate::tools::*;
+use chrono::prelude::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool calculates the anomaly of each observation in a raster time series (i.e. a data cube),
+/// that is, its departure from the expected (baseline) value of its grid cell. The baseline of each
+/// cell is the mean of its valid observations, either over the full series or over a reference period
+/// specified as a range of years (`--baseline`, e.g. `1991,2020`). When `--seasonal` is specified, a
+/// separate baseline is calculated for each calendar month, such that each observation is compared with
+/// observations from the same time of year (i.e. a climatology) and the seasonal cycle is removed.
+///
+/// Anomalies may be expressed (`--method`) as a `difference` from the baseline mean (the default), a
+/// `percent` of the baseline mean, or as a `standardized` anomaly (z-score), i.e. the difference divided
+/// by the standard deviation of the baseline observations.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a
+/// text file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition
+/// date (YYYY-MM-DD). The dates of the bands of a multi-band raster may be specified with the `--dates`
+/// parameter. The `--baseline` and `--seasonal` parameters require a dated time series. The output is a
+/// multi-band raster, with one band for each layer of the input series in chronological order, and must
+/// therefore be written in the GeoTIFF or Whitebox v2 (.wbr) formats.
+///
+/// # See Also
+/// `TimeSeriesTrend`, `TimeSeriesAggregate`, `TimeSeriesGapFill`, `ZScores`
+pub struct TimeSeriesAnomalies {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TimeSeriesAnomalies {
+    pub fn new() -> TimeSeriesAnomalies {
+        // public constructor
+        let name = "TimeSeriesAnomalies".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Calculates the anomalies of a raster time series relative to a baseline period."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Anomaly Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Anomaly method; options are 'difference', 'percent', and 'standardized'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "difference".to_owned(),
+                "percent".to_owned(),
+                "standardized".to_owned(),
+            ]),
+            default_value: Some("difference".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Baseline Period (optional)".to_owned(),
+            flags: vec!["--baseline".to_owned()],
+            description: "First and last years of the baseline period, e.g. 1991,2020.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Calculate monthly baselines?".to_owned(),
+            flags: vec!["--seasonal".to_owned()],
+            description: "Calculate a separate baseline for each calendar month.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=lst_series.txt -o=lst_anomalies.tif --method=standardized --baseline=2001,2015 --seasonal",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TimeSeriesAnomalies {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TimeSeriesAnomalies {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("difference");
+        let mut baseline = String::new();
+        let mut seasonal = false;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-baseline" {
+                baseline = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-seasonal" {
+                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
+                    seasonal = true;
+                }
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        // 0 = difference, 1 = percent, 2 = standardized
+        let method_code = if method.contains("per") {
+            1
+        } else if method.contains("stand") || method.contains("z") {
+            2
+        } else if method.contains("diff") {
+            0
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized --method; options are 'difference', 'percent', and 'standardized'.",
+            ));
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+
+        if (seasonal || !baseline.trim().is_empty()) && !cube.is_dated() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --baseline and --seasonal parameters require a dated time series.",
+            ));
+        }
+
+        // the baseline group (calendar month, or zero) of each layer and whether it
+        // falls within the baseline period
+        let mut groups = vec![0usize; num_layers];
+        let mut in_baseline = vec![true; num_layers];
+        if let Some(ref d) = cube.dates {
+            if seasonal {
+                for t in 0..num_layers {
+                    groups[t] = d[t].month0() as usize;
+                }
+            }
+            if !baseline.trim().is_empty() {
+                let years: Vec<i32> = baseline
+                    .split(|c: char| c == ',' || c == ';' || c == '-')
+                    .filter(|s| !s.trim().is_empty())
+                    .map(|s| {
+                        s.trim()
+                            .parse::<i32>()
+                            .expect("Error parsing --baseline; specify the first and last years, e.g. 1991,2020.")
+                    })
+                    .collect();
+                let (first, last) = match years.len() {
+                    1 => (years[0], years[0]),
+                    2 => (years[0].min(years[1]), years[0].max(years[1])),
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Error parsing --baseline; specify the first and last years, e.g. 1991,2020.",
+                        ))
+                    }
+                };
+                for t in 0..num_layers {
+                    in_baseline[t] = d[t].year() >= first && d[t].year() <= last;
+                }
+                if !in_baseline.iter().any(|b| *b) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "None of the layers of the time series fall within the baseline period.",
+                    ));
+                }
+            }
+        }
+        let num_groups = if seasonal { 12 } else { 1 };
+
+        let groups = Arc::new(groups);
+        let in_baseline = Arc::new(in_baseline);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let groups = groups.clone();
+            let in_baseline = in_baseline.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; num_layers];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        let mut sum = vec![0f64; num_groups];
+                        let mut sum_sqr = vec![0f64; num_groups];
+                        let mut n = vec![0f64; num_groups];
+                        for t in 0..num_layers {
+                            if in_baseline[t] && series[t].is_finite() {
+                                sum[groups[t]] += series[t];
+                                sum_sqr[groups[t]] += series[t] * series[t];
+                                n[groups[t]] += 1f64;
+                            }
+                        }
+                        for t in 0..num_layers {
+                            let g = groups[t];
+                            if !series[t].is_finite() || n[g] == 0f64 {
+                                continue;
+                            }
+                            let mean = sum[g] / n[g];
+                            data[t][col as usize] = match method_code {
+                                0 => series[t] - mean,
+                                1 => {
+                                    if mean != 0f64 {
+                                        100f64 * (series[t] - mean) / mean.abs()
+                                    } else {
+                                        nodata
+                                    }
+                                }
+                                _ => {
+                                    let variance = if n[g] > 1f64 {
+                                        (sum_sqr[g] - sum[g] * sum[g] / n[g]) / (n[g] - 1f64)
+                                    } else {
+                                        0f64
+                                    };
+                                    if variance > 0f64 {
+                                        (series[t] - mean) / variance.sqrt()
+                                    } else {
+                                        nodata
+                                    }
+                                }
+                            };
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = cube.new_output(&output_file, num_layers);
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                output.set_row_data_band(row, band, values);
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if !baseline.trim().is_empty() {
+            output.add_metadata_entry(format!("Baseline period: {}", baseline));
+        }
+        output.add_metadata_entry(format!("Seasonal baselines: {}", seasonal));
+        cube.add_date_metadata(&mut output);
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
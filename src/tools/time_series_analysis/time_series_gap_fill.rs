@@ -0,0 +1,477 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::DataCube;
+use crate::raster::*;
+use crate::tools::*;
+use chrono::prelude::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool fills the gaps, i.e. missing (NoData) observations, in a raster time series (i.e. a data
+/// cube), such as those left by clouds and their shadows in optical imagery or by sensor failures. Each
+/// grid cell's series is filled independently using one of the following methods (`--method`):
+///
+/// - `linear`: linear interpolation in time between the nearest valid observations before and after
+///   the gap (the default). Gaps at the start and end of the series are not filled.
+/// - `nearest`: the value of the valid observation that is nearest in time.
+/// - `climatology`: the mean of the cell's valid observations from the same calendar month in other
+///   years. This method is suited to long gaps in multi-year series with strong seasonal cycles.
+///
+/// The maximum length of the gaps that are filled (`--max_gap`) is specified as a number of consecutive
+/// missing observations; longer gaps are left unfilled. A value of zero (the default) fills gaps of any
+/// length. Cells with no valid observations remain NoData.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a
+/// text file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition
+/// date (YYYY-MM-DD). The dates of the bands of a multi-band raster may be specified with the `--dates`
+/// parameter. When the layers are dated, interpolation accounts for the spacing of the observations in
+/// time; otherwise, they are assumed to be equally spaced. The `climatology` method requires a dated
+/// series. The output is a multi-band raster, with one band for each layer of the input series in
+/// chronological order, and must therefore be written in the GeoTIFF or Whitebox v2 (.wbr) formats.
+///
+/// Within a text file, the date of each raster may be followed by a quality mask produced by the
+/// `CloudMask` tool, e.g. `ndvi_2020_06.tif, 2020-06-15, qa_2020_06.tif`. Cells that are cloud, cloud
+/// shadow, or unobserved in the mask are treated as missing observations and are filled.
+///
+/// # See Also
+/// `TimeSeriesTrend`, `TimeSeriesAnomalies`, `TimeSeriesAggregate`, `PhenologyMetrics`, `TimeSeriesSavitzkyGolay`,
+/// `TimeSeriesWhittaker`, `CloudMask`
+pub struct TimeSeriesGapFill {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TimeSeriesGapFill {
+    pub fn new() -> TimeSeriesGapFill {
+        // public constructor
+        let name = "TimeSeriesGapFill".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description = "Fills missing observations in a raster time series.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Gap-Filling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Gap-filling method; options are 'linear', 'nearest', and 'climatology'."
+                .to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "linear".to_owned(),
+                "nearest".to_owned(),
+                "climatology".to_owned(),
+            ]),
+            default_value: Some("linear".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Gap Length".to_owned(),
+            flags: vec!["--max_gap".to_owned()],
+            description:
+                "Maximum number of consecutive missing observations to fill (0 for no limit)."
+                    .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=ndvi_series.txt -o=ndvi_filled.tif --method=linear --max_gap=3",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TimeSeriesGapFill {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TimeSeriesGapFill {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut method = String::from("linear");
+        let mut max_gap = 0usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-method" {
+                method = if keyval {
+                    vec[1].to_lowercase()
+                } else {
+                    args[i + 1].to_lowercase()
+                };
+            } else if flag_val == "-max_gap" {
+                max_gap = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        // 0 = linear, 1 = nearest, 2 = climatology
+        let method_code = if method.contains("lin") {
+            0
+        } else if method.contains("near") {
+            1
+        } else if method.contains("clim") {
+            2
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized --method; options are 'linear', 'nearest', and 'climatology'.",
+            ));
+        };
+        let max_gap = if max_gap == 0 {
+            usize::max_value()
+        } else {
+            max_gap
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+
+        let months: Vec<usize> = match cube.dates {
+            Some(ref d) => d.iter().map(|date| date.month0() as usize).collect(),
+            None => {
+                if method_code == 2 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The climatology method requires a dated time series.",
+                    ));
+                }
+                vec![0; num_layers]
+            }
+        };
+
+        let times = Arc::new(cube.times());
+        let months = Arc::new(months);
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let times = times.clone();
+            let months = months.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                let mut num_filled = vec![0usize; num_layers];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; num_layers];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        let filled = fill_gaps(&series, &times, &months, method_code, max_gap);
+                        for t in 0..num_layers {
+                            if filled[t].is_finite() {
+                                data[t][col as usize] = filled[t];
+                                if !series[t].is_finite() {
+                                    num_filled[t] += 1;
+                                }
+                            }
+                        }
+                    }
+                    tx.send((row, data, num_filled.clone())).unwrap();
+                    for v in num_filled.iter_mut() {
+                        *v = 0;
+                    }
+                }
+            });
+        }
+
+        let mut output = cube.new_output(&output_file, num_layers);
+        let mut total_filled = vec![0usize; num_layers];
+        for r in 0..rows {
+            let (row, data, num_filled) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                output.set_row_data_band(row, band, values);
+            }
+            for t in 0..num_layers {
+                total_filled[t] += num_filled[t];
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Filled cells per layer:");
+            for t in 0..num_layers {
+                println!("  {}: {}", cube.labels[t], total_filled[t]);
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if max_gap < usize::max_value() {
+            output.add_metadata_entry(format!("Max. gap: {}", max_gap));
+        }
+        cube.add_date_metadata(&mut output);
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Fills the runs of missing (NaN) values in a series that are no longer than `max_gap`.
+fn fill_gaps(
+    series: &[f64],
+    times: &[f64],
+    months: &[usize],
+    method_code: usize,
+    max_gap: usize,
+) -> Vec<f64> {
+    let n = series.len();
+    let mut filled = series.to_vec();
+    if !series.iter().any(|v| v.is_finite()) {
+        return filled;
+    }
+    let mut monthly_mean = [f64::NAN; 12];
+    if method_code == 2 {
+        let mut sum = [0f64; 12];
+        let mut count = [0f64; 12];
+        for t in 0..n {
+            if series[t].is_finite() {
+                sum[months[t]] += series[t];
+                count[months[t]] += 1f64;
+            }
+        }
+        for m in 0..12 {
+            if count[m] > 0f64 {
+                monthly_mean[m] = sum[m] / count[m];
+            }
+        }
+    }
+    let mut t = 0;
+    while t < n {
+        if series[t].is_finite() {
+            t += 1;
+            continue;
+        }
+        // find the run of missing values from t to end - 1
+        let mut end = t;
+        while end < n && !series[end].is_finite() {
+            end += 1;
+        }
+        if end - t <= max_gap {
+            let before = if t > 0 { Some(t - 1) } else { None };
+            let after = if end < n { Some(end) } else { None };
+            for k in t..end {
+                filled[k] = match method_code {
+                    0 => match (before, after) {
+                        (Some(b), Some(a)) => {
+                            let w = if times[a] != times[b] {
+                                (times[k] - times[b]) / (times[a] - times[b])
+                            } else {
+                                0.5f64
+                            };
+                            series[b] + w * (series[a] - series[b])
+                        }
+                        _ => f64::NAN,
+                    },
+                    1 => match (before, after) {
+                        (Some(b), Some(a)) => {
+                            if times[k] - times[b] <= times[a] - times[k] {
+                                series[b]
+                            } else {
+                                series[a]
+                            }
+                        }
+                        (Some(b), None) => series[b],
+                        (None, Some(a)) => series[a],
+                        (None, None) => f64::NAN,
+                    },
+                    _ => monthly_mean[months[k]],
+                };
+            }
+        }
+        t = end;
+    }
+    filled
+}
@@ -0,0 +1,406 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::DataCube;
+use crate::algorithms::{mann_kendall, theil_sen};
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool estimates the monotonic trend in the values of each grid cell of a raster time series
+/// (i.e. a data cube). The trend slope is estimated using the Theil-Sen estimator, the median of the
+/// slopes between all pairs of observations, which is robust to outliers and does not assume normally
+/// distributed residuals. The significance of the trend is assessed using the non-parametric
+/// Mann-Kendall test, corrected for tied values.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a
+/// text file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition
+/// date (YYYY-MM-DD), e.g. `ndvi_2020_06.tif, 2020-06-15`. The dates of the bands of a multi-band
+/// raster may be specified with the `--dates` parameter, as a comma-separated list. When the layers
+/// are dated, they are sorted chronologically and the slope (`--output`) is expressed in units per
+/// year; otherwise the layers are assumed to be equally spaced in time and the slope is expressed in
+/// units per time step. The layers must share a common grid (see the `--resample_inputs` flag).
+///
+/// The two-sided p-value of the Mann-Kendall test (`--p_value`) and Kendall's tau (`--tau`), which
+/// ranges from -1 (a consistently decreasing series) to 1 (a consistently increasing series), may
+/// optionally be output. Missing (NoData) observations are ignored, and cells with fewer than
+/// `--min_obs` valid observations are assigned NoData in the outputs.
+///
+/// # Reference
+/// Sen, P. K. (1968). Estimates of the regression coefficient based on Kendall's tau. *Journal of the
+/// American Statistical Association*, 63(324), 1379-1389.
+///
+/// # See Also
+/// `TimeSeriesAnomalies`, `TimeSeriesAggregate`, `TimeSeriesGapFill`
+pub struct TimeSeriesTrend {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TimeSeriesTrend {
+    pub fn new() -> TimeSeriesTrend {
+        // public constructor
+        let name = "TimeSeriesTrend".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Estimates per-cell Theil-Sen trends and Mann-Kendall significance for a raster time series."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Slope File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output Theil-Sen slope raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output p-value File (optional)".to_owned(),
+            flags: vec!["--p_value".to_owned()],
+            description: "Output Mann-Kendall p-value raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Kendall's Tau File (optional)".to_owned(),
+            flags: vec!["--tau".to_owned()],
+            description: "Output Kendall's tau raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Number of Observations".to_owned(),
+            flags: vec!["--min_obs".to_owned()],
+            description: "Minimum number of valid observations needed to estimate a cell's trend."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("4".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=ndvi_series.txt -o=ndvi_trend.tif --p_value=ndvi_trend_p.tif --min_obs=8",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TimeSeriesTrend {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TimeSeriesTrend {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut p_value_file = String::new();
+        let mut tau_file = String::new();
+        let mut min_obs = 4usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-p_value" {
+                p_value_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-tau" {
+                tau_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-min_obs" {
+                min_obs = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+        if !p_value_file.is_empty() && !p_value_file.contains(&sep) && !p_value_file.contains("/") {
+            p_value_file = format!("{}{}", working_directory, p_value_file);
+        }
+        if !tau_file.is_empty() && !tau_file.contains(&sep) && !tau_file.contains("/") {
+            tau_file = format!("{}{}", working_directory, tau_file);
+        }
+        // the Mann-Kendall test needs at least three observations
+        min_obs = min_obs.max(3);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        if verbose {
+            println!(
+                "Number of layers: {}{}",
+                cube.num_layers(),
+                if cube.is_dated() { "" } else { " (undated)" }
+            );
+        }
+
+        let times = Arc::new(cube.times());
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let times = times.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut slopes = vec![nodata; columns as usize];
+                    let mut p_values = vec![nodata; columns as usize];
+                    let mut taus = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        let n = series.iter().filter(|v| v.is_finite()).count();
+                        if n < min_obs {
+                            continue;
+                        }
+                        if let Some((slope, _)) = theil_sen(&times, &series) {
+                            slopes[col as usize] = slope;
+                        }
+                        if let Some(mk) = mann_kendall(&series) {
+                            p_values[col as usize] = mk.p_value;
+                            taus[col as usize] = mk.tau;
+                        }
+                    }
+                    tx.send((row, slopes, p_values, taus)).unwrap();
+                }
+            });
+        }
+
+        let mut output = cube.new_output(&output_file, 1);
+        let mut p_output = if !p_value_file.is_empty() {
+            Some(cube.new_output(&p_value_file, 1))
+        } else {
+            None
+        };
+        let mut tau_output = if !tau_file.is_empty() {
+            Some(cube.new_output(&tau_file, 1))
+        } else {
+            None
+        };
+        for r in 0..rows {
+            let (row, slopes, p_values, taus) =
+                rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, slopes);
+            if let Some(ref mut p) = p_output {
+                p.set_row_data(row, p_values);
+            }
+            if let Some(ref mut t) = tau_output {
+                t.set_row_data(row, taus);
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Slope units: {}",
+            if cube.is_dated() {
+                "per year"
+            } else {
+                "per time step"
+            }
+        ));
+        output.add_metadata_entry(format!("Min. observations: {}", min_obs));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        for out in vec![p_output, tau_output] {
+            if let Some(mut out) = out {
+                out.add_metadata_entry(format!(
+                    "Created by whitebox_tools\' {} tool",
+                    self.get_tool_name()
+                ));
+                out.add_metadata_entry(format!("Input file: {}", input_file));
+                out.write()?;
+            }
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use super::data_cube::DataCube;
+use crate::algorithms::{upper_envelope_weights, whittaker_smooth};
+use crate::raster::*;
+use crate::tools::*;
+use num_cpus;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+/// This tool smooths a raster time series (i.e. a data cube) along its time dimension using the Whittaker
+/// smoother (Eilers, 2003). The smoothed series of each grid cell is the series that minimizes the sum of
+/// the squared deviations from the observations plus a penalty, `--lambda` (default 10), times the sum of
+/// the squared differences of order `--order` (default 2) of the smoothed values. Larger values of lambda
+/// produce smoother series. The smoother is fast, has a single parameter controlling the degree of
+/// smoothing, and behaves well at the ends of the series, making it a common preprocessing step for
+/// vegetation index time series prior to trend and phenology analysis.
+///
+/// Missing (NoData) observations are given zero weight, and are therefore estimated from the neighbouring
+/// observations in time, such that the output is a gap-filled series. Note that the smoother assumes that
+/// the observations are equally spaced in time; the acquisition dates, if any, are carried through to the
+/// output but are not used in the smoothing. Use `TimeSeriesSavitzkyGolay` for irregularly spaced series.
+///
+/// Vegetation index observations are commonly biased low by undetected clouds and atmospheric effects.
+/// When `--envelope_iterations` is greater than zero, the series is refitted that number of times, with
+/// the observations that fall below the previous fit down-weighted in proportion to their distance below it
+/// (Chen et al., 2004), such that the smoothed series approaches the upper envelope of the observations.
+///
+/// The time series (`--input`) may be either a multi-band raster, with one band per time step, or a text
+/// file (.txt or .csv) listing one raster per line, each optionally followed by its acquisition date
+/// (YYYY-MM-DD). The dates of the bands of a multi-band raster may be specified with the `--dates` parameter.
+/// The output is a multi-band raster, with one band for each layer of the input series in chronological
+/// order, and must therefore be written in the GeoTIFF or Whitebox v2 (.wbr) formats.
+///
+/// # References
+/// Chen, J., Jönsson, P., Tamura, M., Gu, Z., Matsushita, B., & Eklundh, L. (2004). A simple method for
+/// reconstructing a high-quality NDVI time-series data set based on the Savitzky–Golay filter. *Remote
+/// Sensing of Environment*, 91(3-4), 332-344.
+///
+/// Eilers, P. H. C. (2003). A perfect smoother. *Analytical Chemistry*, 75(14), 3631-3636.
+///
+/// # See Also
+/// `TimeSeriesSavitzkyGolay`, `TimeSeriesGapFill`, `PhenologyMetrics`
+pub struct TimeSeriesWhittaker {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TimeSeriesWhittaker {
+    pub fn new() -> TimeSeriesWhittaker {
+        // public constructor
+        let name = "TimeSeriesWhittaker".to_string();
+        let toolbox = "Time Series Analysis".to_string();
+        let description =
+            "Smooths a raster time series along its time dimension using the Whittaker smoother."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Time Series".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description:
+                "Input multi-band raster, or text file listing one raster (and date) per line."
+                    .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Dates (optional)".to_owned(),
+            flags: vec!["--dates".to_owned()],
+            description:
+                "Comma-separated list of the dates (YYYY-MM-DD) of the bands of a multi-band input."
+                    .to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output multi-band raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing Parameter (Lambda)".to_owned(),
+            flags: vec!["--lambda".to_owned()],
+            description:
+                "Penalty on the roughness of the smoothed series; larger values are smoother."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Difference Order".to_owned(),
+            flags: vec!["--order".to_owned()],
+            description: "Order of the differences penalized by the smoother.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Upper Envelope Iterations".to_owned(),
+            flags: vec!["--envelope_iterations".to_owned()],
+            description: "Number of refits down-weighting observations below the fit (0 for none)."
+                .to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let p = format!("{}", env::current_dir().unwrap().display());
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=ndvi_series.txt -o=ndvi_smoothed.tif --lambda=20.0 --order=2 --envelope_iterations=3",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TimeSeriesWhittaker {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TimeSeriesWhittaker {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+    ) -> Result<(), Error> {
+        let mut input_file = String::new();
+        let mut dates = String::new();
+        let mut output_file = String::new();
+        let mut lambda = 10f64;
+        let mut order = 2usize;
+        let mut envelope_iterations = 0usize;
+
+        if args.len() == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Tool run with no parameters.",
+            ));
+        }
+        for i in 0..args.len() {
+            let mut arg = args[i].replace("\"", "");
+            arg = arg.replace("\'", "");
+            let cmd = arg.split("="); // in case an equals sign was used
+            let vec = cmd.collect::<Vec<&str>>();
+            let mut keyval = false;
+            if vec.len() > 1 {
+                keyval = true;
+            }
+            let flag_val = vec[0].to_lowercase().replace("--", "-");
+            if flag_val == "-i" || flag_val == "-input" {
+                input_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-dates" {
+                dates = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-o" || flag_val == "-output" {
+                output_file = if keyval {
+                    vec[1].to_string()
+                } else {
+                    args[i + 1].to_string()
+                };
+            } else if flag_val == "-lambda" {
+                lambda = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val))
+                };
+            } else if flag_val == "-order" {
+                order = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            } else if flag_val == "-envelope_iterations" {
+                envelope_iterations = if keyval {
+                    vec[1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                } else {
+                    args[i + 1]
+                        .to_string()
+                        .parse::<f64>()
+                        .expect(&format!("Error parsing {}", flag_val)) as usize
+                };
+            }
+        }
+
+        if verbose {
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+            println!("* Welcome to {} *", self.get_tool_name());
+            println!("***************{}", "*".repeat(self.get_tool_name().len()));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !input_file.contains(&sep) && !input_file.contains("/") {
+            input_file = format!("{}{}", working_directory, input_file);
+        }
+        if !output_file.contains(&sep) && !output_file.contains("/") {
+            output_file = format!("{}{}", working_directory, output_file);
+        }
+
+        if lambda <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The smoothing parameter --lambda must be greater than zero.",
+            ));
+        }
+        if order < 1 {
+            order = 1;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cube = Arc::new(DataCube::read(&input_file, &dates, working_directory)?);
+
+        let start = Instant::now();
+
+        let rows = cube.configs.rows as isize;
+        let columns = cube.configs.columns as isize;
+        let num_layers = cube.num_layers();
+
+        let num_procs = num_cpus::get() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let cube = cube.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let nodata = -32768f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![nodata; columns as usize]; num_layers];
+                    for col in 0..columns {
+                        let series = cube.get_series(row, col);
+                        let mut weights: Vec<f64> = series
+                            .iter()
+                            .map(|v| if v.is_finite() { 1f64 } else { 0f64 })
+                            .collect();
+                        let mut smoothed = whittaker_smooth(&series, &weights, lambda, order);
+                        for _ in 0..envelope_iterations {
+                            upper_envelope_weights(&series, &smoothed, &mut weights);
+                            smoothed = whittaker_smooth(&series, &weights, lambda, order);
+                        }
+                        for t in 0..num_layers {
+                            if smoothed[t].is_finite() {
+                                data[t][col as usize] = smoothed[t];
+                            }
+                        }
+                    }
+                    tx.send((row, data)).unwrap();
+                }
+            });
+        }
+
+        let mut output = cube.new_output(&output_file, num_layers);
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for (band, values) in data.into_iter().enumerate() {
+                output.set_row_data_band(row, band, values);
+            }
+
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    println!("Progress: {}%", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Lambda: {}", lambda));
+        output.add_metadata_entry(format!("Difference order: {}", order));
+        output.add_metadata_entry(format!("Envelope iterations: {}", envelope_iterations));
+        cube.add_date_metadata(&mut output);
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
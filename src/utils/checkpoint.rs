@@ -0,0 +1,264 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+Checkpoints allow very long-running tools to periodically serialize their
+intermediate state to disk, so that a run that is interrupted by a crash or
+reboot can be resumed rather than restarted. A checkpoint file records the
+stage of the calculation that the tool had reached, along with whatever state
+the tool writes for that stage, and a key describing the tool run (the tool
+name, input files, and parameters). A checkpoint is only resumed when its key
+matches that of the current run. Checkpoints are written to a temporary file
+that then replaces the previous checkpoint, so that an interruption during a
+save cannot corrupt the last good checkpoint.
+*/
+
+use super::{ByteOrderReader, ByteOrderWriter, Endianness, Instant};
+use crate::structures::Array2D;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Seek, Write};
+use std::ops::{AddAssign, SubAssign};
+use std::time::Duration;
+
+const MAGIC: &[u8; 8] = b"WBTCKPT1";
+
+/// The default time between checkpoints, in minutes.
+pub const DEFAULT_CHECKPOINT_INTERVAL: f64 = 15.0;
+
+/// A value that can be written to, and read from, a checkpoint file.
+pub trait CheckpointValue: Copy {
+    fn write_to<W: Write>(&self, writer: &mut ByteOrderWriter<W>) -> Result<(), Error>;
+    fn read_from<R: Read + Seek>(reader: &mut ByteOrderReader<R>) -> Result<Self, Error>;
+}
+
+macro_rules! impl_checkpoint_value {
+    ($t:ty, $write:ident, $read:ident) => {
+        impl CheckpointValue for $t {
+            fn write_to<W: Write>(&self, writer: &mut ByteOrderWriter<W>) -> Result<(), Error> {
+                writer.$write(*self)
+            }
+
+            fn read_from<R: Read + Seek>(reader: &mut ByteOrderReader<R>) -> Result<Self, Error> {
+                reader.$read()
+            }
+        }
+    };
+}
+
+impl_checkpoint_value!(u8, write_u8, read_u8);
+impl_checkpoint_value!(u16, write_u16, read_u16);
+impl_checkpoint_value!(u32, write_u32, read_u32);
+impl_checkpoint_value!(u64, write_u64, read_u64);
+impl_checkpoint_value!(i8, write_i8, read_i8);
+impl_checkpoint_value!(i16, write_i16, read_i16);
+impl_checkpoint_value!(i32, write_i32, read_i32);
+impl_checkpoint_value!(i64, write_i64, read_i64);
+impl_checkpoint_value!(f32, write_f32, read_f32);
+impl_checkpoint_value!(f64, write_f64, read_f64);
+
+impl CheckpointValue for usize {
+    fn write_to<W: Write>(&self, writer: &mut ByteOrderWriter<W>) -> Result<(), Error> {
+        writer.write_u64(*self as u64)
+    }
+
+    fn read_from<R: Read + Seek>(reader: &mut ByteOrderReader<R>) -> Result<Self, Error> {
+        Ok(reader.read_u64()? as usize)
+    }
+}
+
+impl CheckpointValue for isize {
+    fn write_to<W: Write>(&self, writer: &mut ByteOrderWriter<W>) -> Result<(), Error> {
+        writer.write_i64(*self as i64)
+    }
+
+    fn read_from<R: Read + Seek>(reader: &mut ByteOrderReader<R>) -> Result<Self, Error> {
+        Ok(reader.read_i64()? as isize)
+    }
+}
+
+/// Periodically saves, and restores, the intermediate state of a tool run.
+pub struct Checkpoint {
+    file_name: String,
+    key: String,
+    interval: Duration,
+    last_saved: Instant,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint that is saved to `file_name` at most every
+    /// `interval_minutes`. The `key` describes the tool run, and should include
+    /// anything that would invalidate the saved state if it were changed.
+    pub fn new(file_name: &str, key: &str, interval_minutes: f64) -> Checkpoint {
+        Checkpoint {
+            file_name: file_name.to_string(),
+            key: key.to_string(),
+            interval: Duration::from_millis((interval_minutes.max(0.0) * 60000.0) as u64),
+            last_saved: Instant::now(),
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Returns true if the checkpoint interval has elapsed since the last save.
+    pub fn is_due(&self) -> bool {
+        self.last_saved.elapsed() >= self.interval
+    }
+
+    /// Saves the state of a stage of the calculation, written by `write_state`.
+    pub fn save<F>(&mut self, stage: u8, write_state: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut CheckpointWriter) -> Result<(), Error>,
+    {
+        let temp_file = format!("{}.tmp", self.file_name);
+        {
+            let f = File::create(&temp_file)?;
+            let mut writer = CheckpointWriter {
+                bow: ByteOrderWriter::new(BufWriter::new(f), Endianness::LittleEndian),
+            };
+            writer.bow.write_bytes(MAGIC)?;
+            writer.write_values(self.key.as_bytes())?;
+            writer.write(stage)?;
+            write_state(&mut writer)?;
+            writer.bow.into_inner().flush()?;
+        }
+        fs::rename(&temp_file, &self.file_name)?;
+        self.last_saved = Instant::now();
+        Ok(())
+    }
+
+    /// Opens a saved checkpoint, returning the stage that it records and a reader
+    /// positioned at the start of the saved state. None is returned if there is no
+    /// checkpoint file or if it was saved by a different tool run.
+    pub fn load(&self) -> Result<Option<(u8, CheckpointReader)>, Error> {
+        let f = match File::open(&self.file_name) {
+            Ok(f) => f,
+            Err(_) => return Ok(None),
+        };
+        let mut reader = CheckpointReader {
+            bor: ByteOrderReader::new(BufReader::new(f), Endianness::LittleEndian),
+        };
+        let mut magic = [0u8; 8];
+        if reader.bor.len() < MAGIC.len() || reader.bor.read_exact(&mut magic).is_err() {
+            return Ok(None);
+        }
+        if &magic != MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} is not a WhiteboxTools checkpoint file.", self.file_name),
+            ));
+        }
+        let key: Vec<u8> = reader.read_values()?;
+        if key != self.key.as_bytes() {
+            return Ok(None);
+        }
+        let stage: u8 = reader.read()?;
+        Ok(Some((stage, reader)))
+    }
+
+    /// Deletes the checkpoint file, e.g. once the tool has completed successfully.
+    pub fn remove(&self) {
+        let _ = fs::remove_file(&self.file_name);
+    }
+}
+
+/// Writes the state of a tool to a checkpoint file.
+pub struct CheckpointWriter {
+    bow: ByteOrderWriter<BufWriter<File>>,
+}
+
+impl CheckpointWriter {
+    pub fn write<T: CheckpointValue>(&mut self, value: T) -> Result<(), Error> {
+        value.write_to(&mut self.bow)
+    }
+
+    /// Writes a list of values, preceded by its length.
+    pub fn write_values<T: CheckpointValue>(&mut self, values: &[T]) -> Result<(), Error> {
+        self.bow.write_u64(values.len() as u64)?;
+        for v in values {
+            v.write_to(&mut self.bow)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_array2d<T>(&mut self, array: &Array2D<T>) -> Result<(), Error>
+    where
+        T: CheckpointValue + AddAssign + SubAssign,
+    {
+        self.write(array.rows())?;
+        self.write(array.columns())?;
+        for row in 0..array.rows() {
+            for v in array.get_row_data(row) {
+                v.write_to(&mut self.bow)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the state of a tool from a checkpoint file, in the order that it was written.
+pub struct CheckpointReader {
+    bor: ByteOrderReader<BufReader<File>>,
+}
+
+impl CheckpointReader {
+    pub fn read<T: CheckpointValue>(&mut self) -> Result<T, Error> {
+        T::read_from(&mut self.bor)
+    }
+
+    pub fn read_values<T: CheckpointValue>(&mut self) -> Result<Vec<T>, Error> {
+        let len = self.bor.read_u64()? as usize;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(T::read_from(&mut self.bor)?);
+        }
+        Ok(values)
+    }
+
+    /// Reads an array into `array`, which must have the dimensions of the saved array.
+    pub fn read_array2d<T>(&mut self, array: &mut Array2D<T>) -> Result<(), Error>
+    where
+        T: CheckpointValue + AddAssign + SubAssign,
+    {
+        let rows: isize = self.read()?;
+        let columns: isize = self.read()?;
+        if rows != array.rows() || columns != array.columns() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The checkpoint does not match the dimensions of the input data.",
+            ));
+        }
+        for row in 0..rows {
+            let mut values = Vec::with_capacity(columns as usize);
+            for _ in 0..columns {
+                values.push(T::read_from(&mut self.bor)?);
+            }
+            array.set_row_data(row, values);
+        }
+        Ok(())
+    }
+}
+
+/// Returns a signature of a file's size and modification time, used within
+/// checkpoint keys to detect input files that have changed since a checkpoint.
+pub fn file_signature(file_name: &str) -> String {
+    match fs::metadata(file_name) {
+        Ok(m) => {
+            let modified = m
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            format!("{}:{}", m.len(), modified)
+        }
+        Err(_) => String::new(),
+    }
+}
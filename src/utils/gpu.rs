@@ -0,0 +1,247 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+An optional GPU compute backend for embarrassingly parallel raster operations,
+such as convolution filters with large kernels. The backend uses OpenCL and is
+only compiled when WhiteboxTools is built with the `gpu` feature
+(`cargo build --release --features gpu`). Tools use it when the user selects
+`--device=gpu`; if the feature is not compiled in, no GPU device is found, or the
+device cannot run the computation (e.g. it lacks double-precision support), the
+functions in this module return an error and tools fall back to their CPU
+implementations.
+*/
+
+use std::io::{Error, ErrorKind};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the `--device` flag.
+static USE_GPU: AtomicBool = AtomicBool::new(false);
+
+/// The compute device used by tools that support GPU acceleration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Device {
+    Cpu,
+    Gpu,
+}
+
+impl Device {
+    pub fn from_str(s: &str) -> Option<Device> {
+        match s.trim().to_lowercase().as_str() {
+            "cpu" => Some(Device::Cpu),
+            "gpu" => Some(Device::Gpu),
+            _ => None,
+        }
+    }
+}
+
+pub fn set_device(device: Device) {
+    USE_GPU.store(device == Device::Gpu, Ordering::SeqCst);
+}
+
+/// Returns true if the user has requested that tools run on the GPU.
+pub fn use_gpu() -> bool {
+    USE_GPU.load(Ordering::SeqCst)
+}
+
+/// Convolves a raster, stored in row-major order, with a kernel described by the
+/// cell offsets `d_x` and `d_y` and their `weights`. NoData cells, and neighbours
+/// beyond the edges of the raster, are excluded from the weighted sum. When
+/// `normalize` is true, each sum is divided by the sum of the weights of the valid
+/// neighbours, and cells for which that sum is not positive are assigned NoData.
+pub fn convolve(
+    data: &[f64],
+    rows: usize,
+    columns: usize,
+    nodata: f64,
+    d_x: &[isize],
+    d_y: &[isize],
+    weights: &[f64],
+    normalize: bool,
+) -> Result<Vec<f64>, Error> {
+    if data.len() != rows * columns || d_x.len() != weights.len() || d_y.len() != weights.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The convolution inputs have inconsistent dimensions.",
+        ));
+    }
+    opencl::convolve(data, rows, columns, nodata, d_x, d_y, weights, normalize)
+}
+
+#[cfg(feature = "gpu")]
+mod opencl {
+    use ocl::enums::DeviceInfo;
+    use ocl::flags::DeviceType;
+    use ocl::{Buffer, Device, MemFlags, Platform, ProQue};
+    use std::io::{Error, ErrorKind};
+
+    const CONVOLVE_SRC: &str = r#"
+        #pragma OPENCL EXTENSION cl_khr_fp64 : enable
+
+        __kernel void convolve(
+            __global const double* data,
+            __global double* output,
+            __global const int* d_x,
+            __global const int* d_y,
+            __global const double* weights,
+            const int num_weights,
+            const int rows,
+            const int columns,
+            const double nodata,
+            const int normalize)
+        {
+            int i = get_global_id(0);
+            int row = i / columns;
+            int col = i % columns;
+            double z = data[i];
+            if (z == nodata) {
+                output[i] = nodata;
+                return;
+            }
+            double sum = 0.0;
+            double sum_weights = 0.0;
+            for (int a = 0; a < num_weights; a++) {
+                int x = col + d_x[a];
+                int y = row + d_y[a];
+                if (x >= 0 && x < columns && y >= 0 && y < rows) {
+                    double zn = data[y * columns + x];
+                    if (zn != nodata) {
+                        sum += weights[a] * zn;
+                        sum_weights += weights[a];
+                    }
+                }
+            }
+            if (normalize) {
+                output[i] = sum_weights > 0.0 ? sum / sum_weights : nodata;
+            } else {
+                output[i] = sum;
+            }
+        }
+    "#;
+
+    fn to_error(e: ocl::Error) -> Error {
+        Error::new(ErrorKind::Other, format!("OpenCL error: {}", e))
+    }
+
+    /// Finds the first GPU device on any OpenCL platform.
+    fn find_gpu() -> Result<(Platform, Device), Error> {
+        for platform in Platform::list() {
+            if let Ok(devices) = Device::list(platform, Some(DeviceType::GPU)) {
+                if let Some(device) = devices.into_iter().next() {
+                    return Ok((platform, device));
+                }
+            }
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            "No OpenCL GPU device was found.",
+        ))
+    }
+
+    pub fn convolve(
+        data: &[f64],
+        rows: usize,
+        columns: usize,
+        nodata: f64,
+        d_x: &[isize],
+        d_y: &[isize],
+        weights: &[f64],
+        normalize: bool,
+    ) -> Result<Vec<f64>, Error> {
+        let (platform, device) = find_gpu()?;
+        if !device
+            .info(DeviceInfo::Extensions)
+            .map(|e| e.to_string().contains("cl_khr_fp64"))
+            .unwrap_or(false)
+        {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "The GPU does not support double-precision arithmetic.",
+            ));
+        }
+        let pro_que = ProQue::builder()
+            .platform(platform)
+            .device(device)
+            .src(CONVOLVE_SRC)
+            .dims(rows * columns)
+            .build()
+            .map_err(to_error)?;
+        let input = Buffer::<f64>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(data.len())
+            .copy_host_slice(data)
+            .build()
+            .map_err(to_error)?;
+        let output = pro_que.create_buffer::<f64>().map_err(to_error)?;
+        let d_x: Vec<i32> = d_x.iter().map(|v| *v as i32).collect();
+        let d_y: Vec<i32> = d_y.iter().map(|v| *v as i32).collect();
+        let d_x = Buffer::<i32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(d_x.len())
+            .copy_host_slice(&d_x)
+            .build()
+            .map_err(to_error)?;
+        let d_y = Buffer::<i32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(d_y.len())
+            .copy_host_slice(&d_y)
+            .build()
+            .map_err(to_error)?;
+        let weights_buffer = Buffer::<f64>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(MemFlags::new().read_only())
+            .len(weights.len())
+            .copy_host_slice(weights)
+            .build()
+            .map_err(to_error)?;
+        let kernel = pro_que
+            .kernel_builder("convolve")
+            .arg(&input)
+            .arg(&output)
+            .arg(&d_x)
+            .arg(&d_y)
+            .arg(&weights_buffer)
+            .arg(weights.len() as i32)
+            .arg(rows as i32)
+            .arg(columns as i32)
+            .arg(nodata)
+            .arg(if normalize { 1i32 } else { 0i32 })
+            .build()
+            .map_err(to_error)?;
+        unsafe {
+            kernel.enq().map_err(to_error)?;
+        }
+        let mut result = vec![nodata; rows * columns];
+        output.read(&mut result).enq().map_err(to_error)?;
+        Ok(result)
+    }
+}
+
+#[cfg(not(feature = "gpu"))]
+mod opencl {
+    use std::io::{Error, ErrorKind};
+
+    pub fn convolve(
+        _data: &[f64],
+        _rows: usize,
+        _columns: usize,
+        _nodata: f64,
+        _d_x: &[isize],
+        _d_y: &[isize],
+        _weights: &[f64],
+        _normalize: bool,
+    ) -> Result<Vec<f64>, Error> {
+        Err(Error::new(
+            ErrorKind::Other,
+            "This build of WhiteboxTools does not include GPU support; rebuild with --features gpu.",
+        ))
+    }
+}
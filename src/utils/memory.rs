@@ -0,0 +1,250 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+Crate-wide memory accounting. The `--max_mem` flag sets a budget for the memory
+used by the working arrays (`Array2D`) of tools. Arrays are allocated on the heap
+while the budget allows; once it is exhausted, further arrays are backed by
+memory-mapped temporary files instead, so that the operating system can page
+them to and from disk rather than the tool being killed for running out of
+memory. Memory-mapped arrays are slower to access, but they allow large DEMs
+to be processed on memory-constrained systems. Memory mapping is only supported
+on Unix-like systems; elsewhere, arrays are always allocated on the heap.
+*/
+
+#[cfg(unix)]
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The memory budget, in bytes; 0 is unlimited.
+static MAX_MEMORY: AtomicUsize = AtomicUsize::new(0);
+
+/// The memory currently allocated on the heap for budgeted arrays, in bytes.
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Used to give each memory-mapped temporary file a unique name.
+#[cfg(unix)]
+static MAPPED_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Serializes the tests that change the memory budget, directly or through
+/// `apply_framework_flags`, since it is shared by every test in the process.
+#[cfg(test)]
+pub static BUDGET_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Sets the memory budget, in bytes. A budget of 0 is unlimited.
+pub fn set_memory_budget(bytes: usize) {
+    MAX_MEMORY.store(bytes, Ordering::SeqCst);
+}
+
+/// Returns the memory budget, in bytes, or 0 if it is unlimited.
+pub fn memory_budget() -> usize {
+    MAX_MEMORY.load(Ordering::SeqCst)
+}
+
+/// Returns the memory currently allocated against the budget, in bytes.
+pub fn allocated_memory() -> usize {
+    ALLOCATED.load(Ordering::SeqCst)
+}
+
+/// Reserves `bytes` against the budget, returning false, without reserving
+/// anything, if the reservation would exceed the budget.
+pub fn try_reserve(bytes: usize) -> bool {
+    let mut current = ALLOCATED.load(Ordering::SeqCst);
+    loop {
+        let max = MAX_MEMORY.load(Ordering::SeqCst);
+        if max > 0 && current.saturating_add(bytes) > max {
+            return false;
+        }
+        match ALLOCATED.compare_exchange_weak(
+            current,
+            current + bytes,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => return true,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// Reserves `bytes` regardless of the budget, e.g. when an allocation cannot be
+/// moved out of the heap.
+pub fn force_reserve(bytes: usize) {
+    ALLOCATED.fetch_add(bytes, Ordering::SeqCst);
+}
+
+/// Releases a reservation made with `try_reserve` or `force_reserve`.
+pub fn release(bytes: usize) {
+    let mut current = ALLOCATED.load(Ordering::SeqCst);
+    loop {
+        match ALLOCATED.compare_exchange_weak(
+            current,
+            current.saturating_sub(bytes),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => return,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// Parses a memory size, e.g. `512M`, `16G`, or `1.5T`. Sizes without a unit
+/// suffix are in megabytes.
+pub fn parse_memory_size(s: &str) -> Option<usize> {
+    let s = s.trim().to_uppercase();
+    let s = s.trim_end_matches('B');
+    let (number, multiplier) = match s.chars().last() {
+        Some('K') => (&s[..s.len() - 1], 1024f64),
+        Some('M') => (&s[..s.len() - 1], 1024f64 * 1024f64),
+        Some('G') => (&s[..s.len() - 1], 1024f64 * 1024f64 * 1024f64),
+        Some('T') => (&s[..s.len() - 1], 1024f64 * 1024f64 * 1024f64 * 1024f64),
+        _ => (s, 1024f64 * 1024f64),
+    };
+    match number.trim().parse::<f64>() {
+        Ok(v) if v >= 0f64 => Some((v * multiplier) as usize),
+        _ => None,
+    }
+}
+
+/// A fixed-length buffer backed by a memory-mapped temporary file. The file is
+/// removed from the file system as soon as it is mapped, so that its disk space is
+/// reclaimed when the buffer is dropped, even if the process is killed.
+pub struct MappedBuffer<T: Copy> {
+    ptr: *mut T,
+    len: usize,
+}
+
+unsafe impl<T: Copy + Send> Send for MappedBuffer<T> {}
+unsafe impl<T: Copy + Sync> Sync for MappedBuffer<T> {}
+
+impl<T: Copy> MappedBuffer<T> {
+    /// Creates a buffer of `len` elements, each initialized to `value`.
+    #[cfg(unix)]
+    pub fn new(len: usize, value: T) -> Result<MappedBuffer<T>, Error> {
+        use std::fs::{self, OpenOptions};
+        use std::os::unix::io::AsRawFd;
+        use std::ptr;
+
+        let bytes = len * std::mem::size_of::<T>();
+        if bytes == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Empty buffers cannot be memory-mapped.",
+            ));
+        }
+        let file_name = mapped_file_path(MAPPED_FILE_ID.fetch_add(1, Ordering::SeqCst));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&file_name)?;
+        let _ = fs::remove_file(&file_name);
+        file.set_len(bytes as u64)?;
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                bytes,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(Error::last_os_error());
+        }
+        // the mapping remains valid after the file is closed
+        let ptr = ptr as *mut T;
+        for i in 0..len {
+            unsafe {
+                ptr.add(i).write(value);
+            }
+        }
+        Ok(MappedBuffer { ptr, len })
+    }
+
+    /// Creates a buffer of `len` elements, each initialized to `value`.
+    #[cfg(not(unix))]
+    pub fn new(_len: usize, _value: T) -> Result<MappedBuffer<T>, Error> {
+        Err(Error::new(
+            ErrorKind::Other,
+            "Memory-mapped buffers are not supported on this platform.",
+        ))
+    }
+}
+
+/// Returns the path of the temporary file backing the memory-mapped buffer with the given id.
+#[cfg(unix)]
+fn mapped_file_path(id: usize) -> PathBuf {
+    env::temp_dir().join(format!("whitebox_tools_{}_{}.tmp", process::id(), id))
+}
+
+/// Returns the paths of all of the temporary files created for memory-mapped buffers so far.
+#[cfg(all(test, unix))]
+pub fn mapped_file_paths() -> Vec<PathBuf> {
+    (0..MAPPED_FILE_ID.load(Ordering::SeqCst))
+        .map(mapped_file_path)
+        .collect()
+}
+
+impl<T: Copy> Deref for MappedBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T: Copy> DerefMut for MappedBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl<T: Copy> Drop for MappedBuffer<T> {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        unsafe {
+            libc::munmap(
+                self.ptr as *mut libc::c_void,
+                self.len * std::mem::size_of::<T>(),
+            );
+        }
+    }
+}
+
+// memory mapping is only supported on Unix-like systems
+#[cfg(all(test, unix))]
+mod test {
+    use super::{mapped_file_paths, MappedBuffer};
+
+    #[test]
+    fn test_mapped_buffer() {
+        let mut buffer = MappedBuffer::new(1000, 1.5f64).unwrap();
+        assert_eq!(buffer.len(), 1000);
+        assert!(buffer.iter().all(|&v| v == 1.5f64));
+        buffer[999] = -2f64;
+        buffer[0] = 3f64;
+        assert_eq!(buffer[0], 3f64);
+        assert_eq!(buffer[999], -2f64);
+        assert_eq!(buffer.iter().sum::<f64>(), 1.5 * 998f64 + 1f64);
+        // the backing file is removed as soon as it is mapped
+        for path in mapped_file_paths() {
+            assert!(!path.exists(), "{} was not removed", path.display());
+        }
+        drop(buffer);
+        assert!(MappedBuffer::new(0, 0u8).is_err());
+    }
+}
@@ -1,13 +1,38 @@
 // private sub-module defined in other files
 mod byte_order_reader;
 mod byte_order_writer;
+pub mod checkpoint;
+pub mod gpu;
+pub mod memory;
+pub mod random;
+pub mod simd;
+pub mod thread;
+pub mod vfs;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::byte_order_reader::ByteOrderReader;
 pub use self::byte_order_reader::Endianness;
 pub use self::byte_order_writer::ByteOrderWriter;
 
-use std::time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+
+/// A stand-in for `std::time::Instant` on the `wasm32` target, where the system
+/// clock is unavailable. Elapsed times are always reported as zero.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Debug)]
+pub struct Instant;
+
+#[cfg(target_arch = "wasm32")]
+impl Instant {
+    pub fn now() -> Instant {
+        Instant
+    }
+
+    pub fn elapsed(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(0)
+    }
+}
 
 /// Returns a formatted string of elapsed time, e.g.
 /// `1min 34s 852ms`
@@ -21,3 +46,20 @@ pub fn get_formatted_elapsed_time(instant: Instant) -> String {
     }
     format!("{}.{}s", sub_sec, sub_milli)
 }
+
+/// Returns the percentage reported by a tool's progress message, e.g.
+/// `Performing analysis: 45%`, or None if the line does not report progress.
+pub fn parse_progress(line: &str) -> Option<usize> {
+    let line = line.trim();
+    if !line.ends_with('%') {
+        return None;
+    }
+    let value = line[..line.len() - 1]
+        .rsplit(|c: char| c == ':' || c.is_whitespace())
+        .next()
+        .unwrap_or("");
+    match value.parse::<usize>() {
+        Ok(v) if v <= 100 => Some(v),
+        _ => None,
+    }
+}
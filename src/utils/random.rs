@@ -0,0 +1,68 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+Random number generation for the stochastic tools. By default, each generator is
+seeded from the operating system's entropy source and results differ between runs.
+When the user supplies the `--seed` flag, generators are instead seeded from that
+value, making tool outputs reproducible. Tools request a generator for a numbered
+'stream', which should identify a unit of work (e.g. a row or an iteration) rather
+than a thread, so that the random numbers consumed by each unit of work, and hence
+the results, do not depend on the number of threads or the order in which they are
+scheduled. Results are reproducible for a given seed, build, and platform.
+*/
+
+use rand::rngs::SmallRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Set by the `--seed` flag.
+static SEED: AtomicU64 = AtomicU64::new(0);
+static SEEDED: AtomicBool = AtomicBool::new(false);
+
+/// Sets the global random seed, or restores entropy-based seeding if `seed` is `None`.
+pub fn set_seed(seed: Option<u64>) {
+    SEED.store(seed.unwrap_or(0), Ordering::SeqCst);
+    SEEDED.store(seed.is_some(), Ordering::SeqCst);
+}
+
+/// Returns the global random seed, if one has been set.
+pub fn seed() -> Option<u64> {
+    if SEEDED.load(Ordering::SeqCst) {
+        Some(SEED.load(Ordering::SeqCst))
+    } else {
+        None
+    }
+}
+
+/// Returns a random number generator for the specified stream. With a global seed,
+/// the generator's sequence is fully determined by the seed and the stream number.
+pub fn rng(stream: u64) -> SmallRng {
+    match seed() {
+        Some(seed) => SmallRng::seed_from_u64(mix(seed, stream)),
+        None => SmallRng::from_rng(thread_rng()).unwrap_or_else(|_| SmallRng::from_entropy()),
+    }
+}
+
+/// Returns a seed, e.g. for a model that records the seed used to train it. With a
+/// global seed, the value is determined by the seed and the stream number.
+pub fn random_seed(stream: u64) -> u64 {
+    match seed() {
+        Some(seed) => mix(seed, stream),
+        None => thread_rng().gen(),
+    }
+}
+
+/// Combines a seed and stream number into a well-distributed 64-bit value, using the
+/// SplitMix64 finalizer, so that neighbouring streams have uncorrelated seeds.
+fn mix(seed: u64, stream: u64) -> u64 {
+    let mut z = seed ^ stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,404 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+Row-oriented kernels for the hot per-cell loops of the terrain analysis, filtering,
+and raster math tools. Each function processes an entire row of a raster at a time.
+When WhiteboxTools is built with the `simd` feature (`cargo build --release
+--features simd`) on an x86-64 processor that supports AVX, which is detected at run
+time, the interior of each row is processed four cells at a time using explicit SIMD
+intrinsics; otherwise, and for the cells at the ends of rows, scalar code is used.
+Both paths perform the same floating-point operations in the same order, so their
+results are identical. See `benches/raster_kernels.rs` for benchmarks.
+*/
+
+/// Computes a weighted sum of the 3 x 3 neighbourhood of each cell in a row, as used
+/// by the slope, aspect, and curvature tools. `above`, `center`, and `below` are the
+/// rows above, containing, and below the cells to be processed; rows beyond the edges
+/// of a raster should be filled with NoData. The `weights` are in row-major order,
+/// starting with the upper-left neighbour. NoData neighbours, and neighbours beyond the
+/// edges of the raster, take the value of the centre cell. Cells that are themselves
+/// NoData are assigned NoData in `out`.
+pub fn window3x3(
+    above: &[f64],
+    center: &[f64],
+    below: &[f64],
+    nodata: f64,
+    weights: &[f64; 9],
+    out: &mut [f64],
+) {
+    let columns = center.len();
+    assert!(above.len() == columns && below.len() == columns && out.len() >= columns);
+    if columns == 0 {
+        return;
+    }
+    let rows = [above, center, below];
+    // the cells at the ends of the row have neighbours beyond the edges of the raster
+    window3x3_cell(&rows, nodata, weights, 0, out);
+    if columns > 1 {
+        window3x3_cell(&rows, nodata, weights, columns - 1, out);
+    }
+    #[allow(unused_mut)]
+    let mut col = 1;
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx") {
+            col = unsafe { avx::window3x3(&rows, nodata, weights, out) };
+        }
+    }
+    while col + 1 < columns {
+        window3x3_cell(&rows, nodata, weights, col, out);
+        col += 1;
+    }
+}
+
+fn window3x3_cell(
+    rows: &[&[f64]; 3],
+    nodata: f64,
+    weights: &[f64; 9],
+    col: usize,
+    out: &mut [f64],
+) {
+    let z = rows[1][col];
+    if z == nodata {
+        out[col] = nodata;
+        return;
+    }
+    let columns = rows[1].len() as isize;
+    let mut sum = 0f64;
+    for r in 0..3 {
+        for c in 0..3 {
+            let x = col as isize + c as isize - 1;
+            let v = if x < 0 || x >= columns {
+                z
+            } else if rows[r][x as usize] == nodata {
+                z
+            } else {
+                rows[r][x as usize]
+            };
+            sum += weights[r * 3 + c] * v;
+        }
+    }
+    out[col] = sum;
+}
+
+/// Adds `weight` times each valid value in `values` to the corresponding element of
+/// `sum`, and `weight` itself to the corresponding element of `sum_weights`. This is the
+/// inner step of a convolution that excludes NoData values, applied once for each
+/// kernel cell to the appropriately offset part of an input row.
+pub fn accumulate_weighted(
+    values: &[f64],
+    nodata: f64,
+    weight: f64,
+    sum: &mut [f64],
+    sum_weights: &mut [f64],
+) {
+    let len = values.len();
+    assert!(sum.len() >= len && sum_weights.len() >= len);
+    #[allow(unused_mut)]
+    let mut i = 0;
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx") {
+            i = unsafe { avx::accumulate_weighted(values, nodata, weight, sum, sum_weights) };
+        }
+    }
+    accumulate_weighted_scalar(values, nodata, weight, sum, sum_weights, i);
+}
+
+/// The scalar code for `accumulate_weighted`, applied from element `start` onwards.
+fn accumulate_weighted_scalar(
+    values: &[f64],
+    nodata: f64,
+    weight: f64,
+    sum: &mut [f64],
+    sum_weights: &mut [f64],
+    start: usize,
+) {
+    for i in start..values.len() {
+        if values[i] != nodata {
+            sum[i] += weight * values[i];
+            sum_weights[i] += weight;
+        }
+    }
+}
+
+/// A cell-by-cell arithmetic operation on two rasters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    /// Division; cells with a divisor of zero are assigned NoData.
+    Divide,
+}
+
+/// Applies `op` to each pair of cells in the rows `a` and `b`. Cells that are NoData in
+/// either input are assigned `nodata_a` in `out`.
+pub fn binary_op(
+    op: BinaryOp,
+    a: &[f64],
+    nodata_a: f64,
+    b: &[f64],
+    nodata_b: f64,
+    out: &mut [f64],
+) {
+    let len = a.len();
+    assert!(b.len() == len && out.len() >= len);
+    #[allow(unused_mut)]
+    let mut i = 0;
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx") {
+            i = unsafe { avx::binary_op(op, a, nodata_a, b, nodata_b, out) };
+        }
+    }
+    binary_op_scalar(op, a, nodata_a, b, nodata_b, out, i);
+}
+
+/// The scalar code for `binary_op`, applied from element `start` onwards.
+fn binary_op_scalar(
+    op: BinaryOp,
+    a: &[f64],
+    nodata_a: f64,
+    b: &[f64],
+    nodata_b: f64,
+    out: &mut [f64],
+    start: usize,
+) {
+    for i in start..a.len() {
+        let (z1, z2) = (a[i], b[i]);
+        out[i] = if z1 != nodata_a && z2 != nodata_b {
+            match op {
+                BinaryOp::Add => z1 + z2,
+                BinaryOp::Subtract => z1 - z2,
+                BinaryOp::Multiply => z1 * z2,
+                BinaryOp::Divide => {
+                    if z2 != 0f64 {
+                        z1 / z2
+                    } else {
+                        nodata_a
+                    }
+                }
+            }
+        } else {
+            nodata_a
+        };
+    }
+}
+
+/// AVX implementations, each of which processes as much of its input as fits in whole
+/// vectors and returns the index of the first element left for the scalar code.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod avx {
+    use super::BinaryOp;
+    use std::arch::x86_64::*;
+
+    const LANES: usize = 4;
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn window3x3(
+        rows: &[&[f64]; 3],
+        nodata: f64,
+        weights: &[f64; 9],
+        out: &mut [f64],
+    ) -> usize {
+        let columns = rows[1].len();
+        let nd = _mm256_set1_pd(nodata);
+        let mut w = [_mm256_setzero_pd(); 9];
+        for k in 0..9 {
+            w[k] = _mm256_set1_pd(weights[k]);
+        }
+        let mut col = 1;
+        // the cells col..col + LANES, and their neighbours, must lie within the row,
+        // leaving the last column for the scalar code
+        while col + LANES + 1 <= columns {
+            let z = _mm256_loadu_pd(rows[1].as_ptr().add(col));
+            let mut sum = _mm256_setzero_pd();
+            for r in 0..3 {
+                for c in 0..3 {
+                    let v = _mm256_loadu_pd(rows[r].as_ptr().add(col + c - 1));
+                    let v = _mm256_blendv_pd(v, z, _mm256_cmp_pd(v, nd, _CMP_EQ_OQ));
+                    sum = _mm256_add_pd(sum, _mm256_mul_pd(w[r * 3 + c], v));
+                }
+            }
+            let result = _mm256_blendv_pd(sum, nd, _mm256_cmp_pd(z, nd, _CMP_EQ_OQ));
+            _mm256_storeu_pd(out.as_mut_ptr().add(col), result);
+            col += LANES;
+        }
+        col
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn accumulate_weighted(
+        values: &[f64],
+        nodata: f64,
+        weight: f64,
+        sum: &mut [f64],
+        sum_weights: &mut [f64],
+    ) -> usize {
+        let nd = _mm256_set1_pd(nodata);
+        let w = _mm256_set1_pd(weight);
+        let zero = _mm256_setzero_pd();
+        let mut i = 0;
+        while i + LANES <= values.len() {
+            let v = _mm256_loadu_pd(values.as_ptr().add(i));
+            let valid = _mm256_cmp_pd(v, nd, _CMP_NEQ_UQ);
+            let s = _mm256_loadu_pd(sum.as_ptr().add(i));
+            let sw = _mm256_loadu_pd(sum_weights.as_ptr().add(i));
+            let s = _mm256_blendv_pd(s, _mm256_add_pd(s, _mm256_mul_pd(w, v)), valid);
+            let sw = _mm256_add_pd(sw, _mm256_blendv_pd(zero, w, valid));
+            _mm256_storeu_pd(sum.as_mut_ptr().add(i), s);
+            _mm256_storeu_pd(sum_weights.as_mut_ptr().add(i), sw);
+            i += LANES;
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn binary_op(
+        op: BinaryOp,
+        a: &[f64],
+        nodata_a: f64,
+        b: &[f64],
+        nodata_b: f64,
+        out: &mut [f64],
+    ) -> usize {
+        let nda = _mm256_set1_pd(nodata_a);
+        let ndb = _mm256_set1_pd(nodata_b);
+        let zero = _mm256_setzero_pd();
+        let mut i = 0;
+        while i + LANES <= a.len() {
+            let z1 = _mm256_loadu_pd(a.as_ptr().add(i));
+            let z2 = _mm256_loadu_pd(b.as_ptr().add(i));
+            let mut missing = _mm256_or_pd(
+                _mm256_cmp_pd(z1, nda, _CMP_EQ_OQ),
+                _mm256_cmp_pd(z2, ndb, _CMP_EQ_OQ),
+            );
+            let result = match op {
+                BinaryOp::Add => _mm256_add_pd(z1, z2),
+                BinaryOp::Subtract => _mm256_sub_pd(z1, z2),
+                BinaryOp::Multiply => _mm256_mul_pd(z1, z2),
+                BinaryOp::Divide => {
+                    missing = _mm256_or_pd(missing, _mm256_cmp_pd(z2, zero, _CMP_EQ_OQ));
+                    _mm256_div_pd(z1, z2)
+                }
+            };
+            _mm256_storeu_pd(
+                out.as_mut_ptr().add(i),
+                _mm256_blendv_pd(result, nda, missing),
+            );
+            i += LANES;
+        }
+        i
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        accumulate_weighted, accumulate_weighted_scalar, binary_op, binary_op_scalar, window3x3,
+        window3x3_cell, BinaryOp,
+    };
+    use std::f64;
+
+    const NODATA: f64 = -32768f64;
+
+    /// A row of `len` values containing NoData, NaN, zero, and negative zero cells.
+    fn test_row(len: usize, offset: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| match (i + offset) % 7 {
+                0 => NODATA,
+                3 => f64::NAN,
+                5 => 0f64,
+                6 => -0f64,
+                k => (i * 13 + offset) as f64 * 0.37 - 2.5 * k as f64,
+            })
+            .collect()
+    }
+
+    fn assert_same(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for i in 0..a.len() {
+            assert!(
+                a[i].to_bits() == b[i].to_bits() || (a[i].is_nan() && b[i].is_nan()),
+                "element {}: {} != {}",
+                i,
+                a[i],
+                b[i]
+            );
+        }
+    }
+
+    // The lengths are chosen to leave every possible remainder after the four-lane vectors
+    // of the AVX code, which is only used when built with the `simd` feature.
+    #[test]
+    fn test_window3x3_matches_scalar() {
+        let weights = [1.0, -2.0, 0.5, 3.0, 0.25, -1.5, 2.0, 0.75, -0.125];
+        for &nodata in &[NODATA, f64::NAN] {
+            for len in 1..20 {
+                let rows = [test_row(len, 1), test_row(len, 2), test_row(len, 4)];
+                let rows = [&rows[0][..], &rows[1][..], &rows[2][..]];
+                let mut out = vec![0f64; len];
+                window3x3(rows[0], rows[1], rows[2], nodata, &weights, &mut out);
+                let mut expected = vec![0f64; len];
+                for col in 0..len {
+                    window3x3_cell(&rows, nodata, &weights, col, &mut expected);
+                }
+                assert_same(&out, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_accumulate_weighted_matches_scalar() {
+        for &nodata in &[NODATA, f64::NAN] {
+            for len in 0..20 {
+                let values = test_row(len, 0);
+                let mut sum = test_row(len, 3);
+                let mut sum_weights = vec![1f64; len];
+                let mut expected_sum = sum.clone();
+                let mut expected_weights = sum_weights.clone();
+                accumulate_weighted(&values, nodata, 0.6, &mut sum, &mut sum_weights);
+                accumulate_weighted_scalar(
+                    &values,
+                    nodata,
+                    0.6,
+                    &mut expected_sum,
+                    &mut expected_weights,
+                    0,
+                );
+                assert_same(&sum, &expected_sum);
+                assert_same(&sum_weights, &expected_weights);
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_op_matches_scalar() {
+        let ops = [
+            BinaryOp::Add,
+            BinaryOp::Subtract,
+            BinaryOp::Multiply,
+            BinaryOp::Divide,
+        ];
+        for &op in &ops {
+            for &nodata in &[NODATA, f64::NAN] {
+                for len in 0..20 {
+                    let a = test_row(len, 0);
+                    let b = test_row(len, 2);
+                    let mut out = vec![0f64; len];
+                    let mut expected = vec![0f64; len];
+                    binary_op(op, &a, nodata, &b, NODATA, &mut out);
+                    binary_op_scalar(op, &a, nodata, &b, NODATA, &mut expected, 0);
+                    assert_same(&out, &expected);
+                }
+            }
+        }
+    }
+}
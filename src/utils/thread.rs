@@ -0,0 +1,23 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+use std::thread;
+
+/// Spawns a new thread to run `f`. On the `wasm32` target, where threads are not
+/// available, `f` is run to completion on the calling thread instead, and so work
+/// that reports its results over a channel behaves the same on either target.
+pub fn spawn<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    if cfg!(target_arch = "wasm32") {
+        f();
+    } else {
+        thread::spawn(f);
+    }
+}
@@ -0,0 +1,138 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+A minimal virtual file system used by the raster and vector I/O layer. Files whose
+names begin with `vfs://` are held in memory as byte buffers rather than being
+written to disk. On the `wasm32` target, where there is no file system, every file
+name is treated as virtual, which allows the GeoTIFF and Shapefile readers and
+writers to be used unchanged within a web browser.
+*/
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::sync::Mutex;
+
+/// The file name prefix used to identify virtual files.
+pub const VFS_PREFIX: &str = "vfs://";
+
+static FILES: Mutex<BTreeMap<String, Vec<u8>>> = Mutex::new(BTreeMap::new());
+
+/// Returns true if the file name refers to a virtual file.
+pub fn is_virtual(file_name: &str) -> bool {
+    cfg!(target_arch = "wasm32") || file_name.starts_with(VFS_PREFIX)
+}
+
+/// Reads the contents of a file, which may be either virtual or on disk.
+pub fn read_file(file_name: &str) -> Result<Vec<u8>, Error> {
+    if !is_virtual(file_name) {
+        return fs::read(file_name);
+    }
+    match FILES
+        .lock()
+        .expect("Error accessing virtual files.")
+        .get(key(file_name))
+    {
+        Some(bytes) => Ok(bytes.clone()),
+        None => Err(Error::new(
+            ErrorKind::NotFound,
+            format!("No virtual file named {} was found.", file_name),
+        )),
+    }
+}
+
+/// Writes the contents of a file, which may be either virtual or on disk,
+/// replacing any existing file of the same name.
+pub fn write_file(file_name: &str, bytes: Vec<u8>) -> Result<(), Error> {
+    if !is_virtual(file_name) {
+        return fs::write(file_name, bytes);
+    }
+    FILES
+        .lock()
+        .expect("Error accessing virtual files.")
+        .insert(key(file_name).to_string(), bytes);
+    Ok(())
+}
+
+/// Creates a file for writing. Virtual files are stored when the returned
+/// writer is dropped.
+pub fn create_file(file_name: &str) -> Result<Box<dyn Write>, Error> {
+    if !is_virtual(file_name) {
+        return Ok(Box::new(BufWriter::new(File::create(file_name)?)));
+    }
+    Ok(Box::new(VirtualFile {
+        file_name: file_name.to_string(),
+        bytes: vec![],
+    }))
+}
+
+/// Returns true if the file exists.
+pub fn file_exists(file_name: &str) -> bool {
+    if !is_virtual(file_name) {
+        return fs::metadata(file_name).is_ok();
+    }
+    FILES
+        .lock()
+        .expect("Error accessing virtual files.")
+        .contains_key(key(file_name))
+}
+
+/// Removes a virtual file, returning its contents.
+pub fn remove_file(file_name: &str) -> Option<Vec<u8>> {
+    FILES
+        .lock()
+        .expect("Error accessing virtual files.")
+        .remove(key(file_name))
+}
+
+/// Returns the names of all virtual files.
+pub fn list_files() -> Vec<String> {
+    FILES
+        .lock()
+        .expect("Error accessing virtual files.")
+        .keys()
+        .map(|k| {
+            if cfg!(target_arch = "wasm32") {
+                k.clone()
+            } else {
+                format!("{}{}", VFS_PREFIX, k)
+            }
+        })
+        .collect()
+}
+
+/// Virtual files are keyed without the `vfs://` prefix, so that on the `wasm32`
+/// target, `vfs://DEM.tif` and `DEM.tif` refer to the same file.
+fn key(file_name: &str) -> &str {
+    file_name.trim_start_matches(VFS_PREFIX)
+}
+
+struct VirtualFile {
+    file_name: String,
+    bytes: Vec<u8>,
+}
+
+impl Write for VirtualFile {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl Drop for VirtualFile {
+    fn drop(&mut self) {
+        let bytes = std::mem::take(&mut self.bytes);
+        let _ = write_file(&self.file_name, bytes);
+    }
+}
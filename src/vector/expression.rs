@@ -0,0 +1,1128 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+NOTE: A small SQL-like expression language evaluated against the attribute
+records and geometries of a Shapefile. It is used by the attribute query and
+field calculator tools.
+*/
+
+use crate::vector::shapefile::attributes::{FieldData, ShapefileAttributes};
+use crate::vector::shapefile::geometry::{ShapeType, ShapefileGeometry};
+use std::cmp::Ordering;
+use std::f64;
+use std::io::{Error, ErrorKind};
+
+/// The value of an evaluated expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Null,
+}
+
+impl ExpressionValue {
+    /// Converts an attribute table value into an expression value.
+    pub fn from_field_data(value: &FieldData) -> ExpressionValue {
+        match value {
+            FieldData::Int(v) => ExpressionValue::Number(*v as f64),
+            FieldData::Int64(v) => ExpressionValue::Number(*v as f64),
+            FieldData::Real(v) => ExpressionValue::Number(*v),
+            FieldData::Text(v) => ExpressionValue::Text(v.clone()),
+            FieldData::Date(v) => ExpressionValue::Text(v.to_string()),
+            FieldData::DateTime(v) => ExpressionValue::Text(v.to_string()),
+            FieldData::Bool(v) => ExpressionValue::Bool(*v),
+            FieldData::Null => ExpressionValue::Null,
+        }
+    }
+
+    /// Returns true if the value satisfies a query statement. Null values,
+    /// zero, and empty strings are false.
+    pub fn is_true(&self) -> bool {
+        match self {
+            ExpressionValue::Bool(b) => *b,
+            ExpressionValue::Number(v) => *v != 0f64 && !v.is_nan(),
+            ExpressionValue::Text(s) => !s.is_empty(),
+            ExpressionValue::Null => false,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == ExpressionValue::Null
+    }
+
+    /// Returns the value as a number, parsing text where possible.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ExpressionValue::Number(v) => Some(*v),
+            ExpressionValue::Text(s) => s.trim().parse::<f64>().ok(),
+            ExpressionValue::Bool(b) => Some(if *b { 1f64 } else { 0f64 }),
+            ExpressionValue::Null => None,
+        }
+    }
+
+    /// Returns the value as text. Null values are returned as an empty string.
+    pub fn as_text(&self) -> String {
+        match self {
+            ExpressionValue::Number(v) => {
+                if v.fract() == 0f64 && v.abs() < 1e15 {
+                    format!("{}", *v as i64)
+                } else {
+                    format!("{}", v)
+                }
+            }
+            ExpressionValue::Text(s) => s.clone(),
+            ExpressionValue::Bool(b) => if *b { "true" } else { "false" }.to_string(),
+            ExpressionValue::Null => String::new(),
+        }
+    }
+}
+
+/// A parsed expression, e.g. `"AREA" > 1000 AND LANDUSE IN ('forest', 'wetland')`.
+///
+/// Expressions may contain numeric and 'single-quoted' string literals, field
+/// names (optionally written as "double-quoted" or [bracketed] names when they
+/// contain spaces), the arithmetic operators `+ - * / % ^`, the string
+/// concatenation operator `||`, the comparison operators `= == != <> < <= > >=`,
+/// `AND`, `OR`, `NOT`, `LIKE`/`ILIKE` (with `%` and `_` wildcards),
+/// `IN (...)`, `BETWEEN ... AND ...`, `IS [NOT] NULL`, and function calls.
+/// The geometry properties `$area`, `$perimeter`, `$length`, `$x`, `$y`,
+/// `$xmin`, `$xmax`, `$ymin`, `$ymax`, `$num_points`, `$num_parts`, and
+/// `$fid` (the one-based record number) are also available.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    root: Node,
+}
+
+impl Expression {
+    /// Parses an expression, resolving field names against an attribute table.
+    pub fn parse(statement: &str, attributes: &ShapefileAttributes) -> Result<Expression, Error> {
+        let tokens = tokenize(statement).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Error in expression: {}", e),
+            )
+        })?;
+        let mut parser = Parser {
+            tokens: tokens,
+            pos: 0,
+            attributes: attributes,
+        };
+        let root = parser
+            .parse_or()
+            .and_then(|node| {
+                if parser.pos < parser.tokens.len() {
+                    Err(format!("unexpected '{}'", parser.tokens[parser.pos]))
+                } else {
+                    Ok(node)
+                }
+            })
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Error in expression: {}", e),
+                )
+            })?;
+        Ok(Expression { root: root })
+    }
+
+    /// Evaluates the expression for a record, given its attribute values,
+    /// geometry, and zero-based record index.
+    pub fn evaluate(
+        &self,
+        record: &[FieldData],
+        geometry: &ShapefileGeometry,
+        record_index: usize,
+    ) -> ExpressionValue {
+        let context = Context {
+            record: record,
+            geometry: geometry,
+            record_index: record_index,
+        };
+        context.eval(&self.root)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    Field(String),
+    Property(String),
+    Op(String),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Number(v) => write!(f, "{}", v),
+            Token::Text(s) => write!(f, "'{}'", s),
+            Token::Ident(s) => write!(f, "{}", s),
+            Token::Field(s) => write!(f, "\"{}\"", s),
+            Token::Property(s) => write!(f, "${}", s),
+            Token::Op(s) => write!(f, "{}", s),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit())
+        {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let v = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{}'", text))?;
+            tokens.push(Token::Number(v));
+        } else if c == '\'' || c == '"' || c == '[' {
+            // string literals use single quotes, field names double quotes or brackets;
+            // a doubled quote character within a quoted item is an escaped quote.
+            let close = if c == '[' { ']' } else { c };
+            let mut text = String::new();
+            i += 1;
+            loop {
+                if i >= chars.len() {
+                    return Err(format!(
+                        "unterminated {}",
+                        if c == '\'' { "string" } else { "field name" }
+                    ));
+                }
+                if chars[i] == close {
+                    if close != ']' && i + 1 < chars.len() && chars[i + 1] == close {
+                        text.push(close);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                text.push(chars[i]);
+                i += 1;
+            }
+            if c == '\'' {
+                tokens.push(Token::Text(text));
+            } else {
+                tokens.push(Token::Field(text));
+            }
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = if c == '$' { i + 1 } else { i };
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            if c == '$' {
+                if text.is_empty() {
+                    return Err("missing geometry property name after '$'".to_string());
+                }
+                tokens.push(Token::Property(text.to_lowercase()));
+            } else {
+                tokens.push(Token::Ident(text));
+            }
+        } else if c == '(' {
+            tokens.push(Token::LeftParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RightParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            match two.as_str() {
+                "==" | "!=" | "<>" | "<=" | ">=" | "||" => {
+                    tokens.push(Token::Op(two));
+                    i += 2;
+                }
+                _ => match c {
+                    '+' | '-' | '*' | '/' | '%' | '^' | '=' | '<' | '>' => {
+                        tokens.push(Token::Op(c.to_string()));
+                        i += 1;
+                    }
+                    _ => return Err(format!("unexpected character '{}'", c)),
+                },
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    And,
+    Or,
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Concat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Property {
+    Area,
+    Perimeter,
+    Length,
+    X,
+    Y,
+    XMin,
+    XMax,
+    YMin,
+    YMax,
+    NumPoints,
+    NumParts,
+    Fid,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(ExpressionValue),
+    Field(usize),
+    Property(Property),
+    Negate(Box<Node>),
+    Not(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Like(Box<Node>, Box<Node>, bool),
+    In(Box<Node>, Vec<Node>),
+    Between(Box<Node>, Box<Node>, Box<Node>),
+    IsNull(Box<Node>),
+    Function(String, Vec<Node>),
+}
+
+/// Function names and their minimum and maximum numbers of arguments.
+const FUNCTIONS: [(&str, usize, usize); 29] = [
+    ("abs", 1, 1),
+    ("sqrt", 1, 1),
+    ("exp", 1, 1),
+    ("ln", 1, 1),
+    ("log10", 1, 1),
+    ("sin", 1, 1),
+    ("cos", 1, 1),
+    ("tan", 1, 1),
+    ("asin", 1, 1),
+    ("acos", 1, 1),
+    ("atan", 1, 1),
+    ("atan2", 2, 2),
+    ("floor", 1, 1),
+    ("ceil", 1, 1),
+    ("round", 1, 2),
+    ("pow", 2, 2),
+    ("min", 1, usize::MAX),
+    ("max", 1, usize::MAX),
+    ("upper", 1, 1),
+    ("lower", 1, 1),
+    ("trim", 1, 1),
+    ("length", 1, 1),
+    ("substr", 2, 3),
+    ("concat", 1, usize::MAX),
+    ("to_number", 1, 1),
+    ("to_int", 1, 1),
+    ("to_string", 1, 1),
+    ("if", 3, 3),
+    ("coalesce", 1, usize::MAX),
+];
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    attributes: &'a ShapefileAttributes,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        if self.pos < self.tokens.len() {
+            self.pos += 1;
+            Ok(self.tokens[self.pos - 1].clone())
+        } else {
+            Err("unexpected end of expression".to_string())
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(s)) => s.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn peek_keyword_at(&self, offset: usize, keyword: &str) -> bool {
+        match self.tokens.get(self.pos + offset) {
+            Some(Token::Ident(s)) => s.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        let t = self.next()?;
+        if t != token {
+            return Err(format!("expected '{}' but found '{}'", token, t));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            node = Node::Binary(BinaryOp::Or, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_not()?;
+        while self.peek_keyword("and") {
+            self.pos += 1;
+            let rhs = self.parse_not()?;
+            node = Node::Binary(BinaryOp::And, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, String> {
+        if self.peek_keyword("not") {
+            self.pos += 1;
+            let node = self.parse_not()?;
+            return Ok(Node::Not(Box::new(node)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, String> {
+        let lhs = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => match op.as_str() {
+                "=" | "==" => Some(BinaryOp::Eq),
+                "!=" | "<>" => Some(BinaryOp::NotEq),
+                "<" => Some(BinaryOp::Less),
+                "<=" => Some(BinaryOp::LessEq),
+                ">" => Some(BinaryOp::Greater),
+                ">=" => Some(BinaryOp::GreaterEq),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let rhs = self.parse_additive()?;
+            return Ok(Node::Binary(op, Box::new(lhs), Box::new(rhs)));
+        }
+
+        if self.peek_keyword("is") {
+            self.pos += 1;
+            let negated = self.peek_keyword("not");
+            if negated {
+                self.pos += 1;
+            }
+            if !self.peek_keyword("null") {
+                return Err("expected NULL after IS".to_string());
+            }
+            self.pos += 1;
+            let node = Node::IsNull(Box::new(lhs));
+            return Ok(if negated {
+                Node::Not(Box::new(node))
+            } else {
+                node
+            });
+        }
+
+        let negated = self.peek_keyword("not")
+            && (self.peek_keyword_at(1, "like")
+                || self.peek_keyword_at(1, "ilike")
+                || self.peek_keyword_at(1, "in")
+                || self.peek_keyword_at(1, "between"));
+        if negated {
+            self.pos += 1;
+        }
+        let node = if self.peek_keyword("like") || self.peek_keyword("ilike") {
+            let case_insensitive = self.peek_keyword("ilike");
+            self.pos += 1;
+            let pattern = self.parse_additive()?;
+            Node::Like(Box::new(lhs), Box::new(pattern), case_insensitive)
+        } else if self.peek_keyword("in") {
+            self.pos += 1;
+            self.expect(Token::LeftParen)?;
+            let mut list = vec![self.parse_or()?];
+            while self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+                list.push(self.parse_or()?);
+            }
+            self.expect(Token::RightParen)?;
+            Node::In(Box::new(lhs), list)
+        } else if self.peek_keyword("between") {
+            self.pos += 1;
+            let low = self.parse_additive()?;
+            if !self.peek_keyword("and") {
+                return Err("expected AND in BETWEEN".to_string());
+            }
+            self.pos += 1;
+            let high = self.parse_additive()?;
+            Node::Between(Box::new(lhs), Box::new(low), Box::new(high))
+        } else {
+            return Ok(lhs);
+        };
+        Ok(if negated {
+            Node::Not(Box::new(node))
+        } else {
+            node
+        })
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(op)) if op == "+" => BinaryOp::Add,
+                Some(Token::Op(op)) if op == "-" => BinaryOp::Subtract,
+                Some(Token::Op(op)) if op == "||" => BinaryOp::Concat,
+                _ => break,
+            };
+            self.pos += 1;
+            let rhs = self.parse_multiplicative()?;
+            node = Node::Binary(op, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Node, String> {
+        let mut node = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(op)) if op == "*" => BinaryOp::Multiply,
+                Some(Token::Op(op)) if op == "/" => BinaryOp::Divide,
+                Some(Token::Op(op)) if op == "%" => BinaryOp::Modulo,
+                _ => break,
+            };
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            node = Node::Binary(op, Box::new(node), Box::new(rhs));
+        }
+        Ok(node)
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, String> {
+        match self.peek() {
+            Some(Token::Op(op)) if op == "-" => {
+                self.pos += 1;
+                let node = self.parse_unary()?;
+                Ok(Node::Negate(Box::new(node)))
+            }
+            Some(Token::Op(op)) if op == "+" => {
+                self.pos += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_power(),
+        }
+    }
+
+    fn parse_power(&mut self) -> Result<Node, String> {
+        let base = self.parse_primary()?;
+        match self.peek() {
+            Some(Token::Op(op)) if op == "^" => {
+                self.pos += 1;
+                // right associative, and binds more tightly than unary minus on its left
+                let exponent = self.parse_unary()?;
+                Ok(Node::Binary(
+                    BinaryOp::Power,
+                    Box::new(base),
+                    Box::new(exponent),
+                ))
+            }
+            _ => Ok(base),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, String> {
+        match self.next()? {
+            Token::Number(v) => Ok(Node::Literal(ExpressionValue::Number(v))),
+            Token::Text(s) => Ok(Node::Literal(ExpressionValue::Text(s))),
+            Token::Field(name) => self.field(&name),
+            Token::Property(name) => {
+                let property = match name.as_str() {
+                    "area" => Property::Area,
+                    "perimeter" => Property::Perimeter,
+                    "length" => Property::Length,
+                    "x" => Property::X,
+                    "y" => Property::Y,
+                    "xmin" => Property::XMin,
+                    "xmax" => Property::XMax,
+                    "ymin" => Property::YMin,
+                    "ymax" => Property::YMax,
+                    "num_points" => Property::NumPoints,
+                    "num_parts" => Property::NumParts,
+                    "fid" => Property::Fid,
+                    _ => return Err(format!("unrecognized geometry property '${}'", name)),
+                };
+                Ok(Node::Property(property))
+            }
+            Token::LeftParen => {
+                let node = self.parse_or()?;
+                self.expect(Token::RightParen)?;
+                Ok(node)
+            }
+            Token::Ident(name) => {
+                if self.peek() == Some(&Token::LeftParen) {
+                    self.pos += 1;
+                    let mut args = vec![];
+                    if self.peek() != Some(&Token::RightParen) {
+                        args.push(self.parse_or()?);
+                        while self.peek() == Some(&Token::Comma) {
+                            self.pos += 1;
+                            args.push(self.parse_or()?);
+                        }
+                    }
+                    self.expect(Token::RightParen)?;
+                    let lower = name.to_lowercase();
+                    match FUNCTIONS.iter().find(|f| f.0 == lower) {
+                        Some(&(_, min_args, max_args)) => {
+                            if args.len() < min_args || args.len() > max_args {
+                                return Err(format!(
+                                    "wrong number of arguments for function '{}'",
+                                    name
+                                ));
+                            }
+                        }
+                        None => return Err(format!("unrecognized function '{}'", name)),
+                    }
+                    return Ok(Node::Function(lower, args));
+                }
+                match name.to_lowercase().as_str() {
+                    "true" => Ok(Node::Literal(ExpressionValue::Bool(true))),
+                    "false" => Ok(Node::Literal(ExpressionValue::Bool(false))),
+                    "null" => Ok(Node::Literal(ExpressionValue::Null)),
+                    _ => self.field(&name),
+                }
+            }
+            t => Err(format!("unexpected '{}'", t)),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Node, String> {
+        if let Some(index) = self.attributes.get_field_num(name) {
+            return Ok(Node::Field(index));
+        }
+        // DBF field names are frequently upper case; fall back to a case-insensitive match.
+        for (index, field) in self.attributes.get_fields().iter().enumerate() {
+            if field.name.eq_ignore_ascii_case(name) {
+                return Ok(Node::Field(index));
+            }
+        }
+        Err(format!("unrecognized field name '{}'", name))
+    }
+}
+
+struct Context<'a> {
+    record: &'a [FieldData],
+    geometry: &'a ShapefileGeometry,
+    record_index: usize,
+}
+
+impl<'a> Context<'a> {
+    fn eval(&self, node: &Node) -> ExpressionValue {
+        match node {
+            Node::Literal(v) => v.clone(),
+            Node::Field(index) => match self.record.get(*index) {
+                Some(v) => ExpressionValue::from_field_data(v),
+                None => ExpressionValue::Null,
+            },
+            Node::Property(p) => self.property(*p),
+            Node::Negate(n) => match self.eval(n).as_number() {
+                Some(v) => ExpressionValue::Number(-v),
+                None => ExpressionValue::Null,
+            },
+            Node::Not(n) => {
+                let v = self.eval(n);
+                if v.is_null() {
+                    ExpressionValue::Null
+                } else {
+                    ExpressionValue::Bool(!v.is_true())
+                }
+            }
+            Node::Binary(op, lhs, rhs) => self.binary(*op, lhs, rhs),
+            Node::Like(value, pattern, case_insensitive) => {
+                let value = self.eval(value);
+                let pattern = self.eval(pattern);
+                if value.is_null() || pattern.is_null() {
+                    return ExpressionValue::Null;
+                }
+                let (mut v, mut p) = (value.as_text(), pattern.as_text());
+                if *case_insensitive {
+                    v = v.to_lowercase();
+                    p = p.to_lowercase();
+                }
+                let v: Vec<char> = v.chars().collect();
+                let p: Vec<char> = p.chars().collect();
+                ExpressionValue::Bool(like(&v, &p))
+            }
+            Node::In(value, list) => {
+                let value = self.eval(value);
+                if value.is_null() {
+                    return ExpressionValue::Null;
+                }
+                for item in list {
+                    if compare(&value, &self.eval(item)) == Some(Ordering::Equal) {
+                        return ExpressionValue::Bool(true);
+                    }
+                }
+                ExpressionValue::Bool(false)
+            }
+            Node::Between(value, low, high) => {
+                let value = self.eval(value);
+                match (
+                    compare(&value, &self.eval(low)),
+                    compare(&value, &self.eval(high)),
+                ) {
+                    (Some(a), Some(b)) => {
+                        ExpressionValue::Bool(a != Ordering::Less && b != Ordering::Greater)
+                    }
+                    _ => ExpressionValue::Null,
+                }
+            }
+            Node::IsNull(n) => ExpressionValue::Bool(self.eval(n).is_null()),
+            Node::Function(name, args) => self.function(name, args),
+        }
+    }
+
+    fn binary(&self, op: BinaryOp, lhs: &Node, rhs: &Node) -> ExpressionValue {
+        match op {
+            BinaryOp::And => {
+                let a = self.eval(lhs);
+                if !a.is_null() && !a.is_true() {
+                    return ExpressionValue::Bool(false);
+                }
+                let b = self.eval(rhs);
+                if !b.is_null() && !b.is_true() {
+                    return ExpressionValue::Bool(false);
+                }
+                if a.is_null() || b.is_null() {
+                    return ExpressionValue::Null;
+                }
+                ExpressionValue::Bool(true)
+            }
+            BinaryOp::Or => {
+                let a = self.eval(lhs);
+                if a.is_true() {
+                    return ExpressionValue::Bool(true);
+                }
+                let b = self.eval(rhs);
+                if b.is_true() {
+                    return ExpressionValue::Bool(true);
+                }
+                if a.is_null() || b.is_null() {
+                    return ExpressionValue::Null;
+                }
+                ExpressionValue::Bool(false)
+            }
+            BinaryOp::Eq
+            | BinaryOp::NotEq
+            | BinaryOp::Less
+            | BinaryOp::LessEq
+            | BinaryOp::Greater
+            | BinaryOp::GreaterEq => {
+                let ordering = match compare(&self.eval(lhs), &self.eval(rhs)) {
+                    Some(o) => o,
+                    None => return ExpressionValue::Null,
+                };
+                ExpressionValue::Bool(match op {
+                    BinaryOp::Eq => ordering == Ordering::Equal,
+                    BinaryOp::NotEq => ordering != Ordering::Equal,
+                    BinaryOp::Less => ordering == Ordering::Less,
+                    BinaryOp::LessEq => ordering != Ordering::Greater,
+                    BinaryOp::Greater => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                })
+            }
+            BinaryOp::Concat => {
+                let (a, b) = (self.eval(lhs), self.eval(rhs));
+                if a.is_null() || b.is_null() {
+                    return ExpressionValue::Null;
+                }
+                ExpressionValue::Text(format!("{}{}", a.as_text(), b.as_text()))
+            }
+            _ => {
+                let (a, b) = (self.eval(lhs), self.eval(rhs));
+                if op == BinaryOp::Add {
+                    // '+' concatenates when either operand is non-numeric text
+                    if let (ExpressionValue::Text(_), _) | (_, ExpressionValue::Text(_)) = (&a, &b)
+                    {
+                        if !a.is_null()
+                            && !b.is_null()
+                            && (a.as_number().is_none() || b.as_number().is_none())
+                        {
+                            return ExpressionValue::Text(format!(
+                                "{}{}",
+                                a.as_text(),
+                                b.as_text()
+                            ));
+                        }
+                    }
+                }
+                let (x, y) = match (a.as_number(), b.as_number()) {
+                    (Some(x), Some(y)) => (x, y),
+                    _ => return ExpressionValue::Null,
+                };
+                let z = match op {
+                    BinaryOp::Add => x + y,
+                    BinaryOp::Subtract => x - y,
+                    BinaryOp::Multiply => x * y,
+                    BinaryOp::Divide => x / y,
+                    BinaryOp::Modulo => x % y,
+                    _ => x.powf(y),
+                };
+                if z.is_finite() {
+                    ExpressionValue::Number(z)
+                } else {
+                    ExpressionValue::Null
+                }
+            }
+        }
+    }
+
+    fn function(&self, name: &str, args: &[Node]) -> ExpressionValue {
+        match name {
+            "if" => {
+                return if self.eval(&args[0]).is_true() {
+                    self.eval(&args[1])
+                } else {
+                    self.eval(&args[2])
+                };
+            }
+            "coalesce" => {
+                for arg in args {
+                    let v = self.eval(arg);
+                    if !v.is_null() {
+                        return v;
+                    }
+                }
+                return ExpressionValue::Null;
+            }
+            _ => {}
+        }
+
+        let values: Vec<ExpressionValue> = args.iter().map(|a| self.eval(a)).collect();
+        if name == "concat" {
+            // nulls are treated as empty strings
+            return ExpressionValue::Text(values.iter().map(|v| v.as_text()).collect());
+        }
+        if values.iter().any(|v| v.is_null()) {
+            return ExpressionValue::Null;
+        }
+        match name {
+            "upper" => return ExpressionValue::Text(values[0].as_text().to_uppercase()),
+            "lower" => return ExpressionValue::Text(values[0].as_text().to_lowercase()),
+            "trim" => return ExpressionValue::Text(values[0].as_text().trim().to_string()),
+            "length" => return ExpressionValue::Number(values[0].as_text().chars().count() as f64),
+            "to_string" => return ExpressionValue::Text(values[0].as_text()),
+            "substr" => {
+                // one-based start position, as in SQL
+                let s: Vec<char> = values[0].as_text().chars().collect();
+                let start = match values[1].as_number() {
+                    Some(v) => (v.round().max(1f64) as usize - 1).min(s.len()),
+                    None => return ExpressionValue::Null,
+                };
+                let end = if values.len() > 2 {
+                    match values[2].as_number() {
+                        Some(v) => (start + v.round().max(0f64) as usize).min(s.len()),
+                        None => return ExpressionValue::Null,
+                    }
+                } else {
+                    s.len()
+                };
+                return ExpressionValue::Text(s[start..end].iter().collect());
+            }
+            "min" | "max" => {
+                let mut best = values[0].clone();
+                for v in &values[1..] {
+                    let ordering = compare(v, &best);
+                    if (name == "min" && ordering == Some(Ordering::Less))
+                        || (name == "max" && ordering == Some(Ordering::Greater))
+                    {
+                        best = v.clone();
+                    }
+                }
+                return best;
+            }
+            _ => {}
+        }
+
+        let nums: Vec<f64> = match values.iter().map(|v| v.as_number()).collect() {
+            Some(n) => n,
+            None => return ExpressionValue::Null,
+        };
+        let z = match name {
+            "abs" => nums[0].abs(),
+            "sqrt" => nums[0].sqrt(),
+            "exp" => nums[0].exp(),
+            "ln" => nums[0].ln(),
+            "log10" => nums[0].log10(),
+            "sin" => nums[0].sin(),
+            "cos" => nums[0].cos(),
+            "tan" => nums[0].tan(),
+            "asin" => nums[0].asin(),
+            "acos" => nums[0].acos(),
+            "atan" => nums[0].atan(),
+            "atan2" => nums[0].atan2(nums[1]),
+            "floor" => nums[0].floor(),
+            "ceil" => nums[0].ceil(),
+            "round" => {
+                if nums.len() > 1 {
+                    let m = 10f64.powi(nums[1].round() as i32);
+                    (nums[0] * m).round() / m
+                } else {
+                    nums[0].round()
+                }
+            }
+            "pow" => nums[0].powf(nums[1]),
+            "to_number" => nums[0],
+            _ => nums[0].trunc(), // to_int
+        };
+        if z.is_finite() {
+            ExpressionValue::Number(z)
+        } else {
+            ExpressionValue::Null
+        }
+    }
+
+    fn property(&self, property: Property) -> ExpressionValue {
+        let g = self.geometry;
+        let base_type = g.shape_type.base_shape_type();
+        let value = match property {
+            Property::Fid => (self.record_index + 1) as f64,
+            Property::NumPoints => g.num_points as f64,
+            Property::NumParts => g.num_parts as f64,
+            Property::XMin => g.x_min,
+            Property::XMax => g.x_max,
+            Property::YMin => g.y_min,
+            Property::YMax => g.y_max,
+            Property::X | Property::Y => {
+                if g.points.is_empty() {
+                    return ExpressionValue::Null;
+                }
+                // point coordinates for point data, otherwise the centre of the extent
+                let (x, y) = if base_type == ShapeType::Point {
+                    (g.points[0].x, g.points[0].y)
+                } else {
+                    ((g.x_min + g.x_max) / 2f64, (g.y_min + g.y_max) / 2f64)
+                };
+                if property == Property::X {
+                    x
+                } else {
+                    y
+                }
+            }
+            Property::Area | Property::Perimeter | Property::Length => {
+                let is_polygon = base_type == ShapeType::Polygon;
+                let is_line = base_type == ShapeType::PolyLine;
+                if !is_polygon && !(is_line && property == Property::Length) {
+                    return ExpressionValue::Number(0f64);
+                }
+                let mut total = 0f64;
+                for part in 0..g.num_parts as usize {
+                    let start = g.parts[part] as usize;
+                    let end = if part < g.num_parts as usize - 1 {
+                        g.parts[part + 1] as usize
+                    } else {
+                        g.num_points as usize
+                    };
+                    let points = &g.points[start..end];
+                    if property == Property::Area {
+                        let mut area = 0f64;
+                        for i in 0..points.len() {
+                            let j = (i + 1) % points.len();
+                            area += points[i].x * points[j].y - points[j].x * points[i].y;
+                        }
+                        if g.is_hole(part as i32) {
+                            total -= area.abs() / 2f64;
+                        } else {
+                            total += area.abs() / 2f64;
+                        }
+                    } else {
+                        for i in 1..points.len() {
+                            total += points[i].distance(&points[i - 1]);
+                        }
+                    }
+                }
+                total
+            }
+        };
+        ExpressionValue::Number(value)
+    }
+}
+
+/// Compares two values, numerically if both can be interpreted as numbers and
+/// as text otherwise. Returns None if either value is null.
+fn compare(a: &ExpressionValue, b: &ExpressionValue) -> Option<Ordering> {
+    if a.is_null() || b.is_null() {
+        return None;
+    }
+    if let (ExpressionValue::Text(s), ExpressionValue::Text(t)) = (a, b) {
+        return Some(s.cmp(t));
+    }
+    match (a.as_number(), b.as_number()) {
+        (Some(x), Some(y)) => x.partial_cmp(&y),
+        _ => Some(a.as_text().cmp(&b.as_text())),
+    }
+}
+
+/// SQL LIKE matching, where '%' matches any sequence of characters and '_'
+/// matches any single character.
+fn like(value: &[char], pattern: &[char]) -> bool {
+    let (mut v, mut p) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '_' || pattern[p] == value[v]) {
+            v += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '%' {
+            star = Some((p, v));
+            p += 1;
+        } else if let Some((sp, sv)) = star {
+            p = sp + 1;
+            v = sv + 1;
+            star = Some((sp, sv + 1));
+        } else {
+            return false;
+        }
+    }
+    while p < pattern.len() && pattern[p] == '%' {
+        p += 1;
+    }
+    p == pattern.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expression, ExpressionValue};
+    use crate::structures::Point2D;
+    use crate::vector::shapefile::attributes::{
+        AttributeField, FieldData, FieldDataType, ShapefileAttributes,
+    };
+    use crate::vector::shapefile::geometry::{ShapeType, ShapefileGeometry};
+
+    fn setup() -> (ShapefileAttributes, Vec<FieldData>, ShapefileGeometry) {
+        let mut attributes = ShapefileAttributes::default();
+        attributes.add_field(&AttributeField::new("NAME", FieldDataType::Text, 20u8, 0u8));
+        attributes.add_field(&AttributeField::new("POP", FieldDataType::Int, 10u8, 0u8));
+        attributes.add_field(&AttributeField::new(
+            "DENSITY",
+            FieldDataType::Real,
+            12u8,
+            4u8,
+        ));
+        let record = vec![
+            FieldData::Text("Guelph".to_string()),
+            FieldData::Int(135000),
+            FieldData::Null,
+        ];
+        let mut geometry = ShapefileGeometry::new(ShapeType::Polygon);
+        geometry.add_part(&[
+            Point2D::new(0.0, 0.0),
+            Point2D::new(0.0, 10.0),
+            Point2D::new(10.0, 10.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(0.0, 0.0),
+        ]);
+        (attributes, record, geometry)
+    }
+
+    fn eval(statement: &str) -> ExpressionValue {
+        let (attributes, record, geometry) = setup();
+        Expression::parse(statement, &attributes)
+            .unwrap()
+            .evaluate(&record, &geometry, 4)
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3 - 4 / 2"), ExpressionValue::Number(5.0));
+        assert_eq!(eval("-2 ^ 2"), ExpressionValue::Number(-4.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), ExpressionValue::Number(512.0));
+        assert_eq!(eval("round(pop / 1000, 1)"), ExpressionValue::Number(135.0));
+        assert_eq!(eval("density * 2"), ExpressionValue::Null);
+        assert_eq!(eval("1 / 0"), ExpressionValue::Null);
+    }
+
+    #[test]
+    fn test_queries() {
+        assert!(eval("POP > 100000 AND NAME = 'Guelph'").is_true());
+        assert!(eval("name LIKE 'G%ph'").is_true());
+        assert!(!eval("name LIKE 'g%'").is_true());
+        assert!(eval("name ILIKE 'g_elph'").is_true());
+        assert!(eval("NAME IN ('Toronto', 'Guelph')").is_true());
+        assert!(eval("POP NOT BETWEEN 1 AND 1000").is_true());
+        assert!(eval("DENSITY IS NULL AND POP IS NOT NULL").is_true());
+        assert!(!eval("DENSITY > 1 OR DENSITY <= 1").is_true());
+        assert!(eval("NOT (POP < 10)").is_true());
+    }
+
+    #[test]
+    fn test_strings_and_geometry() {
+        assert_eq!(
+            eval("upper(substr(NAME, 2, 3)) || '-' || $fid"),
+            ExpressionValue::Text("UEL-5".to_string())
+        );
+        assert_eq!(eval("$area"), ExpressionValue::Number(100.0));
+        assert_eq!(eval("$perimeter"), ExpressionValue::Number(40.0));
+        assert_eq!(
+            eval("if($x = 5, 'centre', 'other')"),
+            ExpressionValue::Text("centre".to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let (attributes, _, _) = setup();
+        assert!(Expression::parse("AREA > 5", &attributes).is_err());
+        assert!(Expression::parse("POP >", &attributes).is_err());
+        assert!(Expression::parse("sqrt(1, 2)", &attributes).is_err());
+        assert!(Expression::parse("NAME = 'abc", &attributes).is_err());
+    }
+}
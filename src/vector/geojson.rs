@@ -0,0 +1,149 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Output of vector data in the GeoJSON (RFC 7946) format. Coordinates are
+written in the coordinate reference system of the input data, without transformation.
+*/
+
+use crate::vector::shapefile::attributes::FieldData;
+use crate::vector::shapefile::geometry::{ShapeType, ShapefileGeometry};
+use crate::vector::shapefile::Shapefile;
+use serde_json::{json, Map, Value};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+
+/// Returns true if the file name has a GeoJSON extension (.geojson or .json).
+pub fn is_geojson_file(file_name: &str) -> bool {
+    let f = file_name.to_lowercase();
+    f.ends_with(".geojson") || f.ends_with(".json")
+}
+
+/// Writes the geometries and attributes of a vector to a GeoJSON FeatureCollection.
+pub fn write_geojson(vector: &Shapefile, file_name: &str) -> Result<(), Error> {
+    let fields = vector.attributes.get_fields();
+    let mut features = Vec::with_capacity(vector.records.len());
+    for record_num in 0..vector.records.len() {
+        let mut properties = Map::new();
+        if record_num < vector.attributes.header.num_records as usize {
+            let atts = vector.attributes.get_record(record_num);
+            for (field, value) in fields.iter().zip(atts.iter()) {
+                properties.insert(field.name.clone(), field_data_to_json(value));
+            }
+        }
+        features.push(json!({
+            "type": "Feature",
+            "geometry": geometry_to_json(&vector.records[record_num]),
+            "properties": Value::Object(properties),
+        }));
+    }
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": features,
+    });
+
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+    let s = serde_json::to_string(&collection)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("Error writing GeoJSON: {}", e)))?;
+    writer.write_all(s.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn field_data_to_json(value: &FieldData) -> Value {
+    match value {
+        FieldData::Int(v) => json!(v),
+        FieldData::Int64(v) => json!(v),
+        FieldData::Real(v) => {
+            if v.is_finite() {
+                json!(v)
+            } else {
+                Value::Null
+            }
+        }
+        FieldData::Text(v) => json!(v.trim()),
+        FieldData::Date(v) => json!(format!("{}", v)),
+        FieldData::DateTime(v) => json!(format!("{}", v)),
+        FieldData::Bool(v) => json!(v),
+        FieldData::Null => Value::Null,
+    }
+}
+
+fn geometry_to_json(record: &ShapefileGeometry) -> Value {
+    let coordinates = |start: usize, end: usize| -> Vec<Value> {
+        (start..end)
+            .map(|i| {
+                let p = record.points[i];
+                if record.has_z_data() && i < record.z_array.len() {
+                    json!([p.x, p.y, record.z_array[i]])
+                } else {
+                    json!([p.x, p.y])
+                }
+            })
+            .collect()
+    };
+    let num_parts = record.num_parts as usize;
+    let part_range = |part: usize| -> (usize, usize) {
+        let start = record.parts[part] as usize;
+        let end = if part < num_parts - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.points.len()
+        };
+        (start, end)
+    };
+
+    match record.shape_type.base_shape_type() {
+        ShapeType::Point => {
+            if record.points.is_empty() {
+                Value::Null
+            } else {
+                json!({
+                    "type": "Point",
+                    "coordinates": coordinates(0, 1)[0],
+                })
+            }
+        }
+        ShapeType::MultiPoint => json!({
+            "type": "MultiPoint",
+            "coordinates": coordinates(0, record.points.len()),
+        }),
+        ShapeType::PolyLine => {
+            let lines = (0..num_parts)
+                .map(|part| {
+                    let (start, end) = part_range(part);
+                    coordinates(start, end)
+                })
+                .collect::<Vec<Vec<Value>>>();
+            if lines.len() == 1 {
+                json!({ "type": "LineString", "coordinates": lines[0] })
+            } else {
+                json!({ "type": "MultiLineString", "coordinates": lines })
+            }
+        }
+        ShapeType::Polygon => {
+            // holes are grouped with the preceding hull part
+            let mut polygons: Vec<Vec<Vec<Value>>> = vec![];
+            for part in 0..num_parts {
+                let (start, end) = part_range(part);
+                if part > 0 && record.is_hole(part as i32) && !polygons.is_empty() {
+                    let last = polygons.len() - 1;
+                    polygons[last].push(coordinates(start, end));
+                } else {
+                    polygons.push(vec![coordinates(start, end)]);
+                }
+            }
+            if polygons.len() == 1 {
+                json!({ "type": "Polygon", "coordinates": polygons[0] })
+            } else {
+                json!({ "type": "MultiPolygon", "coordinates": polygons })
+            }
+        }
+        _ => Value::Null,
+    }
+}
@@ -14,7 +14,11 @@ for now it's just Shapefiles.
 // use std::fmt;
 
 // private sub-module defined in other files
+mod expression;
+mod geojson;
+mod parquet;
 pub mod shapefile;
+mod spatialite;
 
 // exports identifiers from private sub-modules in the current module namespace
 // pub use self::shapefile::attributes::{
@@ -25,8 +29,18 @@ pub use crate::vector::shapefile::attributes::*;
 pub use crate::vector::shapefile::geometry::*;
 // pub use self::shapefile::geometry::{ShapeType, ShapeTypeDimension, ShapefileGeometry};
 pub use crate::structures::Point2D;
+pub use crate::vector::expression::{Expression, ExpressionValue};
+pub use crate::vector::geojson::{is_geojson_file, write_geojson};
+pub use crate::vector::parquet::{
+    geometry_to_wkb, geoparquet_metadata, is_parquet_file, point_to_wkb, write_parquet,
+    ParquetColumn, ParquetTable,
+};
 pub use crate::vector::shapefile::geometry::ShapeType;
 pub use crate::vector::shapefile::Shapefile;
+pub use crate::vector::spatialite::{
+    is_spatialite_file, list_spatialite_layers, parse_spatialite_path, SpatialiteLayer,
+    SpatialiteSource,
+};
 
 // /// A 2-D point, with x and y fields.
 // #[derive(Default, Copy, Clone, Debug)]
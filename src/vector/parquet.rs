@@ -0,0 +1,761 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Output of tables in the Apache Parquet format, which can be read directly by
+pyarrow, pandas, polars, DuckDB and most other columnar analytics tools. Tables are
+written with a flat schema of nullable columns, in row groups of up to ROW_GROUP_SIZE
+rows. Each column chunk holds a single PLAIN-encoded, GZIP-compressed data page, with
+the nulls recorded as RLE-encoded definition levels. The file metadata are serialized
+with the Thrift compact protocol. Geometries are stored as ISO WKB in a binary column
+and described by the 'geo' key of the file metadata, following GeoParquet 1.0.
+*/
+
+use crate::spatial_ref_system::CoordinateReferenceSystem;
+use crate::vector::shapefile::attributes::FieldData;
+use crate::vector::shapefile::geometry::{ShapeType, ShapefileGeometry};
+use crate::vector::shapefile::Shapefile;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind};
+
+/// The maximum number of rows in a row group.
+pub const ROW_GROUP_SIZE: usize = 1_000_000;
+
+const MAGIC: &[u8; 4] = b"PAR1";
+
+// Parquet physical types, converted types, encodings, and codecs
+const BOOLEAN: i32 = 0;
+const INT32: i32 = 1;
+const INT64: i32 = 2;
+const DOUBLE: i32 = 5;
+const BYTE_ARRAY: i32 = 6;
+const CONVERTED_UTF8: i32 = 0;
+const CONVERTED_DATE: i32 = 6;
+const CONVERTED_TIMESTAMP_MILLIS: i32 = 9;
+const OPTIONAL: i32 = 1;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_GZIP: i32 = 2;
+
+/// Returns true if the file name has a Parquet extension (.parquet or .geoparquet).
+pub fn is_parquet_file(file_name: &str) -> bool {
+    let f = file_name.to_lowercase();
+    f.ends_with(".parquet") || f.ends_with(".geoparquet")
+}
+
+/// A column of a table. Values of None are written as nulls.
+#[derive(Clone, Debug)]
+pub enum ParquetColumn {
+    Boolean(Vec<Option<bool>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    Double(Vec<Option<f64>>),
+    Text(Vec<Option<String>>),
+    /// Dates, as the number of days since 1970-01-01.
+    Date(Vec<Option<i32>>),
+    /// Date-times, as the number of milliseconds since 1970-01-01T00:00:00.
+    Timestamp(Vec<Option<i64>>),
+    Binary(Vec<Option<Vec<u8>>>),
+}
+
+impl ParquetColumn {
+    pub fn len(&self) -> usize {
+        match self {
+            ParquetColumn::Boolean(v) => v.len(),
+            ParquetColumn::Int32(v) | ParquetColumn::Date(v) => v.len(),
+            ParquetColumn::Int64(v) | ParquetColumn::Timestamp(v) => v.len(),
+            ParquetColumn::Double(v) => v.len(),
+            ParquetColumn::Text(v) => v.len(),
+            ParquetColumn::Binary(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn physical_type(&self) -> i32 {
+        match self {
+            ParquetColumn::Boolean(_) => BOOLEAN,
+            ParquetColumn::Int32(_) | ParquetColumn::Date(_) => INT32,
+            ParquetColumn::Int64(_) | ParquetColumn::Timestamp(_) => INT64,
+            ParquetColumn::Double(_) => DOUBLE,
+            ParquetColumn::Text(_) | ParquetColumn::Binary(_) => BYTE_ARRAY,
+        }
+    }
+
+    fn converted_type(&self) -> Option<i32> {
+        match self {
+            ParquetColumn::Text(_) => Some(CONVERTED_UTF8),
+            ParquetColumn::Date(_) => Some(CONVERTED_DATE),
+            ParquetColumn::Timestamp(_) => Some(CONVERTED_TIMESTAMP_MILLIS),
+            _ => None,
+        }
+    }
+
+    /// Returns the validity of each of a range of values, along with the PLAIN encoding of
+    /// the valid values.
+    fn encode(&self, start: usize, end: usize) -> (Vec<bool>, Vec<u8>) {
+        let mut valid = Vec::with_capacity(end - start);
+        let mut bytes = vec![];
+        match self {
+            ParquetColumn::Boolean(v) => {
+                let mut n = 0usize;
+                for b in &v[start..end] {
+                    valid.push(b.is_some());
+                    if let Some(b) = b {
+                        if n % 8 == 0 {
+                            bytes.push(0u8);
+                        }
+                        if *b {
+                            let last = bytes.len() - 1;
+                            bytes[last] |= 1 << (n % 8);
+                        }
+                        n += 1;
+                    }
+                }
+            }
+            ParquetColumn::Int32(v) | ParquetColumn::Date(v) => {
+                for x in &v[start..end] {
+                    valid.push(x.is_some());
+                    if let Some(x) = x {
+                        bytes.extend_from_slice(&x.to_le_bytes());
+                    }
+                }
+            }
+            ParquetColumn::Int64(v) | ParquetColumn::Timestamp(v) => {
+                for x in &v[start..end] {
+                    valid.push(x.is_some());
+                    if let Some(x) = x {
+                        bytes.extend_from_slice(&x.to_le_bytes());
+                    }
+                }
+            }
+            ParquetColumn::Double(v) => {
+                for x in &v[start..end] {
+                    valid.push(x.is_some());
+                    if let Some(x) = x {
+                        bytes.extend_from_slice(&x.to_bits().to_le_bytes());
+                    }
+                }
+            }
+            ParquetColumn::Text(v) => {
+                for x in &v[start..end] {
+                    valid.push(x.is_some());
+                    if let Some(x) = x {
+                        bytes.extend_from_slice(&(x.len() as u32).to_le_bytes());
+                        bytes.extend_from_slice(x.as_bytes());
+                    }
+                }
+            }
+            ParquetColumn::Binary(v) => {
+                for x in &v[start..end] {
+                    valid.push(x.is_some());
+                    if let Some(x) = x {
+                        bytes.extend_from_slice(&(x.len() as u32).to_le_bytes());
+                        bytes.extend_from_slice(x);
+                    }
+                }
+            }
+        }
+        (valid, bytes)
+    }
+}
+
+/// A table of named, equal-length columns and key-value metadata that can be written to a
+/// Parquet file.
+#[derive(Clone, Debug, Default)]
+pub struct ParquetTable {
+    columns: Vec<(String, ParquetColumn)>,
+    metadata: Vec<(String, String)>,
+}
+
+impl ParquetTable {
+    pub fn new() -> ParquetTable {
+        ParquetTable::default()
+    }
+
+    /// Adds a column to the table. All of the columns of a table must be the same length.
+    pub fn add_column(&mut self, name: &str, column: ParquetColumn) -> Result<(), Error> {
+        if !self.columns.is_empty() && column.len() != self.num_rows() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Column {} has {} values but the table has {} rows.",
+                    name,
+                    column.len(),
+                    self.num_rows()
+                ),
+            ));
+        }
+        self.columns.push((name.to_string(), column));
+        Ok(())
+    }
+
+    /// Adds an entry to the file's key-value metadata.
+    pub fn add_metadata(&mut self, key: &str, value: &str) {
+        self.metadata.push((key.to_string(), value.to_string()));
+    }
+
+    pub fn num_rows(&self) -> usize {
+        match self.columns.first() {
+            Some((_, c)) => c.len(),
+            None => 0,
+        }
+    }
+
+    /// Writes the table to a Parquet file.
+    pub fn write(&self, file_name: &str) -> Result<(), Error> {
+        let f = File::create(file_name)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(MAGIC)?;
+        let mut offset = MAGIC.len() as i64;
+
+        let num_rows = self.num_rows();
+        let mut row_groups = vec![];
+        let mut start = 0usize;
+        while start < num_rows {
+            let end = (start + ROW_GROUP_SIZE).min(num_rows);
+            let mut chunks = vec![];
+            for (_, column) in &self.columns {
+                let (valid, values) = column.encode(start, end);
+                let mut page = encode_definition_levels(&valid);
+                page.extend_from_slice(&values);
+                let mut encoder = GzEncoder::new(vec![], Compression::default());
+                encoder.write_all(&page)?;
+                let compressed = encoder.finish()?;
+
+                let mut header = Thrift::new();
+                header.i32(1, 0); // DATA_PAGE
+                header.i32(2, page.len() as i32);
+                header.i32(3, compressed.len() as i32);
+                header.begin_struct(5);
+                header.i32(1, (end - start) as i32);
+                header.i32(2, ENCODING_PLAIN);
+                header.i32(3, ENCODING_RLE);
+                header.i32(4, ENCODING_RLE);
+                header.end_struct();
+                let header = header.finish();
+
+                writer.write_all(&header)?;
+                writer.write_all(&compressed)?;
+                chunks.push(ColumnChunk {
+                    offset: offset,
+                    uncompressed_size: (header.len() + page.len()) as i64,
+                    compressed_size: (header.len() + compressed.len()) as i64,
+                });
+                offset += (header.len() + compressed.len()) as i64;
+            }
+            row_groups.push((end - start, chunks));
+            start = end;
+        }
+
+        let metadata = self.file_metadata(&row_groups);
+        writer.write_all(&metadata)?;
+        writer.write_all(&(metadata.len() as u32).to_le_bytes())?;
+        writer.write_all(MAGIC)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn file_metadata(&self, row_groups: &[(usize, Vec<ColumnChunk>)]) -> Vec<u8> {
+        let mut t = Thrift::new();
+        t.i32(1, 1); // version
+        t.begin_list(2, THRIFT_STRUCT, self.columns.len() + 1);
+        t.begin_element();
+        t.string(4, "schema");
+        t.i32(5, self.columns.len() as i32);
+        t.end_element();
+        for (name, column) in &self.columns {
+            t.begin_element();
+            t.i32(1, column.physical_type());
+            t.i32(3, OPTIONAL);
+            t.string(4, name);
+            if let Some(c) = column.converted_type() {
+                t.i32(6, c);
+            }
+            t.end_element();
+        }
+        t.i64(3, self.num_rows() as i64);
+        t.begin_list(4, THRIFT_STRUCT, row_groups.len());
+        for (rows, chunks) in row_groups {
+            t.begin_element();
+            t.begin_list(1, THRIFT_STRUCT, chunks.len());
+            for ((name, column), chunk) in self.columns.iter().zip(chunks.iter()) {
+                t.begin_element();
+                t.i64(2, chunk.offset);
+                t.begin_struct(3);
+                t.i32(1, column.physical_type());
+                t.begin_list(2, THRIFT_I32, 2);
+                t.list_i32(ENCODING_PLAIN);
+                t.list_i32(ENCODING_RLE);
+                t.begin_list(3, THRIFT_BINARY, 1);
+                t.list_string(name);
+                t.i32(4, CODEC_GZIP);
+                t.i64(5, *rows as i64);
+                t.i64(6, chunk.uncompressed_size);
+                t.i64(7, chunk.compressed_size);
+                t.i64(9, chunk.offset);
+                t.end_struct();
+                t.end_element();
+            }
+            t.i64(2, chunks.iter().map(|c| c.uncompressed_size).sum::<i64>());
+            t.i64(3, *rows as i64);
+            t.end_element();
+        }
+        if !self.metadata.is_empty() {
+            t.begin_list(5, THRIFT_STRUCT, self.metadata.len());
+            for (key, value) in &self.metadata {
+                t.begin_element();
+                t.string(1, key);
+                t.string(2, value);
+                t.end_element();
+            }
+        }
+        t.string(6, "WhiteboxTools");
+        t.finish()
+    }
+}
+
+struct ColumnChunk {
+    offset: i64,
+    uncompressed_size: i64,
+    compressed_size: i64,
+}
+
+/// Encodes the definition levels of a page of an optional column (one bit per value) as
+/// runs of the RLE/bit-packing hybrid encoding, preceded by their length.
+fn encode_definition_levels(valid: &[bool]) -> Vec<u8> {
+    let mut runs = vec![];
+    let mut i = 0usize;
+    while i < valid.len() {
+        let mut j = i + 1;
+        while j < valid.len() && valid[j] == valid[i] {
+            j += 1;
+        }
+        write_varint(&mut runs, ((j - i) as u64) << 1);
+        runs.push(valid[i] as u8);
+        i = j;
+    }
+    let mut bytes = (runs.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&runs);
+    bytes
+}
+
+// Thrift compact protocol type codes
+const THRIFT_I32: u8 = 5;
+const THRIFT_I64: u8 = 6;
+const THRIFT_BINARY: u8 = 8;
+const THRIFT_LIST: u8 = 9;
+const THRIFT_STRUCT: u8 = 12;
+
+/// A minimal writer for the Thrift compact protocol, sufficient for Parquet metadata.
+struct Thrift {
+    buf: Vec<u8>,
+    // the id of the last field written in each of the open structs
+    last_field: Vec<i16>,
+}
+
+impl Thrift {
+    fn new() -> Thrift {
+        Thrift {
+            buf: vec![],
+            last_field: vec![0],
+        }
+    }
+
+    fn field_header(&mut self, id: i16, field_type: u8) {
+        let last = self.last_field.len() - 1;
+        let delta = id - self.last_field[last];
+        if delta > 0 && delta <= 15 {
+            self.buf.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.buf.push(field_type);
+            write_varint(&mut self.buf, zigzag(id as i64));
+        }
+        self.last_field[last] = id;
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field_header(id, THRIFT_I32);
+        write_varint(&mut self.buf, zigzag(value as i64));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field_header(id, THRIFT_I64);
+        write_varint(&mut self.buf, zigzag(value));
+    }
+
+    fn string(&mut self, id: i16, value: &str) {
+        self.field_header(id, THRIFT_BINARY);
+        self.list_string(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field_header(id, THRIFT_STRUCT);
+        self.last_field.push(0);
+    }
+
+    fn end_struct(&mut self) {
+        self.buf.push(0);
+        self.last_field.pop();
+    }
+
+    fn begin_list(&mut self, id: i16, element_type: u8, size: usize) {
+        self.field_header(id, THRIFT_LIST);
+        if size < 15 {
+            self.buf.push(((size as u8) << 4) | element_type);
+        } else {
+            self.buf.push(0xF0 | element_type);
+            write_varint(&mut self.buf, size as u64);
+        }
+    }
+
+    /// Begins a struct that is an element of a list.
+    fn begin_element(&mut self) {
+        self.last_field.push(0);
+    }
+
+    fn end_element(&mut self) {
+        self.end_struct();
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        write_varint(&mut self.buf, zigzag(value as i64));
+    }
+
+    fn list_string(&mut self, value: &str) {
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value.as_bytes());
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.buf.push(0);
+        self.buf
+    }
+}
+
+fn zigzag(v: i64) -> u64 {
+    ((v << 1) ^ (v >> 63)) as u64
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        buf.push((v as u8 & 0x7F) | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+/// Returns the number of days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+pub fn days_since_epoch(year: i32, month: u32, day: u32) -> i32 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * ((month as i32 + 9) % 12) + 2) / 5 + day as i32 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Returns the 'geo' file metadata of GeoParquet 1.0 for a single WKB geometry column.
+/// A CRS of None indicates that the CRS is unknown.
+pub fn geoparquet_metadata(
+    column: &str,
+    geometry_types: &[String],
+    bbox: [f64; 4],
+    crs: Option<&CoordinateReferenceSystem>,
+) -> String {
+    let mut meta = json!({
+        "encoding": "WKB",
+        "geometry_types": geometry_types,
+        "crs": match crs {
+            Some(c) => c.to_projjson(),
+            None => Value::Null,
+        },
+    });
+    if bbox.iter().all(|v| v.is_finite()) {
+        meta["bbox"] = json!(bbox);
+    }
+    let mut columns = serde_json::Map::new();
+    columns.insert(column.to_string(), meta);
+    json!({
+        "version": "1.0.0",
+        "primary_column": column,
+        "columns": Value::Object(columns),
+    })
+    .to_string()
+}
+
+/// Returns the ISO WKB encoding of a point.
+pub fn point_to_wkb(x: f64, y: f64, z: Option<f64>) -> Vec<u8> {
+    let mut wkb = Vec::with_capacity(29);
+    wkb.push(1u8); // little endian
+    match z {
+        Some(z) => {
+            wkb.extend_from_slice(&1001u32.to_le_bytes());
+            for v in &[x, y, z] {
+                wkb.extend_from_slice(&v.to_bits().to_le_bytes());
+            }
+        }
+        None => {
+            wkb.extend_from_slice(&1u32.to_le_bytes());
+            for v in &[x, y] {
+                wkb.extend_from_slice(&v.to_bits().to_le_bytes());
+            }
+        }
+    }
+    wkb
+}
+
+/// Returns the ISO WKB encoding of a shapefile geometry and the name of its GeoParquet
+/// geometry type, or None for null shapes. Polygon holes are grouped with the preceding
+/// hull part, as in the GeoJSON writer.
+pub fn geometry_to_wkb(record: &ShapefileGeometry) -> Option<(Vec<u8>, String)> {
+    let has_z = record.has_z_data() && record.z_array.len() >= record.points.len();
+    let dims = if has_z { 1000u32 } else { 0u32 };
+    let num_parts = record.num_parts as usize;
+    let part_range = |part: usize| -> (usize, usize) {
+        let start = record.parts[part] as usize;
+        let end = if part < num_parts - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.points.len()
+        };
+        (start, end)
+    };
+    let header = |wkb: &mut Vec<u8>, geometry_type: u32| {
+        wkb.push(1u8);
+        wkb.extend_from_slice(&(geometry_type + dims).to_le_bytes());
+    };
+    let coordinates = |wkb: &mut Vec<u8>, start: usize, end: usize| {
+        wkb.extend_from_slice(&((end - start) as u32).to_le_bytes());
+        for i in start..end {
+            wkb.extend_from_slice(&record.points[i].x.to_bits().to_le_bytes());
+            wkb.extend_from_slice(&record.points[i].y.to_bits().to_le_bytes());
+            if has_z {
+                wkb.extend_from_slice(&record.z_array[i].to_bits().to_le_bytes());
+            }
+        }
+    };
+    let suffix = if has_z { " Z" } else { "" };
+    let mut wkb = vec![];
+    let name = match record.shape_type.base_shape_type() {
+        ShapeType::Point => {
+            if record.points.is_empty() {
+                return None;
+            }
+            let z = if has_z { Some(record.z_array[0]) } else { None };
+            wkb = point_to_wkb(record.points[0].x, record.points[0].y, z);
+            "Point"
+        }
+        ShapeType::MultiPoint => {
+            header(&mut wkb, 4);
+            wkb.extend_from_slice(&(record.points.len() as u32).to_le_bytes());
+            for i in 0..record.points.len() {
+                let z = if has_z { Some(record.z_array[i]) } else { None };
+                wkb.extend_from_slice(&point_to_wkb(record.points[i].x, record.points[i].y, z));
+            }
+            "MultiPoint"
+        }
+        ShapeType::PolyLine => {
+            if num_parts == 0 {
+                return None;
+            }
+            if num_parts == 1 {
+                header(&mut wkb, 2);
+                coordinates(&mut wkb, 0, record.points.len());
+                "LineString"
+            } else {
+                header(&mut wkb, 5);
+                wkb.extend_from_slice(&(num_parts as u32).to_le_bytes());
+                for part in 0..num_parts {
+                    let (start, end) = part_range(part);
+                    header(&mut wkb, 2);
+                    coordinates(&mut wkb, start, end);
+                }
+                "MultiLineString"
+            }
+        }
+        ShapeType::Polygon => {
+            if num_parts == 0 {
+                return None;
+            }
+            let mut polygons: Vec<Vec<(usize, usize)>> = vec![];
+            for part in 0..num_parts {
+                if part > 0 && record.is_hole(part as i32) && !polygons.is_empty() {
+                    let last = polygons.len() - 1;
+                    polygons[last].push(part_range(part));
+                } else {
+                    polygons.push(vec![part_range(part)]);
+                }
+            }
+            let write_polygon = |wkb: &mut Vec<u8>, rings: &[(usize, usize)]| {
+                header(wkb, 3);
+                wkb.extend_from_slice(&(rings.len() as u32).to_le_bytes());
+                for &(start, end) in rings {
+                    coordinates(wkb, start, end);
+                }
+            };
+            if polygons.len() == 1 {
+                write_polygon(&mut wkb, &polygons[0]);
+                "Polygon"
+            } else {
+                header(&mut wkb, 6);
+                wkb.extend_from_slice(&(polygons.len() as u32).to_le_bytes());
+                for rings in &polygons {
+                    write_polygon(&mut wkb, rings);
+                }
+                "MultiPolygon"
+            }
+        }
+        _ => return None,
+    };
+    Some((wkb, format!("{}{}", name, suffix)))
+}
+
+/// Writes the attributes of a vector, and optionally its geometries, to a Parquet file.
+/// With geometries, the file is a GeoParquet file.
+pub fn write_parquet(
+    vector: &Shapefile,
+    file_name: &str,
+    include_geometry: bool,
+) -> Result<(), Error> {
+    let num_records = vector.records.len();
+    let mut table = ParquetTable::new();
+    let fields = vector.attributes.get_fields();
+    let records: Vec<Vec<FieldData>> = (0..num_records)
+        .map(|i| {
+            if i < vector.attributes.header.num_records as usize {
+                vector.attributes.get_record(i)
+            } else {
+                vec![]
+            }
+        })
+        .collect();
+    for (f, field) in fields.iter().enumerate() {
+        let values = records.iter().map(|r| r.get(f).unwrap_or(&FieldData::Null));
+        let column = match field.field_type {
+            'N' if field.decimal_count == 0 => ParquetColumn::Int64(
+                values
+                    .map(|v| match v {
+                        FieldData::Int(i) => Some(*i as i64),
+                        FieldData::Int64(i) => Some(*i),
+                        FieldData::Real(r) if r.is_finite() => Some(r.round() as i64),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            'N' | 'F' => ParquetColumn::Double(
+                values
+                    .map(|v| match v {
+                        FieldData::Int(i) => Some(*i as f64),
+                        FieldData::Int64(i) => Some(*i as f64),
+                        FieldData::Real(r) => Some(*r),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            'D' => ParquetColumn::Date(
+                values
+                    .map(|v| match v {
+                        FieldData::Date(d) => Some(days_since_epoch(
+                            d.year as i32,
+                            d.month as u32,
+                            d.day as u32,
+                        )),
+                        FieldData::DateTime(d) => Some(days_since_epoch(
+                            d.year as i32,
+                            d.month as u32,
+                            d.day as u32,
+                        )),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            'T' => ParquetColumn::Timestamp(
+                values
+                    .map(|v| match v {
+                        FieldData::DateTime(d) => Some(
+                            days_since_epoch(d.year as i32, d.month as u32, d.day as u32) as i64
+                                * 86_400_000
+                                + d.hour as i64 * 3_600_000
+                                + d.minute as i64 * 60_000
+                                + d.second as i64 * 1000
+                                + d.millisecond as i64,
+                        ),
+                        FieldData::Date(d) => Some(
+                            days_since_epoch(d.year as i32, d.month as u32, d.day as u32) as i64
+                                * 86_400_000,
+                        ),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            'L' => ParquetColumn::Boolean(
+                values
+                    .map(|v| match v {
+                        FieldData::Bool(b) => Some(*b),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => ParquetColumn::Text(
+                values
+                    .map(|v| match v {
+                        FieldData::Text(s) => Some(s.trim().to_string()),
+                        FieldData::Int(i) => Some(i.to_string()),
+                        FieldData::Int64(i) => Some(i.to_string()),
+                        FieldData::Real(r) => Some(r.to_string()),
+                        FieldData::Date(d) => Some(format!("{}", d)),
+                        FieldData::DateTime(d) => Some(format!("{}", d)),
+                        FieldData::Bool(b) => Some(b.to_string()),
+                        FieldData::Null => None,
+                    })
+                    .collect(),
+            ),
+        };
+        table.add_column(&field.name, column)?;
+    }
+
+    if include_geometry {
+        let mut geometry_types: Vec<String> = vec![];
+        let mut geometries = Vec::with_capacity(num_records);
+        for record in &vector.records {
+            geometries.push(match geometry_to_wkb(record) {
+                Some((wkb, name)) => {
+                    if !geometry_types.contains(&name) {
+                        geometry_types.push(name);
+                    }
+                    Some(wkb)
+                }
+                None => None,
+            });
+        }
+        let column = if fields.iter().any(|f| f.name == "geometry") {
+            "wkb_geometry"
+        } else {
+            "geometry"
+        };
+        table.add_column(column, ParquetColumn::Binary(geometries))?;
+        let crs = CoordinateReferenceSystem::from_wkt(&vector.projection).ok();
+        let bbox = [
+            vector.header.x_min,
+            vector.header.y_min,
+            vector.header.x_max,
+            vector.header.y_max,
+        ];
+        table.add_metadata(
+            "geo",
+            &geoparquet_metadata(column, &geometry_types, bbox, crs.as_ref()),
+        );
+    }
+
+    table.write(file_name)
+}
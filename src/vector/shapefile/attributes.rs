@@ -2,11 +2,15 @@
 This file is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 10/04/2018
-Last Modified: 12/04/2018
+Last Modified: 15/10/2026
 License: MIT
 
 NOTE: Structures and functions for handling the Shapefile attribute table info
-contained with the associated .dbf file.
+contained with the associated .dbf file. The in-memory table is not bound by the
+limits of the dBase format; field names may be of any length, text values are
+UTF-8 strings of arbitrary length, and the table supports 64-bit integers,
+date-times, and nulls of any type. Where a table exceeds the capabilities of the
+dBase format, it is downgraded when the .dbf file is written, with a warning.
 */
 
 use std::collections::HashMap;
@@ -56,12 +60,99 @@ impl fmt::Display for DateData {
     }
 }
 
+/// A date and time of day, without a time zone. Date-times are stored in
+/// memory only; .dbf files hold them as ISO 8601 text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DateTimeData {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub millisecond: u16,
+}
+
+impl DateTimeData {
+    /// Parses an ISO 8601 date-time, e.g. '2026-10-15T13:45:30.250', or a date.
+    /// A space may separate the date and time, and a trailing 'Z' is ignored.
+    pub fn parse(s: &str) -> Option<DateTimeData> {
+        let s = s.trim().trim_end_matches('Z');
+        let (date, time) = match s.find(|c: char| c == 'T' || c == ' ') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+        let d: Vec<&str> = date.split('-').collect();
+        if d.len() != 3 {
+            return None;
+        }
+        let mut dt = DateTimeData {
+            year: d[0].parse::<u16>().ok()?,
+            month: d[1].parse::<u8>().ok()?,
+            day: d[2].parse::<u8>().ok()?,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            millisecond: 0,
+        };
+        if !time.is_empty() {
+            let t: Vec<&str> = time.split(':').collect();
+            if t.len() < 2 || t.len() > 3 {
+                return None;
+            }
+            dt.hour = t[0].parse::<u8>().ok()?;
+            dt.minute = t[1].parse::<u8>().ok()?;
+            if t.len() == 3 {
+                let sec = t[2].parse::<f64>().ok()?;
+                dt.second = sec.trunc() as u8;
+                dt.millisecond = ((sec.fract() * 1000f64).round() as u16).min(999);
+            }
+        }
+        if dt.month < 1
+            || dt.month > 12
+            || dt.day < 1
+            || dt.day > 31
+            || dt.hour > 23
+            || dt.minute > 59
+            || dt.second > 60
+        {
+            return None;
+        }
+        Some(dt)
+    }
+
+    /// Returns the date part of the date-time.
+    pub fn date(&self) -> DateData {
+        DateData {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+}
+
+impl fmt::Display for DateTimeData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.millisecond > 0 {
+            write!(f, ".{:03}", self.millisecond)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldData {
     Int(i32),
+    Int64(i64),
     Real(f64),
     Text(String),
     Date(DateData),
+    DateTime(DateTimeData),
     Bool(bool),
     Null,
 }
@@ -75,9 +166,11 @@ impl fmt::Display for FieldData {
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldDataType {
     Int,
+    Int64,
     Real,
     Text,
     Date,
+    DateTime,
     Bool,
 }
 
@@ -85,9 +178,11 @@ impl FieldDataType {
     pub fn to_char(&self) -> char {
         let c = match *self {
             FieldDataType::Int => 'N',
+            FieldDataType::Int64 => 'N',
             FieldDataType::Real => 'F',
             FieldDataType::Text => 'C',
             FieldDataType::Date => 'D',
+            FieldDataType::DateTime => 'T',
             FieldDataType::Bool => 'L',
         };
         c
@@ -2,7 +2,7 @@
 This code is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/06/2017
-Last Modified: 17/10/2018
+Last Modified: 15/10/2026
 License: MIT
 
 Notes: The logic behind working with the ESRI Shapefile format.
@@ -13,16 +13,17 @@ pub mod geometry;
 
 use self::attributes::*;
 use self::geometry::*;
+use crate::dataset::{get_dataset, is_in_memory, store_dataset, Dataset};
 use crate::structures::Point2D;
+use crate::utils::vfs::{create_file, read_file};
 use crate::utils::{ByteOrderReader, Endianness};
+use crate::vector::spatialite::{is_spatialite_file, read_spatialite, write_spatialite};
 use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
 use chrono::prelude::*;
 use std::f64;
 use std::fmt;
-use std::fs;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind};
+use std::io::{Cursor, Error, ErrorKind};
 use std::path::Path;
 use std::str;
 
@@ -110,6 +111,22 @@ pub struct Shapefile {
 
 impl Shapefile {
     pub fn read<'a>(file_name: &'a str) -> Result<Shapefile, Error> {
+        if is_in_memory(file_name) {
+            return match get_dataset(file_name) {
+                Some(Dataset::Vector(mut sf)) => {
+                    sf.file_name = file_name.to_string();
+                    sf.file_mode = "r".to_string();
+                    Ok(sf)
+                }
+                _ => Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("No in-memory vector named {} was found.", file_name),
+                )),
+            };
+        }
+        if is_spatialite_file(file_name) {
+            return read_spatialite(file_name);
+        }
         let mut sf = Shapefile {
             file_name: file_name.to_string(),
             file_mode: "r".to_string(),
@@ -235,13 +252,9 @@ impl Shapefile {
         ///////////////////////////////
 
         // read the header
-        let mut f = File::open(self.file_name.clone()).unwrap(); //?;
-        let metadata = fs::metadata(self.file_name.clone()).unwrap(); //?;
-        let file_size: usize = metadata.len() as usize;
-        let mut buffer = vec![0; file_size];
-
         // read the file's bytes into a buffer
-        f.read(&mut buffer).unwrap();
+        let buffer = read_file(&self.file_name)?;
+        let file_size = buffer.len();
 
         // Note: the shapefile format uses mixed endianness for whatever reason.
         // The ByteOrderReader was set up to have one consistent endianness. As
@@ -555,12 +568,10 @@ impl Shapefile {
             .into_os_string()
             .into_string()
             .unwrap();
-        match File::open(prj_file) {
-            Ok(f) => {
-                let f = BufReader::new(f);
-                for line in f.lines() {
-                    let line_unwrapped = line.unwrap();
-                    self.projection.push_str(&format!("{}\n", line_unwrapped));
+        match read_file(&prj_file) {
+            Ok(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    self.projection.push_str(&format!("{}\n", line));
                 }
             }
             Err(_) => println!("Warning: Projection file not located."),
@@ -576,13 +587,8 @@ impl Shapefile {
             .into_os_string()
             .into_string()
             .unwrap();
-        let mut f = File::open(dbf_file.clone()).unwrap();
-        let metadata = fs::metadata(dbf_file.clone()).unwrap();
-        let file_size: usize = metadata.len() as usize;
-        let mut buffer = vec![0; file_size];
-
         // read the file's bytes into a buffer
-        f.read(&mut buffer).unwrap();
+        let buffer = read_file(&dbf_file)?;
         let mut bor =
             ByteOrderReader::<Cursor<Vec<u8>>>::new(Cursor::new(buffer), Endianness::LittleEndian);
 
@@ -674,10 +680,22 @@ impl Shapefile {
                 } else {
                     match self.attributes.fields[j as usize].field_type {
                         'N' | 'F' | 'I' | 'O' => {
+                            // values that cannot be parsed are treated as nulls
                             if self.attributes.fields[j as usize].decimal_count == 0 {
-                                r.push(FieldData::Int(str_rep.parse::<i32>().unwrap_or(0)));
+                                if let Ok(v) = str_rep.parse::<i32>() {
+                                    r.push(FieldData::Int(v));
+                                } else if let Ok(v) = str_rep.parse::<i64>() {
+                                    r.push(FieldData::Int64(v));
+                                } else if let Ok(v) = str_rep.parse::<f64>() {
+                                    r.push(FieldData::Real(v));
+                                } else {
+                                    r.push(FieldData::Null);
+                                }
                             } else {
-                                r.push(FieldData::Real(str_rep.parse::<f64>().unwrap_or(0f64)));
+                                match str_rep.parse::<f64>() {
+                                    Ok(v) => r.push(FieldData::Real(v)),
+                                    Err(_) => r.push(FieldData::Null),
+                                }
                             }
                         }
                         'D' => {
@@ -727,13 +745,22 @@ impl Shapefile {
             ));
         }
 
+        if is_in_memory(&self.file_name) {
+            self.calculate_extent();
+            store_dataset(&self.file_name, Dataset::Vector(self.clone()));
+            return Ok(());
+        }
+
+        if is_spatialite_file(&self.file_name) {
+            return write_spatialite(self);
+        }
+
         /////////////////////////////////////////
         // Write the geometry data (.shp file) //
         /////////////////////////////////////////
 
         // write the header
-        let f = File::create(&self.file_name)?;
-        let mut writer = BufWriter::new(f);
+        let mut writer = create_file(&self.file_name)?;
 
         // magic number
         writer.write_i32::<BigEndian>(9994i32)?;
@@ -1056,8 +1083,7 @@ impl Shapefile {
             .into_os_string()
             .into_string()
             .unwrap();
-        let f = File::create(&index_file)?;
-        let mut writer = BufWriter::new(f);
+        let mut writer = create_file(&index_file)?;
 
         // magic number
         writer.write_i32::<BigEndian>(9994i32)?;
@@ -1106,8 +1132,7 @@ impl Shapefile {
                 .into_os_string()
                 .into_string()
                 .unwrap();
-            let f = File::create(&prj_file)?;
-            let mut writer = BufWriter::new(f);
+            let mut writer = create_file(&prj_file)?;
             writer.write_all(self.projection.as_bytes())?;
         }
 
@@ -1115,34 +1140,105 @@ impl Shapefile {
         // Write the attributes file //
         ///////////////////////////////
 
+        // The in-memory table may exceed the limits of the dBase format, in which
+        // case the fields are downgraded and the user is warned of any loss.
+        let num_fields = self.attributes.header.num_fields as usize;
+        let num_records = self.attributes.header.num_records as usize;
+        let mut dbf_fields = vec![];
+        let mut dbf_names: Vec<String> = vec![];
+        for field in &self.attributes.fields {
+            let mut name = truncate_utf8(&field.name, 10);
+            let mut n = 1;
+            while dbf_names.contains(&name.to_uppercase()) {
+                let suffix = format!("_{}", n);
+                name = format!(
+                    "{}{}",
+                    truncate_utf8(&field.name, 10 - suffix.len()),
+                    suffix
+                );
+                n += 1;
+            }
+            if name != field.name {
+                println!(
+                    "Warning: The field name '{}' cannot be stored in a .dbf file; it is written as '{}'.",
+                    field.name, name
+                );
+            }
+            dbf_names.push(name.to_uppercase());
+            let mut dbf_field = field.clone();
+            dbf_field.name = name;
+            if field.field_type == 'T' {
+                println!(
+                    "Warning: .dbf files cannot store date-times; the field '{}' is written as ISO 8601 text.",
+                    field.name
+                );
+                dbf_field.field_type = 'C';
+                dbf_field.decimal_count = 0;
+            }
+            dbf_fields.push(dbf_field);
+        }
+
+        // Format the values, widening the fields where needed to hold them.
+        let mut values: Vec<Vec<String>> = Vec::with_capacity(num_records);
+        let mut num_truncated = vec![0usize; num_fields];
+        for i in 0..num_records {
+            let rec = self.attributes.get_record(i);
+            let mut rec_values = Vec::with_capacity(num_fields);
+            for j in 0..num_fields {
+                let field = &mut dbf_fields[j];
+                let mut v = match &rec[j] {
+                    FieldData::Null => String::new(),
+                    FieldData::Int(v) => v.to_string(),
+                    FieldData::Int64(v) => v.to_string(),
+                    FieldData::Real(v) => format!("{:.*}", field.decimal_count as usize, v),
+                    FieldData::Bool(v) => if *v { "T" } else { "F" }.to_string(),
+                    FieldData::Date(v) => format!("{}", v),
+                    FieldData::DateTime(v) => format!("{}", v),
+                    FieldData::Text(v) => v.clone(),
+                };
+                if v.len() > 254 {
+                    v = truncate_utf8(&v, 254);
+                    num_truncated[j] += 1;
+                }
+                if v.len() > field.field_length as usize {
+                    field.field_length = v.len() as u8;
+                }
+                rec_values.push(v);
+            }
+            values.push(rec_values);
+        }
+        for j in 0..num_fields {
+            if num_truncated[j] > 0 {
+                println!(
+                    "Warning: {} values of the field '{}' exceed the .dbf limit of 254 bytes and were truncated.",
+                    num_truncated[j], self.attributes.fields[j].name
+                );
+            }
+        }
+
         let dbf_file = Path::new(&self.file_name)
             .with_extension("dbf")
             .into_os_string()
             .into_string()
             .unwrap();
-        let f = File::create(&dbf_file)?;
-        let mut writer = BufWriter::new(f);
+        let mut writer = create_file(&dbf_file)?;
 
         self.attributes.header.version = 3;
         writer.write_u8(3u8)?;
 
         // write the date
-        // let now = time::now();
-        // writer.write_u8(now.tm_year as u8)?;
-        // writer.write_u8(now.tm_mon as u8 + 1u8)?;
-        // writer.write_u8(now.tm_mday as u8)?;
         let now = Local::now();
         writer.write_u8((now.year() - 1900) as u8)?;
         writer.write_u8(now.month() as u8)?;
         writer.write_u8(now.day() as u8)?;
 
         writer.write_u32::<LittleEndian>(self.attributes.header.num_records)?; // number of records
-        let header_size = 32u16 + self.attributes.header.num_fields as u16 * 32u16 + 1u16;
+        let header_size = 32u16 + num_fields as u16 * 32u16 + 1u16;
         self.attributes.header.bytes_in_header = header_size;
         writer.write_u16::<LittleEndian>(header_size)?; // header size
 
         let mut bytes_in_record = 0u16;
-        for field in &self.attributes.fields {
+        for field in &dbf_fields {
             bytes_in_record += field.field_length as u16;
         }
         bytes_in_record += 1;
@@ -1155,15 +1251,10 @@ impl Shapefile {
         }
 
         // Field descriptor array
-        for field in &self.attributes.fields {
-            let mut s = field.name.clone();
-            if s.len() > 10 {
-                s = field.name[0..10].to_string();
-            }
-            for _ in s.len()..11 {
-                s.push(char::from(0));
-            }
-            writer.write_all(s.as_bytes())?;
+        for field in &dbf_fields {
+            let mut s = field.name.as_bytes().to_vec();
+            s.resize(11, 0u8);
+            writer.write_all(&s)?;
             writer.write_u8(field.field_type as u8)?;
 
             for _ in 0..4 {
@@ -1180,104 +1271,28 @@ impl Shapefile {
 
         writer.write_u8(0x0D)?; // terminator byte
 
-        // write records
-        for i in 0..self.attributes.header.num_records as usize {
+        // write records; numbers are right-aligned and all other values left-aligned
+        for i in 0..num_records {
             if !self.attributes.is_deleted[i] {
                 writer.write_u8(0x20)?;
             } else {
                 writer.write_u8(0x2A)?;
             }
-            let rec = self.attributes.get_record(i);
-            for j in 0..self.attributes.header.num_fields {
-                let fl = self.attributes.fields[j as usize].field_length as usize;
-                match &rec[j as usize] {
-                    FieldData::Null => {
-                        let spcs: String = vec![' '; fl].into_iter().collect();
-                        writer.write_all(spcs.as_bytes())?;
-                    }
-                    FieldData::Int(v) => {
-                        let b = v.to_string();
-                        if b.len() < fl {
-                            let mut spcs: String = vec![' '; fl - b.len()].into_iter().collect();
-                            spcs.push_str(&b);
-                            writer.write_all(&spcs.as_bytes())?;
-                        } else if b.len() > fl {
-                            writer.write_all(&b[b.len() - fl..b.len()].as_bytes())?;
-                        } else {
-                            writer.write_all(&b.as_bytes())?;
-                        }
-                    }
-                    FieldData::Real(v) => {
-                        let dc = self.attributes.fields[j as usize].decimal_count as usize;
-                        let s = v.to_string();
-                        let e: Vec<&str> = s.split(".").collect();
-                        let f = if e.len() == 2 { e[1].clone() } else { "" };
-                        let mut s: String;
-                        let decimals = if f.len() > dc {
-                            let (e2, _) = f.split_at(dc);
-                            e2
-                        } else if f.len() < dc {
-                            // pad with some trailing zeros
-                            s = f.clone().to_string();
-                            for _ in 0..(dc - f.len()) {
-                                s.push_str("0");
-                            }
-                            &s
-                        } else {
-                            f
-                        };
-                        s = format!("{}.{}", e[0], decimals);
-                        if s.len() < fl {
-                            for _ in 0..(fl - s.len()) {
-                                s.push_str(" ");
-                            }
-                        } else if s.len() > fl {
-                            s.truncate(fl);
-                        }
-                        writer.write_all(&s.as_bytes())?;
-                        // let s = v.to_string();
-                        // let d = v.trunc().to_string();
-                        // let mut c = if s.len() > d.len() {
-                        //     s[d.len() + 1..s.len()].to_string()
-                        // } else {
-                        //     String::new()
-                        // };
-                        // if c.len() > dc {
-                        //     c = c[0..dc].to_string();
-                        // }
-                        // let b = format!("{}.{}", d, c);
-                        // println!("{}", b);
-                        // if b.len() < fl {
-                        //     let mut spcs: String = vec![' '; fl - b.len()].into_iter().collect();
-                        //     spcs.push_str(&b);
-                        //     writer.write_all(&spcs.as_bytes())?;
-                        // } else if b.len() > fl {
-                        //     writer.write_all(&b[b.len() - fl..b.len()].as_bytes())?;
-                        // } else {
-                        //     writer.write_all(&b.as_bytes())?;
-                        // }
-                    }
-                    FieldData::Bool(v) => {
-                        if *v {
-                            writer.write_all("T".as_bytes())?;
-                        } else {
-                            writer.write_all("F".as_bytes())?;
-                        }
-                    }
-                    FieldData::Date(v) => {
-                        writer.write_all(&format!("{}", v).as_bytes())?;
-                    }
-                    FieldData::Text(v) => {
-                        if v.len() < fl {
-                            // add spaces at start
-                            let mut spcs: String = vec![' '; fl - v.len()].into_iter().collect();
-                            spcs.push_str(&v);
-                            writer.write_all(&spcs.as_bytes())?;
-                        } else if v.len() > fl {
-                            writer.write_all(&v[0..fl].as_bytes())?;
-                        } else {
-                            writer.write_all(&v.as_bytes())?;
-                        }
+            for j in 0..num_fields {
+                let fl = dbf_fields[j].field_length as usize;
+                let v = &values[i][j];
+                let padding = vec![b' '; fl - v.len()];
+                match dbf_fields[j].field_type {
+                    'N' | 'F' | 'I' | 'O' => {
+                        writer.write_all(&padding)?;
+                        writer.write_all(v.as_bytes())?;
+                    }
+                    'L' if v.is_empty() => {
+                        writer.write_all(&vec![b'?'; fl])?;
+                    }
+                    _ => {
+                        writer.write_all(v.as_bytes())?;
+                        writer.write_all(&padding)?;
                     }
                 }
             }
@@ -1285,6 +1300,15 @@ impl Shapefile {
 
         writer.write_u8(0x1A)?; // file terminator byte
 
+        // the code page file identifies the text encoding of the .dbf file
+        let cpg_file = Path::new(&self.file_name)
+            .with_extension("cpg")
+            .into_os_string()
+            .into_string()
+            .unwrap();
+        let mut writer = create_file(&cpg_file)?;
+        writer.write_all("UTF-8".as_bytes())?;
+
         Ok(())
     }
 
@@ -1491,3 +1515,15 @@ impl Shapefile {
         }
     }
 }
+
+/// Truncates a string to at most a number of bytes, without splitting a character.
+fn truncate_utf8(s: &str, max_bytes: usize) -> String {
+    if s.len() <= max_bytes {
+        return s.to_string();
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s[..end].to_string()
+}
@@ -0,0 +1,1152 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+Notes: Reading and writing of vector layers stored in SpatiaLite (SQLite) databases, such
+as those used by QGIS projects. A layer is identified by appending its table name to the
+database file name, and an optional SQL filter is applied by the database at read time:
+
+    roads.sqlite|layername=highways|where=lanes >= 4 AND surface = 'paved'
+
+Without a layer name, the only layer of the database is read, or, when writing, a layer named
+after the database file is created. Written layers replace any existing layer of the same name.
+Geometries use the SpatiaLite BLOB format; lines and polygons are stored as MULTILINESTRING
+and MULTIPOLYGON layers. SQLite support is only compiled when WhiteboxTools is built with the
+`spatialite` feature (`cargo build --release --features spatialite`); otherwise, the functions
+in this module return an error. The spatial metadata tables follow the SpatiaLite 4 layout and
+are created if needed, without requiring the SpatiaLite extension itself.
+*/
+
+// the geometry codec is only used by the SQLite backend
+#![cfg_attr(not(feature = "spatialite"), allow(dead_code))]
+
+use crate::structures::Point2D;
+use crate::vector::shapefile::geometry::{ShapeType, ShapefileGeometry};
+use crate::vector::shapefile::Shapefile;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+// SpatiaLite BLOB markers
+const BLOB_START: u8 = 0x00;
+const BLOB_MBR_END: u8 = 0x7C;
+const BLOB_ENTITY: u8 = 0x69;
+const BLOB_END: u8 = 0xFE;
+
+/// Returns true if the file name refers to a SpatiaLite database (.sqlite, .sqlite3,
+/// .spatialite, or .db), with or without a layer specification.
+pub fn is_spatialite_file(file_name: &str) -> bool {
+    let f = parse_spatialite_path(file_name).database.to_lowercase();
+    f.ends_with(".sqlite")
+        || f.ends_with(".sqlite3")
+        || f.ends_with(".spatialite")
+        || f.ends_with(".db")
+}
+
+/// The parts of a SpatiaLite layer specification, e.g. 'data.sqlite|layername=roads|where=...'.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpatialiteSource {
+    pub database: String,
+    pub layer: Option<String>,
+    pub filter: Option<String>,
+}
+
+/// Splits a SpatiaLite layer specification into the database file, layer name, and filter.
+pub fn parse_spatialite_path(file_name: &str) -> SpatialiteSource {
+    let mut parts = file_name.split('|');
+    let mut source = SpatialiteSource {
+        database: parts.next().unwrap_or("").trim().to_string(),
+        ..Default::default()
+    };
+    for part in parts {
+        let (key, value) = match part.find('=') {
+            Some(i) => (part[..i].trim().to_lowercase(), part[i + 1..].trim()),
+            None => continue,
+        };
+        if value.is_empty() {
+            continue;
+        }
+        match key.as_str() {
+            "layername" | "layer" => source.layer = Some(value.to_string()),
+            "where" | "subset" => source.filter = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    source
+}
+
+/// A description of a vector layer in a SpatiaLite database.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialiteLayer {
+    pub name: String,
+    pub geometry_column: String,
+    pub geometry_type: String,
+    pub srid: i32,
+    pub num_features: usize,
+    pub fields: Vec<(String, String)>,
+}
+
+/// Lists the vector layers registered in the spatial metadata of a SpatiaLite database.
+pub fn list_spatialite_layers(database: &str) -> Result<Vec<SpatialiteLayer>, Error> {
+    sqlite::list_layers(&parse_spatialite_path(database).database)
+}
+
+/// Reads a layer of a SpatiaLite database. The records are filtered by the 'where' clause
+/// of the layer specification, if any. The extent of the returned vector is calculated.
+pub fn read_spatialite(file_name: &str) -> Result<Shapefile, Error> {
+    let mut sf = sqlite::read(&parse_spatialite_path(file_name))?;
+    sf.file_name = file_name.to_string();
+    sf.file_mode = "r".to_string();
+    sf.num_records = sf.records.len();
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for record in &sf.records {
+        for p in &record.points {
+            x_min = x_min.min(p.x);
+            y_min = y_min.min(p.y);
+            x_max = x_max.max(p.x);
+            y_max = y_max.max(p.y);
+        }
+    }
+    if x_min.is_finite() {
+        sf.header.x_min = x_min;
+        sf.header.y_min = y_min;
+        sf.header.x_max = x_max;
+        sf.header.y_max = y_max;
+    }
+    Ok(sf)
+}
+
+/// Writes a vector to a layer of a SpatiaLite database, replacing any existing layer with
+/// the same name. The database is created if it does not exist.
+pub fn write_spatialite(vector: &Shapefile) -> Result<(), Error> {
+    let mut source = parse_spatialite_path(&vector.file_name);
+    if source.layer.is_none() {
+        source.layer = Path::new(&source.database)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string());
+    }
+    sqlite::write(vector, &source)
+}
+
+/// Returns the SpatiaLite geometry class (the OGC type code plus 1000 for XYZ and 2000 for
+/// XYM) of a layer of a shape type.
+fn layer_class(shape_type: ShapeType) -> i32 {
+    let base = match shape_type.base_shape_type() {
+        ShapeType::Point => 1,
+        ShapeType::PolyLine => 5,
+        ShapeType::Polygon => 6,
+        ShapeType::MultiPoint => 4,
+        ShapeType::Null => 0,
+        _ => unreachable!(),
+    };
+    base + dimension_offset(shape_type)
+}
+
+fn dimension_offset(shape_type: ShapeType) -> i32 {
+    match shape_type {
+        ShapeType::PointZ | ShapeType::PolyLineZ | ShapeType::PolygonZ | ShapeType::MultiPointZ => {
+            1000
+        }
+        ShapeType::PointM | ShapeType::PolyLineM | ShapeType::PolygonM | ShapeType::MultiPointM => {
+            2000
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the shape type of a SpatiaLite geometry class. XYZM classes map to the Z types.
+fn shape_type_of_class(class: i32) -> Option<ShapeType> {
+    let (base, dims) = (class % 1000, (class % 1_000_000) / 1000);
+    let st = match (base, dims) {
+        (1, 0) => ShapeType::Point,
+        (1, 1) | (1, 3) => ShapeType::PointZ,
+        (1, 2) => ShapeType::PointM,
+        (2, 0) | (5, 0) => ShapeType::PolyLine,
+        (2, 1) | (5, 1) | (2, 3) | (5, 3) => ShapeType::PolyLineZ,
+        (2, 2) | (5, 2) => ShapeType::PolyLineM,
+        (3, 0) | (6, 0) => ShapeType::Polygon,
+        (3, 1) | (6, 1) | (3, 3) | (6, 3) => ShapeType::PolygonZ,
+        (3, 2) | (6, 2) => ShapeType::PolygonM,
+        (4, 0) => ShapeType::MultiPoint,
+        (4, 1) | (4, 3) => ShapeType::MultiPointZ,
+        (4, 2) => ShapeType::MultiPointM,
+        _ => return None,
+    };
+    Some(st)
+}
+
+/// Returns the shape type of a layer from its geometry_columns entry, which is either a
+/// class (SpatiaLite 4) or a type name and dimension model, e.g. 'POLYGON' and 'XYZ'
+/// (SpatiaLite 3).
+fn shape_type_of_layer(geometry_type: &str, coord_dimension: &str) -> Option<ShapeType> {
+    if let Ok(class) = geometry_type.trim().parse::<i32>() {
+        return shape_type_of_class(class);
+    }
+    let base = match geometry_type.trim().to_uppercase().as_str() {
+        "POINT" => 1,
+        "LINESTRING" => 2,
+        "POLYGON" => 3,
+        "MULTIPOINT" => 4,
+        "MULTILINESTRING" => 5,
+        "MULTIPOLYGON" => 6,
+        _ => return None,
+    };
+    let dims = match coord_dimension.trim().to_uppercase().as_str() {
+        "XYZ" | "3" => 1000,
+        "XYM" => 2000,
+        "XYZM" | "4" => 3000,
+        _ => 0,
+    };
+    shape_type_of_class(base + dims)
+}
+
+/// Returns the name of a SpatiaLite geometry class, e.g. 'MULTIPOLYGON Z'.
+fn class_name(class: i32) -> String {
+    let name = match class % 1000 {
+        1 => "POINT",
+        2 => "LINESTRING",
+        3 => "POLYGON",
+        4 => "MULTIPOINT",
+        5 => "MULTILINESTRING",
+        6 => "MULTIPOLYGON",
+        7 => "GEOMETRYCOLLECTION",
+        _ => "GEOMETRY",
+    };
+    match (class % 1_000_000) / 1000 {
+        1 => format!("{} Z", name),
+        2 => format!("{} M", name),
+        3 => format!("{} ZM", name),
+        _ => name.to_string(),
+    }
+}
+
+/// Encodes a shapefile geometry as a SpatiaLite geometry BLOB, or returns None for null
+/// shapes. Polygon holes are grouped with the preceding hull part.
+fn encode_geometry(
+    record: &ShapefileGeometry,
+    shape_type: ShapeType,
+    srid: i32,
+) -> Option<Vec<u8>> {
+    if record.shape_type == ShapeType::Null || record.points.is_empty() {
+        return None;
+    }
+    let offset = dimension_offset(shape_type);
+    let has_z = offset == 1000;
+    let has_m = offset == 2000;
+    let num_parts = record.num_parts.max(0) as usize;
+    let part_range = |part: usize| -> (usize, usize) {
+        let start = record.parts[part] as usize;
+        let end = if part < num_parts - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.points.len()
+        };
+        (start, end)
+    };
+    let coordinate = |blob: &mut Vec<u8>, i: usize| {
+        blob.extend_from_slice(&record.points[i].x.to_le_bytes());
+        blob.extend_from_slice(&record.points[i].y.to_le_bytes());
+        if has_z {
+            let z = record.z_array.get(i).cloned().unwrap_or(0f64);
+            blob.extend_from_slice(&z.to_le_bytes());
+        }
+        if has_m {
+            let m = record.m_array.get(i).cloned().unwrap_or(0f64);
+            blob.extend_from_slice(&m.to_le_bytes());
+        }
+    };
+    let ring = |blob: &mut Vec<u8>, (start, end): (usize, usize)| {
+        blob.extend_from_slice(&((end - start) as i32).to_le_bytes());
+        for i in start..end {
+            coordinate(blob, i);
+        }
+    };
+
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for p in &record.points {
+        x_min = x_min.min(p.x);
+        y_min = y_min.min(p.y);
+        x_max = x_max.max(p.x);
+        y_max = y_max.max(p.y);
+    }
+    let mut blob = vec![BLOB_START, 1u8];
+    blob.extend_from_slice(&srid.to_le_bytes());
+    for v in &[x_min, y_min, x_max, y_max] {
+        blob.extend_from_slice(&v.to_le_bytes());
+    }
+    blob.push(BLOB_MBR_END);
+    blob.extend_from_slice(&layer_class(shape_type).to_le_bytes());
+    match shape_type.base_shape_type() {
+        ShapeType::Point => coordinate(&mut blob, 0),
+        ShapeType::MultiPoint => {
+            blob.extend_from_slice(&(record.points.len() as i32).to_le_bytes());
+            for i in 0..record.points.len() {
+                blob.push(BLOB_ENTITY);
+                blob.extend_from_slice(&(1 + offset).to_le_bytes());
+                coordinate(&mut blob, i);
+            }
+        }
+        ShapeType::PolyLine => {
+            if num_parts == 0 {
+                return None;
+            }
+            blob.extend_from_slice(&(num_parts as i32).to_le_bytes());
+            for part in 0..num_parts {
+                blob.push(BLOB_ENTITY);
+                blob.extend_from_slice(&(2 + offset).to_le_bytes());
+                ring(&mut blob, part_range(part));
+            }
+        }
+        ShapeType::Polygon => {
+            if num_parts == 0 {
+                return None;
+            }
+            let mut polygons: Vec<Vec<(usize, usize)>> = vec![];
+            for part in 0..num_parts {
+                if part > 0 && record.is_hole(part as i32) && !polygons.is_empty() {
+                    let last = polygons.len() - 1;
+                    polygons[last].push(part_range(part));
+                } else {
+                    polygons.push(vec![part_range(part)]);
+                }
+            }
+            blob.extend_from_slice(&(polygons.len() as i32).to_le_bytes());
+            for rings in &polygons {
+                blob.push(BLOB_ENTITY);
+                blob.extend_from_slice(&(3 + offset).to_le_bytes());
+                blob.extend_from_slice(&(rings.len() as i32).to_le_bytes());
+                for &r in rings {
+                    ring(&mut blob, r);
+                }
+            }
+        }
+        _ => return None,
+    }
+    blob.push(BLOB_END);
+    Some(blob)
+}
+
+/// A vertex decoded from a geometry BLOB, as x, y, z, and m.
+type Vertex = (f64, f64, f64, f64);
+
+/// The elementary geometries decoded from a geometry BLOB.
+#[derive(Default)]
+struct DecodedGeometry {
+    points: Vec<Vertex>,
+    lines: Vec<Vec<Vertex>>,
+    polygons: Vec<Vec<Vec<Vertex>>>,
+}
+
+struct BlobReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> BlobReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.pos + n > self.bytes.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The SpatiaLite geometry is truncated.",
+            ));
+        }
+        let b = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(b)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_i32(&mut self) -> Result<i32, Error> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.take(4)?);
+        Ok(if self.little_endian {
+            i32::from_le_bytes(b)
+        } else {
+            i32::from_be_bytes(b)
+        })
+    }
+
+    fn read_f32(&mut self) -> Result<f64, Error> {
+        let mut b = [0u8; 4];
+        b.copy_from_slice(self.take(4)?);
+        Ok(if self.little_endian {
+            f32::from_le_bytes(b)
+        } else {
+            f32::from_be_bytes(b)
+        } as f64)
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(self.take(8)?);
+        Ok(if self.little_endian {
+            f64::from_le_bytes(b)
+        } else {
+            f64::from_be_bytes(b)
+        })
+    }
+
+    fn read_count(&mut self) -> Result<usize, Error> {
+        let n = self.read_i32()?;
+        if n < 0 || n as usize > self.bytes.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The SpatiaLite geometry contains an invalid count.",
+            ));
+        }
+        Ok(n as usize)
+    }
+
+    fn read_vertex(&mut self, has_z: bool, has_m: bool) -> Result<Vertex, Error> {
+        let x = self.read_f64()?;
+        let y = self.read_f64()?;
+        let z = if has_z { self.read_f64()? } else { 0f64 };
+        let m = if has_m { self.read_f64()? } else { 0f64 };
+        Ok((x, y, z, m))
+    }
+
+    /// Reads a sequence of vertices. In compressed geometries, the vertices between the
+    /// first and last are stored as single-precision offsets from the previous vertex,
+    /// except for measures.
+    fn read_vertices(
+        &mut self,
+        has_z: bool,
+        has_m: bool,
+        compressed: bool,
+    ) -> Result<Vec<Vertex>, Error> {
+        let n = self.read_count()?;
+        let mut vertices: Vec<Vertex> = Vec::with_capacity(n);
+        for i in 0..n {
+            if !compressed || i == 0 || i == n - 1 {
+                vertices.push(self.read_vertex(has_z, has_m)?);
+            } else {
+                let (px, py, pz, _) = vertices[i - 1];
+                let x = px + self.read_f32()?;
+                let y = py + self.read_f32()?;
+                let z = if has_z { pz + self.read_f32()? } else { 0f64 };
+                let m = if has_m { self.read_f64()? } else { 0f64 };
+                vertices.push((x, y, z, m));
+            }
+        }
+        Ok(vertices)
+    }
+
+    fn read_body(&mut self, class: i32, geometry: &mut DecodedGeometry) -> Result<(), Error> {
+        let compressed = class >= 1_000_000;
+        let dims = (class % 1_000_000) / 1000;
+        let (has_z, has_m) = (dims == 1 || dims == 3, dims == 2 || dims == 3);
+        match class % 1000 {
+            1 => geometry.points.push(self.read_vertex(has_z, has_m)?),
+            2 => geometry
+                .lines
+                .push(self.read_vertices(has_z, has_m, compressed)?),
+            3 => {
+                let n = self.read_count()?;
+                let mut rings = Vec::with_capacity(n);
+                for _ in 0..n {
+                    rings.push(self.read_vertices(has_z, has_m, compressed)?);
+                }
+                geometry.polygons.push(rings);
+            }
+            4 | 5 | 6 | 7 => {
+                let n = self.read_count()?;
+                for _ in 0..n {
+                    if self.read_u8()? != BLOB_ENTITY {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            "The SpatiaLite geometry is missing an entity marker.",
+                        ));
+                    }
+                    let entity_class = self.read_i32()?;
+                    self.read_body(entity_class, geometry)?;
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Unsupported SpatiaLite geometry class {}.", class),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the geometry class of a SpatiaLite geometry BLOB.
+fn blob_class(blob: &[u8]) -> Option<i32> {
+    if blob.len() < 43 || blob[0] != BLOB_START || blob[38] != BLOB_MBR_END {
+        return None;
+    }
+    let mut b = [0u8; 4];
+    b.copy_from_slice(&blob[39..43]);
+    Some(if blob[1] == 1 {
+        i32::from_le_bytes(b)
+    } else {
+        i32::from_be_bytes(b)
+    })
+}
+
+/// Twice the signed area of a ring; positive for counter-clockwise rings.
+fn signed_area(ring: &[Vertex]) -> f64 {
+    let mut area = 0f64;
+    for i in 0..ring.len() {
+        let (x1, y1, _, _) = ring[i];
+        let (x2, y2, _, _) = ring[(i + 1) % ring.len()];
+        area += x1 * y2 - x2 * y1;
+    }
+    area
+}
+
+/// Decodes a SpatiaLite geometry BLOB into a shapefile geometry of a shape type. Polygon
+/// rings are reoriented to the shapefile convention of clockwise hulls and counter-clockwise
+/// holes.
+fn decode_geometry(blob: &[u8], shape_type: ShapeType) -> Result<ShapefileGeometry, Error> {
+    let class = match blob_class(blob) {
+        Some(c) => c,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The geometry is not in the SpatiaLite BLOB format.",
+            ))
+        }
+    };
+    let mut reader = BlobReader {
+        bytes: blob,
+        pos: 43,
+        little_endian: blob[1] == 1,
+    };
+    let mut decoded = DecodedGeometry::default();
+    reader.read_body(class, &mut decoded)?;
+
+    let mut sfg = ShapefileGeometry::new(shape_type);
+    let add_part = |sfg: &mut ShapefileGeometry, vertices: &[Vertex]| {
+        let points: Vec<Point2D> = vertices.iter().map(|v| Point2D::new(v.0, v.1)).collect();
+        let z: Vec<f64> = vertices.iter().map(|v| v.2).collect();
+        let m: Vec<f64> = vertices.iter().map(|v| v.3).collect();
+        match shape_type {
+            ShapeType::PolyLineZ | ShapeType::PolygonZ => sfg.add_partz(&points, &m, &z),
+            ShapeType::PolyLineM | ShapeType::PolygonM => sfg.add_partm(&points, &m),
+            _ => sfg.add_part(&points),
+        }
+    };
+    let add_point = |sfg: &mut ShapefileGeometry, v: &Vertex| match shape_type {
+        ShapeType::PointZ | ShapeType::MultiPointZ => {
+            sfg.add_pointz(Point2D::new(v.0, v.1), v.3, v.2)
+        }
+        ShapeType::PointM | ShapeType::MultiPointM => sfg.add_pointm(Point2D::new(v.0, v.1), v.3),
+        _ => sfg.add_point(Point2D::new(v.0, v.1)),
+    };
+    match shape_type.base_shape_type() {
+        ShapeType::Point => match decoded.points.first() {
+            Some(v) => add_point(&mut sfg, v),
+            None => sfg = ShapefileGeometry::new(ShapeType::Null),
+        },
+        ShapeType::MultiPoint => {
+            for v in &decoded.points {
+                add_point(&mut sfg, v);
+            }
+        }
+        ShapeType::PolyLine => {
+            for line in &decoded.lines {
+                add_part(&mut sfg, line);
+            }
+        }
+        ShapeType::Polygon => {
+            for polygon in &decoded.polygons {
+                for (r, ring) in polygon.iter().enumerate() {
+                    let mut ring = ring.clone();
+                    let clockwise = signed_area(&ring) < 0f64;
+                    if (r == 0) != clockwise {
+                        ring.reverse();
+                    }
+                    add_part(&mut sfg, &ring);
+                }
+            }
+        }
+        _ => {}
+    }
+    if sfg.points.is_empty() {
+        sfg = ShapefileGeometry::new(ShapeType::Null);
+    }
+    Ok(sfg)
+}
+
+#[cfg(feature = "spatialite")]
+mod sqlite {
+    use super::*;
+    use crate::spatial_ref_system::{esri_wkt_from_epsg, CoordinateReferenceSystem};
+    use crate::vector::shapefile::attributes::{
+        AttributeField, DateData, DateTimeData, FieldData, FieldDataType,
+    };
+    use rusqlite::types::Value;
+    use rusqlite::{Connection, OpenFlags, OptionalExtension, NO_PARAMS};
+
+    fn to_error(e: rusqlite::Error) -> Error {
+        Error::new(ErrorKind::Other, format!("SQLite error: {}", e))
+    }
+
+    fn quote(identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace("\"", "\"\""))
+    }
+
+    fn value_to_string(value: Value) -> String {
+        match value {
+            Value::Integer(i) => i.to_string(),
+            Value::Real(r) => r.to_string(),
+            Value::Text(s) => s,
+            _ => String::new(),
+        }
+    }
+
+    /// Returns the column names of a table.
+    fn table_columns(conn: &Connection, table: &str) -> Result<Vec<(String, String, bool)>, Error> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info({})", quote(table)))
+            .map_err(to_error)?;
+        let mut rows = stmt.query(NO_PARAMS).map_err(to_error)?;
+        let mut columns = vec![];
+        while let Some(row) = rows.next().map_err(to_error)? {
+            let name: String = row.get(1).map_err(to_error)?;
+            let declared_type: Option<String> = row.get(2).map_err(to_error)?;
+            let pk: i64 = row.get(5).map_err(to_error)?;
+            columns.push((name, declared_type.unwrap_or_default(), pk > 0));
+        }
+        Ok(columns)
+    }
+
+    /// Returns the table, geometry column, geometry type, coordinate dimension, and SRID of
+    /// each entry of geometry_columns.
+    fn geometry_columns(
+        conn: &Connection,
+    ) -> Result<Vec<(String, String, String, String, i32)>, Error> {
+        let columns = table_columns(conn, "geometry_columns")?;
+        if columns.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The database does not contain SpatiaLite metadata (a geometry_columns table).",
+            ));
+        }
+        // SpatiaLite 3 uses the 'type' column; SpatiaLite 4 uses 'geometry_type'.
+        let type_column = if columns.iter().any(|c| c.0 == "geometry_type") {
+            "geometry_type"
+        } else {
+            "type"
+        };
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT f_table_name, f_geometry_column, {}, coord_dimension, srid FROM geometry_columns",
+                type_column
+            ))
+            .map_err(to_error)?;
+        let mut rows = stmt.query(NO_PARAMS).map_err(to_error)?;
+        let mut entries = vec![];
+        while let Some(row) = rows.next().map_err(to_error)? {
+            entries.push((
+                value_to_string(row.get(0).map_err(to_error)?),
+                value_to_string(row.get(1).map_err(to_error)?),
+                value_to_string(row.get(2).map_err(to_error)?),
+                value_to_string(row.get(3).map_err(to_error)?),
+                value_to_string(row.get(4).map_err(to_error)?)
+                    .parse::<i32>()
+                    .unwrap_or(0),
+            ));
+        }
+        Ok(entries)
+    }
+
+    pub fn list_layers(database: &str) -> Result<Vec<SpatialiteLayer>, Error> {
+        let conn = Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(to_error)?;
+        let mut layers = vec![];
+        for (table, column, geometry_type, coord_dimension, srid) in geometry_columns(&conn)? {
+            let num_features: i64 = conn
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM {}", quote(&table)),
+                    NO_PARAMS,
+                    |row| row.get(0),
+                )
+                .map_err(to_error)?;
+            let fields = table_columns(&conn, &table)?
+                .into_iter()
+                .filter(|c| !c.0.eq_ignore_ascii_case(&column) && !(c.2 && is_integer(&c.1)))
+                .map(|c| (c.0, c.1))
+                .collect();
+            let geometry_type = match geometry_type.parse::<i32>() {
+                Ok(class) => class_name(class),
+                Err(_) => format!("{} {}", geometry_type, coord_dimension),
+            };
+            layers.push(SpatialiteLayer {
+                name: table,
+                geometry_column: column,
+                geometry_type: geometry_type,
+                srid: srid,
+                num_features: num_features as usize,
+                fields: fields,
+            });
+        }
+        Ok(layers)
+    }
+
+    fn is_integer(declared_type: &str) -> bool {
+        declared_type.to_uppercase().contains("INT")
+    }
+
+    /// Returns the attribute field type of a declared column type.
+    fn field_type_of(declared_type: &str) -> FieldDataType {
+        let t = declared_type.to_uppercase();
+        if t.contains("BOOL") {
+            FieldDataType::Bool
+        } else if t.contains("INT") {
+            FieldDataType::Int64
+        } else if t.contains("REAL")
+            || t.contains("FLOA")
+            || t.contains("DOUB")
+            || t.contains("NUMERIC")
+            || t.contains("DECIMAL")
+        {
+            FieldDataType::Real
+        } else if t.contains("DATETIME") || t.contains("TIMESTAMP") {
+            FieldDataType::DateTime
+        } else if t == "DATE" {
+            FieldDataType::Date
+        } else {
+            FieldDataType::Text
+        }
+    }
+
+    fn to_field_data(value: Value, field_type: &FieldDataType) -> FieldData {
+        let number = match &value {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Real(r) => Some(*r),
+            Value::Text(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        };
+        match (field_type, value) {
+            (_, Value::Null) => FieldData::Null,
+            (FieldDataType::Int64, Value::Integer(i)) => {
+                if i.abs() <= i32::max_value() as i64 {
+                    FieldData::Int(i as i32)
+                } else {
+                    FieldData::Int64(i)
+                }
+            }
+            (FieldDataType::Real, _) | (FieldDataType::Int64, _) => match number {
+                Some(r) => FieldData::Real(r),
+                None => FieldData::Null,
+            },
+            (FieldDataType::Bool, _) => match number {
+                Some(r) => FieldData::Bool(r != 0f64),
+                None => FieldData::Null,
+            },
+            (FieldDataType::Date, Value::Text(s)) | (FieldDataType::DateTime, Value::Text(s)) => {
+                match DateTimeData::parse(&s) {
+                    Some(dt) if *field_type == FieldDataType::Date => FieldData::Date(dt.date()),
+                    Some(dt) => FieldData::DateTime(dt),
+                    None => FieldData::Text(s),
+                }
+            }
+            (_, Value::Blob(_)) => FieldData::Null,
+            (_, v) => FieldData::Text(value_to_string(v)),
+        }
+    }
+
+    pub fn read(source: &SpatialiteSource) -> Result<Shapefile, Error> {
+        if !Path::new(&source.database).exists() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("The database {} does not exist.", source.database),
+            ));
+        }
+        let conn = Connection::open_with_flags(&source.database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(to_error)?;
+        let layers = geometry_columns(&conn)?;
+        let entry = match &source.layer {
+            Some(name) => layers.iter().find(|l| l.0.eq_ignore_ascii_case(name)),
+            None if layers.len() == 1 => layers.first(),
+            None => {
+                let names: Vec<&str> = layers.iter().map(|l| l.0.as_str()).collect();
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The database contains several layers ({}); specify one, e.g. {}|layername={}",
+                        names.join(", "),
+                        source.database,
+                        names.first().unwrap_or(&"name")
+                    ),
+                ));
+            }
+        };
+        let (table, geometry_column, geometry_type, coord_dimension, srid) = match entry {
+            Some(e) => e.clone(),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "The layer {} was not found in {}.",
+                        source.layer.clone().unwrap_or_default(),
+                        source.database
+                    ),
+                ))
+            }
+        };
+
+        // attribute columns, excluding the geometry and the integer primary key
+        let columns: Vec<(String, FieldDataType)> = table_columns(&conn, &table)?
+            .into_iter()
+            .filter(|c| !c.0.eq_ignore_ascii_case(&geometry_column) && !(c.2 && is_integer(&c.1)))
+            .map(|c| {
+                let t = field_type_of(&c.1);
+                (c.0, t)
+            })
+            .collect();
+        let mut sql = format!("SELECT {}", quote(&geometry_column));
+        for (name, _) in &columns {
+            sql.push_str(&format!(", {}", quote(name)));
+        }
+        sql.push_str(&format!(" FROM {}", quote(&table)));
+        if let Some(filter) = &source.filter {
+            sql.push_str(&format!(" WHERE {}", filter));
+        }
+
+        let mut shape_type = shape_type_of_layer(&geometry_type, &coord_dimension);
+        let mut blobs: Vec<Option<Vec<u8>>> = vec![];
+        let mut records: Vec<Vec<FieldData>> = vec![];
+        let mut stmt = conn.prepare(&sql).map_err(to_error)?;
+        let mut rows = stmt.query(NO_PARAMS).map_err(to_error)?;
+        while let Some(row) = rows.next().map_err(to_error)? {
+            let blob = match row.get(0).map_err(to_error)? {
+                Value::Blob(b) => Some(b),
+                _ => None,
+            };
+            if shape_type.is_none() {
+                // generic GEOMETRY layers take the type of their first geometry
+                shape_type = blob
+                    .as_ref()
+                    .and_then(|b| blob_class(b))
+                    .and_then(shape_type_of_class);
+            }
+            blobs.push(blob);
+            let mut rec = Vec::with_capacity(columns.len());
+            for (i, (_, field_type)) in columns.iter().enumerate() {
+                rec.push(to_field_data(row.get(i + 1).map_err(to_error)?, field_type));
+            }
+            records.push(rec);
+        }
+        let shape_type = match shape_type {
+            Some(st) => st,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("The geometry type of the layer {} is not supported.", table),
+                ))
+            }
+        };
+
+        let mut sf = Shapefile::default();
+        sf.header.shape_type = shape_type;
+        for blob in &blobs {
+            sf.records.push(match blob {
+                Some(b) => decode_geometry(b, shape_type)?,
+                None => ShapefileGeometry::new(ShapeType::Null),
+            });
+        }
+
+        for (f, (name, field_type)) in columns.iter().enumerate() {
+            let (mut length, decimals) = match field_type {
+                FieldDataType::Real => (12usize, 6u8),
+                FieldDataType::Bool => (1usize, 0u8),
+                FieldDataType::Date => (8usize, 0u8),
+                _ => (1usize, 0u8),
+            };
+            for rec in &records {
+                length = length.max(match &rec[f] {
+                    FieldData::Int(v) => v.to_string().len(),
+                    FieldData::Int64(v) => v.to_string().len(),
+                    FieldData::DateTime(v) => v.to_string().len(),
+                    FieldData::Text(v) => v.len(),
+                    _ => 0,
+                });
+            }
+            // text read from a typed column is stored in a text field
+            let mixed = records.iter().any(|r| {
+                if let FieldData::Text(_) = r[f] {
+                    true
+                } else {
+                    false
+                }
+            });
+            let field_type = if mixed {
+                FieldDataType::Text
+            } else {
+                field_type.clone()
+            };
+            sf.attributes.add_field(&AttributeField::new(
+                name,
+                field_type,
+                length.min(254) as u8,
+                decimals,
+            ));
+        }
+        for rec in records {
+            sf.attributes.add_record(rec, false);
+        }
+
+        if srid > 0 {
+            let srtext: Option<String> = conn
+                .query_row(
+                    "SELECT srtext FROM spatial_ref_sys WHERE srid = ?",
+                    &[srid],
+                    |row| row.get(0),
+                )
+                .optional()
+                .unwrap_or(None);
+            sf.projection = match srtext {
+                Some(wkt)
+                    if wkt.to_uppercase().starts_with("PROJCS")
+                        || wkt.to_uppercase().starts_with("GEOGCS") =>
+                {
+                    wkt
+                }
+                _ if srid < 65536 => {
+                    let wkt = esri_wkt_from_epsg(srid as u16);
+                    if wkt == "Unknown EPSG Code" {
+                        String::new()
+                    } else {
+                        wkt
+                    }
+                }
+                _ => String::new(),
+            };
+        }
+        Ok(sf)
+    }
+
+    /// Returns the SQL column type and the SQL value of an attribute.
+    fn column_type(field: &AttributeField) -> &'static str {
+        match field.field_type {
+            'N' | 'F' | 'I' | 'O' if field.decimal_count == 0 => "INTEGER",
+            'N' | 'F' | 'I' | 'O' => "DOUBLE",
+            'L' => "BOOLEAN",
+            'D' => "DATE",
+            'T' => "DATETIME",
+            _ => "TEXT",
+        }
+    }
+
+    fn to_value(value: &FieldData) -> Value {
+        match value {
+            FieldData::Int(v) => Value::Integer(*v as i64),
+            FieldData::Int64(v) => Value::Integer(*v),
+            FieldData::Real(v) if v.is_finite() => Value::Real(*v),
+            FieldData::Real(_) => Value::Null,
+            FieldData::Text(v) => Value::Text(v.clone()),
+            FieldData::Bool(v) => Value::Integer(*v as i64),
+            FieldData::Date(d) => Value::Text(format_date(d)),
+            FieldData::DateTime(d) => Value::Text(d.to_string()),
+            FieldData::Null => Value::Null,
+        }
+    }
+
+    fn format_date(d: &DateData) -> String {
+        format!("{:04}-{:02}-{:02}", d.year, d.month, d.day)
+    }
+
+    pub fn write(vector: &Shapefile, source: &SpatialiteSource) -> Result<(), Error> {
+        let table = source
+            .layer
+            .clone()
+            .unwrap_or_else(|| "layer".to_string())
+            .to_lowercase();
+        let shape_type = vector.header.shape_type;
+        if shape_type == ShapeType::Null {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Vectors of the Null shape type cannot be written to a SpatiaLite database.",
+            ));
+        }
+        let crs = CoordinateReferenceSystem::from_wkt(&vector.projection).ok();
+        let srid = match &crs {
+            Some(c) if c.epsg_code > 0 => c.epsg_code as i32,
+            _ => -1,
+        };
+
+        let mut conn = Connection::open(&source.database).map_err(to_error)?;
+        let tx = conn.transaction().map_err(to_error)?;
+        tx.execute_batch(
+            "CREATE TABLE IF NOT EXISTS spatial_ref_sys (
+                srid INTEGER NOT NULL PRIMARY KEY,
+                auth_name TEXT NOT NULL,
+                auth_srid INTEGER NOT NULL,
+                ref_sys_name TEXT NOT NULL DEFAULT 'Unknown',
+                proj4text TEXT NOT NULL,
+                srtext TEXT NOT NULL DEFAULT 'Undefined');
+            CREATE TABLE IF NOT EXISTS geometry_columns (
+                f_table_name TEXT NOT NULL,
+                f_geometry_column TEXT NOT NULL,
+                geometry_type INTEGER NOT NULL,
+                coord_dimension INTEGER NOT NULL,
+                srid INTEGER NOT NULL,
+                spatial_index_enabled INTEGER NOT NULL,
+                CONSTRAINT pk_geom_cols PRIMARY KEY (f_table_name, f_geometry_column));
+            INSERT OR IGNORE INTO spatial_ref_sys VALUES
+                (-1, 'NONE', -1, 'Undefined - Cartesian', '', 'Undefined');",
+        )
+        .map_err(to_error)?;
+        if let Some(c) = &crs {
+            if srid > 0 {
+                tx.execute(
+                    "INSERT OR IGNORE INTO spatial_ref_sys VALUES (?, 'epsg', ?, ?, '', ?)",
+                    &[
+                        &srid as &dyn rusqlite::ToSql,
+                        &srid,
+                        &c.name,
+                        &vector.projection.trim(),
+                    ],
+                )
+                .map_err(to_error)?;
+            }
+        }
+
+        // replace any existing layer of the same name
+        let spatialite3 = table_columns(&tx, "geometry_columns")?
+            .iter()
+            .any(|c| c.0 == "type");
+        tx.execute(
+            "DELETE FROM geometry_columns WHERE lower(f_table_name) = ?",
+            &[&table],
+        )
+        .map_err(to_error)?;
+        tx.execute_batch(&format!("DROP TABLE IF EXISTS {};", quote(&table)))
+            .map_err(to_error)?;
+
+        let fields = vector.attributes.get_fields();
+        let taken = |name: &str| fields.iter().any(|f| f.name.eq_ignore_ascii_case(name));
+        let mut pk = "pk_uid".to_string();
+        while taken(&pk) {
+            pk.push('_');
+        }
+        let mut geometry_column = "geometry".to_string();
+        while taken(&geometry_column) {
+            geometry_column.push('_');
+        }
+        let mut sql = format!(
+            "CREATE TABLE {} ({} INTEGER PRIMARY KEY AUTOINCREMENT",
+            quote(&table),
+            quote(&pk)
+        );
+        for field in fields {
+            sql.push_str(&format!(", {} {}", quote(&field.name), column_type(field)));
+        }
+        sql.push_str(&format!(", {} BLOB)", quote(&geometry_column)));
+        tx.execute_batch(&sql).map_err(to_error)?;
+
+        let class = layer_class(shape_type);
+        let coord_dimension = match class / 1000 {
+            1 | 2 => 3,
+            _ => 2,
+        };
+        if spatialite3 {
+            let dims = match class / 1000 {
+                1 => "XYZ",
+                2 => "XYM",
+                _ => "XY",
+            };
+            tx.execute(
+                "INSERT INTO geometry_columns (f_table_name, f_geometry_column, type, coord_dimension, srid, spatial_index_enabled) VALUES (?, ?, ?, ?, ?, 0)",
+                &[
+                    &table as &dyn rusqlite::ToSql,
+                    &geometry_column,
+                    &class_name(class % 1000),
+                    &dims,
+                    &srid,
+                ],
+            )
+            .map_err(to_error)?;
+        } else {
+            tx.execute(
+                "INSERT INTO geometry_columns VALUES (?, ?, ?, ?, ?, 0)",
+                &[
+                    &table as &dyn rusqlite::ToSql,
+                    &geometry_column,
+                    &class,
+                    &coord_dimension,
+                    &srid,
+                ],
+            )
+            .map_err(to_error)?;
+        }
+
+        {
+            let mut sql = format!("INSERT INTO {} (", quote(&table));
+            for field in fields {
+                sql.push_str(&format!("{}, ", quote(&field.name)));
+            }
+            sql.push_str(&format!("{}) VALUES (", quote(&geometry_column)));
+            sql.push_str(&vec!["?"; fields.len() + 1].join(", "));
+            sql.push_str(")");
+            let mut stmt = tx.prepare(&sql).map_err(to_error)?;
+            let num_attribute_records = vector.attributes.header.num_records as usize;
+            for (i, record) in vector.records.iter().enumerate() {
+                let mut values: Vec<Value> = if i < num_attribute_records {
+                    vector
+                        .attributes
+                        .get_record(i)
+                        .iter()
+                        .map(|v| to_value(v))
+                        .collect()
+                } else {
+                    vec![]
+                };
+                values.resize(fields.len(), Value::Null);
+                values.push(match encode_geometry(record, shape_type, srid) {
+                    Some(blob) => Value::Blob(blob),
+                    None => Value::Null,
+                });
+                stmt.execute(&values).map_err(to_error)?;
+            }
+        }
+        tx.commit().map_err(to_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "spatialite"))]
+mod sqlite {
+    use super::*;
+
+    fn unsupported() -> Error {
+        Error::new(
+            ErrorKind::Other,
+            "This build of WhiteboxTools does not include SpatiaLite support; rebuild with --features spatialite.",
+        )
+    }
+
+    pub fn list_layers(_database: &str) -> Result<Vec<SpatialiteLayer>, Error> {
+        Err(unsupported())
+    }
+
+    pub fn read(_source: &SpatialiteSource) -> Result<Shapefile, Error> {
+        Err(unsupported())
+    }
+
+    pub fn write(_vector: &Shapefile, _source: &SpatialiteSource) -> Result<(), Error> {
+        Err(unsupported())
+    }
+}
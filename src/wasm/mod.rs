@@ -0,0 +1,278 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+*/
+
+/*
+The interface exported by the `wasm32` build of the library, which allows a subset
+of the raster tools to be run within a web browser. It is intended to be used
+through the JavaScript wrapper in `wasm/whitebox_tools.js`, which takes care of
+copying strings and file contents in and out of the module's linear memory.
+
+Since there is no file system in the browser, input files are first copied into
+the library's virtual file system (`wbt_write_file`), the tool is run, and its
+outputs are then copied back out (`wbt_read_file`). Only the GeoTIFF raster format
+and Shapefiles are supported by the virtual file system.
+
+Each function returns one of the status codes below. Any text or bytes that a
+function produces, including error messages, are placed in a result buffer that is
+accessed with `wbt_result_ptr` and `wbt_result_len`, and which remains valid until
+the next call into the library.
+*/
+
+use crate::tools::ToolManager;
+use crate::utils::vfs;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::mem;
+use std::slice;
+use std::str;
+
+/// Returned when the function completes successfully.
+pub const WBT_SUCCESS: i32 = 0;
+/// Returned when the arguments are invalid, e.g. non-UTF-8 or malformed JSON.
+pub const WBT_INVALID_ARGUMENTS: i32 = 1;
+/// Returned when the tool or file operation fails.
+pub const WBT_ERROR: i32 = 2;
+
+/// The tools that are available in the browser build. These tools only read and
+/// write GeoTIFF rasters and do not rely on the system clock or environment.
+pub const BROWSER_TOOLS: [&str; 5] = [
+    "Aspect",
+    "GaussianFilter",
+    "Hillshade",
+    "MeanFilter",
+    "Slope",
+];
+
+thread_local! {
+    static RESULT: RefCell<Vec<u8>> = RefCell::new(vec![]);
+}
+
+/// Allocates a buffer of `len` bytes within the module's memory, used by the host
+/// to pass strings and file contents into the library.
+#[no_mangle]
+pub extern "C" fn wbt_alloc(len: usize) -> *mut u8 {
+    let mut buffer: Vec<u8> = Vec::with_capacity(len);
+    let ptr = buffer.as_mut_ptr();
+    mem::forget(buffer);
+    ptr
+}
+
+/// Releases a buffer allocated by `wbt_alloc`.
+#[no_mangle]
+pub extern "C" fn wbt_dealloc(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        unsafe {
+            drop(Vec::from_raw_parts(ptr, 0, len));
+        }
+    }
+}
+
+/// Returns a pointer to the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_result_ptr() -> *const u8 {
+    RESULT.with(|r| r.borrow().as_ptr())
+}
+
+/// Returns the length, in bytes, of the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_result_len() -> usize {
+    RESULT.with(|r| r.borrow().len())
+}
+
+/// Places the library version in the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_version() -> i32 {
+    set_result(env!("CARGO_PKG_VERSION").as_bytes().to_vec());
+    WBT_SUCCESS
+}
+
+/// Places a JSON array describing the tools that are available in the browser,
+/// each with a `name`, `description`, and `toolbox`, in the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_list_tools() -> i32 {
+    let tm = match ToolManager::new("", &false) {
+        Ok(tm) => tm,
+        Err(e) => return error(WBT_ERROR, &e.to_string()),
+    };
+    let tools: Vec<Value> = tm
+        .get_tool_details()
+        .into_iter()
+        .filter(|(name, _, _)| is_browser_tool(name))
+        .map(|(name, description, toolbox)| {
+            json!({ "name": name, "description": description, "toolbox": toolbox })
+        })
+        .collect();
+    set_result(Value::Array(tools).to_string().into_bytes());
+    WBT_SUCCESS
+}
+
+/// Places the parameters (in json form) of a tool in the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_tool_parameters(name_ptr: *const u8, name_len: usize) -> i32 {
+    let tool_name = match read_str(name_ptr, name_len) {
+        Some(s) => s,
+        None => return error(WBT_INVALID_ARGUMENTS, "The tool name is not valid UTF-8."),
+    };
+    if !is_browser_tool(&tool_name) {
+        return error(WBT_ERROR, &unavailable_message(&tool_name));
+    }
+    let tm = match ToolManager::new("", &false) {
+        Ok(tm) => tm,
+        Err(e) => return error(WBT_ERROR, &e.to_string()),
+    };
+    match tm.get_tool_parameters(&tool_name) {
+        Ok(parameters) => {
+            set_result(parameters.into_bytes());
+            WBT_SUCCESS
+        }
+        Err(e) => error(WBT_ERROR, &e.to_string()),
+    }
+}
+
+/// Runs a tool. The arguments are passed as a JSON array of command-line style
+/// arguments, e.g. `["--dem=DEM.tif", "--output=slope.tif"]`, where the file names
+/// refer to files in the virtual file system.
+#[no_mangle]
+pub extern "C" fn wbt_run_tool(
+    name_ptr: *const u8,
+    name_len: usize,
+    args_ptr: *const u8,
+    args_len: usize,
+) -> i32 {
+    let tool_name = match read_str(name_ptr, name_len) {
+        Some(s) => s,
+        None => return error(WBT_INVALID_ARGUMENTS, "The tool name is not valid UTF-8."),
+    };
+    if !is_browser_tool(&tool_name) {
+        return error(WBT_ERROR, &unavailable_message(&tool_name));
+    }
+    let args: Vec<String> = match read_str(args_ptr, args_len)
+        .and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok())
+    {
+        Some(args) => args.iter().map(|a| a.trim().to_string()).collect(),
+        None => {
+            return error(
+                WBT_INVALID_ARGUMENTS,
+                "The tool arguments must be a JSON array of strings.",
+            )
+        }
+    };
+    let tm = match ToolManager::new("", &false) {
+        Ok(tm) => tm,
+        Err(e) => return error(WBT_ERROR, &e.to_string()),
+    };
+    match tm.run_tool(tool_name, args) {
+        Ok(()) => {
+            set_result(vec![]);
+            WBT_SUCCESS
+        }
+        Err(e) => error(WBT_ERROR, &e.to_string()),
+    }
+}
+
+/// Copies a file into the virtual file system, replacing any existing file of the
+/// same name.
+#[no_mangle]
+pub extern "C" fn wbt_write_file(
+    name_ptr: *const u8,
+    name_len: usize,
+    data_ptr: *const u8,
+    data_len: usize,
+) -> i32 {
+    let file_name = match read_str(name_ptr, name_len) {
+        Some(s) => s,
+        None => return error(WBT_INVALID_ARGUMENTS, "The file name is not valid UTF-8."),
+    };
+    let bytes = read_bytes(data_ptr, data_len).to_vec();
+    match vfs::write_file(&file_name, bytes) {
+        Ok(()) => {
+            set_result(vec![]);
+            WBT_SUCCESS
+        }
+        Err(e) => error(WBT_ERROR, &e.to_string()),
+    }
+}
+
+/// Places the contents of a file in the virtual file system in the result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_read_file(name_ptr: *const u8, name_len: usize) -> i32 {
+    let file_name = match read_str(name_ptr, name_len) {
+        Some(s) => s,
+        None => return error(WBT_INVALID_ARGUMENTS, "The file name is not valid UTF-8."),
+    };
+    match vfs::read_file(&file_name) {
+        Ok(bytes) => {
+            set_result(bytes);
+            WBT_SUCCESS
+        }
+        Err(e) => error(WBT_ERROR, &e.to_string()),
+    }
+}
+
+/// Removes a file from the virtual file system.
+#[no_mangle]
+pub extern "C" fn wbt_remove_file(name_ptr: *const u8, name_len: usize) -> i32 {
+    let file_name = match read_str(name_ptr, name_len) {
+        Some(s) => s,
+        None => return error(WBT_INVALID_ARGUMENTS, "The file name is not valid UTF-8."),
+    };
+    match vfs::remove_file(&file_name) {
+        Some(_) => {
+            set_result(vec![]);
+            WBT_SUCCESS
+        }
+        None => error(
+            WBT_ERROR,
+            &format!("No virtual file named {} was found.", file_name),
+        ),
+    }
+}
+
+/// Places a JSON array of the names of the files in the virtual file system in the
+/// result buffer.
+#[no_mangle]
+pub extern "C" fn wbt_list_files() -> i32 {
+    set_result(json!(vfs::list_files()).to_string().into_bytes());
+    WBT_SUCCESS
+}
+
+fn is_browser_tool(tool_name: &str) -> bool {
+    BROWSER_TOOLS
+        .iter()
+        .any(|t| t.to_lowercase() == tool_name.to_lowercase().replace("_", ""))
+}
+
+fn unavailable_message(tool_name: &str) -> String {
+    format!(
+        "The {} tool is not available in the browser. Available tools include: {}.",
+        tool_name,
+        BROWSER_TOOLS.join(", ")
+    )
+}
+
+fn set_result(bytes: Vec<u8>) {
+    RESULT.with(|r| *r.borrow_mut() = bytes);
+}
+
+fn error(code: i32, message: &str) -> i32 {
+    set_result(message.as_bytes().to_vec());
+    code
+}
+
+fn read_bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+    if ptr.is_null() || len == 0 {
+        return &[];
+    }
+    unsafe { slice::from_raw_parts(ptr, len) }
+}
+
+fn read_str(ptr: *const u8, len: usize) -> Option<String> {
+    str::from_utf8(read_bytes(ptr, len))
+        .ok()
+        .map(|s| s.to_string())
+}
@@ -0,0 +1,159 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 15/10/2026
+Last Modified: 15/10/2026
+License: MIT
+
+A JavaScript binding for the WebAssembly build of WhiteboxTools, which runs a
+subset of the raster tools (Slope, Aspect, Hillshade, MeanFilter, and
+GaussianFilter) within a web browser. The module is built using:
+
+    cargo build --release --lib --target wasm32-unknown-unknown
+
+and then loaded from target/wasm32-unknown-unknown/release/whitebox_tools.wasm.
+Example usage:
+
+    import { WhiteboxTools } from './whitebox_tools.js';
+
+    const wbt = await WhiteboxTools.load('whitebox_tools.wasm');
+    const dem = new Uint8Array(await (await fetch('DEM.tif')).arrayBuffer());
+    wbt.writeFile('DEM.tif', dem);
+    wbt.runTool('Slope', { dem: 'DEM.tif', output: 'slope.tif', units: 'degrees' });
+    const slope = wbt.readFile('slope.tif'); // a GeoTIFF, as a Uint8Array
+*/
+
+const SUCCESS = 0;
+
+export class WhiteboxTools {
+  constructor(instance) {
+    this.exports = instance.exports;
+    this.encoder = new TextEncoder();
+    this.decoder = new TextDecoder();
+  }
+
+  /**
+   * Loads the WebAssembly module from a URL, Response, or an ArrayBuffer of its bytes.
+   */
+  static async load(source) {
+    let result;
+    if (source instanceof ArrayBuffer || ArrayBuffer.isView(source)) {
+      result = await WebAssembly.instantiate(source, {});
+    } else {
+      const response = typeof source === 'string' ? fetch(source) : source;
+      result = await WebAssembly.instantiateStreaming(response, {});
+    }
+    return new WhiteboxTools(result.instance);
+  }
+
+  /** Returns the library version. */
+  version() {
+    this._check(this.exports.wbt_version());
+    return this._resultString();
+  }
+
+  /** Returns the tools that are available in the browser. */
+  listTools() {
+    this._check(this.exports.wbt_list_tools());
+    return JSON.parse(this._resultString());
+  }
+
+  /** Returns the parameters of a tool. */
+  toolParameters(toolName) {
+    const code = this._withBytes(this.encoder.encode(toolName), (ptr, len) =>
+      this.exports.wbt_tool_parameters(ptr, len)
+    );
+    this._check(code);
+    return JSON.parse(this._resultString());
+  }
+
+  /**
+   * Runs a tool. The arguments may be either an array of command-line style
+   * arguments, e.g. ['--dem=DEM.tif', '-o=slope.tif'], or an object mapping
+   * parameter flags to values, e.g. { dem: 'DEM.tif', output: 'slope.tif' }.
+   * File names refer to files in the virtual file system. Throws an Error if the
+   * tool fails.
+   */
+  runTool(toolName, args = []) {
+    if (!Array.isArray(args)) {
+      args = Object.entries(args).map(([flag, value]) =>
+        value === true ? `--${flag}` : `--${flag}=${value}`
+      );
+    }
+    const name = this.encoder.encode(toolName);
+    const argsJson = this.encoder.encode(JSON.stringify(args));
+    const code = this._withBytes(name, (namePtr, nameLen) =>
+      this._withBytes(argsJson, (argsPtr, argsLen) =>
+        this.exports.wbt_run_tool(namePtr, nameLen, argsPtr, argsLen)
+      )
+    );
+    this._check(code);
+  }
+
+  /** Copies a file, given as a Uint8Array, into the virtual file system. */
+  writeFile(fileName, bytes) {
+    const name = this.encoder.encode(fileName);
+    const code = this._withBytes(name, (namePtr, nameLen) =>
+      this._withBytes(bytes, (dataPtr, dataLen) =>
+        this.exports.wbt_write_file(namePtr, nameLen, dataPtr, dataLen)
+      )
+    );
+    this._check(code);
+  }
+
+  /** Returns a copy of a file in the virtual file system, as a Uint8Array. */
+  readFile(fileName) {
+    const code = this._withBytes(this.encoder.encode(fileName), (ptr, len) =>
+      this.exports.wbt_read_file(ptr, len)
+    );
+    this._check(code);
+    return this._resultBytes().slice();
+  }
+
+  /** Removes a file from the virtual file system. */
+  removeFile(fileName) {
+    const code = this._withBytes(this.encoder.encode(fileName), (ptr, len) =>
+      this.exports.wbt_remove_file(ptr, len)
+    );
+    this._check(code);
+  }
+
+  /** Returns the names of the files in the virtual file system. */
+  listFiles() {
+    this._check(this.exports.wbt_list_files());
+    return JSON.parse(this._resultString());
+  }
+
+  // Copies bytes into the module's memory for the duration of a call.
+  _withBytes(bytes, f) {
+    const len = bytes.length;
+    const ptr = len > 0 ? this.exports.wbt_alloc(len) : 0;
+    if (len > 0) {
+      new Uint8Array(this.exports.memory.buffer, ptr, len).set(bytes);
+    }
+    try {
+      return f(ptr, len);
+    } finally {
+      if (len > 0) {
+        this.exports.wbt_dealloc(ptr, len);
+      }
+    }
+  }
+
+  // The memory buffer may be replaced when it grows, so views are created afresh.
+  _resultBytes() {
+    const ptr = this.exports.wbt_result_ptr();
+    const len = this.exports.wbt_result_len();
+    return new Uint8Array(this.exports.memory.buffer, ptr, len);
+  }
+
+  _resultString() {
+    return this.decoder.decode(this._resultBytes());
+  }
+
+  _check(code) {
+    if (code !== SUCCESS) {
+      throw new Error(this._resultString());
+    }
+  }
+}
@@ -6,7 +6,7 @@ See whitebox_example.py for an example of how to use it.
 # This script is part of the WhiteboxTools geospatial library.
 # Authors: Dr. John Lindsay
 # Created: 28/11/2017
-# Last Modified: 09/12/2019
+# Last Modified: 15/10/2026
 # License: MIT
 
 from __future__ import print_function
@@ -15,6 +15,9 @@ from os import path
 import sys
 import platform
 import re
+import json
+import socket
+import time
 # import shutil
 from subprocess import CalledProcessError, Popen, PIPE, STDOUT
 
@@ -62,6 +65,8 @@ class WhiteboxTools(object):
         self.verbose = True
         self.cancel_op = False
         self.default_callback = default_callback
+        self.daemon = None
+        self.daemon_proc = None
 
     def set_whitebox_dir(self, path_str):
         ''' 
@@ -90,6 +95,70 @@ class WhiteboxTools(object):
         '''
         self.verbose = val
 
+    def connect_daemon(self, address="127.0.0.1:8090"):
+        ''' 
+        Connects to a running WhiteboxTools daemon (see start_daemon). While
+        connected, tools are run by the daemon, which keeps input datasets
+        memory-resident between tool runs. The address is either host:port
+        or the path of a Unix domain socket.
+        '''
+        host, _, port = address.rpartition(':')
+        if port.isdigit() and host != '':
+            sock = socket.create_connection((host, int(port)))
+        else:
+            sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+            sock.connect(address)
+        self.daemon = sock.makefile('rw')
+
+    def start_daemon(self, address="127.0.0.1:8090", cache_size=None):
+        ''' 
+        Starts a WhiteboxTools daemon and connects to it. The optional
+        cache_size limits the memory used by cached datasets, in megabytes.
+        '''
+        args = [path.join(self.exe_path, self.exe_name),
+                "--daemon={}".format(address)]
+        if self.work_dir.strip() != "":
+            args.append("--wd={}".format(self.work_dir))
+        if cache_size is not None:
+            args.append("--cache_size={}".format(cache_size))
+        self.daemon_proc = Popen(args, shell=False, stdout=PIPE, stderr=STDOUT)
+        for _ in range(50):
+            try:
+                self.connect_daemon(address)
+                return
+            except (OSError, socket.error):
+                time.sleep(0.1)
+        self.connect_daemon(address)
+
+    def stop_daemon(self):
+        ''' 
+        Shuts down the connected daemon, releasing its cached datasets.
+        '''
+        if self.daemon is not None:
+            self.daemon_request({"command": "shutdown"})
+            self.daemon.close()
+            self.daemon = None
+        if self.daemon_proc is not None:
+            self.daemon_proc.wait()
+            self.daemon_proc = None
+
+    def daemon_request(self, request, callback=None):
+        ''' 
+        Sends a request to the connected daemon, passing any tool messages
+        to the callback, and returns the daemon's final response.
+        '''
+        self.daemon.write(json.dumps(request) + "\n")
+        self.daemon.flush()
+        while True:
+            line = self.daemon.readline()
+            if line == '':
+                return {"status": "error", "error": "The daemon closed the connection."}
+            response = json.loads(line)
+            if "status" in response:
+                return response
+            if callback is not None:
+                callback(response["message"])
+
     def run_tool(self, tool_name, args, callback=None):
         ''' 
         Runs a tool and specifies tool arguments.
@@ -101,6 +170,18 @@ class WhiteboxTools(object):
             if callback is None:
                 callback = self.default_callback
 
+            if self.daemon is not None:
+                response = self.daemon_request({
+                    "command": "run",
+                    "tool": to_camelcase(tool_name),
+                    "args": args,
+                    "working_dir": self.work_dir,
+                    "verbose": self.verbose}, callback)
+                if response["status"] != "ok":
+                    callback(response["error"])
+                    return 1
+                return 0
+
             os.chdir(self.exe_path)
             args2 = []
             args2.append("." + path.sep + self.exe_name)